@@ -1,4 +1,5 @@
 use crate::domain::delegation::DelegatedCapability;
+use crate::domain::profile::AccountDevice;
 use crate::infrastructure::key_pair::KeyAlgorithm;
 
 pub enum KeyTypeMapper {
@@ -15,6 +16,92 @@ impl From<KeyTypeMapper> for KeyAlgorithm {
     }
 }
 
+/// アカウント識別子（did:key）で引いた公開鍵。
+#[derive(Debug, Clone)]
+pub struct AccountPublicKey {
+    pub account_id: String,
+    pub algorithm: KeyAlgorithm,
+    pub public_key: Vec<u8>,
+}
+
+/// アカウント鍵による署名。
+#[derive(Debug, Clone)]
+pub struct AccountSignature {
+    pub account_id: String,
+    pub algorithm: KeyAlgorithm,
+    pub public_key: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+/// アカウントのルート鍵から導出した子鍵の公開情報。
+#[derive(Debug, Clone)]
+pub struct DerivedAccountKey {
+    /// 導出元のアカウント識別子（did:key）。
+    pub account_id: String,
+    pub path: String,
+    /// 子鍵自身の識別子（did:key）。
+    pub key_id: String,
+    pub algorithm: KeyAlgorithm,
+    pub public_key: Vec<u8>,
+}
+
+/// 共有を受け取る HPKE の鍵ペア。
+///
+/// 秘密鍵は共有された CEK を取り出すときにだけ使い、保存しないこと。
+#[derive(Debug, Clone)]
+pub struct ExportedEncryptionKey {
+    pub account_id: String,
+    pub kem: String,
+    pub public_key: Vec<u8>,
+    pub secret_key: Vec<u8>,
+}
+
+/// 子鍵による署名。
+#[derive(Debug, Clone)]
+pub struct DerivedKeySignature {
+    pub key: DerivedAccountKey,
+    pub signature: Vec<u8>,
+}
+
+/// アカウント鍵のバックアップ用ニーモニック。
+///
+/// ニーモニックは秘密鍵そのものなので、取り扱いに注意すること。
+#[derive(Debug, Clone)]
+pub struct AccountMnemonic {
+    pub account_id: String,
+    pub algorithm: KeyAlgorithm,
+    /// BIP-39（英語）の 24 単語。
+    pub mnemonic: String,
+}
+
+/// 標準形式（PEM / JWK）で書き出したアカウント鍵。
+#[derive(Debug, Clone)]
+pub struct ExportedAccountKey {
+    pub account_id: String,
+    pub algorithm: KeyAlgorithm,
+    pub public_key: String,
+    /// 秘密鍵を含めるよう指定した場合のみ。
+    pub private_key: Option<String>,
+}
+
+/// プロフィールの作成・更新で指定する項目。更新では全項目を置き換える。
+#[derive(Debug, Clone)]
+pub struct ProfileFields {
+    pub display_name: String,
+    pub avatar_content_id: Option<String>,
+    pub devices: Vec<AccountDevice>,
+}
+
+/// アカウント鍵の復旧用シェア。
+///
+/// シェアは `threshold` 個集まると秘密鍵を復元できるので、それぞれ別の相手に配ること。
+#[derive(Debug, Clone)]
+pub struct AccountRecoveryShares {
+    pub account_id: String,
+    pub threshold: u8,
+    pub shares: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct IssueDelegatedTokenRequest {
     pub recipient_public_key: Vec<u8>,
@@ -30,3 +117,12 @@ pub struct IssueDelegatedTokenResult {
     pub expires_at: u64,
     pub jti: String,
 }
+
+/// チャレンジレスポンス認証で発行したセッショントークン。
+#[derive(Debug, Clone)]
+pub struct SessionToken {
+    pub account_id: String,
+    pub token: String,
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
@@ -1,6 +1,15 @@
-use crate::application_service::port::AccountKeyStoreError;
+use crate::application_service::port::{
+    AccountKeyStoreError, AuthChallengeStoreError, KeyAuditLogError, KeyRevocationListError,
+    KeyRotationLogError, ProfileRepositoryError,
+};
+use crate::infrastructure::did_key::DidKeyError;
 use crate::infrastructure::jwt_signer::JwtSignerError;
+use crate::infrastructure::key_derivation::KeyDerivationError;
+use crate::infrastructure::key_format::KeyFormatError;
 use crate::infrastructure::key_pair::KeyPairError;
+use crate::infrastructure::keystore_file::KeystoreFileError;
+use crate::infrastructure::mnemonic::MnemonicError;
+use crate::infrastructure::secret_sharing::SecretSharingError;
 
 #[derive(Debug, thiserror::Error)]
 pub enum AccountServiceError {
@@ -19,6 +28,19 @@ pub enum SignError {
     KeyStore(#[from] AccountKeyStoreError),
     #[error("invalid secret key: {0}")]
     InvalidKey(#[from] KeyPairError),
+    #[error("invalid stored public key: {0}")]
+    InvalidPublicKey(#[from] DidKeyError),
+    /// 認証チャレンジなど、アカウントサーバーが予約した文への署名は作らない。
+    #[error("message uses a reserved statement prefix")]
+    ReservedMessage,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LookupPublicKeyError {
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("invalid stored public key: {0}")]
+    InvalidKey(#[from] DidKeyError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -38,3 +60,240 @@ pub enum IssueDelegatedTokenError {
     #[error("failed to get system time: {0}")]
     Time(String),
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExportMnemonicError {
+    #[error("stored account key not found")]
+    NotFound,
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("invalid stored public key: {0}")]
+    InvalidPublicKey(#[from] DidKeyError),
+    #[error("failed to encode mnemonic: {0}")]
+    Mnemonic(#[from] MnemonicError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RestoreFromMnemonicError {
+    #[error("{0}")]
+    InvalidMnemonic(#[from] MnemonicError),
+    #[error("invalid key: {0}")]
+    InvalidKey(#[from] KeyPairError),
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExportKeystoreError {
+    #[error("stored account key not found")]
+    NotFound,
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("{0}")]
+    Keystore(#[from] KeystoreFileError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ImportKeystoreError {
+    #[error("{0}")]
+    Keystore(#[from] KeystoreFileError),
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExportKeyError {
+    #[error("stored account key not found")]
+    NotFound,
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("invalid stored public key: {0}")]
+    InvalidPublicKey(#[from] DidKeyError),
+    #[error("{0}")]
+    Format(#[from] KeyFormatError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ImportKeyError {
+    #[error("{0}")]
+    Format(#[from] KeyFormatError),
+    #[error("invalid key: {0}")]
+    InvalidKey(#[from] KeyPairError),
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SplitRecoverySharesError {
+    #[error("stored account key not found")]
+    NotFound,
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("invalid stored public key: {0}")]
+    InvalidPublicKey(#[from] DidKeyError),
+    #[error("{0}")]
+    SecretSharing(#[from] SecretSharingError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RecoverFromSharesError {
+    #[error("{0}")]
+    InvalidShares(#[from] SecretSharingError),
+    #[error("{0}")]
+    InvalidAccountId(#[from] DidKeyError),
+    #[error("invalid key: {0}")]
+    InvalidKey(#[from] KeyPairError),
+    /// 復元した鍵がシェアに記録されたアカウントと一致しない（シェアの改ざんや破損）。
+    #[error("recovered key does not match the account in the shares")]
+    AccountMismatch,
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerifySignatureError {
+    #[error("{0}")]
+    InvalidInput(#[from] KeyPairError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RotateKeyError {
+    #[error("stored account key not found")]
+    NotFound,
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("rotation log error: {0}")]
+    RotationLog(#[from] KeyRotationLogError),
+    #[error("invalid key: {0}")]
+    InvalidKey(#[from] KeyPairError),
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(#[from] DidKeyError),
+    #[error("failed to get system time: {0}")]
+    Time(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RotationHistoryError {
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("rotation log error: {0}")]
+    RotationLog(#[from] KeyRotationLogError),
+    #[error("invalid stored public key: {0}")]
+    InvalidPublicKey(#[from] DidKeyError),
+}
+
+/// ローテーション記録の連鎖の検証エラー。`sequence` は問題のある記録の通し番号。
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum RotationChainError {
+    #[error("rotation record {sequence} does not continue from the previous record")]
+    Discontinuous { sequence: u64 },
+    #[error("rotation record {sequence} does not match its public keys")]
+    AccountIdMismatch { sequence: u64 },
+    #[error("rotation record {sequence} is not signed by the previous key")]
+    InvalidSignature { sequence: u64 },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IssueAuthChallengeError {
+    #[error("{0}")]
+    InvalidAccountId(#[from] DidKeyError),
+    #[error("challenge store error: {0}")]
+    ChallengeStore(#[from] AuthChallengeStoreError),
+    #[error("failed to get system time: {0}")]
+    Time(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthenticateError {
+    /// チャレンジが存在しない、使用済み、期限切れ、または別のアカウントに発行されたもの。
+    #[error("unknown or expired challenge")]
+    InvalidChallenge,
+    #[error("invalid challenge signature")]
+    InvalidSignature,
+    /// アカウント鍵が失効している。
+    #[error("account key has been revoked")]
+    Revoked,
+    #[error("{0}")]
+    InvalidAccountId(#[from] DidKeyError),
+    #[error("invalid signature encoding: {0}")]
+    InvalidInput(#[from] KeyPairError),
+    #[error("challenge store error: {0}")]
+    ChallengeStore(#[from] AuthChallengeStoreError),
+    #[error("revocation list error: {0}")]
+    RevocationList(#[from] KeyRevocationListError),
+    #[error("{0}")]
+    JwtSigning(#[from] JwtSignerError),
+    #[error("failed to get system time: {0}")]
+    Time(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ManageProfileError {
+    #[error("stored account key not found")]
+    NotFound,
+    #[error("profile not found")]
+    ProfileNotFound,
+    #[error("profile already exists")]
+    AlreadyExists,
+    #[error("validation error: {0}")]
+    Validation(String),
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("profile repository error: {0}")]
+    Repository(#[from] ProfileRepositoryError),
+    #[error("invalid stored public key: {0}")]
+    InvalidPublicKey(#[from] DidKeyError),
+    #[error("invalid stored key: {0}")]
+    InvalidKey(#[from] KeyPairError),
+    /// 保存されていたプロフィールの署名がアカウント鍵と合わない。
+    #[error("profile signature does not match the account key")]
+    InvalidSignature,
+    #[error("failed to get system time: {0}")]
+    Time(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeactivateAccountError {
+    #[error("stored account key not found")]
+    NotFound,
+    #[error("account key has already been revoked")]
+    AlreadyRevoked,
+    #[error("{0}")]
+    Validation(String),
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("revocation list error: {0}")]
+    RevocationList(#[from] KeyRevocationListError),
+    #[error("invalid key: {0}")]
+    InvalidKey(#[from] KeyPairError),
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(#[from] DidKeyError),
+    #[error("failed to get system time: {0}")]
+    Time(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeriveKeyError {
+    #[error("stored account key not found")]
+    NotFound,
+    #[error("{0}")]
+    Derivation(#[from] KeyDerivationError),
+    #[error("key-store error: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(#[from] DidKeyError),
+    #[error("invalid key: {0}")]
+    InvalidKey(#[from] KeyPairError),
+}
+
+/// 監査とレート制限を通した鍵操作のエラー。`E` は操作自体のエラー。
+#[derive(Debug, thiserror::Error)]
+pub enum GuardedKeyOperationError<E> {
+    #[error("too many key operations, retry after {retry_after_secs}s")]
+    RateLimited { retry_after_secs: u64 },
+    /// 監査記録を残せなかったため、操作の結果を返さない。
+    #[error("audit log error: {0}")]
+    AuditLog(KeyAuditLogError),
+    #[error(transparent)]
+    Operation(E),
+}
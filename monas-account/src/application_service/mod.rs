@@ -3,7 +3,23 @@ pub mod error;
 pub mod port;
 pub mod service;
 
-pub use command::{IssueDelegatedTokenRequest, IssueDelegatedTokenResult, KeyTypeMapper};
-pub use error::{AccountServiceError, IssueDelegatedTokenError, SignError};
-pub use port::{AccountKeyStore, AccountKeyStoreError, StoredAccountKey};
-pub use service::AccountService;
+pub use command::{
+    AccountMnemonic, AccountPublicKey, AccountRecoveryShares, AccountSignature, DerivedAccountKey,
+    DerivedKeySignature, ExportedEncryptionKey, IssueDelegatedTokenRequest,
+    IssueDelegatedTokenResult, KeyTypeMapper, ProfileFields, SessionToken,
+};
+pub use error::{
+    AccountServiceError, AuthenticateError, DeactivateAccountError, DeriveKeyError, ExportKeyError,
+    ExportKeystoreError, ExportMnemonicError, GuardedKeyOperationError, ImportKeyError,
+    ImportKeystoreError, IssueAuthChallengeError, IssueDelegatedTokenError, LookupPublicKeyError,
+    ManageProfileError, RecoverFromSharesError, RestoreFromMnemonicError, RotateKeyError,
+    RotationChainError, RotationHistoryError, SignError, SplitRecoverySharesError,
+    VerifySignatureError,
+};
+pub use port::{
+    AccountKeyStore, AccountKeyStoreError, AuthChallengeStore, AuthChallengeStoreError,
+    EventPublisher, EventPublisherError, KeyAuditLog, KeyAuditLogError, KeyOperationLimiter,
+    KeyRevocationList, KeyRevocationListError, KeyRotationLog, KeyRotationLogError,
+    ProfileRepository, ProfileRepositoryError, StoredAccountKey,
+};
+pub use service::{AccountService, NoOpEventPublisher};
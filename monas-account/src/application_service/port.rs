@@ -1,4 +1,11 @@
-use crate::infrastructure::key_pair::KeyAlgorithm;
+use crate::domain::account::AccountKeyPair;
+use crate::domain::events::AccountDomainEvent;
+use crate::domain::key_audit::{KeyAuditEntry, KeyAuditQuery, KeyOperation};
+use crate::domain::key_rotation::KeyRotationRecord;
+use crate::domain::profile::AccountProfile;
+use crate::domain::revocation::KeyRevocationRecord;
+use crate::domain::session::AuthChallenge;
+use crate::infrastructure::key_pair::{KeyAlgorithm, KeyPairError, KeyPairGenerateFactory};
 
 #[derive(Clone)]
 pub struct StoredAccountKey {
@@ -11,6 +18,42 @@ pub trait AccountKeyStore {
     fn save(&self, key: &StoredAccountKey) -> Result<(), AccountKeyStoreError>;
     fn load(&self) -> Result<Option<StoredAccountKey>, AccountKeyStoreError>;
     fn delete(&self) -> Result<(), AccountKeyStoreError>;
+
+    /// `load` で読んだ鍵で署名する鍵ペアを返す。
+    ///
+    /// 既定では保存された秘密鍵から復元する。秘密鍵を外部の署名器に置く実装は上書きする。
+    fn key_pair(&self, key: &StoredAccountKey) -> Result<Box<dyn AccountKeyPair>, KeyPairError> {
+        KeyPairGenerateFactory::from_key_bytes(key.algorithm, &key.public_key, &key.secret_key)
+    }
+
+    /// バッファされた書き込みを永続化する。サーバーの終了前に呼び出す。
+    ///
+    /// 書き込みのたびに永続化する実装では何もしなくてよい。
+    fn flush(&self) -> Result<(), AccountKeyStoreError> {
+        Ok(())
+    }
+}
+
+impl<T: AccountKeyStore + ?Sized> AccountKeyStore for std::sync::Arc<T> {
+    fn save(&self, key: &StoredAccountKey) -> Result<(), AccountKeyStoreError> {
+        (**self).save(key)
+    }
+
+    fn load(&self) -> Result<Option<StoredAccountKey>, AccountKeyStoreError> {
+        (**self).load()
+    }
+
+    fn delete(&self) -> Result<(), AccountKeyStoreError> {
+        (**self).delete()
+    }
+
+    fn key_pair(&self, key: &StoredAccountKey) -> Result<Box<dyn AccountKeyPair>, KeyPairError> {
+        (**self).key_pair(key)
+    }
+
+    fn flush(&self) -> Result<(), AccountKeyStoreError> {
+        (**self).flush()
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -20,4 +63,226 @@ pub enum AccountKeyStoreError {
 
     #[error("invalid key data: {0}")]
     InvalidKeyData(String),
+
+    #[error("failed to decrypt key data: wrong passphrase or corrupted data")]
+    Decryption,
+}
+
+/// アカウント鍵のローテーション記録を保存するポート。
+pub trait KeyRotationLog {
+    fn append(&self, record: &KeyRotationRecord) -> Result<(), KeyRotationLogError>;
+    /// `sequence` の昇順で返す。
+    fn history(&self) -> Result<Vec<KeyRotationRecord>, KeyRotationLogError>;
+    fn clear(&self) -> Result<(), KeyRotationLogError>;
+
+    /// 書き込んだ記録をディスクまで永続化する。サーバーの終了前に呼び出す。
+    ///
+    /// 書き込みのたびに永続化する実装では何もしなくてよい。
+    fn flush(&self) -> Result<(), KeyRotationLogError> {
+        Ok(())
+    }
+}
+
+impl<T: KeyRotationLog + ?Sized> KeyRotationLog for std::sync::Arc<T> {
+    fn append(&self, record: &KeyRotationRecord) -> Result<(), KeyRotationLogError> {
+        (**self).append(record)
+    }
+
+    fn history(&self) -> Result<Vec<KeyRotationRecord>, KeyRotationLogError> {
+        (**self).history()
+    }
+
+    fn clear(&self) -> Result<(), KeyRotationLogError> {
+        (**self).clear()
+    }
+
+    fn flush(&self) -> Result<(), KeyRotationLogError> {
+        (**self).flush()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyRotationLogError {
+    #[error("storage error: {0}")]
+    Storage(String),
+
+    #[error("invalid rotation record: {0}")]
+    InvalidRecord(String),
+}
+
+/// 発行済みの認証チャレンジを保持するポート。
+pub trait AuthChallengeStore {
+    fn insert(&self, challenge: &AuthChallenge) -> Result<(), AuthChallengeStoreError>;
+    /// `nonce` のチャレンジを取り除いて返す。チャレンジは一度しか使えない。
+    fn take(&self, nonce: &str) -> Result<Option<AuthChallenge>, AuthChallengeStoreError>;
+}
+
+impl<T: AuthChallengeStore + ?Sized> AuthChallengeStore for std::sync::Arc<T> {
+    fn insert(&self, challenge: &AuthChallenge) -> Result<(), AuthChallengeStoreError> {
+        (**self).insert(challenge)
+    }
+
+    fn take(&self, nonce: &str) -> Result<Option<AuthChallenge>, AuthChallengeStoreError> {
+        (**self).take(nonce)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthChallengeStoreError {
+    /// 未使用のチャレンジが上限に達している。
+    #[error("too many pending challenges")]
+    Full,
+    #[error("storage error: {0}")]
+    Storage(String),
+}
+
+/// アカウントのプロフィールを保存するポート。
+pub trait ProfileRepository {
+    fn save(&self, profile: &AccountProfile) -> Result<(), ProfileRepositoryError>;
+    fn load(&self, account_id: &str) -> Result<Option<AccountProfile>, ProfileRepositoryError>;
+
+    /// 書き込んだ記録をディスクまで永続化する。サーバーの終了前に呼び出す。
+    ///
+    /// 書き込みのたびに永続化する実装では何もしなくてよい。
+    fn flush(&self) -> Result<(), ProfileRepositoryError> {
+        Ok(())
+    }
+}
+
+impl<T: ProfileRepository + ?Sized> ProfileRepository for std::sync::Arc<T> {
+    fn save(&self, profile: &AccountProfile) -> Result<(), ProfileRepositoryError> {
+        (**self).save(profile)
+    }
+
+    fn load(&self, account_id: &str) -> Result<Option<AccountProfile>, ProfileRepositoryError> {
+        (**self).load(account_id)
+    }
+
+    fn flush(&self) -> Result<(), ProfileRepositoryError> {
+        (**self).flush()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProfileRepositoryError {
+    #[error("storage error: {0}")]
+    Storage(String),
+
+    #[error("invalid profile data: {0}")]
+    InvalidData(String),
+}
+
+/// 失効したアカウント鍵の記録を保存するポート。
+pub trait KeyRevocationList {
+    fn add(&self, record: &KeyRevocationRecord) -> Result<(), KeyRevocationListError>;
+    fn find(&self, account_id: &str)
+        -> Result<Option<KeyRevocationRecord>, KeyRevocationListError>;
+    /// 失効した順に返す。
+    fn list(&self) -> Result<Vec<KeyRevocationRecord>, KeyRevocationListError>;
+
+    /// 書き込んだ記録をディスクまで永続化する。サーバーの終了前に呼び出す。
+    ///
+    /// 書き込みのたびに永続化する実装では何もしなくてよい。
+    fn flush(&self) -> Result<(), KeyRevocationListError> {
+        Ok(())
+    }
+}
+
+impl<T: KeyRevocationList + ?Sized> KeyRevocationList for std::sync::Arc<T> {
+    fn add(&self, record: &KeyRevocationRecord) -> Result<(), KeyRevocationListError> {
+        (**self).add(record)
+    }
+
+    fn find(
+        &self,
+        account_id: &str,
+    ) -> Result<Option<KeyRevocationRecord>, KeyRevocationListError> {
+        (**self).find(account_id)
+    }
+
+    fn list(&self) -> Result<Vec<KeyRevocationRecord>, KeyRevocationListError> {
+        (**self).list()
+    }
+
+    fn flush(&self) -> Result<(), KeyRevocationListError> {
+        (**self).flush()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyRevocationListError {
+    #[error("storage error: {0}")]
+    Storage(String),
+
+    #[error("invalid revocation record: {0}")]
+    InvalidRecord(String),
+}
+
+/// アカウントのドメインイベントを外部コンポーネントへ通知するためのポート。
+///
+/// - 実装は infra 層（monas-event-manager の EventBus など）に置く。
+/// - 永続化が完了した後に呼び出されるため、実装は同期的に戻ること。
+pub trait EventPublisher {
+    fn publish(&self, event: AccountDomainEvent) -> Result<(), EventPublisherError>;
+}
+
+impl<T: EventPublisher + ?Sized> EventPublisher for std::sync::Arc<T> {
+    fn publish(&self, event: AccountDomainEvent) -> Result<(), EventPublisherError> {
+        (**self).publish(event)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventPublisherError {
+    #[error("publish error: {0}")]
+    Publish(String),
+}
+
+/// 秘密鍵を使う操作の監査記録を保存するポート。
+pub trait KeyAuditLog {
+    fn append(&self, entry: &KeyAuditEntry) -> Result<(), KeyAuditLogError>;
+    /// 条件に合う記録を古い順に返す。
+    fn query(&self, query: &KeyAuditQuery) -> Result<Vec<KeyAuditEntry>, KeyAuditLogError>;
+
+    /// 書き込んだ記録をディスクまで永続化する。サーバーの終了前に呼び出す。
+    ///
+    /// 書き込みのたびに永続化する実装では何もしなくてよい。
+    fn flush(&self) -> Result<(), KeyAuditLogError> {
+        Ok(())
+    }
+}
+
+impl<T: KeyAuditLog + ?Sized> KeyAuditLog for std::sync::Arc<T> {
+    fn append(&self, entry: &KeyAuditEntry) -> Result<(), KeyAuditLogError> {
+        (**self).append(entry)
+    }
+
+    fn query(&self, query: &KeyAuditQuery) -> Result<Vec<KeyAuditEntry>, KeyAuditLogError> {
+        (**self).query(query)
+    }
+
+    fn flush(&self) -> Result<(), KeyAuditLogError> {
+        (**self).flush()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyAuditLogError {
+    #[error("storage error: {0}")]
+    Storage(String),
+
+    #[error("invalid audit entry: {0}")]
+    InvalidEntry(String),
+}
+
+/// 秘密鍵を使う操作の頻度を制限するポート。
+pub trait KeyOperationLimiter {
+    /// 操作を 1 回分許可する。上限を超えている場合は、次に許可されるまでの時間を返す。
+    fn try_acquire(&self, operation: KeyOperation) -> Result<(), std::time::Duration>;
+}
+
+impl<T: KeyOperationLimiter + ?Sized> KeyOperationLimiter for std::sync::Arc<T> {
+    fn try_acquire(&self, operation: KeyOperation) -> Result<(), std::time::Duration> {
+        (**self).try_acquire(operation)
+    }
 }
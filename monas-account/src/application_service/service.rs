@@ -1,12 +1,46 @@
 use crate::application_service::command::{
-    IssueDelegatedTokenRequest, IssueDelegatedTokenResult, KeyTypeMapper,
+    AccountMnemonic, AccountPublicKey, AccountRecoveryShares, AccountSignature, DerivedAccountKey,
+    DerivedKeySignature, ExportedAccountKey, ExportedEncryptionKey, IssueDelegatedTokenRequest,
+    IssueDelegatedTokenResult, KeyTypeMapper, ProfileFields, SessionToken,
 };
-use crate::application_service::error::{AccountServiceError, IssueDelegatedTokenError, SignError};
-use crate::application_service::port::AccountKeyStore;
-use crate::domain::account::Account;
+use crate::application_service::error::{
+    AccountServiceError, AuthenticateError, DeactivateAccountError, DeriveKeyError, ExportKeyError,
+    ExportKeystoreError, ExportMnemonicError, GuardedKeyOperationError, ImportKeyError,
+    ImportKeystoreError, IssueAuthChallengeError, IssueDelegatedTokenError, LookupPublicKeyError,
+    ManageProfileError, RecoverFromSharesError, RestoreFromMnemonicError, RotateKeyError,
+    RotationChainError, RotationHistoryError, SignError, SplitRecoverySharesError,
+    VerifySignatureError,
+};
+use crate::application_service::port::{
+    AccountKeyStore, AuthChallengeStore, EventPublisher, EventPublisherError, KeyAuditLog,
+    KeyOperationLimiter, KeyRevocationList, KeyRevocationListError, KeyRotationLog,
+    ProfileRepository,
+};
+use crate::domain::account::{Account, AccountKeyPair};
 use crate::domain::delegation::{DelegatedCapability, DelegationCapabilityClaim, DelegationClaims};
+use crate::domain::encryption_key::AccountEncryptionKey;
+use crate::domain::events::{AccountCreated, AccountDomainEvent, DeviceLinked, KeyRotated};
+use crate::domain::key_audit::{KeyAuditEntry, KeyOperation, KeyOperationOutcome};
+use crate::domain::key_rotation::{rotation_statement, KeyRotationRecord};
+use crate::domain::profile::{AccountDevice, AccountProfile};
+use crate::domain::revocation::{revocation_statement, KeyRevocationRecord};
+use crate::domain::session::{is_reserved_statement, AuthChallenge, SessionClaims};
+use crate::infrastructure::did_key::{
+    did_key_from_public_key, public_key_from_did_key, uncompressed_public_key, DidKeyError,
+};
+use crate::infrastructure::file_key_store::ScryptParams;
 use crate::infrastructure::jwt_signer::sign_es256_jwt_payload;
-use crate::infrastructure::key_pair::{KeyAlgorithm, KeyPairGenerateFactory};
+use crate::infrastructure::key_derivation::{
+    derive_child_key, derive_encryption_key, KeyDerivationPath, ENCRYPTION_KEY_KEM,
+};
+use crate::infrastructure::key_format::{
+    export_private_key, export_public_key, import_private_key, KeyFormat,
+};
+use crate::infrastructure::key_pair::{verify_signature, KeyAlgorithm, KeyPairGenerateFactory};
+use crate::infrastructure::keystore_file::KeystoreFile;
+use crate::infrastructure::mnemonic::{mnemonic_to_secret_key, secret_key_to_mnemonic};
+use crate::infrastructure::secret_sharing::{combine_shares, split_secret_key};
+use crate::infrastructure::session_token::SessionTokenSigner;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::Engine;
 use p256::elliptic_curve::rand_core::{OsRng, RngCore};
@@ -14,9 +48,28 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 pub struct AccountService;
 
+/// イベントを外部へ通知しない `EventPublisher` 実装。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOpEventPublisher;
+
+impl EventPublisher for NoOpEventPublisher {
+    fn publish(&self, _event: AccountDomainEvent) -> Result<(), EventPublisherError> {
+        Ok(())
+    }
+}
+
+/// 認証チャレンジの有効期間（秒）。
+const AUTH_CHALLENGE_TTL_SECS: u64 = 5 * 60;
+/// セッショントークンの有効期間（秒）。
+const SESSION_TTL_SECS: u64 = 15 * 60;
+/// 失効理由の最大長（文字数）。
+const MAX_REVOCATION_REASON_CHARS: usize = 256;
+
 impl AccountService {
-    pub fn create<S: AccountKeyStore>(
+    /// 鍵ペアを生成して保存し、`AccountCreated` を通知する。
+    pub fn create<S: AccountKeyStore, E: EventPublisher>(
         store: &S,
+        events: &E,
         key_type: KeyTypeMapper,
     ) -> Result<Account, AccountServiceError> {
         let algorithm: KeyAlgorithm = key_type.into();
@@ -30,6 +83,21 @@ impl AccountService {
         };
 
         store.save(&stored)?;
+        if let Ok(account_id) = did_key_from_public_key(algorithm, account.public_key_bytes()) {
+            publish_event(
+                events,
+                AccountDomainEvent::Created(AccountCreated {
+                    account_id,
+                    algorithm: match algorithm {
+                        KeyAlgorithm::K256 => "K256",
+                        KeyAlgorithm::P256 => "P256",
+                    }
+                    .to_string(),
+                    public_key: stored.public_key,
+                    created_at: unix_now_secs().unwrap_or_default(),
+                }),
+            );
+        }
         Ok(account)
     }
 
@@ -42,16 +110,720 @@ impl AccountService {
         store: &S,
         msg: &[u8],
     ) -> Result<(Vec<u8>, Option<u8>), SignError> {
+        if is_reserved_statement(msg) {
+            return Err(SignError::ReservedMessage);
+        }
         let stored = store.load()?.ok_or(SignError::NotFound)?;
 
-        let key_pair = KeyPairGenerateFactory::from_key_bytes(
-            stored.algorithm,
-            &stored.public_key,
-            &stored.secret_key,
-        )?;
+        let key_pair = store.key_pair(&stored)?;
 
         let account = Account::new(key_pair);
-        Ok(account.sign(msg))
+        Ok(account.sign(msg)?)
+    }
+
+    /// アカウント識別子（did:key）を指定して署名する。
+    ///
+    /// 秘密鍵は呼び出し側に渡さず、署名とアルゴリズムだけを返す。保存済みのアカウントと
+    /// 識別子が一致しない場合は `SignError::NotFound` を返す。
+    pub fn sign_as<S: AccountKeyStore>(
+        store: &S,
+        account_id: &str,
+        msg: &[u8],
+    ) -> Result<AccountSignature, SignError> {
+        if is_reserved_statement(msg) {
+            return Err(SignError::ReservedMessage);
+        }
+        let stored = store.load()?.ok_or(SignError::NotFound)?;
+        let stored_account_id = did_key_from_public_key(stored.algorithm, &stored.public_key)?;
+        if stored_account_id != account_id {
+            return Err(SignError::NotFound);
+        }
+
+        let key_pair = store.key_pair(&stored)?;
+        let (signature, _recovery_id) = Account::new(key_pair).sign(msg)?;
+        Ok(AccountSignature {
+            account_id: stored_account_id,
+            algorithm: stored.algorithm,
+            public_key: stored.public_key,
+            signature,
+        })
+    }
+
+    /// `sign` / `sign_as` で作った署名を公開鍵で検証する。鍵ストアは使わない。
+    ///
+    /// 署名が一致しない場合は `Ok(false)`、公開鍵・署名の形式が不正な場合はエラーを返す。
+    pub fn verify(
+        key_type: KeyTypeMapper,
+        public_key: &[u8],
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, VerifySignatureError> {
+        Ok(verify_signature(
+            key_type.into(),
+            public_key,
+            msg,
+            signature,
+        )?)
+    }
+
+    /// 保存済みアカウントの識別子（did:key）と公開鍵を返す。
+    pub fn public_key<S: AccountKeyStore>(
+        store: &S,
+    ) -> Result<Option<AccountPublicKey>, LookupPublicKeyError> {
+        let Some(stored) = store.load()? else {
+            return Ok(None);
+        };
+        Ok(Some(AccountPublicKey {
+            account_id: did_key_from_public_key(stored.algorithm, &stored.public_key)?,
+            algorithm: stored.algorithm,
+            public_key: stored.public_key,
+        }))
+    }
+
+    /// アカウント識別子（did:key）から公開鍵を引く。
+    ///
+    /// did:key は公開鍵そのものを含むため、このサーバーに保存されていないアカウントの
+    /// 公開鍵も取り出せる。公開鍵は保存済みの鍵と同じ非圧縮形式の SEC1 で返す。
+    pub fn find_public_key(account_id: &str) -> Result<AccountPublicKey, DidKeyError> {
+        let (algorithm, public_key) = public_key_from_did_key(account_id)?;
+        Ok(AccountPublicKey {
+            account_id: account_id.to_string(),
+            algorithm,
+            public_key: uncompressed_public_key(algorithm, &public_key)?,
+        })
+    }
+
+    /// 秘密鍵を使う操作 `op` をレート制限と監査記録を通して実行する。
+    ///
+    /// 上限を超えている場合は `op` を実行せず `RateLimited` を返す。結果（成功・失敗・制限）は
+    /// すべて `audit` に記録する。成功した操作の記録に失敗した場合は、結果を返さず `AuditLog` を返す。
+    /// `principal` は操作を求めた呼び出し元（セッションのアカウント識別子）で、記録に残す。
+    pub fn run_key_operation<A, L, T, E>(
+        audit: &A,
+        limiter: &L,
+        operation: KeyOperation,
+        principal: Option<&str>,
+        op: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, GuardedKeyOperationError<E>>
+    where
+        A: KeyAuditLog,
+        L: KeyOperationLimiter,
+        E: std::fmt::Display,
+    {
+        let at = unix_now_secs().unwrap_or_default();
+        let entry = |outcome, detail| KeyAuditEntry {
+            operation,
+            outcome,
+            at,
+            principal: principal.map(str::to_string),
+            detail,
+        };
+
+        if let Err(retry_after) = limiter.try_acquire(operation) {
+            // 制限中の記録に失敗しても、拒否する結果は変わらない
+            let _ = audit.append(&entry(KeyOperationOutcome::RateLimited, None));
+            return Err(GuardedKeyOperationError::RateLimited {
+                retry_after_secs: retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0),
+            });
+        }
+
+        match op() {
+            Ok(value) => {
+                audit
+                    .append(&entry(KeyOperationOutcome::Succeeded, None))
+                    .map_err(GuardedKeyOperationError::AuditLog)?;
+                Ok(value)
+            }
+            Err(e) => {
+                let _ = audit.append(&entry(KeyOperationOutcome::Failed, Some(e.to_string())));
+                Err(GuardedKeyOperationError::Operation(e))
+            }
+        }
+    }
+
+    /// 保存済みアカウントのルート鍵から `path` の子鍵を導出し、公開情報を返す。
+    ///
+    /// コンテンツや共有相手ごとに別の鍵を使い、識別鍵を直接使い回さないために使う。
+    /// 子鍵は保存せず、呼び出しのたびに導出し直す。
+    pub fn derive_key<S: AccountKeyStore>(
+        store: &S,
+        path: &KeyDerivationPath,
+    ) -> Result<DerivedAccountKey, DeriveKeyError> {
+        let (key, _) = derive_stored_child_key(store, path)?;
+        Ok(key)
+    }
+
+    /// `path` の子鍵で署名する。秘密鍵は呼び出し側に渡さない。
+    pub fn sign_with_derived_key<S: AccountKeyStore>(
+        store: &S,
+        path: &KeyDerivationPath,
+        msg: &[u8],
+    ) -> Result<DerivedKeySignature, DeriveKeyError> {
+        let (key, child) = derive_stored_child_key(store, path)?;
+        let (signature, _recovery_id) = Account::new(child).sign(msg)?;
+        Ok(DerivedKeySignature { key, signature })
+    }
+
+    /// `account_id` が共有を受け取る HPKE の公開鍵を、アカウント鍵の署名付きで返す。
+    ///
+    /// 鍵はルート鍵から導出するため保存しない。`account_id` が保存済みのアカウントと
+    /// 一致しない場合は `None` を返す。
+    pub fn find_encryption_key<S: AccountKeyStore>(
+        store: &S,
+        account_id: &str,
+    ) -> Result<Option<AccountEncryptionKey>, DeriveKeyError> {
+        let Some(stored) = store.load()? else {
+            return Ok(None);
+        };
+        let stored_account_id = did_key_from_public_key(stored.algorithm, &stored.public_key)?;
+        if stored_account_id != account_id {
+            return Ok(None);
+        }
+
+        let derived = derive_encryption_key(&stored.secret_key, &KeyDerivationPath::share())?;
+        let mut key = AccountEncryptionKey {
+            account_id: stored_account_id,
+            kem: ENCRYPTION_KEY_KEM.to_string(),
+            public_key: derived.public_key,
+            signature: Vec::new(),
+        };
+        key.signature = Account::new(store.key_pair(&stored)?)
+            .sign(&key.statement())?
+            .0;
+        Ok(Some(key))
+    }
+
+    /// 共有を受け取る HPKE の鍵ペアを書き出す。共有された CEK を取り出すときに使う。
+    pub fn export_encryption_key<S: AccountKeyStore>(
+        store: &S,
+    ) -> Result<ExportedEncryptionKey, DeriveKeyError> {
+        let stored = store.load()?.ok_or(DeriveKeyError::NotFound)?;
+        let derived = derive_encryption_key(&stored.secret_key, &KeyDerivationPath::share())?;
+        Ok(ExportedEncryptionKey {
+            account_id: did_key_from_public_key(stored.algorithm, &stored.public_key)?,
+            kem: ENCRYPTION_KEY_KEM.to_string(),
+            public_key: derived.public_key,
+            secret_key: derived.secret_key,
+        })
+    }
+
+    /// 暗号化鍵が `account_id` の鍵で署名されていることを確かめる。
+    pub fn verify_encryption_key(key: &AccountEncryptionKey) -> bool {
+        public_key_from_did_key(&key.account_id).is_ok_and(|(algorithm, public_key)| {
+            verify_signature(algorithm, &public_key, &key.statement(), &key.signature)
+                .unwrap_or(false)
+        })
+    }
+
+    /// 保存済みの鍵を 24 単語の BIP-39 ニーモニックとして書き出す。
+    pub fn export_mnemonic<S: AccountKeyStore>(
+        store: &S,
+    ) -> Result<AccountMnemonic, ExportMnemonicError> {
+        let stored = store.load()?.ok_or(ExportMnemonicError::NotFound)?;
+        Ok(AccountMnemonic {
+            account_id: did_key_from_public_key(stored.algorithm, &stored.public_key)?,
+            algorithm: stored.algorithm,
+            mnemonic: secret_key_to_mnemonic(&stored.secret_key)?,
+        })
+    }
+
+    /// ニーモニックから鍵ペアを復元して保存する。既存の鍵は置き換える。
+    ///
+    /// ニーモニックにはアルゴリズムが含まれないため、書き出したときと同じ `key_type` を指定する。
+    pub fn restore_from_mnemonic<S: AccountKeyStore>(
+        store: &S,
+        key_type: KeyTypeMapper,
+        mnemonic: &str,
+    ) -> Result<Account, RestoreFromMnemonicError> {
+        let algorithm: KeyAlgorithm = key_type.into();
+        let secret_key = mnemonic_to_secret_key(mnemonic)?;
+        let account = Account::new(KeyPairGenerateFactory::from_secret_key_bytes(
+            algorithm,
+            &secret_key,
+        )?);
+
+        store.save(&crate::application_service::StoredAccountKey {
+            algorithm,
+            public_key: account.public_key_bytes().to_vec(),
+            secret_key: account.secret_key_bytes().to_vec(),
+        })?;
+        Ok(account)
+    }
+
+    /// 保存済みの鍵をパスフレーズで暗号化した鍵ファイル（keystore v3 形式）として書き出す。
+    pub fn export_keystore<S: AccountKeyStore>(
+        store: &S,
+        passphrase: &str,
+        params: ScryptParams,
+    ) -> Result<KeystoreFile, ExportKeystoreError> {
+        let stored = store.load()?.ok_or(ExportKeystoreError::NotFound)?;
+        Ok(KeystoreFile::encrypt(&stored, passphrase, params)?)
+    }
+
+    /// 鍵ファイルを復号して保存する。既存の鍵は置き換える。
+    pub fn import_keystore<S: AccountKeyStore>(
+        store: &S,
+        keystore_json: &str,
+        passphrase: &str,
+    ) -> Result<AccountPublicKey, ImportKeystoreError> {
+        let keystore = KeystoreFile::from_json(keystore_json)?;
+        let stored = keystore.decrypt(passphrase)?;
+        store.save(&stored)?;
+        Ok(AccountPublicKey {
+            account_id: keystore.account_id,
+            algorithm: stored.algorithm,
+            public_key: stored.public_key,
+        })
+    }
+
+    /// 保存済みの鍵を `format` で書き出す。秘密鍵は `include_private_key` のときだけ含める。
+    pub fn export_key<S: AccountKeyStore>(
+        store: &S,
+        format: KeyFormat,
+        include_private_key: bool,
+    ) -> Result<ExportedAccountKey, ExportKeyError> {
+        let stored = store.load()?.ok_or(ExportKeyError::NotFound)?;
+        let private_key = include_private_key
+            .then(|| export_private_key(stored.algorithm, &stored.secret_key, format))
+            .transpose()?;
+        Ok(ExportedAccountKey {
+            account_id: did_key_from_public_key(stored.algorithm, &stored.public_key)?,
+            algorithm: stored.algorithm,
+            public_key: export_public_key(stored.algorithm, &stored.public_key, format)?,
+            private_key,
+        })
+    }
+
+    /// 外部で生成された秘密鍵（PEM / PKCS#8、SEC1、JWK）を読み込み、既存の鍵を置き換える。
+    pub fn import_key<S: AccountKeyStore>(
+        store: &S,
+        encoded: &str,
+    ) -> Result<Account, ImportKeyError> {
+        let (algorithm, secret_key) = import_private_key(encoded)?;
+        let account = Account::new(KeyPairGenerateFactory::from_secret_key_bytes(
+            algorithm,
+            &secret_key,
+        )?);
+
+        store.save(&crate::application_service::StoredAccountKey {
+            algorithm,
+            public_key: account.public_key_bytes().to_vec(),
+            secret_key: account.secret_key_bytes().to_vec(),
+        })?;
+        Ok(account)
+    }
+
+    /// 保存済みの鍵を `share_count` 個の復旧用シェアに分割する。復元には `threshold` 個が必要。
+    pub fn split_recovery_shares<S: AccountKeyStore>(
+        store: &S,
+        threshold: u8,
+        share_count: u8,
+    ) -> Result<AccountRecoveryShares, SplitRecoverySharesError> {
+        let stored = store.load()?.ok_or(SplitRecoverySharesError::NotFound)?;
+        let account_id = did_key_from_public_key(stored.algorithm, &stored.public_key)?;
+        let shares = split_secret_key(&stored.secret_key, &account_id, threshold, share_count)?;
+        Ok(AccountRecoveryShares {
+            account_id,
+            threshold,
+            shares,
+        })
+    }
+
+    /// 復旧用シェアから鍵を復元し、既存の鍵を置き換える。
+    ///
+    /// 復元した鍵の識別子がシェアに記録されたアカウントと一致しない場合は保存しない。
+    pub fn recover_from_shares<S: AccountKeyStore>(
+        store: &S,
+        shares: &[String],
+    ) -> Result<Account, RecoverFromSharesError> {
+        let (account_id, secret_key) = combine_shares(shares)?;
+        let (algorithm, _) = public_key_from_did_key(&account_id)?;
+        let account = Account::new(KeyPairGenerateFactory::from_secret_key_bytes(
+            algorithm,
+            &secret_key,
+        )?);
+        if did_key_from_public_key(algorithm, account.public_key_bytes())? != account_id {
+            return Err(RecoverFromSharesError::AccountMismatch);
+        }
+
+        store.save(&crate::application_service::StoredAccountKey {
+            algorithm,
+            public_key: account.public_key_bytes().to_vec(),
+            secret_key: account.secret_key_bytes().to_vec(),
+        })?;
+        Ok(account)
+    }
+
+    /// アカウント鍵をローテーションする。
+    ///
+    /// 新しい鍵ペアを生成し、旧鍵でローテーション文に署名した記録を `log` に追加してから
+    /// 鍵ストアの鍵を置き換える。`key_type` を省略した場合は旧鍵と同じアルゴリズムを使う。
+    ///
+    /// 記録の最後の識別子が現在のアカウントと一致しない場合（ローテーションを経ずに
+    /// アカウントを作り直した場合）は、無関係な記録として破棄してから追加する。
+    pub fn rotate_key<S: AccountKeyStore, L: KeyRotationLog, E: EventPublisher>(
+        store: &S,
+        log: &L,
+        events: &E,
+        key_type: Option<KeyTypeMapper>,
+    ) -> Result<KeyRotationRecord, RotateKeyError> {
+        let previous = store.load()?.ok_or(RotateKeyError::NotFound)?;
+        let previous_account_id =
+            did_key_from_public_key(previous.algorithm, &previous.public_key)?;
+
+        let mut history = log.history()?;
+        let continues = match history.last() {
+            Some(last) => last.new_account_id == previous_account_id,
+            None => true,
+        };
+        if !continues {
+            log.clear()?;
+            history.clear();
+        }
+
+        let algorithm = key_type.map_or(previous.algorithm, KeyAlgorithm::from);
+        let new_account = Account::new(KeyPairGenerateFactory::generate(algorithm));
+        let new_account_id = did_key_from_public_key(algorithm, new_account.public_key_bytes())?;
+        let sequence = history.last().map_or(1, |last| last.sequence + 1);
+        let rotated_at = unix_now_secs().map_err(RotateKeyError::Time)?;
+
+        let previous_account = Account::new(store.key_pair(&previous)?);
+        let (signature, _recovery_id) = previous_account.sign(&rotation_statement(
+            sequence,
+            &previous_account_id,
+            &new_account_id,
+            rotated_at,
+        ))?;
+        let record = KeyRotationRecord {
+            sequence,
+            previous_account_id,
+            previous_algorithm: previous.algorithm,
+            previous_public_key: previous.public_key.clone(),
+            new_account_id,
+            new_algorithm: algorithm,
+            new_public_key: new_account.public_key_bytes().to_vec(),
+            rotated_at,
+            signature,
+        };
+
+        // 新しい鍵を保存してから記録する。記録に失敗した場合は旧鍵に戻す
+        store.save(&crate::application_service::StoredAccountKey {
+            algorithm,
+            public_key: new_account.public_key_bytes().to_vec(),
+            secret_key: new_account.secret_key_bytes().to_vec(),
+        })?;
+        if let Err(e) = log.append(&record) {
+            store.save(&previous)?;
+            return Err(e.into());
+        }
+        publish_event(
+            events,
+            AccountDomainEvent::KeyRotated(KeyRotated {
+                previous_account_id: record.previous_account_id.clone(),
+                new_account_id: record.new_account_id.clone(),
+                sequence: record.sequence,
+                rotated_at: record.rotated_at,
+            }),
+        );
+        Ok(record)
+    }
+
+    /// `account_id` 以降のローテーション記録を返す。
+    ///
+    /// `account_id` が記録の連鎖にも現在のアカウントにも含まれない場合は `None` を返す。
+    /// 現在のアカウントを指定した場合は空の一覧を返す。
+    pub fn rotation_history<S: AccountKeyStore, L: KeyRotationLog>(
+        store: &S,
+        log: &L,
+        account_id: &str,
+    ) -> Result<Option<Vec<KeyRotationRecord>>, RotationHistoryError> {
+        let history = log.history()?;
+        if let Some(start) = history
+            .iter()
+            .position(|record| record.previous_account_id == account_id)
+        {
+            return Ok(Some(history[start..].to_vec()));
+        }
+        match Self::public_key(store) {
+            Ok(Some(current)) if current.account_id == account_id => Ok(Some(Vec::new())),
+            Ok(_) => Ok(None),
+            Err(LookupPublicKeyError::KeyStore(e)) => Err(e.into()),
+            Err(LookupPublicKeyError::InvalidKey(e)) => Err(e.into()),
+        }
+    }
+
+    /// ローテーション記録の連鎖を検証する。
+    ///
+    /// 各記録について、識別子が公開鍵と一致すること、旧鍵の署名が正しいこと、
+    /// 前の記録の新しい識別子から続いていることを確認する。
+    pub fn verify_rotation_chain(records: &[KeyRotationRecord]) -> Result<(), RotationChainError> {
+        for (index, record) in records.iter().enumerate() {
+            let sequence = record.sequence;
+            if let Some(previous) = index.checked_sub(1).map(|i| &records[i]) {
+                if previous.new_account_id != record.previous_account_id
+                    || previous.sequence + 1 != sequence
+                {
+                    return Err(RotationChainError::Discontinuous { sequence });
+                }
+            }
+
+            let ids_match =
+                did_key_from_public_key(record.previous_algorithm, &record.previous_public_key)
+                    .is_ok_and(|id| id == record.previous_account_id)
+                    && did_key_from_public_key(record.new_algorithm, &record.new_public_key)
+                        .is_ok_and(|id| id == record.new_account_id);
+            if !ids_match {
+                return Err(RotationChainError::AccountIdMismatch { sequence });
+            }
+
+            let signed = verify_signature(
+                record.previous_algorithm,
+                &record.previous_public_key,
+                &record.statement(),
+                &record.signature,
+            )
+            .unwrap_or(false);
+            if !signed {
+                return Err(RotationChainError::InvalidSignature { sequence });
+            }
+        }
+        Ok(())
+    }
+
+    /// アカウントを無効化する。
+    ///
+    /// 保存済みの鍵で失効文に署名した記録を `revocations` に追加する。鍵は削除しない。
+    /// 以降、その識別子は [`AccountService::authenticate`] と失効リストを持つ
+    /// [`SessionTokenVerifier`](crate::infrastructure::session_token::SessionTokenVerifier) で拒否され、
+    /// 他のサービスは [`AccountService::find_revocation`] の結果を公開する
+    /// `GET /revocations/{account_id}` で署名を受け入れる前に失効を確認できる。
+    pub fn deactivate<S: AccountKeyStore, R: KeyRevocationList>(
+        store: &S,
+        revocations: &R,
+        reason: Option<String>,
+    ) -> Result<KeyRevocationRecord, DeactivateAccountError> {
+        let reason = reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+        if reason
+            .as_ref()
+            .is_some_and(|r| r.chars().count() > MAX_REVOCATION_REASON_CHARS)
+        {
+            return Err(DeactivateAccountError::Validation(format!(
+                "reason must be at most {MAX_REVOCATION_REASON_CHARS} characters"
+            )));
+        }
+
+        let stored = store.load()?.ok_or(DeactivateAccountError::NotFound)?;
+        let account_id = did_key_from_public_key(stored.algorithm, &stored.public_key)?;
+        if revocations.find(&account_id)?.is_some() {
+            return Err(DeactivateAccountError::AlreadyRevoked);
+        }
+        let revoked_at = unix_now_secs().map_err(DeactivateAccountError::Time)?;
+        let account = Account::new(store.key_pair(&stored)?);
+        let (signature, _recovery_id) = account.sign(&revocation_statement(
+            &account_id,
+            revoked_at,
+            reason.as_deref(),
+        ))?;
+        let record = KeyRevocationRecord {
+            account_id,
+            algorithm: stored.algorithm,
+            public_key: stored.public_key,
+            revoked_at,
+            reason,
+            signature,
+        };
+
+        // 鍵は消さずに失効を記録する。以後の認証と鍵の操作は失効リストで拒否する
+        revocations.add(&record)?;
+        Ok(record)
+    }
+
+    /// `account_id` の失効記録を返す。失効していなければ `None`。
+    pub fn find_revocation<R: KeyRevocationList>(
+        revocations: &R,
+        account_id: &str,
+    ) -> Result<Option<KeyRevocationRecord>, KeyRevocationListError> {
+        revocations.find(account_id)
+    }
+
+    /// 失効記録の識別子が公開鍵と一致し、失効させた鍵自身の署名であることを確かめる。
+    pub fn verify_revocation_record(record: &KeyRevocationRecord) -> bool {
+        did_key_from_public_key(record.algorithm, &record.public_key)
+            .is_ok_and(|id| id == record.account_id)
+            && verify_signature(
+                record.algorithm,
+                &record.public_key,
+                &record.statement(),
+                &record.signature,
+            )
+            .unwrap_or(false)
+    }
+
+    /// 保存済みアカウントのプロフィールを作り、アカウント鍵で署名する。既にある場合は `AlreadyExists`。
+    ///
+    /// 登録した端末ごとに `DeviceLinked` を通知する。
+    pub fn create_profile<S: AccountKeyStore, P: ProfileRepository, E: EventPublisher>(
+        store: &S,
+        profiles: &P,
+        events: &E,
+        fields: ProfileFields,
+    ) -> Result<AccountProfile, ManageProfileError> {
+        let (account_id, account) = stored_profile_signer(store)?;
+        if profiles.load(&account_id)?.is_some() {
+            return Err(ManageProfileError::AlreadyExists);
+        }
+
+        let mut profile = AccountProfile::new(
+            account_id,
+            fields.display_name,
+            fields.avatar_content_id,
+            fields.devices,
+            unix_now_secs().map_err(ManageProfileError::Time)?,
+        )
+        .map_err(ManageProfileError::Validation)?;
+        profile.signature = account.sign(&profile.statement())?.0;
+        profiles.save(&profile)?;
+        publish_linked_devices(events, &[], &profile);
+        Ok(profile)
+    }
+
+    /// 保存済みアカウントのプロフィールを `fields` で置き換え、署名し直す。
+    ///
+    /// 新しく加わった端末ごとに `DeviceLinked` を通知する。
+    pub fn update_profile<S: AccountKeyStore, P: ProfileRepository, E: EventPublisher>(
+        store: &S,
+        profiles: &P,
+        events: &E,
+        fields: ProfileFields,
+    ) -> Result<AccountProfile, ManageProfileError> {
+        let (account_id, account) = stored_profile_signer(store)?;
+        let mut profile = profiles
+            .load(&account_id)?
+            .ok_or(ManageProfileError::ProfileNotFound)?;
+        let previous_devices = profile.devices.clone();
+
+        profile
+            .update(
+                fields.display_name,
+                fields.avatar_content_id,
+                fields.devices,
+                unix_now_secs().map_err(ManageProfileError::Time)?,
+            )
+            .map_err(ManageProfileError::Validation)?;
+        profile.signature = account.sign(&profile.statement())?.0;
+        profiles.save(&profile)?;
+        publish_linked_devices(events, &previous_devices, &profile);
+        Ok(profile)
+    }
+
+    /// アカウント識別子（did:key）でプロフィールを引く。
+    ///
+    /// 署名がそのアカウントの鍵と合わないプロフィールは返さず `InvalidSignature` にする。
+    pub fn get_profile<P: ProfileRepository>(
+        profiles: &P,
+        account_id: &str,
+    ) -> Result<Option<AccountProfile>, ManageProfileError> {
+        let Some(profile) = profiles.load(account_id)? else {
+            return Ok(None);
+        };
+        if profile.account_id != account_id || !Self::verify_profile(&profile) {
+            return Err(ManageProfileError::InvalidSignature);
+        }
+        Ok(Some(profile))
+    }
+
+    /// プロフィールが `account_id` の鍵で署名されていることを確かめる。
+    pub fn verify_profile(profile: &AccountProfile) -> bool {
+        public_key_from_did_key(&profile.account_id).is_ok_and(|(algorithm, public_key)| {
+            verify_signature(
+                algorithm,
+                &public_key,
+                &profile.statement(),
+                &profile.signature,
+            )
+            .unwrap_or(false)
+        })
+    }
+
+    /// `account_id` に対して認証チャレンジを発行する。
+    ///
+    /// クライアントは [`AuthChallenge::message`] にアカウント鍵で署名し、
+    /// [`AccountService::authenticate`] に渡してセッショントークンを受け取る。
+    pub fn issue_auth_challenge<C: AuthChallengeStore>(
+        challenges: &C,
+        account_id: &str,
+    ) -> Result<AuthChallenge, IssueAuthChallengeError> {
+        public_key_from_did_key(account_id)?;
+        let now = unix_now_secs().map_err(IssueAuthChallengeError::Time)?;
+
+        let mut nonce = [0u8; 32];
+        OsRng.fill_bytes(&mut nonce);
+        let challenge = AuthChallenge {
+            nonce: URL_SAFE_NO_PAD.encode(nonce),
+            account_id: account_id.to_string(),
+            expires_at: now.saturating_add(AUTH_CHALLENGE_TTL_SECS),
+        };
+        challenges.insert(&challenge)?;
+        Ok(challenge)
+    }
+
+    /// チャレンジへの署名を `account_id` の公開鍵で検証する。
+    ///
+    /// 鍵の書き出しなどの操作の直前に、セッションに加えて鍵を今も持っていることを確かめるのに
+    /// 使う。チャレンジは検証の成否にかかわらず使用済みになる。失効したアカウントは `Revoked`。
+    pub fn confirm_challenge<C: AuthChallengeStore, R: KeyRevocationList>(
+        challenges: &C,
+        revocations: &R,
+        account_id: &str,
+        nonce: &str,
+        signature: &[u8],
+    ) -> Result<(), AuthenticateError> {
+        let now = unix_now_secs().map_err(AuthenticateError::Time)?;
+        let challenge = challenges
+            .take(nonce)?
+            .filter(|c| c.account_id == account_id && c.expires_at > now)
+            .ok_or(AuthenticateError::InvalidChallenge)?;
+
+        let (algorithm, public_key) = public_key_from_did_key(account_id)?;
+        if !verify_signature(algorithm, &public_key, &challenge.message(), signature)? {
+            return Err(AuthenticateError::InvalidSignature);
+        }
+        if revocations.find(account_id)?.is_some() {
+            return Err(AuthenticateError::Revoked);
+        }
+        Ok(())
+    }
+
+    /// チャレンジへの署名を `account_id` の公開鍵で検証し、短命のセッショントークンを発行する。
+    ///
+    /// チャレンジは検証の成否にかかわらず使用済みになる。失効したアカウントは `Revoked`。
+    pub fn authenticate<C: AuthChallengeStore, R: KeyRevocationList>(
+        challenges: &C,
+        revocations: &R,
+        signer: &SessionTokenSigner,
+        account_id: &str,
+        nonce: &str,
+        signature: &[u8],
+    ) -> Result<SessionToken, AuthenticateError> {
+        Self::confirm_challenge(challenges, revocations, account_id, nonce, signature)?;
+
+        let now = unix_now_secs().map_err(AuthenticateError::Time)?;
+        let expires_at = now.saturating_add(SESSION_TTL_SECS);
+        let token = signer.sign(&SessionClaims {
+            iss: signer.key_id().to_string(),
+            sub: account_id.to_string(),
+            iat: now,
+            exp: expires_at,
+            jti: generate_jti(),
+        })?;
+        Ok(SessionToken {
+            account_id: account_id.to_string(),
+            token,
+            issued_at: now,
+            expires_at,
+        })
     }
 
     pub fn issue_delegated_token<S: AccountKeyStore>(
@@ -94,7 +866,7 @@ impl AccountService {
         }
 
         let owner_key_id = key_id_from_public_key(&stored.public_key);
-        let now = unix_now_secs()?;
+        let now = unix_now_secs().map_err(IssueDelegatedTokenError::Time)?;
         let expires_at = now.saturating_add(req.ttl_secs);
         let jti = generate_jti();
 
@@ -122,15 +894,13 @@ impl AccountService {
             att,
         };
 
-        let key_pair = KeyPairGenerateFactory::from_key_bytes(
-            stored.algorithm,
-            &stored.public_key,
-            &stored.secret_key,
-        )
-        .map_err(IssueDelegatedTokenError::InvalidKey)?;
+        let key_pair = store
+            .key_pair(&stored)
+            .map_err(IssueDelegatedTokenError::InvalidKey)?;
         let account = Account::new(key_pair);
         let delegated_token = sign_es256_jwt_payload(&payload, |signing_input| {
-            let (signature, _recovery_id) = account.sign(signing_input);
+            let (signature, _recovery_id) =
+                account.sign(signing_input).map_err(|e| e.to_string())?;
             Ok(signature)
         })
         .map_err(IssueDelegatedTokenError::JwtSigning)?;
@@ -144,11 +914,68 @@ impl AccountService {
     }
 }
 
-fn unix_now_secs() -> Result<u64, IssueDelegatedTokenError> {
+/// 保存済みアカウントの識別子と、プロフィールに署名するための鍵を返す。
+fn stored_profile_signer<S: AccountKeyStore>(
+    store: &S,
+) -> Result<(String, Account), ManageProfileError> {
+    let stored = store.load()?.ok_or(ManageProfileError::NotFound)?;
+    let account_id = did_key_from_public_key(stored.algorithm, &stored.public_key)?;
+    let account = Account::new(store.key_pair(&stored)?);
+    Ok((account_id, account))
+}
+
+/// イベントを通知する。
+///
+/// 永続化はすでに完了しているため、通知の失敗でユースケース自体を失敗させない。
+/// イベントの購読側は取りこぼしを前提に設計すること。
+fn publish_event<E: EventPublisher>(events: &E, event: AccountDomainEvent) {
+    let _ = events.publish(event);
+}
+
+/// `previous` になかった端末ごとに `DeviceLinked` を通知する。
+fn publish_linked_devices<E: EventPublisher>(
+    events: &E,
+    previous: &[AccountDevice],
+    profile: &AccountProfile,
+) {
+    for device in &profile.devices {
+        if previous.iter().any(|p| p.device_id == device.device_id) {
+            continue;
+        }
+        publish_event(
+            events,
+            AccountDomainEvent::DeviceLinked(DeviceLinked {
+                account_id: profile.account_id.clone(),
+                device_id: device.device_id.clone(),
+                name: device.name.clone(),
+                linked_at: profile.updated_at,
+            }),
+        );
+    }
+}
+
+fn derive_stored_child_key<S: AccountKeyStore>(
+    store: &S,
+    path: &KeyDerivationPath,
+) -> Result<(DerivedAccountKey, Box<dyn AccountKeyPair>), DeriveKeyError> {
+    let stored = store.load()?.ok_or(DeriveKeyError::NotFound)?;
+    let account_id = did_key_from_public_key(stored.algorithm, &stored.public_key)?;
+    let child = derive_child_key(stored.algorithm, &stored.secret_key, path)?;
+    let key = DerivedAccountKey {
+        account_id,
+        path: path.to_string(),
+        key_id: did_key_from_public_key(stored.algorithm, child.public_key_bytes())?,
+        algorithm: stored.algorithm,
+        public_key: child.public_key_bytes().to_vec(),
+    };
+    Ok((key, child))
+}
+
+fn unix_now_secs() -> Result<u64, String> {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|d| d.as_secs())
-        .map_err(|e| IssueDelegatedTokenError::Time(e.to_string()))
+        .map_err(|e| e.to_string())
 }
 
 fn generate_jti() -> String {
@@ -179,19 +1006,670 @@ fn nibble_to_hex(n: u8) -> char {
 
 #[cfg(test)]
 mod tests {
-    use super::AccountService;
+    use super::{AccountService, NoOpEventPublisher};
     use crate::application_service::{
-        IssueDelegatedTokenError, IssueDelegatedTokenRequest, KeyTypeMapper, SignError,
+        AccountKeyStore, AuthenticateError, DeactivateAccountError, DeriveKeyError, ExportKeyError,
+        ExportMnemonicError, ImportKeyError, IssueAuthChallengeError, IssueDelegatedTokenError,
+        IssueDelegatedTokenRequest, KeyRotationLog, KeyTypeMapper, ManageProfileError,
+        ProfileFields, RecoverFromSharesError, RestoreFromMnemonicError, RotateKeyError,
+        RotationChainError, SignError, SplitRecoverySharesError, VerifySignatureError,
     };
+    use crate::application_service::{EventPublisher, EventPublisherError};
+    use crate::application_service::{GuardedKeyOperationError, KeyAuditLog, ProfileRepository};
     use crate::domain::delegation::{DelegatedCapability, DelegationClaims};
+    use crate::domain::events::{AccountDomainEvent, KeyRotated};
+    use crate::domain::key_audit::{KeyAuditQuery, KeyOperation, KeyOperationOutcome};
+    use crate::domain::profile::AccountDevice;
+    use crate::infrastructure::auth_challenge_store::InMemoryAuthChallengeStore;
+    use crate::infrastructure::key_audit_log::InMemoryKeyAuditLog;
+    use crate::infrastructure::key_derivation::KeyDerivationPath;
+    use crate::infrastructure::key_format::KeyFormat;
+    use crate::infrastructure::key_operation_limiter::{
+        KeyOperationLimitConfig, KeyOperationRateLimiter,
+    };
+    use crate::infrastructure::key_pair::KeyAlgorithm;
+    use crate::infrastructure::key_rotation_log::InMemoryKeyRotationLog;
     use crate::infrastructure::key_store::InMemoryAccountKeyStore;
+    use crate::infrastructure::profile_repository::InMemoryProfileRepository;
+    use crate::infrastructure::revocation_list::InMemoryKeyRevocationList;
+    use crate::infrastructure::session_token::SessionTokenSigner;
     use base64::engine::general_purpose::URL_SAFE_NO_PAD;
     use base64::Engine;
+    use std::sync::Mutex;
+
+    #[test]
+    fn mnemonic_export_restores_the_same_key_on_another_store() {
+        for key_type in [KeyTypeMapper::K256, KeyTypeMapper::P256] {
+            let store = InMemoryAccountKeyStore::default();
+            let account = AccountService::create(&store, &NoOpEventPublisher, key_type).unwrap();
+            let exported = AccountService::export_mnemonic(&store).unwrap();
+            assert_eq!(exported.mnemonic.split(' ').count(), 24);
+
+            let key_type = match exported.algorithm {
+                crate::infrastructure::key_pair::KeyAlgorithm::K256 => KeyTypeMapper::K256,
+                crate::infrastructure::key_pair::KeyAlgorithm::P256 => KeyTypeMapper::P256,
+            };
+            let new_device = InMemoryAccountKeyStore::default();
+            let restored =
+                AccountService::restore_from_mnemonic(&new_device, key_type, &exported.mnemonic)
+                    .unwrap();
+            assert_eq!(restored.public_key_bytes(), account.public_key_bytes());
+            assert_eq!(
+                AccountService::public_key(&new_device)
+                    .unwrap()
+                    .unwrap()
+                    .account_id,
+                exported.account_id
+            );
+        }
+    }
+
+    #[test]
+    fn mnemonic_export_and_restore_report_errors() {
+        let store = InMemoryAccountKeyStore::default();
+        assert!(matches!(
+            AccountService::export_mnemonic(&store),
+            Err(ExportMnemonicError::NotFound)
+        ));
+        assert!(matches!(
+            AccountService::restore_from_mnemonic(&store, KeyTypeMapper::P256, "not a mnemonic"),
+            Err(RestoreFromMnemonicError::InvalidMnemonic(_))
+        ));
+        assert!(store.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn sign_as_requires_matching_account_id() {
+        for key_type in [KeyTypeMapper::K256, KeyTypeMapper::P256] {
+            let store = InMemoryAccountKeyStore::default();
+            let account = AccountService::create(&store, &NoOpEventPublisher, key_type).unwrap();
+            let account_id = AccountService::public_key(&store)
+                .unwrap()
+                .unwrap()
+                .account_id;
+
+            let signed = AccountService::sign_as(&store, &account_id, b"operation").unwrap();
+            assert_eq!(signed.account_id, account_id);
+            assert_eq!(signed.public_key, account.public_key_bytes());
+            assert_eq!(signed.signature, account.sign(b"operation").unwrap().0);
+
+            let err = AccountService::sign_as(&store, "did:key:zOther", b"operation").unwrap_err();
+            assert!(matches!(err, SignError::NotFound));
+        }
+    }
+
+    #[test]
+    fn verify_checks_signatures_from_the_stored_key() {
+        let store = InMemoryAccountKeyStore::default();
+        let account =
+            AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
+        let (signature, _) = AccountService::sign(&store, b"operation").unwrap();
+
+        assert!(AccountService::verify(
+            KeyTypeMapper::P256,
+            account.public_key_bytes(),
+            b"operation",
+            &signature
+        )
+        .unwrap());
+        assert!(!AccountService::verify(
+            KeyTypeMapper::P256,
+            account.public_key_bytes(),
+            b"other",
+            &signature
+        )
+        .unwrap());
+        assert!(matches!(
+            AccountService::verify(KeyTypeMapper::K256, b"garbage", b"operation", &signature),
+            Err(VerifySignatureError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn rotate_key_records_a_verifiable_chain() {
+        let store = InMemoryAccountKeyStore::default();
+        let log = InMemoryKeyRotationLog::default();
+        AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
+        let original_id = AccountService::public_key(&store)
+            .unwrap()
+            .unwrap()
+            .account_id;
+
+        let first = AccountService::rotate_key(&store, &log, &NoOpEventPublisher, None).unwrap();
+        assert_eq!(first.sequence, 1);
+        assert_eq!(first.previous_account_id, original_id);
+        assert_eq!(first.new_algorithm, KeyAlgorithm::K256);
+        let second = AccountService::rotate_key(
+            &store,
+            &log,
+            &NoOpEventPublisher,
+            Some(KeyTypeMapper::P256),
+        )
+        .unwrap();
+        assert_eq!(second.sequence, 2);
+        assert_eq!(second.previous_account_id, first.new_account_id);
+
+        // 現在の鍵は最後の記録の新しい鍵
+        let current = AccountService::public_key(&store).unwrap().unwrap();
+        assert_eq!(current.account_id, second.new_account_id);
+        assert_eq!(current.algorithm, KeyAlgorithm::P256);
+
+        let history = AccountService::rotation_history(&store, &log, &original_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(history, vec![first.clone(), second.clone()]);
+        AccountService::verify_rotation_chain(&history).unwrap();
+        assert_eq!(
+            AccountService::rotation_history(&store, &log, &second.new_account_id)
+                .unwrap()
+                .unwrap(),
+            vec![]
+        );
+        assert!(
+            AccountService::rotation_history(&store, &log, "did:key:zUnknown")
+                .unwrap()
+                .is_none()
+        );
+
+        let mut forged = second.clone();
+        forged.rotated_at += 1;
+        assert_eq!(
+            AccountService::verify_rotation_chain(&[first.clone(), forged]),
+            Err(RotationChainError::InvalidSignature { sequence: 2 })
+        );
+        assert_eq!(
+            AccountService::verify_rotation_chain(&[second, first]),
+            Err(RotationChainError::Discontinuous { sequence: 1 })
+        );
+    }
+
+    #[test]
+    fn rotate_key_restarts_history_after_account_is_recreated() {
+        let store = InMemoryAccountKeyStore::default();
+        let log = InMemoryKeyRotationLog::default();
+        assert!(matches!(
+            AccountService::rotate_key(&store, &log, &NoOpEventPublisher, None),
+            Err(RotateKeyError::NotFound)
+        ));
+
+        AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
+        AccountService::rotate_key(&store, &log, &NoOpEventPublisher, None).unwrap();
+        AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
+
+        let record = AccountService::rotate_key(&store, &log, &NoOpEventPublisher, None).unwrap();
+        assert_eq!(record.sequence, 1);
+        assert_eq!(log.history().unwrap(), vec![record]);
+    }
+
+    #[test]
+    fn authenticate_issues_session_token_for_signed_challenge() {
+        let store = InMemoryAccountKeyStore::default();
+        let challenges = InMemoryAuthChallengeStore::default();
+        let revocations = InMemoryKeyRevocationList::default();
+        let signer = SessionTokenSigner::generate();
+        let account =
+            AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
+        let account_id = AccountService::public_key(&store)
+            .unwrap()
+            .unwrap()
+            .account_id;
+
+        let challenge = AccountService::issue_auth_challenge(&challenges, &account_id).unwrap();
+        // サーバー側の署名 API ではチャレンジに署名できない
+        assert!(matches!(
+            AccountService::sign_as(&store, &account_id, &challenge.message()),
+            Err(SignError::ReservedMessage)
+        ));
+        assert!(matches!(
+            AccountService::sign(&store, &challenge.message()),
+            Err(SignError::ReservedMessage)
+        ));
+        let (signature, _) = account.sign(&challenge.message()).unwrap();
+        let session = AccountService::authenticate(
+            &challenges,
+            &revocations,
+            &signer,
+            &account_id,
+            &challenge.nonce,
+            &signature,
+        )
+        .unwrap();
+        assert_eq!(session.account_id, account_id);
+        let claims = signer
+            .verifier()
+            .verify(&session.token, session.issued_at)
+            .unwrap();
+        assert_eq!(claims.sub, account_id);
+        assert_eq!(claims.exp, session.expires_at);
+
+        // チャレンジは使い捨て
+        assert!(matches!(
+            AccountService::authenticate(
+                &challenges,
+                &revocations,
+                &signer,
+                &account_id,
+                &challenge.nonce,
+                &signature,
+            ),
+            Err(AuthenticateError::InvalidChallenge)
+        ));
+
+        let challenge = AccountService::issue_auth_challenge(&challenges, &account_id).unwrap();
+        let wrong = AccountService::sign_as(&store, &account_id, b"other").unwrap();
+        assert!(matches!(
+            AccountService::authenticate(
+                &challenges,
+                &revocations,
+                &signer,
+                &account_id,
+                &challenge.nonce,
+                &wrong.signature,
+            ),
+            Err(AuthenticateError::InvalidSignature)
+        ));
+
+        assert!(matches!(
+            AccountService::issue_auth_challenge(&challenges, "user:abc"),
+            Err(IssueAuthChallengeError::InvalidAccountId(_))
+        ));
+    }
+
+    #[test]
+    fn deactivate_revokes_key_and_rejects_later_authentication() {
+        let store = InMemoryAccountKeyStore::default();
+        let revocations = InMemoryKeyRevocationList::default();
+        let challenges = InMemoryAuthChallengeStore::default();
+        let signer = SessionTokenSigner::generate();
+        assert!(matches!(
+            AccountService::deactivate(&store, &revocations, None),
+            Err(DeactivateAccountError::NotFound)
+        ));
+
+        let account =
+            AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
+        let account_id = AccountService::public_key(&store)
+            .unwrap()
+            .unwrap()
+            .account_id;
+        let challenge = AccountService::issue_auth_challenge(&challenges, &account_id).unwrap();
+        let (signature, _) = account.sign(&challenge.message()).unwrap();
+
+        let record =
+            AccountService::deactivate(&store, &revocations, Some(" lost device ".to_string()))
+                .unwrap();
+        assert_eq!(record.account_id, account_id);
+        assert_eq!(record.reason.as_deref(), Some("lost device"));
+        assert!(AccountService::verify_revocation_record(&record));
+        assert!(store.load().unwrap().is_some());
+        assert_eq!(
+            AccountService::find_revocation(&revocations, &account_id).unwrap(),
+            Some(record.clone())
+        );
+        assert!(matches!(
+            AccountService::deactivate(&store, &revocations, None),
+            Err(DeactivateAccountError::AlreadyRevoked)
+        ));
+
+        let mut tampered = record;
+        tampered.reason = Some("other".to_string());
+        assert!(!AccountService::verify_revocation_record(&tampered));
+
+        // 失効前に署名したチャレンジでもセッションは発行しない
+        assert!(matches!(
+            AccountService::authenticate(
+                &challenges,
+                &revocations,
+                &signer,
+                &account_id,
+                &challenge.nonce,
+                &signature,
+            ),
+            Err(AuthenticateError::Revoked)
+        ));
+    }
+
+    #[test]
+    fn derived_keys_are_stable_per_path_and_sign_without_the_root_key() {
+        let store = InMemoryAccountKeyStore::default();
+        let path = KeyDerivationPath::content("bafy-content").unwrap();
+        assert!(matches!(
+            AccountService::derive_key(&store, &path),
+            Err(DeriveKeyError::NotFound)
+        ));
+
+        AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
+        let root = AccountService::public_key(&store).unwrap().unwrap();
+        let derived = AccountService::derive_key(&store, &path).unwrap();
+        assert_eq!(derived.account_id, root.account_id);
+        assert_eq!(derived.path, "content/bafy-content");
+        assert_ne!(derived.public_key, root.public_key);
+        assert_ne!(derived.key_id, root.account_id);
+        assert_eq!(
+            AccountService::derive_key(&store, &path)
+                .unwrap()
+                .public_key,
+            derived.public_key
+        );
+
+        let peer = KeyDerivationPath::peer(&root.account_id).unwrap();
+        assert_ne!(
+            AccountService::derive_key(&store, &peer)
+                .unwrap()
+                .public_key,
+            derived.public_key
+        );
+
+        let signed = AccountService::sign_with_derived_key(&store, &path, b"hello").unwrap();
+        assert_eq!(signed.key.public_key, derived.public_key);
+        assert!(AccountService::verify(
+            KeyTypeMapper::K256,
+            &derived.public_key,
+            b"hello",
+            &signed.signature
+        )
+        .unwrap());
+        assert!(!AccountService::verify(
+            KeyTypeMapper::K256,
+            &root.public_key,
+            b"hello",
+            &signed.signature
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn encryption_keys_are_signed_by_the_account_and_unknown_for_other_ids() {
+        let store = InMemoryAccountKeyStore::default();
+        AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
+        let root = AccountService::public_key(&store).unwrap().unwrap();
+
+        let key = AccountService::find_encryption_key(&store, &root.account_id)
+            .unwrap()
+            .expect("should exist");
+        assert_eq!(key.kem, "DHKEM(P-256, HKDF-SHA256)");
+        assert_eq!(key.public_key.len(), 65);
+        assert_ne!(key.public_key, root.public_key);
+        assert!(AccountService::verify_encryption_key(&key));
+
+        // 別の鍵を名乗る書き換えは検証に通らない
+        let mut forged = key.clone();
+        forged.public_key = AccountService::derive_key(
+            &store,
+            &KeyDerivationPath::content("bafy-content").unwrap(),
+        )
+        .unwrap()
+        .public_key;
+        assert!(!AccountService::verify_encryption_key(&forged));
+
+        let exported = AccountService::export_encryption_key(&store).unwrap();
+        assert_eq!(exported.public_key, key.public_key);
+        assert_eq!(exported.secret_key.len(), 32);
+
+        assert!(
+            AccountService::find_encryption_key(&store, "did:key:zOther")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn key_operations_are_rate_limited_and_audited() {
+        let store = InMemoryAccountKeyStore::default();
+        let audit = InMemoryKeyAuditLog::default();
+        let limiter = KeyOperationRateLimiter::new(Some(KeyOperationLimitConfig::new(1, 2)));
+        let sign = || AccountService::sign(&store, b"hello");
+
+        // 鍵がない場合は失敗として記録する
+        assert!(matches!(
+            AccountService::run_key_operation(&audit, &limiter, KeyOperation::Sign, None, sign),
+            Err(GuardedKeyOperationError::Operation(SignError::NotFound))
+        ));
+        AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
+        AccountService::run_key_operation(&audit, &limiter, KeyOperation::Sign, None, sign)
+            .unwrap();
+        assert!(matches!(
+            AccountService::run_key_operation(&audit, &limiter, KeyOperation::Sign, Some("did:key:zCaller"), || -> Result<(), SignError> {
+                panic!("rate-limited operation must not run")
+            }),
+            Err(GuardedKeyOperationError::RateLimited { retry_after_secs }) if retry_after_secs > 0
+        ));
+
+        let entries = audit
+            .query(&KeyAuditQuery {
+                since: None,
+                operation: Some(KeyOperation::Sign),
+                limit: 10,
+            })
+            .unwrap();
+        let outcomes: Vec<_> = entries.iter().map(|e| e.outcome).collect();
+        assert_eq!(
+            outcomes,
+            [
+                KeyOperationOutcome::Failed,
+                KeyOperationOutcome::Succeeded,
+                KeyOperationOutcome::RateLimited
+            ]
+        );
+        assert_eq!(
+            entries[0].detail.as_deref(),
+            Some("stored account key not found")
+        );
+        assert_eq!(entries[0].principal, None);
+        assert_eq!(entries[2].principal.as_deref(), Some("did:key:zCaller"));
+    }
+
+    #[derive(Default)]
+    struct RecordingEventPublisher {
+        events: Mutex<Vec<AccountDomainEvent>>,
+    }
+
+    impl EventPublisher for RecordingEventPublisher {
+        fn publish(&self, event: AccountDomainEvent) -> Result<(), EventPublisherError> {
+            self.events.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn account_changes_are_published_as_events() {
+        let store = InMemoryAccountKeyStore::default();
+        let log = InMemoryKeyRotationLog::default();
+        let profiles = InMemoryProfileRepository::default();
+        let events = RecordingEventPublisher::default();
+
+        AccountService::create(&store, &events, KeyTypeMapper::P256).unwrap();
+        let created = AccountService::public_key(&store).unwrap().unwrap();
+        let record = AccountService::rotate_key(&store, &log, &events, None).unwrap();
+
+        let device = |id: &str| AccountDevice {
+            device_id: id.to_string(),
+            name: format!("device {id}"),
+        };
+        let fields = |devices| ProfileFields {
+            display_name: "Alice".into(),
+            avatar_content_id: None,
+            devices,
+        };
+        let profile =
+            AccountService::create_profile(&store, &profiles, &events, fields(vec![device("a")]))
+                .unwrap();
+        AccountService::update_profile(
+            &store,
+            &profiles,
+            &events,
+            fields(vec![device("a"), device("b")]),
+        )
+        .unwrap();
+
+        let events = events.events.into_inner().unwrap();
+        assert_eq!(events.len(), 4);
+        assert!(matches!(
+            &events[0],
+            AccountDomainEvent::Created(e)
+                if e.account_id == created.account_id
+                    && e.algorithm == "P256"
+                    && e.public_key == created.public_key
+        ));
+        assert_eq!(
+            events[1],
+            AccountDomainEvent::KeyRotated(KeyRotated {
+                previous_account_id: created.account_id,
+                new_account_id: record.new_account_id.clone(),
+                sequence: 1,
+                rotated_at: record.rotated_at,
+            })
+        );
+        // 既に登録済みの端末は再通知しない
+        let linked: Vec<_> = events[2..]
+            .iter()
+            .map(|event| match event {
+                AccountDomainEvent::DeviceLinked(e) => {
+                    assert_eq!(e.account_id, profile.account_id);
+                    e.device_id.as_str()
+                }
+                other => panic!("unexpected event: {other:?}"),
+            })
+            .collect();
+        assert_eq!(linked, ["a", "b"]);
+    }
+
+    #[test]
+    fn recovery_shares_restore_the_account_from_threshold_shares() {
+        let store = InMemoryAccountKeyStore::default();
+        AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
+        let original = store.load().unwrap().unwrap();
+        let split = AccountService::split_recovery_shares(&store, 2, 3).unwrap();
+        assert_eq!(split.shares.len(), 3);
+
+        let new_device = InMemoryAccountKeyStore::default();
+        let shares = [split.shares[2].clone(), split.shares[0].clone()];
+        let account = AccountService::recover_from_shares(&new_device, &shares).unwrap();
+        assert_eq!(account.secret_key_bytes(), original.secret_key.as_slice());
+        assert_eq!(
+            AccountService::public_key(&new_device)
+                .unwrap()
+                .unwrap()
+                .account_id,
+            split.account_id
+        );
+
+        // シェアの識別子を書き換えても別のアカウントとしては復元されない
+        let other = InMemoryAccountKeyStore::default();
+        AccountService::create(&other, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
+        let other_id = AccountService::public_key(&other)
+            .unwrap()
+            .unwrap()
+            .account_id;
+        let tampered: Vec<String> = shares
+            .iter()
+            .map(|share| share.replace(&split.account_id, &other_id))
+            .collect();
+        assert!(matches!(
+            AccountService::recover_from_shares(&InMemoryAccountKeyStore::default(), &tampered),
+            Err(RecoverFromSharesError::AccountMismatch)
+        ));
+
+        assert!(matches!(
+            AccountService::split_recovery_shares(&InMemoryAccountKeyStore::default(), 2, 3),
+            Err(SplitRecoverySharesError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn exported_keys_import_into_another_store() {
+        for format in [KeyFormat::Pem, KeyFormat::Jwk] {
+            let store = InMemoryAccountKeyStore::default();
+            AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
+            let public_only = AccountService::export_key(&store, format, false).unwrap();
+            assert!(public_only.private_key.is_none());
+
+            let exported = AccountService::export_key(&store, format, true).unwrap();
+            let new_device = InMemoryAccountKeyStore::default();
+            AccountService::import_key(&new_device, &exported.private_key.unwrap()).unwrap();
+            assert_eq!(
+                AccountService::public_key(&new_device)
+                    .unwrap()
+                    .unwrap()
+                    .account_id,
+                exported.account_id
+            );
+        }
+
+        assert!(matches!(
+            AccountService::export_key(&InMemoryAccountKeyStore::default(), KeyFormat::Pem, true),
+            Err(ExportKeyError::NotFound)
+        ));
+        assert!(matches!(
+            AccountService::import_key(&InMemoryAccountKeyStore::default(), "not a key"),
+            Err(ImportKeyError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn profile_is_created_updated_and_looked_up_by_account_id() {
+        let store = InMemoryAccountKeyStore::default();
+        let profiles = InMemoryProfileRepository::default();
+        let fields = |display_name: &str| ProfileFields {
+            display_name: display_name.to_string(),
+            avatar_content_id: Some("cid-avatar".to_string()),
+            devices: vec![AccountDevice {
+                device_id: "phone".to_string(),
+                name: "Phone".to_string(),
+            }],
+        };
+        assert!(matches!(
+            AccountService::create_profile(&store, &profiles, &NoOpEventPublisher, fields("Alice")),
+            Err(ManageProfileError::NotFound)
+        ));
+
+        AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
+        assert!(matches!(
+            AccountService::update_profile(&store, &profiles, &NoOpEventPublisher, fields("Alice")),
+            Err(ManageProfileError::ProfileNotFound)
+        ));
+        let created =
+            AccountService::create_profile(&store, &profiles, &NoOpEventPublisher, fields("Alice"))
+                .unwrap();
+        assert!(matches!(
+            AccountService::create_profile(&store, &profiles, &NoOpEventPublisher, fields("Alice")),
+            Err(ManageProfileError::AlreadyExists)
+        ));
+
+        let updated = AccountService::update_profile(
+            &store,
+            &profiles,
+            &NoOpEventPublisher,
+            fields("Alice B"),
+        )
+        .unwrap();
+        assert_eq!(updated.account_id, created.account_id);
+        assert!(AccountService::verify_profile(&created));
+        assert!(AccountService::verify_profile(&updated));
+        assert_eq!(
+            AccountService::get_profile(&profiles, &created.account_id).unwrap(),
+            Some(updated.clone())
+        );
+
+        // 署名と合わない内容は返さない
+        let mut tampered = updated;
+        tampered.display_name = "Mallory".to_string();
+        profiles.save(&tampered).unwrap();
+        assert!(matches!(
+            AccountService::get_profile(&profiles, &created.account_id),
+            Err(ManageProfileError::InvalidSignature)
+        ));
+        assert!(matches!(
+            AccountService::update_profile(&store, &profiles, &NoOpEventPublisher, fields("")),
+            Err(ManageProfileError::Validation(_))
+        ));
+    }
 
     #[test]
     fn create_k256_stores_valid_account() {
         let store = InMemoryAccountKeyStore::default();
-        let account = AccountService::create(&store, KeyTypeMapper::K256).unwrap();
+        let account =
+            AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
         assert_eq!(account.public_key_bytes().len(), 65);
         assert_eq!(account.secret_key_bytes().len(), 32);
     }
@@ -199,7 +1677,8 @@ mod tests {
     #[test]
     fn create_p256_stores_valid_account() {
         let store = InMemoryAccountKeyStore::default();
-        let account = AccountService::create(&store, KeyTypeMapper::P256).unwrap();
+        let account =
+            AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
         assert_eq!(account.public_key_bytes().len(), 65);
         assert_eq!(account.secret_key_bytes().len(), 32);
     }
@@ -207,31 +1686,34 @@ mod tests {
     #[test]
     fn sign_uses_stored_key() {
         let store = InMemoryAccountKeyStore::default();
-        let account = AccountService::create(&store, KeyTypeMapper::K256).unwrap();
+        let account =
+            AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
         let msg = b"sign-test-message";
         let (sig_from_service, _rec_id1) = AccountService::sign(&store, msg).unwrap();
-        let (sig_from_account, _rec_id2) = account.sign(msg);
+        let (sig_from_account, _rec_id2) = account.sign(msg).unwrap();
         assert_eq!(sig_from_service, sig_from_account);
     }
 
     #[test]
     fn sign_uses_stored_key_p256() {
         let store = InMemoryAccountKeyStore::default();
-        let account = AccountService::create(&store, KeyTypeMapper::P256).unwrap();
+        let account =
+            AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
         let msg = b"sign-test-message-p256";
         let (sig_from_service, _rec_id1) = AccountService::sign(&store, msg).unwrap();
-        let (sig_from_account, _rec_id2) = account.sign(msg);
+        let (sig_from_account, _rec_id2) = account.sign(msg).unwrap();
         assert_eq!(sig_from_service, sig_from_account);
     }
 
     #[test]
     fn sign_uses_latest_created_key() {
         let store = InMemoryAccountKeyStore::default();
-        AccountService::create(&store, KeyTypeMapper::K256).unwrap();
+        AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
         let msg = b"override-test-message";
-        let account_latest = AccountService::create(&store, KeyTypeMapper::P256).unwrap();
+        let account_latest =
+            AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
         let (sig_from_service, _rec_id1) = AccountService::sign(&store, msg).unwrap();
-        let (sig_from_latest, _rec_id2) = account_latest.sign(msg);
+        let (sig_from_latest, _rec_id2) = account_latest.sign(msg).unwrap();
         assert_eq!(sig_from_service, sig_from_latest);
     }
 
@@ -245,19 +1727,48 @@ mod tests {
     #[test]
     fn delete_removes_stored_key() {
         let store = InMemoryAccountKeyStore::default();
-        AccountService::create(&store, KeyTypeMapper::K256).unwrap();
+        AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
         AccountService::delete(&store).unwrap();
         let err = AccountService::sign(&store, b"after-delete").unwrap_err();
         assert!(matches!(err, SignError::NotFound));
     }
 
+    #[test]
+    fn find_public_key_decodes_any_account_did() {
+        let store = InMemoryAccountKeyStore::default();
+        assert!(AccountService::public_key(&store).unwrap().is_none());
+
+        let account =
+            AccountService::create(&store, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
+        let own = AccountService::public_key(&store).unwrap().unwrap();
+        assert!(own.account_id.starts_with("did:key:z"));
+        assert_eq!(own.public_key, account.public_key_bytes());
+
+        let found = AccountService::find_public_key(&own.account_id).unwrap();
+        assert_eq!(found.public_key, account.public_key_bytes());
+        assert!(AccountService::find_public_key("did:key:zDnOther").is_err());
+
+        // 保存していないアカウントの公開鍵も識別子から取り出せる
+        let other_store = InMemoryAccountKeyStore::default();
+        let other =
+            AccountService::create(&other_store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
+        let other_id = AccountService::public_key(&other_store)
+            .unwrap()
+            .unwrap()
+            .account_id;
+        let found = AccountService::find_public_key(&other_id).unwrap();
+        assert_eq!(found.algorithm, KeyAlgorithm::K256);
+        assert_eq!(found.public_key, other.public_key_bytes());
+    }
+
     #[test]
     fn issue_delegated_token_succeeds_with_p256() {
         let owner_store = InMemoryAccountKeyStore::default();
         let recipient_store = InMemoryAccountKeyStore::default();
         let recipient_account =
-            AccountService::create(&recipient_store, KeyTypeMapper::P256).unwrap();
-        AccountService::create(&owner_store, KeyTypeMapper::P256).unwrap();
+            AccountService::create(&recipient_store, &NoOpEventPublisher, KeyTypeMapper::P256)
+                .unwrap();
+        AccountService::create(&owner_store, &NoOpEventPublisher, KeyTypeMapper::P256).unwrap();
 
         let req = IssueDelegatedTokenRequest {
             recipient_public_key: recipient_account.public_key_bytes().to_vec(),
@@ -285,8 +1796,9 @@ mod tests {
         let owner_store = InMemoryAccountKeyStore::default();
         let recipient_store = InMemoryAccountKeyStore::default();
         let recipient_account =
-            AccountService::create(&recipient_store, KeyTypeMapper::P256).unwrap();
-        AccountService::create(&owner_store, KeyTypeMapper::K256).unwrap();
+            AccountService::create(&recipient_store, &NoOpEventPublisher, KeyTypeMapper::P256)
+                .unwrap();
+        AccountService::create(&owner_store, &NoOpEventPublisher, KeyTypeMapper::K256).unwrap();
 
         let req = IssueDelegatedTokenRequest {
             recipient_public_key: recipient_account.public_key_bytes().to_vec(),
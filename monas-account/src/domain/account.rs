@@ -1,3 +1,5 @@
+use crate::infrastructure::key_pair::KeyPairError;
+
 pub struct Account {
     key_pair: Box<dyn AccountKeyPair>,
 }
@@ -11,8 +13,8 @@ impl Account {
         Account { key_pair }
     }
 
-    /// メッセージに署名する。
-    pub fn sign(&self, msg: &[u8]) -> (Vec<u8>, Option<u8>) {
+    /// メッセージに署名する。外部の署名器を使う鍵ペアでは失敗しうる。
+    pub fn sign(&self, msg: &[u8]) -> Result<(Vec<u8>, Option<u8>), KeyPairError> {
         self.key_pair.sign(msg)
     }
 
@@ -28,7 +30,9 @@ impl Account {
 }
 
 pub trait AccountKeyPair: Send + Sync {
-    fn sign(&self, msg: &[u8]) -> (Vec<u8>, Option<u8>);
+    /// メッセージに署名する。鍵をプロセス内に持つ実装は失敗しない。
+    fn sign(&self, msg: &[u8]) -> Result<(Vec<u8>, Option<u8>), KeyPairError>;
+
     fn public_key_bytes(&self) -> &[u8];
 
     fn secret_key_bytes(&self) -> &[u8];
@@ -50,7 +54,7 @@ mod account_tests {
 
         // 署名が正常に生成できることを確認
         let message = b"test message";
-        let (sig, _rec_id) = account.sign(message);
+        let (sig, _rec_id) = account.sign(message).unwrap();
         assert!(!sig.is_empty());
     }
 }
@@ -0,0 +1,28 @@
+/// 共有の CEK を受け取るための、アカウントの HPKE 公開鍵。
+///
+/// アカウントのルート鍵から導出し、アカウント鍵で [`statement`](Self::statement) に署名する。
+/// 送信者は did:key の公開鍵で署名を確かめてから、この鍵に CEK をラップする。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountEncryptionKey {
+    pub account_id: String,
+    /// HPKE の KEM（例: `DHKEM(P-256, HKDF-SHA256)`）。
+    pub kem: String,
+    /// 非圧縮形式の SEC1 公開鍵。
+    pub public_key: Vec<u8>,
+    /// アカウント鍵による `statement` への署名。
+    pub signature: Vec<u8>,
+}
+
+impl AccountEncryptionKey {
+    /// アカウント鍵で署名する文。
+    pub fn statement(&self) -> Vec<u8> {
+        encryption_key_statement(&self.account_id, &self.kem, &self.public_key)
+    }
+}
+
+/// 暗号化鍵の署名対象。公開鍵は小文字の 16 進で書く。
+pub fn encryption_key_statement(account_id: &str, kem: &str, public_key: &[u8]) -> Vec<u8> {
+    let key: String = public_key.iter().map(|b| format!("{b:02x}")).collect();
+    format!("monas-account-encryption-key:v1\naccount:{account_id}\nkem:{kem}\nkey:{key}")
+        .into_bytes()
+}
@@ -0,0 +1,44 @@
+//! アカウントの変化を他のコンポーネントへ通知するためのドメインイベント。
+//!
+//! コンテンツサービスやステートノード、SDK のキャッシュが購読し、識別子の変化
+//! （作成・鍵のローテーション・端末の追加）に追従できるよう、識別子をペイロードとして持つ。
+
+use serde::{Deserialize, Serialize};
+
+/// アカウントが新規作成されたことを表すイベント。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountCreated {
+    pub account_id: String,
+    /// `K256` または `P256`。
+    pub algorithm: String,
+    pub public_key: Vec<u8>,
+    pub created_at: u64,
+}
+
+/// アカウント鍵がローテーションされたことを表すイベント。
+///
+/// 購読側は `previous_account_id` に紐づけた情報を `new_account_id` に付け替える。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotated {
+    pub previous_account_id: String,
+    pub new_account_id: String,
+    pub sequence: u64,
+    pub rotated_at: u64,
+}
+
+/// プロフィールに端末が追加されたことを表すイベント。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceLinked {
+    pub account_id: String,
+    pub device_id: String,
+    pub name: String,
+    pub linked_at: u64,
+}
+
+/// `EventPublisher` ポートに渡すドメインイベントの列挙。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccountDomainEvent {
+    Created(AccountCreated),
+    KeyRotated(KeyRotated),
+    DeviceLinked(DeviceLinked),
+}
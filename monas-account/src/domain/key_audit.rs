@@ -0,0 +1,107 @@
+//! 秘密鍵を使う操作の監査記録。
+//!
+//! 署名などの回数を後から調べられるよう、秘密鍵に触れる操作ごとに結果と時刻を残す。
+//! 短時間に操作が集中していないか（鍵の不正利用の兆候）を確認するために使う。
+
+use serde::{Deserialize, Serialize};
+
+/// 秘密鍵を使う操作の種類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyOperation {
+    /// アカウント鍵での署名（`/accounts/sign`、`/accounts/{id}/sign`）。
+    Sign,
+    /// 子鍵での署名。
+    SignDerived,
+    /// 委任トークンの発行。
+    IssueDelegatedToken,
+    /// ニーモニックの書き出し。
+    ExportMnemonic,
+    /// 秘密鍵を含む鍵の書き出し。
+    ExportPrivateKey,
+    /// 復旧用シェアへの分割。
+    SplitRecoveryShares,
+    /// 共有を受け取る HPKE の秘密鍵の書き出し。
+    ExportEncryptionKey,
+}
+
+impl KeyOperation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sign => "sign",
+            Self::SignDerived => "sign_derived",
+            Self::IssueDelegatedToken => "issue_delegated_token",
+            Self::ExportMnemonic => "export_mnemonic",
+            Self::ExportPrivateKey => "export_private_key",
+            Self::SplitRecoveryShares => "split_recovery_shares",
+            Self::ExportEncryptionKey => "export_encryption_key",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        [
+            Self::Sign,
+            Self::SignDerived,
+            Self::IssueDelegatedToken,
+            Self::ExportMnemonic,
+            Self::ExportPrivateKey,
+            Self::SplitRecoveryShares,
+            Self::ExportEncryptionKey,
+        ]
+        .into_iter()
+        .find(|op| op.as_str() == s)
+    }
+}
+
+/// 操作の結果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyOperationOutcome {
+    Succeeded,
+    Failed,
+    /// レート制限により実行しなかった。
+    RateLimited,
+}
+
+/// 監査記録の 1 件。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyAuditEntry {
+    pub operation: KeyOperation,
+    pub outcome: KeyOperationOutcome,
+    /// 操作した時刻（UNIX 秒）。
+    pub at: u64,
+    /// 操作を求めたセッションのアカウント識別子。セッションなしの呼び出しでは `None`。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub principal: Option<String>,
+    /// 失敗した場合の理由。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// 監査記録の検索条件。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyAuditQuery {
+    /// この時刻（UNIX 秒）以降の記録に絞る。
+    pub since: Option<u64>,
+    pub operation: Option<KeyOperation>,
+    /// 新しいものから最大何件返すか。
+    pub limit: usize,
+}
+
+impl KeyAuditQuery {
+    /// `entries`（古い順）から条件に合うものを古い順に返す。
+    pub fn select<'a>(
+        &self,
+        entries: impl DoubleEndedIterator<Item = &'a KeyAuditEntry>,
+    ) -> Vec<KeyAuditEntry> {
+        let mut selected: Vec<KeyAuditEntry> = entries
+            .rev()
+            .filter(|e| e.at >= self.since.unwrap_or(0))
+            .filter(|e| self.operation.is_none() || self.operation == Some(e.operation))
+            .take(self.limit)
+            .cloned()
+            .collect();
+        selected.reverse();
+        selected
+    }
+}
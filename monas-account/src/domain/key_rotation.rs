@@ -0,0 +1,47 @@
+use crate::infrastructure::key_pair::KeyAlgorithm;
+
+/// アカウント鍵のローテーション記録。
+///
+/// 旧鍵で [`statement`](Self::statement) に署名し、旧アカウント識別子から新しい識別子への
+/// 引き継ぎを証明する。記録を `sequence` 順にたどると、最初の識別子から現在の識別子までの
+/// 連続性を検証できる。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyRotationRecord {
+    /// 1 から始まる通し番号。
+    pub sequence: u64,
+    pub previous_account_id: String,
+    pub previous_algorithm: KeyAlgorithm,
+    pub previous_public_key: Vec<u8>,
+    pub new_account_id: String,
+    pub new_algorithm: KeyAlgorithm,
+    pub new_public_key: Vec<u8>,
+    /// ローテーションした時刻（UNIX 秒）。
+    pub rotated_at: u64,
+    /// 旧鍵による `statement` への署名。
+    pub signature: Vec<u8>,
+}
+
+impl KeyRotationRecord {
+    /// 旧鍵で署名する文。
+    pub fn statement(&self) -> Vec<u8> {
+        rotation_statement(
+            self.sequence,
+            &self.previous_account_id,
+            &self.new_account_id,
+            self.rotated_at,
+        )
+    }
+}
+
+/// ローテーション記録の署名対象。did:key は公開鍵とアルゴリズムを含むため、識別子だけを並べる。
+pub fn rotation_statement(
+    sequence: u64,
+    previous_account_id: &str,
+    new_account_id: &str,
+    rotated_at: u64,
+) -> Vec<u8> {
+    format!(
+        "monas-account-key-rotation:v1\nsequence:{sequence}\nprevious:{previous_account_id}\nnew:{new_account_id}\nrotated_at:{rotated_at}"
+    )
+    .into_bytes()
+}
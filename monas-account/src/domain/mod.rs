@@ -1,2 +1,9 @@
 pub mod account;
 pub mod delegation;
+pub mod encryption_key;
+pub mod events;
+pub mod key_audit;
+pub mod key_rotation;
+pub mod profile;
+pub mod revocation;
+pub mod session;
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+
+/// 表示名の最大文字数。
+pub const MAX_DISPLAY_NAME_CHARS: usize = 64;
+/// 1 つのプロフィールに登録できる端末数の上限。
+pub const MAX_DEVICES: usize = 32;
+
+/// アカウントのプロフィール。
+///
+/// アカウント自体は鍵ペアなので、コンテンツを共有するときに相手に見せる
+/// 人が読める情報（表示名、アバター、端末一覧）をここに持たせる。
+///
+/// アカウント鍵で [`statement`](Self::statement) に署名しておき、受け取った側が
+/// did:key の公開鍵だけで持ち主の書いた内容であることを確かめられるようにする。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountProfile {
+    pub account_id: String,
+    pub display_name: String,
+    /// アバター画像のコンテンツ ID。
+    pub avatar_content_id: Option<String>,
+    pub devices: Vec<AccountDevice>,
+    /// 最終更新時刻（UNIX 秒）。
+    pub updated_at: u64,
+    /// アカウント鍵による `statement` への署名。内容を変えたら署名し直す。
+    #[serde(default)]
+    pub signature: Vec<u8>,
+}
+
+/// アカウントを使う端末。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountDevice {
+    pub device_id: String,
+    pub name: String,
+}
+
+impl AccountProfile {
+    /// 入力を検証してプロフィールを作る。
+    pub fn new(
+        account_id: String,
+        display_name: String,
+        avatar_content_id: Option<String>,
+        devices: Vec<AccountDevice>,
+        updated_at: u64,
+    ) -> Result<Self, String> {
+        let mut profile = Self {
+            account_id,
+            display_name: String::new(),
+            avatar_content_id: None,
+            devices: Vec::new(),
+            updated_at,
+            signature: Vec::new(),
+        };
+        profile.update(display_name, avatar_content_id, devices, updated_at)?;
+        Ok(profile)
+    }
+
+    /// アカウント鍵で署名する文。`monas-account-profile:v1` の行に続けて、
+    /// 署名以外の項目をこの順の JSON で並べる。
+    pub fn statement(&self) -> Vec<u8> {
+        #[derive(Serialize)]
+        struct Statement<'a> {
+            account_id: &'a str,
+            display_name: &'a str,
+            avatar_content_id: Option<&'a str>,
+            devices: &'a [AccountDevice],
+            updated_at: u64,
+        }
+
+        let body = serde_json::to_string(&Statement {
+            account_id: &self.account_id,
+            display_name: &self.display_name,
+            avatar_content_id: self.avatar_content_id.as_deref(),
+            devices: &self.devices,
+            updated_at: self.updated_at,
+        })
+        .expect("profile fields always serialize");
+        format!("monas-account-profile:v1\n{body}").into_bytes()
+    }
+
+    /// 表示名・アバター・端末一覧を置き換える。検証に失敗した場合は変更しない。
+    ///
+    /// 以前の署名は新しい内容に合わないため消す。
+    pub fn update(
+        &mut self,
+        display_name: String,
+        avatar_content_id: Option<String>,
+        devices: Vec<AccountDevice>,
+        updated_at: u64,
+    ) -> Result<(), String> {
+        let display_name = display_name.trim().to_string();
+        if display_name.is_empty() {
+            return Err("display_name must not be empty".to_string());
+        }
+        if display_name.chars().count() > MAX_DISPLAY_NAME_CHARS {
+            return Err(format!(
+                "display_name must be at most {MAX_DISPLAY_NAME_CHARS} characters"
+            ));
+        }
+        let avatar_content_id = avatar_content_id
+            .map(|cid| cid.trim().to_string())
+            .filter(|cid| !cid.is_empty());
+        if devices.len() > MAX_DEVICES {
+            return Err(format!("at most {MAX_DEVICES} devices are allowed"));
+        }
+        for (index, device) in devices.iter().enumerate() {
+            if device.device_id.trim().is_empty() {
+                return Err("device_id must not be empty".to_string());
+            }
+            if devices[..index]
+                .iter()
+                .any(|other| other.device_id == device.device_id)
+            {
+                return Err(format!("duplicate device_id: {}", device.device_id));
+            }
+        }
+
+        self.display_name = display_name;
+        self.avatar_content_id = avatar_content_id;
+        self.devices = devices;
+        self.updated_at = updated_at;
+        self.signature.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod profile_tests {
+    use super::*;
+
+    fn device(id: &str) -> AccountDevice {
+        AccountDevice {
+            device_id: id.to_string(),
+            name: format!("device {id}"),
+        }
+    }
+
+    #[test]
+    fn validates_display_name_and_devices() {
+        let profile = AccountProfile::new(
+            "did:key:zTest".into(),
+            "  Alice  ".into(),
+            Some(" ".into()),
+            vec![device("a"), device("b")],
+            1,
+        )
+        .unwrap();
+        assert_eq!(profile.display_name, "Alice");
+        assert_eq!(profile.avatar_content_id, None);
+
+        let mut updated = profile.clone();
+        assert!(updated.update(" ".into(), None, Vec::new(), 2).is_err());
+        assert!(updated
+            .update("Alice".into(), None, vec![device("a"), device("a")], 2)
+            .is_err());
+        assert_eq!(updated, profile);
+    }
+
+    #[test]
+    fn statement_covers_every_field_and_is_reserved() {
+        let mut profile =
+            AccountProfile::new("did:key:zTest".into(), "Alice".into(), None, vec![], 1).unwrap();
+        let statement = profile.statement();
+        assert!(crate::domain::session::is_reserved_statement(&statement));
+
+        profile.signature = vec![1, 2, 3];
+        assert_eq!(profile.statement(), statement);
+        profile
+            .update("Alice".into(), None, vec![device("a")], 1)
+            .unwrap();
+        assert_ne!(profile.statement(), statement);
+        assert!(profile.signature.is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+use crate::infrastructure::key_pair::KeyAlgorithm;
+
+/// アカウント鍵の失効記録。
+///
+/// 失効させる鍵自身で [`statement`](Self::statement) に署名する。鍵の持ち主だけが
+/// 失効させられることを、記録を受け取った側が公開鍵だけで確認できる。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyRevocationRecord {
+    pub account_id: String,
+    pub algorithm: KeyAlgorithm,
+    pub public_key: Vec<u8>,
+    /// 失効した時刻（UNIX 秒）。
+    pub revoked_at: u64,
+    pub reason: Option<String>,
+    /// 失効させた鍵による `statement` への署名。
+    pub signature: Vec<u8>,
+}
+
+impl KeyRevocationRecord {
+    /// 失効させる鍵で署名する文。
+    pub fn statement(&self) -> Vec<u8> {
+        revocation_statement(&self.account_id, self.revoked_at, self.reason.as_deref())
+    }
+}
+
+/// 失効記録の署名対象。
+pub fn revocation_statement(account_id: &str, revoked_at: u64, reason: Option<&str>) -> Vec<u8> {
+    format!(
+        "monas-account-key-revocation:v1\naccount:{account_id}\nrevoked_at:{revoked_at}\nreason:{}",
+        reason.unwrap_or_default()
+    )
+    .into_bytes()
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// チャレンジレスポンス認証でサーバーが発行する使い捨てのチャレンジ。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthChallenge {
+    pub nonce: String,
+    /// チャレンジを要求したアカウント識別子（did:key）。
+    pub account_id: String,
+    /// 有効期限（UNIX 秒）。
+    pub expires_at: u64,
+}
+
+impl AuthChallenge {
+    /// クライアントがアカウント鍵で署名する文。
+    pub fn message(&self) -> Vec<u8> {
+        auth_challenge_message(&self.account_id, &self.nonce)
+    }
+}
+
+/// アカウントサーバー自身が署名する文（認証チャレンジ・ローテーション・失効・暗号化鍵）の接頭辞。
+const RESERVED_STATEMENT_PREFIX: &[u8] = b"monas-account-";
+
+/// `msg` がアカウントサーバーの予約した文か。任意のメッセージへの署名でこれらの文を
+/// 作らせないよう、署名 API はこの接頭辞のメッセージを拒否する。
+pub fn is_reserved_statement(msg: &[u8]) -> bool {
+    msg.starts_with(RESERVED_STATEMENT_PREFIX)
+}
+
+/// チャレンジの署名対象。他の用途の署名を認証に流用されないよう、用途と識別子を含める。
+pub fn auth_challenge_message(account_id: &str, nonce: &str) -> Vec<u8> {
+    format!("monas-account-auth:v1\naccount:{account_id}\nnonce:{nonce}").into_bytes()
+}
+
+/// セッショントークン（JWT）のクレーム。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionClaims {
+    /// トークンを発行したサーバーのセッション鍵の識別子（did:key）。
+    pub iss: String,
+    /// 認証されたアカウント識別子（did:key）。
+    pub sub: String,
+    pub iat: u64,
+    pub exp: u64,
+    pub jti: String,
+}
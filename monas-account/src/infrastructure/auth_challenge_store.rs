@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::application_service::{AuthChallengeStore, AuthChallengeStoreError};
+use crate::domain::session::AuthChallenge;
+
+/// 実行時に保存先を切り替えるチャレンジストアの動的型。
+pub type DynAuthChallengeStore = Arc<dyn AuthChallengeStore + Send + Sync>;
+
+/// 既定で保持する未使用チャレンジの上限。
+pub const DEFAULT_MAX_PENDING_CHALLENGES: usize = 1024;
+
+/// 発行済みのチャレンジをプロセス内に保持するインメモリ実装。
+///
+/// チャレンジは短命なので永続化しない。期限切れのチャレンジは追加・取り出しのたびに
+/// 取り除き、未使用のチャレンジが上限に達している間は新しいチャレンジを拒否する。
+#[derive(Clone)]
+pub struct InMemoryAuthChallengeStore {
+    inner: Arc<Mutex<HashMap<String, AuthChallenge>>>,
+    max_pending: usize,
+}
+
+impl Default for InMemoryAuthChallengeStore {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_MAX_PENDING_CHALLENGES)
+    }
+}
+
+impl InMemoryAuthChallengeStore {
+    /// 未使用のチャレンジを最大 `max_pending` 個まで保持するストアを作る。
+    pub fn with_capacity(max_pending: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(HashMap::new())),
+            max_pending,
+        }
+    }
+
+    fn lock(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, HashMap<String, AuthChallenge>>, AuthChallengeStoreError>
+    {
+        let mut challenges = self
+            .inner
+            .lock()
+            .map_err(|e| AuthChallengeStoreError::Storage(e.to_string()))?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        challenges.retain(|_, c| c.expires_at > now);
+        Ok(challenges)
+    }
+}
+
+impl AuthChallengeStore for InMemoryAuthChallengeStore {
+    fn insert(&self, challenge: &AuthChallenge) -> Result<(), AuthChallengeStoreError> {
+        let mut challenges = self.lock()?;
+        if challenges.len() >= self.max_pending {
+            return Err(AuthChallengeStoreError::Full);
+        }
+        challenges.insert(challenge.nonce.clone(), challenge.clone());
+        Ok(())
+    }
+
+    fn take(&self, nonce: &str) -> Result<Option<AuthChallenge>, AuthChallengeStoreError> {
+        Ok(self.lock()?.remove(nonce))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(nonce: &str, expires_at: u64) -> AuthChallenge {
+        AuthChallenge {
+            nonce: nonce.to_string(),
+            account_id: "did:key:zTest".to_string(),
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn rejects_new_challenges_when_full_until_expired_ones_are_swept() {
+        let store = InMemoryAuthChallengeStore::with_capacity(2);
+        store.insert(&challenge("a", u64::MAX)).unwrap();
+        store.insert(&challenge("b", u64::MAX)).unwrap();
+        assert!(matches!(
+            store.insert(&challenge("c", u64::MAX)),
+            Err(AuthChallengeStoreError::Full)
+        ));
+
+        // 使用済みのチャレンジは枠を空ける
+        assert!(store.take("a").unwrap().is_some());
+        assert!(store.take("a").unwrap().is_none());
+        store.insert(&challenge("c", u64::MAX)).unwrap();
+
+        // 期限切れのチャレンジは取り出せず、数にも含めない
+        let expired = InMemoryAuthChallengeStore::with_capacity(1);
+        expired.insert(&challenge("old", 1)).unwrap();
+        expired.insert(&challenge("new", u64::MAX)).unwrap();
+        assert!(expired.take("old").unwrap().is_none());
+    }
+}
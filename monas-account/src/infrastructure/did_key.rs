@@ -0,0 +1,143 @@
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+use crate::infrastructure::key_pair::KeyAlgorithm;
+
+/// multicodec の公開鍵種別（unsigned varint エンコード済み）。
+const SECP256K1_PUB_MULTICODEC: [u8; 2] = [0xe7, 0x01];
+const P256_PUB_MULTICODEC: [u8; 2] = [0x80, 0x24];
+
+/// アカウントの公開鍵から `did:key` 形式のアカウント識別子を作る。
+///
+/// did:key の仕様に従い、圧縮形式の公開鍵に multicodec の種別を前置して
+/// base58btc（multibase の接頭辞 `z`）でエンコードする。
+pub fn did_key_from_public_key(
+    algorithm: KeyAlgorithm,
+    public_key: &[u8],
+) -> Result<String, DidKeyError> {
+    let (multicodec, compressed) = match algorithm {
+        KeyAlgorithm::K256 => (
+            SECP256K1_PUB_MULTICODEC,
+            k256::PublicKey::from_sec1_bytes(public_key)
+                .map_err(|e| DidKeyError::InvalidPublicKey(e.to_string()))?
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+        ),
+        KeyAlgorithm::P256 => (
+            P256_PUB_MULTICODEC,
+            p256::PublicKey::from_sec1_bytes(public_key)
+                .map_err(|e| DidKeyError::InvalidPublicKey(e.to_string()))?
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+        ),
+    };
+
+    let mut bytes = Vec::with_capacity(multicodec.len() + compressed.len());
+    bytes.extend_from_slice(&multicodec);
+    bytes.extend_from_slice(&compressed);
+    Ok(format!("did:key:z{}", bs58::encode(bytes).into_string()))
+}
+
+/// `did:key` 形式のアカウント識別子から公開鍵を取り出す。
+///
+/// 公開鍵は圧縮形式の SEC1 で返す。対応していない multicodec の種別はエラーにする。
+pub fn public_key_from_did_key(did: &str) -> Result<(KeyAlgorithm, Vec<u8>), DidKeyError> {
+    let encoded = did
+        .strip_prefix("did:key:z")
+        .ok_or_else(|| DidKeyError::InvalidDid(format!("not a base58btc did:key: {did}")))?;
+    let bytes = bs58::decode(encoded)
+        .into_vec()
+        .map_err(|e| DidKeyError::InvalidDid(e.to_string()))?;
+
+    let (algorithm, public_key) = if let Some(key) = bytes.strip_prefix(&SECP256K1_PUB_MULTICODEC) {
+        (KeyAlgorithm::K256, key)
+    } else if let Some(key) = bytes.strip_prefix(&P256_PUB_MULTICODEC) {
+        (KeyAlgorithm::P256, key)
+    } else {
+        return Err(DidKeyError::InvalidDid(
+            "unsupported multicodec key type".to_string(),
+        ));
+    };
+
+    // 曲線上の点であることを確認する
+    did_key_from_public_key(algorithm, public_key)?;
+    Ok((algorithm, public_key.to_vec()))
+}
+
+/// 圧縮形式の SEC1 公開鍵を、保存済みのアカウント鍵と同じ非圧縮形式に戻す。
+pub fn uncompressed_public_key(
+    algorithm: KeyAlgorithm,
+    public_key: &[u8],
+) -> Result<Vec<u8>, DidKeyError> {
+    Ok(match algorithm {
+        KeyAlgorithm::K256 => k256::PublicKey::from_sec1_bytes(public_key)
+            .map_err(|e| DidKeyError::InvalidPublicKey(e.to_string()))?
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec(),
+        KeyAlgorithm::P256 => p256::PublicKey::from_sec1_bytes(public_key)
+            .map_err(|e| DidKeyError::InvalidPublicKey(e.to_string()))?
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec(),
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DidKeyError {
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(String),
+    #[error("invalid did:key: {0}")]
+    InvalidDid(String),
+}
+
+#[cfg(test)]
+mod did_key_tests {
+    use super::*;
+    use crate::infrastructure::key_pair::KeyPairGenerateFactory;
+
+    #[test]
+    fn encodes_with_the_curve_specific_prefix() {
+        let p256 = KeyPairGenerateFactory::generate(KeyAlgorithm::P256);
+        let did = did_key_from_public_key(KeyAlgorithm::P256, p256.public_key_bytes()).unwrap();
+        // P-256 の did:key は常に "zDn" で始まる
+        assert!(did.starts_with("did:key:zDn"), "{did}");
+
+        let k256 = KeyPairGenerateFactory::generate(KeyAlgorithm::K256);
+        let did = did_key_from_public_key(KeyAlgorithm::K256, k256.public_key_bytes()).unwrap();
+        // secp256k1 の did:key は常に "zQ3s" で始まる
+        assert!(did.starts_with("did:key:zQ3s"), "{did}");
+    }
+
+    #[test]
+    fn decodes_the_public_key_back_from_did_key() {
+        for algorithm in [KeyAlgorithm::K256, KeyAlgorithm::P256] {
+            let key_pair = KeyPairGenerateFactory::generate(algorithm);
+            let did = did_key_from_public_key(algorithm, key_pair.public_key_bytes()).unwrap();
+
+            let (decoded_algorithm, public_key) = public_key_from_did_key(&did).unwrap();
+            assert_eq!(decoded_algorithm, algorithm);
+            assert_eq!(public_key.len(), 33);
+            assert_eq!(
+                did_key_from_public_key(algorithm, &public_key).unwrap(),
+                did
+            );
+            assert_eq!(
+                uncompressed_public_key(algorithm, &public_key).unwrap(),
+                key_pair.public_key_bytes()
+            );
+        }
+
+        assert!(matches!(
+            public_key_from_did_key("did:web:example.com"),
+            Err(DidKeyError::InvalidDid(_))
+        ));
+        assert!(public_key_from_did_key("did:key:z0OIl").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_public_keys() {
+        assert!(did_key_from_public_key(KeyAlgorithm::P256, &[4, 1, 2]).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+//! ファイルに保存するストアの永続化の補助。
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// `path` のファイルと、名前の変更を記録したその親ディレクトリをディスクに書き出す。
+///
+/// 一時ファイルからの `rename` で書き換えるストアが、終了前に呼び出す。
+/// ファイルがまだない場合は何もしない。
+pub(crate) fn sync_file_and_parent(path: &Path) -> io::Result<()> {
+    match File::open(path) {
+        Ok(file) => file.sync_all()?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    }
+    // ディレクトリの fsync は Unix でのみ行える
+    #[cfg(unix)]
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        File::open(parent)?.sync_all()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn syncs_existing_file_and_ignores_missing_one() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("state.json");
+
+        sync_file_and_parent(&path).expect("missing file is not an error");
+        std::fs::write(&path, b"[]").unwrap();
+        sync_file_and_parent(&path).expect("sync existing file");
+    }
+}
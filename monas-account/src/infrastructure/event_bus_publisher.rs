@@ -0,0 +1,180 @@
+//! monas-event-manager の EventBus を用いた EventPublisher 実装。
+//!
+//! アカウントのドメインイベントを `AccountCreated` / `KeyRotated` / `DeviceLinked` の
+//! 各型のまま EventBus に流すことで、購読側は `EventBus::subscribe::<KeyRotated>` のように
+//! 必要なイベントだけを購読できる。
+
+use std::any::Any;
+use std::sync::Arc;
+
+use monas_event_manager::{EventBus, SerializableEvent};
+use tokio::sync::mpsc;
+
+use crate::application_service::{EventPublisher, EventPublisherError};
+use crate::domain::events::{AccountCreated, AccountDomainEvent, DeviceLinked, KeyRotated};
+
+/// 実行時に通知先を切り替える EventPublisher の動的型。
+pub type DynEventPublisher = Arc<dyn EventPublisher + Send + Sync>;
+
+impl monas_event_manager::event_bus::Event for AccountCreated {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl SerializableEvent for AccountCreated {
+    fn event_type() -> &'static str {
+        "AccountCreated"
+    }
+}
+
+impl monas_event_manager::event_bus::Event for KeyRotated {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl SerializableEvent for KeyRotated {
+    fn event_type() -> &'static str {
+        "KeyRotated"
+    }
+}
+
+impl monas_event_manager::event_bus::Event for DeviceLinked {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl SerializableEvent for DeviceLinked {
+    fn event_type() -> &'static str {
+        "DeviceLinked"
+    }
+}
+
+/// EventBus にドメインイベントを publish する EventPublisher。
+///
+/// `EventBus` は Clone 可能で内部状態を共有するため、同じインスタンスを
+/// コンテンツサービスやステートノードに渡して購読させる。
+///
+/// `AccountService` は同期 API のため、`publish` はイベントをキューに積むだけで、
+/// EventBus への publish はバックグラウンドのタスクが発生順に待ち合わせる。
+#[derive(Clone)]
+pub struct EventBusEventPublisher {
+    event_bus: EventBus,
+    sender: mpsc::UnboundedSender<AccountDomainEvent>,
+}
+
+impl EventBusEventPublisher {
+    /// `event_bus` に publish するタスクを起動する。tokio ランタイムの中で呼ぶこと。
+    pub fn new(event_bus: EventBus) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let bus = event_bus.clone();
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                if let Err(e) = publish_to(&bus, event).await {
+                    eprintln!("failed to publish account event: {e}");
+                }
+            }
+        });
+        Self { event_bus, sender }
+    }
+
+    /// 内部の EventBus を取得する（購読登録用）。
+    pub fn event_bus(&self) -> &EventBus {
+        &self.event_bus
+    }
+
+    /// 永続化付き EventBus で復元できるよう、アカウントイベントの型を登録する。
+    pub async fn register_event_types(&self) {
+        self.event_bus.register_event_type::<AccountCreated>().await;
+        self.event_bus.register_event_type::<KeyRotated>().await;
+        self.event_bus.register_event_type::<DeviceLinked>().await;
+    }
+}
+
+async fn publish_to(
+    event_bus: &EventBus,
+    event: AccountDomainEvent,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    match event {
+        AccountDomainEvent::Created(e) => event_bus.publish(Arc::new(e)).await,
+        AccountDomainEvent::KeyRotated(e) => event_bus.publish(Arc::new(e)).await,
+        AccountDomainEvent::DeviceLinked(e) => event_bus.publish(Arc::new(e)).await,
+    }
+}
+
+impl EventPublisher for EventBusEventPublisher {
+    fn publish(&self, event: AccountDomainEvent) -> Result<(), EventPublisherError> {
+        // 呼び出し元の非同期タスクの中で publish を待つと、購読者の処理が同じワーカーを
+        // 必要とする場合に止まるため、待ち合わせはバックグラウンドのタスクに任せる
+        self.sender.send(event).map_err(|_| {
+            EventPublisherError::Publish("event publisher task has stopped".to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use monas_event_manager::make_subscriber;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn publish_delivers_typed_event_to_subscriber() {
+        let publisher = EventBusEventPublisher::new(EventBus::new());
+        let received: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let sink = received.clone();
+        let subscriber = make_subscriber::<KeyRotated, _, _>(
+            "key-rotated-test".to_string(),
+            move |event: Arc<KeyRotated>| {
+                let sink = sink.clone();
+                async move {
+                    sink.lock().unwrap().push(event.new_account_id.clone());
+                    Ok(())
+                }
+            },
+        );
+        publisher
+            .event_bus()
+            .subscribe::<KeyRotated>(subscriber)
+            .await
+            .unwrap();
+
+        publisher
+            .publish(AccountDomainEvent::KeyRotated(KeyRotated {
+                previous_account_id: "did:key:zOld".into(),
+                new_account_id: "did:key:zNew".into(),
+                sequence: 1,
+                rotated_at: 1,
+            }))
+            .unwrap();
+        // 別の型のイベントは KeyRotated の購読者に届かない
+        publisher
+            .publish(AccountDomainEvent::DeviceLinked(DeviceLinked {
+                account_id: "did:key:zNew".into(),
+                device_id: "phone".into(),
+                name: "Phone".into(),
+                linked_at: 2,
+            }))
+            .unwrap();
+
+        // publish はバックグラウンドで行われるため、届くまで待つ
+        for _ in 0..100 {
+            if !received.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(*received.lock().unwrap(), vec!["did:key:zNew".to_string()]);
+    }
+
+    #[test]
+    fn event_types_are_distinct() {
+        assert_eq!(AccountCreated::event_type(), "AccountCreated");
+        assert_eq!(KeyRotated::event_type(), "KeyRotated");
+        assert_eq!(DeviceLinked::event_type(), "DeviceLinked");
+    }
+}
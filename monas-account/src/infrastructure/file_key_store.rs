@@ -0,0 +1,476 @@
+//! パスフレーズで暗号化したファイルにアカウント鍵を保存する実装。
+//!
+//! パスフレーズから scrypt で鍵暗号化鍵（KEK）を導出し、秘密鍵を AES-256-GCM で包んで
+//! JSON ファイルに書き出す。公開鍵とアルゴリズムは平文で保存し、改ざん検出のため
+//! AAD として暗号文に結び付ける。
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "kdf": { "name": "scrypt", "log_n": 15, "r": 8, "p": 1, "salt_base64": "..." },
+//!   "algorithm": "K256",
+//!   "public_key_base64": "...",
+//!   "nonce_base64": "...",
+//!   "wrapped_secret_key_base64": "..."
+//! }
+//! ```
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use p256::elliptic_curve::rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::application_service::{AccountKeyStore, AccountKeyStoreError, StoredAccountKey};
+use crate::infrastructure::key_pair::KeyAlgorithm;
+
+const FILE_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+/// ファイルから読み込む scrypt が使うメモリ（128 * N * r バイト）の上限。N = 2^18, r = 8 まで。
+pub(crate) const MAX_SCRYPT_MEMORY_BYTES: u64 = 256 * 1024 * 1024;
+/// ファイルから読み込む scrypt の並列度 `p` の上限。
+pub(crate) const MAX_SCRYPT_P: u32 = 16;
+
+/// KEK の導出に使う scrypt のパラメータ。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScryptParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    /// 対話的な用途の推奨値（N = 2^15, r = 8, p = 1）。
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl ScryptParams {
+    /// ファイルから読み込んだパラメータが上限の範囲内にあることを確かめる。
+    ///
+    /// 細工したファイルで過大な N・r・p を指定され、開くだけでメモリや CPU を
+    /// 使い果たすのを防ぐ。範囲外の場合は理由を返す。
+    pub(crate) fn check_bounds(&self) -> Result<(), String> {
+        if self.r == 0 || self.p == 0 || self.p > MAX_SCRYPT_P {
+            return Err(format!(
+                "scrypt r must be positive and p must be between 1 and {MAX_SCRYPT_P}"
+            ));
+        }
+        let n = 1u64.checked_shl(u32::from(self.log_n)).unwrap_or(u64::MAX);
+        if 128u64.saturating_mul(n).saturating_mul(u64::from(self.r)) > MAX_SCRYPT_MEMORY_BYTES {
+            return Err(format!(
+                "scrypt params require more than {MAX_SCRYPT_MEMORY_BYTES} bytes of memory"
+            ));
+        }
+        Ok(())
+    }
+
+    fn derive_kek(
+        &self,
+        passphrase: &[u8],
+        salt: &[u8],
+    ) -> Result<Zeroizing<[u8; 32]>, AccountKeyStoreError> {
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, 32)
+            .map_err(|e| AccountKeyStoreError::InvalidKeyData(format!("scrypt params: {e}")))?;
+        let mut kek = Zeroizing::new([0u8; 32]);
+        scrypt::scrypt(passphrase, salt, &params, kek.as_mut())
+            .map_err(|e| AccountKeyStoreError::Storage(format!("scrypt: {e}")))?;
+        Ok(kek)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct KdfSection {
+    name: String,
+    #[serde(flatten)]
+    params: ScryptParams,
+    salt_base64: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeyFile {
+    version: u32,
+    kdf: KdfSection,
+    algorithm: String,
+    public_key_base64: String,
+    nonce_base64: String,
+    wrapped_secret_key_base64: String,
+}
+
+/// 導出済みの KEK と、その導出に使ったソルト。KEK はストアを破棄するとメモリから消去する。
+struct Kek {
+    params: ScryptParams,
+    salt: Vec<u8>,
+    key: Zeroizing<[u8; 32]>,
+}
+
+/// パスフレーズで暗号化したファイルに鍵を保存するアカウント鍵ストア。
+///
+/// - KEK の導出は重いため、[`open`](Self::open) 時に 1 度だけ行う。
+/// - 既存のファイルがある場合は、そのソルトとパラメータで導出し、パスフレーズが
+///   正しいことを確認する（誤りなら [`AccountKeyStoreError::Decryption`]）。
+/// - 書き込みは一時ファイルへの書き出しとリネームで行い、途中で失敗しても既存の鍵を壊さない。
+#[derive(Clone)]
+pub struct FileAccountKeyStore {
+    path: PathBuf,
+    kek: Arc<Kek>,
+    // 同じストアを共有するリクエスト間で書き込みを直列化する
+    lock: Arc<Mutex<()>>,
+}
+
+impl FileAccountKeyStore {
+    pub fn open<P: AsRef<Path>>(path: P, passphrase: &str) -> Result<Self, AccountKeyStoreError> {
+        Self::open_with_params(path, passphrase, ScryptParams::default())
+    }
+
+    /// 新しく作るファイルの scrypt パラメータを指定して開く。
+    ///
+    /// 既存のファイルがある場合は、ファイルに記録されたパラメータを使う（上限を超える
+    /// パラメータのファイルは [`AccountKeyStoreError::InvalidKeyData`] として拒否する）。
+    pub fn open_with_params<P: AsRef<Path>>(
+        path: P,
+        passphrase: &str,
+        params: ScryptParams,
+    ) -> Result<Self, AccountKeyStoreError> {
+        if passphrase.is_empty() {
+            return Err(AccountKeyStoreError::Storage(
+                "key store passphrase must not be empty".to_string(),
+            ));
+        }
+        let path = path.as_ref().to_path_buf();
+
+        let existing = read_key_file(&path)?;
+        let kek = match &existing {
+            Some(file) => {
+                if file.kdf.name != "scrypt" {
+                    return Err(AccountKeyStoreError::InvalidKeyData(format!(
+                        "unsupported kdf: {}",
+                        file.kdf.name
+                    )));
+                }
+                // 導出の前に検査し、細工したファイルで過大な資源を使わせない
+                file.kdf
+                    .params
+                    .check_bounds()
+                    .map_err(AccountKeyStoreError::InvalidKeyData)?;
+                let salt = decode_field("kdf.salt_base64", &file.kdf.salt_base64)?;
+                let key = file.kdf.params.derive_kek(passphrase.as_bytes(), &salt)?;
+                Kek {
+                    params: file.kdf.params,
+                    salt,
+                    key,
+                }
+            }
+            None => {
+                let mut salt = vec![0u8; SALT_LEN];
+                OsRng.fill_bytes(&mut salt);
+                let key = params.derive_kek(passphrase.as_bytes(), &salt)?;
+                Kek { params, salt, key }
+            }
+        };
+
+        let store = Self {
+            path,
+            kek: Arc::new(kek),
+            lock: Arc::new(Mutex::new(())),
+        };
+        // 誤ったパスフレーズで開いたまま上書きしないよう、ここで復号を確認する
+        if let Some(file) = existing {
+            store.unwrap_key_file(&file)?;
+        }
+        Ok(store)
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(self.kek.key.as_slice()))
+    }
+
+    fn wrap(&self, key: &StoredAccountKey) -> Result<KeyFile, AccountKeyStoreError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let algorithm = algorithm_name(key.algorithm);
+        let aad = associated_data(algorithm, &key.public_key);
+        let wrapped = self
+            .cipher()
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &key.secret_key,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| AccountKeyStoreError::Storage("failed to wrap secret key".to_string()))?;
+
+        Ok(KeyFile {
+            version: FILE_VERSION,
+            kdf: KdfSection {
+                name: "scrypt".to_string(),
+                params: self.kek.params,
+                salt_base64: BASE64_STANDARD.encode(&self.kek.salt),
+            },
+            algorithm: algorithm.to_string(),
+            public_key_base64: BASE64_STANDARD.encode(&key.public_key),
+            nonce_base64: BASE64_STANDARD.encode(nonce),
+            wrapped_secret_key_base64: BASE64_STANDARD.encode(wrapped),
+        })
+    }
+
+    fn unwrap_key_file(&self, file: &KeyFile) -> Result<StoredAccountKey, AccountKeyStoreError> {
+        if file.version != FILE_VERSION {
+            return Err(AccountKeyStoreError::InvalidKeyData(format!(
+                "unsupported key file version: {}",
+                file.version
+            )));
+        }
+        let algorithm = match file.algorithm.as_str() {
+            "K256" => KeyAlgorithm::K256,
+            "P256" => KeyAlgorithm::P256,
+            other => {
+                return Err(AccountKeyStoreError::InvalidKeyData(format!(
+                    "unknown algorithm: {other}"
+                )))
+            }
+        };
+        let public_key = decode_field("public_key_base64", &file.public_key_base64)?;
+        let nonce = decode_field("nonce_base64", &file.nonce_base64)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(AccountKeyStoreError::InvalidKeyData(
+                "nonce must be 12 bytes".to_string(),
+            ));
+        }
+        let wrapped = decode_field("wrapped_secret_key_base64", &file.wrapped_secret_key_base64)?;
+
+        let aad = associated_data(&file.algorithm, &public_key);
+        let secret_key = self
+            .cipher()
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &wrapped,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| AccountKeyStoreError::Decryption)?;
+
+        Ok(StoredAccountKey {
+            algorithm,
+            public_key,
+            secret_key,
+        })
+    }
+
+    fn write_atomically(&self, file: &KeyFile) -> Result<(), AccountKeyStoreError> {
+        let json = serde_json::to_vec_pretty(file)
+            .map_err(|e| AccountKeyStoreError::Storage(e.to_string()))?;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(storage_error)?;
+        }
+
+        let mut tmp_name = self.path.clone().into_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
+        let mut tmp = create_private_file(&tmp_path)?;
+        tmp.write_all(&json).map_err(storage_error)?;
+        tmp.sync_all().map_err(storage_error)?;
+        fs::rename(&tmp_path, &self.path).map_err(storage_error)
+    }
+}
+
+impl AccountKeyStore for FileAccountKeyStore {
+    fn save(&self, key: &StoredAccountKey) -> Result<(), AccountKeyStoreError> {
+        let file = self.wrap(key)?;
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| AccountKeyStoreError::Storage(e.to_string()))?;
+        self.write_atomically(&file)
+    }
+
+    fn load(&self) -> Result<Option<StoredAccountKey>, AccountKeyStoreError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| AccountKeyStoreError::Storage(e.to_string()))?;
+        read_key_file(&self.path)?
+            .map(|file| self.unwrap_key_file(&file))
+            .transpose()
+    }
+
+    fn delete(&self) -> Result<(), AccountKeyStoreError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| AccountKeyStoreError::Storage(e.to_string()))?;
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+}
+
+fn algorithm_name(algorithm: KeyAlgorithm) -> &'static str {
+    match algorithm {
+        KeyAlgorithm::K256 => "K256",
+        KeyAlgorithm::P256 => "P256",
+    }
+}
+
+/// 秘密鍵の暗号文に結び付ける AAD（アルゴリズム名 + 公開鍵）。
+fn associated_data(algorithm: &str, public_key: &[u8]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(algorithm.len() + 1 + public_key.len());
+    aad.extend_from_slice(algorithm.as_bytes());
+    aad.push(0);
+    aad.extend_from_slice(public_key);
+    aad
+}
+
+fn read_key_file(path: &Path) -> Result<Option<KeyFile>, AccountKeyStoreError> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(storage_error(e)),
+    };
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .map_err(|e| AccountKeyStoreError::InvalidKeyData(format!("malformed key file: {e}")))
+}
+
+fn decode_field(name: &str, value: &str) -> Result<Vec<u8>, AccountKeyStoreError> {
+    BASE64_STANDARD
+        .decode(value)
+        .map_err(|e| AccountKeyStoreError::InvalidKeyData(format!("invalid {name}: {e}")))
+}
+
+fn storage_error(e: std::io::Error) -> AccountKeyStoreError {
+    AccountKeyStoreError::Storage(e.to_string())
+}
+
+/// 所有者のみ読み書きできるファイルを作る（Unix 以外では既定の権限）。
+pub(crate) fn create_private_file(path: &Path) -> Result<fs::File, AccountKeyStoreError> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path).map_err(storage_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テストを速くするための軽いパラメータ。
+    fn fast_params() -> ScryptParams {
+        ScryptParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        }
+    }
+
+    fn stored_key() -> StoredAccountKey {
+        StoredAccountKey {
+            algorithm: KeyAlgorithm::P256,
+            public_key: vec![4; 65],
+            secret_key: vec![7; 32],
+        }
+    }
+
+    #[test]
+    fn file_store_survives_reopen_and_does_not_leak_secret_key() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("account.key");
+        let stored = stored_key();
+
+        let store = FileAccountKeyStore::open_with_params(&path, "correct horse", fast_params())
+            .expect("open");
+        assert!(store.load().unwrap().is_none());
+        store.save(&stored).unwrap();
+
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains(&BASE64_STANDARD.encode(&stored.secret_key)));
+
+        let reopened = FileAccountKeyStore::open(&path, "correct horse").expect("reopen");
+        let loaded = reopened.load().unwrap().expect("should exist");
+        assert_eq!(loaded.algorithm, stored.algorithm);
+        assert_eq!(loaded.public_key, stored.public_key);
+        assert_eq!(loaded.secret_key, stored.secret_key);
+
+        reopened.delete().unwrap();
+        assert!(reopened.load().unwrap().is_none());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn wrong_passphrase_and_tampering_are_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("account.key");
+        let store = FileAccountKeyStore::open_with_params(&path, "correct horse", fast_params())
+            .expect("open");
+        store.save(&stored_key()).unwrap();
+
+        assert!(matches!(
+            FileAccountKeyStore::open(&path, "wrong horse"),
+            Err(AccountKeyStoreError::Decryption)
+        ));
+
+        // 公開鍵を差し替えると AAD が一致せず復号できない
+        let mut file: KeyFile = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        file.public_key_base64 = BASE64_STANDARD.encode([5u8; 65]);
+        fs::write(&path, serde_json::to_vec(&file).unwrap()).unwrap();
+        assert!(matches!(
+            store.load(),
+            Err(AccountKeyStoreError::Decryption)
+        ));
+
+        assert!(FileAccountKeyStore::open(dir.path().join("other.key"), "").is_err());
+    }
+
+    #[test]
+    fn excessive_kdf_params_in_file_are_rejected_before_deriving() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("account.key");
+        let store = FileAccountKeyStore::open_with_params(&path, "correct horse", fast_params())
+            .expect("open");
+        store.save(&stored_key()).unwrap();
+        let original: KeyFile = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+
+        for (log_n, r, p) in [
+            (40u8, 8, 1),
+            (18, 16, 1),
+            (4, 8, 64),
+            (4, 0, 1),
+            (255, 8, 1),
+        ] {
+            let mut file: KeyFile = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+            file.kdf.params = ScryptParams { log_n, r, p };
+            fs::write(&path, serde_json::to_vec(&file).unwrap()).unwrap();
+            assert!(
+                matches!(
+                    FileAccountKeyStore::open(&path, "correct horse"),
+                    Err(AccountKeyStoreError::InvalidKeyData(_))
+                ),
+                "log_n={log_n} r={r} p={p}"
+            );
+        }
+
+        fs::write(&path, serde_json::to_vec(&original).unwrap()).unwrap();
+        FileAccountKeyStore::open(&path, "correct horse").expect("within bounds");
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::application_service::{KeyAuditLog, KeyAuditLogError};
+use crate::domain::key_audit::{KeyAuditEntry, KeyAuditQuery};
+
+/// 実行時に保存先を切り替える監査記録の動的型。
+pub type DynKeyAuditLog = Arc<dyn KeyAuditLog + Send + Sync>;
+
+/// インメモリ実装が保持する記録の上限。超えた分は古いものから捨てる。
+pub const MAX_IN_MEMORY_AUDIT_ENTRIES: usize = 10_000;
+
+/// 監査記録をプロセス内に保持するインメモリ実装。
+#[derive(Clone, Default)]
+pub struct InMemoryKeyAuditLog {
+    inner: Arc<Mutex<VecDeque<KeyAuditEntry>>>,
+}
+
+impl KeyAuditLog for InMemoryKeyAuditLog {
+    fn append(&self, entry: &KeyAuditEntry) -> Result<(), KeyAuditLogError> {
+        let mut entries = self
+            .inner
+            .lock()
+            .map_err(|e| KeyAuditLogError::Storage(e.to_string()))?;
+        if entries.len() >= MAX_IN_MEMORY_AUDIT_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry.clone());
+        Ok(())
+    }
+
+    fn query(&self, query: &KeyAuditQuery) -> Result<Vec<KeyAuditEntry>, KeyAuditLogError> {
+        let entries = self
+            .inner
+            .lock()
+            .map_err(|e| KeyAuditLogError::Storage(e.to_string()))?;
+        Ok(query.select(entries.iter()))
+    }
+}
+
+/// 監査記録を JSON Lines ファイルに追記する実装。
+///
+/// 記録は追記のみで書き換えない。1 行 1 記録の JSON で、外部のログ収集にもそのまま渡せる。
+#[derive(Clone)]
+pub struct FileKeyAuditLog {
+    path: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+impl FileKeyAuditLog {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, KeyAuditLogError> {
+        let log = Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Arc::new(Mutex::new(())),
+        };
+        // 壊れたファイルは起動時に検出する
+        log.read()?;
+        Ok(log)
+    }
+
+    fn read(&self) -> Result<Vec<KeyAuditEntry>, KeyAuditLogError> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(KeyAuditLogError::Storage(e.to_string())),
+        };
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line)
+                    .map_err(|e| KeyAuditLogError::InvalidEntry(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+impl KeyAuditLog for FileKeyAuditLog {
+    fn append(&self, entry: &KeyAuditEntry) -> Result<(), KeyAuditLogError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| KeyAuditLogError::Storage(e.to_string()))?;
+        let mut line =
+            serde_json::to_vec(entry).map_err(|e| KeyAuditLogError::Storage(e.to_string()))?;
+        line.push(b'\n');
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| KeyAuditLogError::Storage(e.to_string()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| KeyAuditLogError::Storage(e.to_string()))?;
+        file.write_all(&line)
+            .and_then(|()| file.sync_data())
+            .map_err(|e| KeyAuditLogError::Storage(e.to_string()))
+    }
+
+    fn query(&self, query: &KeyAuditQuery) -> Result<Vec<KeyAuditEntry>, KeyAuditLogError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| KeyAuditLogError::Storage(e.to_string()))?;
+        Ok(query.select(self.read()?.iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::key_audit::{KeyOperation, KeyOperationOutcome};
+
+    fn entry(operation: KeyOperation, at: u64) -> KeyAuditEntry {
+        KeyAuditEntry {
+            operation,
+            outcome: KeyOperationOutcome::Succeeded,
+            at,
+            principal: None,
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn file_log_appends_and_filters_entries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("audit.jsonl");
+
+        let log = FileKeyAuditLog::open(&path).unwrap();
+        log.append(&entry(KeyOperation::Sign, 1)).unwrap();
+        log.append(&entry(KeyOperation::ExportMnemonic, 2)).unwrap();
+        log.append(&entry(KeyOperation::Sign, 3)).unwrap();
+
+        let reopened = FileKeyAuditLog::open(&path).unwrap();
+        let all = KeyAuditQuery {
+            since: None,
+            operation: None,
+            limit: 10,
+        };
+        assert_eq!(reopened.query(&all).unwrap().len(), 3);
+        assert_eq!(
+            reopened
+                .query(&KeyAuditQuery {
+                    operation: Some(KeyOperation::Sign),
+                    ..all.clone()
+                })
+                .unwrap(),
+            vec![entry(KeyOperation::Sign, 1), entry(KeyOperation::Sign, 3)]
+        );
+        // 新しいものから limit 件を古い順に返す
+        assert_eq!(
+            reopened
+                .query(&KeyAuditQuery {
+                    since: Some(2),
+                    limit: 1,
+                    ..all
+                })
+                .unwrap(),
+            vec![entry(KeyOperation::Sign, 3)]
+        );
+    }
+
+    #[test]
+    fn file_log_rejects_corrupted_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("audit.jsonl");
+        fs::write(&path, b"not json\n").unwrap();
+
+        assert!(matches!(
+            FileKeyAuditLog::open(&path),
+            Err(KeyAuditLogError::InvalidEntry(_))
+        ));
+    }
+}
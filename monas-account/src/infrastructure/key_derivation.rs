@@ -0,0 +1,284 @@
+//! アカウントのルート鍵からの子鍵の導出。
+//!
+//! コンテンツごと・共有相手ごとに別の鍵を使い、アカウントの識別鍵を直接使い回さないための仕組み。
+//! ルート秘密鍵を入力鍵素材、導出パスを info として HKDF-SHA256 で子の秘密鍵を決定的に導出する。
+//! 同じルート鍵とパスからは常に同じ鍵が得られるため、子鍵は保存しない。
+//!
+//! パスは `/` 区切りのセグメント列（例: `content/<ContentId>`、`peer/<did:key>`）。
+//! 子鍵の公開鍵から親や兄弟の鍵は辿れない。
+//!
+//! 署名用の子鍵とは別に、共有の CEK を受け取るための HPKE（DHKEM(P-256, HKDF-SHA256)）の
+//! 鍵も同じ方法で導出する。salt を分けているため、同じパスでも署名鍵とは別の鍵になる。
+
+use std::fmt;
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+use crate::domain::account::AccountKeyPair;
+use crate::infrastructure::key_pair::{KeyAlgorithm, KeyPairError, KeyPairGenerateFactory};
+
+/// HKDF の salt。導出方式を変える場合は版を上げる。
+const DERIVATION_SALT: &[u8] = b"monas-account/derive/v1";
+/// HPKE の鍵を導出するときの HKDF の salt。
+const ENCRYPTION_DERIVATION_SALT: &[u8] = b"monas-account/derive-hpke/v1";
+/// 導出した HPKE の鍵の KEM。monas-content の共有で使う KEM と揃える。
+pub const ENCRYPTION_KEY_KEM: &str = "DHKEM(P-256, HKDF-SHA256)";
+/// パスのセグメント数の上限。
+pub const MAX_PATH_DEPTH: usize = 8;
+/// 1 セグメントの最大長（バイト）。
+pub const MAX_SEGMENT_LEN: usize = 128;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyDerivationError {
+    #[error("invalid derivation path: {0}")]
+    InvalidPath(String),
+    #[error("invalid root key: {0}")]
+    InvalidRootKey(String),
+    #[error("failed to derive key: {0}")]
+    Derivation(#[from] KeyPairError),
+}
+
+/// 子鍵の導出パス。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyDerivationPath {
+    segments: Vec<String>,
+}
+
+impl KeyDerivationPath {
+    /// `content/bafy...` のような `/` 区切りの文字列を読む。
+    ///
+    /// 空のセグメント、制御文字、長すぎるセグメント、深すぎるパスは拒否する。
+    pub fn parse(path: &str) -> Result<Self, KeyDerivationError> {
+        Self::from_segments(path.split('/'))
+    }
+
+    /// コンテンツ単位の鍵のパス（`content/{content_id}`）。
+    pub fn content(content_id: &str) -> Result<Self, KeyDerivationError> {
+        Self::from_segments(["content", content_id])
+    }
+
+    /// 共有相手単位の鍵のパス（`peer/{peer_id}`）。
+    pub fn peer(peer_id: &str) -> Result<Self, KeyDerivationError> {
+        Self::from_segments(["peer", peer_id])
+    }
+
+    /// 共有を受け取る HPKE の鍵のパス（`share`）。
+    pub fn share() -> Self {
+        Self {
+            segments: vec!["share".to_string()],
+        }
+    }
+
+    fn from_segments<'a>(
+        segments: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, KeyDerivationError> {
+        let segments: Vec<String> = segments.into_iter().map(str::to_string).collect();
+        if segments.len() > MAX_PATH_DEPTH {
+            return Err(KeyDerivationError::InvalidPath(format!(
+                "path must have at most {MAX_PATH_DEPTH} segments"
+            )));
+        }
+        for segment in &segments {
+            if segment.is_empty() {
+                return Err(KeyDerivationError::InvalidPath(
+                    "path segments must not be empty".to_string(),
+                ));
+            }
+            if segment.len() > MAX_SEGMENT_LEN {
+                return Err(KeyDerivationError::InvalidPath(format!(
+                    "path segments must be at most {MAX_SEGMENT_LEN} bytes"
+                )));
+            }
+            if segment.contains('/') || segment.chars().any(char::is_control) {
+                return Err(KeyDerivationError::InvalidPath(format!(
+                    "invalid character in segment: {segment:?}"
+                )));
+            }
+        }
+        Ok(Self { segments })
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+}
+
+impl fmt::Display for KeyDerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.segments.join("/"))
+    }
+}
+
+/// ルート秘密鍵から `path` の子鍵を導出する。子鍵のアルゴリズムはルート鍵と同じ。
+///
+/// HKDF の出力が曲線の秘密鍵として無効な場合（確率は無視できるほど小さい）は、
+/// info 末尾のカウンタを進めて導出し直す。
+pub fn derive_child_key(
+    algorithm: KeyAlgorithm,
+    root_secret_key: &[u8],
+    path: &KeyDerivationPath,
+) -> Result<Box<dyn AccountKeyPair>, KeyDerivationError> {
+    let label: &[u8] = match algorithm {
+        KeyAlgorithm::K256 => b"K256",
+        KeyAlgorithm::P256 => b"P256",
+    };
+    derive_with(DERIVATION_SALT, label, root_secret_key, path, |secret| {
+        KeyPairGenerateFactory::from_secret_key_bytes(algorithm, secret)
+    })
+}
+
+/// 共有の CEK を受け取るための HPKE の鍵ペア。
+pub struct EncryptionKeyPair {
+    /// 非圧縮形式の SEC1 公開鍵（65 バイト）。HPKE の `pkR` としてそのまま使える。
+    pub public_key: Vec<u8>,
+    /// 32 バイトのスカラー。HPKE の `skR` としてそのまま使える。
+    pub secret_key: Vec<u8>,
+}
+
+/// ルート秘密鍵から `path` の HPKE（[`ENCRYPTION_KEY_KEM`]）の鍵を導出する。
+///
+/// ルート鍵のアルゴリズムによらず P-256 の鍵になる。
+pub fn derive_encryption_key(
+    root_secret_key: &[u8],
+    path: &KeyDerivationPath,
+) -> Result<EncryptionKeyPair, KeyDerivationError> {
+    derive_with(
+        ENCRYPTION_DERIVATION_SALT,
+        b"DHKEM-P256",
+        root_secret_key,
+        path,
+        |secret| {
+            let key_pair =
+                KeyPairGenerateFactory::from_secret_key_bytes(KeyAlgorithm::P256, secret)?;
+            Ok(EncryptionKeyPair {
+                public_key: key_pair.public_key_bytes().to_vec(),
+                secret_key: key_pair.secret_key_bytes().to_vec(),
+            })
+        },
+    )
+}
+
+/// HKDF で 32 バイトを導出し、`accept` が受け付けるまでカウンタを進めて導出し直す。
+fn derive_with<T>(
+    salt: &[u8],
+    label: &[u8],
+    root_secret_key: &[u8],
+    path: &KeyDerivationPath,
+    accept: impl Fn(&[u8]) -> Result<T, KeyPairError>,
+) -> Result<T, KeyDerivationError> {
+    if root_secret_key.len() != 32 {
+        return Err(KeyDerivationError::InvalidRootKey(format!(
+            "expected 32 bytes, got {}",
+            root_secret_key.len()
+        )));
+    }
+
+    let hk = Hkdf::<Sha256>::new(Some(salt), root_secret_key);
+    let mut info = label.to_vec();
+    for segment in &path.segments {
+        // 長さを前置して `a/bc` と `ab/c` を区別する
+        info.extend_from_slice(&(segment.len() as u32).to_be_bytes());
+        info.extend_from_slice(segment.as_bytes());
+    }
+
+    let mut last_error = None;
+    for counter in 0..=u8::MAX {
+        let mut counter_info = info.clone();
+        counter_info.push(counter);
+        let mut secret = [0u8; 32];
+        hk.expand(&counter_info, &mut secret)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        match accept(&secret) {
+            Ok(key) => return Ok(key),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error
+        .expect("at least one derivation attempt was made")
+        .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derivation_is_deterministic_and_separates_paths() {
+        let root: Vec<u8> = (1u8..=32).collect();
+        for algorithm in [KeyAlgorithm::K256, KeyAlgorithm::P256] {
+            let root_key = KeyPairGenerateFactory::from_secret_key_bytes(algorithm, &root).unwrap();
+            let content = KeyDerivationPath::content("bafy-content").unwrap();
+
+            let first = derive_child_key(algorithm, &root, &content).unwrap();
+            let second = derive_child_key(algorithm, &root, &content).unwrap();
+            assert_eq!(first.public_key_bytes(), second.public_key_bytes());
+            assert_ne!(first.public_key_bytes(), root_key.public_key_bytes());
+
+            let peer = derive_child_key(
+                algorithm,
+                &root,
+                &KeyDerivationPath::peer("bafy-content").unwrap(),
+            )
+            .unwrap();
+            assert_ne!(first.public_key_bytes(), peer.public_key_bytes());
+
+            let other_root = [7u8; 32];
+            let other = derive_child_key(algorithm, &other_root, &content).unwrap();
+            assert_ne!(first.public_key_bytes(), other.public_key_bytes());
+        }
+
+        let a = derive_child_key(
+            KeyAlgorithm::P256,
+            &root,
+            &KeyDerivationPath::parse("a/bc").unwrap(),
+        )
+        .unwrap();
+        let b = derive_child_key(
+            KeyAlgorithm::P256,
+            &root,
+            &KeyDerivationPath::parse("ab/c").unwrap(),
+        )
+        .unwrap();
+        assert_ne!(a.public_key_bytes(), b.public_key_bytes());
+    }
+
+    #[test]
+    fn encryption_keys_are_separate_from_signing_keys() {
+        let root: Vec<u8> = (1u8..=32).collect();
+        let path = KeyDerivationPath::share();
+        let first = derive_encryption_key(&root, &path).unwrap();
+        let second = derive_encryption_key(&root, &path).unwrap();
+        assert_eq!(first.public_key, second.public_key);
+        assert_eq!(first.public_key.len(), 65);
+        assert_eq!(first.public_key[0], 0x04);
+
+        let signing = derive_child_key(KeyAlgorithm::P256, &root, &path).unwrap();
+        assert_ne!(first.public_key, signing.public_key_bytes());
+        assert_ne!(
+            first.public_key,
+            derive_encryption_key(&[7u8; 32], &path).unwrap().public_key
+        );
+    }
+
+    #[test]
+    fn invalid_paths_are_rejected() {
+        assert_eq!(
+            KeyDerivationPath::parse("content/bafy")
+                .unwrap()
+                .to_string(),
+            "content/bafy"
+        );
+        for path in ["", "content/", "/content", "a\nb", &"x/".repeat(9)[..17]] {
+            assert!(
+                matches!(
+                    KeyDerivationPath::parse(path),
+                    Err(KeyDerivationError::InvalidPath(_))
+                ),
+                "{path:?}"
+            );
+        }
+        assert!(KeyDerivationPath::peer("did:key:z/evil").is_err());
+        assert!(KeyDerivationPath::content(&"a".repeat(MAX_SEGMENT_LEN + 1)).is_err());
+    }
+}
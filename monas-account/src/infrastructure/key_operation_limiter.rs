@@ -0,0 +1,164 @@
+//! 秘密鍵を使う操作のレート制限（トークンバケット）。
+//!
+//! 鍵ストアはアカウントを 1 つだけ持つため、操作の種類を問わず 1 つのバケットを共有する。
+//! 鍵が盗まれて大量に署名させられる場合でも、単位時間あたりの署名数を抑えられる。
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::application_service::KeyOperationLimiter;
+use crate::domain::key_audit::KeyOperation;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyOperationLimitConfigError {
+    #[error("invalid value for {name}: {value}")]
+    InvalidValue { name: &'static str, value: String },
+}
+
+/// レート制限の設定。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyOperationLimitConfig {
+    /// 1 分あたりに許可する操作数。
+    pub per_minute: u32,
+    /// 連続して許可する操作数。
+    pub burst: u32,
+}
+
+impl KeyOperationLimitConfig {
+    pub fn new(per_minute: u32, burst: u32) -> Self {
+        Self { per_minute, burst }
+    }
+
+    /// 環境変数から設定を読み込む。`MONAS_ACCOUNT_KEY_OPS_PER_MINUTE` が未設定なら `None`（無制限）。
+    ///
+    /// - `MONAS_ACCOUNT_KEY_OPS_PER_MINUTE`: 1 分あたりの操作数（正の整数）
+    /// - `MONAS_ACCOUNT_KEY_OPS_BURST`: 連続して許可する操作数（省略時は 1 分あたりの操作数）
+    pub fn from_env() -> Result<Option<Self>, KeyOperationLimitConfigError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, KeyOperationLimitConfigError> {
+        const PER_MINUTE: &str = "MONAS_ACCOUNT_KEY_OPS_PER_MINUTE";
+        const BURST: &str = "MONAS_ACCOUNT_KEY_OPS_BURST";
+
+        let parse = |name: &'static str, value: String| match value.trim().parse::<u32>() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(KeyOperationLimitConfigError::InvalidValue { name, value }),
+        };
+        let Some(value) = lookup(PER_MINUTE).filter(|v| !v.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let per_minute = parse(PER_MINUTE, value)?;
+        let burst = match lookup(BURST).filter(|v| !v.trim().is_empty()) {
+            Some(value) => parse(BURST, value)?,
+            None => per_minute,
+        };
+        Ok(Some(Self::new(per_minute, burst)))
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// 秘密鍵を使う操作のレート制限器。設定がない場合は常に許可する。
+#[derive(Clone, Default)]
+pub struct KeyOperationRateLimiter {
+    config: Option<KeyOperationLimitConfig>,
+    bucket: Arc<Mutex<Option<Bucket>>>,
+}
+
+impl KeyOperationRateLimiter {
+    pub fn new(config: Option<KeyOperationLimitConfig>) -> Self {
+        Self {
+            config,
+            bucket: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// トークンを 1 つ消費する。不足している場合は次に許可されるまでの時間を返す。
+    pub fn check(&self, now: Instant) -> Result<(), Duration> {
+        let Some(config) = self.config else {
+            return Ok(());
+        };
+        let capacity = f64::from(config.burst);
+        let rate = f64::from(config.per_minute) / 60.0;
+        let mut guard = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = guard.get_or_insert(Bucket {
+            tokens: capacity,
+            refilled_at: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(capacity);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+
+impl KeyOperationLimiter for KeyOperationRateLimiter {
+    fn try_acquire(&self, _operation: KeyOperation) -> Result<(), Duration> {
+        self.check(Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_burst_then_refills() {
+        let limiter = KeyOperationRateLimiter::new(Some(KeyOperationLimitConfig::new(60, 2)));
+        let start = Instant::now();
+
+        assert!(limiter.check(start).is_ok());
+        assert!(limiter.check(start).is_ok());
+        assert_eq!(limiter.check(start).unwrap_err(), Duration::from_secs(1));
+        assert!(limiter.check(start + Duration::from_secs(1)).is_ok());
+
+        let unlimited = KeyOperationRateLimiter::default();
+        for _ in 0..1000 {
+            assert!(unlimited.check(start).is_ok());
+        }
+    }
+
+    #[test]
+    fn config_from_lookup_is_disabled_by_default_and_rejects_garbage() {
+        assert_eq!(
+            KeyOperationLimitConfig::from_lookup(|_| None).unwrap(),
+            None
+        );
+        assert_eq!(
+            KeyOperationLimitConfig::from_lookup(|key| match key {
+                "MONAS_ACCOUNT_KEY_OPS_PER_MINUTE" => Some("30".into()),
+                _ => None,
+            })
+            .unwrap(),
+            Some(KeyOperationLimitConfig::new(30, 30))
+        );
+        assert_eq!(
+            KeyOperationLimitConfig::from_lookup(|key| match key {
+                "MONAS_ACCOUNT_KEY_OPS_PER_MINUTE" => Some("30".into()),
+                "MONAS_ACCOUNT_KEY_OPS_BURST" => Some("5".into()),
+                _ => None,
+            })
+            .unwrap(),
+            Some(KeyOperationLimitConfig::new(30, 5))
+        );
+        for bad in ["0", "-1", "fast"] {
+            assert!(KeyOperationLimitConfig::from_lookup(|key| {
+                (key == "MONAS_ACCOUNT_KEY_OPS_PER_MINUTE").then(|| bad.to_string())
+            })
+            .is_err());
+        }
+    }
+}
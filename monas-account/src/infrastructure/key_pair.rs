@@ -1,5 +1,7 @@
+pub mod http_signer;
 pub mod k256_key_pair;
 pub mod p256_key_pair;
+pub mod remote_signer;
 
 use crate::domain::account::AccountKeyPair;
 use crate::infrastructure::key_pair::k256_key_pair::K256KeyPair;
@@ -14,7 +16,7 @@ pub enum KeyPair {
     //RsaKeyPair(RsaKeyPair),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KeyAlgorithm {
     K256,
     P256,
@@ -45,12 +47,46 @@ impl KeyPairGenerateFactory {
             )?)),
         }
     }
+
+    /// 秘密鍵のバイト列だけから鍵ペアを復元する。ニーモニックからの復元に使う。
+    pub fn from_secret_key_bytes(
+        key_type: KeyAlgorithm,
+        secret_key: &[u8],
+    ) -> Result<Box<dyn AccountKeyPair>, KeyPairError> {
+        match key_type {
+            KeyAlgorithm::K256 => Ok(Box::new(K256KeyPair::from_secret_key_bytes(secret_key)?)),
+            KeyAlgorithm::P256 => Ok(Box::new(P256KeyPair::from_secret_key_bytes(secret_key)?)),
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum KeyPairError {
     #[error("invalid secret key: {0}")]
     InvalidSecretKey(String),
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(String),
+    #[error("invalid signature encoding: {0}")]
+    InvalidSignature(String),
+    #[error("signing failed: {0}")]
+    Signing(String),
+}
+
+/// `AccountKeyPair::sign` で作った署名を検証する。
+///
+/// 署名対象のダイジェストは署名時と同じ（K256 は Keccak-256、P256 は SHA-256）。
+/// 公開鍵は SEC1 形式（圧縮・非圧縮のどちらでもよい）、署名は 64 バイトの `r || s`。
+/// 公開鍵・署名の形式が不正な場合はエラー、署名が一致しない場合は `Ok(false)` を返す。
+pub fn verify_signature(
+    algorithm: KeyAlgorithm,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, KeyPairError> {
+    match algorithm {
+        KeyAlgorithm::K256 => K256KeyPair::verify(public_key, message, signature),
+        KeyAlgorithm::P256 => P256KeyPair::verify(public_key, message, signature),
+    }
 }
 
 #[cfg(test)]
@@ -64,6 +100,35 @@ mod key_pair_tests {
         assert_eq!(k256.secret_key_bytes().len(), 32);
     }
 
+    #[test]
+    fn verify_signature_accepts_own_signatures_only() {
+        use crate::infrastructure::key_pair::{verify_signature, KeyPairError};
+
+        for algorithm in [KeyAlgorithm::K256, KeyAlgorithm::P256] {
+            let key_pair = KeyPairGenerateFactory::generate(algorithm);
+            let (signature, _) = key_pair.sign(b"message").unwrap();
+            let public_key = key_pair.public_key_bytes();
+
+            assert!(verify_signature(algorithm, public_key, b"message", &signature).unwrap());
+            assert!(!verify_signature(algorithm, public_key, b"tampered", &signature).unwrap());
+
+            let other = KeyPairGenerateFactory::generate(algorithm);
+            assert!(
+                !verify_signature(algorithm, other.public_key_bytes(), b"message", &signature)
+                    .unwrap()
+            );
+
+            assert!(matches!(
+                verify_signature(algorithm, &[4; 10], b"message", &signature),
+                Err(KeyPairError::InvalidPublicKey(_))
+            ));
+            assert!(matches!(
+                verify_signature(algorithm, public_key, b"message", &signature[..10]),
+                Err(KeyPairError::InvalidSignature(_))
+            ));
+        }
+    }
+
     #[test]
     fn key_pair_p256_generate_test() {
         let p256 = KeyPairGenerateFactory::generate(KeyAlgorithm::P256);
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::key_pair::remote_signer::SignerBackend;
+
+/// 署名要求の応答を待つ上限。
+const SIGN_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize, Deserialize)]
+struct SignDigestRequest {
+    digest_base64: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignDigestResponse {
+    signature_base64: String,
+}
+
+/// HTTP の KMS にダイジェストへの署名を依頼する [`SignerBackend`]。
+///
+/// `url` に `{"digest_base64": ...}` を POST し、`{"signature_base64": ...}`（64 バイトの
+/// `r || s`）を受け取る。`token` を指定すると `Authorization: Bearer` で送る。
+/// 要求は呼び出したスレッドで同期的に行う。
+pub struct HttpSignerBackend {
+    agent: ureq::Agent,
+    url: String,
+    token: Option<String>,
+}
+
+impl HttpSignerBackend {
+    pub fn new(url: impl Into<String>, token: Option<String>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(SIGN_REQUEST_TIMEOUT))
+            .build()
+            .into();
+        Self {
+            agent,
+            url: url.into(),
+            token,
+        }
+    }
+}
+
+impl SignerBackend for HttpSignerBackend {
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Vec<u8>, String> {
+        let mut request = self.agent.post(&self.url);
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", &format!("Bearer {token}"));
+        }
+        let response: SignDigestResponse = request
+            .send_json(SignDigestRequest {
+                digest_base64: BASE64_STANDARD.encode(digest),
+            })
+            .map_err(|e| format!("signer request to {} failed: {e}", self.url))?
+            .body_mut()
+            .read_json()
+            .map_err(|e| format!("invalid signer response: {e}"))?;
+        BASE64_STANDARD
+            .decode(response.signature_base64)
+            .map_err(|e| format!("invalid signer response: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod http_signer_tests {
+    use super::*;
+    use crate::application_service::AccountService;
+    use crate::domain::account::AccountKeyPair;
+    use crate::infrastructure::key_pair::remote_signer::RemoteSigner;
+    use crate::infrastructure::key_pair::{
+        verify_signature, KeyAlgorithm, KeyPairError, KeyPairGenerateFactory,
+    };
+    use crate::infrastructure::key_store::RemoteAccountKeyStore;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use p256::ecdsa::signature::hazmat::PrehashSigner;
+    use std::sync::Arc;
+
+    /// P256 の秘密鍵でダイジェストに署名する KMS を立て、その URL を返す。
+    fn serve_kms(runtime: &tokio::runtime::Runtime, secret_key: Vec<u8>) -> String {
+        let app = Router::new().route(
+            "/sign",
+            post(
+                move |headers: HeaderMap, Json(request): Json<SignDigestRequest>| async move {
+                    if headers.get("authorization").and_then(|v| v.to_str().ok())
+                        != Some("Bearer kms-token")
+                    {
+                        return Err(StatusCode::UNAUTHORIZED);
+                    }
+                    let digest = BASE64_STANDARD
+                        .decode(request.digest_base64)
+                        .map_err(|_| StatusCode::BAD_REQUEST)?;
+                    let key = p256::ecdsa::SigningKey::from_slice(&secret_key).unwrap();
+                    let signature: p256::ecdsa::Signature = key
+                        .sign_prehash(&digest)
+                        .map_err(|_| StatusCode::BAD_REQUEST)?;
+                    Ok(Json(SignDigestResponse {
+                        signature_base64: BASE64_STANDARD.encode(signature.to_bytes()),
+                    }))
+                },
+            ),
+        );
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        runtime.spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}/sign")
+    }
+
+    #[test]
+    fn account_service_signs_through_http_kms() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let local = KeyPairGenerateFactory::generate(KeyAlgorithm::P256);
+        let url = serve_kms(&runtime, local.secret_key_bytes().to_vec());
+
+        let signer = RemoteSigner::new(
+            KeyAlgorithm::P256,
+            local.public_key_bytes(),
+            Arc::new(HttpSignerBackend::new(&url, Some("kms-token".to_string()))),
+        )
+        .unwrap();
+        let store = RemoteAccountKeyStore::new(signer);
+
+        let (signature, _) = AccountService::sign(&store, b"operation").unwrap();
+        assert!(verify_signature(
+            KeyAlgorithm::P256,
+            local.public_key_bytes(),
+            b"operation",
+            &signature
+        )
+        .unwrap());
+
+        // トークンが違えば KMS は署名せず、署名はエラーになる
+        let unauthorized = RemoteSigner::new(
+            KeyAlgorithm::P256,
+            local.public_key_bytes(),
+            Arc::new(HttpSignerBackend::new(&url, Some("wrong".to_string()))),
+        )
+        .unwrap();
+        assert!(matches!(
+            unauthorized.sign(b"operation"),
+            Err(KeyPairError::Signing(_))
+        ));
+    }
+}
@@ -2,7 +2,8 @@ use crate::domain::account::AccountKeyPair;
 use crate::infrastructure::key_pair::KeyPairError;
 use p256::ecdsa::signature::digest::Digest;
 use p256::ecdsa::signature::DigestSigner;
-use p256::ecdsa::{SigningKey, VerifyingKey};
+use p256::ecdsa::signature::DigestVerifier;
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
 use p256::elliptic_curve::rand_core::OsRng;
 use p256::{EncodedPoint, FieldBytes};
 use sha2::Sha256;
@@ -27,6 +28,41 @@ impl P256KeyPair {
         }
     }
 
+    /// `public_key`（SEC1）に対する署名（`r || s`）を検証する。
+    pub fn verify(
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, KeyPairError> {
+        let verifying_key = VerifyingKey::from_sec1_bytes(public_key)
+            .map_err(|e| KeyPairError::InvalidPublicKey(e.to_string()))?;
+        let signature = Signature::from_slice(signature)
+            .map_err(|e| KeyPairError::InvalidSignature(e.to_string()))?;
+        Ok(verifying_key
+            .verify_digest(Sha256::new_with_prefix(message), &signature)
+            .is_ok())
+    }
+
+    /// 秘密鍵のバイト列から P256KeyPair を復元する（公開鍵は秘密鍵から導出する）。
+    pub fn from_secret_key_bytes(secret_key_bytes: &[u8]) -> Result<Self, KeyPairError> {
+        if secret_key_bytes.len() != 32 {
+            return Err(KeyPairError::InvalidSecretKey(format!(
+                "expected 32 bytes, got {}",
+                secret_key_bytes.len()
+            )));
+        }
+        let field = FieldBytes::from_slice(secret_key_bytes);
+        let secret_key = SigningKey::from_bytes(field)
+            .map_err(|e| KeyPairError::InvalidSecretKey(e.to_string()))?;
+        let public_key_point = VerifyingKey::from(&secret_key).to_encoded_point(false);
+        let secret_key_field_key = secret_key.to_bytes();
+        Ok(Self {
+            secret_key,
+            public_key_point,
+            secret_key_field_key,
+        })
+    }
+
     /// 永続化された鍵バイト列から P256KeyPair を復元する。
     pub fn from_key_bytes(
         public_key: &[u8],
@@ -59,11 +95,11 @@ impl P256KeyPair {
 }
 
 impl AccountKeyPair for P256KeyPair {
-    fn sign(&self, message: &[u8]) -> (Vec<u8>, Option<u8>) {
+    fn sign(&self, message: &[u8]) -> Result<(Vec<u8>, Option<u8>), KeyPairError> {
         let (signature, _) = self
             .secret_key
             .sign_digest(Sha256::new_with_prefix(message));
-        (signature.to_vec(), None)
+        Ok((signature.to_vec(), None))
     }
 
     fn public_key_bytes(&self) -> &[u8] {
@@ -102,7 +138,7 @@ mod p256_key_pair_tests {
         let p256 = P256KeyPair::generate();
         let message = b"test message";
 
-        let (sig_bytes, _) = p256.sign(message);
+        let (sig_bytes, _) = p256.sign(message).unwrap();
 
         let signature =
             Signature::from_slice(sig_bytes.as_slice()).expect("invalid signature bytes");
@@ -118,16 +154,16 @@ mod p256_key_pair_tests {
     #[test]
     fn different_message_gives_different_signature() {
         let p256 = P256KeyPair::generate();
-        let (sig1, _) = p256.sign(b"same");
-        let (sig2, _) = p256.sign(b"different");
+        let (sig1, _) = p256.sign(b"same").unwrap();
+        let (sig2, _) = p256.sign(b"different").unwrap();
         assert_ne!(sig1, sig2);
     }
 
     #[test]
     fn same_message_gives_same_signature() {
         let p256 = P256KeyPair::generate();
-        let (sig1, _) = p256.sign(b"same");
-        let (sig2, _) = p256.sign(b"same");
+        let (sig1, _) = p256.sign(b"same").unwrap();
+        let (sig2, _) = p256.sign(b"same").unwrap();
         assert_eq!(sig1, sig2);
     }
 }
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use crate::domain::account::AccountKeyPair;
+use crate::infrastructure::key_pair::{verify_signature, KeyAlgorithm, KeyPairError};
+use k256::sha2::Digest;
+use sha2::Sha256;
+use sha3::Keccak256;
+
+/// 秘密鍵を保持する外部の署名器（PKCS#11 トークン、OS のキーチェーン、HTTP の KMS など）。
+///
+/// 実装はダイジェストに ECDSA で署名し、64 バイトの `r || s` を返す。ダイジェストは
+/// ローカルの鍵ペアと同じく K256 は Keccak-256、P256 は SHA-256 で計算して渡す。
+pub trait SignerBackend: Send + Sync {
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Vec<u8>, String>;
+}
+
+/// 署名を [`SignerBackend`] に委ねる鍵ペア。
+///
+/// 秘密鍵はプロセスのメモリに置かない。そのため [`AccountKeyPair::secret_key_bytes`] は
+/// 空のスライスを返し、鍵ストアへの保存やニーモニックへの書き出しには使えない。
+#[derive(Clone)]
+pub struct RemoteSigner {
+    algorithm: KeyAlgorithm,
+    public_key: Vec<u8>,
+    backend: Arc<dyn SignerBackend>,
+}
+
+impl RemoteSigner {
+    /// 外部の署名器が持つ鍵の公開鍵（SEC1）を指定して作る。
+    pub fn new(
+        algorithm: KeyAlgorithm,
+        public_key: &[u8],
+        backend: Arc<dyn SignerBackend>,
+    ) -> Result<Self, KeyPairError> {
+        let public_key = match algorithm {
+            KeyAlgorithm::K256 => k256::PublicKey::from_sec1_bytes(public_key)
+                .map_err(|e| KeyPairError::InvalidPublicKey(e.to_string()))?
+                .to_sec1_bytes()
+                .to_vec(),
+            KeyAlgorithm::P256 => p256::PublicKey::from_sec1_bytes(public_key)
+                .map_err(|e| KeyPairError::InvalidPublicKey(e.to_string()))?
+                .to_sec1_bytes()
+                .to_vec(),
+        };
+        Ok(Self {
+            algorithm,
+            public_key,
+            backend,
+        })
+    }
+
+    pub fn algorithm(&self) -> KeyAlgorithm {
+        self.algorithm
+    }
+
+    fn digest(&self, message: &[u8]) -> [u8; 32] {
+        match self.algorithm {
+            KeyAlgorithm::K256 => Keccak256::digest(message).into(),
+            KeyAlgorithm::P256 => Sha256::digest(message).into(),
+        }
+    }
+}
+
+impl AccountKeyPair for RemoteSigner {
+    /// 署名器の応答を公開鍵で検証してから返す。署名器の失敗や別の鍵による署名はエラー。
+    fn sign(&self, message: &[u8]) -> Result<(Vec<u8>, Option<u8>), KeyPairError> {
+        let digest = self.digest(message);
+        let signature = self
+            .backend
+            .sign_digest(&digest)
+            .map_err(KeyPairError::Signing)?;
+
+        let (signature, recovery_id) = match self.algorithm {
+            KeyAlgorithm::K256 => {
+                let signature = k256::ecdsa::Signature::from_slice(&signature)
+                    .map_err(|e| KeyPairError::InvalidSignature(e.to_string()))?;
+                // k256 は low-S の署名しか受け付けないため正規化する
+                let signature = signature.normalize_s().unwrap_or(signature);
+                let verifying_key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&self.public_key)
+                    .map_err(|e| KeyPairError::InvalidPublicKey(e.to_string()))?;
+                let recovery_id = k256::ecdsa::RecoveryId::trial_recovery_from_prehash(
+                    &verifying_key,
+                    &digest,
+                    &signature,
+                )
+                .ok()
+                .map(|id| id.to_byte());
+                (signature.to_vec(), recovery_id)
+            }
+            KeyAlgorithm::P256 => (signature, None),
+        };
+
+        if !verify_signature(self.algorithm, &self.public_key, message, &signature)? {
+            return Err(KeyPairError::Signing(
+                "signer returned a signature that does not match the public key".to_string(),
+            ));
+        }
+        Ok((signature, recovery_id))
+    }
+
+    fn public_key_bytes(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    fn secret_key_bytes(&self) -> &[u8] {
+        &[]
+    }
+}
+
+#[cfg(test)]
+mod remote_signer_tests {
+    use super::*;
+    use crate::domain::account::Account;
+    use crate::infrastructure::key_pair::KeyPairGenerateFactory;
+
+    /// 別の場所にある鍵の代わりに、プロセス内の秘密鍵でダイジェストに署名する。
+    struct LocalBackend {
+        algorithm: KeyAlgorithm,
+        secret_key: Vec<u8>,
+    }
+
+    impl SignerBackend for LocalBackend {
+        fn sign_digest(&self, digest: &[u8; 32]) -> Result<Vec<u8>, String> {
+            use p256::ecdsa::signature::hazmat::PrehashSigner;
+            match self.algorithm {
+                KeyAlgorithm::K256 => {
+                    let key = k256::ecdsa::SigningKey::from_slice(&self.secret_key)
+                        .map_err(|e| e.to_string())?;
+                    let signature: k256::ecdsa::Signature =
+                        key.sign_prehash(digest).map_err(|e| e.to_string())?;
+                    Ok(signature.to_vec())
+                }
+                KeyAlgorithm::P256 => {
+                    let key = p256::ecdsa::SigningKey::from_slice(&self.secret_key)
+                        .map_err(|e| e.to_string())?;
+                    let signature: p256::ecdsa::Signature =
+                        key.sign_prehash(digest).map_err(|e| e.to_string())?;
+                    Ok(signature.to_vec())
+                }
+            }
+        }
+    }
+
+    struct FailingBackend;
+
+    impl SignerBackend for FailingBackend {
+        fn sign_digest(&self, _digest: &[u8; 32]) -> Result<Vec<u8>, String> {
+            Err("token not present".to_string())
+        }
+    }
+
+    #[test]
+    fn remote_signatures_match_local_key_pairs() {
+        for algorithm in [KeyAlgorithm::K256, KeyAlgorithm::P256] {
+            let local = KeyPairGenerateFactory::generate(algorithm);
+            let backend = Arc::new(LocalBackend {
+                algorithm,
+                secret_key: local.secret_key_bytes().to_vec(),
+            });
+            let remote = RemoteSigner::new(algorithm, local.public_key_bytes(), backend).unwrap();
+            assert!(remote.secret_key_bytes().is_empty());
+
+            let account = Account::new(Box::new(remote));
+            let (signature, recovery_id) = account.sign(b"operation").unwrap();
+            assert_eq!(signature, local.sign(b"operation").unwrap().0);
+            assert_eq!(recovery_id.is_some(), algorithm == KeyAlgorithm::K256);
+            assert!(verify_signature(
+                algorithm,
+                local.public_key_bytes(),
+                b"operation",
+                &signature
+            )
+            .unwrap());
+        }
+    }
+
+    #[test]
+    fn rejects_backend_failures_and_foreign_keys() {
+        let local = KeyPairGenerateFactory::generate(KeyAlgorithm::P256);
+        let failing = RemoteSigner::new(
+            KeyAlgorithm::P256,
+            local.public_key_bytes(),
+            Arc::new(FailingBackend),
+        )
+        .unwrap();
+        assert!(matches!(
+            failing.sign(b"operation"),
+            Err(KeyPairError::Signing(_))
+        ));
+
+        // 署名器の鍵と公開鍵が一致しない
+        let other = KeyPairGenerateFactory::generate(KeyAlgorithm::P256);
+        let mismatched = RemoteSigner::new(
+            KeyAlgorithm::P256,
+            local.public_key_bytes(),
+            Arc::new(LocalBackend {
+                algorithm: KeyAlgorithm::P256,
+                secret_key: other.secret_key_bytes().to_vec(),
+            }),
+        )
+        .unwrap();
+        assert!(matches!(
+            mismatched.sign(b"operation"),
+            Err(KeyPairError::Signing(_))
+        ));
+    }
+}
@@ -0,0 +1,251 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use super::durable::sync_file_and_parent;
+use crate::application_service::{KeyRotationLog, KeyRotationLogError};
+use crate::domain::key_rotation::KeyRotationRecord;
+use crate::infrastructure::key_pair::KeyAlgorithm;
+
+/// 実行時に保存先を切り替えるローテーション記録の動的型。
+pub type DynKeyRotationLog = Arc<dyn KeyRotationLog + Send + Sync>;
+
+/// ローテーション記録をプロセス内に保持するインメモリ実装。
+#[derive(Clone, Default)]
+pub struct InMemoryKeyRotationLog {
+    inner: Arc<Mutex<Vec<KeyRotationRecord>>>,
+}
+
+impl KeyRotationLog for InMemoryKeyRotationLog {
+    fn append(&self, record: &KeyRotationRecord) -> Result<(), KeyRotationLogError> {
+        self.inner
+            .lock()
+            .map_err(|e| KeyRotationLogError::Storage(e.to_string()))?
+            .push(record.clone());
+        Ok(())
+    }
+
+    fn history(&self) -> Result<Vec<KeyRotationRecord>, KeyRotationLogError> {
+        Ok(self
+            .inner
+            .lock()
+            .map_err(|e| KeyRotationLogError::Storage(e.to_string()))?
+            .clone())
+    }
+
+    fn clear(&self) -> Result<(), KeyRotationLogError> {
+        self.inner
+            .lock()
+            .map_err(|e| KeyRotationLogError::Storage(e.to_string()))?
+            .clear();
+        Ok(())
+    }
+}
+
+/// ローテーション記録を JSON ファイルに保存する実装。
+///
+/// 記録は公開情報（識別子・公開鍵・署名）のみのため暗号化しない。
+/// 追記のたびにファイル全体を一時ファイル経由で書き換える。
+#[derive(Clone)]
+pub struct FileKeyRotationLog {
+    path: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredRotationRecord {
+    sequence: u64,
+    previous_account_id: String,
+    previous_algorithm: String,
+    previous_public_key_base64: String,
+    new_account_id: String,
+    new_algorithm: String,
+    new_public_key_base64: String,
+    rotated_at: u64,
+    signature_base64: String,
+}
+
+impl FileKeyRotationLog {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, KeyRotationLogError> {
+        let log = Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Arc::new(Mutex::new(())),
+        };
+        // 壊れたファイルは起動時に検出する
+        log.read()?;
+        Ok(log)
+    }
+
+    fn read(&self) -> Result<Vec<KeyRotationRecord>, KeyRotationLogError> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(KeyRotationLogError::Storage(e.to_string())),
+        };
+        let stored: Vec<StoredRotationRecord> = serde_json::from_slice(&bytes)
+            .map_err(|e| KeyRotationLogError::InvalidRecord(e.to_string()))?;
+        stored.into_iter().map(from_stored).collect()
+    }
+
+    fn write(&self, records: &[KeyRotationRecord]) -> Result<(), KeyRotationLogError> {
+        let stored: Vec<StoredRotationRecord> = records.iter().map(to_stored).collect();
+        let json = serde_json::to_vec_pretty(&stored)
+            .map_err(|e| KeyRotationLogError::Storage(e.to_string()))?;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| KeyRotationLogError::Storage(e.to_string()))?;
+        }
+
+        let mut tmp_name = self.path.clone().into_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
+        fs::write(&tmp_path, json).map_err(|e| KeyRotationLogError::Storage(e.to_string()))?;
+        fs::rename(&tmp_path, &self.path).map_err(|e| KeyRotationLogError::Storage(e.to_string()))
+    }
+}
+
+impl KeyRotationLog for FileKeyRotationLog {
+    fn append(&self, record: &KeyRotationRecord) -> Result<(), KeyRotationLogError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| KeyRotationLogError::Storage(e.to_string()))?;
+        let mut records = self.read()?;
+        records.push(record.clone());
+        self.write(&records)
+    }
+
+    fn history(&self) -> Result<Vec<KeyRotationRecord>, KeyRotationLogError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| KeyRotationLogError::Storage(e.to_string()))?;
+        self.read()
+    }
+
+    fn clear(&self) -> Result<(), KeyRotationLogError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| KeyRotationLogError::Storage(e.to_string()))?;
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(KeyRotationLogError::Storage(e.to_string())),
+        }
+    }
+
+    fn flush(&self) -> Result<(), KeyRotationLogError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| KeyRotationLogError::Storage(e.to_string()))?;
+        sync_file_and_parent(&self.path).map_err(|e| KeyRotationLogError::Storage(e.to_string()))
+    }
+}
+
+fn algorithm_name(algorithm: KeyAlgorithm) -> String {
+    match algorithm {
+        KeyAlgorithm::K256 => "K256",
+        KeyAlgorithm::P256 => "P256",
+    }
+    .to_string()
+}
+
+fn parse_algorithm(name: &str) -> Result<KeyAlgorithm, KeyRotationLogError> {
+    match name {
+        "K256" => Ok(KeyAlgorithm::K256),
+        "P256" => Ok(KeyAlgorithm::P256),
+        other => Err(KeyRotationLogError::InvalidRecord(format!(
+            "unknown algorithm: {other}"
+        ))),
+    }
+}
+
+fn decode(name: &str, value: &str) -> Result<Vec<u8>, KeyRotationLogError> {
+    BASE64_STANDARD
+        .decode(value)
+        .map_err(|e| KeyRotationLogError::InvalidRecord(format!("invalid {name}: {e}")))
+}
+
+fn to_stored(record: &KeyRotationRecord) -> StoredRotationRecord {
+    StoredRotationRecord {
+        sequence: record.sequence,
+        previous_account_id: record.previous_account_id.clone(),
+        previous_algorithm: algorithm_name(record.previous_algorithm),
+        previous_public_key_base64: BASE64_STANDARD.encode(&record.previous_public_key),
+        new_account_id: record.new_account_id.clone(),
+        new_algorithm: algorithm_name(record.new_algorithm),
+        new_public_key_base64: BASE64_STANDARD.encode(&record.new_public_key),
+        rotated_at: record.rotated_at,
+        signature_base64: BASE64_STANDARD.encode(&record.signature),
+    }
+}
+
+fn from_stored(stored: StoredRotationRecord) -> Result<KeyRotationRecord, KeyRotationLogError> {
+    Ok(KeyRotationRecord {
+        sequence: stored.sequence,
+        previous_account_id: stored.previous_account_id,
+        previous_algorithm: parse_algorithm(&stored.previous_algorithm)?,
+        previous_public_key: decode(
+            "previous_public_key_base64",
+            &stored.previous_public_key_base64,
+        )?,
+        new_account_id: stored.new_account_id,
+        new_algorithm: parse_algorithm(&stored.new_algorithm)?,
+        new_public_key: decode("new_public_key_base64", &stored.new_public_key_base64)?,
+        rotated_at: stored.rotated_at,
+        signature: decode("signature_base64", &stored.signature_base64)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(sequence: u64) -> KeyRotationRecord {
+        KeyRotationRecord {
+            sequence,
+            previous_account_id: format!("did:key:zOld{sequence}"),
+            previous_algorithm: KeyAlgorithm::K256,
+            previous_public_key: vec![1; 65],
+            new_account_id: format!("did:key:zNew{sequence}"),
+            new_algorithm: KeyAlgorithm::P256,
+            new_public_key: vec![2; 65],
+            rotated_at: 1_700_000_000 + sequence,
+            signature: vec![3; 64],
+        }
+    }
+
+    #[test]
+    fn file_log_persists_records_in_order() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("rotations.json");
+
+        let log = FileKeyRotationLog::open(&path).unwrap();
+        assert!(log.history().unwrap().is_empty());
+        log.append(&record(1)).unwrap();
+        log.append(&record(2)).unwrap();
+
+        let reopened = FileKeyRotationLog::open(&path).unwrap();
+        assert_eq!(reopened.history().unwrap(), vec![record(1), record(2)]);
+
+        reopened.clear().unwrap();
+        assert!(reopened.history().unwrap().is_empty());
+    }
+
+    #[test]
+    fn file_log_rejects_corrupted_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("rotations.json");
+        fs::write(&path, b"not json").unwrap();
+
+        assert!(matches!(
+            FileKeyRotationLog::open(&path),
+            Err(KeyRotationLogError::InvalidRecord(_))
+        ));
+    }
+}
@@ -1,7 +1,223 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-use crate::application_service::{AccountKeyStore, AccountKeyStoreError, StoredAccountKey};
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
+
+use crate::application_service::{
+    AccountKeyStore, AccountKeyStoreError, KeyAuditLogError, KeyRevocationListError,
+    KeyRotationLogError, ProfileRepositoryError, StoredAccountKey,
+};
+use crate::domain::account::AccountKeyPair;
+use crate::infrastructure::file_key_store::FileAccountKeyStore;
+use crate::infrastructure::key_audit_log::{DynKeyAuditLog, FileKeyAuditLog, InMemoryKeyAuditLog};
+use crate::infrastructure::key_pair::http_signer::HttpSignerBackend;
+use crate::infrastructure::key_pair::remote_signer::RemoteSigner;
+use crate::infrastructure::key_pair::{KeyAlgorithm, KeyPairError};
+use crate::infrastructure::key_rotation_log::{
+    DynKeyRotationLog, FileKeyRotationLog, InMemoryKeyRotationLog,
+};
+use crate::infrastructure::profile_repository::{
+    DynProfileRepository, FileProfileRepository, InMemoryProfileRepository,
+};
+use crate::infrastructure::revocation_list::{
+    DynKeyRevocationList, FileKeyRevocationList, InMemoryKeyRevocationList,
+};
+
+/// 実行時に保存先を切り替えるアカウント鍵ストアの動的型。
+pub type DynAccountKeyStore = Arc<dyn AccountKeyStore + Send + Sync>;
+
+/// アカウント鍵の保存先。
+#[derive(Clone, Default, PartialEq, Eq)]
+pub enum AccountKeyStoreConfig {
+    /// プロセス内に保持する（再起動で失われる）。
+    #[default]
+    InMemory,
+    /// パスフレーズで暗号化したファイルに保存する（[`FileAccountKeyStore`]）。
+    File { path: PathBuf, passphrase: String },
+    /// 秘密鍵を HTTP の KMS に置き、署名を依頼する（[`RemoteAccountKeyStore`]）。
+    ///
+    /// `state_path` を指定すると、ローテーション記録などを `{state_path}.*.json` に保存する。
+    Remote {
+        url: String,
+        token: Option<String>,
+        algorithm: KeyAlgorithm,
+        public_key: Vec<u8>,
+        state_path: Option<PathBuf>,
+    },
+}
+
+impl std::fmt::Debug for AccountKeyStoreConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InMemory => f.write_str("InMemory"),
+            // パスフレーズはログに出さない
+            Self::File { path, .. } => f
+                .debug_struct("File")
+                .field("path", path)
+                .field("passphrase", &"<redacted>")
+                .finish(),
+            Self::Remote {
+                url,
+                token,
+                algorithm,
+                state_path,
+                ..
+            } => f
+                .debug_struct("Remote")
+                .field("url", url)
+                .field("token", &token.as_ref().map(|_| "<redacted>"))
+                .field("algorithm", algorithm)
+                .field("state_path", state_path)
+                .finish(),
+        }
+    }
+}
+
+impl AccountKeyStoreConfig {
+    /// 環境変数から保存先を読み込む。`MONAS_ACCOUNT_KEYSTORE_PATH` が未設定ならインメモリ。
+    ///
+    /// - `MONAS_ACCOUNT_KEYSTORE_PATH`: 鍵ファイルのパス
+    /// - `MONAS_ACCOUNT_KEYSTORE_PASSPHRASE`: 鍵ファイルのパスフレーズ（パス指定時は必須）
+    /// - `MONAS_ACCOUNT_REMOTE_SIGNER_URL`: 署名を依頼する KMS の URL。指定すると鍵ファイルは
+    ///   使わず、`MONAS_ACCOUNT_KEYSTORE_PATH` は記録ファイルの置き場所にだけ使う
+    /// - `MONAS_ACCOUNT_REMOTE_SIGNER_PUBLIC_KEY`: KMS の鍵の公開鍵（SEC1 の base64。URL 指定時は必須）
+    /// - `MONAS_ACCOUNT_REMOTE_SIGNER_ALGORITHM`: `K256` または `P256`（既定は `P256`）
+    /// - `MONAS_ACCOUNT_REMOTE_SIGNER_TOKEN`: KMS に送る Bearer トークン
+    pub fn from_env() -> Result<Self, AccountKeyStoreError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, AccountKeyStoreError> {
+        let non_empty = |key: &str| lookup(key).filter(|v| !v.trim().is_empty());
+        if let Some(url) = non_empty("MONAS_ACCOUNT_REMOTE_SIGNER_URL") {
+            let public_key = non_empty("MONAS_ACCOUNT_REMOTE_SIGNER_PUBLIC_KEY").ok_or_else(|| {
+                AccountKeyStoreError::Storage(
+                    "MONAS_ACCOUNT_REMOTE_SIGNER_PUBLIC_KEY is required with MONAS_ACCOUNT_REMOTE_SIGNER_URL"
+                        .to_string(),
+                )
+            })?;
+            let public_key = BASE64_STANDARD.decode(public_key.trim()).map_err(|e| {
+                AccountKeyStoreError::InvalidKeyData(format!(
+                    "MONAS_ACCOUNT_REMOTE_SIGNER_PUBLIC_KEY: {e}"
+                ))
+            })?;
+            let algorithm = match non_empty("MONAS_ACCOUNT_REMOTE_SIGNER_ALGORITHM").as_deref() {
+                None | Some("P256") => KeyAlgorithm::P256,
+                Some("K256") => KeyAlgorithm::K256,
+                Some(other) => {
+                    return Err(AccountKeyStoreError::InvalidKeyData(format!(
+                        "MONAS_ACCOUNT_REMOTE_SIGNER_ALGORITHM: unknown algorithm {other}"
+                    )))
+                }
+            };
+            return Ok(Self::Remote {
+                url,
+                token: non_empty("MONAS_ACCOUNT_REMOTE_SIGNER_TOKEN"),
+                algorithm,
+                public_key,
+                state_path: non_empty("MONAS_ACCOUNT_KEYSTORE_PATH").map(PathBuf::from),
+            });
+        }
+
+        let Some(path) = lookup("MONAS_ACCOUNT_KEYSTORE_PATH").filter(|v| !v.trim().is_empty())
+        else {
+            return Ok(Self::InMemory);
+        };
+        let passphrase = lookup("MONAS_ACCOUNT_KEYSTORE_PASSPHRASE")
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| {
+                AccountKeyStoreError::Storage(
+                    "MONAS_ACCOUNT_KEYSTORE_PASSPHRASE is required with MONAS_ACCOUNT_KEYSTORE_PATH"
+                        .to_string(),
+                )
+            })?;
+        Ok(Self::File {
+            path: PathBuf::from(path),
+            passphrase,
+        })
+    }
+
+    /// 設定に従って鍵ストアを作る。鍵ファイルのパスフレーズが誤っている場合はエラーを返す。
+    pub fn build(&self) -> Result<DynAccountKeyStore, AccountKeyStoreError> {
+        Ok(match self {
+            Self::InMemory => Arc::new(InMemoryAccountKeyStore::default()),
+            Self::File { path, passphrase } => {
+                Arc::new(FileAccountKeyStore::open(path, passphrase)?)
+            }
+            Self::Remote {
+                url,
+                token,
+                algorithm,
+                public_key,
+                ..
+            } => {
+                let backend = HttpSignerBackend::new(url, token.clone());
+                let signer = RemoteSigner::new(*algorithm, public_key, Arc::new(backend))
+                    .map_err(|e| AccountKeyStoreError::InvalidKeyData(e.to_string()))?;
+                Arc::new(RemoteAccountKeyStore::new(signer))
+            }
+        })
+    }
+
+    /// 記録ファイルの名前の元になるパス。インメモリなら `None`。
+    fn state_path(&self) -> Option<&Path> {
+        match self {
+            Self::InMemory => None,
+            Self::File { path, .. } => Some(path),
+            Self::Remote { state_path, .. } => state_path.as_deref(),
+        }
+    }
+
+    /// `{記録ファイルの元のパス}{suffix}`。インメモリなら `None`。
+    fn state_file(&self, suffix: &str) -> Option<PathBuf> {
+        self.state_path().map(|path| {
+            let mut file = path.as_os_str().to_owned();
+            file.push(suffix);
+            PathBuf::from(file)
+        })
+    }
+
+    /// 鍵の保存先に合わせてローテーション記録の保存先を作る。
+    ///
+    /// ファイルに保存する場合は、鍵ファイルの隣の `{鍵ファイル名}.rotations.json` に保存する。
+    pub fn build_rotation_log(&self) -> Result<DynKeyRotationLog, KeyRotationLogError> {
+        Ok(match self.state_file(".rotations.json") {
+            None => Arc::new(InMemoryKeyRotationLog::default()),
+            Some(log_path) => Arc::new(FileKeyRotationLog::open(log_path)?),
+        })
+    }
+
+    /// 鍵の保存先に合わせてプロフィールの保存先を作る。
+    ///
+    /// ファイルに保存する場合は、鍵ファイルの隣の `{鍵ファイル名}.profiles.json` に保存する。
+    /// プロフィールは公開情報のため暗号化しない。
+    pub fn build_profile_repository(&self) -> Result<DynProfileRepository, ProfileRepositoryError> {
+        Ok(match self.state_file(".profiles.json") {
+            None => Arc::new(InMemoryProfileRepository::default()),
+            Some(profiles_path) => Arc::new(FileProfileRepository::open(profiles_path)?),
+        })
+    }
+
+    /// 鍵の保存先に合わせて失効リストの保存先を作る。
+    ///
+    /// ファイルに保存する場合は、鍵ファイルの隣の `{鍵ファイル名}.revocations.json` に保存する。
+    pub fn build_revocation_list(&self) -> Result<DynKeyRevocationList, KeyRevocationListError> {
+        Ok(match self.state_file(".revocations.json") {
+            None => Arc::new(InMemoryKeyRevocationList::default()),
+            Some(list_path) => Arc::new(FileKeyRevocationList::open(list_path)?),
+        })
+    }
+
+    /// 鍵の保存先に合わせて、秘密鍵を使う操作の監査記録の保存先を作る。
+    ///
+    /// ファイルに保存する場合は、鍵ファイルの隣の `{鍵ファイル名}.audit.jsonl` に追記する。
+    pub fn build_audit_log(&self) -> Result<DynKeyAuditLog, KeyAuditLogError> {
+        Ok(match self.state_file(".audit.jsonl") {
+            None => Arc::new(InMemoryKeyAuditLog::default()),
+            Some(log_path) => Arc::new(FileKeyAuditLog::open(log_path)?),
+        })
+    }
+}
 
 /// プロセス内の `AccountKeyMaterial` を保存するインメモリ実装。
 ///
@@ -43,6 +259,53 @@ impl AccountKeyStore for InMemoryAccountKeyStore {
     }
 }
 
+/// 秘密鍵を外部の署名器に置くアカウント鍵ストア。
+///
+/// 公開鍵だけを返し、署名は [`RemoteSigner`] を通して署名器に依頼する。鍵の生成と削除は
+/// 署名器の側で行うため、`save` と `delete` はエラーを返す。
+pub struct RemoteAccountKeyStore {
+    signer: RemoteSigner,
+}
+
+impl RemoteAccountKeyStore {
+    pub fn new(signer: RemoteSigner) -> Self {
+        Self { signer }
+    }
+}
+
+impl AccountKeyStore for RemoteAccountKeyStore {
+    fn save(&self, _key: &StoredAccountKey) -> Result<(), AccountKeyStoreError> {
+        Err(AccountKeyStoreError::Storage(
+            "account key is held by the remote signer and cannot be replaced".to_string(),
+        ))
+    }
+
+    fn load(&self) -> Result<Option<StoredAccountKey>, AccountKeyStoreError> {
+        Ok(Some(StoredAccountKey {
+            algorithm: self.signer.algorithm(),
+            public_key: self.signer.public_key_bytes().to_vec(),
+            secret_key: Vec::new(),
+        }))
+    }
+
+    fn delete(&self) -> Result<(), AccountKeyStoreError> {
+        Err(AccountKeyStoreError::Storage(
+            "account key is held by the remote signer and cannot be deleted".to_string(),
+        ))
+    }
+
+    fn key_pair(&self, key: &StoredAccountKey) -> Result<Box<dyn AccountKeyPair>, KeyPairError> {
+        if key.algorithm != self.signer.algorithm()
+            || key.public_key != self.signer.public_key_bytes()
+        {
+            return Err(KeyPairError::InvalidPublicKey(
+                "key does not belong to the remote signer".to_string(),
+            ));
+        }
+        Ok(Box::new(self.signer.clone()))
+    }
+}
+
 /// sled を用いたアカウント鍵ストア実装。
 ///
 /// - キー: 固定文字列 `"account:signing_key"`（UTF-8 文字列）
@@ -64,8 +327,6 @@ impl SledAccountKeyStore {
 
 impl AccountKeyStore for SledAccountKeyStore {
     fn save(&self, key: &StoredAccountKey) -> Result<(), AccountKeyStoreError> {
-        use crate::infrastructure::key_pair::KeyAlgorithm;
-
         let alg_tag = match key.algorithm {
             KeyAlgorithm::K256 => 1u8,
             KeyAlgorithm::P256 => 2u8,
@@ -90,8 +351,6 @@ impl AccountKeyStore for SledAccountKeyStore {
     }
 
     fn load(&self) -> Result<Option<StoredAccountKey>, AccountKeyStoreError> {
-        use crate::infrastructure::key_pair::KeyAlgorithm;
-
         let opt = self
             .db
             .get(Self::sled_key())
@@ -138,6 +397,13 @@ impl AccountKeyStore for SledAccountKeyStore {
             .map_err(|e| AccountKeyStoreError::Storage(e.to_string()))?;
         Ok(())
     }
+
+    fn flush(&self) -> Result<(), AccountKeyStoreError> {
+        self.db
+            .flush()
+            .map_err(|e| AccountKeyStoreError::Storage(e.to_string()))?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -146,6 +412,75 @@ mod tests {
     use crate::application_service::StoredAccountKey;
     use crate::infrastructure::key_pair::KeyAlgorithm;
 
+    #[test]
+    fn config_from_lookup_selects_file_store_and_requires_passphrase() {
+        assert_eq!(
+            AccountKeyStoreConfig::from_lookup(|_| None).unwrap(),
+            AccountKeyStoreConfig::InMemory
+        );
+
+        let config = AccountKeyStoreConfig::from_lookup(|key| match key {
+            "MONAS_ACCOUNT_KEYSTORE_PATH" => Some("/var/lib/monas/account.key".into()),
+            "MONAS_ACCOUNT_KEYSTORE_PASSPHRASE" => Some("secret".into()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(
+            config,
+            AccountKeyStoreConfig::File {
+                path: PathBuf::from("/var/lib/monas/account.key"),
+                passphrase: "secret".into(),
+            }
+        );
+        assert!(!format!("{config:?}").contains("secret"));
+
+        assert!(AccountKeyStoreConfig::from_lookup(|key| {
+            (key == "MONAS_ACCOUNT_KEYSTORE_PATH").then(|| "account.key".to_string())
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn config_from_lookup_selects_remote_signer() {
+        let public_key =
+            crate::infrastructure::key_pair::KeyPairGenerateFactory::generate(KeyAlgorithm::P256)
+                .public_key_bytes()
+                .to_vec();
+        let encoded = BASE64_STANDARD.encode(&public_key);
+        let config = AccountKeyStoreConfig::from_lookup(|key| match key {
+            "MONAS_ACCOUNT_REMOTE_SIGNER_URL" => Some("https://kms.example/sign".into()),
+            "MONAS_ACCOUNT_REMOTE_SIGNER_PUBLIC_KEY" => Some(encoded.clone()),
+            "MONAS_ACCOUNT_REMOTE_SIGNER_TOKEN" => Some("kms-token".into()),
+            "MONAS_ACCOUNT_KEYSTORE_PATH" => Some("/var/lib/monas/account".into()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(
+            config,
+            AccountKeyStoreConfig::Remote {
+                url: "https://kms.example/sign".into(),
+                token: Some("kms-token".into()),
+                algorithm: KeyAlgorithm::P256,
+                public_key: public_key.clone(),
+                state_path: Some(PathBuf::from("/var/lib/monas/account")),
+            }
+        );
+        assert!(!format!("{config:?}").contains("kms-token"));
+
+        // 鍵は KMS にあるため、公開鍵だけを返し、置き換えや削除はできない
+        let store = config.build().unwrap();
+        let loaded = store.load().unwrap().expect("should exist");
+        assert_eq!(loaded.public_key, public_key);
+        assert!(loaded.secret_key.is_empty());
+        assert!(store.save(&loaded).is_err());
+        assert!(store.delete().is_err());
+
+        assert!(AccountKeyStoreConfig::from_lookup(|key| {
+            (key == "MONAS_ACCOUNT_REMOTE_SIGNER_URL").then(|| "https://kms.example".to_string())
+        })
+        .is_err());
+    }
+
     #[test]
     fn in_memory_store_save_load_delete() {
         let store = InMemoryAccountKeyStore::default();
@@ -194,4 +529,25 @@ mod tests {
         store.delete().unwrap();
         assert!(store.load().unwrap().is_none());
     }
+
+    #[test]
+    fn sled_store_survives_flush_and_reopen() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("account_db");
+
+        let stored = StoredAccountKey {
+            algorithm: KeyAlgorithm::K256,
+            public_key: vec![0; 65],
+            secret_key: vec![3; 32],
+        };
+        {
+            let store = SledAccountKeyStore::open(&path).expect("open sled");
+            store.save(&stored).unwrap();
+            store.flush().unwrap();
+        }
+
+        let store = SledAccountKeyStore::open(&path).expect("reopen sled");
+        let loaded = store.load().unwrap().expect("should exist");
+        assert_eq!(loaded.secret_key, stored.secret_key);
+    }
 }
@@ -0,0 +1,461 @@
+//! パスフレーズで保護した持ち運び用の鍵ファイル（Ethereum の keystore v3 に準じた形式）。
+//!
+//! 鍵ストア（[`FileAccountKeyStore`](super::file_key_store::FileAccountKeyStore)）の内部形式とは
+//! 別に、バックアップや別の端末への移動のための単体のファイル形式を定める。
+//! 暗号部分は keystore v3 と同じ構成で、アカウント固有の情報（アルゴリズムと識別子）を
+//! 最上位に加える。
+//!
+//! ```json
+//! {
+//!   "version": 3,
+//!   "id": "3198bc9c-6672-4ab3-a995-4a25a7c1c0d5",
+//!   "algorithm": "K256",
+//!   "account_id": "did:key:z...",
+//!   "crypto": {
+//!     "cipher": "aes-128-ctr",
+//!     "cipherparams": { "iv": "<hex>" },
+//!     "ciphertext": "<hex>",
+//!     "kdf": "scrypt",
+//!     "kdfparams": { "dklen": 32, "n": 32768, "r": 8, "p": 1, "salt": "<hex>" },
+//!     "mac": "<hex>"
+//!   }
+//! }
+//! ```
+//!
+//! - scrypt で導出した 32 バイトの前半 16 バイトで秘密鍵を AES-128-CTR で暗号化する。
+//! - `mac` は導出鍵の後半 16 バイトと暗号文を連結した Keccak-256。パスフレーズの誤りと
+//!   改ざんはどちらも MAC の不一致として検出する。
+//! - 読み込み時は秘密鍵から公開鍵を計算し直し、`account_id` と一致することを確かめる。
+//! - 読み込むファイルの scrypt パラメータには上限を設ける。外部から渡されたファイルで
+//!   メモリや CPU を使い果たさせないため。
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use p256::elliptic_curve::rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+use crate::application_service::StoredAccountKey;
+use crate::infrastructure::did_key::did_key_from_public_key;
+use crate::infrastructure::file_key_store::{create_private_file, ScryptParams};
+use crate::infrastructure::key_pair::{KeyAlgorithm, KeyPairGenerateFactory};
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// 対応する形式の版。
+pub const KEYSTORE_VERSION: u32 = 3;
+const CIPHER: &str = "aes-128-ctr";
+const KDF: &str = "scrypt";
+const DKLEN: usize = 32;
+const SALT_LEN: usize = 32;
+const IV_LEN: usize = 16;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreFileError {
+    #[error("unsupported keystore version: {0}")]
+    UnsupportedVersion(u32),
+    #[error("unsupported {field}: {value}")]
+    Unsupported { field: &'static str, value: String },
+    /// パスフレーズが誤っているか、ファイルが改ざんされている。
+    #[error("keystore MAC mismatch (wrong passphrase or corrupted file)")]
+    InvalidMac,
+    #[error("invalid keystore: {0}")]
+    Invalid(String),
+    #[error("keystore I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// 鍵ファイルの内容。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreFile {
+    pub version: u32,
+    pub id: String,
+    pub algorithm: String,
+    pub account_id: String,
+    pub crypto: KeystoreCrypto,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdfparams: KdfParams,
+    pub mac: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub dklen: usize,
+    pub n: u64,
+    pub r: u32,
+    pub p: u32,
+    pub salt: String,
+}
+
+impl KeystoreFile {
+    /// `key` をパスフレーズで暗号化する。
+    pub fn encrypt(
+        key: &StoredAccountKey,
+        passphrase: &str,
+        params: ScryptParams,
+    ) -> Result<Self, KeystoreFileError> {
+        if passphrase.is_empty() {
+            return Err(KeystoreFileError::Invalid(
+                "passphrase must not be empty".to_string(),
+            ));
+        }
+        let account_id = did_key_from_public_key(key.algorithm, &key.public_key)
+            .map_err(|e| KeystoreFileError::Invalid(e.to_string()))?;
+
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let mut iv = [0u8; IV_LEN];
+        OsRng.fill_bytes(&mut iv);
+        let derived = derive_key(passphrase, &salt, params.log_n, params.r, params.p)?;
+
+        let mut ciphertext = key.secret_key.clone();
+        Aes128Ctr::new(derived[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+        let mac = mac(&derived, &ciphertext);
+
+        let mut id = [0u8; 16];
+        OsRng.fill_bytes(&mut id);
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            id: uuid_v4(id),
+            algorithm: algorithm_name(key.algorithm).to_string(),
+            account_id,
+            crypto: KeystoreCrypto {
+                cipher: CIPHER.to_string(),
+                cipherparams: CipherParams { iv: to_hex(&iv) },
+                ciphertext: to_hex(&ciphertext),
+                kdf: KDF.to_string(),
+                kdfparams: KdfParams {
+                    dklen: DKLEN,
+                    n: 1u64 << params.log_n,
+                    r: params.r,
+                    p: params.p,
+                    salt: to_hex(&salt),
+                },
+                mac: to_hex(&mac),
+            },
+        })
+    }
+
+    /// パスフレーズで復号し、鍵を取り出す。
+    pub fn decrypt(&self, passphrase: &str) -> Result<StoredAccountKey, KeystoreFileError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreFileError::UnsupportedVersion(self.version));
+        }
+        let crypto = &self.crypto;
+        if crypto.cipher != CIPHER {
+            return Err(KeystoreFileError::Unsupported {
+                field: "cipher",
+                value: crypto.cipher.clone(),
+            });
+        }
+        if crypto.kdf != KDF {
+            return Err(KeystoreFileError::Unsupported {
+                field: "kdf",
+                value: crypto.kdf.clone(),
+            });
+        }
+        let algorithm = match self.algorithm.as_str() {
+            "K256" => KeyAlgorithm::K256,
+            "P256" => KeyAlgorithm::P256,
+            other => {
+                return Err(KeystoreFileError::Unsupported {
+                    field: "algorithm",
+                    value: other.to_string(),
+                })
+            }
+        };
+
+        let kdf = &crypto.kdfparams;
+        if kdf.dklen != DKLEN {
+            return Err(KeystoreFileError::Invalid(format!(
+                "kdfparams.dklen must be {DKLEN}"
+            )));
+        }
+        if !kdf.n.is_power_of_two() || kdf.n < 2 {
+            return Err(KeystoreFileError::Invalid(
+                "kdfparams.n must be a power of two".to_string(),
+            ));
+        }
+        let log_n = kdf.n.trailing_zeros() as u8;
+        // 導出の前に検査し、細工したファイルで過大な資源を使わせない
+        ScryptParams {
+            log_n,
+            r: kdf.r,
+            p: kdf.p,
+        }
+        .check_bounds()
+        .map_err(|e| KeystoreFileError::Invalid(format!("kdfparams: {e}")))?;
+        let salt = from_hex("kdfparams.salt", &kdf.salt)?;
+        let iv = from_hex("cipherparams.iv", &crypto.cipherparams.iv)?;
+        let iv: [u8; IV_LEN] = iv
+            .try_into()
+            .map_err(|_| KeystoreFileError::Invalid(format!("iv must be {IV_LEN} bytes")))?;
+        let ciphertext = from_hex("ciphertext", &crypto.ciphertext)?;
+        let expected_mac = from_hex("mac", &crypto.mac)?;
+
+        let derived = derive_key(passphrase, &salt, log_n, kdf.r, kdf.p)?;
+        if !bool::from(mac(&derived, &ciphertext).as_slice().ct_eq(&expected_mac)) {
+            return Err(KeystoreFileError::InvalidMac);
+        }
+
+        let mut secret_key = ciphertext;
+        Aes128Ctr::new(derived[..16].into(), &iv.into()).apply_keystream(&mut secret_key);
+        let key_pair = KeyPairGenerateFactory::from_secret_key_bytes(algorithm, &secret_key)
+            .map_err(|e| KeystoreFileError::Invalid(e.to_string()))?;
+        let public_key = key_pair.public_key_bytes().to_vec();
+        let account_id = did_key_from_public_key(algorithm, &public_key)
+            .map_err(|e| KeystoreFileError::Invalid(e.to_string()))?;
+        if account_id != self.account_id {
+            return Err(KeystoreFileError::Invalid(
+                "account_id does not match the decrypted key".to_string(),
+            ));
+        }
+
+        Ok(StoredAccountKey {
+            algorithm,
+            public_key,
+            secret_key,
+        })
+    }
+
+    /// JSON 文字列から読み込む。版が異なる場合は `UnsupportedVersion`。
+    pub fn from_json(json: &str) -> Result<Self, KeystoreFileError> {
+        // 版だけ先に読み、将来の形式を「壊れたファイル」と区別する
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: u32,
+        }
+        let versioned: Versioned = serde_json::from_str(json)
+            .map_err(|e| KeystoreFileError::Invalid(format!("malformed keystore: {e}")))?;
+        if versioned.version != KEYSTORE_VERSION {
+            return Err(KeystoreFileError::UnsupportedVersion(versioned.version));
+        }
+        serde_json::from_str(json)
+            .map_err(|e| KeystoreFileError::Invalid(format!("malformed keystore: {e}")))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("keystore serializes to JSON")
+    }
+}
+
+/// `key` を暗号化して `path` に書き出す。既存のファイルは置き換える。
+pub fn save_keystore<P: AsRef<Path>>(
+    path: P,
+    key: &StoredAccountKey,
+    passphrase: &str,
+    params: ScryptParams,
+) -> Result<KeystoreFile, KeystoreFileError> {
+    let file = KeystoreFile::encrypt(key, passphrase, params)?;
+    let path = path.as_ref();
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp_name = path.as_os_str().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+    let mut tmp =
+        create_private_file(&tmp_path).map_err(|e| KeystoreFileError::Invalid(e.to_string()))?;
+    tmp.write_all(file.to_json().as_bytes())?;
+    tmp.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    Ok(file)
+}
+
+/// `path` の鍵ファイルをパスフレーズで復号する。
+pub fn load_keystore<P: AsRef<Path>>(
+    path: P,
+    passphrase: &str,
+) -> Result<StoredAccountKey, KeystoreFileError> {
+    KeystoreFile::from_json(&fs::read_to_string(path)?)?.decrypt(passphrase)
+}
+
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    log_n: u8,
+    r: u32,
+    p: u32,
+) -> Result<Zeroizing<[u8; DKLEN]>, KeystoreFileError> {
+    let params = scrypt::Params::new(log_n, r, p, DKLEN)
+        .map_err(|e| KeystoreFileError::Invalid(format!("scrypt params: {e}")))?;
+    let mut derived = Zeroizing::new([0u8; DKLEN]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, derived.as_mut())
+        .map_err(|e| KeystoreFileError::Invalid(format!("scrypt: {e}")))?;
+    Ok(derived)
+}
+
+fn mac(derived: &[u8; DKLEN], ciphertext: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(&derived[16..]);
+    hasher.update(ciphertext);
+    hasher.finalize().into()
+}
+
+fn algorithm_name(algorithm: KeyAlgorithm) -> &'static str {
+    match algorithm {
+        KeyAlgorithm::K256 => "K256",
+        KeyAlgorithm::P256 => "P256",
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(name: &str, value: &str) -> Result<Vec<u8>, KeystoreFileError> {
+    let invalid = || KeystoreFileError::Invalid(format!("invalid hex in {name}"));
+    value
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .filter(|pair| pair.len() == 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(invalid)
+        })
+        .collect()
+}
+
+/// 乱数 16 バイトから UUID v4 の文字列を作る。
+fn uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = to_hex(&bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テストを速くするための軽いパラメータ。
+    fn fast_params() -> ScryptParams {
+        ScryptParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        }
+    }
+
+    fn stored_key(algorithm: KeyAlgorithm) -> StoredAccountKey {
+        let key_pair = KeyPairGenerateFactory::generate(algorithm);
+        StoredAccountKey {
+            algorithm,
+            public_key: key_pair.public_key_bytes().to_vec(),
+            secret_key: key_pair.secret_key_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn keystore_round_trips_through_a_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for algorithm in [KeyAlgorithm::K256, KeyAlgorithm::P256] {
+            let path = dir.path().join(format!("{algorithm:?}.json"));
+            let key = stored_key(algorithm);
+
+            let saved = save_keystore(&path, &key, "correct horse", fast_params()).unwrap();
+            assert_eq!(saved.version, KEYSTORE_VERSION);
+            assert_eq!(saved.crypto.kdfparams.n, 16);
+            assert_eq!(saved.id.len(), 36);
+
+            let loaded = load_keystore(&path, "correct horse").unwrap();
+            assert_eq!(loaded.algorithm, key.algorithm);
+            assert_eq!(loaded.public_key, key.public_key);
+            assert_eq!(loaded.secret_key, key.secret_key);
+
+            assert!(matches!(
+                load_keystore(&path, "wrong"),
+                Err(KeystoreFileError::InvalidMac)
+            ));
+        }
+    }
+
+    #[test]
+    fn tampered_or_unsupported_files_are_rejected() {
+        let key = stored_key(KeyAlgorithm::K256);
+        let file = KeystoreFile::encrypt(&key, "pass", fast_params()).unwrap();
+
+        let mut tampered = file.clone();
+        let mut ciphertext = from_hex("ciphertext", &tampered.crypto.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        tampered.crypto.ciphertext = to_hex(&ciphertext);
+        assert!(matches!(
+            tampered.decrypt("pass"),
+            Err(KeystoreFileError::InvalidMac)
+        ));
+
+        let mut other_account = file.clone();
+        other_account.account_id = "did:key:zOther".to_string();
+        assert!(matches!(
+            other_account.decrypt("pass"),
+            Err(KeystoreFileError::Invalid(_))
+        ));
+
+        let mut pbkdf2 = file.clone();
+        pbkdf2.crypto.kdf = "pbkdf2".to_string();
+        assert!(matches!(
+            pbkdf2.decrypt("pass"),
+            Err(KeystoreFileError::Unsupported { field: "kdf", .. })
+        ));
+
+        let future = file
+            .to_json()
+            .replacen("\"version\": 3", "\"version\": 4", 1);
+        assert!(matches!(
+            KeystoreFile::from_json(&future),
+            Err(KeystoreFileError::UnsupportedVersion(4))
+        ));
+        assert_eq!(KeystoreFile::from_json(&file.to_json()).unwrap(), file);
+    }
+
+    #[test]
+    fn excessive_kdf_params_are_rejected_before_deriving() {
+        let key = stored_key(KeyAlgorithm::P256);
+        let file = KeystoreFile::encrypt(&key, "pass", fast_params()).unwrap();
+
+        for (n, r, p) in [
+            (1u64 << 40, 8, 1),
+            (1 << 18, 16, 1),
+            (16, 8, 64),
+            (16, 0, 1),
+        ] {
+            let mut expensive = file.clone();
+            expensive.crypto.kdfparams.n = n;
+            expensive.crypto.kdfparams.r = r;
+            expensive.crypto.kdfparams.p = p;
+            assert!(
+                matches!(
+                    expensive.decrypt("pass"),
+                    Err(KeystoreFileError::Invalid(_))
+                ),
+                "n={n} r={r} p={p}"
+            );
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! BIP-39 ニーモニックによる秘密鍵のバックアップ。
+//!
+//! 32 バイトの秘密鍵をそのまま BIP-39 のエントロピー（256 ビット）として扱い、
+//! 英語の単語リストで 24 単語に変換する。シードへの変換（PBKDF2）は行わないため、
+//! 同じ 24 単語からは常に同じ秘密鍵が得られる。
+
+use bip39::{Language, Mnemonic};
+
+/// バックアップに使うニーモニックの単語数。
+pub const MNEMONIC_WORD_COUNT: usize = 24;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MnemonicError {
+    #[error("secret key must be 32 bytes, got {0}")]
+    InvalidSecretKeyLength(usize),
+    #[error("mnemonic must have {MNEMONIC_WORD_COUNT} words, got {0}")]
+    InvalidWordCount(usize),
+    #[error("invalid mnemonic: {0}")]
+    Invalid(String),
+}
+
+/// 秘密鍵を 24 単語のニーモニック（単語を空白 1 つで区切った文字列）に変換する。
+pub fn secret_key_to_mnemonic(secret_key: &[u8]) -> Result<String, MnemonicError> {
+    if secret_key.len() != 32 {
+        return Err(MnemonicError::InvalidSecretKeyLength(secret_key.len()));
+    }
+    Mnemonic::from_entropy_in(Language::English, secret_key)
+        .map(|mnemonic| mnemonic.to_string())
+        .map_err(|e| MnemonicError::Invalid(e.to_string()))
+}
+
+/// ニーモニックから秘密鍵を復元する。
+///
+/// 大文字・小文字と単語間の空白の違いは無視する。チェックサムが一致しない場合はエラー。
+pub fn mnemonic_to_secret_key(phrase: &str) -> Result<Vec<u8>, MnemonicError> {
+    let words: Vec<String> = phrase
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect();
+    if words.len() != MNEMONIC_WORD_COUNT {
+        return Err(MnemonicError::InvalidWordCount(words.len()));
+    }
+    let mnemonic = Mnemonic::parse_in_normalized(Language::English, &words.join(" "))
+        .map_err(|e| MnemonicError::Invalid(e.to_string()))?;
+    Ok(mnemonic.to_entropy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_secret_key_through_24_words() {
+        let secret_key: Vec<u8> = (0u8..32).collect();
+        let phrase = secret_key_to_mnemonic(&secret_key).unwrap();
+        assert_eq!(phrase.split(' ').count(), MNEMONIC_WORD_COUNT);
+
+        let noisy = format!("  {}  ", phrase.to_uppercase().replace(' ', "\n "));
+        assert_eq!(mnemonic_to_secret_key(&noisy).unwrap(), secret_key);
+    }
+
+    #[test]
+    fn rejects_wrong_length_and_bad_checksum() {
+        assert!(matches!(
+            secret_key_to_mnemonic(&[0u8; 16]),
+            Err(MnemonicError::InvalidSecretKeyLength(16))
+        ));
+        assert!(matches!(
+            mnemonic_to_secret_key("abandon abandon"),
+            Err(MnemonicError::InvalidWordCount(2))
+        ));
+
+        // 最後の単語（チェックサムを含む）を入れ替えると検証に失敗する
+        let phrase = secret_key_to_mnemonic(&[7u8; 32]).unwrap();
+        let mut words: Vec<&str> = phrase.split(' ').collect();
+        let last = if words[23] == "zoo" { "abandon" } else { "zoo" };
+        words[23] = last;
+        assert!(matches!(
+            mnemonic_to_secret_key(&words.join(" ")),
+            Err(MnemonicError::Invalid(_))
+        ));
+    }
+}
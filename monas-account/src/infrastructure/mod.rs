@@ -1,4 +1,20 @@
+pub mod auth_challenge_store;
+pub mod did_key;
+mod durable;
+pub mod event_bus_publisher;
+pub mod file_key_store;
 pub mod jwt_signer;
+pub mod key_audit_log;
+pub mod key_derivation;
+pub mod key_format;
+pub mod key_operation_limiter;
 pub mod key_pair;
+pub mod key_rotation_log;
 pub mod key_store;
+pub mod keystore_file;
+pub mod mnemonic;
+pub mod profile_repository;
 pub mod public_key_repository;
+pub mod revocation_list;
+pub mod secret_sharing;
+pub mod session_token;
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use super::durable::sync_file_and_parent;
+use crate::application_service::{ProfileRepository, ProfileRepositoryError};
+use crate::domain::profile::{AccountDevice, AccountProfile};
+
+/// 実行時に保存先を切り替えるプロフィールリポジトリの動的型。
+pub type DynProfileRepository = Arc<dyn ProfileRepository + Send + Sync>;
+
+/// プロフィールをプロセス内に保持するインメモリ実装。
+#[derive(Clone, Default)]
+pub struct InMemoryProfileRepository {
+    inner: Arc<Mutex<HashMap<String, AccountProfile>>>,
+}
+
+impl ProfileRepository for InMemoryProfileRepository {
+    fn save(&self, profile: &AccountProfile) -> Result<(), ProfileRepositoryError> {
+        self.inner
+            .lock()
+            .map_err(|e| ProfileRepositoryError::Storage(e.to_string()))?
+            .insert(profile.account_id.clone(), profile.clone());
+        Ok(())
+    }
+
+    fn load(&self, account_id: &str) -> Result<Option<AccountProfile>, ProfileRepositoryError> {
+        Ok(self
+            .inner
+            .lock()
+            .map_err(|e| ProfileRepositoryError::Storage(e.to_string()))?
+            .get(account_id)
+            .cloned())
+    }
+}
+
+/// プロフィールを JSON ファイルに保存する実装。
+///
+/// プロフィールは公開情報のため暗号化しない。保存のたびにファイル全体を一時ファイル経由で書き換える。
+#[derive(Clone)]
+pub struct FileProfileRepository {
+    path: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredProfile {
+    account_id: String,
+    display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    avatar_content_id: Option<String>,
+    devices: Vec<AccountDevice>,
+    updated_at: u64,
+    signature_base64: String,
+}
+
+impl FileProfileRepository {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ProfileRepositoryError> {
+        let repository = Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Arc::new(Mutex::new(())),
+        };
+        // 壊れたファイルは起動時に検出する
+        repository.read()?;
+        Ok(repository)
+    }
+
+    fn read(&self) -> Result<Vec<AccountProfile>, ProfileRepositoryError> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(ProfileRepositoryError::Storage(e.to_string())),
+        };
+        let stored: Vec<StoredProfile> = serde_json::from_slice(&bytes)
+            .map_err(|e| ProfileRepositoryError::InvalidData(e.to_string()))?;
+        stored.into_iter().map(from_stored).collect()
+    }
+
+    fn write(&self, profiles: &[AccountProfile]) -> Result<(), ProfileRepositoryError> {
+        let stored: Vec<StoredProfile> = profiles.iter().map(to_stored).collect();
+        let json = serde_json::to_vec_pretty(&stored)
+            .map_err(|e| ProfileRepositoryError::Storage(e.to_string()))?;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .map_err(|e| ProfileRepositoryError::Storage(e.to_string()))?;
+        }
+
+        let mut tmp_name = self.path.clone().into_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
+        fs::write(&tmp_path, json).map_err(|e| ProfileRepositoryError::Storage(e.to_string()))?;
+        fs::rename(&tmp_path, &self.path)
+            .map_err(|e| ProfileRepositoryError::Storage(e.to_string()))
+    }
+}
+
+impl ProfileRepository for FileProfileRepository {
+    fn save(&self, profile: &AccountProfile) -> Result<(), ProfileRepositoryError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| ProfileRepositoryError::Storage(e.to_string()))?;
+        let mut profiles = self.read()?;
+        profiles.retain(|p| p.account_id != profile.account_id);
+        profiles.push(profile.clone());
+        self.write(&profiles)
+    }
+
+    fn load(&self, account_id: &str) -> Result<Option<AccountProfile>, ProfileRepositoryError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| ProfileRepositoryError::Storage(e.to_string()))?;
+        Ok(self
+            .read()?
+            .into_iter()
+            .find(|p| p.account_id == account_id))
+    }
+
+    fn flush(&self) -> Result<(), ProfileRepositoryError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| ProfileRepositoryError::Storage(e.to_string()))?;
+        sync_file_and_parent(&self.path).map_err(|e| ProfileRepositoryError::Storage(e.to_string()))
+    }
+}
+
+fn to_stored(profile: &AccountProfile) -> StoredProfile {
+    StoredProfile {
+        account_id: profile.account_id.clone(),
+        display_name: profile.display_name.clone(),
+        avatar_content_id: profile.avatar_content_id.clone(),
+        devices: profile.devices.clone(),
+        updated_at: profile.updated_at,
+        signature_base64: BASE64_STANDARD.encode(&profile.signature),
+    }
+}
+
+fn from_stored(stored: StoredProfile) -> Result<AccountProfile, ProfileRepositoryError> {
+    Ok(AccountProfile {
+        account_id: stored.account_id,
+        display_name: stored.display_name,
+        avatar_content_id: stored.avatar_content_id,
+        devices: stored.devices,
+        updated_at: stored.updated_at,
+        signature: BASE64_STANDARD
+            .decode(&stored.signature_base64)
+            .map_err(|e| ProfileRepositoryError::InvalidData(format!("invalid signature: {e}")))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_repository_survives_reopen() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("profiles.json");
+        let profile = AccountProfile {
+            account_id: "did:key:zTest".into(),
+            display_name: "Alice".into(),
+            avatar_content_id: Some("cid-avatar".into()),
+            devices: vec![AccountDevice {
+                device_id: "laptop".into(),
+                name: "Laptop".into(),
+            }],
+            updated_at: 1_700_000_000,
+            signature: vec![7; 64],
+        };
+        {
+            let repository = FileProfileRepository::open(&path).expect("open file");
+            assert!(repository.load(&profile.account_id).unwrap().is_none());
+            repository.save(&profile).unwrap();
+        }
+
+        let repository = FileProfileRepository::open(&path).expect("reopen file");
+        assert_eq!(repository.load(&profile.account_id).unwrap(), Some(profile));
+        assert!(repository.load("did:key:zOther").unwrap().is_none());
+    }
+}
@@ -0,0 +1,258 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use super::durable::sync_file_and_parent;
+use crate::application_service::{KeyRevocationList, KeyRevocationListError};
+use crate::domain::revocation::KeyRevocationRecord;
+use crate::infrastructure::key_pair::KeyAlgorithm;
+
+/// 実行時に保存先を切り替える失効リストの動的型。
+pub type DynKeyRevocationList = Arc<dyn KeyRevocationList + Send + Sync>;
+
+/// 失効記録をプロセス内に保持するインメモリ実装。
+#[derive(Clone, Default)]
+pub struct InMemoryKeyRevocationList {
+    inner: Arc<Mutex<Vec<KeyRevocationRecord>>>,
+}
+
+impl KeyRevocationList for InMemoryKeyRevocationList {
+    fn add(&self, record: &KeyRevocationRecord) -> Result<(), KeyRevocationListError> {
+        let mut records = self
+            .inner
+            .lock()
+            .map_err(|e| KeyRevocationListError::Storage(e.to_string()))?;
+        upsert(&mut records, record);
+        Ok(())
+    }
+
+    fn find(
+        &self,
+        account_id: &str,
+    ) -> Result<Option<KeyRevocationRecord>, KeyRevocationListError> {
+        Ok(self
+            .inner
+            .lock()
+            .map_err(|e| KeyRevocationListError::Storage(e.to_string()))?
+            .iter()
+            .find(|r| r.account_id == account_id)
+            .cloned())
+    }
+
+    fn list(&self) -> Result<Vec<KeyRevocationRecord>, KeyRevocationListError> {
+        Ok(self
+            .inner
+            .lock()
+            .map_err(|e| KeyRevocationListError::Storage(e.to_string()))?
+            .clone())
+    }
+}
+
+/// 失効記録を JSON ファイルに保存する実装。
+///
+/// 記録は公開情報のため暗号化しない。追加のたびにファイル全体を一時ファイル経由で書き換える。
+#[derive(Clone)]
+pub struct FileKeyRevocationList {
+    path: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredRevocationRecord {
+    account_id: String,
+    algorithm: String,
+    public_key_base64: String,
+    revoked_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    signature_base64: String,
+}
+
+impl FileKeyRevocationList {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, KeyRevocationListError> {
+        let list = Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Arc::new(Mutex::new(())),
+        };
+        // 壊れたファイルは起動時に検出する
+        list.read()?;
+        Ok(list)
+    }
+
+    fn read(&self) -> Result<Vec<KeyRevocationRecord>, KeyRevocationListError> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(KeyRevocationListError::Storage(e.to_string())),
+        };
+        let stored: Vec<StoredRevocationRecord> = serde_json::from_slice(&bytes)
+            .map_err(|e| KeyRevocationListError::InvalidRecord(e.to_string()))?;
+        stored.into_iter().map(from_stored).collect()
+    }
+
+    fn write(&self, records: &[KeyRevocationRecord]) -> Result<(), KeyRevocationListError> {
+        let stored: Vec<StoredRevocationRecord> = records.iter().map(to_stored).collect();
+        let json = serde_json::to_vec_pretty(&stored)
+            .map_err(|e| KeyRevocationListError::Storage(e.to_string()))?;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .map_err(|e| KeyRevocationListError::Storage(e.to_string()))?;
+        }
+
+        let mut tmp_name = self.path.clone().into_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
+        fs::write(&tmp_path, json).map_err(|e| KeyRevocationListError::Storage(e.to_string()))?;
+        fs::rename(&tmp_path, &self.path)
+            .map_err(|e| KeyRevocationListError::Storage(e.to_string()))
+    }
+}
+
+impl KeyRevocationList for FileKeyRevocationList {
+    fn add(&self, record: &KeyRevocationRecord) -> Result<(), KeyRevocationListError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| KeyRevocationListError::Storage(e.to_string()))?;
+        let mut records = self.read()?;
+        upsert(&mut records, record);
+        self.write(&records)
+    }
+
+    fn find(
+        &self,
+        account_id: &str,
+    ) -> Result<Option<KeyRevocationRecord>, KeyRevocationListError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| KeyRevocationListError::Storage(e.to_string()))?;
+        Ok(self
+            .read()?
+            .into_iter()
+            .find(|r| r.account_id == account_id))
+    }
+
+    fn list(&self) -> Result<Vec<KeyRevocationRecord>, KeyRevocationListError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| KeyRevocationListError::Storage(e.to_string()))?;
+        self.read()
+    }
+
+    fn flush(&self) -> Result<(), KeyRevocationListError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| KeyRevocationListError::Storage(e.to_string()))?;
+        sync_file_and_parent(&self.path).map_err(|e| KeyRevocationListError::Storage(e.to_string()))
+    }
+}
+
+/// 同じアカウントの記録は最初の失効を残す（失効は取り消せない）。
+fn upsert(records: &mut Vec<KeyRevocationRecord>, record: &KeyRevocationRecord) {
+    if !records.iter().any(|r| r.account_id == record.account_id) {
+        records.push(record.clone());
+    }
+}
+
+fn algorithm_name(algorithm: KeyAlgorithm) -> String {
+    match algorithm {
+        KeyAlgorithm::K256 => "K256",
+        KeyAlgorithm::P256 => "P256",
+    }
+    .to_string()
+}
+
+fn parse_algorithm(name: &str) -> Result<KeyAlgorithm, KeyRevocationListError> {
+    match name {
+        "K256" => Ok(KeyAlgorithm::K256),
+        "P256" => Ok(KeyAlgorithm::P256),
+        other => Err(KeyRevocationListError::InvalidRecord(format!(
+            "unknown algorithm: {other}"
+        ))),
+    }
+}
+
+fn decode(name: &str, value: &str) -> Result<Vec<u8>, KeyRevocationListError> {
+    BASE64_STANDARD
+        .decode(value)
+        .map_err(|e| KeyRevocationListError::InvalidRecord(format!("invalid {name}: {e}")))
+}
+
+fn to_stored(record: &KeyRevocationRecord) -> StoredRevocationRecord {
+    StoredRevocationRecord {
+        account_id: record.account_id.clone(),
+        algorithm: algorithm_name(record.algorithm),
+        public_key_base64: BASE64_STANDARD.encode(&record.public_key),
+        revoked_at: record.revoked_at,
+        reason: record.reason.clone(),
+        signature_base64: BASE64_STANDARD.encode(&record.signature),
+    }
+}
+
+fn from_stored(
+    stored: StoredRevocationRecord,
+) -> Result<KeyRevocationRecord, KeyRevocationListError> {
+    Ok(KeyRevocationRecord {
+        account_id: stored.account_id,
+        algorithm: parse_algorithm(&stored.algorithm)?,
+        public_key: decode("public_key_base64", &stored.public_key_base64)?,
+        revoked_at: stored.revoked_at,
+        reason: stored.reason,
+        signature: decode("signature_base64", &stored.signature_base64)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(account: &str, revoked_at: u64) -> KeyRevocationRecord {
+        KeyRevocationRecord {
+            account_id: format!("did:key:z{account}"),
+            algorithm: KeyAlgorithm::K256,
+            public_key: vec![1; 65],
+            revoked_at,
+            reason: Some("compromised".to_string()),
+            signature: vec![2; 64],
+        }
+    }
+
+    #[test]
+    fn file_list_persists_records_and_keeps_first_revocation() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("revocations.json");
+
+        let list = FileKeyRevocationList::open(&path).unwrap();
+        assert!(list.list().unwrap().is_empty());
+        list.add(&record("A", 1)).unwrap();
+        list.add(&record("B", 2)).unwrap();
+        list.add(&record("A", 3)).unwrap();
+
+        let reopened = FileKeyRevocationList::open(&path).unwrap();
+        assert_eq!(
+            reopened.list().unwrap(),
+            vec![record("A", 1), record("B", 2)]
+        );
+        assert_eq!(reopened.find("did:key:zA").unwrap(), Some(record("A", 1)));
+        assert_eq!(reopened.find("did:key:zC").unwrap(), None);
+    }
+
+    #[test]
+    fn file_list_rejects_corrupted_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("revocations.json");
+        fs::write(&path, b"not json").unwrap();
+
+        assert!(matches!(
+            FileKeyRevocationList::open(&path),
+            Err(KeyRevocationListError::InvalidRecord(_))
+        ));
+    }
+}
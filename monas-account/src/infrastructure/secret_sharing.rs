@@ -0,0 +1,165 @@
+//! Shamir の秘密分散によるアカウント鍵の復旧。
+//!
+//! 秘密鍵を GF(256) 上で `share_count` 個のシェアに分割し、任意の `threshold` 個から
+//! 復元できるようにする。シェアは信頼できる連絡先や端末に配るための文字列で、
+//! `monas-share.v1.{threshold}.{シェア（base64url）}.{アカウント識別子}` の形式をとる。
+//! 閾値に満たないシェアからは秘密鍵について何も分からない。
+
+use std::collections::HashSet;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use sharks::{Share, Sharks};
+
+const SHARE_PREFIX: &str = "monas-share.v1";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SecretSharingError {
+    #[error("threshold must be between 2 and share_count, and share_count at most 255")]
+    InvalidParameters,
+    #[error("invalid share: {0}")]
+    InvalidShare(String),
+    #[error("shares belong to different accounts or thresholds")]
+    MixedShares,
+    #[error("need {threshold} distinct shares, got {got}")]
+    NotEnoughShares { threshold: u8, got: usize },
+}
+
+/// 秘密鍵を `share_count` 個のシェアに分割する。復元には `threshold` 個が必要。
+pub fn split_secret_key(
+    secret_key: &[u8],
+    account_id: &str,
+    threshold: u8,
+    share_count: u8,
+) -> Result<Vec<String>, SecretSharingError> {
+    if threshold < 2 || share_count < threshold {
+        return Err(SecretSharingError::InvalidParameters);
+    }
+    Ok(Sharks(threshold)
+        .dealer(secret_key)
+        .take(share_count as usize)
+        .map(|share| {
+            format!(
+                "{SHARE_PREFIX}.{threshold}.{}.{account_id}",
+                URL_SAFE_NO_PAD.encode(Vec::from(&share))
+            )
+        })
+        .collect())
+}
+
+/// シェアから秘密鍵を復元し、シェアに記録されたアカウント識別子とあわせて返す。
+///
+/// 同じシェアの重複は 1 つとして数える。閾値以上のシェアがあっても、別のアカウントの
+/// シェアが混ざっている場合はエラーにする。
+pub fn combine_shares(shares: &[String]) -> Result<(String, Vec<u8>), SecretSharingError> {
+    let mut expected: Option<(u8, String)> = None;
+    let mut seen = HashSet::new();
+    let mut parsed = Vec::new();
+
+    for share in shares {
+        let (threshold, account_id, share) = parse_share(share.trim())?;
+        match &expected {
+            None => expected = Some((threshold, account_id)),
+            Some(first) if *first != (threshold, account_id) => {
+                return Err(SecretSharingError::MixedShares)
+            }
+            Some(_) => {}
+        }
+        // 先頭バイトが評価点 x。同じ x のシェアは補間に使えない
+        if seen.insert(Vec::from(&share)[0]) {
+            parsed.push(share);
+        }
+    }
+
+    let (threshold, account_id) = expected.ok_or(SecretSharingError::NotEnoughShares {
+        threshold: 2,
+        got: 0,
+    })?;
+    if parsed.len() < threshold as usize {
+        return Err(SecretSharingError::NotEnoughShares {
+            threshold,
+            got: parsed.len(),
+        });
+    }
+    let secret_key = Sharks(threshold)
+        .recover(parsed.iter())
+        .map_err(|e| SecretSharingError::InvalidShare(e.to_string()))?;
+    Ok((account_id, secret_key))
+}
+
+fn parse_share(share: &str) -> Result<(u8, String, Share), SecretSharingError> {
+    let invalid = |reason: &str| SecretSharingError::InvalidShare(reason.to_string());
+    let rest = share
+        .strip_prefix(SHARE_PREFIX)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .ok_or_else(|| invalid("unknown share format"))?;
+    let mut parts = rest.splitn(3, '.');
+    let (Some(threshold), Some(encoded), Some(account_id)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid("missing fields"));
+    };
+
+    let threshold: u8 = threshold
+        .parse()
+        .map_err(|_| invalid("invalid threshold"))?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|e| SecretSharingError::InvalidShare(e.to_string()))?;
+    if bytes.len() < 2 {
+        return Err(invalid("share is too short"));
+    }
+    let share = Share::try_from(bytes.as_slice()).map_err(invalid)?;
+    Ok((threshold, account_id.to_string(), share))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn any_threshold_subset_recovers_the_secret() {
+        let secret_key: Vec<u8> = (0u8..32).collect();
+        let shares = split_secret_key(&secret_key, "did:key:zTest", 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        let subset = vec![shares[4].clone(), shares[0].clone(), shares[2].clone()];
+        let (account_id, recovered) = combine_shares(&subset).unwrap();
+        assert_eq!(account_id, "did:key:zTest");
+        assert_eq!(recovered, secret_key);
+
+        // 重複したシェアは数えない
+        let duplicated = vec![shares[1].clone(), shares[1].clone(), shares[3].clone()];
+        assert!(matches!(
+            combine_shares(&duplicated),
+            Err(SecretSharingError::NotEnoughShares {
+                threshold: 3,
+                got: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_parameters_and_mixed_shares() {
+        let secret_key = [7u8; 32];
+        assert!(matches!(
+            split_secret_key(&secret_key, "did:key:zTest", 1, 3),
+            Err(SecretSharingError::InvalidParameters)
+        ));
+        assert!(matches!(
+            split_secret_key(&secret_key, "did:key:zTest", 4, 3),
+            Err(SecretSharingError::InvalidParameters)
+        ));
+
+        let ours = split_secret_key(&secret_key, "did:key:zTest", 2, 2).unwrap();
+        let theirs = split_secret_key(&secret_key, "did:key:zOther", 2, 2).unwrap();
+        assert!(matches!(
+            combine_shares(&[ours[0].clone(), theirs[1].clone()]),
+            Err(SecretSharingError::MixedShares)
+        ));
+        assert!(matches!(
+            combine_shares(&["monas-share.v1.2.***.did:key:zTest".to_string()]),
+            Err(SecretSharingError::InvalidShare(_))
+        ));
+    }
+}
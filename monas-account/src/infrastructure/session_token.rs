@@ -0,0 +1,247 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Deserialize;
+
+use crate::application_service::{KeyRevocationList, KeyRevocationListError};
+use crate::domain::account::AccountKeyPair;
+use crate::domain::session::SessionClaims;
+use crate::infrastructure::did_key::{did_key_from_public_key, DidKeyError};
+use crate::infrastructure::jwt_signer::{sign_es256_jwt_payload, JwtSignerError};
+use crate::infrastructure::key_pair::p256_key_pair::P256KeyPair;
+use crate::infrastructure::key_pair::{verify_signature, KeyAlgorithm, KeyPairError};
+use crate::infrastructure::revocation_list::DynKeyRevocationList;
+
+/// セッショントークン（ES256 の JWT）に署名する鍵。
+///
+/// アカウント鍵とは別に、サーバーの起動ごとに P-256 の鍵を生成する。
+/// 再起動すると発行済みのトークンは検証できなくなる。
+#[derive(Clone)]
+pub struct SessionTokenSigner {
+    key_pair: P256KeyPair,
+    key_id: String,
+}
+
+impl SessionTokenSigner {
+    pub fn generate() -> Self {
+        let key_pair = P256KeyPair::generate();
+        let key_id = did_key_from_public_key(KeyAlgorithm::P256, key_pair.public_key_bytes())
+            .expect("generated P-256 public key is always valid");
+        Self { key_pair, key_id }
+    }
+
+    /// トークンの `iss` に入れるセッション鍵の識別子（did:key）。
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// セッション鍵の公開鍵（SEC1 非圧縮形式）。
+    pub fn public_key_bytes(&self) -> &[u8] {
+        self.key_pair.public_key_bytes()
+    }
+
+    pub fn sign(&self, claims: &SessionClaims) -> Result<String, JwtSignerError> {
+        sign_es256_jwt_payload(claims, |signing_input| {
+            let (signature, _recovery_id) = self
+                .key_pair
+                .sign(signing_input)
+                .map_err(|e| e.to_string())?;
+            Ok(signature)
+        })
+    }
+
+    /// このサーバーが発行したトークンを検証する [`SessionTokenVerifier`] を作る。
+    pub fn verifier(&self) -> SessionTokenVerifier {
+        SessionTokenVerifier {
+            public_key: self.public_key_bytes().to_vec(),
+            issuer: self.key_id.clone(),
+            revocations: None,
+        }
+    }
+}
+
+/// セッショントークンを検証する。
+///
+/// 他のサービスは `GET /auth/session-key` で取得した公開鍵から作って使う。
+/// [`with_revocations`](Self::with_revocations) で失効リストを渡すと、発行後に無効化された
+/// アカウントのトークンも拒否する。
+#[derive(Clone)]
+pub struct SessionTokenVerifier {
+    public_key: Vec<u8>,
+    issuer: String,
+    revocations: Option<DynKeyRevocationList>,
+}
+
+impl std::fmt::Debug for SessionTokenVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionTokenVerifier")
+            .field("issuer", &self.issuer)
+            .field("checks_revocations", &self.revocations.is_some())
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+impl SessionTokenVerifier {
+    /// セッション鍵の公開鍵（P-256、SEC1 形式）から作る。
+    pub fn from_public_key(public_key: &[u8]) -> Result<Self, DidKeyError> {
+        Ok(Self {
+            issuer: did_key_from_public_key(KeyAlgorithm::P256, public_key)?,
+            public_key: public_key.to_vec(),
+            revocations: None,
+        })
+    }
+
+    /// トークンの主体が `revocations` で失効していれば拒否する。
+    pub fn with_revocations(mut self, revocations: DynKeyRevocationList) -> Self {
+        self.revocations = Some(revocations);
+        self
+    }
+
+    /// 署名・発行者・有効期限を確認し、クレームを返す。`now` は UNIX 秒。
+    pub fn verify(&self, token: &str, now: u64) -> Result<SessionClaims, SessionTokenError> {
+        let mut parts = token.split('.');
+        let (Some(header_b64), Some(payload_b64), Some(signature_b64), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(SessionTokenError::Malformed(
+                "expected three segments".to_string(),
+            ));
+        };
+
+        let header: JwtHeader = decode_segment(header_b64)?;
+        if header.alg != "ES256" {
+            return Err(SessionTokenError::Malformed(format!(
+                "unsupported alg: {}",
+                header.alg
+            )));
+        }
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature_b64)
+            .map_err(|e| SessionTokenError::Malformed(e.to_string()))?;
+        let signing_input = &token[..header_b64.len() + 1 + payload_b64.len()];
+        let valid = verify_signature(
+            KeyAlgorithm::P256,
+            &self.public_key,
+            signing_input.as_bytes(),
+            &signature,
+        )
+        .map_err(SessionTokenError::InvalidKey)?;
+        if !valid {
+            return Err(SessionTokenError::InvalidSignature);
+        }
+
+        let claims: SessionClaims = decode_segment(payload_b64)?;
+        if claims.iss != self.issuer {
+            return Err(SessionTokenError::InvalidSignature);
+        }
+        if claims.exp <= now {
+            return Err(SessionTokenError::Expired);
+        }
+        if let Some(revocations) = &self.revocations {
+            let revoked = revocations
+                .find(&claims.sub)
+                .map_err(SessionTokenError::RevocationList)?;
+            if revoked.is_some() {
+                return Err(SessionTokenError::Revoked);
+            }
+        }
+        Ok(claims)
+    }
+}
+
+fn decode_segment<T: serde::de::DeserializeOwned>(segment: &str) -> Result<T, SessionTokenError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|e| SessionTokenError::Malformed(e.to_string()))?;
+    serde_json::from_slice(&bytes).map_err(|e| SessionTokenError::Malformed(e.to_string()))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionTokenError {
+    #[error("malformed session token: {0}")]
+    Malformed(String),
+    #[error("invalid session token signature")]
+    InvalidSignature,
+    #[error("session token expired")]
+    Expired,
+    #[error("invalid session token: {0}")]
+    InvalidKey(KeyPairError),
+    /// トークンの発行後にアカウントが無効化された。
+    #[error("account key has been revoked")]
+    Revoked,
+    #[error("revocation list error: {0}")]
+    RevocationList(KeyRevocationListError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(iss: &str, exp: u64) -> SessionClaims {
+        SessionClaims {
+            iss: iss.to_string(),
+            sub: "did:key:zDnaeAccount".to_string(),
+            iat: 100,
+            exp,
+            jti: "jti".to_string(),
+        }
+    }
+
+    #[test]
+    fn verifier_accepts_tokens_from_its_signer_until_expiry() {
+        let signer = SessionTokenSigner::generate();
+        let issued = claims(signer.key_id(), 200);
+        let token = signer.sign(&issued).unwrap();
+
+        let verifier = SessionTokenVerifier::from_public_key(signer.public_key_bytes()).unwrap();
+        assert_eq!(verifier.verify(&token, 150).unwrap(), issued);
+        assert!(matches!(
+            verifier.verify(&token, 200),
+            Err(SessionTokenError::Expired)
+        ));
+
+        // 別のサーバーの鍵で署名したトークンは受け付けない
+        let other = SessionTokenSigner::generate();
+        let forged = other.sign(&claims(signer.key_id(), 200)).unwrap();
+        assert!(matches!(
+            verifier.verify(&forged, 150),
+            Err(SessionTokenError::InvalidSignature)
+        ));
+        assert!(matches!(
+            verifier.verify("not-a-token", 150),
+            Err(SessionTokenError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn verifier_with_revocations_rejects_tokens_of_revoked_accounts() {
+        use crate::domain::revocation::KeyRevocationRecord;
+        use crate::infrastructure::revocation_list::InMemoryKeyRevocationList;
+        use std::sync::Arc;
+
+        let signer = SessionTokenSigner::generate();
+        let token = signer.sign(&claims(signer.key_id(), 200)).unwrap();
+        let revocations = Arc::new(InMemoryKeyRevocationList::default());
+        let verifier = signer.verifier().with_revocations(revocations.clone());
+        assert!(verifier.verify(&token, 150).is_ok());
+
+        revocations
+            .add(&KeyRevocationRecord {
+                account_id: "did:key:zDnaeAccount".to_string(),
+                algorithm: KeyAlgorithm::P256,
+                public_key: vec![],
+                revoked_at: 120,
+                reason: None,
+                signature: vec![],
+            })
+            .unwrap();
+        assert!(matches!(
+            verifier.verify(&token, 150),
+            Err(SessionTokenError::Revoked)
+        ));
+    }
+}
@@ -1,12 +1,73 @@
 use std::net::SocketAddr;
+use std::sync::Arc;
 
+use monas_event_manager::shutdown::{
+    drain_timeout_from_env, serve_with_drain_timeout, shutdown_signal, DrainOutcome,
+};
+use monas_event_manager::{make_subscriber, EventBus};
 use tokio::net::TcpListener;
 
-use monas_account::presentation;
+use monas_account::domain::events::{AccountCreated, DeviceLinked, KeyRotated};
+use monas_account::infrastructure::event_bus_publisher::EventBusEventPublisher;
+use monas_account::infrastructure::key_operation_limiter::KeyOperationLimitConfig;
+use monas_account::infrastructure::key_store::AccountKeyStoreConfig;
+use monas_account::presentation::{self, AppOptions};
+
+/// アカウントのドメインイベントを標準出力に記録する購読者を登録する。
+async fn log_account_events(
+    event_bus: &EventBus,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    event_bus
+        .subscribe::<AccountCreated>(make_subscriber::<AccountCreated, _, _>(
+            "monas-account-log-created".to_string(),
+            |event: Arc<AccountCreated>| async move {
+                println!("account created: {}", event.account_id);
+                Ok(())
+            },
+        ))
+        .await?;
+    event_bus
+        .subscribe::<KeyRotated>(make_subscriber::<KeyRotated, _, _>(
+            "monas-account-log-rotated".to_string(),
+            |event: Arc<KeyRotated>| async move {
+                println!(
+                    "account key rotated: {} -> {}",
+                    event.previous_account_id, event.new_account_id
+                );
+                Ok(())
+            },
+        ))
+        .await?;
+    event_bus
+        .subscribe::<DeviceLinked>(make_subscriber::<DeviceLinked, _, _>(
+            "monas-account-log-device".to_string(),
+            |event: Arc<DeviceLinked>| async move {
+                println!("device linked: {} ({})", event.device_id, event.account_id);
+                Ok(())
+            },
+        ))
+        .await
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let app = presentation::create_router();
+    // MONAS_ACCOUNT_KEYSTORE_PATH を指定すると、鍵をパスフレーズで暗号化したファイルに保存する
+    // MONAS_ACCOUNT_REMOTE_SIGNER_URL を指定すると、鍵をファイルに置かず KMS に署名を依頼する
+    // MONAS_ACCOUNT_KEY_OPS_PER_MINUTE を指定すると、署名などの秘密鍵の操作を制限する
+    let events = EventBusEventPublisher::new(EventBus::new());
+    events.register_event_types().await;
+    log_account_events(events.event_bus())
+        .await
+        .map_err(|e| e as Box<dyn std::error::Error>)?;
+    let drain_timeout = drain_timeout_from_env()?;
+    let (app, shutdown) = presentation::create_app_with_options(
+        &AccountKeyStoreConfig::from_env()?,
+        AppOptions {
+            events: Arc::new(events),
+            key_operation_limit: KeyOperationLimitConfig::from_env()?,
+            ..AppOptions::default()
+        },
+    )?;
 
     let port: u16 = std::env::var("MONAS_ACCOUNT_PORT")
         .ok()
@@ -17,7 +78,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("monas-account server listening on http://{addr}");
 
     let listener = TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // SIGINT / SIGTERM を受け取ったら、処理中のリクエストを終えてから戻る
+    // MONAS_SHUTDOWN_DRAIN_TIMEOUT_SECS（既定 30 秒）を過ぎたら待たずに終了処理へ進む
+    let outcome = serve_with_drain_timeout(shutdown_signal(), drain_timeout, |signal| async move {
+        axum::serve(listener, app)
+            .with_graceful_shutdown(signal)
+            .await
+    })
+    .await?;
+    if outcome == DrainOutcome::TimedOut {
+        eprintln!("in-flight requests did not finish within {drain_timeout:?}, shutting down");
+    }
+
+    shutdown.flush()?;
+    println!("monas-account server stopped");
 
     Ok(())
 }
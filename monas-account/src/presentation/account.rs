@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Json, State},
+    extract::{Json, Path, State},
     http::StatusCode,
-    routing::post,
+    routing::{get, post},
     Router,
 };
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
@@ -11,13 +11,21 @@ use base64::Engine;
 use serde::{Deserialize, Serialize};
 
 use crate::application_service::{
-    AccountKeyStore, AccountService, IssueDelegatedTokenError, IssueDelegatedTokenRequest,
-    SignError,
+    AccountKeyStore, AccountService, DeriveKeyError, DerivedAccountKey, ExportKeyError,
+    ExportKeystoreError, ExportMnemonicError, GuardedKeyOperationError, ImportKeyError,
+    ImportKeystoreError, IssueDelegatedTokenError, IssueDelegatedTokenRequest,
+    RecoverFromSharesError, RestoreFromMnemonicError, RotateKeyError, SignError,
+    SplitRecoverySharesError,
 };
 use crate::domain::delegation::DelegatedCapability;
+use crate::domain::key_audit::KeyOperation;
+use crate::domain::key_rotation::KeyRotationRecord;
+use crate::infrastructure::key_derivation::KeyDerivationPath;
+use crate::infrastructure::key_format::KeyFormat;
 use crate::infrastructure::key_pair::KeyAlgorithm;
+use crate::infrastructure::keystore_file::{KeystoreFile, KeystoreFileError};
 
-use super::AppState;
+use super::{AppState, AuthenticatedAccount, KeyHolder, KeyReplacement};
 
 #[derive(Deserialize)]
 pub struct CreateAccountRequest {
@@ -26,6 +34,7 @@ pub struct CreateAccountRequest {
 
 #[derive(Serialize)]
 pub struct CreateAccountResponse {
+    pub account_id: String,
     pub algorithm: String,
     pub public_key_base64: String,
     pub secret_key_base64: String,
@@ -43,6 +52,206 @@ pub struct SignResponse {
     pub algorithm: String,
 }
 
+#[derive(Deserialize)]
+pub struct VerifyRequest {
+    pub key_type: String,
+    pub public_key_base64: String,
+    pub message_base64: String,
+    pub signature_base64: String,
+}
+
+#[derive(Serialize)]
+pub struct VerifyResponse {
+    pub valid: bool,
+}
+
+#[derive(Serialize)]
+pub struct PublicKeyResponse {
+    pub account_id: String,
+    pub algorithm: String,
+    pub public_key_base64: String,
+}
+
+#[derive(Serialize)]
+pub struct MnemonicResponse {
+    pub account_id: String,
+    pub algorithm: String,
+    pub mnemonic: String,
+}
+
+#[derive(Deserialize)]
+pub struct RestoreAccountRequest {
+    pub key_type: String,
+    pub mnemonic: String,
+}
+
+#[derive(Serialize)]
+pub struct RestoreAccountResponse {
+    pub account_id: String,
+    pub algorithm: String,
+    pub public_key_base64: String,
+}
+
+#[derive(Deserialize)]
+pub struct ExportKeystoreRequest {
+    pub passphrase: String,
+}
+
+#[derive(Deserialize)]
+pub struct ImportKeystoreRequest {
+    /// `/accounts/keystore/export` が返した鍵ファイルの JSON。
+    pub keystore: serde_json::Value,
+    pub passphrase: String,
+}
+
+#[derive(Deserialize)]
+pub struct ExportKeyRequest {
+    /// `pem`（PKCS#8 / SubjectPublicKeyInfo）または `jwk`。
+    pub format: String,
+    #[serde(default)]
+    pub include_private_key: bool,
+}
+
+#[derive(Serialize)]
+pub struct ExportKeyResponse {
+    pub account_id: String,
+    pub algorithm: String,
+    pub public_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ImportKeyRequest {
+    /// PEM（PKCS#8 または SEC1）か JWK の秘密鍵。
+    pub private_key: String,
+}
+
+#[derive(Deserialize)]
+pub struct RecoverySharesRequest {
+    pub threshold: u8,
+    pub share_count: u8,
+}
+
+#[derive(Serialize)]
+pub struct RecoverySharesResponse {
+    pub account_id: String,
+    pub threshold: u8,
+    pub shares: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RecoverAccountRequest {
+    pub shares: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RotateKeyRequest {
+    /// 省略時は現在の鍵と同じアルゴリズム。
+    pub key_type: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct KeyRotationResponse {
+    pub sequence: u64,
+    pub previous_account_id: String,
+    pub previous_algorithm: String,
+    pub previous_public_key_base64: String,
+    pub new_account_id: String,
+    pub new_algorithm: String,
+    pub new_public_key_base64: String,
+    pub rotated_at: u64,
+    /// 旧鍵による署名。署名対象は [`KeyRotationRecord::statement`]。
+    pub signature_base64: String,
+}
+
+impl From<KeyRotationRecord> for KeyRotationResponse {
+    fn from(record: KeyRotationRecord) -> Self {
+        Self {
+            sequence: record.sequence,
+            previous_account_id: record.previous_account_id,
+            previous_algorithm: algorithm_name(record.previous_algorithm),
+            previous_public_key_base64: BASE64_STANDARD.encode(&record.previous_public_key),
+            new_account_id: record.new_account_id,
+            new_algorithm: algorithm_name(record.new_algorithm),
+            new_public_key_base64: BASE64_STANDARD.encode(&record.new_public_key),
+            rotated_at: record.rotated_at,
+            signature_base64: BASE64_STANDARD.encode(&record.signature),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RotateKeyResponse {
+    #[serde(flatten)]
+    pub record: KeyRotationResponse,
+    /// 新しい鍵の秘密鍵。`POST /accounts` と同じく、以後の認証に使うためクライアントが保持する。
+    pub new_secret_key_base64: String,
+}
+
+#[derive(Serialize)]
+pub struct RotationHistoryResponse {
+    pub account_id: String,
+    pub rotations: Vec<KeyRotationResponse>,
+}
+
+#[derive(Deserialize)]
+pub struct DeriveKeyRequest {
+    /// `/` 区切りの導出パス（例: `content/<ContentId>`、`peer/<did:key>`）。
+    pub path: String,
+}
+
+#[derive(Deserialize)]
+pub struct DerivedSignRequest {
+    pub path: String,
+    pub message_base64: String,
+}
+
+#[derive(Serialize)]
+pub struct DerivedKeyResponse {
+    pub account_id: String,
+    pub path: String,
+    pub key_id: String,
+    pub algorithm: String,
+    pub public_key_base64: String,
+}
+
+impl From<DerivedAccountKey> for DerivedKeyResponse {
+    fn from(key: DerivedAccountKey) -> Self {
+        Self {
+            account_id: key.account_id,
+            path: key.path,
+            key_id: key.key_id,
+            algorithm: algorithm_name(key.algorithm),
+            public_key_base64: BASE64_STANDARD.encode(&key.public_key),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DerivedSignResponse {
+    #[serde(flatten)]
+    pub key: DerivedKeyResponse,
+    pub signature_base64: String,
+}
+
+#[derive(Serialize)]
+pub struct EncryptionKeyResponse {
+    pub account_id: String,
+    pub kem: String,
+    pub public_key_base64: String,
+    /// アカウント鍵による署名。送信者は did:key の公開鍵で検証してから鍵を使う。
+    pub signature_base64: String,
+}
+
+#[derive(Serialize)]
+pub struct ExportEncryptionKeyResponse {
+    pub account_id: String,
+    pub kem: String,
+    pub public_key_base64: String,
+    pub secret_key_base64: String,
+}
+
 #[derive(Deserialize)]
 pub struct DelegateTokenRequest {
     pub recipient_public_key_base64: String,
@@ -63,6 +272,32 @@ pub fn routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/accounts", post(create_account).delete(delete_account))
         .route("/accounts/sign", post(sign_account))
+        .route("/accounts/verify", post(verify_signature))
+        .route("/accounts/mnemonic", post(export_mnemonic))
+        .route("/accounts/restore", post(restore_account))
+        .route("/accounts/export", post(export_key))
+        .route("/accounts/import", post(import_key))
+        .route("/accounts/keystore/export", post(export_keystore))
+        .route("/accounts/keystore/import", post(import_keystore))
+        .route("/accounts/recovery-shares", post(split_recovery_shares))
+        .route("/accounts/recover", post(recover_account))
+        .route("/accounts/rotate", post(rotate_key))
+        .route("/accounts/derive", post(derive_key))
+        .route("/accounts/derive/sign", post(sign_with_derived_key))
+        .route("/accounts/{account_id}/public-key", get(get_public_key))
+        .route(
+            "/accounts/{account_id}/encryption-key",
+            get(get_encryption_key),
+        )
+        .route(
+            "/accounts/encryption-key/export",
+            post(export_encryption_key),
+        )
+        .route("/accounts/{account_id}/sign", post(sign_as_account))
+        .route(
+            "/accounts/{account_id}/rotations",
+            get(get_rotation_history),
+        )
         .route("/issuer/delegate", post(delegate_token))
 }
 
@@ -86,13 +321,23 @@ async fn create_account(
 ) -> Result<Json<CreateAccountResponse>, (StatusCode, String)> {
     let key_type = parse_key_type(&req.key_type)?;
 
-    let account = AccountService::create(&state.key_store, key_type)
+    let account = AccountService::create(&state.key_store, &state.events, key_type)
         .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
 
+    let account_id = AccountService::public_key(&state.key_store)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(|stored| stored.account_id)
+        .ok_or_else(|| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "account key not found".to_string(),
+            )
+        })?;
     let public_key_base64 = BASE64_STANDARD.encode(account.public_key_bytes());
     let secret_key_base64 = BASE64_STANDARD.encode(account.secret_key_bytes());
 
     Ok(Json(CreateAccountResponse {
+        account_id,
         algorithm: req.key_type.to_uppercase(),
         public_key_base64,
         secret_key_base64,
@@ -109,6 +354,7 @@ async fn delete_account(
 
 async fn sign_account(
     State(state): State<Arc<AppState>>,
+    caller: Option<AuthenticatedAccount>,
     Json(req): Json<SignRequest>,
 ) -> Result<Json<SignResponse>, (StatusCode, String)> {
     let msg = BASE64_STANDARD.decode(&req.message_base64).map_err(|e| {
@@ -124,21 +370,24 @@ async fn sign_account(
         .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
         .ok_or_else(|| (StatusCode::NOT_FOUND, "account key not found".to_string()))?;
 
-    let (sig, _rec_id) = AccountService::sign(&state.key_store, &msg).map_err(|e| {
+    let principal = caller.as_ref().map(|c| c.account_id.as_str());
+    let (sig, _rec_id) = guard_key_operation(&state, KeyOperation::Sign, principal, || {
+        AccountService::sign(&state.key_store, &msg)
+    })?
+    .map_err(|e| {
         let status = match e {
             SignError::NotFound => StatusCode::NOT_FOUND,
-            SignError::KeyStore(_) | SignError::InvalidKey(_) => StatusCode::BAD_REQUEST,
+            SignError::KeyStore(_)
+            | SignError::InvalidKey(_)
+            | SignError::InvalidPublicKey(_)
+            | SignError::ReservedMessage => StatusCode::BAD_REQUEST,
         };
         (status, e.to_string())
     })?;
 
     let signature_base64 = BASE64_STANDARD.encode(&sig);
     let public_key_base64 = BASE64_STANDARD.encode(&stored.public_key);
-    let algorithm = match stored.algorithm {
-        KeyAlgorithm::K256 => "K256",
-        KeyAlgorithm::P256 => "P256",
-    }
-    .to_string();
+    let algorithm = algorithm_name(stored.algorithm);
 
     Ok(Json(SignResponse {
         signature_base64,
@@ -147,6 +396,576 @@ async fn sign_account(
     }))
 }
 
+/// アカウント識別子（did:key）を指定して署名する。他のサービスが秘密鍵に触れずに
+/// 操作へ署名するために使う。識別子が保存済みのアカウントと一致しなければ 404。
+///
+/// そのアカウントのセッションが必要で、別のアカウントのセッションでは 403。
+async fn sign_as_account(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedAccount,
+    Path(account_id): Path<String>,
+    Json(req): Json<SignRequest>,
+) -> Result<Json<SignResponse>, (StatusCode, String)> {
+    if caller.account_id != account_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "session does not belong to this account".to_string(),
+        ));
+    }
+    let msg = BASE64_STANDARD.decode(&req.message_base64).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("invalid message_base64: {e}"),
+        )
+    })?;
+
+    let signed = guard_key_operation(&state, KeyOperation::Sign, Some(&caller.account_id), || {
+        AccountService::sign_as(&state.key_store, &account_id, &msg)
+    })?
+    .map_err(|e| match e {
+        SignError::NotFound => (StatusCode::NOT_FOUND, "account not found".to_string()),
+        SignError::ReservedMessage => (StatusCode::BAD_REQUEST, e.to_string()),
+        SignError::KeyStore(_) | SignError::InvalidKey(_) | SignError::InvalidPublicKey(_) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        }
+    })?;
+
+    Ok(Json(SignResponse {
+        signature_base64: BASE64_STANDARD.encode(&signed.signature),
+        public_key_base64: BASE64_STANDARD.encode(&signed.public_key),
+        algorithm: algorithm_name(signed.algorithm),
+    }))
+}
+
+/// 署名を公開鍵で検証する。曲線ごとの暗号処理を他のサービスに持たせないために使う。
+///
+/// 署名が一致しない場合も 200（`valid: false`）を返し、入力の形式が不正な場合は 400。
+async fn verify_signature(
+    Json(req): Json<VerifyRequest>,
+) -> Result<Json<VerifyResponse>, (StatusCode, String)> {
+    let key_type = parse_key_type(&req.key_type)?;
+    let decode = |name: &str, value: &str| {
+        BASE64_STANDARD
+            .decode(value)
+            .map_err(|e| (StatusCode::BAD_REQUEST, format!("invalid {name}: {e}")))
+    };
+    let public_key = decode("public_key_base64", &req.public_key_base64)?;
+    let msg = decode("message_base64", &req.message_base64)?;
+    let signature = decode("signature_base64", &req.signature_base64)?;
+
+    let valid = AccountService::verify(key_type, &public_key, &msg, &signature)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    Ok(Json(VerifyResponse { valid }))
+}
+
+/// 秘密鍵を使う操作を、失効確認・レート制限・監査記録を通して実行する。
+///
+/// 外側の `Err` は失効済みの鍵（403）、制限（429）または失効リスト・監査記録の失敗（500）。
+/// 内側は操作自体の結果。
+fn guard_key_operation<T, E: std::fmt::Display>(
+    state: &AppState,
+    operation: KeyOperation,
+    principal: Option<&str>,
+    op: impl FnOnce() -> Result<T, E>,
+) -> Result<Result<T, E>, (StatusCode, String)> {
+    let stored = AccountService::public_key(&state.key_store)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if let Some(stored) = stored {
+        let revoked = AccountService::find_revocation(&state.revocations, &stored.account_id)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        if revoked.is_some() {
+            return Err((
+                StatusCode::FORBIDDEN,
+                format!("account {} has been deactivated", stored.account_id),
+            ));
+        }
+    }
+    match AccountService::run_key_operation(
+        &state.key_audit,
+        &state.key_limiter,
+        operation,
+        principal,
+        op,
+    ) {
+        Ok(value) => Ok(Ok(value)),
+        Err(GuardedKeyOperationError::Operation(e)) => Ok(Err(e)),
+        Err(e @ GuardedKeyOperationError::RateLimited { .. }) => {
+            Err((StatusCode::TOO_MANY_REQUESTS, e.to_string()))
+        }
+        Err(e @ GuardedKeyOperationError::AuditLog(_)) => {
+            Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+        }
+    }
+}
+
+fn algorithm_name(algorithm: KeyAlgorithm) -> String {
+    match algorithm {
+        KeyAlgorithm::K256 => "K256",
+        KeyAlgorithm::P256 => "P256",
+    }
+    .to_string()
+}
+
+/// 保存済みの鍵を BIP-39 の 24 単語として書き出す。別の端末での復元に使う。
+///
+/// セッションと新しいチャレンジへの署名（[`KeyHolder`]）が必要。
+async fn export_mnemonic(
+    State(state): State<Arc<AppState>>,
+    holder: KeyHolder,
+) -> Result<Json<MnemonicResponse>, (StatusCode, String)> {
+    let exported = guard_key_operation(
+        &state,
+        KeyOperation::ExportMnemonic,
+        Some(&holder.account_id),
+        || AccountService::export_mnemonic(&state.key_store),
+    )?
+    .map_err(|e| {
+        let status = match e {
+            ExportMnemonicError::NotFound => StatusCode::NOT_FOUND,
+            ExportMnemonicError::KeyStore(_)
+            | ExportMnemonicError::InvalidPublicKey(_)
+            | ExportMnemonicError::Mnemonic(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, e.to_string())
+    })?;
+
+    Ok(Json(MnemonicResponse {
+        account_id: exported.account_id,
+        algorithm: algorithm_name(exported.algorithm),
+        mnemonic: exported.mnemonic,
+    }))
+}
+
+/// ニーモニックから鍵を復元し、既存の鍵を置き換える。
+///
+/// 既に鍵がある場合は、その鍵の [`KeyHolder`] であることが必要。
+async fn restore_account(
+    State(state): State<Arc<AppState>>,
+    _replacement: KeyReplacement,
+    Json(req): Json<RestoreAccountRequest>,
+) -> Result<Json<RestoreAccountResponse>, (StatusCode, String)> {
+    let key_type = parse_key_type(&req.key_type)?;
+
+    let account = AccountService::restore_from_mnemonic(&state.key_store, key_type, &req.mnemonic)
+        .map_err(|e| {
+            let status = match e {
+                RestoreFromMnemonicError::InvalidMnemonic(_)
+                | RestoreFromMnemonicError::InvalidKey(_) => StatusCode::BAD_REQUEST,
+                RestoreFromMnemonicError::KeyStore(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, e.to_string())
+        })?;
+
+    let account_id = AccountService::public_key(&state.key_store)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(|stored| stored.account_id)
+        .ok_or_else(|| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "account key not found".to_string(),
+            )
+        })?;
+
+    Ok(Json(RestoreAccountResponse {
+        account_id,
+        algorithm: req.key_type.to_uppercase(),
+        public_key_base64: BASE64_STANDARD.encode(account.public_key_bytes()),
+    }))
+}
+
+/// 保存済みの鍵をパスフレーズで暗号化した鍵ファイル（keystore v3 形式）として書き出す。
+///
+/// セッションと新しいチャレンジへの署名（[`KeyHolder`]）が必要。
+async fn export_keystore(
+    State(state): State<Arc<AppState>>,
+    holder: KeyHolder,
+    Json(req): Json<ExportKeystoreRequest>,
+) -> Result<Json<KeystoreFile>, (StatusCode, String)> {
+    if req.passphrase.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "passphrase must not be empty".to_string(),
+        ));
+    }
+    let keystore = guard_key_operation(
+        &state,
+        KeyOperation::ExportPrivateKey,
+        Some(&holder.account_id),
+        || AccountService::export_keystore(&state.key_store, &req.passphrase, state.keystore_kdf),
+    )?
+    .map_err(|e| {
+        let status = match e {
+            ExportKeystoreError::NotFound => StatusCode::NOT_FOUND,
+            ExportKeystoreError::KeyStore(_) | ExportKeystoreError::Keystore(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        (status, e.to_string())
+    })?;
+    Ok(Json(keystore))
+}
+
+/// 鍵ファイルを復号し、既存の鍵を置き換える。
+///
+/// 既に鍵がある場合は、その鍵の [`KeyHolder`] であることが必要。
+async fn import_keystore(
+    State(state): State<Arc<AppState>>,
+    _replacement: KeyReplacement,
+    Json(req): Json<ImportKeystoreRequest>,
+) -> Result<Json<RestoreAccountResponse>, (StatusCode, String)> {
+    let imported = AccountService::import_keystore(
+        &state.key_store,
+        &req.keystore.to_string(),
+        &req.passphrase,
+    )
+    .map_err(|e| {
+        let status = match e {
+            ImportKeystoreError::Keystore(KeystoreFileError::InvalidMac) => {
+                StatusCode::UNAUTHORIZED
+            }
+            ImportKeystoreError::Keystore(KeystoreFileError::Io(_))
+            | ImportKeystoreError::KeyStore(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ImportKeystoreError::Keystore(_) => StatusCode::BAD_REQUEST,
+        };
+        (status, e.to_string())
+    })?;
+
+    Ok(Json(RestoreAccountResponse {
+        account_id: imported.account_id,
+        algorithm: algorithm_name(imported.algorithm),
+        public_key_base64: BASE64_STANDARD.encode(&imported.public_key),
+    }))
+}
+
+fn parse_key_format(s: &str) -> Result<KeyFormat, (StatusCode, String)> {
+    match s.to_lowercase().as_str() {
+        "pem" => Ok(KeyFormat::Pem),
+        "jwk" => Ok(KeyFormat::Jwk),
+        other => Err((
+            StatusCode::BAD_REQUEST,
+            format!("unsupported format: {other}"),
+        )),
+    }
+}
+
+/// 保存済みの鍵を PEM または JWK で書き出す。既存のツールと鍵をやり取りするために使う。
+///
+/// 秘密鍵を含める場合は [`KeyHolder`] であることが必要。
+async fn export_key(
+    State(state): State<Arc<AppState>>,
+    holder: Option<KeyHolder>,
+    Json(req): Json<ExportKeyRequest>,
+) -> Result<Json<ExportKeyResponse>, (StatusCode, String)> {
+    let format = parse_key_format(&req.format)?;
+    if req.include_private_key && holder.is_none() {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            "exporting the private key requires a session and a challenge signature".to_string(),
+        ));
+    }
+
+    let export = || AccountService::export_key(&state.key_store, format, req.include_private_key);
+    let exported = if req.include_private_key {
+        let principal = holder.as_ref().map(|h| h.account_id.as_str());
+        guard_key_operation(&state, KeyOperation::ExportPrivateKey, principal, export)?
+    } else {
+        export()
+    }
+    .map_err(|e| {
+        let status = match e {
+            ExportKeyError::NotFound => StatusCode::NOT_FOUND,
+            ExportKeyError::KeyStore(_)
+            | ExportKeyError::InvalidPublicKey(_)
+            | ExportKeyError::Format(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, e.to_string())
+    })?;
+
+    Ok(Json(ExportKeyResponse {
+        account_id: exported.account_id,
+        algorithm: algorithm_name(exported.algorithm),
+        public_key: exported.public_key,
+        private_key: exported.private_key,
+    }))
+}
+
+/// 外部で生成された秘密鍵を読み込み、既存の鍵を置き換える。
+///
+/// 既に鍵がある場合は、その鍵の [`KeyHolder`] であることが必要。
+async fn import_key(
+    State(state): State<Arc<AppState>>,
+    _replacement: KeyReplacement,
+    Json(req): Json<ImportKeyRequest>,
+) -> Result<Json<RestoreAccountResponse>, (StatusCode, String)> {
+    AccountService::import_key(&state.key_store, &req.private_key).map_err(|e| {
+        let status = match e {
+            ImportKeyError::Format(_) | ImportKeyError::InvalidKey(_) => StatusCode::BAD_REQUEST,
+            ImportKeyError::KeyStore(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, e.to_string())
+    })?;
+
+    let account = AccountService::public_key(&state.key_store)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "account key not found".to_string(),
+            )
+        })?;
+
+    Ok(Json(RestoreAccountResponse {
+        account_id: account.account_id,
+        algorithm: algorithm_name(account.algorithm),
+        public_key_base64: BASE64_STANDARD.encode(&account.public_key),
+    }))
+}
+
+/// 保存済みの鍵を復旧用シェアに分割する。シェアは信頼できる連絡先や端末に配り、
+/// `threshold` 個を集めて `POST /accounts/recover` に渡すと鍵を復元できる。
+///
+/// セッションと新しいチャレンジへの署名（[`KeyHolder`]）が必要。
+async fn split_recovery_shares(
+    State(state): State<Arc<AppState>>,
+    holder: KeyHolder,
+    Json(req): Json<RecoverySharesRequest>,
+) -> Result<Json<RecoverySharesResponse>, (StatusCode, String)> {
+    let split = guard_key_operation(
+        &state,
+        KeyOperation::SplitRecoveryShares,
+        Some(&holder.account_id),
+        || AccountService::split_recovery_shares(&state.key_store, req.threshold, req.share_count),
+    )?
+    .map_err(|e| {
+        let status = match e {
+            SplitRecoverySharesError::NotFound => StatusCode::NOT_FOUND,
+            SplitRecoverySharesError::SecretSharing(_) => StatusCode::BAD_REQUEST,
+            SplitRecoverySharesError::KeyStore(_)
+            | SplitRecoverySharesError::InvalidPublicKey(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, e.to_string())
+    })?;
+
+    Ok(Json(RecoverySharesResponse {
+        account_id: split.account_id,
+        threshold: split.threshold,
+        shares: split.shares,
+    }))
+}
+
+/// 復旧用シェアから鍵を復元し、既存の鍵を置き換える。
+///
+/// 既に鍵がある場合は、その鍵の [`KeyHolder`] であることが必要。
+async fn recover_account(
+    State(state): State<Arc<AppState>>,
+    _replacement: KeyReplacement,
+    Json(req): Json<RecoverAccountRequest>,
+) -> Result<Json<RestoreAccountResponse>, (StatusCode, String)> {
+    AccountService::recover_from_shares(&state.key_store, &req.shares).map_err(|e| {
+        let status = match e {
+            RecoverFromSharesError::InvalidShares(_)
+            | RecoverFromSharesError::InvalidAccountId(_)
+            | RecoverFromSharesError::InvalidKey(_)
+            | RecoverFromSharesError::AccountMismatch => StatusCode::BAD_REQUEST,
+            RecoverFromSharesError::KeyStore(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, e.to_string())
+    })?;
+
+    let account = AccountService::public_key(&state.key_store)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "account key not found".to_string(),
+            )
+        })?;
+
+    Ok(Json(RestoreAccountResponse {
+        account_id: account.account_id,
+        algorithm: algorithm_name(account.algorithm),
+        public_key_base64: BASE64_STANDARD.encode(&account.public_key),
+    }))
+}
+
+/// アカウント識別子（did:key）からアカウントの公開鍵を引く。署名の検証に使う。
+///
+/// did:key に含まれる公開鍵を取り出すため、このサーバーのアカウントに限らず引ける。
+/// アカウント鍵は署名用の鍵で、共有の受信者の鍵（HPKE, DHKEM(P-256)）には使えない
+/// （K256 のアカウント鍵は曲線も異なる）。共有の宛先には、アカウントの鍵の種別によらず
+/// P-256 の鍵を導出して公開する `/accounts/{account_id}/encryption-key` を使う。
+async fn get_public_key(
+    Path(account_id): Path<String>,
+) -> Result<Json<PublicKeyResponse>, (StatusCode, String)> {
+    let account = AccountService::find_public_key(&account_id)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    Ok(Json(PublicKeyResponse {
+        account_id: account.account_id,
+        algorithm: algorithm_name(account.algorithm),
+        public_key_base64: BASE64_STANDARD.encode(&account.public_key),
+    }))
+}
+
+/// 鍵をローテーションし、旧鍵で署名したローテーション記録を返す。
+///
+/// 旧鍵の [`KeyHolder`] であることが必要。本文（`key_type`）は省略できる。
+async fn rotate_key(
+    State(state): State<Arc<AppState>>,
+    _holder: KeyHolder,
+    req: Option<Json<RotateKeyRequest>>,
+) -> Result<Json<RotateKeyResponse>, (StatusCode, String)> {
+    let key_type = req
+        .and_then(|Json(req)| req.key_type)
+        .map(|key_type| parse_key_type(&key_type))
+        .transpose()?;
+
+    let record = AccountService::rotate_key(
+        &state.key_store,
+        &state.rotation_log,
+        &state.events,
+        key_type,
+    )
+    .map_err(|e| {
+        let status = match e {
+            RotateKeyError::NotFound => StatusCode::NOT_FOUND,
+            RotateKeyError::KeyStore(_)
+            | RotateKeyError::RotationLog(_)
+            | RotateKeyError::InvalidKey(_)
+            | RotateKeyError::InvalidPublicKey(_)
+            | RotateKeyError::Time(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, e.to_string())
+    })?;
+
+    let rotated = state
+        .key_store
+        .load()
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "account key not found".to_string(),
+            )
+        })?;
+    Ok(Json(RotateKeyResponse {
+        record: record.into(),
+        new_secret_key_base64: BASE64_STANDARD.encode(&rotated.secret_key),
+    }))
+}
+
+/// `account_id` から現在の鍵までのローテーション記録を返す。
+///
+/// 利用者は各記録の署名と識別子のつながりを検証して、旧識別子と現在の識別子が
+/// 同一のアカウントであることを確認できる。未知の識別子は 404。
+async fn get_rotation_history(
+    State(state): State<Arc<AppState>>,
+    Path(account_id): Path<String>,
+) -> Result<Json<RotationHistoryResponse>, (StatusCode, String)> {
+    let records =
+        AccountService::rotation_history(&state.key_store, &state.rotation_log, &account_id)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or_else(|| (StatusCode::NOT_FOUND, "account not found".to_string()))?;
+
+    Ok(Json(RotationHistoryResponse {
+        account_id,
+        rotations: records.into_iter().map(Into::into).collect(),
+    }))
+}
+
+fn parse_derivation_path(path: &str) -> Result<KeyDerivationPath, (StatusCode, String)> {
+    KeyDerivationPath::parse(path).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))
+}
+
+fn derive_key_error_response(e: DeriveKeyError) -> (StatusCode, String) {
+    let status = match e {
+        DeriveKeyError::NotFound => StatusCode::NOT_FOUND,
+        DeriveKeyError::Derivation(_)
+        | DeriveKeyError::KeyStore(_)
+        | DeriveKeyError::InvalidPublicKey(_)
+        | DeriveKeyError::InvalidKey(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, e.to_string())
+}
+
+/// ルート鍵から `path` の子鍵を導出し、公開鍵を返す。
+///
+/// コンテンツの共有では、識別鍵の代わりにコンテンツや相手ごとの子鍵を使う。
+async fn derive_key(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<DeriveKeyRequest>,
+) -> Result<Json<DerivedKeyResponse>, (StatusCode, String)> {
+    let path = parse_derivation_path(&req.path)?;
+    let key =
+        AccountService::derive_key(&state.key_store, &path).map_err(derive_key_error_response)?;
+    Ok(Json(key.into()))
+}
+
+/// `path` の子鍵で署名する。
+async fn sign_with_derived_key(
+    State(state): State<Arc<AppState>>,
+    caller: Option<AuthenticatedAccount>,
+    Json(req): Json<DerivedSignRequest>,
+) -> Result<Json<DerivedSignResponse>, (StatusCode, String)> {
+    let path = parse_derivation_path(&req.path)?;
+    let msg = BASE64_STANDARD.decode(&req.message_base64).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("invalid message_base64: {e}"),
+        )
+    })?;
+
+    let principal = caller.as_ref().map(|c| c.account_id.as_str());
+    let signed = guard_key_operation(&state, KeyOperation::SignDerived, principal, || {
+        AccountService::sign_with_derived_key(&state.key_store, &path, &msg)
+    })?
+    .map_err(derive_key_error_response)?;
+    Ok(Json(DerivedSignResponse {
+        key: signed.key.into(),
+        signature_base64: BASE64_STANDARD.encode(&signed.signature),
+    }))
+}
+
+/// アカウントが共有を受け取る HPKE の公開鍵を返す。認証は不要。
+async fn get_encryption_key(
+    State(state): State<Arc<AppState>>,
+    Path(account_id): Path<String>,
+) -> Result<Json<EncryptionKeyResponse>, (StatusCode, String)> {
+    let key = AccountService::find_encryption_key(&state.key_store, &account_id)
+        .map_err(derive_key_error_response)?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "account not found".to_string()))?;
+
+    Ok(Json(EncryptionKeyResponse {
+        account_id: key.account_id,
+        kem: key.kem,
+        public_key_base64: BASE64_STANDARD.encode(&key.public_key),
+        signature_base64: BASE64_STANDARD.encode(&key.signature),
+    }))
+}
+
+/// 共有を受け取る HPKE の秘密鍵を書き出す。共有された CEK を取り出すときに使う。
+///
+/// 鍵の [`KeyHolder`] であることが必要。
+async fn export_encryption_key(
+    State(state): State<Arc<AppState>>,
+    holder: KeyHolder,
+) -> Result<Json<ExportEncryptionKeyResponse>, (StatusCode, String)> {
+    let exported = guard_key_operation(
+        &state,
+        KeyOperation::ExportEncryptionKey,
+        Some(&holder.account_id),
+        || AccountService::export_encryption_key(&state.key_store),
+    )?
+    .map_err(derive_key_error_response)?;
+
+    Ok(Json(ExportEncryptionKeyResponse {
+        account_id: exported.account_id,
+        kem: exported.kem,
+        public_key_base64: BASE64_STANDARD.encode(&exported.public_key),
+        secret_key_base64: BASE64_STANDARD.encode(&exported.secret_key),
+    }))
+}
+
 fn parse_capabilities(values: &[String]) -> Result<Vec<DelegatedCapability>, (StatusCode, String)> {
     let mut out = Vec::with_capacity(values.len());
     for capability in values {
@@ -167,6 +986,7 @@ fn parse_capabilities(values: &[String]) -> Result<Vec<DelegatedCapability>, (St
 
 async fn delegate_token(
     State(state): State<Arc<AppState>>,
+    caller: Option<AuthenticatedAccount>,
     Json(req): Json<DelegateTokenRequest>,
 ) -> Result<Json<DelegateTokenResponse>, (StatusCode, String)> {
     let recipient_public_key = BASE64_STANDARD
@@ -180,15 +1000,18 @@ async fn delegate_token(
 
     let capabilities = parse_capabilities(&req.capabilities)?;
 
-    let issued = AccountService::issue_delegated_token(
-        &state.key_store,
-        IssueDelegatedTokenRequest {
-            recipient_public_key,
-            content_id: req.content_id,
-            capabilities,
-            ttl_secs: req.ttl_secs,
-        },
-    )
+    let principal = caller.as_ref().map(|c| c.account_id.as_str());
+    let issued = guard_key_operation(&state, KeyOperation::IssueDelegatedToken, principal, || {
+        AccountService::issue_delegated_token(
+            &state.key_store,
+            IssueDelegatedTokenRequest {
+                recipient_public_key,
+                content_id: req.content_id,
+                capabilities,
+                ttl_secs: req.ttl_secs,
+            },
+        )
+    })?
     .map_err(|e| {
         let status = match e {
             IssueDelegatedTokenError::NotFound => StatusCode::NOT_FOUND,
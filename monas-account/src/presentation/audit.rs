@@ -0,0 +1,92 @@
+//! 秘密鍵を使う操作の監査記録の照会。
+//!
+//! 記録には操作の頻度や時刻が含まれるため、保存済みアカウントのセッションでだけ引ける。
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+    routing::get,
+    Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::application_service::KeyAuditLog;
+use crate::domain::key_audit::{KeyAuditEntry, KeyAuditQuery, KeyOperation, KeyOperationOutcome};
+
+use super::{AppState, AuthenticatedAccount};
+
+/// 1 回の照会で返す記録数の既定値と上限。
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+
+#[derive(Deserialize)]
+pub struct KeyAuditQueryParams {
+    /// この時刻（UNIX 秒）以降の記録に絞る。
+    pub since: Option<u64>,
+    /// `sign`、`export_mnemonic` などの操作名。
+    pub operation: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, Default)]
+pub struct KeyAuditCounts {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub rate_limited: usize,
+}
+
+#[derive(Serialize)]
+pub struct KeyAuditResponse {
+    /// 古い順。
+    pub entries: Vec<KeyAuditEntry>,
+    /// `entries` の結果ごとの件数。
+    pub counts: KeyAuditCounts,
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/audit/key-operations", get(query_key_operations))
+}
+
+/// 秘密鍵を使う操作の記録を返す。`rate_limited` の増加や短時間の集中は不正利用の兆候。
+///
+/// 保存済みアカウントのセッションが必要で、別のアカウントのセッションでは 403。
+async fn query_key_operations(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedAccount,
+    Query(params): Query<KeyAuditQueryParams>,
+) -> Result<Json<KeyAuditResponse>, (StatusCode, String)> {
+    caller.ensure_stored_account(&state)?;
+    let operation = params
+        .operation
+        .map(|name| {
+            KeyOperation::parse(&name).ok_or_else(|| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("unsupported operation: {name}"),
+                )
+            })
+        })
+        .transpose()?;
+    let query = KeyAuditQuery {
+        since: params.since,
+        operation,
+        limit: params.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT),
+    };
+
+    let entries = state
+        .key_audit
+        .query(&query)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let mut counts = KeyAuditCounts::default();
+    for entry in &entries {
+        match entry.outcome {
+            KeyOperationOutcome::Succeeded => counts.succeeded += 1,
+            KeyOperationOutcome::Failed => counts.failed += 1,
+            KeyOperationOutcome::RateLimited => counts.rate_limited += 1,
+        }
+    }
+
+    Ok(Json(KeyAuditResponse { entries, counts }))
+}
@@ -0,0 +1,370 @@
+//! チャレンジレスポンス認証とセッショントークンの検証。
+//!
+//! クライアントは `POST /auth/challenge` でチャレンジを受け取り、アカウント鍵で署名して
+//! `POST /auth/verify` に送るとセッショントークン（ES256 の JWT）を受け取る。
+//! 他のサービスは [`AuthenticatedAccount`] をハンドラーの引数にして、
+//! `Authorization: Bearer <token>` のリクエストを認証できる。
+//!
+//! 鍵の書き出しや置き換えのように秘密鍵そのものを扱う操作は、セッションに加えて
+//! 直前に受け取ったチャレンジへの署名を求める（[`KeyHolder`]）。
+
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::{
+    extract::{FromRef, FromRequestParts, Json, OptionalFromRequestParts, State},
+    http::{header, request::Parts, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::application_service::{
+    AccountService, AuthChallengeStoreError, AuthenticateError, IssueAuthChallengeError,
+};
+use crate::infrastructure::session_token::{SessionTokenError, SessionTokenVerifier};
+
+use super::AppState;
+
+#[derive(Deserialize)]
+pub struct ChallengeRequest {
+    pub account_id: String,
+}
+
+#[derive(Serialize)]
+pub struct ChallengeResponse {
+    pub nonce: String,
+    /// アカウント鍵で署名する文（base64）。
+    pub message_base64: String,
+    pub expires_at: u64,
+}
+
+#[derive(Deserialize)]
+pub struct AuthVerifyRequest {
+    pub account_id: String,
+    pub nonce: String,
+    pub signature_base64: String,
+}
+
+#[derive(Serialize)]
+pub struct SessionTokenResponse {
+    pub account_id: String,
+    pub session_token: String,
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
+
+#[derive(Serialize)]
+pub struct SessionKeyResponse {
+    pub algorithm: String,
+    pub public_key_base64: String,
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/auth/challenge", post(issue_challenge))
+        .route("/auth/verify", post(verify_challenge))
+        .route("/auth/session-key", get(get_session_key))
+}
+
+async fn issue_challenge(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<ChallengeRequest>,
+) -> Result<Json<ChallengeResponse>, (StatusCode, String)> {
+    let challenge = AccountService::issue_auth_challenge(&state.auth_challenges, &req.account_id)
+        .map_err(|e| {
+        let status = match e {
+            IssueAuthChallengeError::InvalidAccountId(_) => StatusCode::BAD_REQUEST,
+            IssueAuthChallengeError::ChallengeStore(AuthChallengeStoreError::Full) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            IssueAuthChallengeError::ChallengeStore(_) | IssueAuthChallengeError::Time(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        (status, e.to_string())
+    })?;
+
+    Ok(Json(ChallengeResponse {
+        message_base64: BASE64_STANDARD.encode(challenge.message()),
+        nonce: challenge.nonce,
+        expires_at: challenge.expires_at,
+    }))
+}
+
+async fn verify_challenge(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<AuthVerifyRequest>,
+) -> Result<Json<SessionTokenResponse>, (StatusCode, String)> {
+    let signature = BASE64_STANDARD.decode(&req.signature_base64).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("invalid signature_base64: {e}"),
+        )
+    })?;
+
+    let session = AccountService::authenticate(
+        &state.auth_challenges,
+        &state.revocations,
+        &state.session_signer,
+        &req.account_id,
+        &req.nonce,
+        &signature,
+    )
+    .map_err(authenticate_error_response)?;
+
+    Ok(Json(SessionTokenResponse {
+        account_id: session.account_id,
+        session_token: session.token,
+        issued_at: session.issued_at,
+        expires_at: session.expires_at,
+    }))
+}
+
+fn authenticate_error_response(e: AuthenticateError) -> (StatusCode, String) {
+    let status = match e {
+        AuthenticateError::InvalidChallenge | AuthenticateError::InvalidSignature => {
+            StatusCode::UNAUTHORIZED
+        }
+        AuthenticateError::Revoked => StatusCode::FORBIDDEN,
+        AuthenticateError::InvalidAccountId(_) | AuthenticateError::InvalidInput(_) => {
+            StatusCode::BAD_REQUEST
+        }
+        AuthenticateError::ChallengeStore(_)
+        | AuthenticateError::RevocationList(_)
+        | AuthenticateError::JwtSigning(_)
+        | AuthenticateError::Time(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, e.to_string())
+}
+
+/// セッショントークンを検証するための公開鍵を返す。他のサービスは
+/// [`SessionTokenVerifier::from_public_key`] に渡して [`AuthenticatedAccount`] を使う。
+async fn get_session_key(State(state): State<Arc<AppState>>) -> Json<SessionKeyResponse> {
+    Json(SessionKeyResponse {
+        algorithm: "P256".to_string(),
+        public_key_base64: BASE64_STANDARD.encode(state.session_signer.public_key_bytes()),
+    })
+}
+
+impl FromRef<Arc<AppState>> for SessionTokenVerifier {
+    fn from_ref(state: &Arc<AppState>) -> Self {
+        state
+            .session_signer
+            .verifier()
+            .with_revocations(state.revocations.clone())
+    }
+}
+
+/// `Authorization: Bearer <session token>` で認証されたアカウント。
+///
+/// ルーターの状態から [`SessionTokenVerifier`] を取り出せれば（`FromRef`）、
+/// どのサービスのハンドラーでも引数に書くだけで使える。認証に失敗した場合は 401。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedAccount {
+    pub account_id: String,
+    pub expires_at: u64,
+}
+
+impl<S> FromRequestParts<S> for AuthenticatedAccount
+where
+    SessionTokenVerifier: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| {
+                (
+                    StatusCode::UNAUTHORIZED,
+                    "missing bearer session token".to_string(),
+                )
+            })?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        let claims = SessionTokenVerifier::from_ref(state)
+            .verify(token.trim(), now)
+            .map_err(|e| {
+                let status = match e {
+                    SessionTokenError::Revoked => StatusCode::FORBIDDEN,
+                    SessionTokenError::RevocationList(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                    _ => StatusCode::UNAUTHORIZED,
+                };
+                (status, e.to_string())
+            })?;
+
+        Ok(Self {
+            account_id: claims.sub,
+            expires_at: claims.exp,
+        })
+    }
+}
+
+impl AuthenticatedAccount {
+    /// セッションの主体が保存済みのアカウントであることを確かめる。
+    ///
+    /// 鍵がなければ 404、別のアカウントのセッションなら 403。
+    pub(crate) fn ensure_stored_account(
+        &self,
+        state: &AppState,
+    ) -> Result<(), (StatusCode, String)> {
+        let stored = AccountService::public_key(&state.key_store)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or_else(|| (StatusCode::NOT_FOUND, "account key not found".to_string()))?;
+        if stored.account_id != self.account_id {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "session does not belong to the stored account".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// `Authorization` ヘッダーがなければ `None`。ヘッダーがある場合は検証し、失敗すれば
+/// リクエストを拒否する。セッションなしでも使える操作で呼び出し元を記録するために使う。
+impl<S> OptionalFromRequestParts<S> for AuthenticatedAccount
+where
+    SessionTokenVerifier: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        if !parts.headers.contains_key(header::AUTHORIZATION) {
+            return Ok(None);
+        }
+        <Self as FromRequestParts<S>>::from_request_parts(parts, state)
+            .await
+            .map(Some)
+    }
+}
+
+/// 直前のチャレンジへの署名を送るヘッダー。`nonce` は `POST /auth/challenge` の応答の値。
+pub const CHALLENGE_NONCE_HEADER: &str = "x-monas-challenge-nonce";
+/// `CHALLENGE_NONCE_HEADER` のチャレンジへのアカウント鍵の署名（base64）。
+pub const CHALLENGE_SIGNATURE_HEADER: &str = "x-monas-challenge-signature";
+
+/// 保存済みのアカウント鍵を今も持っていることを確かめた呼び出し元。
+///
+/// セッショントークンの主体が保存済みのアカウントと一致し、さらに
+/// [`CHALLENGE_NONCE_HEADER`] / [`CHALLENGE_SIGNATURE_HEADER`] で未使用のチャレンジへの
+/// 署名を送った場合だけ取り出せる。盗まれたセッショントークンだけでは鍵を書き出せない。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyHolder {
+    pub account_id: String,
+}
+
+impl FromRequestParts<Arc<AppState>> for KeyHolder {
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let caller =
+            <AuthenticatedAccount as FromRequestParts<_>>::from_request_parts(parts, state).await?;
+        caller.ensure_stored_account(state)?;
+
+        let header_value = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .ok_or_else(|| (StatusCode::UNAUTHORIZED, format!("missing {name} header")))
+        };
+        let nonce = header_value(CHALLENGE_NONCE_HEADER)?;
+        let signature = BASE64_STANDARD
+            .decode(header_value(CHALLENGE_SIGNATURE_HEADER)?)
+            .map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("invalid {CHALLENGE_SIGNATURE_HEADER}: {e}"),
+                )
+            })?;
+
+        AccountService::confirm_challenge(
+            &state.auth_challenges,
+            &state.revocations,
+            &caller.account_id,
+            nonce,
+            &signature,
+        )
+        .map_err(authenticate_error_response)?;
+
+        Ok(Self {
+            account_id: caller.account_id,
+        })
+    }
+}
+
+/// `Authorization` ヘッダーがなければ `None`。ヘッダーがある場合は [`KeyHolder`] と同じく
+/// 検証し、失敗すればリクエストを拒否する。
+impl OptionalFromRequestParts<Arc<AppState>> for KeyHolder {
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        if !parts.headers.contains_key(header::AUTHORIZATION) {
+            return Ok(None);
+        }
+        <Self as FromRequestParts<_>>::from_request_parts(parts, state)
+            .await
+            .map(Some)
+    }
+}
+
+/// 保存済みの鍵を置き換える操作（復元・読み込み）の呼び出し元。
+///
+/// 鍵がまだないか、無効化されていれば誰でも新しい鍵を置ける。有効な鍵がある場合は、
+/// その鍵の [`KeyHolder`] であることを求める。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyReplacement {
+    /// 置き換えられる鍵のアカウント識別子。鍵がなかった場合は `None`。
+    pub replaced_account_id: Option<String>,
+}
+
+impl FromRequestParts<Arc<AppState>> for KeyReplacement {
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let stored = AccountService::public_key(&state.key_store)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        let Some(stored) = stored else {
+            return Ok(Self {
+                replaced_account_id: None,
+            });
+        };
+        // 無効化された鍵はもう使えないため、持ち主の確認なしに置き換えられる
+        let revoked = AccountService::find_revocation(&state.revocations, &stored.account_id)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        if revoked.is_some() {
+            return Ok(Self {
+                replaced_account_id: Some(stored.account_id),
+            });
+        }
+        let holder = <KeyHolder as FromRequestParts<_>>::from_request_parts(parts, state).await?;
+        Ok(Self {
+            replaced_account_id: Some(holder.account_id),
+        })
+    }
+}
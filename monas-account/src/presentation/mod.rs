@@ -1,18 +1,1518 @@
-use crate::infrastructure::key_store::InMemoryAccountKeyStore;
+use crate::application_service::{AccountKeyStoreError, NoOpEventPublisher};
+use crate::infrastructure::auth_challenge_store::{
+    DynAuthChallengeStore, InMemoryAuthChallengeStore,
+};
+use crate::infrastructure::event_bus_publisher::DynEventPublisher;
+use crate::infrastructure::file_key_store::ScryptParams;
+use crate::infrastructure::key_audit_log::DynKeyAuditLog;
+use crate::infrastructure::key_operation_limiter::{
+    KeyOperationLimitConfig, KeyOperationRateLimiter,
+};
+use crate::infrastructure::key_rotation_log::DynKeyRotationLog;
+use crate::infrastructure::key_store::{AccountKeyStoreConfig, DynAccountKeyStore};
+use crate::infrastructure::profile_repository::DynProfileRepository;
+use crate::infrastructure::revocation_list::DynKeyRevocationList;
+use crate::infrastructure::session_token::SessionTokenSigner;
 use axum::Router;
 use std::sync::Arc;
 
 pub mod account;
+pub mod audit;
+pub mod auth;
+pub mod profile;
+pub mod revocation;
+mod shutdown;
+
+pub use auth::{AuthenticatedAccount, KeyHolder, KeyReplacement};
+pub use shutdown::{ShutdownError, ShutdownHook};
 
 #[derive(Clone)]
 pub struct AppState {
-    pub key_store: InMemoryAccountKeyStore,
+    pub key_store: DynAccountKeyStore,
+    pub rotation_log: DynKeyRotationLog,
+    pub profiles: DynProfileRepository,
+    pub revocations: DynKeyRevocationList,
+    pub auth_challenges: DynAuthChallengeStore,
+    pub session_signer: SessionTokenSigner,
+    pub events: DynEventPublisher,
+    pub key_audit: DynKeyAuditLog,
+    pub key_limiter: KeyOperationRateLimiter,
+    pub keystore_kdf: ScryptParams,
+}
+
+/// 鍵の保存先以外のルーターの設定。
+#[derive(Clone)]
+pub struct AppOptions {
+    /// アカウントのドメインイベントの通知先。
+    pub events: DynEventPublisher,
+    /// 秘密鍵を使う操作のレート制限。`None` なら無制限。
+    pub key_operation_limit: Option<KeyOperationLimitConfig>,
+    /// `/accounts/keystore/export` で書き出す鍵ファイルの scrypt パラメータ。
+    pub keystore_kdf: ScryptParams,
+}
+
+impl Default for AppOptions {
+    fn default() -> Self {
+        Self {
+            events: Arc::new(NoOpEventPublisher),
+            key_operation_limit: None,
+            keystore_kdf: ScryptParams::default(),
+        }
+    }
 }
 
+/// 鍵をインメモリに保持するルーターを作る。
 pub fn create_router() -> Router {
+    create_app().0
+}
+
+/// `create_router` と同じルーターに加え、終了前にストアを永続化する [`ShutdownHook`] を返す。
+pub fn create_app() -> (Router, ShutdownHook) {
+    create_app_with_key_store(&AccountKeyStoreConfig::InMemory)
+        .expect("in-memory key store never fails to open")
+}
+
+/// 鍵の保存先を `config` で指定してルーターを作る。
+///
+/// 鍵ファイルが読めない、パスフレーズが誤っているなどの場合はエラーを返す。
+pub fn create_router_with_key_store(
+    config: &AccountKeyStoreConfig,
+) -> Result<Router, AccountKeyStoreError> {
+    create_app_with_key_store(config).map(|(router, _)| router)
+}
+
+/// [`create_router_with_key_store`] と同じルーターに加え、[`ShutdownHook`] を返す。
+pub fn create_app_with_key_store(
+    config: &AccountKeyStoreConfig,
+) -> Result<(Router, ShutdownHook), AccountKeyStoreError> {
+    create_app_with_options(config, AppOptions::default())
+}
+
+/// [`create_app_with_key_store`] に加え、アカウントのドメインイベントを `events` に通知する。
+///
+/// 同じプロセスの他のサービスと EventBus を共有する場合は
+/// [`EventBusEventPublisher`](crate::infrastructure::event_bus_publisher::EventBusEventPublisher)
+/// を渡す。
+pub fn create_app_with_events(
+    config: &AccountKeyStoreConfig,
+    events: DynEventPublisher,
+) -> Result<(Router, ShutdownHook), AccountKeyStoreError> {
+    create_app_with_options(
+        config,
+        AppOptions {
+            events,
+            ..AppOptions::default()
+        },
+    )
+}
+
+/// 鍵の保存先と [`AppOptions`] を指定してルーターと [`ShutdownHook`] を作る。
+pub fn create_app_with_options(
+    config: &AccountKeyStoreConfig,
+    options: AppOptions,
+) -> Result<(Router, ShutdownHook), AccountKeyStoreError> {
+    let key_store = config.build()?;
+    let rotation_log = config
+        .build_rotation_log()
+        .map_err(|e| AccountKeyStoreError::Storage(e.to_string()))?;
+    let profiles = config
+        .build_profile_repository()
+        .map_err(|e| AccountKeyStoreError::Storage(e.to_string()))?;
+    let revocations = config
+        .build_revocation_list()
+        .map_err(|e| AccountKeyStoreError::Storage(e.to_string()))?;
+    let key_audit = config
+        .build_audit_log()
+        .map_err(|e| AccountKeyStoreError::Storage(e.to_string()))?;
     let state = Arc::new(AppState {
-        key_store: InMemoryAccountKeyStore::default(),
+        key_store,
+        rotation_log,
+        profiles,
+        revocations,
+        auth_challenges: Arc::new(InMemoryAuthChallengeStore::default()),
+        session_signer: SessionTokenSigner::generate(),
+        events: options.events,
+        key_audit,
+        key_limiter: KeyOperationRateLimiter::new(options.key_operation_limit),
+        keystore_kdf: options.keystore_kdf,
     });
+    let shutdown = ShutdownHook {
+        state: state.clone(),
+    };
+
+    Ok((
+        Router::new()
+            .merge(account::routes())
+            .merge(audit::routes())
+            .merge(auth::routes())
+            .merge(profile::routes())
+            .merge(revocation::routes())
+            .with_state(state),
+        shutdown,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_router, create_router_with_key_store};
+    use crate::infrastructure::key_store::AccountKeyStoreConfig;
+    use axum::body::Body;
+    use axum::http::{header, Method, Request, StatusCode};
+    use axum::response::Response;
+    use axum::Router;
+    use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+    use base64::Engine;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn send(router: &Router, method: Method, uri: &str, body: Option<Value>) -> Response {
+        send_with(router, method, uri, body, &[]).await
+    }
+
+    async fn send_with(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+        headers: &[(&str, String)],
+    ) -> Response {
+        let mut request = Request::builder().method(method).uri(uri);
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+        let request = match body {
+            Some(body) => request
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .unwrap();
+        router.clone().oneshot(request).await.unwrap()
+    }
+
+    async fn body_bytes(response: Response) -> Vec<u8> {
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec()
+    }
+
+    async fn body_json(response: Response) -> Value {
+        serde_json::from_slice(&body_bytes(response).await).unwrap()
+    }
+
+    async fn body_text(response: Response) -> String {
+        String::from_utf8(body_bytes(response).await).unwrap()
+    }
+
+    async fn create_account(router: &Router, key_type: &str) -> Value {
+        let response = send(
+            router,
+            Method::POST,
+            "/accounts",
+            Some(json!({ "key_type": key_type })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        body_json(response).await
+    }
+
+    /// `POST /accounts` が返した秘密鍵で、クライアント側で署名する。
+    fn sign_locally(created: &Value, message: &[u8]) -> String {
+        use crate::infrastructure::key_pair::{KeyAlgorithm, KeyPairGenerateFactory};
+
+        let algorithm = match created["algorithm"].as_str().unwrap() {
+            "K256" => KeyAlgorithm::K256,
+            _ => KeyAlgorithm::P256,
+        };
+        let secret_key = BASE64_STANDARD
+            .decode(created["secret_key_base64"].as_str().unwrap())
+            .unwrap();
+        let key_pair =
+            KeyPairGenerateFactory::from_secret_key_bytes(algorithm, &secret_key).unwrap();
+        BASE64_STANDARD.encode(key_pair.sign(message).unwrap().0)
+    }
+
+    /// チャレンジを受け取り、クライアント側で署名した `(nonce, signature_base64)` を返す。
+    async fn signed_challenge(router: &Router, created: &Value) -> (String, String) {
+        let response = send(
+            router,
+            Method::POST,
+            "/auth/challenge",
+            Some(json!({ "account_id": created["account_id"] })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let challenge = body_json(response).await;
+        let message = BASE64_STANDARD
+            .decode(challenge["message_base64"].as_str().unwrap())
+            .unwrap();
+        (
+            challenge["nonce"].as_str().unwrap().to_string(),
+            sign_locally(created, &message),
+        )
+    }
+
+    /// チャレンジに応答してセッショントークンを受け取り、`Authorization` ヘッダーにする。
+    async fn session_header(router: &Router, created: &Value) -> (&'static str, String) {
+        let (nonce, signature) = signed_challenge(router, created).await;
+        let response = send(
+            router,
+            Method::POST,
+            "/auth/verify",
+            Some(json!({
+                "account_id": created["account_id"],
+                "nonce": nonce,
+                "signature_base64": signature,
+            })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let token = body_json(response).await["session_token"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        ("authorization", format!("Bearer {token}"))
+    }
+
+    /// 鍵を扱う操作に必要なヘッダー（セッショントークンと新しいチャレンジへの署名）。
+    async fn key_holder_headers(router: &Router, created: &Value) -> Vec<(&'static str, String)> {
+        use super::auth::{CHALLENGE_NONCE_HEADER, CHALLENGE_SIGNATURE_HEADER};
+
+        let session = session_header(router, created).await;
+        let (nonce, signature) = signed_challenge(router, created).await;
+        vec![
+            session,
+            (CHALLENGE_NONCE_HEADER, nonce),
+            (CHALLENGE_SIGNATURE_HEADER, signature),
+        ]
+    }
+
+    #[tokio::test]
+    async fn create_sign_and_delete_account() {
+        let router = create_router();
+
+        let created = create_account(&router, "k256").await;
+        assert_eq!(created["algorithm"], "K256");
+        let public_key = BASE64_STANDARD
+            .decode(created["public_key_base64"].as_str().unwrap())
+            .unwrap();
+        assert_eq!(public_key.len(), 65);
+
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/sign",
+            Some(json!({ "message_base64": BASE64_STANDARD.encode(b"hello") })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let signed = body_json(response).await;
+        assert_eq!(signed["algorithm"], "K256");
+        assert_eq!(signed["public_key_base64"], created["public_key_base64"]);
+        assert!(!signed["signature_base64"].as_str().unwrap().is_empty());
+
+        let response = send(&router, Method::DELETE, "/accounts", None).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // 削除後は署名できない
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/sign",
+            Some(json!({ "message_base64": BASE64_STANDARD.encode(b"hello") })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(body_text(response).await, "account key not found");
+    }
+
+    #[tokio::test]
+    async fn file_key_store_keeps_account_across_restarts() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = AccountKeyStoreConfig::File {
+            path: dir.path().join("account.key"),
+            passphrase: "passphrase".into(),
+        };
+
+        let created = create_account(&create_router_with_key_store(&config).unwrap(), "k256").await;
+
+        // 同じファイルとパスフレーズで作り直したルーターでも同じ鍵で署名できる
+        let router = create_router_with_key_store(&config).unwrap();
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/sign",
+            Some(json!({ "message_base64": BASE64_STANDARD.encode(b"hello") })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_json(response).await["public_key_base64"],
+            created["public_key_base64"]
+        );
+
+        let wrong = AccountKeyStoreConfig::File {
+            path: dir.path().join("account.key"),
+            passphrase: "wrong".into(),
+        };
+        assert!(create_router_with_key_store(&wrong).is_err());
+    }
+
+    #[tokio::test]
+    async fn mnemonic_backup_restores_account_on_new_device() {
+        let router = create_router();
+        let created = create_account(&router, "p256").await;
+
+        // セッションとチャレンジへの署名がなければ書き出せない
+        let response = send(&router, Method::POST, "/accounts/mnemonic", None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let headers = key_holder_headers(&router, &created).await;
+        let response = send_with(&router, Method::POST, "/accounts/mnemonic", None, &headers).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let exported = body_json(response).await;
+
+        // チャレンジへの署名は使い捨て
+        let response = send_with(&router, Method::POST, "/accounts/mnemonic", None, &headers).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(exported["account_id"], created["account_id"]);
+        assert_eq!(exported["algorithm"], "P256");
+        let mnemonic = exported["mnemonic"].as_str().unwrap().to_string();
+        assert_eq!(mnemonic.split(' ').count(), 24);
+
+        let new_device = create_router();
+        let response = send(
+            &new_device,
+            Method::POST,
+            "/accounts/restore",
+            Some(json!({ "key_type": "p256", "mnemonic": mnemonic })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let restored = body_json(response).await;
+        assert_eq!(restored["account_id"], created["account_id"]);
+        assert_eq!(restored["public_key_base64"], created["public_key_base64"]);
+
+        // 鍵のある端末では、その鍵の持ち主しか置き換えられない
+        let response = send(
+            &new_device,
+            Method::POST,
+            "/accounts/restore",
+            Some(json!({ "key_type": "p256", "mnemonic": mnemonic })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = send_with(
+            &new_device,
+            Method::POST,
+            "/accounts/restore",
+            Some(json!({ "key_type": "p256", "mnemonic": mnemonic })),
+            &key_holder_headers(&new_device, &created).await,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(
+            &create_router(),
+            Method::POST,
+            "/accounts/restore",
+            Some(json!({ "key_type": "p256", "mnemonic": "abandon abandon" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn keys_are_exported_and_imported_as_pem_and_jwk() {
+        let router = create_router();
+        let created = create_account(&router, "p256").await;
+
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/export",
+            Some(json!({ "format": "pem" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let public_only = body_json(response).await;
+        assert!(public_only["public_key"]
+            .as_str()
+            .unwrap()
+            .starts_with("-----BEGIN PUBLIC KEY-----"));
+        assert!(public_only.get("private_key").is_none());
+
+        // 秘密鍵の書き出しにはセッションとチャレンジへの署名が必要
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/export",
+            Some(json!({ "format": "pem", "include_private_key": true })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        for format in ["pem", "jwk"] {
+            let response = send_with(
+                &router,
+                Method::POST,
+                "/accounts/export",
+                Some(json!({ "format": format, "include_private_key": true })),
+                &key_holder_headers(&router, &created).await,
+            )
+            .await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let exported = body_json(response).await;
+
+            let new_device = create_router();
+            let response = send(
+                &new_device,
+                Method::POST,
+                "/accounts/import",
+                Some(json!({ "private_key": exported["private_key"] })),
+            )
+            .await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let imported = body_json(response).await;
+            assert_eq!(imported["account_id"], created["account_id"]);
+            assert_eq!(imported["algorithm"], "P256");
+
+            // 鍵のある端末では、鍵の持ち主しか置き換えられない
+            let response = send(
+                &new_device,
+                Method::POST,
+                "/accounts/import",
+                Some(json!({ "private_key": exported["private_key"] })),
+            )
+            .await;
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        let response = send(
+            &create_router(),
+            Method::POST,
+            "/accounts/import",
+            Some(json!({ "private_key": public_only["public_key"] })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/export",
+            Some(json!({ "format": "der" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn recovery_shares_restore_account_on_new_device() {
+        let router = create_router();
+        let created = create_account(&router, "k256").await;
+        let split_shares = |threshold: u8, headers: Vec<(&'static str, String)>| {
+            let router = router.clone();
+            async move {
+                send_with(
+                    &router,
+                    Method::POST,
+                    "/accounts/recovery-shares",
+                    Some(json!({ "threshold": threshold, "share_count": 5 })),
+                    &headers,
+                )
+                .await
+            }
+        };
+
+        let response = split_shares(3, vec![]).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = split_shares(3, key_holder_headers(&router, &created).await).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let split = body_json(response).await;
+        assert_eq!(split["account_id"], created["account_id"]);
+        let shares = split["shares"].as_array().unwrap().clone();
+        assert_eq!(shares.len(), 5);
+
+        let new_device = create_router();
+        let response = send(
+            &new_device,
+            Method::POST,
+            "/accounts/recover",
+            Some(json!({ "shares": shares[..2] })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = send(
+            &new_device,
+            Method::POST,
+            "/accounts/recover",
+            Some(json!({ "shares": [shares[1], shares[3], shares[4]] })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let recovered = body_json(response).await;
+        assert_eq!(recovered["account_id"], created["account_id"]);
+        assert_eq!(recovered["algorithm"], "K256");
+        assert_eq!(recovered["public_key_base64"], created["public_key_base64"]);
+
+        // 鍵のある端末では、シェアだけでは鍵を置き換えられない
+        let response = send(
+            &new_device,
+            Method::POST,
+            "/accounts/recover",
+            Some(json!({ "shares": [shares[0], shares[1], shares[2]] })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = split_shares(1, key_holder_headers(&router, &created).await).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn sign_by_account_id_returns_signature_and_algorithm() {
+        let router = create_router();
+        let created = create_account(&router, "k256").await;
+        let account_id = created["account_id"].as_str().unwrap();
+        let sign_as = |account_id: &str, headers: Vec<(&'static str, String)>| {
+            let router = router.clone();
+            let uri = format!("/accounts/{account_id}/sign");
+            async move {
+                send_with(
+                    &router,
+                    Method::POST,
+                    &uri,
+                    Some(json!({ "message_base64": BASE64_STANDARD.encode(b"operation") })),
+                    &headers,
+                )
+                .await
+            }
+        };
+
+        // セッションがなければ署名しない
+        let response = sign_as(account_id, vec![]).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let session = vec![session_header(&router, &created).await];
+        let response = sign_as(account_id, session.clone()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let signed = body_json(response).await;
+        assert_eq!(signed["algorithm"], "K256");
+        assert_eq!(signed["public_key_base64"], created["public_key_base64"]);
+        assert!(signed.get("secret_key_base64").is_none());
+
+        // 同じ鍵でも /accounts/sign と同じ署名になる
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/sign",
+            Some(json!({ "message_base64": BASE64_STANDARD.encode(b"operation") })),
+        )
+        .await;
+        assert_eq!(
+            body_json(response).await["signature_base64"],
+            signed["signature_base64"]
+        );
+
+        // 別のアカウントのセッションでは署名しない
+        let other = create_account(&create_router(), "k256").await;
+        let other_id = other["account_id"].as_str().unwrap();
+        let other_session = vec![session_header(&router, &other).await];
+        let response = sign_as(account_id, other_session.clone()).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // 保存済みでないアカウントは 404
+        let response = sign_as(other_id, other_session).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(body_text(response).await, "account not found");
+    }
+
+    #[tokio::test]
+    async fn verify_accepts_signatures_from_sign_endpoint() {
+        let router = create_router();
+        let created = create_account(&router, "p256").await;
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/sign",
+            Some(json!({ "message_base64": BASE64_STANDARD.encode(b"operation") })),
+        )
+        .await;
+        let signed = body_json(response).await;
+
+        // 検証には鍵ストアを使わないため、別のサーバーでも検証できる
+        let verifier = create_router();
+        let verify = |message: &[u8]| {
+            json!({
+                "key_type": "p256",
+                "public_key_base64": created["public_key_base64"],
+                "message_base64": BASE64_STANDARD.encode(message),
+                "signature_base64": signed["signature_base64"],
+            })
+        };
+        let response = send(
+            &verifier,
+            Method::POST,
+            "/accounts/verify",
+            Some(verify(b"operation")),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["valid"], true);
+
+        let response = send(
+            &verifier,
+            Method::POST,
+            "/accounts/verify",
+            Some(verify(b"forged")),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["valid"], false);
+
+        let mut malformed = verify(b"operation");
+        malformed["public_key_base64"] = json!(BASE64_STANDARD.encode([1u8; 3]));
+        let response = send(&verifier, Method::POST, "/accounts/verify", Some(malformed)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(body_text(response).await.starts_with("invalid public key"));
+    }
+
+    #[tokio::test]
+    async fn rotated_key_history_links_old_and_new_account_ids() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = AccountKeyStoreConfig::File {
+            path: dir.path().join("account.key"),
+            passphrase: "passphrase".into(),
+        };
+        let router = create_router_with_key_store(&config).unwrap();
+        let created = create_account(&router, "k256").await;
+        let response = send(&router, Method::POST, "/accounts/rotate", None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let headers = key_holder_headers(&router, &created).await;
+        let response = send_with(&router, Method::POST, "/accounts/rotate", None, &headers).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut first = body_json(response).await;
+        assert_eq!(first["sequence"], 1);
+        assert_eq!(first["previous_account_id"], created["account_id"]);
+        assert_eq!(first["new_algorithm"], "K256");
+
+        // 次のローテーションには、応答で受け取った新しい鍵で認証する
+        let rotated = |rotation: &mut Value| {
+            let secret_key = rotation
+                .as_object_mut()
+                .unwrap()
+                .remove("new_secret_key_base64")
+                .unwrap();
+            json!({
+                "account_id": rotation["new_account_id"],
+                "algorithm": rotation["new_algorithm"],
+                "secret_key_base64": secret_key,
+            })
+        };
+        let current = rotated(&mut first);
+        let response = send_with(
+            &router,
+            Method::POST,
+            "/accounts/rotate",
+            Some(json!({ "key_type": "p256" })),
+            &key_holder_headers(&router, &created).await,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = send_with(
+            &router,
+            Method::POST,
+            "/accounts/rotate",
+            Some(json!({ "key_type": "p256" })),
+            &key_holder_headers(&router, &current).await,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut second = body_json(response).await;
+        rotated(&mut second);
+        assert_eq!(second["previous_account_id"], first["new_account_id"]);
+        assert_eq!(second["new_algorithm"], "P256");
+
+        // 再起動後も記録が残り、旧識別子から現在の識別子までたどれる
+        let router = create_router_with_key_store(&config).unwrap();
+        let response = send(
+            &router,
+            Method::GET,
+            &format!(
+                "/accounts/{}/rotations",
+                created["account_id"].as_str().unwrap()
+            ),
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let history = body_json(response).await;
+        assert_eq!(history["rotations"], json!([first, second]));
+
+        // 旧鍵の署名は検証エンドポイントで確認できる
+        let statement = format!(
+            "monas-account-key-rotation:v1\nsequence:1\nprevious:{}\nnew:{}\nrotated_at:{}",
+            first["previous_account_id"].as_str().unwrap(),
+            first["new_account_id"].as_str().unwrap(),
+            first["rotated_at"]
+        );
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/verify",
+            Some(json!({
+                "key_type": "k256",
+                "public_key_base64": first["previous_public_key_base64"],
+                "message_base64": BASE64_STANDARD.encode(statement),
+                "signature_base64": first["signature_base64"],
+            })),
+        )
+        .await;
+        assert_eq!(body_json(response).await["valid"], true);
+
+        let response = send(
+            &router,
+            Method::GET,
+            "/accounts/did:key:zUnknown/rotations",
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn challenge_response_issues_session_token_accepted_by_extractor() {
+        use crate::infrastructure::session_token::SessionTokenVerifier;
+        use crate::presentation::AuthenticatedAccount;
+        use axum::routing::get;
+
+        let router = create_router();
+        let created = create_account(&router, "p256").await;
+        let account_id = created["account_id"].as_str().unwrap();
+
+        let response = send(
+            &router,
+            Method::POST,
+            "/auth/challenge",
+            Some(json!({ "account_id": account_id })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let challenge = body_json(response).await;
+        let message = BASE64_STANDARD
+            .decode(challenge["message_base64"].as_str().unwrap())
+            .unwrap();
+
+        // サーバーの署名 API はチャレンジに署名しない。鍵を持つクライアントが署名する
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/sign",
+            Some(json!({ "message_base64": challenge["message_base64"] })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let verify = json!({
+            "account_id": account_id,
+            "nonce": challenge["nonce"],
+            "signature_base64": sign_locally(&created, &message),
+        });
+        let response = send(&router, Method::POST, "/auth/verify", Some(verify.clone())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let session = body_json(response).await;
+        assert_eq!(session["account_id"], created["account_id"]);
+        let token = session["session_token"].as_str().unwrap().to_string();
+
+        // 同じチャレンジは再利用できない
+        let response = send(&router, Method::POST, "/auth/verify", Some(verify)).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // 別のサービスは公開鍵からトークンを検証する
+        let response = send(&router, Method::GET, "/auth/session-key", None).await;
+        let session_key = BASE64_STANDARD
+            .decode(
+                body_json(response).await["public_key_base64"]
+                    .as_str()
+                    .unwrap(),
+            )
+            .unwrap();
+        let service = Router::new()
+            .route(
+                "/whoami",
+                get(|account: AuthenticatedAccount| async move { account.account_id }),
+            )
+            .with_state(SessionTokenVerifier::from_public_key(&session_key).unwrap());
+
+        let request = Request::builder()
+            .uri("/whoami")
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap();
+        let response = service.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_text(response).await, account_id);
+
+        let response = send(&service, Method::GET, "/whoami", None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = send(
+            &router,
+            Method::POST,
+            "/auth/challenge",
+            Some(json!({ "account_id": "user:abc" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn profile_is_created_read_and_updated() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = AccountKeyStoreConfig::File {
+            path: dir.path().join("account.key"),
+            passphrase: "passphrase".into(),
+        };
+        let router = create_router_with_key_store(&config).unwrap();
+        let profile = json!({
+            "display_name": "Alice",
+            "avatar_content_id": "cid-avatar",
+            "devices": [{ "device_id": "phone", "name": "Phone" }],
+        });
+
+        let created = create_account(&router, "p256").await;
+        let account_id = created["account_id"].as_str().unwrap();
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/profile",
+            Some(profile.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let session = session_header(&router, &created).await;
+        let send_profile = |method: Method, body: Value| {
+            let router = router.clone();
+            let session = session.clone();
+            async move { send_with(&router, method, "/accounts/profile", Some(body), &[session]).await }
+        };
+        let response = send_profile(Method::POST, profile.clone()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = send_profile(Method::POST, profile).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = send_profile(
+            Method::PUT,
+            json!({ "display_name": "Alice B", "devices": [] }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 再起動後も残る
+        drop(router);
+        let router = create_router_with_key_store(&config).unwrap();
+        let response = send(
+            &router,
+            Method::GET,
+            &format!("/accounts/{account_id}/profile"),
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let found = body_json(response).await;
+        assert_eq!(found["account_id"], created["account_id"]);
+        assert_eq!(found["display_name"], "Alice B");
+        assert_eq!(found["avatar_content_id"], Value::Null);
+        assert_eq!(found["devices"], json!([]));
+
+        // 署名はアカウントの公開鍵で検証できる
+        let profile = crate::domain::profile::AccountProfile {
+            account_id: account_id.to_string(),
+            display_name: "Alice B".to_string(),
+            avatar_content_id: None,
+            devices: vec![],
+            updated_at: found["updated_at"].as_u64().unwrap(),
+            signature: BASE64_STANDARD
+                .decode(found["signature_base64"].as_str().unwrap())
+                .unwrap(),
+        };
+        assert!(crate::application_service::AccountService::verify_profile(
+            &profile
+        ));
+
+        let session = session_header(&router, &created).await;
+        let response = send_with(
+            &router,
+            Method::PUT,
+            "/accounts/profile",
+            Some(json!({ "display_name": " " })),
+            &[session],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = send(
+            &router,
+            Method::GET,
+            "/accounts/did:key:zUnknown/profile",
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn deactivated_account_is_listed_as_revoked_across_restarts() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = AccountKeyStoreConfig::File {
+            path: dir.path().join("account.key"),
+            passphrase: "passphrase".into(),
+        };
+        let router = create_router_with_key_store(&config).unwrap();
+        let created = create_account(&router, "k256").await;
+        let account_id = created["account_id"].as_str().unwrap();
+
+        let response = send(
+            &router,
+            Method::GET,
+            &format!("/revocations/{account_id}"),
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["revoked"], false);
+
+        let (nonce, signature) = signed_challenge(&router, &created).await;
+        let verify = json!({
+            "account_id": account_id,
+            "nonce": nonce,
+            "signature_base64": signature,
+        });
+        let session = session_header(&router, &created).await;
+
+        // 鍵の持ち主であることを示さなければ無効化できない
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/deactivate",
+            Some(json!({ "reason": "lost device" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let headers = key_holder_headers(&router, &created).await;
+        let response = send_with(
+            &router,
+            Method::POST,
+            "/accounts/deactivate",
+            Some(json!({ "reason": "lost device" })),
+            &headers,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let record = body_json(response).await;
+        assert_eq!(record["account_id"], created["account_id"]);
+        assert_eq!(record["public_key_base64"], created["public_key_base64"]);
+        assert_eq!(record["reason"], "lost device");
+
+        // 鍵は残るが、署名にも失効前のセッションにも使えない
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/sign",
+            Some(json!({ "message_base64": BASE64_STANDARD.encode(b"hello") })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = send_with(
+            &router,
+            Method::POST,
+            &format!("/accounts/{account_id}/sign"),
+            Some(json!({ "message_base64": BASE64_STANDARD.encode(b"hello") })),
+            std::slice::from_ref(&session),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = send_with(
+            &router,
+            Method::POST,
+            "/accounts/deactivate",
+            None,
+            &[session],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // 失効前に署名したチャレンジでもセッションを発行しない
+        let response = send(&router, Method::POST, "/auth/verify", Some(verify)).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        drop(router);
+        let router = create_router_with_key_store(&config).unwrap();
+        let response = send(
+            &router,
+            Method::GET,
+            &format!("/revocations/{account_id}"),
+            None,
+        )
+        .await;
+        let status = body_json(response).await;
+        assert_eq!(status["revoked"], true);
+        assert_eq!(status["record"], record);
+
+        let response = send(&router, Method::GET, "/revocations", None).await;
+        assert_eq!(body_json(response).await["revocations"], json!([record]));
+    }
+
+    #[tokio::test]
+    async fn derived_keys_are_returned_and_sign_per_path() {
+        let router = create_router();
+        let created = create_account(&router, "p256").await;
+
+        let derive = |path: &'static str| {
+            let router = router.clone();
+            async move {
+                send(
+                    &router,
+                    Method::POST,
+                    "/accounts/derive",
+                    Some(json!({ "path": path })),
+                )
+                .await
+            }
+        };
+        let response = derive("content/bafy-content").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let derived = body_json(response).await;
+        assert_eq!(derived["account_id"], created["account_id"]);
+        assert_eq!(derived["path"], "content/bafy-content");
+        assert_ne!(derived["public_key_base64"], created["public_key_base64"]);
+        assert_eq!(
+            body_json(derive("content/bafy-content").await).await,
+            derived
+        );
+
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/derive/sign",
+            Some(json!({
+                "path": "content/bafy-content",
+                "message_base64": BASE64_STANDARD.encode(b"hello"),
+            })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let signed = body_json(response).await;
+        assert_eq!(signed["key_id"], derived["key_id"]);
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/verify",
+            Some(json!({
+                "key_type": "p256",
+                "public_key_base64": derived["public_key_base64"],
+                "message_base64": BASE64_STANDARD.encode(b"hello"),
+                "signature_base64": signed["signature_base64"],
+            })),
+        )
+        .await;
+        assert_eq!(body_json(response).await["valid"], true);
+
+        let response = derive("content//x").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        send(&router, Method::DELETE, "/accounts", None).await;
+        let response = derive("content/bafy-content").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn encryption_key_is_published_signed_and_exported_to_the_key_holder() {
+        use crate::application_service::AccountService;
+        use crate::domain::encryption_key::AccountEncryptionKey;
+
+        let router = create_router();
+        let created = create_account(&router, "k256").await;
+        let account_id = created["account_id"].as_str().unwrap();
+
+        let response = send(
+            &router,
+            Method::GET,
+            &format!("/accounts/{account_id}/encryption-key"),
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let published = body_json(response).await;
+        assert_eq!(published["kem"], "DHKEM(P-256, HKDF-SHA256)");
+        assert_ne!(published["public_key_base64"], created["public_key_base64"]);
+        let decode = |field: &str| {
+            BASE64_STANDARD
+                .decode(published[field].as_str().unwrap())
+                .unwrap()
+        };
+        assert!(AccountService::verify_encryption_key(
+            &AccountEncryptionKey {
+                account_id: account_id.to_string(),
+                kem: published["kem"].as_str().unwrap().to_string(),
+                public_key: decode("public_key_base64"),
+                signature: decode("signature_base64"),
+            }
+        ));
+
+        let response = send(
+            &router,
+            Method::GET,
+            "/accounts/did:key:zUnknown/encryption-key",
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // 秘密鍵の書き出しには鍵の持ち主であることの証明が必要
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/encryption-key/export",
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let headers = key_holder_headers(&router, &created).await;
+        let response = send_with(
+            &router,
+            Method::POST,
+            "/accounts/encryption-key/export",
+            None,
+            &headers,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let exported = body_json(response).await;
+        assert_eq!(
+            exported["public_key_base64"],
+            published["public_key_base64"]
+        );
+        let secret_key = BASE64_STANDARD
+            .decode(exported["secret_key_base64"].as_str().unwrap())
+            .unwrap();
+        let public_key = p256::SecretKey::from_slice(&secret_key)
+            .unwrap()
+            .public_key();
+        assert_eq!(
+            p256::elliptic_curve::sec1::ToEncodedPoint::to_encoded_point(&public_key, false)
+                .as_bytes(),
+            decode("public_key_base64")
+        );
+    }
+
+    #[tokio::test]
+    async fn key_operations_are_rate_limited_and_queryable() {
+        use super::{create_app_with_options, AppOptions};
+        use crate::infrastructure::key_operation_limiter::KeyOperationLimitConfig;
+
+        let (router, _) = create_app_with_options(
+            &AccountKeyStoreConfig::InMemory,
+            AppOptions {
+                key_operation_limit: Some(KeyOperationLimitConfig::new(1, 2)),
+                ..AppOptions::default()
+            },
+        )
+        .unwrap();
+        let created = create_account(&router, "k256").await;
+        let sign = json!({ "message_base64": BASE64_STANDARD.encode(b"hello") });
+
+        for expected in [
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::TOO_MANY_REQUESTS,
+        ] {
+            let response = send(&router, Method::POST, "/accounts/sign", Some(sign.clone())).await;
+            assert_eq!(response.status(), expected);
+        }
+        // 公開鍵だけの書き出しは秘密鍵の操作に含めない
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/export",
+            Some(json!({ "format": "pem" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = key_holder_headers(&router, &created).await;
+        let response = send_with(&router, Method::POST, "/accounts/mnemonic", None, &headers).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let response = send(&router, Method::GET, "/audit/key-operations", None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let session = session_header(&router, &created).await;
+        let query = |uri: &'static str| {
+            let router = router.clone();
+            let session = session.clone();
+            async move { send_with(&router, Method::GET, uri, None, &[session]).await }
+        };
+
+        let response = query("/audit/key-operations").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let audit = body_json(response).await;
+        assert_eq!(
+            audit["counts"],
+            json!({ "succeeded": 2, "failed": 0, "rate_limited": 2 })
+        );
+        assert_eq!(audit["entries"][0].get("principal"), None);
+        assert_eq!(audit["entries"][3]["operation"], "export_mnemonic");
+        assert_eq!(audit["entries"][3]["principal"], created["account_id"]);
+
+        let response = query("/audit/key-operations?operation=sign&limit=1").await;
+        let audit = body_json(response).await;
+        assert_eq!(audit["entries"].as_array().unwrap().len(), 1);
+        assert_eq!(audit["entries"][0]["outcome"], "rate_limited");
+
+        let response = query("/audit/key-operations?operation=decrypt").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // 鍵を入れ替えた後は、前のアカウントのセッションでは引けない
+        let headers = key_holder_headers(&router, &created).await;
+        let response = send_with(&router, Method::POST, "/accounts/rotate", None, &headers).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = query("/audit/key-operations").await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn keystore_file_moves_account_between_routers() {
+        use super::{create_app_with_options, AppOptions};
+        use crate::infrastructure::file_key_store::ScryptParams;
+
+        let options = AppOptions {
+            keystore_kdf: ScryptParams {
+                log_n: 4,
+                r: 8,
+                p: 1,
+            },
+            ..AppOptions::default()
+        };
+        let (router, _) =
+            create_app_with_options(&AccountKeyStoreConfig::InMemory, options).unwrap();
+        let created = create_account(&router, "p256").await;
+
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/keystore/export",
+            Some(json!({ "passphrase": "correct horse" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = send_with(
+            &router,
+            Method::POST,
+            "/accounts/keystore/export",
+            Some(json!({ "passphrase": "correct horse" })),
+            &key_holder_headers(&router, &created).await,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let keystore = body_json(response).await;
+        assert_eq!(keystore["version"], 3);
+        assert_eq!(keystore["crypto"]["kdf"], "scrypt");
+        assert_eq!(keystore["account_id"], created["account_id"]);
+
+        let other = create_router();
+        let response = send(
+            &other,
+            Method::POST,
+            "/accounts/keystore/import",
+            Some(json!({ "keystore": keystore, "passphrase": "wrong" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = send(
+            &other,
+            Method::POST,
+            "/accounts/keystore/import",
+            Some(json!({ "keystore": keystore, "passphrase": "correct horse" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let imported = body_json(response).await;
+        assert_eq!(imported["account_id"], created["account_id"]);
+        assert_eq!(imported["algorithm"], "P256");
+
+        // 鍵のある端末では、鍵の持ち主しか置き換えられない
+        let response = send(
+            &other,
+            Method::POST,
+            "/accounts/keystore/import",
+            Some(json!({ "keystore": keystore, "passphrase": "correct horse" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = send_with(
+            &router,
+            Method::POST,
+            "/accounts/keystore/export",
+            Some(json!({ "passphrase": "" })),
+            &key_holder_headers(&router, &created).await,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn public_key_is_looked_up_by_account_id() {
+        let router = create_router();
+
+        let created = create_account(&router, "p256").await;
+        let account_id = created["account_id"].as_str().unwrap();
+        assert!(account_id.starts_with("did:key:zDn"));
+
+        let response = send(
+            &router,
+            Method::GET,
+            &format!("/accounts/{account_id}/public-key"),
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let found = body_json(response).await;
+        assert_eq!(found["account_id"], created["account_id"]);
+        assert_eq!(found["algorithm"], "P256");
+        assert_eq!(found["public_key_base64"], created["public_key_base64"]);
+
+        // 別のサーバーのアカウント（K256 を含む）の公開鍵も識別子から引ける
+        let other = create_account(&create_router(), "k256").await;
+        let response = send(
+            &router,
+            Method::GET,
+            &format!(
+                "/accounts/{}/public-key",
+                other["account_id"].as_str().unwrap()
+            ),
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let found = body_json(response).await;
+        assert_eq!(found["algorithm"], "K256");
+        assert_eq!(found["public_key_base64"], other["public_key_base64"]);
+
+        // did:key でない識別子は 400
+        let response = send(
+            &router,
+            Method::GET,
+            "/accounts/did:web:example.com/public-key",
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(body_text(response).await.starts_with("invalid did:key"));
+    }
+
+    #[tokio::test]
+    async fn invalid_requests_are_rejected() {
+        let router = create_router();
+
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts",
+            Some(json!({ "key_type": "ed25519" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_text(response).await, "unsupported key_type: ED25519");
+
+        create_account(&router, "p256").await;
+        let response = send(
+            &router,
+            Method::POST,
+            "/accounts/sign",
+            Some(json!({ "message_base64": "***" })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(body_text(response)
+            .await
+            .starts_with("invalid message_base64"));
+
+        // 必須フィールドの欠落は axum の extractor が 422 で拒否する
+        let response = send(&router, Method::POST, "/accounts", Some(json!({}))).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn delegate_token_issues_jwt_and_validates_input() {
+        let issuer = create_router();
+        let recipient = create_account(&create_router(), "p256").await;
+        let recipient_public_key = recipient["public_key_base64"].clone();
+
+        // 発行者のアカウントがなければ 404
+        let request = json!({
+            "recipient_public_key_base64": recipient_public_key,
+            "content_id": "cid-123",
+            "capabilities": ["read"],
+            "ttl_secs": 60,
+        });
+        let response = send(
+            &issuer,
+            Method::POST,
+            "/issuer/delegate",
+            Some(request.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        create_account(&issuer, "p256").await;
+        let response = send(
+            &issuer,
+            Method::POST,
+            "/issuer/delegate",
+            Some(request.clone()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let issued = body_json(response).await;
+        assert_eq!(
+            issued["delegated_token"]
+                .as_str()
+                .unwrap()
+                .split('.')
+                .count(),
+            3
+        );
+        assert_eq!(
+            issued["expires_at"].as_u64().unwrap() - issued["issued_at"].as_u64().unwrap(),
+            60
+        );
+        assert!(!issued["jti"].as_str().unwrap().is_empty());
+
+        let mut unknown_capability = request.clone();
+        unknown_capability["capabilities"] = json!(["admin"]);
+        let response = send(
+            &issuer,
+            Method::POST,
+            "/issuer/delegate",
+            Some(unknown_capability),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_text(response).await, "unsupported capability: admin");
 
-    Router::new().merge(account::routes()).with_state(state)
+        let mut zero_ttl = request;
+        zero_ttl["ttl_secs"] = json!(0);
+        let response = send(&issuer, Method::POST, "/issuer/delegate", Some(zero_ttl)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
@@ -0,0 +1,157 @@
+//! アカウントのプロフィール。
+//!
+//! プロフィールはアカウント鍵で署名して保存し、応答の `signature_base64` で返す。
+//! 受け取った側は did:key の公開鍵で `monas-account-profile:v1` の署名文を検証できる。
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Router,
+};
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::application_service::{AccountService, ManageProfileError, ProfileFields};
+use crate::domain::profile::{AccountDevice, AccountProfile};
+
+use super::{AppState, AuthenticatedAccount};
+
+#[derive(Deserialize)]
+pub struct ProfileRequest {
+    pub display_name: String,
+    #[serde(default)]
+    pub avatar_content_id: Option<String>,
+    #[serde(default)]
+    pub devices: Vec<DeviceBody>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeviceBody {
+    pub device_id: String,
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct ProfileResponse {
+    pub account_id: String,
+    pub display_name: String,
+    pub avatar_content_id: Option<String>,
+    pub devices: Vec<DeviceBody>,
+    pub updated_at: u64,
+    /// アカウント鍵によるプロフィールへの署名（base64）。
+    pub signature_base64: String,
+}
+
+impl From<ProfileRequest> for ProfileFields {
+    fn from(req: ProfileRequest) -> Self {
+        Self {
+            display_name: req.display_name,
+            avatar_content_id: req.avatar_content_id,
+            devices: req
+                .devices
+                .into_iter()
+                .map(|device| AccountDevice {
+                    device_id: device.device_id,
+                    name: device.name,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<AccountProfile> for ProfileResponse {
+    fn from(profile: AccountProfile) -> Self {
+        Self {
+            account_id: profile.account_id,
+            display_name: profile.display_name,
+            avatar_content_id: profile.avatar_content_id,
+            devices: profile
+                .devices
+                .into_iter()
+                .map(|device| DeviceBody {
+                    device_id: device.device_id,
+                    name: device.name,
+                })
+                .collect(),
+            updated_at: profile.updated_at,
+            signature_base64: BASE64_STANDARD.encode(&profile.signature),
+        }
+    }
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/accounts/profile",
+            post(create_profile).put(update_profile),
+        )
+        .route("/accounts/{account_id}/profile", get(get_profile))
+}
+
+fn profile_error(e: ManageProfileError) -> (StatusCode, String) {
+    let status = match e {
+        ManageProfileError::NotFound | ManageProfileError::ProfileNotFound => StatusCode::NOT_FOUND,
+        ManageProfileError::AlreadyExists => StatusCode::CONFLICT,
+        ManageProfileError::Validation(_) => StatusCode::BAD_REQUEST,
+        ManageProfileError::KeyStore(_)
+        | ManageProfileError::Repository(_)
+        | ManageProfileError::InvalidPublicKey(_)
+        | ManageProfileError::InvalidKey(_)
+        | ManageProfileError::InvalidSignature
+        | ManageProfileError::Time(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, e.to_string())
+}
+
+/// 保存済みアカウントのプロフィールを作る。既にある場合は 409。
+///
+/// アカウント鍵で署名するため、そのアカウントのセッションが必要。
+async fn create_profile(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedAccount,
+    Json(req): Json<ProfileRequest>,
+) -> Result<Json<ProfileResponse>, (StatusCode, String)> {
+    caller.ensure_stored_account(&state)?;
+    let profile = AccountService::create_profile(
+        &state.key_store,
+        &state.profiles,
+        &state.events,
+        req.into(),
+    )
+    .map_err(profile_error)?;
+    Ok(Json(profile.into()))
+}
+
+/// 保存済みアカウントのプロフィールを置き換える。省略した項目は空になる。
+///
+/// 作成と同じく、そのアカウントのセッションが必要。
+async fn update_profile(
+    State(state): State<Arc<AppState>>,
+    caller: AuthenticatedAccount,
+    Json(req): Json<ProfileRequest>,
+) -> Result<Json<ProfileResponse>, (StatusCode, String)> {
+    caller.ensure_stored_account(&state)?;
+    let profile = AccountService::update_profile(
+        &state.key_store,
+        &state.profiles,
+        &state.events,
+        req.into(),
+    )
+    .map_err(profile_error)?;
+    Ok(Json(profile.into()))
+}
+
+/// アカウント識別子（did:key）からプロフィールを引く。SDK が共有相手の表示に使う。
+async fn get_profile(
+    State(state): State<Arc<AppState>>,
+    Path(account_id): Path<String>,
+) -> Result<Json<ProfileResponse>, (StatusCode, String)> {
+    let profile = AccountService::get_profile(&state.profiles, &account_id)
+        .map_err(profile_error)?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "profile not found".to_string()))?;
+    Ok(Json(profile.into()))
+}
@@ -0,0 +1,131 @@
+//! アカウントの無効化と鍵の失効リスト。
+//!
+//! `GET /revocations/{account_id}` は認証なしで引ける。monas-content の共有処理や
+//! ステートノードは、アカウント鍵の署名を受け入れる前にここで失効を確認する。
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Router,
+};
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::application_service::{AccountService, DeactivateAccountError, KeyRevocationList};
+use crate::domain::revocation::KeyRevocationRecord;
+use crate::infrastructure::key_pair::KeyAlgorithm;
+
+use super::{AppState, KeyHolder};
+
+#[derive(Deserialize)]
+pub struct DeactivateAccountRequest {
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct RevocationRecordResponse {
+    pub account_id: String,
+    pub algorithm: String,
+    pub public_key_base64: String,
+    pub revoked_at: u64,
+    pub reason: Option<String>,
+    /// 失効させた鍵による失効文への署名（base64）。
+    pub signature_base64: String,
+}
+
+#[derive(Serialize)]
+pub struct RevocationStatusResponse {
+    pub account_id: String,
+    pub revoked: bool,
+    pub record: Option<RevocationRecordResponse>,
+}
+
+#[derive(Serialize)]
+pub struct RevocationListResponse {
+    pub revocations: Vec<RevocationRecordResponse>,
+}
+
+impl From<KeyRevocationRecord> for RevocationRecordResponse {
+    fn from(record: KeyRevocationRecord) -> Self {
+        Self {
+            account_id: record.account_id,
+            algorithm: match record.algorithm {
+                KeyAlgorithm::K256 => "K256",
+                KeyAlgorithm::P256 => "P256",
+            }
+            .to_string(),
+            public_key_base64: BASE64_STANDARD.encode(&record.public_key),
+            revoked_at: record.revoked_at,
+            reason: record.reason,
+            signature_base64: BASE64_STANDARD.encode(&record.signature),
+        }
+    }
+}
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/accounts/deactivate", post(deactivate_account))
+        .route("/revocations", get(list_revocations))
+        .route("/revocations/{account_id}", get(get_revocation_status))
+}
+
+/// 保存済みアカウントを無効化し、署名済みの失効記録を返す。本文（`reason`）は省略できる。
+///
+/// セッションと新しいチャレンジへの署名（[`KeyHolder`]）が必要。鍵は削除せず、以降の
+/// 署名・セッション・チャレンジ認証は失効リストで拒否される。
+async fn deactivate_account(
+    State(state): State<Arc<AppState>>,
+    _holder: KeyHolder,
+    req: Option<Json<DeactivateAccountRequest>>,
+) -> Result<Json<RevocationRecordResponse>, (StatusCode, String)> {
+    let reason = req.and_then(|Json(req)| req.reason);
+    let record =
+        AccountService::deactivate(&state.key_store, &state.revocations, reason).map_err(|e| {
+            let status = match e {
+                DeactivateAccountError::NotFound => StatusCode::NOT_FOUND,
+                DeactivateAccountError::AlreadyRevoked => StatusCode::CONFLICT,
+                DeactivateAccountError::Validation(_) => StatusCode::BAD_REQUEST,
+                DeactivateAccountError::KeyStore(_)
+                | DeactivateAccountError::RevocationList(_)
+                | DeactivateAccountError::InvalidKey(_)
+                | DeactivateAccountError::InvalidPublicKey(_)
+                | DeactivateAccountError::Time(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, e.to_string())
+        })?;
+
+    Ok(Json(record.into()))
+}
+
+async fn list_revocations(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<RevocationListResponse>, (StatusCode, String)> {
+    let revocations = state
+        .revocations
+        .list()
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(RevocationListResponse {
+        revocations: revocations.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// `account_id` が失効しているかを返す。失効していなければ `revoked: false`。
+async fn get_revocation_status(
+    State(state): State<Arc<AppState>>,
+    Path(account_id): Path<String>,
+) -> Result<Json<RevocationStatusResponse>, (StatusCode, String)> {
+    let record = AccountService::find_revocation(&state.revocations, &account_id)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(RevocationStatusResponse {
+        account_id,
+        revoked: record.is_some(),
+        record: record.map(Into::into),
+    }))
+}
@@ -0,0 +1,49 @@
+//! サーバー終了時の処理。
+//!
+//! SIGINT / SIGTERM を受け取ったら新しい接続の受け付けをやめ、処理中のリクエストを
+//! 終えてから [`ShutdownHook::flush`] で、鍵ストアとローテーション記録・プロフィール・
+//! 失効リスト・監査記録を永続化する。
+
+use std::sync::Arc;
+
+use crate::application_service::{
+    AccountKeyStore, AccountKeyStoreError, KeyAuditLog, KeyAuditLogError, KeyRevocationList,
+    KeyRevocationListError, KeyRotationLog, KeyRotationLogError, ProfileRepository,
+    ProfileRepositoryError,
+};
+
+use super::AppState;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShutdownError {
+    #[error("failed to flush key store: {0}")]
+    KeyStore(#[from] AccountKeyStoreError),
+    #[error("failed to flush key rotation log: {0}")]
+    RotationLog(#[from] KeyRotationLogError),
+    #[error("failed to flush profiles: {0}")]
+    Profiles(#[from] ProfileRepositoryError),
+    #[error("failed to flush revocation list: {0}")]
+    Revocations(#[from] KeyRevocationListError),
+    #[error("failed to flush key audit log: {0}")]
+    KeyAudit(#[from] KeyAuditLogError),
+}
+
+/// ルーターと同じストア一式を保持し、終了前に永続化するフック。
+#[derive(Clone)]
+pub struct ShutdownHook {
+    pub(super) state: Arc<AppState>,
+}
+
+impl ShutdownHook {
+    /// 鍵ストアと各記録の未書き込みのデータを永続化する。
+    ///
+    /// 処理中のリクエストがすべて終わった後に呼び出すこと。
+    pub fn flush(&self) -> Result<(), ShutdownError> {
+        self.state.key_store.flush()?;
+        self.state.rotation_log.flush()?;
+        self.state.profiles.flush()?;
+        self.state.revocations.flush()?;
+        self.state.key_audit.flush()?;
+        Ok(())
+    }
+}
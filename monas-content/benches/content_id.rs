@@ -0,0 +1,55 @@
+//! ContentId 生成のハッシュアルゴリズム（SHA-256 / BLAKE3）の比較ベンチマーク。
+//!
+//! 実行: `cargo bench -p monas-content --bench content_id`
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use monas_content::domain::content_id::{ContentIdAlgorithm, ContentIdGenerator};
+use monas_content::infrastructure::content_id::ConfigurableContentIdGenerator;
+
+/// 代表的なファイルサイズ（小さなテキスト〜大きめのメディアファイル）。
+const SIZES: &[(&str, usize)] = &[
+    ("1KiB", 1024),
+    ("64KiB", 64 * 1024),
+    ("1MiB", 1024 * 1024),
+    ("16MiB", 16 * 1024 * 1024),
+];
+
+const ALGORITHMS: &[ContentIdAlgorithm] = &[ContentIdAlgorithm::Sha256, ContentIdAlgorithm::Blake3];
+
+fn bench_generate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("content_id/generate");
+    for &(label, size) in SIZES {
+        let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        group.throughput(Throughput::Bytes(size as u64));
+        for &algorithm in ALGORITHMS {
+            let generator = ConfigurableContentIdGenerator::new(algorithm);
+            group.bench_with_input(
+                BenchmarkId::new(algorithm.as_str(), label),
+                &data,
+                |b, data| b.iter(|| generator.generate(black_box(data))),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_generate_encrypted(c: &mut Criterion) {
+    let mut group = c.benchmark_group("content_id/generate_encrypted");
+    for &(label, size) in SIZES {
+        let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        group.throughput(Throughput::Bytes(size as u64));
+        for &algorithm in ALGORITHMS {
+            let generator = ConfigurableContentIdGenerator::new(algorithm);
+            let plain_cid = generator.generate(b"plain");
+            group.bench_with_input(
+                BenchmarkId::new(algorithm.as_str(), label),
+                &data,
+                |b, data| b.iter(|| generator.generate_encrypted(&plain_cid, black_box(data))),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_generate, bench_generate_encrypted);
+criterion_main!(benches);
@@ -0,0 +1,182 @@
+//! 100MB のコンテンツを fetch したときのメモリ確保量を計測するベンチマーク。
+//!
+//! リポジトリから読み込んだ暗号文をコピーして復号する従来の経路（`Content::decrypt`）と、
+//! 暗号文バッファをそのまま平文として再利用する `ContentService::fetch` の経路を比較する。
+//! グローバルアロケータで確保バイト数・回数を数えるため、criterion は使わない。
+//!
+//! 実行: `cargo bench -p monas-content --bench fetch`
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use monas_content::application_service::content_service::{
+    ContentEncryptionKeyStore, ContentRepository, ContentRepositoryError, ContentService,
+    CreateContentCommand, MultiStorageContentRepository, NoOpEventPublisher,
+};
+use monas_content::domain::content::Content;
+use monas_content::domain::content_id::ContentId;
+use monas_content::infrastructure::content_id::Sha256ContentIdGenerator;
+use monas_content::infrastructure::encryption::{
+    Aes256CtrContentEncryption, OsRngContentEncryptionKeyGenerator,
+};
+use monas_content::infrastructure::fs_content_repository::FsContentRepository;
+use monas_content::infrastructure::key_store::InMemoryContentEncryptionKeyStore;
+
+const CONTENT_SIZE: usize = 100 * 1024 * 1024;
+const PROVIDER: &str = "fs";
+
+struct CountingAllocator;
+
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// `f` の実行中に確保されたバイト数・回数と経過時間を出力する。
+fn measure<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let bytes_before = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let count_before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes_before;
+    let count = ALLOCATIONS.load(Ordering::Relaxed) - count_before;
+    println!(
+        "{label:<28} allocated {:>8.1} MiB in {count:>5} allocations ({elapsed:.2?})",
+        bytes as f64 / (1024.0 * 1024.0),
+    );
+    result
+}
+
+/// `FsContentRepository` を単一プロバイダーの MultiStorageContentRepository として扱う。
+struct SingleProviderRepository(FsContentRepository);
+
+impl ContentRepository for SingleProviderRepository {
+    fn save(
+        &self,
+        content_id: &ContentId,
+        content: &Content,
+    ) -> Result<(), ContentRepositoryError> {
+        self.0.save(content_id, content)
+    }
+
+    fn find_by_id(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Option<Content>, ContentRepositoryError> {
+        self.0.find_by_id(content_id)
+    }
+}
+
+impl MultiStorageContentRepository for SingleProviderRepository {
+    fn save_to(
+        &self,
+        _provider: &str,
+        content_id: &ContentId,
+        content: &Content,
+    ) -> Result<(), ContentRepositoryError> {
+        self.save(content_id, content)
+    }
+
+    fn find_from(
+        &self,
+        _provider: &str,
+        content_id: &ContentId,
+    ) -> Result<Option<Content>, ContentRepositoryError> {
+        self.find_by_id(content_id)
+    }
+
+    fn connected_providers(&self) -> Result<Vec<String>, ContentRepositoryError> {
+        Ok(vec![PROVIDER.to_string()])
+    }
+
+    fn default_provider(&self) -> Result<String, ContentRepositoryError> {
+        Ok(PROVIDER.to_string())
+    }
+
+    fn connect_provider(
+        &self,
+        _provider: &str,
+        _access_token: String,
+    ) -> Result<(), ContentRepositoryError> {
+        Ok(())
+    }
+
+    fn disconnect_provider(&self, _provider: &str) -> Result<(), ContentRepositoryError> {
+        Ok(())
+    }
+}
+
+fn main() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let repository = FsContentRepository::open(dir.path()).expect("open repository");
+    let cek_store = InMemoryContentEncryptionKeyStore::default();
+    let service = ContentService {
+        content_id_generator: Sha256ContentIdGenerator,
+        content_repository: SingleProviderRepository(repository.clone()),
+        key_generator: OsRngContentEncryptionKeyGenerator,
+        encryptor: Aes256CtrContentEncryption,
+        cek_store: cek_store.clone(),
+        event_publisher: NoOpEventPublisher,
+        quota: Default::default(),
+        chunking: Default::default(),
+        idempotency: Default::default(),
+        metrics: Default::default(),
+    };
+
+    let raw_content: Vec<u8> = (0..CONTENT_SIZE).map(|i| (i % 251) as u8).collect();
+    let content_id = service
+        .create(CreateContentCommand {
+            name: "large.bin".into(),
+            path: "bench/large.bin".into(),
+            raw_content,
+            provider: None,
+            content_sha256: None,
+        })
+        .expect("create")
+        .content_id;
+
+    println!("fetching {} MiB", CONTENT_SIZE / (1024 * 1024));
+
+    // 従来の経路: 読み込んだ暗号文を残したまま、平文用のバッファを別に確保して復号する
+    let copied = measure("find + Content::decrypt", || {
+        let content = repository
+            .find_by_id(&content_id)
+            .expect("find")
+            .expect("content exists");
+        let key = cek_store
+            .load(&content_id)
+            .expect("load key")
+            .expect("key exists");
+        content
+            .decrypt(&key, &Aes256CtrContentEncryption)
+            .expect("decrypt")
+    });
+
+    // 新しい経路: 暗号文のバッファ上で復号し、Bytes のまま返す
+    let fetched = measure("ContentService::fetch", || {
+        service.fetch(content_id.clone(), None).expect("fetch")
+    });
+
+    assert_eq!(fetched.raw_content, copied);
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::content::Content;
+use crate::domain::share::KeyEnvelope;
+
+/// 現在のバンドル形式のバージョン。
+///
+/// 互換性のない変更を行った場合はインクリメントし、import 側で古い形式を拒否する。
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// バンドルのシリアライズ形式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BundleFormat {
+    /// バイナリでコンパクトな CBOR（デフォルト）。
+    #[default]
+    Cbor,
+    /// 人が確認しやすい JSON。
+    Json,
+}
+
+/// オフラインで Monas インスタンス間を持ち運ぶためのコンテンツバンドル。
+///
+/// - `content` は暗号文とメタデータのみを含む（平文は `Content` のシリアライズ対象外）。
+/// - CEK そのものは含めず、受信者ごとにラップされた `KeyEnvelope` として同梱する。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentBundle {
+    pub format_version: u32,
+    pub content: Content,
+    #[serde(default)]
+    pub envelopes: Vec<KeyEnvelope>,
+}
+
+impl ContentBundle {
+    pub fn new(content: Content, envelopes: Vec<KeyEnvelope>) -> Self {
+        Self {
+            format_version: BUNDLE_FORMAT_VERSION,
+            content,
+            envelopes,
+        }
+    }
+
+    /// 指定形式でバイト列にエンコードする。
+    pub fn encode(&self, format: BundleFormat) -> Result<Vec<u8>, BundleCodecError> {
+        match format {
+            BundleFormat::Cbor => {
+                let mut buf = Vec::new();
+                ciborium::into_writer(self, &mut buf)
+                    .map_err(|e| BundleCodecError::Encode(e.to_string()))?;
+                Ok(buf)
+            }
+            BundleFormat::Json => {
+                serde_json::to_vec(self).map_err(|e| BundleCodecError::Encode(e.to_string()))
+            }
+        }
+    }
+
+    /// 指定形式のバイト列からデコードする。
+    pub fn decode(bytes: &[u8], format: BundleFormat) -> Result<Self, BundleCodecError> {
+        match format {
+            BundleFormat::Cbor => {
+                ciborium::from_reader(bytes).map_err(|e| BundleCodecError::Decode(e.to_string()))
+            }
+            BundleFormat::Json => {
+                serde_json::from_slice(bytes).map_err(|e| BundleCodecError::Decode(e.to_string()))
+            }
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleCodecError {
+    #[error("failed to encode bundle: {0}")]
+    Encode(String),
+    #[error("failed to decode bundle: {0}")]
+    Decode(String),
+}
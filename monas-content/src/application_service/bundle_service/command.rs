@@ -0,0 +1,37 @@
+use crate::domain::{content::metadata::Metadata, content_id::ContentId};
+
+use super::BundleFormat;
+
+/// バンドルエクスポートユースケースの入力。
+#[derive(Debug)]
+pub struct ExportBundleCommand {
+    pub content_id: ContentId,
+    pub format: BundleFormat,
+}
+
+/// バンドルエクスポートユースケースの出力。
+#[derive(Debug)]
+pub struct ExportBundleResult {
+    pub content_id: ContentId,
+    /// エンコード済みのバンドル本体。
+    pub bundle: Vec<u8>,
+    /// 同梱した KeyEnvelope の数。
+    pub envelope_count: usize,
+}
+
+/// バンドルインポートユースケースの入力。
+#[derive(Debug)]
+pub struct ImportBundleCommand {
+    pub bundle: Vec<u8>,
+    pub format: BundleFormat,
+}
+
+/// バンドルインポートユースケースの出力。
+#[derive(Debug)]
+pub struct ImportBundleResult {
+    pub content_id: ContentId,
+    pub series_id: ContentId,
+    pub metadata: Metadata,
+    /// 取り込んだ KeyEnvelope の数。
+    pub envelope_count: usize,
+}
@@ -0,0 +1,7 @@
+mod bundle;
+mod command;
+mod service;
+
+pub use bundle::*;
+pub use command::*;
+pub use service::*;
@@ -0,0 +1,359 @@
+use crate::application_service::content_service::{ContentRepository, ContentRepositoryError};
+use crate::application_service::share_service::{
+    KeyEnvelopeRepository, KeyEnvelopeRepositoryError,
+};
+use crate::domain::content_id::{ContentId, ContentIdGenerator};
+
+use super::{
+    BundleCodecError, ContentBundle, ExportBundleCommand, ExportBundleResult, ImportBundleCommand,
+    ImportBundleResult, BUNDLE_FORMAT_VERSION,
+};
+
+/// 暗号化済みコンテンツをバンドルとして書き出し・取り込みするアプリケーションサービス。
+///
+/// - バンドルは暗号文・メタデータ・KeyEnvelope をまとめたもので、CEK の平文は含まない。
+/// - 取り込み時は encCid を再計算して暗号文の改ざんを検出するため、
+///   書き出し側と同じ ContentId 生成方式の `content_id_generator` を渡すこと。
+pub struct BundleService<G, R, ER> {
+    pub content_id_generator: G,
+    pub content_repository: R,
+    pub key_envelope_repository: ER,
+}
+
+impl<G, R, ER> BundleService<G, R, ER>
+where
+    G: ContentIdGenerator,
+    R: ContentRepository,
+    ER: KeyEnvelopeRepository,
+{
+    /// コンテンツと関連する KeyEnvelope をバンドルとしてエクスポートする。
+    pub fn export(&self, cmd: ExportBundleCommand) -> Result<ExportBundleResult, BundleError> {
+        let content = self
+            .content_repository
+            .find_by_id(&cmd.content_id)
+            .map_err(BundleError::ContentRepository)?
+            .ok_or(BundleError::ContentNotFound)?;
+
+        if content.is_deleted() {
+            return Err(BundleError::ContentDeleted);
+        }
+        if content.encrypted_content().is_none() {
+            return Err(BundleError::MissingEncryptedContent);
+        }
+
+        let envelopes = self
+            .key_envelope_repository
+            .list_by_content(content.raw_id())
+            .map_err(BundleError::KeyEnvelopeRepository)?;
+        let envelope_count = envelopes.len();
+
+        let content_id = content.raw_id().clone();
+        let bundle = ContentBundle::new(content, envelopes)
+            .encode(cmd.format)
+            .map_err(BundleError::Codec)?;
+
+        Ok(ExportBundleResult {
+            content_id,
+            bundle,
+            envelope_count,
+        })
+    }
+
+    /// バンドルを検証し、ローカルのリポジトリへインストールする。
+    ///
+    /// 検証に失敗した場合は何も保存しない。同じ ContentId のコンテンツが
+    /// 既に存在する場合は上書きせずにエラーとする。
+    pub fn import(&self, cmd: ImportBundleCommand) -> Result<ImportBundleResult, BundleError> {
+        let bundle = ContentBundle::decode(&cmd.bundle, cmd.format).map_err(BundleError::Codec)?;
+        self.validate_bundle(&bundle)?;
+
+        let content = bundle.content;
+        let content_id = content.raw_id().clone();
+
+        let existing = self
+            .content_repository
+            .find_by_id(&content_id)
+            .map_err(BundleError::ContentRepository)?;
+        if existing.is_some() {
+            return Err(BundleError::AlreadyExists(content_id));
+        }
+
+        self.content_repository
+            .save(&content_id, &content)
+            .map_err(BundleError::ContentRepository)?;
+
+        for envelope in &bundle.envelopes {
+            self.key_envelope_repository
+                .save(envelope)
+                .map_err(BundleError::KeyEnvelopeRepository)?;
+        }
+
+        Ok(ImportBundleResult {
+            content_id,
+            series_id: content.series_id().clone(),
+            metadata: content.metadata().clone(),
+            envelope_count: bundle.envelopes.len(),
+        })
+    }
+
+    /// バンドルの形式・暗号文の整合性・KeyEnvelope の対象を検証する。
+    fn validate_bundle(&self, bundle: &ContentBundle) -> Result<(), BundleError> {
+        if bundle.format_version != BUNDLE_FORMAT_VERSION {
+            return Err(BundleError::UnsupportedVersion(bundle.format_version));
+        }
+
+        let content = &bundle.content;
+        if content.is_deleted() {
+            return Err(BundleError::ContentDeleted);
+        }
+        let ciphertext = content
+            .encrypted_content()
+            .ok_or(BundleError::MissingEncryptedContent)?;
+
+        let expected = self
+            .content_id_generator
+            .generate_encrypted(content.raw_id(), ciphertext);
+        if &expected != content.encrypted_id() {
+            return Err(BundleError::IntegrityMismatch);
+        }
+
+        if let Some(envelope) = bundle
+            .envelopes
+            .iter()
+            .find(|envelope| envelope.content_id() != content.raw_id())
+        {
+            return Err(BundleError::EnvelopeContentMismatch(
+                envelope.content_id().clone(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    #[error("content not found")]
+    ContentNotFound,
+    #[error("content is deleted")]
+    ContentDeleted,
+    #[error("missing encrypted content for content")]
+    MissingEncryptedContent,
+    #[error("content already exists: {0:?}")]
+    AlreadyExists(ContentId),
+    #[error("unsupported bundle format version: {0}")]
+    UnsupportedVersion(u32),
+    #[error("encrypted content does not match its encrypted id")]
+    IntegrityMismatch,
+    #[error("key envelope belongs to another content: {0:?}")]
+    EnvelopeContentMismatch(ContentId),
+    #[error("bundle codec error: {0}")]
+    Codec(BundleCodecError),
+    #[error("content repository error: {0}")]
+    ContentRepository(ContentRepositoryError),
+    #[error("key envelope repository error: {0}")]
+    KeyEnvelopeRepository(KeyEnvelopeRepositoryError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application_service::bundle_service::BundleFormat;
+    use crate::domain::content::encryption::ContentEncryptionKey;
+    use crate::domain::content::Content;
+    use crate::domain::share::{key_envelope::KeyWrapAlgorithm, KeyEnvelope, WrappedRecipientKey};
+    use crate::domain::KeyId;
+    use crate::infrastructure::content_id::Sha256ContentIdGenerator;
+    use crate::infrastructure::encryption::Aes256CtrContentEncryption;
+    use crate::infrastructure::key_envelope_repository::InMemoryKeyEnvelopeRepository;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct TestContentRepository {
+        inner: Arc<Mutex<HashMap<String, Content>>>,
+    }
+
+    impl ContentRepository for TestContentRepository {
+        fn save(
+            &self,
+            content_id: &ContentId,
+            content: &Content,
+        ) -> Result<(), ContentRepositoryError> {
+            self.inner
+                .lock()
+                .unwrap()
+                .insert(content_id.as_str().to_string(), content.clone());
+            Ok(())
+        }
+
+        fn find_by_id(
+            &self,
+            content_id: &ContentId,
+        ) -> Result<Option<Content>, ContentRepositoryError> {
+            Ok(self.inner.lock().unwrap().get(content_id.as_str()).cloned())
+        }
+    }
+
+    type TestService = BundleService<
+        Sha256ContentIdGenerator,
+        TestContentRepository,
+        InMemoryKeyEnvelopeRepository,
+    >;
+
+    fn service() -> TestService {
+        BundleService {
+            content_id_generator: Sha256ContentIdGenerator,
+            content_repository: TestContentRepository::default(),
+            key_envelope_repository: InMemoryKeyEnvelopeRepository::default(),
+        }
+    }
+
+    /// 暗号化済みコンテンツと 1 通の KeyEnvelope を登録し、その ContentId を返す。
+    fn seed(service: &TestService) -> ContentId {
+        let key = ContentEncryptionKey(vec![7u8; 32]);
+        let (content, _) = Content::create(
+            "report.txt".into(),
+            b"quarterly report".to_vec(),
+            "docs/report.txt".into(),
+            None,
+            &Sha256ContentIdGenerator,
+            &key,
+            &Aes256CtrContentEncryption,
+        )
+        .unwrap();
+        let content_id = content.raw_id().clone();
+        service
+            .content_repository
+            .save(&content_id, &content)
+            .unwrap();
+        service
+            .key_envelope_repository
+            .save(&KeyEnvelope::new(
+                content_id.clone(),
+                KeyWrapAlgorithm::HpkeV1,
+                KeyId::new(vec![1]),
+                WrappedRecipientKey::new(KeyId::new(vec![2]), vec![3], vec![4]),
+                Vec::new(),
+            ))
+            .unwrap();
+        content_id
+    }
+
+    #[test]
+    fn export_then_import_roundtrips_in_both_formats() {
+        for format in [BundleFormat::Cbor, BundleFormat::Json] {
+            let source = service();
+            let content_id = seed(&source);
+
+            let exported = source
+                .export(ExportBundleCommand {
+                    content_id: content_id.clone(),
+                    format,
+                })
+                .unwrap();
+            assert_eq!(exported.envelope_count, 1);
+
+            let target = service();
+            let imported = target
+                .import(ImportBundleCommand {
+                    bundle: exported.bundle,
+                    format,
+                })
+                .unwrap();
+            assert_eq!(imported.content_id, content_id);
+            assert_eq!(imported.metadata.name(), "report.txt");
+            assert_eq!(imported.envelope_count, 1);
+
+            let original = source
+                .content_repository
+                .find_by_id(&content_id)
+                .unwrap()
+                .unwrap();
+            let installed = target
+                .content_repository
+                .find_by_id(&content_id)
+                .unwrap()
+                .unwrap();
+            assert_eq!(installed.encrypted_content(), original.encrypted_content());
+            assert_eq!(
+                target
+                    .key_envelope_repository
+                    .list_by_content(&content_id)
+                    .unwrap(),
+                source
+                    .key_envelope_repository
+                    .list_by_content(&content_id)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn import_rejects_tampered_ciphertext() {
+        let source = service();
+        let content_id = seed(&source);
+        let exported = source
+            .export(ExportBundleCommand {
+                content_id,
+                format: BundleFormat::Json,
+            })
+            .unwrap();
+
+        let mut value: serde_json::Value = serde_json::from_slice(&exported.bundle).unwrap();
+        let first = value["content"]["encrypted_content"][0].as_u64().unwrap();
+        value["content"]["encrypted_content"][0] = serde_json::json!(first ^ 0xff);
+        let tampered = serde_json::to_vec(&value).unwrap();
+
+        let target = service();
+        let err = target
+            .import(ImportBundleCommand {
+                bundle: tampered,
+                format: BundleFormat::Json,
+            })
+            .unwrap_err();
+        assert!(matches!(err, BundleError::IntegrityMismatch));
+        assert!(target.content_repository.inner.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn import_rejects_existing_content_and_unknown_version() {
+        let source = service();
+        let content_id = seed(&source);
+        let exported = source
+            .export(ExportBundleCommand {
+                content_id: content_id.clone(),
+                format: BundleFormat::Cbor,
+            })
+            .unwrap();
+
+        let err = source
+            .import(ImportBundleCommand {
+                bundle: exported.bundle.clone(),
+                format: BundleFormat::Cbor,
+            })
+            .unwrap_err();
+        assert!(matches!(err, BundleError::AlreadyExists(id) if id == content_id));
+
+        let mut bundle = ContentBundle::decode(&exported.bundle, BundleFormat::Cbor).unwrap();
+        bundle.format_version = BUNDLE_FORMAT_VERSION + 1;
+        let err = service()
+            .import(ImportBundleCommand {
+                bundle: bundle.encode(BundleFormat::Cbor).unwrap(),
+                format: BundleFormat::Cbor,
+            })
+            .unwrap_err();
+        assert!(matches!(err, BundleError::UnsupportedVersion(_)));
+    }
+
+    #[test]
+    fn export_missing_content_fails() {
+        let err = service()
+            .export(ExportBundleCommand {
+                content_id: ContentId::for_test("missing"),
+                format: BundleFormat::Cbor,
+            })
+            .unwrap_err();
+        assert!(matches!(err, BundleError::ContentNotFound));
+    }
+}
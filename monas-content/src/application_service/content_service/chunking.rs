@@ -0,0 +1,122 @@
+use crate::domain::content::DEFAULT_CHUNK_SIZE;
+
+/// 大きなコンテンツをチャンク分割して暗号化するかどうかの方針。
+///
+/// チャンク分割したコンテンツは、更新時に変更されたチャンクのみ暗号化し直し、
+/// 保存先も変更されたチャンクだけを書き込む。暗号文はチャンクの区切りを含むため、
+/// 共有先（KeyEnvelope・`decrypt_with_cek`）でも CEK だけで復号できる。既定では無効。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChunkingPolicy {
+    /// このバイト数以上のコンテンツをチャンク分割する。`None` なら分割しない。
+    threshold: Option<u64>,
+    chunk_size: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ChunkingPolicyError {
+    #[error("invalid value for {name}: {value}")]
+    InvalidValue { name: &'static str, value: String },
+}
+
+impl ChunkingPolicy {
+    /// チャンク分割しない（デフォルト）。
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// 平文が `threshold` バイト以上のコンテンツを `chunk_size` バイトごとに分割する。
+    pub fn new(threshold: u64, chunk_size: usize) -> Self {
+        Self {
+            threshold: Some(threshold),
+            chunk_size,
+        }
+    }
+
+    /// 環境変数から構築する。
+    ///
+    /// - `MONAS_CONTENT_CHUNK_THRESHOLD`: このバイト数以上のコンテンツを分割する（未設定なら無効）
+    /// - `MONAS_CONTENT_CHUNK_SIZE`: チャンクの平文のバイト数（既定 1 MiB）
+    pub fn from_env() -> Result<Self, ChunkingPolicyError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, ChunkingPolicyError> {
+        const THRESHOLD: &str = "MONAS_CONTENT_CHUNK_THRESHOLD";
+        const CHUNK_SIZE: &str = "MONAS_CONTENT_CHUNK_SIZE";
+
+        let Some(value) = lookup(THRESHOLD).filter(|v| !v.trim().is_empty()) else {
+            return Ok(Self::disabled());
+        };
+        let threshold =
+            value
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| ChunkingPolicyError::InvalidValue {
+                    name: THRESHOLD,
+                    value,
+                })?;
+        let chunk_size = match lookup(CHUNK_SIZE).filter(|v| !v.trim().is_empty()) {
+            Some(value) => match value.trim().parse::<u32>() {
+                Ok(size) if size > 0 => size as usize,
+                _ => {
+                    return Err(ChunkingPolicyError::InvalidValue {
+                        name: CHUNK_SIZE,
+                        value,
+                    })
+                }
+            },
+            None => DEFAULT_CHUNK_SIZE,
+        };
+        Ok(Self::new(threshold, chunk_size))
+    }
+
+    /// `size` バイトのコンテンツに使うチャンクサイズ。分割しない場合は `None`。
+    pub fn chunk_size_for(&self, size: u64) -> Option<usize> {
+        match self.threshold {
+            Some(threshold) if size >= threshold => Some(self.chunk_size),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_only_content_at_or_above_threshold() {
+        let policy = ChunkingPolicy::new(8, 4);
+        assert_eq!(policy.chunk_size_for(7), None);
+        assert_eq!(policy.chunk_size_for(8), Some(4));
+        assert_eq!(ChunkingPolicy::disabled().chunk_size_for(u64::MAX), None);
+    }
+
+    #[test]
+    fn from_lookup_reads_threshold_and_chunk_size() {
+        assert_eq!(
+            ChunkingPolicy::from_lookup(|_| None).unwrap(),
+            ChunkingPolicy::disabled()
+        );
+        let policy = ChunkingPolicy::from_lookup(|key| {
+            (key == "MONAS_CONTENT_CHUNK_THRESHOLD").then(|| "1024".to_string())
+        })
+        .unwrap();
+        assert_eq!(policy, ChunkingPolicy::new(1024, DEFAULT_CHUNK_SIZE));
+        let policy = ChunkingPolicy::from_lookup(|key| match key {
+            "MONAS_CONTENT_CHUNK_THRESHOLD" => Some("16".into()),
+            "MONAS_CONTENT_CHUNK_SIZE" => Some("4".into()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(policy, ChunkingPolicy::new(16, 4));
+
+        for (threshold, chunk_size) in [("big", "4"), ("16", "0"), ("16", "small")] {
+            let err = ChunkingPolicy::from_lookup(|key| match key {
+                "MONAS_CONTENT_CHUNK_THRESHOLD" => Some(threshold.into()),
+                "MONAS_CONTENT_CHUNK_SIZE" => Some(chunk_size.into()),
+                _ => None,
+            });
+            assert!(matches!(err, Err(ChunkingPolicyError::InvalidValue { .. })));
+        }
+    }
+}
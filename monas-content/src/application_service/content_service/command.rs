@@ -1,6 +1,11 @@
+use bytes::Bytes;
+
 use crate::domain::content::provider::StorageProvider;
+use crate::domain::content::ContentStatus;
 use crate::domain::{content::metadata::Metadata, content_id::ContentId};
 
+use super::ContentListFilter;
+
 /// コンテンツ作成ユースケースの入力。
 #[derive(Debug)]
 pub struct CreateContentCommand {
@@ -13,6 +18,23 @@ pub struct CreateContentCommand {
     /// 保存先のストレージプロバイダー。
     /// `None` の場合はデフォルトプロバイダーに保存される。
     pub provider: Option<StorageProvider>,
+    /// クライアントが計算した `raw_content` の SHA-256。
+    /// 指定した場合、暗号化の前に照合し、一致しなければ作成しない。
+    pub content_sha256: Option<[u8; 32]>,
+}
+
+/// クライアント側で暗号化済みのコンテンツを作成するユースケースの入力。
+///
+/// エンドツーエンド暗号化のためのモードで、サーバーは平文も CEK も受け取らない。
+#[derive(Debug)]
+pub struct CreateClientEncryptedContentCommand {
+    pub name: String,
+    pub path: String,
+    /// クライアントが暗号化したコンテンツ本体。
+    pub ciphertext: Vec<u8>,
+    /// クライアントがラップした CEK。サーバーは解釈せずに保存し、取得時にそのまま返す。
+    pub wrapped_cek: Vec<u8>,
+    pub provider: Option<StorageProvider>,
 }
 
 /// コンテンツ作成ユースケースの出力。
@@ -23,7 +45,8 @@ pub struct CreateContentResult {
     /// コンテンツ暗号化に用いた鍵から導出される公開情報など。
     /// 具体的な意味づけは後続の設計で決める。
     pub public_key: String,
-    pub encrypted_content: Vec<u8>,
+    pub status: ContentStatus,
+    pub encrypted_content: Bytes,
 }
 
 /// コンテンツ更新ユースケースの入力。
@@ -33,6 +56,19 @@ pub struct UpdateContentCommand {
     pub new_name: Option<String>,
     pub new_raw_content: Option<Vec<u8>>,
     pub provider: Option<StorageProvider>,
+    /// クライアントが計算した `new_raw_content` の SHA-256。
+    /// 指定した場合、暗号化の前に照合し、一致しなければ更新しない。
+    pub new_raw_content_sha256: Option<[u8; 32]>,
+}
+
+/// コンテンツ本体をクライアント側で暗号化済みの暗号文に置き換えるユースケースの入力。
+#[derive(Debug)]
+pub struct UpdateClientEncryptedContentCommand {
+    pub content_id: ContentId,
+    pub new_name: Option<String>,
+    pub ciphertext: Vec<u8>,
+    pub wrapped_cek: Vec<u8>,
+    pub provider: Option<StorageProvider>,
 }
 
 /// コンテンツ更新ユースケースの出力。
@@ -41,7 +77,8 @@ pub struct UpdateContentResult {
     pub content_id: ContentId,
     pub series_id: ContentId,
     pub metadata: Metadata,
-    pub encrypted_content: Vec<u8>,
+    pub status: ContentStatus,
+    pub encrypted_content: Bytes,
 }
 
 /// コンテンツ削除ユースケースの入力。
@@ -57,6 +94,34 @@ pub struct DeleteContentResult {
     pub content_id: ContentId,
 }
 
+/// ピン留め・ピン留め解除ユースケースの入力。
+#[derive(Debug)]
+pub struct PinContentCommand {
+    pub content_id: ContentId,
+    pub provider: Option<StorageProvider>,
+}
+
+/// ピン留め・ピン留め解除ユースケースの出力。
+#[derive(Debug)]
+pub struct PinContentResult {
+    pub content_id: ContentId,
+    pub pinned: bool,
+}
+
+/// アーカイブ・アーカイブ解除ユースケースの入力。
+#[derive(Debug)]
+pub struct ArchiveContentCommand {
+    pub content_id: ContentId,
+    pub provider: Option<StorageProvider>,
+}
+
+/// アーカイブ・アーカイブ解除ユースケースの出力。
+#[derive(Debug)]
+pub struct ArchiveContentResult {
+    pub content_id: ContentId,
+    pub status: ContentStatus,
+}
+
 /// 削除済みコンテンツ復元ユースケースの入力。
 #[derive(Debug)]
 pub struct RestoreDeletedContentCommand {
@@ -72,20 +137,66 @@ pub struct RestoreDeletedContentCommand {
 pub struct RestoreDeletedContentResult {
     pub content_id: ContentId,
     pub metadata: Metadata,
-    pub encrypted_content: Vec<u8>,
+    pub encrypted_content: Bytes,
 }
 
 /// コンテンツ取得（fetch）ユースケースの出力。
 ///
 /// - `content_id` は現在のコンテンツ本体を識別する ID（コンテンツアドレス）を表す。
 /// - `series_id` は論理的に同一なコンテンツ系列を識別する ID を表す。
-/// - `raw_content` は復号済みのコンテンツバイト列を表す。
+/// - `version` はコンテンツの版を表す短いハッシュ（`Content::version`）。HTTP の ETag に使う。
+/// - `raw_content` は復号済みのコンテンツバイト列を表す。リポジトリから読み込んだバッファを
+///   そのまま復号したもので、呼び出し側へはコピーせずに渡す。
+/// - クライアント側で暗号化されたコンテンツでは `raw_content` は暗号文のままで、
+///   `wrapped_cek` にクライアントがラップした CEK が入る。
 #[derive(Debug)]
 pub struct FetchContentResult {
     pub content_id: ContentId,
     pub series_id: ContentId,
     pub metadata: Metadata,
-    pub raw_content: Vec<u8>,
+    pub version: String,
+    pub status: ContentStatus,
+    /// ピン留めされているかどうか。
+    pub pinned: bool,
+    pub raw_content: Bytes,
+    pub wrapped_cek: Option<Vec<u8>>,
+}
+
+/// メタデータ取得ユースケースの入力。
+#[derive(Debug)]
+pub struct GetContentCommand {
+    pub content_id: ContentId,
+    pub provider: Option<StorageProvider>,
+}
+
+/// コンテンツ一覧ユースケースの入力。
+#[derive(Debug, Default)]
+pub struct ListContentsCommand {
+    pub filter: ContentListFilter,
+    /// 一覧にするプロバイダー（省略時はデフォルト）。
+    pub provider: Option<StorageProvider>,
+}
+
+/// メタデータ取得ユースケースの出力。
+///
+/// 本体は復号せず、削除済みのコンテンツもその状態のまま返す。
+#[derive(Debug)]
+pub struct GetContentResult {
+    pub content_id: ContentId,
+    pub series_id: ContentId,
+    pub metadata: Metadata,
+    pub version: String,
+    pub status: ContentStatus,
+    pub pinned: bool,
+}
+
+/// 条件付き取得（`If-None-Match`）ユースケースの出力。
+#[derive(Debug)]
+pub enum ConditionalFetchResult {
+    /// 呼び出し側が既に持っている版と同じため、本体は返さない。
+    NotModified { version: String },
+    /// 版が異なるため、復号済みの本体を返す。
+    Modified(Box<FetchContentResult>),
 }
 
 /// コンテンツ再暗号化ユースケースの入力。
@@ -100,5 +211,5 @@ pub struct ReencryptContentResult {
     pub encrypted_id: ContentId,
     pub raw_id: ContentId,
     pub metadata: Metadata,
-    pub encrypted_content: Vec<u8>,
+    pub encrypted_content: Bytes,
 }
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+
+use super::CreateContentCommand;
+use crate::domain::content_id::ContentId;
+
+/// 冪等キーの最大長（バイト）。
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// 冪等キーごとに記録するコンテンツ作成の結果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    /// 最初のリクエストで作成したコンテンツの ID。
+    pub content_id: ContentId,
+    /// 保存先のプロバイダー（`None` はデフォルトプロバイダー）。
+    pub provider: Option<String>,
+    /// リクエスト内容のフィンガープリント（同じキーの使い回しを検出する）。
+    pub fingerprint: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 冪等キーと作成結果の対応を保持するためのポート。
+///
+/// - 実装は infra 層（インメモリ / sled など）に置く。
+pub trait IdempotencyStore {
+    fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, IdempotencyStoreError>;
+
+    fn put(&self, key: &str, record: IdempotencyRecord) -> Result<(), IdempotencyStoreError>;
+}
+
+impl<T: IdempotencyStore + ?Sized> IdempotencyStore for Arc<T> {
+    fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, IdempotencyStoreError> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: &str, record: IdempotencyRecord) -> Result<(), IdempotencyStoreError> {
+        (**self).put(key, record)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IdempotencyStoreError {
+    #[error("storage error: {0}")]
+    Storage(String),
+}
+
+/// ContentService に設定するコンテンツ作成の冪等性。
+///
+/// ネットワークの再送で同じ作成リクエストが繰り返されても、同じ呼び出し元が同じ
+/// 冪等キーを使っていれば最初に作成したコンテンツの結果を返す。キーは呼び出し元ごとに
+/// 分けて記録するため、別の呼び出し元の結果が返されることはない。記録は `retention` を
+/// 過ぎると無視される。
+///
+/// キーの検索と記録は別々に行うため、同じキーのリクエストが並行して届いた場合は
+/// それぞれがコンテンツを作成する可能性がある。
+#[derive(Clone, Default)]
+pub struct CreateIdempotency {
+    store: Option<Arc<dyn IdempotencyStore + Send + Sync>>,
+    retention: Option<Duration>,
+}
+
+impl CreateIdempotency {
+    /// 冪等キーを記録しない（デフォルト）。
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// 記録を 24 時間保持する冪等性の設定を作る。
+    pub fn new(store: impl IdempotencyStore + Send + Sync + 'static) -> Self {
+        Self {
+            store: Some(Arc::new(store)),
+            retention: Some(Duration::hours(24)),
+        }
+    }
+
+    /// 記録の保持期間を変更する。`None` の場合は期限なし。
+    pub fn with_retention(mut self, retention: Option<Duration>) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.store.is_some()
+    }
+
+    /// 冪等キーの形式を検査する。
+    pub(crate) fn validate_key(key: &str) -> Result<(), String> {
+        if key.trim().is_empty() {
+            return Err("idempotency key must not be empty".into());
+        }
+        if key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(format!(
+                "idempotency key must be at most {MAX_IDEMPOTENCY_KEY_LEN} bytes"
+            ));
+        }
+        Ok(())
+    }
+
+    /// 名前・パス・プロバイダー・生データから求めるフィンガープリント（SHA-256 の hex）。
+    pub(crate) fn fingerprint(cmd: &CreateContentCommand) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(cmd.name.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(cmd.path.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(cmd.provider.as_ref().map_or("", |p| p.as_str()).as_bytes());
+        hasher.update([0u8]);
+        hasher.update(&cmd.raw_content);
+        hex::encode(hasher.finalize())
+    }
+
+    /// ストアに記録するキー。呼び出し元の SHA-256（hex）と冪等キーを組み合わせる。
+    fn store_key(caller: &str, key: &str) -> String {
+        format!("{}:{key}", hex::encode(Sha256::digest(caller.as_bytes())))
+    }
+
+    /// `caller` が `key` で記録した、保持期間内の記録を返す。
+    pub(crate) fn lookup(
+        &self,
+        caller: &str,
+        key: &str,
+    ) -> Result<Option<IdempotencyRecord>, IdempotencyStoreError> {
+        let Some(store) = &self.store else {
+            return Ok(None);
+        };
+        let record = store.get(&Self::store_key(caller, key))?;
+        Ok(record.filter(|r| match self.retention {
+            Some(retention) => Utc::now() - r.created_at <= retention,
+            None => true,
+        }))
+    }
+
+    pub(crate) fn record(
+        &self,
+        caller: &str,
+        key: &str,
+        record: IdempotencyRecord,
+    ) -> Result<(), IdempotencyStoreError> {
+        match &self.store {
+            Some(store) => store.put(&Self::store_key(caller, key), record),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,7 +1,13 @@
+mod chunking;
 mod command;
+mod idempotency;
 mod port;
+mod quota;
 mod service;
 
+pub use chunking::*;
 pub use command::*;
+pub use idempotency::*;
 pub use port::*;
+pub use quota::*;
 pub use service::*;
@@ -1,5 +1,11 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
 use crate::domain::{
-    content::encryption::ContentEncryptionKey, content::Content, content_id::ContentId,
+    content::encryption::ContentEncryptionKey, content::events::ContentDomainEvent,
+    content::Content, content::ContentStatus, content_id::ContentId,
 };
 
 /// コンテンツを永続化するポート。
@@ -8,6 +14,13 @@ pub trait ContentRepository {
         -> Result<(), ContentRepositoryError>;
     fn find_by_id(&self, content_id: &ContentId)
         -> Result<Option<Content>, ContentRepositoryError>;
+
+    /// バッファされた書き込みを永続化する。サーバーの終了前に呼び出す。
+    ///
+    /// 書き込みのたびに永続化する実装では何もしなくてよい。
+    fn flush(&self) -> Result<(), ContentRepositoryError> {
+        Ok(())
+    }
 }
 
 /// 複数のストレージプロバイダーを扱える ContentRepository の拡張トレイト。
@@ -53,6 +66,63 @@ pub trait MultiStorageContentRepository: ContentRepository {
     fn disconnect_provider(&self, provider: &str) -> Result<(), ContentRepositoryError>;
 }
 
+/// コンテンツ一覧の絞り込み条件。
+///
+/// 既定ではアーカイブ済み・削除済みのコンテンツを含めない。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentListFilter {
+    pub include_archived: bool,
+    pub include_deleted: bool,
+}
+
+impl ContentListFilter {
+    /// `status` のコンテンツを一覧に含めるかどうか。
+    pub fn includes(&self, status: &ContentStatus) -> bool {
+        match status {
+            ContentStatus::Active => true,
+            ContentStatus::Archived => self.include_archived,
+            ContentStatus::Deleting | ContentStatus::Deleted => self.include_deleted,
+        }
+    }
+}
+
+/// 保存済みコンテンツを列挙できる ContentRepository の拡張トレイト。
+pub trait ListableContentRepository: ContentRepository {
+    /// 系列ごとの最新版のうち、`filter` に一致するものを返す。
+    ///
+    /// 一覧用途のため、暗号文は含めなくてよい。
+    fn list(&self, filter: ContentListFilter) -> Result<Vec<Content>, ContentRepositoryError>;
+
+    /// `list` と同じく、プロバイダー `provider` に保存されたものを返す。
+    ///
+    /// プロバイダーを選べない実装ではエラーを返す。
+    fn list_from(
+        &self,
+        provider: &str,
+        filter: ContentListFilter,
+    ) -> Result<Vec<Content>, ContentRepositoryError>;
+}
+
+/// パスから系列ごとの最新版の ContentId を引くインデックスのポート。
+///
+/// パスは先頭の `/` の有無を区別しない。更新日時の新しい順に返す。アーカイブ済みの版を
+/// 含んでもよい（状態は呼び出し側で確認する）。
+pub trait ContentPathIndex {
+    fn find_by_path(&self, path: &str) -> Result<Vec<ContentId>, ContentPathIndexError>;
+}
+
+impl<T: ContentPathIndex + ?Sized> ContentPathIndex for std::sync::Arc<T> {
+    fn find_by_path(&self, path: &str) -> Result<Vec<ContentId>, ContentPathIndexError> {
+        (**self).find_by_path(path)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContentPathIndexError {
+    #[error("storage error: {0}")]
+    Storage(String),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ContentRepositoryError {
     #[error("storage error: {0}")]
@@ -76,6 +146,36 @@ pub trait ContentEncryptionKeyStore {
     ) -> Result<Option<ContentEncryptionKey>, ContentEncryptionKeyStoreError>;
 
     fn delete(&self, content_id: &ContentId) -> Result<(), ContentEncryptionKeyStoreError>;
+
+    /// 系列 `series_id` に属する版 `content_id` の CEK を保存する。
+    ///
+    /// 既定は `save` と同じ。系列ごとに CEK を導出する実装はこれを上書きし、
+    /// 系列の CEK を引き継いだ版の CEK を保存しない。
+    fn save_in_series(
+        &self,
+        content_id: &ContentId,
+        _series_id: &ContentId,
+        key: &ContentEncryptionKey,
+    ) -> Result<(), ContentEncryptionKeyStoreError> {
+        self.save(content_id, key)
+    }
+
+    /// 系列 `series_id` に属する版 `content_id` の CEK を取得する。
+    ///
+    /// 既定は `load` と同じ。系列ごとに CEK を導出する実装はこれを上書きし、
+    /// 版がまだ保存されていなくても（保存前のメタデータの暗号化など）系列から CEK を導出する。
+    fn load_in_series(
+        &self,
+        content_id: &ContentId,
+        _series_id: &ContentId,
+    ) -> Result<Option<ContentEncryptionKey>, ContentEncryptionKeyStoreError> {
+        self.load(content_id)
+    }
+
+    /// バッファされた書き込みを永続化する。サーバーの終了前に呼び出す。
+    fn flush(&self) -> Result<(), ContentEncryptionKeyStoreError> {
+        Ok(())
+    }
 }
 
 /// `Arc<dyn ContentEncryptionKeyStore + Send + Sync>` を `ContentService` の
@@ -101,6 +201,27 @@ impl<T: ContentEncryptionKeyStore + ?Sized> ContentEncryptionKeyStore for std::s
     fn delete(&self, content_id: &ContentId) -> Result<(), ContentEncryptionKeyStoreError> {
         (**self).delete(content_id)
     }
+
+    fn save_in_series(
+        &self,
+        content_id: &ContentId,
+        series_id: &ContentId,
+        key: &ContentEncryptionKey,
+    ) -> Result<(), ContentEncryptionKeyStoreError> {
+        (**self).save_in_series(content_id, series_id, key)
+    }
+
+    fn load_in_series(
+        &self,
+        content_id: &ContentId,
+        series_id: &ContentId,
+    ) -> Result<Option<ContentEncryptionKey>, ContentEncryptionKeyStoreError> {
+        (**self).load_in_series(content_id, series_id)
+    }
+
+    fn flush(&self) -> Result<(), ContentEncryptionKeyStoreError> {
+        (**self).flush()
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -108,3 +229,136 @@ pub enum ContentEncryptionKeyStoreError {
     #[error("storage error: {0}")]
     Storage(String),
 }
+
+/// コンテンツのドメインイベントを外部コンポーネントへ通知するためのポート。
+///
+/// - 実装は infra 層（monas-event-manager の EventBus など）に置く。
+/// - 永続化が完了した後に呼び出されるため、実装は同期的に戻ること。
+pub trait EventPublisher {
+    fn publish(&self, event: ContentDomainEvent) -> Result<(), EventPublisherError>;
+}
+
+impl<T: EventPublisher + ?Sized> EventPublisher for std::sync::Arc<T> {
+    fn publish(&self, event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+        (**self).publish(event)
+    }
+}
+
+/// 2 つの `EventPublisher` に同じイベントを順に渡す。
+///
+/// 先に渡した側が失敗した場合、後の側には渡さない。
+impl<A: EventPublisher, B: EventPublisher> EventPublisher for (A, B) {
+    fn publish(&self, event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+        self.0.publish(event.clone())?;
+        self.1.publish(event)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventPublisherError {
+    #[error("publish error: {0}")]
+    Publish(String),
+}
+
+/// メトリクスの計測対象となる ContentService のユースケース。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentOperation {
+    Create,
+    Update,
+    Fetch,
+    Delete,
+}
+
+impl ContentOperation {
+    /// メトリクスのラベル値として使う名前。
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentOperation::Create => "create",
+            ContentOperation::Update => "update",
+            ContentOperation::Fetch => "fetch",
+            ContentOperation::Delete => "delete",
+        }
+    }
+}
+
+/// ContentService の処理時間・ペイロードサイズなどのメトリクスを記録するポート。
+///
+/// - 実装は infra 層（Prometheus 形式で公開するものなど）に置く。
+/// - 計測はユースケースの成否に影響させないため、実装はエラーを返さない。
+pub trait ContentMetrics {
+    /// ユースケース 1 回分の成否と処理時間（カウンタ・ヒストグラム）。
+    fn record_operation(&self, operation: ContentOperation, success: bool, latency: Duration);
+
+    /// 成功したユースケースで受け渡した平文のバイト数（ヒストグラム）。
+    fn record_payload_size(&self, operation: ContentOperation, bytes: u64);
+
+    /// 暗号化（fetch では復号）に要した時間（ヒストグラム）。
+    fn record_encryption_time(&self, operation: ContentOperation, elapsed: Duration);
+}
+
+impl<T: ContentMetrics + ?Sized> ContentMetrics for std::sync::Arc<T> {
+    fn record_operation(&self, operation: ContentOperation, success: bool, latency: Duration) {
+        (**self).record_operation(operation, success, latency)
+    }
+
+    fn record_payload_size(&self, operation: ContentOperation, bytes: u64) {
+        (**self).record_payload_size(operation, bytes)
+    }
+
+    fn record_encryption_time(&self, operation: ContentOperation, elapsed: Duration) {
+        (**self).record_encryption_time(operation, elapsed)
+    }
+}
+
+/// プッシュ通知のきっかけとなった出来事の種類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PushEventType {
+    /// コンテンツが共有された（受信者向け）。
+    ShareGranted,
+    /// 監視中のコンテンツが更新された。
+    ContentUpdated,
+    /// 監視中のコンテンツが削除された。
+    ContentDeleted,
+    /// 共有されたコンテンツの CEK がローテーションされた（受信者向け）。
+    ///
+    /// 受信者は手元の KeyEnvelope を取得し直す必要がある。
+    KeyRotated,
+}
+
+/// プッシュゲートウェイへ送る通知。
+///
+/// モバイルアプリを起こして同期させるためのものなので、識別子とイベント種別のみを持ち、
+/// 平文・メタデータ（名前やパス）は含めない。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PushNotification {
+    pub event_type: PushEventType,
+    /// 対象コンテンツの現在の ID。
+    pub content_id: ContentId,
+    /// 更新をまたいで変わらない系列 ID（共有通知では `content_id` と同じ）。
+    pub series_id: ContentId,
+    /// 共有先の KeyId（hex）。ゲートウェイが通知先デバイスを引くために使う。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient_key_id: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// モバイルクライアント向けのプッシュ通知を送るポート。
+///
+/// - 実装は infra 層（Webhook など）に置く。
+/// - 通知はベストエフォートであり、失敗しても呼び出し元のユースケースは失敗させない。
+pub trait PushNotifier {
+    fn notify(&self, notification: &PushNotification) -> Result<(), PushNotifierError>;
+}
+
+impl<T: PushNotifier + ?Sized> PushNotifier for std::sync::Arc<T> {
+    fn notify(&self, notification: &PushNotification) -> Result<(), PushNotifierError> {
+        (**self).notify(notification)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PushNotifierError {
+    #[error("push notification error: {0}")]
+    Notify(String),
+}
@@ -0,0 +1,419 @@
+use std::sync::Arc;
+
+/// 1 コンテンツあたりの最大サイズの既定値（256 MiB）。
+pub const DEFAULT_MAX_CONTENT_SIZE: u64 = 256 * 1024 * 1024;
+
+/// 名前空間ごとの合計サイズの上限の既定値（10 GiB）。
+pub const DEFAULT_MAX_NAMESPACE_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
+/// コンテンツ作成・更新時に適用する上限値。
+///
+/// `None` の項目は無制限を表す。サイズはいずれも平文のバイト数で数える。
+/// `Default` は無制限で、サーバーは [`ContentLimits::standard`] を既定とする。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentLimits {
+    /// 1 コンテンツあたりの最大サイズ（バイト）。
+    pub max_content_size: Option<u64>,
+    /// 名前空間ごとの合計サイズの上限（バイト）。テナントの名前空間を使わない場合は
+    /// サービス全体の合計に適用する。
+    pub max_namespace_bytes: Option<u64>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContentLimitsError {
+    #[error("invalid value for {name}: {value}")]
+    InvalidValue { name: &'static str, value: String },
+}
+
+impl ContentLimits {
+    /// サーバーの既定の上限（[`DEFAULT_MAX_CONTENT_SIZE`]・[`DEFAULT_MAX_NAMESPACE_BYTES`]）。
+    pub fn standard() -> Self {
+        Self {
+            max_content_size: Some(DEFAULT_MAX_CONTENT_SIZE),
+            max_namespace_bytes: Some(DEFAULT_MAX_NAMESPACE_BYTES),
+        }
+    }
+
+    /// 環境変数から構築する。未設定の項目は [`standard`](Self::standard) の値を使い、
+    /// `unlimited` を指定した項目は無制限にする。
+    ///
+    /// - `MONAS_CONTENT_MAX_SIZE`: 1 コンテンツあたりの最大サイズ（バイト）
+    /// - `MONAS_CONTENT_MAX_NAMESPACE_BYTES`: 名前空間ごとの合計サイズの上限（バイト）
+    pub fn from_env() -> Result<Self, ContentLimitsError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, ContentLimitsError> {
+        const MAX_SIZE: &str = "MONAS_CONTENT_MAX_SIZE";
+        const MAX_NAMESPACE_BYTES: &str = "MONAS_CONTENT_MAX_NAMESPACE_BYTES";
+
+        let limit = |name: &'static str, default: u64| {
+            let Some(value) = lookup(name).filter(|v| !v.trim().is_empty()) else {
+                return Ok(Some(default));
+            };
+            if value.trim().eq_ignore_ascii_case("unlimited") {
+                return Ok(None);
+            }
+            value
+                .trim()
+                .parse::<u64>()
+                .map(Some)
+                .map_err(|_| ContentLimitsError::InvalidValue { name, value })
+        };
+        Ok(Self {
+            max_content_size: limit(MAX_SIZE, DEFAULT_MAX_CONTENT_SIZE)?,
+            max_namespace_bytes: limit(MAX_NAMESPACE_BYTES, DEFAULT_MAX_NAMESPACE_BYTES)?,
+        })
+    }
+}
+
+/// 名前空間ごとの使用量（バイト数）を保持するためのポート。
+///
+/// - 実装は infra 層（インメモリ / sled など）に置く。
+/// - 使用量は最新版のコンテンツサイズの合計として管理する。
+pub trait NamespaceUsageStore {
+    fn usage(&self, namespace: &str) -> Result<u64, NamespaceUsageStoreError>;
+
+    fn set_usage(&self, namespace: &str, bytes: u64) -> Result<(), NamespaceUsageStoreError>;
+
+    /// 使用量のうち `old_size` バイトを `new_size` バイトに置き換える。
+    ///
+    /// 読み取りから書き込みまでを原子的に行い、並行する書き込み同士で更新を失わない。
+    /// `limit` を指定した場合、置き換え後の使用量がそれを超えるなら変更せずに
+    /// [`UsageAdjustment::LimitExceeded`] を返す。
+    fn adjust(
+        &self,
+        namespace: &str,
+        old_size: u64,
+        new_size: u64,
+        limit: Option<u64>,
+    ) -> Result<UsageAdjustment, NamespaceUsageStoreError>;
+}
+
+impl<T: NamespaceUsageStore + ?Sized> NamespaceUsageStore for Arc<T> {
+    fn usage(&self, namespace: &str) -> Result<u64, NamespaceUsageStoreError> {
+        (**self).usage(namespace)
+    }
+
+    fn set_usage(&self, namespace: &str, bytes: u64) -> Result<(), NamespaceUsageStoreError> {
+        (**self).set_usage(namespace, bytes)
+    }
+
+    fn adjust(
+        &self,
+        namespace: &str,
+        old_size: u64,
+        new_size: u64,
+        limit: Option<u64>,
+    ) -> Result<UsageAdjustment, NamespaceUsageStoreError> {
+        (**self).adjust(namespace, old_size, new_size, limit)
+    }
+}
+
+/// [`NamespaceUsageStore::adjust`] の結果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageAdjustment {
+    /// 使用量を置き換えた。
+    Applied,
+    /// 上限を超えるため置き換えなかった。`used` は現在の使用量。
+    LimitExceeded { used: u64 },
+}
+
+impl UsageAdjustment {
+    /// 使用量 `used` に対する置き換えの結果を求める（ストア実装向けの共通処理）。
+    ///
+    /// 置き換えられる場合は新しい使用量を `Ok` で返す。
+    pub fn evaluate(
+        used: u64,
+        old_size: u64,
+        new_size: u64,
+        limit: Option<u64>,
+    ) -> Result<u64, Self> {
+        let requested = used.saturating_sub(old_size).saturating_add(new_size);
+        match limit {
+            Some(limit) if requested > limit => Err(Self::LimitExceeded { used }),
+            _ => Ok(requested),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum NamespaceUsageStoreError {
+    #[error("storage error: {0}")]
+    Storage(String),
+}
+
+/// 上限を超えたリクエストの内容。
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum QuotaExceeded {
+    #[error("content size {size} bytes exceeds the maximum of {max} bytes")]
+    ContentTooLarge { size: u64, max: u64 },
+    #[error(
+        "namespace '{namespace}' would use {requested} bytes (currently {used}), exceeding the limit of {limit} bytes"
+    )]
+    NamespaceBytes {
+        namespace: String,
+        used: u64,
+        requested: u64,
+        limit: u64,
+    },
+}
+
+/// クォータ検査で発生しうるエラー。
+#[derive(Debug, thiserror::Error)]
+pub enum QuotaError {
+    #[error(transparent)]
+    Exceeded(QuotaExceeded),
+    #[error("namespace usage store error: {0}")]
+    Store(NamespaceUsageStoreError),
+}
+
+/// ContentService に設定するクォータ。
+///
+/// 使用量はパスによらずひとつの名前空間で数える。既定はルート名前空間 `""`
+/// （サービス全体で共有）で、テナントごとの名前空間では [`scoped_to`](Self::scoped_to)
+/// でテナントを指定する。クライアントが選べる論理パスから名前空間を決めると、
+/// 新しいフォルダを使うだけで上限を回避できてしまうため。
+#[derive(Clone, Default)]
+pub struct ContentQuota {
+    limits: ContentLimits,
+    usage_store: Option<Arc<dyn NamespaceUsageStore + Send + Sync>>,
+    namespace: String,
+}
+
+impl ContentQuota {
+    /// 上限を設けないクォータ（デフォルト）。
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// 上限と、名前空間ごとの使用量を保持するストアを指定してクォータを作る。
+    pub fn new(
+        limits: ContentLimits,
+        usage_store: impl NamespaceUsageStore + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            limits,
+            usage_store: Some(Arc::new(usage_store)),
+            namespace: String::new(),
+        }
+    }
+
+    /// すべてのコンテンツを名前空間 `namespace` の使用量として数えるクォータを返す。
+    ///
+    /// テナントごとの名前空間（`/namespaces/{ns}/...`）全体の合計サイズを制限するのに使う。
+    pub fn scoped_to(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    pub fn limits(&self) -> &ContentLimits {
+        &self.limits
+    }
+
+    /// 使用量を数える名前空間。
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// `old_size` バイトのコンテンツを `new_size` バイトに置き換える分の使用量を確保する。
+    ///
+    /// 新規作成の場合は `old_size = 0` を渡す。上限の検査と使用量の更新は
+    /// ストア上で原子的に行うため、並行する書き込みでも上限を超えない。
+    /// 永続化に成功したら [`UsageReservation::commit`] を呼ぶこと（呼ばずに drop すると確保を戻す）。
+    pub(crate) fn reserve(
+        &self,
+        old_size: u64,
+        new_size: u64,
+    ) -> Result<UsageReservation<'_>, QuotaError> {
+        if let Some(max) = self.limits.max_content_size {
+            if new_size > max {
+                return Err(QuotaError::Exceeded(QuotaExceeded::ContentTooLarge {
+                    size: new_size,
+                    max,
+                }));
+            }
+        }
+
+        if let Some(store) = &self.usage_store {
+            // 縮小する更新は常に許可する（上限を下げた後でも削減はできるように）。
+            let limit = self
+                .limits
+                .max_namespace_bytes
+                .filter(|_| new_size > old_size);
+            let adjustment = store
+                .adjust(&self.namespace, old_size, new_size, limit)
+                .map_err(QuotaError::Store)?;
+            if let UsageAdjustment::LimitExceeded { used } = adjustment {
+                return Err(QuotaError::Exceeded(QuotaExceeded::NamespaceBytes {
+                    namespace: self.namespace.clone(),
+                    used,
+                    requested: used.saturating_sub(old_size).saturating_add(new_size),
+                    limit: limit.unwrap_or_default(),
+                }));
+            }
+        }
+        Ok(UsageReservation {
+            quota: self,
+            old_size,
+            new_size,
+            committed: false,
+        })
+    }
+
+    /// 永続化に成功した変更を使用量に反映する（上限は検査しない）。
+    pub(crate) fn record(
+        &self,
+        old_size: u64,
+        new_size: u64,
+    ) -> Result<(), NamespaceUsageStoreError> {
+        let Some(store) = &self.usage_store else {
+            return Ok(());
+        };
+        if old_size == new_size {
+            return Ok(());
+        }
+        store
+            .adjust(&self.namespace, old_size, new_size, None)
+            .map(|_| ())
+    }
+}
+
+/// [`ContentQuota::reserve`] で確保した使用量。
+///
+/// [`commit`](Self::commit) せずに drop した場合（永続化に失敗した場合など）は確保した分を戻す。
+#[must_use = "drop せずに commit しないと確保した使用量が戻される"]
+pub(crate) struct UsageReservation<'a> {
+    quota: &'a ContentQuota,
+    old_size: u64,
+    new_size: u64,
+    committed: bool,
+}
+
+impl UsageReservation<'_> {
+    /// 確保した使用量を確定する。
+    pub(crate) fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for UsageReservation<'_> {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = self.quota.record(self.new_size, self.old_size);
+        }
+    }
+}
+
+impl std::fmt::Debug for ContentQuota {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContentQuota")
+            .field("limits", &self.limits)
+            .field("usage_store", &self.usage_store.is_some())
+            .field("namespace", &self.namespace)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::namespace_usage_store::InMemoryNamespaceUsageStore;
+
+    #[test]
+    fn limits_from_lookup_default_to_standard_and_accept_unlimited() {
+        assert_eq!(
+            ContentLimits::from_lookup(|_| None).unwrap(),
+            ContentLimits::standard()
+        );
+        let limits = ContentLimits::from_lookup(|key| match key {
+            "MONAS_CONTENT_MAX_SIZE" => Some("1024".into()),
+            "MONAS_CONTENT_MAX_NAMESPACE_BYTES" => Some("unlimited".into()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(
+            limits,
+            ContentLimits {
+                max_content_size: Some(1024),
+                max_namespace_bytes: None,
+            }
+        );
+        assert!(matches!(
+            ContentLimits::from_lookup(|_| Some("-1".into())),
+            Err(ContentLimitsError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn quota_counts_every_path_in_one_namespace() {
+        assert_eq!(ContentQuota::unlimited().namespace(), "");
+        assert_eq!(
+            ContentQuota::unlimited().scoped_to("alice").namespace(),
+            "alice"
+        );
+    }
+
+    #[test]
+    fn unlimited_quota_accepts_everything() {
+        let quota = ContentQuota::unlimited();
+        quota.reserve(0, u64::MAX).unwrap().commit();
+        assert!(quota.record(0, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn reserve_enforces_content_size_and_namespace_bytes() {
+        let store = InMemoryNamespaceUsageStore::default();
+        let quota = ContentQuota::new(
+            ContentLimits {
+                max_content_size: Some(10),
+                max_namespace_bytes: Some(15),
+            },
+            store.clone(),
+        );
+
+        assert!(matches!(
+            quota.reserve(0, 11),
+            Err(QuotaError::Exceeded(QuotaExceeded::ContentTooLarge {
+                size: 11,
+                max: 10
+            }))
+        ));
+
+        quota.reserve(0, 10).unwrap().commit();
+        assert_eq!(store.usage("").unwrap(), 10);
+        assert!(matches!(
+            quota.reserve(0, 6),
+            Err(QuotaError::Exceeded(QuotaExceeded::NamespaceBytes {
+                used: 10,
+                requested: 16,
+                ..
+            }))
+        ));
+        assert_eq!(store.usage("").unwrap(), 10);
+        // 既存コンテンツの置き換えは差分で判定する。
+        quota.reserve(10, 4).unwrap().commit();
+        assert_eq!(store.usage("").unwrap(), 4);
+    }
+
+    #[test]
+    fn dropped_reservation_is_released() {
+        let store = InMemoryNamespaceUsageStore::default();
+        let quota = ContentQuota::new(
+            ContentLimits {
+                max_content_size: None,
+                max_namespace_bytes: Some(10),
+            },
+            store.clone(),
+        )
+        .scoped_to("alice");
+
+        let reservation = quota.reserve(0, 8).unwrap();
+        // 確保中の分も上限の判定に含まれる
+        assert!(quota.reserve(0, 3).is_err());
+        drop(reservation);
+        assert_eq!(store.usage("alice").unwrap(), 0);
+        quota.reserve(0, 10).unwrap().commit();
+        assert_eq!(store.usage("alice").unwrap(), 10);
+    }
+}
@@ -1,51 +1,318 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
 use crate::domain::{
     content::encryption::{ContentEncryption, ContentEncryptionKey, ContentEncryptionKeyGenerator},
-    content::{Content, ContentError},
+    content::events::{ContentCreated, ContentDeleted, ContentDomainEvent, ContentUpdated},
+    content::{decrypt_ciphertext, Content, ContentError, ContentStatus},
     content_id::{ContentId, ContentIdGenerator},
 };
 
 use super::{
-    ContentEncryptionKeyStore, ContentEncryptionKeyStoreError, ContentRepositoryError,
-    CreateContentCommand, CreateContentResult, DeleteContentCommand, DeleteContentResult,
-    FetchContentResult, MultiStorageContentRepository, ReencryptContentCommand,
-    ReencryptContentResult, RestoreDeletedContentCommand, RestoreDeletedContentResult,
+    ArchiveContentCommand, ArchiveContentResult, ChunkingPolicy, ConditionalFetchResult,
+    ContentEncryptionKeyStore, ContentEncryptionKeyStoreError, ContentMetrics, ContentOperation,
+    ContentQuota, ContentRepositoryError, CreateClientEncryptedContentCommand,
+    CreateContentCommand, CreateContentResult, CreateIdempotency, DeleteContentCommand,
+    DeleteContentResult, EventPublisher, EventPublisherError, FetchContentResult,
+    GetContentCommand, GetContentResult, IdempotencyRecord, IdempotencyStoreError,
+    ListContentsCommand, ListableContentRepository, MultiStorageContentRepository,
+    NamespaceUsageStoreError, PinContentCommand, PinContentResult, PushNotification, PushNotifier,
+    PushNotifierError, QuotaError, QuotaExceeded, ReencryptContentCommand, ReencryptContentResult,
+    RestoreDeletedContentCommand, RestoreDeletedContentResult, UpdateClientEncryptedContentCommand,
     UpdateContentCommand, UpdateContentResult,
 };
 
+/// `If-None-Match` 形式の値が `version`（`Content::version`）に一致するかを判定する。
+///
+/// `*`、カンマ区切りの複数指定、引用符の有無、弱い比較（`W/` 接頭辞）に対応する。
+pub fn if_none_match_matches(if_none_match: &str, version: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag).trim_matches('"') == version)
+}
+
+/// `raw` の SHA-256 が、クライアントが指定した `expected` と一致するかを判定する。
+///
+/// `expected` が `None`（指定なし）の場合は常に一致とみなす。
+fn checksum_matches(raw: &[u8], expected: Option<&[u8; 32]>) -> bool {
+    match expected {
+        Some(expected) => Sha256::digest(raw).as_slice() == expected,
+        None => true,
+    }
+}
+
+/// プッシュ通知を送らない `PushNotifier` 実装（デフォルト）。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOpPushNotifier;
+
+impl PushNotifier for NoOpPushNotifier {
+    fn notify(&self, _notification: &PushNotification) -> Result<(), PushNotifierError> {
+        Ok(())
+    }
+}
+
+/// イベントを外部へ通知しない `EventPublisher` 実装（デフォルト）。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOpEventPublisher;
+
+impl EventPublisher for NoOpEventPublisher {
+    fn publish(&self, _event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+        Ok(())
+    }
+}
+
+/// メトリクスを記録しない `ContentMetrics` 実装（デフォルト）。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOpContentMetrics;
+
+impl ContentMetrics for NoOpContentMetrics {
+    fn record_operation(&self, _operation: ContentOperation, _success: bool, _latency: Duration) {}
+
+    fn record_payload_size(&self, _operation: ContentOperation, _bytes: u64) {}
+
+    fn record_encryption_time(&self, _operation: ContentOperation, _elapsed: Duration) {}
+}
+
+/// 暗号化・復号に要した時間を積算する `ContentEncryption` のラッパー。
+///
+/// ドメイン層の `Content::create` などに渡し、ユースケース中の暗号処理の時間だけを計測する。
+struct TimedEncryption<'a, E> {
+    inner: &'a E,
+    elapsed: Cell<Duration>,
+    calls: Cell<usize>,
+}
+
+impl<'a, E: ContentEncryption> TimedEncryption<'a, E> {
+    fn new(inner: &'a E) -> Self {
+        Self {
+            inner,
+            elapsed: Cell::new(Duration::ZERO),
+            calls: Cell::new(0),
+        }
+    }
+
+    /// 暗号処理の合計時間。一度も呼ばれていなければ `None`。
+    fn elapsed(&self) -> Option<Duration> {
+        (self.calls.get() > 0).then(|| self.elapsed.get())
+    }
+
+    fn timed<T>(&self, f: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = f();
+        self.elapsed.set(self.elapsed.get() + started.elapsed());
+        self.calls.set(self.calls.get() + 1);
+        result
+    }
+}
+
+impl<E: ContentEncryption> ContentEncryption for TimedEncryption<'_, E> {
+    fn encrypt(
+        &self,
+        key: &ContentEncryptionKey,
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, ContentError> {
+        self.timed(|| self.inner.encrypt(key, plaintext))
+    }
+
+    fn decrypt(
+        &self,
+        key: &ContentEncryptionKey,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, ContentError> {
+        self.timed(|| self.inner.decrypt(key, ciphertext))
+    }
+
+    fn decrypt_bytes(
+        &self,
+        key: &ContentEncryptionKey,
+        ciphertext: Bytes,
+    ) -> Result<Bytes, ContentError> {
+        self.timed(|| self.inner.decrypt_bytes(key, ciphertext))
+    }
+}
+
 /// コンテンツ作成ユースケースのアプリケーションサービス。
-pub struct ContentService<G, R, K, E, S> {
+pub struct ContentService<G, R, K, E, S, P = NoOpEventPublisher, M = NoOpContentMetrics> {
     pub content_id_generator: G,
     pub content_repository: R,
     pub key_generator: K,
     pub encryptor: E,
     pub cek_store: S,
+    pub event_publisher: P,
+    /// コンテンツサイズ・名前空間ごとの合計サイズの上限。
+    pub quota: ContentQuota,
+    /// 大きなコンテンツをチャンク分割して暗号化する方針。
+    pub chunking: ChunkingPolicy,
+    /// 冪等キー付きのコンテンツ作成で、キーと作成結果の対応を記録する先。
+    pub idempotency: CreateIdempotency,
+    /// create / update / fetch / delete の処理時間などの記録先。
+    pub metrics: M,
 }
 
-impl<G, R, K, E, S> ContentService<G, R, K, E, S>
+impl<G, R, K, E, S, P, M> ContentService<G, R, K, E, S, P, M>
 where
     G: ContentIdGenerator,
     R: MultiStorageContentRepository,
     K: ContentEncryptionKeyGenerator,
     E: ContentEncryption,
     S: ContentEncryptionKeyStore,
+    P: EventPublisher,
+    M: ContentMetrics,
 {
+    #[tracing::instrument(
+        name = "content.create",
+        skip_all,
+        fields(size = cmd.raw_content.len(), content_id = tracing::field::Empty)
+    )]
     pub fn create(&self, cmd: CreateContentCommand) -> Result<CreateContentResult, CreateError> {
+        let started = Instant::now();
+        let size = cmd.raw_content.len() as u64;
+        let encryptor = TimedEncryption::new(&self.encryptor);
+        let result = self.create_with(cmd, &encryptor);
+        if let Ok(created) = &result {
+            tracing::Span::current().record("content_id", created.content_id.as_str());
+        }
+        self.observe(
+            ContentOperation::Create,
+            started,
+            encryptor.elapsed(),
+            &result,
+            |_| Some(size),
+        );
+        result
+    }
+
+    /// 冪等キー付きのコンテンツ作成ユースケース。
+    ///
+    /// - `caller` は呼び出し元の識別子（トークンのダイジェストやクライアントのアドレスなど）。
+    ///   冪等キーは呼び出し元ごとに分けて扱う
+    /// - 同じ呼び出し元・同じキーで同じ内容のリクエストが繰り返された場合は新しい
+    ///   コンテンツを作らず、最初に作成したコンテンツの結果を返す
+    /// - 同じキーを異なる内容のリクエストに使った場合は `IdempotencyKeyReused` を返す
+    /// - 記録したコンテンツがストレージから消えている場合は改めて作成する
+    /// - 冪等性が設定されていない場合は `create` と同じ
+    pub fn create_idempotent(
+        &self,
+        caller: &str,
+        idempotency_key: &str,
+        cmd: CreateContentCommand,
+    ) -> Result<CreateContentResult, CreateError> {
+        if !self.idempotency.is_enabled() {
+            return self.create(cmd);
+        }
+        CreateIdempotency::validate_key(idempotency_key).map_err(CreateError::Validation)?;
+
+        let fingerprint = CreateIdempotency::fingerprint(&cmd);
+        if let Some(record) = self
+            .idempotency
+            .lookup(caller, idempotency_key)
+            .map_err(CreateError::IdempotencyStore)?
+        {
+            if record.fingerprint != fingerprint {
+                return Err(CreateError::IdempotencyKeyReused);
+            }
+            if let Some(result) = self.replay_create(&record)? {
+                tracing::debug!(
+                    content_id = result.content_id.as_str(),
+                    "replayed idempotent content creation"
+                );
+                return Ok(result);
+            }
+        }
+
+        let provider = cmd.provider.as_ref().map(|p| p.as_str().to_string());
+        let result = self.create(cmd)?;
+
+        // 作成はすでに完了しているため、記録の失敗でユースケース自体を失敗させない。
+        // 記録できなかった場合、再送されたリクエストは新しいコンテンツを作成する。
+        if let Err(e) = self.idempotency.record(
+            caller,
+            idempotency_key,
+            IdempotencyRecord {
+                content_id: result.content_id.clone(),
+                provider,
+                fingerprint,
+                created_at: chrono::Utc::now(),
+            },
+        ) {
+            tracing::warn!(error = %e, "failed to record idempotency key");
+        }
+        Ok(result)
+    }
+
+    /// 記録済みの作成結果を保存済みのコンテンツから組み立て直す。
+    ///
+    /// コンテンツが見つからない場合は `None` を返す。
+    fn replay_create(
+        &self,
+        record: &IdempotencyRecord,
+    ) -> Result<Option<CreateContentResult>, CreateError> {
+        let content = match &record.provider {
+            Some(p) => self.content_repository.find_from(p, &record.content_id),
+            None => self.content_repository.find_by_id(&record.content_id),
+        }
+        .map_err(CreateError::Repository)?;
+        let Some(content) = content else {
+            return Ok(None);
+        };
+
+        let encrypted_content = content
+            .encrypted_content()
+            .ok_or(CreateError::MissingEncryptedContent)?
+            .clone();
+        Ok(Some(CreateContentResult {
+            content_id: content.raw_id().clone(),
+            metadata: content.metadata().clone(),
+            public_key: String::new(),
+            status: content.content_status().clone(),
+            encrypted_content,
+        }))
+    }
+
+    fn create_with(
+        &self,
+        cmd: CreateContentCommand,
+        encryptor: &TimedEncryption<'_, E>,
+    ) -> Result<CreateContentResult, CreateError> {
         // 簡易バリデーション
         Self::validate_create_command(&cmd)?;
 
-        // CEK の生成
-        let key = self.key_generator.generate();
+        // クォータ検査（使用量を確保し、永続化に失敗したら戻す）
+        let size = cmd.raw_content.len() as u64;
+        let reservation = self.quota.reserve(0, size).map_err(CreateError::from)?;
+
+        // CEK の生成（導出モードでは ContentId から決定的に求める）
+        let key = self
+            .key_generator
+            .generate_for(&self.content_id_generator.generate(&cmd.raw_content));
 
         // ドメインの Content::create を呼び出し、ContentId生成＋暗号化＋メタデータ生成
-        let (content, _event) = Content::create(
-            cmd.name,
-            cmd.raw_content,
-            cmd.path,
-            cmd.provider.clone(),
-            &self.content_id_generator,
-            &key,
-            &self.encryptor,
-        )
+        let (content, _event) = match self.chunking.chunk_size_for(size) {
+            Some(chunk_size) => Content::create_chunked(
+                cmd.name,
+                cmd.raw_content,
+                cmd.path,
+                cmd.provider.clone(),
+                &self.content_id_generator,
+                &key,
+                encryptor,
+                chunk_size,
+            ),
+            None => Content::create(
+                cmd.name,
+                cmd.raw_content,
+                cmd.path,
+                cmd.provider.clone(),
+                &self.content_id_generator,
+                &key,
+                encryptor,
+            ),
+        }
         .map_err(CreateError::Domain)?;
 
         // CEK を保存
@@ -62,6 +329,7 @@ where
             None => self.content_repository.save(content.raw_id(), &content),
         }
         .map_err(CreateError::Repository)?;
+        reservation.commit();
 
         let metadata = content.metadata().clone();
         let content_id = content.raw_id().clone();
@@ -71,14 +339,261 @@ where
             .ok_or(CreateError::MissingEncryptedContent)?
             .clone();
 
+        self.publish_event(ContentDomainEvent::Created(ContentCreated {
+            content_id: content_id.clone(),
+            series_id: content.series_id().clone(),
+            metadata: metadata.clone(),
+        }));
+
         Ok(CreateContentResult {
             content_id,
             metadata,
             public_key: String::new(), // TODO: 将来的に公開鍵を設定
+            status: content.content_status().clone(),
+            encrypted_content,
+        })
+    }
+
+    /// ユースケースの成否・処理時間・ペイロードサイズをメトリクスとして記録する。
+    ///
+    /// 失敗した場合はエラー内容を現在のスパンに紐づけて warn ログに残す。
+    fn observe<T, Err: std::fmt::Display>(
+        &self,
+        operation: ContentOperation,
+        started: Instant,
+        encryption_time: Option<Duration>,
+        result: &Result<T, Err>,
+        payload_size: impl FnOnce(&T) -> Option<u64>,
+    ) {
+        self.metrics
+            .record_operation(operation, result.is_ok(), started.elapsed());
+        match result {
+            Ok(value) => {
+                if let Some(bytes) = payload_size(value) {
+                    self.metrics.record_payload_size(operation, bytes);
+                }
+                if let Some(elapsed) = encryption_time {
+                    self.metrics.record_encryption_time(operation, elapsed);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(operation = operation.as_str(), error = %e, "content operation failed");
+            }
+        }
+    }
+
+    /// 永続化済みの変更を名前空間の使用量に反映する。
+    ///
+    /// 永続化はすでに完了しているため、記録の失敗でユースケース自体を失敗させない。
+    fn record_usage(&self, old_size: u64, new_size: u64) {
+        let _ = self.quota.record(old_size, new_size);
+    }
+
+    /// 保存済みコンテンツの平文サイズ。
+    ///
+    /// サイズを記録していない古いコンテンツは暗号文の長さで近似する。
+    fn stored_size(content: &Content) -> u64 {
+        content.metadata().size().unwrap_or_else(|| {
+            content
+                .encrypted_content()
+                .map(|c| c.len() as u64)
+                .unwrap_or(0)
+        })
+    }
+
+    /// ドメインイベントを通知する。
+    ///
+    /// 永続化はすでに完了しているため、通知の失敗でユースケース自体を失敗させない。
+    /// イベントの購読側は取りこぼしを前提に設計すること。
+    fn publish_event(&self, event: ContentDomainEvent) {
+        let _ = self.event_publisher.publish(event);
+    }
+
+    /// クライアント側で暗号化済みのコンテンツを作成するユースケース（エンドツーエンド暗号化）。
+    ///
+    /// - 暗号化と CEK の保存は行わず、受け取った暗号文とラップされた CEK をそのまま保存する
+    /// - メタデータの検証・クォータ検査・イベントの通知は `create` と同じ
+    /// - クォータには暗号文のサイズを計上する
+    #[tracing::instrument(
+        name = "content.create_client_encrypted",
+        skip_all,
+        fields(size = cmd.ciphertext.len(), content_id = tracing::field::Empty)
+    )]
+    pub fn create_client_encrypted(
+        &self,
+        cmd: CreateClientEncryptedContentCommand,
+    ) -> Result<CreateContentResult, CreateError> {
+        let started = Instant::now();
+        let size = cmd.ciphertext.len() as u64;
+        let result = self.create_client_encrypted_with(cmd);
+        if let Ok(created) = &result {
+            tracing::Span::current().record("content_id", created.content_id.as_str());
+        }
+        self.observe(ContentOperation::Create, started, None, &result, |_| {
+            Some(size)
+        });
+        result
+    }
+
+    fn create_client_encrypted_with(
+        &self,
+        cmd: CreateClientEncryptedContentCommand,
+    ) -> Result<CreateContentResult, CreateError> {
+        Self::validate_create_fields(&cmd.name, &cmd.path)?;
+        Self::validate_client_encrypted_payload(&cmd.ciphertext, &cmd.wrapped_cek)
+            .map_err(CreateError::Validation)?;
+
+        let size = cmd.ciphertext.len() as u64;
+        let reservation = self.quota.reserve(0, size).map_err(CreateError::from)?;
+
+        let (content, _event) = Content::create_client_encrypted(
+            cmd.name,
+            cmd.ciphertext,
+            cmd.wrapped_cek,
+            cmd.path,
+            cmd.provider.clone(),
+            &self.content_id_generator,
+        )
+        .map_err(CreateError::Domain)?;
+
+        match &cmd.provider {
+            Some(provider) => {
+                self.content_repository
+                    .save_to(provider.as_str(), content.raw_id(), &content)
+            }
+            None => self.content_repository.save(content.raw_id(), &content),
+        }
+        .map_err(CreateError::Repository)?;
+        reservation.commit();
+
+        let metadata = content.metadata().clone();
+        let content_id = content.raw_id().clone();
+        let encrypted_content = content
+            .encrypted_content()
+            .ok_or(CreateError::MissingEncryptedContent)?
+            .clone();
+
+        self.publish_event(ContentDomainEvent::Created(ContentCreated {
+            content_id: content_id.clone(),
+            series_id: content.series_id().clone(),
+            metadata: metadata.clone(),
+        }));
+
+        Ok(CreateContentResult {
+            content_id,
+            metadata,
+            public_key: String::new(),
+            status: content.content_status().clone(),
             encrypted_content,
         })
     }
 
+    /// コンテンツ本体をクライアント側で暗号化済みの暗号文に置き換えるユースケース。
+    ///
+    /// - サーバー側で暗号化していたコンテンツも、この更新以降はクライアント側の暗号化になる
+    /// - `new_name` を指定した場合は名前も変更する
+    #[tracing::instrument(
+        name = "content.update_client_encrypted",
+        skip_all,
+        fields(content_id = %cmd.content_id.as_str(), size = cmd.ciphertext.len())
+    )]
+    pub fn update_client_encrypted(
+        &self,
+        cmd: UpdateClientEncryptedContentCommand,
+    ) -> Result<UpdateContentResult, UpdateError> {
+        let started = Instant::now();
+        let size = cmd.ciphertext.len() as u64;
+        let result = self.update_client_encrypted_with(cmd);
+        self.observe(ContentOperation::Update, started, None, &result, |_| {
+            Some(size)
+        });
+        result
+    }
+
+    fn update_client_encrypted_with(
+        &self,
+        cmd: UpdateClientEncryptedContentCommand,
+    ) -> Result<UpdateContentResult, UpdateError> {
+        if cmd
+            .new_name
+            .as_ref()
+            .is_some_and(|name| name.trim().is_empty())
+        {
+            return Err(UpdateError::Validation("name must not be empty".into()));
+        }
+        Self::validate_client_encrypted_payload(&cmd.ciphertext, &cmd.wrapped_cek)
+            .map_err(UpdateError::Validation)?;
+
+        let content = match &cmd.provider {
+            Some(provider) => self
+                .content_repository
+                .find_from(provider.as_str(), &cmd.content_id),
+            None => self.content_repository.find_by_id(&cmd.content_id),
+        }
+        .map_err(UpdateError::Repository)?
+        .ok_or(UpdateError::NotFound)?;
+
+        let old_size = Self::stored_size(&content);
+        let new_size = cmd.ciphertext.len() as u64;
+        let reservation = self
+            .quota
+            .reserve(old_size, new_size)
+            .map_err(UpdateError::from)?;
+
+        let (mut content, _event) = content
+            .update_client_encrypted(cmd.ciphertext, cmd.wrapped_cek, &self.content_id_generator)
+            .map_err(UpdateError::Domain)?;
+        if let Some(name) = cmd.new_name {
+            content = content.rename(name).map_err(UpdateError::Domain)?.0;
+        }
+
+        match content.metadata().provider() {
+            Some(provider) => {
+                self.content_repository
+                    .save_to(provider.as_str(), content.raw_id(), &content)
+            }
+            None => self.content_repository.save(content.raw_id(), &content),
+        }
+        .map_err(UpdateError::Repository)?;
+        reservation.commit();
+
+        let metadata = content.metadata().clone();
+        let content_id = content.raw_id().clone();
+        let series_id = content.series_id().clone();
+        let encrypted_content = content
+            .encrypted_content()
+            .ok_or(UpdateError::MissingEncryptedContent)?
+            .clone();
+
+        self.publish_event(ContentDomainEvent::Updated(ContentUpdated {
+            content_id: content_id.clone(),
+            series_id: series_id.clone(),
+            metadata: metadata.clone(),
+        }));
+
+        Ok(UpdateContentResult {
+            content_id,
+            series_id,
+            metadata,
+            status: content.content_status().clone(),
+            encrypted_content,
+        })
+    }
+
+    /// クライアント側で暗号化済みのペイロードの簡易バリデーション。
+    fn validate_client_encrypted_payload(
+        ciphertext: &[u8],
+        wrapped_cek: &[u8],
+    ) -> Result<(), String> {
+        if ciphertext.is_empty() {
+            return Err("ciphertext must not be empty".into());
+        }
+        if wrapped_cek.is_empty() {
+            return Err("wrapped_cek must not be empty".into());
+        }
+        Ok(())
+    }
+
     /// CreateContentCommand の簡易バリデーション。
     fn validate_create_command(cmd: &CreateContentCommand) -> Result<(), CreateError> {
         if cmd.raw_content.is_empty() {
@@ -86,10 +601,18 @@ where
                 "raw_content must not be empty".into(),
             ));
         }
-        if cmd.name.trim().is_empty() {
+        if !checksum_matches(&cmd.raw_content, cmd.content_sha256.as_ref()) {
+            return Err(CreateError::ChecksumMismatch);
+        }
+        Self::validate_create_fields(&cmd.name, &cmd.path)
+    }
+
+    /// 作成時のメタデータ（name / path）の簡易バリデーション。
+    fn validate_create_fields(name: &str, path: &str) -> Result<(), CreateError> {
+        if name.trim().is_empty() {
             return Err(CreateError::Validation("name must not be empty".into()));
         }
-        if cmd.path.trim().is_empty() {
+        if path.trim().is_empty() {
             return Err(CreateError::Validation("path must not be empty".into()));
         }
         Ok(())
@@ -99,7 +622,34 @@ where
     ///
     /// - `new_name` と `new_raw_content` はどちらか片方だけ、あるいは両方指定可能
     /// - どちらも `None` の場合は Validation エラーとする
+    #[tracing::instrument(
+        name = "content.update",
+        skip_all,
+        fields(
+            content_id = %cmd.content_id.as_str(),
+            size = cmd.new_raw_content.as_ref().map(Vec::len)
+        )
+    )]
     pub fn update(&self, cmd: UpdateContentCommand) -> Result<UpdateContentResult, UpdateError> {
+        let started = Instant::now();
+        let size = cmd.new_raw_content.as_ref().map(|raw| raw.len() as u64);
+        let encryptor = TimedEncryption::new(&self.encryptor);
+        let result = self.update_with(cmd, &encryptor);
+        self.observe(
+            ContentOperation::Update,
+            started,
+            encryptor.elapsed(),
+            &result,
+            |_| size,
+        );
+        result
+    }
+
+    fn update_with(
+        &self,
+        cmd: UpdateContentCommand,
+        encryptor: &TimedEncryption<'_, E>,
+    ) -> Result<UpdateContentResult, UpdateError> {
         // 簡易バリデーション
         Self::validate_update_command(&cmd)?;
 
@@ -113,8 +663,24 @@ where
         .map_err(UpdateError::Repository)?
         .ok_or(UpdateError::NotFound)?;
 
+        let old_size = Self::stored_size(&content);
+        let mut reservation = None;
+
         // バイナリ更新が指定されている場合
         if let Some(raw) = cmd.new_raw_content {
+            // クライアント側で暗号化されたコンテンツはサーバーの CEK を持たない
+            if content.is_client_encrypted() {
+                return Err(UpdateError::Domain(ContentError::ClientEncrypted));
+            }
+
+            // クォータ検査（既存コンテンツとの差分で判定する）
+            let new_size = raw.len() as u64;
+            reservation = Some(
+                self.quota
+                    .reserve(old_size, new_size)
+                    .map_err(UpdateError::from)?,
+            );
+
             // 既存の CEK をキーストアから取得して再利用する。
             // コンテンツごとに 1 つの CEK を持ち、暗号化のたびに IV のみランダムにする前提。
             let key = self
@@ -127,12 +693,22 @@ where
                     ))
                 })?;
 
-            let (updated, _event) = content
-                .update_content(raw, &self.content_id_generator, &key, &self.encryptor)
-                .map_err(UpdateError::Domain)?;
+            // チャンク分割されたコンテンツは変更されたチャンクのみ暗号化し直す
+            let (updated, _event) = match self.chunking.chunk_size_for(new_size) {
+                Some(chunk_size) => content.update_content_chunked(
+                    raw,
+                    &self.content_id_generator,
+                    &key,
+                    encryptor,
+                    chunk_size,
+                ),
+                None => content.update_content(raw, &self.content_id_generator, &key, encryptor),
+            }
+            .map_err(UpdateError::Domain)?;
 
+            // 新しい版も系列の CEK を引き継ぐ
             self.cek_store
-                .save(updated.raw_id(), &key)
+                .save_in_series(updated.raw_id(), updated.series_id(), &key)
                 .map_err(UpdateError::KeyStore)?;
 
             content = updated;
@@ -153,6 +729,9 @@ where
             None => self.content_repository.save(content.raw_id(), &content),
         }
         .map_err(UpdateError::Repository)?;
+        if let Some(reservation) = reservation {
+            reservation.commit();
+        }
 
         let metadata = content.metadata().clone();
         let content_id = content.raw_id().clone();
@@ -163,10 +742,17 @@ where
             .ok_or(UpdateError::MissingEncryptedContent)?
             .clone();
 
+        self.publish_event(ContentDomainEvent::Updated(ContentUpdated {
+            content_id: content_id.clone(),
+            series_id: series_id.clone(),
+            metadata: metadata.clone(),
+        }));
+
         Ok(UpdateContentResult {
             content_id,
             series_id,
             metadata,
+            status: content.content_status().clone(),
             encrypted_content,
         })
     }
@@ -192,6 +778,13 @@ where
                     "new_raw_content must not be empty when provided".into(),
                 ));
             }
+            if !checksum_matches(raw, cmd.new_raw_content_sha256.as_ref()) {
+                return Err(UpdateError::ChecksumMismatch);
+            }
+        } else if cmd.new_raw_content_sha256.is_some() {
+            return Err(UpdateError::Validation(
+                "new_raw_content_sha256 requires new_raw_content".into(),
+            ));
         }
         Ok(())
     }
@@ -206,17 +799,163 @@ where
         content_id: ContentId,
         provider: Option<&str>,
     ) -> Result<FetchContentResult, FetchError> {
-        let content = match provider {
-            Some(p) => self.content_repository.find_from(p, &content_id),
-            None => self.content_repository.find_by_id(&content_id),
+        match self.fetch_if_none_match(content_id, provider, |_| false)? {
+            ConditionalFetchResult::Modified(result) => Ok(*result),
+            ConditionalFetchResult::NotModified { .. } => {
+                unreachable!("fetch never reports NotModified")
+            }
+        }
+    }
+
+    /// 呼び出し側が既に持っている版でなければ復号して返す条件付き fetch。
+    ///
+    /// - `is_known` に現在の版（`Content::version`）を渡し、true が返れば
+    ///   CEK の読み込みと復号を行わずに `NotModified` を返す。
+    /// - 削除済み・未存在の扱いは `fetch` と同じ。
+    #[tracing::instrument(
+        name = "content.fetch",
+        skip_all,
+        fields(content_id = %content_id.as_str())
+    )]
+    pub fn fetch_if_none_match<F>(
+        &self,
+        content_id: ContentId,
+        provider: Option<&str>,
+        is_known: F,
+    ) -> Result<ConditionalFetchResult, FetchError>
+    where
+        F: FnOnce(&str) -> bool,
+    {
+        let started = Instant::now();
+        let encryptor = TimedEncryption::new(&self.encryptor);
+        let result = self
+            .find_for_fetch(&content_id, provider)
+            .and_then(|content| self.fetch_with(content, is_known, &encryptor));
+        self.observe_fetch(started, &encryptor, &result);
+        result
+    }
+
+    /// 候補のうち最初に見つかった有効な（アーカイブ・削除されていない）コンテンツを
+    /// 条件付きで fetch する。
+    ///
+    /// - 候補は先頭から順に 1 回ずつ読み込み、有効でないものは復号せずに飛ばす。
+    /// - 指定したプロバイダーに保存されていない候補も飛ばし、有効なものがなければ `NotFound`。
+    /// - `is_known` の扱いは `fetch_if_none_match` と同じ。
+    #[tracing::instrument(name = "content.fetch", skip_all)]
+    pub fn fetch_first_active_if_none_match<F>(
+        &self,
+        candidates: impl IntoIterator<Item = ContentId>,
+        provider: Option<&str>,
+        is_known: F,
+    ) -> Result<ConditionalFetchResult, FetchError>
+    where
+        F: FnOnce(&str) -> bool,
+    {
+        let started = Instant::now();
+        let encryptor = TimedEncryption::new(&self.encryptor);
+        let result = self
+            .find_first_active(candidates, provider)
+            .and_then(|content| self.fetch_with(content, is_known, &encryptor));
+        self.observe_fetch(started, &encryptor, &result);
+        result
+    }
+
+    fn observe_fetch(
+        &self,
+        started: Instant,
+        encryptor: &TimedEncryption<'_, E>,
+        result: &Result<ConditionalFetchResult, FetchError>,
+    ) {
+        self.observe(
+            ContentOperation::Fetch,
+            started,
+            encryptor.elapsed(),
+            result,
+            |fetched| match fetched {
+                ConditionalFetchResult::Modified(result) => Some(result.raw_content.len() as u64),
+                ConditionalFetchResult::NotModified { .. } => None,
+            },
+        );
+    }
+
+    fn find_for_fetch(
+        &self,
+        content_id: &ContentId,
+        provider: Option<&str>,
+    ) -> Result<Content, FetchError> {
+        match provider {
+            Some(p) => self.content_repository.find_from(p, content_id),
+            None => self.content_repository.find_by_id(content_id),
         }
         .map_err(FetchError::Repository)?
-        .ok_or(FetchError::NotFound)?;
+        .ok_or(FetchError::NotFound)
+    }
 
+    fn find_first_active(
+        &self,
+        candidates: impl IntoIterator<Item = ContentId>,
+        provider: Option<&str>,
+    ) -> Result<Content, FetchError> {
+        for content_id in candidates {
+            match self.find_for_fetch(&content_id, provider) {
+                Ok(content) if *content.content_status() == ContentStatus::Active => {
+                    return Ok(content)
+                }
+                Ok(_) | Err(FetchError::NotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Err(FetchError::NotFound)
+    }
+
+    fn fetch_with<F>(
+        &self,
+        content: Content,
+        is_known: F,
+        encryptor: &TimedEncryption<'_, E>,
+    ) -> Result<ConditionalFetchResult, FetchError>
+    where
+        F: FnOnce(&str) -> bool,
+    {
         if content.is_deleted() {
             return Err(FetchError::Deleted);
         }
 
+        let version = content.version();
+        if is_known(&version) {
+            return Ok(ConditionalFetchResult::NotModified { version });
+        }
+
+        let content_id = content.raw_id().clone();
+        let series_id = content.series_id().clone();
+        let metadata = content.metadata().clone();
+        let status = content.content_status().clone();
+        let pinned = content.is_pinned();
+
+        // クライアント側で暗号化されたコンテンツは復号せず、暗号文とラップされた CEK を返す
+        if let Some(wrapped_cek) = content.client_wrapped_cek().map(<[u8]>::to_vec) {
+            let raw_content = content
+                .encrypted_content()
+                .ok_or_else(|| {
+                    FetchError::Domain(ContentError::DecryptionError(
+                        "Missing encrypted content".to_string(),
+                    ))
+                })?
+                .clone();
+            return Ok(ConditionalFetchResult::Modified(Box::new(
+                FetchContentResult {
+                    content_id,
+                    series_id,
+                    metadata,
+                    version,
+                    status,
+                    pinned,
+                    raw_content,
+                    wrapped_cek: Some(wrapped_cek),
+                },
+            )));
+        }
+
         // CEK をキーストアから取得
         let key = self
             .cek_store
@@ -224,42 +963,76 @@ where
             .map_err(FetchError::KeyStore)?
             .ok_or(FetchError::MissingKey)?;
 
-        // ドメインの decrypt を用いて復号
+        // Content を消費して復号する（暗号文バッファを平文として再利用し、コピーしない）
         let raw_content = content
-            .decrypt(&key, &self.encryptor)
+            .into_decrypted(&key, encryptor)
             .map_err(FetchError::Domain)?;
 
-        Ok(FetchContentResult {
+        Ok(ConditionalFetchResult::Modified(Box::new(
+            FetchContentResult {
+                content_id,
+                series_id,
+                metadata,
+                version,
+                status,
+                pinned,
+                raw_content,
+                wrapped_cek: None,
+            },
+        )))
+    }
+
+    /// コンテンツのメタデータと状態を取得するユースケース。
+    ///
+    /// - 本体の復号は行わないため、CEK がなくても取得できる。
+    /// - 削除済みコンテンツもエラーにせず、`ContentStatus::Deleted` として返す。
+    pub fn get(&self, cmd: GetContentCommand) -> Result<GetContentResult, FetchError> {
+        let content = match &cmd.provider {
+            Some(provider) => self
+                .content_repository
+                .find_from(provider.as_str(), &cmd.content_id),
+            None => self.content_repository.find_by_id(&cmd.content_id),
+        }
+        .map_err(FetchError::Repository)?
+        .ok_or(FetchError::NotFound)?;
+
+        Ok(GetContentResult {
             content_id: content.raw_id().clone(),
             series_id: content.series_id().clone(),
             metadata: content.metadata().clone(),
-            raw_content,
+            version: content.version(),
+            status: content.content_status().clone(),
+            pinned: content.is_pinned(),
         })
     }
 
     /// 外部でアンラップされた CEK と暗号化済みコンテンツを用いて復号するユースケース。
     ///
     /// - 共有フロー（Share）で KeyEnvelope から CEK を取り出した後の復号処理を想定。
-    /// - 復号結果のバイト列から ContentId を再計算し、引数の `content_id` と一致することを検証する。
+    /// - 復号結果のバイト列のダイジェストを `content_id` と同じアルゴリズムで再計算し、一致することを検証する。
     ///   （コンテンツアドレス化に基づく整合性チェック）
+    /// - チャンク分割された暗号文も、暗号文だけから区切りを求めて復号する。
     pub fn decrypt_with_cek(
         &self,
         expected_content_id: ContentId,
         key: ContentEncryptionKey,
         ciphertext: Vec<u8>,
     ) -> Result<Vec<u8>, DecryptWithCekError> {
-        let plaintext = self
-            .encryptor
-            .decrypt(&key, &ciphertext)
+        let mut plaintext = decrypt_ciphertext(&key, &self.encryptor, &ciphertext)
             .map_err(DecryptWithCekError::Domain)?;
 
-        // 復号したプレーンテキストから ContentId(plainCid) を再生成し、
-        // 期待される ID と一致するか確認する（改ざん検知）。
-        let actual_id = self.content_id_generator.generate(&plaintext);
-        if actual_id != expected_content_id {
+        // 復号したプレーンテキストのダイジェストを期待される ID と同じアルゴリズムで計算し、
+        // 一致するか確認する（改ざん検知）。生成器の設定と ID のアルゴリズムが異なっても照合できる。
+        let algorithm = expected_content_id.algorithm();
+        let actual_digest = algorithm.hash(&plaintext);
+        if actual_digest != expected_content_id.digest() {
+            // 呼び出し側に返さない平文はその場で消去する
+            plaintext.zeroize();
+            let actual = ContentId::from_digest(algorithm, &actual_digest)
+                .expect("digest computed with the id algorithm has the expected length");
             return Err(DecryptWithCekError::ContentIdMismatch {
                 expected: expected_content_id.as_str().to_string(),
-                actual: actual_id.as_str().to_string(),
+                actual: actual.into_inner(),
             });
         }
 
@@ -269,7 +1042,19 @@ where
     /// コンテンツ削除ユースケース。
     ///
     /// - 物理削除ではなく、ドメインオブジェクト上で `is_deleted` フラグとバッファをクリアして保存する「論理削除」
+    #[tracing::instrument(
+        name = "content.delete",
+        skip_all,
+        fields(content_id = %cmd.content_id.as_str())
+    )]
     pub fn delete(&self, cmd: DeleteContentCommand) -> Result<DeleteContentResult, DeleteError> {
+        let started = Instant::now();
+        let result = self.delete_with(cmd);
+        self.observe(ContentOperation::Delete, started, None, &result, |_| None);
+        result
+    }
+
+    fn delete_with(&self, cmd: DeleteContentCommand) -> Result<DeleteContentResult, DeleteError> {
         // 既存コンテンツの取得
         let content = match &cmd.provider {
             Some(provider) => self
@@ -280,6 +1065,8 @@ where
         .map_err(DeleteError::Repository)?
         .ok_or(DeleteError::NotFound)?;
 
+        let old_size = Self::stored_size(&content);
+
         // ドメインの削除処理（状態遷移とバリデーション）
         let (deleted_content, _event) = content.delete().map_err(DeleteError::Domain)?;
 
@@ -300,12 +1087,126 @@ where
                 .save(deleted_content.raw_id(), &deleted_content),
         }
         .map_err(DeleteError::Repository)?;
+        self.record_usage(old_size, 0);
 
         let content_id = deleted_content.raw_id().clone();
 
+        self.publish_event(ContentDomainEvent::Deleted(ContentDeleted {
+            content_id: content_id.clone(),
+            series_id: deleted_content.series_id().clone(),
+            deleted_at: deleted_content.metadata().updated_at(),
+        }));
+
         Ok(DeleteContentResult { content_id })
     }
 
+    /// コンテンツをピン留めするユースケース。
+    ///
+    /// ピン留めされたコンテンツは削除できず、リポジトリからも取り除かれない。
+    pub fn pin(&self, cmd: PinContentCommand) -> Result<PinContentResult, PinError> {
+        self.set_pinned(cmd, true)
+    }
+
+    /// コンテンツのピン留めを解除するユースケース。
+    pub fn unpin(&self, cmd: PinContentCommand) -> Result<PinContentResult, PinError> {
+        self.set_pinned(cmd, false)
+    }
+
+    fn set_pinned(
+        &self,
+        cmd: PinContentCommand,
+        pinned: bool,
+    ) -> Result<PinContentResult, PinError> {
+        let content = match &cmd.provider {
+            Some(provider) => self
+                .content_repository
+                .find_from(provider.as_str(), &cmd.content_id),
+            None => self.content_repository.find_by_id(&cmd.content_id),
+        }
+        .map_err(PinError::Repository)?
+        .ok_or(PinError::NotFound)?;
+
+        let updated = if pinned {
+            content.pin().map_err(PinError::Domain)?
+        } else {
+            content.unpin()
+        };
+
+        match updated.metadata().provider() {
+            Some(provider) => {
+                self.content_repository
+                    .save_to(provider.as_str(), updated.raw_id(), &updated)
+            }
+            None => self.content_repository.save(updated.raw_id(), &updated),
+        }
+        .map_err(PinError::Repository)?;
+
+        Ok(PinContentResult {
+            content_id: updated.raw_id().clone(),
+            pinned,
+        })
+    }
+
+    /// コンテンツをアーカイブするユースケース。
+    ///
+    /// アーカイブ済みのコンテンツは既定の一覧に現れないが、ID を指定すれば取得できる。
+    pub fn archive(
+        &self,
+        cmd: ArchiveContentCommand,
+    ) -> Result<ArchiveContentResult, ArchiveError> {
+        self.set_archived(cmd, true)
+    }
+
+    /// コンテンツのアーカイブを解除するユースケース。
+    pub fn unarchive(
+        &self,
+        cmd: ArchiveContentCommand,
+    ) -> Result<ArchiveContentResult, ArchiveError> {
+        self.set_archived(cmd, false)
+    }
+
+    fn set_archived(
+        &self,
+        cmd: ArchiveContentCommand,
+        archived: bool,
+    ) -> Result<ArchiveContentResult, ArchiveError> {
+        let content = match &cmd.provider {
+            Some(provider) => self
+                .content_repository
+                .find_from(provider.as_str(), &cmd.content_id),
+            None => self.content_repository.find_by_id(&cmd.content_id),
+        }
+        .map_err(ArchiveError::Repository)?
+        .ok_or(ArchiveError::NotFound)?;
+
+        let (updated, _event) = if archived {
+            content.archive()
+        } else {
+            content.unarchive()
+        }
+        .map_err(ArchiveError::Domain)?;
+
+        match updated.metadata().provider() {
+            Some(provider) => {
+                self.content_repository
+                    .save_to(provider.as_str(), updated.raw_id(), &updated)
+            }
+            None => self.content_repository.save(updated.raw_id(), &updated),
+        }
+        .map_err(ArchiveError::Repository)?;
+
+        self.publish_event(ContentDomainEvent::Updated(ContentUpdated {
+            content_id: updated.raw_id().clone(),
+            series_id: updated.series_id().clone(),
+            metadata: updated.metadata().clone(),
+        }));
+
+        Ok(ArchiveContentResult {
+            content_id: updated.raw_id().clone(),
+            status: updated.content_status().clone(),
+        })
+    }
+
     /// 削除済みコンテンツを通常状態へ復元するユースケース。
     ///
     /// - 対象は既に存在し、かつ deleted 状態であること
@@ -330,7 +1231,9 @@ where
             return Err(RestoreDeletedError::NotDeleted);
         }
 
-        let key = self.key_generator.generate();
+        let key = self
+            .key_generator
+            .generate_for(&self.content_id_generator.generate(&cmd.raw_content));
         let (restored_content, _event) = Content::create(
             cmd.name,
             cmd.raw_content,
@@ -363,6 +1266,8 @@ where
                 .save(restored_content.raw_id(), &restored_content),
         }
         .map_err(RestoreDeletedError::Repository)?;
+        // 削除時に解放した使用量を戻す（復元は上限検査の対象外）。
+        self.record_usage(0, Self::stored_size(&restored_content));
 
         let metadata = restored_content.metadata().clone();
         let content_id = restored_content.raw_id().clone();
@@ -417,6 +1322,10 @@ where
         if content.is_deleted() {
             return Err(ReencryptError::ContentDeleted);
         }
+        // CEK をクライアントが管理しているため、サーバーでは再暗号化できない
+        if content.is_client_encrypted() {
+            return Err(ReencryptError::Domain(ContentError::ClientEncrypted));
+        }
 
         // Step 2: 既存のCEKで復号
         // reencrypt は「同じ content_id を維持したまま暗号化だけを更新する」前提。
@@ -435,6 +1344,13 @@ where
         let new_cek = self.key_generator.generate();
 
         // Step 4: 再暗号化されたContentを作成
+        // アーカイブ済みでも鍵は回す。アーカイブ状態は再暗号化の前後で引き継ぐ。
+        let archived = content.is_archived();
+        let content = if archived {
+            content.unarchive().map_err(ReencryptError::Domain)?.0
+        } else {
+            content
+        };
         let (reencrypted_content, _event) = content
             .update_content(
                 plaintext,
@@ -443,6 +1359,15 @@ where
                 &self.encryptor,
             )
             .map_err(ReencryptError::Domain)?;
+        let reencrypted_content = reencrypted_content.with_rotated_key();
+        let reencrypted_content = if archived {
+            reencrypted_content
+                .archive()
+                .map_err(ReencryptError::Domain)?
+                .0
+        } else {
+            reencrypted_content
+        };
 
         // reencrypt では平文（復号結果）が同一なので、ContentId（plainCid）は変わらない前提。
         debug_assert_eq!(
@@ -464,7 +1389,11 @@ where
             // ロールバック:
             // - このケースでは content_id が変わらない前提なので delete は危険（旧CEKまで消える）
             // - 旧CEKへ戻して整合性を保つ
-            let _ = self.cek_store.save(&content_id, &old_cek);
+            let _ = self.cek_store.save_in_series(
+                &content_id,
+                reencrypted_content.series_id(),
+                &old_cek,
+            );
             return Err(ReencryptError::ContentRepository(e));
         }
 
@@ -526,6 +1455,45 @@ where
     }
 }
 
+impl<G, R, K, E, S, P, M> ContentService<G, R, K, E, S, P, M>
+where
+    G: ContentIdGenerator,
+    R: MultiStorageContentRepository + ListableContentRepository,
+    K: ContentEncryptionKeyGenerator,
+    E: ContentEncryption,
+    S: ContentEncryptionKeyStore,
+    P: EventPublisher,
+{
+    /// コンテンツ一覧ユースケース。
+    ///
+    /// 系列ごとの最新版を更新日時の新しい順に返す。既定の `filter` ではアーカイブ済み・
+    /// 削除済みを含めない。プロバイダーを指定した場合は、そこに保存されたものだけを返す。
+    pub fn list(
+        &self,
+        cmd: ListContentsCommand,
+    ) -> Result<Vec<GetContentResult>, ContentRepositoryError> {
+        let contents = match &cmd.provider {
+            Some(provider) => self
+                .content_repository
+                .list_from(provider.as_str(), cmd.filter)?,
+            None => self.content_repository.list(cmd.filter)?,
+        };
+        let mut results: Vec<GetContentResult> = contents
+            .into_iter()
+            .map(|content| GetContentResult {
+                content_id: content.raw_id().clone(),
+                series_id: content.series_id().clone(),
+                metadata: content.metadata().clone(),
+                version: content.version(),
+                status: content.content_status().clone(),
+                pinned: content.is_pinned(),
+            })
+            .collect();
+        results.sort_by_key(|r| std::cmp::Reverse(r.metadata.updated_at()));
+        Ok(results)
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum DeleteError {
     #[error("content not found")]
@@ -538,6 +1506,26 @@ pub enum DeleteError {
     KeyStore(ContentEncryptionKeyStoreError),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum PinError {
+    #[error("content not found")]
+    NotFound,
+    #[error("domain error: {0:?}")]
+    Domain(ContentError),
+    #[error("repository error: {0}")]
+    Repository(ContentRepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveError {
+    #[error("content not found")]
+    NotFound,
+    #[error("domain error: {0:?}")]
+    Domain(ContentError),
+    #[error("repository error: {0}")]
+    Repository(ContentRepositoryError),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum CreateError {
     #[error("validation error: {0}")]
@@ -550,6 +1538,25 @@ pub enum CreateError {
     KeyStore(ContentEncryptionKeyStoreError),
     #[error("missing encrypted content")]
     MissingEncryptedContent,
+    #[error("quota exceeded: {0}")]
+    QuotaExceeded(QuotaExceeded),
+    #[error("quota store error: {0}")]
+    QuotaStore(NamespaceUsageStoreError),
+    #[error("idempotency key was already used for a different request")]
+    IdempotencyKeyReused,
+    #[error("idempotency store error: {0}")]
+    IdempotencyStore(IdempotencyStoreError),
+    #[error("content does not match the provided SHA-256 checksum")]
+    ChecksumMismatch,
+}
+
+impl From<QuotaError> for CreateError {
+    fn from(e: QuotaError) -> Self {
+        match e {
+            QuotaError::Exceeded(exceeded) => CreateError::QuotaExceeded(exceeded),
+            QuotaError::Store(err) => CreateError::QuotaStore(err),
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -566,6 +1573,21 @@ pub enum UpdateError {
     KeyStore(ContentEncryptionKeyStoreError),
     #[error("missing encrypted content")]
     MissingEncryptedContent,
+    #[error("quota exceeded: {0}")]
+    QuotaExceeded(QuotaExceeded),
+    #[error("quota store error: {0}")]
+    QuotaStore(NamespaceUsageStoreError),
+    #[error("content does not match the provided SHA-256 checksum")]
+    ChecksumMismatch,
+}
+
+impl From<QuotaError> for UpdateError {
+    fn from(e: QuotaError) -> Self {
+        match e {
+            QuotaError::Exceeded(exceeded) => UpdateError::QuotaExceeded(exceeded),
+            QuotaError::Store(err) => UpdateError::QuotaStore(err),
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -631,11 +1653,11 @@ pub enum ReencryptError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::application_service::content_service::ContentRepository;
+    use crate::application_service::content_service::{ContentListFilter, ContentRepository};
     use crate::domain::{
         content::encryption::{ContentEncryptionKey, ContentEncryptionKeyGenerator},
         content::ContentStatus,
-        content_id::{ContentId, ContentIdGenerator},
+        content_id::{ContentId, ContentIdAlgorithm, ContentIdGenerator},
     };
     use sha2::{Digest, Sha256};
     use std::collections::HashMap;
@@ -647,7 +1669,7 @@ mod tests {
 
     impl ContentIdGenerator for TestIdGenerator {
         fn generate(&self, raw_content: &[u8]) -> ContentId {
-            ContentId::new(format!("test-id-{}", raw_content.len()))
+            ContentId::for_test(&format!("test-id-{}", raw_content.len()))
         }
 
         fn generate_encrypted(&self, plain_cid: &ContentId, ciphertext: &[u8]) -> ContentId {
@@ -656,7 +1678,7 @@ mod tests {
             hasher.update([0u8]);
             hasher.update(ciphertext);
             let digest = hasher.finalize();
-            ContentId::new(format!("test-enc-id-{}", hex::encode(digest)))
+            ContentId::for_test(&format!("test-enc-id-{}", hex::encode(digest)))
         }
     }
 
@@ -853,6 +1875,37 @@ mod tests {
         }
     }
 
+    impl ListableContentRepository for TestContentRepository {
+        fn list(&self, filter: ContentListFilter) -> Result<Vec<Content>, ContentRepositoryError> {
+            let guard = self.inner.lock().unwrap();
+            let mut latest: HashMap<ContentId, Content> = HashMap::new();
+            for content in guard.values() {
+                let newer = match latest.get(content.series_id()) {
+                    Some(current) => {
+                        content.metadata().updated_at() > current.metadata().updated_at()
+                    }
+                    None => true,
+                };
+                if newer {
+                    latest.insert(content.series_id().clone(), content.clone());
+                }
+            }
+            Ok(latest
+                .into_values()
+                .filter(|c| filter.includes(c.content_status()))
+                .collect())
+        }
+
+        fn list_from(
+            &self,
+            _provider: &str,
+            filter: ContentListFilter,
+        ) -> Result<Vec<Content>, ContentRepositoryError> {
+            // テスト用：プロバイダーを無視して通常の list に委譲
+            self.list(filter)
+        }
+    }
+
     impl MultiStorageContentRepository for TestContentRepository {
         fn save_to(
             &self,
@@ -987,6 +2040,57 @@ mod tests {
             key_generator: key_gen,
             encryptor,
             cek_store: key_store,
+            event_publisher: NoOpEventPublisher,
+            quota: ContentQuota::unlimited(),
+            chunking: ChunkingPolicy::disabled(),
+            idempotency: CreateIdempotency::disabled(),
+            metrics: NoOpContentMetrics,
+        }
+    }
+
+    /// 通知されたイベントを記録するテスト用 EventPublisher。
+    #[derive(Clone, Default)]
+    struct RecordingEventPublisher {
+        events: Arc<Mutex<Vec<ContentDomainEvent>>>,
+    }
+
+    impl EventPublisher for RecordingEventPublisher {
+        fn publish(&self, event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+            self.events.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    /// 記録されたメトリクスを保持するテスト用 ContentMetrics。
+    #[derive(Clone, Default)]
+    struct RecordingMetrics {
+        operations: Arc<Mutex<Vec<(ContentOperation, bool)>>>,
+        payload_sizes: Arc<Mutex<Vec<(ContentOperation, u64)>>>,
+        encryptions: Arc<Mutex<Vec<ContentOperation>>>,
+    }
+
+    impl ContentMetrics for RecordingMetrics {
+        fn record_operation(&self, operation: ContentOperation, success: bool, _latency: Duration) {
+            self.operations.lock().unwrap().push((operation, success));
+        }
+
+        fn record_payload_size(&self, operation: ContentOperation, bytes: u64) {
+            self.payload_sizes.lock().unwrap().push((operation, bytes));
+        }
+
+        fn record_encryption_time(&self, operation: ContentOperation, _elapsed: Duration) {
+            self.encryptions.lock().unwrap().push(operation);
+        }
+    }
+
+    /// 常に失敗するテスト用 EventPublisher。
+    struct FailingEventPublisher;
+
+    impl EventPublisher for FailingEventPublisher {
+        fn publish(&self, _event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+            Err(EventPublisherError::Publish(
+                "publish failed in test".into(),
+            ))
         }
     }
 
@@ -1096,6 +2200,7 @@ mod tests {
             path: "path.txt".into(),
             raw_content: b"hello".to_vec(),
             provider: None,
+            content_sha256: None,
         };
 
         let result = service.create(cmd).expect("create should succeed");
@@ -1111,6 +2216,58 @@ mod tests {
         assert_eq!(stored.content_status(), &ContentStatus::Active);
     }
 
+    #[test]
+    fn derived_cek_is_not_stored_across_updates_and_is_recoverable() {
+        use crate::infrastructure::encryption::Aes256CtrContentEncryption;
+        use crate::infrastructure::key_derivation::{
+            AccountMasterKey, DerivedContentEncryptionKeyStore, HkdfContentEncryptionKeyGenerator,
+        };
+
+        let master_key = AccountMasterKey::new([9u8; 32]);
+        let (repo, _) = TestContentRepository::new(false);
+        let (key_store, key_storage) = TestKeyStore::new(false, false);
+        let service = build_service(
+            repo.clone(),
+            HkdfContentEncryptionKeyGenerator::new(master_key.clone()),
+            Aes256CtrContentEncryption,
+            DerivedContentEncryptionKeyStore::new(key_store, repo.clone(), master_key.clone()),
+        );
+
+        let created = service
+            .create(CreateContentCommand {
+                name: "test".into(),
+                path: "path.txt".into(),
+                raw_content: b"hello".to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+            .expect("create should succeed");
+        let updated = service
+            .update(UpdateContentCommand {
+                content_id: created.content_id,
+                new_name: None,
+                new_raw_content: Some(b"hello again".to_vec()),
+                provider: None,
+                new_raw_content_sha256: None,
+            })
+            .expect("update should succeed");
+        // 更新後の版も系列の CEK を使うため、鍵ストアには何も残らない
+        assert!(key_storage.lock().unwrap().is_empty());
+
+        // 空の鍵ストアとルート鍵だけで復号できる
+        let (empty_store, _) = TestKeyStore::new(false, false);
+        let recovered = build_service(
+            repo.clone(),
+            HkdfContentEncryptionKeyGenerator::new(master_key.clone()),
+            Aes256CtrContentEncryption,
+            DerivedContentEncryptionKeyStore::new(empty_store, repo, master_key),
+        );
+        let fetched = recovered
+            .fetch(updated.content_id, None)
+            .expect("fetch should succeed");
+        assert_eq!(fetched.raw_content, b"hello again".to_vec());
+    }
+
     #[test]
     fn create_validation_error_when_name_is_empty() {
         let (repo, _) = TestContentRepository::new(false);
@@ -1122,6 +2279,7 @@ mod tests {
             path: "path.txt".into(),
             raw_content: b"hello".to_vec(),
             provider: None,
+            content_sha256: None,
         };
 
         let err = match service.create(cmd) {
@@ -1131,6 +2289,71 @@ mod tests {
         assert!(matches!(err, CreateError::Validation(_)));
     }
 
+    #[test]
+    fn create_and_update_verify_content_sha256() {
+        let (repo, storage) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let service = build_service(repo, TestKeyGenerator, TestEncryptor, key_store);
+
+        let mismatch = CreateContentCommand {
+            name: "doc".into(),
+            path: "path.txt".into(),
+            raw_content: b"hello".to_vec(),
+            provider: None,
+            content_sha256: Some(Sha256::digest(b"hellp").into()),
+        };
+        assert!(matches!(
+            service.create(mismatch),
+            Err(CreateError::ChecksumMismatch)
+        ));
+        assert!(storage.lock().unwrap().is_empty());
+
+        let created = service
+            .create(CreateContentCommand {
+                name: "doc".into(),
+                path: "path.txt".into(),
+                raw_content: b"hello".to_vec(),
+                provider: None,
+                content_sha256: Some(Sha256::digest(b"hello").into()),
+            })
+            .expect("create with matching checksum should succeed");
+
+        let mismatch = UpdateContentCommand {
+            content_id: created.content_id.clone(),
+            new_name: None,
+            new_raw_content: Some(b"world".to_vec()),
+            provider: None,
+            new_raw_content_sha256: Some(Sha256::digest(b"hello").into()),
+        };
+        assert!(matches!(
+            service.update(mismatch),
+            Err(UpdateError::ChecksumMismatch)
+        ));
+
+        // 新しいコンテンツなしのチェックサムは検証できない
+        let without_content = UpdateContentCommand {
+            content_id: created.content_id.clone(),
+            new_name: Some("renamed".into()),
+            new_raw_content: None,
+            provider: None,
+            new_raw_content_sha256: Some(Sha256::digest(b"world").into()),
+        };
+        assert!(matches!(
+            service.update(without_content),
+            Err(UpdateError::Validation(_))
+        ));
+
+        service
+            .update(UpdateContentCommand {
+                content_id: created.content_id,
+                new_name: None,
+                new_raw_content: Some(b"world".to_vec()),
+                provider: None,
+                new_raw_content_sha256: Some(Sha256::digest(b"world").into()),
+            })
+            .expect("update with matching checksum should succeed");
+    }
+
     #[test]
     fn update_success_changes_content_and_name() {
         let (repo, storage) = TestContentRepository::new(false);
@@ -1142,6 +2365,7 @@ mod tests {
             path: "path.txt".into(),
             raw_content: b"old-data".to_vec(),
             provider: None,
+            content_sha256: None,
         };
         let base_result = service
             .create(base_cmd)
@@ -1152,6 +2376,7 @@ mod tests {
             new_name: Some("new-name".into()),
             new_raw_content: Some(b"new-data".to_vec()),
             provider: None,
+            new_raw_content_sha256: None,
         };
 
         let updated = service.update(update_cmd).expect("update should succeed");
@@ -1178,10 +2403,11 @@ mod tests {
         let service = build_service(repo, TestKeyGenerator, TestEncryptor, key_store);
 
         let update_cmd = UpdateContentCommand {
-            content_id: ContentId::new("unknown-id".into()),
+            content_id: ContentId::for_test("unknown-id"),
             new_name: Some("name".into()),
             new_raw_content: None,
             provider: None,
+            new_raw_content_sha256: None,
         };
 
         let err = match service.update(update_cmd) {
@@ -1202,6 +2428,7 @@ mod tests {
             path: "path.txt".into(),
             raw_content: b"data".to_vec(),
             provider: None,
+            content_sha256: None,
         };
         let base_result = service
             .create(base_cmd)
@@ -1225,6 +2452,55 @@ mod tests {
         assert!(stored.encrypted_content().is_none());
     }
 
+    #[test]
+    fn get_reports_current_status_without_decrypting() {
+        let (repo, _) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let service = build_service(repo, TestKeyGenerator, TestEncryptor, key_store);
+
+        let created = service
+            .create(CreateContentCommand {
+                name: "name".into(),
+                path: "path.txt".into(),
+                raw_content: b"data".to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+            .expect("create should succeed");
+        assert_eq!(created.status, ContentStatus::Active);
+
+        let get_cmd = || GetContentCommand {
+            content_id: created.content_id.clone(),
+            provider: None,
+        };
+        let active = service.get(get_cmd()).expect("get should succeed");
+        assert_eq!(active.status, ContentStatus::Active);
+        assert_eq!(active.metadata.name(), "name");
+
+        service
+            .delete(DeleteContentCommand {
+                content_id: created.content_id.clone(),
+                provider: None,
+            })
+            .expect("delete should succeed");
+
+        // fetch は削除済みを拒否するが、get は状態をそのまま返す
+        assert!(matches!(
+            service.fetch(created.content_id.clone(), None),
+            Err(FetchError::Deleted)
+        ));
+        let deleted = service.get(get_cmd()).expect("get should succeed");
+        assert_eq!(deleted.status, ContentStatus::Deleted);
+
+        assert!(matches!(
+            service.get(GetContentCommand {
+                content_id: ContentId::for_test("unknown-id"),
+                provider: None,
+            }),
+            Err(FetchError::NotFound)
+        ));
+    }
+
     #[test]
     fn delete_not_found_returns_error() {
         let (repo, _) = TestContentRepository::new(false);
@@ -1232,15 +2508,130 @@ mod tests {
         let service = build_service(repo, TestKeyGenerator, TestEncryptor, key_store);
 
         let delete_cmd = DeleteContentCommand {
-            content_id: ContentId::new("unknown-id".into()),
+            content_id: ContentId::for_test("unknown-id"),
+            provider: None,
+        };
+
+        let err = match service.delete(delete_cmd) {
+            Err(e) => e,
+            Ok(_) => panic!("expected not-found error but got Ok"),
+        };
+        assert!(matches!(err, DeleteError::NotFound));
+    }
+
+    #[test]
+    fn archived_content_is_hidden_from_default_list_but_still_fetchable() {
+        let (repo, _) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let service = build_service(repo, TestKeyGenerator, TestEncryptor, key_store);
+
+        let create = |raw: &[u8]| {
+            service
+                .create(CreateContentCommand {
+                    name: "name".into(),
+                    path: "path.txt".into(),
+                    raw_content: raw.to_vec(),
+                    provider: None,
+                    content_sha256: None,
+                })
+                .unwrap()
+        };
+        let kept = create(b"kept");
+        let archived = create(b"archived");
+        let archive_cmd = || ArchiveContentCommand {
+            content_id: archived.content_id.clone(),
+            provider: None,
+        };
+
+        let result = service.archive(archive_cmd()).unwrap();
+        assert_eq!(result.status, ContentStatus::Archived);
+        assert!(matches!(
+            service.archive(archive_cmd()),
+            Err(ArchiveError::Domain(ContentError::Archived))
+        ));
+
+        let listed = service.list(ListContentsCommand::default()).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].content_id, kept.content_id);
+        let all = service
+            .list(ListContentsCommand {
+                filter: ContentListFilter {
+                    include_archived: true,
+                    ..Default::default()
+                },
+                provider: None,
+            })
+            .unwrap();
+        assert_eq!(all.len(), 2);
+
+        // 明示的に指定すれば取得できる
+        let fetched = service.fetch(archived.content_id.clone(), None).unwrap();
+        assert_eq!(fetched.raw_content.as_ref(), b"archived");
+        assert_eq!(fetched.status, ContentStatus::Archived);
+
+        let result = service.unarchive(archive_cmd()).unwrap();
+        assert_eq!(result.status, ContentStatus::Active);
+        assert_eq!(
+            service.list(ListContentsCommand::default()).unwrap().len(),
+            2
+        );
+        assert!(matches!(
+            service.unarchive(archive_cmd()),
+            Err(ArchiveError::Domain(ContentError::NotArchived))
+        ));
+    }
+
+    #[test]
+    fn pinned_content_survives_delete_until_unpinned() {
+        let (repo, storage) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let service = build_service(repo, TestKeyGenerator, TestEncryptor, key_store);
+
+        let created = service
+            .create(CreateContentCommand {
+                name: "name".into(),
+                path: "path.txt".into(),
+                raw_content: b"data".to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+            .unwrap();
+        let pin_cmd = || PinContentCommand {
+            content_id: created.content_id.clone(),
             provider: None,
         };
-
-        let err = match service.delete(delete_cmd) {
-            Err(e) => e,
-            Ok(_) => panic!("expected not-found error but got Ok"),
+        let delete_cmd = || DeleteContentCommand {
+            content_id: created.content_id.clone(),
+            provider: None,
         };
-        assert!(matches!(err, DeleteError::NotFound));
+
+        assert!(service.pin(pin_cmd()).unwrap().pinned);
+        assert!(
+            service
+                .fetch(created.content_id.clone(), None)
+                .unwrap()
+                .pinned
+        );
+        assert!(matches!(
+            service.delete(delete_cmd()),
+            Err(DeleteError::Domain(ContentError::Pinned))
+        ));
+        assert!(storage
+            .lock()
+            .unwrap()
+            .get(created.content_id.as_str())
+            .unwrap()
+            .encrypted_content()
+            .is_some());
+
+        assert!(!service.unpin(pin_cmd()).unwrap().pinned);
+        service
+            .delete(delete_cmd())
+            .expect("unpinned content can be deleted");
+        assert!(matches!(
+            service.pin(pin_cmd()),
+            Err(PinError::Domain(ContentError::AlreadyDeleted))
+        ));
     }
 
     #[test]
@@ -1256,6 +2647,7 @@ mod tests {
             path: "path.txt".into(),
             raw_content: raw.clone(),
             provider: None,
+            content_sha256: None,
         };
 
         let created = service.create(cmd).expect("create should succeed");
@@ -1269,13 +2661,136 @@ mod tests {
         assert_eq!(fetched.raw_content, raw);
     }
 
+    #[test]
+    fn chunking_policy_stores_large_content_in_chunks_and_fetch_round_trips() {
+        let (repo, _) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let mut service = build_service(repo.clone(), TestKeyGenerator, TestEncryptor, key_store);
+        service.chunking = ChunkingPolicy::new(8, 4);
+
+        let created = service
+            .create(CreateContentCommand {
+                name: "chunked".into(),
+                path: "chunked.bin".into(),
+                raw_content: b"aaaabbbbcccc".to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+            .expect("create should succeed");
+        let stored = repo.find_by_id(&created.content_id).unwrap().unwrap();
+        assert!(stored.is_chunked());
+
+        let updated = service
+            .update(UpdateContentCommand {
+                content_id: created.content_id.clone(),
+                new_name: None,
+                new_raw_content: Some(b"aaaaXXXXcccc".to_vec()),
+                provider: None,
+                new_raw_content_sha256: None,
+            })
+            .expect("update should succeed");
+        let stored = repo.find_by_id(&updated.content_id).unwrap().unwrap();
+        assert_eq!(
+            stored.metadata().chunk_manifest().map(|m| m.chunks().len()),
+            Some(3)
+        );
+
+        let fetched = service
+            .fetch(updated.content_id.clone(), None)
+            .expect("fetch should succeed");
+        assert_eq!(fetched.raw_content, b"aaaaXXXXcccc".to_vec());
+
+        // 共有先と同じく、マニフェストなしで暗号文と CEK だけからも復号できる
+        let key = service
+            .cek_store
+            .load(&updated.content_id)
+            .unwrap()
+            .unwrap();
+        let ciphertext = stored.encrypted_content().unwrap().to_vec();
+        let plain_id = ContentId::from_digest(
+            ContentIdAlgorithm::Sha256,
+            &ContentIdAlgorithm::Sha256.hash(b"aaaaXXXXcccc"),
+        )
+        .unwrap();
+        let plaintext = service
+            .decrypt_with_cek(plain_id, key, ciphertext)
+            .expect("chunked ciphertext should decrypt with the CEK alone");
+        assert_eq!(plaintext, b"aaaaXXXXcccc".to_vec());
+
+        // 閾値未満のコンテンツは従来どおり単一の暗号文として保存される
+        let small = service
+            .create(CreateContentCommand {
+                name: "small".into(),
+                path: "small.bin".into(),
+                raw_content: b"tiny".to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+            .expect("create should succeed");
+        let stored = repo.find_by_id(&small.content_id).unwrap().unwrap();
+        assert!(!stored.is_chunked());
+    }
+
+    #[test]
+    fn fetch_if_none_match_skips_decryption_for_known_version() {
+        let (repo, _) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let service = build_service(repo, TestKeyGenerator, TestEncryptor, key_store);
+
+        let created = service
+            .create(CreateContentCommand {
+                name: "etag-test".into(),
+                path: "path.txt".into(),
+                raw_content: b"hello-etag".to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+            .expect("create should succeed");
+
+        let fetched = service
+            .fetch(created.content_id.clone(), None)
+            .expect("fetch should succeed");
+        assert!(!fetched.version.is_empty());
+
+        let known = fetched.version.clone();
+        match service
+            .fetch_if_none_match(created.content_id.clone(), None, |v| v == known)
+            .expect("conditional fetch should succeed")
+        {
+            ConditionalFetchResult::NotModified { version } => assert_eq!(version, known),
+            ConditionalFetchResult::Modified(_) => panic!("expected NotModified"),
+        }
+
+        match service
+            .fetch_if_none_match(created.content_id, None, |v| v == "stale")
+            .expect("conditional fetch should succeed")
+        {
+            ConditionalFetchResult::Modified(result) => {
+                assert_eq!(result.raw_content, b"hello-etag".to_vec());
+                assert_eq!(result.version, known);
+            }
+            ConditionalFetchResult::NotModified { .. } => panic!("expected Modified"),
+        }
+    }
+
+    #[test]
+    fn if_none_match_matches_handles_lists_weak_tags_and_wildcard() {
+        assert!(if_none_match_matches("\"abc\"", "abc"));
+        assert!(if_none_match_matches("abc", "abc"));
+        assert!(if_none_match_matches("W/\"abc\"", "abc"));
+        assert!(if_none_match_matches("\"x\", \"abc\"", "abc"));
+        assert!(if_none_match_matches("*", "abc"));
+        assert!(!if_none_match_matches("\"abd\"", "abc"));
+        assert!(!if_none_match_matches("", "abc"));
+    }
+
     #[test]
     fn fetch_not_found_returns_error() {
         let (repo, _) = TestContentRepository::new(false);
         let (key_store, _) = TestKeyStore::new(false, false);
         let service = build_service(repo, TestKeyGenerator, TestEncryptor, key_store);
 
-        let unknown_id = ContentId::new("unknown-id".into());
+        let unknown_id = ContentId::for_test("unknown-id");
 
         let err = match service.fetch(unknown_id, None) {
             Err(e) => e,
@@ -1295,6 +2810,7 @@ mod tests {
             path: "path.txt".into(),
             raw_content: b"data".to_vec(),
             provider: None,
+            content_sha256: None,
         };
         let created = service.create(cmd).expect("create should succeed");
 
@@ -1324,6 +2840,7 @@ mod tests {
                 path: "/restore.txt".into(),
                 raw_content: raw.clone(),
                 provider: None,
+                content_sha256: None,
             })
             .expect("create should succeed");
 
@@ -1372,6 +2889,7 @@ mod tests {
                 path: "/active.txt".into(),
                 raw_content: b"active".to_vec(),
                 provider: None,
+                content_sha256: None,
             })
             .expect("create should succeed");
 
@@ -1399,6 +2917,7 @@ mod tests {
             path: "path.txt".into(),
             raw_content: b"data".to_vec(),
             provider: None,
+            content_sha256: None,
         };
         let created = service.create(cmd).expect("create should succeed");
 
@@ -1422,16 +2941,21 @@ mod tests {
         let service = build_service(repo, TestKeyGenerator, TestEncryptor, key_store);
 
         let plaintext = b"decrypt-cek-success".to_vec();
-        let expected_cid = service.content_id_generator.generate(&plaintext);
 
-        let key = ContentEncryptionKey(vec![1, 2, 3]);
-        let ciphertext = plaintext.clone();
+        // 生成器の設定に関係なく、ID 自身のアルゴリズムで照合する
+        for algorithm in [ContentIdAlgorithm::Sha256, ContentIdAlgorithm::Blake3] {
+            let expected_cid =
+                ContentId::from_digest(algorithm, &algorithm.hash(&plaintext)).unwrap();
 
-        let result = service
-            .decrypt_with_cek(expected_cid.clone(), key, ciphertext)
-            .expect("decrypt_with_cek should succeed when content_id matches");
+            let key = ContentEncryptionKey(vec![1, 2, 3]);
+            let ciphertext = plaintext.clone();
+
+            let result = service
+                .decrypt_with_cek(expected_cid, key, ciphertext)
+                .expect("decrypt_with_cek should succeed when content_id matches");
 
-        assert_eq!(result, plaintext);
+            assert_eq!(result, plaintext);
+        }
     }
 
     #[test]
@@ -1441,8 +2965,10 @@ mod tests {
         let service = build_service(repo, TestKeyGenerator, TestEncryptor, key_store);
 
         let plaintext = b"decrypt-cek-mismatch".to_vec();
-        let actual_cid = service.content_id_generator.generate(&plaintext);
-        let expected_cid = ContentId::new("some-other-id".into());
+        let algorithm = ContentIdAlgorithm::Blake3;
+        let actual_cid = ContentId::from_digest(algorithm, &algorithm.hash(&plaintext)).unwrap();
+        let expected_cid =
+            ContentId::from_digest(algorithm, &algorithm.hash(b"some-other-content")).unwrap();
 
         let key = ContentEncryptionKey(vec![9, 9, 9]);
         let ciphertext = plaintext.clone();
@@ -1477,6 +3003,7 @@ mod tests {
             path: "path.txt".into(),
             raw_content: b"same-plaintext".to_vec(),
             provider: None,
+            content_sha256: None,
         };
         let created = service.create(create_cmd).expect("create should succeed");
 
@@ -1539,6 +3066,7 @@ mod tests {
             path: "path.txt".into(),
             raw_content: b"same-plaintext".to_vec(),
             provider: None,
+            content_sha256: None,
         };
         let created = service.create(create_cmd).expect("create should succeed");
 
@@ -1582,6 +3110,7 @@ mod tests {
             path: "path.txt".into(),
             raw_content: b"data".to_vec(),
             provider: None,
+            content_sha256: None,
         };
         let created = service.create(create_cmd).expect("create should succeed");
 
@@ -1599,4 +3128,525 @@ mod tests {
             .expect_err("reencrypt should fail when CEK is missing");
         assert!(matches!(err, ReencryptError::MissingContentEncryptionKey));
     }
+
+    #[test]
+    fn create_update_delete_publish_domain_events() {
+        let (repo, _storage) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let publisher = RecordingEventPublisher::default();
+        let service = ContentService {
+            content_id_generator: TestIdGenerator,
+            content_repository: repo,
+            key_generator: TestKeyGenerator,
+            encryptor: TestEncryptor,
+            cek_store: key_store,
+            event_publisher: publisher.clone(),
+            quota: ContentQuota::unlimited(),
+            chunking: ChunkingPolicy::disabled(),
+            idempotency: CreateIdempotency::disabled(),
+            metrics: NoOpContentMetrics,
+        };
+
+        let created = service
+            .create(CreateContentCommand {
+                name: "name".into(),
+                path: "path.txt".into(),
+                raw_content: b"data".to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+            .expect("create should succeed");
+        let updated = service
+            .update(UpdateContentCommand {
+                content_id: created.content_id.clone(),
+                new_name: None,
+                new_raw_content: Some(b"new data".to_vec()),
+                provider: None,
+                new_raw_content_sha256: None,
+            })
+            .expect("update should succeed");
+        service
+            .delete(DeleteContentCommand {
+                content_id: updated.content_id.clone(),
+                provider: None,
+            })
+            .expect("delete should succeed");
+
+        let events = publisher.events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        match &events[0] {
+            ContentDomainEvent::Created(e) => {
+                assert_eq!(e.content_id, created.content_id);
+                assert_eq!(e.series_id, created.content_id);
+                assert_eq!(e.metadata.name(), "name");
+            }
+            other => panic!("expected Created event, got {other:?}"),
+        }
+        match &events[1] {
+            ContentDomainEvent::Updated(e) => {
+                assert_eq!(e.content_id, updated.content_id);
+                assert_eq!(e.series_id, created.content_id);
+            }
+            other => panic!("expected Updated event, got {other:?}"),
+        }
+        match &events[2] {
+            ContentDomainEvent::Deleted(e) => {
+                assert_eq!(e.content_id, updated.content_id);
+                assert_eq!(e.series_id, created.content_id);
+            }
+            other => panic!("expected Deleted event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn failed_create_does_not_publish_event() {
+        let (repo, _storage) = TestContentRepository::new(true);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let publisher = RecordingEventPublisher::default();
+        let service = ContentService {
+            content_id_generator: TestIdGenerator,
+            content_repository: repo,
+            key_generator: TestKeyGenerator,
+            encryptor: TestEncryptor,
+            cek_store: key_store,
+            event_publisher: publisher.clone(),
+            quota: ContentQuota::unlimited(),
+            chunking: ChunkingPolicy::disabled(),
+            idempotency: CreateIdempotency::disabled(),
+            metrics: NoOpContentMetrics,
+        };
+
+        let result = service.create(CreateContentCommand {
+            name: "name".into(),
+            path: "path.txt".into(),
+            raw_content: b"data".to_vec(),
+            provider: None,
+            content_sha256: None,
+        });
+        assert!(result.is_err());
+        assert!(publisher.events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn publish_failure_does_not_fail_create() {
+        let (repo, storage) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let service = ContentService {
+            content_id_generator: TestIdGenerator,
+            content_repository: repo,
+            key_generator: TestKeyGenerator,
+            encryptor: TestEncryptor,
+            cek_store: key_store,
+            event_publisher: FailingEventPublisher,
+            quota: ContentQuota::unlimited(),
+            chunking: ChunkingPolicy::disabled(),
+            idempotency: CreateIdempotency::disabled(),
+            metrics: NoOpContentMetrics,
+        };
+
+        let created = service
+            .create(CreateContentCommand {
+                name: "name".into(),
+                path: "path.txt".into(),
+                raw_content: b"data".to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+            .expect("create should succeed even if publishing fails");
+        assert!(storage
+            .lock()
+            .unwrap()
+            .contains_key(created.content_id.as_str()));
+    }
+
+    #[test]
+    fn create_idempotent_replays_the_first_result_for_the_same_key() {
+        use crate::infrastructure::idempotency_store::InMemoryIdempotencyStore;
+
+        let (repo, storage) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let publisher = RecordingEventPublisher::default();
+        let service = ContentService {
+            content_id_generator: TestIdGenerator,
+            content_repository: repo,
+            key_generator: TestKeyGenerator,
+            encryptor: TestEncryptor,
+            cek_store: key_store,
+            event_publisher: publisher.clone(),
+            quota: ContentQuota::unlimited(),
+            chunking: ChunkingPolicy::disabled(),
+            idempotency: CreateIdempotency::new(InMemoryIdempotencyStore::default()),
+            metrics: NoOpContentMetrics,
+        };
+        let cmd = |raw: &[u8]| CreateContentCommand {
+            name: "name".into(),
+            path: "path.txt".into(),
+            raw_content: raw.to_vec(),
+            provider: None,
+            content_sha256: None,
+        };
+        let created_events = || {
+            publisher
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| matches!(e, ContentDomainEvent::Created(_)))
+                .count()
+        };
+
+        let first = service
+            .create_idempotent("alice", "key-1", cmd(b"data"))
+            .expect("create should succeed");
+        let retried = service
+            .create_idempotent("alice", "key-1", cmd(b"data"))
+            .expect("retry should succeed");
+        assert_eq!(retried.content_id, first.content_id);
+        assert_eq!(retried.metadata.name(), first.metadata.name());
+        assert_eq!(retried.encrypted_content, first.encrypted_content);
+        assert_eq!(created_events(), 1);
+
+        // 同じキーを異なる内容に使い回すと拒否される
+        let err = service
+            .create_idempotent("alice", "key-1", cmd(b"other data"))
+            .expect_err("reused key must be rejected");
+        assert!(matches!(err, CreateError::IdempotencyKeyReused));
+
+        let err = service
+            .create_idempotent("alice", " ", cmd(b"data"))
+            .expect_err("blank key must be rejected");
+        assert!(matches!(err, CreateError::Validation(_)));
+
+        // 別のキーなら新しく作成される
+        service
+            .create_idempotent("alice", "key-2", cmd(b"data"))
+            .expect("create should succeed");
+        assert_eq!(created_events(), 2);
+
+        // 別の呼び出し元が同じキーを使っても、記録した結果は返されない
+        service
+            .create_idempotent("bob", "key-1", cmd(b"data"))
+            .expect("create should succeed");
+        assert_eq!(created_events(), 3);
+        let err = service
+            .create_idempotent("bob", "key-1", cmd(b"other data"))
+            .expect_err("reused key must be rejected");
+        assert!(matches!(err, CreateError::IdempotencyKeyReused));
+
+        // 記録したコンテンツがストレージから消えていれば作成し直す
+        storage.lock().unwrap().clear();
+        let recreated = service
+            .create_idempotent("alice", "key-1", cmd(b"data"))
+            .expect("create should succeed");
+        assert_eq!(created_events(), 4);
+        assert!(storage
+            .lock()
+            .unwrap()
+            .contains_key(recreated.content_id.as_str()));
+    }
+
+    #[test]
+    fn create_and_update_enforce_quota() {
+        use crate::application_service::content_service::{ContentLimits, NamespaceUsageStore};
+        use crate::infrastructure::namespace_usage_store::InMemoryNamespaceUsageStore;
+
+        let (repo, storage) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let usage = InMemoryNamespaceUsageStore::default();
+        let service = ContentService {
+            content_id_generator: TestIdGenerator,
+            content_repository: repo,
+            key_generator: TestKeyGenerator,
+            encryptor: TestEncryptor,
+            cek_store: key_store,
+            event_publisher: NoOpEventPublisher,
+            quota: ContentQuota::new(
+                ContentLimits {
+                    max_content_size: Some(8),
+                    max_namespace_bytes: Some(12),
+                },
+                usage.clone(),
+            ),
+            chunking: ChunkingPolicy::disabled(),
+            idempotency: CreateIdempotency::disabled(),
+            metrics: NoOpContentMetrics,
+        };
+        let create = |path: &str, data: &[u8]| {
+            service.create(CreateContentCommand {
+                name: "name".into(),
+                path: path.into(),
+                raw_content: data.to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+        };
+
+        let err = create("docs/big.bin", b"123456789").expect_err("too large");
+        assert!(matches!(
+            err,
+            CreateError::QuotaExceeded(QuotaExceeded::ContentTooLarge { size: 9, max: 8 })
+        ));
+        assert!(storage.lock().unwrap().is_empty());
+
+        let first = create("docs/a.txt", b"12345678").expect("within quota");
+        assert_eq!(usage.usage("").unwrap(), 8);
+
+        let err = create("docs/b.txt", b"12345").expect_err("namespace full");
+        assert!(matches!(
+            err,
+            CreateError::QuotaExceeded(QuotaExceeded::NamespaceBytes { .. })
+        ));
+        // 新しい先頭フォルダを使っても上限は回避できない
+        let err = create("photos/b.txt", b"12345").expect_err("same namespace");
+        assert!(matches!(
+            err,
+            CreateError::QuotaExceeded(QuotaExceeded::NamespaceBytes { .. })
+        ));
+        assert_eq!(usage.usage("").unwrap(), 8);
+
+        // 縮小する更新は許可され、使用量も減る
+        let updated = service
+            .update(UpdateContentCommand {
+                content_id: first.content_id,
+                new_name: None,
+                new_raw_content: Some(b"1234".to_vec()),
+                provider: None,
+                new_raw_content_sha256: None,
+            })
+            .expect("shrinking update");
+        assert_eq!(usage.usage("").unwrap(), 4);
+
+        let err = service
+            .update(UpdateContentCommand {
+                content_id: updated.content_id.clone(),
+                new_name: None,
+                new_raw_content: Some(b"123456789".to_vec()),
+                provider: None,
+                new_raw_content_sha256: None,
+            })
+            .expect_err("too large update");
+        assert!(matches!(
+            err,
+            UpdateError::QuotaExceeded(QuotaExceeded::ContentTooLarge { .. })
+        ));
+
+        service
+            .delete(DeleteContentCommand {
+                content_id: updated.content_id,
+                provider: None,
+            })
+            .expect("delete");
+        assert_eq!(usage.usage("").unwrap(), 0);
+    }
+
+    #[test]
+    fn failed_save_releases_reserved_usage() {
+        use crate::application_service::content_service::{ContentLimits, NamespaceUsageStore};
+        use crate::infrastructure::namespace_usage_store::InMemoryNamespaceUsageStore;
+
+        let (repo, _) = TestContentRepository::new(true);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let usage = InMemoryNamespaceUsageStore::default();
+        let service = ContentService {
+            content_id_generator: TestIdGenerator,
+            content_repository: repo,
+            key_generator: TestKeyGenerator,
+            encryptor: TestEncryptor,
+            cek_store: key_store,
+            event_publisher: NoOpEventPublisher,
+            quota: ContentQuota::new(
+                ContentLimits {
+                    max_content_size: None,
+                    max_namespace_bytes: Some(12),
+                },
+                usage.clone(),
+            ),
+            chunking: ChunkingPolicy::disabled(),
+            idempotency: CreateIdempotency::disabled(),
+            metrics: NoOpContentMetrics,
+        };
+
+        let err = service
+            .create(CreateContentCommand {
+                name: "name".into(),
+                path: "docs/a.txt".into(),
+                raw_content: b"12345678".to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+            .expect_err("save fails");
+        assert!(matches!(err, CreateError::Repository(_)));
+        assert_eq!(usage.usage("").unwrap(), 0);
+    }
+
+    #[test]
+    fn metrics_record_outcome_payload_size_and_encryption_per_operation() {
+        let (repo, _) = TestContentRepository::new(false);
+        let (key_store, _) = TestKeyStore::new(false, false);
+        let metrics = RecordingMetrics::default();
+        let service = ContentService {
+            content_id_generator: TestIdGenerator,
+            content_repository: repo,
+            key_generator: TestKeyGenerator,
+            encryptor: TestEncryptor,
+            cek_store: key_store,
+            event_publisher: NoOpEventPublisher,
+            quota: ContentQuota::unlimited(),
+            chunking: ChunkingPolicy::disabled(),
+            idempotency: CreateIdempotency::disabled(),
+            metrics: metrics.clone(),
+        };
+
+        let created = service
+            .create(CreateContentCommand {
+                name: "metrics".into(),
+                path: "metrics.txt".into(),
+                raw_content: b"hello".to_vec(),
+                provider: None,
+                content_sha256: None,
+            })
+            .expect("create should succeed");
+        service
+            .fetch(created.content_id.clone(), None)
+            .expect("fetch should succeed");
+        // 既知の版なら復号しないため、暗号処理の時間もペイロードサイズも記録されない
+        let version = service
+            .get(GetContentCommand {
+                content_id: created.content_id.clone(),
+                provider: None,
+            })
+            .unwrap()
+            .version;
+        service
+            .fetch_if_none_match(created.content_id.clone(), None, |v| v == version)
+            .expect("conditional fetch should succeed");
+        assert!(service
+            .delete(DeleteContentCommand {
+                content_id: ContentId::for_test("unknown-id"),
+                provider: None,
+            })
+            .is_err());
+
+        assert_eq!(
+            *metrics.operations.lock().unwrap(),
+            vec![
+                (ContentOperation::Create, true),
+                (ContentOperation::Fetch, true),
+                (ContentOperation::Fetch, true),
+                (ContentOperation::Delete, false),
+            ]
+        );
+        assert_eq!(
+            *metrics.payload_sizes.lock().unwrap(),
+            vec![(ContentOperation::Create, 5), (ContentOperation::Fetch, 5)]
+        );
+        assert_eq!(
+            *metrics.encryptions.lock().unwrap(),
+            vec![ContentOperation::Create, ContentOperation::Fetch]
+        );
+    }
+
+    #[test]
+    fn client_encrypted_content_is_stored_and_returned_without_server_keys() {
+        let (repo, storage) = TestContentRepository::new(false);
+        let (key_store, key_storage) = TestKeyStore::new(false, false);
+        let publisher = RecordingEventPublisher::default();
+        let service = ContentService {
+            content_id_generator: TestIdGenerator,
+            content_repository: repo,
+            key_generator: TestKeyGenerator,
+            encryptor: TestEncryptor,
+            cek_store: key_store,
+            event_publisher: publisher.clone(),
+            quota: ContentQuota::unlimited(),
+            chunking: ChunkingPolicy::disabled(),
+            idempotency: CreateIdempotency::disabled(),
+            metrics: NoOpContentMetrics,
+        };
+
+        let created = service
+            .create_client_encrypted(CreateClientEncryptedContentCommand {
+                name: "secret".into(),
+                path: "e2e/secret.bin".into(),
+                ciphertext: b"opaque-ciphertext".to_vec(),
+                wrapped_cek: b"wrapped".to_vec(),
+                provider: None,
+            })
+            .expect("create should succeed");
+        assert_eq!(created.encrypted_content, &b"opaque-ciphertext"[..]);
+        assert_eq!(created.metadata.size(), Some(17));
+        assert!(key_storage.lock().unwrap().is_empty());
+        assert!(storage.lock().unwrap()[created.content_id.as_str()].is_client_encrypted());
+
+        // 取得は復号せず、暗号文とラップされた CEK をそのまま返す
+        let fetched = service.fetch(created.content_id.clone(), None).unwrap();
+        assert_eq!(fetched.raw_content, &b"opaque-ciphertext"[..]);
+        assert_eq!(fetched.wrapped_cek.as_deref(), Some(&b"wrapped"[..]));
+
+        // サーバー側での暗号化・再暗号化はできない
+        assert!(matches!(
+            service.update(UpdateContentCommand {
+                content_id: created.content_id.clone(),
+                new_name: None,
+                new_raw_content: Some(b"plain".to_vec()),
+                provider: None,
+                new_raw_content_sha256: None,
+            }),
+            Err(UpdateError::Domain(ContentError::ClientEncrypted))
+        ));
+        assert!(matches!(
+            service.reencrypt(ReencryptContentCommand {
+                content_id: created.content_id.clone(),
+            }),
+            Err(ReencryptError::Domain(ContentError::ClientEncrypted))
+        ));
+
+        let updated = service
+            .update_client_encrypted(UpdateClientEncryptedContentCommand {
+                content_id: created.content_id.clone(),
+                new_name: Some("renamed".into()),
+                ciphertext: b"opaque-ciphertext-v2".to_vec(),
+                wrapped_cek: b"wrapped-v2".to_vec(),
+                provider: None,
+            })
+            .expect("update should succeed");
+        assert_eq!(updated.series_id, created.content_id);
+        assert_eq!(updated.metadata.name(), "renamed");
+        let fetched = service.fetch(updated.content_id.clone(), None).unwrap();
+        assert_eq!(fetched.raw_content, &b"opaque-ciphertext-v2"[..]);
+        assert_eq!(fetched.wrapped_cek.as_deref(), Some(&b"wrapped-v2"[..]));
+        assert!(key_storage.lock().unwrap().is_empty());
+
+        let events = publisher.events.lock().unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [
+                ContentDomainEvent::Created(_),
+                ContentDomainEvent::Updated(_)
+            ]
+        ));
+        drop(events);
+
+        // メタデータの検証は通常の作成と同じ
+        assert!(matches!(
+            service.create_client_encrypted(CreateClientEncryptedContentCommand {
+                name: " ".into(),
+                path: "e2e/x".into(),
+                ciphertext: b"c".to_vec(),
+                wrapped_cek: b"w".to_vec(),
+                provider: None,
+            }),
+            Err(CreateError::Validation(_))
+        ));
+        assert!(matches!(
+            service.create_client_encrypted(CreateClientEncryptedContentCommand {
+                name: "x".into(),
+                path: "e2e/x".into(),
+                ciphertext: b"c".to_vec(),
+                wrapped_cek: Vec::new(),
+                provider: None,
+            }),
+            Err(CreateError::Validation(_))
+        ));
+    }
 }
@@ -0,0 +1,7 @@
+mod port;
+mod schedule;
+mod service;
+
+pub use port::*;
+pub use schedule::*;
+pub use service::*;
@@ -0,0 +1,24 @@
+/// 定期実行するメンテナンスジョブ（GC、期限切れ共有の整理、鍵ローテーション、
+/// 監査ログのコンパクションなど）のポート。
+///
+/// - `run` はブロッキングしてよい（ランナーは専用のスレッドプールで実行する）。
+/// - 失敗しても次のスケジュールで再度実行される。
+pub trait MaintenanceJob {
+    fn run(&self) -> Result<(), MaintenanceJobError>;
+}
+
+/// クロージャをそのままジョブとして登録できるようにする blanket impl。
+impl<F> MaintenanceJob for F
+where
+    F: Fn() -> Result<(), MaintenanceJobError>,
+{
+    fn run(&self) -> Result<(), MaintenanceJobError> {
+        self()
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum MaintenanceJobError {
+    #[error("job failed: {0}")]
+    Failed(String),
+}
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, DurationRound, TimeDelta, Timelike, Utc};
+
+/// cron 式の次回実行時刻を探す上限（4 年分の分数。閏日の指定も必ず見つかる）。
+const MAX_CRON_SEARCH_MINUTES: i64 = 4 * 366 * 24 * 60;
+
+/// メンテナンスジョブの実行スケジュール。
+///
+/// 次のいずれかの形式で指定する（時刻はすべて UTC）。
+///
+/// - `@every <n><unit>`: 一定間隔（unit は `ms` / `s` / `m` / `h` / `d`）。例: `@every 15m`
+/// - `@hourly` / `@daily`（`@midnight`）/ `@weekly` / `@monthly` / `@yearly`（`@annually`）
+/// - 5 フィールドの cron 式 `分 時 日 月 曜日`。各フィールドは `*`・数値・範囲 `a-b`・
+///   間隔 `*/n` / `a-b/n`・カンマ区切りのリストを受け付ける。曜日は 0（日）〜 6（土）、7 も日曜。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    Every(Duration),
+    Cron(CronSchedule),
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ScheduleError {
+    #[error("invalid schedule {expr:?}: {reason}")]
+    Invalid { expr: String, reason: String },
+}
+
+impl ScheduleError {
+    fn invalid(expr: &str, reason: impl Into<String>) -> Self {
+        Self::Invalid {
+            expr: expr.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Schedule {
+    pub fn parse(expr: &str) -> Result<Self, ScheduleError> {
+        let trimmed = expr.trim();
+        if let Some(interval) = trimmed.strip_prefix("@every") {
+            let interval = parse_duration(interval.trim())
+                .ok_or_else(|| ScheduleError::invalid(expr, "invalid interval"))?;
+            if interval.is_zero() {
+                return Err(ScheduleError::invalid(expr, "interval must be positive"));
+            }
+            return Ok(Self::Every(interval));
+        }
+
+        let cron = match trimmed {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other if other.starts_with('@') => {
+                return Err(ScheduleError::invalid(expr, "unknown macro"))
+            }
+            other => other,
+        };
+        CronSchedule::parse(cron)
+            .map(Self::Cron)
+            .map_err(|reason| ScheduleError::invalid(expr, reason))
+    }
+
+    /// `after` より後の次回実行時刻。
+    ///
+    /// `Every` は `after` から間隔だけ後、`Cron` は `after` より後で最初に一致する分の先頭。
+    /// 一致する時刻が存在しない cron 式（2 月 30 日など）は `None` を返す。
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Every(interval) => TimeDelta::from_std(*interval)
+                .ok()
+                .and_then(|interval| after.checked_add_signed(interval)),
+            Self::Cron(cron) => cron.next_after(after),
+        }
+    }
+}
+
+impl std::str::FromStr for Schedule {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn parse_duration(value: &str) -> Option<Duration> {
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().ok()?;
+    let secs = match unit {
+        "ms" => return Some(Duration::from_millis(amount)),
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return None,
+    };
+    amount.checked_mul(secs).map(Duration::from_secs)
+}
+
+/// 5 フィールドの cron 式。各フィールドは一致する値のビット集合で持つ。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// 日・曜日のどちらかが `*` 以外か（両方指定時はどちらかに一致すればよい）。
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields[..] else {
+            return Err(format!("expected 5 fields, got {}", fields.len()));
+        };
+        let mut days_of_week = parse_field(dow, 0, 7, "day of week")?;
+        // 7 も日曜日として扱う
+        if days_of_week & (1u64 << 7) != 0 {
+            days_of_week = (days_of_week & !(1u64 << 7)) | 1;
+        }
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days_of_month: parse_field(dom, 1, 31, "day of month")?,
+            months: parse_field(month, 1, 12, "month")?,
+            days_of_week,
+            day_of_month_restricted: dom != "*",
+            day_of_week_restricted: dow != "*",
+        })
+    }
+
+    fn matches(&self, at: DateTime<Utc>) -> bool {
+        let bit = |set: u64, value: u32| set & (1u64 << value) != 0;
+        let dom = bit(self.days_of_month, at.day());
+        let dow = bit(self.days_of_week, at.weekday().num_days_from_sunday());
+        let day = match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => dom || dow,
+            _ => dom && dow,
+        };
+        day && bit(self.minutes, at.minute())
+            && bit(self.hours, at.hour())
+            && bit(self.months, at.month())
+    }
+
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut candidate =
+            after.duration_trunc(TimeDelta::minutes(1)).ok()? + TimeDelta::minutes(1);
+        for _ in 0..MAX_CRON_SEARCH_MINUTES {
+            if self.matches(candidate) {
+                return Some(candidate);
+            }
+            candidate += TimeDelta::minutes(1);
+        }
+        None
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> Result<u64, String> {
+    let invalid = || format!("invalid {name} field: {field:?}");
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid());
+        }
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (
+                    start.parse().map_err(|_| invalid())?,
+                    end.parse().map_err(|_| invalid())?,
+                ),
+                // `a/n` は `a-max/n` として扱う
+                None if part.contains('/') => (range.parse().map_err(|_| invalid())?, max),
+                None => {
+                    let value = range.parse().map_err(|_| invalid())?;
+                    (value, value)
+                }
+            },
+        };
+        if start < min || end > max || start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1u64 << value;
+        }
+    }
+    Ok(set)
+}
+
+/// ジョブ名ごとのスケジュール設定。
+///
+/// 設定のないジョブは、登録時に指定した既定のスケジュールで動く。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobScheduleConfig {
+    schedules: HashMap<String, Schedule>,
+}
+
+impl JobScheduleConfig {
+    pub fn with_schedule(mut self, job: impl Into<String>, schedule: Schedule) -> Self {
+        self.schedules.insert(job.into(), schedule);
+        self
+    }
+
+    /// ジョブのスケジュール。設定がなければ `default` を返す。
+    pub fn schedule_for(&self, job: &str, default: Schedule) -> Schedule {
+        self.schedules.get(job).cloned().unwrap_or(default)
+    }
+
+    /// 環境変数 `MONAS_JOB_SCHEDULES` から読み込む。
+    ///
+    /// `ジョブ名=スケジュール` をセミコロン区切りで並べる。
+    /// 例: `gc=@every 10m; share-expiry=*/5 * * * *`
+    pub fn from_env() -> Result<Self, ScheduleError> {
+        Self::parse(&std::env::var("MONAS_JOB_SCHEDULES").unwrap_or_default())
+    }
+
+    fn parse(value: &str) -> Result<Self, ScheduleError> {
+        let mut config = Self::default();
+        for entry in value.split(';').filter(|e| !e.trim().is_empty()) {
+            let (job, expr) = entry
+                .split_once('=')
+                .ok_or_else(|| ScheduleError::invalid(entry.trim(), "expected <job>=<schedule>"))?;
+            config = config.with_schedule(job.trim(), Schedule::parse(expr)?);
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, s).unwrap()
+    }
+
+    #[test]
+    fn every_schedules_run_at_fixed_intervals() {
+        let schedule = Schedule::parse("@every 15m").unwrap();
+        assert_eq!(schedule, Schedule::Every(Duration::from_secs(15 * 60)));
+        assert_eq!(
+            schedule.next_after(at(2025, 1, 1, 0, 7, 30)),
+            Some(at(2025, 1, 1, 0, 22, 30))
+        );
+        assert_eq!(
+            Schedule::parse("@every 250ms").unwrap(),
+            Schedule::Every(Duration::from_millis(250))
+        );
+    }
+
+    #[test]
+    fn cron_schedules_find_the_next_matching_minute() {
+        let every_five = Schedule::parse("*/5 * * * *").unwrap();
+        assert_eq!(
+            every_five.next_after(at(2025, 1, 1, 10, 7, 30)),
+            Some(at(2025, 1, 1, 10, 10, 0))
+        );
+        // ちょうど一致する時刻からは次の一致まで進む
+        assert_eq!(
+            every_five.next_after(at(2025, 1, 1, 10, 10, 0)),
+            Some(at(2025, 1, 1, 10, 15, 0))
+        );
+
+        let daily = Schedule::parse("@daily").unwrap();
+        assert_eq!(
+            daily.next_after(at(2025, 1, 31, 23, 59, 59)),
+            Some(at(2025, 2, 1, 0, 0, 0))
+        );
+
+        // 平日 9:30。2025-01-04 は土曜日
+        let weekdays = Schedule::parse("30 9 * * 1-5").unwrap();
+        assert_eq!(
+            weekdays.next_after(at(2025, 1, 4, 12, 0, 0)),
+            Some(at(2025, 1, 6, 9, 30, 0))
+        );
+
+        // 日と曜日を両方指定した場合はどちらかに一致すればよい（日曜 = 7）
+        let either = Schedule::parse("0 0 15 * 7").unwrap();
+        assert_eq!(
+            either.next_after(at(2025, 1, 1, 0, 0, 0)),
+            Some(at(2025, 1, 5, 0, 0, 0))
+        );
+
+        assert_eq!(
+            Schedule::parse("0 0 30 2 *")
+                .unwrap()
+                .next_after(at(2025, 1, 1, 0, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_schedules_are_rejected() {
+        for expr in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "@every",
+            "@every 0s",
+            "@every 5x",
+            "@fortnightly",
+        ] {
+            assert!(
+                Schedule::parse(expr).is_err(),
+                "{expr:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn config_overrides_default_schedules() {
+        let config = JobScheduleConfig::parse("gc=@every 10m; share-expiry = */5 * * * *").unwrap();
+        assert_eq!(
+            config.schedule_for("gc", Schedule::Every(Duration::from_secs(1))),
+            Schedule::Every(Duration::from_secs(600))
+        );
+        assert_eq!(
+            config.schedule_for("share-expiry", Schedule::Every(Duration::from_secs(1))),
+            Schedule::parse("*/5 * * * *").unwrap()
+        );
+        assert_eq!(
+            config.schedule_for("audit", Schedule::Every(Duration::from_secs(1))),
+            Schedule::Every(Duration::from_secs(1))
+        );
+
+        assert!(JobScheduleConfig::parse("").unwrap().schedules.is_empty());
+        assert!(JobScheduleConfig::parse("gc").is_err());
+        assert!(JobScheduleConfig::parse("gc=* *").is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+use tokio::task::JoinHandle;
+
+use super::{JobScheduleConfig, MaintenanceJob, MaintenanceJobError, Schedule};
+
+/// ジョブ 1 件分の実行状況。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobStatus {
+    pub name: String,
+    pub schedule: Schedule,
+    /// 完了した実行の回数（失敗を含む）。
+    pub runs: u64,
+    pub failures: u64,
+    /// 実行中なら開始時刻。
+    pub running_since: Option<DateTime<Utc>>,
+    pub last_finished_at: Option<DateTime<Utc>>,
+    /// 直近の実行が失敗した場合のエラー。
+    pub last_error: Option<String>,
+    /// 次回の実行予定。一致する時刻がないスケジュールでは `None`。
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+impl JobStatus {
+    fn new(name: String, schedule: Schedule) -> Self {
+        Self {
+            name,
+            schedule,
+            runs: 0,
+            failures: 0,
+            running_since: None,
+            last_finished_at: None,
+            last_error: None,
+            next_run_at: None,
+        }
+    }
+}
+
+type SharedJob = Arc<dyn MaintenanceJob + Send + Sync>;
+type JobStatuses = Arc<RwLock<BTreeMap<String, JobStatus>>>;
+
+/// メンテナンスジョブをスケジュールに従って実行するランナー。
+///
+/// - ジョブごとに tokio タスクを 1 つ起動し、`run` は `spawn_blocking` で実行する。
+/// - 同じジョブが重なって実行されることはない（前回の完了後に次回の時刻を計算する）。
+/// - スケジュールは `JobScheduleConfig` の設定が登録時の既定より優先される。
+pub struct JobRunner {
+    config: JobScheduleConfig,
+    jobs: Vec<(String, Schedule, SharedJob)>,
+}
+
+impl JobRunner {
+    pub fn new(config: JobScheduleConfig) -> Self {
+        Self {
+            config,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// ジョブを登録する。同名のジョブを登録した場合は後から登録したものが優先される。
+    pub fn register<J>(
+        mut self,
+        name: impl Into<String>,
+        default_schedule: Schedule,
+        job: J,
+    ) -> Self
+    where
+        J: MaintenanceJob + Send + Sync + 'static,
+    {
+        let name = name.into();
+        let schedule = self.config.schedule_for(&name, default_schedule);
+        self.jobs.retain(|(registered, _, _)| *registered != name);
+        self.jobs.push((name, schedule, Arc::new(job)));
+        self
+    }
+
+    /// 登録済みのジョブをすべて起動する。tokio ランタイム内から呼び出すこと。
+    pub fn spawn(self) -> JobRunnerHandle {
+        let statuses: JobStatuses = Arc::new(RwLock::new(
+            self.jobs
+                .iter()
+                .map(|(name, schedule, _)| {
+                    (name.clone(), JobStatus::new(name.clone(), schedule.clone()))
+                })
+                .collect(),
+        ));
+        let tasks = self
+            .jobs
+            .into_iter()
+            .map(|(name, schedule, job)| {
+                tokio::spawn(run_job(name, schedule, job, statuses.clone()))
+            })
+            .collect();
+        JobRunnerHandle { statuses, tasks }
+    }
+}
+
+async fn run_job(name: String, schedule: Schedule, job: SharedJob, statuses: JobStatuses) {
+    let update = |f: &dyn Fn(&mut JobStatus)| {
+        if let Some(status) = statuses.write().unwrap().get_mut(&name) {
+            f(status);
+        }
+    };
+
+    loop {
+        let now = Utc::now();
+        let next = schedule.next_after(now);
+        update(&|status| status.next_run_at = next);
+        let Some(next) = next else {
+            tracing::warn!(job = %name, "schedule has no upcoming run; stopping job");
+            break;
+        };
+        tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+
+        update(&|status| {
+            status.running_since = Some(Utc::now());
+            status.next_run_at = None;
+        });
+        let job = job.clone();
+        let result = tokio::task::spawn_blocking(move || job.run())
+            .await
+            .unwrap_or_else(|e| Err(MaintenanceJobError::Failed(format!("job panicked: {e}"))));
+        if let Err(e) = &result {
+            tracing::warn!(job = %name, error = %e, "maintenance job failed");
+        }
+
+        let finished_at = Utc::now();
+        update(&|status| {
+            status.runs += 1;
+            status.running_since = None;
+            status.last_finished_at = Some(finished_at);
+            status.last_error = result.as_ref().err().map(|e| e.to_string());
+            if result.is_err() {
+                status.failures += 1;
+            }
+        });
+    }
+}
+
+/// 起動したジョブの実行状況の参照と停止に使うハンドル。
+///
+/// ハンドルを破棄するとすべてのジョブを停止する。
+pub struct JobRunnerHandle {
+    statuses: JobStatuses,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl JobRunnerHandle {
+    /// 全ジョブの実行状況（ジョブ名順）。
+    pub fn statuses(&self) -> Vec<JobStatus> {
+        self.statuses.read().unwrap().values().cloned().collect()
+    }
+
+    pub fn status(&self, name: &str) -> Option<JobStatus> {
+        self.statuses.read().unwrap().get(name).cloned()
+    }
+
+    /// すべてのジョブを停止する。実行中の `run` は完了まで続くが、次回は実行されない。
+    pub fn shutdown(&self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+impl Drop for JobRunnerHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    fn every(millis: u64) -> Schedule {
+        Schedule::Every(Duration::from_millis(millis))
+    }
+
+    async fn wait_until(handle: &JobRunnerHandle, name: &str, f: impl Fn(&JobStatus) -> bool) {
+        for _ in 0..200 {
+            if handle.status(name).is_some_and(|s| f(&s)) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {name} did not reach the expected state");
+    }
+
+    #[tokio::test]
+    async fn jobs_run_repeatedly_and_record_failures() {
+        let count = Arc::new(AtomicU64::new(0));
+        let count_in_job = count.clone();
+        let handle = JobRunner::new(JobScheduleConfig::default())
+            .register("gc", every(10), move || {
+                count_in_job.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+            .register("audit-compaction", every(10), || {
+                Err(MaintenanceJobError::Failed("disk full".into()))
+            })
+            .spawn();
+
+        wait_until(&handle, "gc", |s| s.runs >= 3).await;
+        wait_until(&handle, "audit-compaction", |s| s.failures >= 2).await;
+
+        let names: Vec<_> = handle.statuses().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["audit-compaction", "gc"]);
+        let gc = handle.status("gc").unwrap();
+        assert_eq!(gc.failures, 0);
+        assert!(gc.last_error.is_none() && gc.last_finished_at.is_some());
+        let audit = handle.status("audit-compaction").unwrap();
+        assert_eq!(audit.last_error.as_deref(), Some("job failed: disk full"));
+
+        // 停止後は実行されない
+        handle.shutdown();
+        let stopped_at = count.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(count.load(Ordering::SeqCst) <= stopped_at + 1);
+    }
+
+    #[tokio::test]
+    async fn configured_schedules_override_defaults() {
+        let config = JobScheduleConfig::default().with_schedule("share-expiry", every(10));
+        let handle = JobRunner::new(config)
+            .register("share-expiry", Schedule::parse("@yearly").unwrap(), || {
+                Ok(())
+            })
+            .register("key-rotation", Schedule::parse("@yearly").unwrap(), || {
+                Ok(())
+            })
+            .spawn();
+
+        wait_until(&handle, "share-expiry", |s| s.runs >= 1).await;
+        let rotation = handle.status("key-rotation").unwrap();
+        assert_eq!(rotation.runs, 0);
+        assert_eq!(rotation.schedule, Schedule::parse("@yearly").unwrap());
+        assert!(rotation.next_run_at.is_some());
+    }
+}
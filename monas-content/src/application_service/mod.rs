@@ -1,2 +1,5 @@
+pub mod bundle_service;
 pub mod content_service;
+pub mod job_service;
+pub mod rotation_service;
 pub mod share_service;
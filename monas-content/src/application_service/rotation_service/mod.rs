@@ -0,0 +1,7 @@
+mod policy;
+mod port;
+mod service;
+
+pub use policy::*;
+pub use port::*;
+pub use service::*;
@@ -0,0 +1,230 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// 定期評価の既定間隔（1 時間）。
+const DEFAULT_EVALUATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// CEK のローテーションポリシー。
+///
+/// 各上限は `None` の場合は評価しない。すべて `None`（既定）のときポリシーは無効。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotationPolicy {
+    /// CEK を使い続けてよい最大期間。
+    pub max_key_age: Option<Duration>,
+    /// 1 つの CEK で暗号化してよい最大バイト数（暗号文ベース、更新の累計）。
+    pub max_bytes_per_cek: Option<u64>,
+    /// 1 つの CEK を共有してよい最大の相手数（オーナーを除く）。
+    pub max_shares_per_key: Option<usize>,
+    /// 定期評価ジョブの実行間隔。
+    pub evaluation_interval: Duration,
+}
+
+impl Default for RotationPolicy {
+    fn default() -> Self {
+        Self {
+            max_key_age: None,
+            max_bytes_per_cek: None,
+            max_shares_per_key: None,
+            evaluation_interval: DEFAULT_EVALUATION_INTERVAL,
+        }
+    }
+}
+
+/// ポリシー評価の入力となる、コンテンツ 1 件分の CEK の利用状況。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyUsageSnapshot {
+    pub issued_at: DateTime<Utc>,
+    pub bytes_encrypted: u64,
+    pub share_count: usize,
+}
+
+/// ポリシー違反の内容。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyViolation {
+    /// CEK の使用期間が上限を超えた。
+    KeyTooOld { age_secs: u64, max_secs: u64 },
+    /// CEK で暗号化したバイト数が上限を超えた。
+    TooManyBytes { bytes: u64, max: u64 },
+    /// CEK の共有相手が上限を超えた。
+    TooManyShares { shares: usize, max: usize },
+}
+
+impl PolicyViolation {
+    /// CEK のローテーションで解消する違反かどうか。
+    ///
+    /// 共有数の上限超過は鍵を回しても共有相手が変わらないため、共有を解除するまで解消しない。
+    pub fn requires_rotation(&self) -> bool {
+        match self {
+            Self::KeyTooOld { .. } | Self::TooManyBytes { .. } => true,
+            Self::TooManyShares { .. } => false,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RotationPolicyError {
+    #[error("invalid value for {name}: {value}")]
+    InvalidValue { name: &'static str, value: String },
+}
+
+impl RotationPolicy {
+    /// いずれかの上限が設定されているかどうか。
+    pub fn is_enabled(&self) -> bool {
+        self.max_key_age.is_some()
+            || self.max_bytes_per_cek.is_some()
+            || self.max_shares_per_key.is_some()
+    }
+
+    /// 環境変数から構築する。
+    ///
+    /// - `MONAS_ROTATION_MAX_KEY_AGE_SECS`: CEK の最大使用期間（秒）
+    /// - `MONAS_ROTATION_MAX_BYTES_PER_CEK`: CEK あたりの最大暗号化バイト数
+    /// - `MONAS_ROTATION_MAX_SHARES_PER_KEY`: CEK あたりの最大共有数
+    /// - `MONAS_ROTATION_INTERVAL_SECS`: 定期評価の間隔（秒、既定 3600）
+    ///
+    /// 未設定の上限は評価しない。数値として解釈できない場合はエラーを返す。
+    pub fn from_env() -> Result<Self, RotationPolicyError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, RotationPolicyError> {
+        fn parse<T: std::str::FromStr>(
+            lookup: &impl Fn(&str) -> Option<String>,
+            name: &'static str,
+        ) -> Result<Option<T>, RotationPolicyError> {
+            match lookup(name).filter(|v| !v.trim().is_empty()) {
+                Some(value) => value
+                    .trim()
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| RotationPolicyError::InvalidValue { name, value }),
+                None => Ok(None),
+            }
+        }
+
+        let defaults = Self::default();
+        Ok(Self {
+            max_key_age: parse::<u64>(&lookup, "MONAS_ROTATION_MAX_KEY_AGE_SECS")?
+                .map(Duration::from_secs),
+            max_bytes_per_cek: parse(&lookup, "MONAS_ROTATION_MAX_BYTES_PER_CEK")?,
+            max_shares_per_key: parse(&lookup, "MONAS_ROTATION_MAX_SHARES_PER_KEY")?,
+            evaluation_interval: parse::<u64>(&lookup, "MONAS_ROTATION_INTERVAL_SECS")?
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.evaluation_interval),
+        })
+    }
+
+    /// `now` 時点の利用状況を評価し、違反している項目を返す。空なら準拠している。
+    pub fn evaluate(&self, usage: &KeyUsageSnapshot, now: DateTime<Utc>) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+
+        if let Some(max_age) = self.max_key_age {
+            let age = (now - usage.issued_at).to_std().unwrap_or_default();
+            if age > max_age {
+                violations.push(PolicyViolation::KeyTooOld {
+                    age_secs: age.as_secs(),
+                    max_secs: max_age.as_secs(),
+                });
+            }
+        }
+
+        if let Some(max) = self.max_bytes_per_cek {
+            if usage.bytes_encrypted > max {
+                violations.push(PolicyViolation::TooManyBytes {
+                    bytes: usage.bytes_encrypted,
+                    max,
+                });
+            }
+        }
+
+        if let Some(max) = self.max_shares_per_key {
+            if usage.share_count > max {
+                violations.push(PolicyViolation::TooManyShares {
+                    shares: usage.share_count,
+                    max,
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn evaluate_reports_each_exceeded_limit() {
+        let policy = RotationPolicy {
+            max_key_age: Some(Duration::from_secs(60)),
+            max_bytes_per_cek: Some(100),
+            max_shares_per_key: Some(2),
+            ..RotationPolicy::default()
+        };
+        let now = Utc::now();
+
+        let compliant = KeyUsageSnapshot {
+            issued_at: now - chrono::Duration::seconds(30),
+            bytes_encrypted: 100,
+            share_count: 2,
+        };
+        assert!(policy.evaluate(&compliant, now).is_empty());
+
+        let violating = KeyUsageSnapshot {
+            issued_at: now - chrono::Duration::seconds(120),
+            bytes_encrypted: 101,
+            share_count: 3,
+        };
+        assert_eq!(
+            policy.evaluate(&violating, now),
+            vec![
+                PolicyViolation::KeyTooOld {
+                    age_secs: 120,
+                    max_secs: 60
+                },
+                PolicyViolation::TooManyBytes {
+                    bytes: 101,
+                    max: 100
+                },
+                PolicyViolation::TooManyShares { shares: 3, max: 2 },
+            ]
+        );
+
+        // 上限を設定しなければ何も評価しない
+        assert!(!RotationPolicy::default().is_enabled());
+        assert!(RotationPolicy::default()
+            .evaluate(&violating, now)
+            .is_empty());
+    }
+
+    #[test]
+    fn from_lookup_parses_limits_and_rejects_garbage() {
+        let env: HashMap<&str, &str> = [
+            ("MONAS_ROTATION_MAX_KEY_AGE_SECS", "86400"),
+            ("MONAS_ROTATION_MAX_SHARES_PER_KEY", "10"),
+            ("MONAS_ROTATION_INTERVAL_SECS", "60"),
+        ]
+        .into_iter()
+        .collect();
+        let policy = RotationPolicy::from_lookup(|k| env.get(k).map(|v| v.to_string())).unwrap();
+        assert_eq!(policy.max_key_age, Some(Duration::from_secs(86400)));
+        assert_eq!(policy.max_bytes_per_cek, None);
+        assert_eq!(policy.max_shares_per_key, Some(10));
+        assert_eq!(policy.evaluation_interval, Duration::from_secs(60));
+        assert!(policy.is_enabled());
+
+        let err = RotationPolicy::from_lookup(|k| {
+            (k == "MONAS_ROTATION_MAX_BYTES_PER_CEK").then(|| "lots".to_string())
+        });
+        assert!(matches!(
+            err,
+            Err(RotationPolicyError::InvalidValue {
+                name: "MONAS_ROTATION_MAX_BYTES_PER_CEK",
+                ..
+            })
+        ));
+    }
+}
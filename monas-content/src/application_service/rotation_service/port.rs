@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::content_id::ContentId;
+
+use super::PolicyViolation;
+
+/// ローテーションポリシーの評価対象となるコンテンツの一覧を提供するポート。
+///
+/// 削除済み・更新前の旧版を含んでもよい（評価時に読み飛ばす）。
+pub trait ContentCatalog {
+    fn list(&self) -> Result<Vec<ContentId>, ContentCatalogError>;
+}
+
+impl<T: ContentCatalog + ?Sized> ContentCatalog for std::sync::Arc<T> {
+    fn list(&self) -> Result<Vec<ContentId>, ContentCatalogError> {
+        (**self).list()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContentCatalogError {
+    #[error("storage error: {0}")]
+    Storage(String),
+}
+
+/// ポリシーに違反したコンテンツの鍵ローテーション要求。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotationTask {
+    pub content_id: ContentId,
+    pub violations: Vec<PolicyViolation>,
+    pub queued_at: DateTime<Utc>,
+}
+
+/// 鍵ローテーション要求を順番に処理するためのキューのポート。
+pub trait RotationTaskQueue {
+    /// 要求を末尾に追加する。同じコンテンツの要求が既に待機中なら追加せず false を返す。
+    fn enqueue(&self, task: RotationTask) -> Result<bool, RotationTaskQueueError>;
+
+    /// 先頭の要求を取り出す。
+    fn dequeue(&self) -> Result<Option<RotationTask>, RotationTaskQueueError>;
+
+    /// 待機中の要求の数。
+    fn len(&self) -> Result<usize, RotationTaskQueueError>;
+
+    fn is_empty(&self) -> Result<bool, RotationTaskQueueError> {
+        Ok(self.len()? == 0)
+    }
+}
+
+impl<T: RotationTaskQueue + ?Sized> RotationTaskQueue for std::sync::Arc<T> {
+    fn enqueue(&self, task: RotationTask) -> Result<bool, RotationTaskQueueError> {
+        (**self).enqueue(task)
+    }
+
+    fn dequeue(&self) -> Result<Option<RotationTask>, RotationTaskQueueError> {
+        (**self).dequeue()
+    }
+
+    fn len(&self) -> Result<usize, RotationTaskQueueError> {
+        (**self).len()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RotationTaskQueueError {
+    #[error("storage error: {0}")]
+    Storage(String),
+}
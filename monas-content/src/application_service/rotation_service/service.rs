@@ -0,0 +1,445 @@
+use std::sync::{Arc, RwLock, Weak};
+use std::thread::JoinHandle;
+
+use chrono::{DateTime, Utc};
+
+use crate::application_service::content_service::{ContentRepository, ContentRepositoryError};
+use crate::application_service::share_service::{ShareRepository, ShareRepositoryError};
+use crate::domain::content_id::ContentId;
+use crate::domain::share::Share;
+
+use super::{
+    ContentCatalog, ContentCatalogError, KeyUsageSnapshot, PolicyViolation, RotationPolicy,
+    RotationTask, RotationTaskQueue, RotationTaskQueueError,
+};
+
+/// ポリシーに違反したコンテンツ 1 件分の評価結果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentCompliance {
+    pub content_id: ContentId,
+    pub violations: Vec<PolicyViolation>,
+}
+
+/// ポリシー評価の結果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComplianceReport {
+    pub evaluated_at: DateTime<Utc>,
+    /// いずれかの上限が設定されているかどうか。
+    pub policy_enabled: bool,
+    /// 評価したコンテンツの数（削除済み・存在しないものを除く）。
+    pub evaluated: usize,
+    /// ポリシーに違反していたコンテンツ。
+    pub non_compliant: Vec<ContentCompliance>,
+    /// 今回の評価で新たにキューへ追加したローテーション要求の数。
+    pub queued: usize,
+}
+
+impl ComplianceReport {
+    pub fn compliant(&self) -> usize {
+        self.evaluated - self.non_compliant.len()
+    }
+}
+
+/// キューに溜まったローテーション要求を処理した結果。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RotationRunResult {
+    pub rotated: usize,
+    /// 失敗してキューへ戻した要求の数。
+    pub failed: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RotationError {
+    #[error("catalog error: {0}")]
+    Catalog(#[from] ContentCatalogError),
+    #[error("content repository error: {0}")]
+    ContentRepository(#[from] ContentRepositoryError),
+    #[error("share repository error: {0}")]
+    ShareRepository(#[from] ShareRepositoryError),
+    #[error("task queue error: {0}")]
+    TaskQueue(#[from] RotationTaskQueueError),
+}
+
+/// CEK のローテーションポリシーを評価し、違反したコンテンツの鍵ローテーション要求を
+/// キューに積むアプリケーションサービス。
+///
+/// - `evaluate` はカタログ上のすべてのコンテンツを評価し、結果を `last_report` として保持する。
+/// - ローテーション自体（`ContentService::reencrypt` など）は `rotate_queued` に渡す関数が行う。
+/// - 共有数の上限はオーナーを除く現在の共有相手の数で判定するため、
+///   共有を解除するまでは評価のたびに違反として扱われる。鍵を回しても解消しないので、
+///   この違反だけではローテーション要求を積まない。
+pub struct RotationPolicyService<C, R, S, Q> {
+    pub catalog: C,
+    pub content_repository: R,
+    pub share_repository: S,
+    pub task_queue: Q,
+    pub policy: RotationPolicy,
+    last_report: RwLock<Option<ComplianceReport>>,
+}
+
+impl<C, R, S, Q> RotationPolicyService<C, R, S, Q>
+where
+    C: ContentCatalog,
+    R: ContentRepository,
+    S: ShareRepository,
+    Q: RotationTaskQueue,
+{
+    pub fn new(
+        catalog: C,
+        content_repository: R,
+        share_repository: S,
+        task_queue: Q,
+        policy: RotationPolicy,
+    ) -> Self {
+        Self {
+            catalog,
+            content_repository,
+            share_repository,
+            task_queue,
+            policy,
+            last_report: RwLock::new(None),
+        }
+    }
+
+    /// カタログ上のコンテンツを評価し、鍵ローテーションで解消する違反があるものを
+    /// ローテーション要求としてキューに積む。
+    pub fn evaluate(&self) -> Result<ComplianceReport, RotationError> {
+        let now = Utc::now();
+        let mut report = ComplianceReport {
+            evaluated_at: now,
+            policy_enabled: self.policy.is_enabled(),
+            evaluated: 0,
+            non_compliant: Vec::new(),
+            queued: 0,
+        };
+
+        for content_id in self.catalog.list()? {
+            let Some(content) = self.content_repository.find_by_id(&content_id)? else {
+                continue;
+            };
+            // クライアント側で暗号化されたコンテンツは CEK をクライアントが管理するため対象外
+            if content.is_deleted() || content.is_client_encrypted() {
+                continue;
+            }
+            report.evaluated += 1;
+
+            let share_count = self
+                .share_repository
+                .load(&content_id)?
+                .map_or(0, |share| shared_recipient_count(&share));
+            let usage = KeyUsageSnapshot {
+                issued_at: content.key_issued_at(),
+                bytes_encrypted: content.key_usage().bytes_encrypted(),
+                share_count,
+            };
+
+            let violations = self.policy.evaluate(&usage, now);
+            if violations.is_empty() {
+                continue;
+            }
+
+            if violations.iter().any(PolicyViolation::requires_rotation) {
+                let queued = self.task_queue.enqueue(RotationTask {
+                    content_id: content_id.clone(),
+                    violations: violations.clone(),
+                    queued_at: now,
+                })?;
+                if queued {
+                    report.queued += 1;
+                }
+            }
+            report.non_compliant.push(ContentCompliance {
+                content_id,
+                violations,
+            });
+        }
+
+        *self.last_report.write().unwrap() = Some(report.clone());
+        Ok(report)
+    }
+
+    /// 直近の評価結果。まだ評価していなければ `None`。
+    pub fn last_report(&self) -> Option<ComplianceReport> {
+        self.last_report.read().unwrap().clone()
+    }
+
+    /// 処理待ちのローテーション要求の数。
+    pub fn pending_tasks(&self) -> Result<usize, RotationError> {
+        Ok(self.task_queue.len()?)
+    }
+
+    /// 現在キューにある要求を 1 回ずつ `rotate` に渡して処理する。
+    ///
+    /// 失敗した要求はキューの末尾へ戻し、次回以降に再試行する。
+    pub fn rotate_queued<F, E>(&self, mut rotate: F) -> Result<RotationRunResult, RotationError>
+    where
+        F: FnMut(&ContentId) -> Result<(), E>,
+    {
+        let mut result = RotationRunResult::default();
+        for _ in 0..self.task_queue.len()? {
+            let Some(task) = self.task_queue.dequeue()? else {
+                break;
+            };
+            match rotate(&task.content_id) {
+                Ok(()) => result.rotated += 1,
+                Err(_) => {
+                    result.failed += 1;
+                    self.task_queue.enqueue(task)?;
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<C, R, S, Q> RotationPolicyService<C, R, S, Q>
+where
+    C: ContentCatalog + Send + Sync + 'static,
+    R: ContentRepository + Send + Sync + 'static,
+    S: ShareRepository + Send + Sync + 'static,
+    Q: RotationTaskQueue + Send + Sync + 'static,
+{
+    /// `policy.evaluation_interval` ごとに評価とローテーションを行うスレッドを起動する。
+    ///
+    /// サービスへの参照がすべて破棄されると、次の周期でスレッドは終了する。
+    pub fn spawn_scheduler<F, E>(self: &Arc<Self>, mut rotate: F) -> JoinHandle<()>
+    where
+        F: FnMut(&ContentId) -> Result<(), E> + Send + 'static,
+    {
+        let service: Weak<Self> = Arc::downgrade(self);
+        let interval = self.policy.evaluation_interval;
+        std::thread::Builder::new()
+            .name("monas-key-rotation".into())
+            .spawn(move || loop {
+                std::thread::sleep(interval);
+                let Some(service) = service.upgrade() else {
+                    break;
+                };
+                // 評価・ローテーションの失敗は次の周期で再試行する
+                let _ = service.evaluate();
+                let _ = service.rotate_queued(&mut rotate);
+            })
+            .expect("failed to spawn key rotation thread")
+    }
+}
+
+/// オーナーを除く共有相手の数。
+fn shared_recipient_count(share: &Share) -> usize {
+    let owner = share.owner_key_id();
+    share
+        .recipients()
+        .keys()
+        .filter(|key_id| Some(*key_id) != owner)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::domain::content::encryption::ContentEncryptionKey;
+    use crate::domain::content::Content;
+    use crate::domain::share::KeyId;
+    use crate::infrastructure::content_id::Sha256ContentIdGenerator;
+    use crate::infrastructure::encryption::Aes256CtrContentEncryption;
+    use crate::infrastructure::rotation::{InMemoryContentCatalog, InMemoryRotationTaskQueue};
+    use crate::infrastructure::share_repository::InMemoryShareRepository;
+
+    #[derive(Clone, Default)]
+    struct TestContentRepository {
+        contents: Arc<Mutex<HashMap<String, Content>>>,
+    }
+
+    impl ContentRepository for TestContentRepository {
+        fn save(
+            &self,
+            content_id: &ContentId,
+            content: &Content,
+        ) -> Result<(), ContentRepositoryError> {
+            self.contents
+                .lock()
+                .unwrap()
+                .insert(content_id.as_str().to_string(), content.clone());
+            Ok(())
+        }
+
+        fn find_by_id(
+            &self,
+            content_id: &ContentId,
+        ) -> Result<Option<Content>, ContentRepositoryError> {
+            Ok(self
+                .contents
+                .lock()
+                .unwrap()
+                .get(content_id.as_str())
+                .cloned())
+        }
+    }
+
+    fn store_content(
+        repo: &TestContentRepository,
+        catalog: &InMemoryContentCatalog,
+        raw: &str,
+    ) -> ContentId {
+        let (content, _) = Content::create(
+            "name".into(),
+            raw.as_bytes().to_vec(),
+            "path.txt".into(),
+            None,
+            &Sha256ContentIdGenerator,
+            &ContentEncryptionKey(vec![1u8; 32]),
+            &Aes256CtrContentEncryption,
+        )
+        .unwrap();
+        let id = content.raw_id().clone();
+        repo.save(&id, &content).unwrap();
+        catalog.insert(id.clone(), id.clone());
+        id
+    }
+
+    fn build_service(
+        policy: RotationPolicy,
+    ) -> (
+        RotationPolicyService<
+            InMemoryContentCatalog,
+            TestContentRepository,
+            InMemoryShareRepository,
+            InMemoryRotationTaskQueue,
+        >,
+        TestContentRepository,
+        InMemoryShareRepository,
+    ) {
+        let repo = TestContentRepository::default();
+        let shares = InMemoryShareRepository::default();
+        let service = RotationPolicyService::new(
+            InMemoryContentCatalog::default(),
+            repo.clone(),
+            shares.clone(),
+            InMemoryRotationTaskQueue::default(),
+            policy,
+        );
+        (service, repo, shares)
+    }
+
+    #[test]
+    fn evaluate_reports_share_violations_without_queuing_rotation() {
+        let (service, repo, shares) = build_service(RotationPolicy {
+            max_shares_per_key: Some(1),
+            ..RotationPolicy::default()
+        });
+        store_content(&repo, &service.catalog, "quiet");
+        let popular = store_content(&repo, &service.catalog, "popular");
+
+        let mut share = Share::new(popular.clone());
+        share.grant_owner(KeyId::new(vec![0])).unwrap();
+        share.grant_read(KeyId::new(vec![1])).unwrap();
+        share.grant_read(KeyId::new(vec![2])).unwrap();
+        shares.save(&share).unwrap();
+
+        let report = service.evaluate().unwrap();
+        assert!(report.policy_enabled);
+        assert_eq!(report.evaluated, 2);
+        assert_eq!(report.compliant(), 1);
+        assert_eq!(
+            report.non_compliant,
+            vec![ContentCompliance {
+                content_id: popular,
+                violations: vec![PolicyViolation::TooManyShares { shares: 2, max: 1 }],
+            }]
+        );
+        assert_eq!(service.last_report(), Some(report.clone()));
+
+        // 鍵を回しても共有数は減らないので、ローテーション要求は積まない
+        assert_eq!(report.queued, 0);
+        assert_eq!(service.pending_tasks().unwrap(), 0);
+    }
+
+    #[test]
+    fn evaluate_queues_non_compliant_content_once() {
+        let (service, repo, _) = build_service(RotationPolicy {
+            max_bytes_per_cek: Some(1),
+            ..RotationPolicy::default()
+        });
+        let id = store_content(&repo, &service.catalog, "large");
+
+        assert_eq!(service.evaluate().unwrap().queued, 1);
+        // 処理待ちの要求は重複して積まない
+        assert_eq!(service.evaluate().unwrap().queued, 0);
+        assert_eq!(service.pending_tasks().unwrap(), 1);
+
+        let mut rotated = Vec::new();
+        let result = service
+            .rotate_queued(|id| {
+                rotated.push(id.clone());
+                Ok::<_, ()>(())
+            })
+            .unwrap();
+        assert_eq!(result.rotated, 1);
+        assert_eq!(rotated, vec![id]);
+        assert_eq!(service.pending_tasks().unwrap(), 0);
+    }
+
+    #[test]
+    fn evaluate_skips_deleted_content_and_failed_rotations_are_retried() {
+        let (service, repo, _) = build_service(RotationPolicy {
+            max_bytes_per_cek: Some(1),
+            ..RotationPolicy::default()
+        });
+        let live = store_content(&repo, &service.catalog, "live");
+        let gone = store_content(&repo, &service.catalog, "gone");
+        let deleted = repo.find_by_id(&gone).unwrap().unwrap().delete().unwrap().0;
+        repo.save(&gone, &deleted).unwrap();
+
+        let report = service.evaluate().unwrap();
+        assert_eq!(report.evaluated, 1);
+        assert_eq!(report.non_compliant[0].content_id, live);
+        assert!(matches!(
+            report.non_compliant[0].violations[0],
+            PolicyViolation::TooManyBytes { max: 1, .. }
+        ));
+
+        let result = service.rotate_queued(|_| Err("offline")).unwrap();
+        assert_eq!(
+            result,
+            RotationRunResult {
+                rotated: 0,
+                failed: 1
+            }
+        );
+        assert_eq!(service.pending_tasks().unwrap(), 1);
+    }
+
+    #[test]
+    fn scheduler_evaluates_and_rotates_periodically() {
+        let (service, repo, _) = build_service(RotationPolicy {
+            max_key_age: Some(Duration::ZERO),
+            evaluation_interval: Duration::from_millis(10),
+            ..RotationPolicy::default()
+        });
+        let id = store_content(&repo, &service.catalog, "aging");
+        let service = Arc::new(service);
+
+        let rotated = Arc::new(Mutex::new(Vec::new()));
+        let rotated_in_job = rotated.clone();
+        let handle = service.spawn_scheduler(move |id: &ContentId| {
+            rotated_in_job.lock().unwrap().push(id.clone());
+            Ok::<_, ()>(())
+        });
+
+        for _ in 0..100 {
+            if !rotated.lock().unwrap().is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(rotated.lock().unwrap().first(), Some(&id));
+        assert!(service.last_report().is_some());
+
+        // サービスを破棄するとスレッドは終了する
+        drop(service);
+        handle.join().unwrap();
+    }
+}
@@ -1,14 +1,27 @@
+use chrono::{DateTime, Utc};
+
 use crate::domain::content_id::ContentId;
 use crate::domain::share::{KeyEnvelope, KeyId, Permission};
 
+/// 共有の受信者の指定方法。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareRecipient {
+    /// 受信者の公開鍵バイト列を直接指定する。
+    PublicKey(Vec<u8>),
+    /// 受信者のアカウント識別子（monas-account の did:key）を指定する。
+    ///
+    /// 公開鍵は `PublicKeyDirectory::find_public_key_by_account` で解決する。
+    Account(String),
+}
+
 /// コンテンツを 1 人の受信者と共有するユースケースの入力。
 ///
-/// - クライアントは受信者の公開鍵バイト列を渡すだけでよく、KeyId の生成と保存はアプリケーション側で行う。
+/// - クライアントは受信者の公開鍵バイト列かアカウント識別子を渡すだけでよく、KeyId の生成と保存はアプリケーション側で行う。
 #[derive(Debug)]
 pub struct GrantShareCommand {
     pub content_id: ContentId,
     pub sender_key_id: KeyId,
-    pub recipient_public_key: Vec<u8>,
+    pub recipient: ShareRecipient,
     pub permission: Permission,
 }
 
@@ -34,3 +47,48 @@ pub struct RevokeShareResult {
     pub recipient_key_id: KeyId,
     pub envelopes: Vec<KeyEnvelope>,
 }
+
+/// 受信者が秘密鍵を保持していることの証明。
+///
+/// `KeyPossessionProof::message` で組み立てたバイト列に、受信者の鍵で署名したもの。
+#[derive(Debug, Clone)]
+pub struct KeyPossessionProof {
+    /// 署名した時刻。古すぎる・未来すぎる証明は受け付けない。
+    pub signed_at: DateTime<Utc>,
+    pub signature: Vec<u8>,
+}
+
+impl KeyPossessionProof {
+    /// 署名対象のメッセージ。
+    ///
+    /// `monas-shared-fetch-v1\n<content_id>\n<hex(recipient_key_id)>\n<signed_at の UNIX 秒>`
+    pub fn message(
+        content_id: &ContentId,
+        recipient_key_id: &KeyId,
+        signed_at: DateTime<Utc>,
+    ) -> Vec<u8> {
+        format!(
+            "monas-shared-fetch-v1\n{}\n{}\n{}",
+            content_id.as_str(),
+            hex::encode(recipient_key_id.as_bytes()),
+            signed_at.timestamp()
+        )
+        .into_bytes()
+    }
+}
+
+/// 受信者が共有されたコンテンツを取得するユースケースの入力。
+#[derive(Debug)]
+pub struct FetchSharedCommand {
+    pub content_id: ContentId,
+    pub recipient_key_id: KeyId,
+    pub proof: KeyPossessionProof,
+}
+
+/// 受信者向け取得ユースケースの出力。
+#[derive(Debug)]
+pub struct FetchSharedResult {
+    /// 受信者の公開鍵で CEK をラップし直した KeyEnvelope（暗号文を含む）。
+    pub envelope: KeyEnvelope,
+    pub permissions: Vec<Permission>,
+}
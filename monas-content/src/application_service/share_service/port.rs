@@ -2,7 +2,7 @@ use crate::application_service::content_service::{
     ContentEncryptionKeyStoreError, ContentRepositoryError,
 };
 use crate::domain::content_id::ContentId;
-use crate::domain::share::{KeyId, Share, ShareError};
+use crate::domain::share::{EscrowAuditEvent, KeyEnvelope, KeyId, Share, ShareError};
 
 /// 共有状態（ACL）を永続化するためのポート。
 ///
@@ -12,6 +12,13 @@ pub trait ShareRepository {
     fn load(&self, content_id: &ContentId) -> Result<Option<Share>, ShareRepositoryError>;
 
     fn save(&self, share: &Share) -> Result<(), ShareRepositoryError>;
+
+    /// バッファされた書き込みを永続化する。サーバーの終了前に呼び出す。
+    ///
+    /// 書き込みのたびに永続化する実装では何もしなくてよい。
+    fn flush(&self) -> Result<(), ShareRepositoryError> {
+        Ok(())
+    }
 }
 
 /// `Arc<dyn ShareRepository + Send + Sync>` を `ShareService` の型パラメータに
@@ -24,6 +31,10 @@ impl<T: ShareRepository + ?Sized> ShareRepository for std::sync::Arc<T> {
     fn save(&self, share: &Share) -> Result<(), ShareRepositoryError> {
         (**self).save(share)
     }
+
+    fn flush(&self) -> Result<(), ShareRepositoryError> {
+        (**self).flush()
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -32,6 +43,72 @@ pub enum ShareRepositoryError {
     Storage(String),
 }
 
+/// 発行済みの KeyEnvelope を記録するためのポート。
+///
+/// - key: `(content_id, recipient key_id)`
+/// - 同じキーで保存した場合は上書きする。
+pub trait KeyEnvelopeRepository {
+    fn save(&self, envelope: &KeyEnvelope) -> Result<(), KeyEnvelopeRepositoryError>;
+
+    fn find(
+        &self,
+        content_id: &ContentId,
+        recipient_key_id: &KeyId,
+    ) -> Result<Option<KeyEnvelope>, KeyEnvelopeRepositoryError>;
+
+    /// 指定コンテンツについて記録されているすべての KeyEnvelope を返す。
+    fn list_by_content(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Vec<KeyEnvelope>, KeyEnvelopeRepositoryError>;
+}
+
+impl<T: KeyEnvelopeRepository + ?Sized> KeyEnvelopeRepository for std::sync::Arc<T> {
+    fn save(&self, envelope: &KeyEnvelope) -> Result<(), KeyEnvelopeRepositoryError> {
+        (**self).save(envelope)
+    }
+
+    fn find(
+        &self,
+        content_id: &ContentId,
+        recipient_key_id: &KeyId,
+    ) -> Result<Option<KeyEnvelope>, KeyEnvelopeRepositoryError> {
+        (**self).find(content_id, recipient_key_id)
+    }
+
+    fn list_by_content(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Vec<KeyEnvelope>, KeyEnvelopeRepositoryError> {
+        (**self).list_by_content(content_id)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyEnvelopeRepositoryError {
+    #[error("storage error: {0}")]
+    Storage(String),
+}
+
+/// キーエスクローの監査イベントを記録するためのポート。
+///
+/// - 記録に失敗した場合、呼び出し側は対象の操作自体を中止しなければならない（strict audit）。
+pub trait EscrowAuditLog {
+    fn record(&self, event: EscrowAuditEvent) -> Result<(), EscrowAuditLogError>;
+}
+
+impl<T: EscrowAuditLog + ?Sized> EscrowAuditLog for std::sync::Arc<T> {
+    fn record(&self, event: EscrowAuditEvent) -> Result<(), EscrowAuditLogError> {
+        (**self).record(event)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EscrowAuditLogError {
+    #[error("audit log error: {0}")]
+    Record(String),
+}
+
 /// KeyId と HPKE 用公開鍵バイト列を管理するためのポート。
 ///
 /// - 実装は、ローカルのキーストア / State Node / 外部 KMS などを想定。
@@ -53,6 +130,20 @@ pub trait PublicKeyDirectory {
     /// - 補償トランザクション（ロールバック）に使用する。
     /// - 存在しない KeyId を削除しようとしてもエラーにならない（冪等）。
     fn delete_public_key(&self, key_id: &KeyId) -> Result<(), PublicKeyDirectoryError>;
+
+    /// アカウント識別子（monas-account の did:key）から、そのアカウントが共有を受け取る
+    /// HPKE の公開鍵を取得する。
+    ///
+    /// - `grant_share` で受信者をアカウント識別子で指定した場合に使う。
+    /// - アカウントの署名鍵（ECDSA）ではなく、共有用に導出された鍵を返すこと。
+    /// - アカウント解決をサポートしない実装はデフォルトで `None` を返す。
+    fn find_public_key_by_account(
+        &self,
+        account_id: &str,
+    ) -> Result<Option<Vec<u8>>, PublicKeyDirectoryError> {
+        let _ = account_id;
+        Ok(None)
+    }
 }
 
 /// `Arc<dyn PublicKeyDirectory + Send + Sync>` を `ShareService` の型パラメータに
@@ -73,6 +164,13 @@ impl<T: PublicKeyDirectory + ?Sized> PublicKeyDirectory for std::sync::Arc<T> {
     fn delete_public_key(&self, key_id: &KeyId) -> Result<(), PublicKeyDirectoryError> {
         (**self).delete_public_key(key_id)
     }
+
+    fn find_public_key_by_account(
+        &self,
+        account_id: &str,
+    ) -> Result<Option<Vec<u8>>, PublicKeyDirectoryError> {
+        (**self).find_public_key_by_account(account_id)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -81,6 +179,37 @@ pub enum PublicKeyDirectoryError {
     Lookup(String),
 }
 
+/// 受信者が公開鍵に対応する秘密鍵を持っていることを検証するためのポート。
+///
+/// - 受信者向け fetch で、`KeyPossessionProof` の署名を検証するのに使う。
+pub trait KeyPossessionVerifier {
+    fn verify(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), KeyPossessionError>;
+}
+
+impl<T: KeyPossessionVerifier + ?Sized> KeyPossessionVerifier for std::sync::Arc<T> {
+    fn verify(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), KeyPossessionError> {
+        (**self).verify(public_key, message, signature)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyPossessionError {
+    #[error("invalid proof: {0}")]
+    Invalid(String),
+    #[error("key possession verification is not configured")]
+    Unsupported,
+}
+
 /// Share 用アプリケーションサービスで発生しうるエラー。
 #[derive(Debug, thiserror::Error)]
 pub enum ShareApplicationError {
@@ -114,6 +243,15 @@ pub enum ShareApplicationError {
     #[error("missing public key for key_id")]
     MissingPublicKey,
 
+    #[error("recipient account not found: {0}")]
+    RecipientAccountNotFound(String),
+
     #[error("key wrapping error: {0}")]
     KeyWrapping(String),
+
+    #[error("content is not shared with the recipient")]
+    NotShared,
+
+    #[error("invalid key possession proof: {0}")]
+    InvalidKeyPossessionProof(String),
 }
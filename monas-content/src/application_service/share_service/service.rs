@@ -1,32 +1,62 @@
-use crate::application_service::content_service::{ContentEncryptionKeyStore, ContentRepository};
+use crate::application_service::content_service::{
+    ContentEncryptionKeyStore, ContentRepository, NoOpPushNotifier, PushEventType,
+    PushNotification, PushNotifier,
+};
 use crate::domain::content::encryption::ContentEncryptionKey;
 use crate::domain::share::{
     encryption::KeyWrapping, key_envelope::KeyWrapAlgorithm, KeyEnvelope, Share,
 };
 
 use super::{
-    GrantShareCommand, GrantShareResult, PublicKeyDirectory, RevokeShareCommand, RevokeShareResult,
-    ShareApplicationError, ShareRepository,
+    FetchSharedCommand, FetchSharedResult, GrantShareCommand, GrantShareResult, KeyPossessionError,
+    KeyPossessionProof, KeyPossessionVerifier, PublicKeyDirectory, RevokeShareCommand,
+    RevokeShareResult, ShareApplicationError, ShareRecipient, ShareRepository,
 };
 
+/// `KeyPossessionProof` の署名時刻として受け付ける現在時刻からのずれの上限（秒）。
+const KEY_POSSESSION_PROOF_MAX_SKEW_SECS: i64 = 300;
+
+/// 所有証明を検証しない `KeyPossessionVerifier` 実装（デフォルト）。
+///
+/// 常に `Unsupported` を返すため、受信者向け fetch は利用できない。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoKeyPossessionVerifier;
+
+impl KeyPossessionVerifier for NoKeyPossessionVerifier {
+    fn verify(
+        &self,
+        _public_key: &[u8],
+        _message: &[u8],
+        _signature: &[u8],
+    ) -> Result<(), KeyPossessionError> {
+        Err(KeyPossessionError::Unsupported)
+    }
+}
+
 /// コンテンツ共有ユースケースのアプリケーションサービス。
 ///
 /// - ContentService とは独立に、「共有（ACL と KeyEnvelope 生成 / CEK 復号）」に責務を限定する。
-pub struct ShareService<SR, CR, KS, KD, KW> {
+pub struct ShareService<SR, CR, KS, KD, KW, PN = NoOpPushNotifier, PV = NoKeyPossessionVerifier> {
     pub share_repository: SR,
     pub content_repository: CR,
     pub cek_store: KS,
     pub public_key_directory: KD,
     pub key_wrapper: KW,
+    /// 共有の付与を受信者のモバイルアプリへ知らせる通知先。
+    pub push_notifier: PN,
+    /// 受信者向け fetch で、受信者の鍵の所有証明を検証する。
+    pub possession_verifier: PV,
 }
 
-impl<SR, CR, KS, KD, KW> ShareService<SR, CR, KS, KD, KW>
+impl<SR, CR, KS, KD, KW, PN, PV> ShareService<SR, CR, KS, KD, KW, PN, PV>
 where
     SR: ShareRepository,
     CR: ContentRepository,
     KS: ContentEncryptionKeyStore,
     KD: PublicKeyDirectory,
     KW: KeyWrapping,
+    PN: PushNotifier,
+    PV: KeyPossessionVerifier,
 {
     fn build_envelope_for_recipient(
         &self,
@@ -92,7 +122,7 @@ where
 
         let ciphertext = content
             .encrypted_content()
-            .cloned()
+            .map(|c| c.to_vec())
             .ok_or(ShareApplicationError::MissingEncryptedContent)?;
 
         // 2. CEK の取得
@@ -102,10 +132,20 @@ where
             .map_err(ShareApplicationError::ContentEncryptionKeyStore)?
             .ok_or(ShareApplicationError::MissingContentEncryptionKey)?;
 
-        // 3. KeyId を計算
+        // 3. 受信者の公開鍵を解決し、KeyId を計算
+        let recipient_public_key = match &cmd.recipient {
+            ShareRecipient::PublicKey(public_key) => public_key.clone(),
+            ShareRecipient::Account(account_id) => self
+                .public_key_directory
+                .find_public_key_by_account(account_id)
+                .map_err(ShareApplicationError::PublicKeyDirectory)?
+                .ok_or_else(|| {
+                    ShareApplicationError::RecipientAccountNotFound(account_id.clone())
+                })?,
+        };
         let recipient_key_id = self
             .public_key_directory
-            .compute_key_id(&cmd.recipient_public_key);
+            .compute_key_id(&recipient_public_key);
 
         // 4. Share をロード
         let mut share = self
@@ -125,15 +165,14 @@ where
         let _ = event;
 
         // 6. CEK をラップ
-        let recipient_public_key = &cmd.recipient_public_key;
         let (enc, wrapped_cek) = self
             .key_wrapper
-            .wrap_cek(&cek, recipient_public_key, &cmd.content_id)
+            .wrap_cek(&cek, &recipient_public_key, &cmd.content_id)
             .map_err(|e| ShareApplicationError::KeyWrapping(format!("{e:?}")))?;
 
         // 7. 公開鍵を登録
         self.public_key_directory
-            .register_public_key(&cmd.recipient_public_key)
+            .register_public_key(&recipient_public_key)
             .map_err(ShareApplicationError::PublicKeyDirectory)?;
 
         // 8. Share を保存（失敗時は公開鍵を削除してロールバック）
@@ -159,6 +198,15 @@ where
             ciphertext,
         );
 
+        // 10. 受信者へプッシュ通知（ベストエフォート。失敗しても共有は成立している）
+        let _ = self.push_notifier.notify(&PushNotification {
+            event_type: PushEventType::ShareGranted,
+            content_id: cmd.content_id.clone(),
+            series_id: content.series_id().clone(),
+            recipient_key_id: Some(hex::encode(recipient_key_id.as_bytes())),
+            occurred_at: chrono::Utc::now(),
+        });
+
         Ok(GrantShareResult {
             envelope,
             recipient_key_id,
@@ -233,6 +281,162 @@ where
         })
     }
 
+    /// CEK のローテーション（`ContentService::reencrypt`）後に、共有相手全員の KeyEnvelope を
+    /// 新しい CEK と暗号文で再発行する。
+    ///
+    /// - 共有がなければ何もせず空の一覧を返す。
+    /// - 返す KeyEnvelope の `sender_key_id` は Owner の KeyId（未設定なら空）。
+    /// - Owner 以外の受信者には `KeyRotated` をプッシュ通知する（ベストエフォート）。
+    pub fn rewrap_shares(
+        &self,
+        content_id: &crate::domain::content_id::ContentId,
+    ) -> Result<Vec<KeyEnvelope>, ShareApplicationError> {
+        let Some(share) = self
+            .share_repository
+            .load(content_id)
+            .map_err(ShareApplicationError::ShareRepository)?
+        else {
+            return Ok(Vec::new());
+        };
+
+        let content = self
+            .content_repository
+            .find_by_id(content_id)
+            .map_err(ShareApplicationError::ContentRepository)?
+            .ok_or(ShareApplicationError::ContentNotFound)?;
+
+        if content.is_deleted() {
+            return Err(ShareApplicationError::ContentDeleted);
+        }
+
+        let ciphertext = content
+            .encrypted_content()
+            .cloned()
+            .ok_or(ShareApplicationError::MissingEncryptedContent)?;
+
+        let cek = self
+            .cek_store
+            .load(content_id)
+            .map_err(ShareApplicationError::ContentEncryptionKeyStore)?
+            .ok_or(ShareApplicationError::MissingContentEncryptionKey)?;
+
+        let owner_key_id = share.owner_key_id().cloned();
+        let sender_key_id = owner_key_id
+            .clone()
+            .unwrap_or_else(|| crate::domain::share::KeyId::new(Vec::new()));
+
+        let mut recipient_key_ids: Vec<_> = share.recipients().keys().cloned().collect();
+        recipient_key_ids.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+
+        let mut envelopes = Vec::with_capacity(recipient_key_ids.len());
+        for recipient_key_id in &recipient_key_ids {
+            envelopes.push(self.build_envelope_for_recipient(
+                content_id,
+                &sender_key_id,
+                recipient_key_id,
+                &cek,
+                &ciphertext,
+            )?);
+        }
+
+        for recipient_key_id in recipient_key_ids
+            .iter()
+            .filter(|key_id| Some(*key_id) != owner_key_id.as_ref())
+        {
+            let _ = self.push_notifier.notify(&PushNotification {
+                event_type: PushEventType::KeyRotated,
+                content_id: content_id.clone(),
+                series_id: content.series_id().clone(),
+                recipient_key_id: Some(hex::encode(recipient_key_id.as_bytes())),
+                occurred_at: chrono::Utc::now(),
+            });
+        }
+
+        Ok(envelopes)
+    }
+
+    /// 共有を受けた受信者が、コンテンツの暗号文と自分宛てにラップされた CEK を取得する。
+    ///
+    /// - 受信者は owner の CEK ストアに触れず、サービスが CEK をその場で受信者の公開鍵にラップし直す。
+    /// - 受信者は KeyId に対応する秘密鍵で `KeyPossessionProof` に署名して本人であることを示す。
+    /// - 返す KeyEnvelope の `sender_key_id` は Owner の KeyId（未設定なら空）。
+    pub fn fetch_shared(
+        &self,
+        cmd: FetchSharedCommand,
+    ) -> Result<FetchSharedResult, ShareApplicationError> {
+        // 1. 受信者が共有先に含まれ、読み取り権限を持つこと
+        let share = self
+            .share_repository
+            .load(&cmd.content_id)
+            .map_err(ShareApplicationError::ShareRepository)?
+            .ok_or(ShareApplicationError::NotShared)?;
+        let recipient = share
+            .recipient(&cmd.recipient_key_id)
+            .filter(|r| r.can_read())
+            .ok_or(ShareApplicationError::NotShared)?;
+        let permissions = recipient.permissions().to_vec();
+
+        // 2. 秘密鍵の所有証明を検証
+        let skew = chrono::Utc::now().signed_duration_since(cmd.proof.signed_at);
+        if skew.num_seconds().abs() > KEY_POSSESSION_PROOF_MAX_SKEW_SECS {
+            return Err(ShareApplicationError::InvalidKeyPossessionProof(
+                "proof is expired or signed in the future".into(),
+            ));
+        }
+        let recipient_public_key = self
+            .public_key_directory
+            .find_public_key(&cmd.recipient_key_id)
+            .map_err(ShareApplicationError::PublicKeyDirectory)?
+            .ok_or(ShareApplicationError::MissingPublicKey)?;
+        let message = KeyPossessionProof::message(
+            &cmd.content_id,
+            &cmd.recipient_key_id,
+            cmd.proof.signed_at,
+        );
+        self.possession_verifier
+            .verify(&recipient_public_key, &message, &cmd.proof.signature)
+            .map_err(|e| ShareApplicationError::InvalidKeyPossessionProof(e.to_string()))?;
+
+        // 3. コンテンツと CEK を取得し、受信者向けにラップし直す
+        let content = self
+            .content_repository
+            .find_by_id(&cmd.content_id)
+            .map_err(ShareApplicationError::ContentRepository)?
+            .ok_or(ShareApplicationError::ContentNotFound)?;
+
+        if content.is_deleted() {
+            return Err(ShareApplicationError::ContentDeleted);
+        }
+
+        let ciphertext = content
+            .encrypted_content()
+            .cloned()
+            .ok_or(ShareApplicationError::MissingEncryptedContent)?;
+
+        let cek = self
+            .cek_store
+            .load(&cmd.content_id)
+            .map_err(ShareApplicationError::ContentEncryptionKeyStore)?
+            .ok_or(ShareApplicationError::MissingContentEncryptionKey)?;
+
+        let sender_key_id = share
+            .owner_key_id()
+            .cloned()
+            .unwrap_or_else(|| crate::domain::share::KeyId::new(Vec::new()));
+        let envelope = self.build_envelope_for_recipient(
+            &cmd.content_id,
+            &sender_key_id,
+            &cmd.recipient_key_id,
+            &cek,
+            &ciphertext,
+        )?;
+
+        Ok(FetchSharedResult {
+            envelope,
+            permissions,
+        })
+    }
+
     /// KeyEnvelope と受信者の秘密鍵バイト列から CEK を復号（アンラップ）する。
     ///
     /// - monas-account など別サービスが秘密鍵を管理し、このサービスにはバイト列として渡ってくる前提。
@@ -260,14 +464,16 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::ShareService;
+    use super::{NoKeyPossessionVerifier, ShareService};
     use crate::application_service::content_service::{
         ContentEncryptionKeyStore, ContentEncryptionKeyStoreError, ContentRepository,
-        ContentRepositoryError,
+        ContentRepositoryError, NoOpPushNotifier, PushEventType, PushNotification, PushNotifier,
+        PushNotifierError,
     };
     use crate::application_service::share_service::{
-        GrantShareCommand, PublicKeyDirectory, PublicKeyDirectoryError, RevokeShareCommand,
-        ShareApplicationError, ShareRepository, ShareRepositoryError,
+        FetchSharedCommand, GrantShareCommand, KeyPossessionError, KeyPossessionProof,
+        KeyPossessionVerifier, PublicKeyDirectory, PublicKeyDirectoryError, RevokeShareCommand,
+        ShareApplicationError, ShareRecipient, ShareRepository, ShareRepositoryError,
     };
     use crate::domain::{
         content::{Content, ContentEncryptionKey, Metadata},
@@ -425,6 +631,7 @@ mod tests {
     struct TestPublicKeyDirectory {
         registered: Arc<Mutex<Vec<Vec<u8>>>>,
         deleted: Arc<Mutex<Vec<KeyId>>>,
+        accounts: Arc<Mutex<HashMap<String, Vec<u8>>>>,
     }
 
     impl PublicKeyDirectory for TestPublicKeyDirectory {
@@ -460,6 +667,17 @@ mod tests {
             guard.push(key_id.clone());
             Ok(())
         }
+
+        fn find_public_key_by_account(
+            &self,
+            account_id: &str,
+        ) -> Result<Option<Vec<u8>>, PublicKeyDirectoryError> {
+            let guard = self
+                .accounts
+                .lock()
+                .map_err(|e| PublicKeyDirectoryError::Lookup(e.to_string()))?;
+            Ok(guard.get(account_id).cloned())
+        }
     }
 
     #[derive(Clone, Default)]
@@ -534,7 +752,7 @@ mod tests {
     }
 
     fn cid() -> ContentId {
-        ContentId::new("test-content-id".into())
+        ContentId::for_test("test-content-id")
     }
 
     fn sender_key_id() -> KeyId {
@@ -586,6 +804,8 @@ mod tests {
             cek_store: key_store,
             public_key_directory: public_key_dir,
             key_wrapper,
+            push_notifier: NoOpPushNotifier,
+            possession_verifier: NoKeyPossessionVerifier,
         }
     }
 
@@ -697,7 +917,7 @@ mod tests {
         let cmd = GrantShareCommand {
             content_id: cid.clone(),
             sender_key_id: sender_key_id(),
-            recipient_public_key: vec![1, 2, 3, 4],
+            recipient: ShareRecipient::PublicKey(vec![1, 2, 3, 4]),
             permission: Permission::Read,
         };
 
@@ -723,6 +943,194 @@ mod tests {
         assert_eq!(perms, &[Permission::Read]);
     }
 
+    #[test]
+    fn grant_share_resolves_recipient_by_account_id() {
+        let (content_repo, content_storage) = TestContentRepository::new();
+        let (key_store, key_storage) = TestKeyStore::new();
+        let (share_repo, _share_storage) = TestShareRepository::new();
+        let public_key_dir = TestPublicKeyDirectory::default();
+        public_key_dir
+            .accounts
+            .lock()
+            .unwrap()
+            .insert("did:key:zDnRecipient".into(), vec![7, 7, 7]);
+
+        let cid = cid();
+        {
+            let mut guard = content_storage.lock().unwrap();
+            guard.insert(
+                cid.as_str().to_string(),
+                build_content(&cid, Some(encrypted()), false),
+            );
+        }
+        {
+            let mut guard = key_storage.lock().unwrap();
+            guard.insert(cid.as_str().to_string(), cek());
+        }
+
+        let service = build_service(
+            share_repo,
+            content_repo,
+            key_store,
+            public_key_dir.clone(),
+            TestKeyWrapper,
+        );
+
+        // 解決できないアカウントは共有できない
+        let err = service
+            .grant_share(GrantShareCommand {
+                content_id: cid.clone(),
+                sender_key_id: sender_key_id(),
+                recipient: ShareRecipient::Account("did:key:zDnUnknown".into()),
+                permission: Permission::Read,
+            })
+            .expect_err("unknown account should be rejected");
+        assert!(matches!(
+            err,
+            ShareApplicationError::RecipientAccountNotFound(ref id) if id == "did:key:zDnUnknown"
+        ));
+        assert!(public_key_dir.registered.lock().unwrap().is_empty());
+
+        service
+            .grant_share(GrantShareCommand {
+                content_id: cid,
+                sender_key_id: sender_key_id(),
+                recipient: ShareRecipient::Account("did:key:zDnRecipient".into()),
+                permission: Permission::Read,
+            })
+            .expect("grant_share by account id should succeed");
+
+        // アカウントから解決した公開鍵が登録される
+        assert_eq!(
+            public_key_dir.registered.lock().unwrap().as_slice(),
+            &[vec![7, 7, 7]]
+        );
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordingPushNotifier {
+        sent: Arc<Mutex<Vec<PushNotification>>>,
+    }
+
+    impl PushNotifier for RecordingPushNotifier {
+        fn notify(&self, notification: &PushNotification) -> Result<(), PushNotifierError> {
+            self.sent.lock().unwrap().push(notification.clone());
+            // 通知の失敗は共有の成否に影響しないことを確認するため、常にエラーを返す。
+            Err(PushNotifierError::Notify("gateway unavailable".into()))
+        }
+    }
+
+    #[test]
+    fn grant_share_notifies_recipient_without_failing_on_push_error() {
+        let (content_repo, content_storage) = TestContentRepository::new();
+        let (key_store, key_storage) = TestKeyStore::new();
+        let (share_repo, _share_storage) = TestShareRepository::new();
+        let notifier = RecordingPushNotifier::default();
+
+        let cid = cid();
+        {
+            let mut guard = content_storage.lock().unwrap();
+            guard.insert(
+                cid.as_str().to_string(),
+                build_content(&cid, Some(encrypted()), false),
+            );
+        }
+        {
+            let mut guard = key_storage.lock().unwrap();
+            guard.insert(cid.as_str().to_string(), cek());
+        }
+
+        let service = ShareService {
+            share_repository: share_repo,
+            content_repository: content_repo,
+            cek_store: key_store,
+            public_key_directory: TestPublicKeyDirectory::default(),
+            key_wrapper: TestKeyWrapper,
+            push_notifier: notifier.clone(),
+            possession_verifier: NoKeyPossessionVerifier,
+        };
+
+        service
+            .grant_share(GrantShareCommand {
+                content_id: cid.clone(),
+                sender_key_id: sender_key_id(),
+                recipient: ShareRecipient::PublicKey(vec![1, 2, 3, 4]),
+                permission: Permission::Read,
+            })
+            .expect("grant_share should succeed even if the push fails");
+
+        let sent = notifier.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].event_type, PushEventType::ShareGranted);
+        assert_eq!(sent[0].content_id, cid);
+        assert_eq!(sent[0].recipient_key_id.as_deref(), Some("010203"));
+    }
+
+    #[test]
+    fn rewrap_shares_reissues_envelopes_and_notifies_recipients() {
+        let (content_repo, content_storage) = TestContentRepository::new();
+        let (key_store, key_storage) = TestKeyStore::new();
+        let (share_repo, share_storage) = TestShareRepository::new();
+        let notifier = RecordingPushNotifier::default();
+
+        let cid = cid();
+        {
+            let mut guard = content_storage.lock().unwrap();
+            guard.insert(
+                cid.as_str().to_string(),
+                build_content(&cid, Some(encrypted()), false),
+            );
+        }
+        {
+            let mut guard = key_storage.lock().unwrap();
+            guard.insert(cid.as_str().to_string(), cek());
+        }
+
+        let public_key_dir = TestPublicKeyDirectory::default();
+        public_key_dir
+            .register_public_key(&[1, 2, 3, 4])
+            .expect("public key registration should succeed");
+
+        let service = ShareService {
+            share_repository: share_repo,
+            content_repository: content_repo,
+            cek_store: key_store,
+            public_key_directory: public_key_dir,
+            key_wrapper: TestKeyWrapper,
+            push_notifier: notifier.clone(),
+            possession_verifier: NoKeyPossessionVerifier,
+        };
+
+        // 共有がなければ何もしない
+        assert!(service.rewrap_shares(&cid).unwrap().is_empty());
+        assert!(notifier.sent.lock().unwrap().is_empty());
+
+        let owner = KeyId::new(vec![9]);
+        let reader = KeyId::new(vec![1, 2, 3]);
+        let mut share = Share::new(cid.clone());
+        share.grant_owner(owner.clone()).unwrap();
+        share.grant_read(reader.clone()).unwrap();
+        share_storage
+            .lock()
+            .unwrap()
+            .insert(cid.as_str().to_string(), share);
+
+        let envelopes = service.rewrap_shares(&cid).expect("rewrap should succeed");
+        assert_eq!(envelopes.len(), 2);
+        assert_eq!(envelopes[0].recipient().key_id(), &reader);
+        assert_eq!(envelopes[1].recipient().key_id(), &owner);
+        for envelope in &envelopes {
+            assert_eq!(envelope.sender_key_id(), &owner);
+            assert_eq!(envelope.ciphertext(), encrypted().as_slice());
+        }
+
+        // Owner 以外の受信者だけに通知する
+        let sent = notifier.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].event_type, PushEventType::KeyRotated);
+        assert_eq!(sent[0].recipient_key_id.as_deref(), Some("010203"));
+    }
+
     #[test]
     fn grant_share_with_write_sets_write_permission_and_implies_read() {
         let (content_repo, content_storage) = TestContentRepository::new();
@@ -753,7 +1161,7 @@ mod tests {
         let cmd = GrantShareCommand {
             content_id: cid.clone(),
             sender_key_id: sender_key_id(),
-            recipient_public_key: vec![1, 2, 3, 4],
+            recipient: ShareRecipient::PublicKey(vec![1, 2, 3, 4]),
             permission: Permission::Write,
         };
 
@@ -793,7 +1201,7 @@ mod tests {
         let cmd = GrantShareCommand {
             content_id: cid(),
             sender_key_id: sender_key_id(),
-            recipient_public_key: vec![1, 2, 3],
+            recipient: ShareRecipient::PublicKey(vec![1, 2, 3]),
             permission: Permission::Read,
         };
 
@@ -829,7 +1237,7 @@ mod tests {
         let cmd = GrantShareCommand {
             content_id: cid,
             sender_key_id: sender_key_id(),
-            recipient_public_key: vec![1, 2, 3],
+            recipient: ShareRecipient::PublicKey(vec![1, 2, 3]),
             permission: Permission::Read,
         };
 
@@ -865,7 +1273,7 @@ mod tests {
         let cmd = GrantShareCommand {
             content_id: cid,
             sender_key_id: sender_key_id(),
-            recipient_public_key: vec![1, 2, 3],
+            recipient: ShareRecipient::PublicKey(vec![1, 2, 3]),
             permission: Permission::Read,
         };
 
@@ -904,7 +1312,7 @@ mod tests {
         let cmd = GrantShareCommand {
             content_id: cid,
             sender_key_id: sender_key_id(),
-            recipient_public_key: vec![1, 2, 3],
+            recipient: ShareRecipient::PublicKey(vec![1, 2, 3]),
             permission: Permission::Read,
         };
 
@@ -957,7 +1365,7 @@ mod tests {
         let cmd = GrantShareCommand {
             content_id: cid,
             sender_key_id: sender_key_id(),
-            recipient_public_key: vec![9, 9, 9],
+            recipient: ShareRecipient::PublicKey(vec![9, 9, 9]),
             permission: Permission::Read,
         };
 
@@ -1000,7 +1408,7 @@ mod tests {
         let cmd = GrantShareCommand {
             content_id: cid,
             sender_key_id: sender_key_id(),
-            recipient_public_key: vec![1, 2, 3],
+            recipient: ShareRecipient::PublicKey(vec![1, 2, 3]),
             permission: Permission::Read,
         };
 
@@ -1035,12 +1443,14 @@ mod tests {
             cek_store: key_store,
             public_key_directory: public_key_dir.clone(),
             key_wrapper,
+            push_notifier: NoOpPushNotifier,
+            possession_verifier: NoKeyPossessionVerifier,
         };
 
         let cmd = GrantShareCommand {
             content_id: cid,
             sender_key_id: sender_key_id(),
-            recipient_public_key: vec![1, 2, 3, 4],
+            recipient: ShareRecipient::PublicKey(vec![1, 2, 3, 4]),
             permission: Permission::Read,
         };
 
@@ -1069,6 +1479,104 @@ mod tests {
         );
     }
 
+    /// 「公開鍵 || メッセージ」を正しい署名とみなすテスト用の検証器。
+    struct ConcatSignatureVerifier;
+
+    impl KeyPossessionVerifier for ConcatSignatureVerifier {
+        fn verify(
+            &self,
+            public_key: &[u8],
+            message: &[u8],
+            signature: &[u8],
+        ) -> Result<(), KeyPossessionError> {
+            if signature == [public_key, message].concat() {
+                Ok(())
+            } else {
+                Err(KeyPossessionError::Invalid("signature mismatch".into()))
+            }
+        }
+    }
+
+    #[test]
+    fn fetch_shared_rewraps_cek_for_recipient_with_valid_proof() {
+        let (content_repo, content_storage) = TestContentRepository::new();
+        let (key_store, key_storage) = TestKeyStore::new();
+        let (share_repo, _) = TestShareRepository::new();
+
+        let cid = cid();
+        let content = build_content(&cid, Some(encrypted()), false);
+        content_storage
+            .lock()
+            .unwrap()
+            .insert(cid.as_str().to_string(), content);
+        key_storage
+            .lock()
+            .unwrap()
+            .insert(cid.as_str().to_string(), cek());
+
+        let service = ShareService {
+            share_repository: share_repo,
+            content_repository: content_repo,
+            cek_store: key_store,
+            public_key_directory: TestPublicKeyDirectory::default(),
+            key_wrapper: TestKeyWrapper,
+            push_notifier: NoOpPushNotifier,
+            possession_verifier: ConcatSignatureVerifier,
+        };
+        let recipient_public_key = vec![1, 2, 3, 4];
+        let recipient_key_id = service
+            .grant_share(GrantShareCommand {
+                content_id: cid.clone(),
+                sender_key_id: sender_key_id(),
+                recipient: ShareRecipient::PublicKey(recipient_public_key.clone()),
+                permission: Permission::Read,
+            })
+            .unwrap()
+            .recipient_key_id;
+
+        let command = |key_id: KeyId, signed_at: chrono::DateTime<chrono::Utc>| {
+            let message = KeyPossessionProof::message(&cid, &key_id, signed_at);
+            FetchSharedCommand {
+                content_id: cid.clone(),
+                recipient_key_id: key_id,
+                proof: KeyPossessionProof {
+                    signed_at,
+                    signature: [recipient_public_key.as_slice(), &message].concat(),
+                },
+            }
+        };
+        let now = chrono::Utc::now();
+
+        let result = service
+            .fetch_shared(command(recipient_key_id.clone(), now))
+            .expect("recipient with a valid proof can fetch");
+        assert_eq!(result.permissions, vec![Permission::Read]);
+        assert_eq!(result.envelope.ciphertext(), encrypted().as_slice());
+        assert_eq!(result.envelope.recipient().key_id(), &recipient_key_id);
+        assert_eq!(
+            result.envelope.recipient().wrapped_cek(),
+            &[0x11, 0x22, 0x33]
+        );
+
+        let stale = now - chrono::Duration::minutes(10);
+        assert!(matches!(
+            service.fetch_shared(command(recipient_key_id.clone(), stale)),
+            Err(ShareApplicationError::InvalidKeyPossessionProof(_))
+        ));
+
+        let mut forged = command(recipient_key_id, now);
+        forged.proof.signature = vec![0; 8];
+        assert!(matches!(
+            service.fetch_shared(forged),
+            Err(ShareApplicationError::InvalidKeyPossessionProof(_))
+        ));
+
+        assert!(matches!(
+            service.fetch_shared(command(KeyId::new(vec![9, 9]), now)),
+            Err(ShareApplicationError::NotShared)
+        ));
+    }
+
     #[test]
     fn revoke_share_success_updates_acl() {
         let (content_repo, content_storage) = TestContentRepository::new();
@@ -0,0 +1,430 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::domain::content::encryption::{ContentEncryption, ContentEncryptionKey};
+use crate::domain::content::ContentError;
+
+/// チャンク分割する場合の既定のチャンクサイズ（平文 1 MiB）。
+pub const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// チャンクのダイジェストを CEK の他の用途と区別するためのラベル。
+const CHUNK_DIGEST_LABEL: &[u8] = b"monas-content/chunk-digest/v1";
+
+/// チャンク分割した暗号文の先頭に置くマジックバイト（末尾は形式のバージョン）。
+const CHUNKED_MAGIC: &[u8; 4] = b"MNK\x01";
+
+/// 各チャンクの暗号文の前に置く長さ（u32 ビッグエンディアン）のバイト数。
+const FRAME_LEN_SIZE: usize = 4;
+
+/// 固定長チャンクごとに暗号化したコンテンツの構成（マニフェスト）。
+///
+/// - 暗号文は各チャンクを個別に暗号化したものを、長さを付けて順に連結したもの
+///   （形式は [`join_chunked_ciphertext`] を参照）。暗号文だけで区切りが分かるため、
+///   CEK さえあればマニフェストなしでも [`decrypt_ciphertext`] で復号できる
+/// - `digest` は CEK を鍵とした平文チャンクの HMAC で、更新時に変更のないチャンクを
+///   見つけるために使う（CEK を持たない者には平文の推測に使えない）
+/// - `ciphertext_sha256` は暗号化後のチャンクのアドレスで、保存先はこれをキーに
+///   チャンクを個別に保存する（変更のないチャンクは書き込み直さない）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkManifest {
+    chunk_size: u32,
+    chunks: Vec<ChunkEntry>,
+}
+
+/// マニフェスト中の 1 チャンク分の情報。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkEntry {
+    /// 平文チャンクの鍵付きダイジェスト（HMAC-SHA256, hex）。
+    pub digest: String,
+    /// 暗号化後のチャンクのバイト数。
+    pub encrypted_len: u64,
+    /// 暗号化後のチャンクの SHA-256（hex）。
+    pub ciphertext_sha256: String,
+}
+
+/// チャンク単位の暗号化結果。
+pub(crate) struct ChunkedCiphertext {
+    pub ciphertext: Vec<u8>,
+    pub manifest: ChunkManifest,
+    /// 今回新たに暗号化したチャンクの暗号文のバイト数（再利用した分は含まない）。
+    pub encrypted_bytes: u64,
+}
+
+impl ChunkManifest {
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size as usize
+    }
+
+    pub fn chunks(&self) -> &[ChunkEntry] {
+        &self.chunks
+    }
+
+    /// 各チャンクの暗号文のバイト数の合計。
+    pub fn encrypted_len(&self) -> u64 {
+        self.chunks.iter().map(|c| c.encrypted_len).sum()
+    }
+
+    /// `ciphertext` をチャンクごとの暗号文に分け、このマニフェストと一致することを確かめる。
+    ///
+    /// 長さに加えて各チャンクの SHA-256 も照合するため、チャンクの入れ替え・差し替えは
+    /// （個々のチャンクが正しく復号できるものであっても）検出される。
+    fn split<'a>(&self, ciphertext: &'a [u8]) -> Result<Vec<&'a [u8]>, ContentError> {
+        let chunks = split_chunked_ciphertext(ciphertext).ok_or_else(|| {
+            ContentError::DecryptionError("Ciphertext is not in the chunked format".to_string())
+        })?;
+        let matches = chunks.len() == self.chunks.len()
+            && chunks.iter().zip(&self.chunks).all(|(chunk, entry)| {
+                chunk.len() as u64 == entry.encrypted_len
+                    && hex::encode(Sha256::digest(chunk)) == entry.ciphertext_sha256
+            });
+        if !matches {
+            return Err(ContentError::DecryptionError(format!(
+                "Ciphertext chunks do not match chunk manifest ({} chunks, {} bytes)",
+                self.chunks.len(),
+                self.encrypted_len()
+            )));
+        }
+        Ok(chunks)
+    }
+}
+
+/// チャンクごとの暗号文を連結して、チャンク分割した暗号文を作る。
+///
+/// ```text
+/// MAGIC (4 bytes: "MNK" 0x01) || { len (u32 BE) || チャンクの暗号文 } * チャンク数
+/// ```
+///
+/// 単一の暗号文（暗号スイートのヘッダ `"MNC" 0x01` またはレガシー形式の IV で始まる）とは
+/// 先頭のマジックで区別する。レガシー形式の IV がたまたまマジックに一致し、かつ残りが
+/// 長さ付きのチャンク列として矛盾なく読める確率は無視できるほど小さい。
+pub fn join_chunked_ciphertext<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> Vec<u8> {
+    let mut joined = CHUNKED_MAGIC.to_vec();
+    for chunk in chunks {
+        let len = u32::try_from(chunk.len()).expect("chunk ciphertext fits in u32");
+        joined.extend_from_slice(&len.to_be_bytes());
+        joined.extend_from_slice(chunk);
+    }
+    joined
+}
+
+/// チャンク分割した暗号文をチャンクごとの暗号文に分ける。
+///
+/// チャンク分割の形式でない（単一の暗号文である）場合や、長さが矛盾する場合は `None`。
+pub fn split_chunked_ciphertext(data: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut rest = data.strip_prefix(CHUNKED_MAGIC.as_slice())?;
+    let mut chunks = Vec::new();
+    while !rest.is_empty() {
+        let len = u32::from_be_bytes(rest.get(..FRAME_LEN_SIZE)?.try_into().ok()?) as usize;
+        let end = FRAME_LEN_SIZE.checked_add(len)?;
+        chunks.push(rest.get(FRAME_LEN_SIZE..end)?);
+        rest = &rest[end..];
+    }
+    Some(chunks)
+}
+
+/// 暗号文を復号する。チャンク分割した暗号文であればチャンクごとに復号して連結する。
+///
+/// マニフェストを持たない経路（共有先での `decrypt_with_cek` など）で使う。
+pub fn decrypt_ciphertext<E>(
+    key: &ContentEncryptionKey,
+    encryption: &E,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, ContentError>
+where
+    E: ContentEncryption,
+{
+    match split_chunked_ciphertext(ciphertext) {
+        Some(chunks) => decrypt_each(key, encryption, &chunks),
+        None => encryption.decrypt(key, ciphertext),
+    }
+}
+
+/// 平文をチャンクに分割して暗号化する。
+///
+/// `previous` に現在の暗号文とマニフェストを渡すと、同じ CEK・同じチャンクサイズで
+/// ダイジェストが一致するチャンクは暗号文をそのまま再利用し、変更されたチャンクのみ
+/// 暗号化する。
+pub(crate) fn encrypt_chunks<E>(
+    key: &ContentEncryptionKey,
+    encryption: &E,
+    plaintext: &[u8],
+    chunk_size: usize,
+    previous: Option<(&ChunkManifest, &[u8])>,
+) -> Result<ChunkedCiphertext, ContentError>
+where
+    E: ContentEncryption,
+{
+    if chunk_size == 0 || chunk_size > u32::MAX as usize {
+        return Err(ContentError::EncryptionError(format!(
+            "Invalid chunk size: {chunk_size}"
+        )));
+    }
+
+    // 再利用できる既存チャンク（ダイジェスト → 暗号文とそのアドレス）
+    let mut reusable: HashMap<&str, (&[u8], &str)> = HashMap::new();
+    if let Some((manifest, ciphertext)) = previous {
+        if manifest.chunk_size() == chunk_size {
+            if let Ok(existing) = manifest.split(ciphertext) {
+                for (entry, chunk) in manifest.chunks.iter().zip(existing) {
+                    reusable.insert(
+                        entry.digest.as_str(),
+                        (chunk, entry.ciphertext_sha256.as_str()),
+                    );
+                }
+            }
+        }
+    }
+
+    let mut encrypted_chunks: Vec<Cow<'_, [u8]>> = Vec::new();
+    let mut chunks = Vec::with_capacity(plaintext.len().div_ceil(chunk_size));
+    let mut encrypted_bytes = 0u64;
+    for chunk in plaintext.chunks(chunk_size) {
+        let digest = chunk_digest(key, chunk);
+        let ciphertext_sha256 = match reusable.get(digest.as_str()) {
+            Some(&(existing, sha256)) => {
+                encrypted_chunks.push(Cow::Borrowed(existing));
+                sha256.to_string()
+            }
+            None => {
+                let encrypted = encryption.encrypt(key, chunk)?;
+                if encrypted.len() > u32::MAX as usize {
+                    return Err(ContentError::EncryptionError(format!(
+                        "Encrypted chunk is too large: {} bytes",
+                        encrypted.len()
+                    )));
+                }
+                encrypted_bytes += encrypted.len() as u64;
+                let sha256 = hex::encode(Sha256::digest(&encrypted));
+                encrypted_chunks.push(Cow::Owned(encrypted));
+                sha256
+            }
+        };
+        chunks.push(ChunkEntry {
+            digest,
+            encrypted_len: encrypted_chunks.last().map_or(0, |c| c.len() as u64),
+            ciphertext_sha256,
+        });
+    }
+
+    Ok(ChunkedCiphertext {
+        ciphertext: join_chunked_ciphertext(encrypted_chunks.iter().map(AsRef::as_ref)),
+        manifest: ChunkManifest {
+            chunk_size: chunk_size as u32,
+            chunks,
+        },
+        encrypted_bytes,
+    })
+}
+
+/// マニフェストに従ってチャンクごとに復号し、平文を連結して返す。
+pub(crate) fn decrypt_chunks<E>(
+    key: &ContentEncryptionKey,
+    encryption: &E,
+    manifest: &ChunkManifest,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, ContentError>
+where
+    E: ContentEncryption,
+{
+    let chunks = manifest.split(ciphertext)?;
+    decrypt_each(key, encryption, &chunks)
+}
+
+/// チャンクごとの暗号文を順に復号し、平文を連結して返す。
+fn decrypt_each<E>(
+    key: &ContentEncryptionKey,
+    encryption: &E,
+    chunks: &[&[u8]],
+) -> Result<Vec<u8>, ContentError>
+where
+    E: ContentEncryption,
+{
+    // 途中で失敗した場合に復号済みの部分が残らないよう、完了までは Zeroizing で保持する。
+    // 容量は平文の上限（暗号文の合計）で確保しているため、連結中に再確保で古いバッファが
+    // 残ることもない。
+    let capacity = chunks.iter().map(|chunk| chunk.len()).sum();
+    let mut plaintext = Zeroizing::new(Vec::with_capacity(capacity));
+    for chunk in chunks {
+        let chunk = Zeroizing::new(encryption.decrypt(key, chunk)?);
+        plaintext.extend_from_slice(&chunk);
+    }
+    Ok(std::mem::take(&mut *plaintext))
+}
+
+fn chunk_digest(key: &ContentEncryptionKey, chunk: &[u8]) -> String {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&key.0)
+        .expect("HMAC-SHA256 accepts keys of any length");
+    mac.update(CHUNK_DIGEST_LABEL);
+    mac.update(chunk);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テスト用の暗号化実装。暗号化のたびに異なる 1 バイトの接頭辞を付け、
+    /// 再利用されたチャンクと暗号化し直したチャンクを見分けられるようにする。
+    struct CountingEncryption(std::sync::atomic::AtomicU8);
+
+    impl ContentEncryption for CountingEncryption {
+        fn encrypt(
+            &self,
+            _key: &ContentEncryptionKey,
+            plaintext: &[u8],
+        ) -> Result<Vec<u8>, ContentError> {
+            let n = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let mut out = vec![n];
+            out.extend(plaintext.iter().map(|b| b ^ 0x5a));
+            Ok(out)
+        }
+
+        fn decrypt(
+            &self,
+            _key: &ContentEncryptionKey,
+            ciphertext: &[u8],
+        ) -> Result<Vec<u8>, ContentError> {
+            Ok(ciphertext[1..].iter().map(|b| b ^ 0x5a).collect())
+        }
+    }
+
+    fn key() -> ContentEncryptionKey {
+        ContentEncryptionKey(vec![7u8; 32])
+    }
+
+    #[test]
+    fn chunks_round_trip_and_last_chunk_may_be_short() {
+        let encryption = CountingEncryption(Default::default());
+        let plaintext: Vec<u8> = (0..10u8).collect();
+
+        let chunked = encrypt_chunks(&key(), &encryption, &plaintext, 4, None).unwrap();
+        assert_eq!(chunked.manifest.chunk_size(), 4);
+        assert_eq!(chunked.manifest.chunks().len(), 3);
+        assert_eq!(chunked.manifest.chunks()[2].encrypted_len, 3);
+        assert_eq!(chunked.encrypted_bytes, chunked.manifest.encrypted_len());
+
+        let decrypted =
+            decrypt_chunks(&key(), &encryption, &chunked.manifest, &chunked.ciphertext).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn only_changed_chunks_are_encrypted_again() {
+        let encryption = CountingEncryption(Default::default());
+        let original = b"aaaabbbbcccc".to_vec();
+        let first = encrypt_chunks(&key(), &encryption, &original, 4, None).unwrap();
+
+        let updated = b"aaaaXXXXccccdd".to_vec();
+        let second = encrypt_chunks(
+            &key(),
+            &encryption,
+            &updated,
+            4,
+            Some((&first.manifest, &first.ciphertext)),
+        )
+        .unwrap();
+
+        // 1 番目・3 番目のチャンクは暗号文ごと（アドレスも同じまま）再利用される
+        let before = split_chunked_ciphertext(&first.ciphertext).unwrap();
+        let after = split_chunked_ciphertext(&second.ciphertext).unwrap();
+        assert_eq!(after[0], before[0]);
+        assert_ne!(after[1], before[1]);
+        assert_eq!(after[2], before[2]);
+        assert_eq!(
+            second.manifest.chunks()[2].ciphertext_sha256,
+            first.manifest.chunks()[2].ciphertext_sha256
+        );
+        // 新たに暗号化したのは変更された 2 番目と追加された 4 番目のみ
+        assert_eq!(second.encrypted_bytes, 5 + 3);
+        assert_eq!(
+            decrypt_chunks(&key(), &encryption, &second.manifest, &second.ciphertext).unwrap(),
+            updated
+        );
+    }
+
+    #[test]
+    fn nothing_is_reused_under_another_key_or_chunk_size() {
+        let encryption = CountingEncryption(Default::default());
+        let plaintext = b"aaaabbbb".to_vec();
+        let first = encrypt_chunks(&key(), &encryption, &plaintext, 4, None).unwrap();
+        let previous = Some((&first.manifest, first.ciphertext.as_slice()));
+
+        let other_key = ContentEncryptionKey(vec![8u8; 32]);
+        let rekeyed = encrypt_chunks(&other_key, &encryption, &plaintext, 4, previous).unwrap();
+        assert_eq!(rekeyed.encrypted_bytes, rekeyed.manifest.encrypted_len());
+
+        let resized = encrypt_chunks(&key(), &encryption, &plaintext, 2, previous).unwrap();
+        assert_eq!(resized.encrypted_bytes, resized.manifest.encrypted_len());
+    }
+
+    #[test]
+    fn decrypt_rejects_ciphertext_not_matching_manifest() {
+        let encryption = CountingEncryption(Default::default());
+        let chunked = encrypt_chunks(&key(), &encryption, b"aaaabbbb", 4, None).unwrap();
+
+        let truncated = &chunked.ciphertext[..chunked.ciphertext.len() - 1];
+        let result = decrypt_chunks(&key(), &encryption, &chunked.manifest, truncated);
+        assert!(matches!(result, Err(ContentError::DecryptionError(_))));
+
+        let other = encrypt_chunks(&key(), &encryption, b"aaaabbbbcc", 4, None).unwrap();
+        let result = decrypt_chunks(&key(), &encryption, &chunked.manifest, &other.ciphertext);
+        assert!(matches!(result, Err(ContentError::DecryptionError(_))));
+    }
+
+    #[test]
+    fn decrypt_rejects_reordered_chunks() {
+        let encryption = CountingEncryption(Default::default());
+        let chunked = encrypt_chunks(&key(), &encryption, b"aaaabbbb", 4, None).unwrap();
+
+        // 長さが同じで個々には復号できるチャンクでも、順序の入れ替えは拒否する
+        let chunks = split_chunked_ciphertext(&chunked.ciphertext).unwrap();
+        let swapped = join_chunked_ciphertext([chunks[1], chunks[0]]);
+        let result = decrypt_chunks(&key(), &encryption, &chunked.manifest, &swapped);
+        assert!(matches!(result, Err(ContentError::DecryptionError(_))));
+
+        // 入れ替えた暗号文は再利用の候補にもならない
+        let reencrypted = encrypt_chunks(
+            &key(),
+            &encryption,
+            b"aaaabbbb",
+            4,
+            Some((&chunked.manifest, &swapped)),
+        )
+        .unwrap();
+        assert_eq!(
+            reencrypted.encrypted_bytes,
+            reencrypted.manifest.encrypted_len()
+        );
+    }
+
+    #[test]
+    fn ciphertext_alone_is_enough_to_decrypt() {
+        let encryption = CountingEncryption(Default::default());
+        let chunked = encrypt_chunks(&key(), &encryption, b"aaaabbbbcc", 4, None).unwrap();
+
+        // マニフェストなしでもチャンクの区切りが分かる
+        let chunks = split_chunked_ciphertext(&chunked.ciphertext).unwrap();
+        assert_eq!(chunks.len(), 3);
+        for (chunk, entry) in chunks.iter().zip(chunked.manifest.chunks()) {
+            assert_eq!(entry.ciphertext_sha256, hex::encode(Sha256::digest(chunk)));
+        }
+        assert_eq!(join_chunked_ciphertext(chunks), chunked.ciphertext);
+        assert_eq!(
+            decrypt_ciphertext(&key(), &encryption, &chunked.ciphertext).unwrap(),
+            b"aaaabbbbcc"
+        );
+
+        // 単一の暗号文はそのまま復号する
+        let single = encryption.encrypt(&key(), b"single").unwrap();
+        assert!(split_chunked_ciphertext(&single).is_none());
+        assert_eq!(
+            decrypt_ciphertext(&key(), &encryption, &single).unwrap(),
+            b"single"
+        );
+    }
+}
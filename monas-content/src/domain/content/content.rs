@@ -1,14 +1,23 @@
+use crate::domain::content::chunking::{decrypt_chunks, encrypt_chunks, ChunkManifest};
 use crate::domain::content::encryption::{ContentEncryption, ContentEncryptionKey};
+use crate::domain::content::key_usage::KeyUsage;
+use crate::domain::content::mime::{sniff_content_type, DEFAULT_CONTENT_TYPE};
 use crate::domain::content::provider::StorageProvider;
 use crate::domain::content::Metadata;
 use crate::domain::content_id::{ContentId, ContentIdGenerator};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
 
+/// コンテンツのライフサイクル上の状態。
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ContentStatus {
     Active,
     Deleting,
     Deleted,
+    /// 保管のみを目的とした状態。明示的に指定すれば取得できる。
+    Archived,
 }
 
 #[derive(Debug, PartialEq)]
@@ -16,6 +25,14 @@ pub enum ContentError {
     EncryptionError(String),
     DecryptionError(String),
     AlreadyDeleted,
+    /// ピン留めされているため削除できない。
+    Pinned,
+    /// アーカイブ済みのため変更できない。先にアーカイブを解除する。
+    Archived,
+    /// アーカイブされていないため解除できない。
+    NotArchived,
+    /// クライアント側で暗号化されているため、サーバーでは暗号化・復号できない。
+    ClientEncrypted,
     StorageError(String),
     Other(String),
 }
@@ -27,18 +44,42 @@ pub enum ContentEvent {
     Deleted,
 }
 
+/// `Content::seal_metadata` で暗号化対象となるメタデータのフィールド。
+#[derive(Serialize, Deserialize)]
+struct SealedMetadataFields {
+    name: String,
+    path: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Content {
     raw_id: ContentId,
     series_id: ContentId,
     encrypted_id: ContentId,
     metadata: Metadata,
+    /// CEK 由来の鍵で暗号化されたメタデータ（name / path）。
+    ///
+    /// `seal_metadata` 済みの場合のみ `Some` となり、その間 `metadata` の
+    /// name / path は空文字に置き換えられる。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sealed_metadata: Option<Vec<u8>>,
     /// 永続化時は含めない（暗号化済みデータのみ保存）
     #[serde(skip)]
-    raw_content: Option<Vec<u8>>,
-    encrypted_content: Option<Vec<u8>>,
+    raw_content: Option<Bytes>,
+    encrypted_content: Option<Bytes>,
     is_deleted: bool,
     content_status: ContentStatus,
+    /// ピン留めされたコンテンツは削除・退避（GC / リモートからの追い出し）の対象にしない。
+    #[serde(default)]
+    pinned: bool,
+    /// 現在の CEK の利用状況（鍵ローテーションポリシーの評価に使う）。
+    #[serde(default)]
+    key_usage: KeyUsage,
+    /// クライアント側で暗号化されたコンテンツの、クライアントがラップした CEK。
+    ///
+    /// `Some` の場合、サーバーは CEK を持たず平文を扱わない（エンドツーエンド暗号化）。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client_wrapped_cek: Option<Vec<u8>>,
     // TODO: 必要性があるかもしれないので追加した
     // last_updated_by: Option<StateNodeId>, // 最後に更新を行ったStateNodeのID
 }
@@ -57,10 +98,14 @@ impl Content {
             series_id: id.clone(),
             encrypted_id: id,
             metadata,
-            raw_content,
-            encrypted_content,
+            raw_content: raw_content.map(Bytes::from),
+            encrypted_content: encrypted_content.map(Bytes::from),
             is_deleted,
             content_status: ContentStatus::Active,
+            sealed_metadata: None,
+            pinned: false,
+            key_usage: KeyUsage::default(),
+            client_wrapped_cek: None,
         }
     }
 
@@ -73,12 +118,69 @@ impl Content {
         key: &ContentEncryptionKey,
         encryption: &E,
     ) -> Result<(Self, ContentEvent), ContentError>
+    where
+        G: ContentIdGenerator,
+        E: ContentEncryption,
+    {
+        Self::create_with_layout(
+            name,
+            raw_content,
+            path,
+            provider,
+            id_generator,
+            key,
+            encryption,
+            None,
+        )
+    }
+
+    /// `chunk_size` バイトごとのチャンクに分けて暗号化したコンテンツを作成する。
+    ///
+    /// チャンク構成はメタデータのマニフェストに記録され、以降の `update_content` では
+    /// 変更されたチャンクのみ暗号化し直す。
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_chunked<G, E>(
+        name: String,
+        raw_content: Vec<u8>,
+        path: String,
+        provider: Option<StorageProvider>,
+        id_generator: &G,
+        key: &ContentEncryptionKey,
+        encryption: &E,
+        chunk_size: usize,
+    ) -> Result<(Self, ContentEvent), ContentError>
+    where
+        G: ContentIdGenerator,
+        E: ContentEncryption,
+    {
+        Self::create_with_layout(
+            name,
+            raw_content,
+            path,
+            provider,
+            id_generator,
+            key,
+            encryption,
+            Some(chunk_size),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_with_layout<G, E>(
+        name: String,
+        raw_content: Vec<u8>,
+        path: String,
+        provider: Option<StorageProvider>,
+        id_generator: &G,
+        key: &ContentEncryptionKey,
+        encryption: &E,
+        chunk_size: Option<usize>,
+    ) -> Result<(Self, ContentEvent), ContentError>
     where
         G: ContentIdGenerator,
         E: ContentEncryption,
     {
         let cid = id_generator.generate(&raw_content);
-        let metadata = Metadata::new(name, path, cid.clone(), provider);
 
         if key.0.is_empty() {
             return Err(ContentError::EncryptionError(
@@ -86,29 +188,133 @@ impl Content {
             ));
         }
 
-        let encrypted_content = encryption.encrypt(key, &raw_content)?;
+        let (encrypted_content, chunk_manifest, encrypted_bytes) =
+            encrypt_payload(key, encryption, &raw_content, chunk_size, None)?;
+        let metadata = Metadata::new(name, path, cid.clone(), provider)
+            .with_content_info(sniff_content_type(&raw_content), raw_content.len() as u64)
+            .with_chunk_manifest(chunk_manifest);
         // encCid は plainCid と暗号文から生成する（state-node 等が暗号文整合性を検証できるようにする）。
         let enc_cid = id_generator.generate_encrypted(&cid, &encrypted_content);
+        let key_usage = KeyUsage::issued(Utc::now(), encrypted_bytes);
+
+        let content = Self {
+            raw_id: cid.clone(),
+            series_id: cid,
+            encrypted_id: enc_cid,
+            metadata,
+            raw_content: Some(Bytes::from(raw_content)),
+            encrypted_content: Some(Bytes::from(encrypted_content)),
+            is_deleted: false,
+            content_status: ContentStatus::Active,
+            sealed_metadata: None,
+            pinned: false,
+            key_usage,
+            client_wrapped_cek: None,
+        };
+
+        Ok((content, ContentEvent::Created))
+    }
+
+    /// クライアント側で暗号化済みのコンテンツを作成する（エンドツーエンド暗号化）。
+    ///
+    /// - サーバーは平文も CEK も扱わない。`wrapped_cek` は解釈せずにそのまま保存する
+    /// - 平文が得られないため、ContentId（plainCid）は暗号文から計算する
+    /// - Content-Type は判定できないため `application/octet-stream`、サイズは暗号文のバイト数
+    /// - CEK の利用状況は記録しない（鍵ローテーションはクライアントの責務）
+    pub fn create_client_encrypted<G>(
+        name: String,
+        ciphertext: Vec<u8>,
+        wrapped_cek: Vec<u8>,
+        path: String,
+        provider: Option<StorageProvider>,
+        id_generator: &G,
+    ) -> Result<(Self, ContentEvent), ContentError>
+    where
+        G: ContentIdGenerator,
+    {
+        if wrapped_cek.is_empty() {
+            return Err(ContentError::EncryptionError(
+                "Missing wrapped content encryption key".to_string(),
+            ));
+        }
+
+        let cid = id_generator.generate(&ciphertext);
+        let enc_cid = id_generator.generate_encrypted(&cid, &ciphertext);
+        let metadata = Metadata::new(name, path, cid.clone(), provider)
+            .with_content_info(DEFAULT_CONTENT_TYPE, ciphertext.len() as u64);
 
         let content = Self {
             raw_id: cid.clone(),
             series_id: cid,
             encrypted_id: enc_cid,
             metadata,
-            raw_content: Some(raw_content),
-            encrypted_content: Some(encrypted_content),
+            raw_content: None,
+            encrypted_content: Some(Bytes::from(ciphertext)),
             is_deleted: false,
             content_status: ContentStatus::Active,
+            sealed_metadata: None,
+            pinned: false,
+            key_usage: KeyUsage::default(),
+            client_wrapped_cek: Some(wrapped_cek),
         };
 
         Ok((content, ContentEvent::Created))
     }
 
+    /// コンテンツ本体をクライアント側で暗号化済みの暗号文に置き換える。
+    ///
+    /// `create_client_encrypted` と同様に ContentId は暗号文から計算する。
+    /// サーバー側で暗号化していたコンテンツもこの更新でクライアント側の暗号化に切り替わる。
+    pub fn update_client_encrypted<G>(
+        &self,
+        ciphertext: Vec<u8>,
+        wrapped_cek: Vec<u8>,
+        id_generator: &G,
+    ) -> Result<(Self, ContentEvent), ContentError>
+    where
+        G: ContentIdGenerator,
+    {
+        self.ensure_not_deleted()?;
+        self.ensure_not_archived()?;
+
+        if wrapped_cek.is_empty() {
+            return Err(ContentError::EncryptionError(
+                "Missing wrapped content encryption key".to_string(),
+            ));
+        }
+
+        let new_id = id_generator.generate(&ciphertext);
+        let new_enc_id = id_generator.generate_encrypted(&new_id, &ciphertext);
+        let new_metadata = self
+            .metadata
+            .with_new_id(new_id.clone())
+            .with_content_info(DEFAULT_CONTENT_TYPE, ciphertext.len() as u64)
+            .with_chunk_manifest(None);
+
+        let content = Self {
+            raw_id: new_id,
+            series_id: self.series_id.clone(),
+            encrypted_id: new_enc_id,
+            metadata: new_metadata,
+            raw_content: None,
+            encrypted_content: Some(Bytes::from(ciphertext)),
+            is_deleted: false,
+            content_status: ContentStatus::Active,
+            sealed_metadata: None,
+            pinned: self.pinned,
+            key_usage: KeyUsage::default(),
+            client_wrapped_cek: Some(wrapped_cek),
+        };
+
+        Ok((content, ContentEvent::Updated))
+    }
+
     /// コンテンツ本体（バイナリ）のみを更新する。
     ///
     /// - name / path / series_id は変更しない
     /// - `raw_id`（plainCid）は新しいバイナリから再計算される（コンテンツアドレス化）
     /// - `metadata.updated_at` は現在時刻に更新される
+    /// - チャンク分割されたコンテンツは同じチャンクサイズのまま、変更されたチャンクのみ暗号化し直す
     pub fn update_content<G, E>(
         &self,
         raw_content: Vec<u8>,
@@ -116,11 +322,57 @@ impl Content {
         key: &ContentEncryptionKey,
         encryption: &E,
     ) -> Result<(Self, ContentEvent), ContentError>
+    where
+        G: ContentIdGenerator,
+        E: ContentEncryption,
+    {
+        let chunk_size = self
+            .metadata
+            .chunk_manifest()
+            .map(ChunkManifest::chunk_size);
+        self.update_content_with_layout(raw_content, id_generator, key, encryption, chunk_size)
+    }
+
+    /// コンテンツ本体を `chunk_size` バイトごとのチャンクに分けて暗号化し直して更新する。
+    ///
+    /// 既存のチャンク構成と CEK・チャンクサイズが同じであれば、内容が変わっていない
+    /// チャンクの暗号文はそのまま再利用する。
+    pub fn update_content_chunked<G, E>(
+        &self,
+        raw_content: Vec<u8>,
+        id_generator: &G,
+        key: &ContentEncryptionKey,
+        encryption: &E,
+        chunk_size: usize,
+    ) -> Result<(Self, ContentEvent), ContentError>
+    where
+        G: ContentIdGenerator,
+        E: ContentEncryption,
+    {
+        self.update_content_with_layout(
+            raw_content,
+            id_generator,
+            key,
+            encryption,
+            Some(chunk_size),
+        )
+    }
+
+    fn update_content_with_layout<G, E>(
+        &self,
+        raw_content: Vec<u8>,
+        id_generator: &G,
+        key: &ContentEncryptionKey,
+        encryption: &E,
+        chunk_size: Option<usize>,
+    ) -> Result<(Self, ContentEvent), ContentError>
     where
         G: ContentIdGenerator,
         E: ContentEncryption,
     {
         self.ensure_not_deleted()?;
+        self.ensure_not_archived()?;
+        self.ensure_server_encrypted()?;
 
         if key.0.is_empty() {
             return Err(ContentError::EncryptionError(
@@ -128,23 +380,39 @@ impl Content {
             ));
         }
 
-        let encrypted_content = encryption.encrypt(key, &raw_content)?;
+        let previous = self
+            .metadata
+            .chunk_manifest()
+            .zip(self.encrypted_content.as_deref());
+        let (encrypted_content, chunk_manifest, encrypted_bytes) =
+            encrypt_payload(key, encryption, &raw_content, chunk_size, previous)?;
 
         let new_id = id_generator.generate(&raw_content);
         // encCid は plainCid と暗号文から生成する。
         let new_enc_id = id_generator.generate_encrypted(&new_id, &encrypted_content);
+        // 同じ CEK を使い続ける前提で利用量を加算する（新しい CEK なら呼び出し側で `with_rotated_key`）。
+        // 再利用したチャンクは新たに暗号化していないので数えない。
+        let key_usage = self.key_usage.record(encrypted_bytes);
 
-        let new_metadata = self.metadata.with_new_id(new_id.clone());
+        let new_metadata = self
+            .metadata
+            .with_new_id(new_id.clone())
+            .with_content_info(sniff_content_type(&raw_content), raw_content.len() as u64)
+            .with_chunk_manifest(chunk_manifest);
 
         let content = Self {
             raw_id: new_id,
             series_id: self.series_id.clone(),
             encrypted_id: new_enc_id,
             metadata: new_metadata,
-            raw_content: Some(raw_content),
-            encrypted_content: Some(encrypted_content),
+            raw_content: Some(Bytes::from(raw_content)),
+            encrypted_content: Some(Bytes::from(encrypted_content)),
             is_deleted: false,
             content_status: ContentStatus::Active,
+            sealed_metadata: None,
+            pinned: self.pinned,
+            key_usage,
+            client_wrapped_cek: None,
         };
 
         Ok((content, ContentEvent::Updated))
@@ -156,6 +424,7 @@ impl Content {
     /// - `metadata.updated_at` は現在時刻に更新される
     pub fn rename(&self, new_name: String) -> Result<(Self, ContentEvent), ContentError> {
         self.ensure_not_deleted()?;
+        self.ensure_not_archived()?;
 
         let new_metadata = self.metadata.rename(new_name);
 
@@ -168,6 +437,10 @@ impl Content {
             encrypted_content: self.encrypted_content.clone(),
             is_deleted: self.is_deleted,
             content_status: self.content_status.clone(),
+            sealed_metadata: None,
+            pinned: self.pinned,
+            key_usage: self.key_usage.clone(),
+            client_wrapped_cek: self.client_wrapped_cek.clone(),
         };
 
         Ok((content, ContentEvent::Updated))
@@ -176,6 +449,9 @@ impl Content {
     // 複数のノードでのコンセンサスやキャンセル可能性が必要な場合は段階的な削除処理が必要
     pub fn delete(&self) -> Result<(Self, ContentEvent), ContentError> {
         self.ensure_not_deleted()?;
+        if self.pinned {
+            return Err(ContentError::Pinned);
+        }
 
         // 削除操作も更新の一種なので updated_at を進める
         let new_metadata = self.metadata.touch();
@@ -189,11 +465,179 @@ impl Content {
             encrypted_content: None,
             is_deleted: true,
             content_status: ContentStatus::Deleted,
+            sealed_metadata: None,
+            pinned: false,
+            key_usage: KeyUsage::default(),
+            client_wrapped_cek: None,
         };
 
         Ok((content, ContentEvent::Deleted))
     }
 
+    /// 新しい CEK で暗号化し直した直後として、CEK の利用状況をリセットしたコンテンツを返す。
+    ///
+    /// 再暗号化（鍵ローテーション）で `update_content` に新しい CEK を渡した後に呼ぶ。
+    pub fn with_rotated_key(&self) -> Self {
+        let mut content = self.clone();
+        let bytes = self
+            .encrypted_content
+            .as_ref()
+            .map_or(0, |c| c.len() as u64);
+        content.key_usage = KeyUsage::issued(Utc::now(), bytes);
+        content
+    }
+
+    /// アーカイブしたコンテンツを返す。
+    ///
+    /// アーカイブ済みのコンテンツは既定の一覧から外れ、アーカイブを解除するまで
+    /// 更新・名前変更できない。取得と削除はそのまま行える。
+    pub fn archive(&self) -> Result<(Self, ContentEvent), ContentError> {
+        self.ensure_not_deleted()?;
+        self.ensure_not_archived()?;
+        Ok((
+            self.with_status(ContentStatus::Archived),
+            ContentEvent::Updated,
+        ))
+    }
+
+    /// アーカイブを解除し、`Active` に戻したコンテンツを返す。
+    pub fn unarchive(&self) -> Result<(Self, ContentEvent), ContentError> {
+        self.ensure_not_deleted()?;
+        if !self.is_archived() {
+            return Err(ContentError::NotArchived);
+        }
+        Ok((
+            self.with_status(ContentStatus::Active),
+            ContentEvent::Updated,
+        ))
+    }
+
+    // 状態の変更も更新の一種なので updated_at を進める
+    fn with_status(&self, status: ContentStatus) -> Self {
+        let mut content = self.clone();
+        content.metadata = self.metadata.touch();
+        content.content_status = status;
+        content
+    }
+
+    /// ピン留めしたコンテンツを返す。
+    ///
+    /// 版（`version`）や `updated_at` は変えない。削除済みのコンテンツはピン留めできない。
+    pub fn pin(&self) -> Result<Self, ContentError> {
+        self.ensure_not_deleted()?;
+        let mut content = self.clone();
+        content.pinned = true;
+        Ok(content)
+    }
+
+    /// ピン留めを解除したコンテンツを返す。
+    pub fn unpin(&self) -> Self {
+        let mut content = self.clone();
+        content.pinned = false;
+        content
+    }
+
+    /// メタデータの name / path を暗号化し、平文側を空にしたコンテンツを返す。
+    ///
+    /// - ContentId・タイムスタンプ・プロバイダーなどのルーティング情報は平文のまま残す
+    /// - `key` には CEK もしくは CEK から導出したメタデータ用の鍵を渡す
+    /// - 既に封印済みの場合はそのまま返す
+    pub fn seal_metadata<E>(
+        &self,
+        key: &ContentEncryptionKey,
+        encryption: &E,
+    ) -> Result<Self, ContentError>
+    where
+        E: ContentEncryption,
+    {
+        if self.sealed_metadata.is_some() {
+            return Ok(self.clone());
+        }
+        if key.0.is_empty() {
+            return Err(ContentError::EncryptionError(
+                "Missing metadata encryption key".to_string(),
+            ));
+        }
+
+        let fields = SealedMetadataFields {
+            name: self.metadata.name().to_string(),
+            path: self.metadata.path().to_string(),
+        };
+        let plaintext = serde_json::to_vec(&fields)
+            .map_err(|e| ContentError::EncryptionError(format!("metadata serialization: {e}")))?;
+        let sealed = encryption.encrypt(key, &plaintext)?;
+
+        let mut content = self.clone();
+        content.metadata = self
+            .metadata
+            .with_name_and_path(String::new(), String::new());
+        content.sealed_metadata = Some(sealed);
+        Ok(content)
+    }
+
+    /// `seal_metadata` で暗号化されたメタデータを復号し、name / path を復元したコンテンツを返す。
+    ///
+    /// 封印されていない場合はそのまま返す。
+    pub fn unseal_metadata<E>(
+        &self,
+        key: &ContentEncryptionKey,
+        encryption: &E,
+    ) -> Result<Self, ContentError>
+    where
+        E: ContentEncryption,
+    {
+        let Some(sealed) = self.sealed_metadata.as_ref() else {
+            return Ok(self.clone());
+        };
+        if key.0.is_empty() {
+            return Err(ContentError::DecryptionError(
+                "Missing metadata encryption key".to_string(),
+            ));
+        }
+
+        // name / path の平文 JSON は復元後に消去する
+        let plaintext = Zeroizing::new(encryption.decrypt(key, sealed)?);
+        let fields: SealedMetadataFields = serde_json::from_slice(&plaintext)
+            .map_err(|e| ContentError::DecryptionError(format!("metadata deserialization: {e}")))?;
+
+        let mut content = self.clone();
+        content.metadata = self.metadata.with_name_and_path(fields.name, fields.path);
+        content.sealed_metadata = None;
+        Ok(content)
+    }
+
+    /// メタデータの name / path を破棄したコンテンツを返す。
+    ///
+    /// CEK が削除され封印できない削除済みコンテンツを、平文メタデータなしで保存するために使う。
+    pub fn redact_metadata(&self) -> Self {
+        let mut content = self.clone();
+        content.metadata = self
+            .metadata
+            .with_name_and_path(String::new(), String::new());
+        content.sealed_metadata = None;
+        content
+    }
+
+    /// メタデータが暗号化された状態かどうか。
+    pub fn has_sealed_metadata(&self) -> bool {
+        self.sealed_metadata.is_some()
+    }
+
+    /// 暗号文を取り除いたコンテンツを返す。
+    ///
+    /// 暗号文とメタデータを別々の場所に保存するリポジトリ実装で使う。
+    pub(crate) fn without_encrypted_content(&self) -> Self {
+        let mut content = self.clone();
+        content.encrypted_content = None;
+        content
+    }
+
+    /// 別途保存していた暗号文を戻したコンテンツを返す。
+    pub(crate) fn with_encrypted_content(mut self, encrypted_content: impl Into<Bytes>) -> Self {
+        self.encrypted_content = Some(encrypted_content.into());
+        self
+    }
+
     pub fn decrypt<E>(
         &self,
         key: &ContentEncryptionKey,
@@ -203,6 +647,7 @@ impl Content {
         E: ContentEncryption,
     {
         self.ensure_not_deleted()?;
+        self.ensure_server_encrypted()?;
 
         let Some(encrypted) = self.encrypted_content.as_ref() else {
             return Err(ContentError::DecryptionError(
@@ -216,7 +661,45 @@ impl Content {
             ));
         }
 
-        encryption.decrypt(key, encrypted)
+        match self.metadata.chunk_manifest() {
+            Some(manifest) => decrypt_chunks(key, encryption, manifest, encrypted),
+            None => encryption.decrypt(key, encrypted),
+        }
+    }
+
+    /// コンテンツを消費して復号する。
+    ///
+    /// 暗号文バッファを他から参照されていなければ、その場で復号してコピーせずに返す。
+    /// 取得（fetch）のように復号後の `Content` が不要な経路ではこちらを使う。
+    pub fn into_decrypted<E>(
+        mut self,
+        key: &ContentEncryptionKey,
+        encryption: &E,
+    ) -> Result<Bytes, ContentError>
+    where
+        E: ContentEncryption,
+    {
+        self.ensure_not_deleted()?;
+        self.ensure_server_encrypted()?;
+
+        let Some(encrypted) = self.encrypted_content.take() else {
+            return Err(ContentError::DecryptionError(
+                "Missing encrypted content".to_string(),
+            ));
+        };
+
+        if key.0.is_empty() {
+            return Err(ContentError::DecryptionError(
+                "Missing content encryption key".to_string(),
+            ));
+        }
+
+        match self.metadata.chunk_manifest() {
+            Some(manifest) => {
+                decrypt_chunks(key, encryption, manifest, &encrypted).map(Bytes::from)
+            }
+            None => encryption.decrypt_bytes(key, encrypted),
+        }
     }
 
     /// - `is_deleted == true` の場合は `ContentError::AlreadyDeleted` を返す。
@@ -228,6 +711,23 @@ impl Content {
         }
     }
 
+    fn ensure_not_archived(&self) -> Result<(), ContentError> {
+        if self.is_archived() {
+            Err(ContentError::Archived)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// クライアント側で暗号化されたコンテンツはサーバーの CEK で暗号化・復号できない。
+    fn ensure_server_encrypted(&self) -> Result<(), ContentError> {
+        if self.is_client_encrypted() {
+            Err(ContentError::ClientEncrypted)
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn metadata(&self) -> &Metadata {
         &self.metadata
     }
@@ -244,11 +744,11 @@ impl Content {
         &self.encrypted_id
     }
 
-    pub fn raw_content(&self) -> Option<&Vec<u8>> {
+    pub fn raw_content(&self) -> Option<&Bytes> {
         self.raw_content.as_ref()
     }
 
-    pub fn encrypted_content(&self) -> Option<&Vec<u8>> {
+    pub fn encrypted_content(&self) -> Option<&Bytes> {
         self.encrypted_content.as_ref()
     }
 
@@ -259,6 +759,98 @@ impl Content {
     pub fn content_status(&self) -> &ContentStatus {
         &self.content_status
     }
+
+    /// 暗号文がチャンク分割されているかどうか。
+    pub fn is_chunked(&self) -> bool {
+        self.metadata.chunk_manifest().is_some()
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.content_status == ContentStatus::Archived
+    }
+
+    pub fn key_usage(&self) -> &KeyUsage {
+        &self.key_usage
+    }
+
+    /// 現在の CEK を発行した時刻。記録がない場合は作成日時で代用する。
+    pub fn key_issued_at(&self) -> DateTime<Utc> {
+        self.key_usage
+            .issued_at()
+            .unwrap_or_else(|| self.metadata.created_at())
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    /// クライアント側で暗号化されたコンテンツかどうか。
+    pub fn is_client_encrypted(&self) -> bool {
+        self.client_wrapped_cek.is_some()
+    }
+
+    /// クライアントがラップした CEK。サーバー側で暗号化したコンテンツでは `None`。
+    pub fn client_wrapped_cek(&self) -> Option<&[u8]> {
+        self.client_wrapped_cek.as_deref()
+    }
+
+    /// GC やストレージの容量確保のために取り除いてよいかどうか。
+    ///
+    /// ピン留めされたコンテンツは常に保持する。
+    pub fn is_evictable(&self) -> bool {
+        !self.pinned
+    }
+
+    /// コンテンツの版を識別する短いハッシュ（HTTP の ETag などに使う）。
+    ///
+    /// 暗号文由来の `encrypted_id` と `updated_at`・削除状態から計算するため、
+    /// 復号せずに求められ、本体の更新・リネーム・削除のいずれでも値が変わる。
+    pub fn version(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.encrypted_id.as_str().as_bytes());
+        hasher.update([0u8]);
+        hasher.update(
+            self.metadata
+                .updated_at()
+                .timestamp_nanos_opt()
+                .unwrap_or_default()
+                .to_be_bytes(),
+        );
+        hasher.update([self.is_deleted as u8]);
+        hex::encode(&hasher.finalize()[..16])
+    }
+}
+
+/// 平文を暗号化する。`chunk_size` が指定されていればチャンク単位で暗号化する。
+///
+/// 戻り値は（暗号文, チャンク構成, 今回新たに暗号化した暗号文のバイト数）。
+fn encrypt_payload<E>(
+    key: &ContentEncryptionKey,
+    encryption: &E,
+    raw_content: &[u8],
+    chunk_size: Option<usize>,
+    previous: Option<(&ChunkManifest, &[u8])>,
+) -> Result<(Vec<u8>, Option<ChunkManifest>, u64), ContentError>
+where
+    E: ContentEncryption,
+{
+    match chunk_size {
+        Some(chunk_size) => {
+            let chunked = encrypt_chunks(key, encryption, raw_content, chunk_size, previous)?;
+            Ok((
+                chunked.ciphertext,
+                Some(chunked.manifest),
+                chunked.encrypted_bytes,
+            ))
+        }
+        None => {
+            let encrypted = encryption.encrypt(key, raw_content)?;
+            let len = encrypted.len() as u64;
+            Ok((encrypted, None, len))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -294,7 +886,7 @@ mod tests {
         Metadata::new(
             "test_content".to_string(),
             "test/path".to_string(),
-            ContentId::new("test-content-id".into()),
+            ContentId::for_test("test-content-id"),
             None,
         )
     }
@@ -309,11 +901,11 @@ mod tests {
     impl ContentIdGenerator for MockIdGenerator {
         fn generate(&self, raw_content: &[u8]) -> ContentId {
             // テスト用の単純な ID 生成: 長さに応じて異なる ID を返す。
-            ContentId::new(format!("test-content-id-{}", raw_content.len()))
+            ContentId::for_test(&format!("test-content-id-{}", raw_content.len()))
         }
 
         fn generate_encrypted(&self, plain_cid: &ContentId, ciphertext: &[u8]) -> ContentId {
-            ContentId::new(format!(
+            ContentId::for_test(&format!(
                 "test-enc-id-{}-{}",
                 plain_cid.as_str(),
                 ciphertext.len()
@@ -348,10 +940,41 @@ mod tests {
         assert_eq!(content.content_status(), &ContentStatus::Active);
         assert_eq!(event, ContentEvent::Created);
         assert!(content.encrypted_content().is_some());
-        assert!(content.raw_id().as_str().starts_with("test-content-id-"));
+        assert_eq!(
+            content.raw_id(),
+            &ContentId::for_test(&format!("test-content-id-{}", raw_data.len()))
+        );
         assert_eq!(content.raw_id(), content.series_id());
     }
 
+    #[test]
+    fn version_changes_on_update_and_delete() {
+        let (key, encryption) = test_key_and_cipher();
+        let id_gen = MockIdGenerator;
+
+        let (content, _) = Content::create(
+            "test".to_string(),
+            b"old".to_vec(),
+            "path.txt".to_string(),
+            None,
+            &id_gen,
+            &key,
+            &encryption,
+        )
+        .unwrap();
+        let version = content.version();
+        assert_eq!(version, content.clone().version());
+        assert_eq!(version.len(), 32);
+
+        let (updated, _) = content
+            .update_content(b"new content".to_vec(), &id_gen, &key, &encryption)
+            .unwrap();
+        assert_ne!(updated.version(), version);
+
+        let (deleted, _) = updated.delete().unwrap();
+        assert_ne!(deleted.version(), updated.version());
+    }
+
     #[test]
     fn update_changes_raw_content_and_keeps_path() {
         let (key, encryption) = test_key_and_cipher();
@@ -378,13 +1001,111 @@ mod tests {
         assert_eq!(updated_content.metadata().path(), content.metadata().path());
         assert_ne!(updated_content.raw_id(), content.raw_id());
         assert_eq!(updated_content.series_id(), content.series_id());
+        assert_eq!(content.metadata().size(), Some(3));
+        assert_eq!(
+            updated_content.metadata().size(),
+            Some(updated_data.len() as u64)
+        );
+        assert_eq!(
+            updated_content.metadata().content_type(),
+            Some("text/plain")
+        );
+    }
+
+    #[test]
+    fn chunked_update_reencrypts_only_changed_chunks() {
+        let (key, encryption) = test_key_and_cipher();
+        let id_gen = MockIdGenerator;
+
+        let (content, _) = Content::create_chunked(
+            "large".to_string(),
+            b"aaaabbbbcccc".to_vec(),
+            "large.bin".to_string(),
+            None,
+            &id_gen,
+            &key,
+            &encryption,
+            4,
+        )
+        .unwrap();
+        assert!(content.is_chunked());
+        assert_eq!(
+            content.metadata().chunk_manifest().unwrap().chunks().len(),
+            3
+        );
+        assert_eq!(content.key_usage().bytes_encrypted(), 12);
+
+        // チャンクサイズを指定しない更新でもチャンク構成は引き継がれる
+        let (updated, _) = content
+            .update_content(b"aaaaXXXXcccc".to_vec(), &id_gen, &key, &encryption)
+            .unwrap();
+        assert!(updated.is_chunked());
+        assert_eq!(updated.key_usage().bytes_encrypted(), 12 + 4);
+        assert_eq!(
+            updated.decrypt(&key, &encryption).unwrap(),
+            b"aaaaXXXXcccc".to_vec()
+        );
+        assert_eq!(
+            updated.into_decrypted(&key, &encryption).unwrap(),
+            Bytes::from_static(b"aaaaXXXXcccc")
+        );
+    }
+
+    #[test]
+    fn update_content_chunked_converts_single_blob_content() {
+        let (key, encryption) = test_key_and_cipher();
+        let id_gen = MockIdGenerator;
+
+        let (content, _) = Content::create(
+            "doc".to_string(),
+            b"aaaabbbb".to_vec(),
+            "doc.bin".to_string(),
+            None,
+            &id_gen,
+            &key,
+            &encryption,
+        )
+        .unwrap();
+        assert!(!content.is_chunked());
+
+        let (updated, _) = content
+            .update_content_chunked(b"aaaabbbbcc".to_vec(), &id_gen, &key, &encryption, 4)
+            .unwrap();
+        assert_eq!(
+            updated.metadata().chunk_manifest().unwrap().chunks().len(),
+            3
+        );
+        assert_eq!(
+            updated.decrypt(&key, &encryption).unwrap(),
+            b"aaaabbbbcc".to_vec()
+        );
+    }
+
+    #[test]
+    fn create_records_sniffed_content_type_and_size() {
+        let (key, encryption) = test_key_and_cipher();
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+
+        let (content, _) = Content::create(
+            "image.png".to_string(),
+            png.clone(),
+            "image.png".to_string(),
+            None,
+            &MockIdGenerator,
+            &key,
+            &encryption,
+        )
+        .unwrap();
+
+        assert_eq!(content.metadata().content_type(), Some("image/png"));
+        assert_eq!(content.metadata().size(), Some(png.len() as u64));
     }
 
     #[test]
     fn rename_updates_name_and_metadata_timestamp() {
         let metadata = create_test_metadata();
         let content = Content::new(
-            ContentId::new("test-content-id".into()),
+            ContentId::for_test("test-content-id"),
             metadata,
             None,
             None,
@@ -430,7 +1151,7 @@ mod tests {
     fn delete_on_already_deleted_returns_error() {
         let metadata = create_test_metadata();
         let deleted_content = Content::new(
-            ContentId::new("test-content-id".into()),
+            ContentId::for_test("test-content-id"),
             metadata,
             None,
             None,
@@ -441,11 +1162,136 @@ mod tests {
         assert!(matches!(result, Err(ContentError::AlreadyDeleted)));
     }
 
+    #[test]
+    fn pinned_content_cannot_be_deleted_until_unpinned() {
+        let (key, encryption) = test_key_and_cipher();
+        let (content, _) = Content::create(
+            "test".to_string(),
+            b"data".to_vec(),
+            "path.txt".to_string(),
+            None,
+            &MockIdGenerator,
+            &key,
+            &encryption,
+        )
+        .unwrap();
+
+        let pinned = content.pin().unwrap();
+        assert!(pinned.is_pinned());
+        assert!(!pinned.is_evictable());
+        assert_eq!(pinned.version(), content.version());
+        assert!(matches!(pinned.delete(), Err(ContentError::Pinned)));
+
+        // 更新・リネーム後もピン留めは維持される
+        let (renamed, _) = pinned.rename("renamed".to_string()).unwrap();
+        assert!(renamed.is_pinned());
+
+        let unpinned = renamed.unpin();
+        assert!(unpinned.is_evictable());
+        assert!(unpinned.delete().is_ok());
+    }
+
+    #[test]
+    fn key_usage_accumulates_until_key_is_rotated() {
+        let (key, encryption) = test_key_and_cipher();
+        let (content, _) = Content::create(
+            "test".to_string(),
+            b"data".to_vec(),
+            "path.txt".to_string(),
+            None,
+            &MockIdGenerator,
+            &key,
+            &encryption,
+        )
+        .unwrap();
+        let first = content.encrypted_content().unwrap().len() as u64;
+        assert_eq!(content.key_usage().bytes_encrypted(), first);
+        assert!(content.key_usage().issued_at().is_some());
+
+        let (updated, _) = content
+            .update_content(b"more data".to_vec(), &MockIdGenerator, &key, &encryption)
+            .unwrap();
+        let second = updated.encrypted_content().unwrap().len() as u64;
+        assert_eq!(updated.key_usage().bytes_encrypted(), first + second);
+        assert_eq!(updated.key_issued_at(), content.key_issued_at());
+
+        let rotated = updated.with_rotated_key();
+        assert_eq!(rotated.key_usage().bytes_encrypted(), second);
+        assert!(rotated.key_issued_at() >= updated.key_issued_at());
+    }
+
+    #[test]
+    fn pin_on_deleted_content_returns_error() {
+        let deleted_content = Content::new(
+            ContentId::for_test("test-content-id"),
+            create_test_metadata(),
+            None,
+            None,
+            true,
+        );
+
+        assert!(matches!(
+            deleted_content.pin(),
+            Err(ContentError::AlreadyDeleted)
+        ));
+    }
+
+    #[test]
+    fn archive_and_unarchive_transition_status() {
+        let content = Content::new(
+            ContentId::for_test("test-content-id"),
+            create_test_metadata(),
+            None,
+            None,
+            false,
+        );
+
+        let (archived, event) = content.archive().unwrap();
+        assert!(archived.is_archived());
+        assert_eq!(archived.content_status(), &ContentStatus::Archived);
+        assert_eq!(event, ContentEvent::Updated);
+        assert!(matches!(archived.archive(), Err(ContentError::Archived)));
+        assert!(matches!(
+            archived.rename("renamed".into()),
+            Err(ContentError::Archived)
+        ));
+
+        let (restored, _) = archived.unarchive().unwrap();
+        assert_eq!(restored.content_status(), &ContentStatus::Active);
+        assert!(matches!(
+            restored.unarchive(),
+            Err(ContentError::NotArchived)
+        ));
+    }
+
+    #[test]
+    fn archived_content_can_still_be_deleted() {
+        let content = Content::new(
+            ContentId::for_test("test-content-id"),
+            create_test_metadata(),
+            None,
+            None,
+            false,
+        );
+        let (archived, _) = content.archive().unwrap();
+
+        let (deleted, _) = archived.delete().unwrap();
+        assert_eq!(deleted.content_status(), &ContentStatus::Deleted);
+        assert!(matches!(
+            deleted.archive(),
+            Err(ContentError::AlreadyDeleted)
+        ));
+        assert!(matches!(
+            deleted.unarchive(),
+            Err(ContentError::AlreadyDeleted)
+        ));
+    }
+
     #[test]
     fn update_on_deleted_content_returns_error() {
         let metadata = create_test_metadata();
         let deleted_content = Content::new(
-            ContentId::new("test-content-id".into()),
+            ContentId::for_test("test-content-id"),
             metadata,
             None,
             None,
@@ -463,7 +1309,7 @@ mod tests {
     fn decrypt_on_deleted_content_returns_error() {
         let metadata = create_test_metadata();
         let deleted_content = Content::new(
-            ContentId::new("test-content-id".into()),
+            ContentId::for_test("test-content-id"),
             metadata,
             None,
             None,
@@ -501,13 +1347,34 @@ mod tests {
         assert_eq!(decrypted_data, raw_data);
     }
 
+    #[test]
+    fn into_decrypted_matches_decrypt() {
+        let (key, encryption) = test_key_and_cipher();
+        let raw_data = b"Sensitive information".to_vec();
+        let (content, _) = Content::create(
+            "test file".to_string(),
+            raw_data.clone(),
+            "documents/secrets.txt".to_string(),
+            None,
+            &MockIdGenerator,
+            &key,
+            &encryption,
+        )
+        .unwrap();
+
+        let copied = content.decrypt(&key, &encryption).unwrap();
+        let decrypted = content.into_decrypted(&key, &encryption).unwrap();
+        assert_eq!(decrypted, raw_data);
+        assert_eq!(decrypted, copied);
+    }
+
     #[test]
     fn decrypt_error_when_missing_encrypted_content() {
         let (key, encryption) = test_key_and_cipher();
 
         let metadata = create_test_metadata();
         let content_missing_encrypted = Content::new(
-            ContentId::new("test-content-id".into()),
+            ContentId::for_test("test-content-id"),
             metadata,
             Some(b"Raw data".to_vec()),
             None,
@@ -522,7 +1389,7 @@ mod tests {
     fn decrypt_error_when_missing_key() {
         let metadata = create_test_metadata();
         let content_with_encrypted = Content::new(
-            ContentId::new("test-content-id".into()),
+            ContentId::for_test("test-content-id"),
             metadata,
             Some(b"Raw data".to_vec()),
             Some(b"Encrypted data".to_vec()),
@@ -535,4 +1402,65 @@ mod tests {
         let result = content_with_encrypted.decrypt(&empty_key, &encryption);
         assert!(matches!(result, Err(ContentError::DecryptionError(_))));
     }
+
+    #[test]
+    fn seal_and_unseal_metadata_roundtrip() {
+        let (key, encryption) = test_key_and_cipher();
+        let (content, _) = Content::create(
+            "secret name".to_string(),
+            b"data".to_vec(),
+            "secret/path.txt".to_string(),
+            None,
+            &MockIdGenerator,
+            &key,
+            &encryption,
+        )
+        .unwrap();
+
+        let sealed = content.seal_metadata(&key, &encryption).unwrap();
+        assert!(sealed.has_sealed_metadata());
+        assert_eq!(sealed.metadata().name(), "");
+        assert_eq!(sealed.metadata().path(), "");
+        assert_eq!(sealed.raw_id(), content.raw_id());
+        assert_eq!(
+            sealed.metadata().created_at(),
+            content.metadata().created_at()
+        );
+
+        let unsealed = sealed.unseal_metadata(&key, &encryption).unwrap();
+        assert!(!unsealed.has_sealed_metadata());
+        assert_eq!(unsealed.metadata().name(), "secret name");
+        assert_eq!(unsealed.metadata().path(), "secret/path.txt");
+    }
+
+    #[test]
+    fn unseal_metadata_without_seal_is_noop() {
+        let (key, encryption) = test_key_and_cipher();
+        let content = Content::new(
+            ContentId::for_test("test-content-id"),
+            create_test_metadata(),
+            None,
+            None,
+            false,
+        );
+
+        let result = content.unseal_metadata(&key, &encryption).unwrap();
+        assert_eq!(result.metadata().name(), "test_content");
+    }
+
+    #[test]
+    fn redact_metadata_clears_name_and_path() {
+        let content = Content::new(
+            ContentId::for_test("test-content-id"),
+            create_test_metadata(),
+            None,
+            None,
+            true,
+        );
+
+        let redacted = content.redact_metadata();
+        assert_eq!(redacted.metadata().name(), "");
+        assert_eq!(redacted.metadata().path(), "");
+        assert!(!redacted.has_sealed_metadata());
+    }
 }
@@ -1,14 +1,55 @@
+use std::fmt;
+
+use bytes::Bytes;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
 use crate::domain::content::ContentError;
+use crate::domain::content_id::ContentId;
 
 /// コンテンツ暗号化に用いる共有鍵 (CEK: Content Encryption Key) を表す値オブジェクト。
 ///
 /// 具体的な鍵素材の生成アルゴリズムや長さ（AES-256 など）は infra 側の実装に委ねる。
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// - drop 時に鍵素材をゼロで上書きする（clone したものもそれぞれ消去される）。
+/// - `Debug` では鍵素材を出力せず、長さのみを表示する（ログへの漏洩防止）。
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
 pub struct ContentEncryptionKey(pub Vec<u8>);
 
+impl fmt::Debug for ContentEncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ContentEncryptionKey([REDACTED; {} bytes])",
+            self.0.len()
+        )
+    }
+}
+
 /// CEK を生成するためのポート。
 pub trait ContentEncryptionKeyGenerator {
     fn generate(&self) -> ContentEncryptionKey;
+
+    /// 指定したコンテンツ向けの CEK を生成する。
+    ///
+    /// デフォルトは `generate` と同じランダム生成。アカウントのルート鍵から
+    /// ContentId ごとに CEK を導出する実装はこれを上書きする。
+    fn generate_for(&self, _content_id: &ContentId) -> ContentEncryptionKey {
+        self.generate()
+    }
+}
+
+/// `Arc<dyn ContentEncryptionKeyGenerator + Send + Sync>` を `ContentService` の
+/// 型パラメータに直接渡せるようにする blanket impl（ランダム CEK / 導出 CEK を実行時に切り替える）。
+impl<T: ContentEncryptionKeyGenerator + ?Sized> ContentEncryptionKeyGenerator
+    for std::sync::Arc<T>
+{
+    fn generate(&self) -> ContentEncryptionKey {
+        (**self).generate()
+    }
+
+    fn generate_for(&self, content_id: &ContentId) -> ContentEncryptionKey {
+        (**self).generate_for(content_id)
+    }
 }
 
 /// CEK を用いてコンテンツを暗号化/復号するためのポート。
@@ -26,4 +67,17 @@ pub trait ContentEncryption {
         key: &ContentEncryptionKey,
         ciphertext: &[u8],
     ) -> Result<Vec<u8>, ContentError>;
+
+    /// 所有権ごと受け取った暗号文を復号する。
+    ///
+    /// デフォルトは `decrypt` に委譲する（平文用のバッファを新たに確保する）。
+    /// ストリーム暗号など、暗号文のバッファをそのまま平文に書き換えられる実装は
+    /// これを上書きして、大きなコンテンツの取得時のコピーを省く。
+    fn decrypt_bytes(
+        &self,
+        key: &ContentEncryptionKey,
+        ciphertext: Bytes,
+    ) -> Result<Bytes, ContentError> {
+        self.decrypt(key, &ciphertext).map(Bytes::from)
+    }
 }
@@ -0,0 +1,44 @@
+//! コンテンツのライフサイクルを外部コンポーネントへ通知するためのドメインイベント。
+//!
+//! `ContentEvent` はドメイン内部の状態遷移を表すだけのマーカーだが、
+//! こちらはイベントバス経由で他コンポーネント（state node など）が購読できるよう、
+//! 識別子やメタデータをペイロードとして保持する。
+
+use crate::domain::content::metadata::Metadata;
+use crate::domain::content_id::ContentId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// コンテンツが新規作成されたことを表すイベント。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentCreated {
+    pub content_id: ContentId,
+    pub series_id: ContentId,
+    pub metadata: Metadata,
+}
+
+/// コンテンツ本体または名前が更新されたことを表すイベント。
+///
+/// 本体が更新された場合 `content_id` は新しい ID、`series_id` は作成時の ID のまま。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentUpdated {
+    pub content_id: ContentId,
+    pub series_id: ContentId,
+    pub metadata: Metadata,
+}
+
+/// コンテンツが論理削除されたことを表すイベント。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentDeleted {
+    pub content_id: ContentId,
+    pub series_id: ContentId,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// `EventPublisher` ポートに渡すドメインイベントの列挙。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ContentDomainEvent {
+    Created(ContentCreated),
+    Updated(ContentUpdated),
+    Deleted(ContentDeleted),
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// コンテンツの現在の CEK の利用状況。
+///
+/// 鍵ローテーションポリシー（鍵の最大使用期間・CEK あたりの最大暗号化バイト数）の評価に使う。
+/// 記録前に保存されたコンテンツでは `issued_at` が `None` となる。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyUsage {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    issued_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    bytes_encrypted: u64,
+}
+
+impl KeyUsage {
+    /// 新しい CEK で `bytes` バイトを暗号化した直後の利用状況。
+    pub fn issued(issued_at: DateTime<Utc>, bytes: u64) -> Self {
+        Self {
+            issued_at: Some(issued_at),
+            bytes_encrypted: bytes,
+        }
+    }
+
+    /// 同じ CEK でさらに `bytes` バイトを暗号化した後の利用状況を返す。
+    pub fn record(&self, bytes: u64) -> Self {
+        Self {
+            issued_at: self.issued_at,
+            bytes_encrypted: self.bytes_encrypted.saturating_add(bytes),
+        }
+    }
+
+    /// CEK を発行した時刻。記録がない場合は `None`。
+    pub fn issued_at(&self) -> Option<DateTime<Utc>> {
+        self.issued_at
+    }
+
+    /// この CEK でこれまでに暗号化したバイト数（暗号文ベース）。
+    pub fn bytes_encrypted(&self) -> u64 {
+        self.bytes_encrypted
+    }
+}
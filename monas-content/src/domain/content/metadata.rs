@@ -1,3 +1,4 @@
+use crate::domain::content::chunking::ChunkManifest;
 use crate::domain::content::provider::StorageProvider;
 use crate::domain::content_id::ContentId;
 use chrono::{DateTime, Utc};
@@ -12,6 +13,15 @@ pub struct Metadata {
     updated_at: DateTime<Utc>,
     id: ContentId,
     provider: Option<StorageProvider>,
+    /// コンテンツ本体から推定した Content-Type。記録前に保存されたデータでは `None`。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+    /// コンテンツ本体（平文）のバイトサイズ。記録前に保存されたデータでは `None`。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    /// チャンク分割して暗号化したコンテンツの構成。単一の暗号文として保存した場合は `None`。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chunk_manifest: Option<ChunkManifest>,
 }
 
 impl Metadata {
@@ -30,6 +40,9 @@ impl Metadata {
             updated_at: now,
             id,
             provider,
+            content_type: None,
+            size: None,
+            chunk_manifest: None,
         }
     }
 
@@ -43,6 +56,9 @@ impl Metadata {
             updated_at: now,
             id: self.id.clone(),
             provider: self.provider.clone(),
+            content_type: self.content_type.clone(),
+            size: self.size,
+            chunk_manifest: self.chunk_manifest.clone(),
         }
     }
 
@@ -59,6 +75,9 @@ impl Metadata {
             updated_at: now,
             id: new_id,
             provider: self.provider.clone(),
+            content_type: self.content_type.clone(),
+            size: self.size,
+            chunk_manifest: self.chunk_manifest.clone(),
         }
     }
 
@@ -72,6 +91,43 @@ impl Metadata {
             updated_at: now,
             id: self.id.clone(),
             provider: self.provider.clone(),
+            content_type: self.content_type.clone(),
+            size: self.size,
+            chunk_manifest: self.chunk_manifest.clone(),
+        }
+    }
+
+    /// name / path のみを差し替えた Metadata を返す（タイムスタンプは維持する）。
+    ///
+    /// メタデータの暗号化・復号時に使う。内容の変更ではないため `updated_at` は進めない。
+    pub(crate) fn with_name_and_path(&self, name: String, path: String) -> Self {
+        Self {
+            name,
+            path,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            id: self.id.clone(),
+            provider: self.provider.clone(),
+            content_type: self.content_type.clone(),
+            size: self.size,
+            chunk_manifest: self.chunk_manifest.clone(),
+        }
+    }
+
+    /// コンテンツ本体の Content-Type とサイズを記録した Metadata を返す（タイムスタンプは維持する）。
+    pub fn with_content_info(&self, content_type: impl Into<String>, size: u64) -> Self {
+        Self {
+            content_type: Some(content_type.into()),
+            size: Some(size),
+            ..self.clone()
+        }
+    }
+
+    /// 暗号文のチャンク構成を差し替えた Metadata を返す（タイムスタンプは維持する）。
+    pub fn with_chunk_manifest(&self, chunk_manifest: Option<ChunkManifest>) -> Self {
+        Self {
+            chunk_manifest,
+            ..self.clone()
         }
     }
 
@@ -98,6 +154,18 @@ impl Metadata {
     pub fn provider(&self) -> Option<&StorageProvider> {
         self.provider.as_ref()
     }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    pub fn chunk_manifest(&self) -> Option<&ChunkManifest> {
+        self.chunk_manifest.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -107,7 +175,7 @@ mod tests {
 
     #[test]
     fn test_metadata_holds_content_id() {
-        let cid = ContentId::new("cid-1234".to_string());
+        let cid = ContentId::for_test("cid-1234");
         let metadata = Metadata::new("name".to_string(), "/path".to_string(), cid.clone(), None);
 
         assert_eq!(metadata.id(), &cid);
@@ -117,7 +185,7 @@ mod tests {
     fn test_metadata_creation_and_hash_validation() {
         let name = "テストファイル".to_string();
         let path = "/test/path".to_string();
-        let cid = ContentId::new("cid-5678".to_string());
+        let cid = ContentId::for_test("cid-5678");
         let metadata = Metadata::new(name.clone(), path.clone(), cid.clone(), None);
 
         assert_eq!(metadata.name(), name);
@@ -129,7 +197,7 @@ mod tests {
     #[test]
     fn test_metadata_with_provider() {
         use crate::domain::content::provider::StorageProvider;
-        let cid = ContentId::new("cid-provider".to_string());
+        let cid = ContentId::for_test("cid-provider");
         let metadata = Metadata::new(
             "name".to_string(),
             "/path".to_string(),
@@ -143,7 +211,7 @@ mod tests {
     #[test]
     fn test_metadata_provider_preserved_on_touch() {
         use crate::domain::content::provider::StorageProvider;
-        let cid = ContentId::new("cid-touch".to_string());
+        let cid = ContentId::for_test("cid-touch");
         let metadata = Metadata::new(
             "name".to_string(),
             "/path".to_string(),
@@ -154,4 +222,30 @@ mod tests {
         let touched = metadata.touch();
         assert_eq!(touched.provider(), Some(&StorageProvider::Local));
     }
+
+    #[test]
+    fn test_content_info_preserved_across_updates() {
+        let cid = ContentId::for_test("cid-content-info");
+        let metadata = Metadata::new("name".to_string(), "/path".to_string(), cid, None)
+            .with_content_info("text/plain", 11);
+
+        assert_eq!(metadata.content_type(), Some("text/plain"));
+        assert_eq!(metadata.size(), Some(11));
+
+        let renamed = metadata.rename("other".to_string());
+        assert_eq!(renamed.content_type(), Some("text/plain"));
+        assert_eq!(renamed.size(), Some(11));
+    }
+
+    #[test]
+    fn test_content_info_defaults_when_missing_in_stored_json() {
+        let cid = ContentId::for_test("cid-legacy");
+        let metadata = Metadata::new("name".to_string(), "/path".to_string(), cid, None);
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(!json.contains("content_type"));
+
+        let restored: Metadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.content_type(), None);
+        assert_eq!(restored.size(), None);
+    }
 }
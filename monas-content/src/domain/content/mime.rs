@@ -0,0 +1,179 @@
+//! マジックバイトによる Content-Type の推定。
+//!
+//! クライアントがダウンロードせずに表示方法を決められるよう、作成・更新時に
+//! 先頭バイト列から MIME タイプを推定して `Metadata` に記録する。
+//! 判定できないバイナリは `application/octet-stream` とする。
+
+/// 判定できなかった場合の Content-Type。
+pub const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// 推定に使う先頭のバイト数。これより後ろは読まない。
+pub const SNIFF_LEN: usize = 8 * 1024;
+
+/// 先頭バイト列のシグネチャと対応する MIME タイプ。
+const SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (b"\x28\xb5\x2f\xfd", "application/zstd"),
+    (b"OggS", "audio/ogg"),
+    (b"fLaC", "audio/flac"),
+    (b"ID3", "audio/mpeg"),
+    (b"\x1a\x45\xdf\xa3", "video/webm"),
+    (b"\0asm", "application/wasm"),
+];
+
+/// バイト列から Content-Type を推定する。
+///
+/// 先頭の [`SNIFF_LEN`] バイトだけを調べるため、コンテンツの大きさによらず
+/// 一定の時間で終わる。
+pub fn sniff_content_type(bytes: &[u8]) -> &'static str {
+    let truncated = bytes.len() > SNIFF_LEN;
+    let bytes = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if let Some((_, mime)) = SIGNATURES
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+    {
+        return mime;
+    }
+
+    // RIFF コンテナはフォーマット識別子（オフセット 8）で判別する。
+    if bytes.len() >= 12 && bytes.starts_with(b"RIFF") {
+        match &bytes[8..12] {
+            b"WEBP" => return "image/webp",
+            b"WAVE" => return "audio/wav",
+            b"AVI " => return "video/x-msvideo",
+            _ => {}
+        }
+    }
+
+    // ISO BMFF（MP4 など）はオフセット 4 に `ftyp` ボックスを持つ。
+    if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+        return match &bytes[8..12] {
+            b"heic" | b"heix" => "image/heic",
+            b"avif" => "image/avif",
+            b"qt  " => "video/quicktime",
+            _ => "video/mp4",
+        };
+    }
+
+    sniff_text(bytes, truncated).unwrap_or(DEFAULT_CONTENT_TYPE)
+}
+
+/// テキストとして妥当な UTF-8 であれば、JSON / HTML / XML / プレーンテキストを判別する。
+///
+/// `truncated` の場合、`bytes` はコンテンツの先頭部分なので、末尾で途切れた
+/// 文字や JSON は妥当なものとして扱う。
+fn sniff_text(bytes: &[u8], truncated: bool) -> Option<&'static str> {
+    let bytes = bytes.strip_prefix(b"\xef\xbb\xbf").unwrap_or(bytes);
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        // 途切れたマルチバイト文字の手前までを使う
+        Err(e) if truncated && e.error_len().is_none() => {
+            std::str::from_utf8(&bytes[..e.valid_up_to()]).ok()?
+        }
+        Err(_) => return None,
+    };
+    if text.chars().any(|c| c.is_control() && !c.is_whitespace()) {
+        return None;
+    }
+
+    let trimmed = text.trim_start();
+    let lower: String = trimmed.chars().take(16).collect::<String>().to_lowercase();
+    if lower.starts_with("<!doctype html") || lower.starts_with("<html") {
+        Some("text/html")
+    } else if lower.starts_with("<?xml") {
+        Some("application/xml")
+    } else if lower.starts_with("<svg") {
+        Some("image/svg+xml")
+    } else if (trimmed.starts_with('{') || trimmed.starts_with('[')) && is_json(trimmed, truncated)
+    {
+        Some("application/json")
+    } else {
+        Some("text/plain")
+    }
+}
+
+/// `text` が JSON か。`truncated` の場合は、途中で終わっていても妥当なら JSON とみなす。
+fn is_json(text: &str, truncated: bool) -> bool {
+    match serde_json::from_str::<serde::de::IgnoredAny>(text) {
+        Ok(_) => true,
+        Err(e) => truncated && e.is_eof(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_binary_formats_by_magic_bytes() {
+        assert_eq!(
+            sniff_content_type(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            "image/png"
+        );
+        assert_eq!(
+            sniff_content_type(b"\xff\xd8\xff\xe0\0\x10JFIF"),
+            "image/jpeg"
+        );
+        assert_eq!(sniff_content_type(b"%PDF-1.7\n"), "application/pdf");
+        assert_eq!(sniff_content_type(b"RIFF\0\0\0\0WEBPVP8 "), "image/webp");
+        assert_eq!(
+            sniff_content_type(b"\0\0\0\x18ftypmp42\0\0\0\0"),
+            "video/mp4"
+        );
+    }
+
+    #[test]
+    fn detects_text_formats() {
+        assert_eq!(sniff_content_type(b"Hello World"), "text/plain");
+        assert_eq!(sniff_content_type(b"  {\"a\": 1}"), "application/json");
+        assert_eq!(
+            sniff_content_type(b"<!DOCTYPE html><html></html>"),
+            "text/html"
+        );
+        assert_eq!(
+            sniff_content_type(b"<?xml version=\"1.0\"?>"),
+            "application/xml"
+        );
+        // JSON として不正なものはプレーンテキスト扱い
+        assert_eq!(sniff_content_type(b"{not json"), "text/plain");
+    }
+
+    #[test]
+    fn inspects_only_a_bounded_prefix() {
+        let mut json = b"{\"items\": [".to_vec();
+        while json.len() <= SNIFF_LEN {
+            json.extend_from_slice(b"\"\xe3\x81\x82\", ");
+        }
+        json.extend_from_slice(b"\"end\"]}");
+        assert_eq!(sniff_content_type(&json), "application/json");
+
+        // 先頭より後ろのバイナリは判定に影響しない
+        let mut text = vec![b'a'; SNIFF_LEN];
+        text.extend_from_slice(&[0x00, 0xff]);
+        assert_eq!(sniff_content_type(&text), "text/plain");
+
+        // 先頭部分で JSON として不正ならプレーンテキスト
+        let mut broken = b"{not json".to_vec();
+        broken.resize(SNIFF_LEN + 1, b' ');
+        assert_eq!(sniff_content_type(&broken), "text/plain");
+    }
+
+    #[test]
+    fn falls_back_to_octet_stream() {
+        assert_eq!(
+            sniff_content_type(&[0x00, 0x01, 0x02, 0xfe]),
+            DEFAULT_CONTENT_TYPE
+        );
+        assert_eq!(
+            sniff_content_type(&[0xff, 0xfe, 0xfd]),
+            DEFAULT_CONTENT_TYPE
+        );
+    }
+}
@@ -1,10 +1,21 @@
+pub mod chunking;
 #[allow(clippy::module_inception)]
 pub mod content;
 pub mod encryption;
+pub mod events;
+pub mod key_usage;
 pub mod metadata;
+pub mod mime;
 pub mod provider;
 
+pub use chunking::{
+    decrypt_ciphertext, join_chunked_ciphertext, split_chunked_ciphertext, ChunkEntry,
+    ChunkManifest, DEFAULT_CHUNK_SIZE,
+};
 pub use content::{Content, ContentError, ContentEvent, ContentStatus};
 pub use encryption::{ContentEncryption, ContentEncryptionKey, ContentEncryptionKeyGenerator};
+pub use events::{ContentCreated, ContentDeleted, ContentDomainEvent, ContentUpdated};
+pub use key_usage::KeyUsage;
 pub use metadata::Metadata;
+pub use mime::sniff_content_type;
 pub use provider::StorageProvider;
@@ -1,14 +1,189 @@
+/// ContentId の生成に使われたハッシュアルゴリズム。
+///
+/// コード値は multihash のコード表に合わせている。ID の先頭に
+/// `<code><digest length>` を付与することで、将来ハッシュ関数を移行しても
+/// どのアルゴリズムで生成された ID なのかを曖昧さなく判別できる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentIdAlgorithm {
+    Sha256,
+    Blake3,
+}
+
+impl ContentIdAlgorithm {
+    /// multihash におけるアルゴリズムコード。
+    pub const fn code(self) -> u8 {
+        match self {
+            ContentIdAlgorithm::Sha256 => 0x12,
+            ContentIdAlgorithm::Blake3 => 0x1e,
+        }
+    }
+
+    /// ダイジェストのバイト長。
+    pub const fn digest_len(self) -> usize {
+        match self {
+            ContentIdAlgorithm::Sha256 => 32,
+            ContentIdAlgorithm::Blake3 => 32,
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0x12 => Some(ContentIdAlgorithm::Sha256),
+            0x1e => Some(ContentIdAlgorithm::Blake3),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentIdAlgorithm::Sha256 => "sha2-256",
+            ContentIdAlgorithm::Blake3 => "blake3",
+        }
+    }
+    /// 名前からアルゴリズムを得る（大文字小文字は区別しない）。
+    ///
+    /// `as_str` の表記に加え、`sha256` / `sha-256` も SHA-256 として受け付ける。
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "sha2-256" | "sha256" | "sha-256" => Some(ContentIdAlgorithm::Sha256),
+            "blake3" => Some(ContentIdAlgorithm::Blake3),
+            _ => None,
+        }
+    }
+
+    /// `data` のダイジェストをこのアルゴリズムで計算する。
+    pub fn hash(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            ContentIdAlgorithm::Sha256 => {
+                use sha2::{Digest, Sha256};
+                Sha256::digest(data).to_vec()
+            }
+            ContentIdAlgorithm::Blake3 => blake3::hash(data).as_bytes().to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ContentIdError {
+    #[error("content id must not be empty")]
+    Empty,
+    #[error("content id must be hex encoded: {0}")]
+    InvalidHex(String),
+    #[error("content id is too short to carry an algorithm prefix")]
+    MissingPrefix,
+    #[error("unsupported hash algorithm code: 0x{0:02x}")]
+    UnsupportedAlgorithm(u8),
+    #[error("unknown hash algorithm name: {0}")]
+    UnknownAlgorithmName(String),
+    #[error("digest length mismatch for {algorithm}: expected {expected} bytes, got {actual}")]
+    DigestLengthMismatch {
+        algorithm: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// プレフィックス導入前の ID（SHA-256 の 16 進文字列そのもの）の長さ。
+const LEGACY_SHA256_HEX_LEN: usize = 64;
+
 /// Content を一意に識別するための ID。
 ///
-/// 実際の生成ロジックやフォーマット（ハッシュ・CID 等）は別ライブラリ/infra 側で実装し、
-/// ドメイン側では「ContentId という概念」と最小限の操作だけを提供する。
+/// 形式は multihash 風の `hex(<algorithm code> || <digest length> || <digest>)`。
+/// 例えば SHA-256 なら `1220` + 64 文字、BLAKE3 なら `1e20` + 64 文字になる。
+///
+/// プレフィックス導入前に発行された 64 文字の SHA-256 16 進文字列も
+/// 既存データとの互換性のために受け付け、SHA-256 として扱う。
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub struct ContentId(String);
 
 impl ContentId {
-    pub fn new(value: String) -> Self {
-        // 将来的にフォーマット検証などをここに追加してもよい。
-        Self(value)
+    /// 文字列をパースして ContentId を作る。
+    ///
+    /// 16 進表記・アルゴリズムコード・ダイジェスト長を検証し、
+    /// 表記は小文字に正規化する。
+    pub fn new(value: String) -> Result<Self, ContentIdError> {
+        if value.is_empty() {
+            return Err(ContentIdError::Empty);
+        }
+        let bytes = hex::decode(&value).map_err(|e| ContentIdError::InvalidHex(e.to_string()))?;
+
+        if value.len() == LEGACY_SHA256_HEX_LEN {
+            return Ok(Self(value.to_ascii_lowercase()));
+        }
+
+        let [code, len, digest @ ..] = bytes.as_slice() else {
+            return Err(ContentIdError::MissingPrefix);
+        };
+        let algorithm = ContentIdAlgorithm::from_code(*code)
+            .ok_or(ContentIdError::UnsupportedAlgorithm(*code))?;
+        let expected = algorithm.digest_len();
+        if *len as usize != expected || digest.len() != expected {
+            return Err(ContentIdError::DigestLengthMismatch {
+                algorithm: algorithm.as_str(),
+                expected,
+                actual: digest.len(),
+            });
+        }
+
+        Ok(Self(value.to_ascii_lowercase()))
+    }
+
+    /// アルゴリズムとダイジェストから ContentId を組み立てる。
+    pub fn from_digest(
+        algorithm: ContentIdAlgorithm,
+        digest: &[u8],
+    ) -> Result<Self, ContentIdError> {
+        let expected = algorithm.digest_len();
+        if digest.len() != expected {
+            return Err(ContentIdError::DigestLengthMismatch {
+                algorithm: algorithm.as_str(),
+                expected,
+                actual: digest.len(),
+            });
+        }
+        let mut bytes = Vec::with_capacity(2 + digest.len());
+        bytes.push(algorithm.code());
+        bytes.push(expected as u8);
+        bytes.extend_from_slice(digest);
+        Ok(Self(hex::encode(bytes)))
+    }
+
+    /// この ID の生成に使われたハッシュアルゴリズム。
+    ///
+    /// プレフィックスを持たない旧形式の ID は SHA-256 とみなす。
+    pub fn algorithm(&self) -> ContentIdAlgorithm {
+        if self.is_legacy() {
+            return ContentIdAlgorithm::Sha256;
+        }
+        // new / from_digest で検証済みのため、ここでは失敗しない。
+        u8::from_str_radix(&self.0[..2], 16)
+            .ok()
+            .and_then(ContentIdAlgorithm::from_code)
+            .expect("validated content id prefix")
+    }
+
+    /// プレフィックスを除いたダイジェスト本体。
+    pub fn digest(&self) -> Vec<u8> {
+        let hex_digest = if self.is_legacy() {
+            &self.0[..]
+        } else {
+            &self.0[4..]
+        };
+        hex::decode(hex_digest).expect("validated content id digest")
+    }
+
+    /// アルゴリズムプレフィックス導入前の形式かどうか。
+    pub fn is_legacy(&self) -> bool {
+        self.0.len() == LEGACY_SHA256_HEX_LEN
+    }
+
+    /// `raw_content` がこの ID のコンテンツか。
+    ///
+    /// 生成器の設定ではなく ID 自身のアルゴリズムでハッシュを計算し、ダイジェスト同士を
+    /// 比べる。旧形式の ID もプレフィックスの有無に関係なく照合できる。
+    pub fn matches_content(&self, raw_content: &[u8]) -> bool {
+        self.algorithm().hash(raw_content) == self.digest()
     }
 
     pub fn as_str(&self) -> &str {
@@ -18,6 +193,31 @@ impl ContentId {
     pub fn into_inner(self) -> String {
         self.0
     }
+
+    /// テスト用: ラベル文字列の SHA-256 から有効な ContentId を作る。
+    #[cfg(test)]
+    pub(crate) fn for_test(label: &str) -> Self {
+        use sha2::{Digest, Sha256};
+        Self::from_digest(
+            ContentIdAlgorithm::Sha256,
+            &Sha256::digest(label.as_bytes()),
+        )
+        .expect("sha256 digest has the expected length")
+    }
+}
+
+impl TryFrom<String> for ContentId {
+    type Error = ContentIdError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<ContentId> for String {
+    fn from(id: ContentId) -> Self {
+        id.0
+    }
 }
 
 /// ContentId を生成するためのポート。
@@ -34,3 +234,94 @@ pub trait ContentIdGenerator {
     /// - 平文そのものを state-node に渡さない前提。
     fn generate_encrypted(&self, plain_cid: &ContentId, ciphertext: &[u8]) -> ContentId;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: [u8; 32] = [0xab; 32];
+
+    #[test]
+    fn from_digest_prefixes_algorithm_code_and_length() {
+        let sha = ContentId::from_digest(ContentIdAlgorithm::Sha256, &DIGEST).unwrap();
+        assert!(sha.as_str().starts_with("1220"));
+        assert_eq!(sha.algorithm(), ContentIdAlgorithm::Sha256);
+        assert_eq!(sha.digest(), DIGEST.to_vec());
+
+        let blake = ContentId::from_digest(ContentIdAlgorithm::Blake3, &DIGEST).unwrap();
+        assert!(blake.as_str().starts_with("1e20"));
+        assert_eq!(blake.algorithm(), ContentIdAlgorithm::Blake3);
+
+        // 同じダイジェストでもアルゴリズムが違えば別の ID になる。
+        assert_ne!(sha, blake);
+    }
+
+    #[test]
+    fn matches_content_uses_the_id_algorithm() {
+        let data = b"hello";
+        let sha = ContentId::from_digest(
+            ContentIdAlgorithm::Sha256,
+            &ContentIdAlgorithm::Sha256.hash(data),
+        )
+        .unwrap();
+        let blake = ContentId::from_digest(
+            ContentIdAlgorithm::Blake3,
+            &ContentIdAlgorithm::Blake3.hash(data),
+        )
+        .unwrap();
+        let legacy = ContentId::new(hex::encode(ContentIdAlgorithm::Sha256.hash(data))).unwrap();
+
+        for id in [&sha, &blake, &legacy] {
+            assert!(id.matches_content(data));
+            assert!(!id.matches_content(b"hellO"));
+        }
+    }
+
+    #[test]
+    fn new_roundtrips_prefixed_ids() {
+        let id = ContentId::from_digest(ContentIdAlgorithm::Blake3, &DIGEST).unwrap();
+        let parsed = ContentId::new(id.as_str().to_uppercase()).unwrap();
+        assert_eq!(parsed, id);
+    }
+
+    #[test]
+    fn new_accepts_legacy_sha256_hex() {
+        let legacy = hex::encode(DIGEST);
+        let id = ContentId::new(legacy.clone()).unwrap();
+        assert!(id.is_legacy());
+        assert_eq!(id.algorithm(), ContentIdAlgorithm::Sha256);
+        assert_eq!(id.digest(), DIGEST.to_vec());
+        assert_eq!(id.as_str(), legacy);
+    }
+
+    #[test]
+    fn new_rejects_malformed_ids() {
+        assert_eq!(ContentId::new(String::new()), Err(ContentIdError::Empty));
+        assert!(matches!(
+            ContentId::new("not-hex".into()),
+            Err(ContentIdError::InvalidHex(_))
+        ));
+        assert_eq!(
+            ContentId::new("12".into()),
+            Err(ContentIdError::MissingPrefix)
+        );
+        assert_eq!(
+            ContentId::new(format!("9920{}", hex::encode(DIGEST))),
+            Err(ContentIdError::UnsupportedAlgorithm(0x99))
+        );
+        assert!(matches!(
+            ContentId::new(format!("1220{}", hex::encode([0u8; 16]))),
+            Err(ContentIdError::DigestLengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn deserialize_validates_format() {
+        let id = ContentId::from_digest(ContentIdAlgorithm::Sha256, &DIGEST).unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+        let back: ContentId = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, id);
+
+        assert!(serde_json::from_str::<ContentId>("\"cid-1\"").is_err());
+    }
+}
@@ -1,5 +1,7 @@
 pub mod content;
 pub mod content_id;
+pub mod namespace;
 pub mod share;
 
+pub use namespace::Namespace;
 pub use share::KeyId;
@@ -0,0 +1,82 @@
+/// コンテンツを所有するテナント（アカウント）の名前空間。
+///
+/// 名前空間ごとにコンテンツ・CEK の保存先を分け、別の名前空間のコンテンツには
+/// アクセスできないようにする。ストレージのパスやキーにそのまま埋め込むため、
+/// 英小文字・数字・`-`・`_` の 1〜64 文字（先頭は英小文字か数字）に制限する。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Namespace(String);
+
+/// 名前空間の最大長。
+pub const MAX_NAMESPACE_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NamespaceError {
+    #[error("namespace must not be empty")]
+    Empty,
+    #[error("namespace must be at most {MAX_NAMESPACE_LEN} characters")]
+    TooLong,
+    #[error("namespace contains an invalid character: {0:?}")]
+    InvalidCharacter(char),
+}
+
+impl Namespace {
+    pub fn new(value: impl Into<String>) -> Result<Self, NamespaceError> {
+        let value = value.into();
+        if value.is_empty() {
+            return Err(NamespaceError::Empty);
+        }
+        if value.len() > MAX_NAMESPACE_LEN {
+            return Err(NamespaceError::TooLong);
+        }
+        for (i, c) in value.chars().enumerate() {
+            let allowed =
+                c.is_ascii_lowercase() || c.is_ascii_digit() || (i > 0 && (c == '-' || c == '_'));
+            if !allowed {
+                return Err(NamespaceError::InvalidCharacter(c));
+            }
+        }
+        Ok(Self(value))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::str::FromStr for Namespace {
+    type Err = NamespaceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl std::fmt::Display for Namespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_path_safe_names_only() {
+        assert_eq!(Namespace::new("alice").unwrap().as_str(), "alice");
+        assert!(Namespace::new("team-42_dev").is_ok());
+
+        assert_eq!(Namespace::new(""), Err(NamespaceError::Empty));
+        assert_eq!(
+            Namespace::new("a".repeat(MAX_NAMESPACE_LEN + 1)),
+            Err(NamespaceError::TooLong)
+        );
+        assert_eq!(
+            Namespace::new("-alice"),
+            Err(NamespaceError::InvalidCharacter('-'))
+        );
+        for invalid in ["Alice", "a/b", "..", "a:b", "a b"] {
+            assert!(Namespace::new(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! 組織向けキーエスクローの監査イベント。
+//!
+//! エスクローモードでは、新しく発行された CEK を組織のエスクロー公開鍵にもラップして保存する。
+//! 鍵の預託・復旧はいずれも強い権限を伴う操作のため、成否を問わず監査イベントとして記録する。
+
+use crate::domain::content_id::ContentId;
+use crate::domain::KeyId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// キーエスクローに関する監査イベント。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EscrowAuditEvent {
+    /// CEK をエスクロー公開鍵にラップし、KeyEnvelope として保存した。
+    CekEscrowed {
+        content_id: ContentId,
+        escrow_key_id: KeyId,
+        at: DateTime<Utc>,
+    },
+    /// CEK のエスクローに失敗した（CEK の保存自体も中止される）。
+    EscrowFailed {
+        content_id: ContentId,
+        escrow_key_id: KeyId,
+        reason: String,
+        at: DateTime<Utc>,
+    },
+    /// エスクロー秘密鍵を用いて CEK を復旧した。
+    CekRecovered {
+        content_id: ContentId,
+        escrow_key_id: KeyId,
+        at: DateTime<Utc>,
+    },
+    /// エスクロー秘密鍵を用いた CEK の復旧に失敗した。
+    RecoveryFailed {
+        content_id: ContentId,
+        escrow_key_id: KeyId,
+        reason: String,
+        at: DateTime<Utc>,
+    },
+}
+
+impl EscrowAuditEvent {
+    pub fn content_id(&self) -> &ContentId {
+        match self {
+            EscrowAuditEvent::CekEscrowed { content_id, .. }
+            | EscrowAuditEvent::EscrowFailed { content_id, .. }
+            | EscrowAuditEvent::CekRecovered { content_id, .. }
+            | EscrowAuditEvent::RecoveryFailed { content_id, .. } => content_id,
+        }
+    }
+
+    pub fn escrow_key_id(&self) -> &KeyId {
+        match self {
+            EscrowAuditEvent::CekEscrowed { escrow_key_id, .. }
+            | EscrowAuditEvent::EscrowFailed { escrow_key_id, .. }
+            | EscrowAuditEvent::CekRecovered { escrow_key_id, .. }
+            | EscrowAuditEvent::RecoveryFailed { escrow_key_id, .. } => escrow_key_id,
+        }
+    }
+}
@@ -1,11 +1,12 @@
 use crate::domain::content_id::ContentId;
 use crate::domain::KeyId;
+use serde::{Deserialize, Serialize};
 
 /// 1 人分の CEK ラップ情報。
 ///
 /// - `key_id` ごとに HPKE でラップされた CEK と、その際に生成された `enc` を保持する。
 /// - 実際の HPKE アルゴリズムやパラメータは infra 層に委譲し、ここでは「結果としてのバイト列」のみを扱う。
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WrappedRecipientKey {
     key_id: KeyId,
     enc: Vec<u8>,
@@ -37,7 +38,7 @@ impl WrappedRecipientKey {
 /// CEK をどの方式でラップしたかを表すアルゴリズム。
 ///
 /// - 今フェーズでは HPKE 1 種類のみを想定するが、将来的な拡張に備えて enum として定義しておく。
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum KeyWrapAlgorithm {
     /// HPKE による CEK ラップ。
     HpkeV1,
@@ -48,7 +49,7 @@ pub enum KeyWrapAlgorithm {
 /// - ある時点の `content_id` と `sender_key_id`、および 1 人分の CEK ラップ情報と
 ///   コンテンツ本体の暗号データを束ねる。
 /// - ローカル環境など、単一のパッケージだけで復号を完結させたいユースケースを想定。
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct KeyEnvelope {
     content_id: ContentId,
     key_wrap_algorithm: KeyWrapAlgorithm,
@@ -101,7 +102,7 @@ mod tests {
     use crate::domain::content_id::ContentId;
 
     fn cid() -> ContentId {
-        ContentId::new("test-content-id".into())
+        ContentId::for_test("test-content-id")
     }
 
     fn key_id(bytes: &[u8]) -> KeyId {
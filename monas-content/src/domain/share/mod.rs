@@ -1,10 +1,12 @@
 pub mod encryption;
+pub mod escrow;
 pub mod key_envelope;
 pub mod key_id;
 #[allow(clippy::module_inception)]
 pub mod share;
 
 pub use encryption::{KeyWrapping, KeyWrappingError};
+pub use escrow::EscrowAuditEvent;
 pub use key_envelope::{KeyEnvelope, WrappedRecipientKey};
 pub use key_id::KeyId;
 pub use share::{Permission, Share, ShareError, ShareEvent, ShareRecipient};
@@ -98,10 +98,40 @@ pub enum ShareEvent {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Share {
     content_id: ContentId,
-    /// key = KeyId
+    /// key = KeyId（JSON のマップのキーには使えないため、受信者の配列としてシリアライズする）
+    #[serde(with = "recipients_as_list")]
     recipients: HashMap<KeyId, ShareRecipient>,
 }
 
+/// 受信者を KeyId 順の配列としてシリアライズする。受信者は自身の KeyId を持つ。
+mod recipients_as_list {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::ShareRecipient;
+    use crate::domain::KeyId;
+
+    pub fn serialize<S: Serializer>(
+        recipients: &HashMap<KeyId, ShareRecipient>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut list: Vec<&ShareRecipient> = recipients.values().collect();
+        list.sort_by(|a, b| a.key_id.as_bytes().cmp(b.key_id.as_bytes()));
+        serializer.collect_seq(list)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<KeyId, ShareRecipient>, D::Error> {
+        let list = Vec::<ShareRecipient>::deserialize(deserializer)?;
+        Ok(list
+            .into_iter()
+            .map(|recipient| (recipient.key_id.clone(), recipient))
+            .collect())
+    }
+}
+
 impl Share {
     /// 指定された content_id に対応する空の Share を生成する。
     ///
@@ -241,7 +271,7 @@ mod tests {
     use super::*;
 
     fn cid() -> ContentId {
-        ContentId::new("test-content-id".into())
+        ContentId::for_test("test-content-id")
     }
 
     fn key_id(bytes: &[u8]) -> KeyId {
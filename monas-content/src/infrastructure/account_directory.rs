@@ -0,0 +1,330 @@
+//! monas-account に問い合わせて受信者のアカウント識別子から公開鍵を解決する
+//! `PublicKeyDirectory`。
+//!
+//! - KeyId による登録・検索・削除は内側のディレクトリへそのまま委譲する。
+//! - アカウント識別子（did:key）は `GET {base_url}/accounts/{account_id}/encryption-key`
+//!   で、アカウントが共有を受け取る HPKE の公開鍵に解決する。404 は「アカウントが
+//!   存在しない」（`None`）として扱う。
+//! - 応答の鍵はアカウント鍵で署名されている。did:key の公開鍵で署名を検証し、
+//!   アカウントの持ち主が公開した鍵であることを確かめてから使う。
+
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use k256::ecdsa::signature::DigestVerifier;
+use p256::ecdsa::signature::Verifier;
+use serde::Deserialize;
+use sha3::{Digest, Keccak256};
+
+use crate::application_service::share_service::{PublicKeyDirectory, PublicKeyDirectoryError};
+use crate::domain::share::KeyId;
+
+/// 共有の CEK のラップに使う KEM（`HpkeV1KeyWrapping` と同じ）。
+const SHARE_KEM: &str = "DHKEM(P-256, HKDF-SHA256)";
+
+/// multicodec の公開鍵種別（unsigned varint エンコード済み）。
+const SECP256K1_PUB_MULTICODEC: [u8; 2] = [0xe7, 0x01];
+const P256_PUB_MULTICODEC: [u8; 2] = [0x80, 0x24];
+
+/// 問い合わせの既定タイムアウト。
+const DEFAULT_ACCOUNT_LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// monas-account の接続設定。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDirectoryConfig {
+    /// monas-account のベース URL（例: `http://127.0.0.1:4002`）。
+    pub base_url: String,
+    pub timeout: Duration,
+}
+
+impl AccountDirectoryConfig {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            timeout: DEFAULT_ACCOUNT_LOOKUP_TIMEOUT,
+        }
+    }
+
+    /// 環境変数 `MONAS_ACCOUNT_SERVICE_URL` から設定を読み込む。
+    /// 未設定の場合は `None`（アカウント識別子による共有は行えない）。
+    pub fn from_env() -> Option<Self> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let url = lookup("MONAS_ACCOUNT_SERVICE_URL").filter(|v| !v.trim().is_empty())?;
+        Some(Self::new(url.trim()))
+    }
+}
+
+/// `GET /accounts/{account_id}/encryption-key` の応答。
+#[derive(Deserialize)]
+struct AccountEncryptionKeyResponse {
+    account_id: String,
+    kem: String,
+    public_key_base64: String,
+    signature_base64: String,
+}
+
+/// monas-account がアカウント鍵で署名する暗号化鍵の文。
+fn encryption_key_statement(account_id: &str, kem: &str, public_key: &[u8]) -> Vec<u8> {
+    format!(
+        "monas-account-encryption-key:v1\naccount:{account_id}\nkem:{kem}\nkey:{}",
+        hex::encode(public_key)
+    )
+    .into_bytes()
+}
+
+/// `signature` が `account_id`（did:key）の鍵による `message` への署名かを確かめる。
+///
+/// 署名は monas-account と同じく、P-256 は SHA-256、secp256k1 は Keccak-256 のダイジェストに対する
+/// 64 バイトの `r || s`。
+fn verify_account_signature(account_id: &str, message: &[u8], signature: &[u8]) -> bool {
+    let Some(bytes) = account_id
+        .strip_prefix("did:key:z")
+        .and_then(|encoded| bs58::decode(encoded).into_vec().ok())
+    else {
+        return false;
+    };
+
+    if let Some(key) = bytes.strip_prefix(&P256_PUB_MULTICODEC) {
+        let (Ok(key), Ok(signature)) = (
+            p256::ecdsa::VerifyingKey::from_sec1_bytes(key),
+            p256::ecdsa::Signature::from_slice(signature),
+        ) else {
+            return false;
+        };
+        key.verify(message, &signature).is_ok()
+    } else if let Some(key) = bytes.strip_prefix(&SECP256K1_PUB_MULTICODEC) {
+        let (Ok(key), Ok(signature)) = (
+            k256::ecdsa::VerifyingKey::from_sec1_bytes(key),
+            k256::ecdsa::Signature::from_slice(signature),
+        ) else {
+            return false;
+        };
+        key.verify_digest(Keccak256::new_with_prefix(message), &signature)
+            .is_ok()
+    } else {
+        false
+    }
+}
+
+/// アカウント識別子の解決を monas-account に問い合わせる `PublicKeyDirectory`。
+#[derive(Clone)]
+pub struct HttpAccountPublicKeyDirectory<D> {
+    inner: D,
+    agent: ureq::Agent,
+    config: AccountDirectoryConfig,
+}
+
+impl<D> HttpAccountPublicKeyDirectory<D> {
+    pub fn new(inner: D, config: AccountDirectoryConfig) -> Self {
+        let agent = ureq::Agent::new_with_config(
+            ureq::Agent::config_builder()
+                .timeout_global(Some(config.timeout))
+                .build(),
+        );
+        Self {
+            inner,
+            agent,
+            config,
+        }
+    }
+}
+
+impl<D: PublicKeyDirectory> PublicKeyDirectory for HttpAccountPublicKeyDirectory<D> {
+    fn compute_key_id(&self, public_key: &[u8]) -> KeyId {
+        self.inner.compute_key_id(public_key)
+    }
+
+    fn register_public_key(&self, public_key: &[u8]) -> Result<KeyId, PublicKeyDirectoryError> {
+        self.inner.register_public_key(public_key)
+    }
+
+    fn find_public_key(&self, key_id: &KeyId) -> Result<Option<Vec<u8>>, PublicKeyDirectoryError> {
+        self.inner.find_public_key(key_id)
+    }
+
+    fn delete_public_key(&self, key_id: &KeyId) -> Result<(), PublicKeyDirectoryError> {
+        self.inner.delete_public_key(key_id)
+    }
+
+    fn find_public_key_by_account(
+        &self,
+        account_id: &str,
+    ) -> Result<Option<Vec<u8>>, PublicKeyDirectoryError> {
+        // did:key に含まれる文字は英数字と ':' のみなので、パスにそのまま埋め込める
+        if !account_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == ':')
+        {
+            return Ok(None);
+        }
+
+        let url = format!(
+            "{}/accounts/{}/encryption-key",
+            self.config.base_url, account_id
+        );
+        let mut response = match self.agent.get(&url).call() {
+            Ok(response) => response,
+            Err(ureq::Error::StatusCode(404)) => return Ok(None),
+            Err(e) => return Err(PublicKeyDirectoryError::Lookup(e.to_string())),
+        };
+
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| PublicKeyDirectoryError::Lookup(e.to_string()))?;
+        let parsed: AccountEncryptionKeyResponse = serde_json::from_str(&body)
+            .map_err(|e| PublicKeyDirectoryError::Lookup(format!("invalid response: {e}")))?;
+        if parsed.account_id != account_id || parsed.kem != SHARE_KEM {
+            return Err(PublicKeyDirectoryError::Lookup(format!(
+                "unexpected encryption key for {account_id}: {}",
+                parsed.kem
+            )));
+        }
+        let public_key = BASE64_STANDARD
+            .decode(parsed.public_key_base64)
+            .map_err(|e| PublicKeyDirectoryError::Lookup(format!("invalid public key: {e}")))?;
+        let signature = BASE64_STANDARD
+            .decode(parsed.signature_base64)
+            .map_err(|e| PublicKeyDirectoryError::Lookup(format!("invalid signature: {e}")))?;
+
+        let statement = encryption_key_statement(account_id, &parsed.kem, &public_key);
+        if !verify_account_signature(account_id, &statement, &signature) {
+            return Err(PublicKeyDirectoryError::Lookup(format!(
+                "encryption key for {account_id} is not signed by the account"
+            )));
+        }
+        Ok(Some(public_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::public_key_directory::InMemoryPublicKeyDirectory;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// 1 リクエストだけ応答する HTTP サーバーを立て、リクエスト行を返す。
+    fn serve_once(status: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+            request_line.trim().to_string()
+        });
+        (base_url, server)
+    }
+
+    /// P-256 のアカウント鍵を作り、その did:key と、暗号化鍵の署名付き応答を返す。
+    fn signed_encryption_key(public_key: &[u8]) -> (String, serde_json::Value) {
+        use p256::ecdsa::signature::Signer;
+
+        let account_key = p256::ecdsa::SigningKey::random(&mut rand_core::OsRng);
+        let mut did_bytes = P256_PUB_MULTICODEC.to_vec();
+        did_bytes.extend_from_slice(
+            account_key
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes(),
+        );
+        let account_id = format!("did:key:z{}", bs58::encode(did_bytes).into_string());
+
+        let signature: p256::ecdsa::Signature = account_key.sign(&encryption_key_statement(
+            &account_id,
+            SHARE_KEM,
+            public_key,
+        ));
+        let body = serde_json::json!({
+            "account_id": account_id,
+            "kem": SHARE_KEM,
+            "public_key_base64": BASE64_STANDARD.encode(public_key),
+            "signature_base64": BASE64_STANDARD.encode(signature.to_bytes()),
+        });
+        (account_id, body)
+    }
+
+    #[test]
+    fn resolves_signed_encryption_key_from_account_service() {
+        let (account_id, body) = signed_encryption_key(&[4, 1, 2, 3]);
+        let (base_url, server) = serve_once("200 OK", &body.to_string());
+        let directory = HttpAccountPublicKeyDirectory::new(
+            InMemoryPublicKeyDirectory::default(),
+            AccountDirectoryConfig::new(format!("{base_url}/")),
+        );
+
+        let found = directory.find_public_key_by_account(&account_id).unwrap();
+        assert_eq!(found, Some(vec![4, 1, 2, 3]));
+        assert_eq!(
+            server.join().unwrap(),
+            format!("GET /accounts/{account_id}/encryption-key HTTP/1.1")
+        );
+    }
+
+    #[test]
+    fn rejects_encryption_keys_not_signed_by_the_account() {
+        let (account_id, mut body) = signed_encryption_key(&[4, 1, 2, 3]);
+        // 署名したものとは別の鍵に差し替えられている
+        body["public_key_base64"] = BASE64_STANDARD.encode([4, 9, 9, 9]).into();
+        let (base_url, server) = serve_once("200 OK", &body.to_string());
+        let directory = HttpAccountPublicKeyDirectory::new(
+            InMemoryPublicKeyDirectory::default(),
+            AccountDirectoryConfig::new(base_url),
+        );
+
+        assert!(matches!(
+            directory.find_public_key_by_account(&account_id),
+            Err(PublicKeyDirectoryError::Lookup(_))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn unknown_accounts_resolve_to_none() {
+        let (base_url, server) = serve_once("404 Not Found", "account not found");
+        let directory = HttpAccountPublicKeyDirectory::new(
+            InMemoryPublicKeyDirectory::default(),
+            AccountDirectoryConfig::new(base_url),
+        );
+
+        assert_eq!(
+            directory
+                .find_public_key_by_account("did:key:zDnB")
+                .unwrap(),
+            None
+        );
+        server.join().unwrap();
+
+        // パスに埋め込めない識別子は問い合わせずに None
+        assert_eq!(
+            directory.find_public_key_by_account("../admin").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn config_from_lookup_requires_url() {
+        assert!(AccountDirectoryConfig::from_lookup(|_| None).is_none());
+        let config =
+            AccountDirectoryConfig::from_lookup(|_| Some(" http://account:4002/ ".into())).unwrap();
+        assert_eq!(config.base_url, "http://account:4002");
+    }
+}
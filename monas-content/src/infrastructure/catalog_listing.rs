@@ -0,0 +1,261 @@
+//! コンテンツ一覧（`ContentCatalog`）からリポジトリの内容を列挙するデコレーター。
+
+use crate::application_service::content_service::{
+    ContentListFilter, ContentRepository, ContentRepositoryError, ListableContentRepository,
+    MultiStorageContentRepository,
+};
+use crate::application_service::rotation_service::ContentCatalog;
+use crate::domain::content::Content;
+use crate::domain::content_id::ContentId;
+
+/// 保存先を列挙できないリポジトリ（`MultiStorageRepository` など）を、カタログに記録した
+/// 系列ごとの最新版で列挙できるようにするデコレーター。
+///
+/// 読み書きはそのまま内側のリポジトリに委ねる。一覧には、指定したプロバイダー
+/// （省略時はデフォルト）に保存されているコンテンツだけを含める。
+#[derive(Clone)]
+pub struct CatalogListingRepository<R, C> {
+    inner: R,
+    catalog: C,
+}
+
+impl<R, C> CatalogListingRepository<R, C> {
+    pub fn new(inner: R, catalog: C) -> Self {
+        Self { inner, catalog }
+    }
+}
+
+impl<R, C: ContentCatalog> CatalogListingRepository<R, C> {
+    /// カタログの ContentId を `find` で読み込み、`filter` に一致するものを返す。
+    fn list_with(
+        &self,
+        filter: ContentListFilter,
+        find: impl Fn(&ContentId) -> Result<Option<Content>, ContentRepositoryError>,
+    ) -> Result<Vec<Content>, ContentRepositoryError> {
+        let content_ids = self
+            .catalog
+            .list()
+            .map_err(|e| ContentRepositoryError::Storage(e.to_string()))?;
+        let mut contents = Vec::with_capacity(content_ids.len());
+        for content_id in &content_ids {
+            // 別のプロバイダーに保存されたコンテンツは見つからないため含めない
+            if let Some(content) = find(content_id)? {
+                if filter.includes(content.content_status()) {
+                    contents.push(content);
+                }
+            }
+        }
+        Ok(contents)
+    }
+}
+
+impl<R: ContentRepository, C> ContentRepository for CatalogListingRepository<R, C> {
+    fn save(
+        &self,
+        content_id: &ContentId,
+        content: &Content,
+    ) -> Result<(), ContentRepositoryError> {
+        self.inner.save(content_id, content)
+    }
+
+    fn find_by_id(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Option<Content>, ContentRepositoryError> {
+        self.inner.find_by_id(content_id)
+    }
+
+    fn flush(&self) -> Result<(), ContentRepositoryError> {
+        self.inner.flush()
+    }
+}
+
+impl<R: MultiStorageContentRepository, C> MultiStorageContentRepository
+    for CatalogListingRepository<R, C>
+{
+    fn save_to(
+        &self,
+        provider: &str,
+        content_id: &ContentId,
+        content: &Content,
+    ) -> Result<(), ContentRepositoryError> {
+        self.inner.save_to(provider, content_id, content)
+    }
+
+    fn find_from(
+        &self,
+        provider: &str,
+        content_id: &ContentId,
+    ) -> Result<Option<Content>, ContentRepositoryError> {
+        self.inner.find_from(provider, content_id)
+    }
+
+    fn connected_providers(&self) -> Result<Vec<String>, ContentRepositoryError> {
+        self.inner.connected_providers()
+    }
+
+    fn default_provider(&self) -> Result<String, ContentRepositoryError> {
+        self.inner.default_provider()
+    }
+
+    fn connect_provider(
+        &self,
+        provider: &str,
+        access_token: String,
+    ) -> Result<(), ContentRepositoryError> {
+        self.inner.connect_provider(provider, access_token)
+    }
+
+    fn disconnect_provider(&self, provider: &str) -> Result<(), ContentRepositoryError> {
+        self.inner.disconnect_provider(provider)
+    }
+}
+
+impl<R: MultiStorageContentRepository, C: ContentCatalog> ListableContentRepository
+    for CatalogListingRepository<R, C>
+{
+    fn list(&self, filter: ContentListFilter) -> Result<Vec<Content>, ContentRepositoryError> {
+        self.list_with(filter, |content_id| self.inner.find_by_id(content_id))
+    }
+
+    fn list_from(
+        &self,
+        provider: &str,
+        filter: ContentListFilter,
+    ) -> Result<Vec<Content>, ContentRepositoryError> {
+        self.list_with(filter, |content_id| {
+            self.inner.find_from(provider, content_id)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::content::encryption::ContentEncryptionKey;
+    use crate::domain::content::ContentStatus;
+    use crate::infrastructure::content_id::Sha256ContentIdGenerator;
+    use crate::infrastructure::encryption::Aes256CtrContentEncryption;
+    use crate::infrastructure::rotation::InMemoryContentCatalog;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// プロバイダーごとにコンテンツを保持するテスト用リポジトリ。
+    #[derive(Clone, Default)]
+    struct ProviderRepository {
+        inner: Arc<Mutex<HashMap<(String, ContentId), Content>>>,
+    }
+
+    impl ContentRepository for ProviderRepository {
+        fn save(
+            &self,
+            content_id: &ContentId,
+            content: &Content,
+        ) -> Result<(), ContentRepositoryError> {
+            self.save_to("local", content_id, content)
+        }
+
+        fn find_by_id(
+            &self,
+            content_id: &ContentId,
+        ) -> Result<Option<Content>, ContentRepositoryError> {
+            self.find_from("local", content_id)
+        }
+    }
+
+    impl MultiStorageContentRepository for ProviderRepository {
+        fn save_to(
+            &self,
+            provider: &str,
+            content_id: &ContentId,
+            content: &Content,
+        ) -> Result<(), ContentRepositoryError> {
+            self.inner
+                .lock()
+                .unwrap()
+                .insert((provider.to_string(), content_id.clone()), content.clone());
+            Ok(())
+        }
+
+        fn find_from(
+            &self,
+            provider: &str,
+            content_id: &ContentId,
+        ) -> Result<Option<Content>, ContentRepositoryError> {
+            let key = (provider.to_string(), content_id.clone());
+            Ok(self.inner.lock().unwrap().get(&key).cloned())
+        }
+
+        fn connected_providers(&self) -> Result<Vec<String>, ContentRepositoryError> {
+            Ok(vec!["local".to_string(), "google-drive".to_string()])
+        }
+
+        fn default_provider(&self) -> Result<String, ContentRepositoryError> {
+            Ok("local".to_string())
+        }
+
+        fn connect_provider(
+            &self,
+            _provider: &str,
+            _access_token: String,
+        ) -> Result<(), ContentRepositoryError> {
+            Ok(())
+        }
+
+        fn disconnect_provider(&self, _provider: &str) -> Result<(), ContentRepositoryError> {
+            Ok(())
+        }
+    }
+
+    fn content(raw: &[u8]) -> Content {
+        Content::create(
+            "a.txt".into(),
+            raw.to_vec(),
+            "docs/a.txt".into(),
+            None,
+            &Sha256ContentIdGenerator,
+            &ContentEncryptionKey(vec![7u8; 32]),
+            &Aes256CtrContentEncryption,
+        )
+        .unwrap()
+        .0
+    }
+
+    #[test]
+    fn lists_cataloged_contents_stored_in_the_provider() {
+        let catalog = InMemoryContentCatalog::default();
+        let repo = CatalogListingRepository::new(ProviderRepository::default(), catalog.clone());
+
+        let local = content(b"local");
+        let archived = content(b"archived").archive().unwrap().0;
+        let remote = content(b"remote");
+        repo.save(local.raw_id(), &local).unwrap();
+        repo.save(archived.raw_id(), &archived).unwrap();
+        repo.save_to("google-drive", remote.raw_id(), &remote)
+            .unwrap();
+        for content in [&local, &archived, &remote] {
+            catalog.insert(content.series_id().clone(), content.raw_id().clone());
+        }
+
+        let listed = repo.list(ContentListFilter::default()).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].raw_id(), local.raw_id());
+
+        let with_archived = repo
+            .list(ContentListFilter {
+                include_archived: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(with_archived.len(), 2);
+        assert!(with_archived
+            .iter()
+            .any(|c| *c.content_status() == ContentStatus::Archived));
+
+        let remote_listed = repo
+            .list_from("google-drive", ContentListFilter::default())
+            .unwrap();
+        assert_eq!(remote_listed.len(), 1);
+        assert_eq!(remote_listed[0].raw_id(), remote.raw_id());
+    }
+}
@@ -0,0 +1,324 @@
+//! アルゴリズム識別子で `ContentEncryption` 実装を切り替える暗号スイート。
+//!
+//! 暗号文の先頭に使用したアルゴリズムの識別子を書き込み、復号時はそれを見て
+//! 実装を選ぶ。新しい暗号化には設定で選んだアルゴリズムを使いつつ、別の
+//! アルゴリズムで暗号化された既存データもそのまま復号できる。
+//!
+//! ## 暗号文の形式
+//!
+//! ```text
+//! MAGIC (4 bytes: "MNC" 0x01) || id_len (1 byte) || id (UTF-8) || 実装の暗号文
+//! ```
+//!
+//! ヘッダを持たない暗号文（スイート導入前に `Aes256CtrContentEncryption` で
+//! 暗号化されたデータ）はレガシー形式として AES-256-CTR で復号する。
+//! レガシー形式の IV がたまたま MAGIC と登録済みの識別子に一致する確率は
+//! 無視できるほど小さいが、一致しても未登録の識別子であればレガシー形式として扱う。
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use bytes::{Buf, Bytes};
+
+use crate::domain::content::encryption::{ContentEncryption, ContentEncryptionKey};
+use crate::domain::content::ContentError;
+use crate::infrastructure::encryption::{Aes256CtrContentEncryption, Aes256GcmContentEncryption};
+
+/// `Aes256CtrContentEncryption` の識別子。
+pub const AES_256_CTR: &str = "aes-256-ctr";
+
+/// `Aes256GcmContentEncryption` の識別子。
+pub const AES_256_GCM: &str = "aes-256-gcm";
+
+/// スイートのヘッダの先頭に置くマジックバイト（末尾はヘッダのバージョン）。
+const MAGIC: &[u8; 4] = b"MNC\x01";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CipherSuiteError {
+    #[error("unknown cipher algorithm: {0}")]
+    UnknownAlgorithm(String),
+    #[error("invalid cipher algorithm id (must be 1-255 bytes): {0:?}")]
+    InvalidAlgorithmId(String),
+}
+
+/// 暗号文をスイートのヘッダの識別子と、実装の暗号文に分ける。
+///
+/// ヘッダの形式だけを見るため、識別子が登録済みかどうかは確認しない。
+/// ヘッダを持たない（レガシー形式の）暗号文では `None`。
+pub fn split_header(data: &[u8]) -> Option<(&str, &[u8])> {
+    let rest = data.strip_prefix(MAGIC.as_slice())?;
+    let (&id_len, rest) = rest.split_first()?;
+    let (id, body) = (rest.get(..id_len as usize)?, &rest[id_len as usize..]);
+    Some((std::str::from_utf8(id).ok()?, body))
+}
+
+type DynContentEncryption = Arc<dyn ContentEncryption + Send + Sync>;
+
+/// アルゴリズム識別子をキーに `ContentEncryption` 実装を登録するレジストリ。
+///
+/// - 暗号化: `selected` のアルゴリズムで暗号化し、識別子のヘッダを付ける。
+/// - 復号: ヘッダの識別子に対応する実装で復号する。ヘッダがなければ `legacy` を使う。
+///
+/// 登録した実装は `Arc` で保持するため、`Clone` で安価に共有できる。
+#[derive(Clone)]
+pub struct CipherSuite {
+    algorithms: BTreeMap<String, DynContentEncryption>,
+    selected: String,
+    legacy: String,
+}
+
+impl Default for CipherSuite {
+    /// 組み込みのアルゴリズムを登録し、AES-256-CTR を選んだスイート。
+    fn default() -> Self {
+        Self::builtin(AES_256_CTR).expect("aes-256-ctr is a builtin algorithm")
+    }
+}
+
+impl CipherSuite {
+    /// 組み込みのアルゴリズム（`aes-256-ctr` / `aes-256-gcm`）を登録し、
+    /// `selected` を新しい暗号化に使うスイートを作る。レガシー形式は AES-256-CTR。
+    pub fn builtin(selected: &str) -> Result<Self, CipherSuiteError> {
+        let suite = Self {
+            algorithms: BTreeMap::new(),
+            selected: AES_256_CTR.to_string(),
+            legacy: AES_256_CTR.to_string(),
+        }
+        .with_algorithm(AES_256_CTR, Aes256CtrContentEncryption)?
+        .with_algorithm(AES_256_GCM, Aes256GcmContentEncryption)?;
+        suite.select(selected)
+    }
+
+    /// 環境変数 `MONAS_CONTENT_CIPHER`（`aes-256-ctr` / `aes-256-gcm`）から構築する。
+    ///
+    /// 未設定の場合は AES-256-CTR。不明な名前の場合はエラーを返す。
+    pub fn from_env() -> Result<Self, CipherSuiteError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, CipherSuiteError> {
+        match lookup("MONAS_CONTENT_CIPHER").filter(|v| !v.trim().is_empty()) {
+            Some(name) => Self::builtin(&name.trim().to_ascii_lowercase()),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// 実装を登録する。同じ識別子が登録済みの場合は置き換える。
+    pub fn with_algorithm<E>(mut self, id: &str, encryption: E) -> Result<Self, CipherSuiteError>
+    where
+        E: ContentEncryption + Send + Sync + 'static,
+    {
+        if id.is_empty() || id.len() > u8::MAX as usize {
+            return Err(CipherSuiteError::InvalidAlgorithmId(id.to_string()));
+        }
+        self.algorithms.insert(id.to_string(), Arc::new(encryption));
+        Ok(self)
+    }
+
+    /// 新しい暗号化に使うアルゴリズムを選ぶ。
+    pub fn select(mut self, id: &str) -> Result<Self, CipherSuiteError> {
+        self.ensure_registered(id)?;
+        self.selected = id.to_string();
+        Ok(self)
+    }
+
+    /// ヘッダを持たない暗号文の復号に使うアルゴリズムを指定する。
+    pub fn with_legacy(mut self, id: &str) -> Result<Self, CipherSuiteError> {
+        self.ensure_registered(id)?;
+        self.legacy = id.to_string();
+        Ok(self)
+    }
+
+    /// 新しい暗号化に使うアルゴリズムの識別子。
+    pub fn selected(&self) -> &str {
+        &self.selected
+    }
+
+    /// 登録済みのアルゴリズムの識別子（昇順）。
+    pub fn algorithms(&self) -> impl Iterator<Item = &str> {
+        self.algorithms.keys().map(String::as_str)
+    }
+
+    /// 暗号文を復号するアルゴリズムの識別子。ヘッダがなければレガシー形式の識別子。
+    pub fn algorithm_of(&self, ciphertext: &[u8]) -> &str {
+        self.parse_header(ciphertext)
+            .map_or(self.legacy.as_str(), |(id, _)| id)
+    }
+
+    fn ensure_registered(&self, id: &str) -> Result<(), CipherSuiteError> {
+        if self.algorithms.contains_key(id) {
+            Ok(())
+        } else {
+            Err(CipherSuiteError::UnknownAlgorithm(id.to_string()))
+        }
+    }
+
+    /// ヘッダを読み取り、登録済みの識別子とヘッダの長さを返す。
+    fn parse_header<'a>(&'a self, data: &[u8]) -> Option<(&'a str, usize)> {
+        let (id, body) = split_header(data)?;
+        let (id, _) = self.algorithms.get_key_value(id)?;
+        Some((id.as_str(), data.len() - body.len()))
+    }
+
+    /// 暗号文に対応する実装と、実装に渡す部分の開始位置を返す。
+    fn resolve(&self, data: &[u8]) -> (&(dyn ContentEncryption + Send + Sync), usize) {
+        let (id, offset) = self.parse_header(data).unwrap_or((self.legacy.as_str(), 0));
+        (self.algorithms[id].as_ref(), offset)
+    }
+}
+
+impl ContentEncryption for CipherSuite {
+    fn encrypt(
+        &self,
+        key: &ContentEncryptionKey,
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, ContentError> {
+        let ciphertext = self.algorithms[&self.selected].encrypt(key, plaintext)?;
+        let mut result =
+            Vec::with_capacity(MAGIC.len() + 1 + self.selected.len() + ciphertext.len());
+        result.extend_from_slice(MAGIC);
+        result.push(self.selected.len() as u8);
+        result.extend_from_slice(self.selected.as_bytes());
+        result.extend_from_slice(&ciphertext);
+        Ok(result)
+    }
+
+    fn decrypt(&self, key: &ContentEncryptionKey, data: &[u8]) -> Result<Vec<u8>, ContentError> {
+        let (encryption, offset) = self.resolve(data);
+        encryption.decrypt(key, &data[offset..])
+    }
+
+    /// ヘッダをコピーせずに読み飛ばし、暗号文のバッファの所有権ごと実装に渡す。
+    fn decrypt_bytes(
+        &self,
+        key: &ContentEncryptionKey,
+        mut data: Bytes,
+    ) -> Result<Bytes, ContentError> {
+        let (encryption, offset) = self.resolve(&data);
+        data.advance(offset);
+        encryption.decrypt_bytes(key, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::encryption::conformance;
+
+    fn key() -> ContentEncryptionKey {
+        ContentEncryptionKey(vec![42u8; 32])
+    }
+
+    #[test]
+    fn encrypts_with_selected_algorithm_and_records_its_id() {
+        let suite = CipherSuite::builtin(AES_256_GCM).unwrap();
+        let ciphertext = suite.encrypt(&key(), b"hello").unwrap();
+
+        assert!(ciphertext.starts_with(b"MNC\x01\x0baes-256-gcm"));
+        assert_eq!(suite.algorithm_of(&ciphertext), AES_256_GCM);
+        assert_eq!(suite.decrypt(&key(), &ciphertext).unwrap(), b"hello");
+        // GCM 部分だけを取り出しても復号できる
+        let offset = MAGIC.len() + 1 + AES_256_GCM.len();
+        assert_eq!(
+            Aes256GcmContentEncryption
+                .decrypt(&key(), &ciphertext[offset..])
+                .unwrap(),
+            b"hello"
+        );
+    }
+
+    #[test]
+    fn decrypts_data_written_under_other_algorithms_and_legacy_format() {
+        let ctr = CipherSuite::default();
+        let gcm = CipherSuite::builtin(AES_256_GCM).unwrap();
+        let legacy = Aes256CtrContentEncryption
+            .encrypt(&key(), b"legacy")
+            .unwrap();
+        let from_ctr = ctr.encrypt(&key(), b"from ctr").unwrap();
+        let from_gcm = gcm.encrypt(&key(), b"from gcm").unwrap();
+
+        for suite in [&ctr, &gcm] {
+            assert_eq!(suite.algorithm_of(&legacy), AES_256_CTR);
+            assert_eq!(suite.decrypt(&key(), &legacy).unwrap(), b"legacy");
+            assert_eq!(suite.decrypt(&key(), &from_ctr).unwrap(), b"from ctr");
+            assert_eq!(
+                suite
+                    .decrypt_bytes(&key(), Bytes::from(from_gcm.clone()))
+                    .unwrap(),
+                &b"from gcm"[..]
+            );
+        }
+    }
+
+    #[test]
+    fn decrypt_bytes_skips_header_without_copying() {
+        let suite = CipherSuite::default();
+        let ciphertext = Bytes::from(suite.encrypt(&key(), b"in place").unwrap());
+        let start = ciphertext.as_ptr();
+
+        let decrypted = suite.decrypt_bytes(&key(), ciphertext).unwrap();
+        assert_eq!(decrypted, &b"in place"[..]);
+        // ヘッダと IV の分だけ先頭がずれた同じバッファ
+        assert_eq!(
+            decrypted.as_ptr(),
+            start.wrapping_add(MAGIC.len() + 1 + AES_256_CTR.len() + 16)
+        );
+    }
+
+    #[test]
+    fn unknown_header_ids_fall_back_to_legacy() {
+        let suite = CipherSuite::default();
+        let mut data = b"MNC\x01\x07chacha2".to_vec();
+        data.extend_from_slice(&[0u8; 8]);
+        assert_eq!(suite.algorithm_of(&data), AES_256_CTR);
+        assert_eq!(suite.algorithm_of(b"MNC"), AES_256_CTR);
+        assert_eq!(suite.algorithm_of(b"MNC\x01\xffaes"), AES_256_CTR);
+    }
+
+    #[test]
+    fn custom_algorithms_can_be_registered_and_selected() {
+        let suite = CipherSuite::default()
+            .with_algorithm("aes-256-gcm-v2", Aes256GcmContentEncryption)
+            .unwrap()
+            .select("aes-256-gcm-v2")
+            .unwrap();
+        assert_eq!(suite.selected(), "aes-256-gcm-v2");
+        assert_eq!(
+            suite.algorithms().collect::<Vec<_>>(),
+            vec![AES_256_CTR, AES_256_GCM, "aes-256-gcm-v2"]
+        );
+        let ciphertext = suite.encrypt(&key(), b"v2").unwrap();
+        assert_eq!(suite.algorithm_of(&ciphertext), "aes-256-gcm-v2");
+
+        assert_eq!(
+            CipherSuite::default().select("rot13").err(),
+            Some(CipherSuiteError::UnknownAlgorithm("rot13".into()))
+        );
+        assert!(matches!(
+            CipherSuite::default().with_algorithm("", Aes256GcmContentEncryption),
+            Err(CipherSuiteError::InvalidAlgorithmId(_))
+        ));
+    }
+
+    #[test]
+    fn reads_selected_algorithm_from_lookup() {
+        let from = |value: Option<&str>| CipherSuite::from_lookup(|_| value.map(str::to_string));
+        assert_eq!(from(None).unwrap().selected(), AES_256_CTR);
+        assert_eq!(from(Some(" ")).unwrap().selected(), AES_256_CTR);
+        assert_eq!(from(Some("AES-256-GCM")).unwrap().selected(), AES_256_GCM);
+        assert_eq!(
+            from(Some("des")).err(),
+            Some(CipherSuiteError::UnknownAlgorithm("des".into()))
+        );
+    }
+
+    #[test]
+    fn passes_conformance_suite_for_every_builtin_algorithm() {
+        for id in [AES_256_CTR, AES_256_GCM] {
+            conformance::check_conformance(
+                &CipherSuite::builtin(id).unwrap(),
+                &key(),
+                &ContentEncryptionKey(vec![43u8; 32]),
+            );
+        }
+    }
+}
@@ -0,0 +1,596 @@
+//! 取得したコンテンツをメモリに保持する ContentRepository デコレータ。
+//!
+//! 頻繁に取得されるコンテンツについて、ストレージへの読み込みを省く。
+//! キャッシュするのはリポジトリが返す `Content`（本体は暗号文）であり、
+//! 平文はメモリに残さない。復号は従来どおり取得のたびに行う。
+//!
+//! エントリ数と暗号文の合計サイズの上限を超えたら、最も長く使われていないものから捨てる。
+//! 上限は [`ContentCache`] 単位で、`scoped` で名前空間ごとに分けたリポジトリも
+//! 同じ上限を共有する。
+//! 保存（更新・削除・アーカイブなどの状態変更を含む）を行った ContentId のエントリは
+//! 破棄し、次の取得でストレージから読み直す。
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use crate::application_service::content_service::{
+    ContentListFilter, ContentRepository, ContentRepositoryError, ListableContentRepository,
+    MultiStorageContentRepository,
+};
+use crate::domain::content::Content;
+use crate::domain::content_id::ContentId;
+use crate::domain::namespace::Namespace;
+
+/// `MONAS_CONTENT_CACHE_MAX_BYTES` を省略したときの暗号文の合計サイズの上限（64 MiB）。
+pub const DEFAULT_CACHE_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContentCacheConfigError {
+    #[error("invalid value for {name}: {value}")]
+    InvalidValue { name: &'static str, value: String },
+}
+
+/// キャッシュの上限。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentCacheConfig {
+    /// 保持するコンテンツの最大数。0 の場合はキャッシュしない。
+    pub max_entries: usize,
+    /// 保持する暗号文の合計サイズの上限（バイト）。これを超えるコンテンツは保持しない。
+    /// [`ContentCache::scoped`] で分けた名前空間すべての合計に適用する。
+    pub max_bytes: u64,
+}
+
+impl Default for ContentCacheConfig {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl ContentCacheConfig {
+    pub fn new(max_entries: usize, max_bytes: u64) -> Self {
+        Self {
+            max_entries,
+            max_bytes,
+        }
+    }
+
+    /// キャッシュしない設定。
+    pub fn disabled() -> Self {
+        Self::new(0, 0)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_entries > 0 && self.max_bytes > 0
+    }
+
+    /// 環境変数から設定を読み込む。`MONAS_CONTENT_CACHE_MAX_ENTRIES` が未設定ならキャッシュしない。
+    ///
+    /// - `MONAS_CONTENT_CACHE_MAX_ENTRIES`: 保持するコンテンツの最大数
+    /// - `MONAS_CONTENT_CACHE_MAX_BYTES`: 暗号文の合計サイズの上限（省略時は 64 MiB）
+    pub fn from_env() -> Result<Self, ContentCacheConfigError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ContentCacheConfigError> {
+        const MAX_ENTRIES: &str = "MONAS_CONTENT_CACHE_MAX_ENTRIES";
+        const MAX_BYTES: &str = "MONAS_CONTENT_CACHE_MAX_BYTES";
+
+        let Some(value) = lookup(MAX_ENTRIES).filter(|v| !v.trim().is_empty()) else {
+            return Ok(Self::disabled());
+        };
+        let max_entries =
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| ContentCacheConfigError::InvalidValue {
+                    name: MAX_ENTRIES,
+                    value: value.clone(),
+                })?;
+        let max_bytes =
+            match lookup(MAX_BYTES).filter(|v| !v.trim().is_empty()) {
+                Some(value) => value.trim().parse::<u64>().map_err(|_| {
+                    ContentCacheConfigError::InvalidValue {
+                        name: MAX_BYTES,
+                        value: value.clone(),
+                    }
+                })?,
+                None => DEFAULT_CACHE_MAX_BYTES,
+            };
+        Ok(Self::new(max_entries, max_bytes))
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    content: Content,
+    size: u64,
+    last_used: u64,
+}
+
+/// エントリのキー。名前空間（`None` は名前空間なし）と ContentId の組。
+type CacheKey = (Option<Namespace>, ContentId);
+
+/// LRU の状態。`order` は最終利用時刻（単調増加のカウンタ）からキーを引く。
+#[derive(Debug, Default)]
+struct LruState {
+    entries: HashMap<CacheKey, CacheEntry>,
+    order: BTreeMap<u64, CacheKey>,
+    clock: u64,
+    bytes: u64,
+    /// 破棄のたびに進める。読み込み中に破棄されたコンテンツを古いまま保持しないために使う。
+    generation: u64,
+}
+
+impl LruState {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn get(&mut self, key: &CacheKey) -> Option<Content> {
+        let now = self.tick();
+        let entry = self.entries.get_mut(key)?;
+        self.order.remove(&entry.last_used);
+        entry.last_used = now;
+        self.order.insert(now, key.clone());
+        Some(entry.content.clone())
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.last_used);
+            self.bytes -= entry.size;
+        }
+    }
+
+    fn insert(&mut self, config: &ContentCacheConfig, key: CacheKey, content: Content) {
+        let size = content
+            .encrypted_content()
+            .map_or(0, |bytes| bytes.len() as u64);
+        if size > config.max_bytes {
+            return;
+        }
+        self.remove(&key);
+        while self.entries.len() >= config.max_entries || self.bytes + size > config.max_bytes {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.bytes -= entry.size;
+            }
+        }
+
+        let now = self.tick();
+        self.order.insert(now, key.clone());
+        self.entries.insert(
+            key,
+            CacheEntry {
+                content,
+                size,
+                last_used: now,
+            },
+        );
+        self.bytes += size;
+    }
+
+    fn invalidate(&mut self, key: &CacheKey) {
+        self.generation += 1;
+        self.remove(key);
+    }
+
+    /// 名前空間 `scope` のエントリをすべて破棄する。
+    fn clear(&mut self, scope: &Option<Namespace>) {
+        self.generation += 1;
+        let mut removed = 0;
+        self.entries.retain(|(namespace, _), entry| {
+            let keep = namespace != scope;
+            if !keep {
+                removed += entry.size;
+            }
+            keep
+        });
+        self.order.retain(|_, (namespace, _)| namespace != scope);
+        self.bytes -= removed;
+    }
+}
+
+/// エントリ数と合計サイズの上限を共有するキャッシュ。
+///
+/// `scoped` で作ったキャッシュはエントリを名前空間ごとに分けるが、上限は元の
+/// キャッシュと共有する。名前空間がいくつあっても、保持する暗号文の合計は
+/// `max_bytes` を超えない。クローンも状態を共有する。
+#[derive(Clone)]
+pub struct ContentCache {
+    config: ContentCacheConfig,
+    state: Arc<Mutex<LruState>>,
+    scope: Option<Namespace>,
+}
+
+impl ContentCache {
+    pub fn new(config: ContentCacheConfig) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(LruState::default())),
+            scope: None,
+        }
+    }
+
+    /// 上限を共有したまま、エントリを名前空間 `namespace` に分けたキャッシュを返す。
+    pub fn scoped(&self, namespace: &Namespace) -> Self {
+        Self {
+            config: self.config,
+            state: self.state.clone(),
+            scope: Some(namespace.clone()),
+        }
+    }
+
+    /// すべての名前空間で保持しているコンテンツの合計サイズ（バイト）。
+    pub fn total_bytes(&self) -> u64 {
+        self.state.lock().unwrap().bytes
+    }
+
+    fn key(&self, content_id: &ContentId) -> CacheKey {
+        (self.scope.clone(), content_id.clone())
+    }
+}
+
+/// `find_by_id` の結果を LRU で保持する ContentRepository。
+///
+/// - `find_from`（プロバイダー指定の取得）はキャッシュを使わない。
+/// - プロバイダーの接続・切断でデフォルトプロバイダーの内容が変わりうるため、
+///   その際はキャッシュをすべて破棄する。
+/// - クローンはキャッシュを共有する。
+#[derive(Clone)]
+pub struct CachingContentRepository<R> {
+    inner: R,
+    cache: ContentCache,
+}
+
+impl<R> CachingContentRepository<R> {
+    /// 専用のキャッシュを持つリポジトリを作る。
+    pub fn new(inner: R, config: ContentCacheConfig) -> Self {
+        Self::with_cache(inner, ContentCache::new(config))
+    }
+
+    /// `cache` を使うリポジトリを作る。上限は `cache` を共有するリポジトリ全体に適用される。
+    pub fn with_cache(inner: R, cache: ContentCache) -> Self {
+        Self { inner, cache }
+    }
+
+    /// 内部のリポジトリを取得する。
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// このリポジトリの名前空間で現在保持しているコンテンツの数。
+    pub fn cached_len(&self) -> usize {
+        let state = self.cache.state.lock().unwrap();
+        state
+            .entries
+            .keys()
+            .filter(|(namespace, _)| *namespace == self.cache.scope)
+            .count()
+    }
+
+    fn invalidate(&self, content_id: &ContentId) {
+        let key = self.cache.key(content_id);
+        self.cache.state.lock().unwrap().invalidate(&key);
+    }
+
+    fn clear(&self) {
+        self.cache.state.lock().unwrap().clear(&self.cache.scope);
+    }
+}
+
+impl<R: ContentRepository> ContentRepository for CachingContentRepository<R> {
+    fn save(
+        &self,
+        content_id: &ContentId,
+        content: &Content,
+    ) -> Result<(), ContentRepositoryError> {
+        // 保存に失敗した場合もストレージの状態が分からないため破棄する
+        let result = self.inner.save(content_id, content);
+        self.invalidate(content_id);
+        result
+    }
+
+    fn find_by_id(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Option<Content>, ContentRepositoryError> {
+        if !self.cache.config.is_enabled() {
+            return self.inner.find_by_id(content_id);
+        }
+
+        let key = self.cache.key(content_id);
+        let generation = {
+            let mut state = self.cache.state.lock().unwrap();
+            if let Some(content) = state.get(&key) {
+                return Ok(Some(content));
+            }
+            state.generation
+        };
+
+        let found = self.inner.find_by_id(content_id)?;
+        if let Some(content) = &found {
+            let mut state = self.cache.state.lock().unwrap();
+            // 読み込み中に保存された場合、読み込んだ内容は古い可能性がある
+            if state.generation == generation {
+                state.insert(&self.cache.config, key, content.clone());
+            }
+        }
+        Ok(found)
+    }
+
+    fn flush(&self) -> Result<(), ContentRepositoryError> {
+        self.inner.flush()
+    }
+}
+
+impl<R: MultiStorageContentRepository> MultiStorageContentRepository
+    for CachingContentRepository<R>
+{
+    fn save_to(
+        &self,
+        provider: &str,
+        content_id: &ContentId,
+        content: &Content,
+    ) -> Result<(), ContentRepositoryError> {
+        // デフォルトプロバイダーへの保存かどうかに関わらず破棄する
+        let result = self.inner.save_to(provider, content_id, content);
+        self.invalidate(content_id);
+        result
+    }
+
+    fn find_from(
+        &self,
+        provider: &str,
+        content_id: &ContentId,
+    ) -> Result<Option<Content>, ContentRepositoryError> {
+        self.inner.find_from(provider, content_id)
+    }
+
+    fn connected_providers(&self) -> Result<Vec<String>, ContentRepositoryError> {
+        self.inner.connected_providers()
+    }
+
+    fn default_provider(&self) -> Result<String, ContentRepositoryError> {
+        self.inner.default_provider()
+    }
+
+    fn connect_provider(
+        &self,
+        provider: &str,
+        access_token: String,
+    ) -> Result<(), ContentRepositoryError> {
+        let result = self.inner.connect_provider(provider, access_token);
+        self.clear();
+        result
+    }
+
+    fn disconnect_provider(&self, provider: &str) -> Result<(), ContentRepositoryError> {
+        let result = self.inner.disconnect_provider(provider);
+        self.clear();
+        result
+    }
+}
+
+impl<R: ListableContentRepository> ListableContentRepository for CachingContentRepository<R> {
+    fn list(&self, filter: ContentListFilter) -> Result<Vec<Content>, ContentRepositoryError> {
+        self.inner.list(filter)
+    }
+
+    fn list_from(
+        &self,
+        provider: &str,
+        filter: ContentListFilter,
+    ) -> Result<Vec<Content>, ContentRepositoryError> {
+        self.inner.list_from(provider, filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::content::encryption::ContentEncryptionKeyGenerator;
+    use crate::infrastructure::content_id::Sha256ContentIdGenerator;
+    use crate::infrastructure::encryption::{
+        Aes256CtrContentEncryption, OsRngContentEncryptionKeyGenerator,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 取得回数を数えるテスト用リポジトリ。
+    #[derive(Clone, Default)]
+    struct CountingRepository {
+        inner: Arc<Mutex<HashMap<String, Content>>>,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl ContentRepository for CountingRepository {
+        fn save(
+            &self,
+            content_id: &ContentId,
+            content: &Content,
+        ) -> Result<(), ContentRepositoryError> {
+            self.inner
+                .lock()
+                .unwrap()
+                .insert(content_id.as_str().to_string(), content.clone());
+            Ok(())
+        }
+
+        fn find_by_id(
+            &self,
+            content_id: &ContentId,
+        ) -> Result<Option<Content>, ContentRepositoryError> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            Ok(self.inner.lock().unwrap().get(content_id.as_str()).cloned())
+        }
+    }
+
+    fn create_content(raw: &[u8]) -> Content {
+        let key = OsRngContentEncryptionKeyGenerator.generate();
+        let (content, _) = Content::create(
+            "name".to_string(),
+            raw.to_vec(),
+            "docs/name".to_string(),
+            None,
+            &Sha256ContentIdGenerator,
+            &key,
+            &Aes256CtrContentEncryption,
+        )
+        .unwrap();
+        content
+    }
+
+    #[test]
+    fn hits_skip_the_inner_repository_until_the_content_is_saved() {
+        let inner = CountingRepository::default();
+        let repo = CachingContentRepository::new(inner.clone(), ContentCacheConfig::new(8, 1024));
+        let content = create_content(b"hot");
+        let id = content.raw_id().clone();
+        repo.save(&id, &content).unwrap();
+
+        for _ in 0..3 {
+            assert!(repo.find_by_id(&id).unwrap().is_some());
+        }
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 1);
+
+        // 削除の保存でエントリが破棄され、次の取得で新しい状態を読み直す
+        let (deleted, _) = content.delete().unwrap();
+        repo.save(&id, &deleted).unwrap();
+        assert!(repo.find_by_id(&id).unwrap().unwrap().is_deleted());
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 2);
+
+        // 見つからなかった結果は保持しない
+        let missing = create_content(b"missing");
+        assert!(repo.find_by_id(missing.raw_id()).unwrap().is_none());
+        assert!(repo.find_by_id(missing.raw_id()).unwrap().is_none());
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_within_entry_and_byte_limits() {
+        let inner = CountingRepository::default();
+        let repo = CachingContentRepository::new(inner.clone(), ContentCacheConfig::new(2, 1024));
+        let contents: Vec<Content> = [b"a".as_slice(), b"bb", b"ccc"]
+            .into_iter()
+            .map(create_content)
+            .collect();
+        for content in &contents {
+            inner.save(content.raw_id(), content).unwrap();
+        }
+        let [a, b, c] = [0, 1, 2].map(|i| contents[i].raw_id().clone());
+
+        repo.find_by_id(&a).unwrap();
+        repo.find_by_id(&b).unwrap();
+        // a を使うと、次に追い出されるのは b になる
+        repo.find_by_id(&a).unwrap();
+        repo.find_by_id(&c).unwrap();
+        assert_eq!(repo.cached_len(), 2);
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 3);
+
+        repo.find_by_id(&a).unwrap();
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 3);
+        repo.find_by_id(&b).unwrap();
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 4);
+
+        // 上限を超える大きさのコンテンツは保持しない
+        let small = CachingContentRepository::new(inner.clone(), ContentCacheConfig::new(8, 2));
+        small.find_by_id(&c).unwrap();
+        assert_eq!(small.cached_len(), 0);
+    }
+
+    #[test]
+    fn scoped_repositories_share_one_budget() {
+        let inner = CountingRepository::default();
+        let contents: Vec<Content> = [b"aaaa".as_slice(), b"bbbb", b"cccc"]
+            .into_iter()
+            .map(create_content)
+            .collect();
+        for content in &contents {
+            inner.save(content.raw_id(), content).unwrap();
+        }
+        let size = contents[0].encrypted_content().unwrap().len() as u64;
+        let cache = ContentCache::new(ContentCacheConfig::new(8, size * 2));
+        let alice = CachingContentRepository::with_cache(
+            inner.clone(),
+            cache.scoped(&Namespace::new("alice").unwrap()),
+        );
+        let bob = CachingContentRepository::with_cache(
+            inner.clone(),
+            cache.scoped(&Namespace::new("bob").unwrap()),
+        );
+
+        alice.find_by_id(contents[0].raw_id()).unwrap();
+        bob.find_by_id(contents[1].raw_id()).unwrap();
+        // 名前空間が異なっても上限は共有し、最も古いエントリを追い出す
+        bob.find_by_id(contents[2].raw_id()).unwrap();
+        assert_eq!(cache.total_bytes(), size * 2);
+        assert_eq!(alice.cached_len(), 0);
+        assert_eq!(bob.cached_len(), 2);
+
+        // 同じ ContentId でも名前空間ごとに分けて保持する
+        alice.find_by_id(contents[1].raw_id()).unwrap();
+        assert_eq!(alice.cached_len(), 1);
+
+        // プロバイダーの切り替え時の破棄は自分の名前空間のエントリだけに及ぶ
+        alice.clear();
+        assert_eq!(alice.cached_len(), 0);
+        assert_eq!(bob.cached_len(), 1);
+        assert_eq!(cache.total_bytes(), size);
+    }
+
+    #[test]
+    fn disabled_cache_always_reads_through() {
+        let inner = CountingRepository::default();
+        let repo = CachingContentRepository::new(inner.clone(), ContentCacheConfig::disabled());
+        let content = create_content(b"cold");
+        repo.save(content.raw_id(), &content).unwrap();
+
+        repo.find_by_id(content.raw_id()).unwrap();
+        repo.find_by_id(content.raw_id()).unwrap();
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 2);
+        assert_eq!(repo.cached_len(), 0);
+    }
+
+    #[test]
+    fn config_from_lookup_is_disabled_by_default_and_rejects_garbage() {
+        assert_eq!(
+            ContentCacheConfig::from_lookup(|_| None).unwrap(),
+            ContentCacheConfig::disabled()
+        );
+
+        let config = ContentCacheConfig::from_lookup(|key| {
+            (key == "MONAS_CONTENT_CACHE_MAX_ENTRIES").then(|| "100".to_string())
+        })
+        .unwrap();
+        assert_eq!(
+            config,
+            ContentCacheConfig::new(100, DEFAULT_CACHE_MAX_BYTES)
+        );
+
+        let config = ContentCacheConfig::from_lookup(|key| match key {
+            "MONAS_CONTENT_CACHE_MAX_ENTRIES" => Some("10".into()),
+            "MONAS_CONTENT_CACHE_MAX_BYTES" => Some("4096".into()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(config, ContentCacheConfig::new(10, 4096));
+
+        let err = ContentCacheConfig::from_lookup(|key| match key {
+            "MONAS_CONTENT_CACHE_MAX_ENTRIES" => Some("10".into()),
+            "MONAS_CONTENT_CACHE_MAX_BYTES" => Some("lots".into()),
+            _ => None,
+        });
+        assert!(matches!(
+            err,
+            Err(ContentCacheConfigError::InvalidValue {
+                name: "MONAS_CONTENT_CACHE_MAX_BYTES",
+                ..
+            })
+        ));
+    }
+}
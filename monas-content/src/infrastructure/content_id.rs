@@ -1,8 +1,10 @@
-use crate::domain::content_id::{ContentId, ContentIdGenerator};
+use crate::domain::content_id::{
+    ContentId, ContentIdAlgorithm, ContentIdError, ContentIdGenerator,
+};
 use sha2::{Digest, Sha256};
 
 /// シンプルな ContentIdGenerator 実装。
-/// v1 では raw_content の SHA-256 ハッシュをそのまま ContentId にする。
+/// raw_content の SHA-256 ハッシュに `sha2-256` のプレフィックスを付けて ContentId にする。
 /// todo: crslのcid生成を使用する
 pub struct Sha256ContentIdGenerator;
 
@@ -11,8 +13,8 @@ impl ContentIdGenerator for Sha256ContentIdGenerator {
         let mut hasher = Sha256::new();
         hasher.update(raw_content);
         let hash = hasher.finalize();
-        let hex = hex::encode(hash);
-        ContentId::new(hex)
+        ContentId::from_digest(ContentIdAlgorithm::Sha256, &hash)
+            .expect("sha256 digest has the expected length")
     }
 
     fn generate_encrypted(&self, plain_cid: &ContentId, ciphertext: &[u8]) -> ContentId {
@@ -23,7 +25,173 @@ impl ContentIdGenerator for Sha256ContentIdGenerator {
         hasher.update([0u8]);
         hasher.update(ciphertext);
         let hash = hasher.finalize();
-        let hex = hex::encode(hash);
-        ContentId::new(hex)
+        ContentId::from_digest(ContentIdAlgorithm::Sha256, &hash)
+            .expect("sha256 digest has the expected length")
+    }
+}
+
+/// BLAKE3 を使う ContentIdGenerator 実装。
+/// ID には `blake3` のプレフィックスが付くため、SHA-256 の ID と混在しても区別できる。
+pub struct Blake3ContentIdGenerator;
+
+impl ContentIdGenerator for Blake3ContentIdGenerator {
+    fn generate(&self, raw_content: &[u8]) -> ContentId {
+        let hash = blake3::hash(raw_content);
+        ContentId::from_digest(ContentIdAlgorithm::Blake3, hash.as_bytes())
+            .expect("blake3 digest has the expected length")
+    }
+
+    fn generate_encrypted(&self, plain_cid: &ContentId, ciphertext: &[u8]) -> ContentId {
+        // encCid = blake3(plainCid || 0x00 || ciphertext)
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(plain_cid.as_str().as_bytes());
+        hasher.update(&[0u8]);
+        hasher.update(ciphertext);
+        let hash = hasher.finalize();
+        ContentId::from_digest(ContentIdAlgorithm::Blake3, hash.as_bytes())
+            .expect("blake3 digest has the expected length")
+    }
+}
+
+/// 設定で選んだハッシュアルゴリズムで ContentId を生成する実装。
+///
+/// 既定は SHA-256。大きなファイルを扱う場合は BLAKE3 を選ぶと高速になる
+/// （`benches/content_id.rs` を参照）。どちらを選んでも ID にはアルゴリズムの
+/// プレフィックスが付くため、既存の ID と混在しても区別できる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigurableContentIdGenerator {
+    algorithm: ContentIdAlgorithm,
+}
+
+impl Default for ConfigurableContentIdGenerator {
+    fn default() -> Self {
+        Self::new(ContentIdAlgorithm::Sha256)
+    }
+}
+
+impl ConfigurableContentIdGenerator {
+    pub fn new(algorithm: ContentIdAlgorithm) -> Self {
+        Self { algorithm }
+    }
+
+    pub fn algorithm(&self) -> ContentIdAlgorithm {
+        self.algorithm
+    }
+
+    /// 環境変数 `MONAS_CONTENT_ID_ALGORITHM`（`sha2-256` / `blake3`）から構築する。
+    ///
+    /// 未設定の場合は SHA-256。不明な名前の場合はエラーを返す。
+    pub fn from_env() -> Result<Self, ContentIdError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, ContentIdError> {
+        match lookup("MONAS_CONTENT_ID_ALGORITHM").filter(|v| !v.trim().is_empty()) {
+            Some(name) => ContentIdAlgorithm::from_name(&name)
+                .map(Self::new)
+                .ok_or(ContentIdError::UnknownAlgorithmName(name)),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+impl ContentIdGenerator for ConfigurableContentIdGenerator {
+    fn generate(&self, raw_content: &[u8]) -> ContentId {
+        match self.algorithm {
+            ContentIdAlgorithm::Sha256 => Sha256ContentIdGenerator.generate(raw_content),
+            ContentIdAlgorithm::Blake3 => Blake3ContentIdGenerator.generate(raw_content),
+        }
+    }
+
+    fn generate_encrypted(&self, plain_cid: &ContentId, ciphertext: &[u8]) -> ContentId {
+        match self.algorithm {
+            ContentIdAlgorithm::Sha256 => {
+                Sha256ContentIdGenerator.generate_encrypted(plain_cid, ciphertext)
+            }
+            ContentIdAlgorithm::Blake3 => {
+                Blake3ContentIdGenerator.generate_encrypted(plain_cid, ciphertext)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_generator_emits_prefixed_id() {
+        let id = Sha256ContentIdGenerator.generate(b"hello");
+        assert_eq!(id.algorithm(), ContentIdAlgorithm::Sha256);
+        assert_eq!(id.digest(), Sha256::digest(b"hello").to_vec());
+        assert_eq!(ContentId::new(id.as_str().to_string()).unwrap(), id);
+    }
+
+    #[test]
+    fn blake3_generator_emits_prefixed_id() {
+        let id = Blake3ContentIdGenerator.generate(b"hello");
+        assert_eq!(id.algorithm(), ContentIdAlgorithm::Blake3);
+        assert_eq!(id.digest(), blake3::hash(b"hello").as_bytes().to_vec());
+        assert_eq!(ContentId::new(id.as_str().to_string()).unwrap(), id);
+    }
+
+    #[test]
+    fn blake3_encrypted_id_depends_on_plain_cid_and_ciphertext() {
+        let generator = Blake3ContentIdGenerator;
+        let plain = generator.generate(b"plain");
+        let other_plain = generator.generate(b"other");
+
+        let enc = generator.generate_encrypted(&plain, b"cipher");
+        assert_eq!(enc, generator.generate_encrypted(&plain, b"cipher"));
+        assert_ne!(enc, generator.generate_encrypted(&other_plain, b"cipher"));
+        assert_ne!(enc, generator.generate_encrypted(&plain, b"cipher2"));
+        assert_eq!(enc.algorithm(), ContentIdAlgorithm::Blake3);
+    }
+
+    #[test]
+    fn same_content_yields_distinct_ids_per_algorithm() {
+        let sha = Sha256ContentIdGenerator.generate(b"data");
+        let blake = Blake3ContentIdGenerator.generate(b"data");
+        assert_ne!(sha, blake);
+    }
+
+    #[test]
+    fn configurable_generator_uses_selected_algorithm() {
+        let blake = ConfigurableContentIdGenerator::new(ContentIdAlgorithm::Blake3);
+        assert_eq!(
+            blake.generate(b"data"),
+            Blake3ContentIdGenerator.generate(b"data")
+        );
+        let plain = blake.generate(b"plain");
+        assert_eq!(
+            blake.generate_encrypted(&plain, b"cipher"),
+            Blake3ContentIdGenerator.generate_encrypted(&plain, b"cipher")
+        );
+
+        let sha = ConfigurableContentIdGenerator::default();
+        assert_eq!(
+            sha.generate(b"data"),
+            Sha256ContentIdGenerator.generate(b"data")
+        );
+    }
+
+    #[test]
+    fn configurable_generator_reads_algorithm_from_lookup() {
+        let from = |value: Option<&str>| {
+            ConfigurableContentIdGenerator::from_lookup(|_| value.map(str::to_string))
+        };
+        assert_eq!(from(None).unwrap().algorithm(), ContentIdAlgorithm::Sha256);
+        assert_eq!(
+            from(Some("BLAKE3")).unwrap().algorithm(),
+            ContentIdAlgorithm::Blake3
+        );
+        assert_eq!(
+            from(Some("sha256")).unwrap().algorithm(),
+            ContentIdAlgorithm::Sha256
+        );
+        assert!(matches!(
+            from(Some("md5")),
+            Err(ContentIdError::UnknownAlgorithmName(_))
+        ));
     }
 }
@@ -4,12 +4,20 @@ use crate::domain::content::encryption::{
 use crate::domain::content::ContentError;
 
 use aes::Aes256;
+use aes_gcm::aead::{AeadInPlace, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce, Tag};
+use bytes::Bytes;
 use ctr::cipher::{KeyIvInit, StreamCipher};
 use ctr::Ctr128BE;
+use hkdf::Hkdf;
 use rand_core::{OsRng, RngCore};
+use sha2::Sha256;
 
 type Aes256Ctr = Ctr128BE<Aes256>;
 
+#[cfg(test)]
+pub(crate) mod conformance;
+
 /// Implementation for generating a CEK suitable for AES-256.
 ///
 /// Produces a 32-byte random key using an OS-backed cryptographically secure RNG.
@@ -17,10 +25,11 @@ pub struct OsRngContentEncryptionKeyGenerator;
 
 impl ContentEncryptionKeyGenerator for OsRngContentEncryptionKeyGenerator {
     fn generate(&self) -> ContentEncryptionKey {
-        let mut key_bytes = [0u8; 32];
+        // 鍵素材のコピーを残さないよう、CEK が所有するバッファへ直接書き込む
+        let mut key = ContentEncryptionKey(vec![0u8; KEY_LEN]);
         let mut rng = OsRng;
-        rng.fill_bytes(&mut key_bytes);
-        ContentEncryptionKey(key_bytes.to_vec())
+        rng.fill_bytes(&mut key.0);
+        key
     }
 }
 
@@ -52,52 +61,167 @@ impl ContentEncryption for Aes256CtrContentEncryption {
         let mut rng = OsRng;
         rng.fill_bytes(&mut iv);
 
-        let mut buffer = plaintext.to_vec();
         let mut cipher = Aes256Ctr::new_from_slices(key.0.as_slice(), &iv).map_err(|_| {
             ContentError::EncryptionError(
                 "Invalid key or IV length for AES-256-CTR (expected 32-byte key, 16-byte IV)"
                     .into(),
             )
         })?;
-        cipher.apply_keystream(&mut buffer);
-        let mut result = Vec::with_capacity(IV_LEN + buffer.len());
+        // Encrypt in the output buffer so that no plaintext copy is left behind.
+        let mut result = Vec::with_capacity(IV_LEN + plaintext.len());
         result.extend_from_slice(&iv);
-        result.extend_from_slice(&buffer);
+        result.extend_from_slice(plaintext);
+        cipher.apply_keystream(&mut result[IV_LEN..]);
         Ok(result)
     }
 
     fn decrypt(&self, key: &ContentEncryptionKey, data: &[u8]) -> Result<Vec<u8>, ContentError> {
-        if key.0.len() != KEY_LEN {
-            return Err(ContentError::DecryptionError(format!(
+        validate_decryption_input(key, data)?;
+
+        let (iv_bytes, ciphertext) = data.split_at(IV_LEN);
+
+        let mut buffer = ciphertext.to_vec();
+        decryption_cipher(key, iv_bytes)?.apply_keystream(&mut buffer);
+
+        Ok(buffer)
+    }
+
+    /// Decrypts in place when `data` is the only handle to its buffer.
+    ///
+    /// The IV prefix is split off without copying, so fetching content read
+    /// straight from storage allocates nothing beyond the stored ciphertext.
+    /// Falls back to [`decrypt`](Self::decrypt) when the buffer is shared.
+    fn decrypt_bytes(
+        &self,
+        key: &ContentEncryptionKey,
+        data: Bytes,
+    ) -> Result<Bytes, ContentError> {
+        validate_decryption_input(key, &data)?;
+
+        match data.try_into_mut() {
+            Ok(mut buffer) => {
+                let iv_bytes = buffer.split_to(IV_LEN);
+                decryption_cipher(key, &iv_bytes)?.apply_keystream(&mut buffer);
+                Ok(buffer.freeze())
+            }
+            Err(shared) => self.decrypt(key, &shared).map(Bytes::from),
+        }
+    }
+}
+
+fn validate_decryption_input(key: &ContentEncryptionKey, data: &[u8]) -> Result<(), ContentError> {
+    if key.0.len() != KEY_LEN {
+        return Err(ContentError::DecryptionError(format!(
+            "Invalid content encryption key length; expected {} bytes, got {} bytes",
+            KEY_LEN,
+            key.0.len()
+        )));
+    }
+
+    if data.len() <= IV_LEN {
+        return Err(ContentError::DecryptionError(
+            "Ciphertext is too short to contain IV and data (must be longer than IV only)".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn decryption_cipher(key: &ContentEncryptionKey, iv: &[u8]) -> Result<Aes256Ctr, ContentError> {
+    Aes256Ctr::new_from_slices(key.0.as_slice(), iv).map_err(|_| {
+        ContentError::DecryptionError(
+            "Invalid key or IV length for AES-256-CTR (expected 32-byte key, 16-byte IV)".into(),
+        )
+    })
+}
+
+/// Content encryption/decryption implementation using AES-256-GCM.
+///
+/// - Encryption: generates a 12-byte random nonce and returns a byte sequence in the form
+///   `[nonce || ciphertext || tag]` with a 16-byte tag and no associated data.
+/// - Decryption: verifies the tag before releasing any plaintext; tampered or truncated
+///   input and a wrong key are reported as `DecryptionError`.
+pub struct Aes256GcmContentEncryption;
+
+const GCM_NONCE_LEN: usize = 12;
+const GCM_TAG_LEN: usize = 16;
+
+impl ContentEncryption for Aes256GcmContentEncryption {
+    fn encrypt(
+        &self,
+        key: &ContentEncryptionKey,
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, ContentError> {
+        let cipher = Aes256Gcm::new_from_slice(key.0.as_slice()).map_err(|_| {
+            ContentError::EncryptionError(format!(
                 "Invalid content encryption key length; expected {} bytes, got {} bytes",
                 KEY_LEN,
                 key.0.len()
-            )));
-        }
+            ))
+        })?;
+        let mut nonce = [0u8; GCM_NONCE_LEN];
+        let mut rng = OsRng;
+        rng.fill_bytes(&mut nonce);
+
+        // Encrypt in the output buffer so that no plaintext copy is left behind.
+        let mut result = Vec::with_capacity(GCM_NONCE_LEN + plaintext.len() + GCM_TAG_LEN);
+        result.extend_from_slice(&nonce);
+        result.extend_from_slice(plaintext);
+        let tag = cipher
+            .encrypt_in_place_detached(Nonce::from_slice(&nonce), b"", &mut result[GCM_NONCE_LEN..])
+            .map_err(|_| ContentError::EncryptionError("AES-256-GCM encryption failed".into()))?;
+        result.extend_from_slice(&tag);
+        Ok(result)
+    }
 
-        if data.len() <= IV_LEN {
+    fn decrypt(&self, key: &ContentEncryptionKey, data: &[u8]) -> Result<Vec<u8>, ContentError> {
+        let cipher = Aes256Gcm::new_from_slice(key.0.as_slice()).map_err(|_| {
+            ContentError::DecryptionError(format!(
+                "Invalid content encryption key length; expected {} bytes, got {} bytes",
+                KEY_LEN,
+                key.0.len()
+            ))
+        })?;
+        if data.len() < GCM_NONCE_LEN + GCM_TAG_LEN {
             return Err(ContentError::DecryptionError(
-                "Ciphertext is too short to contain IV and data (must be longer than IV only)"
-                    .into(),
+                "Ciphertext is too short to contain nonce and tag".into(),
             ));
         }
 
-        let (iv_bytes, ciphertext) = data.split_at(IV_LEN);
-
+        let (nonce, rest) = data.split_at(GCM_NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at(rest.len() - GCM_TAG_LEN);
+        // The tag is checked before decrypting, so a failure leaves only ciphertext behind.
         let mut buffer = ciphertext.to_vec();
-
-        let mut cipher = Aes256Ctr::new_from_slices(key.0.as_slice(), iv_bytes).map_err(|_| {
-            ContentError::DecryptionError(
-                "Invalid key or IV length for AES-256-CTR (expected 32-byte key, 16-byte IV)"
-                    .into(),
+        cipher
+            .decrypt_in_place_detached(
+                Nonce::from_slice(nonce),
+                b"",
+                &mut buffer,
+                Tag::from_slice(tag),
             )
-        })?;
-        cipher.apply_keystream(&mut buffer);
-
+            .map_err(|_| {
+                ContentError::DecryptionError(
+                    "AES-256-GCM authentication failed (wrong key or tampered ciphertext)".into(),
+                )
+            })?;
         Ok(buffer)
     }
 }
 
+/// HKDF info string used to derive the metadata key from a CEK.
+const METADATA_KEY_INFO: &[u8] = b"monas-content/metadata-key/v1";
+
+/// Derives a metadata encryption key from a CEK using HKDF-SHA256.
+///
+/// Keeps metadata and payload under separate keys while only the CEK has to be
+/// stored or shared. Always returns a 32-byte key suitable for AES-256.
+pub fn derive_metadata_key(cek: &ContentEncryptionKey) -> ContentEncryptionKey {
+    let hk = Hkdf::<Sha256>::new(None, &cek.0);
+    let mut key = ContentEncryptionKey(vec![0u8; KEY_LEN]);
+    hk.expand(METADATA_KEY_INFO, &mut key.0)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,6 +247,33 @@ mod tests {
         assert_eq!(decrypted, plaintext);
     }
 
+    #[test]
+    fn decrypt_bytes_reuses_unique_buffer_and_copies_shared_one() {
+        let key = ContentEncryptionKey(vec![42u8; 32]);
+        let encryptor = Aes256CtrContentEncryption;
+        let plaintext = b"Monas content encryption test".to_vec();
+        let ciphertext = encryptor.encrypt(&key, &plaintext).unwrap();
+
+        // A uniquely owned buffer is decrypted in place
+        let unique = Bytes::from(ciphertext.clone());
+        let start = unique.as_ptr();
+        let decrypted = encryptor.decrypt_bytes(&key, unique).unwrap();
+        assert_eq!(decrypted, plaintext);
+        assert_eq!(decrypted.as_ptr(), start.wrapping_add(IV_LEN));
+
+        // A shared buffer is copied so the original ciphertext stays intact
+        let shared = Bytes::from(ciphertext.clone());
+        let decrypted = encryptor.decrypt_bytes(&key, shared.clone()).unwrap();
+        assert_eq!(decrypted, plaintext);
+        assert_eq!(shared, ciphertext);
+
+        let too_short = Bytes::from(vec![0u8; IV_LEN]);
+        assert!(matches!(
+            encryptor.decrypt_bytes(&key, too_short),
+            Err(ContentError::DecryptionError(_))
+        ));
+    }
+
     #[test]
     fn encrypt_fails_with_invalid_key_length() {
         let key = ContentEncryptionKey(vec![1u8; 16]);
@@ -292,4 +443,111 @@ mod tests {
         println!("OK: After restoring byte, plaintext matches original");
         println!("========== END TEST 1 ==========\n");
     }
+
+    #[test]
+    fn derive_metadata_key_is_deterministic_and_distinct_from_cek() {
+        let cek = ContentEncryptionKey(vec![7u8; 32]);
+
+        let first = derive_metadata_key(&cek);
+        let second = derive_metadata_key(&cek);
+
+        assert_eq!(first, second);
+        assert_eq!(first.0.len(), KEY_LEN);
+        assert_ne!(first, cek);
+        assert_ne!(
+            first,
+            derive_metadata_key(&ContentEncryptionKey(vec![8u8; 32]))
+        );
+    }
+
+    #[test]
+    fn aes256_ctr_matches_nist_known_answers() {
+        conformance::assert_ctr_known_answers(&Aes256CtrContentEncryption);
+    }
+
+    #[test]
+    fn aes256_ctr_passes_conformance_suite() {
+        conformance::check_conformance(
+            &Aes256CtrContentEncryption,
+            &OsRngContentEncryptionKeyGenerator.generate(),
+            &OsRngContentEncryptionKeyGenerator.generate(),
+        );
+    }
+
+    #[test]
+    fn aes256_gcm_matches_known_answers() {
+        use aes_gcm::aead::{Aead, Payload};
+
+        conformance::assert_gcm_known_answers(|key, nonce, aad, plaintext| {
+            Aes256Gcm::new_from_slice(key)
+                .unwrap()
+                .encrypt(
+                    Nonce::from_slice(nonce),
+                    Payload {
+                        msg: plaintext,
+                        aad,
+                    },
+                )
+                .unwrap()
+        });
+    }
+
+    #[test]
+    fn key_material_is_redacted_from_debug_and_wiped_on_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut key = OsRngContentEncryptionKeyGenerator.generate();
+        assert_eq!(key.0.len(), KEY_LEN);
+        let debug = format!("{key:?}");
+        assert_eq!(debug, "ContentEncryptionKey([REDACTED; 32 bytes])");
+        assert!(!debug.contains(&format!("{:?}", key.0)));
+
+        // Zeroize (also run on drop) wipes the key material.
+        key.zeroize();
+        assert!(key.0.is_empty());
+    }
+
+    #[test]
+    fn aes256_gcm_passes_conformance_suite() {
+        conformance::check_conformance(
+            &Aes256GcmContentEncryption,
+            &OsRngContentEncryptionKeyGenerator.generate(),
+            &OsRngContentEncryptionKeyGenerator.generate(),
+        );
+    }
+
+    #[test]
+    fn aes256_gcm_rejects_tampered_truncated_and_foreign_ciphertexts() {
+        let key = ContentEncryptionKey(vec![42u8; 32]);
+        let encryptor = Aes256GcmContentEncryption;
+        let plaintext = b"Secret message that should not be tampered with!";
+        let ciphertext = encryptor.encrypt(&key, plaintext).unwrap();
+        assert_eq!(
+            ciphertext.len(),
+            GCM_NONCE_LEN + plaintext.len() + GCM_TAG_LEN
+        );
+
+        let mut tampered = ciphertext.clone();
+        tampered[GCM_NONCE_LEN] ^= 0x11;
+        assert!(matches!(
+            encryptor.decrypt(&key, &tampered),
+            Err(ContentError::DecryptionError(_))
+        ));
+        assert!(matches!(
+            encryptor.decrypt(&key, &ciphertext[..GCM_NONCE_LEN + GCM_TAG_LEN - 1]),
+            Err(ContentError::DecryptionError(_))
+        ));
+        assert!(matches!(
+            encryptor.decrypt(&ContentEncryptionKey(vec![43u8; 32]), &ciphertext),
+            Err(ContentError::DecryptionError(_))
+        ));
+        assert!(matches!(
+            encryptor.encrypt(&ContentEncryptionKey(vec![1u8; 16]), plaintext),
+            Err(ContentError::EncryptionError(_))
+        ));
+
+        // An empty plaintext still carries a tag and round-trips
+        let empty = encryptor.encrypt(&key, b"").unwrap();
+        assert!(encryptor.decrypt(&key, &empty).unwrap().is_empty());
+    }
 }
@@ -0,0 +1,205 @@
+//! monas-event-manager の EventBus を用いた EventPublisher 実装。
+//!
+//! ContentService のドメインイベントを `ContentCreated` / `ContentUpdated` /
+//! `ContentDeleted` の各型のまま EventBus に流すことで、購読側は
+//! `EventBus::subscribe::<ContentCreated>` のように必要なイベントだけを購読できる。
+
+use std::any::Any;
+use std::sync::Arc;
+
+use monas_event_manager::{EventBus, SerializableEvent};
+use tokio::sync::mpsc;
+
+use crate::application_service::content_service::{EventPublisher, EventPublisherError};
+use crate::domain::content::events::{
+    ContentCreated, ContentDeleted, ContentDomainEvent, ContentUpdated,
+};
+
+impl monas_event_manager::event_bus::Event for ContentCreated {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl SerializableEvent for ContentCreated {
+    fn event_type() -> &'static str {
+        "ContentCreated"
+    }
+}
+
+impl monas_event_manager::event_bus::Event for ContentUpdated {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl SerializableEvent for ContentUpdated {
+    fn event_type() -> &'static str {
+        "ContentUpdated"
+    }
+}
+
+impl monas_event_manager::event_bus::Event for ContentDeleted {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl SerializableEvent for ContentDeleted {
+    fn event_type() -> &'static str {
+        "ContentDeleted"
+    }
+}
+
+/// 実行時に通知先を切り替える EventPublisher の動的型。
+pub type DynEventPublisher = Arc<dyn EventPublisher + Send + Sync>;
+
+/// EventBus にドメインイベントを publish する EventPublisher。
+///
+/// `EventBus` は Clone 可能で内部状態を共有するため、同じインスタンスを
+/// 他コンポーネントに渡して購読させる。
+///
+/// `ContentService` は同期 API のため、`publish` はイベントをキューに積むだけで、
+/// EventBus への publish はバックグラウンドのタスクが発生順に待ち合わせる。
+#[derive(Clone)]
+pub struct EventBusEventPublisher {
+    event_bus: EventBus,
+    sender: mpsc::UnboundedSender<ContentDomainEvent>,
+}
+
+impl EventBusEventPublisher {
+    /// `event_bus` に publish するタスクを起動する。tokio ランタイムの中で呼ぶこと。
+    pub fn new(event_bus: EventBus) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let bus = event_bus.clone();
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                if let Err(e) = publish_to(&bus, event).await {
+                    tracing::warn!(error = %e, "failed to publish content event");
+                }
+            }
+        });
+        Self { event_bus, sender }
+    }
+
+    /// 内部の EventBus を取得する（購読登録用）。
+    pub fn event_bus(&self) -> &EventBus {
+        &self.event_bus
+    }
+
+    /// 永続化付き EventBus で復元できるよう、コンテンツイベントの型を登録する。
+    pub async fn register_event_types(&self) {
+        self.event_bus.register_event_type::<ContentCreated>().await;
+        self.event_bus.register_event_type::<ContentUpdated>().await;
+        self.event_bus.register_event_type::<ContentDeleted>().await;
+    }
+}
+
+async fn publish_to(
+    event_bus: &EventBus,
+    event: ContentDomainEvent,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    match event {
+        ContentDomainEvent::Created(e) => event_bus.publish(Arc::new(e)).await,
+        ContentDomainEvent::Updated(e) => event_bus.publish(Arc::new(e)).await,
+        ContentDomainEvent::Deleted(e) => event_bus.publish(Arc::new(e)).await,
+    }
+}
+
+impl EventPublisher for EventBusEventPublisher {
+    fn publish(&self, event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+        // HTTP ハンドラーのタスクの中で publish を待つと、購読者の処理が同じワーカーを
+        // 必要とする場合に止まるため、待ち合わせはバックグラウンドのタスクに任せる
+        self.sender.send(event).map_err(|_| {
+            EventPublisherError::Publish("event publisher task has stopped".to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::content::metadata::Metadata;
+    use crate::domain::content_id::ContentId;
+    use monas_event_manager::make_subscriber;
+    use std::sync::Mutex;
+
+    fn sample_metadata(id: &ContentId) -> Metadata {
+        Metadata::new("name".into(), "path.txt".into(), id.clone(), None)
+    }
+
+    #[tokio::test]
+    async fn publish_delivers_typed_event_to_subscriber() {
+        let publisher = EventBusEventPublisher::new(EventBus::new());
+        let received: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let sink = received.clone();
+        let subscriber = make_subscriber::<ContentCreated, _, _>(
+            "content-created-test".to_string(),
+            move |event: Arc<ContentCreated>| {
+                let sink = sink.clone();
+                async move {
+                    sink.lock()
+                        .unwrap()
+                        .push(event.content_id.as_str().to_string());
+                    Ok(())
+                }
+            },
+        );
+        publisher
+            .event_bus()
+            .subscribe::<ContentCreated>(subscriber)
+            .await
+            .unwrap();
+
+        let id = ContentId::for_test("cid-1");
+        publisher
+            .publish(ContentDomainEvent::Created(ContentCreated {
+                content_id: id.clone(),
+                series_id: id.clone(),
+                metadata: sample_metadata(&id),
+            }))
+            .unwrap();
+        // 別の型のイベントは ContentCreated の購読者に届かない
+        publisher
+            .publish(ContentDomainEvent::Deleted(ContentDeleted {
+                content_id: id.clone(),
+                series_id: id,
+                deleted_at: chrono::Utc::now(),
+            }))
+            .unwrap();
+
+        // publish はバックグラウンドで行われるため、届くまで待つ
+        for _ in 0..100 {
+            if !received.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![ContentId::for_test("cid-1").into_inner()]
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_without_subscribers_succeeds() {
+        let publisher = EventBusEventPublisher::new(EventBus::new());
+        let id = ContentId::for_test("cid-1");
+
+        let result = publisher.publish(ContentDomainEvent::Updated(ContentUpdated {
+            content_id: id.clone(),
+            series_id: id.clone(),
+            metadata: sample_metadata(&id),
+        }));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn event_types_are_distinct() {
+        assert_eq!(ContentCreated::event_type(), "ContentCreated");
+        assert_eq!(ContentUpdated::event_type(), "ContentUpdated");
+        assert_eq!(ContentDeleted::event_type(), "ContentDeleted");
+    }
+}
@@ -5,17 +5,298 @@
 //! ## 提供するリポジトリ
 //!
 //! - [`MultiStorageRepository`] - 複数のストレージプロバイダーを使い分け
+//!
+//! ## 設定
+//!
+//! - [`ContentStorageConfig`] - プレゼンテーション層などで使うリポジトリの構成
+//! - [`ProviderPathMapping`] - プロバイダーごとの保存先パスの対応表
+//!
+//! ## 同期 / 非同期の橋渡し
+//!
+//! `ContentRepository` は同期トレイトだが、ストレージプロバイダーは非同期 API を持つ。
+//! マルチスレッドの tokio ランタイム上では `block_in_place` で待ち、
+//! current_thread ランタイム上やランタイム外から呼ばれた場合は
+//! プロセス共有のフォールバックランタイムで実行する。
 
 use crate::application_service::content_service::{
-    ContentRepository, ContentRepositoryError, MultiStorageContentRepository,
+    ChunkingPolicy, ContentLimits, ContentRepository, ContentRepositoryError,
+    MultiStorageContentRepository,
 };
-use crate::domain::content::Content;
+use crate::domain::content::{join_chunked_ciphertext, split_chunked_ciphertext, Content};
 use crate::domain::content_id::ContentId;
-use monas_filesync::{AuthSession, FetcherRegistry, StorageProvider};
+use crate::domain::namespace::Namespace;
+use crate::infrastructure::content_cache::ContentCacheConfig;
+use crate::infrastructure::key_derivation::AccountMasterKey;
+use crate::infrastructure::metadata_encryption::MetadataKeyMode;
+use monas_filesync::{AuthSession, FetcherRegistry, FilesyncConfig, StorageProvider};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::future::Future;
 use std::path::PathBuf;
-use std::sync::{Arc, RwLock};
-use tokio::runtime::Handle;
+use std::sync::{Arc, OnceLock, RwLock};
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+
+/// パスの対応が無いプロバイダーで使う保存先ディレクトリ。
+pub const DEFAULT_CONTENT_PATH_PREFIX: &str = "content";
+
+/// プロバイダーごとの保存先パスの対応表。
+///
+/// コンテンツは `{provider}://{prefix}/{content_id}.json` に保存される。
+/// `prefix` はプロバイダーごとに設定でき、未設定なら [`DEFAULT_CONTENT_PATH_PREFIX`]。
+/// 名前空間に属するコンテンツは `{prefix}/namespaces/{namespace}/` の下に保存される。
+/// チャンク分割したコンテンツの暗号文は、同じディレクトリの
+/// `chunks/{ciphertext_sha256}.bin` にチャンクごとに保存される。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProviderPathMapping {
+    prefixes: HashMap<String, String>,
+}
+
+impl ProviderPathMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `provider` の保存先ディレクトリを設定する。
+    ///
+    /// 前後の `/` は取り除く。`local` でも絶対パスにはならないため、
+    /// 保存場所はプロバイダーの `base_path` からの相対パスになる。
+    pub fn with_prefix(mut self, provider: impl Into<String>, prefix: impl AsRef<str>) -> Self {
+        self.prefixes.insert(
+            provider.into(),
+            prefix.as_ref().trim_matches('/').to_string(),
+        );
+        self
+    }
+
+    /// `provider` の保存先ディレクトリを取得する。
+    pub fn prefix(&self, provider: &str) -> &str {
+        self.prefixes
+            .get(provider)
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_CONTENT_PATH_PREFIX)
+    }
+
+    /// コンテンツIDからストレージパスを生成する。
+    pub fn content_path(&self, provider: &str, content_id: &ContentId) -> String {
+        let prefix = self.prefix(provider);
+        if prefix.is_empty() {
+            format!("{provider}://{}.json", content_id.as_str())
+        } else {
+            format!("{provider}://{prefix}/{}.json", content_id.as_str())
+        }
+    }
+
+    /// 名前空間 `namespace` に属するコンテンツのストレージパスを生成する。
+    pub fn namespaced_content_path(
+        &self,
+        provider: &str,
+        namespace: &Namespace,
+        content_id: &ContentId,
+    ) -> String {
+        let prefix = self.prefix(provider);
+        let prefix = if prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{prefix}/")
+        };
+        format!(
+            "{provider}://{prefix}namespaces/{namespace}/{}.json",
+            content_id.as_str()
+        )
+    }
+
+    /// SHA-256 が `ciphertext_sha256` のチャンクの暗号文のストレージパスを生成する。
+    pub fn chunk_path(
+        &self,
+        provider: &str,
+        namespace: Option<&Namespace>,
+        ciphertext_sha256: &str,
+    ) -> String {
+        let mut dir = self.prefix(provider).to_string();
+        if let Some(namespace) = namespace {
+            if !dir.is_empty() {
+                dir.push('/');
+            }
+            dir.push_str(&format!("namespaces/{namespace}"));
+        }
+        if dir.is_empty() {
+            format!("{provider}://chunks/{ciphertext_sha256}.bin")
+        } else {
+            format!("{provider}://{dir}/chunks/{ciphertext_sha256}.bin")
+        }
+    }
+}
+
+/// ランタイム外や current_thread ランタイム上での呼び出しに使うランタイム。
+///
+/// ドロップ時に非同期コンテキスト内だと panic するため、プロセス終了まで保持する。
+fn fallback_runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("monas-filesync-bridge")
+            .enable_all()
+            .build()
+            .expect("failed to build filesync bridge runtime")
+    })
+}
+
+/// 同期コンテキストから非同期のストレージ操作を完了まで待つ。
+fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| handle.block_on(future))
+        }
+        // current_thread ランタイムでは block_in_place が使えず、同じスレッドで
+        // block_on するとランタイムの入れ子になるため、別スレッドで待つ。
+        Ok(_) => std::thread::scope(|scope| {
+            scope
+                .spawn(|| fallback_runtime().block_on(future))
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        }),
+        Err(_) => fallback_runtime().block_on(future),
+    }
+}
+
+/// [`MultiStorageRepository`] の構成。
+///
+/// プレゼンテーション層や SDK から、どのプロバイダーにどのパスで保存するかを
+/// まとめて指定するために使う。
+#[derive(Debug, Clone)]
+pub struct ContentStorageConfig {
+    /// 登録するストレージプロバイダーの設定
+    pub filesync: FilesyncConfig,
+    /// `ContentRepository` トレイト経由の保存・取得に使うプロバイダー
+    pub default_provider: String,
+    /// 認証情報の保存先（`None` の場合は永続化しない）
+    pub credentials_path: Option<PathBuf>,
+    /// プロバイダーごとの保存先パス
+    pub path_mapping: ProviderPathMapping,
+    /// 取得したコンテンツのキャッシュ（既定ではキャッシュしない）。
+    ///
+    /// [`build`](Self::build) では使わず、リポジトリを
+    /// [`CachingContentRepository`](crate::infrastructure::content_cache::CachingContentRepository)
+    /// で包む側が参照する。
+    pub cache: ContentCacheConfig,
+    /// 大きなコンテンツのチャンク分割の方針（既定では分割しない）。
+    ///
+    /// [`build`](Self::build) では使わず、コンテンツを作成・更新するサービス側が参照する。
+    /// リポジトリはこの設定によらず、チャンク分割したコンテンツをチャンクごとに保存する。
+    pub chunking: ChunkingPolicy,
+    /// コンテンツのサイズと名前空間ごとの合計サイズの上限（既定は [`ContentLimits::standard`]）。
+    ///
+    /// [`build`](Self::build) では使わず、クォータを適用するサービス側が参照する。
+    pub limits: ContentLimits,
+    /// CEK を導出するアカウントのマスター鍵（`None` の場合はランダムな CEK を保存する）。
+    ///
+    /// [`build`](Self::build) では使わず、CEK を生成・保存するサービス側が参照する。
+    pub master_key: Option<AccountMasterKey>,
+    /// name / path を暗号化して保存する場合の鍵の選択（`None` の場合は平文で保存する）。
+    ///
+    /// [`build`](Self::build) では使わず、リポジトリを
+    /// [`MetadataEncryptingRepository`](crate::infrastructure::metadata_encryption::MetadataEncryptingRepository)
+    /// で包む側が参照する。
+    pub metadata_encryption: Option<MetadataKeyMode>,
+    /// コンテンツ一覧などサーバーの状態を保存する sled DB の保存先
+    /// （`None` の場合は永続化せず、再起動で失われる）
+    pub data_dir: Option<PathBuf>,
+}
+
+impl Default for ContentStorageConfig {
+    fn default() -> Self {
+        Self {
+            filesync: FilesyncConfig::default(),
+            default_provider: "local".to_string(),
+            credentials_path: None,
+            path_mapping: ProviderPathMapping::default(),
+            cache: ContentCacheConfig::default(),
+            chunking: ChunkingPolicy::disabled(),
+            limits: ContentLimits::standard(),
+            master_key: None,
+            metadata_encryption: None,
+            data_dir: None,
+        }
+    }
+}
+
+impl ContentStorageConfig {
+    /// 環境変数から構成を読み込む（未設定の項目は既定値）。
+    ///
+    /// - プロバイダーの設定: [`FilesyncConfig::from_env`]（`MONAS_LOCAL_BASE_PATH` など）
+    /// - `MONAS_CONTENT_STORAGE_PROVIDER`: デフォルトプロバイダー
+    /// - `MONAS_CONTENT_CREDENTIALS_PATH`: 認証情報の保存先
+    /// - `MONAS_CONTENT_PATH_PREFIX`: デフォルトプロバイダーの保存先ディレクトリ
+    /// - `MONAS_CONTENT_DATA_DIR`: サーバーの状態を保存するディレクトリ
+    ///
+    /// キャッシュ・チャンク分割・上限・マスター鍵・メタデータの暗号化の設定は読み込まない
+    /// （[`ContentCacheConfig::from_env`]・[`ChunkingPolicy::from_env`]・
+    /// [`ContentLimits::from_env`]・[`AccountMasterKey::from_env`]・
+    /// [`MetadataKeyMode::from_env`] を使う）。
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup<F>(mut lookup: F) -> Self
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        let mut config = Self {
+            filesync: FilesyncConfig::from_env(),
+            ..Self::default()
+        };
+        if let Some(provider) = lookup("MONAS_CONTENT_STORAGE_PROVIDER") {
+            config.default_provider = provider;
+        }
+        config.credentials_path = lookup("MONAS_CONTENT_CREDENTIALS_PATH").map(PathBuf::from);
+        config.data_dir = lookup("MONAS_CONTENT_DATA_DIR").map(PathBuf::from);
+        if let Some(prefix) = lookup("MONAS_CONTENT_PATH_PREFIX") {
+            config.path_mapping = config
+                .path_mapping
+                .with_prefix(config.default_provider.clone(), prefix);
+        }
+        config
+    }
+
+    /// 構成に従って [`MultiStorageRepository`] を作る。
+    ///
+    /// デフォルトプロバイダーが登録されていない場合はエラーを返す。
+    pub fn build(&self) -> Result<MultiStorageRepository, ContentRepositoryError> {
+        let registry = Arc::new(FetcherRegistry::from_config(&self.filesync));
+        if registry.resolve(&self.default_provider).is_none() {
+            return Err(ContentRepositoryError::Storage(format!(
+                "unknown storage provider: {}",
+                self.default_provider
+            )));
+        }
+        let repo = match &self.credentials_path {
+            Some(path) => {
+                MultiStorageRepository::new(registry, self.default_provider.clone(), path)?
+            }
+            None => MultiStorageRepository::in_memory(registry, self.default_provider.clone()),
+        };
+        repo.set_path_mapping(self.path_mapping.clone())?;
+        Ok(repo)
+    }
+
+    /// サーバーの状態を保存する sled DB を開く。
+    ///
+    /// `data_dir` が `None` の場合は、閉じると消える一時 DB を返す。
+    pub fn open_state_db(&self) -> Result<sled::Db, ContentRepositoryError> {
+        let config = match &self.data_dir {
+            Some(path) => sled::Config::new().path(path),
+            None => sled::Config::new().temporary(true),
+        };
+        config
+            .open()
+            .map_err(|e| ContentRepositoryError::Storage(e.to_string()))
+    }
+}
 
 /// 複数のストレージプロバイダーを使い分けられる ContentRepository 実装。
 ///
@@ -53,6 +334,8 @@ use tokio::runtime::Handle;
 #[derive(Clone)]
 pub struct MultiStorageRepository {
     inner: Arc<MultiStorageRepositoryInner>,
+    /// 保存先を分ける名前空間（`None` の場合は名前空間に属さない）
+    namespace: Option<Namespace>,
 }
 
 /// MultiStorageRepository の内部状態
@@ -64,6 +347,8 @@ struct MultiStorageRepositoryInner {
     default_provider: RwLock<String>,
     /// 認証情報の保存先パス（None の場合は永続化しない）
     credentials_path: Option<PathBuf>,
+    /// プロバイダーごとの保存先パス
+    path_mapping: RwLock<ProviderPathMapping>,
 }
 
 /// ファイルに保存する認証情報の形式
@@ -148,10 +433,43 @@ impl MultiStorageRepository {
                 auth_sessions: RwLock::new(auth_sessions),
                 default_provider: RwLock::new(default_provider),
                 credentials_path,
+                path_mapping: RwLock::new(ProviderPathMapping::default()),
             }),
+            namespace: None,
         }
     }
 
+    /// 名前空間 `namespace` のコンテンツだけを保存・取得するリポジトリを返す。
+    ///
+    /// プロバイダーの認証セッションと保存先パスの設定は元のリポジトリと共有する。
+    /// 別の名前空間（名前空間に属さないものを含む）のコンテンツは見つからない。
+    pub fn scoped(&self, namespace: &Namespace) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            namespace: Some(namespace.clone()),
+        }
+    }
+
+    /// このリポジトリの名前空間。
+    pub fn namespace(&self) -> Option<&Namespace> {
+        self.namespace.as_ref()
+    }
+
+    /// プロバイダーごとの保存先パスを差し替える。
+    ///
+    /// 既に保存済みのコンテンツは移動しないため、運用開始前に設定すること。
+    pub fn set_path_mapping(
+        &self,
+        mapping: ProviderPathMapping,
+    ) -> Result<(), ContentRepositoryError> {
+        let mut current =
+            self.inner.path_mapping.write().map_err(|e| {
+                ContentRepositoryError::Storage(format!("failed to acquire lock: {e}"))
+            })?;
+        *current = mapping;
+        Ok(())
+    }
+
     /// ストレージプロバイダーを接続する（認証セッションを登録）。
     ///
     /// ユーザーがUIで「Google Drive を接続」などを選択したときに呼び出す。
@@ -306,23 +624,49 @@ impl MultiStorageRepository {
     }
 
     /// 指定したストレージプロバイダーにコンテンツを保存する。
+    ///
+    /// ピン留めされたコンテンツを、暗号文を持たない状態（削除済みなど）で
+    /// 上書きしようとした場合はエラーを返し、保存済みの暗号文を保持する。
     pub fn save_to(
         &self,
         provider: &str,
         content_id: &ContentId,
         content: &Content,
     ) -> Result<(), ContentRepositoryError> {
-        let (storage_provider, auth) = self.get_provider_and_auth(provider)?;
-        let path = Self::content_path(provider, content_id);
+        if content.encrypted_content().is_none() {
+            if let Some(existing) = self.find_from(provider, content_id)? {
+                if existing.is_pinned() {
+                    return Err(ContentRepositoryError::Storage(format!(
+                        "content {} is pinned and must be retained",
+                        content_id.as_str()
+                    )));
+                }
+            }
+        }
 
-        let data = serde_json::to_vec(content)
-            .map_err(|e| ContentRepositoryError::Storage(format!("serialization error: {e}")))?;
+        let (storage_provider, auth) = self.get_provider_and_auth(provider)?;
+        let path = self.content_path(provider, content_id)?;
+
+        let data = match chunk_blobs(content)? {
+            Some(chunks) => {
+                for (ciphertext_sha256, chunk) in chunks {
+                    let chunk_path = self.chunk_path(provider, ciphertext_sha256)?;
+                    // 同じアドレスのチャンクが保存済みなら書き込み直さない
+                    let stored = block_on(storage_provider.size_and_mtime(&auth, &chunk_path));
+                    if matches!(stored, Ok((size, _)) if size == chunk.len() as u64) {
+                        continue;
+                    }
+                    block_on(storage_provider.save(&auth, &chunk_path, chunk))
+                        .map_err(|e| ContentRepositoryError::Storage(e.message))?;
+                }
+                serde_json::to_vec(&content.without_encrypted_content())
+            }
+            None => serde_json::to_vec(content),
+        }
+        .map_err(|e| ContentRepositoryError::Storage(format!("serialization error: {e}")))?;
 
-        tokio::task::block_in_place(|| {
-            Handle::current()
-                .block_on(async { storage_provider.save(&auth, &path, &data).await })
-                .map_err(|e| ContentRepositoryError::Storage(e.message))
-        })
+        block_on(storage_provider.save(&auth, &path, &data))
+            .map_err(|e| ContentRepositoryError::Storage(e.message))
     }
 
     /// 指定したストレージプロバイダーからコンテンツを取得する。
@@ -332,18 +676,17 @@ impl MultiStorageRepository {
         content_id: &ContentId,
     ) -> Result<Option<Content>, ContentRepositoryError> {
         let (storage_provider, auth) = self.get_provider_and_auth(provider)?;
-        let path = Self::content_path(provider, content_id);
+        let path = self.content_path(provider, content_id)?;
 
-        let result = tokio::task::block_in_place(|| {
-            Handle::current().block_on(async { storage_provider.fetch(&auth, &path).await })
-        });
+        let result = block_on(storage_provider.fetch(&auth, &path));
 
         match result {
             Ok(bytes) => {
                 let content: Content = serde_json::from_slice(&bytes).map_err(|e| {
                     ContentRepositoryError::Storage(format!("deserialization error: {e}"))
                 })?;
-                Ok(Some(content))
+                self.load_chunks(provider, storage_provider.as_ref(), &auth, content)
+                    .map(Some)
             }
             Err(e) => {
                 if e.message.contains("failed to read") || e.message.contains("not found") {
@@ -355,6 +698,41 @@ impl MultiStorageRepository {
         }
     }
 
+    /// チャンクごとに保存した暗号文を読み込み、`content` に戻す。
+    ///
+    /// チャンク分割していないコンテンツや、暗号文を持たない（削除済みなど）コンテンツは
+    /// そのまま返す。読み込んだチャンクはマニフェストの SHA-256 と照合する。
+    fn load_chunks(
+        &self,
+        provider: &str,
+        storage_provider: &dyn StorageProvider,
+        auth: &AuthSession,
+        content: Content,
+    ) -> Result<Content, ContentRepositoryError> {
+        let Some(manifest) = content.metadata().chunk_manifest() else {
+            return Ok(content);
+        };
+        if content.encrypted_content().is_some() || content.is_deleted() {
+            return Ok(content);
+        }
+
+        let mut chunks = Vec::with_capacity(manifest.chunks().len());
+        for entry in manifest.chunks() {
+            let chunk_path = self.chunk_path(provider, &entry.ciphertext_sha256)?;
+            let chunk = block_on(storage_provider.fetch(auth, &chunk_path))
+                .map_err(|e| ContentRepositoryError::Storage(e.message))?;
+            if hex::encode(Sha256::digest(&chunk)) != entry.ciphertext_sha256 {
+                return Err(ContentRepositoryError::Storage(format!(
+                    "chunk {} is corrupted",
+                    entry.ciphertext_sha256
+                )));
+            }
+            chunks.push(chunk);
+        }
+        let ciphertext = join_chunked_ciphertext(chunks.iter().map(Vec::as_slice));
+        Ok(content.with_encrypted_content(ciphertext))
+    }
+
     /// プロバイダーと認証セッションを取得する。
     fn get_provider_and_auth(
         &self,
@@ -381,9 +759,70 @@ impl MultiStorageRepository {
     }
 
     /// コンテンツIDからストレージパスを生成する。
-    fn content_path(provider: &str, content_id: &ContentId) -> String {
-        format!("{}://content/{}.json", provider, content_id.as_str())
+    fn content_path(
+        &self,
+        provider: &str,
+        content_id: &ContentId,
+    ) -> Result<String, ContentRepositoryError> {
+        let mapping =
+            self.inner.path_mapping.read().map_err(|e| {
+                ContentRepositoryError::Storage(format!("failed to acquire lock: {e}"))
+            })?;
+        Ok(match &self.namespace {
+            Some(namespace) => mapping.namespaced_content_path(provider, namespace, content_id),
+            None => mapping.content_path(provider, content_id),
+        })
+    }
+
+    /// チャンクの暗号文のストレージパスを生成する。
+    fn chunk_path(
+        &self,
+        provider: &str,
+        ciphertext_sha256: &str,
+    ) -> Result<String, ContentRepositoryError> {
+        let mapping =
+            self.inner.path_mapping.read().map_err(|e| {
+                ContentRepositoryError::Storage(format!("failed to acquire lock: {e}"))
+            })?;
+        Ok(mapping.chunk_path(provider, self.namespace.as_ref(), ciphertext_sha256))
+    }
+}
+
+/// チャンクの保存先のアドレス（暗号文の SHA-256）と、チャンクの暗号文の組。
+type ChunkBlob<'a> = (&'a str, &'a [u8]);
+
+/// チャンク分割したコンテンツの暗号文を、`(SHA-256, チャンクの暗号文)` の組に分ける。
+///
+/// チャンク分割していない、または暗号文を持たないコンテンツは `None`。
+fn chunk_blobs(content: &Content) -> Result<Option<Vec<ChunkBlob<'_>>>, ContentRepositoryError> {
+    let (Some(manifest), Some(ciphertext)) = (
+        content.metadata().chunk_manifest(),
+        content.encrypted_content(),
+    ) else {
+        return Ok(None);
+    };
+    let invalid = || {
+        ContentRepositoryError::Storage(
+            "chunked ciphertext does not match chunk manifest".to_string(),
+        )
+    };
+    let chunks = split_chunked_ciphertext(ciphertext).ok_or_else(invalid)?;
+    if chunks.len() != manifest.chunks().len() {
+        return Err(invalid());
     }
+    chunks
+        .into_iter()
+        .zip(manifest.chunks())
+        .map(|(chunk, entry)| {
+            // 保存先のアドレスと中身を一致させる
+            if hex::encode(Sha256::digest(chunk)) == entry.ciphertext_sha256 {
+                Ok((entry.ciphertext_sha256.as_str(), chunk))
+            } else {
+                Err(invalid())
+            }
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
 }
 
 /// ContentRepository トレイトの実装（デフォルトプロバイダーを使用）
@@ -404,6 +843,11 @@ impl ContentRepository for MultiStorageRepository {
         let default = MultiStorageContentRepository::default_provider(self)?;
         self.find_from(&default, content_id)
     }
+
+    /// コンテンツはプロバイダーへ直接書き込むため、認証情報だけを保存し直す。
+    fn flush(&self) -> Result<(), ContentRepositoryError> {
+        self.save_credentials()
+    }
 }
 
 /// MultiStorageContentRepository トレイトの実装
@@ -472,8 +916,10 @@ base_path = "{}""#,
         Arc::new(monas_filesync::FetcherRegistry::from_config(&config))
     }
 
-    /// テスト用の Content を作成する。
-    fn create_test_content(id: &str) -> Content {
+    /// テスト用の Content を作成する。ContentId はラベルから求める。
+    fn create_test_content(label: &str) -> Content {
+        let id = ContentId::for_test(label);
+        let id = id.as_str();
         serde_json::from_value(serde_json::json!({
             "raw_id": id,
             "series_id": id,
@@ -520,7 +966,7 @@ base_path = "{}""#,
         let registry = create_test_registry(&temp_dir);
         let repo = MultiStorageRepository::in_memory(registry, "local");
 
-        let content_id = ContentId::new("multi-test-123".to_string());
+        let content_id = ContentId::for_test("multi-test-123");
         let content = create_test_content("multi-test-123");
 
         // デフォルトプロバイダー（local）に保存
@@ -536,13 +982,39 @@ base_path = "{}""#,
         assert_eq!(found.raw_id(), content.raw_id());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_multi_storage_repository_retains_pinned_content() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let registry = create_test_registry(&temp_dir);
+        let repo = MultiStorageRepository::in_memory(registry, "local");
+
+        let content_id = ContentId::for_test("pinned-123");
+        let pinned = create_test_content("pinned-123").pin().unwrap();
+        repo.save(&content_id, &pinned)
+            .expect("failed to save content");
+
+        // 暗号文を持たない状態での上書きは拒否される
+        let stripped = pinned.without_encrypted_content();
+        assert!(repo.save(&content_id, &stripped).is_err());
+        let found = repo.find_by_id(&content_id).unwrap().unwrap();
+        assert!(found.is_pinned());
+        assert_eq!(found.encrypted_content().unwrap(), &vec![1u8, 2, 3, 4]);
+
+        // ピン留めを解除すれば取り除ける
+        repo.save(&content_id, &pinned.unpin()).unwrap();
+        let (deleted, _) = pinned.unpin().delete().unwrap();
+        repo.save(&content_id, &deleted)
+            .expect("unpinned content can be removed");
+        assert!(repo.find_by_id(&content_id).unwrap().unwrap().is_deleted());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_multi_storage_repository_save_to_specific_provider() {
         let temp_dir = TempDir::new().expect("failed to create temp dir");
         let registry = create_test_registry(&temp_dir);
         let repo = MultiStorageRepository::in_memory(registry, "local");
 
-        let content_id = ContentId::new("specific-provider-test".to_string());
+        let content_id = ContentId::for_test("specific-provider-test");
         let content = create_test_content("specific-provider-test");
 
         // 明示的に "local" を指定して保存
@@ -563,7 +1035,7 @@ base_path = "{}""#,
         let registry = Arc::new(init_registry_default());
         let repo = MultiStorageRepository::in_memory(registry, "local");
 
-        let content_id = ContentId::new("test".to_string());
+        let content_id = ContentId::for_test("test");
         let content = create_test_content("test");
 
         // google-drive は接続していない
@@ -648,6 +1120,21 @@ base_path = "{}""#,
         assert!(connected.contains(&"google-drive".to_string()));
     }
 
+    #[test]
+    fn test_multi_storage_repository_flush_writes_credentials_file() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let credentials_path = temp_dir.path().join("credentials.json");
+
+        let registry = Arc::new(init_registry_default());
+        let repo = MultiStorageRepository::new(registry, "local", &credentials_path)
+            .expect("failed to create repo");
+        assert!(!credentials_path.exists());
+
+        // 終了時の flush で認証情報ファイルが書き出される
+        ContentRepository::flush(&repo).unwrap();
+        assert!(credentials_path.exists());
+    }
+
     #[test]
     fn test_multi_storage_repository_persistence_disconnect_removes_from_file() {
         let temp_dir = TempDir::new().expect("failed to create temp dir");
@@ -698,4 +1185,229 @@ base_path = "{}""#,
         // 切断してもエラーにならない
         repo.disconnect("google-drive").unwrap();
     }
+
+    // ========================================================================
+    // 構成・パス対応・同期/非同期の橋渡しのテスト
+    // ========================================================================
+
+    /// 一時ディレクトリを `local` の base_path とした構成を作る。
+    fn create_test_config(temp_dir: &TempDir) -> ContentStorageConfig {
+        let mut config = ContentStorageConfig::default();
+        config.filesync.local.base_path = Some(temp_dir.path().display().to_string());
+        config
+    }
+
+    #[test]
+    fn test_provider_path_mapping() {
+        let content_id = ContentId::for_test("mapping");
+        let mapping = ProviderPathMapping::new()
+            .with_prefix("local", "/monas/blobs/")
+            .with_prefix("google-drive", "");
+
+        assert_eq!(
+            mapping.content_path("local", &content_id),
+            format!("local://monas/blobs/{}.json", content_id.as_str())
+        );
+        assert_eq!(
+            mapping.content_path("google-drive", &content_id),
+            format!("google-drive://{}.json", content_id.as_str())
+        );
+        assert_eq!(
+            mapping.content_path("onedrive", &content_id),
+            format!("onedrive://content/{}.json", content_id.as_str())
+        );
+        let namespace = Namespace::new("alice").unwrap();
+        assert_eq!(
+            mapping.namespaced_content_path("local", &namespace, &content_id),
+            format!(
+                "local://monas/blobs/namespaces/alice/{}.json",
+                content_id.as_str()
+            )
+        );
+        assert_eq!(
+            mapping.namespaced_content_path("google-drive", &namespace, &content_id),
+            format!(
+                "google-drive://namespaces/alice/{}.json",
+                content_id.as_str()
+            )
+        );
+        assert_eq!(
+            mapping.chunk_path("local", None, "ab12"),
+            "local://monas/blobs/chunks/ab12.bin"
+        );
+        assert_eq!(
+            mapping.chunk_path("google-drive", Some(&namespace), "ab12"),
+            "google-drive://namespaces/alice/chunks/ab12.bin"
+        );
+    }
+
+    #[test]
+    fn test_content_storage_config_saves_under_mapped_path_without_runtime() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let mut config = create_test_config(&temp_dir);
+        config.path_mapping = ProviderPathMapping::new().with_prefix("local", "blobs");
+        let repo = config.build().expect("failed to build repo");
+
+        let content_id = ContentId::for_test("config-test");
+        let content = create_test_content("config-test");
+
+        // tokio ランタイム外からでも保存・取得できる
+        repo.save(&content_id, &content).unwrap();
+        assert!(temp_dir
+            .path()
+            .join("blobs")
+            .join(format!("{}.json", content_id.as_str()))
+            .exists());
+        let found = repo.find_by_id(&content_id).unwrap().unwrap();
+        assert_eq!(found.raw_id(), content.raw_id());
+    }
+
+    #[test]
+    fn test_scoped_repository_isolates_namespaces() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let repo = create_test_config(&temp_dir).build().unwrap();
+        let alice = repo.scoped(&Namespace::new("alice").unwrap());
+        let bob = repo.scoped(&Namespace::new("bob").unwrap());
+
+        let content_id = ContentId::for_test("tenant-test");
+        let content = create_test_content("tenant-test");
+        alice.save(&content_id, &content).unwrap();
+
+        assert!(temp_dir
+            .path()
+            .join("content/namespaces/alice")
+            .join(format!("{}.json", content_id.as_str()))
+            .exists());
+        assert!(alice.find_by_id(&content_id).unwrap().is_some());
+        // 別の名前空間や、名前空間に属さないリポジトリからは見えない
+        assert!(bob.find_by_id(&content_id).unwrap().is_none());
+        assert!(repo.find_by_id(&content_id).unwrap().is_none());
+        assert_eq!(alice.namespace().map(Namespace::as_str), Some("alice"));
+        assert!(repo.namespace().is_none());
+    }
+
+    #[test]
+    fn test_chunked_content_stores_each_chunk_once() {
+        use crate::domain::content::encryption::ContentEncryptionKeyGenerator;
+        use crate::infrastructure::content_id::Sha256ContentIdGenerator;
+        use crate::infrastructure::encryption::{
+            Aes256CtrContentEncryption, OsRngContentEncryptionKeyGenerator,
+        };
+
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let repo = create_test_config(&temp_dir).build().unwrap();
+        let key = OsRngContentEncryptionKeyGenerator.generate();
+        let encryption = Aes256CtrContentEncryption;
+        let (content, _) = Content::create_chunked(
+            "large".to_string(),
+            b"aaaabbbbcccc".to_vec(),
+            "large.bin".to_string(),
+            None,
+            &Sha256ContentIdGenerator,
+            &key,
+            &encryption,
+            4,
+        )
+        .unwrap();
+        let content_id = content.raw_id().clone();
+        repo.save(&content_id, &content).unwrap();
+
+        let chunk_count = || {
+            std::fs::read_dir(temp_dir.path().join("content/chunks"))
+                .unwrap()
+                .count()
+        };
+        assert_eq!(chunk_count(), 3);
+        // コンテンツの JSON には暗号文を含めない
+        let json: serde_json::Value = serde_json::from_slice(
+            &std::fs::read(
+                temp_dir
+                    .path()
+                    .join(format!("content/{}.json", content_id.as_str())),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(json["encrypted_content"].is_null());
+
+        // 変更したチャンクだけが新たに書き込まれる
+        let (updated, _) = content
+            .update_content(
+                b"aaaaXXXXcccc".to_vec(),
+                &Sha256ContentIdGenerator,
+                &key,
+                &encryption,
+            )
+            .unwrap();
+        repo.save(&content_id, &updated).unwrap();
+        assert_eq!(chunk_count(), 4);
+
+        let found = repo.find_by_id(&content_id).unwrap().unwrap();
+        assert_eq!(found.encrypted_content(), updated.encrypted_content());
+        assert_eq!(
+            found.decrypt(&key, &encryption).unwrap(),
+            b"aaaaXXXXcccc".to_vec()
+        );
+
+        // 壊れたチャンクは読み込み時に検出する
+        let entry = &updated.metadata().chunk_manifest().unwrap().chunks()[1];
+        std::fs::write(
+            temp_dir
+                .path()
+                .join(format!("content/chunks/{}.bin", entry.ciphertext_sha256)),
+            b"corrupted",
+        )
+        .unwrap();
+        assert!(repo.find_by_id(&content_id).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_multi_storage_repository_works_on_current_thread_runtime() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let repo = create_test_config(&temp_dir).build().unwrap();
+
+        let content_id = ContentId::for_test("current-thread");
+        let content = create_test_content("current-thread");
+
+        repo.save(&content_id, &content).unwrap();
+        assert!(repo.find_by_id(&content_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_content_storage_config_rejects_unknown_provider() {
+        let config = ContentStorageConfig {
+            default_provider: "unknown-provider".into(),
+            ..ContentStorageConfig::default()
+        };
+        let err = config.build().err().expect("unknown provider must fail");
+        assert!(err.to_string().contains("unknown storage provider"));
+    }
+
+    #[test]
+    fn test_content_storage_config_from_lookup() {
+        let vars: HashMap<&str, &str> = [
+            ("MONAS_CONTENT_STORAGE_PROVIDER", "google-drive"),
+            (
+                "MONAS_CONTENT_CREDENTIALS_PATH",
+                "/tmp/monas/credentials.json",
+            ),
+            ("MONAS_CONTENT_PATH_PREFIX", "monas"),
+            ("MONAS_CONTENT_DATA_DIR", "/tmp/monas/state"),
+        ]
+        .into_iter()
+        .collect();
+        let config = ContentStorageConfig::from_lookup(|key| vars.get(key).map(|v| v.to_string()));
+
+        assert_eq!(config.default_provider, "google-drive");
+        assert_eq!(
+            config.credentials_path,
+            Some(PathBuf::from("/tmp/monas/credentials.json"))
+        );
+        assert_eq!(config.path_mapping.prefix("google-drive"), "monas");
+        assert_eq!(config.data_dir, Some(PathBuf::from("/tmp/monas/state")));
+        assert_eq!(
+            config.path_mapping.prefix("local"),
+            DEFAULT_CONTENT_PATH_PREFIX
+        );
+    }
 }
@@ -0,0 +1,580 @@
+//! ファイルシステム上に暗号文を保存する ContentRepository 実装。
+//!
+//! ## ディレクトリ構成
+//!
+//! ```text
+//! <root>/
+//!   blobs/<ab>/<cd>/<sha256(暗号文)>   暗号文（コンテンツアドレス）
+//!   index/                             sled DB（ContentId → メタデータ + blob 参照）
+//!   tmp/                               書き込み途中のファイル
+//! ```
+//!
+//! - 暗号文は SHA-256 をファイル名とするため、同じ暗号文は 1 ファイルにまとまる。
+//! - blob は一時ファイルに書き込んでから rename するため、途中で落ちても
+//!   不完全な blob が参照されることはない。
+//! - blob の参照数は開いたときにインデックスから数え直し、保存がコミットされた後、
+//!   どのエントリからも参照されなくなった blob を削除する。同じ暗号文を持つ別の
+//!   コンテンツがあれば、その blob は残る。
+//! - 系列の新しい版を保存すると、置き換えられた旧版はメタデータだけを残して暗号文を手放す。
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::application_service::content_service::{
+    ContentListFilter, ContentRepository, ContentRepositoryError, ListableContentRepository,
+};
+use crate::domain::content::Content;
+use crate::domain::content_id::ContentId;
+
+const BLOBS_DIR: &str = "blobs";
+const INDEX_DIR: &str = "index";
+const TMP_DIR: &str = "tmp";
+
+/// インデックスに保存するエントリ。
+#[derive(Serialize, Deserialize)]
+struct IndexEntry {
+    /// 暗号文を取り除いたコンテンツ。
+    content: Content,
+    /// 暗号文 blob の SHA-256（hex）。暗号文を持たないコンテンツでは `None`。
+    blob: Option<String>,
+}
+
+/// 系列の最新版。
+struct LatestVersion {
+    content_id: String,
+    updated_at: DateTime<Utc>,
+}
+
+/// blob ごとの参照数と系列ごとの最新版。インデックスから組み立て直せるため、
+/// メモリ上にだけ持つ。
+#[derive(Default)]
+struct BlobRefs {
+    counts: HashMap<String, u64>,
+    latest: HashMap<ContentId, LatestVersion>,
+}
+
+impl BlobRefs {
+    fn is_latest(&self, content_id: &ContentId, content: &Content) -> bool {
+        match self.latest.get(content.series_id()) {
+            Some(latest) => {
+                latest.content_id == content_id.as_str()
+                    || content.metadata().updated_at() >= latest.updated_at
+            }
+            None => true,
+        }
+    }
+
+    fn set_latest(&mut self, content_id: &ContentId, content: &Content) {
+        self.latest.insert(
+            content.series_id().clone(),
+            LatestVersion {
+                content_id: content_id.as_str().to_string(),
+                updated_at: content.metadata().updated_at(),
+            },
+        );
+    }
+}
+
+/// 暗号文をファイル、メタデータを sled のインデックスに保存する ContentRepository。
+///
+/// KV ストアではなくディスク上のファイルとして blob を持ちたいノード運用者向け。
+/// 更新で置き換えられた版は `find_by_id` で見つかるが、暗号文を持たない。
+#[derive(Clone)]
+pub struct FsContentRepository {
+    root: PathBuf,
+    index: sled::Db,
+    /// 保存は参照数の更新と blob の削除をまとめて行うため、このロックで直列化する。
+    refs: Arc<Mutex<BlobRefs>>,
+}
+
+impl FsContentRepository {
+    /// `root` 以下にリポジトリを開く。ディレクトリが無ければ作成する。
+    pub fn open<P: AsRef<Path>>(root: P) -> Result<Self, ContentRepositoryError> {
+        let root = root.as_ref().to_path_buf();
+        for dir in [BLOBS_DIR, TMP_DIR] {
+            fs::create_dir_all(root.join(dir)).map_err(|e| {
+                ContentRepositoryError::Storage(format!("failed to create {dir} directory: {e}"))
+            })?;
+        }
+        let index = sled::open(root.join(INDEX_DIR))
+            .map_err(|e| ContentRepositoryError::Storage(e.to_string()))?;
+        let repo = Self {
+            root,
+            index,
+            refs: Arc::default(),
+        };
+        repo.rebuild_refs()?;
+        Ok(repo)
+    }
+
+    /// インデックスから blob の参照数と系列の最新版を数え直し、
+    /// 前回の終了までに削除しきれなかった未参照の blob を削除する。
+    fn rebuild_refs(&self) -> Result<(), ContentRepositoryError> {
+        let mut refs = BlobRefs::default();
+        for item in self.index.iter() {
+            let (key, value) = item.map_err(|e| ContentRepositoryError::Storage(e.to_string()))?;
+            let entry = decode_entry(&value)?;
+            if let Some(digest) = entry.blob {
+                *refs.counts.entry(digest).or_default() += 1;
+            }
+            let content_id = ContentId::try_from(String::from_utf8_lossy(&key).into_owned())
+                .map_err(|e| ContentRepositoryError::Storage(e.to_string()))?;
+            if refs.is_latest(&content_id, &entry.content) {
+                refs.set_latest(&content_id, &entry.content);
+            }
+        }
+
+        for shard in read_dirs(&self.root.join(BLOBS_DIR))? {
+            for dir in read_dirs(&shard)? {
+                for blob in read_dirs(&dir)? {
+                    let referenced = blob
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|digest| refs.counts.contains_key(digest));
+                    if !referenced {
+                        self.remove_blob_file(&blob);
+                    }
+                }
+            }
+        }
+
+        *self
+            .refs
+            .lock()
+            .map_err(|e| ContentRepositoryError::Storage(e.to_string()))? = refs;
+        Ok(())
+    }
+
+    fn load_entry(&self, key: &str) -> Result<Option<IndexEntry>, ContentRepositoryError> {
+        self.index
+            .get(key)
+            .map_err(|e| ContentRepositoryError::Storage(e.to_string()))?
+            .map(|value| decode_entry(&value))
+            .transpose()
+    }
+
+    /// 参照されなくなった blob ファイルを削除する。失敗しても保存自体は成功しているため、
+    /// 警告を残して次に開いたときの削除に任せる。
+    fn remove_blob_file(&self, path: &Path) {
+        if let Err(e) = fs::remove_file(path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %e,
+                    "failed to remove unreferenced blob"
+                );
+            }
+        }
+    }
+
+    /// blob の SHA-256（hex）からファイルパスを求める。
+    fn blob_path(&self, digest: &str) -> PathBuf {
+        self.root
+            .join(BLOBS_DIR)
+            .join(&digest[..2])
+            .join(&digest[2..4])
+            .join(digest)
+    }
+
+    /// 暗号文を blob として書き込み、その SHA-256（hex）を返す。
+    fn write_blob(&self, data: &[u8]) -> Result<String, ContentRepositoryError> {
+        let digest = hex::encode(Sha256::digest(data));
+        let path = self.blob_path(&digest);
+        if path.exists() {
+            return Ok(digest);
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                ContentRepositoryError::Storage(format!("failed to create blob directory: {e}"))
+            })?;
+        }
+
+        let mut suffix = [0u8; 8];
+        rand::thread_rng().fill_bytes(&mut suffix);
+        let tmp_path = self
+            .root
+            .join(TMP_DIR)
+            .join(format!("{digest}.{}", hex::encode(suffix)));
+        let result = (|| {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(data)?;
+            file.sync_all()?;
+            fs::rename(&tmp_path, &path)
+        })();
+        if let Err(e) = result {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(ContentRepositoryError::Storage(format!(
+                "failed to write blob {digest}: {e}"
+            )));
+        }
+        Ok(digest)
+    }
+
+    /// blob を読み込み、SHA-256 が一致することを確認する。
+    fn read_blob(&self, digest: &str) -> Result<Vec<u8>, ContentRepositoryError> {
+        let data = fs::read(self.blob_path(digest)).map_err(|e| {
+            ContentRepositoryError::Storage(format!("failed to read blob {digest}: {e}"))
+        })?;
+        if hex::encode(Sha256::digest(&data)) != digest {
+            return Err(ContentRepositoryError::Storage(format!(
+                "blob {digest} is corrupted"
+            )));
+        }
+        Ok(data)
+    }
+}
+
+impl ContentRepository for FsContentRepository {
+    fn save(
+        &self,
+        content_id: &ContentId,
+        content: &Content,
+    ) -> Result<(), ContentRepositoryError> {
+        let mut refs = self
+            .refs
+            .lock()
+            .map_err(|e| ContentRepositoryError::Storage(e.to_string()))?;
+
+        // blob を先に書くことで、インデックスが存在しない blob を指さないようにする。
+        let blob = content
+            .encrypted_content()
+            .map(|data| self.write_blob(data))
+            .transpose()?;
+        let entry = IndexEntry {
+            content: content.without_encrypted_content(),
+            blob: blob.clone(),
+        };
+        let mut batch = sled::Batch::default();
+        batch.insert(content_id.as_str(), encode_entry(&entry)?);
+
+        // 上書きするエントリと、この保存で置き換えられる旧版が参照していた blob
+        let mut released: Vec<String> = self
+            .load_entry(content_id.as_str())?
+            .and_then(|previous| previous.blob)
+            .into_iter()
+            .collect();
+        let is_latest = refs.is_latest(content_id, content);
+        if let Some(latest) = refs.latest.get(content.series_id()) {
+            if is_latest && latest.content_id != content_id.as_str() {
+                if let Some(mut superseded) = self.load_entry(&latest.content_id)? {
+                    if let Some(digest) = superseded.blob.take() {
+                        batch.insert(latest.content_id.as_str(), encode_entry(&superseded)?);
+                        released.push(digest);
+                    }
+                }
+            }
+        }
+
+        self.index
+            .apply_batch(batch)
+            .map_err(|e| ContentRepositoryError::Storage(e.to_string()))?;
+        self.index
+            .flush()
+            .map_err(|e| ContentRepositoryError::Storage(e.to_string()))?;
+
+        // コミット後に参照数を反映し、どこからも参照されなくなった blob を削除する
+        if let Some(digest) = blob {
+            *refs.counts.entry(digest).or_default() += 1;
+        }
+        if is_latest {
+            refs.set_latest(content_id, content);
+        }
+        for digest in released {
+            let Some(count) = refs.counts.get_mut(&digest) else {
+                continue;
+            };
+            *count -= 1;
+            if *count == 0 {
+                refs.counts.remove(&digest);
+                self.remove_blob_file(&self.blob_path(&digest));
+            }
+        }
+        Ok(())
+    }
+
+    fn find_by_id(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Option<Content>, ContentRepositoryError> {
+        let Some(entry) = self.load_entry(content_id.as_str())? else {
+            return Ok(None);
+        };
+        match entry.blob {
+            Some(digest) => Ok(Some(
+                entry
+                    .content
+                    .with_encrypted_content(self.read_blob(&digest)?),
+            )),
+            None => Ok(Some(entry.content)),
+        }
+    }
+
+    fn flush(&self) -> Result<(), ContentRepositoryError> {
+        self.index
+            .flush()
+            .map_err(|e| ContentRepositoryError::Storage(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl ListableContentRepository for FsContentRepository {
+    /// インデックスだけを走査するため、返すコンテンツに暗号文は含まれない。
+    fn list(&self, filter: ContentListFilter) -> Result<Vec<Content>, ContentRepositoryError> {
+        let mut latest: HashMap<ContentId, Content> = HashMap::new();
+        for item in self.index.iter() {
+            let (_, value) = item.map_err(|e| ContentRepositoryError::Storage(e.to_string()))?;
+            let content = decode_entry(&value)?.content;
+            // 更新前の旧版もインデックスに残るため、系列ごとに最新のものだけを残す
+            let newer = match latest.get(content.series_id()) {
+                Some(current) => content.metadata().updated_at() > current.metadata().updated_at(),
+                None => true,
+            };
+            if newer {
+                latest.insert(content.series_id().clone(), content);
+            }
+        }
+        Ok(latest
+            .into_values()
+            .filter(|content| filter.includes(content.content_status()))
+            .collect())
+    }
+
+    fn list_from(
+        &self,
+        provider: &str,
+        _filter: ContentListFilter,
+    ) -> Result<Vec<Content>, ContentRepositoryError> {
+        Err(ContentRepositoryError::Storage(format!(
+            "provider selection is not supported: {provider}"
+        )))
+    }
+}
+
+fn encode_entry(entry: &IndexEntry) -> Result<Vec<u8>, ContentRepositoryError> {
+    serde_json::to_vec(entry)
+        .map_err(|e| ContentRepositoryError::Storage(format!("serialization error: {e}")))
+}
+
+fn decode_entry(value: &[u8]) -> Result<IndexEntry, ContentRepositoryError> {
+    serde_json::from_slice(value)
+        .map_err(|e| ContentRepositoryError::Storage(format!("deserialization error: {e}")))
+}
+
+/// `dir` 直下のエントリのパス。
+fn read_dirs(dir: &Path) -> Result<Vec<PathBuf>, ContentRepositoryError> {
+    fs::read_dir(dir)
+        .and_then(|entries| entries.map(|entry| entry.map(|e| e.path())).collect())
+        .map_err(|e| {
+            ContentRepositoryError::Storage(format!("failed to read {}: {e}", dir.display()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::content::encryption::ContentEncryptionKey;
+    use crate::infrastructure::content_id::Sha256ContentIdGenerator;
+    use crate::infrastructure::encryption::Aes256CtrContentEncryption;
+    use crate::infrastructure::test_support::reopen;
+    use tempfile::TempDir;
+
+    fn content(raw: &[u8]) -> Content {
+        Content::create(
+            "report.txt".into(),
+            raw.to_vec(),
+            "docs/report.txt".into(),
+            None,
+            &Sha256ContentIdGenerator,
+            &ContentEncryptionKey(vec![7u8; 32]),
+            &Aes256CtrContentEncryption,
+        )
+        .unwrap()
+        .0
+    }
+
+    #[test]
+    fn save_then_find_roundtrips_and_stores_ciphertext_as_file() {
+        let dir = TempDir::new().unwrap();
+        let repo = FsContentRepository::open(dir.path()).unwrap();
+        let content = content(b"quarterly report");
+        let id = content.raw_id().clone();
+
+        repo.save(&id, &content).unwrap();
+
+        let found = repo.find_by_id(&id).unwrap().unwrap();
+        assert_eq!(found.encrypted_content(), content.encrypted_content());
+        assert_eq!(found.metadata().name(), "report.txt");
+
+        let ciphertext = content.encrypted_content().unwrap();
+        let digest = hex::encode(Sha256::digest(ciphertext));
+        assert_eq!(&fs::read(repo.blob_path(&digest)).unwrap(), ciphertext);
+        assert!(repo
+            .find_by_id(&ContentId::for_test("missing"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn contents_survive_reopen() {
+        let dir = TempDir::new().unwrap();
+        let content = content(b"persist me");
+        let id = content.raw_id().clone();
+        {
+            let repo = FsContentRepository::open(dir.path()).unwrap();
+            repo.save(&id, &content).unwrap();
+        }
+
+        let repo = reopen(|| FsContentRepository::open(dir.path()));
+        let found = repo.find_by_id(&id).unwrap().unwrap();
+        assert_eq!(found.encrypted_content(), content.encrypted_content());
+    }
+
+    #[test]
+    fn corrupted_blob_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let repo = FsContentRepository::open(dir.path()).unwrap();
+        let content = content(b"do not tamper");
+        let id = content.raw_id().clone();
+        repo.save(&id, &content).unwrap();
+
+        let digest = hex::encode(Sha256::digest(content.encrypted_content().unwrap()));
+        fs::write(repo.blob_path(&digest), b"tampered").unwrap();
+
+        assert!(matches!(
+            repo.find_by_id(&id),
+            Err(ContentRepositoryError::Storage(_))
+        ));
+    }
+
+    #[test]
+    fn list_returns_latest_version_per_series_and_applies_filter() {
+        let dir = TempDir::new().unwrap();
+        let repo = FsContentRepository::open(dir.path()).unwrap();
+        let key = ContentEncryptionKey(vec![7u8; 32]);
+
+        let original = content(b"v1");
+        repo.save(original.raw_id(), &original).unwrap();
+        let (updated, _) = original
+            .update_content(
+                b"v2".to_vec(),
+                &Sha256ContentIdGenerator,
+                &key,
+                &Aes256CtrContentEncryption,
+            )
+            .unwrap();
+        repo.save(updated.raw_id(), &updated).unwrap();
+
+        let (archived, _) = content(b"old notes").archive().unwrap();
+        repo.save(archived.raw_id(), &archived).unwrap();
+
+        let listed = repo.list(ContentListFilter::default()).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].raw_id(), updated.raw_id());
+        assert!(listed[0].encrypted_content().is_none());
+
+        let with_archived = repo
+            .list(ContentListFilter {
+                include_archived: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(with_archived.len(), 2);
+        assert!(with_archived
+            .iter()
+            .any(|c| c.raw_id() == archived.raw_id()));
+    }
+
+    fn blob_of(repo: &FsContentRepository, content: &Content) -> PathBuf {
+        repo.blob_path(&hex::encode(Sha256::digest(
+            content.encrypted_content().unwrap(),
+        )))
+    }
+
+    #[test]
+    fn update_and_delete_remove_blobs_no_longer_referenced() {
+        let dir = TempDir::new().unwrap();
+        let repo = FsContentRepository::open(dir.path()).unwrap();
+        let key = ContentEncryptionKey(vec![7u8; 32]);
+
+        let original = content(b"v1");
+        repo.save(original.raw_id(), &original).unwrap();
+        let (updated, _) = original
+            .update_content(
+                b"v2".to_vec(),
+                &Sha256ContentIdGenerator,
+                &key,
+                &Aes256CtrContentEncryption,
+            )
+            .unwrap();
+        repo.save(updated.raw_id(), &updated).unwrap();
+
+        // 置き換えられた版はメタデータだけが残る
+        assert!(!blob_of(&repo, &original).exists());
+        let superseded = repo.find_by_id(original.raw_id()).unwrap().unwrap();
+        assert!(superseded.encrypted_content().is_none());
+        assert!(blob_of(&repo, &updated).exists());
+
+        let (deleted, _) = updated.delete().unwrap();
+        repo.save(deleted.raw_id(), &deleted).unwrap();
+        assert!(!blob_of(&repo, &updated).exists());
+    }
+
+    #[test]
+    fn shared_blob_is_kept_while_any_content_references_it() {
+        let dir = TempDir::new().unwrap();
+        let repo = FsContentRepository::open(dir.path()).unwrap();
+        let first = content(b"shared");
+        let second =
+            content(b"other").with_encrypted_content(first.encrypted_content().unwrap().to_vec());
+        repo.save(first.raw_id(), &first).unwrap();
+        repo.save(second.raw_id(), &second).unwrap();
+
+        let (deleted, _) = first.delete().unwrap();
+        repo.save(deleted.raw_id(), &deleted).unwrap();
+        assert!(blob_of(&repo, &first).exists());
+        let found = repo.find_by_id(second.raw_id()).unwrap().unwrap();
+        assert_eq!(found.encrypted_content(), first.encrypted_content());
+
+        let (deleted, _) = second.delete().unwrap();
+        repo.save(deleted.raw_id(), &deleted).unwrap();
+        assert!(!blob_of(&repo, &first).exists());
+    }
+
+    #[test]
+    fn reopen_removes_unreferenced_blobs_and_keeps_counting() {
+        let dir = TempDir::new().unwrap();
+        let key = ContentEncryptionKey(vec![7u8; 32]);
+        let original = content(b"v1");
+        let orphan = {
+            let repo = FsContentRepository::open(dir.path()).unwrap();
+            repo.save(original.raw_id(), &original).unwrap();
+            let orphan = repo.write_blob(b"left behind by a crash").unwrap();
+            repo.blob_path(&orphan)
+        };
+        assert!(orphan.exists());
+
+        let repo = reopen(|| FsContentRepository::open(dir.path()));
+        assert!(!orphan.exists());
+        assert!(blob_of(&repo, &original).exists());
+
+        // 開き直した後も系列の旧版として扱われる
+        let (updated, _) = original
+            .update_content(
+                b"v2".to_vec(),
+                &Sha256ContentIdGenerator,
+                &key,
+                &Aes256CtrContentEncryption,
+            )
+            .unwrap();
+        repo.save(updated.raw_id(), &updated).unwrap();
+        assert!(!blob_of(&repo, &original).exists());
+    }
+}
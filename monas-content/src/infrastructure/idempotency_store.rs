@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+
+use crate::application_service::content_service::{
+    IdempotencyRecord, IdempotencyStore, IdempotencyStoreError,
+};
+
+/// 既定で保持する記録の最大件数。
+pub const DEFAULT_IDEMPOTENCY_CAPACITY: usize = 10_000;
+
+/// シンプルなインメモリ実装の IdempotencyStore。
+///
+/// - key: 冪等キー
+/// - value: 作成結果の記録
+///
+/// 記録は最大 `capacity` 件まで保持する。上限に達すると、まず `retention` を過ぎた
+/// 記録を捨て、それでも空きがなければ最も古い記録を捨てる。
+#[derive(Clone)]
+pub struct InMemoryIdempotencyStore {
+    inner: Arc<Mutex<HashMap<String, IdempotencyRecord>>>,
+    capacity: usize,
+    retention: Duration,
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new(capacity: usize, retention: Duration) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(HashMap::new())),
+            capacity,
+            retention,
+        }
+    }
+}
+
+impl Default for InMemoryIdempotencyStore {
+    /// 10,000 件・24 時間まで保持する。
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_CAPACITY, Duration::hours(24))
+    }
+}
+
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, IdempotencyStoreError> {
+        let guard = self
+            .inner
+            .lock()
+            .map_err(|e| IdempotencyStoreError::Storage(e.to_string()))?;
+        Ok(guard.get(key).cloned())
+    }
+
+    fn put(&self, key: &str, record: IdempotencyRecord) -> Result<(), IdempotencyStoreError> {
+        let mut guard = self
+            .inner
+            .lock()
+            .map_err(|e| IdempotencyStoreError::Storage(e.to_string()))?;
+        if guard.len() >= self.capacity && !guard.contains_key(key) {
+            let now = Utc::now();
+            guard.retain(|_, r| now - r.created_at <= self.retention);
+            if guard.len() >= self.capacity {
+                let oldest = guard
+                    .iter()
+                    .min_by_key(|(_, r)| r.created_at)
+                    .map(|(k, _)| k.clone());
+                if let Some(oldest) = oldest {
+                    guard.remove(&oldest);
+                }
+            }
+        }
+        guard.insert(key.to_string(), record);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::content_id::ContentId;
+
+    fn record(age: Duration) -> IdempotencyRecord {
+        IdempotencyRecord {
+            content_id: ContentId::for_test("id"),
+            provider: None,
+            fingerprint: "fp".into(),
+            created_at: Utc::now() - age,
+        }
+    }
+
+    #[test]
+    fn evicts_expired_then_oldest_records_at_capacity() {
+        let store = InMemoryIdempotencyStore::new(2, Duration::hours(1));
+        store.put("expired", record(Duration::hours(2))).unwrap();
+        store.put("old", record(Duration::minutes(30))).unwrap();
+
+        // 保持期間を過ぎた記録から捨てる
+        store.put("new", record(Duration::zero())).unwrap();
+        assert!(store.get("expired").unwrap().is_none());
+        assert!(store.get("old").unwrap().is_some());
+
+        // 期限内の記録しかなければ最も古い記録を捨てる
+        store.put("newer", record(Duration::zero())).unwrap();
+        assert!(store.get("old").unwrap().is_none());
+        assert!(store.get("new").unwrap().is_some());
+        assert!(store.get("newer").unwrap().is_some());
+
+        // 既存のキーの上書きでは捨てない
+        store.put("new", record(Duration::zero())).unwrap();
+        assert!(store.get("newer").unwrap().is_some());
+    }
+}
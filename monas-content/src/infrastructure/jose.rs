@@ -0,0 +1,443 @@
+//! KeyEnvelope を JWE（RFC 7516）の Flattened JSON Serialization として書き出す。
+//!
+//! 受信者の秘密鍵さえあれば、HPKE（RFC 9180）と AES-CTR の標準的な実装だけで
+//! 共有されたコンテンツを復号できるようにするための交換形式。
+//!
+//! ## アルゴリズムの対応
+//!
+//! | KeyEnvelope            | JWE ヘッダ | 内容                                            |
+//! |------------------------|------------|-------------------------------------------------|
+//! | `KeyWrapAlgorithm::HpkeV1` | `alg`  | `HPKE-P256-SHA256-A256GCM`（DHKEM(P-256) / HKDF-SHA256 / AES-256-GCM, Base モード） |
+//! | コンテンツ暗号         | `enc`      | `A256CTR`（AES-256-CTR, 16 バイト IV, タグなし）|
+//!
+//! どちらも IANA には登録されていない名前なので、JOSE ライブラリの自動判定には頼れない。
+//! 受信側は以下の手順で復号する。
+//!
+//! 1. `protected` をデコードし、`ek`（HPKE の encapsulated key）と `cid` を取り出す
+//! 2. HPKE の `info` と `aad` にはどちらも `cid` の UTF-8 バイト列を使い、
+//!    `encrypted_key` を開封して CEK を得る
+//! 3. CEK と `iv` で `ciphertext` を AES-256-CTR 復号する
+//!
+//! コンテンツ暗号は認証タグを持たないため `tag` は出力しない。
+//! 改ざん検知は復号結果から ContentId を再計算して `cid` と比較することで行う。
+//!
+//! AES-256-GCM で暗号化したコンテンツやチャンク分割したコンテンツは `enc` で表せないため、
+//! JWE には書き出さない（[`to_jwe_if_supported`] は `None` を返す）。
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::content::split_chunked_ciphertext;
+use crate::domain::content_id::ContentId;
+use crate::domain::share::key_envelope::{KeyEnvelope, KeyWrapAlgorithm, WrappedRecipientKey};
+use crate::domain::KeyId;
+use crate::infrastructure::cipher_suite::{self, AES_256_CTR, AES_256_GCM};
+
+/// `KeyWrapAlgorithm::HpkeV1` に対応する JWE の `alg`。
+pub const HPKE_V1_ALG: &str = "HPKE-P256-SHA256-A256GCM";
+
+/// コンテンツ暗号（`Aes256CtrContentEncryption`）に対応する JWE の `enc`。
+pub const AES_256_CTR_ENC: &str = "A256CTR";
+
+/// チャンク分割したコンテンツを JWE に書き出せない理由として返す名前。
+const CHUNKED: &str = "chunked";
+
+/// `Aes256CtrContentEncryption` が暗号文の先頭に付ける IV の長さ。
+const IV_LEN: usize = 16;
+
+#[derive(Debug, thiserror::Error)]
+pub enum JoseError {
+    #[error("invalid base64url in {field}: {message}")]
+    InvalidBase64 {
+        field: &'static str,
+        message: String,
+    },
+    #[error("invalid protected header: {0}")]
+    InvalidHeader(String),
+    #[error("unsupported alg: {0}")]
+    UnsupportedAlgorithm(String),
+    #[error("unsupported enc: {0}")]
+    UnsupportedEncryption(String),
+    #[error("ciphertext is too short to contain an IV")]
+    CiphertextTooShort,
+    #[error("iv must be 16 bytes, got {0}")]
+    InvalidIvLength(usize),
+}
+
+/// JWE の保護ヘッダ。
+///
+/// `kid` / `skid` / `ek` はバイト列を base64url（パディングなし）で表す。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JweProtectedHeader {
+    pub alg: String,
+    pub enc: String,
+    /// 受信者の KeyId。
+    pub kid: String,
+    /// 送信者の KeyId。
+    pub skid: String,
+    /// HPKE の encapsulated key。
+    pub ek: String,
+    /// 対象コンテンツの ContentId。HPKE の `info` / `aad` にも使う。
+    pub cid: String,
+}
+
+/// JWE の Flattened JSON Serialization（RFC 7516 §7.2.2）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlattenedJwe {
+    /// base64url(UTF-8(JSON(`JweProtectedHeader`)))
+    pub protected: String,
+    /// HPKE でラップされた CEK。
+    pub encrypted_key: String,
+    pub iv: String,
+    pub ciphertext: String,
+}
+
+/// KeyWrapAlgorithm に対応する JWE の `alg` を返す。
+pub fn jose_alg(algorithm: &KeyWrapAlgorithm) -> &'static str {
+    match algorithm {
+        KeyWrapAlgorithm::HpkeV1 => HPKE_V1_ALG,
+    }
+}
+
+/// JWE の `alg` から対応する KeyWrapAlgorithm を求める。
+pub fn key_wrap_algorithm_from_jose(alg: &str) -> Option<KeyWrapAlgorithm> {
+    match alg {
+        HPKE_V1_ALG => Some(KeyWrapAlgorithm::HpkeV1),
+        _ => None,
+    }
+}
+
+/// KeyEnvelope を JWE に変換する。
+///
+/// 暗号スイートのヘッダ付きの暗号文はヘッダを取り除いて書き出す。
+/// AES-256-CTR 以外で暗号化されたコンテンツや、チャンク分割したコンテンツは
+/// `enc` で表せないためエラーになる。
+pub fn to_jwe(envelope: &KeyEnvelope) -> Result<FlattenedJwe, JoseError> {
+    if split_chunked_ciphertext(envelope.ciphertext()).is_some() {
+        return Err(JoseError::UnsupportedEncryption(CHUNKED.to_string()));
+    }
+    let content_ciphertext = match cipher_suite::split_header(envelope.ciphertext()) {
+        Some((AES_256_CTR, body)) => body,
+        Some((AES_256_GCM, _)) => {
+            return Err(JoseError::UnsupportedEncryption(AES_256_GCM.to_string()))
+        }
+        _ => envelope.ciphertext(),
+    };
+    if content_ciphertext.len() < IV_LEN {
+        return Err(JoseError::CiphertextTooShort);
+    }
+    let (iv, ciphertext) = content_ciphertext.split_at(IV_LEN);
+    let recipient = envelope.recipient();
+
+    let header = JweProtectedHeader {
+        alg: jose_alg(envelope.key_wrap_algorithm()).to_string(),
+        enc: AES_256_CTR_ENC.to_string(),
+        kid: URL_SAFE_NO_PAD.encode(recipient.key_id().as_bytes()),
+        skid: URL_SAFE_NO_PAD.encode(envelope.sender_key_id().as_bytes()),
+        ek: URL_SAFE_NO_PAD.encode(recipient.enc()),
+        cid: envelope.content_id().as_str().to_string(),
+    };
+    let header_json =
+        serde_json::to_vec(&header).map_err(|e| JoseError::InvalidHeader(e.to_string()))?;
+
+    Ok(FlattenedJwe {
+        protected: URL_SAFE_NO_PAD.encode(header_json),
+        encrypted_key: URL_SAFE_NO_PAD.encode(recipient.wrapped_cek()),
+        iv: URL_SAFE_NO_PAD.encode(iv),
+        ciphertext: URL_SAFE_NO_PAD.encode(ciphertext),
+    })
+}
+
+/// KeyEnvelope を、JWE で表せる場合だけ JWE に変換する。
+///
+/// AES-256-GCM で暗号化したコンテンツやチャンク分割したコンテンツは `Ok(None)`。
+pub fn to_jwe_if_supported(envelope: &KeyEnvelope) -> Result<Option<FlattenedJwe>, JoseError> {
+    match to_jwe(envelope) {
+        Ok(jwe) => Ok(Some(jwe)),
+        Err(JoseError::UnsupportedEncryption(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// JWE から KeyEnvelope を復元する。
+pub fn from_jwe(jwe: &FlattenedJwe) -> Result<KeyEnvelope, JoseError> {
+    let header = decode_header(&jwe.protected)?;
+    let algorithm = key_wrap_algorithm_from_jose(&header.alg)
+        .ok_or_else(|| JoseError::UnsupportedAlgorithm(header.alg.clone()))?;
+    if header.enc != AES_256_CTR_ENC {
+        return Err(JoseError::UnsupportedEncryption(header.enc));
+    }
+    let content_id = ContentId::new(header.cid.clone())
+        .map_err(|e| JoseError::InvalidHeader(format!("cid: {e}")))?;
+
+    let mut ciphertext = decode(&jwe.iv, "iv")?;
+    if ciphertext.len() != IV_LEN {
+        return Err(JoseError::InvalidIvLength(ciphertext.len()));
+    }
+    ciphertext.extend_from_slice(&decode(&jwe.ciphertext, "ciphertext")?);
+
+    let recipient = WrappedRecipientKey::new(
+        KeyId::new(decode(&header.kid, "kid")?),
+        decode(&header.ek, "ek")?,
+        decode(&jwe.encrypted_key, "encrypted_key")?,
+    );
+    Ok(KeyEnvelope::new(
+        content_id,
+        algorithm,
+        KeyId::new(decode(&header.skid, "skid")?),
+        recipient,
+        ciphertext,
+    ))
+}
+
+/// `protected` をデコードしてヘッダを取り出す。
+pub fn decode_header(protected: &str) -> Result<JweProtectedHeader, JoseError> {
+    let json = decode(protected, "protected")?;
+    serde_json::from_slice(&json).map_err(|e| JoseError::InvalidHeader(e.to_string()))
+}
+
+fn decode(value: &str, field: &'static str) -> Result<Vec<u8>, JoseError> {
+    URL_SAFE_NO_PAD
+        .decode(value)
+        .map_err(|e| JoseError::InvalidBase64 {
+            field,
+            message: e.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::content::encryption::{ContentEncryption, ContentEncryptionKey};
+    use crate::domain::content::join_chunked_ciphertext;
+    use crate::domain::share::encryption::KeyWrapping;
+    use crate::infrastructure::encryption::Aes256CtrContentEncryption;
+    use crate::infrastructure::key_wrapping::HpkeV1KeyWrapping;
+    use hpke_rs::hpke_types::{AeadAlgorithm, KdfAlgorithm, KemAlgorithm};
+    use hpke_rs::prelude::*;
+    use hpke_rs_rust_crypto::HpkeRustCrypto;
+
+    fn vector_content_id() -> ContentId {
+        ContentId::new(format!("1220{}", "ab".repeat(32))).unwrap()
+    }
+
+    fn vector_envelope() -> KeyEnvelope {
+        let mut ciphertext: Vec<u8> = (0u8..16).collect();
+        ciphertext.extend_from_slice(&[0xaa, 0xbb, 0xcc]);
+        KeyEnvelope::new(
+            vector_content_id(),
+            KeyWrapAlgorithm::HpkeV1,
+            KeyId::new(vec![1, 2, 3]),
+            WrappedRecipientKey::new(KeyId::new(vec![4, 5, 6]), vec![0x04; 4], vec![0x11; 8]),
+            ciphertext,
+        )
+    }
+
+    #[test]
+    fn serializes_envelope_to_known_vector() {
+        let jwe = to_jwe(&vector_envelope()).unwrap();
+
+        assert_eq!(
+            decode(&jwe.protected, "protected").unwrap(),
+            br#"{"alg":"HPKE-P256-SHA256-A256GCM","enc":"A256CTR","kid":"BAUG","skid":"AQID","ek":"BAQEBA","cid":"1220abababababababababababababababababababababababababababababababab"}"#
+        );
+        assert_eq!(
+            jwe.protected,
+            "eyJhbGciOiJIUEtFLVAyNTYtU0hBMjU2LUEyNTZHQ00iLCJlbmMiOiJBMjU2Q1RSIiwia2lkIjoiQkFVRyIsInNraWQiOiJBUUlEIiwiZWsiOiJCQVFFQkEiLCJjaWQiOiIxMjIwYWJhYmFiYWJhYmFiYWJhYmFiYWJhYmFiYWJhYmFiYWJhYmFiYWJhYmFiYWJhYmFiYWJhYmFiYWJhYmFiYWJhYiJ9"
+        );
+        assert_eq!(jwe.encrypted_key, "ERERERERERE");
+        assert_eq!(jwe.iv, "AAECAwQFBgcICQoLDA0ODw");
+        assert_eq!(jwe.ciphertext, "qrvM");
+    }
+
+    #[test]
+    fn jwe_roundtrips_to_the_same_envelope() {
+        let envelope = vector_envelope();
+        let json = serde_json::to_string(&to_jwe(&envelope).unwrap()).unwrap();
+
+        let parsed: FlattenedJwe = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_jwe(&parsed).unwrap(), envelope);
+    }
+
+    #[test]
+    fn rejects_unknown_algorithms() {
+        let mut header = decode_header(&to_jwe(&vector_envelope()).unwrap().protected).unwrap();
+        header.alg = "RSA-OAEP".into();
+        let jwe = FlattenedJwe {
+            protected: URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header).unwrap()),
+            ..to_jwe(&vector_envelope()).unwrap()
+        };
+
+        assert!(matches!(
+            from_jwe(&jwe),
+            Err(JoseError::UnsupportedAlgorithm(alg)) if alg == "RSA-OAEP"
+        ));
+    }
+
+    #[test]
+    fn strips_cipher_suite_header_and_rejects_non_ctr_content() {
+        let with_header = |id: &str| {
+            let legacy = vector_envelope();
+            let mut ciphertext = b"MNC\x01".to_vec();
+            ciphertext.push(id.len() as u8);
+            ciphertext.extend_from_slice(id.as_bytes());
+            ciphertext.extend_from_slice(legacy.ciphertext());
+            KeyEnvelope::new(
+                legacy.content_id().clone(),
+                KeyWrapAlgorithm::HpkeV1,
+                legacy.sender_key_id().clone(),
+                legacy.recipient().clone(),
+                ciphertext,
+            )
+        };
+
+        assert_eq!(
+            to_jwe(&with_header(AES_256_CTR)).unwrap(),
+            to_jwe(&vector_envelope()).unwrap()
+        );
+        assert!(matches!(
+            to_jwe(&with_header(AES_256_GCM)),
+            Err(JoseError::UnsupportedEncryption(enc)) if enc == AES_256_GCM
+        ));
+        assert_eq!(
+            to_jwe_if_supported(&with_header(AES_256_GCM)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn does_not_export_chunked_content() {
+        let legacy = vector_envelope();
+        let chunked = KeyEnvelope::new(
+            legacy.content_id().clone(),
+            KeyWrapAlgorithm::HpkeV1,
+            legacy.sender_key_id().clone(),
+            legacy.recipient().clone(),
+            join_chunked_ciphertext([legacy.ciphertext()]),
+        );
+
+        assert!(matches!(
+            to_jwe(&chunked),
+            Err(JoseError::UnsupportedEncryption(enc)) if enc == CHUNKED
+        ));
+        assert_eq!(to_jwe_if_supported(&chunked).unwrap(), None);
+        assert!(to_jwe_if_supported(&legacy).unwrap().is_some());
+    }
+
+    /// RFC 9180 の Base モードを p256 / hkdf / aes-gcm で直接組み立てて CEK を開封する。
+    ///
+    /// `HpkeV1KeyWrapping`（hpke-rs）とは独立した実装で開けることを確かめ、
+    /// 他の HPKE 実装でも復号できることの裏付けにする。
+    fn open_with_rfc9180_primitives(
+        recipient_private_key: &[u8],
+        recipient_public_key: &[u8],
+        ek: &[u8],
+        info: &[u8],
+        aad: &[u8],
+        sealed: &[u8],
+    ) -> Vec<u8> {
+        use aes_gcm::aead::{Aead, KeyInit, Payload};
+        use aes_gcm::{Aes256Gcm, Nonce};
+        use hkdf::Hkdf;
+        use sha2::Sha256;
+
+        // DHKEM(P-256, HKDF-SHA256) = 0x0010, HKDF-SHA256 = 0x0001, AES-256-GCM = 0x0002
+        let kem_suite: &[u8] = b"KEM\x00\x10";
+        let hpke_suite: &[u8] = b"HPKE\x00\x10\x00\x01\x00\x02";
+
+        let labeled_extract = |suite: &[u8], salt: &[u8], label: &[u8], ikm: &[u8]| {
+            let input = [b"HPKE-v1".as_slice(), suite, label, ikm].concat();
+            Hkdf::<Sha256>::extract(Some(salt), &input).0.to_vec()
+        };
+        let labeled_expand = |suite: &[u8], prk: &[u8], label: &[u8], info: &[u8], len: u16| {
+            let labeled_info = [
+                len.to_be_bytes().as_slice(),
+                b"HPKE-v1".as_slice(),
+                suite,
+                label,
+                info,
+            ]
+            .concat();
+            let mut out = vec![0u8; len as usize];
+            Hkdf::<Sha256>::from_prk(prk)
+                .unwrap()
+                .expand(&labeled_info, &mut out)
+                .unwrap();
+            out
+        };
+
+        let sk = p256::SecretKey::from_slice(recipient_private_key).unwrap();
+        let pk_e = p256::PublicKey::from_sec1_bytes(ek).unwrap();
+        let dh = p256::ecdh::diffie_hellman(sk.to_nonzero_scalar(), pk_e.as_affine());
+        let kem_context = [ek, recipient_public_key].concat();
+        let eae_prk = labeled_extract(kem_suite, b"", b"eae_prk", dh.raw_secret_bytes().as_slice());
+        let shared_secret = labeled_expand(kem_suite, &eae_prk, b"shared_secret", &kem_context, 32);
+
+        let psk_id_hash = labeled_extract(hpke_suite, b"", b"psk_id_hash", b"");
+        let info_hash = labeled_extract(hpke_suite, b"", b"info_hash", info);
+        let context = [[0u8].as_slice(), &psk_id_hash, &info_hash].concat();
+        let secret = labeled_extract(hpke_suite, &shared_secret, b"secret", b"");
+        let key = labeled_expand(hpke_suite, &secret, b"key", &context, 32);
+        let base_nonce = labeled_expand(hpke_suite, &secret, b"base_nonce", &context, 12);
+
+        Aes256Gcm::new_from_slice(&key)
+            .unwrap()
+            .decrypt(Nonce::from_slice(&base_nonce), Payload { msg: sealed, aad })
+            .unwrap()
+    }
+
+    #[test]
+    fn exported_jwe_decrypts_with_independent_primitives() {
+        use aes::cipher::{KeyIvInit, StreamCipher};
+
+        let plaintext = b"shared with a third-party tool".to_vec();
+        let content_id = vector_content_id();
+        let cek = ContentEncryptionKey((0u8..32).collect());
+
+        let mut hpke = Hpke::<HpkeRustCrypto>::new(
+            Mode::Base,
+            KemAlgorithm::DhKemP256,
+            KdfAlgorithm::HkdfSha256,
+            AeadAlgorithm::Aes256Gcm,
+        );
+        let keypair = hpke.generate_key_pair().unwrap();
+        let pk_r = keypair.public_key().as_slice().to_vec();
+        let sk_r = keypair.private_key().as_slice().to_vec();
+
+        let (enc, wrapped_cek) = HpkeV1KeyWrapping
+            .wrap_cek(&cek, &pk_r, &content_id)
+            .unwrap();
+        let ciphertext = Aes256CtrContentEncryption
+            .encrypt(&cek, &plaintext)
+            .unwrap();
+        let envelope = KeyEnvelope::new(
+            content_id,
+            KeyWrapAlgorithm::HpkeV1,
+            KeyId::new(vec![1]),
+            WrappedRecipientKey::new(KeyId::new(vec![2]), enc, wrapped_cek),
+            ciphertext,
+        );
+        let json = serde_json::to_string(&to_jwe(&envelope).unwrap()).unwrap();
+
+        // ここからは受信者側。JSON と base64url と暗号プリミティブだけで復号する
+        let jwe: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let field = |name: &str| URL_SAFE_NO_PAD.decode(jwe[name].as_str().unwrap()).unwrap();
+        let header: serde_json::Value = serde_json::from_slice(&field("protected")).unwrap();
+        assert_eq!(header["alg"], HPKE_V1_ALG);
+        assert_eq!(header["enc"], AES_256_CTR_ENC);
+        let ek = URL_SAFE_NO_PAD
+            .decode(header["ek"].as_str().unwrap())
+            .unwrap();
+        let cid = header["cid"].as_str().unwrap().as_bytes();
+
+        let cek =
+            open_with_rfc9180_primitives(&sk_r, &pk_r, &ek, cid, cid, &field("encrypted_key"));
+        let mut content = field("ciphertext");
+        ctr::Ctr128BE::<aes::Aes256>::new_from_slices(&cek, &field("iv"))
+            .unwrap()
+            .apply_keystream(&mut content);
+
+        assert_eq!(content, plaintext);
+    }
+}
@@ -0,0 +1,491 @@
+//! アカウントのルート鍵からの CEK 導出。
+//!
+//! コンテンツごとにランダムな CEK を保存する代わりに、アカウントのマスター鍵と
+//! 系列 ID（初版の ContentId）から HKDF-SHA256 で CEK を決定的に導出するモード。
+//!
+//! - `HkdfContentEncryptionKeyGenerator` は `generate_for` で導出した CEK を返す。
+//!   ContentId に紐付かない `generate`（再暗号化による鍵ローテーションなど）は従来どおりランダム生成。
+//! - `DerivedContentEncryptionKeyStore` は任意の CEK ストアをラップするデコレータで、
+//!   導出可能な CEK は保存せず、読み込み時に導出し直す。ローテーション済みのランダムな CEK と
+//!   削除の墓標だけが内側のストアに残るため、鍵ストアの状態が減り、ルート鍵だけから CEK を復旧できる。
+//!
+//! ランダム CEK モードを使う場合は、従来どおり `OsRngContentEncryptionKeyGenerator` と
+//! 任意の CEK ストアを組み合わせればよい。サーバーでは [`AccountMasterKey::from_env`] で
+//! マスター鍵を指定した場合に導出モードになる。
+//!
+//! CEK は各版の ContentId ではなく系列 ID から導出する。更新後の版も初版と同じ CEK で
+//! 暗号化されるため（`Content::update_content`）、共有相手の KeyEnvelope は版が増えても
+//! そのまま使える。版ごとに導出すると、更新のたびにすべての共有を再発行する必要がある。
+
+use std::fmt;
+use std::path::PathBuf;
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::application_service::content_service::{
+    ContentEncryptionKeyStore, ContentEncryptionKeyStoreError, ContentRepository,
+};
+use crate::domain::content::encryption::{ContentEncryptionKey, ContentEncryptionKeyGenerator};
+use crate::domain::content_id::ContentId;
+use crate::infrastructure::encryption::OsRngContentEncryptionKeyGenerator;
+
+/// CEK 導出に用いる HKDF の info プレフィックス（後ろに ContentId が続く）。
+const CEK_DERIVATION_INFO: &[u8] = b"monas-content/cek/v1/";
+
+const MASTER_KEY_LEN: usize = 32;
+const CEK_LEN: usize = 32;
+
+/// CEK 導出の元になるアカウントのマスター鍵 (32 バイト)。drop 時に消去する。
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct AccountMasterKey([u8; MASTER_KEY_LEN]);
+
+#[derive(Debug, thiserror::Error)]
+pub enum AccountMasterKeyConfigError {
+    /// 鍵の値はログに残さないよう、エラーメッセージに含めない。
+    #[error("{name} must be a 32-byte key encoded as 64 hex characters")]
+    InvalidKey { name: &'static str },
+    #[error("failed to read {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl AccountMasterKey {
+    pub fn new(bytes: [u8; MASTER_KEY_LEN]) -> Self {
+        Self(bytes)
+    }
+
+    /// 環境変数からマスター鍵を読み込む。どちらも未設定なら `None`（ランダム CEK モード）。
+    ///
+    /// - `MONAS_CONTENT_MASTER_KEY_FILE`: 鍵を 16 進数（64 文字）で書いたファイルのパス
+    /// - `MONAS_CONTENT_MASTER_KEY`: 鍵の 16 進数（ファイルを指定した場合は使わない）
+    pub fn from_env() -> Result<Option<Self>, AccountMasterKeyConfigError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, AccountMasterKeyConfigError> {
+        const KEY_FILE: &str = "MONAS_CONTENT_MASTER_KEY_FILE";
+        const KEY: &str = "MONAS_CONTENT_MASTER_KEY";
+
+        let (name, encoded) = match lookup(KEY_FILE).filter(|v| !v.trim().is_empty()) {
+            Some(path) => {
+                let path = PathBuf::from(path);
+                let encoded = std::fs::read_to_string(&path)
+                    .map_err(|source| AccountMasterKeyConfigError::Read { path, source })?;
+                (KEY_FILE, encoded)
+            }
+            None => match lookup(KEY).filter(|v| !v.trim().is_empty()) {
+                Some(encoded) => (KEY, encoded),
+                None => return Ok(None),
+            },
+        };
+        let encoded = Zeroizing::new(encoded);
+        let bytes = Zeroizing::new(
+            hex::decode(encoded.trim())
+                .map_err(|_| AccountMasterKeyConfigError::InvalidKey { name })?,
+        );
+        Self::from_slice(&bytes)
+            .map(Some)
+            .ok_or(AccountMasterKeyConfigError::InvalidKey { name })
+    }
+
+    /// バイト列から構築する。長さが 32 バイトでない場合は `None`。
+    pub fn from_slice(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok().map(Self)
+    }
+
+    /// 系列 `series_id`（初版の ContentId）向けの CEK を導出する。
+    ///
+    /// 同じマスター鍵と系列 ID からは常に同じ CEK が得られる。
+    pub fn derive_cek(&self, series_id: &ContentId) -> ContentEncryptionKey {
+        let hk = Hkdf::<Sha256>::new(None, &self.0);
+        let mut info = Vec::with_capacity(CEK_DERIVATION_INFO.len() + series_id.as_str().len());
+        info.extend_from_slice(CEK_DERIVATION_INFO);
+        info.extend_from_slice(series_id.as_str().as_bytes());
+
+        let mut cek = ContentEncryptionKey(vec![0u8; CEK_LEN]);
+        hk.expand(&info, &mut cek.0)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        cek
+    }
+}
+
+impl fmt::Debug for AccountMasterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AccountMasterKey(..)")
+    }
+}
+
+/// 系列ごとの CEK をマスター鍵から導出する CEK ジェネレータ。
+///
+/// `generate_for` には作成するコンテンツの ContentId（＝新しい系列の ID）が渡される。
+#[derive(Debug, Clone)]
+pub struct HkdfContentEncryptionKeyGenerator {
+    master_key: AccountMasterKey,
+}
+
+impl HkdfContentEncryptionKeyGenerator {
+    pub fn new(master_key: AccountMasterKey) -> Self {
+        Self { master_key }
+    }
+}
+
+impl ContentEncryptionKeyGenerator for HkdfContentEncryptionKeyGenerator {
+    /// ContentId に紐付かない CEK（鍵ローテーション用）はランダムに生成する。
+    fn generate(&self) -> ContentEncryptionKey {
+        OsRngContentEncryptionKeyGenerator.generate()
+    }
+
+    fn generate_for(&self, content_id: &ContentId) -> ContentEncryptionKey {
+        self.master_key.derive_cek(content_id)
+    }
+}
+
+/// 導出可能な CEK を保存せず、読み込み時にマスター鍵から導出する CEK ストアのデコレータ。
+///
+/// CEK は系列 ID（初版の ContentId）から導出し、更新後の版も同じ CEK を使う。
+/// 版の系列はコンテンツ本体から引くため、鍵ストアを失ってもマスター鍵と
+/// コンテンツストレージだけで CEK を復旧できる。
+///
+/// - `save` / `save_in_series`: 系列から導出した CEK と一致する CEK は保存しない
+///   （以前にローテーションした CEK が残っていれば削除する）。
+///   一致しない CEK（ローテーション後のランダムな CEK）は内側のストアに保存する。
+/// - `load`: 内側のストアにあればそれを返し、なければ系列から導出した CEK を返す。
+///   コンテンツが存在しない場合や削除済みの場合は `None`。
+/// - `delete`: 内側のストアに墓標（空の CEK）を残し、以後の `load` は `None` を返す。
+///   墓標は版と系列の両方に残すため、同じ系列のほかの版からも CEK を導出できなくなる
+///   （クリプトシュレッディング）。
+///
+/// `load` のたびにコンテンツを取得するため、`content_repository` にはキャッシュ付きの
+/// リポジトリを渡すとよい。
+pub struct DerivedContentEncryptionKeyStore<S, R> {
+    inner: S,
+    content_repository: R,
+    master_key: AccountMasterKey,
+}
+
+impl<S, R> DerivedContentEncryptionKeyStore<S, R> {
+    pub fn new(inner: S, content_repository: R, master_key: AccountMasterKey) -> Self {
+        Self {
+            inner,
+            content_repository,
+            master_key,
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S: ContentEncryptionKeyStore, R: ContentRepository> DerivedContentEncryptionKeyStore<S, R> {
+    /// `content_id` の系列 ID。コンテンツが存在しなければ `None`。
+    fn series_of(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Option<ContentId>, ContentEncryptionKeyStoreError> {
+        let content = self
+            .content_repository
+            .find_by_id(content_id)
+            .map_err(|e| ContentEncryptionKeyStoreError::Storage(e.to_string()))?;
+        Ok(content.map(|content| content.series_id().clone()))
+    }
+
+    fn is_deleted(&self, content_id: &ContentId) -> Result<bool, ContentEncryptionKeyStoreError> {
+        Ok(self
+            .inner
+            .load(content_id)?
+            .is_some_and(|key| is_tombstone(&key)))
+    }
+}
+
+/// 削除済みを表す墓標。正規の CEK は空にならない。
+fn tombstone() -> ContentEncryptionKey {
+    ContentEncryptionKey(Vec::new())
+}
+
+fn is_tombstone(key: &ContentEncryptionKey) -> bool {
+    key.0.is_empty()
+}
+
+impl<S: ContentEncryptionKeyStore, R: ContentRepository> ContentEncryptionKeyStore
+    for DerivedContentEncryptionKeyStore<S, R>
+{
+    /// 系列の初版（`content_id` が系列 ID）の CEK として保存する。
+    fn save(
+        &self,
+        content_id: &ContentId,
+        key: &ContentEncryptionKey,
+    ) -> Result<(), ContentEncryptionKeyStoreError> {
+        self.save_in_series(content_id, content_id, key)
+    }
+
+    fn save_in_series(
+        &self,
+        content_id: &ContentId,
+        series_id: &ContentId,
+        key: &ContentEncryptionKey,
+    ) -> Result<(), ContentEncryptionKeyStoreError> {
+        if *key == self.master_key.derive_cek(series_id) {
+            self.inner.delete(content_id)
+        } else {
+            self.inner.save(content_id, key)
+        }
+    }
+
+    fn load(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Option<ContentEncryptionKey>, ContentEncryptionKeyStoreError> {
+        match self.inner.load(content_id)? {
+            Some(key) if is_tombstone(&key) => return Ok(None),
+            Some(key) => return Ok(Some(key)),
+            None => {}
+        }
+        let Some(series_id) = self.series_of(content_id)? else {
+            return Ok(None);
+        };
+        self.load_in_series(content_id, &series_id)
+    }
+
+    /// コンテンツを引かずに `series_id` から導出する。保存前の版にも使える。
+    fn load_in_series(
+        &self,
+        content_id: &ContentId,
+        series_id: &ContentId,
+    ) -> Result<Option<ContentEncryptionKey>, ContentEncryptionKeyStoreError> {
+        match self.inner.load(content_id)? {
+            Some(key) if is_tombstone(&key) => return Ok(None),
+            Some(key) => return Ok(Some(key)),
+            None => {}
+        }
+        if series_id != content_id && self.is_deleted(series_id)? {
+            return Ok(None);
+        }
+        Ok(Some(self.master_key.derive_cek(series_id)))
+    }
+
+    fn delete(&self, content_id: &ContentId) -> Result<(), ContentEncryptionKeyStoreError> {
+        if let Some(series_id) = self.series_of(content_id)? {
+            if series_id != *content_id {
+                self.inner.save(&series_id, &tombstone())?;
+            }
+        }
+        self.inner.save(content_id, &tombstone())
+    }
+
+    fn flush(&self) -> Result<(), ContentEncryptionKeyStoreError> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::content::Content;
+    use crate::domain::content_id::ContentIdGenerator;
+    use crate::infrastructure::content_id::Sha256ContentIdGenerator;
+    use crate::infrastructure::encryption::Aes256CtrContentEncryption;
+    use crate::infrastructure::fs_content_repository::FsContentRepository;
+    use crate::infrastructure::key_store::InMemoryContentEncryptionKeyStore;
+
+    fn master_key(byte: u8) -> AccountMasterKey {
+        AccountMasterKey::new([byte; MASTER_KEY_LEN])
+    }
+
+    #[test]
+    fn derive_cek_is_deterministic_per_master_key_and_content() {
+        let a = ContentId::for_test("content-a");
+        let b = ContentId::for_test("content-b");
+
+        let key = master_key(1).derive_cek(&a);
+        assert_eq!(key.0.len(), CEK_LEN);
+        assert_eq!(key, master_key(1).derive_cek(&a));
+        assert_ne!(key, master_key(1).derive_cek(&b));
+        assert_ne!(key, master_key(2).derive_cek(&a));
+    }
+
+    #[test]
+    fn generator_derives_for_content_and_randomizes_otherwise() {
+        let generator = HkdfContentEncryptionKeyGenerator::new(master_key(1));
+        let id = ContentId::for_test("content-a");
+
+        assert_eq!(generator.generate_for(&id), master_key(1).derive_cek(&id));
+        assert_ne!(generator.generate(), generator.generate());
+    }
+
+    #[test]
+    fn from_slice_requires_32_bytes() {
+        assert!(AccountMasterKey::from_slice(&[0u8; 32]).is_some());
+        assert!(AccountMasterKey::from_slice(&[0u8; 16]).is_none());
+    }
+
+    #[test]
+    fn from_lookup_reads_hex_key_from_file_or_variable() {
+        let lookup = |vars: Vec<(&'static str, String)>| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, value)| value.clone())
+            }
+        };
+        assert!(AccountMasterKey::from_lookup(lookup(vec![]))
+            .unwrap()
+            .is_none());
+
+        let key = AccountMasterKey::from_lookup(lookup(vec![(
+            "MONAS_CONTENT_MASTER_KEY",
+            "01".repeat(32),
+        )]))
+        .unwrap();
+        assert_eq!(key, Some(master_key(1)));
+
+        // ファイルを指定した場合はファイルの鍵を使う
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("master.key");
+        std::fs::write(&path, format!("{}\n", "02".repeat(32))).unwrap();
+        let key = AccountMasterKey::from_lookup(lookup(vec![
+            ("MONAS_CONTENT_MASTER_KEY_FILE", path.display().to_string()),
+            ("MONAS_CONTENT_MASTER_KEY", "01".repeat(32)),
+        ]))
+        .unwrap();
+        assert_eq!(key, Some(master_key(2)));
+
+        assert!(matches!(
+            AccountMasterKey::from_lookup(lookup(vec![(
+                "MONAS_CONTENT_MASTER_KEY",
+                "01".repeat(16)
+            )])),
+            Err(AccountMasterKeyConfigError::InvalidKey { .. })
+        ));
+        assert!(matches!(
+            AccountMasterKey::from_lookup(lookup(vec![(
+                "MONAS_CONTENT_MASTER_KEY_FILE",
+                dir.path().join("missing").display().to_string()
+            )])),
+            Err(AccountMasterKeyConfigError::Read { .. })
+        ));
+    }
+
+    /// 系列の初版と更新後の版を保存したリポジトリを返す。
+    fn series_with_update(
+        dir: &tempfile::TempDir,
+        master_key: &AccountMasterKey,
+    ) -> (FsContentRepository, Content, Content) {
+        let repo = FsContentRepository::open(dir.path()).unwrap();
+        let generator = HkdfContentEncryptionKeyGenerator::new(master_key.clone());
+        let v1_id = Sha256ContentIdGenerator.generate(b"v1");
+        let key = generator.generate_for(&v1_id);
+        let (v1, _) = Content::create(
+            "name".into(),
+            b"v1".to_vec(),
+            "path.txt".into(),
+            None,
+            &Sha256ContentIdGenerator,
+            &key,
+            &Aes256CtrContentEncryption,
+        )
+        .unwrap();
+        let (v2, _) = v1
+            .update_content(
+                b"v2".to_vec(),
+                &Sha256ContentIdGenerator,
+                &key,
+                &Aes256CtrContentEncryption,
+            )
+            .unwrap();
+        repo.save(v1.raw_id(), &v1).unwrap();
+        repo.save(v2.raw_id(), &v2).unwrap();
+        (repo, v1, v2)
+    }
+
+    #[test]
+    fn store_keeps_only_rotated_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let (repo, v1, v2) = series_with_update(&dir, &master_key(1));
+        let inner = InMemoryContentEncryptionKeyStore::default();
+        let store = DerivedContentEncryptionKeyStore::new(inner.clone(), repo, master_key(1));
+        let derived = master_key(1).derive_cek(v1.raw_id());
+
+        // 導出可能な CEK は保存されず、更新後の版も系列の CEK を使う
+        store.save(v1.raw_id(), &derived).unwrap();
+        store
+            .save_in_series(v2.raw_id(), v2.series_id(), &derived)
+            .unwrap();
+        assert!(inner.load(v1.raw_id()).unwrap().is_none());
+        assert!(inner.load(v2.raw_id()).unwrap().is_none());
+        assert_eq!(store.load(v1.raw_id()).unwrap(), Some(derived.clone()));
+        assert_eq!(store.load(v2.raw_id()).unwrap(), Some(derived.clone()));
+
+        // ローテーション後の CEK は内側のストアに保存され、優先される
+        let rotated = OsRngContentEncryptionKeyGenerator.generate();
+        store.save(v2.raw_id(), &rotated).unwrap();
+        assert_eq!(inner.load(v2.raw_id()).unwrap(), Some(rotated.clone()));
+        assert_eq!(store.load(v2.raw_id()).unwrap(), Some(rotated));
+
+        // 導出された CEK に戻すと保存済みの CEK は削除される
+        store
+            .save_in_series(v2.raw_id(), v2.series_id(), &derived)
+            .unwrap();
+        assert!(inner.load(v2.raw_id()).unwrap().is_none());
+
+        // 存在しないコンテンツの CEK は導出しないが、系列を指定すれば保存前の版にも導出する
+        let unknown = ContentId::for_test("unknown");
+        assert!(store.load(&unknown).unwrap().is_none());
+        assert_eq!(
+            store.load_in_series(&unknown, v1.raw_id()).unwrap(),
+            Some(derived.clone())
+        );
+    }
+
+    #[test]
+    fn delete_shreds_every_version_of_the_series() {
+        let dir = tempfile::tempdir().unwrap();
+        let (repo, v1, v2) = series_with_update(&dir, &master_key(1));
+        let store = DerivedContentEncryptionKeyStore::new(
+            InMemoryContentEncryptionKeyStore::default(),
+            repo,
+            master_key(1),
+        );
+
+        store.delete(v2.raw_id()).unwrap();
+        assert!(store.load(v2.raw_id()).unwrap().is_none());
+        assert!(store.load(v1.raw_id()).unwrap().is_none());
+
+        // 初版を作り直すと（削除済みコンテンツの復元）再び導出できる
+        store
+            .save(v1.raw_id(), &master_key(1).derive_cek(v1.raw_id()))
+            .unwrap();
+        assert!(store.load(v1.raw_id()).unwrap().is_some());
+    }
+
+    #[test]
+    fn keys_are_recoverable_from_master_key_and_content_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let (repo, v1, v2) = series_with_update(&dir, &master_key(7));
+        let key = master_key(7).derive_cek(v1.raw_id());
+        let store = DerivedContentEncryptionKeyStore::new(
+            InMemoryContentEncryptionKeyStore::default(),
+            repo.clone(),
+            master_key(7),
+        );
+        store.save(v1.raw_id(), &key).unwrap();
+        store
+            .save_in_series(v2.raw_id(), v2.series_id(), &key)
+            .unwrap();
+
+        // 鍵ストアを失っても、同じマスター鍵とコンテンツから同じ CEK を得られる
+        let recovered = DerivedContentEncryptionKeyStore::new(
+            InMemoryContentEncryptionKeyStore::default(),
+            repo,
+            master_key(7),
+        );
+        assert_eq!(recovered.load(v1.raw_id()).unwrap(), Some(key.clone()));
+        assert_eq!(recovered.load(v2.raw_id()).unwrap(), Some(key));
+    }
+}
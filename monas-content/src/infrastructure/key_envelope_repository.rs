@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::application_service::share_service::{
+    KeyEnvelopeRepository, KeyEnvelopeRepositoryError,
+};
+use crate::domain::content_id::ContentId;
+use crate::domain::namespace::Namespace;
+use crate::domain::share::{KeyEnvelope, KeyId};
+
+/// `(content_id.as_str(), key_id のバイト列)`
+type EnvelopeKey = (String, Vec<u8>);
+
+/// シンプルなインメモリ実装の KeyEnvelopeRepository。
+///
+/// - key: `(content_id.as_str(), key_id のバイト列)`
+/// - value: `KeyEnvelope`
+#[derive(Clone, Default)]
+pub struct InMemoryKeyEnvelopeRepository {
+    inner: Arc<Mutex<HashMap<EnvelopeKey, KeyEnvelope>>>,
+}
+
+impl KeyEnvelopeRepository for InMemoryKeyEnvelopeRepository {
+    fn save(&self, envelope: &KeyEnvelope) -> Result<(), KeyEnvelopeRepositoryError> {
+        let mut guard = self
+            .inner
+            .lock()
+            .map_err(|e| KeyEnvelopeRepositoryError::Storage(e.to_string()))?;
+
+        let key = (
+            envelope.content_id().as_str().to_string(),
+            envelope.recipient().key_id().as_bytes().to_vec(),
+        );
+        guard.insert(key, envelope.clone());
+        Ok(())
+    }
+
+    fn find(
+        &self,
+        content_id: &ContentId,
+        recipient_key_id: &KeyId,
+    ) -> Result<Option<KeyEnvelope>, KeyEnvelopeRepositoryError> {
+        let guard = self
+            .inner
+            .lock()
+            .map_err(|e| KeyEnvelopeRepositoryError::Storage(e.to_string()))?;
+
+        let key = (
+            content_id.as_str().to_string(),
+            recipient_key_id.as_bytes().to_vec(),
+        );
+        Ok(guard.get(&key).cloned())
+    }
+
+    fn list_by_content(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Vec<KeyEnvelope>, KeyEnvelopeRepositoryError> {
+        let guard = self
+            .inner
+            .lock()
+            .map_err(|e| KeyEnvelopeRepositoryError::Storage(e.to_string()))?;
+
+        let mut envelopes: Vec<_> = guard
+            .iter()
+            .filter(|((cid, _), _)| cid == content_id.as_str())
+            .map(|(_, envelope)| envelope.clone())
+            .collect();
+        envelopes.sort_by(|a, b| {
+            a.recipient()
+                .key_id()
+                .as_bytes()
+                .cmp(b.recipient().key_id().as_bytes())
+        });
+        Ok(envelopes)
+    }
+}
+
+/// sled を用いた KeyEnvelopeRepository 実装。
+///
+/// - キー: `"envelope:{content_id.as_str()}:{key_id の 16 進数}"`。
+///   名前空間付きは `"ns:{namespace}:envelope:{content_id.as_str()}:{key_id の 16 進数}"`
+/// - 値: `KeyEnvelope` を JSON でシリアライズしたバイト列
+///
+/// CEK ストアなどと同じ DB を共有できるよう、`"envelope:"` プレフィックスでキー空間を分ける。
+#[derive(Clone)]
+pub struct SledKeyEnvelopeRepository {
+    db: sled::Db,
+    namespace: Option<Namespace>,
+}
+
+impl SledKeyEnvelopeRepository {
+    /// 既存の `sled::Db` ハンドルを共有してインスタンスを構築する。
+    pub fn with_db(db: sled::Db) -> Self {
+        Self {
+            db,
+            namespace: None,
+        }
+    }
+
+    /// 同じ DB を共有し、名前空間 `namespace` の KeyEnvelope だけを扱うリポジトリを返す。
+    pub fn scoped(&self, namespace: &Namespace) -> Self {
+        Self {
+            db: self.db.clone(),
+            namespace: Some(namespace.clone()),
+        }
+    }
+
+    /// `content_id` の KeyEnvelope のキーに共通するプレフィックス。
+    fn content_prefix(&self, content_id: &ContentId) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("ns:{namespace}:envelope:{}:", content_id.as_str()),
+            None => format!("envelope:{}:", content_id.as_str()),
+        }
+    }
+
+    fn sled_key(&self, content_id: &ContentId, key_id: &KeyId) -> String {
+        format!(
+            "{}{}",
+            self.content_prefix(content_id),
+            hex::encode(key_id.as_bytes())
+        )
+    }
+}
+
+impl KeyEnvelopeRepository for SledKeyEnvelopeRepository {
+    fn save(&self, envelope: &KeyEnvelope) -> Result<(), KeyEnvelopeRepositoryError> {
+        let key = self.sled_key(envelope.content_id(), envelope.recipient().key_id());
+        let value = serde_json::to_vec(envelope)
+            .map_err(|e| KeyEnvelopeRepositoryError::Storage(e.to_string()))?;
+
+        self.db
+            .insert(key, value)
+            .map_err(|e| KeyEnvelopeRepositoryError::Storage(e.to_string()))?;
+        self.db
+            .flush()
+            .map_err(|e| KeyEnvelopeRepositoryError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    fn find(
+        &self,
+        content_id: &ContentId,
+        recipient_key_id: &KeyId,
+    ) -> Result<Option<KeyEnvelope>, KeyEnvelopeRepositoryError> {
+        let value = self
+            .db
+            .get(self.sled_key(content_id, recipient_key_id))
+            .map_err(|e| KeyEnvelopeRepositoryError::Storage(e.to_string()))?;
+        value
+            .map(|value| {
+                serde_json::from_slice(&value)
+                    .map_err(|e| KeyEnvelopeRepositoryError::Storage(e.to_string()))
+            })
+            .transpose()
+    }
+
+    /// キーは KeyId の 16 進数で並ぶため、KeyId のバイト順に返す。
+    fn list_by_content(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Vec<KeyEnvelope>, KeyEnvelopeRepositoryError> {
+        self.db
+            .scan_prefix(self.content_prefix(content_id))
+            .map(|entry| {
+                let (_, value) =
+                    entry.map_err(|e| KeyEnvelopeRepositoryError::Storage(e.to_string()))?;
+                serde_json::from_slice(&value)
+                    .map_err(|e| KeyEnvelopeRepositoryError::Storage(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::share::{key_envelope::KeyWrapAlgorithm, WrappedRecipientKey};
+
+    fn envelope(content_id: &ContentId, key_id: u8) -> KeyEnvelope {
+        KeyEnvelope::new(
+            content_id.clone(),
+            KeyWrapAlgorithm::HpkeV1,
+            KeyId::new(vec![0xff]),
+            WrappedRecipientKey::new(KeyId::new(vec![key_id]), vec![1], vec![2]),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn save_find_and_list_envelopes() {
+        let repo = InMemoryKeyEnvelopeRepository::default();
+        let cid = ContentId::for_test("envelope-repo");
+        let other = ContentId::for_test("envelope-repo-other");
+
+        repo.save(&envelope(&cid, 2)).unwrap();
+        repo.save(&envelope(&cid, 1)).unwrap();
+        repo.save(&envelope(&other, 1)).unwrap();
+
+        let found = repo.find(&cid, &KeyId::new(vec![1])).unwrap();
+        assert_eq!(found, Some(envelope(&cid, 1)));
+        assert!(repo.find(&cid, &KeyId::new(vec![3])).unwrap().is_none());
+
+        let listed = repo.list_by_content(&cid).unwrap();
+        assert_eq!(listed, vec![envelope(&cid, 1), envelope(&cid, 2)]);
+    }
+
+    #[test]
+    fn sled_repository_scopes_envelopes_by_namespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = SledKeyEnvelopeRepository::with_db(sled::open(dir.path()).unwrap());
+        let scoped = repo.scoped(&Namespace::new("alice").unwrap());
+        let cid = ContentId::for_test("envelope-repo");
+
+        repo.save(&envelope(&cid, 2)).unwrap();
+        repo.save(&envelope(&cid, 1)).unwrap();
+        scoped.save(&envelope(&cid, 3)).unwrap();
+
+        assert_eq!(
+            repo.find(&cid, &KeyId::new(vec![1])).unwrap(),
+            Some(envelope(&cid, 1))
+        );
+        assert!(scoped.find(&cid, &KeyId::new(vec![1])).unwrap().is_none());
+        assert_eq!(
+            repo.list_by_content(&cid).unwrap(),
+            vec![envelope(&cid, 1), envelope(&cid, 2)]
+        );
+        assert_eq!(
+            scoped.list_by_content(&cid).unwrap(),
+            vec![envelope(&cid, 3)]
+        );
+    }
+}
@@ -0,0 +1,610 @@
+//! 組織向けキーエスクロー。
+//!
+//! `EscrowingContentEncryptionKeyStore` は任意の CEK ストアをラップするデコレータで、
+//! 新しい CEK が保存されるたびに組織のエスクロー公開鍵向けの KeyEnvelope を生成し、
+//! KeyEnvelopeRepository に記録する。従業員の鍵が失われた場合でも、
+//! エスクロー秘密鍵から CEK を復旧できることを保証するための仕組み。
+//!
+//! - エスクローと監査記録の両方が成功した場合に限り、内側のストアへ CEK を保存する。
+//!   どちらかが失敗した場合は CEK の保存自体を失敗させ、復旧不能な CEK が発行されないようにする。
+//! - CEK を削除してもエスクロー済みの KeyEnvelope は残す（復旧のため）。
+//!
+//! サーバーでは [`KeyEscrowConfig::from_env`] でエスクロー公開鍵を指定した場合に有効になり、
+//! KeyEnvelope と監査イベントは状態 DB（[`SledKeyEnvelopeRepository`] と [`SledEscrowAuditLog`]）に
+//! 記録する。
+//!
+//! [`SledKeyEnvelopeRepository`]: crate::infrastructure::key_envelope_repository::SledKeyEnvelopeRepository
+
+use std::sync::{Arc, Mutex};
+
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use chrono::Utc;
+
+use crate::application_service::content_service::{
+    ContentEncryptionKeyStore, ContentEncryptionKeyStoreError,
+};
+use crate::application_service::share_service::{
+    EscrowAuditLog, EscrowAuditLogError, KeyEnvelopeRepository, KeyEnvelopeRepositoryError,
+    PublicKeyDirectory,
+};
+use crate::domain::content::encryption::ContentEncryptionKey;
+use crate::domain::content_id::ContentId;
+use crate::domain::namespace::Namespace;
+use crate::domain::share::{
+    encryption::KeyWrapping, key_envelope::KeyWrapAlgorithm, EscrowAuditEvent, KeyEnvelope, KeyId,
+    WrappedRecipientKey,
+};
+use crate::infrastructure::public_key_directory::InMemoryPublicKeyDirectory;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyEscrowConfigError {
+    #[error("invalid value for {name}: {value}")]
+    InvalidValue { name: &'static str, value: String },
+}
+
+/// エスクロー先となる組織の鍵の設定。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyEscrowConfig {
+    escrow_key_id: KeyId,
+    escrow_public_key: Vec<u8>,
+}
+
+impl KeyEscrowConfig {
+    /// - `escrow_key_id`: エスクロー鍵の KeyId（KeyEnvelope の受信者として記録される）。
+    /// - `escrow_public_key`: CEK のラップに使うエスクロー公開鍵バイト列。
+    pub fn new(escrow_key_id: KeyId, escrow_public_key: Vec<u8>) -> Self {
+        Self {
+            escrow_key_id,
+            escrow_public_key,
+        }
+    }
+
+    /// 環境変数から設定を読み込む。`MONAS_CONTENT_ESCROW_PUBLIC_KEY` が未設定なら `None`
+    /// （エスクローしない）。
+    ///
+    /// - `MONAS_CONTENT_ESCROW_PUBLIC_KEY`: エスクロー公開鍵（P-256 の SEC1 形式を Base64 で）
+    ///
+    /// KeyId は共有の受信者と同じく、公開鍵の SHA-256 の先頭 16 バイトから計算する。
+    pub fn from_env() -> Result<Option<Self>, KeyEscrowConfigError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, KeyEscrowConfigError> {
+        const PUBLIC_KEY: &str = "MONAS_CONTENT_ESCROW_PUBLIC_KEY";
+
+        let Some(value) = lookup(PUBLIC_KEY).filter(|v| !v.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let public_key = match BASE64_STANDARD.decode(value.trim()) {
+            Ok(bytes) if p256::PublicKey::from_sec1_bytes(&bytes).is_ok() => bytes,
+            _ => {
+                return Err(KeyEscrowConfigError::InvalidValue {
+                    name: PUBLIC_KEY,
+                    value,
+                })
+            }
+        };
+        let escrow_key_id = InMemoryPublicKeyDirectory::default().compute_key_id(&public_key);
+        Ok(Some(Self::new(escrow_key_id, public_key)))
+    }
+
+    pub fn escrow_key_id(&self) -> &KeyId {
+        &self.escrow_key_id
+    }
+
+    pub fn escrow_public_key(&self) -> &[u8] {
+        &self.escrow_public_key
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyEscrowError {
+    #[error("no escrowed envelope for content")]
+    NotEscrowed,
+
+    #[error("key wrapping error: {0}")]
+    KeyWrapping(String),
+
+    #[error("key envelope repository error: {0}")]
+    KeyEnvelopeRepository(KeyEnvelopeRepositoryError),
+
+    #[error("escrow audit error: {0}")]
+    AuditLog(EscrowAuditLogError),
+}
+
+/// CEK の保存時にエスクロー用 KeyEnvelope を記録する CEK ストアのデコレータ。
+pub struct EscrowingContentEncryptionKeyStore<KS, KW, ER, A> {
+    inner: KS,
+    key_wrapper: KW,
+    envelope_repository: ER,
+    audit_log: A,
+    config: KeyEscrowConfig,
+}
+
+impl<KS, KW, ER, A> EscrowingContentEncryptionKeyStore<KS, KW, ER, A>
+where
+    KS: ContentEncryptionKeyStore,
+    KW: KeyWrapping,
+    ER: KeyEnvelopeRepository,
+    A: EscrowAuditLog,
+{
+    pub fn new(
+        inner: KS,
+        key_wrapper: KW,
+        envelope_repository: ER,
+        audit_log: A,
+        config: KeyEscrowConfig,
+    ) -> Self {
+        Self {
+            inner,
+            key_wrapper,
+            envelope_repository,
+            audit_log,
+            config,
+        }
+    }
+
+    pub fn inner(&self) -> &KS {
+        &self.inner
+    }
+
+    pub fn config(&self) -> &KeyEscrowConfig {
+        &self.config
+    }
+
+    /// エスクロー秘密鍵を用いて、指定コンテンツの CEK を復旧する。
+    ///
+    /// 復旧の成否はいずれも監査イベントとして記録する。
+    /// 成功時の監査記録に失敗した場合は CEK を返さない。
+    pub fn recover_cek(
+        &self,
+        content_id: &ContentId,
+        escrow_private_key: &[u8],
+    ) -> Result<ContentEncryptionKey, KeyEscrowError> {
+        let recovered = self.unwrap_escrowed_cek(content_id, escrow_private_key);
+
+        match recovered {
+            Ok(cek) => {
+                self.audit_log
+                    .record(EscrowAuditEvent::CekRecovered {
+                        content_id: content_id.clone(),
+                        escrow_key_id: self.config.escrow_key_id.clone(),
+                        at: Utc::now(),
+                    })
+                    .map_err(KeyEscrowError::AuditLog)?;
+                Ok(cek)
+            }
+            Err(e) => {
+                self.audit_log
+                    .record(EscrowAuditEvent::RecoveryFailed {
+                        content_id: content_id.clone(),
+                        escrow_key_id: self.config.escrow_key_id.clone(),
+                        reason: e.to_string(),
+                        at: Utc::now(),
+                    })
+                    .map_err(KeyEscrowError::AuditLog)?;
+                Err(e)
+            }
+        }
+    }
+
+    fn unwrap_escrowed_cek(
+        &self,
+        content_id: &ContentId,
+        escrow_private_key: &[u8],
+    ) -> Result<ContentEncryptionKey, KeyEscrowError> {
+        let envelope = self
+            .envelope_repository
+            .find(content_id, &self.config.escrow_key_id)
+            .map_err(KeyEscrowError::KeyEnvelopeRepository)?
+            .ok_or(KeyEscrowError::NotEscrowed)?;
+
+        let recipient = envelope.recipient();
+        self.key_wrapper
+            .unwrap_cek(
+                recipient.enc(),
+                recipient.wrapped_cek(),
+                escrow_private_key,
+                content_id,
+            )
+            .map_err(|e| KeyEscrowError::KeyWrapping(format!("{e:?}")))
+    }
+
+    /// CEK をエスクロー公開鍵でラップし、KeyEnvelope として記録する。
+    ///
+    /// エスクロー用の KeyEnvelope は CEK の配送ではなく復旧のみを目的とするため、
+    /// 暗号文は含めない（暗号文はコンテンツストレージから取得する）。
+    /// 送信者はエスクロー鍵自身として記録する。
+    fn escrow(
+        &self,
+        content_id: &ContentId,
+        key: &ContentEncryptionKey,
+    ) -> Result<(), KeyEscrowError> {
+        let (enc, wrapped_cek) = self
+            .key_wrapper
+            .wrap_cek(key, &self.config.escrow_public_key, content_id)
+            .map_err(|e| KeyEscrowError::KeyWrapping(format!("{e:?}")))?;
+
+        let envelope = KeyEnvelope::new(
+            content_id.clone(),
+            KeyWrapAlgorithm::HpkeV1,
+            self.config.escrow_key_id.clone(),
+            WrappedRecipientKey::new(self.config.escrow_key_id.clone(), enc, wrapped_cek),
+            Vec::new(),
+        );
+
+        self.envelope_repository
+            .save(&envelope)
+            .map_err(KeyEscrowError::KeyEnvelopeRepository)
+    }
+
+    /// CEK をエスクローし、成否を監査ログに記録する。
+    fn escrow_audited(
+        &self,
+        content_id: &ContentId,
+        key: &ContentEncryptionKey,
+    ) -> Result<(), ContentEncryptionKeyStoreError> {
+        if let Err(e) = self.escrow(content_id, key) {
+            // 失敗の監査記録が失敗しても、元のエスクロー失敗を優先して返す。
+            let _ = self.audit_log.record(EscrowAuditEvent::EscrowFailed {
+                content_id: content_id.clone(),
+                escrow_key_id: self.config.escrow_key_id.clone(),
+                reason: e.to_string(),
+                at: Utc::now(),
+            });
+            return Err(ContentEncryptionKeyStoreError::Storage(format!(
+                "key escrow failed: {e}"
+            )));
+        }
+
+        self.audit_log
+            .record(EscrowAuditEvent::CekEscrowed {
+                content_id: content_id.clone(),
+                escrow_key_id: self.config.escrow_key_id.clone(),
+                at: Utc::now(),
+            })
+            .map_err(|e| {
+                ContentEncryptionKeyStoreError::Storage(format!("key escrow audit failed: {e}"))
+            })
+    }
+}
+
+impl<KS, KW, ER, A> ContentEncryptionKeyStore for EscrowingContentEncryptionKeyStore<KS, KW, ER, A>
+where
+    KS: ContentEncryptionKeyStore,
+    KW: KeyWrapping,
+    ER: KeyEnvelopeRepository,
+    A: EscrowAuditLog,
+{
+    fn save(
+        &self,
+        content_id: &ContentId,
+        key: &ContentEncryptionKey,
+    ) -> Result<(), ContentEncryptionKeyStoreError> {
+        self.escrow_audited(content_id, key)?;
+        self.inner.save(content_id, key)
+    }
+
+    fn save_in_series(
+        &self,
+        content_id: &ContentId,
+        series_id: &ContentId,
+        key: &ContentEncryptionKey,
+    ) -> Result<(), ContentEncryptionKeyStoreError> {
+        self.escrow_audited(content_id, key)?;
+        self.inner.save_in_series(content_id, series_id, key)
+    }
+
+    fn load(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Option<ContentEncryptionKey>, ContentEncryptionKeyStoreError> {
+        self.inner.load(content_id)
+    }
+
+    fn load_in_series(
+        &self,
+        content_id: &ContentId,
+        series_id: &ContentId,
+    ) -> Result<Option<ContentEncryptionKey>, ContentEncryptionKeyStoreError> {
+        self.inner.load_in_series(content_id, series_id)
+    }
+
+    fn delete(&self, content_id: &ContentId) -> Result<(), ContentEncryptionKeyStoreError> {
+        self.inner.delete(content_id)
+    }
+
+    fn flush(&self) -> Result<(), ContentEncryptionKeyStoreError> {
+        self.inner.flush()
+    }
+}
+
+/// プロセス内にエスクロー監査イベントを蓄積するインメモリ実装。
+///
+/// - ローカル開発やテスト用途を想定。本番では改ざん耐性のある監査ログに差し替えること。
+#[derive(Clone, Default)]
+pub struct InMemoryEscrowAuditLog {
+    events: Arc<Mutex<Vec<EscrowAuditEvent>>>,
+}
+
+impl InMemoryEscrowAuditLog {
+    /// 記録済みのイベントを記録順に返す。
+    pub fn events(&self) -> Vec<EscrowAuditEvent> {
+        self.events
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl EscrowAuditLog for InMemoryEscrowAuditLog {
+    fn record(&self, event: EscrowAuditEvent) -> Result<(), EscrowAuditLogError> {
+        let mut guard = self
+            .events
+            .lock()
+            .map_err(|e| EscrowAuditLogError::Record(e.to_string()))?;
+        guard.push(event);
+        Ok(())
+    }
+}
+
+/// sled を用いた EscrowAuditLog 実装。
+///
+/// - キー: `"escrow-audit:{連番（16 桁の 16 進数）}"`。
+///   名前空間付きは `"ns:{namespace}:escrow-audit:{連番}"`
+/// - 値: `EscrowAuditEvent` を JSON でシリアライズしたバイト列
+///
+/// 監査イベントを失わないよう、記録のたびに flush する。
+#[derive(Clone)]
+pub struct SledEscrowAuditLog {
+    db: sled::Db,
+    namespace: Option<Namespace>,
+}
+
+impl SledEscrowAuditLog {
+    /// 既存の `sled::Db` ハンドルを共有してインスタンスを構築する。
+    pub fn with_db(db: sled::Db) -> Self {
+        Self {
+            db,
+            namespace: None,
+        }
+    }
+
+    /// 同じ DB を共有し、名前空間 `namespace` の監査イベントだけを扱うログを返す。
+    pub fn scoped(&self, namespace: &Namespace) -> Self {
+        Self {
+            db: self.db.clone(),
+            namespace: Some(namespace.clone()),
+        }
+    }
+
+    fn prefix(&self) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("ns:{namespace}:escrow-audit:"),
+            None => "escrow-audit:".to_string(),
+        }
+    }
+
+    /// 記録済みのイベントを記録順に返す。
+    pub fn events(&self) -> Result<Vec<EscrowAuditEvent>, EscrowAuditLogError> {
+        self.db
+            .scan_prefix(self.prefix())
+            .map(|entry| {
+                let (_, value) = entry.map_err(|e| EscrowAuditLogError::Record(e.to_string()))?;
+                serde_json::from_slice(&value)
+                    .map_err(|e| EscrowAuditLogError::Record(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+impl EscrowAuditLog for SledEscrowAuditLog {
+    fn record(&self, event: EscrowAuditEvent) -> Result<(), EscrowAuditLogError> {
+        let sequence = self
+            .db
+            .generate_id()
+            .map_err(|e| EscrowAuditLogError::Record(e.to_string()))?;
+        let value =
+            serde_json::to_vec(&event).map_err(|e| EscrowAuditLogError::Record(e.to_string()))?;
+
+        self.db
+            .insert(format!("{}{sequence:016x}", self.prefix()), value)
+            .map_err(|e| EscrowAuditLogError::Record(e.to_string()))?;
+        self.db
+            .flush()
+            .map_err(|e| EscrowAuditLogError::Record(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::key_envelope_repository::InMemoryKeyEnvelopeRepository;
+    use crate::infrastructure::key_store::InMemoryContentEncryptionKeyStore;
+    use crate::infrastructure::key_wrapping::HpkeV1KeyWrapping;
+    use hpke_rs::hpke_types::{AeadAlgorithm, KdfAlgorithm, KemAlgorithm};
+    use hpke_rs::prelude::*;
+    use hpke_rs_rust_crypto::HpkeRustCrypto;
+
+    struct FailingAuditLog;
+
+    impl EscrowAuditLog for FailingAuditLog {
+        fn record(&self, _event: EscrowAuditEvent) -> Result<(), EscrowAuditLogError> {
+            Err(EscrowAuditLogError::Record("audit sink unavailable".into()))
+        }
+    }
+
+    fn escrow_keypair() -> (Vec<u8>, Vec<u8>) {
+        let mut hpke = Hpke::<HpkeRustCrypto>::new(
+            Mode::Base,
+            KemAlgorithm::DhKemP256,
+            KdfAlgorithm::HkdfSha256,
+            AeadAlgorithm::Aes256Gcm,
+        );
+        let keypair = hpke
+            .generate_key_pair()
+            .expect("failed to generate HPKE key pair");
+        (
+            keypair.public_key().as_slice().to_vec(),
+            keypair.private_key().as_slice().to_vec(),
+        )
+    }
+
+    fn escrow_key_id() -> KeyId {
+        KeyId::new(b"org-escrow".to_vec())
+    }
+
+    #[test]
+    fn save_escrows_cek_and_records_audit_event() {
+        let (pk, sk) = escrow_keypair();
+        let envelopes = InMemoryKeyEnvelopeRepository::default();
+        let audit = InMemoryEscrowAuditLog::default();
+        let store = EscrowingContentEncryptionKeyStore::new(
+            InMemoryContentEncryptionKeyStore::default(),
+            HpkeV1KeyWrapping,
+            envelopes.clone(),
+            audit.clone(),
+            KeyEscrowConfig::new(escrow_key_id(), pk),
+        );
+        let cid = ContentId::for_test("escrowed");
+        let cek = ContentEncryptionKey(vec![0x42; 32]);
+
+        store.save(&cid, &cek).unwrap();
+
+        assert_eq!(store.load(&cid).unwrap(), Some(cek.clone()));
+        let envelope = envelopes.find(&cid, &escrow_key_id()).unwrap().unwrap();
+        assert_eq!(envelope.recipient().key_id(), &escrow_key_id());
+        assert!(matches!(
+            audit.events().as_slice(),
+            [EscrowAuditEvent::CekEscrowed { content_id, .. }] if content_id == &cid
+        ));
+
+        // 元の CEK を削除しても、エスクロー秘密鍵から復旧できる。
+        store.delete(&cid).unwrap();
+        assert_eq!(store.recover_cek(&cid, &sk).unwrap(), cek);
+        assert!(matches!(
+            audit.events().last(),
+            Some(EscrowAuditEvent::CekRecovered { .. })
+        ));
+    }
+
+    #[test]
+    fn save_fails_without_storing_cek_when_wrapping_fails() {
+        let audit = InMemoryEscrowAuditLog::default();
+        let store = EscrowingContentEncryptionKeyStore::new(
+            InMemoryContentEncryptionKeyStore::default(),
+            HpkeV1KeyWrapping,
+            InMemoryKeyEnvelopeRepository::default(),
+            audit.clone(),
+            KeyEscrowConfig::new(escrow_key_id(), vec![0u8; 3]),
+        );
+        let cid = ContentId::for_test("invalid-escrow-key");
+
+        let result = store.save(&cid, &ContentEncryptionKey(vec![1; 32]));
+
+        assert!(result.is_err());
+        assert_eq!(store.load(&cid).unwrap(), None);
+        assert!(matches!(
+            audit.events().as_slice(),
+            [EscrowAuditEvent::EscrowFailed { .. }]
+        ));
+    }
+
+    #[test]
+    fn save_fails_without_storing_cek_when_audit_fails() {
+        let (pk, _sk) = escrow_keypair();
+        let store = EscrowingContentEncryptionKeyStore::new(
+            InMemoryContentEncryptionKeyStore::default(),
+            HpkeV1KeyWrapping,
+            InMemoryKeyEnvelopeRepository::default(),
+            FailingAuditLog,
+            KeyEscrowConfig::new(escrow_key_id(), pk),
+        );
+        let cid = ContentId::for_test("audit-down");
+
+        let result = store.save(&cid, &ContentEncryptionKey(vec![1; 32]));
+
+        assert!(result.is_err());
+        assert_eq!(store.load(&cid).unwrap(), None);
+    }
+
+    #[test]
+    fn from_lookup_requires_a_p256_public_key() {
+        let (pk, _sk) = escrow_keypair();
+        let lookup = |value: Option<String>| {
+            move |key: &str| (key == "MONAS_CONTENT_ESCROW_PUBLIC_KEY").then(|| value.clone())?
+        };
+
+        assert!(KeyEscrowConfig::from_lookup(lookup(None))
+            .unwrap()
+            .is_none());
+        let config = KeyEscrowConfig::from_lookup(lookup(Some(BASE64_STANDARD.encode(&pk))))
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.escrow_public_key(), pk.as_slice());
+        assert_eq!(
+            config.escrow_key_id(),
+            &InMemoryPublicKeyDirectory::default().compute_key_id(&pk)
+        );
+        assert!(
+            KeyEscrowConfig::from_lookup(lookup(Some(BASE64_STANDARD.encode([0u8; 3])))).is_err()
+        );
+    }
+
+    #[test]
+    fn sled_audit_log_keeps_events_in_order_per_namespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = SledEscrowAuditLog::with_db(sled::open(dir.path()).unwrap());
+        let scoped = log.scoped(&Namespace::new("alice").unwrap());
+        let event = |label: &str| EscrowAuditEvent::CekEscrowed {
+            content_id: ContentId::for_test(label),
+            escrow_key_id: escrow_key_id(),
+            at: Utc::now(),
+        };
+
+        log.record(event("first")).unwrap();
+        log.record(event("second")).unwrap();
+        scoped.record(event("scoped")).unwrap();
+
+        let contents = |events: Vec<EscrowAuditEvent>| {
+            events
+                .iter()
+                .map(|event| event.content_id().clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            contents(log.events().unwrap()),
+            vec![ContentId::for_test("first"), ContentId::for_test("second")]
+        );
+        assert_eq!(
+            contents(scoped.events().unwrap()),
+            vec![ContentId::for_test("scoped")]
+        );
+    }
+
+    #[test]
+    fn recover_without_envelope_records_failure() {
+        let (pk, sk) = escrow_keypair();
+        let audit = InMemoryEscrowAuditLog::default();
+        let store = EscrowingContentEncryptionKeyStore::new(
+            InMemoryContentEncryptionKeyStore::default(),
+            HpkeV1KeyWrapping,
+            InMemoryKeyEnvelopeRepository::default(),
+            audit.clone(),
+            KeyEscrowConfig::new(escrow_key_id(), pk),
+        );
+        let cid = ContentId::for_test("never-escrowed");
+
+        let result = store.recover_cek(&cid, &sk);
+
+        assert!(matches!(result, Err(KeyEscrowError::NotEscrowed)));
+        assert!(matches!(
+            audit.events().as_slice(),
+            [EscrowAuditEvent::RecoveryFailed { .. }]
+        ));
+    }
+}
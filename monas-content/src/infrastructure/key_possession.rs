@@ -0,0 +1,80 @@
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+
+use crate::application_service::share_service::{KeyPossessionError, KeyPossessionVerifier};
+
+/// ECDSA P-256 / SHA-256 の署名で鍵の所有を確認する実装。
+///
+/// - 公開鍵は HPKE の受信者鍵と同じ SEC1 形式（uncompressed 65 バイト / compressed 33 バイト）を受け付ける。
+/// - 署名は固定長（r || s, 64 バイト）と DER のどちらでもよい。
+#[derive(Debug, Default, Clone, Copy)]
+pub struct P256EcdsaKeyPossessionVerifier;
+
+impl KeyPossessionVerifier for P256EcdsaKeyPossessionVerifier {
+    fn verify(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), KeyPossessionError> {
+        let verifying_key = VerifyingKey::from_sec1_bytes(public_key)
+            .map_err(|e| KeyPossessionError::Invalid(format!("invalid public key: {e}")))?;
+        let signature = Signature::from_slice(signature)
+            .or_else(|_| Signature::from_der(signature))
+            .map_err(|e| KeyPossessionError::Invalid(format!("invalid signature: {e}")))?;
+        verifying_key
+            .verify(message, &signature)
+            .map_err(|_| KeyPossessionError::Invalid("signature verification failed".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::signature::Signer;
+    use p256::ecdsa::SigningKey;
+    use rand_core::OsRng;
+
+    #[test]
+    fn accepts_fixed_and_der_signatures_from_the_key_holder() {
+        let signing_key = SigningKey::random(&mut OsRng);
+        let public_key = signing_key
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec();
+        let message = b"monas-shared-fetch-v1";
+        let signature: Signature = signing_key.sign(message);
+
+        let verifier = P256EcdsaKeyPossessionVerifier;
+        verifier
+            .verify(&public_key, message, &signature.to_bytes())
+            .unwrap();
+        verifier
+            .verify(&public_key, message, signature.to_der().as_bytes())
+            .unwrap();
+    }
+
+    #[test]
+    fn rejects_signatures_over_other_messages_or_keys() {
+        let signing_key = SigningKey::random(&mut OsRng);
+        let other_key = SigningKey::random(&mut OsRng);
+        let public_key = signing_key
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec();
+        let signature: Signature = signing_key.sign(b"original");
+        let other_signature: Signature = other_key.sign(b"original");
+
+        let verifier = P256EcdsaKeyPossessionVerifier;
+        assert!(matches!(
+            verifier.verify(&public_key, b"tampered", &signature.to_bytes()),
+            Err(KeyPossessionError::Invalid(_))
+        ));
+        assert!(matches!(
+            verifier.verify(&public_key, b"original", &other_signature.to_bytes()),
+            Err(KeyPossessionError::Invalid(_))
+        ));
+    }
+}
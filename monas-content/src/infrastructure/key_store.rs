@@ -5,7 +5,9 @@ use std::sync::{Arc, Mutex};
 use crate::application_service::content_service::{
     ContentEncryptionKeyStore, ContentEncryptionKeyStoreError,
 };
-use crate::domain::{content::encryption::ContentEncryptionKey, content_id::ContentId};
+use crate::domain::{
+    content::encryption::ContentEncryptionKey, content_id::ContentId, namespace::Namespace,
+};
 
 /// プロセス内の `HashMap` に CEK を保存するインメモリ実装。
 ///
@@ -56,7 +58,8 @@ impl ContentEncryptionKeyStore for InMemoryContentEncryptionKeyStore {
 
 /// sled を用いた CEK ストア実装。
 ///
-/// - キー: `"cek:{content_id.as_str()}"`（UTF-8 文字列）
+/// - キー: `"cek:{content_id.as_str()}"`（UTF-8 文字列）。名前空間に属する場合は
+///   `"ns:{namespace}:cek:{content_id.as_str()}"`
 /// - 値: CEK のバイト列（`ContentEncryptionKey.0`）
 ///
 /// NOTE:
@@ -64,15 +67,20 @@ impl ContentEncryptionKeyStore for InMemoryContentEncryptionKeyStore {
 ///   同じ DB ファイルを共有しても、プレフィックスによりキー空間が分離される。
 /// - sled 実装はあくまでローカル用の暫定実装であり、
 ///   本番環境では別の KVS / ストレージに置き換える可能性がある。
+#[derive(Clone)]
 pub struct SledContentEncryptionKeyStore {
     db: sled::Db,
+    namespace: Option<Namespace>,
 }
 
 impl SledContentEncryptionKeyStore {
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ContentEncryptionKeyStoreError> {
         let db =
             sled::open(path).map_err(|e| ContentEncryptionKeyStoreError::Storage(e.to_string()))?;
-        Ok(Self { db })
+        Ok(Self {
+            db,
+            namespace: None,
+        })
     }
 
     /// 既存の `sled::Db` ハンドルを共有してインスタンスを構築する。
@@ -82,7 +90,25 @@ impl SledContentEncryptionKeyStore {
     /// 同じ DB ファイルに同居させたい場合は、外側で 1 度だけ `sled::open` した
     /// `sled::Db` をこのコンストラクタ経由で渡す。
     pub fn with_db(db: sled::Db) -> Self {
-        Self { db }
+        Self {
+            db,
+            namespace: None,
+        }
+    }
+
+    /// 同じ DB を共有し、名前空間 `namespace` の CEK だけを扱うストアを返す。
+    pub fn scoped(&self, namespace: &Namespace) -> Self {
+        Self {
+            db: self.db.clone(),
+            namespace: Some(namespace.clone()),
+        }
+    }
+
+    fn sled_key(&self, content_id: &ContentId) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("ns:{namespace}:cek:{}", content_id.as_str()),
+            None => format!("cek:{}", content_id.as_str()),
+        }
     }
 }
 
@@ -92,9 +118,9 @@ impl ContentEncryptionKeyStore for SledContentEncryptionKeyStore {
         content_id: &ContentId,
         key: &ContentEncryptionKey,
     ) -> Result<(), ContentEncryptionKeyStoreError> {
-        let sled_key = format!("cek:{}", content_id.as_str());
+        let sled_key = self.sled_key(content_id);
         self.db
-            .insert(sled_key, key.0.clone())
+            .insert(sled_key, key.0.as_slice())
             .map_err(|e| ContentEncryptionKeyStoreError::Storage(e.to_string()))?;
         self.db
             .flush()
@@ -106,7 +132,7 @@ impl ContentEncryptionKeyStore for SledContentEncryptionKeyStore {
         &self,
         content_id: &ContentId,
     ) -> Result<Option<ContentEncryptionKey>, ContentEncryptionKeyStoreError> {
-        let sled_key = format!("cek:{}", content_id.as_str());
+        let sled_key = self.sled_key(content_id);
         let opt = self
             .db
             .get(sled_key)
@@ -116,7 +142,7 @@ impl ContentEncryptionKeyStore for SledContentEncryptionKeyStore {
     }
 
     fn delete(&self, content_id: &ContentId) -> Result<(), ContentEncryptionKeyStoreError> {
-        let sled_key = format!("cek:{}", content_id.as_str());
+        let sled_key = self.sled_key(content_id);
         self.db
             .remove(sled_key)
             .map_err(|e| ContentEncryptionKeyStoreError::Storage(e.to_string()))?;
@@ -125,4 +151,39 @@ impl ContentEncryptionKeyStore for SledContentEncryptionKeyStore {
             .map_err(|e| ContentEncryptionKeyStoreError::Storage(e.to_string()))?;
         Ok(())
     }
+
+    fn flush(&self) -> Result<(), ContentEncryptionKeyStoreError> {
+        self.db
+            .flush()
+            .map_err(|e| ContentEncryptionKeyStoreError::Storage(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sled_store_scopes_keys_by_namespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SledContentEncryptionKeyStore::open(dir.path()).unwrap();
+        let alice = store.scoped(&Namespace::new("alice").unwrap());
+        let bob = store.scoped(&Namespace::new("bob").unwrap());
+        let content_id = ContentId::for_test("shared-plaintext");
+
+        alice
+            .save(&content_id, &ContentEncryptionKey(vec![1; 32]))
+            .unwrap();
+        bob.save(&content_id, &ContentEncryptionKey(vec![2; 32]))
+            .unwrap();
+
+        assert_eq!(alice.load(&content_id).unwrap().unwrap().0, vec![1; 32]);
+        assert_eq!(bob.load(&content_id).unwrap().unwrap().0, vec![2; 32]);
+        assert!(store.load(&content_id).unwrap().is_none());
+
+        alice.delete(&content_id).unwrap();
+        assert!(alice.load(&content_id).unwrap().is_none());
+        assert!(bob.load(&content_id).unwrap().is_some());
+    }
 }
@@ -103,7 +103,7 @@ mod tests {
         let wrapper = HpkeV1KeyWrapping;
         let cek = ContentEncryptionKey(vec![0x11; 32]);
         let (pk_bytes, _sk) = generate_p256_keypair();
-        let cid = ContentId::new("test-content-id".into());
+        let cid = ContentId::for_test("test-content-id");
 
         let (enc, wrapped) = wrapper
             .wrap_cek(&cek, &pk_bytes, &cid)
@@ -117,7 +117,7 @@ mod tests {
     fn wrap_cek_can_be_decrypted_by_hpke_receiver() {
         let wrapper = HpkeV1KeyWrapping;
         let cek = ContentEncryptionKey((0u8..32).collect());
-        let cid = ContentId::new("roundtrip-test".into());
+        let cid = ContentId::for_test("roundtrip-test");
 
         let (mode, kem, kdf, aead) = HpkeV1KeyWrapping::hpke_config();
         let mut hpke = Hpke::<HpkeRustCrypto>::new(mode, kem, kdf, aead);
@@ -147,7 +147,7 @@ mod tests {
     fn decrypt_fails_with_wrong_content_id() {
         let wrapper = HpkeV1KeyWrapping;
         let cek = ContentEncryptionKey(vec![0xAA; 32]);
-        let cid = ContentId::new("correct-content-id".into());
+        let cid = ContentId::for_test("correct-content-id");
 
         let (mode, kem, kdf, aead) = HpkeV1KeyWrapping::hpke_config();
         let mut hpke = Hpke::<HpkeRustCrypto>::new(mode, kem, kdf, aead);
@@ -162,7 +162,7 @@ mod tests {
             .wrap_cek(&cek, &pk_bytes, &cid)
             .expect("hpke wrap_cek should succeed");
 
-        let wrong_cid = ContentId::new("wrong-content-id".into());
+        let wrong_cid = ContentId::for_test("wrong-content-id");
         let wrong_info = wrong_cid.as_str().as_bytes();
         let wrong_aad = wrong_info;
 
@@ -181,7 +181,7 @@ mod tests {
     fn wrap_cek_fails_with_invalid_public_key_bytes() {
         let wrapper = HpkeV1KeyWrapping;
         let cek = ContentEncryptionKey(vec![0x42; 32]);
-        let cid = ContentId::new("invalid-pk-test".into());
+        let cid = ContentId::for_test("invalid-pk-test");
         let invalid_pk = vec![0u8; 10];
 
         let result = wrapper.wrap_cek(&cek, &invalid_pk, &cid);
@@ -196,7 +196,7 @@ mod tests {
     fn unwrap_cek_roundtrip_with_valid_private_key_bytes() {
         let wrapper = HpkeV1KeyWrapping;
         let cek = ContentEncryptionKey((0u8..32).collect());
-        let cid = ContentId::new("unwrap-roundtrip".into());
+        let cid = ContentId::for_test("unwrap-roundtrip");
 
         let (mode, kem, kdf, aead) = HpkeV1KeyWrapping::hpke_config();
         let mut hpke = Hpke::<HpkeRustCrypto>::new(mode, kem, kdf, aead);
@@ -225,7 +225,7 @@ mod tests {
     fn unwrap_cek_fails_with_invalid_private_key_bytes() {
         let wrapper = HpkeV1KeyWrapping;
         let cek = ContentEncryptionKey(vec![0x33; 32]);
-        let cid = ContentId::new("invalid-sk-test".into());
+        let cid = ContentId::for_test("invalid-sk-test");
 
         let (mode, kem, kdf, aead) = HpkeV1KeyWrapping::hpke_config();
         let mut hpke = Hpke::<HpkeRustCrypto>::new(mode, kem, kdf, aead);
@@ -0,0 +1,449 @@
+//! メタデータ（name / path）を暗号化して保存する ContentRepository デコレータ。
+//!
+//! コンテンツ本体は CEK で暗号化されるが、`Metadata` の name / path は平文のまま
+//! ストレージに保存される。このデコレータで既存のリポジトリを包むと、保存時に
+//! name / path を暗号化し、ContentId・タイムスタンプ・プロバイダーといった
+//! ルーティングに必要な情報だけを平文で残す。
+//!
+//! CEK は ContentService が保存した CEK ストアから取得するため、
+//! ContentService と同じ CEK ストアを渡すこと。
+//!
+//! サーバーでは [`MetadataKeyMode::from_env`] で鍵の選択を指定した場合に有効になる。
+//! パスによる取得に使うパスのインデックスとコンテンツ一覧は、ドメインイベントから
+//! サーバーの状態 DB に記録するため、暗号化した行でもそのまま使える。
+
+use crate::application_service::content_service::{
+    ContentEncryptionKeyStore, ContentRepository, ContentRepositoryError,
+    MultiStorageContentRepository,
+};
+use crate::domain::content::encryption::{ContentEncryption, ContentEncryptionKey};
+use crate::domain::content::Content;
+use crate::domain::content_id::ContentId;
+use crate::infrastructure::encryption::derive_metadata_key;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MetadataKeyModeError {
+    #[error("invalid value for {name}: {value}")]
+    InvalidValue { name: &'static str, value: String },
+}
+
+/// メタデータの暗号化に使う鍵の選択。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MetadataKeyMode {
+    /// CEK をそのまま使う。
+    Cek,
+    /// CEK から HKDF で導出したメタデータ専用鍵を使う。
+    #[default]
+    Derived,
+}
+
+impl MetadataKeyMode {
+    /// 環境変数から設定を読み込む。`MONAS_CONTENT_METADATA_ENCRYPTION` が未設定なら `None`
+    /// （メタデータを暗号化しない）。
+    ///
+    /// - `MONAS_CONTENT_METADATA_ENCRYPTION`: `derived`（メタデータ専用鍵）または `cek`
+    pub fn from_env() -> Result<Option<Self>, MetadataKeyModeError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, MetadataKeyModeError> {
+        const MODE: &str = "MONAS_CONTENT_METADATA_ENCRYPTION";
+
+        let Some(value) = lookup(MODE).filter(|v| !v.trim().is_empty()) else {
+            return Ok(None);
+        };
+        match value.trim().to_ascii_lowercase().as_str() {
+            "derived" => Ok(Some(Self::Derived)),
+            "cek" => Ok(Some(Self::Cek)),
+            _ => Err(MetadataKeyModeError::InvalidValue { name: MODE, value }),
+        }
+    }
+}
+
+/// 保存時にメタデータを暗号化し、取得時に復号する ContentRepository。
+///
+/// - CEK が削除済み（論理削除後）のコンテンツは暗号化できないため、
+///   name / path を破棄して保存する。削除済みコンテンツは復号できないので情報は失われない。
+/// - クライアント側で暗号化したコンテンツはサーバーが CEK を持たないため、暗号化せずに保存する
+///   （name / path を秘匿する場合はクライアントが暗号化した値を渡す）。
+/// - 暗号化されていない既存データはそのまま読み出せる。
+/// - `with_sealing(false)` の場合は保存時に暗号化しない（暗号化済みのデータは引き続き復号する）。
+#[derive(Clone)]
+pub struct MetadataEncryptingRepository<R, S, E> {
+    inner: R,
+    cek_store: S,
+    encryptor: E,
+    key_mode: MetadataKeyMode,
+    sealing: bool,
+}
+
+impl<R, S, E> MetadataEncryptingRepository<R, S, E>
+where
+    S: ContentEncryptionKeyStore,
+    E: ContentEncryption,
+{
+    pub fn new(inner: R, cek_store: S, encryptor: E) -> Self {
+        Self {
+            inner,
+            cek_store,
+            encryptor,
+            key_mode: MetadataKeyMode::default(),
+            sealing: true,
+        }
+    }
+
+    /// メタデータの暗号化に使う鍵を切り替える。
+    pub fn with_key_mode(mut self, key_mode: MetadataKeyMode) -> Self {
+        self.key_mode = key_mode;
+        self
+    }
+
+    /// 保存時にメタデータを暗号化するかどうかを切り替える。
+    pub fn with_sealing(mut self, sealing: bool) -> Self {
+        self.sealing = sealing;
+        self
+    }
+
+    /// 内部のリポジトリを取得する。
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// 版はまだ保存されていないことがあるため、系列を指定して CEK を取得する。
+    fn metadata_key(
+        &self,
+        content: &Content,
+    ) -> Result<Option<ContentEncryptionKey>, ContentRepositoryError> {
+        let cek = self
+            .cek_store
+            .load_in_series(content.raw_id(), content.series_id())
+            .map_err(|e| ContentRepositoryError::Storage(e.to_string()))?;
+
+        Ok(cek.map(|cek| match self.key_mode {
+            MetadataKeyMode::Cek => cek,
+            MetadataKeyMode::Derived => derive_metadata_key(&cek),
+        }))
+    }
+
+    fn seal(&self, content: &Content) -> Result<Content, ContentRepositoryError> {
+        if !self.sealing || content.is_client_encrypted() {
+            return Ok(content.clone());
+        }
+        match self.metadata_key(content)? {
+            Some(key) => content.seal_metadata(&key, &self.encryptor).map_err(|e| {
+                ContentRepositoryError::Storage(format!("metadata encryption error: {e:?}"))
+            }),
+            None if content.is_deleted() => Ok(content.redact_metadata()),
+            None => Err(ContentRepositoryError::Storage(
+                "missing content encryption key for metadata encryption".to_string(),
+            )),
+        }
+    }
+
+    fn unseal(&self, content: Content) -> Result<Content, ContentRepositoryError> {
+        if !content.has_sealed_metadata() {
+            return Ok(content);
+        }
+
+        let key = self.metadata_key(&content)?.ok_or_else(|| {
+            ContentRepositoryError::Storage(
+                "missing content encryption key for metadata decryption".to_string(),
+            )
+        })?;
+
+        content.unseal_metadata(&key, &self.encryptor).map_err(|e| {
+            ContentRepositoryError::Storage(format!("metadata decryption error: {e:?}"))
+        })
+    }
+}
+
+impl<R, S, E> ContentRepository for MetadataEncryptingRepository<R, S, E>
+where
+    R: ContentRepository,
+    S: ContentEncryptionKeyStore,
+    E: ContentEncryption,
+{
+    fn save(
+        &self,
+        content_id: &ContentId,
+        content: &Content,
+    ) -> Result<(), ContentRepositoryError> {
+        let sealed = self.seal(content)?;
+        self.inner.save(content_id, &sealed)
+    }
+
+    fn find_by_id(
+        &self,
+        content_id: &ContentId,
+    ) -> Result<Option<Content>, ContentRepositoryError> {
+        self.inner
+            .find_by_id(content_id)?
+            .map(|content| self.unseal(content))
+            .transpose()
+    }
+
+    fn flush(&self) -> Result<(), ContentRepositoryError> {
+        self.inner.flush()
+    }
+}
+
+impl<R, S, E> MultiStorageContentRepository for MetadataEncryptingRepository<R, S, E>
+where
+    R: MultiStorageContentRepository,
+    S: ContentEncryptionKeyStore,
+    E: ContentEncryption,
+{
+    fn save_to(
+        &self,
+        provider: &str,
+        content_id: &ContentId,
+        content: &Content,
+    ) -> Result<(), ContentRepositoryError> {
+        let sealed = self.seal(content)?;
+        self.inner.save_to(provider, content_id, &sealed)
+    }
+
+    fn find_from(
+        &self,
+        provider: &str,
+        content_id: &ContentId,
+    ) -> Result<Option<Content>, ContentRepositoryError> {
+        self.inner
+            .find_from(provider, content_id)?
+            .map(|content| self.unseal(content))
+            .transpose()
+    }
+
+    fn connected_providers(&self) -> Result<Vec<String>, ContentRepositoryError> {
+        self.inner.connected_providers()
+    }
+
+    fn default_provider(&self) -> Result<String, ContentRepositoryError> {
+        self.inner.default_provider()
+    }
+
+    fn connect_provider(
+        &self,
+        provider: &str,
+        access_token: String,
+    ) -> Result<(), ContentRepositoryError> {
+        self.inner.connect_provider(provider, access_token)
+    }
+
+    fn disconnect_provider(&self, provider: &str) -> Result<(), ContentRepositoryError> {
+        self.inner.disconnect_provider(provider)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::content::encryption::ContentEncryptionKeyGenerator;
+    use crate::infrastructure::content_id::Sha256ContentIdGenerator;
+    use crate::infrastructure::encryption::{
+        Aes256CtrContentEncryption, OsRngContentEncryptionKeyGenerator,
+    };
+    use crate::infrastructure::key_store::InMemoryContentEncryptionKeyStore;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// 保存されたコンテンツをそのまま保持するテスト用リポジトリ。
+    #[derive(Clone, Default)]
+    struct RawRepository {
+        inner: Arc<Mutex<HashMap<String, Content>>>,
+    }
+
+    impl ContentRepository for RawRepository {
+        fn save(
+            &self,
+            content_id: &ContentId,
+            content: &Content,
+        ) -> Result<(), ContentRepositoryError> {
+            self.inner
+                .lock()
+                .unwrap()
+                .insert(content_id.as_str().to_string(), content.clone());
+            Ok(())
+        }
+
+        fn find_by_id(
+            &self,
+            content_id: &ContentId,
+        ) -> Result<Option<Content>, ContentRepositoryError> {
+            Ok(self.inner.lock().unwrap().get(content_id.as_str()).cloned())
+        }
+    }
+
+    fn create_content(
+        cek_store: &InMemoryContentEncryptionKeyStore,
+    ) -> (Content, ContentEncryptionKey) {
+        let key = OsRngContentEncryptionKeyGenerator.generate();
+        let (content, _) = Content::create(
+            "secret-name".to_string(),
+            b"payload".to_vec(),
+            "private/dir/file.txt".to_string(),
+            None,
+            &Sha256ContentIdGenerator,
+            &key,
+            &Aes256CtrContentEncryption,
+        )
+        .unwrap();
+        cek_store.save(content.raw_id(), &key).unwrap();
+        (content, key)
+    }
+
+    #[test]
+    fn save_encrypts_metadata_and_find_decrypts_it() {
+        let raw = RawRepository::default();
+        let cek_store = InMemoryContentEncryptionKeyStore::default();
+        let repo = MetadataEncryptingRepository::new(
+            raw.clone(),
+            cek_store.clone(),
+            Aes256CtrContentEncryption,
+        );
+        let (content, _) = create_content(&cek_store);
+
+        repo.save(content.raw_id(), &content).unwrap();
+
+        // ストレージ上では name / path が平文で残っていないこと
+        let stored = raw.find_by_id(content.raw_id()).unwrap().unwrap();
+        assert!(stored.has_sealed_metadata());
+        assert_eq!(stored.metadata().name(), "");
+        assert_eq!(stored.metadata().path(), "");
+        assert_eq!(stored.raw_id(), content.raw_id());
+        let serialized = String::from_utf8(serde_json::to_vec(&stored).unwrap()).unwrap();
+        assert!(!serialized.contains("secret-name"));
+        assert!(!serialized.contains("private/dir"));
+
+        let found = repo.find_by_id(content.raw_id()).unwrap().unwrap();
+        assert!(!found.has_sealed_metadata());
+        assert_eq!(found.metadata().name(), "secret-name");
+        assert_eq!(found.metadata().path(), "private/dir/file.txt");
+    }
+
+    #[test]
+    fn cek_mode_decrypts_with_cek_directly() {
+        let raw = RawRepository::default();
+        let cek_store = InMemoryContentEncryptionKeyStore::default();
+        let repo = MetadataEncryptingRepository::new(
+            raw.clone(),
+            cek_store.clone(),
+            Aes256CtrContentEncryption,
+        )
+        .with_key_mode(MetadataKeyMode::Cek);
+        let (content, key) = create_content(&cek_store);
+
+        repo.save(content.raw_id(), &content).unwrap();
+
+        let stored = raw.find_by_id(content.raw_id()).unwrap().unwrap();
+        let unsealed = stored
+            .unseal_metadata(&key, &Aes256CtrContentEncryption)
+            .unwrap();
+        assert_eq!(unsealed.metadata().name(), "secret-name");
+    }
+
+    #[test]
+    fn deleted_content_without_cek_is_saved_with_redacted_metadata() {
+        let raw = RawRepository::default();
+        let cek_store = InMemoryContentEncryptionKeyStore::default();
+        let repo = MetadataEncryptingRepository::new(
+            raw.clone(),
+            cek_store.clone(),
+            Aes256CtrContentEncryption,
+        );
+        let (content, _) = create_content(&cek_store);
+        let (deleted, _) = content.delete().unwrap();
+        cek_store.delete(deleted.raw_id()).unwrap();
+
+        repo.save(deleted.raw_id(), &deleted).unwrap();
+
+        let found = repo.find_by_id(deleted.raw_id()).unwrap().unwrap();
+        assert!(found.is_deleted());
+        assert_eq!(found.metadata().name(), "");
+        assert_eq!(found.metadata().path(), "");
+    }
+
+    #[test]
+    fn client_encrypted_content_and_disabled_sealing_keep_metadata_in_plaintext() {
+        let raw = RawRepository::default();
+        let cek_store = InMemoryContentEncryptionKeyStore::default();
+        let repo = MetadataEncryptingRepository::new(
+            raw.clone(),
+            cek_store.clone(),
+            Aes256CtrContentEncryption,
+        );
+        let (content, _) = Content::create_client_encrypted(
+            "client-name".to_string(),
+            b"client ciphertext".to_vec(),
+            b"client wrapped cek".to_vec(),
+            "client/file.bin".to_string(),
+            None,
+            &Sha256ContentIdGenerator,
+        )
+        .unwrap();
+
+        // サーバーが CEK を持たなくても保存できる
+        repo.save(content.raw_id(), &content).unwrap();
+        let stored = raw.find_by_id(content.raw_id()).unwrap().unwrap();
+        assert!(!stored.has_sealed_metadata());
+        assert_eq!(stored.metadata().name(), "client-name");
+
+        // 無効にすると暗号化せずに保存し、暗号化済みのデータは引き続き復号する
+        let (sealed, _) = create_content(&cek_store);
+        repo.save(sealed.raw_id(), &sealed).unwrap();
+        let (plain, _) = Content::create(
+            "plain-name".to_string(),
+            b"plain".to_vec(),
+            "plain.txt".to_string(),
+            None,
+            &Sha256ContentIdGenerator,
+            &OsRngContentEncryptionKeyGenerator.generate(),
+            &Aes256CtrContentEncryption,
+        )
+        .unwrap();
+        let repo = repo.with_sealing(false);
+        repo.save(plain.raw_id(), &plain).unwrap();
+        assert!(!raw
+            .find_by_id(plain.raw_id())
+            .unwrap()
+            .unwrap()
+            .has_sealed_metadata());
+        let found = repo.find_by_id(sealed.raw_id()).unwrap().unwrap();
+        assert_eq!(found.metadata().name(), "secret-name");
+    }
+
+    #[test]
+    fn from_lookup_selects_the_metadata_key() {
+        let lookup = |value: &'static str| {
+            move |key: &str| (key == "MONAS_CONTENT_METADATA_ENCRYPTION").then(|| value.to_string())
+        };
+
+        assert_eq!(MetadataKeyMode::from_lookup(lookup("")).unwrap(), None);
+        assert_eq!(
+            MetadataKeyMode::from_lookup(lookup("derived")).unwrap(),
+            Some(MetadataKeyMode::Derived)
+        );
+        assert_eq!(
+            MetadataKeyMode::from_lookup(lookup("CEK")).unwrap(),
+            Some(MetadataKeyMode::Cek)
+        );
+        assert!(MetadataKeyMode::from_lookup(lookup("plain")).is_err());
+    }
+
+    #[test]
+    fn active_content_without_cek_fails_to_save() {
+        let raw = RawRepository::default();
+        let cek_store = InMemoryContentEncryptionKeyStore::default();
+        let repo = MetadataEncryptingRepository::new(
+            raw,
+            InMemoryContentEncryptionKeyStore::default(),
+            Aes256CtrContentEncryption,
+        );
+        let (content, _) = create_content(&cek_store);
+
+        let result = repo.save(content.raw_id(), &content);
+        assert!(matches!(result, Err(ContentRepositoryError::Storage(_))));
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::application_service::content_service::{ContentMetrics, ContentOperation};
+
+/// 処理時間（秒）のヒストグラムのバケット境界。
+const LATENCY_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// ペイロードサイズ（バイト）のヒストグラムのバケット境界。
+const SIZE_BUCKETS: &[f64] = &[
+    1024.0,
+    16384.0,
+    65536.0,
+    262144.0,
+    1048576.0,
+    4194304.0,
+    16777216.0,
+    67108864.0,
+    268435456.0,
+];
+
+/// 累積バケット形式のヒストグラム。
+#[derive(Debug, Clone)]
+struct Histogram {
+    bounds: &'static [f64],
+    /// `bounds` の各境界以下の観測数（最後の要素は `+Inf`）。
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: vec![0; bounds.len() + 1],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (i, bound) in self.bounds.iter().enumerate() {
+            if value <= *bound {
+                self.counts[i] += 1;
+            }
+        }
+        self.counts[self.bounds.len()] += 1;
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, operation: &str) {
+        for (i, bound) in self.bounds.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{operation=\"{operation}\",le=\"{bound}\"}} {}",
+                self.counts[i]
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{operation=\"{operation}\",le=\"+Inf\"}} {}",
+            self.counts[self.bounds.len()]
+        );
+        let _ = writeln!(out, "{name}_sum{{operation=\"{operation}\"}} {}", self.sum);
+        let _ = writeln!(
+            out,
+            "{name}_count{{operation=\"{operation}\"}} {}",
+            self.count
+        );
+    }
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    /// (ユースケース, 結果) → 回数
+    operations: BTreeMap<(&'static str, &'static str), u64>,
+    latency: BTreeMap<&'static str, Histogram>,
+    payload_size: BTreeMap<&'static str, Histogram>,
+    encryption_time: BTreeMap<&'static str, Histogram>,
+}
+
+/// ContentService のメトリクスを集計し、Prometheus のテキスト形式で出力する実装。
+///
+/// - `monas_content_operations_total{operation, outcome}`: ユースケースの実行回数
+/// - `monas_content_operation_duration_seconds{operation}`: ユースケースの処理時間
+/// - `monas_content_payload_bytes{operation}`: 受け渡した平文のサイズ
+/// - `monas_content_encryption_duration_seconds{operation}`: 暗号化・復号の処理時間
+///
+/// クローンしたインスタンス同士は集計結果を共有する。
+#[derive(Debug, Clone, Default)]
+pub struct PrometheusContentMetrics {
+    inner: Arc<Mutex<Registry>>,
+}
+
+impl PrometheusContentMetrics {
+    /// 集計結果を Prometheus のテキスト形式（0.0.4）で返す。
+    pub fn render(&self) -> String {
+        let registry = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+
+        out.push_str("# HELP monas_content_operations_total Content operations by outcome.\n");
+        out.push_str("# TYPE monas_content_operations_total counter\n");
+        for ((operation, outcome), count) in &registry.operations {
+            let _ = writeln!(
+                out,
+                "monas_content_operations_total{{operation=\"{operation}\",outcome=\"{outcome}\"}} {count}"
+            );
+        }
+
+        for (name, help, histograms) in [
+            (
+                "monas_content_operation_duration_seconds",
+                "Content operation latency in seconds.",
+                &registry.latency,
+            ),
+            (
+                "monas_content_payload_bytes",
+                "Plaintext payload size of content operations in bytes.",
+                &registry.payload_size,
+            ),
+            (
+                "monas_content_encryption_duration_seconds",
+                "Time spent encrypting or decrypting content in seconds.",
+                &registry.encryption_time,
+            ),
+        ] {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} histogram");
+            for (operation, histogram) in histograms {
+                histogram.render(&mut out, name, operation);
+            }
+        }
+        out
+    }
+
+    fn with_registry(&self, f: impl FnOnce(&mut Registry)) {
+        let mut registry = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut registry);
+    }
+}
+
+impl ContentMetrics for PrometheusContentMetrics {
+    fn record_operation(&self, operation: ContentOperation, success: bool, latency: Duration) {
+        let outcome = if success { "success" } else { "failure" };
+        self.with_registry(|registry| {
+            *registry
+                .operations
+                .entry((operation.as_str(), outcome))
+                .or_default() += 1;
+            registry
+                .latency
+                .entry(operation.as_str())
+                .or_insert_with(|| Histogram::new(LATENCY_BUCKETS))
+                .observe(latency.as_secs_f64());
+        });
+    }
+
+    fn record_payload_size(&self, operation: ContentOperation, bytes: u64) {
+        self.with_registry(|registry| {
+            registry
+                .payload_size
+                .entry(operation.as_str())
+                .or_insert_with(|| Histogram::new(SIZE_BUCKETS))
+                .observe(bytes as f64);
+        });
+    }
+
+    fn record_encryption_time(&self, operation: ContentOperation, elapsed: Duration) {
+        self.with_registry(|registry| {
+            registry
+                .encryption_time
+                .entry(operation.as_str())
+                .or_insert_with(|| Histogram::new(LATENCY_BUCKETS))
+                .observe(elapsed.as_secs_f64());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_counters_and_cumulative_histogram_buckets() {
+        let metrics = PrometheusContentMetrics::default();
+        metrics.record_operation(ContentOperation::Create, true, Duration::from_millis(3));
+        metrics.record_operation(ContentOperation::Create, false, Duration::from_millis(30));
+        metrics.record_payload_size(ContentOperation::Create, 2048);
+        metrics.record_encryption_time(ContentOperation::Create, Duration::from_micros(200));
+
+        let text = metrics.clone().render();
+        assert!(text.contains(
+            "monas_content_operations_total{operation=\"create\",outcome=\"success\"} 1"
+        ));
+        assert!(text.contains(
+            "monas_content_operations_total{operation=\"create\",outcome=\"failure\"} 1"
+        ));
+        // 3ms と 30ms の観測は 0.005 以下に 1 件、0.05 以下に 2 件（累積）
+        assert!(text.contains(
+            "monas_content_operation_duration_seconds_bucket{operation=\"create\",le=\"0.005\"} 1"
+        ));
+        assert!(text.contains(
+            "monas_content_operation_duration_seconds_bucket{operation=\"create\",le=\"0.05\"} 2"
+        ));
+        assert!(
+            text.contains("monas_content_operation_duration_seconds_count{operation=\"create\"} 2")
+        );
+        assert!(
+            text.contains("monas_content_payload_bytes_bucket{operation=\"create\",le=\"1024\"} 0")
+        );
+        assert!(text
+            .contains("monas_content_payload_bytes_bucket{operation=\"create\",le=\"16384\"} 1"));
+        assert!(text
+            .contains("monas_content_encryption_duration_seconds_count{operation=\"create\"} 1"));
+    }
+}
@@ -1,12 +1,34 @@
+pub mod account_directory;
+pub mod catalog_listing;
+pub mod cipher_suite;
+pub mod content_cache;
 pub mod content_id;
 pub mod encryption;
+pub mod event_bus_publisher;
+pub mod fs_content_repository;
+pub mod idempotency_store;
+pub mod jose;
+pub mod key_derivation;
+pub mod key_envelope_repository;
+pub mod key_escrow;
+pub mod key_possession;
 pub mod key_store;
 pub mod key_wrapping;
+pub mod metadata_encryption;
+pub mod metrics;
+pub mod namespace_usage_store;
+pub mod path_index;
 pub mod public_key_directory;
+pub mod push_notifier;
+pub mod rotation;
 pub mod share_repository;
+pub mod signed_envelope;
+
+#[cfg(test)]
+pub(crate) mod test_support;
 
 #[cfg(feature = "filesync")]
 pub mod filesync_repository;
 
 #[cfg(feature = "filesync")]
-pub use filesync_repository::MultiStorageRepository;
+pub use filesync_repository::{ContentStorageConfig, MultiStorageRepository, ProviderPathMapping};
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::application_service::content_service::{
+    NamespaceUsageStore, NamespaceUsageStoreError, UsageAdjustment,
+};
+use crate::domain::namespace::Namespace;
+
+/// シンプルなインメモリ実装の NamespaceUsageStore。
+///
+/// - key: 名前空間
+/// - value: 使用量（バイト）
+#[derive(Clone, Default)]
+pub struct InMemoryNamespaceUsageStore {
+    inner: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl NamespaceUsageStore for InMemoryNamespaceUsageStore {
+    fn usage(&self, namespace: &str) -> Result<u64, NamespaceUsageStoreError> {
+        let guard = self
+            .inner
+            .lock()
+            .map_err(|e| NamespaceUsageStoreError::Storage(e.to_string()))?;
+        Ok(guard.get(namespace).copied().unwrap_or(0))
+    }
+
+    fn set_usage(&self, namespace: &str, bytes: u64) -> Result<(), NamespaceUsageStoreError> {
+        let mut guard = self
+            .inner
+            .lock()
+            .map_err(|e| NamespaceUsageStoreError::Storage(e.to_string()))?;
+        if bytes == 0 {
+            guard.remove(namespace);
+        } else {
+            guard.insert(namespace.to_string(), bytes);
+        }
+        Ok(())
+    }
+
+    fn adjust(
+        &self,
+        namespace: &str,
+        old_size: u64,
+        new_size: u64,
+        limit: Option<u64>,
+    ) -> Result<UsageAdjustment, NamespaceUsageStoreError> {
+        let mut guard = self
+            .inner
+            .lock()
+            .map_err(|e| NamespaceUsageStoreError::Storage(e.to_string()))?;
+        let used = guard.get(namespace).copied().unwrap_or(0);
+        match UsageAdjustment::evaluate(used, old_size, new_size, limit) {
+            Ok(0) => {
+                guard.remove(namespace);
+            }
+            Ok(bytes) => {
+                guard.insert(namespace.to_string(), bytes);
+            }
+            Err(exceeded) => return Ok(exceeded),
+        }
+        Ok(UsageAdjustment::Applied)
+    }
+}
+
+/// 名前空間ごとの使用量を sled に保存する NamespaceUsageStore。
+///
+/// 再起動後も使用量が失われないため、クォータが起動のたびにリセットされない。
+///
+/// - キー: `"usage:{namespace}"`。テナントの名前空間に属する場合は
+///   `"ns:{tenant}:usage:{namespace}"`
+/// - 値: 使用量（バイト）の u64 ビッグエンディアン
+#[derive(Clone)]
+pub struct SledNamespaceUsageStore {
+    db: sled::Db,
+    tenant: Option<Namespace>,
+}
+
+impl SledNamespaceUsageStore {
+    /// 既存の `sled::Db` ハンドルを共有してインスタンスを構築する。
+    pub fn with_db(db: sled::Db) -> Self {
+        Self { db, tenant: None }
+    }
+
+    /// 同じ DB を共有し、テナントの名前空間 `namespace` の使用量だけを扱うストアを返す。
+    pub fn scoped(&self, namespace: &Namespace) -> Self {
+        Self {
+            db: self.db.clone(),
+            tenant: Some(namespace.clone()),
+        }
+    }
+
+    fn sled_key(&self, namespace: &str) -> String {
+        match &self.tenant {
+            Some(tenant) => format!("ns:{tenant}:usage:{namespace}"),
+            None => format!("usage:{namespace}"),
+        }
+    }
+}
+
+impl NamespaceUsageStore for SledNamespaceUsageStore {
+    fn usage(&self, namespace: &str) -> Result<u64, NamespaceUsageStoreError> {
+        let value = self
+            .db
+            .get(self.sled_key(namespace))
+            .map_err(|e| NamespaceUsageStoreError::Storage(e.to_string()))?;
+        decode_usage(namespace, value.as_deref())
+    }
+
+    fn set_usage(&self, namespace: &str, bytes: u64) -> Result<(), NamespaceUsageStoreError> {
+        let key = self.sled_key(namespace);
+        let result = if bytes == 0 {
+            self.db.remove(key).map(|_| ())
+        } else {
+            self.db.insert(key, &bytes.to_be_bytes()).map(|_| ())
+        };
+        result.map_err(|e| NamespaceUsageStoreError::Storage(e.to_string()))
+    }
+
+    /// compare-and-swap で読み取った値が変わっていないときだけ書き込み、
+    /// 競合した場合は読み直してやり直す。
+    fn adjust(
+        &self,
+        namespace: &str,
+        old_size: u64,
+        new_size: u64,
+        limit: Option<u64>,
+    ) -> Result<UsageAdjustment, NamespaceUsageStoreError> {
+        let key = self.sled_key(namespace);
+        loop {
+            let current = self
+                .db
+                .get(&key)
+                .map_err(|e| NamespaceUsageStoreError::Storage(e.to_string()))?;
+            let used = decode_usage(namespace, current.as_deref())?;
+            let bytes = match UsageAdjustment::evaluate(used, old_size, new_size, limit) {
+                Ok(bytes) => bytes,
+                Err(exceeded) => return Ok(exceeded),
+            };
+            let next = (bytes != 0).then(|| bytes.to_be_bytes().to_vec());
+            match self
+                .db
+                .compare_and_swap(&key, current, next)
+                .map_err(|e| NamespaceUsageStoreError::Storage(e.to_string()))?
+            {
+                Ok(()) => return Ok(UsageAdjustment::Applied),
+                Err(_) => continue,
+            }
+        }
+    }
+}
+
+fn decode_usage(namespace: &str, value: Option<&[u8]>) -> Result<u64, NamespaceUsageStoreError> {
+    let Some(value) = value else {
+        return Ok(0);
+    };
+    let bytes: [u8; 8] = value
+        .try_into()
+        .map_err(|_| NamespaceUsageStoreError::Storage(format!("invalid usage for {namespace}")))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::test_support::reopen;
+
+    #[test]
+    fn sled_usage_survives_reopen_and_is_separated_per_tenant() {
+        let dir = tempfile::tempdir().unwrap();
+        let alice = Namespace::new("alice").unwrap();
+        {
+            let store = SledNamespaceUsageStore::with_db(sled::open(dir.path()).unwrap());
+            store.set_usage("docs", 42).unwrap();
+            store.scoped(&alice).set_usage("docs", 7).unwrap();
+            store.db.flush().unwrap();
+        }
+
+        let store = SledNamespaceUsageStore::with_db(reopen(|| sled::open(dir.path())));
+        assert_eq!(store.usage("docs").unwrap(), 42);
+        assert_eq!(store.scoped(&alice).usage("docs").unwrap(), 7);
+        assert_eq!(store.usage("photos").unwrap(), 0);
+
+        store.set_usage("docs", 0).unwrap();
+        assert_eq!(store.usage("docs").unwrap(), 0);
+        assert_eq!(store.scoped(&alice).usage("docs").unwrap(), 7);
+    }
+
+    #[test]
+    fn sled_adjust_is_atomic_under_concurrent_reservations() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SledNamespaceUsageStore::with_db(sled::open(dir.path()).unwrap());
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    (0..10)
+                        .filter(|_| {
+                            store.adjust("", 0, 1, Some(50)).unwrap() == UsageAdjustment::Applied
+                        })
+                        .count()
+                })
+            })
+            .collect();
+        let applied: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+        // 80 件の並行リクエストのうち、上限ちょうどの 50 件だけが通る
+        assert_eq!(applied, 50);
+        assert_eq!(store.usage("").unwrap(), 50);
+        assert_eq!(
+            store.adjust("", 0, 1, Some(50)).unwrap(),
+            UsageAdjustment::LimitExceeded { used: 50 }
+        );
+        store.adjust("", 50, 0, None).unwrap();
+        assert_eq!(store.usage("").unwrap(), 0);
+    }
+}
@@ -0,0 +1,351 @@
+//! パスによるコンテンツ検索用のインデックス。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+
+use crate::application_service::content_service::{
+    ContentPathIndex, ContentPathIndexError, EventPublisher, EventPublisherError,
+};
+use crate::domain::content::ContentDomainEvent;
+use crate::domain::content_id::ContentId;
+use crate::domain::namespace::Namespace;
+
+/// ドメインイベントから系列ごとの最新版とそのパスを記録するインデックス。
+///
+/// `EventPublisher` として `ContentService` に渡すと、作成・更新で最新版のパスを記録し、
+/// 削除で系列ごと取り除く。同じパスに複数の系列がある場合はすべて返す。
+#[derive(Clone, Default)]
+pub struct InMemoryContentPathIndex {
+    /// 系列 ID → 最新版の記録
+    latest: Arc<Mutex<HashMap<ContentId, IndexedContent>>>,
+}
+
+#[derive(Clone)]
+struct IndexedContent {
+    content_id: ContentId,
+    /// 正規化したパス
+    path: String,
+    updated_at: DateTime<Utc>,
+}
+
+impl InMemoryContentPathIndex {
+    /// 系列 `series_id` の最新版として `content_id` を `path` に記録する。
+    pub fn insert(
+        &self,
+        series_id: ContentId,
+        content_id: ContentId,
+        path: &str,
+        updated_at: DateTime<Utc>,
+    ) {
+        self.latest.lock().unwrap().insert(
+            series_id,
+            IndexedContent {
+                content_id,
+                path: normalize(path).to_string(),
+                updated_at,
+            },
+        );
+    }
+
+    pub fn remove(&self, series_id: &ContentId) {
+        self.latest.lock().unwrap().remove(series_id);
+    }
+}
+
+/// `/docs/a.txt` と `docs/a.txt` を同じパスとして扱う。
+fn normalize(path: &str) -> &str {
+    path.trim_start_matches('/')
+}
+
+/// 更新日時の新しい順に並べて ContentId を返す。
+fn newest_first(mut entries: Vec<(DateTime<Utc>, ContentId)>) -> Vec<ContentId> {
+    entries.sort_by_key(|(updated_at, _)| std::cmp::Reverse(*updated_at));
+    entries
+        .into_iter()
+        .map(|(_, content_id)| content_id)
+        .collect()
+}
+
+impl ContentPathIndex for InMemoryContentPathIndex {
+    fn find_by_path(&self, path: &str) -> Result<Vec<ContentId>, ContentPathIndexError> {
+        let path = normalize(path);
+        let guard = self
+            .latest
+            .lock()
+            .map_err(|e| ContentPathIndexError::Storage(e.to_string()))?;
+        Ok(newest_first(
+            guard
+                .values()
+                .filter(|indexed| indexed.path == path)
+                .map(|indexed| (indexed.updated_at, indexed.content_id.clone()))
+                .collect(),
+        ))
+    }
+}
+
+impl EventPublisher for InMemoryContentPathIndex {
+    fn publish(&self, event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+        match event {
+            ContentDomainEvent::Created(e) => self.insert(
+                e.series_id,
+                e.content_id,
+                e.metadata.path(),
+                e.metadata.updated_at(),
+            ),
+            ContentDomainEvent::Updated(e) => self.insert(
+                e.series_id,
+                e.content_id,
+                e.metadata.path(),
+                e.metadata.updated_at(),
+            ),
+            ContentDomainEvent::Deleted(e) => self.remove(&e.series_id),
+        }
+        Ok(())
+    }
+}
+
+/// 系列ごとの最新版とそのパスを sled に記録するインデックス。
+///
+/// [`InMemoryContentPathIndex`] と同じくドメインイベントから記録するが、再起動後も
+/// パスから引ける。パスごとの記録は 1 回の前方一致の走査でまとめて読み出す。
+///
+/// - `path:{パス}\0{series_id}` → 更新日時（ミリ秒、8 バイト BE）+ 最新版の ContentId
+/// - `path-series:{series_id}` → パス（更新・削除で古いパスの記録を消すため）
+///
+/// 名前空間付きはそれぞれ `ns:{namespace}:` を前に付ける。
+#[derive(Clone)]
+pub struct SledContentPathIndex {
+    db: sled::Db,
+    namespace: Option<Namespace>,
+    /// 同じ系列の記録の読み出しと書き換えを直列にする。
+    lock: Arc<Mutex<()>>,
+}
+
+impl SledContentPathIndex {
+    /// 既存の `sled::Db` ハンドルを共有してインデックスを構築する。
+    pub fn with_db(db: sled::Db) -> Self {
+        Self {
+            db,
+            namespace: None,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// 同じ DB を共有し、名前空間 `namespace` の系列だけを扱うインデックスを返す。
+    pub fn scoped(&self, namespace: &Namespace) -> Self {
+        Self {
+            db: self.db.clone(),
+            namespace: Some(namespace.clone()),
+            lock: self.lock.clone(),
+        }
+    }
+
+    fn prefix(&self) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("ns:{namespace}:"),
+            None => String::new(),
+        }
+    }
+
+    fn path_prefix(&self, path: &str) -> String {
+        format!("{}path:{path}\0", self.prefix())
+    }
+
+    fn series_key(&self, series_id: &ContentId) -> String {
+        format!("{}path-series:{}", self.prefix(), series_id.as_str())
+    }
+
+    /// 系列 `series_id` の最新版として `content_id` を `path` に記録する。
+    pub fn insert(
+        &self,
+        series_id: &ContentId,
+        content_id: &ContentId,
+        path: &str,
+        updated_at: DateTime<Utc>,
+    ) -> Result<(), ContentPathIndexError> {
+        let path = normalize(path);
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| ContentPathIndexError::Storage(e.to_string()))?;
+        let mut batch = self.remove_batch(series_id)?;
+        let mut value = updated_at.timestamp_millis().to_be_bytes().to_vec();
+        value.extend_from_slice(content_id.as_str().as_bytes());
+        batch.insert(
+            format!("{}{}", self.path_prefix(path), series_id.as_str()).as_bytes(),
+            value,
+        );
+        batch.insert(self.series_key(series_id).as_bytes(), path.as_bytes());
+        self.db
+            .apply_batch(batch)
+            .map_err(|e| ContentPathIndexError::Storage(e.to_string()))
+    }
+
+    pub fn remove(&self, series_id: &ContentId) -> Result<(), ContentPathIndexError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|e| ContentPathIndexError::Storage(e.to_string()))?;
+        let batch = self.remove_batch(series_id)?;
+        self.db
+            .apply_batch(batch)
+            .map_err(|e| ContentPathIndexError::Storage(e.to_string()))
+    }
+
+    /// 系列 `series_id` の現在の記録を消す書き込みをまとめる。
+    fn remove_batch(&self, series_id: &ContentId) -> Result<sled::Batch, ContentPathIndexError> {
+        let series_key = self.series_key(series_id);
+        let mut batch = sled::Batch::default();
+        let previous = self
+            .db
+            .get(&series_key)
+            .map_err(|e| ContentPathIndexError::Storage(e.to_string()))?;
+        if let Some(previous) = previous {
+            let previous = String::from_utf8_lossy(&previous);
+            batch.remove(
+                format!("{}{}", self.path_prefix(&previous), series_id.as_str()).as_bytes(),
+            );
+            batch.remove(series_key.as_bytes());
+        }
+        Ok(batch)
+    }
+}
+
+impl ContentPathIndex for SledContentPathIndex {
+    fn find_by_path(&self, path: &str) -> Result<Vec<ContentId>, ContentPathIndexError> {
+        let mut entries = Vec::new();
+        for entry in self.db.scan_prefix(self.path_prefix(normalize(path))) {
+            let (_, value) = entry.map_err(|e| ContentPathIndexError::Storage(e.to_string()))?;
+            let invalid = || ContentPathIndexError::Storage("invalid path index entry".into());
+            let millis: [u8; 8] = value
+                .get(..8)
+                .and_then(|millis| millis.try_into().ok())
+                .ok_or_else(invalid)?;
+            let updated_at =
+                DateTime::from_timestamp_millis(i64::from_be_bytes(millis)).ok_or_else(invalid)?;
+            let content_id = std::str::from_utf8(&value[8..])
+                .ok()
+                .and_then(|id| ContentId::new(id.to_string()).ok())
+                .ok_or_else(invalid)?;
+            entries.push((updated_at, content_id));
+        }
+        Ok(newest_first(entries))
+    }
+}
+
+impl EventPublisher for SledContentPathIndex {
+    fn publish(&self, event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+        let result = match event {
+            ContentDomainEvent::Created(e) => self.insert(
+                &e.series_id,
+                &e.content_id,
+                e.metadata.path(),
+                e.metadata.updated_at(),
+            ),
+            ContentDomainEvent::Updated(e) => self.insert(
+                &e.series_id,
+                &e.content_id,
+                e.metadata.path(),
+                e.metadata.updated_at(),
+            ),
+            ContentDomainEvent::Deleted(e) => self.remove(&e.series_id),
+        };
+        result.map_err(|e| EventPublisherError::Publish(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::content::{ContentCreated, ContentDeleted, ContentUpdated, Metadata};
+    use crate::infrastructure::test_support::reopen;
+    use chrono::Utc;
+
+    fn metadata(id: &ContentId, path: &str) -> Metadata {
+        Metadata::new("name".into(), path.into(), id.clone(), None)
+    }
+
+    #[test]
+    fn index_resolves_latest_version_by_path() {
+        let index = InMemoryContentPathIndex::default();
+        let series = ContentId::for_test("series");
+        let other = ContentId::for_test("other");
+        let v2 = ContentId::for_test("v2");
+
+        for (content_id, series_id, path) in [
+            (&series, &series, "docs/a.txt"),
+            (&other, &other, "docs/b.txt"),
+        ] {
+            index
+                .publish(ContentDomainEvent::Created(ContentCreated {
+                    content_id: content_id.clone(),
+                    series_id: series_id.clone(),
+                    metadata: metadata(content_id, path),
+                }))
+                .unwrap();
+        }
+        index
+            .publish(ContentDomainEvent::Updated(ContentUpdated {
+                content_id: v2.clone(),
+                series_id: series.clone(),
+                metadata: metadata(&v2, "docs/a.txt"),
+            }))
+            .unwrap();
+
+        assert_eq!(index.find_by_path("/docs/a.txt").unwrap(), vec![v2.clone()]);
+        assert_eq!(index.find_by_path("docs/b.txt").unwrap(), vec![other]);
+        assert!(index.find_by_path("docs/c.txt").unwrap().is_empty());
+
+        index
+            .publish(ContentDomainEvent::Deleted(ContentDeleted {
+                content_id: v2,
+                series_id: series,
+                deleted_at: Utc::now(),
+            }))
+            .unwrap();
+        assert!(index.find_by_path("docs/a.txt").unwrap().is_empty());
+    }
+
+    #[test]
+    fn sled_index_survives_reopen_and_orders_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let older = ContentId::for_test("older");
+        let newer = ContentId::for_test("newer");
+        let moved = ContentId::for_test("moved");
+        let now = Utc::now();
+        {
+            let index = SledContentPathIndex::with_db(sled::open(dir.path()).unwrap());
+            index
+                .insert(
+                    &older,
+                    &older,
+                    "/docs/a.txt",
+                    now - chrono::Duration::hours(1),
+                )
+                .unwrap();
+            index.insert(&newer, &newer, "docs/a.txt", now).unwrap();
+            index.insert(&moved, &moved, "docs/a.txt", now).unwrap();
+            // 別のパスへ移した系列は元のパスから消える
+            index.insert(&moved, &moved, "docs/b.txt", now).unwrap();
+            // 名前空間付きのインデックスとは混ざらない
+            index
+                .scoped(&Namespace::new("alice").unwrap())
+                .insert(&older, &older, "docs/a.txt", now)
+                .unwrap();
+        }
+
+        let index = SledContentPathIndex::with_db(reopen(|| sled::open(dir.path())));
+        assert_eq!(
+            index.find_by_path("docs/a.txt").unwrap(),
+            vec![newer.clone(), older.clone()]
+        );
+        assert_eq!(index.find_by_path("/docs/b.txt").unwrap(), vec![moved]);
+        // 前方一致で別のパスを拾わない
+        assert!(index.find_by_path("docs/a").unwrap().is_empty());
+
+        index.remove(&newer).unwrap();
+        assert_eq!(index.find_by_path("docs/a.txt").unwrap(), vec![older]);
+    }
+}
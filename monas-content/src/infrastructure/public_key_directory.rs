@@ -13,6 +13,7 @@ use sha2::{Digest, Sha256};
 #[derive(Clone, Default)]
 pub struct InMemoryPublicKeyDirectory {
     inner: Arc<Mutex<HashMap<KeyId, Vec<u8>>>>,
+    accounts: Arc<Mutex<HashMap<String, Vec<u8>>>>,
 }
 
 impl InMemoryPublicKeyDirectory {
@@ -22,6 +23,13 @@ impl InMemoryPublicKeyDirectory {
             guard.insert(key_id, public_key);
         }
     }
+
+    /// アカウント識別子と公開鍵の対応を登録するヘルパ（主にテスト用）。
+    pub fn register_account(&self, account_id: impl Into<String>, public_key: Vec<u8>) {
+        if let Ok(mut guard) = self.accounts.lock() {
+            guard.insert(account_id.into(), public_key);
+        }
+    }
 }
 
 impl InMemoryPublicKeyDirectory {
@@ -68,6 +76,18 @@ impl PublicKeyDirectory for InMemoryPublicKeyDirectory {
         guard.remove(key_id);
         Ok(())
     }
+
+    fn find_public_key_by_account(
+        &self,
+        account_id: &str,
+    ) -> Result<Option<Vec<u8>>, PublicKeyDirectoryError> {
+        let guard = self
+            .accounts
+            .lock()
+            .map_err(|e| PublicKeyDirectoryError::Lookup(e.to_string()))?;
+
+        Ok(guard.get(account_id).cloned())
+    }
 }
 
 /// sled を用いた公開鍵ディレクトリ実装。
@@ -0,0 +1,377 @@
+//! プッシュゲートウェイへの Webhook 通知。
+//!
+//! モバイルアプリは常時接続を持たないため、共有の付与や監視中コンテンツの更新を
+//! 外部のプッシュゲートウェイ（APNs / FCM への中継）へ Webhook で知らせ、
+//! アプリを起こして同期させる。
+//!
+//! - ペイロードは `PushNotification` の JSON で、ID とイベント種別のみを含む（平文は送らない）。
+//! - 共有シークレットを設定した場合は `X-Monas-Signature: sha256=<hex>` に
+//!   ボディの HMAC-SHA256 を付ける。
+//! - 送信はバックグラウンドスレッドで行い、ユースケースの応答を待たせない。
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::application_service::content_service::{
+    EventPublisher, EventPublisherError, PushEventType, PushNotification, PushNotifier,
+    PushNotifierError,
+};
+use crate::domain::content::events::ContentDomainEvent;
+use crate::domain::content_id::ContentId;
+
+/// 署名ヘッダ名。
+pub const PUSH_SIGNATURE_HEADER: &str = "X-Monas-Signature";
+
+/// Webhook の既定タイムアウト。
+const DEFAULT_PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// プッシュゲートウェイの接続設定。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushGatewayConfig {
+    /// 通知を POST する URL。
+    pub url: String,
+    /// ボディに HMAC-SHA256 署名を付けるための共有シークレット。
+    pub secret: Option<Vec<u8>>,
+    pub timeout: Duration,
+}
+
+impl PushGatewayConfig {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            secret: None,
+            timeout: DEFAULT_PUSH_TIMEOUT,
+        }
+    }
+
+    pub fn with_secret(mut self, secret: impl Into<Vec<u8>>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// 環境変数から設定を読み込む。URL が未設定の場合は `None`（通知しない）。
+    ///
+    /// - `MONAS_PUSH_GATEWAY_URL`: 通知先 URL
+    /// - `MONAS_PUSH_GATEWAY_SECRET`: 署名用の共有シークレット（任意）
+    pub fn from_env() -> Option<Self> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let url = lookup("MONAS_PUSH_GATEWAY_URL").filter(|v| !v.trim().is_empty())?;
+        let config = Self::new(url.trim());
+        Some(
+            match lookup("MONAS_PUSH_GATEWAY_SECRET").filter(|v| !v.is_empty()) {
+                Some(secret) => config.with_secret(secret.into_bytes()),
+                None => config,
+            },
+        )
+    }
+}
+
+/// ボディの HMAC-SHA256 署名（ヘッダ値）を計算する。
+pub fn sign_push_payload(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// プッシュゲートウェイへ Webhook を送る `PushNotifier`。
+#[derive(Clone)]
+pub struct WebhookPushNotifier {
+    sender: mpsc::Sender<PushNotification>,
+    failed: Arc<AtomicU64>,
+}
+
+impl WebhookPushNotifier {
+    /// 送信用のバックグラウンドスレッドを起動する。
+    ///
+    /// スレッドはすべての `WebhookPushNotifier` が drop されると終了する。
+    pub fn spawn(config: PushGatewayConfig) -> Self {
+        let (sender, receiver) = mpsc::channel::<PushNotification>();
+        let failed = Arc::new(AtomicU64::new(0));
+        let failed_in_worker = failed.clone();
+        std::thread::Builder::new()
+            .name("monas-push-notifier".into())
+            .spawn(move || {
+                let agent = ureq::Agent::new_with_config(
+                    ureq::Agent::config_builder()
+                        .timeout_global(Some(config.timeout))
+                        .build(),
+                );
+                for notification in receiver {
+                    if deliver(&agent, &config, &notification).is_err() {
+                        failed_in_worker.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            })
+            .expect("failed to spawn push notifier thread");
+        Self { sender, failed }
+    }
+
+    /// 配送に失敗した通知の数。
+    pub fn failed_deliveries(&self) -> u64 {
+        self.failed.load(Ordering::Relaxed)
+    }
+}
+
+fn deliver(
+    agent: &ureq::Agent,
+    config: &PushGatewayConfig,
+    notification: &PushNotification,
+) -> Result<(), PushNotifierError> {
+    let body = serde_json::to_vec(notification)
+        .map_err(|e| PushNotifierError::Notify(format!("serialization error: {e}")))?;
+    let mut request = agent
+        .post(&config.url)
+        .header("Content-Type", "application/json");
+    if let Some(secret) = &config.secret {
+        request = request.header(PUSH_SIGNATURE_HEADER, sign_push_payload(secret, &body));
+    }
+    request
+        .send(&body[..])
+        .map(|_| ())
+        .map_err(|e| PushNotifierError::Notify(e.to_string()))
+}
+
+impl PushNotifier for WebhookPushNotifier {
+    fn notify(&self, notification: &PushNotification) -> Result<(), PushNotifierError> {
+        self.sender
+            .send(notification.clone())
+            .map_err(|_| PushNotifierError::Notify("push notifier thread has stopped".into()))
+    }
+}
+
+/// 監視中のコンテンツの更新・削除をプッシュ通知する `EventPublisher`。
+///
+/// 内側の `EventPublisher` へイベントをそのまま渡した上で、系列 ID が監視対象なら
+/// `PushNotifier` へ通知する。通知の失敗はイベント発行の失敗として扱わない。
+#[derive(Clone)]
+pub struct PushNotifyingEventPublisher<P, N> {
+    inner: P,
+    notifier: N,
+    watched: Arc<RwLock<HashSet<ContentId>>>,
+}
+
+impl<P, N> PushNotifyingEventPublisher<P, N> {
+    pub fn new(inner: P, notifier: N) -> Self {
+        Self {
+            inner,
+            notifier,
+            watched: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// 系列 ID を監視対象にする。既に監視中なら false を返す。
+    pub fn watch(&self, series_id: ContentId) -> bool {
+        self.watched.write().unwrap().insert(series_id)
+    }
+
+    /// 系列 ID の監視をやめる。監視していなかった場合は false を返す。
+    pub fn unwatch(&self, series_id: &ContentId) -> bool {
+        self.watched.write().unwrap().remove(series_id)
+    }
+
+    pub fn is_watched(&self, series_id: &ContentId) -> bool {
+        self.watched.read().unwrap().contains(series_id)
+    }
+}
+
+impl<P, N> EventPublisher for PushNotifyingEventPublisher<P, N>
+where
+    P: EventPublisher,
+    N: PushNotifier,
+{
+    fn publish(&self, event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+        let notification = match &event {
+            ContentDomainEvent::Updated(e) if self.is_watched(&e.series_id) => {
+                Some(PushNotification {
+                    event_type: PushEventType::ContentUpdated,
+                    content_id: e.content_id.clone(),
+                    series_id: e.series_id.clone(),
+                    recipient_key_id: None,
+                    occurred_at: e.metadata.updated_at(),
+                })
+            }
+            ContentDomainEvent::Deleted(e) if self.is_watched(&e.series_id) => {
+                Some(PushNotification {
+                    event_type: PushEventType::ContentDeleted,
+                    content_id: e.content_id.clone(),
+                    series_id: e.series_id.clone(),
+                    recipient_key_id: None,
+                    occurred_at: e.deleted_at,
+                })
+            }
+            _ => None,
+        };
+
+        self.inner.publish(event)?;
+        if let Some(notification) = notification {
+            let _ = self.notifier.notify(&notification);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application_service::content_service::NoOpEventPublisher;
+    use crate::domain::content::events::{ContentCreated, ContentDeleted, ContentUpdated};
+    use crate::domain::content::Metadata;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct RecordingNotifier {
+        sent: Arc<Mutex<Vec<PushNotification>>>,
+    }
+
+    impl PushNotifier for RecordingNotifier {
+        fn notify(&self, notification: &PushNotification) -> Result<(), PushNotifierError> {
+            self.sent.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    fn metadata(id: &ContentId) -> Metadata {
+        Metadata::new(
+            "secret.txt".into(),
+            "docs/secret.txt".into(),
+            id.clone(),
+            None,
+        )
+    }
+
+    fn notification() -> PushNotification {
+        PushNotification {
+            event_type: PushEventType::ShareGranted,
+            content_id: ContentId::for_test("content-1"),
+            series_id: ContentId::for_test("content-1"),
+            recipient_key_id: Some("0102".into()),
+            occurred_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn only_watched_series_are_notified() {
+        let notifier = RecordingNotifier::default();
+        let publisher = PushNotifyingEventPublisher::new(NoOpEventPublisher, notifier.clone());
+        let watched = ContentId::for_test("series-watched");
+        let other = ContentId::for_test("series-other");
+        assert!(publisher.watch(watched.clone()));
+        assert!(!publisher.watch(watched.clone()));
+
+        let updated = |series: &ContentId| {
+            ContentDomainEvent::Updated(ContentUpdated {
+                content_id: ContentId::for_test("v2"),
+                series_id: series.clone(),
+                metadata: metadata(series),
+            })
+        };
+        publisher.publish(updated(&watched)).unwrap();
+        publisher.publish(updated(&other)).unwrap();
+        publisher
+            .publish(ContentDomainEvent::Created(ContentCreated {
+                content_id: watched.clone(),
+                series_id: watched.clone(),
+                metadata: metadata(&watched),
+            }))
+            .unwrap();
+        publisher
+            .publish(ContentDomainEvent::Deleted(ContentDeleted {
+                content_id: ContentId::for_test("v2"),
+                series_id: watched.clone(),
+                deleted_at: chrono::Utc::now(),
+            }))
+            .unwrap();
+
+        {
+            let sent = notifier.sent.lock().unwrap();
+            let types: Vec<_> = sent.iter().map(|n| n.event_type).collect();
+            assert_eq!(
+                types,
+                vec![PushEventType::ContentUpdated, PushEventType::ContentDeleted]
+            );
+            assert!(sent.iter().all(|n| n.series_id == watched));
+        }
+
+        assert!(publisher.unwatch(&watched));
+        publisher.publish(updated(&watched)).unwrap();
+        assert_eq!(notifier.sent.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn webhook_posts_signed_payload_without_plaintext() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/push", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                headers.push(line.trim().to_string());
+            }
+            let length: usize = headers
+                .iter()
+                .find_map(|h| {
+                    h.to_ascii_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|v| v.trim().parse().unwrap())
+                })
+                .unwrap();
+            let mut body = vec![0u8; length];
+            reader.read_exact(&mut body).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            (headers, body)
+        });
+
+        let notifier =
+            WebhookPushNotifier::spawn(PushGatewayConfig::new(url).with_secret("s3cret"));
+        notifier.notify(&notification()).unwrap();
+        let (headers, body) = server.join().unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["event_type"], "share_granted");
+        assert_eq!(
+            json["content_id"],
+            ContentId::for_test("content-1").as_str()
+        );
+        assert!(json.get("name").is_none() && json.get("path").is_none());
+        assert_eq!(json["recipient_key_id"], "0102");
+        let signature = format!(
+            "{}: {}",
+            PUSH_SIGNATURE_HEADER.to_ascii_lowercase(),
+            sign_push_payload(b"s3cret", &body)
+        );
+        assert!(headers.iter().any(|h| h.eq_ignore_ascii_case(&signature)));
+    }
+
+    #[test]
+    fn config_from_lookup_requires_url() {
+        assert!(PushGatewayConfig::from_lookup(|_| None).is_none());
+
+        let config = PushGatewayConfig::from_lookup(|key| match key {
+            "MONAS_PUSH_GATEWAY_URL" => Some(" https://push.example/hook ".into()),
+            "MONAS_PUSH_GATEWAY_SECRET" => Some("k".into()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(config.url, "https://push.example/hook");
+        assert_eq!(config.secret.as_deref(), Some(&b"k"[..]));
+    }
+}
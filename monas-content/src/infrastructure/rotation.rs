@@ -0,0 +1,298 @@
+//! 鍵ローテーションポリシー用のカタログ・キューの実装。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use crate::application_service::content_service::{EventPublisher, EventPublisherError};
+use crate::application_service::rotation_service::{
+    ContentCatalog, ContentCatalogError, RotationTask, RotationTaskQueue, RotationTaskQueueError,
+};
+use crate::domain::content::ContentDomainEvent;
+use crate::domain::content_id::ContentId;
+use crate::domain::namespace::Namespace;
+
+/// ドメインイベントから系列ごとの最新の ContentId を記録するカタログ。
+///
+/// `EventPublisher` として `ContentService` に渡すと、作成・更新で最新版を記録し、
+/// 削除で系列ごと取り除く。更新前の旧版は評価対象にしない。
+#[derive(Clone, Default)]
+pub struct InMemoryContentCatalog {
+    latest: Arc<Mutex<HashMap<ContentId, ContentId>>>,
+}
+
+impl InMemoryContentCatalog {
+    /// 系列 `series_id` の最新版として `content_id` を記録する。
+    pub fn insert(&self, series_id: ContentId, content_id: ContentId) {
+        self.latest.lock().unwrap().insert(series_id, content_id);
+    }
+
+    pub fn remove(&self, series_id: &ContentId) {
+        self.latest.lock().unwrap().remove(series_id);
+    }
+}
+
+impl ContentCatalog for InMemoryContentCatalog {
+    fn list(&self) -> Result<Vec<ContentId>, ContentCatalogError> {
+        let guard = self
+            .latest
+            .lock()
+            .map_err(|e| ContentCatalogError::Storage(e.to_string()))?;
+        Ok(guard.values().cloned().collect())
+    }
+}
+
+impl EventPublisher for InMemoryContentCatalog {
+    fn publish(&self, event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+        match event {
+            ContentDomainEvent::Created(e) => self.insert(e.series_id, e.content_id),
+            ContentDomainEvent::Updated(e) => self.insert(e.series_id, e.content_id),
+            ContentDomainEvent::Deleted(e) => self.remove(&e.series_id),
+        }
+        Ok(())
+    }
+}
+
+/// 系列ごとの最新の ContentId を sled に記録するカタログ。
+///
+/// [`InMemoryContentCatalog`] と同じくドメインイベントから記録するが、再起動後も
+/// 一覧とローテーションポリシーの評価対象が失われない。
+/// キーは `catalog:{series_id}`（名前空間付きは `ns:{namespace}:catalog:{series_id}`）。
+#[derive(Clone)]
+pub struct SledContentCatalog {
+    db: sled::Db,
+    namespace: Option<Namespace>,
+}
+
+impl SledContentCatalog {
+    /// 既存の `sled::Db` ハンドルを共有してカタログを構築する。
+    pub fn with_db(db: sled::Db) -> Self {
+        Self {
+            db,
+            namespace: None,
+        }
+    }
+
+    /// 同じ DB を共有し、名前空間 `namespace` の系列だけを扱うカタログを返す。
+    pub fn scoped(&self, namespace: &Namespace) -> Self {
+        Self {
+            db: self.db.clone(),
+            namespace: Some(namespace.clone()),
+        }
+    }
+
+    fn prefix(&self) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("ns:{namespace}:catalog:"),
+            None => "catalog:".to_string(),
+        }
+    }
+
+    fn sled_key(&self, series_id: &ContentId) -> String {
+        format!("{}{}", self.prefix(), series_id.as_str())
+    }
+
+    /// 系列 `series_id` の最新版として `content_id` を記録する。
+    pub fn insert(
+        &self,
+        series_id: &ContentId,
+        content_id: &ContentId,
+    ) -> Result<(), ContentCatalogError> {
+        self.db
+            .insert(self.sled_key(series_id), content_id.as_str().as_bytes())
+            .map_err(|e| ContentCatalogError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    pub fn remove(&self, series_id: &ContentId) -> Result<(), ContentCatalogError> {
+        self.db
+            .remove(self.sled_key(series_id))
+            .map_err(|e| ContentCatalogError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 未書き込みの記録をディスクへ書き出す。
+    pub fn flush(&self) -> Result<(), ContentCatalogError> {
+        self.db
+            .flush()
+            .map_err(|e| ContentCatalogError::Storage(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl ContentCatalog for SledContentCatalog {
+    fn list(&self) -> Result<Vec<ContentId>, ContentCatalogError> {
+        self.db
+            .scan_prefix(self.prefix())
+            .values()
+            .map(|value| {
+                let value = value.map_err(|e| ContentCatalogError::Storage(e.to_string()))?;
+                let content_id = String::from_utf8(value.to_vec())
+                    .map_err(|e| ContentCatalogError::Storage(e.to_string()))?;
+                ContentId::new(content_id).map_err(|e| ContentCatalogError::Storage(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+impl EventPublisher for SledContentCatalog {
+    fn publish(&self, event: ContentDomainEvent) -> Result<(), EventPublisherError> {
+        let result = match event {
+            ContentDomainEvent::Created(e) => self.insert(&e.series_id, &e.content_id),
+            ContentDomainEvent::Updated(e) => self.insert(&e.series_id, &e.content_id),
+            ContentDomainEvent::Deleted(e) => self.remove(&e.series_id),
+        };
+        result.map_err(|e| EventPublisherError::Publish(e.to_string()))
+    }
+}
+
+/// プロセス内の `VecDeque` にローテーション要求を保持するキュー。
+///
+/// 同じコンテンツの要求は 1 件だけ待機させる。
+#[derive(Clone, Default)]
+pub struct InMemoryRotationTaskQueue {
+    tasks: Arc<Mutex<VecDeque<RotationTask>>>,
+}
+
+impl RotationTaskQueue for InMemoryRotationTaskQueue {
+    fn enqueue(&self, task: RotationTask) -> Result<bool, RotationTaskQueueError> {
+        let mut tasks = self
+            .tasks
+            .lock()
+            .map_err(|e| RotationTaskQueueError::Storage(e.to_string()))?;
+        if tasks.iter().any(|t| t.content_id == task.content_id) {
+            return Ok(false);
+        }
+        tasks.push_back(task);
+        Ok(true)
+    }
+
+    fn dequeue(&self) -> Result<Option<RotationTask>, RotationTaskQueueError> {
+        let mut tasks = self
+            .tasks
+            .lock()
+            .map_err(|e| RotationTaskQueueError::Storage(e.to_string()))?;
+        Ok(tasks.pop_front())
+    }
+
+    fn len(&self) -> Result<usize, RotationTaskQueueError> {
+        let tasks = self
+            .tasks
+            .lock()
+            .map_err(|e| RotationTaskQueueError::Storage(e.to_string()))?;
+        Ok(tasks.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::content::{ContentCreated, ContentDeleted, ContentUpdated, Metadata};
+    use crate::infrastructure::test_support::reopen;
+    use chrono::Utc;
+
+    fn metadata(id: &ContentId) -> Metadata {
+        Metadata::new("name".into(), "path".into(), id.clone(), None)
+    }
+
+    #[test]
+    fn catalog_tracks_latest_version_per_series() {
+        let catalog = InMemoryContentCatalog::default();
+        let series = ContentId::for_test("series");
+        let v2 = ContentId::for_test("v2");
+
+        catalog
+            .publish(ContentDomainEvent::Created(ContentCreated {
+                content_id: series.clone(),
+                series_id: series.clone(),
+                metadata: metadata(&series),
+            }))
+            .unwrap();
+        catalog
+            .publish(ContentDomainEvent::Updated(ContentUpdated {
+                content_id: v2.clone(),
+                series_id: series.clone(),
+                metadata: metadata(&v2),
+            }))
+            .unwrap();
+        assert_eq!(catalog.list().unwrap(), vec![v2.clone()]);
+
+        catalog
+            .publish(ContentDomainEvent::Deleted(ContentDeleted {
+                content_id: v2,
+                series_id: series,
+                deleted_at: Utc::now(),
+            }))
+            .unwrap();
+        assert!(catalog.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn sled_catalog_survives_reopen_and_separates_namespaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let series = ContentId::for_test("series");
+        let v2 = ContentId::for_test("v2");
+        {
+            let catalog = SledContentCatalog::with_db(sled::open(dir.path()).unwrap());
+            catalog
+                .publish(ContentDomainEvent::Created(ContentCreated {
+                    content_id: series.clone(),
+                    series_id: series.clone(),
+                    metadata: metadata(&series),
+                }))
+                .unwrap();
+            catalog
+                .publish(ContentDomainEvent::Updated(ContentUpdated {
+                    content_id: v2.clone(),
+                    series_id: series.clone(),
+                    metadata: metadata(&v2),
+                }))
+                .unwrap();
+            catalog.flush().unwrap();
+        }
+
+        let catalog = SledContentCatalog::with_db(reopen(|| sled::open(dir.path())));
+        assert_eq!(catalog.list().unwrap(), vec![v2.clone()]);
+
+        let alice = catalog.scoped(&Namespace::new("alice").unwrap());
+        assert!(alice.list().unwrap().is_empty());
+        alice.insert(&series, &series).unwrap();
+        assert_eq!(alice.list().unwrap(), vec![series.clone()]);
+        assert_eq!(catalog.list().unwrap(), vec![v2.clone()]);
+
+        catalog
+            .publish(ContentDomainEvent::Deleted(ContentDeleted {
+                content_id: v2,
+                series_id: series.clone(),
+                deleted_at: Utc::now(),
+            }))
+            .unwrap();
+        assert!(catalog.list().unwrap().is_empty());
+        assert_eq!(alice.list().unwrap(), vec![series]);
+    }
+
+    #[test]
+    fn queue_is_fifo_and_deduplicates_by_content() {
+        let queue = InMemoryRotationTaskQueue::default();
+        let task = |id: &str| RotationTask {
+            content_id: ContentId::for_test(id),
+            violations: Vec::new(),
+            queued_at: Utc::now(),
+        };
+
+        assert!(queue.enqueue(task("a")).unwrap());
+        assert!(queue.enqueue(task("b")).unwrap());
+        assert!(!queue.enqueue(task("a")).unwrap());
+        assert_eq!(queue.len().unwrap(), 2);
+
+        assert_eq!(
+            queue.dequeue().unwrap().unwrap().content_id,
+            ContentId::for_test("a")
+        );
+        assert_eq!(
+            queue.dequeue().unwrap().unwrap().content_id,
+            ContentId::for_test("b")
+        );
+        assert!(queue.dequeue().unwrap().is_none());
+        assert!(queue.is_empty().unwrap());
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::{Arc, Mutex};
 
 use crate::application_service::share_service::{ShareRepository, ShareRepositoryError};
 use crate::domain::content_id::ContentId;
+use crate::domain::namespace::Namespace;
 use crate::domain::share::Share;
 
 /// シンプルなインメモリ実装の ShareRepository。
@@ -38,7 +39,8 @@ impl ShareRepository for InMemoryShareRepository {
 
 /// sled を用いた ShareRepository 実装。
 ///
-/// - キー: `"share:{content_id.as_str()}"`（UTF-8 文字列）
+/// - キー: `"share:{content_id.as_str()}"`（UTF-8 文字列）。
+///   名前空間付きは `"ns:{namespace}:share:{content_id.as_str()}"`
 /// - 値: `Share` を JSON でシリアライズしたバイト列
 ///
 /// NOTE:
@@ -47,13 +49,17 @@ impl ShareRepository for InMemoryShareRepository {
 #[derive(Clone)]
 pub struct SledShareRepository {
     db: sled::Db,
+    namespace: Option<Namespace>,
 }
 
 impl SledShareRepository {
     /// 指定されたパスに sled DB を開く。
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ShareRepositoryError> {
         let db = sled::open(path).map_err(|e| ShareRepositoryError::Storage(e.to_string()))?;
-        Ok(Self { db })
+        Ok(Self {
+            db,
+            namespace: None,
+        })
     }
 
     /// 既存の `sled::Db` ハンドルを共有してインスタンスを構築する。
@@ -61,13 +67,31 @@ impl SledShareRepository {
     /// CEK ストアと同じ DB ファイルを共有したい場合に使う
     /// (`SledContentEncryptionKeyStore::with_db` と同じ `sled::Db` を渡す)。
     pub fn with_db(db: sled::Db) -> Self {
-        Self { db }
+        Self {
+            db,
+            namespace: None,
+        }
+    }
+
+    /// 同じ DB を共有し、名前空間 `namespace` の共有だけを扱うリポジトリを返す。
+    pub fn scoped(&self, namespace: &Namespace) -> Self {
+        Self {
+            db: self.db.clone(),
+            namespace: Some(namespace.clone()),
+        }
+    }
+
+    fn sled_key(&self, content_id: &ContentId) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("ns:{namespace}:share:{}", content_id.as_str()),
+            None => format!("share:{}", content_id.as_str()),
+        }
     }
 }
 
 impl ShareRepository for SledShareRepository {
     fn load(&self, content_id: &ContentId) -> Result<Option<Share>, ShareRepositoryError> {
-        let sled_key = format!("share:{}", content_id.as_str());
+        let sled_key = self.sled_key(content_id);
         let opt = self
             .db
             .get(sled_key)
@@ -83,7 +107,7 @@ impl ShareRepository for SledShareRepository {
     }
 
     fn save(&self, share: &Share) -> Result<(), ShareRepositoryError> {
-        let key = format!("share:{}", share.content_id().as_str());
+        let key = self.sled_key(share.content_id());
         let value =
             serde_json::to_vec(share).map_err(|e| ShareRepositoryError::Storage(e.to_string()))?;
 
@@ -96,4 +120,35 @@ impl ShareRepository for SledShareRepository {
 
         Ok(())
     }
+
+    fn flush(&self) -> Result<(), ShareRepositoryError> {
+        self.db
+            .flush()
+            .map_err(|e| ShareRepositoryError::Storage(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::KeyId;
+
+    #[test]
+    fn sled_repository_scopes_shares_by_namespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = SledShareRepository::open(dir.path()).unwrap();
+        let alice = repository.scoped(&Namespace::new("alice").unwrap());
+        let bob = repository.scoped(&Namespace::new("bob").unwrap());
+        let content_id = ContentId::for_test("shared-plaintext");
+
+        let mut share = Share::new(content_id.clone());
+        share.grant_read(KeyId::new(vec![1, 2, 3])).unwrap();
+        alice.save(&share).unwrap();
+
+        let loaded = alice.load(&content_id).unwrap().unwrap();
+        assert_eq!(loaded.recipients(), share.recipients());
+        assert!(bob.load(&content_id).unwrap().is_none());
+        assert!(repository.load(&content_id).unwrap().is_none());
+    }
 }
@@ -0,0 +1,399 @@
+//! KeyEnvelope を送信者の署名付きで CBOR（RFC 8949）にシリアライズする。
+//!
+//! 受信者が「誰から共有されたのか」を確認できるよう、送信者は自分の P-256 鍵で封筒の
+//! すべてのフィールド（署名と公開鍵を除く）に署名し、公開鍵と一緒に封筒へ同梱する。
+//! 読み込み時は署名と、公開鍵から計算した KeyId が `sender_key_id` と一致することを検証する。
+//! encapsulated key や暗号文だけを差し替えた封筒も署名の検証で拒否される。
+//!
+//! ## 形式
+//!
+//! 長さ確定の CBOR 配列で、要素の順序は固定（マップを使わないので並び順の揺れがない）。
+//!
+//! | # | 型     | 内容                                                   |
+//! |---|--------|--------------------------------------------------------|
+//! | 0 | uint   | 形式のバージョン（`SIGNED_ENVELOPE_VERSION`）          |
+//! | 1 | text   | ContentId                                              |
+//! | 2 | text   | ラップ方式（JWE の `alg` と同じ名前）                  |
+//! | 3 | bytes  | 送信者の KeyId                                         |
+//! | 4 | bytes  | 受信者の KeyId                                         |
+//! | 5 | bytes  | HPKE の encapsulated key                               |
+//! | 6 | bytes  | ラップされた CEK                                       |
+//! | 7 | bytes  | コンテンツの暗号文                                     |
+//! | 8 | bytes  | 送信者の公開鍵（SEC1）                                 |
+//! | 9 | bytes  | 署名（ECDSA P-256 / SHA-256, r \|\| s の 64 バイト）    |
+//!
+//! 署名対象は `[SIGNATURE_CONTEXT, #1..#7]`（バージョン・公開鍵・署名を除くすべての要素）の
+//! CBOR エンコード。デコード時は再エンコードした結果と入力が一致することも確かめ、
+//! 同じ内容を別のバイト列で表した（非正準な）入力を拒否する。
+//!
+//! 署名が ContentId・受信者の KeyId・ラップされた CEK しか覆っていなかった
+//! バージョン 1 の封筒は、差し替えを検出できないため受け付けない。
+//!
+//! 受信側で KeyEnvelope を取り込むときは `import_verified` を使い、
+//! 検証に通ったものだけを `KeyEnvelopeRepository` に保存する。
+//! `POST /shares/unwrap-signed` も検証に通った封筒からだけ CEK を取り出す。
+
+use ciborium::value::Value;
+use p256::ecdsa::signature::{Signer, Verifier};
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+
+use crate::application_service::share_service::{
+    KeyEnvelopeRepository, KeyEnvelopeRepositoryError, PublicKeyDirectory,
+};
+use crate::domain::content_id::ContentId;
+use crate::domain::share::key_envelope::{KeyEnvelope, WrappedRecipientKey};
+use crate::domain::KeyId;
+use crate::infrastructure::jose::{jose_alg, key_wrap_algorithm_from_jose};
+
+/// 現在の署名付き KeyEnvelope 形式のバージョン。
+pub const SIGNED_ENVELOPE_VERSION: u64 = 2;
+
+/// 署名対象の先頭に置くドメイン分離用の文字列。
+pub const SIGNATURE_CONTEXT: &str = "monas-key-envelope-signature-v2";
+
+const FIELD_COUNT: usize = 10;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignedEnvelopeError {
+    #[error("failed to encode signed envelope: {0}")]
+    Encode(String),
+    #[error("malformed signed envelope: {0}")]
+    Malformed(String),
+    #[error("signed envelope is not canonically encoded")]
+    NonCanonical,
+    #[error("unsupported signed envelope version: {0}")]
+    UnsupportedVersion(u64),
+    #[error("unsupported key wrap algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+    #[error("sender public key does not match sender_key_id")]
+    SenderKeyMismatch,
+    #[error("invalid sender public key: {0}")]
+    InvalidPublicKey(String),
+    #[error("sender signature verification failed")]
+    InvalidSignature,
+    #[error("key envelope repository error: {0}")]
+    Repository(KeyEnvelopeRepositoryError),
+}
+
+/// 署名を検証済みの KeyEnvelope と、署名した送信者の公開鍵。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedKeyEnvelope {
+    pub envelope: KeyEnvelope,
+    /// 送信者の公開鍵（SEC1 uncompressed）。KeyId は `envelope.sender_key_id()`。
+    pub sender_public_key: Vec<u8>,
+}
+
+/// 送信者が署名する内容の CBOR エンコード。
+///
+/// ラップ方式・送信者と受信者の KeyId・encapsulated key・ラップされた CEK・暗号文を
+/// すべて含む。
+pub fn signing_payload(envelope: &KeyEnvelope) -> Result<Vec<u8>, SignedEnvelopeError> {
+    let recipient = envelope.recipient();
+    encode_value(&Value::Array(vec![
+        Value::Text(SIGNATURE_CONTEXT.to_string()),
+        Value::Text(envelope.content_id().as_str().to_string()),
+        Value::Text(jose_alg(envelope.key_wrap_algorithm()).to_string()),
+        Value::Bytes(envelope.sender_key_id().as_bytes().to_vec()),
+        Value::Bytes(recipient.key_id().as_bytes().to_vec()),
+        Value::Bytes(recipient.enc().to_vec()),
+        Value::Bytes(recipient.wrapped_cek().to_vec()),
+        Value::Bytes(envelope.ciphertext().to_vec()),
+    ]))
+}
+
+/// KeyEnvelope に送信者の署名を付けて CBOR にエンコードする。
+///
+/// `signing_key` の公開鍵から `key_ids` で計算した KeyId が
+/// `envelope.sender_key_id()` と一致しない場合は `SenderKeyMismatch` を返す。
+pub fn encode_signed<D: PublicKeyDirectory + ?Sized>(
+    envelope: &KeyEnvelope,
+    signing_key: &SigningKey,
+    key_ids: &D,
+) -> Result<Vec<u8>, SignedEnvelopeError> {
+    let sender_public_key = signing_key
+        .verifying_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    if &key_ids.compute_key_id(&sender_public_key) != envelope.sender_key_id() {
+        return Err(SignedEnvelopeError::SenderKeyMismatch);
+    }
+
+    let signature: Signature = signing_key.sign(&signing_payload(envelope)?);
+    let recipient = envelope.recipient();
+    encode_value(&Value::Array(vec![
+        Value::Integer(SIGNED_ENVELOPE_VERSION.into()),
+        Value::Text(envelope.content_id().as_str().to_string()),
+        Value::Text(jose_alg(envelope.key_wrap_algorithm()).to_string()),
+        Value::Bytes(envelope.sender_key_id().as_bytes().to_vec()),
+        Value::Bytes(recipient.key_id().as_bytes().to_vec()),
+        Value::Bytes(recipient.enc().to_vec()),
+        Value::Bytes(recipient.wrapped_cek().to_vec()),
+        Value::Bytes(envelope.ciphertext().to_vec()),
+        Value::Bytes(sender_public_key),
+        Value::Bytes(signature.to_bytes().to_vec()),
+    ]))
+}
+
+/// 署名付き KeyEnvelope をデコードし、送信者の署名を検証する。
+pub fn decode_verified<D: PublicKeyDirectory + ?Sized>(
+    bytes: &[u8],
+    key_ids: &D,
+) -> Result<VerifiedKeyEnvelope, SignedEnvelopeError> {
+    let value: Value =
+        ciborium::from_reader(bytes).map_err(|e| SignedEnvelopeError::Malformed(e.to_string()))?;
+    if encode_value(&value)? != bytes {
+        return Err(SignedEnvelopeError::NonCanonical);
+    }
+
+    let fields = match value {
+        Value::Array(fields) if fields.len() == FIELD_COUNT => fields,
+        Value::Array(fields) => {
+            return Err(SignedEnvelopeError::Malformed(format!(
+                "expected {FIELD_COUNT} fields, got {}",
+                fields.len()
+            )))
+        }
+        _ => return Err(SignedEnvelopeError::Malformed("expected an array".into())),
+    };
+    let mut fields = fields.into_iter();
+    let mut next = || fields.next().expect("field count was checked");
+
+    let version = match next() {
+        Value::Integer(v) => u64::try_from(v)
+            .map_err(|_| SignedEnvelopeError::Malformed("version: out of range".into()))?,
+        _ => {
+            return Err(SignedEnvelopeError::Malformed(
+                "version: expected uint".into(),
+            ))
+        }
+    };
+    if version != SIGNED_ENVELOPE_VERSION {
+        return Err(SignedEnvelopeError::UnsupportedVersion(version));
+    }
+    let content_id = ContentId::new(text(next(), "content_id")?)
+        .map_err(|e| SignedEnvelopeError::Malformed(format!("content_id: {e}")))?;
+    let alg = text(next(), "alg")?;
+    let algorithm =
+        key_wrap_algorithm_from_jose(&alg).ok_or(SignedEnvelopeError::UnsupportedAlgorithm(alg))?;
+    let sender_key_id = KeyId::new(bytes_field(next(), "sender_key_id")?);
+    let recipient = WrappedRecipientKey::new(
+        KeyId::new(bytes_field(next(), "recipient_key_id")?),
+        bytes_field(next(), "enc")?,
+        bytes_field(next(), "wrapped_cek")?,
+    );
+    let ciphertext = bytes_field(next(), "ciphertext")?;
+    let sender_public_key = bytes_field(next(), "sender_public_key")?;
+    let signature = bytes_field(next(), "signature")?;
+
+    let envelope = KeyEnvelope::new(content_id, algorithm, sender_key_id, recipient, ciphertext);
+
+    if &key_ids.compute_key_id(&sender_public_key) != envelope.sender_key_id() {
+        return Err(SignedEnvelopeError::SenderKeyMismatch);
+    }
+    let verifying_key = VerifyingKey::from_sec1_bytes(&sender_public_key)
+        .map_err(|e| SignedEnvelopeError::InvalidPublicKey(e.to_string()))?;
+    let signature =
+        Signature::from_slice(&signature).map_err(|_| SignedEnvelopeError::InvalidSignature)?;
+    verifying_key
+        .verify(&signing_payload(&envelope)?, &signature)
+        .map_err(|_| SignedEnvelopeError::InvalidSignature)?;
+
+    Ok(VerifiedKeyEnvelope {
+        envelope,
+        sender_public_key,
+    })
+}
+
+/// 署名付き KeyEnvelope を検証し、検証に成功した場合だけリポジトリへ保存する。
+pub fn import_verified<D, ER>(
+    bytes: &[u8],
+    key_ids: &D,
+    repository: &ER,
+) -> Result<VerifiedKeyEnvelope, SignedEnvelopeError>
+where
+    D: PublicKeyDirectory + ?Sized,
+    ER: KeyEnvelopeRepository + ?Sized,
+{
+    let verified = decode_verified(bytes, key_ids)?;
+    repository
+        .save(&verified.envelope)
+        .map_err(SignedEnvelopeError::Repository)?;
+    Ok(verified)
+}
+
+fn encode_value(value: &Value) -> Result<Vec<u8>, SignedEnvelopeError> {
+    let mut buf = Vec::new();
+    ciborium::into_writer(value, &mut buf)
+        .map_err(|e| SignedEnvelopeError::Encode(e.to_string()))?;
+    Ok(buf)
+}
+
+fn text(value: Value, field: &str) -> Result<String, SignedEnvelopeError> {
+    match value {
+        Value::Text(s) => Ok(s),
+        _ => Err(SignedEnvelopeError::Malformed(format!(
+            "{field}: expected text"
+        ))),
+    }
+}
+
+fn bytes_field(value: Value, field: &str) -> Result<Vec<u8>, SignedEnvelopeError> {
+    match value {
+        Value::Bytes(b) => Ok(b),
+        _ => Err(SignedEnvelopeError::Malformed(format!(
+            "{field}: expected bytes"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::share::key_envelope::KeyWrapAlgorithm;
+    use crate::infrastructure::key_envelope_repository::InMemoryKeyEnvelopeRepository;
+    use crate::infrastructure::public_key_directory::InMemoryPublicKeyDirectory;
+    use rand_core::OsRng;
+
+    fn public_key(signing_key: &SigningKey) -> Vec<u8> {
+        signing_key
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec()
+    }
+
+    fn envelope(sender_key_id: KeyId) -> KeyEnvelope {
+        KeyEnvelope::new(
+            ContentId::new(format!("1220{}", "cd".repeat(32))).unwrap(),
+            KeyWrapAlgorithm::HpkeV1,
+            sender_key_id,
+            WrappedRecipientKey::new(KeyId::new(vec![7; 16]), vec![1, 2, 3], vec![4, 5, 6]),
+            vec![0xAA; 24],
+        )
+    }
+
+    #[test]
+    fn round_trips_and_identifies_the_sender() {
+        let directory = InMemoryPublicKeyDirectory::default();
+        let signing_key = SigningKey::random(&mut OsRng);
+        let sender_key_id = directory.compute_key_id(&public_key(&signing_key));
+        let original = envelope(sender_key_id);
+
+        let encoded = encode_signed(&original, &signing_key, &directory).unwrap();
+        // ECDSA の署名は乱数を使わない（RFC 6979）ため、同じ入力なら同じバイト列になる
+        assert_eq!(
+            encode_signed(&original, &signing_key, &directory).unwrap(),
+            encoded
+        );
+
+        let verified = decode_verified(&encoded, &directory).unwrap();
+        assert_eq!(verified.envelope, original);
+        assert_eq!(verified.sender_public_key, public_key(&signing_key));
+    }
+
+    #[test]
+    fn rejects_tampered_fields_and_foreign_keys() {
+        let directory = InMemoryPublicKeyDirectory::default();
+        let signing_key = SigningKey::random(&mut OsRng);
+        let sender_key_id = directory.compute_key_id(&public_key(&signing_key));
+        let original = envelope(sender_key_id.clone());
+        let encoded = encode_signed(&original, &signing_key, &directory).unwrap();
+
+        // 署名対象（ラップされた CEK）を書き換える
+        let Value::Array(mut fields) = ciborium::from_reader::<Value, _>(&encoded[..]).unwrap()
+        else {
+            unreachable!()
+        };
+        // encapsulated key・ラップされた CEK・暗号文のどれを差し替えても署名の検証に失敗する
+        for (index, replacement) in [(5, vec![8, 8, 8]), (6, vec![9, 9, 9]), (7, vec![0xBB; 24])] {
+            let mut tampered = fields.clone();
+            tampered[index] = Value::Bytes(replacement);
+            let tampered = encode_value(&Value::Array(tampered)).unwrap();
+            assert!(
+                matches!(
+                    decode_verified(&tampered, &directory),
+                    Err(SignedEnvelopeError::InvalidSignature)
+                ),
+                "field {index} was not covered by the signature"
+            );
+        }
+
+        // 別の鍵で署名し直しても、sender_key_id と公開鍵が一致しない
+        let other_key = SigningKey::random(&mut OsRng);
+        fields[8] = Value::Bytes(public_key(&other_key));
+        let substituted = encode_value(&Value::Array(fields)).unwrap();
+        assert!(matches!(
+            decode_verified(&substituted, &directory),
+            Err(SignedEnvelopeError::SenderKeyMismatch)
+        ));
+
+        // 送信者の KeyId と異なる鍵では署名できない
+        assert!(matches!(
+            encode_signed(&original, &other_key, &directory),
+            Err(SignedEnvelopeError::SenderKeyMismatch)
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_versions_and_malformed_input() {
+        let directory = InMemoryPublicKeyDirectory::default();
+        let signing_key = SigningKey::random(&mut OsRng);
+        let sender_key_id = directory.compute_key_id(&public_key(&signing_key));
+        let encoded = encode_signed(&envelope(sender_key_id), &signing_key, &directory).unwrap();
+
+        let Value::Array(mut fields) = ciborium::from_reader::<Value, _>(&encoded[..]).unwrap()
+        else {
+            unreachable!()
+        };
+        // 署名が一部のフィールドしか覆っていないバージョン 1 の封筒も受け付けない
+        for version in [1u64, 3] {
+            fields[0] = Value::Integer(version.into());
+            let other = encode_value(&Value::Array(fields.clone())).unwrap();
+            assert!(matches!(
+                decode_verified(&other, &directory),
+                Err(SignedEnvelopeError::UnsupportedVersion(v)) if v == version
+            ));
+        }
+
+        assert!(matches!(
+            decode_verified(&encoded[..encoded.len() - 1], &directory),
+            Err(SignedEnvelopeError::Malformed(_))
+        ));
+
+        // 末尾に余分なバイトがあるものは正準な形式ではない
+        let mut trailing = encoded.clone();
+        trailing.push(0x00);
+        assert!(matches!(
+            decode_verified(&trailing, &directory),
+            Err(SignedEnvelopeError::NonCanonical)
+        ));
+    }
+
+    #[test]
+    fn import_saves_only_verified_envelopes() {
+        let directory = InMemoryPublicKeyDirectory::default();
+        let repository = InMemoryKeyEnvelopeRepository::default();
+        let signing_key = SigningKey::random(&mut OsRng);
+        let sender_key_id = directory.compute_key_id(&public_key(&signing_key));
+        let original = envelope(sender_key_id);
+        let encoded = encode_signed(&original, &signing_key, &directory).unwrap();
+
+        let mut corrupted = encoded.clone();
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 0x01;
+        assert!(import_verified(&corrupted, &directory, &repository).is_err());
+        assert!(repository
+            .find(original.content_id(), original.recipient().key_id())
+            .unwrap()
+            .is_none());
+
+        import_verified(&encoded, &directory, &repository).unwrap();
+        assert_eq!(
+            repository
+                .find(original.content_id(), original.recipient().key_id())
+                .unwrap(),
+            Some(original)
+        );
+    }
+}
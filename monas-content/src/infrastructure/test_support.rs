@@ -0,0 +1,17 @@
+//! infrastructure のテストで共有する補助関数。
+
+use std::time::Duration;
+
+/// 閉じた直後の sled DB を使うストアを開き直す。
+///
+/// sled は DB を閉じた後もしばらくバックグラウンドのスレッドがファイルロックを保持するため、
+/// ロックが解放されるまで `open` を繰り返す。
+pub(crate) fn reopen<T, E: std::fmt::Debug>(open: impl Fn() -> Result<T, E>) -> T {
+    for _ in 0..100 {
+        if let Ok(value) = open() {
+            return value;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    open().expect("failed to reopen the store")
+}
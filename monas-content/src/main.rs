@@ -1,12 +1,104 @@
 use std::net::SocketAddr;
+use std::sync::Arc;
 
+use monas_event_manager::shutdown::{
+    drain_timeout_from_env, serve_with_drain_timeout, shutdown_signal, DrainOutcome,
+};
+use monas_event_manager::{make_subscriber, EventBus};
 use tokio::net::TcpListener;
+use tracing_subscriber::EnvFilter;
 
-use monas_content::presentation;
+use monas_content::application_service::content_service::{ChunkingPolicy, ContentLimits};
+use monas_content::application_service::job_service::JobScheduleConfig;
+use monas_content::application_service::rotation_service::RotationPolicy;
+use monas_content::domain::content::events::{ContentCreated, ContentDeleted, ContentUpdated};
+use monas_content::infrastructure::account_directory::AccountDirectoryConfig;
+use monas_content::infrastructure::cipher_suite::CipherSuite;
+use monas_content::infrastructure::content_cache::ContentCacheConfig;
+use monas_content::infrastructure::content_id::ConfigurableContentIdGenerator;
+use monas_content::infrastructure::event_bus_publisher::EventBusEventPublisher;
+use monas_content::infrastructure::key_derivation::AccountMasterKey;
+use monas_content::infrastructure::key_escrow::KeyEscrowConfig;
+use monas_content::infrastructure::metadata_encryption::MetadataKeyMode;
+use monas_content::infrastructure::push_notifier::PushGatewayConfig;
+use monas_content::infrastructure::ContentStorageConfig;
+use monas_content::presentation::{self, ContentAppOptions, NamespaceConfig, RateLimitConfig};
+
+/// コンテンツのドメインイベントをログに記録する購読者を登録する。
+async fn log_content_events(
+    event_bus: &EventBus,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    event_bus
+        .subscribe::<ContentCreated>(make_subscriber::<ContentCreated, _, _>(
+            "monas-content-log-created".to_string(),
+            |event: Arc<ContentCreated>| async move {
+                tracing::info!(content_id = %event.content_id.as_str(), "content created");
+                Ok(())
+            },
+        ))
+        .await?;
+    event_bus
+        .subscribe::<ContentUpdated>(make_subscriber::<ContentUpdated, _, _>(
+            "monas-content-log-updated".to_string(),
+            |event: Arc<ContentUpdated>| async move {
+                tracing::info!(
+                    content_id = %event.content_id.as_str(),
+                    series_id = %event.series_id.as_str(),
+                    "content updated"
+                );
+                Ok(())
+            },
+        ))
+        .await?;
+    event_bus
+        .subscribe::<ContentDeleted>(make_subscriber::<ContentDeleted, _, _>(
+            "monas-content-log-deleted".to_string(),
+            |event: Arc<ContentDeleted>| async move {
+                tracing::info!(content_id = %event.content_id.as_str(), "content deleted");
+                Ok(())
+            },
+        ))
+        .await
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let app = presentation::create_router();
+    // ContentService のスパン・ログは RUST_LOG で絞り込める（既定は info）
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .init();
+
+    let events = EventBusEventPublisher::new(EventBus::new());
+    events.register_event_types().await;
+    log_content_events(events.event_bus())
+        .await
+        .map_err(|e| e as Box<dyn std::error::Error>)?;
+    let drain_timeout = drain_timeout_from_env()?;
+    let storage = ContentStorageConfig {
+        cache: ContentCacheConfig::from_env()?,
+        chunking: ChunkingPolicy::from_env()?,
+        limits: ContentLimits::from_env()?,
+        master_key: AccountMasterKey::from_env()?,
+        metadata_encryption: MetadataKeyMode::from_env()?,
+        ..ContentStorageConfig::from_env()
+    };
+    let (app, shutdown) = presentation::create_app_with_config(
+        &storage,
+        ContentAppOptions {
+            push_gateway: PushGatewayConfig::from_env(),
+            content_id_generator: ConfigurableContentIdGenerator::from_env()?,
+            rotation_policy: RotationPolicy::from_env()?,
+            account_directory: AccountDirectoryConfig::from_env(),
+            cipher_suite: CipherSuite::from_env()?,
+            rate_limit: RateLimitConfig::from_env()?,
+            namespaces: NamespaceConfig::from_env()?,
+            jobs: JobScheduleConfig::from_env()?,
+            events: Arc::new(events),
+            key_escrow: KeyEscrowConfig::from_env()?,
+        },
+    )?;
 
     let port: u16 = std::env::var("MONAS_CONTENT_PORT")
         .ok()
@@ -17,7 +109,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("monas-content server listening on http://{addr}");
 
     let listener = TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // レート制限で接続元 IP を識別できるよう、接続情報をリクエストに載せる
+    // SIGINT / SIGTERM を受け取ったら、処理中のリクエストを終えてから戻る
+    // MONAS_SHUTDOWN_DRAIN_TIMEOUT_SECS（既定 30 秒）を過ぎたら待たずに終了処理へ進む
+    let outcome = serve_with_drain_timeout(
+        async {
+            shutdown_signal().await;
+            tracing::info!("shutdown signal received, draining in-flight requests");
+        },
+        drain_timeout,
+        |signal| async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(signal)
+            .await
+        },
+    )
+    .await?;
+    if outcome == DrainOutcome::TimedOut {
+        tracing::warn!(
+            ?drain_timeout,
+            "in-flight requests did not finish in time, shutting down"
+        );
+    }
+
+    shutdown.flush()?;
+    println!("monas-content server stopped");
 
     Ok(())
 }
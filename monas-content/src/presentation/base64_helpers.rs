@@ -1,9 +1,10 @@
-use axum::http::StatusCode;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 
 use crate::domain::{content::encryption::ContentEncryptionKey, KeyId};
 
+use super::ApiError;
+
 // ============================================================================
 // Base64デコードヘルパー関数
 // ============================================================================
@@ -16,17 +17,11 @@ use crate::domain::{content::encryption::ContentEncryptionKey, KeyId};
 ///
 /// # 戻り値
 /// - 成功時: デコードされたバイト列
-/// - 失敗時: `(StatusCode::BAD_REQUEST, エラーメッセージ)`
-pub(super) fn decode_base64(
-    base64_str: &str,
-    field_name: &str,
-) -> Result<Vec<u8>, (StatusCode, String)> {
-    BASE64_STANDARD.decode(base64_str).map_err(|e| {
-        (
-            StatusCode::BAD_REQUEST,
-            format!("invalid {field_name}: {e}"),
-        )
-    })
+/// - 失敗時: `ApiError::bad_request`（400）
+pub(super) fn decode_base64(base64_str: &str, field_name: &str) -> Result<Vec<u8>, ApiError> {
+    BASE64_STANDARD
+        .decode(base64_str)
+        .map_err(|e| ApiError::bad_request(format!("invalid {field_name}: {e}")))
 }
 
 /// base64エンコードされたKeyIdをデコードするヘルパー関数。
@@ -37,11 +32,8 @@ pub(super) fn decode_base64(
 ///
 /// # 戻り値
 /// - 成功時: デコードされたKeyId
-/// - 失敗時: `(StatusCode::BAD_REQUEST, エラーメッセージ)`
-pub(super) fn decode_key_id_base64(
-    base64_str: &str,
-    field_name: &str,
-) -> Result<KeyId, (StatusCode, String)> {
+/// - 失敗時: `ApiError::bad_request`（400）
+pub(super) fn decode_key_id_base64(base64_str: &str, field_name: &str) -> Result<KeyId, ApiError> {
     let bytes = decode_base64(base64_str, field_name)?;
     Ok(KeyId::new(bytes))
 }
@@ -54,11 +46,11 @@ pub(super) fn decode_key_id_base64(
 ///
 /// # 戻り値
 /// - 成功時: デコードされたContentEncryptionKey
-/// - 失敗時: `(StatusCode::BAD_REQUEST, エラーメッセージ)`
+/// - 失敗時: `ApiError::bad_request`（400）
 pub(super) fn decode_cek_base64(
     base64_str: &str,
     field_name: &str,
-) -> Result<ContentEncryptionKey, (StatusCode, String)> {
+) -> Result<ContentEncryptionKey, ApiError> {
     let bytes = decode_base64(base64_str, field_name)?;
     Ok(ContentEncryptionKey(bytes))
 }
@@ -72,11 +64,11 @@ pub(super) fn decode_cek_base64(
 /// # 戻り値
 /// - `None`の場合: `Ok(None)`
 /// - `Some(base64_str)`の場合: デコード結果を`Some`でラップ
-/// - 失敗時: `(StatusCode::BAD_REQUEST, エラーメッセージ)`
+/// - 失敗時: `ApiError::bad_request`（400）
 pub(super) fn decode_base64_optional(
     base64_str_opt: Option<&str>,
     field_name: &str,
-) -> Result<Option<Vec<u8>>, (StatusCode, String)> {
+) -> Result<Option<Vec<u8>>, ApiError> {
     match base64_str_opt {
         Some(base64_str) => decode_base64(base64_str, field_name).map(Some),
         None => Ok(None),
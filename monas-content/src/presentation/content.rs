@@ -1,66 +1,231 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use axum::{
-    extract::{Json, Path, Query, State},
-    http::StatusCode,
-    routing::{delete, get, patch, post},
-    Router,
+    extract::{ConnectInfo, Json, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
+    Extension, Router,
 };
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, OpenApi, ToSchema};
 
 use crate::{
+    application_service::content_service::ContentPathIndex,
     application_service::content_service::{
-        ContentRepositoryError, CreateContentCommand, CreateContentResult, DecryptWithCekError,
-        DeleteContentCommand, ReencryptContentCommand, ReencryptError, UpdateContentCommand,
+        if_none_match_matches, ArchiveContentCommand, ArchiveContentResult, ConditionalFetchResult,
+        ContentListFilter, CreateClientEncryptedContentCommand, CreateContentCommand,
+        CreateContentResult, DeleteContentCommand, GetContentCommand, GetContentResult,
+        ListContentsCommand, PinContentCommand, ReencryptContentCommand,
+        UpdateClientEncryptedContentCommand, UpdateContentCommand,
     },
-    domain::{content::provider::StorageProvider, content::ContentStatus, content_id::ContentId},
+    domain::{content::provider::StorageProvider, content::ContentStatus},
 };
 
-use super::{decode_base64, decode_base64_optional, decode_cek_base64, AppState};
+use super::{
+    decode_base64, decode_base64_optional, decode_cek_base64, parse_content_id, parse_provider,
+    share::{envelope_response, KeyEnvelopeResponse},
+    ApiError, AppState, ErrorResponse,
+};
+
+/// コンテンツ作成の再送を識別するヘッダ。
+const IDEMPOTENCY_KEY_HEADER: header::HeaderName =
+    header::HeaderName::from_static("idempotency-key");
+
+/// 冪等キーを分けて扱うための呼び出し元の識別子。
+///
+/// `Authorization` ヘッダがあればその値、なければ接続元 IP で識別する。識別子は
+/// サービス側でハッシュ化してから記録する。
+fn idempotency_caller(
+    headers: &HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+) -> String {
+    if let Some(authorization) = headers.get(header::AUTHORIZATION) {
+        return format!("auth:{}", String::from_utf8_lossy(authorization.as_bytes()));
+    }
+    match connect_info {
+        Some(Extension(ConnectInfo(addr))) => format!("ip:{}", addr.ip()),
+        // 接続情報のないルーター（テストなど）では全リクエストを 1 呼び出し元として扱う
+        None => "ip:unknown".to_string(),
+    }
+}
 
-#[derive(Deserialize)]
+/// 平文コンテンツの SHA-256（hex）を指定するヘッダ。転送中の破損を検出する。
+const CONTENT_SHA256_HEADER: header::HeaderName =
+    header::HeaderName::from_static("x-content-sha256");
+
+/// `X-Content-Sha256` ヘッダを読み取る。形式が不正な場合は 400。
+fn content_sha256(headers: &HeaderMap) -> Result<Option<[u8; 32]>, ApiError> {
+    let Some(value) = headers.get(CONTENT_SHA256_HEADER) else {
+        return Ok(None);
+    };
+    let mut digest = [0u8; 32];
+    value
+        .to_str()
+        .ok()
+        .and_then(|v| hex::decode_to_slice(v.trim(), &mut digest).ok())
+        .ok_or_else(|| {
+            ApiError::bad_request("X-Content-Sha256 must be a hex-encoded SHA-256 digest")
+        })?;
+    Ok(Some(digest))
+}
+
+/// コンテンツ作成リクエスト。
+///
+/// 本体は次のどちらか一方で指定する。
+/// - `content_base64`: 平文。サーバー側で暗号化する
+/// - `ciphertext_base64` と `wrapped_cek_base64`: クライアント側で暗号化済みの暗号文と、
+///   クライアントがラップした CEK（エンドツーエンド暗号化）。サーバーは平文を扱わない
+#[derive(Deserialize, ToSchema)]
 pub struct CreateContentRequest {
     pub name: String,
     pub path: String,
-    pub content_base64: String,
+    pub content_base64: Option<String>,
+    pub ciphertext_base64: Option<String>,
+    pub wrapped_cek_base64: Option<String>,
     pub provider: Option<String>,
 }
 
-#[derive(Serialize)]
+/// レスポンスに含めるコンテンツの状態。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub enum ContentStatusResponse {
+    Active,
+    Deleting,
+    Deleted,
+    Archived,
+}
+
+impl From<&ContentStatus> for ContentStatusResponse {
+    fn from(status: &ContentStatus) -> Self {
+        match status {
+            ContentStatus::Active => Self::Active,
+            ContentStatus::Deleting => Self::Deleting,
+            ContentStatus::Deleted => Self::Deleted,
+            ContentStatus::Archived => Self::Archived,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
 pub struct CreateContentResponse {
     pub content_id: String,
     pub name: String,
     pub path: String,
-    pub status: String,
+    pub status: ContentStatusResponse,
 }
 
-#[derive(Deserialize)]
+/// コンテンツ更新リクエスト。本体の指定方法は `CreateContentRequest` と同じ。
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateContentRequest {
     pub name: Option<String>,
     pub content_base64: Option<String>,
+    pub ciphertext_base64: Option<String>,
+    pub wrapped_cek_base64: Option<String>,
     /// 取得元のストレージプロバイダー（省略時はデフォルト）。
     pub provider: Option<String>,
 }
 
+/// リクエストで受け取ったコンテンツ本体。
+enum ContentPayload {
+    /// サーバー側で暗号化する平文。
+    Plain(Vec<u8>),
+    /// クライアント側で暗号化済みの暗号文と、クライアントがラップした CEK。
+    ClientEncrypted {
+        ciphertext: Vec<u8>,
+        wrapped_cek: Vec<u8>,
+    },
+}
+
+/// 平文・クライアント側で暗号化済みのどちらで本体が指定されたかを判定してデコードする。
+///
+/// 両方の指定、暗号文と CEK の片方だけの指定は 400。本体の指定がなければ `None`。
+fn decode_payload(
+    content_base64: Option<&str>,
+    ciphertext_base64: Option<&str>,
+    wrapped_cek_base64: Option<&str>,
+) -> Result<Option<ContentPayload>, ApiError> {
+    let content = decode_base64_optional(content_base64, "content_base64")?;
+    let ciphertext = decode_base64_optional(ciphertext_base64, "ciphertext_base64")?;
+    let wrapped_cek = decode_base64_optional(wrapped_cek_base64, "wrapped_cek_base64")?;
+
+    match (content, ciphertext, wrapped_cek) {
+        (None, None, None) => Ok(None),
+        (Some(raw), None, None) => Ok(Some(ContentPayload::Plain(raw))),
+        (None, Some(ciphertext), Some(wrapped_cek)) => Ok(Some(ContentPayload::ClientEncrypted {
+            ciphertext,
+            wrapped_cek,
+        })),
+        (Some(_), _, _) => Err(ApiError::bad_request(
+            "content_base64 cannot be combined with ciphertext_base64 / wrapped_cek_base64",
+        )),
+        (None, _, _) => Err(ApiError::bad_request(
+            "ciphertext_base64 and wrapped_cek_base64 must be provided together",
+        )),
+    }
+}
+
 /// fetch / delete 用のクエリパラメータ。
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ProviderQuery {
     /// ストレージプロバイダー（省略時はデフォルト）。
     pub provider: Option<String>,
 }
 
+/// コンテンツ・プロバイダー API の OpenAPI 定義。
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_content,
+        list_contents,
+        get_content,
+        update_content,
+        delete_content,
+        fetch_content,
+        fetch_content_by_path,
+        pin_content,
+        unpin_content,
+        archive_content,
+        unarchive_content,
+        decrypt_with_cek,
+        reencrypt_content,
+        watch_content,
+        unwatch_content,
+        list_providers,
+        connect_provider,
+        disconnect_provider,
+    ),
+    tags(
+        (name = "contents", description = "コンテンツの作成・取得・更新・削除"),
+        (name = "providers", description = "ストレージプロバイダーの接続管理"),
+    )
+)]
+pub(super) struct ContentApi;
+
 pub fn routes() -> Router<Arc<AppState>> {
     Router::new()
-        .route("/contents", post(create_content))
+        .route("/contents", get(list_contents).post(create_content))
+        .route("/contents/by-path", get(fetch_content_by_path))
         .route(
             "/contents/{id}",
-            patch(update_content).delete(delete_content),
+            get(get_content)
+                .patch(update_content)
+                .delete(delete_content),
         )
         .route("/contents/{id}/fetch", get(fetch_content))
         .route("/contents/{id}/decrypt", post(decrypt_with_cek))
         .route("/contents/{id}/reencrypt", post(reencrypt_content))
+        .route("/contents/{id}/pin", post(pin_content))
+        .route("/contents/{id}/unpin", post(unpin_content))
+        .route("/contents/{id}/archive", post(archive_content))
+        .route("/contents/{id}/unarchive", post(unarchive_content))
+        .route(
+            "/contents/{id}/watch",
+            put(watch_content).delete(unwatch_content),
+        )
         .route("/providers", get(list_providers))
         .route("/providers/{provider}/connect", post(connect_provider))
         .route(
@@ -69,23 +234,79 @@ pub fn routes() -> Router<Arc<AppState>> {
         )
 }
 
+#[utoipa::path(
+    post,
+    path = "/contents",
+    tag = "contents",
+    params(
+        ("Idempotency-Key" = Option<String>, Header,
+            description = "再送時に同じ値を指定すると、最初に作成したコンテンツを返す。\
+                キーは呼び出し元（Authorization ヘッダまたは接続元 IP）ごとに扱う"),
+        ("X-Content-Sha256" = Option<String>, Header,
+            description = "平文コンテンツの SHA-256（hex）。一致しない場合は作成しない"),
+    ),
+    request_body = CreateContentRequest,
+    responses(
+        (status = 200, description = "作成したコンテンツ", body = CreateContentResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 413, description = "クォータ超過", body = ErrorResponse),
+        (status = 422, description = "入力値の検証に失敗、チェックサムが不一致、\
+            または冪等キーが別の内容で使用済み", body = ErrorResponse),
+    )
+)]
 async fn create_content(
     State(state): State<Arc<AppState>>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
     Json(req): Json<CreateContentRequest>,
-) -> Result<Json<CreateContentResponse>, (StatusCode, String)> {
-    let raw = decode_base64(&req.content_base64, "content_base64")?;
-
-    let provider = match req.provider {
-        Some(p) => match p.parse::<StorageProvider>() {
-            Ok(provider) => Some(provider),
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    format!("invalid storage provider: {p}"),
-                ))
+) -> Result<Json<CreateContentResponse>, ApiError> {
+    let payload = decode_payload(
+        req.content_base64.as_deref(),
+        req.ciphertext_base64.as_deref(),
+        req.wrapped_cek_base64.as_deref(),
+    )?
+    .ok_or_else(|| {
+        ApiError::bad_request("either content_base64 or ciphertext_base64 must be provided")
+    })?;
+
+    let provider = parse_provider(req.provider)?;
+
+    let idempotency_key = headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|v| {
+            v.to_str()
+                .map_err(|_| ApiError::bad_request("Idempotency-Key must be visible ASCII"))
+        })
+        .transpose()?;
+    let checksum = content_sha256(&headers)?;
+
+    let raw = match payload {
+        ContentPayload::Plain(raw) => raw,
+        ContentPayload::ClientEncrypted {
+            ciphertext,
+            wrapped_cek,
+        } => {
+            if checksum.is_some() {
+                return Err(ApiError::bad_request(
+                    "X-Content-Sha256 is not supported for client-encrypted content",
+                ));
             }
-        },
-        None => None,
+            if idempotency_key.is_some() {
+                return Err(ApiError::bad_request(
+                    "Idempotency-Key is not supported for client-encrypted content",
+                ));
+            }
+            let result = state.content_service.create_client_encrypted(
+                CreateClientEncryptedContentCommand {
+                    name: req.name,
+                    path: req.path,
+                    ciphertext,
+                    wrapped_cek,
+                    provider,
+                },
+            )?;
+            return Ok(Json(to_response(result)));
+        }
     };
 
     let cmd = CreateContentCommand {
@@ -93,12 +314,16 @@ async fn create_content(
         path: req.path,
         raw_content: raw,
         provider,
+        content_sha256: checksum,
     };
 
-    let result = state
-        .content_service
-        .create(cmd)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let result = match idempotency_key {
+        Some(key) => {
+            let caller = idempotency_caller(&headers, connect_info);
+            state.content_service.create_idempotent(&caller, key, cmd)?
+        }
+        None => state.content_service.create(cmd)?,
+    };
 
     Ok(Json(to_response(result)))
 }
@@ -109,174 +334,601 @@ fn to_response(result: CreateContentResult) -> CreateContentResponse {
         content_id: result.content_id.as_str().to_string(),
         name: metadata.name().to_string(),
         path: metadata.path().to_string(),
-        status: format!("{:?}", crate::domain::content::ContentStatus::Active),
+        status: (&result.status).into(),
     }
 }
 
+#[utoipa::path(
+    patch,
+    path = "/contents/{id}",
+    tag = "contents",
+    params(
+        ("id" = String, Path, description = "ContentId"),
+        ("X-Content-Sha256" = Option<String>, Header,
+            description = "新しい平文コンテンツの SHA-256（hex）。一致しない場合は更新しない"),
+    ),
+    request_body = UpdateContentRequest,
+    responses(
+        (status = 200, description = "更新後のコンテンツ", body = CreateContentResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツが存在しない", body = ErrorResponse),
+        (status = 409, description = "コンテンツが削除済み", body = ErrorResponse),
+        (status = 413, description = "クォータ超過", body = ErrorResponse),
+        (status = 422, description = "入力値の検証に失敗、またはチェックサムが不一致",
+            body = ErrorResponse),
+    )
+)]
 async fn update_content(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(req): Json<UpdateContentRequest>,
-) -> Result<Json<CreateContentResponse>, (StatusCode, String)> {
-    let content_id = ContentId::new(id);
+) -> Result<Json<CreateContentResponse>, ApiError> {
+    let content_id = parse_content_id(id)?;
+    let checksum = content_sha256(&headers)?;
 
-    // content_base64 が指定されている場合のみデコード
-    let raw_opt = decode_base64_optional(req.content_base64.as_deref(), "content_base64")?;
+    // 本体が指定されている場合のみデコード
+    let payload = decode_payload(
+        req.content_base64.as_deref(),
+        req.ciphertext_base64.as_deref(),
+        req.wrapped_cek_base64.as_deref(),
+    )?;
 
-    if let Some(ref bytes) = raw_opt {
+    if let Some(ContentPayload::Plain(ref bytes)) = payload {
         if bytes.is_empty() {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                "raw_content must not be empty".to_string(),
-            ));
+            return Err(ApiError::validation("raw_content must not be empty"));
         }
     }
 
-    let provider = match req.provider {
-        Some(p) => match p.parse::<StorageProvider>() {
-            Ok(provider) => Some(provider),
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    format!("invalid storage provider: {p}"),
-                ))
-            }
-        },
-        None => None,
-    };
+    let provider = parse_provider(req.provider)?;
 
-    let cmd = UpdateContentCommand {
-        content_id,
-        new_name: req.name,
-        new_raw_content: raw_opt,
-        provider,
-    };
+    if checksum.is_some() && !matches!(payload, Some(ContentPayload::Plain(_))) {
+        return Err(ApiError::bad_request(
+            "X-Content-Sha256 requires content_base64",
+        ));
+    }
 
-    let result = state
-        .content_service
-        .update(cmd)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let result =
+        match payload {
+            Some(ContentPayload::ClientEncrypted {
+                ciphertext,
+                wrapped_cek,
+            }) => state.content_service.update_client_encrypted(
+                UpdateClientEncryptedContentCommand {
+                    content_id,
+                    new_name: req.name,
+                    ciphertext,
+                    wrapped_cek,
+                    provider,
+                },
+            )?,
+            payload => state.content_service.update(UpdateContentCommand {
+                content_id,
+                new_name: req.name,
+                new_raw_content: payload.map(|payload| match payload {
+                    ContentPayload::Plain(raw) => raw,
+                    ContentPayload::ClientEncrypted { .. } => unreachable!("handled above"),
+                }),
+                provider,
+                new_raw_content_sha256: checksum,
+            })?,
+        };
 
     let metadata = &result.metadata;
     Ok(Json(CreateContentResponse {
         content_id: result.content_id.as_str().to_string(),
         name: metadata.name().to_string(),
         path: metadata.path().to_string(),
-        status: format!("{:?}", crate::domain::content::ContentStatus::Active),
+        status: (&result.status).into(),
     }))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/contents/{id}",
+    tag = "contents",
+    params(("id" = String, Path, description = "ContentId"), ProviderQuery),
+    responses(
+        (status = 204, description = "削除した"),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツが存在しない", body = ErrorResponse),
+        (status = 409, description = "コンテンツが削除済み、またはピン留めされている", body = ErrorResponse),
+    )
+)]
 async fn delete_content(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
     Query(query): Query<ProviderQuery>,
-) -> Result<StatusCode, (StatusCode, String)> {
-    let content_id = ContentId::new(id);
-
-    let provider = match query.provider {
-        Some(p) => match p.parse::<StorageProvider>() {
-            Ok(provider) => Some(provider),
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    format!("invalid storage provider: {p}"),
-                ))
-            }
-        },
-        None => None,
-    };
+) -> Result<StatusCode, ApiError> {
+    let content_id = parse_content_id(id)?;
+
+    let provider = parse_provider(query.provider)?;
 
     let cmd = DeleteContentCommand {
         content_id,
         provider,
     };
 
-    state
-        .content_service
-        .delete(cmd)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    state.content_service.delete(cmd)?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
+pub struct ContentMetadataResponse {
+    pub content_id: String,
+    pub series_id: String,
+    pub name: String,
+    pub path: String,
+    pub status: ContentStatusResponse,
+    /// マジックバイトから推定した Content-Type（記録前のコンテンツでは省略）。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// 平文コンテンツのバイトサイズ（記録前のコンテンツでは省略）。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    pub pinned: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 本体を復号せずにメタデータと状態を返す。削除済みのコンテンツも `Deleted` として返す。
+#[utoipa::path(
+    get,
+    path = "/contents/{id}",
+    tag = "contents",
+    params(("id" = String, Path, description = "ContentId"), ProviderQuery),
+    responses(
+        (status = 200, description = "コンテンツのメタデータ", body = ContentMetadataResponse,
+            headers(("ETag" = String, description = "コンテンツのバージョン"))),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツが存在しない", body = ErrorResponse),
+    )
+)]
+async fn get_content(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<ProviderQuery>,
+) -> Result<Response, ApiError> {
+    let content_id = parse_content_id(id)?;
+
+    let provider = parse_provider(query.provider)?;
+
+    let result = state.content_service.get(GetContentCommand {
+        content_id,
+        provider,
+    })?;
+
+    let version = result.version.clone();
+    let body = Json(metadata_response(result));
+    Ok(([etag_header(&version)], body).into_response())
+}
+
+fn metadata_response(result: GetContentResult) -> ContentMetadataResponse {
+    let metadata = &result.metadata;
+    ContentMetadataResponse {
+        content_id: result.content_id.as_str().to_string(),
+        series_id: result.series_id.as_str().to_string(),
+        name: metadata.name().to_string(),
+        path: metadata.path().to_string(),
+        status: (&result.status).into(),
+        content_type: metadata.content_type().map(str::to_string),
+        size: metadata.size(),
+        pinned: result.pinned,
+        created_at: metadata.created_at().to_rfc3339(),
+        updated_at: metadata.updated_at().to_rfc3339(),
+    }
+}
+
+/// 一覧用のクエリパラメータ。
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListContentsQuery {
+    /// アーカイブ済みのコンテンツも含める（既定は `false`）。
+    #[serde(default)]
+    pub include_archived: bool,
+    /// ストレージプロバイダー（省略時はデフォルト）。
+    pub provider: Option<String>,
+}
+
+/// 系列ごとの最新版のメタデータを更新日時の新しい順に返す。
+///
+/// 削除済みのコンテンツは含めない。アーカイブ済みのコンテンツは
+/// `include_archived=true` を指定した場合のみ含める。
+#[utoipa::path(
+    get,
+    path = "/contents",
+    tag = "contents",
+    params(ListContentsQuery),
+    responses(
+        (status = 200, description = "コンテンツのメタデータ一覧", body = [ContentMetadataResponse]),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+    )
+)]
+async fn list_contents(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ListContentsQuery>,
+) -> Result<Json<Vec<ContentMetadataResponse>>, ApiError> {
+    let provider = parse_provider(query.provider)?;
+    let results = state.content_service.list(ListContentsCommand {
+        filter: ContentListFilter {
+            include_archived: query.include_archived,
+            include_deleted: false,
+        },
+        provider,
+    })?;
+
+    Ok(Json(results.into_iter().map(metadata_response).collect()))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PinContentResponse {
+    pub content_id: String,
+    pub pinned: bool,
+}
+
+#[utoipa::path(
+    post,
+    path = "/contents/{id}/pin",
+    tag = "contents",
+    params(("id" = String, Path, description = "ContentId"), ProviderQuery),
+    responses(
+        (status = 200, description = "ピン留めした", body = PinContentResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツが存在しない", body = ErrorResponse),
+        (status = 409, description = "コンテンツが削除済み", body = ErrorResponse),
+    )
+)]
+async fn pin_content(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<ProviderQuery>,
+) -> Result<Json<PinContentResponse>, ApiError> {
+    let cmd = pin_command(id, query)?;
+    let result = state.content_service.pin(cmd)?;
+    Ok(Json(PinContentResponse {
+        content_id: result.content_id.as_str().to_string(),
+        pinned: result.pinned,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/contents/{id}/unpin",
+    tag = "contents",
+    params(("id" = String, Path, description = "ContentId"), ProviderQuery),
+    responses(
+        (status = 200, description = "ピン留めを解除した", body = PinContentResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツが存在しない", body = ErrorResponse),
+    )
+)]
+async fn unpin_content(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<ProviderQuery>,
+) -> Result<Json<PinContentResponse>, ApiError> {
+    let cmd = pin_command(id, query)?;
+    let result = state.content_service.unpin(cmd)?;
+    Ok(Json(PinContentResponse {
+        content_id: result.content_id.as_str().to_string(),
+        pinned: result.pinned,
+    }))
+}
+
+fn pin_command(id: String, query: ProviderQuery) -> Result<PinContentCommand, ApiError> {
+    let content_id = parse_content_id(id)?;
+    let provider = parse_provider(query.provider)?;
+    Ok(PinContentCommand {
+        content_id,
+        provider,
+    })
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ArchiveContentResponse {
+    pub content_id: String,
+    pub status: ContentStatusResponse,
+}
+
+impl From<ArchiveContentResult> for ArchiveContentResponse {
+    fn from(result: ArchiveContentResult) -> Self {
+        Self {
+            content_id: result.content_id.as_str().to_string(),
+            status: (&result.status).into(),
+        }
+    }
+}
+
+/// アーカイブ済みのコンテンツは一覧から外れるが、ID を指定すれば取得できる。
+#[utoipa::path(
+    post,
+    path = "/contents/{id}/archive",
+    tag = "contents",
+    params(("id" = String, Path, description = "ContentId"), ProviderQuery),
+    responses(
+        (status = 200, description = "アーカイブした", body = ArchiveContentResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツが存在しない", body = ErrorResponse),
+        (status = 409, description = "コンテンツが削除済み、またはアーカイブ済み", body = ErrorResponse),
+    )
+)]
+async fn archive_content(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<ProviderQuery>,
+) -> Result<Json<ArchiveContentResponse>, ApiError> {
+    let cmd = archive_command(id, query)?;
+    let result = state.content_service.archive(cmd)?;
+    Ok(Json(result.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/contents/{id}/unarchive",
+    tag = "contents",
+    params(("id" = String, Path, description = "ContentId"), ProviderQuery),
+    responses(
+        (status = 200, description = "アーカイブを解除した", body = ArchiveContentResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツが存在しない", body = ErrorResponse),
+        (status = 409, description = "コンテンツが削除済み、またはアーカイブされていない", body = ErrorResponse),
+    )
+)]
+async fn unarchive_content(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<ProviderQuery>,
+) -> Result<Json<ArchiveContentResponse>, ApiError> {
+    let cmd = archive_command(id, query)?;
+    let result = state.content_service.unarchive(cmd)?;
+    Ok(Json(result.into()))
+}
+
+fn archive_command(id: String, query: ProviderQuery) -> Result<ArchiveContentCommand, ApiError> {
+    let content_id = parse_content_id(id)?;
+    let provider = parse_provider(query.provider)?;
+    Ok(ArchiveContentCommand {
+        content_id,
+        provider,
+    })
+}
+
+#[derive(Serialize, ToSchema)]
 pub struct FetchContentResponse {
     pub content_id: String,
     pub series_id: String,
     pub name: String,
     pub path: String,
-    pub status: String,
+    pub status: ContentStatusResponse,
+    /// マジックバイトから推定した Content-Type（記録前のコンテンツでは省略）。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// 平文コンテンツのバイトサイズ（記録前のコンテンツでは省略）。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// ピン留めされているかどうか。
+    pub pinned: bool,
     /// Base64でエンコードされた復号済みコンテンツバイナリ。
+    /// クライアント側で暗号化されたコンテンツでは暗号文のまま返す。
     pub content_base64: String,
+    /// クライアント側で暗号化されたコンテンツの、クライアントがラップした CEK（Base64）。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrapped_cek_base64: Option<String>,
+}
+
+fn etag_header(version: &str) -> (header::HeaderName, HeaderValue) {
+    // version は hex のみで構成されるため、ヘッダ値として常に有効。
+    (
+        header::ETAG,
+        HeaderValue::from_str(&format!("\"{version}\"")).expect("hex ETag is a valid header"),
+    )
 }
 
+#[utoipa::path(
+    get,
+    path = "/contents/{id}/fetch",
+    tag = "contents",
+    params(
+        ("id" = String, Path, description = "ContentId"),
+        ProviderQuery,
+        ("If-None-Match" = Option<String>, Header, description = "前回取得時の ETag"),
+    ),
+    responses(
+        (status = 200, description = "復号済みコンテンツ", body = FetchContentResponse,
+            headers(("ETag" = String, description = "コンテンツのバージョン"))),
+        (status = 304, description = "If-None-Match と一致（未変更）",
+            headers(("ETag" = String, description = "コンテンツのバージョン"))),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツが存在しない、または削除済み", body = ErrorResponse),
+    )
+)]
 async fn fetch_content(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
     Query(query): Query<ProviderQuery>,
-) -> Result<Json<FetchContentResponse>, (StatusCode, String)> {
-    let content_id = ContentId::new(id);
-
-    let provider_str = match query.provider {
-        Some(p) => match p.parse::<StorageProvider>() {
-            Ok(provider) => Some(provider.as_str()),
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    format!("invalid storage provider: {p}"),
-                ))
-            }
-        },
-        None => None,
-    };
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let content_id = parse_content_id(id)?;
+    let provider = parse_provider(query.provider)?;
+    let result = state.content_service.fetch_if_none_match(
+        content_id,
+        provider.as_ref().map(StorageProvider::as_str),
+        |version| is_known_version(&headers, version),
+    )?;
+    Ok(fetch_response(result))
+}
 
-    let result = state
-        .content_service
-        .fetch(content_id, provider_str)
-        .map_err(|e| {
-            // とりあえず NotFound 系は 404、それ以外は 400 として扱う。
-            let status = match e {
-                crate::application_service::content_service::FetchError::NotFound
-                | crate::application_service::content_service::FetchError::Deleted => {
-                    StatusCode::NOT_FOUND
-                }
-                _ => StatusCode::BAD_REQUEST,
-            };
-            (status, e.to_string())
-        })?;
+/// `If-None-Match` ヘッダが `version` に一致するか（呼び出し側が既に持っている版か）。
+fn is_known_version(headers: &HeaderMap, version: &str) -> bool {
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|h| if_none_match_matches(h, version))
+}
+
+/// `fetch_content` と `fetch_content_by_path` で共通の、復号済みコンテンツの応答を組み立てる。
+fn fetch_response(result: ConditionalFetchResult) -> Response {
+    let result = match result {
+        ConditionalFetchResult::NotModified { version } => {
+            return (StatusCode::NOT_MODIFIED, [etag_header(&version)]).into_response()
+        }
+        ConditionalFetchResult::Modified(result) => *result,
+    };
 
     let metadata = &result.metadata;
-    let status = format!("{:?}", ContentStatus::Active);
+    let status = (&result.status).into();
 
     let content_base64 = BASE64_STANDARD.encode(&result.raw_content);
 
-    Ok(Json(FetchContentResponse {
+    let body = Json(FetchContentResponse {
         content_id: result.content_id.as_str().to_string(),
         series_id: result.series_id.as_str().to_string(),
         name: metadata.name().to_string(),
         path: metadata.path().to_string(),
         status,
+        content_type: metadata.content_type().map(str::to_string),
+        size: metadata.size(),
+        pinned: result.pinned,
         content_base64,
-    }))
+        wrapped_cek_base64: result
+            .wrapped_cek
+            .as_ref()
+            .map(|cek| BASE64_STANDARD.encode(cek)),
+    });
+    ([etag_header(&result.version)], body).into_response()
+}
+
+/// パス指定による取得のクエリパラメータ。
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FetchByPathQuery {
+    /// コンテンツのパス（先頭の `/` は省略可）。
+    pub path: String,
+    /// ストレージプロバイダー（省略時はデフォルト）。
+    pub provider: Option<String>,
+}
+
+/// パスにある最新の有効なコンテンツを復号して返す。
+///
+/// 同じパスに複数の系列がある場合は、更新日時が最も新しいものを返す。
+/// アーカイブ済み・削除済みのコンテンツは対象にしない。
+#[utoipa::path(
+    get,
+    path = "/contents/by-path",
+    tag = "contents",
+    params(
+        FetchByPathQuery,
+        ("If-None-Match" = Option<String>, Header, description = "前回取得時の ETag"),
+    ),
+    responses(
+        (status = 200, description = "復号済みコンテンツ", body = FetchContentResponse,
+            headers(("ETag" = String, description = "コンテンツのバージョン"))),
+        (status = 304, description = "If-None-Match と一致（未変更）",
+            headers(("ETag" = String, description = "コンテンツのバージョン"))),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "パスに有効なコンテンツが存在しない", body = ErrorResponse),
+    )
+)]
+async fn fetch_content_by_path(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<FetchByPathQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    if query.path.trim_start_matches('/').is_empty() {
+        return Err(ApiError::bad_request("path must not be empty"));
+    }
+    let provider = parse_provider(query.provider)?;
+
+    // インデックスは更新日時の新しい順に返すため、最初に見つかった有効な版を返せばよく、
+    // 候補すべてを読み込まずに済む（別のプロバイダーに保存されたコンテンツは含めない）
+    let content_ids = state
+        .path_index
+        .find_by_path(&query.path)
+        .map_err(|e| ApiError::internal("internal_error", e.to_string()))?;
+    let result = state.content_service.fetch_first_active_if_none_match(
+        content_ids,
+        provider.as_ref().map(StorageProvider::as_str),
+        |version| is_known_version(&headers, version),
+    )?;
+    Ok(fetch_response(result))
+}
+
+/// 系列 ID を監視対象にし、更新・削除時にプッシュゲートウェイへ通知させる。
+#[utoipa::path(
+    put,
+    path = "/contents/{id}/watch",
+    tag = "contents",
+    params(("id" = String, Path, description = "系列 ID")),
+    responses(
+        (status = 204, description = "監視対象にした"),
+        (status = 400, description = "ID が不正", body = ErrorResponse),
+    )
+)]
+async fn watch_content(
+    State(state): State<Arc<AppState>>,
+    Path(series_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let series_id = parse_content_id(series_id)?;
+    state.content_service.event_publisher.watch(series_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/contents/{id}/watch",
+    tag = "contents",
+    params(("id" = String, Path, description = "系列 ID")),
+    responses(
+        (status = 204, description = "監視を解除した"),
+        (status = 400, description = "ID が不正", body = ErrorResponse),
+        (status = 404, description = "監視対象ではない", body = ErrorResponse),
+    )
+)]
+async fn unwatch_content(
+    State(state): State<Arc<AppState>>,
+    Path(series_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let series_id = parse_content_id(series_id)?;
+    if state.content_service.event_publisher.unwatch(&series_id) {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found(
+            "content_not_watched",
+            "content is not watched",
+        ))
+    }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct DecryptWithCekRequest {
     pub cek_base64: String,
     pub ciphertext_base64: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct DecryptWithCekResponse {
     pub content_base64: String,
 }
 
+#[utoipa::path(
+    post,
+    path = "/contents/{id}/decrypt",
+    tag = "contents",
+    params(("id" = String, Path, description = "ContentId")),
+    request_body = DecryptWithCekRequest,
+    responses(
+        (status = 200, description = "復号済みコンテンツ", body = DecryptWithCekResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 422, description = "ContentId の不一致、または復号に失敗", body = ErrorResponse),
+    )
+)]
 async fn decrypt_with_cek(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
     Json(req): Json<DecryptWithCekRequest>,
-) -> Result<Json<DecryptWithCekResponse>, (StatusCode, String)> {
-    let content_id = ContentId::new(id);
+) -> Result<Json<DecryptWithCekResponse>, ApiError> {
+    let content_id = parse_content_id(id)?;
 
     let cek = decode_cek_base64(&req.cek_base64, "cek_base64")?;
 
@@ -284,21 +936,14 @@ async fn decrypt_with_cek(
 
     let plaintext = state
         .content_service
-        .decrypt_with_cek(content_id, cek, ciphertext)
-        .map_err(|e| {
-            let status = match e {
-                DecryptWithCekError::ContentIdMismatch { .. } => StatusCode::BAD_REQUEST,
-                DecryptWithCekError::Domain(_) => StatusCode::BAD_REQUEST,
-            };
-            (status, e.to_string())
-        })?;
+        .decrypt_with_cek(content_id, cek, ciphertext)?;
 
     let content_base64 = BASE64_STANDARD.encode(&plaintext);
 
     Ok(Json(DecryptWithCekResponse { content_base64 }))
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ReencryptContentResponse {
     pub encrypted_id: String,
     pub raw_id: String,
@@ -306,26 +951,43 @@ pub struct ReencryptContentResponse {
     pub path: String,
     pub updated_at: String,
     pub encrypted_content_base64: String,
+    /// 新しい CEK で再発行した共有相手ごとの KeyEnvelope（共有がなければ空）。
+    pub new_envelopes: Vec<KeyEnvelopeResponse>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/contents/{id}/reencrypt",
+    tag = "contents",
+    params(("id" = String, Path, description = "ContentId")),
+    responses(
+        (status = 200, description = "再暗号化したコンテンツ", body = ReencryptContentResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツが存在しない", body = ErrorResponse),
+        (status = 409, description = "コンテンツが削除済み", body = ErrorResponse),
+    )
+)]
 async fn reencrypt_content(
     State(state): State<Arc<AppState>>,
     Path(content_id_str): Path<String>,
-) -> Result<Json<ReencryptContentResponse>, (StatusCode, String)> {
-    let content_id = ContentId::new(content_id_str);
+) -> Result<Json<ReencryptContentResponse>, ApiError> {
+    let content_id = parse_content_id(content_id_str)?;
 
     // ReencryptContentCommandを構築
-    let cmd = ReencryptContentCommand { content_id };
+    let cmd = ReencryptContentCommand {
+        content_id: content_id.clone(),
+    };
 
     // ContentService::reencrypt()を呼び出し
-    let result = state.content_service.reencrypt(cmd).map_err(|e| {
-        let status = match e {
-            ReencryptError::ContentNotFound => StatusCode::NOT_FOUND,
-            ReencryptError::ContentDeleted => StatusCode::NOT_FOUND,
-            _ => StatusCode::BAD_REQUEST,
-        };
-        (status, e.to_string())
-    })?;
+    let result = state.content_service.reencrypt(cmd)?;
+
+    // 共有相手が新しい CEK で復号できるよう KeyEnvelope を再発行する
+    let new_envelopes = state
+        .share_service
+        .rewrap_shares(&content_id)?
+        .iter()
+        .map(envelope_response)
+        .collect::<Result<Vec<_>, _>>()?;
 
     // ReencryptContentResponseに変換
     let metadata = &result.metadata;
@@ -338,39 +1000,42 @@ async fn reencrypt_content(
         path: metadata.path().to_string(),
         updated_at: metadata.updated_at().to_rfc3339(),
         encrypted_content_base64,
+        new_envelopes,
     }))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct ConnectProviderRequest {
     pub access_token: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ConnectProviderResponse {
     pub provider: String,
     pub message: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ProviderListResponse {
     pub providers: Vec<String>,
     pub default_provider: String,
 }
 
 /// 接続済みのプロバイダー一覧を取得する
+#[utoipa::path(
+    get,
+    path = "/providers",
+    tag = "providers",
+    responses(
+        (status = 200, description = "接続済みのプロバイダー", body = ProviderListResponse),
+        (status = 500, description = "内部エラー", body = ErrorResponse),
+    )
+)]
 async fn list_providers(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<ProviderListResponse>, (StatusCode, String)> {
-    let providers = state
-        .content_service
-        .connected_providers()
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-    let default_provider = state
-        .content_service
-        .default_provider()
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+) -> Result<Json<ProviderListResponse>, ApiError> {
+    let providers = state.content_service.connected_providers()?;
+    let default_provider = state.content_service.default_provider()?;
 
     Ok(Json(ProviderListResponse {
         providers,
@@ -379,25 +1044,26 @@ async fn list_providers(
 }
 
 /// ストレージプロバイダーを接続する（認証トークンを登録）
+#[utoipa::path(
+    post,
+    path = "/providers/{provider}/connect",
+    tag = "providers",
+    params(("provider" = String, Path, description = "ストレージプロバイダー")),
+    request_body = ConnectProviderRequest,
+    responses(
+        (status = 200, description = "接続した", body = ConnectProviderResponse),
+        (status = 400, description = "不明なプロバイダー", body = ErrorResponse),
+        (status = 500, description = "内部エラー", body = ErrorResponse),
+    )
+)]
 async fn connect_provider(
     State(state): State<Arc<AppState>>,
     Path(provider): Path<String>,
     Json(req): Json<ConnectProviderRequest>,
-) -> Result<Json<ConnectProviderResponse>, (StatusCode, String)> {
+) -> Result<Json<ConnectProviderResponse>, ApiError> {
     state
         .content_service
-        .connect_provider(provider.clone(), req.access_token)
-        .map_err(|e| {
-            let status = match &e {
-                ContentRepositoryError::Storage(ref msg)
-                    if msg.contains("unknown storage provider") =>
-                {
-                    StatusCode::BAD_REQUEST
-                }
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            (status, e.to_string())
-        })?;
+        .connect_provider(provider.clone(), req.access_token)?;
 
     Ok(Json(ConnectProviderResponse {
         provider: provider.clone(),
@@ -406,14 +1072,21 @@ async fn connect_provider(
 }
 
 /// ストレージプロバイダーを切断する（認証トークンを削除）
+#[utoipa::path(
+    delete,
+    path = "/providers/{provider}/disconnect",
+    tag = "providers",
+    params(("provider" = String, Path, description = "ストレージプロバイダー")),
+    responses(
+        (status = 204, description = "切断した"),
+        (status = 500, description = "内部エラー", body = ErrorResponse),
+    )
+)]
 async fn disconnect_provider(
     State(state): State<Arc<AppState>>,
     Path(provider): Path<String>,
-) -> Result<StatusCode, (StatusCode, String)> {
-    state
-        .content_service
-        .disconnect_provider(provider)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+) -> Result<StatusCode, ApiError> {
+    state.content_service.disconnect_provider(provider)?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -0,0 +1,501 @@
+//! HTTP API 共通のエラーレスポンス。
+//!
+//! ハンドラは [`ApiError`] を返し、本文は `{ code, message, trace_id }` の JSON になる。
+//! アプリケーション層のエラーは `From` 実装でステータスコードとエラーコードに対応付ける。
+//! エラーは trace_id と共にログに記録する。5xx の詳細はログにだけ残し、本文には
+//! 汎用のメッセージを返す。
+
+use axum::{
+    http::{HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use rand_core::{OsRng, RngCore};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    application_service::{
+        content_service::{
+            ArchiveError, ContentRepositoryError, CreateError, DecryptWithCekError, DeleteError,
+            FetchError, PinError, ReencryptError, UpdateError,
+        },
+        rotation_service::RotationError,
+        share_service::ShareApplicationError,
+    },
+    domain::{content::ContentError, share::ShareError},
+    infrastructure::{jose::JoseError, signed_envelope::SignedEnvelopeError},
+};
+
+/// 5xx のレスポンスで内部のエラーメッセージの代わりに返すメッセージ。
+const INTERNAL_ERROR_MESSAGE: &str = "internal server error";
+
+/// エラーレスポンスの trace_id を返すヘッダ。
+const TRACE_ID_HEADER: HeaderName = HeaderName::from_static("x-trace-id");
+
+/// エラーレスポンスの本文。
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// 機械判別用のエラーコード（例: `content_not_found`）。
+    pub code: String,
+    /// 人間向けのエラーメッセージ。
+    pub message: String,
+    /// ログと突き合わせるための ID（`X-Trace-Id` ヘッダにも同じ値を返す）。
+    pub trace_id: String,
+}
+
+/// ハンドラが返すエラー。
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// リクエストの形式が不正（400）。
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "bad_request", message)
+    }
+
+    /// 形式は正しいが内容を処理できない（422）。
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "validation_failed",
+            message,
+        )
+    }
+
+    /// 対象が存在しない（404）。
+    pub fn not_found(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, code, message)
+    }
+
+    /// サーバー側の障害（500）。
+    pub fn internal(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, code, message)
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+}
+
+fn new_trace_id() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let trace_id = new_trace_id();
+        let header = HeaderValue::from_str(&trace_id).expect("hex trace id is a valid header");
+        let message = if self.status.is_server_error() {
+            tracing::error!(
+                trace_id = %trace_id,
+                status = self.status.as_u16(),
+                code = self.code,
+                message = %self.message,
+                "request failed"
+            );
+            INTERNAL_ERROR_MESSAGE.to_string()
+        } else {
+            tracing::debug!(
+                trace_id = %trace_id,
+                status = self.status.as_u16(),
+                code = self.code,
+                message = %self.message,
+                "request rejected"
+            );
+            self.message
+        };
+        let body = Json(ErrorResponse {
+            code: self.code.to_string(),
+            message,
+            trace_id,
+        });
+        (self.status, [(TRACE_ID_HEADER, header)], body).into_response()
+    }
+}
+
+impl From<ContentError> for ApiError {
+    fn from(e: ContentError) -> Self {
+        match e {
+            ContentError::AlreadyDeleted => Self::new(
+                StatusCode::CONFLICT,
+                "content_deleted",
+                "content is already deleted",
+            ),
+            ContentError::Pinned => Self::new(
+                StatusCode::CONFLICT,
+                "content_pinned",
+                "content is pinned; unpin it first",
+            ),
+            ContentError::Archived => Self::new(
+                StatusCode::CONFLICT,
+                "content_archived",
+                "content is archived; unarchive it first",
+            ),
+            ContentError::NotArchived => Self::new(
+                StatusCode::CONFLICT,
+                "content_not_archived",
+                "content is not archived",
+            ),
+            ContentError::ClientEncrypted => Self::new(
+                StatusCode::CONFLICT,
+                "content_client_encrypted",
+                "content is encrypted on the client side; use the encrypted endpoints",
+            ),
+            ContentError::DecryptionError(msg) => {
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, "decryption_failed", msg)
+            }
+            ContentError::EncryptionError(msg) => Self::internal("encryption_failed", msg),
+            ContentError::StorageError(msg) => Self::internal("storage_error", msg),
+            ContentError::Other(msg) => Self::internal("internal_error", msg),
+        }
+    }
+}
+
+impl From<ContentRepositoryError> for ApiError {
+    fn from(e: ContentRepositoryError) -> Self {
+        match e {
+            ContentRepositoryError::Storage(ref msg)
+                if msg.contains("unknown storage provider") =>
+            {
+                Self::bad_request(e.to_string())
+            }
+            ContentRepositoryError::Storage(_) => Self::internal("storage_error", e.to_string()),
+        }
+    }
+}
+
+fn content_not_found() -> ApiError {
+    ApiError::not_found("content_not_found", "content not found")
+}
+
+fn checksum_mismatch(message: String) -> ApiError {
+    ApiError::new(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "checksum_mismatch",
+        message,
+    )
+}
+
+fn quota_exceeded(message: String) -> ApiError {
+    ApiError::new(StatusCode::PAYLOAD_TOO_LARGE, "quota_exceeded", message)
+}
+
+impl From<CreateError> for ApiError {
+    fn from(e: CreateError) -> Self {
+        match e {
+            CreateError::Validation(msg) => Self::validation(msg),
+            CreateError::Domain(e) => e.into(),
+            CreateError::Repository(e) => e.into(),
+            CreateError::QuotaExceeded(e) => quota_exceeded(e.to_string()),
+            CreateError::ChecksumMismatch => checksum_mismatch(e.to_string()),
+            CreateError::IdempotencyKeyReused => Self::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "idempotency_key_reused",
+                e.to_string(),
+            ),
+            CreateError::KeyStore(_)
+            | CreateError::MissingEncryptedContent
+            | CreateError::QuotaStore(_)
+            | CreateError::IdempotencyStore(_) => Self::internal("internal_error", e.to_string()),
+        }
+    }
+}
+
+impl From<UpdateError> for ApiError {
+    fn from(e: UpdateError) -> Self {
+        match e {
+            UpdateError::Validation(msg) => Self::validation(msg),
+            UpdateError::NotFound => content_not_found(),
+            UpdateError::Domain(e) => e.into(),
+            UpdateError::Repository(e) => e.into(),
+            UpdateError::QuotaExceeded(e) => quota_exceeded(e.to_string()),
+            UpdateError::ChecksumMismatch => checksum_mismatch(e.to_string()),
+            UpdateError::KeyStore(_)
+            | UpdateError::MissingEncryptedContent
+            | UpdateError::QuotaStore(_) => Self::internal("internal_error", e.to_string()),
+        }
+    }
+}
+
+impl From<FetchError> for ApiError {
+    fn from(e: FetchError) -> Self {
+        match e {
+            FetchError::NotFound => content_not_found(),
+            FetchError::Deleted => Self::not_found("content_deleted", "content is deleted"),
+            FetchError::Domain(e) => e.into(),
+            FetchError::Repository(e) => e.into(),
+            FetchError::MissingKey => Self::internal("missing_key", e.to_string()),
+            FetchError::KeyStore(_) => Self::internal("internal_error", e.to_string()),
+        }
+    }
+}
+
+impl From<DeleteError> for ApiError {
+    fn from(e: DeleteError) -> Self {
+        match e {
+            DeleteError::NotFound => content_not_found(),
+            DeleteError::Domain(e) => e.into(),
+            DeleteError::Repository(e) => e.into(),
+            DeleteError::KeyStore(_) => Self::internal("internal_error", e.to_string()),
+        }
+    }
+}
+
+impl From<PinError> for ApiError {
+    fn from(e: PinError) -> Self {
+        match e {
+            PinError::NotFound => content_not_found(),
+            PinError::Domain(e) => e.into(),
+            PinError::Repository(e) => e.into(),
+        }
+    }
+}
+
+impl From<ArchiveError> for ApiError {
+    fn from(e: ArchiveError) -> Self {
+        match e {
+            ArchiveError::NotFound => content_not_found(),
+            ArchiveError::Domain(e) => e.into(),
+            ArchiveError::Repository(e) => e.into(),
+        }
+    }
+}
+
+impl From<JoseError> for ApiError {
+    fn from(e: JoseError) -> Self {
+        Self::internal("jwe_export_failed", e.to_string())
+    }
+}
+
+impl From<DecryptWithCekError> for ApiError {
+    fn from(e: DecryptWithCekError) -> Self {
+        match e {
+            DecryptWithCekError::ContentIdMismatch { .. } => Self::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "content_id_mismatch",
+                e.to_string(),
+            ),
+            DecryptWithCekError::Domain(e) => e.into(),
+        }
+    }
+}
+
+impl From<SignedEnvelopeError> for ApiError {
+    fn from(e: SignedEnvelopeError) -> Self {
+        match e {
+            SignedEnvelopeError::Malformed(_)
+            | SignedEnvelopeError::NonCanonical
+            | SignedEnvelopeError::UnsupportedVersion(_)
+            | SignedEnvelopeError::UnsupportedAlgorithm(_) => {
+                Self::bad_request(format!("invalid signed_envelope_base64: {e}"))
+            }
+            SignedEnvelopeError::SenderKeyMismatch
+            | SignedEnvelopeError::InvalidPublicKey(_)
+            | SignedEnvelopeError::InvalidSignature => Self::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "invalid_envelope_signature",
+                e.to_string(),
+            ),
+            SignedEnvelopeError::Encode(_) | SignedEnvelopeError::Repository(_) => {
+                Self::internal("internal_error", e.to_string())
+            }
+        }
+    }
+}
+
+impl From<ReencryptError> for ApiError {
+    fn from(e: ReencryptError) -> Self {
+        match e {
+            ReencryptError::ContentNotFound => content_not_found(),
+            ReencryptError::ContentDeleted => ContentError::AlreadyDeleted.into(),
+            ReencryptError::Domain(e) => e.into(),
+            ReencryptError::ContentRepository(e) => e.into(),
+            ReencryptError::MissingContentEncryptionKey => {
+                Self::internal("missing_key", e.to_string())
+            }
+            ReencryptError::KeyStore(_) | ReencryptError::MissingEncryptedContent => {
+                Self::internal("internal_error", e.to_string())
+            }
+        }
+    }
+}
+
+impl From<ShareApplicationError> for ApiError {
+    fn from(e: ShareApplicationError) -> Self {
+        match e {
+            ShareApplicationError::ContentNotFound => content_not_found(),
+            ShareApplicationError::ContentDeleted => ContentError::AlreadyDeleted.into(),
+            ShareApplicationError::Share(ShareError::AlreadyShared) => Self::new(
+                StatusCode::CONFLICT,
+                "already_shared",
+                "content is already shared with the recipient",
+            ),
+            ShareApplicationError::Share(ShareError::RecipientNotFound) => {
+                Self::not_found("recipient_not_found", "recipient not found")
+            }
+            ShareApplicationError::Share(ShareError::InvalidOperation(msg)) => {
+                Self::validation(msg)
+            }
+            ShareApplicationError::MissingPublicKey => {
+                Self::not_found("public_key_not_found", e.to_string())
+            }
+            ShareApplicationError::RecipientAccountNotFound(_) => {
+                Self::not_found("recipient_account_not_found", e.to_string())
+            }
+            ShareApplicationError::KeyWrapping(msg) => {
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, "key_wrapping_failed", msg)
+            }
+            ShareApplicationError::NotShared => Self::new(
+                StatusCode::FORBIDDEN,
+                "not_shared",
+                "content is not shared with the recipient",
+            ),
+            ShareApplicationError::InvalidKeyPossessionProof(msg) => Self::new(
+                StatusCode::UNAUTHORIZED,
+                "invalid_key_possession_proof",
+                msg,
+            ),
+            ShareApplicationError::ContentRepository(e) => e.into(),
+            ShareApplicationError::MissingContentEncryptionKey => {
+                Self::internal("missing_key", e.to_string())
+            }
+            ShareApplicationError::MissingEncryptedContent
+            | ShareApplicationError::ContentEncryptionKeyStore(_)
+            | ShareApplicationError::ShareRepository(_)
+            | ShareApplicationError::PublicKeyDirectory(_) => {
+                Self::internal("internal_error", e.to_string())
+            }
+        }
+    }
+}
+
+impl From<RotationError> for ApiError {
+    fn from(e: RotationError) -> Self {
+        match e {
+            RotationError::ContentRepository(e) => e.into(),
+            RotationError::Catalog(_)
+            | RotationError::ShareRepository(_)
+            | RotationError::TaskQueue(_) => Self::internal("internal_error", e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application_service::content_service::QuotaExceeded;
+
+    #[test]
+    fn application_errors_map_to_status_and_code() {
+        let cases: Vec<(ApiError, StatusCode, &str)> = vec![
+            (
+                FetchError::NotFound.into(),
+                StatusCode::NOT_FOUND,
+                "content_not_found",
+            ),
+            (
+                DeleteError::Domain(ContentError::AlreadyDeleted).into(),
+                StatusCode::CONFLICT,
+                "content_deleted",
+            ),
+            (
+                CreateError::QuotaExceeded(QuotaExceeded::ContentTooLarge { size: 2, max: 1 })
+                    .into(),
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "quota_exceeded",
+            ),
+            (
+                UpdateError::Validation("name must not be empty".into()).into(),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "validation_failed",
+            ),
+            (
+                DeleteError::Domain(ContentError::Pinned).into(),
+                StatusCode::CONFLICT,
+                "content_pinned",
+            ),
+            (
+                UpdateError::Domain(ContentError::Archived).into(),
+                StatusCode::CONFLICT,
+                "content_archived",
+            ),
+            (
+                ContentRepositoryError::Storage("unknown storage provider: x".into()).into(),
+                StatusCode::BAD_REQUEST,
+                "bad_request",
+            ),
+            (
+                ShareApplicationError::Share(ShareError::AlreadyShared).into(),
+                StatusCode::CONFLICT,
+                "already_shared",
+            ),
+            (
+                ShareApplicationError::InvalidKeyPossessionProof("expired".into()).into(),
+                StatusCode::UNAUTHORIZED,
+                "invalid_key_possession_proof",
+            ),
+        ];
+        for (error, status, code) in cases {
+            assert_eq!(error.status(), status, "{code}");
+            assert_eq!(error.code(), code);
+        }
+    }
+
+    #[tokio::test]
+    async fn response_body_carries_code_message_and_trace_id() {
+        let response =
+            ApiError::not_found("content_not_found", "content not found").into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let header_trace_id = response
+            .headers()
+            .get("x-trace-id")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "content_not_found");
+        assert_eq!(json["message"], "content not found");
+        assert_eq!(json["trace_id"], header_trace_id.as_str());
+        assert_eq!(header_trace_id.len(), 32);
+    }
+
+    #[tokio::test]
+    async fn server_errors_hide_internal_messages() {
+        let response = ApiError::from(ContentRepositoryError::Storage(
+            "disk /var/x is full".into(),
+        ))
+        .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "storage_error");
+        assert_eq!(json["message"], INTERNAL_ERROR_MESSAGE);
+        assert!(!String::from_utf8_lossy(&body).contains("/var/x"));
+    }
+}
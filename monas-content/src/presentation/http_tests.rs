@@ -0,0 +1,1466 @@
+//! ルーター全体に対する HTTP レベルのテスト。
+//!
+//! サーバーを起動せず、`tower::ServiceExt::oneshot` でリクエストを 1 件ずつ流し込む。
+//! 保存先は一時ディレクトリを base path にした `local` プロバイダー。
+
+use std::net::SocketAddr;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Method, Request, StatusCode},
+    response::Response,
+    Router,
+};
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+use rand_core::OsRng;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+use super::{
+    create_app_with_config, create_router_with_config, create_router_with_storage,
+    ContentAppOptions, NamespaceConfig, RateLimitConfig,
+};
+use crate::{
+    application_service::content_service::{ContentEncryptionKeyStore, ContentLimits},
+    application_service::rotation_service::RotationPolicy,
+    application_service::share_service::{
+        KeyEnvelopeRepository, KeyPossessionProof, PublicKeyDirectory,
+    },
+    domain::{
+        content_id::{ContentId, ContentIdGenerator},
+        share::{
+            encryption::KeyWrapping,
+            key_envelope::{KeyEnvelope, KeyWrapAlgorithm, WrappedRecipientKey},
+            EscrowAuditEvent,
+        },
+        KeyId,
+    },
+    infrastructure::{
+        content_cache::ContentCacheConfig,
+        content_id::ConfigurableContentIdGenerator,
+        key_derivation::AccountMasterKey,
+        key_envelope_repository::SledKeyEnvelopeRepository,
+        key_escrow::{KeyEscrowConfig, SledEscrowAuditLog},
+        key_store::SledContentEncryptionKeyStore,
+        key_wrapping::HpkeV1KeyWrapping,
+        metadata_encryption::MetadataKeyMode,
+        public_key_directory::InMemoryPublicKeyDirectory,
+        signed_envelope,
+        test_support::reopen,
+        ContentStorageConfig,
+    },
+};
+
+/// 一時ディレクトリに保存するルーターを作る。ディレクトリはテスト終了まで保持すること。
+fn app() -> (Router, TempDir) {
+    let dir = TempDir::new().unwrap();
+    let mut config = ContentStorageConfig::default();
+    config.filesync.local.base_path = Some(dir.path().to_string_lossy().into_owned());
+    (create_router_with_storage(&config).unwrap(), dir)
+}
+
+async fn send(router: &Router, method: Method, uri: &str, body: Option<Value>) -> Response {
+    send_with_token(router, method, uri, body, None).await
+}
+
+/// `token` を `Authorization: Bearer` で付けてリクエストを送る。
+async fn send_with_token(
+    router: &Router,
+    method: Method,
+    uri: &str,
+    body: Option<Value>,
+    token: Option<&str>,
+) -> Response {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+    router.clone().oneshot(request).await.unwrap()
+}
+
+async fn body_bytes(response: Response) -> Vec<u8> {
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+        .to_vec()
+}
+
+async fn body_json(response: Response) -> Value {
+    serde_json::from_slice(&body_bytes(response).await).unwrap()
+}
+
+async fn create(router: &Router, name: &str, raw: &[u8]) -> Value {
+    let response = send(
+        router,
+        Method::POST,
+        "/contents",
+        Some(json!({
+            "name": name,
+            "path": format!("docs/{name}"),
+            "content_base64": BASE64_STANDARD.encode(raw),
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    body_json(response).await
+}
+
+/// エラーレスポンスが `{ code, message, trace_id }` 形式で、`X-Trace-Id` と一致することを確かめる。
+async fn assert_error(response: Response, status: StatusCode, code: &str) -> Value {
+    assert_eq!(response.status(), status);
+    let trace_id = response
+        .headers()
+        .get("x-trace-id")
+        .expect("error responses carry X-Trace-Id")
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = body_json(response).await;
+    assert_eq!(body["code"], code, "unexpected error body: {body}");
+    assert!(body["message"].is_string());
+    assert_eq!(body["trace_id"], trace_id.as_str());
+    body
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn health_and_metrics_are_served() {
+    let (router, _dir) = app();
+
+    let response = send(&router, Method::GET, "/health", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_bytes(response).await, b"ok");
+
+    create(&router, "a.txt", b"hello").await;
+    let response = send(&router, Method::GET, "/metrics", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain"));
+    let text = String::from_utf8(body_bytes(response).await).unwrap();
+    assert!(
+        text.contains("monas_content_operations_total{operation=\"create\",outcome=\"success\"} 1")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn content_lifecycle_over_http() {
+    let (router, _dir) = app();
+
+    let created = create(&router, "note.txt", b"first version").await;
+    assert_eq!(created["name"], "note.txt");
+    assert_eq!(created["path"], "docs/note.txt");
+    assert_eq!(created["status"], "Active");
+    let id = created["content_id"].as_str().unwrap().to_string();
+
+    // メタデータ取得は ETag を返す
+    let response = send(&router, Method::GET, &format!("/contents/{id}"), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response.headers()[header::ETAG]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let metadata = body_json(response).await;
+    assert_eq!(metadata["content_id"], id.as_str());
+    assert_eq!(metadata["size"], 13);
+    assert_eq!(metadata["pinned"], false);
+
+    // fetch は復号済みの本文を返し、同じ ETag なら 304
+    let response = send(&router, Method::GET, &format!("/contents/{id}/fetch"), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let fetched = body_json(response).await;
+    assert_eq!(
+        fetched["content_base64"],
+        BASE64_STANDARD.encode(b"first version")
+    );
+    let request = Request::builder()
+        .uri(format!("/contents/{id}/fetch"))
+        .header(header::IF_NONE_MATCH, &etag)
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    // 更新すると新しい ContentId になり、一覧には最新版のみが現れる
+    let response = send(
+        &router,
+        Method::PATCH,
+        &format!("/contents/{id}"),
+        Some(json!({ "content_base64": BASE64_STANDARD.encode(b"second version") })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let updated = body_json(response).await;
+    let new_id = updated["content_id"].as_str().unwrap().to_string();
+    assert_ne!(new_id, id);
+
+    let response = send(&router, Method::GET, "/contents", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let listed = body_json(response).await;
+    let listed_ids: Vec<&str> = listed
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["content_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(listed_ids, vec![new_id.as_str()]);
+
+    // 削除後の fetch は 404（content_deleted）
+    let response = send(
+        &router,
+        Method::DELETE,
+        &format!("/contents/{new_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = send(
+        &router,
+        Method::GET,
+        &format!("/contents/{new_id}/fetch"),
+        None,
+    )
+    .await;
+    assert_error(response, StatusCode::NOT_FOUND, "content_deleted").await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cached_contents_reflect_updates_and_deletes() {
+    let dir = TempDir::new().unwrap();
+    let mut config = ContentStorageConfig::default();
+    config.filesync.local.base_path = Some(dir.path().to_string_lossy().into_owned());
+    config.cache = ContentCacheConfig::new(16, 1024 * 1024);
+    let router = create_router_with_storage(&config).unwrap();
+
+    let id = create(&router, "hot.txt", b"hot").await["content_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    for _ in 0..2 {
+        let response = send(&router, Method::GET, &format!("/contents/{id}/fetch"), None).await;
+        assert_eq!(
+            body_json(response).await["content_base64"],
+            BASE64_STANDARD.encode(b"hot")
+        );
+    }
+
+    let response = send(
+        &router,
+        Method::PATCH,
+        &format!("/contents/{id}"),
+        Some(json!({ "name": "renamed.txt" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let new_id = body_json(response).await["content_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = send(&router, Method::GET, &format!("/contents/{new_id}"), None).await;
+    assert_eq!(body_json(response).await["name"], "renamed.txt");
+
+    let response = send(
+        &router,
+        Method::DELETE,
+        &format!("/contents/{new_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = send(
+        &router,
+        Method::GET,
+        &format!("/contents/{new_id}/fetch"),
+        None,
+    )
+    .await;
+    assert_error(response, StatusCode::NOT_FOUND, "content_deleted").await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn derived_cek_mode_recovers_keys_without_the_key_store() {
+    let dir = TempDir::new().unwrap();
+    let config = |master_key: Option<AccountMasterKey>| {
+        let mut config = ContentStorageConfig {
+            master_key,
+            ..ContentStorageConfig::default()
+        };
+        config.filesync.local.base_path = Some(dir.path().to_string_lossy().into_owned());
+        config
+    };
+
+    for (master_key, raw) in [
+        (None, b"random cek".as_slice()),
+        (
+            Some(AccountMasterKey::new([7; 32])),
+            b"derived cek".as_slice(),
+        ),
+    ] {
+        let router = create_router_with_storage(&config(master_key.clone())).unwrap();
+        let id = create(&router, "keyed.txt", raw).await["content_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let response = send(
+            &router,
+            Method::PATCH,
+            &format!("/contents/{id}"),
+            Some(json!({ "content_base64": BASE64_STANDARD.encode([raw, b" v2"].concat()) })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let new_id = body_json(response).await["content_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        // 状態 DB（CEK ストア）を失ったサーバーでは、導出モードでのみ復号できる
+        let restarted = create_router_with_storage(&config(master_key.clone())).unwrap();
+        let response = send(
+            &restarted,
+            Method::GET,
+            &format!("/contents/{new_id}/fetch"),
+            None,
+        )
+        .await;
+        if master_key.is_some() {
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                body_json(response).await["content_base64"],
+                BASE64_STANDARD.encode([raw, b" v2"].concat())
+            );
+        } else {
+            assert_ne!(response.status(), StatusCode::OK);
+        }
+    }
+}
+
+/// `dir` 以下に保存されたファイルのいずれかが `needle` を含むかどうか。
+fn stored_files_contain(dir: &std::path::Path, needle: &[u8]) -> bool {
+    std::fs::read_dir(dir).unwrap().any(|entry| {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            stored_files_contain(&path, needle)
+        } else {
+            let bytes = std::fs::read(&path).unwrap();
+            bytes.windows(needle.len()).any(|window| window == needle)
+        }
+    })
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sealed_metadata_keeps_listing_and_path_lookup_working() {
+    for (metadata_encryption, master_key) in [
+        (None, None),
+        (Some(MetadataKeyMode::Derived), None),
+        (
+            Some(MetadataKeyMode::Derived),
+            Some(AccountMasterKey::new([7; 32])),
+        ),
+    ] {
+        let dir = TempDir::new().unwrap();
+        let mut config = ContentStorageConfig {
+            metadata_encryption,
+            master_key,
+            ..ContentStorageConfig::default()
+        };
+        config.filesync.local.base_path = Some(dir.path().to_string_lossy().into_owned());
+        let router = create_router_with_storage(&config).unwrap();
+
+        let id = create(&router, "secret-name.txt", b"first").await["content_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let response = send(
+            &router,
+            Method::PATCH,
+            &format!("/contents/{id}"),
+            Some(json!({ "content_base64": BASE64_STANDARD.encode(b"second") })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        // サーバーが CEK を持たないクライアント側の暗号化もそのまま保存できる
+        let response = send(
+            &router,
+            Method::POST,
+            "/contents",
+            Some(json!({
+                "name": "client.bin",
+                "path": "e2e/client.bin",
+                "ciphertext_base64": BASE64_STANDARD.encode(b"client ciphertext"),
+                "wrapped_cek_base64": BASE64_STANDARD.encode(b"client wrapped cek"),
+            })),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 保存先に name / path が平文で残るのは暗号化しない場合だけ
+        assert_eq!(
+            stored_files_contain(dir.path(), b"secret-name"),
+            metadata_encryption.is_none()
+        );
+
+        // 一覧・パスによる取得は暗号化の有無によらず平文の name / path を返す
+        let response = send(&router, Method::GET, "/contents", None).await;
+        let listed = body_json(response).await;
+        let mut names: Vec<&str> = listed
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|content| content["name"].as_str().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["client.bin", "secret-name.txt"]);
+        let response = send(
+            &router,
+            Method::GET,
+            "/contents/by-path?path=/docs/secret-name.txt",
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let fetched = body_json(response).await;
+        assert_eq!(fetched["name"], "secret-name.txt");
+        assert_eq!(fetched["path"], "docs/secret-name.txt");
+        assert_eq!(fetched["content_base64"], BASE64_STANDARD.encode(b"second"));
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn key_escrow_records_an_envelope_and_an_audit_event_on_create() {
+    let dir = TempDir::new().unwrap();
+    let state_dir = TempDir::new().unwrap();
+    let mut config = ContentStorageConfig {
+        data_dir: Some(state_dir.path().to_path_buf()),
+        ..ContentStorageConfig::default()
+    };
+    config.filesync.local.base_path = Some(dir.path().to_string_lossy().into_owned());
+    let (escrow_key, _) = recipient_key();
+    let escrow_public_key = escrow_key
+        .verifying_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    let escrow_key_id = KeyId::new(b"org-escrow".to_vec());
+    let (router, shutdown) = create_app_with_config(
+        &config,
+        ContentAppOptions {
+            key_escrow: Some(KeyEscrowConfig::new(
+                escrow_key_id.clone(),
+                escrow_public_key,
+            )),
+            ..ContentAppOptions::default()
+        },
+    )
+    .unwrap();
+
+    let id = create(&router, "escrowed.txt", b"escrowed").await["content_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let id = ContentId::new(id).unwrap();
+    shutdown.flush().unwrap();
+    drop((router, shutdown));
+
+    // エスクローの KeyEnvelope と監査イベントは状態 DB に残り、エスクロー秘密鍵で CEK を取り出せる
+    let db = reopen(|| sled::open(state_dir.path()));
+    let envelope = SledKeyEnvelopeRepository::with_db(db.clone())
+        .find(&id, &escrow_key_id)
+        .unwrap()
+        .expect("the CEK is escrowed on create");
+    let recovered = HpkeV1KeyWrapping
+        .unwrap_cek(
+            envelope.recipient().enc(),
+            envelope.recipient().wrapped_cek(),
+            &escrow_key.to_bytes(),
+            &id,
+        )
+        .unwrap();
+    assert_eq!(
+        SledContentEncryptionKeyStore::with_db(db.clone())
+            .load(&id)
+            .unwrap(),
+        Some(recovered)
+    );
+    assert!(matches!(
+        SledEscrowAuditLog::with_db(db).events().unwrap().as_slice(),
+        [EscrowAuditEvent::CekEscrowed { content_id, escrow_key_id: key_id, .. }]
+            if *content_id == id && *key_id == escrow_key_id
+    ));
+}
+
+/// alice・bob の名前空間を登録し、名前空間あたり `max_bytes` バイトまで保存できるルーター。
+fn namespaced_app(max_bytes: Option<u64>) -> (Router, TempDir) {
+    let dir = TempDir::new().unwrap();
+    let mut config = ContentStorageConfig::default();
+    config.filesync.local.base_path = Some(dir.path().to_string_lossy().into_owned());
+    let namespaces = NamespaceConfig::new(max_bytes)
+        .with_owner("alice".parse().unwrap(), "alice-token")
+        .with_owner("bob".parse().unwrap(), "bob-token");
+    let router = create_router_with_config(
+        &config,
+        ContentAppOptions {
+            namespaces,
+            ..ContentAppOptions::default()
+        },
+    )
+    .unwrap();
+    (router, dir)
+}
+
+#[tokio::test]
+async fn rotation_policy_is_evaluated_by_the_job_runner() {
+    let dir = TempDir::new().unwrap();
+    let mut config = ContentStorageConfig::default();
+    config.filesync.local.base_path = Some(dir.path().to_string_lossy().into_owned());
+    let policy = RotationPolicy {
+        max_key_age: Some(std::time::Duration::from_secs(3600)),
+        evaluation_interval: std::time::Duration::from_millis(20),
+        ..RotationPolicy::default()
+    };
+    let router = create_router_with_config(
+        &config,
+        ContentAppOptions {
+            rotation_policy: policy,
+            ..ContentAppOptions::default()
+        },
+    )
+    .unwrap();
+    create(&router, "a.txt", b"hello").await;
+
+    // 評価結果は key-rotation ジョブが定期的に更新する
+    let evaluated_at = |router: Router| async move {
+        let response = send(&router, Method::GET, "/rotation/compliance", None).await;
+        body_json(response).await["evaluated_at"].clone()
+    };
+    let first = evaluated_at(router.clone()).await;
+    for _ in 0..200 {
+        if evaluated_at(router.clone()).await != first {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("rotation policy was not evaluated by the job runner");
+}
+
+/// 名前空間 `ns` のオーナーとして `path` にコンテンツを作る。
+async fn create_in_namespace(router: &Router, ns: &str, path: &str, raw: &[u8]) -> Response {
+    send_with_token(
+        router,
+        Method::POST,
+        &format!("/namespaces/{ns}/contents"),
+        Some(json!({
+            "name": "a.txt",
+            "path": path,
+            "content_base64": BASE64_STANDARD.encode(raw),
+        })),
+        Some(&format!("{ns}-token")),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn namespaces_isolate_contents_and_keys() {
+    let (router, _dir) = namespaced_app(None);
+    let create_in = |ns: &'static str| {
+        let router = router.clone();
+        async move {
+            let response = create_in_namespace(&router, ns, "docs/a.txt", b"same plaintext").await;
+            assert_eq!(response.status(), StatusCode::OK);
+            body_json(response).await["content_id"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        }
+    };
+
+    // 同じ平文は同じ ContentId になるが、名前空間ごとに別々に保存される
+    let id = create_in("alice").await;
+    assert_eq!(create_in("bob").await, id);
+
+    let response = send_with_token(
+        &router,
+        Method::GET,
+        &format!("/namespaces/alice/contents/{id}/fetch"),
+        None,
+        Some("alice-token"),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        body_json(response).await["content_base64"],
+        BASE64_STANDARD.encode(b"same plaintext")
+    );
+    let response = send_with_token(
+        &router,
+        Method::GET,
+        "/namespaces/alice/contents/by-path?path=/docs/a.txt",
+        None,
+        Some("alice-token"),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 名前空間なしの API からは見えない
+    let response = send(&router, Method::GET, &format!("/contents/{id}/fetch"), None).await;
+    assert_error(response, StatusCode::NOT_FOUND, "content_not_found").await;
+    let response = send(&router, Method::GET, "/contents", None).await;
+    assert_eq!(body_json(response).await, json!([]));
+
+    // alice の削除は bob に影響しない
+    let response = send_with_token(
+        &router,
+        Method::DELETE,
+        &format!("/namespaces/alice/contents/{id}"),
+        None,
+        Some("alice-token"),
+    )
+    .await;
+    assert!(response.status().is_success());
+    let response = send_with_token(
+        &router,
+        Method::GET,
+        &format!("/namespaces/bob/contents/{id}/fetch"),
+        None,
+        Some("bob-token"),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send(&router, Method::GET, "/namespaces/Bad%20NS/contents", None).await;
+    assert_error(response, StatusCode::BAD_REQUEST, "bad_request").await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn namespaces_require_the_owner_token() {
+    let (router, _dir) = namespaced_app(None);
+
+    // 登録されていない名前空間にはルーターを作らない
+    let response = send_with_token(
+        &router,
+        Method::GET,
+        "/namespaces/carol/contents",
+        None,
+        Some("carol-token"),
+    )
+    .await;
+    assert_error(response, StatusCode::NOT_FOUND, "namespace_not_found").await;
+
+    for token in [None, Some("bob-token"), Some("wrong")] {
+        let response = send_with_token(
+            &router,
+            Method::GET,
+            "/namespaces/alice/contents",
+            None,
+            token,
+        )
+        .await;
+        assert_error(response, StatusCode::UNAUTHORIZED, "unauthorized").await;
+    }
+    let response = send_with_token(
+        &router,
+        Method::GET,
+        "/namespaces/alice/contents",
+        None,
+        Some("alice-token"),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn namespace_quota_counts_every_path() {
+    let (router, _dir) = namespaced_app(Some(10));
+
+    let response = create_in_namespace(&router, "alice", "docs/a.txt", b"123456").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    // パスの先頭セグメントが違っても同じ名前空間の使用量として数える
+    let response = create_in_namespace(&router, "alice", "notes/b.txt", b"123456").await;
+    assert_error(response, StatusCode::PAYLOAD_TOO_LARGE, "quota_exceeded").await;
+
+    // 他の名前空間の使用量には影響しない
+    let response = create_in_namespace(&router, "bob", "notes/b.txt", b"123456").await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn configured_limits_apply_without_namespaces() {
+    let dir = TempDir::new().unwrap();
+    let mut config = ContentStorageConfig::default();
+    config.filesync.local.base_path = Some(dir.path().to_string_lossy().into_owned());
+    config.limits = ContentLimits {
+        max_content_size: Some(8),
+        max_namespace_bytes: Some(10),
+    };
+    let router = create_router_with_storage(&config).unwrap();
+    let create_at = |path: &'static str, raw: &'static [u8]| {
+        let router = router.clone();
+        async move {
+            send(
+                &router,
+                Method::POST,
+                "/contents",
+                Some(json!({
+                    "name": "a.txt",
+                    "path": path,
+                    "content_base64": BASE64_STANDARD.encode(raw),
+                })),
+            )
+            .await
+        }
+    };
+
+    assert_eq!(
+        create_at("docs/a.txt", b"123456").await.status(),
+        StatusCode::OK
+    );
+    let response = create_at("notes/big.txt", b"123456789").await;
+    assert_error(response, StatusCode::PAYLOAD_TOO_LARGE, "quota_exceeded").await;
+    let response = create_at("docs/b.txt", b"123456").await;
+    assert_error(response, StatusCode::PAYLOAD_TOO_LARGE, "quota_exceeded").await;
+    // 新しい先頭フォルダを使っても、サービス全体の上限は回避できない
+    for path in ["notes/b.txt", "c.txt", "/photos/2024/b.txt"] {
+        let response = create_at(path, b"123456").await;
+        assert_error(response, StatusCode::PAYLOAD_TOO_LARGE, "quota_exceeded").await;
+    }
+    assert_eq!(
+        create_at("notes/small.txt", b"1234").await.status(),
+        StatusCode::OK
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rate_limit_rejects_excess_requests_per_client() {
+    let dir = TempDir::new().unwrap();
+    let mut config = ContentStorageConfig::default();
+    config.filesync.local.base_path = Some(dir.path().to_string_lossy().into_owned());
+    let router = create_router_with_config(
+        &config,
+        ContentAppOptions {
+            rate_limit: Some(RateLimitConfig::new(0.5, 2)),
+            ..ContentAppOptions::default()
+        },
+    )
+    .unwrap();
+
+    let list = |peer: [u8; 4], api_key: &'static str| {
+        let mut request = Request::builder()
+            .method(Method::GET)
+            .uri("/contents")
+            .header("x-api-key", api_key)
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((peer, 40000))));
+        router.clone().oneshot(request)
+    };
+
+    let peer = [192, 0, 2, 1];
+    for _ in 0..2 {
+        assert_eq!(list(peer, "a").await.unwrap().status(), StatusCode::OK);
+    }
+    let response = list(peer, "a").await.unwrap();
+    assert_eq!(response.headers()[header::RETRY_AFTER], "2");
+    assert_error(response, StatusCode::TOO_MANY_REQUESTS, "rate_limited").await;
+
+    // ヘッダを変えても同じ接続元のバケットを使う
+    let response = list(peer, "b").await.unwrap();
+    assert_error(response, StatusCode::TOO_MANY_REQUESTS, "rate_limited").await;
+    // 接続元ごとに別のバケットを使う
+    assert_eq!(
+        list([192, 0, 2, 2], "a").await.unwrap().status(),
+        StatusCode::OK
+    );
+    // ヘルスチェックは制限しない
+    let response = send(&router, Method::GET, "/health", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fetch_by_path_returns_latest_active_content() {
+    let (router, _dir) = app();
+    let created = create(&router, "a.txt", b"first").await;
+    let id = created["content_id"].as_str().unwrap().to_string();
+    create(&router, "b.txt", b"other").await;
+
+    let response = send(
+        &router,
+        Method::PATCH,
+        &format!("/contents/{id}"),
+        Some(json!({ "content_base64": BASE64_STANDARD.encode(b"second") })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 先頭の `/` の有無は区別しない
+    for path in ["/docs/a.txt", "docs/a.txt"] {
+        let response = send(
+            &router,
+            Method::GET,
+            &format!("/contents/by-path?path={path}"),
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().contains_key(header::ETAG));
+        let fetched = body_json(response).await;
+        assert_eq!(fetched["content_base64"], BASE64_STANDARD.encode(b"second"));
+        // 系列 ID は初版の ContentId
+        assert_eq!(fetched["series_id"], id.as_str());
+    }
+
+    let response = send(
+        &router,
+        Method::GET,
+        "/contents/by-path?path=/docs/missing.txt",
+        None,
+    )
+    .await;
+    assert_error(response, StatusCode::NOT_FOUND, "content_not_found").await;
+
+    let response = send(&router, Method::GET, "/contents/by-path?path=/", None).await;
+    assert_error(response, StatusCode::BAD_REQUEST, "bad_request").await;
+    let response = send(
+        &router,
+        Method::GET,
+        "/contents/by-path?path=/docs/a.txt&provider=ftp",
+        None,
+    )
+    .await;
+    assert_error(response, StatusCode::BAD_REQUEST, "bad_request").await;
+
+    // アーカイブ済みのコンテンツは返さない
+    let latest = send(
+        &router,
+        Method::GET,
+        "/contents/by-path?path=/docs/a.txt",
+        None,
+    )
+    .await;
+    let latest_id = body_json(latest).await["content_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = send(
+        &router,
+        Method::POST,
+        &format!("/contents/{latest_id}/archive"),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(
+        &router,
+        Method::GET,
+        "/contents/by-path?path=/docs/a.txt",
+        None,
+    )
+    .await;
+    assert_error(response, StatusCode::NOT_FOUND, "content_not_found").await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn client_encrypted_content_round_trips_as_ciphertext() {
+    let (router, _dir) = app();
+
+    let response = send(
+        &router,
+        Method::POST,
+        "/contents",
+        Some(json!({
+            "name": "secret.bin",
+            "path": "e2e/secret.bin",
+            "ciphertext_base64": BASE64_STANDARD.encode(b"client ciphertext"),
+            "wrapped_cek_base64": BASE64_STANDARD.encode(b"client wrapped cek"),
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let id = body_json(response).await["content_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // fetch は暗号文とラップされた CEK をそのまま返す
+    let response = send(&router, Method::GET, &format!("/contents/{id}/fetch"), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let fetched = body_json(response).await;
+    assert_eq!(
+        fetched["content_base64"],
+        BASE64_STANDARD.encode(b"client ciphertext")
+    );
+    assert_eq!(
+        fetched["wrapped_cek_base64"],
+        BASE64_STANDARD.encode(b"client wrapped cek")
+    );
+
+    // 平文での更新はできない
+    let response = send(
+        &router,
+        Method::PATCH,
+        &format!("/contents/{id}"),
+        Some(json!({ "content_base64": BASE64_STANDARD.encode(b"plain") })),
+    )
+    .await;
+    assert_error(response, StatusCode::CONFLICT, "content_client_encrypted").await;
+
+    let response = send(
+        &router,
+        Method::PATCH,
+        &format!("/contents/{id}"),
+        Some(json!({
+            "ciphertext_base64": BASE64_STANDARD.encode(b"client ciphertext v2"),
+            "wrapped_cek_base64": BASE64_STANDARD.encode(b"client wrapped cek v2"),
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 暗号文と CEK は組で、平文とは排他で指定する
+    for body in [
+        json!({ "name": "a", "path": "a", "ciphertext_base64": "AA==" }),
+        json!({
+            "name": "a",
+            "path": "a",
+            "content_base64": "AA==",
+            "ciphertext_base64": "AA==",
+            "wrapped_cek_base64": "AA==",
+        }),
+        json!({ "name": "a", "path": "a" }),
+    ] {
+        let response = send(&router, Method::POST, "/contents", Some(body)).await;
+        assert_error(response, StatusCode::BAD_REQUEST, "bad_request").await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pinned_and_archived_content_reject_conflicting_operations() {
+    let (router, _dir) = app();
+    let created = create(&router, "keep.txt", b"keep me").await;
+    let id = created["content_id"].as_str().unwrap();
+
+    let response = send(&router, Method::POST, &format!("/contents/{id}/pin"), None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(&router, Method::DELETE, &format!("/contents/{id}"), None).await;
+    assert_error(response, StatusCode::CONFLICT, "content_pinned").await;
+
+    let response = send(
+        &router,
+        Method::POST,
+        &format!("/contents/{id}/archive"),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(&router, Method::GET, "/contents", None).await;
+    assert_eq!(body_json(response).await, json!([]));
+    let response = send(
+        &router,
+        Method::GET,
+        "/contents?include_archived=true",
+        None,
+    )
+    .await;
+    assert_eq!(body_json(response).await.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn create_with_idempotency_key_returns_the_original_content() {
+    let (router, _dir) = app();
+    let create_with_key = |key: &'static str, raw: &'static [u8]| {
+        let router = router.clone();
+        async move {
+            let request = Request::builder()
+                .method(Method::POST)
+                .uri("/contents")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("idempotency-key", key)
+                .body(Body::from(
+                    json!({
+                        "name": "retry.txt",
+                        "path": "docs/retry.txt",
+                        "content_base64": BASE64_STANDARD.encode(raw),
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            router.oneshot(request).await.unwrap()
+        }
+    };
+
+    let response = create_with_key("req-1", b"payload").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let first = body_json(response).await;
+
+    // 再送は同じコンテンツを返し、新しく作成しない
+    let response = create_with_key("req-1", b"payload").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_json(response).await, first);
+
+    let response = send(&router, Method::GET, "/contents", None).await;
+    assert_eq!(body_json(response).await.as_array().unwrap().len(), 1);
+
+    // 同じキーで内容が異なるリクエストは拒否される
+    let response = create_with_key("req-1", b"other payload").await;
+    assert_error(
+        response,
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "idempotency_key_reused",
+    )
+    .await;
+
+    // 別の呼び出し元は同じキーを使っても、記録された結果を受け取らない
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri("/contents")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("idempotency-key", "req-1")
+        .body(Body::from(
+            json!({
+                "name": "retry.txt",
+                "path": "docs/retry.txt",
+                "content_base64": BASE64_STANDARD.encode(b"other payload"),
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 2], 40000))));
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(body_json(response).await["content_id"], first["content_id"]);
+
+    // キーを付けなければ記録と照合せずに作成される
+    create(&router, "retry.txt", b"third payload").await;
+    let response = send(&router, Method::GET, "/contents", None).await;
+    assert_eq!(body_json(response).await.as_array().unwrap().len(), 3);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn content_sha256_header_is_verified_on_upload() {
+    let (router, _dir) = app();
+    let upload = |method: Method, uri: String, checksum: String, raw: &'static [u8]| {
+        let router = router.clone();
+        async move {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("x-content-sha256", checksum)
+                .body(Body::from(
+                    json!({
+                        "name": "sum.txt",
+                        "path": "docs/sum.txt",
+                        "content_base64": BASE64_STANDARD.encode(raw),
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            router.oneshot(request).await.unwrap()
+        }
+    };
+    let sha256_hex = |raw: &[u8]| hex::encode(Sha256::digest(raw));
+
+    // 転送中に壊れた内容は暗号化・保存されない
+    let response = upload(
+        Method::POST,
+        "/contents".into(),
+        sha256_hex(b"original"),
+        b"corrupted",
+    )
+    .await;
+    assert_error(
+        response,
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "checksum_mismatch",
+    )
+    .await;
+    let response = send(&router, Method::GET, "/contents", None).await;
+    assert!(body_json(response).await.as_array().unwrap().is_empty());
+
+    let response = upload(
+        Method::POST,
+        "/contents".into(),
+        "not-hex".into(),
+        b"original",
+    )
+    .await;
+    assert_error(response, StatusCode::BAD_REQUEST, "bad_request").await;
+
+    let response = upload(
+        Method::POST,
+        "/contents".into(),
+        sha256_hex(b"original"),
+        b"original",
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let id = body_json(response).await["content_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = upload(
+        Method::PATCH,
+        format!("/contents/{id}"),
+        sha256_hex(b"updated"),
+        b"corrupted",
+    )
+    .await;
+    assert_error(
+        response,
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "checksum_mismatch",
+    )
+    .await;
+
+    let response = upload(
+        Method::PATCH,
+        format!("/contents/{id}"),
+        sha256_hex(b"updated"),
+        b"updated",
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn malformed_requests_are_rejected_with_error_envelope() {
+    let (router, _dir) = app();
+
+    // base64 として不正
+    let response = send(
+        &router,
+        Method::POST,
+        "/contents",
+        Some(json!({ "name": "a", "path": "a", "content_base64": "***" })),
+    )
+    .await;
+    assert_error(response, StatusCode::BAD_REQUEST, "bad_request").await;
+
+    // 形式は正しいが名前が空
+    let response = send(
+        &router,
+        Method::POST,
+        "/contents",
+        Some(json!({
+            "name": " ",
+            "path": "a",
+            "content_base64": BASE64_STANDARD.encode(b"x"),
+        })),
+    )
+    .await;
+    assert_error(
+        response,
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "validation_failed",
+    )
+    .await;
+
+    // 不明なプロバイダー
+    let response = send(
+        &router,
+        Method::POST,
+        "/contents",
+        Some(json!({
+            "name": "a",
+            "path": "a",
+            "content_base64": BASE64_STANDARD.encode(b"x"),
+            "provider": "floppy-disk",
+        })),
+    )
+    .await;
+    assert_error(response, StatusCode::BAD_REQUEST, "bad_request").await;
+
+    // ContentId として不正なパス
+    let response = send(&router, Method::GET, "/contents/not-a-content-id", None).await;
+    assert_error(response, StatusCode::BAD_REQUEST, "bad_request").await;
+
+    // 形式は正しいが存在しない ContentId
+    let missing = ConfigurableContentIdGenerator::default().generate(b"never stored");
+    let response = send(
+        &router,
+        Method::GET,
+        &format!("/contents/{}", missing.as_str()),
+        None,
+    )
+    .await;
+    assert_error(response, StatusCode::NOT_FOUND, "content_not_found").await;
+
+    // JSON として読めない本文は axum の extractor が拒否する
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/contents")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from("{not json"))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+/// 受信者の鍵ペアと、その公開鍵（SEC1 uncompressed）の base64。
+fn recipient_key() -> (SigningKey, String) {
+    let signing_key = SigningKey::random(&mut OsRng);
+    let public_key = signing_key
+        .verifying_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    (signing_key, BASE64_STANDARD.encode(public_key))
+}
+
+fn possession_proof(
+    signing_key: &SigningKey,
+    content_id: &str,
+    recipient_key_id_base64: &str,
+) -> Value {
+    let signed_at = chrono::Utc::now();
+    let recipient_key_id = KeyId::new(BASE64_STANDARD.decode(recipient_key_id_base64).unwrap());
+    let message = KeyPossessionProof::message(
+        &ContentId::new(content_id.to_string()).unwrap(),
+        &recipient_key_id,
+        signed_at,
+    );
+    let signature: Signature = signing_key.sign(&message);
+    json!({
+        "recipient_key_id_base64": recipient_key_id_base64,
+        "signed_at": signed_at.to_rfc3339(),
+        "signature_base64": BASE64_STANDARD.encode(signature.to_bytes()),
+    })
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn share_grant_and_recipient_fetch_require_key_possession() {
+    let (router, _dir) = app();
+    let created = create(&router, "shared.txt", b"for your eyes only").await;
+    let id = created["content_id"].as_str().unwrap().to_string();
+    let (recipient, recipient_public_key) = recipient_key();
+
+    let response = send(
+        &router,
+        Method::POST,
+        "/shares",
+        Some(json!({
+            "content_id": id,
+            "sender_key_id_base64": BASE64_STANDARD.encode(b"sender"),
+            "recipient_public_key_base64": recipient_public_key,
+            "permission": "read",
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let granted = body_json(response).await;
+    let recipient_key_id = granted["recipient_key_id"].as_str().unwrap().to_string();
+    // KeyEnvelope は JWE flattened JSON としても返す
+    for field in ["protected", "encrypted_key", "iv", "ciphertext"] {
+        assert!(granted["jwe"][field].is_string(), "jwe.{field} is missing");
+    }
+
+    // 同じ受信者への二重付与は 409
+    let response = send(
+        &router,
+        Method::POST,
+        "/shares",
+        Some(json!({
+            "content_id": id,
+            "sender_key_id_base64": BASE64_STANDARD.encode(b"sender"),
+            "recipient_public_key_base64": recipient_public_key,
+            "permission": "read",
+        })),
+    )
+    .await;
+    assert_error(response, StatusCode::CONFLICT, "already_shared").await;
+
+    // 別の鍵で署名した所有証明は 401
+    let (impostor, _) = recipient_key();
+    let response = send(
+        &router,
+        Method::POST,
+        &format!("/shares/{id}/fetch"),
+        Some(possession_proof(&impostor, &id, &recipient_key_id)),
+    )
+    .await;
+    assert_error(
+        response,
+        StatusCode::UNAUTHORIZED,
+        "invalid_key_possession_proof",
+    )
+    .await;
+
+    // 共有されていない KeyId は 403
+    let response = send(
+        &router,
+        Method::POST,
+        &format!("/shares/{id}/fetch"),
+        Some(possession_proof(
+            &recipient,
+            &id,
+            &BASE64_STANDARD.encode(b"someone else"),
+        )),
+    )
+    .await;
+    assert_error(response, StatusCode::FORBIDDEN, "not_shared").await;
+
+    // 正しい所有証明なら受信者向けの KeyEnvelope を返す
+    let response = send(
+        &router,
+        Method::POST,
+        &format!("/shares/{id}/fetch"),
+        Some(possession_proof(&recipient, &id, &recipient_key_id)),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let fetched = body_json(response).await;
+    assert_eq!(fetched["permissions"], json!(["read"]));
+    assert_eq!(
+        fetched["envelope"]["recipient_key_id"],
+        recipient_key_id.as_str()
+    );
+    assert!(fetched["envelope"]["wrapped_cek_base64"].is_string());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn share_recipient_must_be_a_public_key_or_a_known_account() {
+    let (router, _dir) = app();
+    let created = create(&router, "shared.txt", b"for your eyes only").await;
+    let id = created["content_id"].as_str().unwrap().to_string();
+    let (_, recipient_public_key) = recipient_key();
+
+    // 公開鍵とアカウント識別子はどちらか一方だけを指定する
+    for recipient in [
+        json!({}),
+        json!({
+            "recipient_public_key_base64": recipient_public_key,
+            "recipient_account_id": "did:key:zDnRecipient",
+        }),
+    ] {
+        let mut body = json!({
+            "content_id": id,
+            "sender_key_id_base64": BASE64_STANDARD.encode(b"sender"),
+            "permission": "read",
+        });
+        body.as_object_mut()
+            .unwrap()
+            .extend(recipient.as_object().unwrap().clone());
+        let response = send(&router, Method::POST, "/shares", Some(body)).await;
+        assert_error(response, StatusCode::BAD_REQUEST, "bad_request").await;
+    }
+
+    // monas-account が設定されていなければアカウント識別子は解決できない
+    let response = send(
+        &router,
+        Method::POST,
+        "/shares",
+        Some(json!({
+            "content_id": id,
+            "sender_key_id_base64": BASE64_STANDARD.encode(b"sender"),
+            "recipient_account_id": "did:key:zDnRecipient",
+            "permission": "read",
+        })),
+    )
+    .await;
+    assert_error(
+        response,
+        StatusCode::NOT_FOUND,
+        "recipient_account_not_found",
+    )
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn signed_envelope_is_verified_before_unwrapping() {
+    let (router, _dir) = app();
+    let created = create(&router, "signed.txt", b"signed by the sender").await;
+    let id = created["content_id"].as_str().unwrap().to_string();
+    let (sender, sender_public_key) = recipient_key();
+    let (recipient, recipient_public_key) = recipient_key();
+    let directory = InMemoryPublicKeyDirectory::default();
+    let sender_key_id =
+        directory.compute_key_id(&BASE64_STANDARD.decode(&sender_public_key).unwrap());
+
+    let response = send(
+        &router,
+        Method::POST,
+        "/shares",
+        Some(json!({
+            "content_id": id,
+            "sender_key_id_base64": BASE64_STANDARD.encode(sender_key_id.as_bytes()),
+            "recipient_public_key_base64": recipient_public_key,
+            "permission": "read",
+        })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let granted = body_json(response).await;
+    let field = |name: &str| {
+        BASE64_STANDARD
+            .decode(granted[name].as_str().unwrap())
+            .unwrap()
+    };
+    let envelope = KeyEnvelope::new(
+        ContentId::new(id.clone()).unwrap(),
+        KeyWrapAlgorithm::HpkeV1,
+        sender_key_id,
+        WrappedRecipientKey::new(
+            KeyId::new(field("recipient_key_id")),
+            field("enc_base64"),
+            field("wrapped_cek_base64"),
+        ),
+        field("ciphertext_base64"),
+    );
+    let signed = signed_envelope::encode_signed(&envelope, &sender, &directory).unwrap();
+    let request = |signed: &[u8]| {
+        json!({
+            "signed_envelope_base64": BASE64_STANDARD.encode(signed),
+            "recipient_private_key_base64": BASE64_STANDARD.encode(recipient.to_bytes()),
+        })
+    };
+
+    let response = send(
+        &router,
+        Method::POST,
+        "/shares/unwrap-signed",
+        Some(request(&signed)),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let unwrapped = body_json(response).await;
+    assert_eq!(unwrapped["content_id"], id.as_str());
+    assert_eq!(
+        unwrapped["sender_public_key_base64"],
+        sender_public_key.as_str()
+    );
+    assert!(unwrapped["cek_base64"].is_string());
+
+    // 署名を壊した封筒からは CEK を取り出さない
+    let mut corrupted = signed;
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 0x01;
+    let response = send(
+        &router,
+        Method::POST,
+        "/shares/unwrap-signed",
+        Some(request(&corrupted)),
+    )
+    .await;
+    assert_error(
+        response,
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "invalid_envelope_signature",
+    )
+    .await;
+}
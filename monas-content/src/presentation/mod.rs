@@ -6,88 +6,695 @@
 
 use std::sync::Arc;
 
-use axum::{routing::get, Router};
+use axum::{
+    extract::State, http::header, middleware, response::IntoResponse, routing::get, Router,
+};
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
-    application_service::{content_service::ContentService, share_service::ShareService},
+    application_service::{
+        content_service::{
+            ChunkingPolicy, ContentEncryptionKeyStore, ContentLimits, ContentQuota,
+            ContentRepositoryError, ContentService, CreateIdempotency, NoOpEventPublisher,
+            NoOpPushNotifier, PushNotifier, ReencryptContentCommand,
+        },
+        job_service::{JobRunner, JobRunnerHandle, JobScheduleConfig, Schedule},
+        rotation_service::{RotationPolicy, RotationPolicyService},
+        share_service::{PublicKeyDirectory, ShareService},
+    },
+    domain::{
+        content::encryption::ContentEncryptionKeyGenerator, content::provider::StorageProvider,
+        content_id::ContentId, namespace::Namespace,
+    },
     infrastructure::{
-        content_id::Sha256ContentIdGenerator,
-        encryption::{Aes256CtrContentEncryption, OsRngContentEncryptionKeyGenerator},
-        key_store::InMemoryContentEncryptionKeyStore,
+        account_directory::{AccountDirectoryConfig, HttpAccountPublicKeyDirectory},
+        catalog_listing::CatalogListingRepository,
+        cipher_suite::CipherSuite,
+        content_cache::{CachingContentRepository, ContentCache},
+        content_id::ConfigurableContentIdGenerator,
+        encryption::OsRngContentEncryptionKeyGenerator,
+        event_bus_publisher::DynEventPublisher,
+        idempotency_store::InMemoryIdempotencyStore,
+        key_derivation::{
+            AccountMasterKey, DerivedContentEncryptionKeyStore, HkdfContentEncryptionKeyGenerator,
+        },
+        key_envelope_repository::SledKeyEnvelopeRepository,
+        key_escrow::{EscrowingContentEncryptionKeyStore, KeyEscrowConfig, SledEscrowAuditLog},
+        key_possession::P256EcdsaKeyPossessionVerifier,
+        key_store::SledContentEncryptionKeyStore,
         key_wrapping::HpkeV1KeyWrapping,
+        metadata_encryption::{MetadataEncryptingRepository, MetadataKeyMode},
+        metrics::PrometheusContentMetrics,
+        namespace_usage_store::SledNamespaceUsageStore,
+        path_index::SledContentPathIndex,
         public_key_directory::InMemoryPublicKeyDirectory,
-        share_repository::InMemoryShareRepository,
-        MultiStorageRepository,
+        push_notifier::{PushGatewayConfig, PushNotifyingEventPublisher, WebhookPushNotifier},
+        rotation::{InMemoryRotationTaskQueue, SledContentCatalog},
+        share_repository::SledShareRepository,
+        ContentStorageConfig, MultiStorageRepository,
     },
 };
 
 mod base64_helpers;
 mod content;
+mod error;
+mod namespace;
+mod rate_limit;
+mod rotation;
 mod share;
+mod shutdown;
+
+#[cfg(test)]
+mod http_tests;
 
 use base64_helpers::{
     decode_base64, decode_base64_optional, decode_cek_base64, decode_key_id_base64,
 };
+pub use error::{ApiError, ErrorResponse};
+pub use namespace::{NamespaceConfig, NamespaceConfigError};
+pub use rate_limit::{RateLimitConfig, RateLimitConfigError};
+pub use shutdown::{ShutdownError, ShutdownHook};
 
-#[derive(Clone)]
-struct AppState {
-    pub content_service: Arc<
-        ContentService<
-            Sha256ContentIdGenerator,
-            MultiStorageRepository,
-            OsRngContentEncryptionKeyGenerator,
-            Aes256CtrContentEncryption,
-            InMemoryContentEncryptionKeyStore,
-        >,
+/// リクエストで受け取った文字列を ContentId としてパースする。
+///
+/// 形式が不正な場合は `ApiError::bad_request`（400）を返す。
+fn parse_content_id(value: String) -> Result<ContentId, ApiError> {
+    ContentId::new(value).map_err(|e| ApiError::bad_request(format!("invalid content_id: {e}")))
+}
+
+/// リクエストで受け取ったストレージプロバイダー名をパースする（省略時は `None`）。
+///
+/// 未知のプロバイダーの場合は `ApiError::bad_request`（400）を返す。
+fn parse_provider(value: Option<String>) -> Result<Option<StorageProvider>, ApiError> {
+    value
+        .map(|p| {
+            p.parse::<StorageProvider>()
+                .map_err(|_| ApiError::bad_request(format!("invalid storage provider: {p}")))
+        })
+        .transpose()
+}
+
+/// 実行時に Webhook / 無効を切り替えるプッシュ通知の動的型。
+type DynPushNotifier = Arc<dyn PushNotifier + Send + Sync>;
+
+/// 実行時に monas-account への問い合わせ有無を切り替える公開鍵ディレクトリの動的型。
+type DynPublicKeyDirectory = Arc<dyn PublicKeyDirectory + Send + Sync>;
+
+/// 実行時にランダム / マスター鍵からの導出を切り替える CEK ジェネレータの動的型。
+type DynContentEncryptionKeyGenerator = Arc<dyn ContentEncryptionKeyGenerator + Send + Sync>;
+
+/// 実行時に導出モードのデコレータを重ねる CEK ストアの動的型。
+type DynContentEncryptionKeyStore = Arc<dyn ContentEncryptionKeyStore + Send + Sync>;
+
+/// 各サービスが使うリポジトリ（取得したコンテンツをキャッシュし、設定に応じてメタデータを
+/// 暗号化し、コンテンツ一覧で列挙する）。
+type AppContentRepository = CatalogListingRepository<
+    MetadataEncryptingRepository<
+        CachingContentRepository<MultiStorageRepository>,
+        DynContentEncryptionKeyStore,
+        CipherSuite,
     >,
-    pub share_service: Arc<
-        ShareService<
-            InMemoryShareRepository,
-            MultiStorageRepository,
-            InMemoryContentEncryptionKeyStore,
-            InMemoryPublicKeyDirectory,
-            HpkeV1KeyWrapping,
-        >,
+    SledContentCatalog,
+>;
+
+/// コンテンツの作成・取得・更新を行うサービス。
+type AppContentService = ContentService<
+    ConfigurableContentIdGenerator,
+    AppContentRepository,
+    DynContentEncryptionKeyGenerator,
+    CipherSuite,
+    DynContentEncryptionKeyStore,
+    PushNotifyingEventPublisher<
+        (
+            (SledContentCatalog, SledContentPathIndex),
+            DynEventPublisher,
+        ),
+        DynPushNotifier,
     >,
+    PrometheusContentMetrics,
+>;
+
+/// コンテンツの共有を行うサービス。
+type AppShareService = ShareService<
+    SledShareRepository,
+    AppContentRepository,
+    DynContentEncryptionKeyStore,
+    DynPublicKeyDirectory,
+    HpkeV1KeyWrapping,
+    DynPushNotifier,
+    P256EcdsaKeyPossessionVerifier,
+>;
+
+/// CEK のローテーションポリシーを評価するサービス。
+type AppRotationService = RotationPolicyService<
+    SledContentCatalog,
+    AppContentRepository,
+    SledShareRepository,
+    InMemoryRotationTaskQueue,
+>;
+
+#[derive(Clone)]
+struct AppState {
+    pub content_service: Arc<AppContentService>,
+    pub share_service: Arc<AppShareService>,
+    pub rotation_service: Arc<AppRotationService>,
+    /// 系列ごとの最新版の ContentId（コンテンツ一覧に使う）。
+    pub catalog: SledContentCatalog,
+    /// パスごとの系列の最新版（パスによる取得に使う）。
+    pub path_index: SledContentPathIndex,
+    /// ContentService のメトリクス（`/metrics` で公開する）。
+    pub metrics: PrometheusContentMetrics,
 }
 
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses((status = 200, description = "サーバーが稼働している", body = String))
+)]
 async fn health() -> &'static str {
     "ok"
 }
 
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "health",
+    responses((
+        status = 200,
+        description = "Prometheus のテキスト形式のメトリクス",
+        body = String,
+        content_type = "text/plain"
+    ))
+)]
+async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "monas-content API"),
+    paths(health, metrics),
+    tags((name = "health", description = "ヘルスチェック・メトリクス"))
+)]
+struct ApiDoc;
+
+/// 全ルートの OpenAPI ドキュメント。
+///
+/// `/openapi.json` で配信され、`/swagger-ui` から閲覧できる。
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut doc = ApiDoc::openapi();
+    doc.merge(content::ContentApi::openapi());
+    doc.merge(share::ShareApi::openapi());
+    doc.merge(rotation::RotationApi::openapi());
+    doc
+}
+
+/// 保存先以外のルーターの設定。
+pub struct ContentAppOptions {
+    /// 共有・監視中コンテンツの更新を通知するプッシュゲートウェイ。`None` なら通知しない。
+    pub push_gateway: Option<PushGatewayConfig>,
+    /// ContentId の生成に使うハッシュアルゴリズム。
+    pub content_id_generator: ConfigurableContentIdGenerator,
+    /// CEK のローテーションポリシー。
+    pub rotation_policy: RotationPolicy,
+    /// 受信者のアカウント識別子を解決する monas-account の接続先。
+    /// `None` の場合、アカウント識別子による共有は 404 になる。
+    pub account_directory: Option<AccountDirectoryConfig>,
+    /// コンテンツの暗号化に使う暗号スイート。
+    pub cipher_suite: CipherSuite,
+    /// コンテンツ・共有 API のクライアントごとのレート制限。`None` なら制限しない。
+    pub rate_limit: Option<RateLimitConfig>,
+    /// `/namespaces/{ns}/...` で提供する名前空間。
+    pub namespaces: NamespaceConfig,
+    /// メンテナンスジョブのスケジュール。
+    pub jobs: JobScheduleConfig,
+    /// すべての名前空間のコンテンツのドメインイベントの通知先。
+    pub events: DynEventPublisher,
+    /// 新しい CEK を組織のエスクロー公開鍵にもラップして記録する設定。`None` ならエスクローしない。
+    pub key_escrow: Option<KeyEscrowConfig>,
+}
+
+impl Default for ContentAppOptions {
+    fn default() -> Self {
+        Self {
+            push_gateway: None,
+            content_id_generator: ConfigurableContentIdGenerator::default(),
+            rotation_policy: RotationPolicy::default(),
+            account_directory: None,
+            cipher_suite: CipherSuite::default(),
+            rate_limit: None,
+            namespaces: NamespaceConfig::default(),
+            jobs: JobScheduleConfig::default(),
+            events: Arc::new(NoOpEventPublisher),
+            key_escrow: None,
+        }
+    }
+}
+
+/// 既定の構成（`local` プロバイダー、認証情報は永続化しない）でルーターを作る。
 pub fn create_router() -> Router {
-    // 共通の infra 実装を生成し、ContentService / ShareService の両方で共有する。
-    let registry = Arc::new(monas_filesync::init_registry_default());
-    let content_repository = MultiStorageRepository::in_memory(registry, "local");
-
-    let cek_store = InMemoryContentEncryptionKeyStore::default();
-    let public_key_directory = InMemoryPublicKeyDirectory::default();
-    let share_repository = InMemoryShareRepository::default();
-
-    let content_service = ContentService {
-        content_id_generator: Sha256ContentIdGenerator,
-        content_repository: content_repository.clone(),
-        key_generator: OsRngContentEncryptionKeyGenerator,
-        encryptor: Aes256CtrContentEncryption,
-        cek_store: cek_store.clone(),
-    };
+    create_router_with_storage(&ContentStorageConfig::default())
+        .expect("default content storage config uses the built-in local provider")
+}
+
+/// コンテンツの保存先を `config` で指定してルーターを作る。
+///
+/// デフォルトプロバイダーが登録されていない、認証情報ファイルが読めないなどの場合は
+/// エラーを返す。
+pub fn create_router_with_storage(
+    config: &ContentStorageConfig,
+) -> Result<Router, ContentRepositoryError> {
+    create_router_with_push_gateway(config, None)
+}
+
+/// コンテンツの保存先に加え、共有・監視中コンテンツの更新を通知する
+/// プッシュゲートウェイを指定してルーターを作る。`None` の場合は通知しない。
+pub fn create_router_with_push_gateway(
+    config: &ContentStorageConfig,
+    push_gateway: Option<PushGatewayConfig>,
+) -> Result<Router, ContentRepositoryError> {
+    create_router_with_config(
+        config,
+        ContentAppOptions {
+            push_gateway,
+            ..ContentAppOptions::default()
+        },
+    )
+}
+
+/// コンテンツの保存先と [`ContentAppOptions`] を指定してルーターを作る。
+///
+/// ローテーションポリシーが有効な場合は、評価と鍵ローテーションをメンテナンスジョブ
+/// （`key-rotation`）として定期的に行う。ジョブは tokio のタスクで動くため、tokio ランタイムの
+/// 中で呼ぶこと。
+/// 取得したコンテンツは `config.cache` の上限まで（すべての名前空間の合計で）キャッシュし、
+/// `config.chunking` の閾値以上のコンテンツはチャンク分割して保存する。
+/// `config.master_key` を指定した場合は、CEK をマスター鍵と系列 ID から導出し、
+/// 導出できる CEK は鍵ストアに保存しない。
+/// `config.metadata_encryption` を指定した場合は、name / path を CEK（またはそこから導出した鍵）で
+/// 暗号化して保存する。
+/// `options.key_escrow` を指定した場合は、保存するすべての CEK をエスクロー公開鍵にラップした
+/// KeyEnvelope と監査イベントを状態 DB に記録する（記録できなければ CEK の保存を失敗させる）。
+/// レート制限はコンテンツ・共有 API にのみ適用する。クライアントは接続元 IP で識別するため、
+/// `into_make_service_with_connect_info` で起動すること
+/// （接続情報がない場合は全リクエストを 1 クライアントとして扱う）。
+///
+/// `/namespaces/{ns}/...` では、`options.namespaces` に登録した名前空間ごとに、コンテンツ・
+/// 共有・ローテーションの API を分離した保存先・CEK ストア・クォータで提供する
+/// （例: `/namespaces/alice/contents`）。リクエストにはオーナーのトークンを
+/// `Authorization: Bearer <token>` で付ける。別の名前空間のコンテンツにはアクセスできない。
+pub fn create_router_with_config(
+    config: &ContentStorageConfig,
+    options: ContentAppOptions,
+) -> Result<Router, ContentRepositoryError> {
+    create_app_with_config(config, options).map(|(router, _)| router)
+}
 
-    let share_service = ShareService {
-        share_repository,
-        content_repository,
-        cek_store,
+/// [`create_router_with_config`] と同じルーターに加え、終了前にリポジトリと鍵ストアを
+/// 永続化する [`ShutdownHook`] を返す。
+///
+/// メンテナンスジョブは登録時の既定のスケジュールで動き、`options.jobs` に設定したジョブは
+/// そのスケジュールで動く。
+///
+/// コンテンツのドメインイベント（作成・更新・削除）は、すべての名前空間のものを
+/// `options.events` に通知する。同じプロセスの他のサービスと EventBus を共有する場合は
+/// [`EventBusEventPublisher`](crate::infrastructure::event_bus_publisher::EventBusEventPublisher)
+/// を渡す。
+pub fn create_app_with_config(
+    config: &ContentStorageConfig,
+    options: ContentAppOptions,
+) -> Result<(Router, ShutdownHook), ContentRepositoryError> {
+    let ContentAppOptions {
+        push_gateway,
+        content_id_generator,
+        rotation_policy,
+        account_directory,
+        cipher_suite,
+        rate_limit,
+        namespaces,
+        jobs,
+        events,
+        key_escrow,
+    } = options;
+    // 名前空間をまたいで共有する infra 実装を生成する。
+    let content_repository = config.build()?;
+    let push_notifier: DynPushNotifier = match push_gateway {
+        Some(gateway) => Arc::new(WebhookPushNotifier::spawn(gateway)),
+        None => Arc::new(NoOpPushNotifier),
+    };
+    let public_key_directory: DynPublicKeyDirectory = match account_directory {
+        Some(account_directory) => Arc::new(HttpAccountPublicKeyDirectory::new(
+            InMemoryPublicKeyDirectory::default(),
+            account_directory,
+        )),
+        None => Arc::new(InMemoryPublicKeyDirectory::default()),
+    };
+    // CEK・共有・コンテンツ一覧・パスのインデックスは同じ DB に置き、名前空間ごとにキーで分ける
+    let state_db = config.open_state_db()?;
+    let scheduler = rotation_policy
+        .is_enabled()
+        .then(|| Arc::new(rotation::RotationScheduler::default()));
+    let mut runner = JobRunner::new(jobs);
+    if let Some(scheduler) = scheduler.clone() {
+        runner = runner.register(
+            rotation::KEY_ROTATION_JOB,
+            Schedule::Every(rotation_policy.evaluation_interval),
+            move || {
+                scheduler.run();
+                Ok(())
+            },
+        );
+    }
+    let jobs = Arc::new(runner.spawn());
+    let shared = SharedComponents {
+        content_id_generator,
+        cipher_suite,
+        rotation_policy,
+        push_notifier,
+        events,
         public_key_directory,
-        key_wrapper: HpkeV1KeyWrapping,
+        metrics: PrometheusContentMetrics::default(),
+        cache: ContentCache::new(config.cache),
+        chunking: config.chunking,
+        master_key: config.master_key.clone(),
+        metadata_encryption: config.metadata_encryption,
+        catalog: SledContentCatalog::with_db(state_db.clone()),
+        path_index: SledContentPathIndex::with_db(state_db.clone()),
+        cek_store: SledContentEncryptionKeyStore::with_db(state_db.clone()),
+        usage_store: SledNamespaceUsageStore::with_db(state_db.clone()),
+        key_escrow,
+        escrow_envelopes: SledKeyEnvelopeRepository::with_db(state_db.clone()),
+        escrow_audit_log: SledEscrowAuditLog::with_db(state_db.clone()),
+        share_repository: SledShareRepository::with_db(state_db),
+        scheduler,
+        _jobs: jobs.clone(),
+        limits: config.limits,
+        max_bytes_per_namespace: namespaces.max_bytes_per_namespace,
     };
 
-    let state = Arc::new(AppState {
-        content_service: Arc::new(content_service),
-        share_service: Arc::new(share_service),
-    });
+    let state = shared.build_state(content_repository.clone(), None);
 
-    Router::new()
-        .route("/health", get(health))
+    // 登録された名前空間ごとに保存先・CEK ストアを分けた API を、初回アクセス時に作る
+    let namespaces = Arc::new(namespace::NamespaceRouters::new(namespaces, move |ns| {
+        let state = shared.build_state(content_repository.scoped(ns), Some(ns));
+        let router = Router::new()
+            .merge(content::routes())
+            .merge(share::routes())
+            .merge(rotation::routes())
+            .with_state(state.clone());
+        (router, state)
+    }));
+    let shutdown = ShutdownHook {
+        default: state.clone(),
+        namespaces: namespaces.clone(),
+        jobs,
+    };
+
+    // 暗号化・復号を伴う API だけをレート制限の対象にする
+    let mut api = Router::new()
         .merge(content::routes())
         .merge(share::routes())
+        .with_state(state.clone())
+        .merge(namespace::routes(namespaces));
+    if let Some(config) = rate_limit {
+        api = api.layer(middleware::from_fn_with_state(
+            rate_limit::RateLimiter::new(config),
+            rate_limit::rate_limit,
+        ));
+    }
+
+    let router = Router::new()
+        .route("/health", get(health))
+        .route("/metrics", get(metrics))
+        .merge(rotation::routes())
         .with_state(state)
+        .merge(api)
+        .merge(SwaggerUi::new("/swagger-ui").url("/openapi.json", openapi()));
+    Ok((router, shutdown))
+}
+
+/// 名前空間をまたいで共有する構成要素。
+struct SharedComponents {
+    content_id_generator: ConfigurableContentIdGenerator,
+    cipher_suite: CipherSuite,
+    rotation_policy: RotationPolicy,
+    push_notifier: DynPushNotifier,
+    /// すべての名前空間のドメインイベントの通知先。
+    events: DynEventPublisher,
+    public_key_directory: DynPublicKeyDirectory,
+    /// すべての名前空間の計測結果を `/metrics` にまとめて公開する。
+    metrics: PrometheusContentMetrics,
+    /// 取得したコンテンツのキャッシュ（名前空間ごとに `scoped` で分け、上限は共有する）。
+    cache: ContentCache,
+    /// 大きなコンテンツのチャンク分割の方針。
+    chunking: ChunkingPolicy,
+    /// CEK を導出するアカウントのマスター鍵。`None` ならランダムな CEK を保存する。
+    master_key: Option<AccountMasterKey>,
+    /// name / path の暗号化に使う鍵の選択。`None` なら平文で保存する。
+    metadata_encryption: Option<MetadataKeyMode>,
+    /// 永続化したコンテンツ一覧（名前空間ごとに `scoped` で分ける）。
+    catalog: SledContentCatalog,
+    /// 永続化したパスのインデックス（名前空間ごとに `scoped` で分ける）。
+    path_index: SledContentPathIndex,
+    /// 永続化した CEK（名前空間ごとに `scoped` で分ける）。
+    cek_store: SledContentEncryptionKeyStore,
+    /// 永続化したクォータの使用量（名前空間ごとに `scoped` で分ける）。
+    usage_store: SledNamespaceUsageStore,
+    /// CEK のエスクロー先。`None` ならエスクローしない。
+    key_escrow: Option<KeyEscrowConfig>,
+    /// エスクローした KeyEnvelope（名前空間ごとに `scoped` で分ける）。
+    escrow_envelopes: SledKeyEnvelopeRepository,
+    /// エスクローの監査イベント（名前空間ごとに `scoped` で分ける）。
+    escrow_audit_log: SledEscrowAuditLog,
+    /// 永続化した共有（名前空間ごとに `scoped` で分ける）。
+    share_repository: SledShareRepository,
+    /// すべての名前空間の鍵ローテーションを行うスケジューラ。ポリシーが無効なら `None`。
+    scheduler: Option<Arc<rotation::RotationScheduler>>,
+    /// 起動したメンテナンスジョブ。ルーターが破棄されるとジョブも止まる。
+    _jobs: Arc<JobRunnerHandle>,
+    /// コンテンツのサイズと、サービス全体の合計サイズの上限。
+    limits: ContentLimits,
+    /// 名前空間ごとの合計サイズの上限（バイト）。`None` なら `limits` の上限を使う。
+    max_bytes_per_namespace: Option<u64>,
+}
+
+impl SharedComponents {
+    /// `content_repository` を保存先とするサービス一式を作る。
+    ///
+    /// `namespace` を指定した場合は、CEK・共有・コンテンツ一覧をその名前空間に分け、
+    /// 名前空間全体の合計サイズにクォータを適用する。ローテーションポリシーが有効な場合は、
+    /// 評価と鍵ローテーション（共有相手の KeyEnvelope の再発行を含む）を共有の
+    /// スケジューラに登録する。
+    fn build_state(
+        &self,
+        content_repository: MultiStorageRepository,
+        namespace: Option<&Namespace>,
+    ) -> Arc<AppState> {
+        // ドメインイベントからコンテンツ一覧・ローテーションポリシーの評価対象とパスのインデックスを記録する
+        let (cek_store, share_repository, catalog, path_index, cache, quota) = match namespace {
+            Some(namespace) => (
+                self.cek_store.scoped(namespace),
+                self.share_repository.scoped(namespace),
+                self.catalog.scoped(namespace),
+                self.path_index.scoped(namespace),
+                self.cache.scoped(namespace),
+                ContentQuota::new(
+                    ContentLimits {
+                        max_namespace_bytes: self
+                            .max_bytes_per_namespace
+                            .or(self.limits.max_namespace_bytes),
+                        ..self.limits
+                    },
+                    self.usage_store.scoped(namespace),
+                )
+                .scoped_to(namespace.as_str()),
+            ),
+            None => (
+                self.cek_store.clone(),
+                self.share_repository.clone(),
+                self.catalog.clone(),
+                self.path_index.clone(),
+                self.cache.clone(),
+                ContentQuota::new(self.limits, self.usage_store.clone()),
+            ),
+        };
+        let content_repository = CachingContentRepository::with_cache(content_repository, cache);
+        // 導出モードでは系列から導出できる CEK を保存せず、読み込み時に導出し直す
+        let (key_generator, cek_store): (
+            DynContentEncryptionKeyGenerator,
+            DynContentEncryptionKeyStore,
+        ) = match &self.master_key {
+            Some(master_key) => (
+                Arc::new(HkdfContentEncryptionKeyGenerator::new(master_key.clone())),
+                Arc::new(DerivedContentEncryptionKeyStore::new(
+                    cek_store,
+                    content_repository.clone(),
+                    master_key.clone(),
+                )),
+            ),
+            None => (
+                Arc::new(OsRngContentEncryptionKeyGenerator),
+                Arc::new(cek_store),
+            ),
+        };
+        // エスクローは最も外側に重ね、導出モードで保存を省く CEK もエスクローする
+        let cek_store: DynContentEncryptionKeyStore = match &self.key_escrow {
+            Some(config) => {
+                let (envelopes, audit_log) = match namespace {
+                    Some(namespace) => (
+                        self.escrow_envelopes.scoped(namespace),
+                        self.escrow_audit_log.scoped(namespace),
+                    ),
+                    None => (self.escrow_envelopes.clone(), self.escrow_audit_log.clone()),
+                };
+                Arc::new(EscrowingContentEncryptionKeyStore::new(
+                    cek_store,
+                    HpkeV1KeyWrapping,
+                    envelopes,
+                    audit_log,
+                    config.clone(),
+                ))
+            }
+            None => cek_store,
+        };
+        // メタデータの暗号化は CEK を引くため、導出モードが系列を引くリポジトリより外側に重ねる
+        let content_repository = MetadataEncryptingRepository::new(
+            content_repository,
+            cek_store.clone(),
+            self.cipher_suite.clone(),
+        )
+        .with_key_mode(self.metadata_encryption.unwrap_or_default())
+        .with_sealing(self.metadata_encryption.is_some());
+        // 保存先は列挙できないため、一覧はコンテンツ一覧（catalog）に記録した最新版から作る
+        let content_repository = CatalogListingRepository::new(content_repository, catalog.clone());
+
+        let content_service = ContentService {
+            content_id_generator: self.content_id_generator,
+            content_repository: content_repository.clone(),
+            key_generator,
+            encryptor: self.cipher_suite.clone(),
+            cek_store: cek_store.clone(),
+            event_publisher: PushNotifyingEventPublisher::new(
+                ((catalog.clone(), path_index.clone()), self.events.clone()),
+                self.push_notifier.clone(),
+            ),
+            quota,
+            chunking: self.chunking,
+            idempotency: CreateIdempotency::new(InMemoryIdempotencyStore::default()),
+            metrics: self.metrics.clone(),
+        };
+
+        let rotation_service = Arc::new(RotationPolicyService::new(
+            catalog.clone(),
+            content_repository.clone(),
+            share_repository.clone(),
+            InMemoryRotationTaskQueue::default(),
+            self.rotation_policy.clone(),
+        ));
+
+        let share_service = Arc::new(ShareService {
+            share_repository,
+            content_repository,
+            cek_store: cek_store.clone(),
+            public_key_directory: self.public_key_directory.clone(),
+            key_wrapper: HpkeV1KeyWrapping,
+            push_notifier: self.push_notifier.clone(),
+            possession_verifier: P256EcdsaKeyPossessionVerifier,
+        });
+
+        let content_service = Arc::new(content_service);
+        if let Some(scheduler) = &self.scheduler {
+            let content_service = content_service.clone();
+            let share_service = share_service.clone();
+            let rotation_service = rotation_service.clone();
+            scheduler.register(move || {
+                // 評価・ローテーションの失敗は次の周期で再試行する
+                let _ = rotation_service.evaluate();
+                let _ = rotation_service.rotate_queued(|content_id| {
+                    content_service
+                        .reencrypt(ReencryptContentCommand {
+                            content_id: content_id.clone(),
+                        })
+                        .map_err(|e| e.to_string())?;
+                    // 共有相手が新しい CEK で復号できるよう KeyEnvelope を再発行する
+                    share_service
+                        .rewrap_shares(content_id)
+                        .map(|_| ())
+                        .map_err(|e| e.to_string())
+                });
+            });
+        }
+
+        Arc::new(AppState {
+            content_service,
+            share_service,
+            rotation_service,
+            catalog,
+            path_index,
+            metrics: self.metrics.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn openapi_documents_every_route() {
+        let doc = openapi();
+        let documented: Vec<(&str, usize)> = doc
+            .paths
+            .paths
+            .iter()
+            .map(|(path, item)| {
+                let methods = [
+                    item.get.is_some(),
+                    item.post.is_some(),
+                    item.put.is_some(),
+                    item.patch.is_some(),
+                    item.delete.is_some(),
+                ];
+                (path.as_str(), methods.iter().filter(|m| **m).count())
+            })
+            .collect();
+
+        let expected = [
+            ("/health", 1),
+            ("/metrics", 1),
+            ("/contents", 2),
+            ("/contents/by-path", 1),
+            ("/contents/{id}", 3),
+            ("/contents/{id}/fetch", 1),
+            ("/contents/{id}/decrypt", 1),
+            ("/contents/{id}/reencrypt", 1),
+            ("/contents/{id}/pin", 1),
+            ("/contents/{id}/unpin", 1),
+            ("/contents/{id}/archive", 1),
+            ("/contents/{id}/unarchive", 1),
+            ("/contents/{id}/watch", 2),
+            ("/providers", 1),
+            ("/providers/{provider}/connect", 1),
+            ("/providers/{provider}/disconnect", 1),
+            ("/shares", 1),
+            ("/shares/unwrap", 1),
+            ("/shares/unwrap-signed", 1),
+            ("/shares/{content_id}", 1),
+            ("/shares/{content_id}/{recipient_key_id}", 1),
+            ("/shares/{content_id}/fetch", 1),
+            ("/rotation/compliance", 1),
+        ];
+        for (path, methods) in expected {
+            assert!(
+                documented.contains(&(path, methods)),
+                "{path} is not documented with {methods} operation(s)"
+            );
+        }
+        assert_eq!(documented.len(), expected.len());
+    }
 }
@@ -0,0 +1,294 @@
+//! 名前空間ごとに分離した API（`/namespaces/{ns}/...`）。
+//!
+//! [`NamespaceConfig`] に登録した名前空間だけを受け付け、`Authorization: Bearer <token>`
+//! でオーナーを認証する。名前空間ごとにリポジトリの保存先・CEK ストア・共有・一覧を
+//! 分けたルーターを初回アクセス時に作り、`/namespaces/{ns}` を取り除いたパスで転送する。
+//! 別の名前空間（名前空間なしの API を含む）のコンテンツは 404 になる。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, StatusCode, Uri},
+    response::Response,
+    routing::any,
+    Router,
+};
+use sha2::{Digest, Sha256};
+use tower::ServiceExt;
+
+use crate::domain::namespace::Namespace;
+
+use super::{ApiError, AppState};
+
+#[derive(Debug, thiserror::Error)]
+pub enum NamespaceConfigError {
+    #[error("invalid value for {name}: {value}")]
+    InvalidValue { name: &'static str, value: String },
+}
+
+/// 名前空間 API で受け付ける名前空間と、そのオーナーのトークン・クォータ。
+///
+/// 登録されていない名前空間へのリクエストは 404 になるため、作られるルーター
+/// （とそのストア）の数は登録した名前空間の数で抑えられる。
+#[derive(Clone, Default)]
+pub struct NamespaceConfig {
+    /// 名前空間ごとのオーナーのトークンの SHA-256。
+    owners: HashMap<Namespace, [u8; 32]>,
+    /// 名前空間ごとの合計サイズの上限（バイト）。`None` ならサーバーの既定の上限を使う。
+    pub max_bytes_per_namespace: Option<u64>,
+}
+
+impl NamespaceConfig {
+    pub fn new(max_bytes_per_namespace: Option<u64>) -> Self {
+        Self {
+            owners: HashMap::new(),
+            max_bytes_per_namespace,
+        }
+    }
+
+    /// 名前空間 `namespace` を、`token` を持つオーナーのものとして登録する。
+    pub fn with_owner(mut self, namespace: Namespace, token: &str) -> Self {
+        self.owners.insert(namespace, token_digest(token));
+        self
+    }
+
+    /// 環境変数から設定を読み込む。未設定なら名前空間 API はすべて 404 になる。
+    ///
+    /// - `MONAS_NAMESPACE_OWNERS`: `alice=token,bob=token` 形式の名前空間とトークンの組
+    /// - `MONAS_NAMESPACE_MAX_BYTES`: 名前空間ごとの合計サイズの上限（バイト）。未設定なら
+    ///   `MONAS_CONTENT_MAX_NAMESPACE_BYTES` の上限を使う
+    pub fn from_env() -> Result<Self, NamespaceConfigError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, NamespaceConfigError> {
+        const OWNERS: &str = "MONAS_NAMESPACE_OWNERS";
+        const MAX_BYTES: &str = "MONAS_NAMESPACE_MAX_BYTES";
+
+        let max_bytes = match lookup(MAX_BYTES).filter(|v| !v.trim().is_empty()) {
+            Some(value) => match value.trim().parse::<u64>() {
+                Ok(max) => Some(max),
+                Err(_) => {
+                    return Err(NamespaceConfigError::InvalidValue {
+                        name: MAX_BYTES,
+                        value,
+                    })
+                }
+            },
+            None => None,
+        };
+
+        let mut config = Self::new(max_bytes);
+        for entry in lookup(OWNERS)
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            // トークンはエラーメッセージに含めない
+            let invalid = || NamespaceConfigError::InvalidValue {
+                name: OWNERS,
+                value: entry.split('=').next().unwrap_or_default().to_string(),
+            };
+            let (namespace, token) = entry.split_once('=').ok_or_else(invalid)?;
+            let namespace = namespace
+                .trim()
+                .parse::<Namespace>()
+                .map_err(|_| invalid())?;
+            let token = token.trim();
+            if token.is_empty() {
+                return Err(invalid());
+            }
+            config = config.with_owner(namespace, token);
+        }
+        Ok(config)
+    }
+
+    fn is_registered(&self, namespace: &Namespace) -> bool {
+        self.owners.contains_key(namespace)
+    }
+
+    /// `token` が `namespace` のオーナーのトークンか。
+    ///
+    /// トークンそのものではなくダイジェストを比較するため、比較にかかる時間から
+    /// トークンの内容は推測できない。
+    fn authenticate(&self, namespace: &Namespace, token: &str) -> bool {
+        self.owners
+            .get(namespace)
+            .is_some_and(|digest| *digest == token_digest(token))
+    }
+}
+
+impl fmt::Debug for NamespaceConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut namespaces: Vec<&str> = self.owners.keys().map(Namespace::as_str).collect();
+        namespaces.sort_unstable();
+        f.debug_struct("NamespaceConfig")
+            .field("namespaces", &namespaces)
+            .field("max_bytes_per_namespace", &self.max_bytes_per_namespace)
+            .finish()
+    }
+}
+
+fn token_digest(token: &str) -> [u8; 32] {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+/// 名前空間ごとのルーターとサービス一式を作る関数。
+type BuildRouter = Box<dyn Fn(&Namespace) -> (Router, Arc<AppState>) + Send + Sync>;
+
+/// 作成済みの名前空間ごとのルーター。
+pub(super) struct NamespaceRouters {
+    config: NamespaceConfig,
+    build: BuildRouter,
+    routers: Mutex<HashMap<Namespace, (Router, Arc<AppState>)>>,
+}
+
+impl NamespaceRouters {
+    pub(super) fn new(
+        config: NamespaceConfig,
+        build: impl Fn(&Namespace) -> (Router, Arc<AppState>) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            config,
+            build: Box::new(build),
+            routers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `namespace` のルーターを返す。未作成なら作る。
+    fn router(&self, namespace: &Namespace) -> Router {
+        let mut routers = self.routers.lock().unwrap();
+        routers
+            .entry(namespace.clone())
+            .or_insert_with(|| (self.build)(namespace))
+            .0
+            .clone()
+    }
+
+    /// 作成済みのすべての名前空間のサービス一式。
+    pub(super) fn states(&self) -> Vec<Arc<AppState>> {
+        let routers = self.routers.lock().unwrap();
+        routers.values().map(|(_, state)| state.clone()).collect()
+    }
+}
+
+pub(super) fn routes(namespaces: Arc<NamespaceRouters>) -> Router {
+    Router::new()
+        .route("/namespaces/{ns}/{*rest}", any(forward))
+        .with_state(namespaces)
+}
+
+/// `/namespaces/{ns}/rest?query` を、名前空間 `ns` のルーターへ `/rest?query` として渡す。
+///
+/// 登録されていない名前空間は 404（`namespace_not_found`）、オーナーのトークンが
+/// ない・一致しない場合は 401（`unauthorized`）を返す。
+async fn forward(
+    State(namespaces): State<Arc<NamespaceRouters>>,
+    request: Request,
+) -> Result<Response, ApiError> {
+    // パスパラメータはデコード済みになるため、元の URI から切り出す
+    let path = request.uri().path();
+    let (namespace, rest) = path
+        .strip_prefix("/namespaces/")
+        .and_then(|scoped| scoped.split_once('/'))
+        .ok_or_else(|| ApiError::bad_request("missing namespace"))?;
+    let namespace = namespace
+        .parse::<Namespace>()
+        .map_err(|e| ApiError::bad_request(format!("invalid namespace: {e}")))?;
+
+    if !namespaces.config.is_registered(&namespace) {
+        return Err(ApiError::not_found(
+            "namespace_not_found",
+            format!("namespace not found: {namespace}"),
+        ));
+    }
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if !namespaces.config.authenticate(&namespace, token) {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "unauthorized",
+            "missing or invalid namespace owner token",
+        ));
+    }
+
+    let path_and_query = match request.uri().query() {
+        Some(query) => format!("/{rest}?{query}"),
+        None => format!("/{rest}"),
+    };
+    let uri = path_and_query
+        .parse::<Uri>()
+        .map_err(|e| ApiError::bad_request(format!("invalid path: {e}")))?;
+
+    // 拡張には外側のルーターが取り出したパスパラメータが残っているため、
+    // 接続情報だけを引き継いだリクエストを作り直す
+    let (parts, body) = request.into_parts();
+    let mut forwarded = Request::new(body);
+    *forwarded.method_mut() = parts.method;
+    *forwarded.uri_mut() = uri;
+    *forwarded.version_mut() = parts.version;
+    *forwarded.headers_mut() = parts.headers;
+    if let Some(connect_info) = parts.extensions.get::<ConnectInfo<SocketAddr>>() {
+        forwarded.extensions_mut().insert(*connect_info);
+    }
+
+    let router = namespaces.router(&namespace);
+    match router.oneshot(forwarded).await {
+        Ok(response) => Ok(response),
+        Err(infallible) => match infallible {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn config_reads_owners_and_quota_from_env() {
+        let config = NamespaceConfig::from_lookup(lookup(&[
+            ("MONAS_NAMESPACE_OWNERS", "alice=secret-a, bob=secret-b"),
+            ("MONAS_NAMESPACE_MAX_BYTES", "1024"),
+        ]))
+        .unwrap();
+        let alice = Namespace::new("alice").unwrap();
+
+        assert_eq!(config.max_bytes_per_namespace, Some(1024));
+        assert!(config.authenticate(&alice, "secret-a"));
+        assert!(!config.authenticate(&alice, "secret-b"));
+        assert!(!config.is_registered(&Namespace::new("carol").unwrap()));
+        assert!(!format!("{config:?}").contains("secret"));
+
+        let empty = NamespaceConfig::from_lookup(lookup(&[])).unwrap();
+        assert!(!empty.is_registered(&alice));
+        assert_eq!(empty.max_bytes_per_namespace, None);
+    }
+
+    #[test]
+    fn config_rejects_invalid_values_without_leaking_tokens() {
+        for owners in ["alice", "Alice=secret", "alice="] {
+            let err = NamespaceConfig::from_lookup(lookup(&[("MONAS_NAMESPACE_OWNERS", owners)]))
+                .unwrap_err();
+            assert!(!err.to_string().contains("secret"));
+        }
+        assert!(
+            NamespaceConfig::from_lookup(lookup(&[("MONAS_NAMESPACE_MAX_BYTES", "-1")])).is_err()
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! クライアントごとのトークンバケットによるレート制限。
+//!
+//! クライアントは接続元 IP で識別する。`X-Api-Key` などリクエストごとに自由に変えられる
+//! ヘッダでは識別しない（ヘッダを変えるだけで制限を回避できてしまうため）。
+//! 上限を超えたリクエストは 429（`rate_limited`）と `Retry-After` ヘッダで拒否する。
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use super::ApiError;
+
+/// バケット数がこれを超えたら、満タンに戻ったバケットを捨てる。
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimitConfigError {
+    #[error("invalid value for {name}: {value}")]
+    InvalidValue { name: &'static str, value: String },
+}
+
+/// レート制限の設定。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitConfig {
+    /// 1 秒あたりに補充するトークン数（定常的に許可するリクエスト数）。
+    pub requests_per_second: f64,
+    /// バケットの容量（連続して許可するリクエスト数）。
+    pub burst: u32,
+}
+
+impl RateLimitConfig {
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        Self {
+            requests_per_second,
+            burst,
+        }
+    }
+
+    /// 環境変数から設定を読み込む。`MONAS_CONTENT_RATE_LIMIT_RPS` が未設定なら `None`（無制限）。
+    ///
+    /// - `MONAS_CONTENT_RATE_LIMIT_RPS`: 1 秒あたりのリクエスト数（正の数）
+    /// - `MONAS_CONTENT_RATE_LIMIT_BURST`: バケットの容量（省略時は RPS の切り上げ）
+    pub fn from_env() -> Result<Option<Self>, RateLimitConfigError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, RateLimitConfigError> {
+        const RPS: &str = "MONAS_CONTENT_RATE_LIMIT_RPS";
+        const BURST: &str = "MONAS_CONTENT_RATE_LIMIT_BURST";
+
+        let Some(value) = lookup(RPS).filter(|v| !v.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let requests_per_second = match value.trim().parse::<f64>() {
+            Ok(rps) if rps.is_finite() && rps > 0.0 => rps,
+            _ => return Err(RateLimitConfigError::InvalidValue { name: RPS, value }),
+        };
+        let burst = match lookup(BURST).filter(|v| !v.trim().is_empty()) {
+            Some(value) => match value.trim().parse::<u32>() {
+                Ok(burst) if burst > 0 => burst,
+                _ => return Err(RateLimitConfigError::InvalidValue { name: BURST, value }),
+            },
+            None => requests_per_second.ceil() as u32,
+        };
+        Ok(Some(Self::new(requests_per_second, burst)))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// クライアントごとのトークンバケットを保持するレート制限器。
+#[derive(Clone)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// `client` のトークンを 1 つ消費する。不足している場合は次に許可されるまでの時間を返す。
+    pub fn check(&self, client: &str, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(self.config.burst);
+        let rate = self.config.requests_per_second;
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(client) {
+            // 満タンまで補充されたバケットは、新しく作り直しても同じ状態になる
+            buckets.retain(|_, bucket| {
+                let elapsed = now.saturating_duration_since(bucket.refilled_at);
+                bucket.tokens + elapsed.as_secs_f64() * rate < capacity
+            });
+        }
+
+        let bucket = buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            refilled_at: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+
+/// リクエストの送信元を識別するキー。
+fn client_key(request: &Request) -> String {
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        // 接続情報のないルーター（テストなど）では全リクエストを 1 クライアントとして扱う
+        None => "ip:unknown".to_string(),
+    }
+}
+
+/// `axum::middleware::from_fn_with_state` に渡すミドルウェア。
+pub async fn rate_limit(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match limiter.check(&client_key(&request), Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            // Retry-After は秒単位の整数なので切り上げる
+            let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            let mut response = ApiError::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                "rate_limited",
+                "too many requests",
+            )
+            .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_burst_then_refills_per_client() {
+        let limiter = RateLimiter::new(RateLimitConfig::new(2.0, 3));
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check("a", start).is_ok());
+        }
+        let retry_after = limiter.check("a", start).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(500));
+        // 別のクライアントは影響を受けない
+        assert!(limiter.check("b", start).is_ok());
+
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.check("a", later).is_ok());
+        assert!(limiter.check("a", later).is_err());
+    }
+
+    #[test]
+    fn config_from_lookup_is_disabled_by_default_and_rejects_garbage() {
+        assert_eq!(RateLimitConfig::from_lookup(|_| None).unwrap(), None);
+
+        let config = RateLimitConfig::from_lookup(|key| {
+            (key == "MONAS_CONTENT_RATE_LIMIT_RPS").then(|| "2.5".to_string())
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(config, RateLimitConfig::new(2.5, 3));
+
+        let config = RateLimitConfig::from_lookup(|key| match key {
+            "MONAS_CONTENT_RATE_LIMIT_RPS" => Some("10".into()),
+            "MONAS_CONTENT_RATE_LIMIT_BURST" => Some("50".into()),
+            _ => None,
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(config, RateLimitConfig::new(10.0, 50));
+
+        for (key, value) in [
+            ("MONAS_CONTENT_RATE_LIMIT_RPS", "0"),
+            ("MONAS_CONTENT_RATE_LIMIT_RPS", "fast"),
+        ] {
+            let err = RateLimitConfig::from_lookup(|k| (k == key).then(|| value.to_string()));
+            assert!(matches!(
+                err,
+                Err(RateLimitConfigError::InvalidValue { name, .. }) if name == key
+            ));
+        }
+        let err = RateLimitConfig::from_lookup(|key| match key {
+            "MONAS_CONTENT_RATE_LIMIT_RPS" => Some("1".into()),
+            "MONAS_CONTENT_RATE_LIMIT_BURST" => Some("0".into()),
+            _ => None,
+        });
+        assert!(matches!(
+            err,
+            Err(RateLimitConfigError::InvalidValue {
+                name: "MONAS_CONTENT_RATE_LIMIT_BURST",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    extract::{Json, State},
+    routing::get,
+    Router,
+};
+use serde::Serialize;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::application_service::rotation_service::{
+    ComplianceReport, ContentCompliance, PolicyViolation,
+};
+
+use super::{ApiError, AppState, ErrorResponse};
+
+#[derive(Serialize, ToSchema)]
+pub struct PolicyViolationResponse {
+    /// `key_too_old` / `too_many_bytes` / `too_many_shares`
+    pub kind: String,
+    /// 現在の値（秒・バイト数・共有数）。
+    pub value: u64,
+    /// ポリシーの上限。
+    pub limit: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ContentComplianceResponse {
+    pub content_id: String,
+    pub violations: Vec<PolicyViolationResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ComplianceReportResponse {
+    pub evaluated_at: String,
+    pub policy_enabled: bool,
+    pub evaluated: usize,
+    pub compliant: usize,
+    pub non_compliant: Vec<ContentComplianceResponse>,
+    /// 処理待ちの鍵ローテーション要求の数。
+    pub pending_rotations: usize,
+}
+
+/// 鍵ローテーションポリシー API の OpenAPI 定義。
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_compliance),
+    tags((name = "rotation", description = "CEK ローテーションポリシーの準拠状況"))
+)]
+pub(super) struct RotationApi;
+
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/rotation/compliance", get(get_compliance))
+}
+
+#[utoipa::path(
+    get,
+    path = "/rotation/compliance",
+    tag = "rotation",
+    responses(
+        (status = 200, description = "直近のポリシー評価の結果", body = ComplianceReportResponse),
+        (status = 500, description = "評価に失敗", body = ErrorResponse),
+    )
+)]
+async fn get_compliance(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<ComplianceReportResponse>, ApiError> {
+    // 定期評価がまだ走っていなければその場で評価する
+    let report = match state.rotation_service.last_report() {
+        Some(report) => report,
+        None => state.rotation_service.evaluate()?,
+    };
+    let pending_rotations = state.rotation_service.pending_tasks()?;
+
+    Ok(Json(report_response(report, pending_rotations)))
+}
+
+fn report_response(report: ComplianceReport, pending_rotations: usize) -> ComplianceReportResponse {
+    ComplianceReportResponse {
+        evaluated_at: report.evaluated_at.to_rfc3339(),
+        policy_enabled: report.policy_enabled,
+        evaluated: report.evaluated,
+        compliant: report.compliant(),
+        non_compliant: report
+            .non_compliant
+            .into_iter()
+            .map(content_compliance_response)
+            .collect(),
+        pending_rotations,
+    }
+}
+
+fn content_compliance_response(compliance: ContentCompliance) -> ContentComplianceResponse {
+    ContentComplianceResponse {
+        content_id: compliance.content_id.as_str().to_string(),
+        violations: compliance
+            .violations
+            .into_iter()
+            .map(violation_response)
+            .collect(),
+    }
+}
+
+fn violation_response(violation: PolicyViolation) -> PolicyViolationResponse {
+    let (kind, value, limit) = match violation {
+        PolicyViolation::KeyTooOld { age_secs, max_secs } => ("key_too_old", age_secs, max_secs),
+        PolicyViolation::TooManyBytes { bytes, max } => ("too_many_bytes", bytes, max),
+        PolicyViolation::TooManyShares { shares, max } => {
+            ("too_many_shares", shares as u64, max as u64)
+        }
+    };
+    PolicyViolationResponse {
+        kind: kind.to_string(),
+        value,
+        limit,
+    }
+}
+
+/// 定期的に実行する評価・鍵ローテーションの処理。
+type RotationJob = Box<dyn FnMut() + Send>;
+
+/// 評価と鍵ローテーションを行うメンテナンスジョブの名前（`MONAS_JOB_SCHEDULES` で使う）。
+pub(super) const KEY_ROTATION_JOB: &str = "key-rotation";
+
+/// すべての名前空間の評価と鍵ローテーションを順に行うスケジューラ。
+///
+/// 各名前空間の処理を [`register`](Self::register) で登録し、ジョブランナーの
+/// [`KEY_ROTATION_JOB`] ジョブから [`run`](Self::run) でまとめて実行する。
+#[derive(Default)]
+pub(super) struct RotationScheduler {
+    jobs: Mutex<Vec<RotationJob>>,
+}
+
+impl RotationScheduler {
+    pub(super) fn register(&self, job: impl FnMut() + Send + 'static) {
+        self.jobs.lock().unwrap().push(Box::new(job));
+    }
+
+    /// 登録済みの処理をすべて実行する。
+    pub(super) fn run(&self) {
+        for job in self.jobs.lock().unwrap().iter_mut() {
+            job();
+        }
+    }
+}
@@ -2,31 +2,44 @@ use std::sync::Arc;
 
 use axum::{
     extract::{Json, Path, State},
-    http::StatusCode,
     routing::{delete, get, post},
     Router,
 };
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, OpenApi, ToSchema};
 
 use crate::{
-    application_service::share_service::{GrantShareCommand, RevokeShareCommand},
+    application_service::share_service::{
+        FetchSharedCommand, GrantShareCommand, KeyPossessionProof, RevokeShareCommand,
+        ShareRecipient,
+    },
     domain::share::key_envelope::{KeyEnvelope, KeyWrapAlgorithm, WrappedRecipientKey},
-    domain::{content_id::ContentId, share::Permission},
+    domain::share::Permission,
+    infrastructure::jose::{self, FlattenedJwe},
+    infrastructure::signed_envelope,
 };
 
-use super::{decode_base64, decode_key_id_base64, AppState};
+use super::{
+    decode_base64, decode_key_id_base64, parse_content_id, ApiError, AppState, ErrorResponse,
+};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct GrantShareRequest {
     pub content_id: String,
     pub sender_key_id_base64: String,
-    pub recipient_public_key_base64: String,
+    /// 受信者の公開鍵。`recipient_account_id` とどちらか一方を指定する。
+    #[serde(default)]
+    pub recipient_public_key_base64: Option<String>,
+    /// 受信者のアカウント識別子（did:key）。monas-account がそのアカウントの署名付きで公開する
+    /// 共有用の HPKE 公開鍵に解決する。
+    #[serde(default)]
+    pub recipient_account_id: Option<String>,
     pub permission: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct GrantShareResponse {
     pub content_id: String,
     pub sender_key_id: String,
@@ -35,9 +48,35 @@ pub struct GrantShareResponse {
     pub enc_base64: String,
     pub wrapped_cek_base64: String,
     pub ciphertext_base64: String,
+    /// 同じ KeyEnvelope を JWE として表したもの。AES-256-GCM で暗号化したコンテンツや
+    /// チャンク分割したコンテンツは JWE で表せないため `null`。
+    pub jwe: Option<JweResponse>,
+}
+
+/// KeyEnvelope の JWE（Flattened JSON Serialization）表現。
+///
+/// 値はすべて base64url（パディングなし）。`alg` / `enc` の対応は
+/// `infrastructure::jose` のモジュールドキュメントを参照。
+#[derive(Serialize, ToSchema)]
+pub struct JweResponse {
+    pub protected: String,
+    pub encrypted_key: String,
+    pub iv: String,
+    pub ciphertext: String,
+}
+
+impl From<FlattenedJwe> for JweResponse {
+    fn from(jwe: FlattenedJwe) -> Self {
+        Self {
+            protected: jwe.protected,
+            encrypted_key: jwe.encrypted_key,
+            iv: jwe.iv,
+            ciphertext: jwe.ciphertext,
+        }
+    }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UnwrapCekRequest {
     pub content_id: String,
     pub sender_key_id_base64: String,
@@ -45,22 +84,41 @@ pub struct UnwrapCekRequest {
     pub enc_base64: String,
     pub wrapped_cek_base64: String,
     pub ciphertext_base64: String,
+    /// 受信者の HPKE 秘密鍵。アカウント識別子で共有された場合は monas-account の
+    /// `POST /accounts/encryption-key/export` で得た鍵を渡す。
     pub recipient_private_key_base64: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct UnwrapCekResponse {
     pub cek_base64: String,
 }
 
-#[derive(Serialize)]
+#[derive(Deserialize, ToSchema)]
+pub struct UnwrapSignedCekRequest {
+    /// 送信者の署名付き KeyEnvelope（`infrastructure::signed_envelope` の CBOR 形式）。
+    pub signed_envelope_base64: String,
+    /// 受信者の HPKE 秘密鍵（`POST /shares/unwrap` と同じ）。
+    pub recipient_private_key_base64: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UnwrapSignedCekResponse {
+    pub content_id: String,
+    /// 署名を検証した送信者の KeyId と公開鍵（SEC1 uncompressed）。
+    pub sender_key_id_base64: String,
+    pub sender_public_key_base64: String,
+    pub cek_base64: String,
+}
+
+#[derive(Serialize, ToSchema)]
 pub struct RevokeShareResponse {
     pub content_id: String,
     pub recipient_key_id: String,
     pub new_envelopes: Vec<KeyEnvelopeResponse>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct KeyEnvelopeResponse {
     pub content_id: String,
     pub sender_key_id: String,
@@ -68,72 +126,116 @@ pub struct KeyEnvelopeResponse {
     pub enc_base64: String,
     pub wrapped_cek_base64: String,
     pub ciphertext_base64: String,
+    /// JWE 表現（[`GrantShareResponse::jwe`] と同じく、表せない場合は `null`）。
+    pub jwe: Option<JweResponse>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
+pub struct FetchSharedRequest {
+    pub recipient_key_id_base64: String,
+    /// 署名した時刻（RFC 3339）。現在時刻から 5 分以上ずれていると拒否する。
+    pub signed_at: String,
+    /// `monas-shared-fetch-v1\n<content_id>\n<hex(recipient_key_id)>\n<signed_at の UNIX 秒>` に
+    /// 受信者の鍵で付けた ECDSA P-256 / SHA-256 署名（r || s または DER）。
+    pub signature_base64: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct FetchSharedResponse {
+    /// 受信者向けにラップし直した CEK と暗号文。
+    pub envelope: KeyEnvelopeResponse,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RevokeShareQuery {
     pub sender_key_id_base64: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ShareRecipientView {
     pub recipient_key_id: String,
     pub permissions: Vec<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct GetShareResponse {
     pub content_id: String,
     pub recipients: Vec<ShareRecipientView>,
 }
 
+/// 共有 API の OpenAPI 定義。
+#[derive(OpenApi)]
+#[openapi(
+    paths(grant_share, unwrap_cek, unwrap_signed_cek, revoke_share, get_share, fetch_shared),
+    tags((name = "shares", description = "コンテンツの共有と KeyEnvelope の管理"))
+)]
+pub(super) struct ShareApi;
+
 pub fn routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/shares", post(grant_share))
         .route("/shares/unwrap", post(unwrap_cek))
+        .route("/shares/unwrap-signed", post(unwrap_signed_cek))
         .route(
             "/shares/{content_id}/{recipient_key_id}",
             delete(revoke_share),
         )
         .route("/shares/{content_id}", get(get_share))
+        .route("/shares/{content_id}/fetch", post(fetch_shared))
 }
 
+#[utoipa::path(
+    post,
+    path = "/shares",
+    tag = "shares",
+    request_body = GrantShareRequest,
+    responses(
+        (status = 200, description = "受信者向けに作成した KeyEnvelope", body = GrantShareResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツまたは受信者アカウントが存在しない", body = ErrorResponse),
+        (status = 409, description = "共有済み、またはコンテンツが削除済み", body = ErrorResponse),
+    )
+)]
 async fn grant_share(
     State(state): State<Arc<AppState>>,
     Json(req): Json<GrantShareRequest>,
-) -> Result<Json<GrantShareResponse>, (StatusCode, String)> {
-    let content_id = ContentId::new(req.content_id.clone());
+) -> Result<Json<GrantShareResponse>, ApiError> {
+    let content_id = parse_content_id(req.content_id.clone())?;
 
     let sender_key_id = decode_key_id_base64(&req.sender_key_id_base64, "sender_key_id_base64")?;
 
-    let recipient_pubkey = decode_base64(
-        &req.recipient_public_key_base64,
-        "recipient_public_key_base64",
-    )?;
+    let recipient =
+        match (&req.recipient_public_key_base64, &req.recipient_account_id) {
+            (Some(public_key), None) => {
+                ShareRecipient::PublicKey(decode_base64(public_key, "recipient_public_key_base64")?)
+            }
+            (None, Some(account_id)) => ShareRecipient::Account(account_id.clone()),
+            _ => return Err(ApiError::bad_request(
+                "exactly one of recipient_public_key_base64 or recipient_account_id is required",
+            )),
+        };
 
     let permission = match req.permission.to_lowercase().trim() {
         "read" => Permission::Read,
         "write" => Permission::Write,
         "owner" => Permission::Owner,
         other => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                format!("invalid permission value: {other}"),
-            ))
+            return Err(ApiError::bad_request(format!(
+                "invalid permission value: {other}"
+            )))
         }
     };
 
     let cmd = GrantShareCommand {
         content_id,
         sender_key_id,
-        recipient_public_key: recipient_pubkey,
+        recipient,
         permission,
     };
 
-    let result = state
-        .share_service
-        .grant_share(cmd)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let result = state.share_service.grant_share(cmd)?;
 
     let env = result.envelope;
     let recipient = env.recipient();
@@ -142,6 +244,7 @@ async fn grant_share(
     let enc_b64 = BASE64_STANDARD.encode(recipient.enc());
     let wrapped_cek_b64 = BASE64_STANDARD.encode(recipient.wrapped_cek());
     let ciphertext_b64 = BASE64_STANDARD.encode(env.ciphertext());
+    let jwe = jose::to_jwe_if_supported(&env)?.map(Into::into);
 
     Ok(Json(GrantShareResponse {
         content_id: env.content_id().as_str().to_string(),
@@ -151,14 +254,26 @@ async fn grant_share(
         enc_base64: enc_b64,
         wrapped_cek_base64: wrapped_cek_b64,
         ciphertext_base64: ciphertext_b64,
+        jwe,
     }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/shares/unwrap",
+    tag = "shares",
+    request_body = UnwrapCekRequest,
+    responses(
+        (status = 200, description = "取り出した CEK", body = UnwrapCekResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 422, description = "アンラップに失敗", body = ErrorResponse),
+    )
+)]
 async fn unwrap_cek(
     State(state): State<Arc<AppState>>,
     Json(req): Json<UnwrapCekRequest>,
-) -> Result<Json<UnwrapCekResponse>, (StatusCode, String)> {
-    let content_id = ContentId::new(req.content_id.clone());
+) -> Result<Json<UnwrapCekResponse>, ApiError> {
+    let content_id = parse_content_id(req.content_id.clone())?;
 
     let sender_key_id = decode_key_id_base64(&req.sender_key_id_base64, "sender_key_id_base64")?;
 
@@ -184,19 +299,74 @@ async fn unwrap_cek(
 
     let cek = state
         .share_service
-        .unwrap_cek_from_envelope(&envelope, &recipient_private_key)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+        .unwrap_cek_from_envelope(&envelope, &recipient_private_key)?;
     let cek_base64 = BASE64_STANDARD.encode(&cek.0);
 
     Ok(Json(UnwrapCekResponse { cek_base64 }))
 }
 
+/// 送信者の署名付き KeyEnvelope を検証し、検証に通った場合だけ CEK を取り出す。
+///
+/// 署名は封筒のすべてのフィールドを覆うため、encapsulated key や暗号文を差し替えた
+/// 封筒は 422（`invalid_envelope_signature`）になる。
+#[utoipa::path(
+    post,
+    path = "/shares/unwrap-signed",
+    tag = "shares",
+    request_body = UnwrapSignedCekRequest,
+    responses(
+        (status = 200, description = "取り出した CEK と署名した送信者", body = UnwrapSignedCekResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 422, description = "署名の検証またはアンラップに失敗", body = ErrorResponse),
+    )
+)]
+async fn unwrap_signed_cek(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<UnwrapSignedCekRequest>,
+) -> Result<Json<UnwrapSignedCekResponse>, ApiError> {
+    let signed_envelope = decode_base64(&req.signed_envelope_base64, "signed_envelope_base64")?;
+    let recipient_private_key = decode_base64(
+        &req.recipient_private_key_base64,
+        "recipient_private_key_base64",
+    )?;
+
+    let verified = signed_envelope::decode_verified(
+        &signed_envelope,
+        &state.share_service.public_key_directory,
+    )?;
+    let cek = state
+        .share_service
+        .unwrap_cek_from_envelope(&verified.envelope, &recipient_private_key)?;
+
+    Ok(Json(UnwrapSignedCekResponse {
+        content_id: verified.envelope.content_id().as_str().to_string(),
+        sender_key_id_base64: BASE64_STANDARD.encode(verified.envelope.sender_key_id().as_bytes()),
+        sender_public_key_base64: BASE64_STANDARD.encode(&verified.sender_public_key),
+        cek_base64: BASE64_STANDARD.encode(&cek.0),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/shares/{content_id}/{recipient_key_id}",
+    tag = "shares",
+    params(
+        ("content_id" = String, Path, description = "ContentId"),
+        ("recipient_key_id" = String, Path, description = "受信者の KeyId (Base64)"),
+        RevokeShareQuery,
+    ),
+    responses(
+        (status = 200, description = "残りの受信者向けに再発行した KeyEnvelope", body = RevokeShareResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "コンテンツまたは受信者が存在しない", body = ErrorResponse),
+    )
+)]
 async fn revoke_share(
     State(state): State<Arc<AppState>>,
     Path((content_id_str, recipient_key_id_b64)): Path<(String, String)>,
     axum::extract::Query(q): axum::extract::Query<RevokeShareQuery>,
-) -> Result<Json<RevokeShareResponse>, (StatusCode, String)> {
-    let content_id = ContentId::new(content_id_str.clone());
+) -> Result<Json<RevokeShareResponse>, ApiError> {
+    let content_id = parse_content_id(content_id_str.clone())?;
 
     let sender_key_id = decode_key_id_base64(&q.sender_key_id_base64, "sender_key_id_base64")?;
 
@@ -209,26 +379,13 @@ async fn revoke_share(
         recipient_key_id,
     };
 
-    let result = state
-        .share_service
-        .revoke_share(cmd)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let result = state.share_service.revoke_share(cmd)?;
 
     let new_envelopes = result
         .envelopes
         .into_iter()
-        .map(|env| {
-            let recipient = env.recipient();
-            KeyEnvelopeResponse {
-                content_id: env.content_id().as_str().to_string(),
-                sender_key_id: BASE64_STANDARD.encode(env.sender_key_id().as_bytes()),
-                recipient_key_id: BASE64_STANDARD.encode(recipient.key_id().as_bytes()),
-                enc_base64: BASE64_STANDARD.encode(recipient.enc()),
-                wrapped_cek_base64: BASE64_STANDARD.encode(recipient.wrapped_cek()),
-                ciphertext_base64: BASE64_STANDARD.encode(env.ciphertext()),
-            }
-        })
-        .collect();
+        .map(|env| envelope_response(&env))
+        .collect::<Result<_, ApiError>>()?;
 
     Ok(Json(RevokeShareResponse {
         content_id: result.content_id.as_str().to_string(),
@@ -237,23 +394,31 @@ async fn revoke_share(
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/shares/{content_id}",
+    tag = "shares",
+    params(("content_id" = String, Path, description = "ContentId")),
+    responses(
+        (status = 200, description = "共有先の一覧", body = GetShareResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 404, description = "共有が存在しない", body = ErrorResponse),
+    )
+)]
 async fn get_share(
     State(state): State<Arc<AppState>>,
     Path(content_id_str): Path<String>,
-) -> Result<Json<GetShareResponse>, (StatusCode, String)> {
-    let content_id = ContentId::new(content_id_str.clone());
+) -> Result<Json<GetShareResponse>, ApiError> {
+    let content_id = parse_content_id(content_id_str.clone())?;
 
-    let share_opt = state
-        .share_service
-        .get_share(content_id)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let share_opt = state.share_service.get_share(content_id)?;
 
     let share = match share_opt {
         Some(s) => s,
         None => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                "share not found for content".to_string(),
+            return Err(ApiError::not_found(
+                "share_not_found",
+                "share not found for content",
             ))
         }
     };
@@ -264,11 +429,7 @@ async fn get_share(
         let permissions = recipient
             .permissions()
             .iter()
-            .map(|p| match p {
-                Permission::Read => "read".to_string(),
-                Permission::Write => "write".to_string(),
-                Permission::Owner => "owner".to_string(),
-            })
+            .map(permission_name)
             .collect();
 
         recipients.push(ShareRecipientView {
@@ -282,3 +443,69 @@ async fn get_share(
         recipients,
     }))
 }
+
+fn permission_name(permission: &Permission) -> String {
+    match permission {
+        Permission::Read => "read".to_string(),
+        Permission::Write => "write".to_string(),
+        Permission::Owner => "owner".to_string(),
+    }
+}
+
+pub(super) fn envelope_response(env: &KeyEnvelope) -> Result<KeyEnvelopeResponse, ApiError> {
+    let recipient = env.recipient();
+    Ok(KeyEnvelopeResponse {
+        content_id: env.content_id().as_str().to_string(),
+        sender_key_id: BASE64_STANDARD.encode(env.sender_key_id().as_bytes()),
+        recipient_key_id: BASE64_STANDARD.encode(recipient.key_id().as_bytes()),
+        enc_base64: BASE64_STANDARD.encode(recipient.enc()),
+        wrapped_cek_base64: BASE64_STANDARD.encode(recipient.wrapped_cek()),
+        ciphertext_base64: BASE64_STANDARD.encode(env.ciphertext()),
+        jwe: jose::to_jwe_if_supported(env)?.map(Into::into),
+    })
+}
+
+/// 共有を受けた受信者が、暗号文と自分宛てにラップされた CEK を取得する。
+///
+/// 受信者は owner の CEK ストアに触れずに済む。鍵の所有は `signature_base64` で示す。
+#[utoipa::path(
+    post,
+    path = "/shares/{content_id}/fetch",
+    tag = "shares",
+    params(("content_id" = String, Path, description = "ContentId")),
+    request_body = FetchSharedRequest,
+    responses(
+        (status = 200, description = "受信者向けの KeyEnvelope", body = FetchSharedResponse),
+        (status = 400, description = "リクエストが不正", body = ErrorResponse),
+        (status = 401, description = "所有証明が不正、または期限切れ", body = ErrorResponse),
+        (status = 403, description = "受信者と共有されていない", body = ErrorResponse),
+        (status = 404, description = "コンテンツが存在しない", body = ErrorResponse),
+    )
+)]
+async fn fetch_shared(
+    State(state): State<Arc<AppState>>,
+    Path(content_id_str): Path<String>,
+    Json(req): Json<FetchSharedRequest>,
+) -> Result<Json<FetchSharedResponse>, ApiError> {
+    let content_id = parse_content_id(content_id_str)?;
+    let recipient_key_id =
+        decode_key_id_base64(&req.recipient_key_id_base64, "recipient_key_id_base64")?;
+    let signed_at = chrono::DateTime::parse_from_rfc3339(&req.signed_at)
+        .map_err(|e| ApiError::bad_request(format!("invalid signed_at: {e}")))?
+        .with_timezone(&chrono::Utc);
+    let signature = decode_base64(&req.signature_base64, "signature_base64")?;
+
+    let result = state.share_service.fetch_shared(FetchSharedCommand {
+        content_id,
+        recipient_key_id,
+        proof: KeyPossessionProof {
+            signed_at,
+            signature,
+        },
+    })?;
+
+    Ok(Json(FetchSharedResponse {
+        envelope: envelope_response(&result.envelope)?,
+        permissions: result.permissions.iter().map(permission_name).collect(),
+    }))
+}
@@ -0,0 +1,56 @@
+//! サーバー終了時の処理。
+//!
+//! SIGINT / SIGTERM を受け取ったら新しい接続の受け付けをやめ、処理中のリクエストを
+//! 終えてから [`ShutdownHook::flush`] で、メンテナンスジョブを止めたうえで、すべての
+//! 名前空間のリポジトリ・鍵ストア・共有・コンテンツ一覧を永続化する。
+
+use std::sync::Arc;
+
+use crate::application_service::{
+    content_service::{
+        ContentEncryptionKeyStore, ContentEncryptionKeyStoreError, ContentRepository,
+        ContentRepositoryError,
+    },
+    job_service::JobRunnerHandle,
+    rotation_service::ContentCatalogError,
+    share_service::{ShareRepository, ShareRepositoryError},
+};
+
+use super::{namespace::NamespaceRouters, AppState};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShutdownError {
+    #[error("failed to flush content repository: {0}")]
+    Repository(#[from] ContentRepositoryError),
+    #[error("failed to flush key store: {0}")]
+    KeyStore(#[from] ContentEncryptionKeyStoreError),
+    #[error("failed to flush share repository: {0}")]
+    Shares(#[from] ShareRepositoryError),
+    #[error("failed to flush content catalog: {0}")]
+    Catalog(#[from] ContentCatalogError),
+}
+
+/// ルーターと同じサービス一式（名前空間ごとのものを含む）を保持し、終了前に永続化するフック。
+#[derive(Clone)]
+pub struct ShutdownHook {
+    pub(super) default: Arc<AppState>,
+    pub(super) namespaces: Arc<NamespaceRouters>,
+    pub(super) jobs: Arc<JobRunnerHandle>,
+}
+
+impl ShutdownHook {
+    /// 名前空間なしの API と、作成済みのすべての名前空間について、リポジトリ・鍵ストア・
+    /// 共有・コンテンツ一覧の未書き込みのデータを永続化する。
+    ///
+    /// 処理中のリクエストがすべて終わった後に呼び出すこと。以降のメンテナンスジョブは実行しない。
+    pub fn flush(&self) -> Result<(), ShutdownError> {
+        self.jobs.shutdown();
+        for state in std::iter::once(self.default.clone()).chain(self.namespaces.states()) {
+            state.content_service.content_repository.flush()?;
+            state.content_service.cek_store.flush()?;
+            state.share_service.share_repository.flush()?;
+            state.catalog.flush()?;
+        }
+        Ok(())
+    }
+}
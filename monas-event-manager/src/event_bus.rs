@@ -47,6 +47,102 @@ impl EventBus {
         self.event_subscriptions.subscribe::<T>(subscriber).await
     }
 
+    /// Subscribe as a member of a consumer group.
+    ///
+    /// Each event of type `T` is delivered to exactly one member of the group, which lets
+    /// several instances of the same service share the load. When a member unsubscribes,
+    /// its pending retries are rebalanced to the remaining members.
+    pub async fn subscribe_group<T>(
+        &self,
+        group_id: &str,
+        subscriber: Arc<crate::event_subscription::Subscriber>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: Event + 'static,
+    {
+        self.event_subscriptions
+            .subscribe_group::<T>(group_id, subscriber)
+            .await
+    }
+
+    /// Subscribe and record the subscription in the persistence database.
+    ///
+    /// Durable subscriptions let [`Self::validate_persisted_messages`] tell apart dead
+    /// letters whose consumer merely has not re-subscribed yet from ones nobody consumes.
+    /// Without persistence this is the same as [`Self::subscribe`].
+    pub async fn subscribe_durable<T>(
+        &self,
+        subscriber: Arc<crate::event_subscription::Subscriber>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: crate::event_subscription::SerializableEvent + 'static,
+    {
+        self.event_subscriptions
+            .subscribe_durable::<T>(subscriber)
+            .await
+    }
+
+    /// Join a consumer group and record the group subscription in the persistence database.
+    pub async fn subscribe_group_durable<T>(
+        &self,
+        group_id: &str,
+        subscriber: Arc<crate::event_subscription::Subscriber>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: crate::event_subscription::SerializableEvent + 'static,
+    {
+        self.event_subscriptions
+            .subscribe_group_durable::<T>(group_id, subscriber)
+            .await
+    }
+
+    /// List the durable subscriptions recorded in the persistence database.
+    pub fn durable_subscriptions(
+        &self,
+    ) -> Result<
+        Vec<crate::sled_persistence::PersistentSubscription>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        self.event_subscriptions.durable_subscriptions()
+    }
+
+    /// Check persisted messages against the current subscribers.
+    ///
+    /// Call this at startup after all subscribers are registered and before restoring
+    /// dead letters. A warning is printed for every persisted message that no current
+    /// subscriber would receive, and the offending messages are returned.
+    pub async fn validate_persisted_messages(
+        &self,
+    ) -> Result<
+        Vec<crate::event_subscription::OrphanedMessage>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let orphaned = self.event_subscriptions.find_orphaned_messages().await?;
+        for message in &orphaned {
+            let consumer = message.subscriber_id.as_deref().unwrap_or("<unknown>");
+            if message.durably_registered {
+                eprintln!(
+                    "Warning: persisted message {} ({}) belongs to {consumer}, which is durably registered but has not subscribed",
+                    message.message_id, message.event_type
+                );
+            } else {
+                eprintln!(
+                    "Warning: persisted message {} ({}) has no registered consumer (expected {consumer})",
+                    message.message_id, message.event_type
+                );
+            }
+        }
+        Ok(orphaned)
+    }
+
+    /// List the subscriber IDs currently in a consumer group.
+    pub async fn group_members<T>(&self, group_id: &str) -> Vec<String>
+    where
+        T: Event + 'static,
+    {
+        self.event_subscriptions.group_members::<T>(group_id).await
+    }
+
     pub async fn unsubscribe<T>(
         &self,
         subscriber_id: &str,
@@ -884,4 +980,264 @@ mod event_bus_tests {
         let result = event_bus.restore_and_retry_dead_letters().await;
         assert!(result.is_ok()); // Should not error even without persistence
     }
+
+    fn counting_subscriber(
+        id: &str,
+        received: Arc<AsyncMutex<Vec<String>>>,
+    ) -> Arc<crate::event_subscription::Subscriber> {
+        let id_owned = id.to_string();
+        make_subscriber::<TestEvent, _, _>(id.to_string(), move |event| {
+            let received = Arc::clone(&received);
+            let entry = format!("{}:{}", id_owned, event.data);
+            async move {
+                received.lock().await.push(entry);
+                Ok(())
+            }
+        })
+    }
+
+    #[async_std::test]
+    async fn test_consumer_group_delivers_each_event_to_one_member() {
+        let event_bus = EventBus::new();
+        let received = Arc::new(AsyncMutex::new(Vec::new()));
+
+        event_bus
+            .subscribe_group::<TestEvent>("workers", counting_subscriber("w1", received.clone()))
+            .await
+            .unwrap();
+        event_bus
+            .subscribe_group::<TestEvent>("workers", counting_subscriber("w2", received.clone()))
+            .await
+            .unwrap();
+        event_bus
+            .subscribe::<TestEvent>(counting_subscriber("audit", received.clone()))
+            .await
+            .unwrap();
+
+        for i in 0..4 {
+            event_bus
+                .publish(Arc::new(TestEvent::new(&format!("e{i}"))))
+                .await
+                .unwrap();
+        }
+
+        let messages = received.lock().await;
+        let count = |prefix: &str| messages.iter().filter(|m| m.starts_with(prefix)).count();
+        // Broadcast subscribers still see every event
+        assert_eq!(count("audit:"), 4);
+        // Group members share the events round-robin
+        assert_eq!(count("w1:"), 2);
+        assert_eq!(count("w2:"), 2);
+        for i in 0..4 {
+            let delivered = messages
+                .iter()
+                .filter(|m| m.starts_with('w') && m.ends_with(&format!(":e{i}")))
+                .count();
+            assert_eq!(delivered, 1, "event e{i} must reach exactly one member");
+        }
+    }
+
+    #[async_std::test]
+    async fn test_independent_consumer_groups_each_receive_events() {
+        let event_bus = EventBus::new();
+        let received = Arc::new(AsyncMutex::new(Vec::new()));
+
+        event_bus
+            .subscribe_group::<TestEvent>("indexer", counting_subscriber("i1", received.clone()))
+            .await
+            .unwrap();
+        event_bus
+            .subscribe_group::<TestEvent>("notifier", counting_subscriber("n1", received.clone()))
+            .await
+            .unwrap();
+
+        event_bus
+            .publish(Arc::new(TestEvent::new("shared")))
+            .await
+            .unwrap();
+
+        let messages = received.lock().await;
+        assert_eq!(messages.len(), 2);
+        assert!(messages.contains(&"i1:shared".to_string()));
+        assert!(messages.contains(&"n1:shared".to_string()));
+    }
+
+    #[async_std::test]
+    async fn test_consumer_group_rebalances_pending_retries_on_leave() {
+        let event_bus = EventBus::new();
+        let received = Arc::new(AsyncMutex::new(Vec::new()));
+
+        let failing = make_subscriber_with_config::<TestEvent, _, _>(
+            "failing".to_string(),
+            |_event| async move { Err("worker crashed".into()) },
+            SubscriberConfig {
+                max_retries: 5,
+                retry_delay_secs: 0,
+                connection_timeout_secs: 30,
+                heartbeat_interval_secs: 10,
+            },
+        );
+        event_bus
+            .subscribe_group::<TestEvent>("workers", failing)
+            .await
+            .unwrap();
+        event_bus
+            .subscribe_group::<TestEvent>("workers", counting_subscriber("ok", received.clone()))
+            .await
+            .unwrap();
+
+        // Round-robin starts at the first member, whose handler fails
+        event_bus
+            .publish(Arc::new(TestEvent::new("pending")))
+            .await
+            .unwrap();
+        assert!(received.lock().await.is_empty());
+
+        event_bus.unsubscribe::<TestEvent>("failing").await.unwrap();
+        assert_eq!(
+            event_bus.group_members::<TestEvent>("workers").await,
+            vec!["ok".to_string()]
+        );
+
+        // The pending message was handed over to the remaining member
+        event_bus.retry_failed_messages().await.unwrap();
+        assert_eq!(*received.lock().await, vec!["ok:pending".to_string()]);
+    }
+
+    #[async_std::test]
+    async fn test_consumer_group_rejects_duplicate_member() {
+        let event_bus = EventBus::new();
+        let received = Arc::new(AsyncMutex::new(Vec::new()));
+
+        event_bus
+            .subscribe_group::<TestEvent>("workers", counting_subscriber("w1", received.clone()))
+            .await
+            .unwrap();
+        let result = event_bus
+            .subscribe_group::<TestEvent>("workers", counting_subscriber("w1", received))
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            event_bus.group_members::<TestEvent>("workers").await.len(),
+            1
+        );
+    }
+
+    fn failing_subscriber(id: &str) -> Arc<crate::event_subscription::Subscriber> {
+        make_subscriber_with_config::<TestEvent, _, _>(
+            id.to_string(),
+            |_event| async move { Err("Always fail".into()) },
+            SubscriberConfig {
+                max_retries: 1,
+                retry_delay_secs: 0,
+                connection_timeout_secs: 30,
+                heartbeat_interval_secs: 10,
+            },
+        )
+    }
+
+    #[async_std::test]
+    async fn test_validate_persisted_messages_after_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let persistence_manager =
+            SledPersistenceManager::new(temp_dir.path().to_str().unwrap()).unwrap();
+
+        {
+            let event_bus = EventBus::with_persistence(persistence_manager.clone());
+            event_bus
+                .subscribe_durable::<TestEvent>(failing_subscriber("durable"))
+                .await
+                .unwrap();
+            event_bus
+                .subscribe::<TestEvent>(failing_subscriber("ephemeral"))
+                .await
+                .unwrap();
+
+            event_bus
+                .publish(Arc::new(TestEvent::new("dead")))
+                .await
+                .unwrap();
+            event_bus.retry_failed_messages().await.unwrap();
+            assert_eq!(
+                event_bus.get_persistence_stats().unwrap()["message_count"],
+                2
+            );
+        }
+
+        // Restart (same database) without registering any subscriber
+        let event_bus = EventBus::with_persistence(persistence_manager);
+        event_bus.register_event_type::<TestEvent>().await;
+        let subscriptions = event_bus.durable_subscriptions().unwrap();
+        assert_eq!(subscriptions.len(), 1);
+        assert_eq!(subscriptions[0].subscriber_id, "durable");
+        assert_eq!(subscriptions[0].event_types, vec!["TestEvent".to_string()]);
+
+        let mut orphaned = event_bus.validate_persisted_messages().await.unwrap();
+        orphaned.sort_by(|a, b| a.subscriber_id.cmp(&b.subscriber_id));
+        assert_eq!(orphaned.len(), 2);
+        assert_eq!(orphaned[0].subscriber_id.as_deref(), Some("durable"));
+        assert!(orphaned[0].durably_registered);
+        assert_eq!(orphaned[1].subscriber_id.as_deref(), Some("ephemeral"));
+        assert!(!orphaned[1].durably_registered);
+
+        // Once the durable consumer subscribes again only the other message is orphaned
+        event_bus
+            .subscribe_durable::<TestEvent>(failing_subscriber("durable"))
+            .await
+            .unwrap();
+        let orphaned = event_bus.validate_persisted_messages().await.unwrap();
+        assert_eq!(orphaned.len(), 1);
+        assert_eq!(orphaned[0].subscriber_id.as_deref(), Some("ephemeral"));
+    }
+
+    #[async_std::test]
+    async fn test_unsubscribe_forgets_durable_subscription() {
+        let temp_dir = TempDir::new().unwrap();
+        let persistence_manager =
+            SledPersistenceManager::new(temp_dir.path().to_str().unwrap()).unwrap();
+        let event_bus = EventBus::with_persistence(persistence_manager);
+        let received = Arc::new(AsyncMutex::new(Vec::new()));
+
+        event_bus
+            .subscribe_durable::<TestEvent>(counting_subscriber("solo", received.clone()))
+            .await
+            .unwrap();
+        for member in ["w1", "w2"] {
+            event_bus
+                .subscribe_group_durable::<TestEvent>(
+                    "workers",
+                    counting_subscriber(member, received.clone()),
+                )
+                .await
+                .unwrap();
+        }
+        let mut ids: Vec<String> = event_bus
+            .durable_subscriptions()
+            .unwrap()
+            .into_iter()
+            .map(|subscription| subscription.subscriber_id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["group:workers".to_string(), "solo".to_string()]);
+
+        event_bus.unsubscribe::<TestEvent>("solo").await.unwrap();
+        event_bus.unsubscribe::<TestEvent>("w1").await.unwrap();
+        // The group keeps its durable subscription while it still has members
+        assert_eq!(event_bus.durable_subscriptions().unwrap().len(), 1);
+
+        event_bus.unsubscribe::<TestEvent>("w2").await.unwrap();
+        assert!(event_bus.durable_subscriptions().unwrap().is_empty());
+    }
+
+    #[async_std::test]
+    async fn test_validate_persisted_messages_without_persistence() {
+        let event_bus = EventBus::new();
+        assert!(event_bus
+            .validate_persisted_messages()
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(event_bus.durable_subscriptions().unwrap().is_empty());
+    }
 }
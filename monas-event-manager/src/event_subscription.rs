@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::config::SubscriberConfig;
 use crate::event_bus::Event;
-use crate::sled_persistence::SledPersistenceManager;
+use crate::sled_persistence::{PersistentSubscription, SledPersistenceManager};
 
 // Type aliases for complex types
 type EventHandler = Arc<
@@ -131,6 +131,18 @@ impl std::fmt::Debug for EventMessage {
     }
 }
 
+/// A persisted message that no subscriber in this process can receive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanedMessage {
+    pub message_id: String,
+    pub event_type: String,
+    /// Consumer parsed from the message ID (subscriber ID or `group:{group_id}`)
+    pub subscriber_id: Option<String>,
+    /// Whether the consumer has a durable subscription for the event type, i.e. it
+    /// was registered in a previous run but has not subscribed again yet
+    pub durably_registered: bool,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConnectionStatus {
     Connected,
@@ -325,6 +337,11 @@ impl Subscriber {
         }
     }
 
+    /// Take all pending messages out of the retry queue (used when rebalancing a consumer group)
+    pub async fn take_retry_queue(&self) -> Vec<EventMessage> {
+        self.message_queue.lock().await.drain(..).collect()
+    }
+
     pub async fn get_failed_messages(&self) -> Vec<EventMessage> {
         self.failed_messages.lock().await.clone()
     }
@@ -334,9 +351,47 @@ impl Subscriber {
     }
 }
 
+/// A set of subscribers sharing the work for one event type (competing consumers).
+///
+/// Each published event is delivered to exactly one member, chosen round-robin
+/// among the healthy members.
+#[derive(Default)]
+struct ConsumerGroup {
+    members: Vec<Arc<Subscriber>>,
+    next: usize,
+}
+
+impl ConsumerGroup {
+    /// Pick the member that should receive the next message.
+    ///
+    /// Unhealthy members are skipped; if no member is healthy the next member in
+    /// round-robin order is used so the message still lands in a retry queue.
+    async fn select_member(&mut self) -> Option<Arc<Subscriber>> {
+        let len = self.members.len();
+        if len == 0 {
+            return None;
+        }
+        let start = self.next % len;
+        let mut selected = start;
+        for offset in 0..len {
+            let idx = (start + offset) % len;
+            if self.members[idx].is_healthy().await {
+                selected = idx;
+                break;
+            }
+        }
+        self.next = (selected + 1) % len;
+        Some(Arc::clone(&self.members[selected]))
+    }
+}
+
+type ConsumerGroups = Arc<RwLock<HashMap<TypeId, HashMap<String, ConsumerGroup>>>>;
+
 #[derive(Clone)]
 pub struct EventSubscriptions {
     subscriptions: Arc<RwLock<HashMap<TypeId, Vec<Arc<Subscriber>>>>>,
+    // Consumer groups: each event is delivered to one member per group
+    consumer_groups: ConsumerGroups,
     // In-memory message management (fast)
     message_store: Arc<Mutex<HashMap<String, EventMessage>>>,
     // Dead letter persistence manager (failed messages only)
@@ -351,6 +406,7 @@ impl EventSubscriptions {
     pub fn new() -> Self {
         Self {
             subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            consumer_groups: Arc::new(RwLock::new(HashMap::new())),
             message_store: Arc::new(Mutex::new(HashMap::new())),
             dead_letter_manager: None,
             event_registry: Arc::new(RwLock::new(HashMap::new())),
@@ -362,6 +418,7 @@ impl EventSubscriptions {
     pub fn with_persistence(persistence_manager: SledPersistenceManager) -> Self {
         Self {
             subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            consumer_groups: Arc::new(RwLock::new(HashMap::new())),
             message_store: Arc::new(Mutex::new(HashMap::new())),
             dead_letter_manager: Some(persistence_manager),
             event_registry: Arc::new(RwLock::new(HashMap::new())),
@@ -391,7 +448,226 @@ impl EventSubscriptions {
         let type_id = TypeId::of::<T>();
         let mut subscriptions = self.subscriptions.write().await;
 
-        // Set dead letter callback
+        self.attach_dead_letter_callback(&subscriber).await;
+
+        subscriptions
+            .entry(type_id)
+            .or_insert_with(Vec::new)
+            .push(subscriber);
+
+        Ok(())
+    }
+
+    /// Register subscriber as a member of a consumer group
+    ///
+    /// Events of type `T` are delivered to exactly one member of each group instead of
+    /// to every member. A new member takes part in round-robin delivery immediately.
+    pub async fn subscribe_group<T>(
+        &self,
+        group_id: &str,
+        subscriber: Arc<Subscriber>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: Event + 'static,
+    {
+        let type_id = TypeId::of::<T>();
+        let mut groups = self.consumer_groups.write().await;
+
+        let group = groups
+            .entry(type_id)
+            .or_default()
+            .entry(group_id.to_string())
+            .or_default();
+        if group
+            .members
+            .iter()
+            .any(|member| member.id() == subscriber.id())
+        {
+            return Err(format!(
+                "subscriber {} is already a member of group {group_id}",
+                subscriber.id()
+            )
+            .into());
+        }
+
+        self.attach_dead_letter_callback(&subscriber).await;
+        group.members.push(subscriber);
+
+        Ok(())
+    }
+
+    /// Register subscriber and record the subscription durably
+    ///
+    /// The subscription is stored in the persistence database so that a later run can
+    /// detect dead letters whose consumer was not registered again. Without persistence
+    /// this behaves like [`Self::subscribe`].
+    pub async fn subscribe_durable<T>(
+        &self,
+        subscriber: Arc<Subscriber>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: SerializableEvent + 'static,
+    {
+        self.register_event_type::<T>().await;
+        let subscriber_id = subscriber.id().to_string();
+        self.subscribe::<T>(subscriber).await?;
+        self.record_durable_subscription(&subscriber_id, T::event_type())
+    }
+
+    /// Register subscriber as a consumer group member and record the group durably
+    pub async fn subscribe_group_durable<T>(
+        &self,
+        group_id: &str,
+        subscriber: Arc<Subscriber>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: SerializableEvent + 'static,
+    {
+        self.register_event_type::<T>().await;
+        self.subscribe_group::<T>(group_id, subscriber).await?;
+        self.record_durable_subscription(&group_consumer_id(group_id), T::event_type())
+    }
+
+    /// Add an event type to the durable subscription of a consumer
+    fn record_durable_subscription(
+        &self,
+        subscriber_id: &str,
+        event_type: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some(persistence) = &self.dead_letter_manager else {
+            return Ok(());
+        };
+        let mut subscription = persistence
+            .load_subscription(subscriber_id)?
+            .unwrap_or_else(|| PersistentSubscription {
+                subscriber_id: subscriber_id.to_string(),
+                event_types: Vec::new(),
+            });
+        if subscription.event_types.iter().any(|t| t == event_type) {
+            return Ok(());
+        }
+        subscription.event_types.push(event_type.to_string());
+        persistence.save_subscription(&subscription)
+    }
+
+    /// Remove the event types registered for `type_id` from a durable subscription
+    async fn forget_durable_subscription(
+        &self,
+        subscriber_id: &str,
+        type_id: TypeId,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some(persistence) = &self.dead_letter_manager else {
+            return Ok(());
+        };
+        let Some(mut subscription) = persistence.load_subscription(subscriber_id)? else {
+            return Ok(());
+        };
+        let registry = self.event_registry.read().await;
+        subscription
+            .event_types
+            .retain(|event_type| registry.get(event_type) != Some(&type_id));
+        drop(registry);
+
+        if subscription.event_types.is_empty() {
+            persistence.delete_subscription(subscriber_id)
+        } else {
+            persistence.save_subscription(&subscription)
+        }
+    }
+
+    /// List the durable subscriptions recorded in the persistence database
+    pub fn durable_subscriptions(
+        &self,
+    ) -> Result<Vec<PersistentSubscription>, Box<dyn std::error::Error + Send + Sync>> {
+        match &self.dead_letter_manager {
+            Some(persistence) => persistence.load_subscriptions(),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Find persisted messages that no current subscriber would receive
+    ///
+    /// A message is matched to its consumer through the subscriber (or group) ID
+    /// embedded in its message ID. Messages whose event type was never registered
+    /// cannot be matched at all and are always reported.
+    pub async fn find_orphaned_messages(
+        &self,
+    ) -> Result<Vec<OrphanedMessage>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(persistence) = &self.dead_letter_manager else {
+            return Ok(Vec::new());
+        };
+        let messages = persistence.load_messages()?;
+        let durable: HashMap<String, Vec<String>> = persistence
+            .load_subscriptions()?
+            .into_iter()
+            .map(|subscription| (subscription.subscriber_id, subscription.event_types))
+            .collect();
+
+        let registry = self.event_registry.read().await;
+        let subscriptions = self.subscriptions.read().await;
+        let groups = self.consumer_groups.read().await;
+
+        let mut orphaned = Vec::new();
+        for message in messages {
+            let subscriber_id = message
+                .id
+                .split_once("::")
+                .map(|(_, consumer)| consumer.to_string());
+
+            let has_live_consumer = match registry.get(&message.event_type) {
+                Some(type_id) => match subscriber_id.as_deref() {
+                    Some(consumer) => match consumer.strip_prefix("group:") {
+                        Some(group_id) => groups
+                            .get(type_id)
+                            .is_some_and(|type_groups| type_groups.contains_key(group_id)),
+                        None => subscriptions.get(type_id).is_some_and(|subscribers| {
+                            subscribers.iter().any(|sub| sub.id() == consumer)
+                        }),
+                    },
+                    None => subscriptions.contains_key(type_id) || groups.contains_key(type_id),
+                },
+                None => false,
+            };
+            if has_live_consumer {
+                continue;
+            }
+
+            let durably_registered = subscriber_id
+                .as_ref()
+                .and_then(|consumer| durable.get(consumer))
+                .is_some_and(|event_types| event_types.contains(&message.event_type));
+            orphaned.push(OrphanedMessage {
+                message_id: message.id,
+                event_type: message.event_type,
+                subscriber_id,
+                durably_registered,
+            });
+        }
+
+        Ok(orphaned)
+    }
+
+    /// List the subscriber IDs of a consumer group
+    pub async fn group_members<T>(&self, group_id: &str) -> Vec<String>
+    where
+        T: Event + 'static,
+    {
+        let groups = self.consumer_groups.read().await;
+        groups
+            .get(&TypeId::of::<T>())
+            .and_then(|groups| groups.get(group_id))
+            .map(|group| {
+                group
+                    .members
+                    .iter()
+                    .map(|member| member.id().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Persist failed messages of this subscriber as dead letters
+    async fn attach_dead_letter_callback(&self, subscriber: &Subscriber) {
         let dead_letter_manager = self.dead_letter_manager.clone();
         subscriber
             .set_dead_letter_callback(move |message| {
@@ -404,13 +680,6 @@ impl EventSubscriptions {
                 }
             })
             .await;
-
-        subscriptions
-            .entry(type_id)
-            .or_insert_with(Vec::new)
-            .push(subscriber);
-
-        Ok(())
     }
 
     /// Remove subscriber
@@ -430,10 +699,65 @@ impl EventSubscriptions {
                 subscriptions.remove(&type_id);
             }
         }
+        drop(subscriptions);
+
+        let emptied_groups = self.leave_groups(type_id, subscriber_id).await;
+
+        self.forget_durable_subscription(subscriber_id, type_id)
+            .await?;
+        for group_id in emptied_groups {
+            self.forget_durable_subscription(&group_consumer_id(&group_id), type_id)
+                .await?;
+        }
 
         Ok(())
     }
 
+    /// Remove a subscriber from every consumer group of the event type and rebalance
+    ///
+    /// Messages still waiting in the leaving member's retry queue are handed over to
+    /// the remaining members so that no message is lost. If the group becomes empty
+    /// the pending messages are moved to the dead letter store. Returns the IDs of the
+    /// groups that became empty.
+    async fn leave_groups(&self, type_id: TypeId, subscriber_id: &str) -> Vec<String> {
+        let mut groups = self.consumer_groups.write().await;
+        let Some(type_groups) = groups.get_mut(&type_id) else {
+            return Vec::new();
+        };
+
+        for group in type_groups.values_mut() {
+            let Some(pos) = group
+                .members
+                .iter()
+                .position(|member| member.id() == subscriber_id)
+            else {
+                continue;
+            };
+            let leaving = group.members.remove(pos);
+            if group.next > pos {
+                group.next -= 1;
+            }
+
+            for message in leaving.take_retry_queue().await {
+                match group.select_member().await {
+                    Some(member) => member.add_to_retry_queue(message).await,
+                    None => self.save_to_dead_letter(&message).await,
+                }
+            }
+        }
+
+        let emptied: Vec<String> = type_groups
+            .iter()
+            .filter(|(_, group)| group.members.is_empty())
+            .map(|(group_id, _)| group_id.clone())
+            .collect();
+        type_groups.retain(|_, group| !group.members.is_empty());
+        if type_groups.is_empty() {
+            groups.remove(&type_id);
+        }
+        emptied
+    }
+
     /// Publish event
     pub async fn publish<T>(
         &self,
@@ -451,35 +775,62 @@ impl EventSubscriptions {
             for subscriber in subscribers {
                 // Generate a unique message ID per subscriber
                 let message_id = format!("msg_{}::{}", base_uuid, subscriber.id());
-                let message = EventMessage {
-                    id: message_id.clone(),
-                    event: event.clone(),
-                    event_type: T::event_type().to_string(),
-                    event_data: serde_json::to_string(&*event).unwrap(),
-                    timestamp: Instant::now(),
-                    status: DeliveryStatus::Pending,
-                    retry_count: 0,
-                    max_retries: subscriber.config.max_retries,
-                };
-                // Save message to in-memory store (fast)
-                self.message_store
-                    .lock()
-                    .await
-                    .insert(message_id.clone(), message.clone());
-
-                let result = subscriber.process_event(&message).await;
-                if let Err(e) = result {
-                    eprintln!("Error processing event: {e}");
-                    // Add failed message to retry queue
-                    let mut failed_message = message.clone();
-                    failed_message.status = DeliveryStatus::Retrying;
-                    subscriber.add_to_retry_queue(failed_message).await;
+                self.deliver(subscriber, message_id, &event).await;
+            }
+        }
+        drop(subscriptions);
+
+        // Deliver to exactly one member of each consumer group. Members are selected
+        // under the lock, but handlers run after it is released so they may publish.
+        let mut selected = Vec::new();
+        {
+            let mut groups = self.consumer_groups.write().await;
+            if let Some(type_groups) = groups.get_mut(&type_id) {
+                for (group_id, group) in type_groups.iter_mut() {
+                    if let Some(member) = group.select_member().await {
+                        selected.push((group_id.clone(), member));
+                    }
                 }
             }
         }
+        for (group_id, member) in selected {
+            let message_id = format!("msg_{base_uuid}::{}", group_consumer_id(&group_id));
+            self.deliver(&member, message_id, &event).await;
+        }
         Ok(())
     }
 
+    /// Deliver an event to a single subscriber, queueing it for retry on failure
+    async fn deliver<T>(&self, subscriber: &Subscriber, message_id: String, event: &Arc<T>)
+    where
+        T: SerializableEvent + 'static,
+    {
+        let message = EventMessage {
+            id: message_id.clone(),
+            event: event.clone(),
+            event_type: T::event_type().to_string(),
+            event_data: serde_json::to_string(&**event).unwrap(),
+            timestamp: Instant::now(),
+            status: DeliveryStatus::Pending,
+            retry_count: 0,
+            max_retries: subscriber.config.max_retries,
+        };
+        // Save message to in-memory store (fast)
+        self.message_store
+            .lock()
+            .await
+            .insert(message_id, message.clone());
+
+        let result = subscriber.process_event(&message).await;
+        if let Err(e) = result {
+            eprintln!("Error processing event: {e}");
+            // Add failed message to retry queue
+            let mut failed_message = message;
+            failed_message.status = DeliveryStatus::Retrying;
+            subscriber.add_to_retry_queue(failed_message).await;
+        }
+    }
+
     /// Health check
     pub async fn health_check(&self) -> HashMap<String, ConnectionStatus> {
         let subscriptions = self.subscriptions.read().await;
@@ -495,6 +846,19 @@ impl EventSubscriptions {
                 health_status.insert(subscriber.id().to_string(), status);
             }
         }
+        drop(subscriptions);
+
+        let groups = self.consumer_groups.read().await;
+        for group in groups.values().flat_map(|type_groups| type_groups.values()) {
+            for member in &group.members {
+                let status = if member.is_healthy().await {
+                    ConnectionStatus::Connected
+                } else {
+                    ConnectionStatus::Disconnected
+                };
+                health_status.insert(member.id().to_string(), status);
+            }
+        }
 
         health_status
     }
@@ -510,6 +874,14 @@ impl EventSubscriptions {
                 subscriber.process_retry_queue(persistence).await;
             }
         }
+        drop(subscriptions);
+
+        let groups = self.consumer_groups.read().await;
+        for group in groups.values().flat_map(|type_groups| type_groups.values()) {
+            for member in &group.members {
+                member.process_retry_queue(persistence).await;
+            }
+        }
         Ok(())
     }
 
@@ -584,6 +956,18 @@ impl EventSubscriptions {
                 subscriber.add_to_retry_queue(message.clone()).await;
             }
         }
+        drop(subscriptions);
+
+        // ...and of one member per consumer group
+        let mut groups = self.consumer_groups.write().await;
+        for group in groups
+            .values_mut()
+            .flat_map(|type_groups| type_groups.values_mut())
+        {
+            if let Some(member) = group.select_member().await {
+                member.add_to_retry_queue(message.clone()).await;
+            }
+        }
     }
 
     /// Persist message to dead letter (failed messages only)
@@ -626,6 +1010,11 @@ impl EventSubscriptions {
     }
 }
 
+/// Consumer ID of a consumer group as used in message IDs and durable subscriptions
+fn group_consumer_id(group_id: &str) -> String {
+    format!("group:{group_id}")
+}
+
 impl Default for EventSubscriptions {
     fn default() -> Self {
         Self::new()
@@ -2,11 +2,13 @@ pub mod config;
 pub mod event_bus;
 pub mod event_subscription;
 pub mod sled_persistence;
+#[cfg(feature = "shutdown")]
+pub mod shutdown;
 
 pub use config::SubscriberConfig;
 pub use event_bus::EventBus;
 pub use event_subscription::{
     make_subscriber, make_subscriber_with_config, ConnectionStatus, DefaultEventRestorer,
-    DeliveryStatus, EventMessage, EventRestorer, SerializableEvent, Subscriber,
+    DeliveryStatus, EventMessage, EventRestorer, OrphanedMessage, SerializableEvent, Subscriber,
 };
-pub use sled_persistence::SledPersistenceManager;
+pub use sled_persistence::{PersistentSubscription, SledPersistenceManager};
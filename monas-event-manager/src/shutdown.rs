@@ -0,0 +1,179 @@
+//! Graceful shutdown helpers shared by the Monas HTTP servers
+//!
+//! Enabled with the `shutdown` feature.
+
+use std::future::Future;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+/// Environment variable holding the drain timeout in seconds
+pub const DRAIN_TIMEOUT_ENV: &str = "MONAS_SHUTDOWN_DRAIN_TIMEOUT_SECS";
+
+/// Drain timeout used when [`DRAIN_TIMEOUT_ENV`] is not set
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShutdownConfigError {
+    InvalidValue { name: &'static str, value: String },
+}
+
+impl std::fmt::Display for ShutdownConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidValue { name, value } => write!(f, "invalid value for {name}: {value}"),
+        }
+    }
+}
+
+impl std::error::Error for ShutdownConfigError {}
+
+/// How the server stopped after [`serve_with_drain_timeout`] returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrainOutcome {
+    /// All in-flight requests finished (or the server stopped on its own)
+    Completed,
+    /// In-flight requests were still running when the drain timeout elapsed
+    TimedOut,
+}
+
+/// Reads the drain timeout from [`DRAIN_TIMEOUT_ENV`], defaulting to [`DEFAULT_DRAIN_TIMEOUT`]
+pub fn drain_timeout_from_env() -> Result<Duration, ShutdownConfigError> {
+    drain_timeout_from_lookup(|key| std::env::var(key).ok())
+}
+
+fn drain_timeout_from_lookup(
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<Duration, ShutdownConfigError> {
+    match lookup(DRAIN_TIMEOUT_ENV).filter(|v| !v.trim().is_empty()) {
+        Some(value) => value
+            .trim()
+            .parse::<u64>()
+            .map(Duration::from_secs)
+            .map_err(|_| ShutdownConfigError::InvalidValue {
+                name: DRAIN_TIMEOUT_ENV,
+                value,
+            }),
+        None => Ok(DEFAULT_DRAIN_TIMEOUT),
+    }
+}
+
+/// Waits until SIGINT (Ctrl+C) or SIGTERM is received
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Runs a server until `signal` resolves, then gives in-flight requests at most
+/// `drain_timeout` to finish
+///
+/// `serve` receives the future to pass to `with_graceful_shutdown`. If the drain
+/// does not finish in time the server future is dropped and
+/// [`DrainOutcome::TimedOut`] is returned, so the caller can flush its stores and exit
+/// instead of waiting on a stuck connection forever.
+pub async fn serve_with_drain_timeout<S, F, E>(
+    signal: impl Future<Output = ()> + Send + 'static,
+    drain_timeout: Duration,
+    serve: S,
+) -> Result<DrainOutcome, E>
+where
+    S: FnOnce(BoxFuture<'static, ()>) -> F,
+    F: Future<Output = Result<(), E>>,
+{
+    let (signalled_tx, signalled_rx) = tokio::sync::oneshot::channel();
+    let signal = async move {
+        signal.await;
+        let _ = signalled_tx.send(());
+    }
+    .boxed();
+
+    let server = serve(signal);
+    tokio::pin!(server);
+    tokio::select! {
+        result = &mut server => return result.map(|()| DrainOutcome::Completed),
+        Ok(()) = signalled_rx => {}
+    }
+
+    match tokio::time::timeout(drain_timeout, server).await {
+        Ok(result) => result.map(|()| DrainOutcome::Completed),
+        Err(_) => Ok(DrainOutcome::TimedOut),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_timeout_reads_seconds_from_env() {
+        assert_eq!(drain_timeout_from_lookup(|_| None), Ok(DEFAULT_DRAIN_TIMEOUT));
+        assert_eq!(
+            drain_timeout_from_lookup(|_| Some("5".into())),
+            Ok(Duration::from_secs(5))
+        );
+        assert!(matches!(
+            drain_timeout_from_lookup(|_| Some("soon".into())),
+            Err(ShutdownConfigError::InvalidValue { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn returns_completed_when_drain_finishes_in_time() {
+        let outcome = serve_with_drain_timeout(
+            async {},
+            Duration::from_secs(5),
+            |signal| async move {
+                signal.await;
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ok::<(), ()>(())
+            },
+        )
+        .await;
+        assert_eq!(outcome, Ok(DrainOutcome::Completed));
+    }
+
+    #[tokio::test]
+    async fn stops_waiting_when_drain_times_out() {
+        let outcome = serve_with_drain_timeout(
+            async {},
+            Duration::from_millis(10),
+            |signal| async move {
+                signal.await;
+                std::future::pending::<()>().await;
+                Ok::<(), ()>(())
+            },
+        )
+        .await;
+        assert_eq!(outcome, Ok(DrainOutcome::TimedOut));
+    }
+
+    #[tokio::test]
+    async fn returns_server_error_without_signal() {
+        let outcome = serve_with_drain_timeout(
+            std::future::pending(),
+            Duration::from_millis(10),
+            |_signal| async { Err::<(), _>("bind failed") },
+        )
+        .await;
+        assert_eq!(outcome, Err("bind failed"));
+    }
+}
@@ -16,6 +16,16 @@ pub struct PersistentMessage {
     pub max_retries: u32,
 }
 
+/// Durable record of which event types a consumer subscribes to.
+///
+/// `subscriber_id` is the consumer as it appears in persisted message IDs: the
+/// subscriber ID for plain subscriptions, `group:{group_id}` for consumer groups.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PersistentSubscription {
+    pub subscriber_id: String,
+    pub event_types: Vec<String>,
+}
+
 #[derive(Clone)]
 pub struct SledPersistenceManager {
     db: Arc<sled::Db>,
@@ -96,6 +106,72 @@ impl SledPersistenceManager {
         Ok(())
     }
 
+    /// Persist a durable subscription, replacing any previous record of the consumer
+    pub fn save_subscription(
+        &self,
+        subscription: &PersistentSubscription,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let key = format!("subscription_{}", subscription.subscriber_id);
+        let value = serde_json::to_vec(subscription)
+            .map_err(|e| format!("Failed to serialize subscription: {e}"))?;
+        self.db
+            .insert(key, value)
+            .map_err(|e| format!("Failed to insert subscription: {e}"))?;
+        self.db
+            .flush()
+            .map_err(|e| format!("Failed to flush database: {e}"))?;
+        Ok(())
+    }
+
+    /// Load the durable subscription of a consumer, if any
+    pub fn load_subscription(
+        &self,
+        subscriber_id: &str,
+    ) -> Result<Option<PersistentSubscription>, Box<dyn std::error::Error + Send + Sync>> {
+        let key = format!("subscription_{subscriber_id}");
+        let Some(value) = self
+            .db
+            .get(key)
+            .map_err(|e| format!("Failed to read subscription: {e}"))?
+        else {
+            return Ok(None);
+        };
+        let subscription = serde_json::from_slice(&value)
+            .map_err(|e| format!("Failed to deserialize subscription: {e}"))?;
+        Ok(Some(subscription))
+    }
+
+    /// Load all durable subscriptions
+    pub fn load_subscriptions(
+        &self,
+    ) -> Result<Vec<PersistentSubscription>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut subscriptions = Vec::new();
+
+        for result in self.db.scan_prefix("subscription_") {
+            let (_, value) = result.map_err(|e| format!("Failed to iterate database: {e}"))?;
+            if let Ok(subscription) = serde_json::from_slice::<PersistentSubscription>(&value) {
+                subscriptions.push(subscription);
+            }
+        }
+
+        Ok(subscriptions)
+    }
+
+    /// Delete the durable subscription of a consumer
+    pub fn delete_subscription(
+        &self,
+        subscriber_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let key = format!("subscription_{subscriber_id}");
+        self.db
+            .remove(key)
+            .map_err(|e| format!("Failed to delete subscription: {e}"))?;
+        self.db
+            .flush()
+            .map_err(|e| format!("Failed to flush database: {e}"))?;
+        Ok(())
+    }
+
     /// Remove messages older than the given age (seconds)
     pub fn cleanup_old_messages(
         &self,
@@ -891,4 +967,35 @@ mod sled_persistence_tests {
         assert_eq!(messages[0].retry_count, 2);
         assert_eq!(messages[0].status, DeliveryStatus::Failed);
     }
+
+    #[test]
+    fn test_subscription_roundtrip_and_delete() {
+        let (manager, _temp_dir) = create_temp_manager();
+
+        let subscription = PersistentSubscription {
+            subscriber_id: "indexer".to_string(),
+            event_types: vec!["TestEvent".to_string()],
+        };
+        manager.save_subscription(&subscription).unwrap();
+        manager
+            .save_subscription(&PersistentSubscription {
+                subscriber_id: "group:workers".to_string(),
+                event_types: vec!["TestEvent".to_string(), "OtherEvent".to_string()],
+            })
+            .unwrap();
+
+        assert_eq!(
+            manager.load_subscription("indexer").unwrap(),
+            Some(subscription)
+        );
+        assert_eq!(manager.load_subscriptions().unwrap().len(), 2);
+
+        // Subscriptions are not counted as messages
+        assert!(manager.load_messages().unwrap().is_empty());
+        assert_eq!(manager.get_stats().unwrap()["message_count"], 0);
+
+        manager.delete_subscription("indexer").unwrap();
+        assert_eq!(manager.load_subscription("indexer").unwrap(), None);
+        assert_eq!(manager.load_subscriptions().unwrap().len(), 1);
+    }
 }
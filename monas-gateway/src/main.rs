@@ -1,12 +1,14 @@
 use axum::{
     extract::{Path, State},
-    http::{HeaderMap, StatusCode},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
 use monas_sdk::models::content::{
     CreateContentInput, DeleteContentInput, GetContentInput, UpdateContentInput,
 };
+use monas_sdk::models::doctor::DoctorInput;
 use monas_sdk::models::keypair::GenerateKeypairInput;
 use monas_sdk::models::share::{DecryptSharedContentInput, RevokeShareInput, ShareContentInput};
 use monas_sdk::models::state::{GetHistoryInput, GetLatestVersionInput, VerifyIntegrityInput};
@@ -40,10 +42,32 @@ async fn main() {
             .expect("failed to initialize MonasController persistence"),
     );
 
-    let app_state = AppState { controller };
+    // `monas-gateway doctor` はサーバーを起動せず、診断レポートを出力して終了する。
+    if std::env::args().nth(1).as_deref() == Some("doctor") {
+        run_doctor(controller).await;
+        return;
+    }
+
+    let app = router(AppState { controller });
 
-    let app = Router::new()
+    let port: u16 = std::env::var("MONAS_API_PORT")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(3000);
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    eprintln!("monas-gateway listening on http://{addr}");
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .expect("failed to bind");
+    axum::serve(listener, app).await.expect("server error");
+}
+
+fn router(app_state: AppState) -> Router {
+    Router::new()
         .route("/health", get(health))
+        .route("/doctor", get(doctor))
         .route("/keypair", post(generate_keypair))
         .route("/content", post(create_content))
         .route(
@@ -58,24 +82,50 @@ async fn main() {
         .route("/state/latest-version", post(get_latest_version))
         .route("/state/history", post(get_history))
         .route("/state/verify-integrity", post(verify_integrity))
-        .with_state(app_state);
+        .with_state(app_state)
+}
 
-    let port: u16 = std::env::var("MONAS_API_PORT")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(3000);
+async fn health() -> StatusCode {
+    StatusCode::OK
+}
 
-    let addr = SocketAddr::from(([127, 0, 0, 1], port));
-    eprintln!("monas-gateway listening on http://{addr}");
+/// 環境変数から診断の入力を組み立てる。
+fn doctor_input() -> DoctorInput {
+    DoctorInput {
+        dead_letter_dir: std::env::var("MONAS_DEAD_LETTER_DIR").ok(),
+        min_free_disk_bytes: std::env::var("MONAS_DOCTOR_MIN_FREE_BYTES")
+            .ok()
+            .and_then(|s| s.parse().ok()),
+    }
+}
 
-    let listener = tokio::net::TcpListener::bind(addr)
-        .await
-        .expect("failed to bind");
-    axum::serve(listener, app).await.expect("server error");
+/// 診断レポートを JSON で標準出力に書き出す。`Fail` の項目があれば終了コード 1。
+async fn run_doctor(controller: Arc<MonasController>) {
+    let response = controller.doctor_async(doctor_input()).await;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&response).expect("doctor report is serializable")
+    );
+    let healthy = response
+        .data
+        .as_ref()
+        .is_some_and(|report| report.is_healthy());
+    if !healthy {
+        std::process::exit(1);
+    }
 }
 
-async fn health() -> StatusCode {
-    StatusCode::OK
+async fn doctor(
+    State(state): State<AppState>,
+) -> (
+    StatusCode,
+    Json<ApiResponse<monas_sdk::models::doctor::DoctorReport>>,
+) {
+    api_json(
+        Arc::clone(&state.controller)
+            .doctor_async(doctor_input())
+            .await,
+    )
 }
 
 async fn generate_keypair(
@@ -111,15 +161,32 @@ async fn create_content(
     )
 }
 
+/// `If-None-Match` を SDK に渡し、一致すれば 304、そうでなければ ETag 付きで返す。
 async fn get_content(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> (
-    StatusCode,
-    Json<ApiResponse<monas_sdk::models::content::GetContentOutput>>,
-) {
-    let input = GetContentInput { content_id: id };
-    api_json(Arc::clone(&state.controller).get_content_async(input).await)
+    headers: HeaderMap,
+) -> Response {
+    let input = GetContentInput {
+        content_id: id,
+        if_none_match: headers
+            .get(header::IF_NONE_MATCH)
+            .and_then(|v| v.to_str().ok())
+            .map(ToOwned::to_owned),
+    };
+    let response = Arc::clone(&state.controller).get_content_async(input).await;
+    let etag = response
+        .data
+        .as_ref()
+        .and_then(|output| HeaderValue::from_str(&format!("\"{}\"", output.version)).ok());
+    let not_modified = response.data.as_ref().is_some_and(|o| o.not_modified);
+    match etag {
+        Some(etag) if not_modified => {
+            (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response()
+        }
+        Some(etag) => ([(header::ETAG, etag)], api_json(response)).into_response(),
+        None => api_json(response).into_response(),
+    }
 }
 
 async fn update_content(
@@ -315,8 +382,11 @@ fn build_state_node_auth_context(headers: &HeaderMap) -> Result<StateNodeAuthCon
 #[allow(deprecated)] // tests use the test/dev-only `with_state_node_url` constructor
 mod tests {
     use super::*;
+    use axum::body::Body;
+    use axum::http::{Method, Request};
     use monas_sdk::models::content::{ContentMetadata, CreateContentInput};
     use std::sync::Arc;
+    use tower::ServiceExt;
 
     fn headers_with_timestamp(value: &str) -> HeaderMap {
         let mut headers = HeaderMap::new();
@@ -339,6 +409,7 @@ mod tests {
             metadata: Some(ContentMetadata {
                 name: Some("gateway.txt".into()),
                 content_type: None,
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -374,6 +445,7 @@ mod tests {
             metadata: Some(ContentMetadata {
                 name: Some("invalid.txt".into()),
                 content_type: None,
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -432,9 +504,143 @@ mod tests {
         let state = State(AppState { controller });
         let path = Path(String::from("missing-content-id"));
 
-        let (status, Json(body)) = get_content(state, path).await;
-        assert_eq!(status, StatusCode::NOT_FOUND);
-        assert!(!body.success);
-        assert!(matches!(body.error, Some(ApiError::NotFound(_))));
+        let response = get_content(state, path, HeaderMap::new()).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.headers().get(header::ETAG).is_none());
+        let body = response_json(response).await;
+        assert_eq!(body["success"], false);
+        assert_eq!(body["error"]["type"], "NotFound");
+    }
+
+    // ========================================================================
+    // ルーター経由のテスト
+    // ========================================================================
+
+    fn test_router() -> Router {
+        let controller = Arc::new(MonasController::with_state_node_url(
+            "http://127.0.0.1:8080",
+        ));
+        router(AppState { controller })
+    }
+
+    async fn response_json(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    /// ルーターにリクエストを送る。`body` があれば JSON として送る。
+    async fn send(
+        method: Method,
+        uri: &str,
+        headers: &[(&str, &str)],
+        body: Option<serde_json::Value>,
+    ) -> Response {
+        let mut request = Request::builder().method(method).uri(uri);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let request = match body {
+            Some(body) => request
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        };
+        test_router().oneshot(request.unwrap()).await.unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn router_serves_health_and_unknown_routes() {
+        let response = send(Method::GET, "/health", &[], None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(Method::GET, "/unknown", &[], None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = send(Method::GET, "/share", &[], None).await;
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn router_requires_request_timestamp_on_state_node_routes() {
+        let create = serde_json::json!({ "content": "Z2F0ZXdheS1jb250ZW50" });
+        let update = serde_json::json!({
+            "local_content_id": "ignored",
+            "remote_content_id": "remote",
+            "content": "Z2F0ZXdheS1jb250ZW50",
+        });
+        let delete = serde_json::json!({
+            "local_content_id": "ignored",
+            "remote_content_id": "remote",
+        });
+        for (method, uri, body) in [
+            (Method::POST, "/content", create),
+            (Method::PUT, "/content/local", update),
+            (Method::DELETE, "/content/local", delete),
+        ] {
+            let response = send(method.clone(), uri, &[], Some(body.clone())).await;
+            assert_eq!(
+                response.status(),
+                StatusCode::UNAUTHORIZED,
+                "{method} {uri}"
+            );
+            let json = response_json(response).await;
+            assert_eq!(json["success"], false);
+            assert_eq!(json["error"]["type"], "Unauthorized");
+            assert!(json["trace_id"].is_string());
+
+            let response = send(
+                method.clone(),
+                uri,
+                &[("x-request-timestamp", "yesterday")],
+                Some(body),
+            )
+            .await;
+            assert_eq!(
+                response.status(),
+                StatusCode::UNAUTHORIZED,
+                "{method} {uri}"
+            );
+            let json = response_json(response).await;
+            assert!(json["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("valid Unix timestamp"));
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn router_rejects_malformed_bodies() {
+        let response = send(
+            Method::POST,
+            "/content",
+            &[],
+            Some(serde_json::json!({ "metadata": {} })),
+        )
+        .await;
+        assert!(response.status().is_client_error());
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/keypair")
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = test_router().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn router_returns_not_found_without_etag_for_missing_content() {
+        let response = send(
+            Method::GET,
+            "/content/missing-content-id",
+            &[("if-none-match", "\"1\"")],
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.headers().get(header::ETAG).is_none());
+        let json = response_json(response).await;
+        assert_eq!(json["error"]["type"], "NotFound");
     }
 }
@@ -19,6 +19,8 @@ pub enum ApiError {
     NotFound(String),
     /// 競合（同時更新等） (409)
     Conflict(String),
+    /// サイズ・クォータの上限超過 (413)
+    PayloadTooLarge(String),
     /// State Nodeとの通信タイムアウト (408)
     Timeout(String),
     /// 予期せぬ例外 (500)
@@ -33,6 +35,7 @@ impl fmt::Display for ApiError {
             ApiError::Forbidden(msg) => write!(f, "Forbidden: {msg}"),
             ApiError::NotFound(msg) => write!(f, "Not found: {msg}"),
             ApiError::Conflict(msg) => write!(f, "Conflict: {msg}"),
+            ApiError::PayloadTooLarge(msg) => write!(f, "Payload too large: {msg}"),
             ApiError::Timeout(msg) => write!(f, "Timeout: {msg}"),
             ApiError::Internal(msg) => write!(f, "Internal error: {msg}"),
         }
@@ -50,6 +53,7 @@ impl ApiError {
             ApiError::Forbidden(_) => 403,
             ApiError::NotFound(_) => 404,
             ApiError::Conflict(_) => 409,
+            ApiError::PayloadTooLarge(_) => 413,
             ApiError::Timeout(_) => 408,
             ApiError::Internal(_) => 500,
         }
@@ -80,6 +84,7 @@ mod tests {
         assert_eq!(ApiError::Forbidden("test".into()).status_code(), 403);
         assert_eq!(ApiError::NotFound("test".into()).status_code(), 404);
         assert_eq!(ApiError::Conflict("test".into()).status_code(), 409);
+        assert_eq!(ApiError::PayloadTooLarge("test".into()).status_code(), 413);
         assert_eq!(ApiError::Timeout("test".into()).status_code(), 408);
         assert_eq!(ApiError::Internal("test".into()).status_code(), 500);
     }
@@ -14,13 +14,16 @@
 //! sync API は引き続き直接呼べるが、tokio runtime 上で呼ぶときは必ず
 //! async ラッパー側を使うこと。
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::common::{ApiError, ApiResponse, StateNodeAuthContext};
+use crate::models::bulk::{BulkOptions, BulkOutput};
 use crate::models::content::{
     CreateContentInput, CreateContentOutput, DeleteContentInput, DeleteContentOutput,
     GetContentInput, GetContentOutput, UpdateContentInput, UpdateContentOutput,
 };
+use crate::models::doctor::{DoctorInput, DoctorReport};
 use crate::models::keypair::{GenerateKeypairInput, GenerateKeypairOutput};
 use crate::models::share::{
     DecryptSharedContentInput, DecryptSharedContentOutput, RevokeShareInput, RevokeShareOutput,
@@ -99,6 +102,34 @@ impl MonasController {
         }
     }
 
+    /// `upload_dir` の async 版。
+    pub async fn upload_dir_async(
+        self: Arc<Self>,
+        path: PathBuf,
+        options: BulkOptions,
+        auth: Option<StateNodeAuthContext>,
+    ) -> ApiResponse<BulkOutput<CreateContentOutput>> {
+        match tokio::task::spawn_blocking(move || self.upload_dir(path, &options, auth.as_ref()))
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => map_join_error(e, fallback_trace_id()),
+        }
+    }
+
+    /// `download_many` の async 版。
+    pub async fn download_many_async(
+        self: Arc<Self>,
+        content_ids: Vec<String>,
+        options: BulkOptions,
+    ) -> ApiResponse<BulkOutput<GetContentOutput>> {
+        match tokio::task::spawn_blocking(move || self.download_many(&content_ids, &options)).await
+        {
+            Ok(resp) => resp,
+            Err(e) => map_join_error(e, fallback_trace_id()),
+        }
+    }
+
     /// `share_content` の async 版。
     pub async fn share_content_async(
         self: Arc<Self>,
@@ -183,4 +214,12 @@ impl MonasController {
             Err(e) => map_join_error(e, fallback_trace_id()),
         }
     }
+
+    /// `doctor` の async 版。
+    pub async fn doctor_async(self: Arc<Self>, input: DoctorInput) -> ApiResponse<DoctorReport> {
+        match tokio::task::spawn_blocking(move || self.doctor(input)).await {
+            Ok(resp) => resp,
+            Err(e) => map_join_error(e, fallback_trace_id()),
+        }
+    }
 }
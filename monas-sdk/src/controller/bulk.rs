@@ -0,0 +1,230 @@
+//! 複数コンテンツをまとめて扱う一括操作 (`upload_dir` / `download_many`)。
+//!
+//! 項目ごとに既存の `create_content` / `get_content` を呼び出し、
+//! `BulkOptions::concurrency` 本のワーカースレッドで並行に処理する。
+//! ワーカーは `std::thread::scope` の中で起動するため、メソッドから戻る時点で
+//! すべての項目の処理が終わっている。
+//!
+//! 1 項目の失敗で残りの処理は止めず、結果は項目ごとに `BulkOutput` にまとめて返す。
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::common::{
+    encode_base64url, generate_trace_id, ApiError, ApiResponse, StateNodeAuthContext,
+};
+use crate::models::bulk::{BulkItemOutcome, BulkItemResult, BulkOptions, BulkOutput, BulkProgress};
+use crate::models::content::{
+    ContentMetadata, CreateContentInput, CreateContentOutput, GetContentInput, GetContentOutput,
+};
+
+use super::MonasController;
+
+impl MonasController {
+    /// ディレクトリ配下のファイルをすべてコンテンツとして作成し、State Node に登録する。
+    ///
+    /// - サブディレクトリも再帰的にたどり、シンボリックリンクは対象外とする
+    /// - 各ファイルの `metadata.name` はディレクトリからの相対パス（区切りは `/`）
+    /// - ファイルの読み込みは各ワーカーが処理する直前に行う
+    pub fn upload_dir(
+        &self,
+        path: impl AsRef<Path>,
+        options: &BulkOptions,
+        auth: Option<&StateNodeAuthContext>,
+    ) -> ApiResponse<BulkOutput<CreateContentOutput>> {
+        let trace_id = generate_trace_id();
+        let root = path.as_ref();
+
+        if let Err(e) = Self::validate_bulk_options(options) {
+            return ApiResponse::error(e, trace_id);
+        }
+        if !root.is_dir() {
+            return ApiResponse::error(
+                ApiError::Validation(format!("{} is not a directory", root.display())),
+                trace_id,
+            );
+        }
+
+        let mut files = Vec::new();
+        if let Err(e) = Self::collect_files(root, &mut files) {
+            return ApiResponse::error(
+                ApiError::Internal(format!("Failed to read directory {}: {e}", root.display())),
+                trace_id,
+            );
+        }
+        files.sort();
+
+        let items = files
+            .into_iter()
+            .map(|file| {
+                let name = file
+                    .strip_prefix(root)
+                    .unwrap_or(&file)
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                (name, file)
+            })
+            .collect();
+
+        let output = Self::run_bulk(items, options, |name, file| {
+            let bytes = match std::fs::read(file) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    return ApiResponse::error(
+                        ApiError::Internal(format!("Failed to read {}: {e}", file.display())),
+                        generate_trace_id(),
+                    );
+                }
+            };
+            self.create_content(
+                CreateContentInput {
+                    content: encode_base64url(&bytes),
+                    metadata: Some(ContentMetadata {
+                        name: Some(name.to_string()),
+                        content_type: None,
+                        size: None,
+                        created_at: None,
+                        updated_at: None,
+                    }),
+                },
+                auth,
+            )
+        });
+
+        ApiResponse::success(output, trace_id)
+    }
+
+    /// 複数のコンテンツを取得・復号する。
+    ///
+    /// 結果は `content_ids` と同じ順に並ぶ。
+    pub fn download_many(
+        &self,
+        content_ids: &[String],
+        options: &BulkOptions,
+    ) -> ApiResponse<BulkOutput<GetContentOutput>> {
+        let trace_id = generate_trace_id();
+
+        if let Err(e) = Self::validate_bulk_options(options) {
+            return ApiResponse::error(e, trace_id);
+        }
+
+        let items = content_ids.iter().map(|id| (id.clone(), ())).collect();
+        let output = Self::run_bulk(items, options, |content_id, _| {
+            self.get_content(GetContentInput {
+                content_id: content_id.to_string(),
+                if_none_match: None,
+            })
+        });
+
+        ApiResponse::success(output, trace_id)
+    }
+
+    fn validate_bulk_options(options: &BulkOptions) -> Result<(), ApiError> {
+        if options.concurrency == 0 {
+            return Err(ApiError::Validation(
+                "concurrency must be at least 1".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// `dir` 配下の通常ファイルを再帰的に集める。
+    fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                Self::collect_files(&entry.path(), files)?;
+            } else if file_type.is_file() {
+                files.push(entry.path());
+            }
+        }
+        Ok(())
+    }
+
+    /// `items` を最大 `options.concurrency` 並列で `op` に渡し、結果を入力順にまとめる。
+    ///
+    /// 各ワーカーは次の項目を取る前に中断要求を確認する。中断後に残った項目は
+    /// `Cancelled` として結果に含める。
+    fn run_bulk<I, T, F>(items: Vec<(String, I)>, options: &BulkOptions, op: F) -> BulkOutput<T>
+    where
+        I: Sync,
+        T: Send,
+        F: Fn(&str, &I) -> ApiResponse<T> + Sync,
+    {
+        let total = items.len();
+        let next = AtomicUsize::new(0);
+        let outcomes: Mutex<Vec<Option<BulkItemOutcome<T>>>> =
+            Mutex::new((0..total).map(|_| None).collect());
+        let progress = Mutex::new(BulkProgress {
+            total,
+            completed: 0,
+            succeeded: 0,
+            failed: 0,
+        });
+
+        let is_cancelled = || {
+            options
+                .cancellation
+                .as_ref()
+                .is_some_and(|c| c.is_cancelled())
+        };
+
+        let worker = || loop {
+            if is_cancelled() {
+                break;
+            }
+            let index = next.fetch_add(1, Ordering::SeqCst);
+            let Some((key, item)) = items.get(index) else {
+                break;
+            };
+
+            let response = op(key, item);
+            let outcome = match (response.data, response.error) {
+                (Some(data), _) if response.success => BulkItemOutcome::Succeeded {
+                    data,
+                    trace_id: response.trace_id,
+                },
+                (_, error) => BulkItemOutcome::Failed {
+                    error: error
+                        .unwrap_or_else(|| ApiError::Internal("operation returned no data".into())),
+                    trace_id: response.trace_id,
+                },
+            };
+
+            // 進捗の通知は直列化し、呼び出し側が受け取る値が単調に増えるようにする
+            let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+            progress.completed += 1;
+            match outcome {
+                BulkItemOutcome::Succeeded { .. } => progress.succeeded += 1,
+                _ => progress.failed += 1,
+            }
+            outcomes.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(outcome);
+            if let Some(on_progress) = &options.on_progress {
+                on_progress(&progress);
+            }
+        };
+
+        let workers = options.concurrency.min(total);
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(&worker);
+            }
+        });
+
+        let outcomes = outcomes.into_inner().unwrap_or_else(|e| e.into_inner());
+        BulkOutput::new(
+            items
+                .into_iter()
+                .zip(outcomes)
+                .map(|((key, _), outcome)| BulkItemResult {
+                    key,
+                    outcome: outcome.unwrap_or(BulkItemOutcome::Cancelled),
+                })
+                .collect(),
+        )
+    }
+}
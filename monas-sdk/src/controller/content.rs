@@ -16,16 +16,16 @@ use crate::models::state_node::{
 };
 
 use monas_content::application_service::content_service::{
-    ContentEncryptionKeyStore, ContentRepository, ContentService, CreateContentCommand,
-    DeleteContentCommand, DeleteError, FetchError, RestoreDeletedContentCommand,
-    RestoreDeletedError, UpdateContentCommand, UpdateError,
+    if_none_match_matches, ConditionalFetchResult, ContentEncryptionKeyStore, ContentRepository,
+    ContentService, CreateContentCommand, CreateError, DeleteContentCommand, DeleteError,
+    FetchError, RestoreDeletedContentCommand, RestoreDeletedError, UpdateContentCommand,
+    UpdateError,
 };
 use monas_content::domain::content::{Content, ContentEncryptionKey, StorageProvider};
 use monas_content::domain::content_id::ContentId;
 use monas_content::infrastructure::{
-    content_id::Sha256ContentIdGenerator,
-    encryption::{Aes256CtrContentEncryption, OsRngContentEncryptionKeyGenerator},
-    MultiStorageRepository,
+    cipher_suite::CipherSuite, content_id::Sha256ContentIdGenerator,
+    encryption::OsRngContentEncryptionKeyGenerator, MultiStorageRepository,
 };
 
 use super::MonasController;
@@ -34,11 +34,13 @@ use super::MonasController;
 ///
 /// CEK ストアは `Arc<dyn ContentEncryptionKeyStore + Send + Sync>` を受けるので、
 /// 実行時に in-memory / sled などの persistence backend を切り替えられる。
+/// 暗号化は [`CipherSuite`] で行うため、AES-256-GCM で暗号化しつつ、以前の
+/// AES-256-CTR の暗号文や共有された暗号文もアルゴリズムのヘッダを見て復号できる。
 pub(super) type ContentServiceInstance = ContentService<
     Sha256ContentIdGenerator,
     MultiStorageRepository,
     OsRngContentEncryptionKeyGenerator,
-    Aes256CtrContentEncryption,
+    CipherSuite,
     DynCekStore,
 >;
 
@@ -253,6 +255,7 @@ impl MonasController {
             404 => ApiResponse::error(ApiError::NotFound(message), trace_id),
             408 => ApiResponse::error(ApiError::Timeout(message), trace_id),
             409 => ApiResponse::error(ApiError::Conflict(message), trace_id),
+            413 => ApiResponse::error(ApiError::PayloadTooLarge(message), trace_id),
             _ => ApiResponse::error(ApiError::Internal(message), trace_id),
         }
     }
@@ -304,6 +307,9 @@ impl MonasController {
             UpdateError::MissingEncryptedContent => {
                 ApiError::Internal("Missing encrypted content".into())
             }
+            UpdateError::QuotaExceeded(err) => ApiError::PayloadTooLarge(err.to_string()),
+            UpdateError::QuotaStore(err) => ApiError::Internal(format!("Quota store error: {err}")),
+            UpdateError::ChecksumMismatch => ApiError::Validation(e.to_string()),
         }
     }
 
@@ -337,6 +343,12 @@ impl MonasController {
         }
     }
 
+    /// 文字列をContentIdとしてパースする（形式不正はValidationエラー）
+    fn parse_content_id(value: &str) -> Result<ContentId, ApiError> {
+        ContentId::new(value.to_string())
+            .map_err(|e| ApiError::Validation(format!("Invalid content_id: {e}")))
+    }
+
     /// content_idのバリデーション
     /// エラーがある場合はSome(ApiResponse)を返し、成功時はNoneを返す
     fn validate_content_id<T>(content_id: &str, trace_id: String) -> Option<ApiResponse<T>> {
@@ -389,7 +401,7 @@ impl MonasController {
         let fetched = self.content_service.fetch(content_id, None)?;
         Ok(LocalContentSnapshot {
             content_id: fetched.content_id,
-            raw_content: fetched.raw_content,
+            raw_content: fetched.raw_content.to_vec(),
             name: fetched.metadata.name().to_string(),
             path: fetched.metadata.path().to_string(),
             provider: fetched.metadata.provider().cloned(),
@@ -780,10 +792,14 @@ impl MonasController {
             name,
             path,
             provider: None,
+            content_sha256: None,
         };
 
         let result = match content_service.create(cmd) {
             Ok(result) => result,
+            Err(CreateError::QuotaExceeded(err)) => {
+                return ApiResponse::error(ApiError::PayloadTooLarge(err.to_string()), trace_id);
+            }
             Err(e) => {
                 return ApiResponse::error(
                     ApiError::Internal(format!("Failed to create content: {e}")),
@@ -832,7 +848,8 @@ impl MonasController {
     /// 処理フロー:
     /// 1. 入力のバリデーション（content_id）
     /// 2. ContentIdに変換
-    /// 3. ContentService::fetchを呼び出して以下を実行:
+    /// 3. ContentService::fetch_if_none_matchを呼び出して以下を実行:
+    ///    - `if_none_match` が現在の版と一致すれば復号せず `not_modified` を返す
     ///    - リポジトリから暗号化されたコンテンツを取得
     ///    - キーストアからCEKを取得
     ///    - CEKでコンテンツを復号
@@ -846,12 +863,28 @@ impl MonasController {
             return response;
         }
 
-        let content_id = ContentId::new(input.content_id.clone());
+        let content_id = match Self::parse_content_id(&input.content_id) {
+            Ok(id) => id,
+            Err(e) => return ApiResponse::error(e, trace_id),
+        };
 
         let content_service = &self.content_service;
 
-        let result = match content_service.fetch(content_id, None) {
-            Ok(result) => result,
+        let if_none_match = input.if_none_match.as_deref();
+        let result = match content_service.fetch_if_none_match(content_id.clone(), None, |v| {
+            if_none_match.is_some_and(|known| if_none_match_matches(known, v))
+        }) {
+            Ok(ConditionalFetchResult::Modified(result)) => result,
+            Ok(ConditionalFetchResult::NotModified { version }) => {
+                let output = GetContentOutput {
+                    content_id: content_id.as_str().to_string(),
+                    content: String::new(),
+                    metadata: None,
+                    version,
+                    not_modified: true,
+                };
+                return ApiResponse::success(output, trace_id);
+            }
             Err(e) => {
                 return ApiResponse::error(Self::map_fetch_error(e), trace_id);
             }
@@ -861,7 +894,8 @@ impl MonasController {
 
         let metadata = crate::models::content::ContentMetadata {
             name: Some(result.metadata.name().to_string()),
-            content_type: None,
+            content_type: result.metadata.content_type().map(str::to_string),
+            size: result.metadata.size(),
             created_at: Some(result.metadata.created_at().to_rfc3339()),
             updated_at: Some(result.metadata.updated_at().to_rfc3339()),
         };
@@ -870,6 +904,8 @@ impl MonasController {
             content_id: result.content_id.as_str().to_string(),
             content: content_base64url,
             metadata: Some(metadata),
+            version: result.version,
+            not_modified: false,
         };
 
         ApiResponse::success(output, trace_id)
@@ -913,7 +949,10 @@ impl MonasController {
 
         // 2. ContentIdに変換
         let base_version_id = input.local_content_id.clone();
-        let content_id = ContentId::new(base_version_id.clone());
+        let content_id = match Self::parse_content_id(&base_version_id) {
+            Ok(id) => id,
+            Err(e) => return ApiResponse::error(e, trace_id),
+        };
         let before_update = match self.capture_stored_content_snapshot(&content_id) {
             Ok(snapshot) => snapshot,
             Err(error) => return ApiResponse::error(error, trace_id),
@@ -949,6 +988,7 @@ impl MonasController {
             new_name,
             new_raw_content,
             provider: None,
+            new_raw_content_sha256: None,
         };
 
         let result = match content_service.update(cmd) {
@@ -1026,7 +1066,10 @@ impl MonasController {
         }
 
         // 2. ContentIdに変換
-        let content_id = ContentId::new(input.local_content_id.clone());
+        let content_id = match Self::parse_content_id(&input.local_content_id) {
+            Ok(id) => id,
+            Err(e) => return ApiResponse::error(e, trace_id),
+        };
 
         let snapshot = match self.capture_local_content_snapshot(content_id.clone()) {
             Ok(snapshot) => snapshot,
@@ -0,0 +1,335 @@
+//! Monas インストールの診断 (`doctor`)。
+//!
+//! State Node / Account への疎通、ローカル sled ストア、CEK ストア、ピア接続数、
+//! 時刻ずれ、空き容量、未処理の dead letter をまとめて検査し、
+//! サポート問い合わせやバグ報告に添付できるレポートを返す。
+//!
+//! 各検査は独立しており、1 つが失敗しても残りは実行する。
+
+use std::path::Path;
+
+use monas_content::application_service::content_service::ContentEncryptionKeyStore;
+use monas_content::domain::content::encryption::ContentEncryptionKey;
+use monas_content::domain::content_id::ContentIdGenerator;
+use monas_content::infrastructure::content_id::Sha256ContentIdGenerator;
+use serde::Deserialize;
+
+use crate::common::{generate_trace_id, ApiResponse, PersistenceConfig};
+use crate::models::doctor::{DoctorCheck, DoctorInput, DoctorReport, DEFAULT_MIN_FREE_DISK_BYTES};
+
+use super::MonasController;
+
+/// CEK ストアの書き込み検査に使う ContentId の元になるラベル。
+const KEY_STORE_PROBE_LABEL: &[u8] = b"monas-sdk/doctor/key-store-probe";
+
+/// State Node `GET /health` のレスポンス
+#[derive(Debug, Deserialize)]
+struct StateNodeHealth {
+    node_id: String,
+}
+
+/// State Node `GET /health/ready` のレスポンス
+#[derive(Debug, Deserialize)]
+struct StateNodeReadiness {
+    peers: usize,
+    database: String,
+}
+
+/// HTTP プローブの結果
+struct Probe {
+    status: u16,
+    /// `Date` レスポンスヘッダ
+    date: Option<String>,
+    body: String,
+}
+
+impl MonasController {
+    /// ローカルの Monas 構成を診断し、項目ごとの結果をまとめたレポートを返す。
+    ///
+    /// 診断自体は常に成功として返し、各項目の結果は `DoctorReport::checks` に入る。
+    pub fn doctor(&self, input: DoctorInput) -> ApiResponse<DoctorReport> {
+        let trace_id = generate_trace_id();
+        let mut checks = Vec::new();
+
+        let health = self.probe(&format!("{}/health", self.state_node_url));
+        checks.push(Self::check_state_node_reachable(&health));
+        checks.push(self.check_clock_skew(&health));
+        checks.extend(self.check_state_node_readiness());
+        checks.push(self.check_account_reachable());
+        checks.push(self.check_local_store());
+        checks.push(self.check_key_store());
+        checks.push(
+            self.check_disk_space(
+                input
+                    .min_free_disk_bytes
+                    .unwrap_or(DEFAULT_MIN_FREE_DISK_BYTES),
+            ),
+        );
+        checks.push(Self::check_dead_letters(input.dead_letter_dir.as_deref()));
+
+        ApiResponse::success(
+            DoctorReport::new(
+                self.state_node_url.clone(),
+                self.account_url.clone(),
+                chrono::Utc::now().to_rfc3339(),
+                checks,
+            ),
+            trace_id,
+        )
+    }
+
+    /// GET リクエストを送り、HTTP ステータスに関わらず結果を返す。
+    fn probe(&self, url: &str) -> Result<Probe, String> {
+        let resp = self
+            .agent
+            .get(url)
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .call()
+            .map_err(|e| e.to_string())?;
+        let status = resp.status().as_u16();
+        let date = resp
+            .headers()
+            .get("date")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let body = resp
+            .into_body()
+            .read_to_string()
+            .map_err(|e| format!("failed to read response body: {e}"))?;
+        Ok(Probe { status, date, body })
+    }
+
+    fn check_state_node_reachable(health: &Result<Probe, String>) -> DoctorCheck {
+        const NAME: &str = "state_node_reachable";
+        match health {
+            Ok(probe) if (200..300).contains(&probe.status) => {
+                match serde_json::from_str::<StateNodeHealth>(&probe.body) {
+                    Ok(h) => DoctorCheck::ok(NAME, format!("node_id={}", h.node_id)),
+                    Err(e) => DoctorCheck::warn(
+                        NAME,
+                        format!("unexpected /health response: {e}"),
+                        "MONAS_STATE_NODE_URL may point to a service other than a State Node",
+                    ),
+                }
+            }
+            Ok(probe) => DoctorCheck::fail(
+                NAME,
+                format!("/health returned HTTP {}", probe.status),
+                "check the State Node logs",
+            ),
+            Err(e) => DoctorCheck::fail(
+                NAME,
+                e.clone(),
+                "start the State Node or fix MONAS_STATE_NODE_URL",
+            ),
+        }
+    }
+
+    /// State Node の `Date` ヘッダとローカル時刻を比較する。
+    ///
+    /// 署名付きリクエストは `request_timestamp_skew` を超えるずれで拒否されるため、
+    /// その半分を超えたら警告、超えたら失敗とする。
+    fn check_clock_skew(&self, health: &Result<Probe, String>) -> DoctorCheck {
+        const NAME: &str = "clock_skew";
+        let Some(date) = health.as_ref().ok().and_then(|p| p.date.as_deref()) else {
+            return DoctorCheck::skipped(NAME, "State Node did not return a Date header");
+        };
+        let remote = match chrono::DateTime::parse_from_rfc2822(date) {
+            Ok(t) => t.timestamp(),
+            Err(e) => {
+                return DoctorCheck::skipped(NAME, format!("unparseable Date header {date:?}: {e}"))
+            }
+        };
+        let skew = remote.abs_diff(chrono::Utc::now().timestamp());
+        let max = self.request_timestamp_skew.as_secs();
+        let detail = format!("{skew}s difference from State Node (max {max}s)");
+        if skew > max {
+            DoctorCheck::fail(
+                NAME,
+                detail,
+                "signed requests will be rejected; enable NTP on this host and the State Node",
+            )
+        } else if skew > max / 2 {
+            DoctorCheck::warn(NAME, detail, "enable NTP on this host and the State Node")
+        } else {
+            DoctorCheck::ok(NAME, detail)
+        }
+    }
+
+    /// State Node の DB 状態とピア接続数を検査する。
+    fn check_state_node_readiness(&self) -> Vec<DoctorCheck> {
+        const DB: &str = "state_node_database";
+        const PEERS: &str = "peer_connectivity";
+        let readiness = self
+            .probe(&format!("{}/health/ready", self.state_node_url))
+            .and_then(|p| {
+                serde_json::from_str::<StateNodeReadiness>(&p.body)
+                    .map_err(|e| format!("unexpected /health/ready response: {e}"))
+            });
+        let readiness = match readiness {
+            Ok(r) => r,
+            Err(e) => {
+                return vec![
+                    DoctorCheck::skipped(DB, e.clone()),
+                    DoctorCheck::skipped(PEERS, e),
+                ]
+            }
+        };
+
+        let db = if readiness.database == "ok" {
+            DoctorCheck::ok(DB, "database responsive")
+        } else {
+            DoctorCheck::fail(
+                DB,
+                format!("database reported {:?}", readiness.database),
+                "check free space and permissions of the State Node data directory",
+            )
+        };
+        let peers = if readiness.peers > 0 {
+            DoctorCheck::ok(PEERS, format!("{} connected peer(s)", readiness.peers))
+        } else {
+            DoctorCheck::warn(
+                PEERS,
+                "no connected peers; content will not be replicated",
+                "check the State Node --bootstrap addresses and that its libp2p listen port is reachable",
+            )
+        };
+        vec![db, peers]
+    }
+
+    fn check_account_reachable(&self) -> DoctorCheck {
+        const NAME: &str = "account_reachable";
+        match self.probe(&self.account_url) {
+            // Account は専用の health エンドポイントを持たないため、HTTP 応答があれば疎通とみなす。
+            Ok(probe) if probe.status < 500 => {
+                DoctorCheck::ok(NAME, format!("HTTP {}", probe.status))
+            }
+            Ok(probe) => DoctorCheck::fail(
+                NAME,
+                format!("HTTP {}", probe.status),
+                "check the Account service logs",
+            ),
+            Err(e) => DoctorCheck::fail(
+                NAME,
+                e,
+                "start the Account service or fix MONAS_ACCOUNT_URL",
+            ),
+        }
+    }
+
+    /// ローカル sled DB の整合性を検査する。
+    fn check_local_store(&self) -> DoctorCheck {
+        const NAME: &str = "local_store";
+        let Some(db) = &self.local_db else {
+            return DoctorCheck::warn(
+                NAME,
+                "in-memory persistence; CEKs and shares are lost on restart",
+                "set MONAS_PERSISTENCE_DIR (MonasConfig::with_persistence_dir)",
+            );
+        };
+        match db.checksum() {
+            Ok(_) => DoctorCheck::ok(
+                NAME,
+                format!(
+                    "sled healthy ({} entries, {} bytes on disk)",
+                    db.len(),
+                    db.size_on_disk().unwrap_or(0)
+                ),
+            ),
+            Err(e) => DoctorCheck::fail(
+                NAME,
+                format!("sled checksum failed: {e}"),
+                "restore the persistence directory from backup",
+            ),
+        }
+    }
+
+    /// CEK ストアに検査用の鍵を書き込み、読み出して削除できるか確認する。
+    fn check_key_store(&self) -> DoctorCheck {
+        const NAME: &str = "key_store";
+        let probe_id = Sha256ContentIdGenerator.generate(KEY_STORE_PROBE_LABEL);
+        let probe_key = ContentEncryptionKey(uuid::Uuid::new_v4().as_bytes().to_vec());
+        let store = &self.content_service.cek_store;
+
+        let result = store
+            .save(&probe_id, &probe_key)
+            .and_then(|_| store.load(&probe_id))
+            .and_then(|loaded| {
+                let matches = loaded.is_some_and(|k| k.0 == probe_key.0);
+                store.delete(&probe_id).map(|_| matches)
+            });
+        match result {
+            Ok(true) => DoctorCheck::ok(NAME, "read/write round-trip succeeded"),
+            Ok(false) => DoctorCheck::fail(
+                NAME,
+                "key read back does not match the key written",
+                "the CEK store is corrupted; restore it from backup",
+            ),
+            Err(e) => DoctorCheck::fail(
+                NAME,
+                e.to_string(),
+                "check permissions of the persistence directory",
+            ),
+        }
+    }
+
+    fn check_disk_space(&self, min_free_bytes: u64) -> DoctorCheck {
+        const NAME: &str = "disk_space";
+        let dir = match &self.persistence {
+            PersistenceConfig::Sled { dir } => dir,
+            _ => return DoctorCheck::skipped(NAME, "no local persistence directory"),
+        };
+        match fs2::available_space(dir) {
+            Ok(free) if free >= min_free_bytes => {
+                DoctorCheck::ok(NAME, format!("{free} bytes free in {}", dir.display()))
+            }
+            Ok(free) => DoctorCheck::fail(
+                NAME,
+                format!(
+                    "{free} bytes free in {} (need {min_free_bytes})",
+                    dir.display()
+                ),
+                "free up disk space or move MONAS_PERSISTENCE_DIR",
+            ),
+            Err(e) => DoctorCheck::fail(
+                NAME,
+                format!("failed to stat {}: {e}", dir.display()),
+                "check that MONAS_PERSISTENCE_DIR exists and is readable",
+            ),
+        }
+    }
+
+    /// monas-event-manager の dead letter 数を数える。
+    ///
+    /// sled はプロセス単位で DB をロックするため、稼働中のプロセスが開いている場合は
+    /// 読めずに警告となる。
+    fn check_dead_letters(dir: Option<&str>) -> DoctorCheck {
+        const NAME: &str = "dead_letters";
+        let Some(dir) = dir else {
+            return DoctorCheck::skipped(NAME, "no dead letter directory configured");
+        };
+        // 存在しないパスを開くと空の DB が作られてしまうため先に確認する。
+        if !Path::new(dir).exists() {
+            return DoctorCheck::ok(NAME, format!("no dead letter store at {dir}"));
+        }
+        let stats = monas_event_manager::SledPersistenceManager::new(dir)
+            .and_then(|store| store.get_stats());
+        match stats {
+            Ok(stats) => match stats.get("message_count").copied().unwrap_or(0) {
+                0 => DoctorCheck::ok(NAME, "no pending dead letters"),
+                count => DoctorCheck::warn(
+                    NAME,
+                    format!("{count} pending dead letter(s)"),
+                    "inspect subscriber errors, then run EventBus::restore_and_retry_dead_letters",
+                ),
+            },
+            Err(e) => DoctorCheck::warn(
+                NAME,
+                format!("failed to open dead letter store: {e}"),
+                "stop the process holding the store, or inspect it from that process",
+            ),
+        }
+    }
+}
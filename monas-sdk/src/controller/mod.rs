@@ -1,5 +1,7 @@
 mod async_api;
+mod bulk;
 mod content;
+mod doctor;
 mod keypair;
 mod share;
 mod state;
@@ -48,6 +50,7 @@ pub(super) fn combine_rollback_failure(
         ApiError::Forbidden(_) => ApiError::Forbidden(suffix),
         ApiError::NotFound(_) => ApiError::NotFound(suffix),
         ApiError::Conflict(_) => ApiError::Conflict(suffix),
+        ApiError::PayloadTooLarge(_) => ApiError::PayloadTooLarge(suffix),
         ApiError::Timeout(_) => ApiError::Timeout(suffix),
         ApiError::Internal(_) => ApiError::Internal(suffix),
     }
@@ -63,6 +66,10 @@ pub struct MonasController {
     pub(super) agent: ureq::Agent,
     /// `X-Request-Timestamp` の許容 skew (Gateway 経由で渡された timestamp が古すぎる/未来すぎる場合 reject)
     pub(super) request_timestamp_skew: std::time::Duration,
+    /// ローカル persistence backend の設定 (doctor の空き容量検査で使う)
+    pub(super) persistence: PersistenceConfig,
+    /// `Sled` persistence 時に共有している sled DB (doctor の整合性検査で使う)
+    pub(super) local_db: Option<sled::Db>,
     /// ContentService
     content_service: ContentServiceInstance,
     /// ShareService
@@ -159,7 +166,7 @@ impl MonasController {
         // stateless thin client and push CEK / share ownership to State Node,
         // or (b) define an explicit pluggable port for CEK ownership semantics.
         let content_repository = Self::create_content_repository();
-        let (cek_store, share_repository, public_key_directory) =
+        let (cek_store, share_repository, public_key_directory, local_db) =
             Self::create_persistence(&config.persistence)?;
         let agent = Self::build_agent(&config);
 
@@ -168,6 +175,8 @@ impl MonasController {
             account_url: config.account_url,
             agent,
             request_timestamp_skew: config.request_timestamp_skew,
+            persistence: config.persistence,
+            local_db,
             content_service: Self::create_content_service(
                 content_repository.clone(),
                 cek_store.clone(),
@@ -202,7 +211,7 @@ impl MonasController {
     }
 
     /// `PersistenceConfig` から CEK ストア / Share repository / Public key directory の
-    /// 動的インスタンスを構築する。
+    /// 動的インスタンスを構築する。`Sled` の場合は共有している `sled::Db` も返す。
     ///
     /// `InMemory` 選択時は揮発する旨の警告を stderr に 1 度だけ出す。
     ///
@@ -213,7 +222,15 @@ impl MonasController {
     /// キー空間は `cek:` / `share:` / `pubkey:` プレフィックスで分離されている。
     fn create_persistence(
         persistence: &PersistenceConfig,
-    ) -> Result<(DynCekStore, DynShareRepository, DynPublicKeyDirectory), ApiError> {
+    ) -> Result<
+        (
+            DynCekStore,
+            DynShareRepository,
+            DynPublicKeyDirectory,
+            Option<sled::Db>,
+        ),
+        ApiError,
+    > {
         use monas_content::infrastructure::{
             key_store::{InMemoryContentEncryptionKeyStore, SledContentEncryptionKeyStore},
             public_key_directory::{InMemoryPublicKeyDirectory, SledPublicKeyDirectory},
@@ -230,7 +247,7 @@ impl MonasController {
                 let cek: DynCekStore = Arc::new(InMemoryContentEncryptionKeyStore::default());
                 let share: DynShareRepository = Arc::new(InMemoryShareRepository::default());
                 let pkd: DynPublicKeyDirectory = Arc::new(InMemoryPublicKeyDirectory::default());
-                Ok((cek, share, pkd))
+                Ok((cek, share, pkd, None))
             }
             PersistenceConfig::Sled { dir } => {
                 if let Err(e) = std::fs::create_dir_all(dir) {
@@ -245,11 +262,11 @@ impl MonasController {
                 })?;
                 let cek = SledContentEncryptionKeyStore::with_db(db.clone());
                 let share = SledShareRepository::with_db(db.clone());
-                let pkd = SledPublicKeyDirectory::with_db(db);
+                let pkd = SledPublicKeyDirectory::with_db(db.clone());
                 let cek: DynCekStore = Arc::new(cek);
                 let share: DynShareRepository = Arc::new(share);
                 let pkd: DynPublicKeyDirectory = Arc::new(pkd);
-                Ok((cek, share, pkd))
+                Ok((cek, share, pkd, Some(db)))
             }
         }
     }
@@ -259,18 +276,28 @@ impl MonasController {
         content_repository: monas_content::infrastructure::MultiStorageRepository,
         cek_store: DynCekStore,
     ) -> ContentServiceInstance {
-        use monas_content::application_service::content_service::ContentService;
+        use monas_content::application_service::content_service::{
+            ChunkingPolicy, ContentQuota, ContentService, CreateIdempotency, NoOpContentMetrics,
+            NoOpEventPublisher,
+        };
         use monas_content::infrastructure::{
+            cipher_suite::{CipherSuite, AES_256_GCM},
             content_id::Sha256ContentIdGenerator,
-            encryption::{Aes256CtrContentEncryption, OsRngContentEncryptionKeyGenerator},
+            encryption::OsRngContentEncryptionKeyGenerator,
         };
 
         ContentService {
             content_id_generator: Sha256ContentIdGenerator,
             content_repository,
             key_generator: OsRngContentEncryptionKeyGenerator,
-            encryptor: Aes256CtrContentEncryption,
+            encryptor: CipherSuite::builtin(AES_256_GCM)
+                .expect("aes-256-gcm is a builtin algorithm"),
             cek_store,
+            event_publisher: NoOpEventPublisher,
+            quota: ContentQuota::unlimited(),
+            chunking: ChunkingPolicy::disabled(),
+            idempotency: CreateIdempotency::disabled(),
+            metrics: NoOpContentMetrics,
         }
     }
 
@@ -281,7 +308,10 @@ impl MonasController {
         share_repository: DynShareRepository,
         public_key_directory: DynPublicKeyDirectory,
     ) -> ShareServiceInstance {
-        use monas_content::application_service::share_service::ShareService;
+        use monas_content::application_service::content_service::NoOpPushNotifier;
+        use monas_content::application_service::share_service::{
+            NoKeyPossessionVerifier, ShareService,
+        };
         use monas_content::infrastructure::key_wrapping::HpkeV1KeyWrapping;
 
         ShareService {
@@ -290,6 +320,8 @@ impl MonasController {
             cek_store,
             public_key_directory,
             key_wrapper: HpkeV1KeyWrapping,
+            push_notifier: NoOpPushNotifier,
+            possession_verifier: NoKeyPossessionVerifier,
         }
     }
 }
@@ -17,7 +17,8 @@ use monas_content::application_service::content_service::{
     ReencryptError,
 };
 use monas_content::application_service::share_service::{
-    GrantShareCommand, RevokeShareCommand, ShareApplicationError, ShareRepository, ShareService,
+    GrantShareCommand, RevokeShareCommand, ShareApplicationError, ShareRecipient, ShareRepository,
+    ShareService,
 };
 use monas_content::domain::content::{Content, ContentEncryptionKey};
 use monas_content::domain::content_id::ContentId;
@@ -102,6 +103,11 @@ impl MonasController {
         Ok(())
     }
 
+    fn parse_content_id(value: &str) -> Result<ContentId, ApiError> {
+        ContentId::new(value.to_string())
+            .map_err(|e| ApiError::Validation(format!("Invalid content_id: {e}")))
+    }
+
     fn decode_base64url_field(field: &'static str, value: &str) -> Result<Vec<u8>, ApiError> {
         decode_base64url(value)
             .map_err(|e| ApiError::Validation(format!("Invalid {field} base64url: {e}")))
@@ -217,9 +223,18 @@ impl MonasController {
             ShareApplicationError::MissingPublicKey => {
                 ApiError::NotFound("Missing public key".into())
             }
+            ShareApplicationError::RecipientAccountNotFound(account_id) => {
+                ApiError::NotFound(format!("Recipient account not found: {account_id}"))
+            }
             ShareApplicationError::KeyWrapping(msg) => {
                 ApiError::Internal(format!("Key wrapping error: {msg}"))
             }
+            ShareApplicationError::NotShared => {
+                ApiError::Forbidden("Content is not shared with the recipient".into())
+            }
+            ShareApplicationError::InvalidKeyPossessionProof(msg) => {
+                ApiError::Unauthorized(format!("Invalid key possession proof: {msg}"))
+            }
         }
     }
 
@@ -305,7 +320,10 @@ impl MonasController {
         }
 
         // 2. ContentIdに変換
-        let content_id = ContentId::new(input.content_id.clone());
+        let content_id = match Self::parse_content_id(&input.content_id) {
+            Ok(id) => id,
+            Err(e) => return ApiResponse::error(e, trace_id),
+        };
 
         // 3. 送信者の公開鍵をデコードしてsender_key_idを計算
         let sender_public_key_bytes =
@@ -340,7 +358,7 @@ impl MonasController {
         let cmd = GrantShareCommand {
             content_id: content_id.clone(),
             sender_key_id,
-            recipient_public_key: recipient_public_key_bytes.clone(),
+            recipient: ShareRecipient::PublicKey(recipient_public_key_bytes.clone()),
             permission: permission.clone(),
         };
 
@@ -432,7 +450,10 @@ impl MonasController {
         }
 
         // 2. ContentIdに変換
-        let content_id = ContentId::new(input.content_id.clone());
+        let content_id = match Self::parse_content_id(&input.content_id) {
+            Ok(id) => id,
+            Err(e) => return ApiResponse::error(e, trace_id),
+        };
 
         let snapshot = match self.capture_revoke_share_snapshot(&content_id) {
             Ok(snapshot) => snapshot,
@@ -458,7 +479,7 @@ impl MonasController {
 
         // 4. ShareService::revoke_shareを呼び出し
         let cmd = RevokeShareCommand {
-            content_id,
+            content_id: content_id.clone(),
             sender_key_id,
             recipient_key_id,
         };
@@ -487,9 +508,10 @@ impl MonasController {
         };
 
         // revoke後に再暗号し、State Nodeのバージョンを進める
-        let reencryption = match self.content_service.reencrypt(ReencryptContentCommand {
-            content_id: ContentId::new(input.content_id.clone()),
-        }) {
+        let reencryption = match self
+            .content_service
+            .reencrypt(ReencryptContentCommand { content_id })
+        {
             Ok(result) => result,
             Err(e) => {
                 // reencrypt に失敗した時点で ACL は既に変更済み。
@@ -591,7 +613,10 @@ impl MonasController {
         }
 
         // 2. ContentIdに変換
-        let content_id = ContentId::new(input.content_id.clone());
+        let content_id = match Self::parse_content_id(&input.content_id) {
+            Ok(id) => id,
+            Err(e) => return ApiResponse::error(e, trace_id),
+        };
 
         // 3. sender_key_idとrecipient_key_idをデコード
         let sender_key_id_bytes =
@@ -0,0 +1,163 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::ApiError;
+
+// ============================================
+// upload_dir / download_many
+// ============================================
+
+/// 一括操作の既定の同時実行数。
+pub const DEFAULT_BULK_CONCURRENCY: usize = 4;
+
+/// 一括操作の中断を要求するためのハンドル。
+///
+/// クローンしたハンドル同士は状態を共有するため、別スレッドから `cancel` できる。
+/// 中断しても実行中の項目は最後まで処理され、未着手の項目だけが
+/// `BulkItemOutcome::Cancelled` になる。
+#[derive(Debug, Clone, Default)]
+pub struct BulkCancellation {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl BulkCancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// 一括操作の進捗
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkProgress {
+    /// 対象の項目数
+    pub total: usize,
+    /// 処理を終えた項目数（成功 + 失敗）
+    pub completed: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+/// 進捗の通知先。項目の処理が終わるたびに呼ばれる（呼び出しは直列化される）。
+pub type BulkProgressCallback = Arc<dyn Fn(&BulkProgress) + Send + Sync>;
+
+/// 一括操作のオプション
+#[derive(Clone)]
+pub struct BulkOptions {
+    /// 同時に処理する項目数の上限（1 以上）
+    pub concurrency: usize,
+    pub cancellation: Option<BulkCancellation>,
+    pub on_progress: Option<BulkProgressCallback>,
+}
+
+impl Default for BulkOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_BULK_CONCURRENCY,
+            cancellation: None,
+            on_progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for BulkOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkOptions")
+            .field("concurrency", &self.concurrency)
+            .field("cancellation", &self.cancellation)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+impl BulkOptions {
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancellation: BulkCancellation) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    pub fn with_progress(
+        mut self,
+        on_progress: impl Fn(&BulkProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_progress = Some(Arc::new(on_progress));
+        self
+    }
+}
+
+/// 1 項目分の処理結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BulkItemOutcome<T> {
+    Succeeded {
+        data: T,
+        trace_id: String,
+    },
+    Failed {
+        error: ApiError,
+        trace_id: String,
+    },
+    /// 中断されたため処理しなかった
+    Cancelled,
+}
+
+/// 1 項目分の結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkItemResult<T> {
+    /// 項目を識別する値（`upload_dir` ではディレクトリからの相対パス、
+    /// `download_many` では content_id）
+    pub key: String,
+    #[serde(flatten)]
+    pub outcome: BulkItemOutcome<T>,
+}
+
+/// 一括操作のレスポンス
+///
+/// `items` は入力（`upload_dir` ではパスの昇順）と同じ順に並ぶ。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkOutput<T> {
+    pub items: Vec<BulkItemResult<T>>,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+}
+
+impl<T> BulkOutput<T> {
+    pub fn new(items: Vec<BulkItemResult<T>>) -> Self {
+        let mut output = Self {
+            items: Vec::new(),
+            succeeded: 0,
+            failed: 0,
+            cancelled: 0,
+        };
+        for item in &items {
+            match item.outcome {
+                BulkItemOutcome::Succeeded { .. } => output.succeeded += 1,
+                BulkItemOutcome::Failed { .. } => output.failed += 1,
+                BulkItemOutcome::Cancelled => output.cancelled += 1,
+            }
+        }
+        output.items = items;
+        output
+    }
+
+    /// すべての項目が成功したか
+    pub fn is_complete(&self) -> bool {
+        self.failed == 0 && self.cancelled == 0
+    }
+}
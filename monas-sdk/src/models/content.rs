@@ -7,6 +7,9 @@ pub struct ContentMetadata {
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_type: Option<String>,
+    /// コンテンツ本体のバイトサイズ（取得時に設定される）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -44,16 +47,25 @@ pub struct CreateContentOutput {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetContentInput {
     pub content_id: String,
+    /// 手元にあるコンテンツの版（前回の `GetContentOutput::version`、`If-None-Match` 形式も可）。
+    /// 現在の版と一致する場合は復号せず `not_modified` を返す。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_none_match: Option<String>,
 }
 
 /// コンテンツ取得レスポンス
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetContentOutput {
     pub content_id: String,
-    /// 復号されたコンテンツ（base64url）
+    /// 復号されたコンテンツ（base64url）。`not_modified` の場合は空。
     pub content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<ContentMetadata>,
+    /// コンテンツの版（ETag として使える短いハッシュ）
+    pub version: String,
+    /// `if_none_match` が現在の版と一致し、本体を返していない場合は true
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub not_modified: bool,
 }
 
 // ============================================
@@ -117,6 +129,7 @@ mod tests {
         let metadata = ContentMetadata {
             name: Some("test.txt".into()),
             content_type: Some("text/plain".into()),
+            size: None,
             created_at: None,
             updated_at: None,
         };
@@ -134,6 +147,7 @@ mod tests {
             metadata: Some(ContentMetadata {
                 name: Some("hello.txt".into()),
                 content_type: Some("text/plain".into()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -161,6 +175,7 @@ mod tests {
         let json = r#"{"content_id": "test_id"}"#;
         let input: GetContentInput = serde_json::from_str(json).unwrap();
         assert_eq!(input.content_id, "test_id");
+        assert!(input.if_none_match.is_none());
     }
 
     #[test]
@@ -169,11 +184,15 @@ mod tests {
             content_id: "test_id".into(),
             content: "SGVsbG8gV29ybGQ=".into(),
             metadata: None,
+            version: "0123abcd".into(),
+            not_modified: false,
         };
         let json = serde_json::to_string(&output).unwrap();
         assert!(json.contains("\"content_id\":\"test_id\""));
         assert!(json.contains("\"content\":\"SGVsbG8gV29ybGQ=\""));
         assert!(!json.contains("metadata"));
+        assert!(json.contains("\"version\":\"0123abcd\""));
+        assert!(!json.contains("not_modified"));
     }
 
     #[test]
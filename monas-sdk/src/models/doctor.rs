@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================
+// doctor
+// ============================================
+
+/// 診断リクエスト
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DoctorInput {
+    /// monas-event-manager の dead letter を保存している sled DB のディレクトリ。
+    /// 未指定の場合、dead letter の検査はスキップする。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_letter_dir: Option<String>,
+    /// ローカル persistence ディレクトリに必要な最小空き容量（バイト）。
+    /// 未指定の場合は `DEFAULT_MIN_FREE_DISK_BYTES`。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_free_disk_bytes: Option<u64>,
+}
+
+/// 空き容量の既定下限 (512 MiB)。
+pub const DEFAULT_MIN_FREE_DISK_BYTES: u64 = 512 * 1024 * 1024;
+
+/// 各診断項目の判定
+///
+/// 並び順は深刻度の昇順（`Skipped < Ok < Warn < Fail`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    /// 設定上検査できなかった
+    Skipped,
+    Ok,
+    /// 動作はするが対処を推奨
+    Warn,
+    /// 動作に支障がある
+    Fail,
+}
+
+/// 1 項目分の診断結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DoctorCheck {
+    /// 項目名（例: `state_node_reachable`）
+    pub name: String,
+    pub status: CheckStatus,
+    /// 観測した値やエラー内容
+    pub detail: String,
+    /// 利用者が取るべき対処
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    pub fn ok(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Ok, detail, None)
+    }
+
+    pub fn skipped(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Skipped, detail, None)
+    }
+
+    pub fn warn(name: &str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Warn, detail, Some(hint.into()))
+    }
+
+    pub fn fail(name: &str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Fail, detail, Some(hint.into()))
+    }
+
+    fn new(
+        name: &str,
+        status: CheckStatus,
+        detail: impl Into<String>,
+        hint: Option<String>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+            hint,
+        }
+    }
+}
+
+/// 診断レポート
+///
+/// サポート問い合わせやバグ報告にそのまま添付できるよう JSON で出力する。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DoctorReport {
+    /// 全項目のうち最も深刻な判定
+    pub status: CheckStatus,
+    /// SDK のバージョン
+    pub sdk_version: String,
+    /// 診断時刻（RFC 3339）
+    pub checked_at: String,
+    pub state_node_url: String,
+    pub account_url: String,
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    /// 診断結果からレポートを組み立てる。
+    pub fn new(
+        state_node_url: String,
+        account_url: String,
+        checked_at: String,
+        checks: Vec<DoctorCheck>,
+    ) -> Self {
+        let status = checks
+            .iter()
+            .map(|c| c.status)
+            .max()
+            .unwrap_or(CheckStatus::Ok)
+            .max(CheckStatus::Ok);
+        Self {
+            status,
+            sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+            checked_at,
+            state_node_url,
+            account_url,
+            checks,
+        }
+    }
+
+    /// `Fail` の項目が無ければ true
+    pub fn is_healthy(&self) -> bool {
+        self.status != CheckStatus::Fail
+    }
+
+    /// 項目名で診断結果を探す。
+    pub fn check(&self, name: &str) -> Option<&DoctorCheck> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_status_is_worst_check() {
+        let report = DoctorReport::new(
+            "http://a".into(),
+            "http://b".into(),
+            "2025-12-05T12:34:56Z".into(),
+            vec![
+                DoctorCheck::ok("a", "fine"),
+                DoctorCheck::warn("b", "slow", "check network"),
+                DoctorCheck::skipped("c", "not configured"),
+            ],
+        );
+        assert_eq!(report.status, CheckStatus::Warn);
+        assert!(report.is_healthy());
+        assert_eq!(
+            report.check("b").unwrap().hint.as_deref(),
+            Some("check network")
+        );
+    }
+
+    #[test]
+    fn test_report_only_skipped_is_ok() {
+        let report = DoctorReport::new(
+            "http://a".into(),
+            "http://b".into(),
+            "2025-12-05T12:34:56Z".into(),
+            vec![DoctorCheck::skipped("c", "not configured")],
+        );
+        assert_eq!(report.status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_check_serializes_lowercase_status_without_empty_hint() {
+        let json = serde_json::to_string(&DoctorCheck::ok("disk_space", "10 GiB free")).unwrap();
+        assert!(json.contains("\"status\":\"ok\""));
+        assert!(!json.contains("hint"));
+    }
+}
@@ -1,10 +1,14 @@
+pub mod bulk;
 pub mod content;
+pub mod doctor;
 pub mod keypair;
 pub mod share;
 pub mod state;
 pub mod state_node;
 
+pub use bulk::*;
 pub use content::*;
+pub use doctor::*;
 pub use keypair::*;
 pub use share::*;
 pub use state::*;
@@ -0,0 +1,182 @@
+// Integration tests intentionally use the test/dev-only constructors
+// (`MonasController::with_state_node_url` / `with_urls`) marked
+// `#[deprecated]` for production gateways.
+#![allow(deprecated)]
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use mockito::Server;
+use monas_sdk::models::bulk::{BulkCancellation, BulkItemOutcome, BulkOptions, BulkProgress};
+use monas_sdk::{ApiError, MonasController};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+mod support;
+use support::{acquire_test_lock, cleanup_content_artifacts};
+
+fn tmp_dir(label: &str) -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!(
+        "monas-sdk-bulk-{}-{}-{}",
+        label,
+        std::process::id(),
+        nanos
+    ));
+    std::fs::create_dir_all(&dir).expect("create tmp dir");
+    dir
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn upload_dir_and_download_many_report_per_item_results() {
+    let _guard = acquire_test_lock();
+    let mut server = Server::new_async().await;
+    let create_mock = server
+        .mock("POST", "/content")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"content_id":"bafkqaaa-bulk-remote"}"#)
+        .expect(3)
+        .create_async()
+        .await;
+
+    let dir = tmp_dir("upload");
+    std::fs::create_dir_all(dir.join("sub")).unwrap();
+    std::fs::write(dir.join("a.txt"), b"first file").unwrap();
+    std::fs::write(dir.join("b.txt"), b"second file").unwrap();
+    std::fs::write(dir.join("sub/c.txt"), b"nested file").unwrap();
+    // 空ファイルは create_content のバリデーションで失敗する
+    std::fs::write(dir.join("empty.txt"), b"").unwrap();
+
+    let controller = MonasController::with_state_node_url(server.url());
+    let progress: Arc<Mutex<Vec<BulkProgress>>> = Arc::default();
+    let recorded = progress.clone();
+    let options = BulkOptions::default()
+        .with_concurrency(2)
+        .with_progress(move |p| recorded.lock().unwrap().push(*p));
+
+    let uploaded = controller
+        .upload_dir(&dir, &options, None)
+        .data
+        .expect("upload_dir should return data");
+    let _ = std::fs::remove_dir_all(&dir);
+    create_mock.assert();
+
+    let keys: Vec<&str> = uploaded.items.iter().map(|i| i.key.as_str()).collect();
+    assert_eq!(keys, vec!["a.txt", "b.txt", "empty.txt", "sub/c.txt"]);
+    assert_eq!((uploaded.succeeded, uploaded.failed), (3, 1));
+    assert!(!uploaded.is_complete());
+    assert!(matches!(
+        &uploaded.items[2].outcome,
+        BulkItemOutcome::Failed {
+            error: ApiError::Validation(_),
+            ..
+        }
+    ));
+
+    let progress = progress.lock().unwrap();
+    assert_eq!(progress.len(), 4);
+    assert!(progress.windows(2).all(|w| w[0].completed < w[1].completed));
+    let last = progress.last().unwrap();
+    assert_eq!((last.total, last.succeeded, last.failed), (4, 3, 1));
+
+    let mut content_ids: Vec<String> = uploaded
+        .items
+        .iter()
+        .filter_map(|item| match &item.outcome {
+            BulkItemOutcome::Succeeded { data, .. } => Some(data.content_id.clone()),
+            _ => None,
+        })
+        .collect();
+    content_ids.push("1220missing".to_string());
+
+    let downloaded = controller
+        .download_many(&content_ids, &BulkOptions::default())
+        .data
+        .expect("download_many should return data");
+    assert_eq!((downloaded.succeeded, downloaded.failed), (3, 1));
+    let contents: Vec<Vec<u8>> = downloaded
+        .items
+        .iter()
+        .filter_map(|item| match &item.outcome {
+            BulkItemOutcome::Succeeded { data, .. } => {
+                Some(URL_SAFE_NO_PAD.decode(&data.content).unwrap())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        contents,
+        vec![
+            b"first file".to_vec(),
+            b"second file".to_vec(),
+            b"nested file".to_vec()
+        ]
+    );
+    assert_eq!(downloaded.items[3].key, "1220missing");
+    cleanup_content_artifacts();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cancelled_bulk_operation_skips_remaining_items() {
+    let _guard = acquire_test_lock();
+    let mut server = Server::new_async().await;
+    let create_mock = server
+        .mock("POST", "/content")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"content_id":"bafkqaaa-bulk-remote"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let dir = tmp_dir("cancel");
+    for i in 0..5 {
+        std::fs::write(dir.join(format!("{i}.txt")), format!("file {i}")).unwrap();
+    }
+
+    // 最初の項目が終わった時点で中断する
+    let cancellation = BulkCancellation::new();
+    let trigger = cancellation.clone();
+    let options = BulkOptions::default()
+        .with_concurrency(1)
+        .with_cancellation(cancellation)
+        .with_progress(move |_| trigger.cancel());
+
+    let controller = MonasController::with_state_node_url(server.url());
+    let output = controller
+        .upload_dir(&dir, &options, None)
+        .data
+        .expect("upload_dir should return data");
+    let _ = std::fs::remove_dir_all(&dir);
+    create_mock.assert();
+
+    assert_eq!((output.succeeded, output.cancelled), (1, 4));
+    assert!(output.items[1..]
+        .iter()
+        .all(|item| matches!(item.outcome, BulkItemOutcome::Cancelled)));
+    cleanup_content_artifacts();
+}
+
+#[test]
+fn bulk_operations_validate_input() {
+    let controller = MonasController::with_state_node_url("http://127.0.0.1:1");
+
+    let response = controller.download_many(
+        &["id".to_string()],
+        &BulkOptions::default().with_concurrency(0),
+    );
+    assert!(matches!(response.error, Some(ApiError::Validation(_))));
+
+    let missing = tmp_dir("missing").join("does-not-exist");
+    let response = controller.upload_dir(&missing, &BulkOptions::default(), None);
+    assert!(matches!(response.error, Some(ApiError::Validation(_))));
+
+    // 空のリストは何もせずに成功する
+    let output = controller
+        .download_many(&[], &BulkOptions::default())
+        .data
+        .expect("download_many should return data");
+    assert!(output.items.is_empty());
+    assert!(output.is_complete());
+}
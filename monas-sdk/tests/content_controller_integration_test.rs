@@ -20,7 +20,8 @@ use support::{acquire_test_lock, cleanup_content_artifacts};
 fn compute_content_id(raw_content: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(raw_content);
-    format!("{:x}", hasher.finalize())
+    // `Sha256ContentIdGenerator` と同じく multihash 風の `sha2-256` プレフィックス（0x12, 0x20）を付ける。
+    format!("1220{:x}", hasher.finalize())
 }
 
 /// `X-Request-Timestamp` の skew チェックを実質的に無効化した `MonasController`。
@@ -58,6 +59,7 @@ async fn create_content_and_get_content_round_trip_succeeds_with_mock_state_node
         metadata: Some(ContentMetadata {
             name: Some("test.txt".to_string()),
             content_type: Some("text/plain".to_string()),
+            size: None,
             created_at: None,
             updated_at: None,
         }),
@@ -77,7 +79,8 @@ async fn create_content_and_get_content_round_trip_succeeds_with_mock_state_node
     create_mock.assert();
 
     let get_response = controller.get_content(GetContentInput {
-        content_id: created.content_id,
+        content_id: created.content_id.clone(),
+        if_none_match: None,
     });
 
     assert!(get_response.success, "get_content should succeed");
@@ -89,6 +92,23 @@ async fn create_content_and_get_content_round_trip_succeeds_with_mock_state_node
         .expect("fetched content should be base64url");
 
     assert_eq!(fetched_bytes, raw_content);
+    let fetched_metadata = fetched
+        .metadata
+        .expect("get_content should return metadata");
+    assert_eq!(fetched_metadata.size, Some(raw_content.len() as u64));
+    assert_eq!(fetched_metadata.content_type.as_deref(), Some("text/plain"));
+    assert!(!fetched.not_modified);
+
+    let conditional = controller
+        .get_content(GetContentInput {
+            content_id: created.content_id,
+            if_none_match: Some(format!("\"{}\"", fetched.version)),
+        })
+        .data
+        .expect("conditional get_content should return data");
+    assert!(conditional.not_modified);
+    assert_eq!(conditional.version, fetched.version);
+    assert!(conditional.content.is_empty());
     cleanup_content_artifacts();
 }
 
@@ -119,6 +139,7 @@ async fn delete_content_round_trip_succeeds() {
             metadata: Some(ContentMetadata {
                 name: Some("delete.txt".to_string()),
                 content_type: Some("text/plain".to_string()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -148,6 +169,7 @@ async fn delete_content_round_trip_succeeds() {
 
     let get_response = controller.get_content(GetContentInput {
         content_id: created.content_id,
+        if_none_match: None,
     });
     assert!(
         !get_response.success,
@@ -197,6 +219,7 @@ async fn delete_content_rolls_back_locally_when_state_node_delete_fails() {
             metadata: Some(ContentMetadata {
                 name: Some("rollback.txt".to_string()),
                 content_type: Some("text/plain".to_string()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -225,6 +248,7 @@ async fn delete_content_rolls_back_locally_when_state_node_delete_fails() {
 
     let get_response = controller.get_content(GetContentInput {
         content_id: created.content_id.clone(),
+        if_none_match: None,
     });
     assert!(
         get_response.success,
@@ -273,6 +297,7 @@ async fn update_content_round_trip_succeeds_with_mock_state_node() {
             metadata: Some(ContentMetadata {
                 name: Some("before.txt".to_string()),
                 content_type: Some("text/plain".to_string()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -309,6 +334,7 @@ async fn update_content_round_trip_succeeds_with_mock_state_node() {
             metadata: Some(ContentMetadata {
                 name: Some("after.txt".to_string()),
                 content_type: None,
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -340,6 +366,7 @@ async fn update_content_round_trip_succeeds_with_mock_state_node() {
             metadata: Some(ContentMetadata {
                 name: Some("after-second.txt".to_string()),
                 content_type: None,
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -363,6 +390,7 @@ async fn update_content_round_trip_succeeds_with_mock_state_node() {
 
     let get_response = controller.get_content(GetContentInput {
         content_id: second_updated.version_id,
+        if_none_match: None,
     });
     assert!(
         get_response.success,
@@ -397,6 +425,7 @@ async fn create_content_rolls_back_locally_when_state_node_create_fails_and_can_
         metadata: Some(ContentMetadata {
             name: Some("create-rollback.txt".to_string()),
             content_type: Some("text/plain".to_string()),
+            size: None,
             created_at: None,
             updated_at: None,
         }),
@@ -431,6 +460,7 @@ async fn create_content_rolls_back_locally_when_state_node_create_fails_and_can_
 
     let get_response = controller.get_content(GetContentInput {
         content_id: created.content_id,
+        if_none_match: None,
     });
     assert!(get_response.success, "created content should be readable");
     let fetched = get_response.data.expect("get should return data");
@@ -485,6 +515,7 @@ async fn create_content_uses_account_signature_for_state_node_request() {
             metadata: Some(ContentMetadata {
                 name: Some("account-signed.txt".to_string()),
                 content_type: Some("text/plain".to_string()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -529,6 +560,7 @@ async fn create_content_fails_fast_when_account_key_is_not_p256() {
             metadata: Some(ContentMetadata {
                 name: Some("invalid-algorithm.txt".to_string()),
                 content_type: Some("text/plain".to_string()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -563,6 +595,7 @@ async fn update_content_rolls_back_new_version_when_state_node_update_fails() {
             metadata: Some(ContentMetadata {
                 name: Some("before-rollback.txt".to_string()),
                 content_type: Some("text/plain".to_string()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -603,6 +636,7 @@ async fn update_content_rolls_back_new_version_when_state_node_update_fails() {
             metadata: Some(ContentMetadata {
                 name: Some("after-rollback.txt".to_string()),
                 content_type: None,
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -617,6 +651,7 @@ async fn update_content_rolls_back_new_version_when_state_node_update_fails() {
 
     let old_version_response = controller.get_content(GetContentInput {
         content_id: created.content_id.clone(),
+        if_none_match: None,
     });
     assert!(
         old_version_response.success,
@@ -631,6 +666,7 @@ async fn update_content_rolls_back_new_version_when_state_node_update_fails() {
     let expected_new_version_id = compute_content_id(updated_raw);
     let rolled_back_version_response = controller.get_content(GetContentInput {
         content_id: expected_new_version_id.clone(),
+        if_none_match: None,
     });
     assert!(
         !rolled_back_version_response.success,
@@ -673,6 +709,7 @@ async fn update_content_rolls_back_new_version_when_state_node_update_fails() {
             metadata: Some(ContentMetadata {
                 name: Some("after-rollback.txt".to_string()),
                 content_type: None,
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -717,6 +754,7 @@ async fn delete_content_uses_account_signature_for_metadata_request() {
                 metadata: Some(ContentMetadata {
                     name: Some("delete-account-signed.txt".to_string()),
                     content_type: Some("text/plain".to_string()),
+                    size: None,
                     created_at: None,
                     updated_at: None,
                 }),
@@ -811,6 +849,7 @@ async fn create_content_fails_when_state_node_omits_content_id() {
             metadata: Some(ContentMetadata {
                 name: Some("silent-none.txt".into()),
                 content_type: Some("text/plain".into()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -860,6 +899,7 @@ async fn create_content_returns_timeout_when_state_node_hangs() {
         metadata: Some(ContentMetadata {
             name: Some("timeout.txt".into()),
             content_type: Some("text/plain".into()),
+            size: None,
             created_at: None,
             updated_at: None,
         }),
@@ -911,6 +951,7 @@ async fn create_content_rejects_far_future_timestamp_with_unauthorized() {
             metadata: Some(ContentMetadata {
                 name: Some("freshness.txt".into()),
                 content_type: Some("text/plain".into()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -952,6 +993,7 @@ async fn create_content_rejects_missing_timestamp_with_unauthorized() {
             metadata: Some(ContentMetadata {
                 name: Some("freshness-missing.txt".into()),
                 content_type: Some("text/plain".into()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -994,6 +1036,7 @@ async fn create_content_rejects_far_past_timestamp_with_unauthorized() {
             metadata: Some(ContentMetadata {
                 name: Some("freshness-past.txt".into()),
                 content_type: Some("text/plain".into()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
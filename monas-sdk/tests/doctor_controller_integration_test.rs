@@ -0,0 +1,147 @@
+//! `MonasController::doctor` の診断レポートのテスト。
+
+use mockito::Server;
+use monas_sdk::models::doctor::{CheckStatus, DoctorInput, DoctorReport};
+use monas_sdk::{MonasConfig, MonasController};
+use std::path::PathBuf;
+
+mod support;
+use support::acquire_test_lock;
+
+fn tmp_dir(label: &str) -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!(
+        "monas-sdk-doctor-{}-{}-{}",
+        label,
+        std::process::id(),
+        nanos
+    ));
+    std::fs::create_dir_all(&dir).expect("create tmp dir");
+    dir
+}
+
+fn status_of(report: &DoctorReport, name: &str) -> CheckStatus {
+    report
+        .check(name)
+        .unwrap_or_else(|| panic!("missing check {name}: {report:?}"))
+        .status
+}
+
+fn http_date_now() -> String {
+    chrono::Utc::now()
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn doctor_reports_each_component() {
+    let _guard = acquire_test_lock();
+    let mut state_node = Server::new_async().await;
+    let mut account = Server::new_async().await;
+    state_node
+        .mock("GET", "/health")
+        .with_status(200)
+        .with_header("date", &http_date_now())
+        .with_body(r#"{"status":"ok","node_id":"12D3KooWTest"}"#)
+        .create_async()
+        .await;
+    state_node
+        .mock("GET", "/health/ready")
+        .with_status(200)
+        .with_body(r#"{"status":"ready","peers":0,"database":"ok"}"#)
+        .create_async()
+        .await;
+    account
+        .mock("GET", "/")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let controller =
+        MonasController::with_config(MonasConfig::new(state_node.url(), account.url())).unwrap();
+    let response = controller.doctor(DoctorInput::default());
+
+    assert!(response.success);
+    let report = response.data.unwrap();
+    assert_eq!(status_of(&report, "state_node_reachable"), CheckStatus::Ok);
+    assert_eq!(status_of(&report, "clock_skew"), CheckStatus::Ok);
+    assert_eq!(status_of(&report, "state_node_database"), CheckStatus::Ok);
+    assert_eq!(status_of(&report, "peer_connectivity"), CheckStatus::Warn);
+    assert_eq!(status_of(&report, "account_reachable"), CheckStatus::Ok);
+    // in-memory persistence は再起動で揮発するため警告になる。
+    assert_eq!(status_of(&report, "local_store"), CheckStatus::Warn);
+    assert_eq!(status_of(&report, "key_store"), CheckStatus::Ok);
+    assert_eq!(status_of(&report, "disk_space"), CheckStatus::Skipped);
+    assert_eq!(status_of(&report, "dead_letters"), CheckStatus::Skipped);
+    assert_eq!(report.status, CheckStatus::Warn);
+    assert!(report.is_healthy());
+    assert!(report.check("peer_connectivity").unwrap().hint.is_some());
+}
+
+#[test]
+fn doctor_fails_when_servers_are_unreachable() {
+    let controller =
+        MonasController::with_config(MonasConfig::new("http://127.0.0.1:1", "http://127.0.0.1:2"))
+            .unwrap();
+    let report = controller.doctor(DoctorInput::default()).data.unwrap();
+
+    assert_eq!(
+        status_of(&report, "state_node_reachable"),
+        CheckStatus::Fail
+    );
+    assert_eq!(status_of(&report, "account_reachable"), CheckStatus::Fail);
+    assert_eq!(status_of(&report, "clock_skew"), CheckStatus::Skipped);
+    assert_eq!(
+        status_of(&report, "peer_connectivity"),
+        CheckStatus::Skipped
+    );
+    assert!(!report.is_healthy());
+}
+
+#[test]
+fn doctor_checks_sled_store_and_disk_space() {
+    let dir = tmp_dir("sled");
+    {
+        let controller = MonasController::with_config(
+            MonasConfig::new("http://127.0.0.1:1", "http://127.0.0.1:2").with_persistence_dir(&dir),
+        )
+        .unwrap();
+
+        let report = controller.doctor(DoctorInput::default()).data.unwrap();
+        assert_eq!(status_of(&report, "local_store"), CheckStatus::Ok);
+        assert_eq!(status_of(&report, "key_store"), CheckStatus::Ok);
+
+        let report = controller
+            .doctor(DoctorInput {
+                min_free_disk_bytes: Some(u64::MAX),
+                ..Default::default()
+            })
+            .data
+            .unwrap();
+        assert_eq!(status_of(&report, "disk_space"), CheckStatus::Fail);
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn doctor_reports_missing_dead_letter_store_as_ok() {
+    let dir = tmp_dir("dead-letters");
+    let missing = dir.join("missing");
+    let controller =
+        MonasController::with_config(MonasConfig::new("http://127.0.0.1:1", "http://127.0.0.1:2"))
+            .unwrap();
+
+    let report = controller
+        .doctor(DoctorInput {
+            dead_letter_dir: Some(missing.to_string_lossy().into_owned()),
+            ..Default::default()
+        })
+        .data
+        .unwrap();
+    assert_eq!(status_of(&report, "dead_letters"), CheckStatus::Ok);
+    assert!(!missing.exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -55,6 +55,7 @@ async fn share_content_succeeds_after_content_creation() {
             metadata: Some(ContentMetadata {
                 name: Some("share.txt".to_string()),
                 content_type: Some("text/plain".to_string()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -164,6 +165,7 @@ async fn revoke_share_updates_state_node_version() {
             metadata: Some(ContentMetadata {
                 name: Some("revoke.txt".to_string()),
                 content_type: Some("text/plain".to_string()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -257,6 +259,7 @@ async fn revoke_share_rolls_back_local_state_when_state_node_sync_fails() {
             metadata: Some(ContentMetadata {
                 name: Some("revoke-rollback.txt".to_string()),
                 content_type: Some("text/plain".to_string()),
+                size: None,
                 created_at: None,
                 updated_at: None,
             }),
@@ -389,6 +392,7 @@ async fn revoke_share_rollback_fires_on_inner_share_service_error() {
                 metadata: Some(ContentMetadata {
                     name: Some("inner-rollback.txt".into()),
                     content_type: Some("text/plain".into()),
+                    size: None,
                     created_at: None,
                     updated_at: None,
                 }),
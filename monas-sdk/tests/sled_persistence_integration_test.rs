@@ -103,11 +103,12 @@ fn sled_persistence_creates_dir_if_missing() {
 fn sled_cek_store_persists_across_reopen() {
     use monas_content::application_service::content_service::ContentEncryptionKeyStore;
     use monas_content::domain::content::encryption::ContentEncryptionKey;
-    use monas_content::domain::content_id::ContentId;
+    use monas_content::domain::content_id::ContentIdGenerator;
+    use monas_content::infrastructure::content_id::Sha256ContentIdGenerator;
     use monas_content::infrastructure::key_store::SledContentEncryptionKeyStore;
 
     let dir = tmp_dir("cek-rt");
-    let cid = ContentId::new("round-trip-cek-content-id".to_string());
+    let cid = Sha256ContentIdGenerator.generate(b"round-trip-cek-content-id");
     let key = ContentEncryptionKey(vec![0x42; 32]);
 
     {
@@ -159,12 +160,13 @@ fn sled_public_key_directory_persists_across_reopen() {
 #[test]
 fn sled_share_repository_persists_across_reopen() {
     use monas_content::application_service::share_service::ShareRepository;
-    use monas_content::domain::content_id::ContentId;
+    use monas_content::domain::content_id::ContentIdGenerator;
     use monas_content::domain::share::Share;
+    use monas_content::infrastructure::content_id::Sha256ContentIdGenerator;
     use monas_content::infrastructure::share_repository::SledShareRepository;
 
     let dir = tmp_dir("share-rt");
-    let cid = ContentId::new("round-trip-share-content-id".to_string());
+    let cid = Sha256ContentIdGenerator.generate(b"round-trip-share-content-id");
     let share_before = Share::new(cid.clone());
 
     {
@@ -9,6 +9,8 @@ NODE_ROLE="${NODE_ROLE:-member}"
 BOOTSTRAP_ADDR="${BOOTSTRAP_ADDR:-}"
 BOOTSTRAP_DNS="${BOOTSTRAP_DNS:-}"
 BOOTSTRAP_PEER_ID="${BOOTSTRAP_PEER_ID:-}"
+# Containers on a private bridge network have no publicly reachable address.
+LOCAL_NETWORK="${LOCAL_NETWORK:-true}"
 
 ARGS=(
     --data-dir "$DATA_DIR"
@@ -17,6 +19,10 @@ ARGS=(
     --log-level "$LOG_LEVEL"
 )
 
+if [ "$LOCAL_NETWORK" = "true" ]; then
+    ARGS+=(--local-network)
+fi
+
 # For member nodes, resolve bootstrap address dynamically if DNS is provided
 if [ "$NODE_ROLE" != "bootstrap" ]; then
     if [ -n "$BOOTSTRAP_ADDR" ]; then
@@ -76,7 +76,7 @@ mkdir -p data/node1 data/node2 data/node3 data/node4
 # --- bootstrap ノード(node1)-------------------------------------------------
 log "node1 (bootstrap) を :${HTTP_PORTS[0]} / p2p :${P2P_PORTS[0]} で起動..."
 "$BIN" --data-dir ./data/node1 -l "127.0.0.1:${HTTP_PORTS[0]}" \
-    --p2p-port "${P2P_PORTS[0]}" --log-level info > "$LOG_DIR/node1.log" 2>&1 &
+    --p2p-port "${P2P_PORTS[0]}" --local-network --log-level info > "$LOG_DIR/node1.log" 2>&1 &
 PIDS+=($!)
 
 if ! wait_for_health "${HTTP_PORTS[0]}"; then
@@ -97,7 +97,7 @@ for i in 1 2 3; do
     n=$((i + 1))
     log "node$n を :${HTTP_PORTS[$i]} / p2p :${P2P_PORTS[$i]} で起動 (-> bootstrap)..."
     "$BIN" --data-dir "./data/node$n" -l "127.0.0.1:${HTTP_PORTS[$i]}" \
-        --p2p-port "${P2P_PORTS[$i]}" -b "$BOOTSTRAP" --log-level info \
+        --p2p-port "${P2P_PORTS[$i]}" -b "$BOOTSTRAP" --local-network --log-level info \
         > "$LOG_DIR/node$n.log" 2>&1 &
     PIDS+=($!)
     if ! wait_for_health "${HTTP_PORTS[$i]}"; then
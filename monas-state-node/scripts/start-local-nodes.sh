@@ -86,6 +86,7 @@ log_info "ノード1（ブートストラップノード）を起動していま
     -l 127.0.0.1:8080 \
     --p2p-port 9091 \
     --log-level info \
+    --local-network \
     > "$LOG_DIR/node1.log" 2>&1 &
 NODE1_PID=$!
 echo "$NODE1_PID" >> "$PID_FILE"
@@ -144,6 +145,7 @@ log_info "ノード2を起動しています..."
     --p2p-port 9092 \
     -b "$BOOTSTRAP_ADDR" \
     --log-level info \
+    --local-network \
     > "$LOG_DIR/node2.log" 2>&1 &
 NODE2_PID=$!
 echo "$NODE2_PID" >> "$PID_FILE"
@@ -171,6 +173,7 @@ log_info "ノード3を起動しています..."
     --p2p-port 9093 \
     -b "$BOOTSTRAP_ADDR" \
     --log-level info \
+    --local-network \
     > "$LOG_DIR/node3.log" 2>&1 &
 NODE3_PID=$!
 echo "$NODE3_PID" >> "$PID_FILE"
@@ -0,0 +1,682 @@
+//! Content Fetch Service - Retrieves content from whichever peers serve it
+//! fastest.
+//!
+//! [`PeerNetwork::fetch_content`] talks to a single peer. This service finds
+//! every node that can serve a content (DHT providers and the content
+//! network members) and spreads the work across them:
+//!
+//! - The first chunk is requested from several peers at once; the first
+//!   answer also tells the size and version of the data.
+//! - The remaining chunks are requested in parallel, each from a different
+//!   peer, and a chunk that fails is retried on the next peer.
+//! - The assembled data is checked against the expected SHA-256 digest. If
+//!   it does not match, each peer is asked for the whole content in turn,
+//!   so a corrupt peer can be told apart from the honest ones.
+//!
+//! Candidates with a lower measured round-trip time are queried first.
+//! Nodes that are not members of the content network present a read
+//! capability, which the serving members verify.
+
+use crate::domain::errors::{NetworkError, StateNodeError};
+use crate::domain::peer_latency::sort_by_latency;
+use crate::port::content_repository::SerializedOperation;
+use crate::port::peer_network::{ContentChunk, PeerNetwork, ReadCapability};
+use crate::port::persistence::PersistentContentRepository;
+use futures::stream::{FuturesUnordered, StreamExt};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// Default size of the byte ranges requested from different peers.
+pub const DEFAULT_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// Default upper bound on the size of fetched content.
+pub const DEFAULT_MAX_CONTENT_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Parallel fetch parameters.
+#[derive(Debug, Clone)]
+pub struct ContentFetchConfig {
+    /// Maximum number of requests in flight at the same time.
+    pub max_parallel: usize,
+    /// How long to wait for a single peer before moving on to the next one.
+    pub peer_timeout: Duration,
+    /// Size of the byte ranges requested from different peers.
+    pub chunk_size: u64,
+    /// Content announced as larger than this is not fetched.
+    pub max_content_size: u64,
+}
+
+impl Default for ContentFetchConfig {
+    fn default() -> Self {
+        Self {
+            max_parallel: 3,
+            peer_timeout: Duration::from_secs(10),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_content_size: DEFAULT_MAX_CONTENT_SIZE,
+        }
+    }
+}
+
+/// Content fetched from peers.
+#[derive(Debug, Clone)]
+pub struct FetchedContent {
+    /// The content data.
+    pub data: Vec<u8>,
+    /// The peer that served the first chunk.
+    pub peer_id: String,
+    /// Peers that failed before the data was complete, with the reason.
+    pub failures: Vec<(String, String)>,
+}
+
+/// Service for fetching content from multiple peers in parallel.
+pub struct ContentFetchService<P, C>
+where
+    P: PeerNetwork,
+    C: PersistentContentRepository,
+{
+    peer_network: Arc<P>,
+    content_network_repo: Arc<RwLock<C>>,
+    local_node_id: String,
+    config: ContentFetchConfig,
+}
+
+impl<P, C> ContentFetchService<P, C>
+where
+    P: PeerNetwork,
+    C: PersistentContentRepository,
+{
+    /// Create a new ContentFetchService.
+    pub fn new(
+        peer_network: Arc<P>,
+        content_network_repo: Arc<RwLock<C>>,
+        local_node_id: String,
+        config: ContentFetchConfig,
+    ) -> Self {
+        Self {
+            peer_network,
+            content_network_repo,
+            local_node_id,
+            config,
+        }
+    }
+
+    /// Fetch the latest data of `content_id` from the peers serving it.
+    ///
+    /// When `expected_sha256` is given, data whose SHA-256 digest differs is
+    /// discarded. `capability` is presented to peers when this node is not a
+    /// member of the content network.
+    pub async fn fetch(
+        &self,
+        content_id: &str,
+        expected_sha256: Option<&[u8; 32]>,
+        capability: Option<&ReadCapability>,
+    ) -> Result<FetchedContent, StateNodeError> {
+        let candidates = self.candidates(content_id).await;
+        if candidates.is_empty() {
+            return Err(StateNodeError::NoAvailableMembers);
+        }
+
+        let mut failures = Vec::new();
+        let chunk_size = self.config.chunk_size.max(1);
+        let first = self
+            .race(
+                &candidates,
+                content_id,
+                chunk_size,
+                expected_sha256,
+                capability,
+                &mut failures,
+            )
+            .await;
+        if let Some((peer_id, chunk)) = first {
+            if chunk.data.len() as u64 == chunk.total_size {
+                return Ok(Self::fetched(content_id, chunk.data, peer_id, failures));
+            }
+
+            match self
+                .fetch_remaining(&candidates, content_id, chunk, capability, &mut failures)
+                .await
+            {
+                Some(data) if Self::verify(&data, expected_sha256) => {
+                    return Ok(Self::fetched(content_id, data, peer_id, failures));
+                }
+                Some(_) => {
+                    tracing::warn!("Chunks of {} failed hash check", content_id);
+                    failures.push((peer_id, "assembled data hash mismatch".to_string()));
+                }
+                None => {}
+            }
+
+            // Fall back to whole-content requests, verified per peer.
+            let whole = self
+                .race(
+                    &candidates,
+                    content_id,
+                    self.config.max_content_size,
+                    expected_sha256,
+                    capability,
+                    &mut failures,
+                )
+                .await;
+            if let Some((peer_id, chunk)) = whole {
+                return Ok(Self::fetched(content_id, chunk.data, peer_id, failures));
+            }
+        }
+
+        Err(StateNodeError::NetworkError(
+            NetworkError::ConnectionFailed(format!(
+                "Failed to fetch {} after {} failed requests: [{}]",
+                content_id,
+                failures.len(),
+                failures
+                    .iter()
+                    .map(|(peer, reason)| format!("{peer}: {reason}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        ))
+    }
+
+    /// Fetch all CRDT operations of `genesis_cid` from whichever of `peers`
+    /// first answers with a non-empty history, querying several at once.
+    ///
+    /// Returns the serving peer and its operations, or `None` if no peer
+    /// supplied any.
+    pub async fn fetch_operations(
+        &self,
+        genesis_cid: &str,
+        peers: &[String],
+        capability: Option<&ReadCapability>,
+    ) -> Option<(String, Vec<SerializedOperation>)> {
+        let mut peers = peers.to_vec();
+        let latencies = self.peer_network.peer_latencies().await;
+        sort_by_latency(&mut peers, &latencies, String::as_str);
+
+        let mut pending = peers.into_iter();
+        let mut in_flight = FuturesUnordered::new();
+        for peer_id in pending.by_ref().take(self.config.max_parallel.max(1)) {
+            in_flight.push(self.fetch_operations_from(peer_id, genesis_cid, capability));
+        }
+        while let Some((peer_id, result)) = in_flight.next().await {
+            match result {
+                Ok(operations) if !operations.is_empty() => return Some((peer_id, operations)),
+                Ok(_) => {
+                    tracing::debug!("{} returned no operations for {}", peer_id, genesis_cid);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to fetch operations of {} from {}: {}",
+                        genesis_cid,
+                        peer_id,
+                        e
+                    );
+                }
+            }
+            if let Some(next) = pending.next() {
+                in_flight.push(self.fetch_operations_from(next, genesis_cid, capability));
+            }
+        }
+        None
+    }
+
+    /// Peers that may serve `content_id`: DHT providers first, then known
+    /// content network members, without duplicates or the local node.
+    /// Ordered by round-trip time, nearest first.
+    async fn candidates(&self, content_id: &str) -> Vec<String> {
+        let mut candidates = match self.peer_network.find_content_providers(content_id).await {
+            Ok(providers) => providers,
+            Err(e) => {
+                tracing::debug!("Failed to find providers of {}: {}", content_id, e);
+                Vec::new()
+            }
+        };
+
+        if let Ok(Some(network)) = self
+            .content_network_repo
+            .read()
+            .await
+            .get_content_network(content_id)
+            .await
+        {
+            candidates.extend(
+                network
+                    .member_nodes()
+                    .iter()
+                    .map(|node_id| node_id.as_str().to_string()),
+            );
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        candidates.retain(|peer_id| *peer_id != self.local_node_id && seen.insert(peer_id.clone()));
+        let latencies = self.peer_network.peer_latencies().await;
+        sort_by_latency(&mut candidates, &latencies, String::as_str);
+        candidates
+    }
+
+    /// Request the first `length` bytes from several candidates at once and
+    /// return the first acceptable answer. An answer holding the whole data
+    /// must match `expected_sha256`; a partial one is checked once assembled.
+    async fn race(
+        &self,
+        candidates: &[String],
+        content_id: &str,
+        length: u64,
+        expected_sha256: Option<&[u8; 32]>,
+        capability: Option<&ReadCapability>,
+        failures: &mut Vec<(String, String)>,
+    ) -> Option<(String, ContentChunk)> {
+        let mut pending = candidates.iter();
+        let mut in_flight = FuturesUnordered::new();
+        for peer_id in pending.by_ref().take(self.config.max_parallel.max(1)) {
+            in_flight.push(self.fetch_range(peer_id, content_id, 0, length, capability));
+        }
+
+        while let Some((peer_id, result)) = in_flight.next().await {
+            let rejection = match result {
+                Ok(chunk) if chunk.total_size > self.config.max_content_size => {
+                    format!("content too large ({} bytes)", chunk.total_size)
+                }
+                Ok(chunk) if chunk.data.len() as u64 != length.min(chunk.total_size) => {
+                    "short range".to_string()
+                }
+                Ok(chunk)
+                    if (chunk.data.len() as u64) < chunk.total_size
+                        || Self::verify(&chunk.data, expected_sha256) =>
+                {
+                    return Some((peer_id, chunk));
+                }
+                Ok(_) => {
+                    tracing::warn!("Content {} from {} failed hash check", content_id, peer_id);
+                    "hash mismatch".to_string()
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to fetch {} from {}: {}", content_id, peer_id, e);
+                    e
+                }
+            };
+            failures.push((peer_id, rejection));
+
+            if let Some(next) = pending.next() {
+                in_flight.push(self.fetch_range(next, content_id, 0, length, capability));
+            }
+        }
+        None
+    }
+
+    /// Fetch the chunks after `first` in parallel, each from a different
+    /// candidate, and assemble the data. A chunk that fails, or that comes
+    /// from another version of the data, is retried on the next candidate.
+    ///
+    /// Returns `None` if some chunk could not be fetched from any candidate.
+    async fn fetch_remaining(
+        &self,
+        candidates: &[String],
+        content_id: &str,
+        first: ContentChunk,
+        capability: Option<&ReadCapability>,
+        failures: &mut Vec<(String, String)>,
+    ) -> Option<Vec<u8>> {
+        let chunk_size = self.config.chunk_size.max(1);
+        let total_size = first.total_size;
+        let offsets: Vec<u64> = (first.data.len() as u64..total_size)
+            .step_by(chunk_size as usize)
+            .collect();
+
+        let fetch_chunk = |index: usize, attempt: usize| {
+            let offset = offsets[index];
+            let peer_id = &candidates[(index + attempt) % candidates.len()];
+            let length = chunk_size.min(total_size - offset);
+            let request = self.fetch_range(peer_id, content_id, offset, length, capability);
+            async move { (index, attempt, length, request.await) }
+        };
+
+        let mut chunks = BTreeMap::new();
+        let mut pending = 0..offsets.len();
+        let mut in_flight = FuturesUnordered::new();
+        for index in pending.by_ref().take(self.config.max_parallel.max(1)) {
+            in_flight.push(fetch_chunk(index, 0));
+        }
+
+        while let Some((index, attempt, length, (peer_id, result))) = in_flight.next().await {
+            match result {
+                Ok(chunk)
+                    if chunk.version == first.version
+                        && chunk.total_size == total_size
+                        && chunk.data.len() as u64 == length =>
+                {
+                    chunks.insert(index, chunk.data);
+                    if let Some(next) = pending.next() {
+                        in_flight.push(fetch_chunk(next, 0));
+                    }
+                }
+                result => {
+                    let reason = match result {
+                        Ok(_) => "chunk from another version".to_string(),
+                        Err(e) => e,
+                    };
+                    tracing::debug!(
+                        "Failed to fetch chunk {} of {} from {}: {}",
+                        index,
+                        content_id,
+                        peer_id,
+                        reason
+                    );
+                    failures.push((peer_id, reason));
+                    if attempt + 1 >= candidates.len() {
+                        return None;
+                    }
+                    in_flight.push(fetch_chunk(index, attempt + 1));
+                }
+            }
+        }
+
+        let mut data = first.data;
+        for chunk in chunks.into_values() {
+            data.extend(chunk);
+        }
+        Some(data)
+    }
+
+    async fn fetch_range(
+        &self,
+        peer_id: &str,
+        content_id: &str,
+        offset: u64,
+        length: u64,
+        capability: Option<&ReadCapability>,
+    ) -> (String, Result<ContentChunk, String>) {
+        let result = match tokio::time::timeout(
+            self.config.peer_timeout,
+            self.peer_network
+                .fetch_content_range(peer_id, content_id, offset, length, capability),
+        )
+        .await
+        {
+            Ok(Ok(chunk)) => Ok(chunk),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err("timed out".to_string()),
+        };
+        (peer_id.to_string(), result)
+    }
+
+    async fn fetch_operations_from(
+        &self,
+        peer_id: String,
+        genesis_cid: &str,
+        capability: Option<&ReadCapability>,
+    ) -> (String, Result<Vec<SerializedOperation>, String>) {
+        let request = async {
+            match capability {
+                Some(capability) => {
+                    self.peer_network
+                        .fetch_operations_with_capability(&peer_id, genesis_cid, capability)
+                        .await
+                }
+                None => {
+                    self.peer_network
+                        .fetch_operations(&peer_id, genesis_cid, None)
+                        .await
+                }
+            }
+        };
+        let result = match tokio::time::timeout(self.config.peer_timeout, request).await {
+            Ok(Ok(operations)) => Ok(operations),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err("timed out".to_string()),
+        };
+        (peer_id, result)
+    }
+
+    fn fetched(
+        content_id: &str,
+        data: Vec<u8>,
+        peer_id: String,
+        failures: Vec<(String, String)>,
+    ) -> FetchedContent {
+        tracing::debug!(
+            "Fetched {} ({} bytes) from {} after {} failures",
+            content_id,
+            data.len(),
+            peer_id,
+            failures.len()
+        );
+        FetchedContent {
+            data,
+            peer_id,
+            failures,
+        }
+    }
+
+    fn verify(data: &[u8], expected_sha256: Option<&[u8; 32]>) -> bool {
+        match expected_sha256 {
+            Some(expected) => Sha256::digest(data).as_slice() == expected,
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_network, MockContentNetworkRepository, MockPeerNetwork};
+    use std::collections::HashMap;
+
+    type TestFetchService = ContentFetchService<MockPeerNetwork, MockContentNetworkRepository>;
+
+    fn create_service(peer_network: MockPeerNetwork, members: Vec<&str>) -> TestFetchService {
+        let content_network_repo = Arc::new(RwLock::new(
+            MockContentNetworkRepository::new()
+                .with_network(create_test_network("content-1", members)),
+        ));
+        ContentFetchService::new(
+            Arc::new(peer_network),
+            content_network_repo,
+            "node-1".to_string(),
+            ContentFetchConfig {
+                max_parallel: 2,
+                peer_timeout: Duration::from_secs(5),
+                ..ContentFetchConfig::default()
+            },
+        )
+    }
+
+    /// A service fetching in 4-byte chunks, with no content network record.
+    fn create_chunking_service(peer_network: MockPeerNetwork) -> TestFetchService {
+        ContentFetchService::new(
+            Arc::new(peer_network),
+            Arc::new(RwLock::new(MockContentNetworkRepository::new())),
+            "node-1".to_string(),
+            ContentFetchConfig {
+                max_parallel: 2,
+                chunk_size: 4,
+                ..ContentFetchConfig::default()
+            },
+        )
+    }
+
+    fn contents(entries: &[(&str, &[u8])]) -> HashMap<String, Vec<u8>> {
+        entries
+            .iter()
+            .map(|(peer, data)| (peer.to_string(), data.to_vec()))
+            .collect()
+    }
+
+    fn sha256(data: &[u8]) -> [u8; 32] {
+        Sha256::digest(data).into()
+    }
+
+    #[tokio::test]
+    async fn test_fetch_returns_fastest_peer() {
+        let peer_network = MockPeerNetwork::new()
+            .with_providers(vec!["node-2".to_string(), "node-3".to_string()])
+            .with_peer_contents(contents(&[("node-2", b"data"), ("node-3", b"data")]))
+            .with_fetch_delays(HashMap::from([(
+                "node-2".to_string(),
+                Duration::from_millis(500),
+            )]));
+        let service = create_service(peer_network, vec!["node-1"]);
+
+        let fetched = service
+            .fetch("content-1", Some(&sha256(b"data")), None)
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.peer_id, "node-3");
+        assert_eq!(fetched.data, b"data");
+        assert!(fetched.failures.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_queries_nearest_peers_first() {
+        // Only two peers are queried at once; node-2 would answer first but
+        // has not been measured, so the nearer node-4 and node-3 are asked.
+        let peer_network = MockPeerNetwork::new()
+            .with_providers(vec![
+                "node-2".to_string(),
+                "node-3".to_string(),
+                "node-4".to_string(),
+            ])
+            .with_peer_contents(contents(&[
+                ("node-2", b"data"),
+                ("node-3", b"data"),
+                ("node-4", b"data"),
+            ]))
+            .with_fetch_delays(HashMap::from([(
+                "node-3".to_string(),
+                Duration::from_millis(500),
+            )]))
+            .with_latencies(HashMap::from([
+                ("node-3".to_string(), Duration::from_millis(40)),
+                ("node-4".to_string(), Duration::from_millis(5)),
+            ]));
+        let service = create_service(peer_network, vec!["node-1"]);
+
+        let fetched = service.fetch("content-1", None, None).await.unwrap();
+
+        assert_eq!(fetched.peer_id, "node-4");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_falls_back_past_failing_and_corrupt_peers() {
+        // node-2 doesn't have the content and node-3 serves corrupt data; the
+        // member node-4 is only queried once one of them has failed.
+        let peer_network = MockPeerNetwork::new()
+            .with_providers(vec!["node-2".to_string(), "node-3".to_string()])
+            .with_peer_contents(contents(&[("node-3", b"corrupt"), ("node-4", b"data")]));
+        let service = create_service(peer_network, vec!["node-1", "node-4"]);
+
+        let fetched = service
+            .fetch("content-1", Some(&sha256(b"data")), None)
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.peer_id, "node-4");
+        assert_eq!(fetched.failures.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_fails_when_no_peer_serves_valid_content() {
+        let peer_network = MockPeerNetwork::new()
+            .with_providers(vec!["node-2".to_string()])
+            .with_peer_contents(contents(&[("node-2", b"corrupt")]));
+        let service = create_service(peer_network, vec!["node-1"]);
+
+        let result = service
+            .fetch("content-1", Some(&sha256(b"data")), None)
+            .await;
+        assert!(matches!(result, Err(StateNodeError::NetworkError(_))));
+
+        let service = create_service(MockPeerNetwork::new(), vec!["node-1"]);
+        assert!(matches!(
+            service.fetch("content-1", None, None).await,
+            Err(StateNodeError::NoAvailableMembers)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_spreads_chunks_across_peers() {
+        let data = b"0123456789ab";
+        let peer_network = MockPeerNetwork::new()
+            .with_providers(vec!["node-2".to_string(), "node-3".to_string()])
+            .with_peer_contents(contents(&[("node-2", data), ("node-3", data)]));
+        let service = create_chunking_service(peer_network);
+
+        let fetched = service
+            .fetch("content-1", Some(&sha256(data)), None)
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.data, data);
+        let ranges = service.peer_network.range_fetches.lock().await.clone();
+        // The first chunk is raced; the remaining two go to different peers.
+        let later: Vec<_> = ranges.iter().filter(|(_, offset)| *offset > 0).collect();
+        assert_eq!(later.len(), 2);
+        assert_ne!(later[0].0, later[1].0);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_falls_back_to_whole_content_when_chunks_mismatch() {
+        // node-3 serves corrupt data of the same size, so the assembled
+        // chunks fail the hash check and each peer is asked for the whole
+        // content.
+        let data = b"0123456789ab";
+        let peer_network = MockPeerNetwork::new()
+            .with_providers(vec!["node-2".to_string(), "node-3".to_string()])
+            .with_peer_contents(contents(&[("node-2", data), ("node-3", b"01234567xxab")]));
+        let service = create_chunking_service(peer_network);
+
+        let fetched = service
+            .fetch("content-1", Some(&sha256(data)), None)
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.data, data);
+        assert_eq!(fetched.peer_id, "node-2");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_presents_read_capability() {
+        let peer_network = MockPeerNetwork::new()
+            .with_providers(vec!["node-2".to_string()])
+            .with_peer_contents(contents(&[("node-2", b"data")]));
+        let service = create_service(peer_network, vec!["node-1"]);
+        let capability = ReadCapability {
+            auth_token: "token".to_string(),
+            request_signature: vec![1, 2, 3],
+            timestamp: 1,
+        };
+
+        service
+            .fetch("content-1", None, Some(&capability))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *service.peer_network.capability_fetches.lock().await,
+            vec![("node-2".to_string(), capability)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_operations_takes_first_non_empty_history() {
+        let peer_network = MockPeerNetwork::new();
+        peer_network.fetched_operations.lock().await.push(
+            crate::test_utils::create_test_operation("content-1", "node-2"),
+        );
+        let service = create_service(peer_network, vec!["node-1"]);
+
+        let (peer_id, operations) = service
+            .fetch_operations("content-1", &["node-2".to_string()], None)
+            .await
+            .unwrap();
+
+        assert_eq!(peer_id, "node-2");
+        assert_eq!(operations.len(), 1);
+        assert!(service
+            .fetch_operations("content-1", &[], None)
+            .await
+            .is_none());
+    }
+}
@@ -1,10 +1,26 @@
 //! Content Sync Service - Handles synchronization of CRDT content between nodes.
+//!
+//! Besides event-driven sync, the node runs an anti-entropy loop: every
+//! content network it belongs to is periodically reconciled with its members
+//! and DHT providers, so operations whose gossip was missed (e.g. while
+//! offline) are still picked up. Each content is scheduled independently,
+//! with jitter and exponential backoff on failure.
+//!
+//! Every pull is also recorded in a [`SyncStatusTracker`], which operators
+//! and users read to see whether a content is fully replicated.
 
 use crate::domain::errors::{NetworkError, StateNodeError};
+use crate::domain::events::current_timestamp;
+use crate::domain::sync_status::{SyncObservation, SyncStatusTracker};
+use crate::domain::version_record::{version_record_key, VersionRecord};
 use crate::port::content_repository::ContentRepository;
-use crate::port::peer_network::PeerNetwork;
+use crate::port::peer_network::{DhtRecord, PeerNetwork};
 use crate::port::persistence::PersistentContentRepository;
+use rand::Rng;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
 /// Result of a sync operation.
@@ -14,10 +30,140 @@ pub struct SyncResult {
     pub operations_applied: usize,
     /// Number of providers contacted.
     pub providers_contacted: usize,
+    /// Number of contacted providers whose operations could not be fetched.
+    pub providers_failed: usize,
     /// Any errors encountered during sync (non-fatal).
     pub errors: Vec<String>,
 }
 
+impl SyncResult {
+    /// Whether the sync reached at least one provider, or had none to reach.
+    pub fn is_success(&self) -> bool {
+        self.providers_contacted == 0 || self.providers_failed < self.providers_contacted
+    }
+}
+
+/// Anti-entropy scheduling parameters.
+#[derive(Debug, Clone)]
+pub struct AntiEntropyConfig {
+    /// Base interval between syncs of the same content.
+    pub interval: Duration,
+    /// Fraction of the delay by which each sync is randomly shifted
+    /// (0.2 = ±20%), so nodes don't sync the same content in lockstep.
+    pub jitter: f64,
+    /// Upper bound of the delay after repeated failures.
+    pub max_backoff: Duration,
+}
+
+impl Default for AntiEntropyConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            jitter: 0.2,
+            max_backoff: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+impl AntiEntropyConfig {
+    /// Delay before the next sync of a content after `failures` consecutive
+    /// failed attempts, shifted by `jitter_sample` (in `-1.0..=1.0`).
+    pub fn next_delay(&self, failures: u32, jitter_sample: f64) -> Duration {
+        let backoff = self
+            .interval
+            .saturating_mul(1u32 << failures.min(16))
+            .min(self.max_backoff.max(self.interval));
+        let shift = self.jitter.clamp(0.0, 1.0) * jitter_sample.clamp(-1.0, 1.0);
+        backoff.mul_f64(1.0 + shift)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ScheduleEntry {
+    next_due: Instant,
+    failures: u32,
+}
+
+/// Anti-entropy state of one content, as reported to operators.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScheduledSync {
+    /// Genesis CID of the content.
+    pub content_id: String,
+    /// Number of consecutive failed syncs.
+    pub consecutive_failures: u32,
+    /// Seconds until the next sync (0 if already due).
+    pub next_sync_in_secs: u64,
+}
+
+/// Per-content anti-entropy state: when each content is next due and how
+/// many consecutive syncs of it failed.
+#[derive(Debug, Clone, Default)]
+pub struct AntiEntropySchedule {
+    entries: HashMap<String, ScheduleEntry>,
+}
+
+impl AntiEntropySchedule {
+    /// Whether `content_id` should be synced at `now`. Content not seen
+    /// before is due immediately.
+    pub fn is_due(&self, content_id: &str, now: Instant) -> bool {
+        self.entries
+            .get(content_id)
+            .map(|entry| entry.next_due <= now)
+            .unwrap_or(true)
+    }
+
+    /// Number of consecutive failed syncs of `content_id`.
+    pub fn failures(&self, content_id: &str) -> u32 {
+        self.entries
+            .get(content_id)
+            .map(|entry| entry.failures)
+            .unwrap_or(0)
+    }
+
+    /// Record the outcome of a sync and schedule the next one.
+    pub fn record(
+        &mut self,
+        content_id: &str,
+        success: bool,
+        now: Instant,
+        config: &AntiEntropyConfig,
+    ) {
+        let failures = if success {
+            0
+        } else {
+            self.failures(content_id).saturating_add(1)
+        };
+        let jitter_sample = rand::thread_rng().gen_range(-1.0..=1.0);
+        self.entries.insert(
+            content_id.to_string(),
+            ScheduleEntry {
+                next_due: now + config.next_delay(failures, jitter_sample),
+                failures,
+            },
+        );
+    }
+
+    /// Forget content that is no longer synced.
+    pub fn retain(&mut self, content_ids: &[String]) {
+        self.entries.retain(|id, _| content_ids.contains(id));
+    }
+
+    /// State of every scheduled content at `now`, ordered by content ID.
+    pub fn scheduled(&self, now: Instant) -> Vec<ScheduledSync> {
+        let mut scheduled: Vec<ScheduledSync> = self
+            .entries
+            .iter()
+            .map(|(content_id, entry)| ScheduledSync {
+                content_id: content_id.clone(),
+                consecutive_failures: entry.failures,
+                next_sync_in_secs: entry.next_due.saturating_duration_since(now).as_secs(),
+            })
+            .collect();
+        scheduled.sort_by(|a, b| a.content_id.cmp(&b.content_id));
+        scheduled
+    }
+}
+
 /// Result of a push operation.
 #[derive(Debug, Clone)]
 pub struct PushResult {
@@ -45,6 +191,8 @@ where
     crdt_repo: Arc<R>,
     content_network_repo: Arc<RwLock<C>>,
     local_node_id: String,
+    /// Sync status of each content, updated on every pull.
+    status: Arc<RwLock<SyncStatusTracker>>,
 }
 
 impl<P, R, C> ContentSyncService<P, R, C>
@@ -65,9 +213,22 @@ where
             crdt_repo,
             content_network_repo,
             local_node_id,
+            status: Arc::new(RwLock::new(SyncStatusTracker::default())),
         }
     }
 
+    /// Record sync status into `status` (builder pattern), e.g. to share it
+    /// with the application service.
+    pub fn with_status_tracker(mut self, status: Arc<RwLock<SyncStatusTracker>>) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// The sync status tracker this service records into.
+    pub fn status_tracker(&self) -> Arc<RwLock<SyncStatusTracker>> {
+        self.status.clone()
+    }
+
     /// Sync content from other nodes (pull-based).
     ///
     /// This fetches operations from content providers and applies them locally.
@@ -75,6 +236,7 @@ where
         let mut result = SyncResult {
             operations_applied: 0,
             providers_contacted: 0,
+            providers_failed: 0,
             errors: Vec::new(),
         };
 
@@ -109,9 +271,32 @@ where
             .ok()
             .and_then(|h| h.last().cloned());
 
-        // 3. Fetch operations from each member node
-        for node_id in network.member_nodes() {
-            let node_id_str = node_id.as_str();
+        // 3. Fetch operations from each member node, then from any other
+        // node announcing the content in the DHT (e.g. a member that joined
+        // while this node was offline and whose membership we missed).
+        let mut sources: Vec<String> = network
+            .member_nodes()
+            .iter()
+            .map(|node_id| node_id.as_str().to_string())
+            .collect();
+        match self.peer_network.find_content_providers(genesis_cid).await {
+            Ok(providers) => {
+                for provider in providers {
+                    if !sources.contains(&provider) {
+                        sources.push(provider);
+                    }
+                }
+            }
+            Err(e) => {
+                result
+                    .errors
+                    .push(format!("Failed to find providers: {}", e));
+            }
+        }
+
+        let mut remote_versions: Vec<(String, String)> = Vec::new();
+        let mut pending_ops = 0;
+        for node_id_str in sources.iter().map(String::as_str) {
             if node_id_str == self.local_node_id {
                 continue; // Skip self
             }
@@ -120,10 +305,13 @@ where
 
             match self
                 .peer_network
-                .fetch_operations(node_id_str, genesis_cid, local_version.as_deref())
+                .fetch_operations_with_version(node_id_str, genesis_cid, local_version.as_deref())
                 .await
             {
-                Ok(ops) => {
+                Ok((ops, remote_version)) => {
+                    if let Some(version) = remote_version {
+                        remote_versions.push((node_id_str.to_string(), version));
+                    }
                     if ops.is_empty() {
                         continue;
                     }
@@ -140,6 +328,7 @@ where
                             );
                         }
                         Err(e) => {
+                            pending_ops += ops.len();
                             result.errors.push(format!(
                                 "Failed to apply operations from {}: {}",
                                 node_id_str, e
@@ -148,6 +337,7 @@ where
                     }
                 }
                 Err(e) => {
+                    result.providers_failed += 1;
                     result
                         .errors
                         .push(format!("Failed to fetch from {}: {}", node_id_str, e));
@@ -155,6 +345,56 @@ where
             }
         }
 
+        // 4. If no peer reported its version (e.g. no member is online), fall
+        // back to the latest version a member announced in the DHT.
+        if remote_versions.is_empty() {
+            let members: Vec<String> = network
+                .member_nodes()
+                .iter()
+                .map(|node_id| node_id.as_str().to_string())
+                .filter(|node_id| node_id != &self.local_node_id)
+                .collect();
+            // Each member announces under its own key.
+            let lookups = members.iter().map(|member| {
+                self.peer_network
+                    .get_records(version_record_key(genesis_cid, member))
+            });
+            let mut records = Vec::new();
+            for lookup in futures::future::join_all(lookups).await {
+                match lookup {
+                    Ok(found) => records.extend(found),
+                    Err(e) => {
+                        tracing::debug!("No version record for {}: {}", genesis_cid, e);
+                    }
+                }
+            }
+            remote_versions.extend(announced_version(genesis_cid, &members, &records));
+        }
+
+        // 5. Record the sync status. A peer ahead of (or diverged from) the
+        // local replica is the one worth reporting.
+        let local_version = self
+            .crdt_repo
+            .get_history(genesis_cid)
+            .await
+            .ok()
+            .and_then(|h| h.last().cloned());
+        let remote = remote_versions
+            .iter()
+            .find(|(_, version)| local_version.as_ref() != Some(version))
+            .or(remote_versions.first())
+            .cloned();
+        self.status.write().await.record(
+            genesis_cid,
+            SyncObservation {
+                local_version,
+                remote,
+                reached_peer: result.providers_failed < result.providers_contacted,
+                pending_ops,
+            },
+            current_timestamp(),
+        );
+
         Ok(result)
     }
 
@@ -245,7 +485,64 @@ where
     pub async fn sync_all_content(&self) -> Result<Vec<(String, SyncResult)>, StateNodeError> {
         let mut results = Vec::new();
 
-        // Get all content networks
+        for content_id in self.member_content_ids().await? {
+            match self.sync_from_peers(&content_id).await {
+                Ok(result) => {
+                    results.push((content_id, result));
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to sync content {}: {}", content_id, e);
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Run one anti-entropy round: sync every member content that is due
+    /// according to `schedule`, then reschedule it (backing off on failure).
+    ///
+    /// Meant to be called frequently; content that is not due is skipped.
+    pub async fn anti_entropy_round(
+        &self,
+        schedule: &mut AntiEntropySchedule,
+        config: &AntiEntropyConfig,
+    ) -> Result<Vec<(String, SyncResult)>, StateNodeError> {
+        let content_ids = self.member_content_ids().await?;
+        schedule.retain(&content_ids);
+        self.status.write().await.retain(&content_ids);
+
+        let mut results = Vec::new();
+        for content_id in content_ids {
+            if !schedule.is_due(&content_id, Instant::now()) {
+                continue;
+            }
+            let success = match self.sync_from_peers(&content_id).await {
+                Ok(result) => {
+                    let success = result.is_success();
+                    results.push((content_id.clone(), result));
+                    success
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to sync content {}: {}", content_id, e);
+                    false
+                }
+            };
+            schedule.record(&content_id, success, Instant::now(), config);
+            if !success {
+                tracing::debug!(
+                    "Anti-entropy sync of {} failed {} times in a row; backing off",
+                    content_id,
+                    schedule.failures(&content_id)
+                );
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Content networks this node is a member of.
+    async fn member_content_ids(&self) -> Result<Vec<String>, StateNodeError> {
         let content_ids = self
             .content_network_repo
             .read()
@@ -254,8 +551,8 @@ where
             .await
             .map_err(|e| StateNodeError::StorageError(e.to_string()))?;
 
+        let mut members = Vec::new();
         for content_id in content_ids {
-            // Check if we're a member
             // NOTE: Acquire read lock transiently to avoid holding it across sync_from_peers
             // (which makes network calls with 30s timeouts). Holding the lock would block
             // write acquisitions from the event handler, causing effective deadlock.
@@ -271,18 +568,10 @@ where
                 .unwrap_or(false);
 
             if is_member {
-                match self.sync_from_peers(&content_id).await {
-                    Ok(result) => {
-                        results.push((content_id, result));
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to sync content {}: {}", content_id, e);
-                    }
-                }
+                members.push(content_id);
             }
         }
-
-        Ok(results)
+        Ok(members)
     }
 
     /// Broadcast a new operation to all peers.
@@ -312,10 +601,31 @@ where
             crdt_repo: self.crdt_repo.clone(),
             content_network_repo: self.content_network_repo.clone(),
             local_node_id: self.local_node_id.clone(),
+            status: self.status.clone(),
         }
     }
 }
 
+/// The latest version of `genesis_cid` announced in a DHT record by one of
+/// `members`, with the announcing member. Records published by non-members
+/// are ignored.
+fn announced_version(
+    genesis_cid: &str,
+    members: &[String],
+    records: &[DhtRecord],
+) -> Option<(String, String)> {
+    records
+        .iter()
+        .filter(|record| members.contains(&record.publisher))
+        .filter_map(|record| {
+            VersionRecord::from_bytes(&record.value)
+                .filter(|version| version.genesis_cid == genesis_cid)
+                .map(|version| (record.published_at, &record.publisher, version))
+        })
+        .max_by_key(|(published_at, _, _)| *published_at)
+        .map(|(_, publisher, version)| (publisher.clone(), version.version_cid))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -411,6 +721,85 @@ mod tests {
         assert_eq!(result.operations_applied, 2);
         assert_eq!(result.providers_contacted, 1);
         assert!(result.errors.is_empty());
+
+        let status = service.status_tracker();
+        let status = status.read().await;
+        let status = status.get("content-1").unwrap();
+        assert!(status.last_success_at.is_some());
+        assert_eq!(status.pending_ops, 0);
+        assert!(status.is_in_sync());
+    }
+
+    #[tokio::test]
+    async fn test_sync_status_reports_peer_ahead() {
+        let peer_network = Arc::new(
+            MockPeerNetwork::new()
+                .with_local_peer_id("node-1")
+                .with_remote_version("remote-head"),
+        );
+        let content_network_repo = Arc::new(RwLock::new(
+            MockContentNetworkRepository::new()
+                .with_network(create_test_network("content-1", vec!["node-1", "node-2"])),
+        ));
+        let service = ContentSyncService::new(
+            peer_network,
+            Arc::new(MockContentRepository::new()),
+            content_network_repo,
+            "node-1".to_string(),
+        );
+
+        service.sync_from_peers("content-1").await.unwrap();
+
+        let status = service.status_tracker();
+        let status = status.read().await;
+        let status = status.get("content-1").unwrap();
+        assert_eq!(status.remote_version.as_deref(), Some("remote-head"));
+        assert_eq!(status.remote_peer.as_deref(), Some("node-2"));
+        assert!(!status.is_in_sync());
+    }
+
+    #[tokio::test]
+    async fn test_sync_status_falls_back_to_version_record() {
+        let record = |publisher: &str, version: &str, published_at: u64| DhtRecord {
+            publisher: publisher.to_string(),
+            value: VersionRecord {
+                genesis_cid: "content-1".to_string(),
+                version_cid: version.to_string(),
+                content_sha256: None,
+            }
+            .to_bytes(),
+            published_at,
+        };
+        let peer_network = Arc::new(
+            MockPeerNetwork::new()
+                .with_local_peer_id("node-1")
+                .with_records(
+                    version_record_key("content-1", "node-2"),
+                    vec![
+                        record("node-2", "member-head", 10),
+                        // Newer, but not published by a member.
+                        record("node-9", "forged-head", 20),
+                    ],
+                ),
+        );
+        let content_network_repo = Arc::new(RwLock::new(
+            MockContentNetworkRepository::new()
+                .with_network(create_test_network("content-1", vec!["node-1", "node-2"])),
+        ));
+        let service = ContentSyncService::new(
+            peer_network,
+            Arc::new(MockContentRepository::new()),
+            content_network_repo,
+            "node-1".to_string(),
+        );
+
+        service.sync_from_peers("content-1").await.unwrap();
+
+        let status = service.status_tracker();
+        let status = status.read().await;
+        let status = status.get("content-1").unwrap();
+        assert_eq!(status.remote_version.as_deref(), Some("member-head"));
+        assert_eq!(status.remote_peer.as_deref(), Some("node-2"));
     }
 
     #[tokio::test]
@@ -432,6 +821,111 @@ mod tests {
         assert!(result.errors.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_sync_from_peers_includes_dht_providers() {
+        let peer_network = Arc::new(
+            MockPeerNetwork::new()
+                .with_local_peer_id("node-1")
+                .with_providers(vec![
+                    "node-1".to_string(),
+                    "node-2".to_string(),
+                    "node-4".to_string(),
+                ])
+                .with_fetched_operations(vec![create_test_operation("content-1", "node-4")]),
+        );
+        let content_network_repo = Arc::new(RwLock::new(
+            MockContentNetworkRepository::new()
+                .with_network(create_test_network("content-1", vec!["node-1", "node-2"])),
+        ));
+        let service = ContentSyncService::new(
+            peer_network,
+            Arc::new(MockContentRepository::new()),
+            content_network_repo,
+            "node-1".to_string(),
+        );
+
+        let result = service.sync_from_peers("content-1").await.unwrap();
+
+        // node-2 (member) and node-4 (provider only); self and duplicates skipped
+        assert_eq!(result.providers_contacted, 2);
+        assert!(result.is_success());
+    }
+
+    #[test]
+    fn test_anti_entropy_delay_backs_off_and_is_capped() {
+        let config = AntiEntropyConfig {
+            interval: Duration::from_secs(30),
+            jitter: 0.2,
+            max_backoff: Duration::from_secs(300),
+        };
+
+        assert_eq!(config.next_delay(0, 0.0), Duration::from_secs(30));
+        assert_eq!(config.next_delay(2, 0.0), Duration::from_secs(120));
+        assert_eq!(config.next_delay(10, 0.0), Duration::from_secs(300));
+        assert_eq!(config.next_delay(u32::MAX, 0.0), Duration::from_secs(300));
+        assert_eq!(config.next_delay(0, 1.0), Duration::from_secs(36));
+        assert_eq!(config.next_delay(0, -1.0), Duration::from_secs(24));
+    }
+
+    #[test]
+    fn test_anti_entropy_schedule_tracks_failures() {
+        let config = AntiEntropyConfig::default();
+        let mut schedule = AntiEntropySchedule::default();
+        let now = Instant::now();
+
+        assert!(schedule.is_due("content-1", now));
+
+        schedule.record("content-1", false, now, &config);
+        schedule.record("content-1", false, now, &config);
+        assert_eq!(schedule.failures("content-1"), 2);
+        assert!(!schedule.is_due("content-1", now));
+        // Two failures: at least 4x the interval minus jitter
+        assert!(schedule.is_due("content-1", now + config.interval * 5));
+        assert!(!schedule.is_due("content-1", now + config.interval * 3));
+
+        let scheduled = schedule.scheduled(now);
+        assert_eq!(scheduled.len(), 1);
+        assert_eq!(scheduled[0].consecutive_failures, 2);
+        assert!(scheduled[0].next_sync_in_secs >= config.interval.as_secs() * 3);
+        assert_eq!(
+            schedule.scheduled(now + config.interval * 5)[0].next_sync_in_secs,
+            0
+        );
+
+        schedule.record("content-1", true, now, &config);
+        assert_eq!(schedule.failures("content-1"), 0);
+
+        schedule.retain(&[]);
+        assert!(schedule.scheduled(now).is_empty());
+        assert!(schedule.is_due("content-1", now));
+    }
+
+    #[tokio::test]
+    async fn test_anti_entropy_round_skips_content_not_due() {
+        let service = create_service_with_members(
+            "node-1",
+            "content-1",
+            vec!["node-1", "node-2"],
+            vec![create_test_operation("content-1", "node-2")],
+        );
+        let config = AntiEntropyConfig::default();
+        let mut schedule = AntiEntropySchedule::default();
+
+        let results = service
+            .anti_entropy_round(&mut schedule, &config)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1.operations_applied, 1);
+
+        // Just synced: not due again until the interval elapses
+        let results = service
+            .anti_entropy_round(&mut schedule, &config)
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
     #[tokio::test]
     async fn test_push_to_peers_no_network() {
         let service = create_test_service("node-1");
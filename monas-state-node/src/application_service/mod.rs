@@ -1,5 +1,8 @@
+pub mod content_fetch_service;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod content_sync_service;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod node;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod node_config;
 pub mod state_node_service;
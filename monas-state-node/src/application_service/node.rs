@@ -1,36 +1,84 @@
 //! State Node - Main node structure combining all components.
 
 #[cfg(not(target_arch = "wasm32"))]
-use crate::application_service::content_sync_service::ContentSyncService;
+use crate::application_service::content_fetch_service::ContentFetchService;
 #[cfg(not(target_arch = "wasm32"))]
-use crate::application_service::state_node_service::{ServiceConfig, StateNodeService};
+use crate::application_service::content_sync_service::{
+    AntiEntropyConfig, AntiEntropySchedule, ContentSyncService,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::application_service::state_node_service::{
+    ApplyOutcome, ServiceConfig, StateNodeService,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::domain::events::Event;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::domain::eviction::DEFAULT_EVICTION_GRACE_SECS;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::domain::known_peer::{select_known_peers, DEFAULT_KNOWN_PEER_TTL_SECS, MAX_KNOWN_PEERS};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::domain::mirror::{MirrorConfig, MirrorRole};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::domain::node_attestation::TrustedAccounts;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::domain::peer_access::PeerAccess;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::domain::sync_rules::SyncRules;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::infrastructure::auth::{HttpAccountRevocationList, MonasAccountAdapter, UcanAdapter};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::infrastructure::crdt_repository::{CrslCrdtRepository, DEFAULT_COMPACT_AFTER_OPS};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::infrastructure::auth::{MonasAccountAdapter, UcanAdapter};
+use crate::infrastructure::disk_capacity;
 #[cfg(not(target_arch = "wasm32"))]
-use crate::infrastructure::crdt_repository::CrslCrdtRepository;
+use crate::infrastructure::event_dedup::SeenEvents;
 #[cfg(not(target_arch = "wasm32"))]
-use crate::infrastructure::gossipsub_publisher::GossipsubEventPublisher;
+use crate::infrastructure::event_journal::{SledEventJournal, DEFAULT_APPLIED_RETENTION};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::infrastructure::event_log::EventLogRecorder;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::infrastructure::gossipsub_publisher::{
+    GossipsubEventPublisher, PublishQueue, DEFAULT_PUBLISH_QUEUE_CAPACITY,
+};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::infrastructure::inbox_persistence::SledInboxPersistence;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::infrastructure::key_management::{KeyStore, NodeKeyPair};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::infrastructure::network::{Libp2pNetwork, Libp2pNetworkConfig};
+use crate::infrastructure::network::{
+    Libp2pNetwork, Libp2pNetworkConfig, ReceivedEvent, PROVIDER_REPUBLISH_INTERVAL,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::infrastructure::node_attestation::load_node_attestation;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::infrastructure::outbox_persistence::SledOutboxPersistence;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::infrastructure::persistence::SledAccessControlRepository;
 #[cfg(not(target_arch = "wasm32"))]
-use crate::infrastructure::persistence::{SledContentNetworkRepository, SledNodeRegistry};
+use crate::infrastructure::persistence::{
+    AtRestKeySource, SledContentNetworkRepository, SledNodeRegistry,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::infrastructure::persistence::{SledDenylistRepository, SledEvictionRepository};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::infrastructure::reliable_event_publisher::{
     ReliableEventPublisher, ReliablePublisherConfig,
 };
 #[cfg(not(target_arch = "wasm32"))]
+use crate::infrastructure::storage_tiers::{StorageTierConfig, TieredBlobStore};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::port::content_repository::ContentRepository;
+#[cfg(not(target_arch = "wasm32"))]
 use crate::port::peer_network::PeerNetwork;
 #[cfg(not(target_arch = "wasm32"))]
+use crate::port::persistence::{
+    PersistentEvictionRepository, PersistentPeerAccessRepository, PersistentPeerStore,
+};
+#[cfg(not(target_arch = "wasm32"))]
 use crate::port::public_key_registry::PublicKeyRegistry;
 #[cfg(not(target_arch = "wasm32"))]
+use crate::presentation::admin_api::{create_admin_router, AdminContext, LogFilterControl};
+#[cfg(not(target_arch = "wasm32"))]
 use crate::presentation::http_api::{create_router, AppState};
 #[cfg(not(target_arch = "wasm32"))]
 use anyhow::{Context, Result};
@@ -43,7 +91,7 @@ use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Duration;
 #[cfg(not(target_arch = "wasm32"))]
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 #[cfg(not(target_arch = "wasm32"))]
 use tokio_util::sync::CancellationToken;
 
@@ -57,9 +105,13 @@ pub struct StateNodeConfig {
     pub http_addr: SocketAddr,
     /// Network configuration.
     pub network_config: Libp2pNetworkConfig,
-    /// Node ID (optional, generated if not provided).
+    /// Node ID (optional). The node ID is always the libp2p PeerId, since
+    /// events are signed with the identity key behind it; when set, this
+    /// must match that PeerId and only guards against starting with the
+    /// wrong identity.
     pub node_id: Option<String>,
-    /// Sync interval in seconds (default: 30).
+    /// Interval in seconds at which each content network is reconciled with
+    /// its peers by the anti-entropy sync (default: 30).
     pub sync_interval_secs: u64,
     /// Outbox retry interval in seconds (default: 10).
     pub outbox_retry_interval_secs: u64,
@@ -69,6 +121,85 @@ pub struct StateNodeConfig {
     /// Capacity threshold in bytes below which a node is considered low on storage (default: 1GB).
     /// Can be set via CAPACITY_THRESHOLD_BYTES environment variable.
     pub capacity_threshold_bytes: u64,
+    /// Maximum number of DHT peers tried when the closest peers lack capacity
+    /// for new content (default: 64).
+    /// Can be set via MAX_PLACEMENT_CANDIDATES environment variable.
+    pub max_placement_candidates: usize,
+    /// Loopback address of the operator admin API (peers, dialing, sync
+    /// state, retry queues, log level). Disabled by default.
+    /// Can be set via ADMIN_ADDR environment variable.
+    pub admin_addr: Option<SocketAddr>,
+    /// Maximum bytes the CRDT content store may use. New content is refused
+    /// when nearly full, and capacity reports never exceed the quota.
+    /// Unlimited by default.
+    /// Can be set via STORAGE_QUOTA_BYTES environment variable.
+    pub storage_quota_bytes: Option<u64>,
+    /// Seconds the local data of content this node no longer hosts is kept
+    /// before it is purged (default: 86400).
+    /// Can be set via EVICTION_GRACE_SECS environment variable.
+    pub eviction_grace_secs: u64,
+    /// Operations after the last snapshot at which a content's CRDT log is
+    /// compacted into a new snapshot (default: 256). `0` disables compaction.
+    /// Can be set via COMPACT_AFTER_OPS environment variable.
+    pub compact_after_ops: usize,
+    /// Operator token for the admin API (e.g. `POST /admin/denylist`).
+    /// Admin endpoints are disabled when unset.
+    /// Can be set via ADMIN_TOKEN environment variable.
+    pub admin_token: Option<String>,
+    /// Selective sync rules: which content this node is willing to replicate.
+    /// Replicates everything by default.
+    /// Can be set via SYNC_LABELS, SYNC_NAMESPACES and SYNC_MAX_CONTENT_SIZE
+    /// environment variables.
+    pub sync_rules: SyncRules,
+    /// At-rest encryption of the node registry and content network stores.
+    /// Disabled by default; existing plaintext data is migrated when enabled.
+    /// Can be set via ENCRYPT_AT_REST (derive from node identity) or
+    /// AT_REST_KEY (hex-encoded 32-byte key, e.g. from an OS keystore).
+    pub at_rest_encryption: AtRestKeySource,
+    /// Mirror mode (warm standby between two personal nodes).
+    /// Disabled by default.
+    /// Can be set via MIRROR_ROLE (`primary` or `secondary`) and MIRROR_PEER
+    /// (node ID of the paired node) environment variables.
+    pub mirror: Option<MirrorConfig>,
+    /// Path of a JSON Lines file to which every received network event is
+    /// appended, for replaying real sessions in tests.
+    /// Disabled by default.
+    /// Can be set via EVENT_LOG_PATH environment variable.
+    pub event_log_path: Option<PathBuf>,
+    /// Maximum number of events kept in the Gossipsub publish queue while
+    /// the network is unavailable (default: 10000).
+    /// Can be set via PUBLISH_QUEUE_CAPACITY environment variable.
+    pub publish_queue_capacity: usize,
+    /// Cold storage roots (e.g. HDDs) and the tiering policy that moves
+    /// infrequently accessed content blobs off the data directory.
+    /// Single-directory storage by default.
+    /// Can be set via COLD_DATA_DIRS and TIERING_COLD_AFTER_SECS environment
+    /// variables.
+    pub storage_tiers: StorageTierConfig,
+    /// Path of a JSON file holding this node's attestation, signed by the
+    /// owning account and presented to peers via identify.
+    /// Not presented by default.
+    /// Can be set via NODE_ATTESTATION_PATH environment variable.
+    pub node_attestation_path: Option<PathBuf>,
+    /// Accounts whose nodes may be selected as members of new content.
+    /// Placement is unrestricted by default.
+    /// Can be set via TRUSTED_ACCOUNTS environment variable (comma-separated
+    /// hex-encoded account public keys).
+    pub trusted_accounts: Option<TrustedAccounts>,
+    /// Hand off content and announce departure on shutdown, for planned
+    /// shutdowns that should not degrade availability.
+    /// Disabled by default.
+    /// Can be set via LEAVE_ON_SHUTDOWN environment variable.
+    pub leave_on_shutdown: bool,
+    /// Interval in seconds at which disk capacity is re-measured and
+    /// significant changes are announced (default: 300).
+    /// Can be set via CAPACITY_REPORT_INTERVAL_SECS environment variable.
+    pub capacity_report_interval_secs: u64,
+    /// Base URL of the monas-account server whose revocation list is checked
+    /// before account signatures and tokens are accepted.
+    /// Revocations are not checked by default.
+    /// Can be set via ACCOUNT_URL environment variable.
+    pub account_url: Option<String>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -89,6 +220,50 @@ impl Default for StateNodeConfig {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(1_073_741_824), // 1GB
+            max_placement_candidates: std::env::var("MAX_PLACEMENT_CANDIDATES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(ServiceConfig::default().max_placement_candidates),
+            admin_addr: std::env::var("ADMIN_ADDR")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            storage_quota_bytes: std::env::var("STORAGE_QUOTA_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            eviction_grace_secs: std::env::var("EVICTION_GRACE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_EVICTION_GRACE_SECS),
+            compact_after_ops: std::env::var("COMPACT_AFTER_OPS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_COMPACT_AFTER_OPS),
+            admin_token: std::env::var("ADMIN_TOKEN").ok().filter(|v| !v.is_empty()),
+            sync_rules: SyncRules::from_env(),
+            at_rest_encryption: AtRestKeySource::from_env(),
+            mirror: MirrorConfig::from_env(),
+            event_log_path: std::env::var("EVENT_LOG_PATH")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from),
+            publish_queue_capacity: std::env::var("PUBLISH_QUEUE_CAPACITY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_PUBLISH_QUEUE_CAPACITY),
+            storage_tiers: StorageTierConfig::from_env(),
+            node_attestation_path: std::env::var("NODE_ATTESTATION_PATH")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from),
+            trusted_accounts: TrustedAccounts::from_env(),
+            leave_on_shutdown: std::env::var("LEAVE_ON_SHUTDOWN")
+                .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(false),
+            capacity_report_interval_secs: std::env::var("CAPACITY_REPORT_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            account_url: std::env::var("ACCOUNT_URL").ok().filter(|v| !v.is_empty()),
         }
     }
 }
@@ -98,6 +273,10 @@ impl Default for StateNodeConfig {
 pub type SyncService =
     ContentSyncService<Libp2pNetwork, CrslCrdtRepository, SledContentNetworkRepository>;
 
+/// Type alias for the parallel content fetch service.
+#[cfg(not(target_arch = "wasm32"))]
+pub type FetchService = ContentFetchService<Libp2pNetwork, SledContentNetworkRepository>;
+
 /// Type alias for the reliable event publisher.
 #[cfg(not(target_arch = "wasm32"))]
 pub type ReliablePublisher = ReliableEventPublisher<Libp2pNetwork>;
@@ -118,8 +297,42 @@ pub struct StateNode {
     node_key_pair: NodeKeyPair,
     /// Public key registry.
     public_key_registry: Arc<dyn PublicKeyRegistry>,
+    /// Content blob store spread over the hot and cold storage tiers.
+    blob_store: Arc<TieredBlobStore>,
+    /// Peers identified in this and earlier runs, dialed again at startup.
+    peer_store: Arc<dyn PersistentPeerStore>,
+    /// Anti-entropy schedule as of the last round, reported by the admin API.
+    sync_schedule: Arc<RwLock<AntiEntropySchedule>>,
+    /// Runtime log filter control for the admin API.
+    log_filter: Option<Arc<dyn LogFilterControl>>,
+    /// Write-ahead log of received events, replayed on startup.
+    event_journal: Arc<SledEventJournal>,
 }
 
+/// Interval at which evicted content due for purging is removed from disk.
+#[cfg(not(target_arch = "wasm32"))]
+const EVICTION_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Interval at which CRDT logs are checked for compaction.
+#[cfg(not(target_arch = "wasm32"))]
+const COMPACTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Interval at which old applied events are removed from the event journal.
+#[cfg(not(target_arch = "wasm32"))]
+const JOURNAL_CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Interval at which journaled events that failed to apply are retried.
+#[cfg(not(target_arch = "wasm32"))]
+const JOURNAL_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Interval at which the replication monitor checks member liveness.
+#[cfg(not(target_arch = "wasm32"))]
+pub const REPLICATION_MONITOR_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Interval at which identified peers are saved to the peer store.
+#[cfg(not(target_arch = "wasm32"))]
+const KNOWN_PEERS_SAVE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 #[cfg(not(target_arch = "wasm32"))]
 impl StateNode {
     /// Create a new StateNode with the given configuration.
@@ -127,23 +340,66 @@ impl StateNode {
         // Ensure data directory exists
         std::fs::create_dir_all(&config.data_dir).context("Failed to create data directory")?;
 
-        // Initialize persistence
-        let node_registry = SledNodeRegistry::open(config.data_dir.join("nodes"))
+        // Initialize key store and load/generate P-256 key pair
+        let key_store = KeyStore::new(config.data_dir.join("keys"));
+        let node_key_pair = key_store
+            .get_default_node_key()
+            .context("Failed to load/generate node key")?;
+
+        // Initialize persistence (optionally encrypted at rest)
+        let at_rest_key = config.at_rest_encryption.resolve(&node_key_pair);
+        let mut node_registry = SledNodeRegistry::open(config.data_dir.join("nodes"))
             .context("Failed to open node registry")?;
-        let content_repo = Arc::new(RwLock::new(
+        let mut content_network_repo =
             SledContentNetworkRepository::open(config.data_dir.join("content"))
-                .context("Failed to open content repository")?,
-        ));
+                .context("Failed to open content repository")?;
+        if let Some(key) = &at_rest_key {
+            node_registry = node_registry
+                .with_encryption(key)
+                .context("Failed to enable node registry encryption")?;
+            content_network_repo = content_network_repo
+                .with_encryption(key)
+                .context("Failed to enable content repository encryption")?;
+        }
+        let content_repo = Arc::new(RwLock::new(content_network_repo));
         let access_control_repo =
             SledAccessControlRepository::open(config.data_dir.join("access_control"))
                 .context("Failed to open access control repository")?;
 
-        // Initialize CRDT repository
-        let crdt_repo = Arc::new(
-            CrslCrdtRepository::open(config.data_dir.join("crdt"))
-                .context("Failed to open CRDT repository")?,
+        // Initialize denylist (tombstones for administratively denied content)
+        // and eviction records (content this node no longer hosts), sharing
+        // one database
+        let denylist_db = sled::open(config.data_dir.join("denylist"))
+            .context("Failed to open denylist repository")?;
+        let denylist: Arc<dyn crate::port::persistence::PersistentDenylistRepository> =
+            Arc::new(SledDenylistRepository::with_db(denylist_db.clone()));
+        let evictions: Arc<dyn PersistentEvictionRepository> =
+            Arc::new(SledEvictionRepository::with_db(denylist_db));
+
+        // Operator peer access list and known peers, stored alongside the
+        // node registry and sealed with the same keys
+        let peer_access_repo: Arc<dyn PersistentPeerAccessRepository> =
+            Arc::new(node_registry.clone());
+        let peer_store: Arc<dyn PersistentPeerStore> = Arc::new(node_registry.clone());
+
+        // Initialize tiered blob storage (hot = data directory, cold = extra
+        // roots) holding the latest version of each content
+        let blob_store = Arc::new(
+            TieredBlobStore::open(&config.data_dir, &config.storage_tiers)
+                .context("Failed to open tiered blob store")?,
         );
 
+        // Initialize CRDT repository
+        let mut crdt_repo = CrslCrdtRepository::open(config.data_dir.join("crdt"))
+            .context("Failed to open CRDT repository")?
+            .with_denylist(denylist.clone())
+            .with_evictions(evictions.clone())
+            .with_blob_store(blob_store.clone());
+        if let Some(quota_bytes) = config.storage_quota_bytes {
+            crdt_repo = crdt_repo.with_quota(quota_bytes);
+        }
+        let crdt_repo = Arc::new(crdt_repo);
+
         // Initialize network with CRDT repository and content network repository for member verification
         let crdt_repo_dyn: Arc<dyn crate::port::content_repository::ContentRepository> =
             crdt_repo.clone();
@@ -152,9 +408,36 @@ impl StateNode {
                 dyn crate::port::persistence::PersistentContentRepository + Send + Sync,
             >,
         > = content_repo.clone();
+
+        // Selective sync rules are advertised and enforced by the network layer,
+        // along with the capacity aggregated across all storage tiers.
+        let mut network_config = config.network_config.clone();
+        network_config.sync_rules = config.sync_rules.clone();
+        network_config.storage_dirs = blob_store.roots();
+        for (peer_id, access) in peer_access_repo
+            .list_peer_access()
+            .await
+            .context("Failed to load peer access list")?
+        {
+            let Ok(peer_id) = peer_id.parse() else {
+                tracing::warn!("Ignoring invalid peer ID in peer access list: {}", peer_id);
+                continue;
+            };
+            match access {
+                PeerAccess::Blocked => network_config.blocked_peers.push(peer_id),
+                PeerAccess::Allowed => network_config.allowed_peers.push(peer_id),
+            }
+        }
+        network_config.known_peers = Self::load_known_peers(peer_store.as_ref())
+            .await
+            .context("Failed to load known peers")?;
+        if let Some(path) = &config.node_attestation_path {
+            network_config.node_attestation =
+                Some(load_node_attestation(path).context("Failed to load node attestation")?);
+        }
         let network = Arc::new(
             Libp2pNetwork::with_content_network_repo(
-                config.network_config.clone(),
+                network_config,
                 crdt_repo_dyn.clone(),
                 config.data_dir.clone(),
                 Some(content_repo_dyn),
@@ -164,23 +447,27 @@ impl StateNode {
         );
 
         // Initialize event publisher with Gossipsub support
-        let event_publisher = GossipsubEventPublisher::new(network.clone(), None);
+        let publish_queue = PublishQueue::open(
+            config.data_dir.join("publish_queue"),
+            config.publish_queue_capacity,
+        )
+        .context("Failed to open publish queue")?;
+        let event_publisher =
+            GossipsubEventPublisher::new(network.clone(), None).with_publish_queue(publish_queue);
         event_publisher.register_event_type().await;
 
-        // Initialize key store and load/generate P-256 key pair
-        let key_store = KeyStore::new(config.data_dir.join("keys"));
-        let node_key_pair = key_store
-            .get_default_node_key()
-            .context("Failed to load/generate node key")?;
-
         // Use libp2p PeerId as NodeId for consistency with DHT peer discovery
-        let node_id = if let Some(ref provided_id) = config.node_id {
-            // If a node ID is explicitly provided, use it (for backward compatibility)
-            provided_id.clone()
-        } else {
-            // Use the libp2p PeerId so that NodeId matches what find_closest_peers returns
-            network.local_peer_id()
-        };
+        // and so that the events this node signs name it as their author.
+        let node_id = network.local_peer_id();
+        if let Some(ref provided_id) = config.node_id {
+            if provided_id != &node_id {
+                anyhow::bail!(
+                    "Configured node ID {} does not match the PeerId {} of the node identity key",
+                    provided_id,
+                    node_id
+                );
+            }
+        }
 
         // Initialize public key registry and register our key
         let public_key_registry: Arc<dyn PublicKeyRegistry> =
@@ -203,6 +490,10 @@ impl StateNode {
             .context("Failed to open outbox persistence")?;
         let inbox = SledInboxPersistence::open(config.data_dir.join("inbox"))
             .context("Failed to open inbox persistence")?;
+        let event_journal = Arc::new(
+            SledEventJournal::open(config.data_dir.join("event_journal"))
+                .context("Failed to open event journal")?,
+        );
         let reliable_publisher = Arc::new(ReliableEventPublisher::new(
             network.clone(),
             outbox,
@@ -218,29 +509,47 @@ impl StateNode {
             )
             .context("Failed to open auth public key repository")?,
         );
-        let auth_service = MonasAccountAdapter::new();
+        let mut auth_service = MonasAccountAdapter::new();
+        if let Some(account_url) = &config.account_url {
+            auth_service = auth_service
+                .with_revocations(Arc::new(HttpAccountRevocationList::new(account_url)));
+        }
         let authz_service =
             UcanAdapter::new(crdt_repo_dyn.clone()).with_nonce_store(auth_public_key_repo.clone());
 
         // Create service with CRDT repository
-        let service = Arc::new(
-            StateNodeService::with_config(
-                node_registry,
-                content_repo,
-                network.clone(),
-                event_publisher,
-                crdt_repo.clone(),
-                node_id,
-                ServiceConfig {
-                    min_replication_factor: config.min_replication_factor,
-                    capacity_threshold_bytes: config.capacity_threshold_bytes,
-                    ..ServiceConfig::default()
-                },
-            )
-            .with_access_control_repo(access_control_repo)
-            .with_authentication_service(auth_service)
-            .with_authorization_service(authz_service),
-        );
+        let mut service = StateNodeService::with_config(
+            node_registry,
+            content_repo,
+            network.clone(),
+            event_publisher,
+            crdt_repo.clone(),
+            node_id,
+            ServiceConfig {
+                min_replication_factor: config.min_replication_factor,
+                capacity_threshold_bytes: config.capacity_threshold_bytes,
+                max_placement_candidates: config.max_placement_candidates,
+                eviction_grace_secs: config.eviction_grace_secs,
+                ..ServiceConfig::default()
+            },
+        )
+        .with_access_control_repo(access_control_repo)
+        .with_authentication_service(auth_service)
+        .with_authorization_service(authz_service)
+        .with_denylist(denylist)
+        .with_evictions(evictions)
+        .with_peer_access_repo(peer_access_repo)
+        .with_sync_status(sync_service.status_tracker());
+        if let Some(admin_token) = &config.admin_token {
+            service = service.with_admin_token(admin_token.clone());
+        }
+        if let Some(mirror) = &config.mirror {
+            service = service.with_mirror(mirror.clone(), node_key_pair.clone());
+        }
+        if let Some(trusted_accounts) = &config.trusted_accounts {
+            service = service.with_trusted_accounts(trusted_accounts.clone());
+        }
+        let service = Arc::new(service);
 
         Ok(Self {
             config,
@@ -251,9 +560,69 @@ impl StateNode {
             reliable_publisher,
             node_key_pair,
             public_key_registry,
+            blob_store,
+            peer_store,
+            sync_schedule: Arc::new(RwLock::new(AntiEntropySchedule::default())),
+            log_filter: None,
+            event_journal,
         })
     }
 
+    /// Let the admin API change the log filter at runtime (builder pattern).
+    pub fn with_log_filter(mut self, log_filter: Arc<dyn LogFilterControl>) -> Self {
+        self.log_filter = Some(log_filter);
+        self
+    }
+
+    /// Load the known peers worth dialing, forgetting stale ones.
+    async fn load_known_peers(
+        peer_store: &dyn PersistentPeerStore,
+    ) -> Result<Vec<(libp2p::PeerId, Vec<libp2p::Multiaddr>)>> {
+        let stored = peer_store.list_known_peers().await?;
+        let stored_count = stored.len();
+        let selected = select_known_peers(
+            stored.clone(),
+            crate::domain::events::current_timestamp(),
+            DEFAULT_KNOWN_PEER_TTL_SECS,
+            MAX_KNOWN_PEERS,
+        );
+        for peer in &stored {
+            if !selected.iter().any(|p| p.peer_id == peer.peer_id) {
+                peer_store.remove_known_peer(&peer.peer_id).await?;
+            }
+        }
+        if selected.len() < stored_count {
+            tracing::info!("Forgot {} stale known peers", stored_count - selected.len());
+        }
+
+        let mut known_peers = Vec::new();
+        for peer in selected {
+            let Ok(peer_id) = peer.peer_id.parse::<libp2p::PeerId>() else {
+                tracing::warn!("Ignoring invalid known peer ID: {}", peer.peer_id);
+                continue;
+            };
+            let addrs: Vec<libp2p::Multiaddr> =
+                peer.addrs.iter().filter_map(|a| a.parse().ok()).collect();
+            if !addrs.is_empty() {
+                known_peers.push((peer_id, addrs));
+            }
+        }
+        Ok(known_peers)
+    }
+
+    /// Save the peers identified since startup to the peer store.
+    async fn save_known_peers(
+        network: &Libp2pNetwork,
+        peer_store: &dyn PersistentPeerStore,
+    ) -> Result<usize> {
+        let peers = network.identified_peers().await;
+        for peer in &peers {
+            peer_store.save_known_peer(peer).await?;
+        }
+        peer_store.flush().await?;
+        Ok(peers.len())
+    }
+
     /// Get the node ID.
     pub fn node_id(&self) -> &str {
         self.service.local_node_id()
@@ -294,11 +663,21 @@ impl StateNode {
         &self.sync_service
     }
 
+    /// Get a reference to the parallel content fetch service.
+    pub fn fetch_service(&self) -> &FetchService {
+        self.service.fetch_service()
+    }
+
     /// Get a reference to the reliable event publisher.
     pub fn reliable_publisher(&self) -> &Arc<ReliablePublisher> {
         &self.reliable_publisher
     }
 
+    /// Get a reference to the tiered content blob store.
+    pub fn blob_store(&self) -> &Arc<TieredBlobStore> {
+        &self.blob_store
+    }
+
     /// Connect to another node at the given multiaddr.
     pub async fn dial(&self, addr: &str) -> Result<()> {
         let multiaddr: libp2p::Multiaddr = addr.parse().context("Invalid multiaddr")?;
@@ -335,7 +714,9 @@ impl StateNode {
             let service_for_relay = self.service.clone();
             let token_relay = token.clone();
             tokio::spawn(async move {
-                use crate::infrastructure::network::libp2p_network::RelayRequestKind;
+                use crate::infrastructure::network::libp2p_network::{
+                    RelayReply, RelayRequestKind,
+                };
                 use crate::port::auth_token::AuthToken;
                 tracing::info!("Started relay request handler");
                 loop {
@@ -364,7 +745,7 @@ impl StateNode {
                                             timestamp,
                                         )
                                         .await
-                                        .map(|_| ())
+                                        .map(|_| RelayReply::Done)
                                 }
                                 RelayRequestKind::DeleteContent {
                                     content_id,
@@ -381,7 +762,7 @@ impl StateNode {
                                             timestamp,
                                         )
                                         .await
-                                        .map(|_| ())
+                                        .map(|_| RelayReply::Done)
                                 }
                                 RelayRequestKind::InvalidateTokens {
                                     content_id,
@@ -398,7 +779,43 @@ impl StateNode {
                                             timestamp,
                                         )
                                         .await
-                                        .map(|_| ())
+                                        .map(|_| RelayReply::Done)
+                                }
+                                RelayRequestKind::AuthorizeRead {
+                                    content_id,
+                                    requester_peer_id,
+                                    capability,
+                                } => service_for_relay
+                                    .authorize_read_via_relay(
+                                        &content_id,
+                                        &requester_peer_id,
+                                        &capability,
+                                    )
+                                    .await
+                                    .map(|_| RelayReply::Done),
+                                RelayRequestKind::MirrorPair {
+                                    sender_peer_id,
+                                    request,
+                                } => {
+                                    let result = service_for_relay
+                                        .accept_mirror_pairing(&sender_peer_id, request)
+                                        .await;
+                                    if result.is_ok() {
+                                        // Start mirroring right away instead of
+                                        // waiting for the next periodic pass.
+                                        let service = service_for_relay.clone();
+                                        tokio::spawn(async move {
+                                            if let Err(e) =
+                                                service.mirror_content_networks().await
+                                            {
+                                                tracing::warn!(
+                                                    "Mirroring content networks failed: {}",
+                                                    e
+                                                );
+                                            }
+                                        });
+                                    }
+                                    result.map(RelayReply::MirrorPairAccepted)
                                 }
                             };
                             let _ = req
@@ -411,118 +828,92 @@ impl StateNode {
             });
         }
 
-        // Subscribe to network events
-        let mut event_rx = self.network.subscribe_events();
         let service = self.service.clone();
         let service_for_redundancy = service.clone();
-        let sync_service_for_events = self.sync_service.clone();
+        let service_for_providers = service.clone();
+        let service_for_purge = service.clone();
+        let mut event_handler = ReceivedEventHandler::new(
+            service.clone(),
+            self.sync_service.clone(),
+            self.event_journal.clone(),
+        );
+        if let Some(path) = &self.config.event_log_path {
+            match EventLogRecorder::open(path) {
+                Ok(recorder) => {
+                    tracing::info!("Recording received events to {}", path.display());
+                    event_handler = event_handler.with_event_log(recorder);
+                }
+                Err(e) => tracing::warn!("Event recording disabled: {:#}", e),
+            }
+        }
 
-        // Spawn event handler task
-        let token_events = token.clone();
+        // Spawn event handler task.
+        tokio::spawn(event_handler.run(self.network.subscribe_events(), token.clone()));
+
+        // Spawn journal cleanup task: forgets applied events once copies of
+        // them are no longer expected.
+        let journal_for_cleanup = self.event_journal.clone();
+        let token_journal = token.clone();
         tokio::spawn(async move {
-            tracing::info!("Started network event handler");
+            let mut interval = tokio::time::interval(JOURNAL_CLEANUP_INTERVAL);
             loop {
                 tokio::select! {
-                    _ = token_events.cancelled() => {
-                        tracing::info!("Network event handler shutting down");
-                        break;
-                    }
-                    result = event_rx.recv() => {
-                        match result {
-                            Ok(received) => {
-                                tracing::debug!(
-                                    "Received event from {}: {:?}",
-                                    received.source,
-                                    received.event.event_type()
-                                );
-
-                                // Forward to service for processing (with source PeerID for verification)
-                                match service
-                                    .handle_sync_event(&received.event, Some(&received.source))
-                                    .await
-                                {
-                                    Ok(outcome) => {
-                                        tracing::debug!("Processed sync event: {:?}", outcome);
-
-                                        // If sync is needed, perform it
-                                        if let crate::application_service::state_node_service::ApplyOutcome::NeedsSync { content_id } = outcome {
-                                            tracing::info!("Content sync needed for {}, initiating sync", content_id);
-                                            match sync_service_for_events.sync_from_peers(&content_id).await {
-                                                Ok(result) => {
-                                                    tracing::info!(
-                                                        "Content sync completed for {}: {} operations applied from {} providers",
-                                                        content_id,
-                                                        result.operations_applied,
-                                                        result.providers_contacted
-                                                    );
-                                                    if !result.errors.is_empty() {
-                                                        tracing::warn!(
-                                                            "Sync had {} errors: {:?}",
-                                                            result.errors.len(),
-                                                            result.errors
-                                                        );
-                                                    }
-                                                }
-                                                Err(e) => {
-                                                    tracing::error!(
-                                                        "Failed to sync content {}: {}",
-                                                        content_id,
-                                                        e
-                                                    );
-                                                }
-                                            }
-                                        }
-                                    }
-                                    Err(e) => {
-                                        tracing::error!("Failed to process sync event: {}", e);
-                                    }
-                                }
-                            }
-                            Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                                tracing::warn!("Event handler lagged, missed {} events", n);
-                            }
-                            Err(tokio::sync::broadcast::error::RecvError::Closed) => {
-                                tracing::info!("Event channel closed, stopping handler");
-                                break;
+                    _ = token_journal.cancelled() => break,
+                    _ = interval.tick() => {
+                        match journal_for_cleanup.cleanup_applied(DEFAULT_APPLIED_RETENTION) {
+                            Ok(0) => {}
+                            Ok(removed) => {
+                                tracing::debug!("Removed {} applied events from the journal", removed)
                             }
+                            Err(e) => tracing::warn!("Event journal cleanup failed: {:#}", e),
                         }
                     }
                 }
             }
         });
 
-        // Spawn periodic sync task
+        // Spawn anti-entropy sync task. Each content network is reconciled
+        // with its members and DHT providers about once per sync interval
+        // (jittered, backing off while its peers are unreachable); the loop
+        // itself wakes more often so that due content is picked up promptly.
         let sync_service = self.sync_service.clone();
-        let sync_interval = Duration::from_secs(self.config.sync_interval_secs);
+        let sync_schedule = self.sync_schedule.clone();
+        let anti_entropy = AntiEntropyConfig {
+            interval: Duration::from_secs(self.config.sync_interval_secs),
+            ..AntiEntropyConfig::default()
+        };
+        let tick = (anti_entropy.interval / 4).max(Duration::from_secs(1));
         let token_sync = token.clone();
         tokio::spawn(async move {
             tracing::info!(
-                "Started periodic sync task (interval: {}s)",
-                sync_interval.as_secs()
+                "Started anti-entropy sync task (interval: {}s)",
+                anti_entropy.interval.as_secs()
             );
-            let mut interval = tokio::time::interval(sync_interval);
+            let mut schedule = AntiEntropySchedule::default();
+            let mut interval = tokio::time::interval(tick);
             loop {
                 tokio::select! {
                     _ = token_sync.cancelled() => {
-                        tracing::info!("Periodic sync task shutting down");
+                        tracing::info!("Anti-entropy sync task shutting down");
                         break;
                     }
                     _ = interval.tick() => {
-                        tracing::debug!("Running periodic content sync");
-                        match sync_service.sync_all_content().await {
+                        let round = sync_service.anti_entropy_round(&mut schedule, &anti_entropy).await;
+                        *sync_schedule.write().await = schedule.clone();
+                        match round {
                             Ok(results) => {
                                 let total_applied: usize =
                                     results.iter().map(|(_, r)| r.operations_applied).sum();
                                 if total_applied > 0 {
                                     tracing::info!(
-                                        "Periodic sync completed: {} operations applied across {} contents",
+                                        "Anti-entropy sync completed: {} operations applied across {} contents",
                                         total_applied,
                                         results.len()
                                     );
                                 }
                             }
                             Err(e) => {
-                                tracing::warn!("Periodic sync failed: {}", e);
+                                tracing::warn!("Anti-entropy sync failed: {}", e);
                             }
                         }
                     }
@@ -561,8 +952,289 @@ impl StateNode {
             }
         });
 
-        // Spawn outbox retry task
+        // Spawn replication monitor task.
+        tokio::spawn(run_replication_monitor(
+            service.clone(),
+            REPLICATION_MONITOR_INTERVAL,
+            token.clone(),
+        ));
+
+        // Spawn capacity reporting task. The first tick fires immediately,
+        // so the registry reflects the disk as measured at startup.
+        let service_for_capacity = self.service.clone();
+        let crdt_repo_for_capacity = self.crdt_repo.clone();
+        let capacity_roots = self.blob_store.roots();
+        let capacity_interval =
+            Duration::from_secs(self.config.capacity_report_interval_secs.max(1));
+        let token_capacity = token.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(capacity_interval);
+            tracing::info!(
+                "Started capacity reporting task (interval: {}s)",
+                capacity_interval.as_secs()
+            );
+            loop {
+                tokio::select! {
+                    _ = token_capacity.cancelled() => {
+                        tracing::info!("Capacity reporting task shutting down");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        let capacity =
+                            match disk_capacity::get_aggregate_disk_capacity(&capacity_roots) {
+                                Ok(capacity) => capacity,
+                                Err(e) => {
+                                    tracing::warn!("Failed to measure disk capacity: {}", e);
+                                    continue;
+                                }
+                            };
+                        let (total, available) = match crdt_repo_for_capacity.storage_usage() {
+                            Some(usage) => usage.clamp_capacity(capacity),
+                            None => capacity,
+                        };
+                        match service_for_capacity.report_capacity(total, available).await {
+                            Ok(Some(_)) => {
+                                tracing::info!(
+                                    "Reported capacity: {} of {} bytes available",
+                                    available,
+                                    total
+                                );
+                            }
+                            Ok(None) => {}
+                            Err(e) => {
+                                tracing::warn!("Failed to report capacity: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        // Spawn known peer saving task
+        let network_for_peers = self.network.clone();
+        let peer_store = self.peer_store.clone();
+        let token_peers = token.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval_at(
+                tokio::time::Instant::now() + KNOWN_PEERS_SAVE_INTERVAL,
+                KNOWN_PEERS_SAVE_INTERVAL,
+            );
+            tracing::info!(
+                "Started known peer saving task (interval: {}s)",
+                KNOWN_PEERS_SAVE_INTERVAL.as_secs()
+            );
+            loop {
+                tokio::select! {
+                    _ = token_peers.cancelled() => {
+                        tracing::info!("Known peer saving task shutting down");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        match Self::save_known_peers(&network_for_peers, peer_store.as_ref()).await {
+                            Ok(saved) => tracing::debug!("Saved {} known peers", saved),
+                            Err(e) => tracing::warn!("Failed to save known peers: {}", e),
+                        }
+                    }
+                }
+            }
+        });
+
+        // Spawn provider record republication task. The first tick fires
+        // immediately, re-announcing everything held after a restart.
+        let token_providers = token.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PROVIDER_REPUBLISH_INTERVAL);
+            tracing::info!(
+                "Started provider record republication task (interval: {}s)",
+                PROVIDER_REPUBLISH_INTERVAL.as_secs()
+            );
+            loop {
+                tokio::select! {
+                    _ = token_providers.cancelled() => {
+                        tracing::info!("Provider record republication task shutting down");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        match service_for_providers.republish_provider_records().await {
+                            Ok(announced) => {
+                                if !announced.is_empty() {
+                                    tracing::info!(
+                                        "Re-announced provider records for {} content networks",
+                                        announced.len()
+                                    );
+                                }
+                            }
+                            Err(e) => {
+                                tracing::warn!("Provider record republication failed: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        // Spawn eviction purge task: removes the local data of content this
+        // node no longer hosts once its grace period has passed.
+        let crdt_repo_for_purge = self.crdt_repo.clone();
+        let token_purge = token.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(EVICTION_PURGE_INTERVAL);
+            tracing::info!(
+                "Started eviction purge task (interval: {}s)",
+                EVICTION_PURGE_INTERVAL.as_secs()
+            );
+            loop {
+                tokio::select! {
+                    _ = token_purge.cancelled() => {
+                        tracing::info!("Eviction purge task shutting down");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        let due = match service_for_purge.due_evictions().await {
+                            Ok(due) if !due.is_empty() => due,
+                            Ok(_) => continue,
+                            Err(e) => {
+                                tracing::warn!("Failed to list due evictions: {}", e);
+                                continue;
+                            }
+                        };
+                        // One content at a time, re-checking right before
+                        // its data goes that the eviction is still due.
+                        let mut purged = Vec::new();
+                        for genesis_cid in due {
+                            let crdt_repo = crdt_repo_for_purge.clone();
+                            let service = service_for_purge.clone();
+                            let handle = tokio::runtime::Handle::current();
+                            let content_id = genesis_cid.clone();
+                            let result = tokio::task::spawn_blocking(move || {
+                                crdt_repo.purge(&content_id, || {
+                                    handle
+                                        .block_on(service.is_eviction_due(&content_id))
+                                        .unwrap_or(false)
+                                })
+                            })
+                            .await;
+                            match result {
+                                Ok(Ok(true)) => purged.push(genesis_cid),
+                                Ok(Ok(false)) => {}
+                                Ok(Err(e)) => tracing::warn!(
+                                    "Eviction purge of {} failed: {}",
+                                    genesis_cid,
+                                    e
+                                ),
+                                Err(e) => tracing::warn!("Eviction purge task panicked: {}", e),
+                            }
+                        }
+                        if purged.is_empty() {
+                            continue;
+                        }
+                        tracing::info!("Purged {} evicted contents", purged.len());
+                        if let Err(e) = service_for_purge.mark_evictions_purged(&purged).await {
+                            tracing::warn!("Failed to record purged evictions: {}", e);
+                        }
+                    }
+                }
+            }
+        });
+
+        // Spawn compaction task: snapshots frequently updated content and
+        // truncates its CRDT log.
+        if self.config.compact_after_ops > 0 {
+            let crdt_repo_for_compaction = self.crdt_repo.clone();
+            let compact_after_ops = self.config.compact_after_ops;
+            let token_compaction = token.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(COMPACTION_INTERVAL);
+                tracing::info!(
+                    "Started compaction task (interval: {}s, after {} ops)",
+                    COMPACTION_INTERVAL.as_secs(),
+                    compact_after_ops
+                );
+                loop {
+                    tokio::select! {
+                        _ = token_compaction.cancelled() => {
+                            tracing::info!("Compaction task shutting down");
+                            break;
+                        }
+                        _ = interval.tick() => {
+                            let crdt_repo = crdt_repo_for_compaction.clone();
+                            let result = tokio::task::spawn_blocking(move || {
+                                crdt_repo.compact(compact_after_ops)
+                            })
+                            .await;
+                            match result {
+                                Ok(Ok(0)) => {}
+                                Ok(Ok(compacted)) => {
+                                    tracing::info!("Compacted {} CRDT logs", compacted)
+                                }
+                                Ok(Err(e)) => tracing::warn!("CRDT compaction failed: {}", e),
+                                Err(e) => tracing::warn!("CRDT compaction task panicked: {}", e),
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        // Spawn mirror mode task: the secondary (re-)runs the pairing
+        // handshake, and the primary adds its secondary to any content
+        // network it coordinates that does not include it yet.
+        if let Some(mirror) = self.config.mirror.clone() {
+            let service_for_mirror = self.service.clone();
+            let mirror_interval = Duration::from_secs(self.config.sync_interval_secs);
+            let token_mirror = token.clone();
+            tokio::spawn(async move {
+                tracing::info!(
+                    "Started mirror task as {:?} of {} (interval: {}s)",
+                    mirror.role,
+                    mirror.peer_node_id,
+                    mirror_interval.as_secs()
+                );
+                let mut interval = tokio::time::interval(mirror_interval);
+                loop {
+                    tokio::select! {
+                        _ = token_mirror.cancelled() => {
+                            tracing::info!("Mirror task shutting down");
+                            break;
+                        }
+                        _ = interval.tick() => match mirror.role {
+                            MirrorRole::Secondary => {
+                                match service_for_mirror.pair_with_mirror_primary().await {
+                                    Ok(_) => tracing::debug!(
+                                        "Mirror pairing with {} confirmed",
+                                        mirror.peer_node_id
+                                    ),
+                                    Err(e) => tracing::warn!(
+                                        "Mirror pairing with {} failed: {}",
+                                        mirror.peer_node_id,
+                                        e
+                                    ),
+                                }
+                            }
+                            MirrorRole::Primary => {
+                                match service_for_mirror.mirror_content_networks().await {
+                                    Ok(mirrored) => {
+                                        if !mirrored.is_empty() {
+                                            tracing::info!(
+                                                "Added mirror secondary to {} content networks",
+                                                mirrored.len()
+                                            );
+                                        }
+                                    }
+                                    Err(e) => {
+                                        tracing::warn!("Mirroring content networks failed: {}", e)
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        // Spawn outbox and publish queue retry task
         let reliable_publisher = self.reliable_publisher.clone();
+        let service_for_queue = self.service.clone();
         let retry_interval = Duration::from_secs(self.config.outbox_retry_interval_secs);
         let token_outbox = token.clone();
         tokio::spawn(async move {
@@ -594,11 +1266,92 @@ impl StateNode {
                                 tracing::warn!("Outbox retry failed: {}", e);
                             }
                         }
+                        match service_for_queue.event_publisher().retry_queued().await {
+                            Ok(result) => {
+                                if result.published > 0 {
+                                    tracing::info!(
+                                        "Publish queue retry: {} published, {} remaining",
+                                        result.published,
+                                        result.remaining
+                                    );
+                                }
+                            }
+                            Err(e) => {
+                                tracing::warn!("Publish queue retry failed: {}", e);
+                            }
+                        }
                     }
                 }
             }
         });
 
+        // Spawn storage tiering task: move idle blobs to the cold tier
+        if self.config.storage_tiers.is_tiered() {
+            let blob_store = self.blob_store.clone();
+            let tiering_interval = self
+                .config
+                .storage_tiers
+                .cold_after
+                .clamp(Duration::from_secs(60), Duration::from_secs(3600));
+            let token_tiering = token.clone();
+            tokio::spawn(async move {
+                tracing::info!(
+                    "Started storage tiering task (interval: {}s)",
+                    tiering_interval.as_secs()
+                );
+                let mut interval = tokio::time::interval(tiering_interval);
+                loop {
+                    tokio::select! {
+                        _ = token_tiering.cancelled() => {
+                            tracing::info!("Storage tiering task shutting down");
+                            break;
+                        }
+                        _ = interval.tick() => {
+                            let store = blob_store.clone();
+                            match tokio::task::spawn_blocking(move || store.demote_idle()).await {
+                                Ok(Ok(moved)) => {
+                                    if moved > 0 {
+                                        tracing::info!("Moved {} idle blobs to the cold tier", moved);
+                                    }
+                                }
+                                Ok(Err(e)) => tracing::warn!("Storage tiering failed: {}", e),
+                                Err(e) => tracing::warn!("Storage tiering task panicked: {}", e),
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        if let Some(admin_addr) = self.config.admin_addr {
+            let admin_router = create_admin_router(Arc::new(AdminContext {
+                service: self.service.clone(),
+                network: self.network.clone(),
+                reliable_publisher: self.reliable_publisher.clone(),
+                sync_schedule: self.sync_schedule.clone(),
+                log_filter: self.log_filter.clone(),
+            }));
+            let admin_listener = tokio::net::TcpListener::bind(admin_addr)
+                .await
+                .context("Failed to bind admin API listener")?;
+            tracing::info!("Admin API listening on {}", admin_addr);
+            if self.config.admin_token.is_none() {
+                tracing::warn!("ADMIN_TOKEN is not set; every admin API request will be refused");
+            }
+            let token_admin = token.clone();
+            tokio::spawn(async move {
+                let result = axum::serve(
+                    admin_listener,
+                    admin_router.into_make_service_with_connect_info::<SocketAddr>(),
+                )
+                .with_graceful_shutdown(token_admin.cancelled_owned())
+                .await;
+                if let Err(e) = result {
+                    tracing::error!("Admin API server error: {}", e);
+                }
+            });
+        }
+
         let listener = tokio::net::TcpListener::bind(&self.config.http_addr)
             .await
             .context("Failed to bind HTTP listener")?;
@@ -618,11 +1371,288 @@ impl StateNode {
         .await
         .context("HTTP server error")?;
 
+        match Self::save_known_peers(&self.network, self.peer_store.as_ref()).await {
+            Ok(saved) => tracing::info!("Saved {} known peers", saved),
+            Err(e) => tracing::warn!("Failed to save known peers: {}", e),
+        }
+
+        if self.config.leave_on_shutdown {
+            match self.service.leave_network().await {
+                Ok(_) => tracing::info!("Departure announced"),
+                Err(e) => tracing::warn!("Failed to leave the network: {}", e),
+            }
+        }
+
         tracing::info!("HTTP server stopped. Shutdown complete.");
         Ok(())
     }
 }
 
+/// Handles the events received from the network, as `StateNode::run` does.
+///
+/// Each event is applied once: copies arriving again via other nodes or
+/// outbox retries are ignored. Events are journaled before they are applied,
+/// so that they survive a crash; those left pending by a previous run are
+/// replayed first, and those that fail to apply are retried periodically.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct ReceivedEventHandler {
+    service: AppState,
+    sync_service: SyncService,
+    journal: Arc<SledEventJournal>,
+    event_log: Option<EventLogRecorder>,
+    seen_events: SeenEvents,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ReceivedEventHandler {
+    pub fn new(
+        service: AppState,
+        sync_service: SyncService,
+        journal: Arc<SledEventJournal>,
+    ) -> Self {
+        Self {
+            service,
+            sync_service,
+            journal,
+            event_log: None,
+            seen_events: SeenEvents::default(),
+        }
+    }
+
+    /// Also record every received event to `recorder`.
+    pub fn with_event_log(mut self, recorder: EventLogRecorder) -> Self {
+        self.event_log = Some(recorder);
+        self
+    }
+
+    /// Handle `events` until `token` is cancelled or the channel closes.
+    pub async fn run(
+        mut self,
+        mut events: broadcast::Receiver<ReceivedEvent>,
+        token: CancellationToken,
+    ) {
+        tracing::info!("Started network event handler");
+        self.replay_journal().await;
+        let mut retry = tokio::time::interval_at(
+            tokio::time::Instant::now() + JOURNAL_RETRY_INTERVAL,
+            JOURNAL_RETRY_INTERVAL,
+        );
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => {
+                    tracing::info!("Network event handler shutting down");
+                    break;
+                }
+                _ = retry.tick() => self.replay_journal().await,
+                result = events.recv() => match result {
+                    Ok(received) => self.handle(&received).await,
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::warn!("Event handler lagged, missed {} events", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        tracing::info!("Event channel closed, stopping handler");
+                        break;
+                    }
+                },
+            }
+        }
+    }
+
+    /// Handle one event received from the network.
+    pub async fn handle(&mut self, received: &ReceivedEvent) {
+        tracing::debug!(
+            "Received event from {}: {:?}",
+            received.source,
+            received.event.event_type()
+        );
+
+        if !self.seen_events.insert(&received.event) {
+            tracing::debug!(
+                "Ignoring duplicate {} event from {}",
+                received.event.event_type(),
+                received.source
+            );
+            return;
+        }
+
+        // Skip the event if it was already applied before a restart.
+        let seq = match self
+            .journal
+            .append(&received.event, Some(&received.source))
+            .await
+        {
+            Ok(Some(seq)) => Some(seq),
+            Ok(None) => {
+                tracing::debug!(
+                    "Ignoring already applied {} event from {}",
+                    received.event.event_type(),
+                    received.source
+                );
+                return;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to journal event: {:#}", e);
+                None
+            }
+        };
+
+        if let Some(recorder) = &self.event_log {
+            if let Err(e) = recorder.record(&received.event, Some(&received.source)) {
+                tracing::warn!("Failed to record event: {:#}", e);
+            }
+        }
+
+        let applied = apply_received_event(
+            &self.service,
+            &self.sync_service,
+            &received.event,
+            Some(&received.source),
+        )
+        .await;
+        if let Some(seq) = seq {
+            finish_journal_entry(&self.journal, seq, &received.event, applied).await;
+        }
+        if !applied {
+            // Let a later copy retry it, too.
+            self.seen_events.remove(&received.event);
+        }
+    }
+
+    /// Apply the events still pending in the journal: those left by the
+    /// previous run, and those that failed to apply before.
+    pub async fn replay_journal(&self) {
+        let entries = match self.journal.pending() {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::error!("Failed to read event journal: {:#}", e);
+                return;
+            }
+        };
+        if !entries.is_empty() {
+            tracing::info!("Replaying {} journaled events", entries.len());
+        }
+        for entry in entries {
+            let applied = apply_received_event(
+                &self.service,
+                &self.sync_service,
+                &entry.event,
+                entry.source_peer_id.as_deref(),
+            )
+            .await;
+            finish_journal_entry(&self.journal, entry.seq, &entry.event, applied).await;
+        }
+    }
+}
+
+/// Track member liveness every `interval` and replace members that stay
+/// unreachable, until `token` is cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn run_replication_monitor(
+    service: AppState,
+    interval: Duration,
+    token: CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    tracing::info!(
+        "Started replication monitor task (interval: {}s)",
+        interval.as_secs()
+    );
+    loop {
+        tokio::select! {
+            _ = token.cancelled() => {
+                tracing::info!("Replication monitor task shutting down");
+                break;
+            }
+            _ = ticker.tick() => {
+                match service.repair_all_replication().await {
+                    Ok(repaired) => {
+                        if !repaired.is_empty() {
+                            tracing::info!(
+                                "Replaced unreachable members of {} content networks",
+                                repaired.len()
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("Replication monitor round failed: {}", e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Apply an event received from `source_peer_id`, syncing its content from
+/// peers if the event shows the local replica is behind. Returns whether the
+/// event was handled, which requires the sync, if any, to have succeeded.
+#[cfg(not(target_arch = "wasm32"))]
+async fn apply_received_event(
+    service: &AppState,
+    sync_service: &SyncService,
+    event: &Event,
+    source_peer_id: Option<&str>,
+) -> bool {
+    // Forward to service for processing (with source PeerID for verification)
+    let outcome = match service.handle_sync_event(event, source_peer_id).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            tracing::error!("Failed to process sync event: {}", e);
+            return false;
+        }
+    };
+    tracing::debug!("Processed sync event: {:?}", outcome);
+
+    // If sync is needed, perform it
+    if let ApplyOutcome::NeedsSync { content_id } = outcome {
+        tracing::info!("Content sync needed for {}, initiating sync", content_id);
+        match sync_service.sync_from_peers(&content_id).await {
+            Ok(result) => {
+                tracing::info!(
+                    "Content sync completed for {}: {} operations applied from {} providers",
+                    content_id,
+                    result.operations_applied,
+                    result.providers_contacted
+                );
+                if !result.errors.is_empty() {
+                    tracing::warn!(
+                        "Sync had {} errors: {:?}",
+                        result.errors.len(),
+                        result.errors
+                    );
+                }
+                return result.is_success();
+            }
+            Err(e) => {
+                tracing::error!("Failed to sync content {}: {}", content_id, e);
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Mark the journal entry `seq` applied, or keep it for a retry if handling
+/// failed.
+#[cfg(not(target_arch = "wasm32"))]
+async fn finish_journal_entry(journal: &SledEventJournal, seq: u64, event: &Event, applied: bool) {
+    let result = if applied {
+        journal.mark_applied(seq, event).await
+    } else {
+        journal.record_failure(seq).map(|kept| {
+            if !kept {
+                tracing::warn!(
+                    "Dropping {} event after repeated failures to apply it",
+                    event.event_type()
+                );
+            }
+        })
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to update event journal: {:#}", e);
+    }
+}
+
 #[cfg(test)]
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
@@ -642,6 +1672,7 @@ mod tests {
         assert_eq!(config.outbox_retry_interval_secs, 10);
         assert_eq!(config.min_replication_factor, 3);
         assert_eq!(config.capacity_threshold_bytes, 1_073_741_824);
+        assert_eq!(config.max_placement_candidates, 64);
     }
 
     #[tokio::test]
@@ -657,6 +1688,7 @@ mod tests {
                 enable_mdns: false,
                 gossipsub_topics: vec!["test".to_string()],
                 external_addrs: vec![],
+                ..Default::default()
             },
             node_id: Some("test-node-id".to_string()),
             sync_interval_secs: 30,
@@ -664,15 +1696,63 @@ mod tests {
             ..StateNodeConfig::default()
         };
 
-        let node = StateNode::new(config).await.unwrap();
+        // A node ID other than the identity key's PeerId is refused.
+        let other_dir = tempdir().unwrap();
+        assert!(StateNode::new(StateNodeConfig {
+            data_dir: other_dir.path().to_path_buf(),
+            ..config.clone()
+        })
+        .await
+        .is_err());
+
+        let node = StateNode::new(StateNodeConfig {
+            node_id: None,
+            ..config
+        })
+        .await
+        .unwrap();
 
         // Test accessors
-        assert_eq!(node.node_id(), "test-node-id");
+        assert_eq!(node.node_id(), node.network().local_peer_id());
         assert!(!node.service().local_node_id().is_empty());
         assert!(node.crdt_repo().list_contents().await.unwrap().is_empty());
         assert!(!node.network().local_peer_id().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_state_node_with_cold_storage_tier() {
+        let tmp_dir = tempdir().unwrap();
+        let cold_dir = tempdir().unwrap();
+
+        let config = StateNodeConfig {
+            data_dir: tmp_dir.path().to_path_buf(),
+            http_addr: "127.0.0.1:0".parse().unwrap(),
+            network_config: Libp2pNetworkConfig {
+                listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+                enable_mdns: false,
+                ..Default::default()
+            },
+            storage_tiers: StorageTierConfig {
+                cold_dirs: vec![cold_dir.path().to_path_buf()],
+                cold_after: Duration::from_secs(60),
+            },
+            ..StateNodeConfig::default()
+        };
+
+        let node = StateNode::new(config).await.unwrap();
+
+        assert_eq!(
+            node.blob_store().roots(),
+            vec![tmp_dir.path().to_path_buf(), cold_dir.path().to_path_buf()]
+        );
+        assert!(cold_dir.path().join("blobs").is_dir());
+        node.blob_store().put("bafy-test", b"data").unwrap();
+        assert_eq!(
+            node.blob_store().get("bafy-test").unwrap(),
+            Some(b"data".to_vec())
+        );
+    }
+
     #[tokio::test]
     async fn test_state_node_listen_addrs() {
         let tmp_dir = tempdir().unwrap();
@@ -686,6 +1766,7 @@ mod tests {
                 enable_mdns: false,
                 gossipsub_topics: vec!["test".to_string()],
                 external_addrs: vec![],
+                ..Default::default()
             },
             node_id: None,
             sync_interval_secs: 30,
@@ -716,6 +1797,7 @@ mod tests {
                 enable_mdns: false,
                 gossipsub_topics: vec!["test".to_string()],
                 external_addrs: vec![],
+                ..Default::default()
             },
             node_id: None, // Will be auto-generated from libp2p PeerId
             sync_interval_secs: 30,
@@ -747,6 +1829,7 @@ mod tests {
                 enable_mdns: false,
                 gossipsub_topics: vec!["test".to_string()],
                 external_addrs: vec![],
+                ..Default::default()
             },
             node_id: None,
             sync_interval_secs: 30,
@@ -0,0 +1,720 @@
+//! Configuration file loader for the state node.
+//!
+//! [`StateNodeConfig::from_file`] reads a TOML file, applies environment
+//! variable overrides and validates the result. Settings missing from the
+//! file keep their defaults. Without a file,
+//! [`StateNodeConfig::with_env_overrides`] applies the same overrides to a
+//! default configuration. Precedence, lowest first: defaults, file,
+//! environment, command line (applied by the binary).
+//!
+//! ```toml
+//! data_dir = "/var/lib/monas"
+//! http_addr = "0.0.0.0:8080"
+//! admin_addr = "127.0.0.1:8081"
+//! account_url = "http://127.0.0.1:3000"
+//!
+//! [network]
+//! listen_addrs = ["/ip4/0.0.0.0/tcp/9090"]
+//! bootstrap_peers = ["/ip4/203.0.113.5/tcp/9090/p2p/12D3KooW..."]
+//! topics = ["monas-events"]
+//!
+//! [replication]
+//! min_replication_factor = 3
+//!
+//! [sync]
+//! interval_secs = 30
+//!
+//! [bandwidth]
+//! upload_bytes_per_sec = 1048576
+//!
+//! [transport]
+//! swarm_key_path = "/etc/monas/swarm.key"
+//! ```
+
+use crate::application_service::node::StateNodeConfig;
+use crate::infrastructure::network::transport::load_swarm_key;
+use anyhow::{Context, Result};
+use libp2p::multiaddr::Protocol;
+use libp2p::{Multiaddr, PeerId};
+use serde::Deserialize;
+use std::fmt::Display;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Contents of a configuration file. Every setting is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    data_dir: Option<PathBuf>,
+    http_addr: Option<SocketAddr>,
+    /// Loopback address of the operator admin API.
+    admin_addr: Option<SocketAddr>,
+    node_id: Option<String>,
+    /// monas-account server whose revocation list is checked.
+    account_url: Option<String>,
+    #[serde(default)]
+    network: NetworkSection,
+    #[serde(default)]
+    replication: ReplicationSection,
+    #[serde(default)]
+    sync: SyncSection,
+    #[serde(default)]
+    bandwidth: BandwidthSection,
+    #[serde(default)]
+    transport: TransportSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NetworkSection {
+    listen_addrs: Option<Vec<String>>,
+    /// Multiaddrs ending in `/p2p/<peer id>`.
+    bootstrap_peers: Option<Vec<String>>,
+    external_addrs: Option<Vec<String>>,
+    relay_addrs: Option<Vec<String>>,
+    autonat_servers: Option<Vec<String>>,
+    /// Gossipsub topics to subscribe to.
+    topics: Option<Vec<String>>,
+    enable_mdns: Option<bool>,
+    advertise_confirmed_addrs_only: Option<bool>,
+    /// Always serve Kademlia queries instead of waiting for AutoNAT.
+    kademlia_server: Option<bool>,
+    enforce_peer_allowlist: Option<bool>,
+    /// Seconds between random-walk Kademlia queries; `0` disables them.
+    random_walk_interval_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReplicationSection {
+    min_replication_factor: Option<usize>,
+    capacity_threshold_bytes: Option<u64>,
+    max_placement_candidates: Option<usize>,
+    storage_quota_bytes: Option<u64>,
+    eviction_grace_secs: Option<u64>,
+    /// Operations after the last snapshot at which a CRDT log is compacted;
+    /// `0` disables compaction.
+    compact_after_ops: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SyncSection {
+    interval_secs: Option<u64>,
+    outbox_retry_interval_secs: Option<u64>,
+    capacity_report_interval_secs: Option<u64>,
+}
+
+/// Limits of the content protocol transfers.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BandwidthSection {
+    upload_bytes_per_sec: Option<u64>,
+    download_bytes_per_sec: Option<u64>,
+    max_transfers_per_peer: Option<usize>,
+}
+
+/// Connection handshake and private network settings.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransportSection {
+    /// Private network key file in the `/key/swarm/psk/1.0.0/` format.
+    swarm_key_path: Option<PathBuf>,
+    noise_prologue: Option<String>,
+    upgrade_timeout_secs: Option<u64>,
+    yamux_max_streams: Option<usize>,
+}
+
+impl StateNodeConfig {
+    /// Load the configuration from a TOML file.
+    ///
+    /// The following environment variables override the file:
+    /// - `STATE_NODE_DATA_DIR`, `STATE_NODE_HTTP_ADDR`, `STATE_NODE_ID`,
+    ///   `ADMIN_ADDR`, `ACCOUNT_URL`
+    /// - `STATE_NODE_LISTEN_ADDRS`, `STATE_NODE_BOOTSTRAP_PEERS`,
+    ///   `STATE_NODE_TOPICS` (comma-separated)
+    /// - `MIN_REPLICATION_FACTOR`, `CAPACITY_THRESHOLD_BYTES`,
+    ///   `MAX_PLACEMENT_CANDIDATES`, `STORAGE_QUOTA_BYTES`, `EVICTION_GRACE_SECS`
+    /// - `SYNC_INTERVAL_SECS`, `OUTBOX_RETRY_INTERVAL_SECS`,
+    ///   `CAPACITY_REPORT_INTERVAL_SECS`
+    /// - `UPLOAD_BYTES_PER_SEC`, `DOWNLOAD_BYTES_PER_SEC`, `MAX_TRANSFERS_PER_PEER`
+    /// - `SWARM_KEY_PATH`, `NOISE_PROLOGUE`, `TRANSPORT_UPGRADE_TIMEOUT_SECS`,
+    ///   `YAMUX_MAX_STREAMS`
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        Self::from_toml(&content, |name| std::env::var(name).ok())
+            .with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Apply the environment variable overrides listed in
+    /// [`from_file`](Self::from_file) and validate the result.
+    pub fn with_env_overrides(self) -> Result<Self> {
+        self.with_env(|name| std::env::var(name).ok())
+    }
+
+    fn from_toml(content: &str, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let file: ConfigFile = toml::from_str(content).context("Failed to parse TOML")?;
+        let mut config = Self::default();
+        config.apply_file(file)?;
+        config.with_env(env)
+    }
+
+    fn with_env(mut self, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        self.apply_env_overrides(|name| env(name).filter(|v| !v.trim().is_empty()))?;
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Check the configuration for values the node cannot run with.
+    pub fn validate(&self) -> Result<()> {
+        if self.network_config.listen_addrs.is_empty() {
+            anyhow::bail!("At least one P2P listen address is required");
+        }
+        if self.network_config.gossipsub_topics.is_empty() {
+            anyhow::bail!("At least one Gossipsub topic is required");
+        }
+        if self
+            .network_config
+            .gossipsub_topics
+            .iter()
+            .any(|topic| topic.trim().is_empty())
+        {
+            anyhow::bail!("Gossipsub topics must not be empty");
+        }
+        if self.network_config.transport.psk.is_some() {
+            // QUIC and WebRTC cannot carry the private network handshake.
+            if let Some(addr) = self
+                .network_config
+                .listen_addrs
+                .iter()
+                .find(|addr| addr.iter().any(|p| matches!(p, Protocol::Udp(_))))
+            {
+                anyhow::bail!("A private network only listens on TCP, got {}", addr);
+            }
+        }
+        if self
+            .node_id
+            .as_deref()
+            .is_some_and(|id| id.trim().is_empty())
+        {
+            anyhow::bail!("node_id must not be empty");
+        }
+        if let Some(admin_addr) = self.admin_addr {
+            if !admin_addr.ip().is_loopback() {
+                anyhow::bail!("admin_addr must be a loopback address, got {}", admin_addr);
+            }
+        }
+        if self.min_replication_factor == 0 {
+            anyhow::bail!("min_replication_factor must be at least 1");
+        }
+        if self.max_placement_candidates == 0 {
+            anyhow::bail!("max_placement_candidates must be at least 1");
+        }
+        if self.storage_quota_bytes == Some(0) {
+            anyhow::bail!("storage_quota_bytes must be greater than 0");
+        }
+        let bandwidth = &self.network_config.bandwidth;
+        if bandwidth.max_transfers_per_peer == 0 {
+            anyhow::bail!("bandwidth max_transfers_per_peer must be at least 1");
+        }
+        let transport = &self.network_config.transport;
+        if transport.upgrade_timeout.is_zero() {
+            anyhow::bail!("transport upgrade_timeout_secs must be greater than 0");
+        }
+        if transport.yamux_max_streams == Some(0) {
+            anyhow::bail!("transport yamux_max_streams must be at least 1");
+        }
+        if bandwidth.upload_bytes_per_sec == Some(0) || bandwidth.download_bytes_per_sec == Some(0)
+        {
+            anyhow::bail!("bandwidth rates must be greater than 0");
+        }
+        for (name, value) in [
+            ("sync interval_secs", self.sync_interval_secs),
+            (
+                "sync outbox_retry_interval_secs",
+                self.outbox_retry_interval_secs,
+            ),
+            (
+                "sync capacity_report_interval_secs",
+                self.capacity_report_interval_secs,
+            ),
+        ] {
+            if value == 0 {
+                anyhow::bail!("{} must be greater than 0", name);
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_file(&mut self, file: ConfigFile) -> Result<()> {
+        if let Some(data_dir) = file.data_dir {
+            self.data_dir = data_dir;
+        }
+        if let Some(http_addr) = file.http_addr {
+            self.http_addr = http_addr;
+        }
+        if file.admin_addr.is_some() {
+            self.admin_addr = file.admin_addr;
+        }
+        if file.node_id.is_some() {
+            self.node_id = file.node_id;
+        }
+        if file.account_url.is_some() {
+            self.account_url = file.account_url;
+        }
+
+        let network = file.network;
+        let net = &mut self.network_config;
+        if let Some(addrs) = network.listen_addrs {
+            net.listen_addrs = parse_multiaddrs(&addrs, "listen address")?;
+        }
+        if let Some(peers) = network.bootstrap_peers {
+            net.bootstrap_nodes = parse_bootstrap_peers(&peers)?;
+        }
+        if let Some(addrs) = network.external_addrs {
+            net.external_addrs = parse_multiaddrs(&addrs, "external address")?;
+        }
+        if let Some(addrs) = network.relay_addrs {
+            net.relay_addrs = parse_multiaddrs(&addrs, "relay address")?;
+        }
+        if let Some(addrs) = network.autonat_servers {
+            net.autonat_servers = parse_multiaddrs(&addrs, "AutoNAT server")?;
+        }
+        if let Some(topics) = network.topics {
+            net.gossipsub_topics = topics;
+        }
+        if let Some(enable_mdns) = network.enable_mdns {
+            net.enable_mdns = enable_mdns;
+        }
+        if let Some(confirmed_only) = network.advertise_confirmed_addrs_only {
+            net.advertise_confirmed_addrs_only = confirmed_only;
+        }
+        if let Some(server) = network.kademlia_server {
+            net.kademlia_mode = server.then_some(libp2p::kad::Mode::Server);
+        }
+        if let Some(enforce) = network.enforce_peer_allowlist {
+            net.enforce_peer_allowlist = enforce;
+        }
+        if let Some(secs) = network.random_walk_interval_secs {
+            net.random_walk_interval = (secs > 0).then(|| Duration::from_secs(secs));
+        }
+
+        let replication = file.replication;
+        if let Some(factor) = replication.min_replication_factor {
+            self.min_replication_factor = factor;
+        }
+        if let Some(bytes) = replication.capacity_threshold_bytes {
+            self.capacity_threshold_bytes = bytes;
+        }
+        if let Some(candidates) = replication.max_placement_candidates {
+            self.max_placement_candidates = candidates;
+        }
+        if replication.storage_quota_bytes.is_some() {
+            self.storage_quota_bytes = replication.storage_quota_bytes;
+        }
+        if let Some(secs) = replication.eviction_grace_secs {
+            self.eviction_grace_secs = secs;
+        }
+        if let Some(ops) = replication.compact_after_ops {
+            self.compact_after_ops = ops;
+        }
+
+        let sync = file.sync;
+        if let Some(secs) = sync.interval_secs {
+            self.sync_interval_secs = secs;
+        }
+        if let Some(secs) = sync.outbox_retry_interval_secs {
+            self.outbox_retry_interval_secs = secs;
+        }
+        if let Some(secs) = sync.capacity_report_interval_secs {
+            self.capacity_report_interval_secs = secs;
+        }
+
+        let bandwidth = file.bandwidth;
+        let limits = &mut self.network_config.bandwidth;
+        if bandwidth.upload_bytes_per_sec.is_some() {
+            limits.upload_bytes_per_sec = bandwidth.upload_bytes_per_sec;
+        }
+        if bandwidth.download_bytes_per_sec.is_some() {
+            limits.download_bytes_per_sec = bandwidth.download_bytes_per_sec;
+        }
+        if let Some(max) = bandwidth.max_transfers_per_peer {
+            limits.max_transfers_per_peer = max;
+        }
+
+        let transport = file.transport;
+        let handshake = &mut self.network_config.transport;
+        if let Some(path) = transport.swarm_key_path {
+            handshake.psk = Some(load_swarm_key(&path)?);
+        }
+        if let Some(prologue) = transport.noise_prologue {
+            handshake.noise_prologue = prologue.into_bytes();
+        }
+        if let Some(secs) = transport.upgrade_timeout_secs {
+            handshake.upgrade_timeout = Duration::from_secs(secs);
+        }
+        if transport.yamux_max_streams.is_some() {
+            handshake.yamux_max_streams = transport.yamux_max_streams;
+        }
+        Ok(())
+    }
+
+    fn apply_env_overrides(&mut self, env: impl Fn(&str) -> Option<String>) -> Result<()> {
+        if let Some(data_dir) = env("STATE_NODE_DATA_DIR") {
+            self.data_dir = PathBuf::from(data_dir);
+        }
+        if let Some(http_addr) = parse_env(&env, "STATE_NODE_HTTP_ADDR")? {
+            self.http_addr = http_addr;
+        }
+        if let Some(admin_addr) = parse_env(&env, "ADMIN_ADDR")? {
+            self.admin_addr = Some(admin_addr);
+        }
+        if let Some(node_id) = env("STATE_NODE_ID") {
+            self.node_id = Some(node_id);
+        }
+        if let Some(account_url) = env("ACCOUNT_URL") {
+            self.account_url = Some(account_url);
+        }
+        if let Some(addrs) = env("STATE_NODE_LISTEN_ADDRS") {
+            self.network_config.listen_addrs =
+                parse_multiaddrs(&split_list(&addrs), "listen address")
+                    .context("Invalid STATE_NODE_LISTEN_ADDRS")?;
+        }
+        if let Some(peers) = env("STATE_NODE_BOOTSTRAP_PEERS") {
+            self.network_config.bootstrap_nodes = parse_bootstrap_peers(&split_list(&peers))
+                .context("Invalid STATE_NODE_BOOTSTRAP_PEERS")?;
+        }
+        if let Some(topics) = env("STATE_NODE_TOPICS") {
+            self.network_config.gossipsub_topics = split_list(&topics);
+        }
+        if let Some(factor) = parse_env(&env, "MIN_REPLICATION_FACTOR")? {
+            self.min_replication_factor = factor;
+        }
+        if let Some(bytes) = parse_env(&env, "CAPACITY_THRESHOLD_BYTES")? {
+            self.capacity_threshold_bytes = bytes;
+        }
+        if let Some(candidates) = parse_env(&env, "MAX_PLACEMENT_CANDIDATES")? {
+            self.max_placement_candidates = candidates;
+        }
+        if let Some(bytes) = parse_env(&env, "STORAGE_QUOTA_BYTES")? {
+            self.storage_quota_bytes = Some(bytes);
+        }
+        if let Some(secs) = parse_env(&env, "EVICTION_GRACE_SECS")? {
+            self.eviction_grace_secs = secs;
+        }
+        if let Some(ops) = parse_env(&env, "COMPACT_AFTER_OPS")? {
+            self.compact_after_ops = ops;
+        }
+        if let Some(secs) = parse_env(&env, "SYNC_INTERVAL_SECS")? {
+            self.sync_interval_secs = secs;
+        }
+        if let Some(secs) = parse_env(&env, "OUTBOX_RETRY_INTERVAL_SECS")? {
+            self.outbox_retry_interval_secs = secs;
+        }
+        if let Some(secs) = parse_env(&env, "CAPACITY_REPORT_INTERVAL_SECS")? {
+            self.capacity_report_interval_secs = secs;
+        }
+        let limits = &mut self.network_config.bandwidth;
+        if let Some(rate) = parse_env(&env, "UPLOAD_BYTES_PER_SEC")? {
+            limits.upload_bytes_per_sec = Some(rate);
+        }
+        if let Some(rate) = parse_env(&env, "DOWNLOAD_BYTES_PER_SEC")? {
+            limits.download_bytes_per_sec = Some(rate);
+        }
+        if let Some(max) = parse_env(&env, "MAX_TRANSFERS_PER_PEER")? {
+            limits.max_transfers_per_peer = max;
+        }
+        let handshake = &mut self.network_config.transport;
+        if let Some(path) = env("SWARM_KEY_PATH") {
+            handshake.psk =
+                Some(load_swarm_key(Path::new(&path)).context("Invalid SWARM_KEY_PATH")?);
+        }
+        if let Some(prologue) = env("NOISE_PROLOGUE") {
+            handshake.noise_prologue = prologue.into_bytes();
+        }
+        if let Some(secs) = parse_env(&env, "TRANSPORT_UPGRADE_TIMEOUT_SECS")? {
+            handshake.upgrade_timeout = Duration::from_secs(secs);
+        }
+        if let Some(max) = parse_env(&env, "YAMUX_MAX_STREAMS")? {
+            handshake.yamux_max_streams = Some(max);
+        }
+        Ok(())
+    }
+}
+
+/// Parse a bootstrap peer multiaddr ending in `/p2p/<peer id>` into the peer
+/// ID and the address without the `/p2p` suffix.
+pub fn parse_bootstrap_peer(addr: &str) -> Result<(PeerId, Multiaddr)> {
+    let addr = Multiaddr::from_str(addr)
+        .with_context(|| format!("Invalid bootstrap address: {}", addr))?;
+    let Some(Protocol::P2p(peer_id)) = addr.iter().last() else {
+        anyhow::bail!("Bootstrap address missing peer ID: {}", addr);
+    };
+    let addr_without_p2p = addr
+        .iter()
+        .filter(|p| !matches!(p, Protocol::P2p(_)))
+        .collect();
+    Ok((peer_id, addr_without_p2p))
+}
+
+fn parse_bootstrap_peers(addrs: &[String]) -> Result<Vec<(PeerId, Multiaddr)>> {
+    addrs
+        .iter()
+        .map(|addr| parse_bootstrap_peer(addr))
+        .collect()
+}
+
+fn parse_multiaddrs(addrs: &[String], what: &str) -> Result<Vec<Multiaddr>> {
+    addrs
+        .iter()
+        .map(|addr| {
+            Multiaddr::from_str(addr).with_context(|| format!("Invalid {}: {}", what, addr))
+        })
+        .collect()
+}
+
+fn parse_env<T>(env: &impl Fn(&str) -> Option<String>, name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    env(name)
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid {}={}: {}", name, value, e))
+        })
+        .transpose()
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn load(content: &str, env: &[(&str, &str)]) -> Result<StateNodeConfig> {
+        let env: HashMap<String, String> = env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        StateNodeConfig::from_toml(content, |name| env.get(name).cloned())
+    }
+
+    #[test]
+    fn test_load_config_file() {
+        let peer = PeerId::random();
+        let config = load(
+            &format!(
+                r#"
+                data_dir = "/var/lib/monas"
+                http_addr = "0.0.0.0:8081"
+                admin_addr = "127.0.0.1:8082"
+
+                [network]
+                listen_addrs = ["/ip4/0.0.0.0/tcp/9090"]
+                bootstrap_peers = ["/ip4/203.0.113.5/tcp/9090/p2p/{peer}"]
+                topics = ["monas-events", "monas-test"]
+                enable_mdns = false
+                random_walk_interval_secs = 0
+                advertise_confirmed_addrs_only = false
+                kademlia_server = true
+
+                [replication]
+                min_replication_factor = 5
+
+                [sync]
+                interval_secs = 60
+                "#
+            ),
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(config.data_dir, PathBuf::from("/var/lib/monas"));
+        assert_eq!(config.http_addr, "0.0.0.0:8081".parse().unwrap());
+        assert_eq!(config.admin_addr, Some("127.0.0.1:8082".parse().unwrap()));
+        let net = &config.network_config;
+        assert_eq!(
+            net.listen_addrs,
+            vec!["/ip4/0.0.0.0/tcp/9090".parse().unwrap()]
+        );
+        assert_eq!(net.bootstrap_nodes.len(), 1);
+        assert_eq!(net.bootstrap_nodes[0].0, peer);
+        assert_eq!(
+            net.bootstrap_nodes[0].1,
+            "/ip4/203.0.113.5/tcp/9090".parse().unwrap()
+        );
+        assert_eq!(net.gossipsub_topics, vec!["monas-events", "monas-test"]);
+        assert!(!net.enable_mdns);
+        assert_eq!(net.random_walk_interval, None);
+        assert!(!net.advertise_confirmed_addrs_only);
+        assert_eq!(net.kademlia_mode, Some(libp2p::kad::Mode::Server));
+        assert_eq!(config.min_replication_factor, 5);
+        assert_eq!(config.sync_interval_secs, 60);
+        // Unset values keep their defaults.
+        assert_eq!(config.outbox_retry_interval_secs, 10);
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let config = load(
+            r#"
+            http_addr = "0.0.0.0:8081"
+            [replication]
+            min_replication_factor = 5
+            eviction_grace_secs = 60
+            compact_after_ops = 64
+            "#,
+            &[
+                ("STATE_NODE_HTTP_ADDR", "127.0.0.1:9000"),
+                ("MIN_REPLICATION_FACTOR", "2"),
+                ("EVICTION_GRACE_SECS", "120"),
+                ("STATE_NODE_TOPICS", "a, b"),
+                ("SYNC_INTERVAL_SECS", ""),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(config.http_addr, "127.0.0.1:9000".parse().unwrap());
+        assert_eq!(config.min_replication_factor, 2);
+        assert_eq!(config.eviction_grace_secs, 120);
+        assert_eq!(config.compact_after_ops, 64);
+        assert_eq!(config.network_config.gossipsub_topics, vec!["a", "b"]);
+        assert_eq!(config.sync_interval_secs, 30);
+
+        let err = load("", &[("MIN_REPLICATION_FACTOR", "many")]).unwrap_err();
+        assert!(err.to_string().contains("MIN_REPLICATION_FACTOR"));
+    }
+
+    #[test]
+    fn test_env_overrides_default_config() {
+        let env = [
+            ("STATE_NODE_DATA_DIR", "/srv/monas"),
+            ("OUTBOX_RETRY_INTERVAL_SECS", "5"),
+        ];
+        let config = StateNodeConfig::default()
+            .with_env(|name| {
+                env.iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| v.to_string())
+            })
+            .unwrap();
+
+        assert_eq!(config.data_dir, PathBuf::from("/srv/monas"));
+        assert_eq!(config.outbox_retry_interval_secs, 5);
+        assert_eq!(config.sync_interval_secs, 30);
+    }
+
+    #[test]
+    fn test_private_network_key_from_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let key_path = dir.path().join("swarm.key");
+        std::fs::write(
+            &key_path,
+            format!("/key/swarm/psk/1.0.0/\n/base16/\n{}\n", "ab".repeat(32)),
+        )
+        .unwrap();
+
+        let config = load(
+            &format!(
+                "[transport]\nswarm_key_path = {:?}\nupgrade_timeout_secs = 5",
+                key_path
+            ),
+            &[],
+        )
+        .unwrap();
+        let transport = &config.network_config.transport;
+        assert_eq!(
+            transport.psk.map(|psk| psk.fingerprint().to_string()),
+            Some(
+                libp2p::pnet::PreSharedKey::new([0xab; 32])
+                    .fingerprint()
+                    .to_string()
+            )
+        );
+        assert_eq!(transport.upgrade_timeout, Duration::from_secs(5));
+
+        let missing = dir.path().join("missing.key");
+        assert!(load(&format!("[transport]\nswarm_key_path = {:?}", missing), &[]).is_err());
+        assert!(load("", &[("SWARM_KEY_PATH", missing.to_str().unwrap())]).is_err());
+    }
+
+    #[test]
+    fn test_bandwidth_limits() {
+        let config = load(
+            r#"
+            [bandwidth]
+            upload_bytes_per_sec = 1048576
+            max_transfers_per_peer = 4
+            "#,
+            &[("DOWNLOAD_BYTES_PER_SEC", "2097152")],
+        )
+        .unwrap();
+
+        let bandwidth = &config.network_config.bandwidth;
+        assert_eq!(bandwidth.upload_bytes_per_sec, Some(1_048_576));
+        assert_eq!(bandwidth.download_bytes_per_sec, Some(2_097_152));
+        assert_eq!(bandwidth.max_transfers_per_peer, 4);
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        // Unknown keys are typos, not silently ignored settings.
+        assert!(load("data_directory = \"x\"", &[]).is_err());
+        assert!(load("[network]\nlisten_addrs = []", &[]).is_err());
+        assert!(load("[network]\nlisten_addrs = [\"not-an-addr\"]", &[]).is_err());
+        assert!(load("[network]\nbootstrap_peers = [\"/ip4/1.2.3.4/tcp/1\"]", &[]).is_err());
+        assert!(load("[replication]\nmin_replication_factor = 0", &[]).is_err());
+        assert!(load("[replication]\nstorage_quota_bytes = 0", &[]).is_err());
+        assert!(load("[sync]\ninterval_secs = 0", &[]).is_err());
+        assert!(load("[bandwidth]\nmax_transfers_per_peer = 0", &[]).is_err());
+        assert!(load("[bandwidth]\nupload_bytes_per_sec = 0", &[]).is_err());
+        // The admin API must not be reachable from other hosts.
+        assert!(load("admin_addr = \"0.0.0.0:8082\"", &[]).is_err());
+    }
+
+    #[test]
+    fn test_private_network_requires_tcp_listen_addrs() {
+        let mut config = load(
+            "[network]\nlisten_addrs = [\"/ip4/0.0.0.0/udp/0/quic-v1\"]",
+            &[],
+        )
+        .unwrap();
+        config.network_config.transport.psk = Some(libp2p::pnet::PreSharedKey::new([7; 32]));
+        assert!(config.validate().is_err());
+
+        config.network_config.listen_addrs = vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()];
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_from_file_reports_path() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("node.toml");
+        std::fs::write(&path, "[sync]\ninterval_secs = 15\n").unwrap();
+        assert_eq!(
+            StateNodeConfig::from_file(&path)
+                .unwrap()
+                .sync_interval_secs,
+            15
+        );
+
+        let missing = dir.path().join("missing.toml");
+        let err = StateNodeConfig::from_file(&missing).unwrap_err();
+        assert!(err.to_string().contains("missing.toml"));
+    }
+}
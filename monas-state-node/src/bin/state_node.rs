@@ -5,30 +5,49 @@
 use anyhow::{Context, Result};
 use clap::Parser;
 use libp2p::Multiaddr;
+use monas_state_node::application_service::node_config::parse_bootstrap_peer;
 use monas_state_node::{StateNode, StateNodeConfig};
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
-use tracing_subscriber::EnvFilter;
+use std::sync::Arc;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{reload, EnvFilter};
+
+/// P2P listen port used when neither `--p2p-port` nor a config file is given.
+const DEFAULT_P2P_PORT: u16 = 9090;
 
 /// State Node CLI arguments.
 #[derive(Parser, Debug)]
 #[command(name = "state-node")]
 #[command(about = "Monas State Node - Distributed content management")]
 struct Args {
-    /// Data directory for persistence.
-    #[arg(short, long, default_value = "data")]
-    data_dir: PathBuf,
+    /// TOML configuration file. Command line options take precedence over
+    /// the file and environment variables.
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Data directory for persistence [default: data].
+    #[arg(short, long)]
+    data_dir: Option<PathBuf>,
+
+    /// HTTP API listen address [default: 127.0.0.1:8080].
+    #[arg(short = 'l', long)]
+    listen: Option<SocketAddr>,
 
-    /// HTTP API listen address.
-    #[arg(short = 'l', long, default_value = "127.0.0.1:8080")]
-    listen: SocketAddr,
+    /// Admin API listen address (loopback only, e.g. `127.0.0.1:8081`).
+    /// The admin API is disabled unless given here, in the configuration
+    /// file or via ADMIN_ADDR.
+    #[arg(long)]
+    admin_listen: Option<SocketAddr>,
 
-    /// Node ID (optional, auto-generated if not provided).
+    /// Expected node ID (optional). The node ID is the PeerId of the node
+    /// identity key; startup fails if this does not match it.
     #[arg(short, long)]
     node_id: Option<String>,
 
-    /// Bootstrap node addresses (multiaddr format).
+    /// Bootstrap node addresses (multiaddr format), in addition to those in
+    /// the configuration file.
     #[arg(short, long)]
     bootstrap: Vec<String>,
 
@@ -38,11 +57,46 @@ struct Args {
     #[arg(long)]
     external_address: Vec<String>,
 
-    /// P2P listen port. Defaults to a fixed port so the advertised address is
-    /// stable across restarts (important for production). Pass `0` for a random
-    /// port (e.g. when running multiple nodes on one host).
-    #[arg(long, default_value = "9090")]
-    p2p_port: u16,
+    /// Circuit relay servers for reaching this node from behind NAT
+    /// (multiaddr ending in `/p2p/<peer id>`). The node reserves a slot on each
+    /// relay and attempts DCUtR hole punching. May be repeated.
+    #[arg(long)]
+    relay: Vec<String>,
+
+    /// Extra AutoNAT servers used to check whether this node is publicly
+    /// reachable (multiaddr ending in `/p2p/<peer id>`). May be repeated.
+    #[arg(long)]
+    autonat_server: Vec<String>,
+
+    /// Run on a LAN or a single host: announce every listen address and
+    /// serve Kademlia queries right away. Otherwise only addresses confirmed
+    /// by AutoNAT (which ignores private addresses) or `--external-address`
+    /// are announced, and Kademlia waits for such a confirmation.
+    #[arg(long)]
+    local_network: bool,
+
+    /// Only connect to peers allowed through the admin API
+    /// (`PUT /admin/peers/<peer id>`); every other peer is refused.
+    #[arg(long)]
+    peer_allowlist: bool,
+
+    /// Hand off member content to other nodes and announce departure on
+    /// shutdown (for planned shutdowns).
+    #[arg(long)]
+    leave_on_shutdown: bool,
+
+    /// P2P listen port. Defaults to a fixed port (9090) so the advertised
+    /// address is stable across restarts (important for production). Pass `0`
+    /// for a random port (e.g. when running multiple nodes on one host).
+    /// Replaces the listen addresses of the configuration file when given.
+    #[arg(long)]
+    p2p_port: Option<u16>,
+
+    /// Cold storage root for infrequently accessed content blobs (e.g. an
+    /// HDD mount). The data directory remains the hot tier. May be repeated;
+    /// overrides COLD_DATA_DIRS when given.
+    #[arg(long)]
+    cold_data_dir: Vec<PathBuf>,
 
     /// Log level (trace, debug, info, warn, error).
     #[arg(long, default_value = "info")]
@@ -53,47 +107,64 @@ struct Args {
 async fn main() -> Result<()> {
     let args = Args::parse();
 
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&args.log_level)),
-        )
+    // Initialize tracing. The filter can be changed at runtime through the
+    // admin API.
+    let (filter, log_filter) = reload::Layer::new(
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&args.log_level)),
+    );
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
         .init();
 
     tracing::info!("Starting Monas State Node");
-    tracing::info!("Data directory: {:?}", args.data_dir);
-    tracing::info!("HTTP listen address: {}", args.listen);
 
-    // Build configuration
-    let mut network_config = monas_state_node::infrastructure::network::Libp2pNetworkConfig {
-        listen_addrs: vec![format!("/ip4/0.0.0.0/tcp/{}", args.p2p_port)
-            .parse::<Multiaddr>()
-            .context("Failed to parse P2P listen address")?],
-        ..Default::default()
+    // Build configuration: defaults < config file < environment < CLI
+    let mut config = match &args.config {
+        Some(path) => {
+            tracing::info!("Config file: {:?}", path);
+            StateNodeConfig::from_file(path)?
+        }
+        None => {
+            let mut config = StateNodeConfig::default();
+            config.network_config.listen_addrs =
+                vec![format!("/ip4/0.0.0.0/tcp/{}", DEFAULT_P2P_PORT).parse()?];
+            config.with_env_overrides()?
+        }
     };
+    if let Some(data_dir) = args.data_dir {
+        config.data_dir = data_dir;
+    }
+    if let Some(listen) = args.listen {
+        config.http_addr = listen;
+    }
+    if args.admin_listen.is_some() {
+        config.admin_addr = args.admin_listen;
+    }
+    if args.node_id.is_some() {
+        config.node_id = args.node_id;
+    }
+    tracing::info!("Data directory: {:?}", config.data_dir);
+    tracing::info!("HTTP listen address: {}", config.http_addr);
+
+    let network_config = &mut config.network_config;
+    if let Some(p2p_port) = args.p2p_port {
+        network_config.listen_addrs = vec![format!("/ip4/0.0.0.0/tcp/{}", p2p_port)
+            .parse::<Multiaddr>()
+            .context("Failed to parse P2P listen address")?];
+    }
+    network_config.identity =
+        monas_state_node::infrastructure::network::PeerIdentityConfig::from_env();
 
     // Parse and add bootstrap addresses
     for addr_str in &args.bootstrap {
         tracing::info!("Bootstrap address: {}", addr_str);
-
-        // Parse multiaddr and extract peer ID
-        if let Ok(addr) = Multiaddr::from_str(addr_str) {
-            // Extract peer ID from the multiaddr (last component should be /p2p/<peer_id>)
-            if let Some(libp2p::multiaddr::Protocol::P2p(peer_id)) = addr.iter().last() {
-                // Create address without the /p2p/ suffix for Kademlia
-                let addr_without_p2p: Multiaddr = addr
-                    .iter()
-                    .filter(|p| !matches!(p, libp2p::multiaddr::Protocol::P2p(_)))
-                    .collect();
-                network_config
-                    .bootstrap_nodes
-                    .push((peer_id, addr_without_p2p));
+        match parse_bootstrap_peer(addr_str) {
+            Ok((peer_id, addr)) => {
+                network_config.bootstrap_nodes.push((peer_id, addr));
                 tracing::info!("Added bootstrap peer: {}", peer_id);
-            } else {
-                tracing::warn!("Bootstrap address missing peer ID: {}", addr_str);
             }
-        } else {
-            tracing::warn!("Failed to parse bootstrap address: {}", addr_str);
+            Err(e) => tracing::warn!("{}", e),
         }
     }
 
@@ -108,20 +179,53 @@ async fn main() -> Result<()> {
         }
     }
 
-    let config = StateNodeConfig {
-        data_dir: args.data_dir,
-        http_addr: args.listen,
-        network_config,
-        node_id: args.node_id,
-        sync_interval_secs: 30,
-        outbox_retry_interval_secs: 10,
-        ..StateNodeConfig::default()
-    };
+    // Parse relay server addresses.
+    for addr_str in &args.relay {
+        match Multiaddr::from_str(addr_str) {
+            Ok(addr) => {
+                tracing::info!("Relay address: {}", addr);
+                network_config.relay_addrs.push(addr);
+            }
+            Err(e) => tracing::warn!("Failed to parse relay address {}: {}", addr_str, e),
+        }
+    }
+
+    // Parse AutoNAT server addresses.
+    for addr_str in &args.autonat_server {
+        match Multiaddr::from_str(addr_str) {
+            Ok(addr) => {
+                tracing::info!("AutoNAT server: {}", addr);
+                network_config.autonat_servers.push(addr);
+            }
+            Err(e) => tracing::warn!("Failed to parse AutoNAT server {}: {}", addr_str, e),
+        }
+    }
+    if args.local_network {
+        network_config.advertise_confirmed_addrs_only = false;
+        network_config.kademlia_mode = Some(libp2p::kad::Mode::Server);
+    }
+    if args.peer_allowlist {
+        network_config.enforce_peer_allowlist = true;
+    }
+    network_config.command_queue =
+        monas_state_node::infrastructure::network::CommandQueueConfig::from_env();
+
+    if !args.cold_data_dir.is_empty() {
+        config.storage_tiers.cold_dirs = args.cold_data_dir;
+    }
+    for dir in &config.storage_tiers.cold_dirs {
+        tracing::info!("Cold storage directory: {:?}", dir);
+    }
+    if args.leave_on_shutdown {
+        config.leave_on_shutdown = true;
+    }
+    config.validate().context("Invalid configuration")?;
 
     // Create and run the node
     let node = StateNode::new(config)
         .await
-        .context("Failed to create state node")?;
+        .context("Failed to create state node")?
+        .with_log_filter(Arc::new(log_filter));
 
     tracing::info!("Node ID: {}", node.node_id());
 
@@ -100,16 +100,19 @@ impl ContentNetwork {
 
 /// Add a member node to a content network (pure function for event sourcing).
 ///
-/// Returns the updated network and a ContentNetworkManagerAdded event.
+/// Returns the updated network and a ContentNetworkManagerAdded event
+/// authored by `added_by`.
 pub fn add_member_node(
     mut network: ContentNetwork,
     node_id: NodeId,
+    added_by: &NodeId,
 ) -> Result<(ContentNetwork, Vec<Event>), ValueError> {
     network.add_member(node_id.clone());
     let event = Event::ContentNetworkManagerAdded {
         content_id: network.content_id().as_str().to_string(),
         added_node_id: node_id.as_str().to_string(),
         member_nodes: network.member_nodes_as_strings(),
+        added_by_node_id: added_by.as_str().to_string(),
         timestamp: current_timestamp(),
     };
     Ok((network, vec![event]))
@@ -117,11 +120,12 @@ pub fn add_member_node(
 
 /// Add a member node to a content network (pure function for event sourcing).
 ///
-/// Returns the updated network and a ContentNetworkManagerAdded event.
-/// The NodeId is derived from the public key.
+/// Returns the updated network and a ContentNetworkManagerAdded event
+/// authored by `added_by`. The NodeId is derived from the public key.
 pub fn add_member_node_from_public_key(
     mut network: ContentNetwork,
     public_key: Vec<u8>,
+    added_by: &NodeId,
 ) -> Result<(ContentNetwork, Vec<Event>), ValueError> {
     let added_node_id = NodeId::from_public_key(&public_key)?;
     network.add_member_from_public_key(public_key)?;
@@ -129,6 +133,7 @@ pub fn add_member_node_from_public_key(
         content_id: network.content_id().as_str().to_string(),
         added_node_id: added_node_id.as_str().to_string(),
         member_nodes: network.member_nodes_as_strings(),
+        added_by_node_id: added_by.as_str().to_string(),
         timestamp: current_timestamp(),
     };
     Ok((network, vec![event]))
@@ -136,12 +141,14 @@ pub fn add_member_node_from_public_key(
 
 /// Remove a member node from a content network (pure function for event sourcing).
 ///
-/// Returns the updated network and a ContentNetworkManagerRemoved event.
-/// If the node is not a member, returns the network unchanged with no events.
+/// Returns the updated network and a ContentNetworkManagerRemoved event
+/// authored by `removed_by`. If the node is not a member, returns the
+/// network unchanged with no events.
 pub fn remove_member_node(
     mut network: ContentNetwork,
     removed_node_id: NodeId,
     reason: String,
+    removed_by: &NodeId,
 ) -> (ContentNetwork, Vec<Event>) {
     if !network.remove_member(&removed_node_id) {
         // Node was not a member, no change
@@ -152,6 +159,7 @@ pub fn remove_member_node(
         removed_node_id: removed_node_id.as_str().to_string(),
         member_nodes: network.member_nodes_as_strings(),
         reason,
+        removed_by_node_id: removed_by.as_str().to_string(),
         timestamp: current_timestamp(),
     };
     (network, vec![event])
@@ -179,7 +187,7 @@ mod tests {
 
         let (_, key_a) = generate_test_keypair();
         let node_a = NodeId::from_public_key(&key_a).unwrap();
-        let (net, events) = add_member_node(net, node_a.clone()).unwrap();
+        let (net, events) = add_member_node(net, node_a.clone(), &initial_node).unwrap();
 
         assert!(net.has_member(&node_a));
         assert_eq!(events.len(), 1);
@@ -188,10 +196,12 @@ mod tests {
                 content_id,
                 added_node_id,
                 member_nodes,
+                added_by_node_id,
                 ..
             } => {
                 assert_eq!(content_id, "cid-1");
                 assert_eq!(added_node_id, node_a.as_str());
+                assert_eq!(added_by_node_id, initial_node.as_str());
                 assert!(member_nodes.contains(&node_a.as_str().to_string()));
             }
             _ => panic!("expected ContentNetworkManagerAdded"),
@@ -209,7 +219,7 @@ mod tests {
         let mut net = ContentNetwork::new(content_id, node_a.clone()).unwrap();
         net.add_member(node_b.clone());
 
-        let (net, events) = remove_member_node(net, node_a.clone(), "low_capacity".into());
+        let (net, events) = remove_member_node(net, node_a.clone(), "low_capacity".into(), &node_b);
 
         assert!(!net.has_member(&node_a));
         assert!(net.has_member(&node_b));
@@ -220,6 +230,7 @@ mod tests {
                 removed_node_id,
                 member_nodes,
                 reason,
+                removed_by_node_id,
                 ..
             } => {
                 assert_eq!(content_id, "cid-1");
@@ -227,6 +238,7 @@ mod tests {
                 assert!(!member_nodes.contains(&node_a.as_str().to_string()));
                 assert!(member_nodes.contains(&node_b.as_str().to_string()));
                 assert_eq!(reason, "low_capacity");
+                assert_eq!(removed_by_node_id, node_b.as_str());
             }
             _ => panic!("expected ContentNetworkManagerRemoved"),
         }
@@ -237,11 +249,11 @@ mod tests {
         let content_id = ContentId::new("cid-1".to_string()).unwrap();
         let (_, public_key) = generate_test_keypair();
         let initial_node = NodeId::from_public_key(&public_key).unwrap();
-        let net = ContentNetwork::new(content_id, initial_node).unwrap();
+        let net = ContentNetwork::new(content_id, initial_node.clone()).unwrap();
 
         let (_, key_x) = generate_test_keypair();
         let node_x = NodeId::from_public_key(&key_x).unwrap();
-        let (net, events) = remove_member_node(net, node_x.clone(), "test".into());
+        let (net, events) = remove_member_node(net, node_x.clone(), "test".into(), &initial_node);
 
         assert!(events.is_empty());
         assert!(!net.has_member(&node_x));
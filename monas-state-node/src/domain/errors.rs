@@ -15,6 +15,9 @@ pub enum StateNodeError {
     #[error("Content already exists: {0}")]
     ContentAlreadyExists(ContentId),
 
+    #[error("Content has been denied by this node: {0}")]
+    ContentDenied(ContentId),
+
     // Node-related errors
     #[error("Node not found: {0}")]
     NodeNotFound(NodeId),
@@ -25,6 +28,17 @@ pub enum StateNodeError {
     #[error("No available member nodes found")]
     NoAvailableMembers,
 
+    #[error(
+        "No available member nodes found: {eligible} of {required} eligible after trying [{}]",
+        .attempted.join(", ")
+    )]
+    PlacementExhausted {
+        required: usize,
+        eligible: usize,
+        /// Peers that were considered for placement, closest first.
+        attempted: Vec<String>,
+    },
+
     #[error("Node {node_id} is not a member of content network {content_id}")]
     NotAMember {
         node_id: String,
@@ -107,12 +121,14 @@ impl StateNodeError {
         match self {
             StateNodeError::ContentNotFound(_) => StatusCode::NOT_FOUND,
             StateNodeError::ContentAlreadyExists(_) => StatusCode::CONFLICT,
+            StateNodeError::ContentDenied(_) => StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS,
             StateNodeError::PermissionDenied(_) => StatusCode::FORBIDDEN,
             StateNodeError::InvalidUcanToken(_) => StatusCode::UNAUTHORIZED,
             StateNodeError::AuthenticationFailed(_) => StatusCode::UNAUTHORIZED,
             StateNodeError::AuthorizationFailed(_) => StatusCode::FORBIDDEN,
             StateNodeError::InsufficientCapacity { .. } => StatusCode::INSUFFICIENT_STORAGE,
             StateNodeError::NoAvailableMembers => StatusCode::SERVICE_UNAVAILABLE,
+            StateNodeError::PlacementExhausted { .. } => StatusCode::SERVICE_UNAVAILABLE,
             StateNodeError::NotAMember { .. } => StatusCode::FORBIDDEN,
             StateNodeError::InvalidCid(_) => StatusCode::BAD_REQUEST,
             StateNodeError::InvalidConfiguration(_) => StatusCode::BAD_REQUEST,
@@ -162,6 +178,20 @@ mod tests {
         assert_eq!(err.to_http_status(), StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    fn test_content_denied_error() {
+        let content_id = ContentId::new("content-1".to_string()).unwrap();
+        let err = StateNodeError::ContentDenied(content_id);
+        assert_eq!(
+            err.to_string(),
+            "Content has been denied by this node: content-1"
+        );
+        assert_eq!(
+            err.to_http_status(),
+            StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS
+        );
+    }
+
     #[test]
     fn test_not_a_member_error() {
         let content_id = ContentId::new("content-1".to_string()).unwrap();
@@ -196,6 +226,20 @@ mod tests {
         assert_eq!(err.to_http_status(), StatusCode::SERVICE_UNAVAILABLE);
     }
 
+    #[test]
+    fn test_placement_exhausted_error_lists_attempted_peers() {
+        let err = StateNodeError::PlacementExhausted {
+            required: 3,
+            eligible: 1,
+            attempted: vec!["peer-1".to_string(), "peer-2".to_string()],
+        };
+        assert_eq!(
+            err.to_string(),
+            "No available member nodes found: 1 of 3 eligible after trying [peer-1, peer-2]"
+        );
+        assert_eq!(err.to_http_status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
     #[test]
     fn test_network_error() {
         let network_err = NetworkError::ConnectionFailed("peer offline".to_string());
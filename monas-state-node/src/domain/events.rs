@@ -16,6 +16,14 @@ pub enum Event {
         timestamp: u64,
     },
 
+    /// A node's measured storage capacity has changed.
+    NodeCapacityUpdated {
+        node_id: String,
+        total_capacity: u64,
+        available_capacity: u64,
+        timestamp: u64,
+    },
+
     /// Content assignment has been decided.
     AssignmentDecided {
         assigning_node_id: String,
@@ -29,6 +37,9 @@ pub enum Event {
         content_id: String,
         added_node_id: String,
         member_nodes: Vec<String>,
+        /// The member that made the change (the event's author).
+        #[serde(default)]
+        added_by_node_id: String,
         timestamp: u64,
     },
 
@@ -40,6 +51,9 @@ pub enum Event {
         member_nodes: Vec<String>,
         /// Reason for removal (e.g., "low_capacity", "offline").
         reason: String,
+        /// The member that made the change (the event's author).
+        #[serde(default)]
+        removed_by_node_id: String,
         timestamp: u64,
     },
 
@@ -89,6 +103,17 @@ pub enum Event {
         /// Deletion timestamp.
         timestamp: u64,
     },
+
+    /// A node is leaving the network (planned shutdown).
+    ///
+    /// Published after the node has handed off the content networks it was
+    /// a member of.
+    NodeDeparted {
+        node_id: String,
+        /// Content networks the node was a member of and handed off.
+        content_ids: Vec<String>,
+        timestamp: u64,
+    },
 }
 
 impl Event {
@@ -96,6 +121,7 @@ impl Event {
     pub fn event_type(&self) -> &'static str {
         match self {
             Event::NodeCreated { .. } => "NodeCreated",
+            Event::NodeCapacityUpdated { .. } => "NodeCapacityUpdated",
             Event::AssignmentDecided { .. } => "AssignmentDecided",
             Event::ContentNetworkManagerAdded { .. } => "ContentNetworkManagerAdded",
             Event::ContentNetworkManagerRemoved { .. } => "ContentNetworkManagerRemoved",
@@ -103,6 +129,7 @@ impl Event {
             Event::ContentCreated { .. } => "ContentCreated",
             Event::ContentSyncRequested { .. } => "ContentSyncRequested",
             Event::ContentDeleted { .. } => "ContentDeleted",
+            Event::NodeDeparted { .. } => "NodeDeparted",
         }
     }
 
@@ -116,7 +143,39 @@ impl Event {
             Event::ContentCreated { content_id, .. } => Some(content_id),
             Event::ContentSyncRequested { content_id, .. } => Some(content_id),
             Event::ContentDeleted { content_id, .. } => Some(content_id),
-            Event::NodeCreated { .. } => None,
+            Event::NodeCreated { .. }
+            | Event::NodeCapacityUpdated { .. }
+            | Event::NodeDeparted { .. } => None,
+        }
+    }
+
+    /// Returns the node that claims to have authored this event.
+    pub fn author_node_id(&self) -> &str {
+        match self {
+            Event::NodeCreated { node_id, .. }
+            | Event::NodeCapacityUpdated { node_id, .. }
+            | Event::NodeDeparted { node_id, .. } => node_id,
+            Event::AssignmentDecided {
+                assigning_node_id, ..
+            } => assigning_node_id,
+            Event::ContentUpdated {
+                updated_node_id, ..
+            } => updated_node_id,
+            Event::ContentCreated {
+                creator_node_id, ..
+            } => creator_node_id,
+            Event::ContentSyncRequested {
+                requesting_node_id, ..
+            } => requesting_node_id,
+            Event::ContentDeleted {
+                deleted_by_node_id, ..
+            } => deleted_by_node_id,
+            Event::ContentNetworkManagerAdded {
+                added_by_node_id, ..
+            } => added_by_node_id,
+            Event::ContentNetworkManagerRemoved {
+                removed_by_node_id, ..
+            } => removed_by_node_id,
         }
     }
 
@@ -124,6 +183,7 @@ impl Event {
     pub fn timestamp(&self) -> u64 {
         match self {
             Event::NodeCreated { timestamp, .. } => *timestamp,
+            Event::NodeCapacityUpdated { timestamp, .. } => *timestamp,
             Event::AssignmentDecided { timestamp, .. } => *timestamp,
             Event::ContentNetworkManagerAdded { timestamp, .. } => *timestamp,
             Event::ContentNetworkManagerRemoved { timestamp, .. } => *timestamp,
@@ -131,10 +191,34 @@ impl Event {
             Event::ContentCreated { timestamp, .. } => *timestamp,
             Event::ContentSyncRequested { timestamp, .. } => *timestamp,
             Event::ContentDeleted { timestamp, .. } => *timestamp,
+            Event::NodeDeparted { timestamp, .. } => *timestamp,
         }
     }
 }
 
+/// A domain event signed with the publishing node's identity key.
+///
+/// This is the wire format of events on Gossipsub. Receivers verify the
+/// signature and that the key belongs to the event's claimed author.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedEvent {
+    pub event: Event,
+    /// The signer's libp2p public key (protobuf encoding).
+    pub public_key: Vec<u8>,
+    /// Signature over [`Self::signing_message`].
+    pub signature: Vec<u8>,
+}
+
+impl SignedEvent {
+    /// Get the message to sign for `event`.
+    pub fn signing_message(event: &Event) -> Vec<u8> {
+        let mut message = b"monas-event:".to_vec();
+        // Serializing a plain enum of strings and integers cannot fail.
+        message.extend(serde_json::to_vec(event).unwrap_or_default());
+        message
+    }
+}
+
 /// Get the current timestamp in seconds since UNIX epoch.
 pub fn current_timestamp() -> u64 {
     std::time::SystemTime::now()
@@ -167,6 +251,25 @@ mod tests {
         assert_eq!(event.event_type(), "ContentCreated");
     }
 
+    #[test]
+    fn test_event_author_node_id() {
+        let event = Event::ContentDeleted {
+            content_id: "cid-1".to_string(),
+            deleted_by_node_id: "node-1".to_string(),
+            timestamp: 12345,
+        };
+        assert_eq!(event.author_node_id(), "node-1");
+
+        let event = Event::ContentNetworkManagerAdded {
+            content_id: "cid-1".to_string(),
+            added_node_id: "node-2".to_string(),
+            member_nodes: vec!["node-1".to_string(), "node-2".to_string()],
+            added_by_node_id: "node-1".to_string(),
+            timestamp: 12345,
+        };
+        assert_eq!(event.author_node_id(), "node-1");
+    }
+
     #[test]
     fn test_event_content_id() {
         let event = Event::ContentUpdated {
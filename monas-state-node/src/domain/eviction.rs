@@ -0,0 +1,119 @@
+//! Eviction - Record of content this node no longer hosts.
+//!
+//! When the node is removed from a content network, or the content is
+//! deleted network-wide, its local CRDT data is no longer needed. Instead of
+//! deleting it immediately, the content is first evicted: it stops being
+//! served or accepted, and its data is purged from disk once a grace period
+//! has passed. The grace period gives remaining members time to finish
+//! syncing from this node and lets a quickly reverted removal keep its data.
+//! Unlike a [`Tombstone`](super::tombstone::Tombstone), an eviction is
+//! cancelled when the node becomes a member of the content again.
+
+use serde::{Deserialize, Serialize};
+
+use super::events::current_timestamp;
+
+/// Default time an evicted content's data is kept before it is purged (1 day).
+pub const DEFAULT_EVICTION_GRACE_SECS: u64 = 24 * 60 * 60;
+
+/// Why content was evicted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionReason {
+    /// This node was removed from the content network.
+    RemovedFromNetwork,
+    /// The content was deleted network-wide.
+    ContentDeleted,
+}
+
+/// A local eviction record for a single content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eviction {
+    /// The genesis CID of the evicted content.
+    genesis_cid: String,
+    reason: EvictionReason,
+    /// Unix timestamp (seconds) when the content was evicted.
+    evicted_at: u64,
+    /// Unix timestamp (seconds) after which the local data may be purged.
+    purge_after: u64,
+    /// Unix timestamp (seconds) when the local data was purged.
+    purged_at: Option<u64>,
+}
+
+impl Eviction {
+    /// Create a new eviction stamped with the current time, purgeable after
+    /// `grace_secs`.
+    pub fn new(genesis_cid: String, reason: EvictionReason, grace_secs: u64) -> Self {
+        let evicted_at = current_timestamp();
+        Self {
+            genesis_cid,
+            reason,
+            evicted_at,
+            purge_after: evicted_at.saturating_add(grace_secs),
+            purged_at: None,
+        }
+    }
+
+    /// Get the genesis CID.
+    pub fn genesis_cid(&self) -> &str {
+        &self.genesis_cid
+    }
+
+    /// Get the eviction reason.
+    pub fn reason(&self) -> EvictionReason {
+        self.reason
+    }
+
+    /// Get the eviction timestamp.
+    pub fn evicted_at(&self) -> u64 {
+        self.evicted_at
+    }
+
+    /// Get the timestamp after which the data may be purged.
+    pub fn purge_after(&self) -> u64 {
+        self.purge_after
+    }
+
+    /// Get the purge timestamp, if the data has been purged.
+    pub fn purged_at(&self) -> Option<u64> {
+        self.purged_at
+    }
+
+    /// Whether the local data should be purged at `now`.
+    pub fn is_due(&self, now: u64) -> bool {
+        self.purged_at.is_none() && now >= self.purge_after
+    }
+
+    /// Record that the local data was purged at `now`.
+    pub fn mark_purged(&mut self, now: u64) {
+        self.purged_at = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eviction_becomes_due_after_grace_period() {
+        let mut eviction =
+            Eviction::new("cid-1".to_string(), EvictionReason::RemovedFromNetwork, 60);
+        assert_eq!(eviction.genesis_cid(), "cid-1");
+        assert_eq!(eviction.purge_after(), eviction.evicted_at() + 60);
+        assert!(!eviction.is_due(eviction.evicted_at()));
+        assert!(eviction.is_due(eviction.evicted_at() + 60));
+
+        eviction.mark_purged(eviction.evicted_at() + 61);
+        assert!(!eviction.is_due(eviction.evicted_at() + 120));
+        assert_eq!(eviction.purged_at(), Some(eviction.evicted_at() + 61));
+    }
+
+    #[test]
+    fn test_eviction_serde_roundtrip() {
+        let eviction = Eviction::new("cid-1".to_string(), EvictionReason::ContentDeleted, 0);
+        let json = serde_json::to_string(&eviction).unwrap();
+        assert!(json.contains("\"content_deleted\""));
+        let decoded: Eviction = serde_json::from_str(&json).unwrap();
+        assert_eq!(eviction, decoded);
+    }
+}
@@ -0,0 +1,73 @@
+//! Known peers - Peers this node has successfully connected to.
+//!
+//! Known peers are remembered across restarts and dialed at startup in
+//! addition to the configured bootstrap nodes, so a node can rejoin a small
+//! network even when no static bootstrap node is reachable.
+
+use serde::{Deserialize, Serialize};
+
+/// Default time after which a peer that hasn't been seen is forgotten.
+pub const DEFAULT_KNOWN_PEER_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Maximum number of known peers reloaded at startup.
+pub const MAX_KNOWN_PEERS: usize = 128;
+
+/// Maximum number of addresses remembered per peer.
+pub const MAX_KNOWN_PEER_ADDRS: usize = 8;
+
+/// A peer and the addresses it was last reachable at.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnownPeer {
+    /// libp2p peer ID.
+    pub peer_id: String,
+    /// Listen addresses the peer announced (multiaddr format).
+    pub addrs: Vec<String>,
+    /// Unix timestamp (seconds) at which the peer was last identified.
+    pub last_seen: u64,
+}
+
+/// Select the peers worth dialing at startup: those seen within `ttl_secs`,
+/// most recently seen first, at most `limit`.
+pub fn select_known_peers(
+    mut peers: Vec<KnownPeer>,
+    now: u64,
+    ttl_secs: u64,
+    limit: usize,
+) -> Vec<KnownPeer> {
+    peers.retain(|peer| !peer.addrs.is_empty() && now.saturating_sub(peer.last_seen) < ttl_secs);
+    peers.sort_by_key(|peer| std::cmp::Reverse(peer.last_seen));
+    peers.truncate(limit);
+    peers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(peer_id: &str, last_seen: u64) -> KnownPeer {
+        KnownPeer {
+            peer_id: peer_id.to_string(),
+            addrs: vec!["/ip4/192.0.2.1/tcp/9090".to_string()],
+            last_seen,
+        }
+    }
+
+    #[test]
+    fn test_select_known_peers_drops_stale_and_prefers_recent() {
+        let mut no_addrs = peer("peer-4", 990);
+        no_addrs.addrs.clear();
+        let peers = vec![
+            peer("peer-1", 900),
+            peer("peer-2", 100),
+            peer("peer-3", 950),
+            no_addrs,
+        ];
+
+        let selected = select_known_peers(peers.clone(), 1000, 500, 10);
+        let ids: Vec<&str> = selected.iter().map(|p| p.peer_id.as_str()).collect();
+        assert_eq!(ids, vec!["peer-3", "peer-1"]);
+
+        let selected = select_known_peers(peers, 1000, 500, 1);
+        assert_eq!(selected, vec![peer("peer-3", 950)]);
+    }
+}
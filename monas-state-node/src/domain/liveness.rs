@@ -0,0 +1,118 @@
+//! Liveness of content network members.
+//!
+//! A member is only considered gone after it has been unreachable (no
+//! connection and no answer to a heartbeat probe) for a grace period, so that
+//! restarts and short outages don't trigger a replacement.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default grace period before an unreachable member is considered gone.
+pub const DEFAULT_MEMBER_DEAD_AFTER_SECS: u64 = 600;
+
+/// Last observed state of a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PeerLiveness {
+    /// Connected or answered a heartbeat at `last_seen`.
+    Alive { last_seen: u64 },
+    /// Unreachable since `since`.
+    Unreachable { since: u64 },
+}
+
+/// Liveness tracker for the members of the content networks this node
+/// belongs to. Timestamps are Unix seconds.
+#[derive(Debug, Clone)]
+pub struct MemberLiveness {
+    peers: HashMap<String, PeerLiveness>,
+    dead_after_secs: u64,
+}
+
+impl Default for MemberLiveness {
+    fn default() -> Self {
+        Self::new(DEFAULT_MEMBER_DEAD_AFTER_SECS)
+    }
+}
+
+impl MemberLiveness {
+    /// Create a tracker that considers a peer gone after `dead_after_secs`
+    /// of unreachability.
+    pub fn new(dead_after_secs: u64) -> Self {
+        Self {
+            peers: HashMap::new(),
+            dead_after_secs,
+        }
+    }
+
+    /// Record that `peer_id` is connected or answered a heartbeat.
+    pub fn record_alive(&mut self, peer_id: &str, now: u64) {
+        self.peers
+            .insert(peer_id.to_string(), PeerLiveness::Alive { last_seen: now });
+    }
+
+    /// Record that `peer_id` could not be reached. Keeps the start of an
+    /// ongoing outage.
+    pub fn record_unreachable(&mut self, peer_id: &str, now: u64) {
+        self.peers
+            .entry(peer_id.to_string())
+            .and_modify(|state| {
+                if let PeerLiveness::Alive { .. } = state {
+                    *state = PeerLiveness::Unreachable { since: now };
+                }
+            })
+            .or_insert(PeerLiveness::Unreachable { since: now });
+    }
+
+    /// Last observed state of `peer_id`, if it has been observed at all.
+    pub fn get(&self, peer_id: &str) -> Option<PeerLiveness> {
+        self.peers.get(peer_id).copied()
+    }
+
+    /// Whether `peer_id` has been unreachable for at least the grace period.
+    /// Peers never observed are not considered dead.
+    pub fn is_dead(&self, peer_id: &str, now: u64) -> bool {
+        matches!(
+            self.peers.get(peer_id),
+            Some(PeerLiveness::Unreachable { since }) if now.saturating_sub(*since) >= self.dead_after_secs
+        )
+    }
+
+    /// Stop tracking `peer_id` (e.g. after it was removed from all networks).
+    pub fn forget(&mut self, peer_id: &str) {
+        self.peers.remove(peer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_member_is_dead_after_grace_period() {
+        let mut liveness = MemberLiveness::new(60);
+        assert!(!liveness.is_dead("node-2", 1000));
+
+        liveness.record_unreachable("node-2", 1000);
+        assert!(!liveness.is_dead("node-2", 1059));
+        // Repeated failures don't restart the outage.
+        liveness.record_unreachable("node-2", 1030);
+        assert!(liveness.is_dead("node-2", 1060));
+    }
+
+    #[test]
+    fn test_heartbeat_resets_outage() {
+        let mut liveness = MemberLiveness::new(60);
+        liveness.record_unreachable("node-2", 1000);
+        liveness.record_alive("node-2", 1050);
+        assert_eq!(
+            liveness.get("node-2"),
+            Some(PeerLiveness::Alive { last_seen: 1050 })
+        );
+
+        liveness.record_unreachable("node-2", 1100);
+        assert!(!liveness.is_dead("node-2", 1120));
+        assert!(liveness.is_dead("node-2", 1160));
+
+        liveness.forget("node-2");
+        assert!(liveness.get("node-2").is_none());
+    }
+}
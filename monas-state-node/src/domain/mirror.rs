@@ -0,0 +1,226 @@
+//! Mirror mode - warm standby between two personal nodes.
+//!
+//! A user running two nodes (e.g. a home server and a NAS) can pair them so
+//! that the secondary automatically joins every content network the primary
+//! coordinates and keeps a complete hot copy for failover. Placement still
+//! selects the regular replication set; the paired secondary is added on top.
+//!
+//! Both nodes must name each other in their configuration. The secondary then
+//! sends a pairing request signed with its node key, and the primary answers
+//! with an acceptance signed with its own key that commits to the request
+//! signature. Only after this handshake does the primary start mirroring.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum accepted clock skew for pairing request timestamps (seconds).
+pub const MIRROR_PAIRING_MAX_SKEW_SECS: u64 = 300;
+
+/// Role of this node in a mirror pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MirrorRole {
+    /// Coordinates content networks and adds its secondary to each of them.
+    Primary,
+    /// Follows the primary and replicates everything it coordinates.
+    Secondary,
+}
+
+/// Mirror mode configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MirrorConfig {
+    /// Role of this node.
+    pub role: MirrorRole,
+    /// Node ID (libp2p PeerId) of the paired node.
+    pub peer_node_id: String,
+}
+
+impl MirrorConfig {
+    /// Build the mirror configuration from environment variables.
+    ///
+    /// - `MIRROR_ROLE`: `primary` or `secondary`
+    /// - `MIRROR_PEER`: node ID of the paired node
+    ///
+    /// Returns `None` (mirror mode disabled) unless both are set and valid.
+    pub fn from_env() -> Option<Self> {
+        let role = match std::env::var("MIRROR_ROLE")
+            .ok()?
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "primary" => MirrorRole::Primary,
+            "secondary" => MirrorRole::Secondary,
+            _ => return None,
+        };
+        let peer_node_id = std::env::var("MIRROR_PEER").ok()?.trim().to_string();
+        if peer_node_id.is_empty() {
+            return None;
+        }
+        Some(Self { role, peer_node_id })
+    }
+}
+
+/// Pairing request sent by the secondary to the primary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirrorPairingRequest {
+    pub primary_node_id: String,
+    pub secondary_node_id: String,
+    /// Unix timestamp (seconds) at which the request was signed.
+    pub timestamp: u64,
+    /// The secondary's P-256 public key (SEC1 uncompressed).
+    pub signer_public_key: Vec<u8>,
+    /// Signature over [`Self::signing_message`].
+    pub signature: Vec<u8>,
+}
+
+impl MirrorPairingRequest {
+    /// Create a new unsigned pairing request.
+    pub fn new(primary_node_id: String, secondary_node_id: String, timestamp: u64) -> Self {
+        Self {
+            primary_node_id,
+            secondary_node_id,
+            timestamp,
+            signer_public_key: Vec::new(),
+            signature: Vec::new(),
+        }
+    }
+
+    /// Get the message to sign.
+    pub fn signing_message(&self) -> Vec<u8> {
+        format!(
+            "monas-mirror-pair-request:{}:{}:{}",
+            self.primary_node_id, self.secondary_node_id, self.timestamp
+        )
+        .into_bytes()
+    }
+
+    /// Set the signature and signer's public key.
+    pub fn with_signature(mut self, signature: Vec<u8>, signer_public_key: Vec<u8>) -> Self {
+        self.signature = signature;
+        self.signer_public_key = signer_public_key;
+        self
+    }
+
+    /// Returns true if the request timestamp is within the allowed skew of `now`.
+    pub fn is_fresh(&self, now: u64) -> bool {
+        self.timestamp.abs_diff(now) <= MIRROR_PAIRING_MAX_SKEW_SECS
+    }
+}
+
+/// Pairing acceptance returned by the primary.
+///
+/// Commits to the request signature so it cannot be replayed as the answer
+/// to another request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirrorPairingAcceptance {
+    pub primary_node_id: String,
+    pub secondary_node_id: String,
+    /// Signature of the request being accepted.
+    pub request_signature: Vec<u8>,
+    /// Unix timestamp (seconds) at which the acceptance was signed.
+    pub timestamp: u64,
+    /// The primary's P-256 public key (SEC1 uncompressed).
+    pub signer_public_key: Vec<u8>,
+    /// Signature over [`Self::signing_message`].
+    pub signature: Vec<u8>,
+}
+
+impl MirrorPairingAcceptance {
+    /// Create a new unsigned acceptance for `request`.
+    pub fn new(request: &MirrorPairingRequest, timestamp: u64) -> Self {
+        Self {
+            primary_node_id: request.primary_node_id.clone(),
+            secondary_node_id: request.secondary_node_id.clone(),
+            request_signature: request.signature.clone(),
+            timestamp,
+            signer_public_key: Vec::new(),
+            signature: Vec::new(),
+        }
+    }
+
+    /// Get the message to sign.
+    pub fn signing_message(&self) -> Vec<u8> {
+        format!(
+            "monas-mirror-pair-accept:{}:{}:{}:{}",
+            self.primary_node_id,
+            self.secondary_node_id,
+            hex::encode(&self.request_signature),
+            self.timestamp
+        )
+        .into_bytes()
+    }
+
+    /// Set the signature and signer's public key.
+    pub fn with_signature(mut self, signature: Vec<u8>, signer_public_key: Vec<u8>) -> Self {
+        self.signature = signature;
+        self.signer_public_key = signer_public_key;
+        self
+    }
+
+    /// Returns true if this acceptance answers `request`.
+    pub fn answers(&self, request: &MirrorPairingRequest) -> bool {
+        self.primary_node_id == request.primary_node_id
+            && self.secondary_node_id == request.secondary_node_id
+            && self.request_signature == request.signature
+    }
+}
+
+/// An established mirror pairing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirrorPairing {
+    pub primary_node_id: String,
+    pub secondary_node_id: String,
+    pub primary_public_key: Vec<u8>,
+    pub secondary_public_key: Vec<u8>,
+    /// Unix timestamp (seconds) of the accepted handshake.
+    pub paired_at: u64,
+}
+
+impl MirrorPairing {
+    /// Build the pairing record from a completed handshake.
+    pub fn from_handshake(
+        request: &MirrorPairingRequest,
+        acceptance: &MirrorPairingAcceptance,
+    ) -> Self {
+        Self {
+            primary_node_id: acceptance.primary_node_id.clone(),
+            secondary_node_id: acceptance.secondary_node_id.clone(),
+            primary_public_key: acceptance.signer_public_key.clone(),
+            secondary_public_key: request.signer_public_key.clone(),
+            paired_at: acceptance.timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> MirrorPairingRequest {
+        MirrorPairingRequest::new("primary".to_string(), "secondary".to_string(), 1_000)
+            .with_signature(vec![1, 2, 3], vec![4])
+    }
+
+    #[test]
+    fn test_request_freshness() {
+        let req = request();
+        assert!(req.is_fresh(1_000 + MIRROR_PAIRING_MAX_SKEW_SECS));
+        assert!(req.is_fresh(1_000 - MIRROR_PAIRING_MAX_SKEW_SECS));
+        assert!(!req.is_fresh(1_000 + MIRROR_PAIRING_MAX_SKEW_SECS + 1));
+    }
+
+    #[test]
+    fn test_acceptance_commits_to_request_signature() {
+        let req = request();
+        let acceptance = MirrorPairingAcceptance::new(&req, 1_001);
+        assert!(acceptance.answers(&req));
+        assert!(acceptance
+            .signing_message()
+            .ends_with(format!(":{}:1001", hex::encode([1, 2, 3])).as_bytes()));
+
+        let other =
+            MirrorPairingRequest::new("primary".to_string(), "secondary".to_string(), 1_000)
+                .with_signature(vec![9], vec![4]);
+        assert!(!acceptance.answers(&other));
+    }
+}
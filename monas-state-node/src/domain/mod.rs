@@ -6,10 +6,22 @@ pub mod auth_token_verifier;
 pub mod content_network;
 pub mod errors;
 pub mod events;
+pub mod eviction;
 pub mod identity;
+pub mod known_peer;
+pub mod liveness;
+pub mod mirror;
+pub mod node_attestation;
+pub mod peer_access;
+pub mod peer_latency;
 pub mod placement;
 pub mod state_node;
+pub mod storage_budget;
+pub mod sync_rules;
+pub mod sync_status;
+pub mod tombstone;
 pub mod value_objects;
+pub mod version_record;
 
 pub use access_control::{
     AccessControlError, AccessControlEvent, AccessControlUpdate, ContentAccessControl,
@@ -19,6 +31,18 @@ pub use auth_capability::AuthCapability;
 pub use auth_token::{AuthToken, AuthTokenParseError, Capability, CapabilityAction, KeyId};
 pub use auth_token_verifier::{AuthTokenVerifier, AuthTokenVerifyError, VerifiedToken};
 pub use errors::{CrdtError, NetworkError, StateNodeError};
+pub use eviction::{Eviction, EvictionReason};
 pub use identity::{Identity, IdentityError, IdentityType};
+pub use known_peer::KnownPeer;
+pub use mirror::{
+    MirrorConfig, MirrorPairing, MirrorPairingAcceptance, MirrorPairingRequest, MirrorRole,
+};
+pub use node_attestation::{NodeAttestation, TrustedAccounts};
+pub use peer_access::PeerAccess;
 pub use placement::{NodeCandidate, PlacementError, PlacementPolicy};
+pub use storage_budget::StorageUsage;
+pub use sync_rules::{ContentSyncAttributes, SyncRules};
+pub use sync_status::{ContentSyncStatus, SyncStatusTracker};
+pub use tombstone::Tombstone;
 pub use value_objects::{ContentId, NodeId, NonEmptySet, ValueError};
+pub use version_record::VersionRecord;
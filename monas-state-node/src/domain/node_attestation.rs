@@ -0,0 +1,169 @@
+//! Node identity attestation - binds a state node's libp2p identity to the
+//! account that operates it.
+//!
+//! The owning account signs a certificate naming the node's PeerId with its
+//! P-256 account key. The node presents the certificate to peers during
+//! identify; peers verify it and remember which account owns the node, so
+//! that placement can be restricted to nodes owned by trusted accounts
+//! (the trust layer for private networks).
+//!
+//! Accounts are identified by their hex-encoded public key (SEC1 uncompressed).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Maximum accepted clock skew for attestation issue timestamps (seconds).
+pub const NODE_ATTESTATION_MAX_SKEW_SECS: u64 = 300;
+
+/// Account-signed certificate stating that an account operates a node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeAttestation {
+    /// Node ID (libp2p PeerId) of the attested node.
+    pub peer_id: String,
+    /// The owning account's P-256 public key (SEC1 uncompressed).
+    pub account_public_key: Vec<u8>,
+    /// Unix timestamp (seconds) at which the attestation was issued.
+    pub issued_at: u64,
+    /// Unix timestamp (seconds) after which the attestation is no longer
+    /// valid. Never expires when unset.
+    pub expires_at: Option<u64>,
+    /// Account signature over [`Self::signing_message`].
+    pub signature: Vec<u8>,
+}
+
+impl NodeAttestation {
+    /// Create a new unsigned attestation.
+    pub fn new(
+        peer_id: String,
+        account_public_key: Vec<u8>,
+        issued_at: u64,
+        expires_at: Option<u64>,
+    ) -> Self {
+        Self {
+            peer_id,
+            account_public_key,
+            issued_at,
+            expires_at,
+            signature: Vec::new(),
+        }
+    }
+
+    /// Get the message to sign.
+    pub fn signing_message(&self) -> Vec<u8> {
+        format!(
+            "monas-node-attestation:{}:{}:{}:{}",
+            self.peer_id,
+            self.account_id(),
+            self.issued_at,
+            self.expires_at.map(|t| t.to_string()).unwrap_or_default()
+        )
+        .into_bytes()
+    }
+
+    /// Set the account signature.
+    pub fn with_signature(mut self, signature: Vec<u8>) -> Self {
+        self.signature = signature;
+        self
+    }
+
+    /// Identifier of the owning account (hex-encoded public key).
+    pub fn account_id(&self) -> String {
+        hex::encode(&self.account_public_key)
+    }
+
+    /// Returns true if the attestation is valid at `now`: it was not issued in
+    /// the future (beyond the allowed skew) and has not expired.
+    pub fn is_valid_at(&self, now: u64) -> bool {
+        if self.issued_at > now.saturating_add(NODE_ATTESTATION_MAX_SKEW_SECS) {
+            return false;
+        }
+        match self.expires_at {
+            Some(expires_at) => now < expires_at,
+            None => true,
+        }
+    }
+}
+
+/// Accounts whose nodes may be selected for content placement.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedAccounts {
+    accounts: HashSet<String>,
+}
+
+impl TrustedAccounts {
+    /// Create a trust list from account IDs (hex-encoded public keys).
+    pub fn new(accounts: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            accounts: accounts
+                .into_iter()
+                .map(|a| a.trim().to_ascii_lowercase())
+                .filter(|a| !a.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Build the trust list from the `TRUSTED_ACCOUNTS` environment variable
+    /// (comma-separated account IDs).
+    ///
+    /// Returns `None` (placement unrestricted) when unset or empty.
+    pub fn from_env() -> Option<Self> {
+        let trusted = Self::new(
+            std::env::var("TRUSTED_ACCOUNTS")
+                .ok()?
+                .split(',')
+                .map(str::to_string),
+        );
+        (!trusted.is_empty()).then_some(trusted)
+    }
+
+    /// Returns true if the account is trusted.
+    pub fn contains(&self, account_id: &str) -> bool {
+        self.accounts.contains(&account_id.to_ascii_lowercase())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_message_commits_to_all_fields() {
+        let base = NodeAttestation::new("peer-a".to_string(), vec![4, 1, 2], 100, None);
+        let messages = [
+            base.signing_message(),
+            NodeAttestation::new("peer-b".to_string(), vec![4, 1, 2], 100, None).signing_message(),
+            NodeAttestation::new("peer-a".to_string(), vec![4, 1, 3], 100, None).signing_message(),
+            NodeAttestation::new("peer-a".to_string(), vec![4, 1, 2], 101, None).signing_message(),
+            NodeAttestation::new("peer-a".to_string(), vec![4, 1, 2], 100, Some(200))
+                .signing_message(),
+        ];
+        let unique: HashSet<_> = messages.iter().collect();
+        assert_eq!(unique.len(), messages.len());
+        assert_eq!(base.account_id(), "040102");
+    }
+
+    #[test]
+    fn test_validity_window() {
+        let attestation = NodeAttestation::new("peer".to_string(), vec![4], 1_000, Some(2_000));
+        assert!(attestation.is_valid_at(1_000));
+        assert!(attestation.is_valid_at(1_000 - NODE_ATTESTATION_MAX_SKEW_SECS));
+        assert!(!attestation.is_valid_at(1_000 - NODE_ATTESTATION_MAX_SKEW_SECS - 1));
+        assert!(!attestation.is_valid_at(2_000));
+
+        let unbounded = NodeAttestation::new("peer".to_string(), vec![4], 1_000, None);
+        assert!(unbounded.is_valid_at(u64::MAX));
+    }
+
+    #[test]
+    fn test_trusted_accounts_are_case_insensitive() {
+        let trusted = TrustedAccounts::new(vec![" ABCD ".to_string(), String::new()]);
+        assert!(trusted.contains("abcd"));
+        assert!(trusted.contains("AbCd"));
+        assert!(!trusted.contains("ef"));
+        assert!(TrustedAccounts::new(Vec::new()).is_empty());
+    }
+}
@@ -0,0 +1,36 @@
+//! Peer access - Operator decisions about which peers may connect.
+//!
+//! Blocked peers are refused at the connection level, and any existing
+//! connection to them is closed. Allowed peers only matter when the node
+//! enforces an allowlist, in which case every peer not allowed is refused.
+//! Peers without an entry follow the default (connect unless an allowlist is
+//! enforced).
+
+use serde::{Deserialize, Serialize};
+
+/// Access decision for a single peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerAccess {
+    /// Connections from and to the peer are allowed.
+    Allowed,
+    /// Connections from and to the peer are refused.
+    Blocked,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_access_serializes_as_snake_case() {
+        assert_eq!(
+            serde_json::to_string(&PeerAccess::Blocked).unwrap(),
+            "\"blocked\""
+        );
+        assert_eq!(
+            serde_json::from_str::<PeerAccess>("\"allowed\"").unwrap(),
+            PeerAccess::Allowed
+        );
+    }
+}
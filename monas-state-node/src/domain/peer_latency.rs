@@ -0,0 +1,119 @@
+//! Peer latency - Rolling round-trip times of connected peers.
+//!
+//! The network pings every connected peer periodically. The tracker keeps the
+//! last few RTT samples per peer and reports their mean, so a single slow
+//! ping does not reorder peers. Fetch uses it to query nearby peers first;
+//! placement only uses it to break ties between peers of equal capacity.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Number of RTT samples kept per peer.
+pub const DEFAULT_LATENCY_WINDOW: usize = 8;
+
+/// Peers whose RTTs fall into the same bucket are considered equally close.
+pub const LATENCY_BUCKET: Duration = Duration::from_millis(10);
+
+/// Rolling RTT samples of connected peers.
+#[derive(Debug, Clone)]
+pub struct LatencyTracker {
+    samples: HashMap<String, VecDeque<Duration>>,
+    window: usize,
+}
+
+impl Default for LatencyTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_LATENCY_WINDOW)
+    }
+}
+
+impl LatencyTracker {
+    /// Create a tracker averaging the last `window` samples of each peer.
+    pub fn new(window: usize) -> Self {
+        Self {
+            samples: HashMap::new(),
+            window: window.max(1),
+        }
+    }
+
+    /// Record a measured round trip to `peer_id`.
+    pub fn record(&mut self, peer_id: &str, rtt: Duration) {
+        let samples = self.samples.entry(peer_id.to_string()).or_default();
+        if samples.len() == self.window {
+            samples.pop_front();
+        }
+        samples.push_back(rtt);
+    }
+
+    /// Forget `peer_id`, e.g. once it disconnected.
+    pub fn remove(&mut self, peer_id: &str) {
+        self.samples.remove(peer_id);
+    }
+
+    /// Mean RTT of `peer_id`, or `None` if it has not been measured.
+    pub fn rtt(&self, peer_id: &str) -> Option<Duration> {
+        let samples = self.samples.get(peer_id)?;
+        let total: Duration = samples.iter().sum();
+        Some(total / samples.len() as u32)
+    }
+
+    /// Mean RTT of every measured peer.
+    pub fn snapshot(&self) -> HashMap<String, Duration> {
+        self.samples
+            .keys()
+            .filter_map(|peer_id| Some((peer_id.clone(), self.rtt(peer_id)?)))
+            .collect()
+    }
+}
+
+/// Stably sort `items` so that peers with a lower RTT come first.
+///
+/// RTTs are compared by [`LATENCY_BUCKET`], so peers of similar latency keep
+/// their relative order. Peers without a measured RTT go last.
+pub fn sort_by_latency<T>(
+    items: &mut [T],
+    latencies: &HashMap<String, Duration>,
+    peer_id: impl Fn(&T) -> &str,
+) {
+    items.sort_by_key(|item| match latencies.get(peer_id(item)) {
+        Some(rtt) => (false, rtt.as_millis() / LATENCY_BUCKET.as_millis()),
+        None => (true, 0),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtt_is_mean_of_recent_samples() {
+        let mut tracker = LatencyTracker::new(2);
+        assert_eq!(tracker.rtt("peer-1"), None);
+
+        tracker.record("peer-1", Duration::from_millis(100));
+        tracker.record("peer-1", Duration::from_millis(20));
+        assert_eq!(tracker.rtt("peer-1"), Some(Duration::from_millis(60)));
+
+        // The oldest sample drops out of the window.
+        tracker.record("peer-1", Duration::from_millis(40));
+        assert_eq!(tracker.rtt("peer-1"), Some(Duration::from_millis(30)));
+
+        tracker.remove("peer-1");
+        assert!(tracker.snapshot().is_empty());
+    }
+
+    #[test]
+    fn test_sort_by_latency_puts_unmeasured_peers_last() {
+        let latencies = HashMap::from([
+            ("peer-2".to_string(), Duration::from_millis(80)),
+            ("peer-3".to_string(), Duration::from_millis(12)),
+            ("peer-4".to_string(), Duration::from_millis(15)),
+        ]);
+        let mut peers = vec!["peer-1", "peer-2", "peer-4", "peer-3"];
+
+        sort_by_latency(&mut peers, &latencies, |peer| peer);
+
+        // peer-4 and peer-3 share a bucket and keep their order.
+        assert_eq!(peers, vec!["peer-4", "peer-3", "peer-2", "peer-1"]);
+    }
+}
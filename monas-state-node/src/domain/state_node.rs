@@ -6,6 +6,10 @@ pub struct NodeSnapshot {
     pub node_id: String,
     pub total_capacity: u64,
     pub available_capacity: u64,
+    /// Timestamp of the announcement this capacity comes from. Announcements
+    /// that are not newer are ignored (see [`supersedes`]).
+    #[serde(default)]
+    pub updated_at: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -23,22 +27,80 @@ pub struct AssignmentResponse {
 }
 
 pub fn create_node(node_id: String, total_capacity: u64) -> (NodeSnapshot, Vec<Event>) {
+    let timestamp = current_timestamp();
     let snapshot = NodeSnapshot {
         node_id: node_id.clone(),
         total_capacity,
         available_capacity: total_capacity,
+        updated_at: timestamp,
     };
 
     let events = vec![Event::NodeCreated {
         node_id,
         total_capacity,
         available_capacity: total_capacity,
-        timestamp: current_timestamp(),
+        timestamp,
+    }];
+
+    (snapshot, events)
+}
+
+/// Minimum change of available capacity, in percent of the total capacity,
+/// worth announcing. Free space fluctuates constantly; smaller changes don't
+/// affect placement.
+pub const CAPACITY_CHANGE_THRESHOLD_PERCENT: u64 = 1;
+
+/// Apply a new capacity measurement to a node.
+///
+/// Emits `NodeCapacityUpdated` when the node was not known yet, its total
+/// capacity changed, or its available capacity moved by at least
+/// [`CAPACITY_CHANGE_THRESHOLD_PERCENT`] of the total. Otherwise the snapshot
+/// is returned unchanged with no events.
+///
+/// The event timestamp is always later than the current snapshot's, so peers
+/// can order announcements made within the same second.
+pub fn update_capacity(
+    current: Option<&NodeSnapshot>,
+    node_id: &str,
+    total_capacity: u64,
+    available_capacity: u64,
+) -> (NodeSnapshot, Vec<Event>) {
+    if let Some(current) = current {
+        let threshold = (total_capacity / 100).saturating_mul(CAPACITY_CHANGE_THRESHOLD_PERCENT);
+        if current.total_capacity == total_capacity
+            && current.available_capacity.abs_diff(available_capacity) < threshold.max(1)
+        {
+            return (current.clone(), vec![]);
+        }
+    }
+
+    let timestamp = match current {
+        Some(current) => current_timestamp().max(current.updated_at + 1),
+        None => current_timestamp(),
+    };
+    let snapshot = NodeSnapshot {
+        node_id: node_id.to_string(),
+        total_capacity,
+        available_capacity,
+        updated_at: timestamp,
+    };
+    let events = vec![Event::NodeCapacityUpdated {
+        node_id: node_id.to_string(),
+        total_capacity,
+        available_capacity,
+        timestamp,
     }];
 
     (snapshot, events)
 }
 
+/// Whether a capacity announcement made at `timestamp` replaces the stored
+/// snapshot. Gossip does not preserve order, so a delayed or replayed
+/// announcement must not overwrite a newer one.
+pub fn supersedes(current: Option<&NodeSnapshot>, timestamp: u64) -> bool {
+    current.map_or(true, |current| timestamp > current.updated_at)
+}
+
 pub fn build_assignment_request(snapshot: &NodeSnapshot) -> AssignmentRequest {
     AssignmentRequest {
         requesting_node_id: snapshot.node_id.clone(),
@@ -103,6 +165,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn update_capacity_ignores_small_changes() {
+        let (snap, _) = create_node("node-A".into(), 10_000);
+
+        let (same, events) = update_capacity(Some(&snap), "node-A", 10_000, 9_950);
+        assert_eq!(same, snap);
+        assert!(events.is_empty());
+
+        let (updated, events) = update_capacity(Some(&snap), "node-A", 10_000, 9_000);
+        assert_eq!(updated.available_capacity, 9_000);
+        assert!(matches!(
+            events[..],
+            [Event::NodeCapacityUpdated {
+                available_capacity: 9_000,
+                ..
+            }]
+        ));
+
+        let (_, events) = update_capacity(None, "node-A", 10_000, 9_950);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn update_capacity_orders_announcements() {
+        let (snap, _) = create_node("node-A".into(), 10_000);
+        let future = NodeSnapshot {
+            updated_at: snap.updated_at + 100,
+            ..snap
+        };
+
+        let (updated, events) = update_capacity(Some(&future), "node-A", 10_000, 5_000);
+        assert_eq!(updated.updated_at, future.updated_at + 1);
+        assert_eq!(events[0].timestamp(), updated.updated_at);
+
+        assert!(supersedes(None, 0));
+        assert!(supersedes(Some(&future), updated.updated_at));
+        assert!(!supersedes(Some(&updated), future.updated_at));
+        assert!(!supersedes(Some(&updated), updated.updated_at));
+    }
+
     #[test]
     fn build_assignment_request_uses_snapshot_values() {
         let (snap, _) = create_node("node-A".into(), 700);
@@ -0,0 +1,104 @@
+//! Storage budget - How much disk the content store may use.
+//!
+//! A node advertises its capacity to peers, but without a budget nothing
+//! stops the content store from growing until the disk is full. The budget
+//! caps the bytes the store may use: new content is refused once the store
+//! is nearly full, leaving the remaining headroom for updates to content the
+//! node already hosts, and writes stop altogether at the quota.
+
+use serde::{Deserialize, Serialize};
+
+/// Usage (in percent of the quota) from which new content is refused.
+pub const NEARLY_FULL_PERCENT: u64 = 95;
+
+/// Bytes used by the content store against its quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageUsage {
+    /// Bytes currently used on disk.
+    pub used_bytes: u64,
+    /// Maximum bytes the store may use.
+    pub quota_bytes: u64,
+}
+
+impl StorageUsage {
+    /// Bytes left before the quota is reached.
+    pub fn available_bytes(&self) -> u64 {
+        self.quota_bytes.saturating_sub(self.used_bytes)
+    }
+
+    /// Whether the quota has been reached.
+    pub fn is_full(&self) -> bool {
+        self.used_bytes >= self.quota_bytes
+    }
+
+    /// Whether usage has reached [`NEARLY_FULL_PERCENT`] of the quota.
+    pub fn is_nearly_full(&self) -> bool {
+        self.used_bytes >= self.nearly_full_bytes()
+    }
+
+    /// Whether new content of `size` bytes fits without making the store
+    /// nearly full.
+    pub fn accepts_new_content(&self, size: u64) -> bool {
+        self.used_bytes.saturating_add(size) <= self.nearly_full_bytes()
+    }
+
+    /// Whether a write of `size` bytes to content already hosted fits the
+    /// quota.
+    pub fn accepts_update(&self, size: u64) -> bool {
+        self.used_bytes.saturating_add(size) <= self.quota_bytes
+    }
+
+    /// Limit a measured disk capacity `(total, available)` to the budget.
+    pub fn clamp_capacity(&self, (total, available): (u64, u64)) -> (u64, u64) {
+        (
+            total.min(self.quota_bytes),
+            available.min(self.available_bytes()),
+        )
+    }
+
+    fn nearly_full_bytes(&self) -> u64 {
+        // Widen to avoid overflow for quotas close to u64::MAX.
+        (u128::from(self.quota_bytes) * u128::from(NEARLY_FULL_PERCENT) / 100) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(used_bytes: u64) -> StorageUsage {
+        StorageUsage {
+            used_bytes,
+            quota_bytes: 1000,
+        }
+    }
+
+    #[test]
+    fn test_new_content_is_refused_when_nearly_full() {
+        assert!(usage(900).accepts_new_content(50));
+        assert!(!usage(900).accepts_new_content(51));
+        assert!(!usage(900).is_nearly_full());
+        assert!(usage(950).is_nearly_full());
+        assert!(!usage(950).is_full());
+        assert!(usage(1000).is_full());
+        assert_eq!(usage(1200).available_bytes(), 0);
+        assert!(usage(950).accepts_update(50));
+        assert!(!usage(950).accepts_update(51));
+    }
+
+    #[test]
+    fn test_clamp_capacity_to_quota() {
+        assert_eq!(usage(300).clamp_capacity((10_000, 5_000)), (1000, 700));
+        assert_eq!(usage(300).clamp_capacity((800, 100)), (800, 100));
+    }
+
+    #[test]
+    fn test_large_quota_does_not_overflow() {
+        let usage = StorageUsage {
+            used_bytes: 0,
+            quota_bytes: u64::MAX,
+        };
+        assert!(usage.accepts_new_content(u64::MAX / 2));
+        assert!(!usage.is_nearly_full());
+    }
+}
@@ -0,0 +1,175 @@
+//! Selective sync rules - which content a node is willing to replicate.
+//!
+//! Not every node wants a full replica of a user's content set: a laptop may
+//! only keep `sync:high` content while a NAS replicates everything. Each node
+//! declares its rules locally, advertises them alongside its capacity, and
+//! refuses to join content networks whose attributes do not match.
+
+use serde::{Deserialize, Serialize};
+
+/// Attributes of a content that selective sync rules are evaluated against.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentSyncAttributes {
+    /// Size of the content payload in bytes.
+    pub size: u64,
+    /// Free-form labels attached by the creator (e.g. `sync:high`).
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Namespace the content belongs to, if any.
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+/// Per-node selective sync rules.
+///
+/// Empty lists and `None` mean "no restriction", so the default value
+/// replicates everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncRules {
+    /// Only replicate content carrying at least one of these labels.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Only replicate content in one of these namespaces.
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// Only replicate content up to this size in bytes.
+    #[serde(default)]
+    pub max_content_size: Option<u64>,
+}
+
+impl SyncRules {
+    /// Rules that accept every content.
+    pub fn replicate_all() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if these rules impose no restriction.
+    pub fn is_replicate_all(&self) -> bool {
+        self.labels.is_empty() && self.namespaces.is_empty() && self.max_content_size.is_none()
+    }
+
+    /// Build rules from environment variables.
+    ///
+    /// - `SYNC_LABELS`: comma-separated labels (e.g. `sync:high,photos`)
+    /// - `SYNC_NAMESPACES`: comma-separated namespaces
+    /// - `SYNC_MAX_CONTENT_SIZE`: maximum content size in bytes
+    pub fn from_env() -> Self {
+        Self {
+            labels: parse_list(std::env::var("SYNC_LABELS").ok()),
+            namespaces: parse_list(std::env::var("SYNC_NAMESPACES").ok()),
+            max_content_size: std::env::var("SYNC_MAX_CONTENT_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+        }
+    }
+
+    /// Returns true if a node with these rules is willing to replicate content
+    /// with the given attributes. All configured conditions must match.
+    pub fn accepts(&self, attributes: &ContentSyncAttributes) -> bool {
+        if let Some(max) = self.max_content_size {
+            if attributes.size > max {
+                return false;
+            }
+        }
+        if !self.labels.is_empty() && !attributes.labels.iter().any(|l| self.labels.contains(l)) {
+            return false;
+        }
+        if !self.namespaces.is_empty() {
+            match &attributes.namespace {
+                Some(ns) if self.namespaces.contains(ns) => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+fn parse_list(value: Option<String>) -> Vec<String> {
+    value
+        .map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attrs(size: u64, labels: &[&str], namespace: Option<&str>) -> ContentSyncAttributes {
+        ContentSyncAttributes {
+            size,
+            labels: labels.iter().map(|s| s.to_string()).collect(),
+            namespace: namespace.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_default_rules_accept_everything() {
+        let rules = SyncRules::default();
+        assert!(rules.is_replicate_all());
+        assert!(rules.accepts(&attrs(u64::MAX, &[], None)));
+        assert!(rules.accepts(&attrs(10, &["sync:low"], Some("photos"))));
+    }
+
+    #[test]
+    fn test_label_rule() {
+        let rules = SyncRules {
+            labels: vec!["sync:high".to_string()],
+            ..Default::default()
+        };
+        assert!(rules.accepts(&attrs(10, &["sync:high", "photos"], None)));
+        assert!(!rules.accepts(&attrs(10, &["sync:low"], None)));
+        assert!(!rules.accepts(&attrs(10, &[], None)));
+    }
+
+    #[test]
+    fn test_namespace_rule() {
+        let rules = SyncRules {
+            namespaces: vec!["work".to_string()],
+            ..Default::default()
+        };
+        assert!(rules.accepts(&attrs(10, &[], Some("work"))));
+        assert!(!rules.accepts(&attrs(10, &[], Some("personal"))));
+        assert!(!rules.accepts(&attrs(10, &[], None)));
+    }
+
+    #[test]
+    fn test_size_rule() {
+        let rules = SyncRules {
+            max_content_size: Some(100),
+            ..Default::default()
+        };
+        assert!(rules.accepts(&attrs(100, &[], None)));
+        assert!(!rules.accepts(&attrs(101, &[], None)));
+    }
+
+    #[test]
+    fn test_all_conditions_must_match() {
+        let rules = SyncRules {
+            labels: vec!["sync:high".to_string()],
+            namespaces: vec![],
+            max_content_size: Some(100),
+        };
+        assert!(rules.accepts(&attrs(50, &["sync:high"], None)));
+        assert!(!rules.accepts(&attrs(500, &["sync:high"], None)));
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            parse_list(Some(" sync:high, photos ,,".to_string())),
+            vec!["sync:high".to_string(), "photos".to_string()]
+        );
+        assert!(parse_list(None).is_empty());
+    }
+
+    #[test]
+    fn test_rules_serde_defaults() {
+        let rules: SyncRules = serde_json::from_str("{}").unwrap();
+        assert!(rules.is_replicate_all());
+    }
+}
@@ -0,0 +1,167 @@
+//! Sync status - How far each content's local replica is behind its peers.
+//!
+//! The sync service records what it observes on every pull: the local
+//! version after applying what peers sent, the latest version a peer
+//! reported, and operations it fetched but could not apply. A content is
+//! fully replicated when the last sync reached a peer, nothing is pending and
+//! the peer's version matches the local one.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Replication state of a single content. Timestamps are Unix seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentSyncStatus {
+    /// The genesis CID of the content.
+    pub content_id: String,
+    /// Latest local version CID.
+    pub local_version: Option<String>,
+    /// Latest version CID reported by a peer. Unknown until a peer that
+    /// reports its version has been reached.
+    pub remote_version: Option<String>,
+    /// Peer that reported `remote_version`.
+    pub remote_peer: Option<String>,
+    /// When the content was last synced, successfully or not.
+    pub last_sync_at: Option<u64>,
+    /// When a sync last reached a peer.
+    pub last_success_at: Option<u64>,
+    /// Operations fetched in the last sync that could not be applied.
+    pub pending_ops: usize,
+}
+
+impl ContentSyncStatus {
+    /// Status of a content that has not been synced yet.
+    pub fn new(content_id: String) -> Self {
+        Self {
+            content_id,
+            local_version: None,
+            remote_version: None,
+            remote_peer: None,
+            last_sync_at: None,
+            last_success_at: None,
+            pending_ops: 0,
+        }
+    }
+
+    /// Whether the local replica is known to match its peers.
+    pub fn is_in_sync(&self) -> bool {
+        self.last_success_at.is_some()
+            && self.pending_ops == 0
+            && (self.remote_version.is_none() || self.remote_version == self.local_version)
+    }
+
+    /// Seconds since a sync last reached a peer, or `None` if none has.
+    pub fn lag_secs(&self, now: u64) -> Option<u64> {
+        self.last_success_at.map(|at| now.saturating_sub(at))
+    }
+}
+
+/// What a single sync of a content observed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncObservation {
+    /// Latest local version CID after the sync.
+    pub local_version: Option<String>,
+    /// Latest version reported by a peer, with the reporting peer.
+    pub remote: Option<(String, String)>,
+    /// Whether any peer answered.
+    pub reached_peer: bool,
+    /// Operations fetched that could not be applied.
+    pub pending_ops: usize,
+}
+
+/// Sync status of every content the node syncs.
+#[derive(Debug, Clone, Default)]
+pub struct SyncStatusTracker {
+    statuses: HashMap<String, ContentSyncStatus>,
+}
+
+impl SyncStatusTracker {
+    /// Record the outcome of a sync of `content_id` at `now`.
+    pub fn record(&mut self, content_id: &str, observation: SyncObservation, now: u64) {
+        let status = self
+            .statuses
+            .entry(content_id.to_string())
+            .or_insert_with(|| ContentSyncStatus::new(content_id.to_string()));
+        status.local_version = observation.local_version;
+        if let Some((peer, version)) = observation.remote {
+            status.remote_peer = Some(peer);
+            status.remote_version = Some(version);
+        }
+        status.last_sync_at = Some(now);
+        if observation.reached_peer {
+            status.last_success_at = Some(now);
+        }
+        status.pending_ops = observation.pending_ops;
+    }
+
+    /// Status of `content_id`, if it has been synced.
+    pub fn get(&self, content_id: &str) -> Option<&ContentSyncStatus> {
+        self.statuses.get(content_id)
+    }
+
+    /// Status of every synced content, ordered by content ID.
+    pub fn list(&self) -> Vec<ContentSyncStatus> {
+        let mut statuses: Vec<ContentSyncStatus> = self.statuses.values().cloned().collect();
+        statuses.sort_by(|a, b| a.content_id.cmp(&b.content_id));
+        statuses
+    }
+
+    /// Forget content that is no longer synced.
+    pub fn retain(&mut self, content_ids: &[String]) {
+        self.statuses.retain(|id, _| content_ids.contains(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(local: &str, remote: Option<&str>, pending_ops: usize) -> SyncObservation {
+        SyncObservation {
+            local_version: Some(local.to_string()),
+            remote: remote.map(|version| ("peer-1".to_string(), version.to_string())),
+            reached_peer: true,
+            pending_ops,
+        }
+    }
+
+    #[test]
+    fn test_status_is_in_sync_when_versions_match() {
+        let mut tracker = SyncStatusTracker::default();
+        tracker.record("cid-1", observation("v2", Some("v3"), 0), 100);
+        let status = tracker.get("cid-1").unwrap();
+        assert!(!status.is_in_sync());
+        assert_eq!(status.remote_peer.as_deref(), Some("peer-1"));
+
+        tracker.record("cid-1", observation("v3", Some("v3"), 0), 130);
+        let status = tracker.get("cid-1").unwrap();
+        assert!(status.is_in_sync());
+        assert_eq!(status.lag_secs(140), Some(10));
+
+        tracker.record("cid-1", observation("v3", Some("v3"), 2), 160);
+        assert!(!tracker.get("cid-1").unwrap().is_in_sync());
+    }
+
+    #[test]
+    fn test_failed_sync_keeps_last_success_and_remote_version() {
+        let mut tracker = SyncStatusTracker::default();
+        tracker.record("cid-1", observation("v1", Some("v1"), 0), 100);
+        tracker.record(
+            "cid-1",
+            SyncObservation {
+                local_version: Some("v1".to_string()),
+                ..SyncObservation::default()
+            },
+            200,
+        );
+
+        let status = tracker.get("cid-1").unwrap();
+        assert_eq!(status.last_sync_at, Some(200));
+        assert_eq!(status.last_success_at, Some(100));
+        assert_eq!(status.remote_version.as_deref(), Some("v1"));
+        assert_eq!(status.lag_secs(200), Some(100));
+
+        tracker.retain(&[]);
+        assert!(tracker.list().is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+//! Tombstone - Record of content that this node refuses to host.
+//!
+//! Node operators may need to take down specific content (abuse, legal
+//! requests). A tombstone is a local, persistent marker keyed by the genesis
+//! CID. Unlike a `ContentDeleted` event it is never overwritten by later
+//! announcements, so denied content cannot be re-synced onto the node.
+
+use serde::{Deserialize, Serialize};
+
+use super::events::current_timestamp;
+
+/// A local takedown record for a single content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tombstone {
+    /// The genesis CID of the denied content.
+    genesis_cid: String,
+    /// Operator-supplied reason for the takedown.
+    reason: String,
+    /// Unix timestamp (seconds) when the tombstone was created.
+    created_at: u64,
+}
+
+impl Tombstone {
+    /// Create a new tombstone stamped with the current time.
+    pub fn new(genesis_cid: String, reason: String) -> Self {
+        Self {
+            genesis_cid,
+            reason,
+            created_at: current_timestamp(),
+        }
+    }
+
+    /// Get the genesis CID.
+    pub fn genesis_cid(&self) -> &str {
+        &self.genesis_cid
+    }
+
+    /// Get the takedown reason.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    /// Get the creation timestamp.
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_tombstone() {
+        let tombstone = Tombstone::new("cid-1".to_string(), "abuse".to_string());
+        assert_eq!(tombstone.genesis_cid(), "cid-1");
+        assert_eq!(tombstone.reason(), "abuse");
+        assert!(tombstone.created_at() > 0);
+    }
+
+    #[test]
+    fn test_tombstone_serde_roundtrip() {
+        let tombstone = Tombstone::new("cid-1".to_string(), "legal".to_string());
+        let json = serde_json::to_vec(&tombstone).unwrap();
+        let decoded: Tombstone = serde_json::from_slice(&json).unwrap();
+        assert_eq!(tombstone, decoded);
+    }
+}
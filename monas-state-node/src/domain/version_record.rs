@@ -0,0 +1,101 @@
+//! Version record - DHT pointer to the latest version of a content.
+//!
+//! A member that commits an update stores a record naming the new version
+//! under a key derived from the genesis CID and its own peer ID. Nodes that
+//! cannot reach any member can still look up the members' records to learn
+//! how far behind they are.
+//!
+//! Each publisher has its own key, so a node outside the content network can
+//! only write records that readers ignore, never replace a member's record.
+
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the DHT keys of version records. Keeps them apart from the
+/// provider records, which are keyed by the bare genesis CID.
+const VERSION_RECORD_KEY_PREFIX: &str = "/monas/version/";
+
+/// Latest version of a content, as announced by a member.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionRecord {
+    /// The genesis CID of the content.
+    pub genesis_cid: String,
+    /// The latest version CID.
+    pub version_cid: String,
+    /// Hex-encoded SHA-256 digest of the data of the version. Records
+    /// published before the digest was added lack it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_sha256: Option<String>,
+}
+
+impl VersionRecord {
+    /// Encode the record as a DHT record value.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Serializing strings cannot fail.
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+
+    /// Decode a DHT record value, or `None` if it is not a version record.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice(bytes).ok()
+    }
+
+    /// The decoded data digest, or `None` if the record has none or it is
+    /// not a hex-encoded SHA-256 digest.
+    pub fn content_sha256(&self) -> Option<[u8; 32]> {
+        let digest = hex::decode(self.content_sha256.as_ref()?).ok()?;
+        digest.try_into().ok()
+    }
+}
+
+/// DHT key under which `publisher` stores the version record of
+/// `genesis_cid`.
+pub fn version_record_key(genesis_cid: &str, publisher: &str) -> Vec<u8> {
+    format!("{}{}/{}", VERSION_RECORD_KEY_PREFIX, genesis_cid, publisher).into_bytes()
+}
+
+/// The publisher a version record key belongs to, or `None` if `key` is not
+/// a version record key.
+pub fn version_record_publisher(key: &[u8]) -> Option<&str> {
+    let key = std::str::from_utf8(key).ok()?;
+    let (_, publisher) = key
+        .strip_prefix(VERSION_RECORD_KEY_PREFIX)?
+        .rsplit_once('/')?;
+    Some(publisher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_record_roundtrip() {
+        let record = VersionRecord {
+            genesis_cid: "cid-1".to_string(),
+            version_cid: "cid-3".to_string(),
+            content_sha256: Some(hex::encode([7u8; 32])),
+        };
+        assert_eq!(
+            VersionRecord::from_bytes(&record.to_bytes()),
+            Some(record.clone())
+        );
+        assert_eq!(record.content_sha256(), Some([7u8; 32]));
+        assert_eq!(VersionRecord::from_bytes(b"not json"), None);
+    }
+
+    #[test]
+    fn test_version_record_without_digest() {
+        let record =
+            VersionRecord::from_bytes(br#"{"genesis_cid":"cid-1","version_cid":"cid-3"}"#).unwrap();
+        assert_eq!(record.content_sha256, None);
+        assert_eq!(record.content_sha256(), None);
+    }
+
+    #[test]
+    fn test_version_record_key_differs_from_provider_key() {
+        let key = version_record_key("cid-1", "peer-1");
+        assert_ne!(key, b"cid-1".to_vec());
+        assert_ne!(key, version_record_key("cid-1", "peer-2"));
+        assert_eq!(version_record_publisher(&key), Some("peer-1"));
+        assert_eq!(version_record_publisher(b"cid-1"), None);
+    }
+}
@@ -0,0 +1,121 @@
+//! Revocation list served by monas-account.
+//!
+//! Fetches `GET {account_url}/revocations` and caches the revoked public
+//! keys for a short time, so a deactivated account stops being accepted
+//! within one refresh interval without a request per signature.
+
+use crate::port::key_revocation::KeyRevocationChecker;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Default time a fetched revocation list is reused.
+pub const DEFAULT_REVOCATION_CACHE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+struct RevocationListResponse {
+    revocations: Vec<RevocationRecord>,
+}
+
+#[derive(Deserialize)]
+struct RevocationRecord {
+    algorithm: String,
+    public_key_base64: String,
+}
+
+struct CachedRevocations {
+    fetched_at: Instant,
+    /// Uncompressed SEC1 encodings of the revoked P-256 keys.
+    keys: HashSet<Vec<u8>>,
+}
+
+/// [`KeyRevocationChecker`] backed by a monas-account server.
+pub struct HttpAccountRevocationList {
+    client: reqwest::Client,
+    url: String,
+    ttl: Duration,
+    cache: Mutex<Option<CachedRevocations>>,
+}
+
+impl HttpAccountRevocationList {
+    /// Create a checker for the monas-account server at `account_url`.
+    pub fn new(account_url: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: format!("{}/revocations", account_url.trim_end_matches('/')),
+            ttl: DEFAULT_REVOCATION_CACHE_TTL,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Set how long a fetched list is reused.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    async fn fetch(&self) -> Result<HashSet<Vec<u8>>> {
+        let response: RevocationListResponse = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .with_context(|| format!("Failed to fetch revocation list from {}", self.url))?
+            .json()
+            .await
+            .context("Invalid revocation list response")?;
+
+        let mut keys = HashSet::new();
+        for record in response.revocations {
+            // Only P-256 account keys are accepted by this node
+            if record.algorithm != "P256" {
+                continue;
+            }
+            let public_key = base64::engine::general_purpose::STANDARD
+                .decode(&record.public_key_base64)
+                .context("Invalid public key in revocation list")?;
+            if let Some(key) = normalize_p256(&public_key) {
+                keys.insert(key);
+            }
+        }
+        Ok(keys)
+    }
+}
+
+/// Uncompressed SEC1 encoding of a P-256 key given in either SEC1 form.
+fn normalize_p256(public_key: &[u8]) -> Option<Vec<u8>> {
+    let key = p256::PublicKey::from_sec1_bytes(public_key).ok()?;
+    Some(
+        p256::elliptic_curve::sec1::ToEncodedPoint::to_encoded_point(&key, false)
+            .as_bytes()
+            .to_vec(),
+    )
+}
+
+#[async_trait]
+impl KeyRevocationChecker for HttpAccountRevocationList {
+    async fn is_revoked(&self, public_key: &[u8]) -> Result<bool> {
+        let Some(key) = normalize_p256(public_key) else {
+            return Ok(false);
+        };
+
+        let mut cache = self.cache.lock().await;
+        let fresh = cache
+            .as_ref()
+            .is_some_and(|cached| cached.fetched_at.elapsed() < self.ttl);
+        if !fresh {
+            *cache = Some(CachedRevocations {
+                fetched_at: Instant::now(),
+                keys: self.fetch().await?,
+            });
+        }
+        Ok(cache
+            .as_ref()
+            .is_some_and(|cached| cached.keys.contains(&key)))
+    }
+}
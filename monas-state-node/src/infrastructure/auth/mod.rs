@@ -1,17 +1,23 @@
 //! Authentication and authorization infrastructure implementations.
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod account_revocation_list;
 pub mod auth_token;
 pub mod monas_account_adapter;
 pub mod node_auth_adapter;
 pub mod signature_verifier;
 #[cfg(test)]
 pub mod test_helpers;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod ucan_adapter;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use account_revocation_list::HttpAccountRevocationList;
 pub use auth_token::{
     AuthToken, AuthTokenError, AuthTokenHeader, AuthTokenPayload, Capability, CapabilityAction,
 };
 pub use monas_account_adapter::MonasAccountAdapter;
 pub use node_auth_adapter::NodeAuthAdapter;
 pub use signature_verifier::SignatureVerifier;
+#[cfg(not(target_arch = "wasm32"))]
 pub use ucan_adapter::UcanAdapter;
@@ -8,8 +8,10 @@ use crate::infrastructure::auth::auth_token::AuthToken as InfraAuthToken;
 use crate::infrastructure::auth::signature_verifier::SignatureVerifier;
 use crate::port::auth_token::{AuthContext, AuthToken};
 use crate::port::authentication_service::AuthenticationService;
+use crate::port::key_revocation::KeyRevocationChecker;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use std::sync::Arc;
 
 /// Signature verification context for authentication.
 ///
@@ -50,12 +52,37 @@ impl SignatureContext {
 ///
 /// All key IDs must be self-contained: "type:{public_key_hex}"
 /// where public_key_hex is 130 hex chars (65 bytes uncompressed P256, starting with "04").
-pub struct MonasAccountAdapter;
+///
+/// With [`with_revocations`](Self::with_revocations), signatures and tokens of
+/// accounts deactivated in monas-account are rejected.
+pub struct MonasAccountAdapter {
+    revocations: Option<Arc<dyn KeyRevocationChecker>>,
+}
 
 impl MonasAccountAdapter {
     /// Create a new adapter
     pub fn new() -> Self {
-        Self
+        Self { revocations: None }
+    }
+
+    /// Reject keys that `revocations` reports as revoked.
+    pub fn with_revocations(mut self, revocations: Arc<dyn KeyRevocationChecker>) -> Self {
+        self.revocations = Some(revocations);
+        self
+    }
+
+    /// Fail if the key has been revoked by its account.
+    async fn ensure_not_revoked(&self, public_key: &[u8]) -> Result<()> {
+        if let Some(revocations) = &self.revocations {
+            if revocations
+                .is_revoked(public_key)
+                .await
+                .context("Failed to check key revocation")?
+            {
+                anyhow::bail!("Account key has been revoked");
+            }
+        }
+        Ok(())
     }
 
     /// Parse key ID from token string
@@ -119,6 +146,8 @@ impl MonasAccountAdapter {
         )
         .context("Signature verification failed")?;
 
+        self.ensure_not_revoked(&public_key).await?;
+
         // Check timestamp to prevent replay attacks
         if let Some(timestamp) = context.timestamp {
             let now = std::time::SystemTime::now()
@@ -209,7 +238,9 @@ impl AuthenticationService for MonasAccountAdapter {
 
         // Verify P-256 signature
         SignatureVerifier::verify_auth_token_signature(&parsed, &public_key)
-            .context("JWT signature verification failed")
+            .context("JWT signature verification failed")?;
+
+        self.ensure_not_revoked(&public_key).await
     }
 
     async fn verify_request_signature(
@@ -363,6 +394,45 @@ mod tests {
             .contains("timestamp too old"));
     }
 
+    struct RevokedKeys(Vec<Vec<u8>>);
+
+    #[async_trait]
+    impl KeyRevocationChecker for RevokedKeys {
+        async fn is_revoked(&self, public_key: &[u8]) -> Result<bool> {
+            Ok(self.0.iter().any(|key| key == public_key))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature_rejects_revoked_key() {
+        let (_, signing_key, key_id) = create_test_adapter();
+        let public_key = signing_key
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec();
+        let adapter =
+            MonasAccountAdapter::new().with_revocations(Arc::new(RevokedKeys(vec![public_key])));
+
+        let message = "test message";
+        use p256::ecdsa::signature::Signer;
+        let signature: p256::ecdsa::Signature = signing_key.sign(message.as_bytes());
+        let context = SignatureContext::new(message.to_string(), signature.to_vec());
+
+        let result = adapter
+            .verify_signature_with_context(&key_id, &context)
+            .await;
+        assert!(result.unwrap_err().to_string().contains("revoked"));
+
+        let (_, other_key, other_key_id) = create_test_adapter();
+        let signature: p256::ecdsa::Signature = other_key.sign(message.as_bytes());
+        let context = SignatureContext::new(message.to_string(), signature.to_vec());
+        adapter
+            .verify_signature_with_context(&other_key_id, &context)
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn test_authenticate_invalid_key_id_format() {
         let adapter = MonasAccountAdapter::new();
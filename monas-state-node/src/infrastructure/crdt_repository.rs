@@ -4,9 +4,14 @@
 //! using crsl-lib for CRDT-based content versioning.
 
 use crate::domain::access_policy::AccessPolicy;
+use crate::domain::events::current_timestamp;
+use crate::domain::storage_budget::StorageUsage;
+use crate::infrastructure::disk_capacity;
+use crate::infrastructure::storage_tiers::TieredBlobStore;
 use crate::port::content_repository::{
-    CommitResult, ContentRepository, PreparedCreate, SerializedOperation,
+    CommitResult, ContentRepository, ContentSnapshot, PreparedCreate, SerializedOperation,
 };
+use crate::port::persistence::{PersistentDenylistRepository, PersistentEvictionRepository};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -22,7 +27,11 @@ use crsl_lib::storage::SharedLeveldb;
 use multihash_codetable::{Code, MultihashDigest};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 /// Payload type for content storage.
 /// Contains raw binary content data and an optional access policy.
@@ -36,6 +45,23 @@ pub struct ContentPayload {
 type OpStore = LeveldbStorage<Cid, ContentPayload>;
 type NodeStore = LeveldbNodeStorage<ContentPayload, ContentMetadata>;
 type ContentRepo = Repo<OpStore, NodeStore, ContentPayload>;
+type Operations = Vec<SerializedOperation>;
+
+/// Name of the LevelDB directory used when no pointer file exists.
+const DEFAULT_DB_NAME: &str = "crdt_db";
+
+/// File under the base path naming the LevelDB directory in use.
+///
+/// Purging rebuilds the database into a fresh directory and then switches
+/// this pointer, so an interrupted purge leaves the previous database intact.
+const CURRENT_DB_FILE: &str = "CURRENT_DB";
+
+/// File under the base path holding the snapshots of compacted contents.
+const SNAPSHOTS_FILE: &str = "snapshots.json";
+
+/// Default number of operations after the last snapshot (or the Create
+/// operation) at which a content is compacted.
+pub const DEFAULT_COMPACT_AFTER_OPS: usize = 256;
 
 /// CRDT Repository implementation using crsl-lib.
 ///
@@ -47,6 +73,29 @@ pub struct CrslCrdtRepository {
     /// The crsl-lib repository wrapped in a Mutex for thread safety.
     /// Repo methods require &mut self, so we need interior mutability.
     repo: Mutex<ContentRepo>,
+    /// Optional denylist. Operations and fetches for tombstoned content are refused.
+    denylist: Option<Arc<dyn PersistentDenylistRepository>>,
+    /// Optional eviction records. Evicted content is treated like denied
+    /// content until it is purged or the eviction is cancelled.
+    evictions: Option<Arc<dyn PersistentEvictionRepository>>,
+    /// Base storage directory.
+    base: PathBuf,
+    /// Name of the LevelDB directory currently in use under `base`.
+    db_name: Mutex<String>,
+    /// Maximum bytes the database may use. Unlimited when unset.
+    quota_bytes: Option<u64>,
+    /// Bytes used by the database: measured when the quota is set and after
+    /// each rebuild, and counted up by every write in between.
+    used_bytes: AtomicU64,
+    /// Snapshot of each compacted content, by genesis CID.
+    snapshots: Mutex<HashMap<String, ContentSnapshot>>,
+    /// Held while the database is rebuilt, so purges and compactions do not
+    /// overlap.
+    maintenance: Mutex<()>,
+    /// Optional store for the latest version of each content, keyed by
+    /// genesis CID. Reads are served from it and it is refreshed when the
+    /// latest version changes; idle contents move to the cold storage tier.
+    blob_store: Option<Arc<TieredBlobStore>>,
 }
 
 impl CrslCrdtRepository {
@@ -55,9 +104,34 @@ impl CrslCrdtRepository {
         let base = base_path.as_ref();
         std::fs::create_dir_all(base).context("Failed to create CRDT storage directory")?;
 
+        let db_name = match std::fs::read_to_string(base.join(CURRENT_DB_FILE)) {
+            Ok(name) => name.trim().to_string(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => DEFAULT_DB_NAME.to_string(),
+            Err(e) => return Err(e).context("Failed to read CRDT database pointer"),
+        };
+        Self::remove_stale_dbs(base, &db_name);
+
+        let repo = Self::open_repo(base.join(&db_name))?;
+        let snapshots = Self::load_snapshots(base)?;
+
+        Ok(Self {
+            repo: Mutex::new(repo),
+            denylist: None,
+            evictions: None,
+            base: base.to_path_buf(),
+            db_name: Mutex::new(db_name),
+            quota_bytes: None,
+            used_bytes: AtomicU64::new(0),
+            snapshots: Mutex::new(snapshots),
+            maintenance: Mutex::new(()),
+            blob_store: None,
+        })
+    }
+
+    fn open_repo(path: PathBuf) -> Result<ContentRepo> {
         // Use a single shared LevelDB instance for both operation and node storage
         // This is required for transactions to work correctly
-        let shared_db = SharedLeveldb::open(base.join("crdt_db"))
+        let shared_db = SharedLeveldb::open(path)
             .map_err(|e| anyhow::anyhow!("Failed to open shared LevelDB: {}", e))?;
 
         let op_storage = LeveldbStorage::new(shared_db.clone());
@@ -65,14 +139,530 @@ impl CrslCrdtRepository {
 
         let state = CrdtState::new(op_storage);
         let dag = DagGraph::new(node_storage);
-        let repo = Repo::new(state, dag);
+        Ok(Repo::new(state, dag))
+    }
 
-        Ok(Self {
-            repo: Mutex::new(repo),
-        })
+    /// Remove database directories left behind by an interrupted purge.
+    fn remove_stale_dbs(base: &Path, current: &str) {
+        let Ok(entries) = std::fs::read_dir(base) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name != current && Self::db_generation(&name).is_some() {
+                tracing::info!("Removing stale CRDT database {}", name);
+                if let Err(e) = std::fs::remove_dir_all(entry.path()) {
+                    tracing::warn!("Failed to remove stale CRDT database {}: {}", name, e);
+                }
+            }
+        }
+    }
+
+    fn load_snapshots(base: &Path) -> Result<HashMap<String, ContentSnapshot>> {
+        match std::fs::read(base.join(SNAPSHOTS_FILE)) {
+            Ok(bytes) => serde_json::from_slice(&bytes).context("Failed to parse CRDT snapshots"),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(e).context("Failed to read CRDT snapshots"),
+        }
+    }
+
+    fn save_snapshots(&self, snapshots: &HashMap<String, ContentSnapshot>) -> Result<()> {
+        let tmp = self.base.join(format!("{}.tmp", SNAPSHOTS_FILE));
+        std::fs::write(&tmp, serde_json::to_vec(snapshots)?)
+            .context("Failed to write CRDT snapshots")?;
+        std::fs::rename(&tmp, self.base.join(SNAPSHOTS_FILE))
+            .context("Failed to replace CRDT snapshots")
+    }
+
+    /// Generation number of a database directory name (`crdt_db` is 0,
+    /// `crdt_db.<n>` is n), or `None` for other names.
+    fn db_generation(name: &str) -> Option<u64> {
+        if name == DEFAULT_DB_NAME {
+            return Some(0);
+        }
+        name.strip_prefix(DEFAULT_DB_NAME)?
+            .strip_prefix('.')?
+            .parse()
+            .ok()
+    }
+
+    /// Set the denylist (builder pattern).
+    ///
+    /// Once set, `apply_operations` drops operations for denied content and
+    /// read/sync methods behave as if denied content does not exist.
+    pub fn with_denylist(mut self, denylist: Arc<dyn PersistentDenylistRepository>) -> Self {
+        self.denylist = Some(denylist);
+        self
+    }
+
+    /// Set the eviction records (builder pattern).
+    ///
+    /// Once set, evicted content is hidden the same way as denied content.
+    pub fn with_evictions(mut self, evictions: Arc<dyn PersistentEvictionRepository>) -> Self {
+        self.evictions = Some(evictions);
+        self
+    }
+
+    /// Set the store holding the latest version of each content (builder
+    /// pattern).
+    pub fn with_blob_store(mut self, blob_store: Arc<TieredBlobStore>) -> Self {
+        self.blob_store = Some(blob_store);
+        self
+    }
+
+    /// Set the storage quota in bytes (builder pattern).
+    ///
+    /// Once set, new content is refused when the database is nearly full and
+    /// all writes are refused once it reaches the quota.
+    pub fn with_quota(mut self, quota_bytes: u64) -> Self {
+        self.quota_bytes = Some(quota_bytes);
+        self.refresh_usage();
+        self
+    }
+
+    /// Re-measure the bytes used by the database by walking its directory.
+    /// No-op without a quota. Blocking; writes keep the count up to date
+    /// with [`reserve`](Self::reserve) instead.
+    fn refresh_usage(&self) {
+        if self.quota_bytes.is_none() {
+            return;
+        }
+        let path = self.base.join(self.db_name.lock().as_str());
+        match disk_capacity::directory_size(&path) {
+            Ok(used) => self.used_bytes.store(used, Ordering::Relaxed),
+            Err(e) => tracing::warn!("Failed to measure CRDT database size: {}", e),
+        }
+    }
+
+    /// Reserve the budget for a write adding `new_content_bytes` of new
+    /// content and `update_bytes` to content already hosted, or fail if it
+    /// does not fit. Checking and reserving is one atomic step, so
+    /// concurrent writes cannot together overshoot the quota. The operation
+    /// bytes stand in for the (unknown) bytes the database will write.
+    fn reserve(&self, new_content_bytes: u64, update_bytes: u64) -> Result<()> {
+        let Some(quota_bytes) = self.quota_bytes else {
+            return Ok(());
+        };
+        let bytes = new_content_bytes.saturating_add(update_bytes);
+        let reserved =
+            self.used_bytes
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |used_bytes| {
+                    let usage = StorageUsage {
+                        used_bytes,
+                        quota_bytes,
+                    };
+                    let fits = (new_content_bytes == 0
+                        || usage.accepts_new_content(new_content_bytes))
+                        && usage.accepts_update(bytes);
+                    fits.then(|| used_bytes.saturating_add(bytes))
+                });
+        match reserved {
+            Ok(_) => Ok(()),
+            Err(used_bytes) => {
+                let usage = StorageUsage {
+                    used_bytes,
+                    quota_bytes,
+                };
+                if new_content_bytes > 0 && !usage.accepts_new_content(new_content_bytes) {
+                    anyhow::bail!(
+                        "Storage quota nearly full: {} of {} bytes used, refusing {} bytes of new content",
+                        used_bytes,
+                        quota_bytes,
+                        new_content_bytes
+                    );
+                }
+                anyhow::bail!(
+                    "Storage quota exceeded: {} of {} bytes used, refusing {} bytes",
+                    used_bytes,
+                    quota_bytes,
+                    bytes
+                )
+            }
+        }
+    }
+
+    /// Return budget reserved for a write that did not happen.
+    fn release(&self, bytes: u64) {
+        if self.quota_bytes.is_none() || bytes == 0 {
+            return;
+        }
+        let _ = self
+            .used_bytes
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |used_bytes| {
+                Some(used_bytes.saturating_sub(bytes))
+            });
+    }
+
+    /// Whether `genesis_cid` is denied or evicted.
+    async fn is_hidden(&self, genesis_cid: &str) -> Result<bool> {
+        if self.is_denied(genesis_cid).await? {
+            return Ok(true);
+        }
+        match &self.evictions {
+            Some(evictions) => evictions.is_evicted(genesis_cid).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Physically remove the data of `genesis_cid` from disk.
+    ///
+    /// crsl-lib has no per-content deletion, so the database is rebuilt
+    /// without the content (see [`rebuild`](Self::rebuild)). `still_due` is
+    /// called with the repository locked right before the rebuilt database
+    /// replaces the old one; if it returns false (e.g. the eviction was
+    /// cancelled in the meantime) the rebuild is discarded. Returns whether
+    /// the content was purged. Blocks for the duration of the rebuild; run it
+    /// on a blocking thread.
+    pub fn purge(&self, genesis_cid: &str, still_due: impl FnOnce() -> bool) -> Result<bool> {
+        let purged = self.rebuild(
+            |cid, ops| Ok((cid != genesis_cid).then(|| ops.to_vec())),
+            still_due,
+        )?;
+        if !purged {
+            return Ok(false);
+        }
+        self.refresh_usage();
+        if let Some(blob_store) = &self.blob_store {
+            blob_store.remove(genesis_cid)?;
+        }
+
+        let mut snapshots = self.snapshots.lock();
+        if snapshots.remove(genesis_cid).is_some() {
+            self.save_snapshots(&snapshots)?;
+        }
+        Ok(true)
+    }
+
+    /// Compact the operation log of every content with at least `min_ops`
+    /// operations after its last snapshot.
+    ///
+    /// Each such content gets a new snapshot of its latest state, and its
+    /// log is truncated to the snapshot's two operations (see
+    /// [`ContentSnapshot`]) by rebuilding the database the same way as
+    /// [`purge`](Self::purge). The retained operations keep their parents
+    /// and node timestamps, so version CIDs do not change; versions before
+    /// the snapshot are dropped. Returns the number of contents compacted.
+    /// Blocks like `purge`; run it on a blocking thread.
+    pub fn compact(&self, min_ops: usize) -> Result<usize> {
+        let due = |genesis_cid: &str, op_count: usize| {
+            let retained = self
+                .snapshots
+                .lock()
+                .get(genesis_cid)
+                .map_or(1, |snapshot| snapshot.operations().len());
+            op_count > 2 && op_count - retained >= min_ops.max(1)
+        };
+
+        // Only rebuild when some content is due.
+        let contents = Self::genesis_cids(&self.repo.lock())?;
+        let mut any_due = false;
+        for genesis_cid in &contents {
+            let genesis = Self::parse_cid(genesis_cid)?;
+            let op_count = self
+                .repo
+                .lock()
+                .get_operations_with_index(&genesis)
+                .map_err(|e| anyhow::anyhow!("Failed to get operations: {}", e))?
+                .len();
+            if due(genesis_cid, op_count) {
+                any_due = true;
+                break;
+            }
+        }
+        if !any_due {
+            return Ok(0);
+        }
+
+        let taken = RefCell::new(HashMap::new());
+        self.rebuild(
+            |genesis_cid, ops| {
+                if !due(genesis_cid, ops.len()) {
+                    return Ok(Some(ops.to_vec()));
+                }
+                let previous = self.snapshots.lock().get(genesis_cid).cloned();
+                match Self::take_snapshot(genesis_cid, ops, previous.as_ref())? {
+                    Some(snapshot) => {
+                        let kept = snapshot.operations();
+                        taken.borrow_mut().insert(genesis_cid.to_string(), snapshot);
+                        Ok(Some(kept))
+                    }
+                    None => Ok(Some(ops.to_vec())),
+                }
+            },
+            || !taken.borrow().is_empty(),
+        )?;
+        let taken = taken.into_inner();
+        if taken.is_empty() {
+            return Ok(0);
+        }
+        self.refresh_usage();
+
+        let compacted = taken.len();
+        let mut snapshots = self.snapshots.lock();
+        snapshots.extend(taken);
+        self.save_snapshots(&snapshots)?;
+        Ok(compacted)
+    }
+
+    /// Snapshot the latest state of a content whose operations are `ops`.
+    ///
+    /// Returns `None` when the latest operation carries no state (e.g. a
+    /// Delete), as there is nothing to snapshot.
+    fn take_snapshot(
+        genesis_cid: &str,
+        ops: &[SerializedOperation],
+        previous: Option<&ContentSnapshot>,
+    ) -> Result<Option<ContentSnapshot>> {
+        let (Some(create_op), Some(latest)) =
+            (ops.first(), ops.iter().max_by_key(|op| op.timestamp))
+        else {
+            return Ok(None);
+        };
+        // The state operation is kept as is, parents included, so that it
+        // reproduces the same version CID on every node.
+        let state_op = match Self::decode_operation(latest)?.kind {
+            OperationType::Create(_) => None,
+            OperationType::Update(_) => Some(latest.clone()),
+            _ => return Ok(None),
+        };
+
+        // Versions folded into the previous snapshot are no longer in `ops`.
+        let folded = previous.map_or(0, |snapshot| {
+            snapshot.version as usize - snapshot.operations().len()
+        });
+        Ok(Some(ContentSnapshot {
+            genesis_cid: genesis_cid.to_string(),
+            version: (folded + ops.len()) as u64,
+            create_op: create_op.clone(),
+            state_op,
+            created_at: current_timestamp(),
+        }))
+    }
+
+    /// Rebuild the database into a fresh directory, then switch the pointer
+    /// to it and remove the old directory.
+    ///
+    /// `plan` is given each content's operations and returns the operations
+    /// to keep, or `None` to drop the content. The new database is filled
+    /// one content at a time, taking the repository lock only to read each
+    /// content, so reads and writes carry on during the rebuild. The lock is
+    /// then held for the final step: operations committed in the meantime
+    /// are copied over, `confirm` is asked whether to go ahead, every kept
+    /// content is checked to have the same latest version (and, if nothing
+    /// was dropped from it, the same history) and the databases are swapped.
+    /// The pointer file is replaced atomically, so an interrupted rebuild
+    /// leaves the previous database in use. Returns whether the rebuilt
+    /// database was swapped in.
+    fn rebuild(
+        &self,
+        mut plan: impl FnMut(&str, &[SerializedOperation]) -> Result<Option<Operations>>,
+        confirm: impl FnOnce() -> bool,
+    ) -> Result<bool> {
+        let _maintenance = self.maintenance.lock();
+
+        let generation = Self::db_generation(&self.db_name.lock()).unwrap_or(0);
+        let new_name = format!("{}.{}", DEFAULT_DB_NAME, generation + 1);
+        let new_path = self.base.join(&new_name);
+        if new_path.exists() {
+            std::fs::remove_dir_all(&new_path)
+                .context("Failed to clear CRDT database directory")?;
+        }
+        let mut new_repo = Self::open_repo(new_path.clone())?;
+
+        let mut copied = HashMap::new();
+        let contents = Self::genesis_cids(&self.repo.lock())?;
+        let filled = contents.iter().try_for_each(|genesis_cid| -> Result<()> {
+            let ops = Self::serialize_operations(&self.repo.lock(), genesis_cid, None)?;
+            let seen: HashSet<Vec<u8>> = ops.iter().map(Self::operation_digest).collect();
+            if let Some(kept) = plan(genesis_cid, &ops)? {
+                Self::commit_operations(&mut new_repo, genesis_cid, &kept)?;
+                copied.insert(genesis_cid.clone(), (seen, kept.len() == ops.len()));
+            }
+            Ok(())
+        });
+
+        let mut repo = self.repo.lock();
+        let swapped = filled.and_then(|()| {
+            if !confirm() {
+                return Ok(false);
+            }
+            for genesis_cid in Self::genesis_cids(&repo)? {
+                // Contents dropped by the plan stay dropped; contents created
+                // during the rebuild are copied in full.
+                if contents.contains(&genesis_cid) && !copied.contains_key(&genesis_cid) {
+                    continue;
+                }
+                let (seen, complete) = copied
+                    .entry(genesis_cid.clone())
+                    .or_insert_with(|| (HashSet::new(), true));
+                let missed: Vec<SerializedOperation> =
+                    Self::serialize_operations(&repo, &genesis_cid, None)?
+                        .into_iter()
+                        .filter(|op| !seen.contains(&Self::operation_digest(op)))
+                        .collect();
+                Self::commit_operations(&mut new_repo, &genesis_cid, &missed)?;
+                Self::verify_rebuilt(&repo, &new_repo, &genesis_cid, *complete)?;
+            }
+            Ok(true)
+        });
+        match swapped {
+            Ok(true) => {}
+            result => {
+                drop(new_repo);
+                let _ = std::fs::remove_dir_all(&new_path);
+                return result.context("Failed to rebuild CRDT database");
+            }
+        }
+
+        let pointer_tmp = self.base.join(format!("{}.tmp", CURRENT_DB_FILE));
+        std::fs::write(&pointer_tmp, &new_name).context("Failed to write CRDT database pointer")?;
+        std::fs::rename(&pointer_tmp, self.base.join(CURRENT_DB_FILE))
+            .context("Failed to switch CRDT database pointer")?;
+
+        // Dropping the old repo closes its LevelDB before the directory goes.
+        let mut db_name = self.db_name.lock();
+        let old_path = self.base.join(db_name.as_str());
+        drop(std::mem::replace(&mut *repo, new_repo));
+        drop(repo);
+        if let Err(e) = std::fs::remove_dir_all(&old_path) {
+            tracing::warn!("Failed to remove old CRDT database {:?}: {}", old_path, e);
+        }
+        *db_name = new_name;
+        Ok(true)
+    }
+
+    /// Commit transferred operations of `genesis_cid` into `repo`.
+    fn commit_operations(
+        repo: &mut ContentRepo,
+        genesis_cid: &str,
+        ops: &[SerializedOperation],
+    ) -> Result<()> {
+        for serialized_op in ops {
+            let op = Self::decode_operation(serialized_op)?;
+            repo.commit_operation(op).map_err(|e| {
+                anyhow::anyhow!("Failed to replay operation of {}: {}", genesis_cid, e)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Check that `genesis_cid` has the same latest version in the rebuilt
+    /// database as in the original, and the same history if `complete`.
+    fn verify_rebuilt(
+        from: &ContentRepo,
+        to: &ContentRepo,
+        genesis_cid: &str,
+        complete: bool,
+    ) -> Result<()> {
+        let genesis = Self::parse_cid(genesis_cid)?;
+        if from.latest(&genesis) != to.latest(&genesis) {
+            anyhow::bail!("Rebuilt latest version of {} does not match", genesis_cid);
+        }
+        if complete {
+            let expected = from
+                .linear_history(&genesis)
+                .map_err(|e| anyhow::anyhow!("Failed to get history: {}", e))?;
+            let actual = to
+                .linear_history(&genesis)
+                .map_err(|e| anyhow::anyhow!("Failed to get history: {}", e))?;
+            if expected != actual {
+                anyhow::bail!("Rebuilt history of {} does not match", genesis_cid);
+            }
+        }
+        Ok(())
+    }
+
+    /// Identity of a transferred operation, used to tell which operations
+    /// were committed while a rebuild was in progress.
+    fn operation_digest(op: &SerializedOperation) -> Vec<u8> {
+        Code::Sha2_256.digest(&op.data).to_bytes()
+    }
+
+    /// Genesis CIDs of all contents stored in `repo`.
+    fn genesis_cids(repo: &ContentRepo) -> Result<HashSet<String>> {
+        // Get all nodes and collect unique genesis CIDs
+        let node_map = repo
+            .dag
+            .storage
+            .get_node_map()
+            .map_err(|e| anyhow::anyhow!("Failed to get node map: {}", e))?;
+
+        let mut genesis_cids = HashSet::new();
+        for cid in node_map.keys() {
+            // Try to get the genesis for each node
+            if let Ok(genesis) = repo.get_genesis(cid) {
+                genesis_cids.insert(genesis.to_string());
+            }
+        }
+
+        Ok(genesis_cids)
+    }
+
+    /// Deserialize a transferred operation for import.
+    fn decode_operation(
+        serialized_op: &SerializedOperation,
+    ) -> Result<Operation<Cid, ContentPayload>> {
+        let mut op: Operation<Cid, ContentPayload> = serde_json::from_slice(&serialized_op.data)
+            .map_err(|e| anyhow::anyhow!("Failed to deserialize operation: {}", e))?;
+
+        // Set node_timestamp for import mode to ensure CID consistency across replicas
+        op.node_timestamp = Some(serialized_op.node_timestamp);
+        Ok(op)
     }
 
     /// Check if the repository is healthy (can list contents).
+    /// The latest version of a content and its version CID.
+    ///
+    /// With a blob store the data is read from the stored blob when it still
+    /// holds the latest version, and otherwise read from the DAG and stored.
+    fn read_latest(&self, genesis_cid: &str) -> Result<Option<(Vec<u8>, String)>> {
+        let genesis = Self::parse_cid(genesis_cid)?;
+        let Some(latest_cid) = self.repo.lock().latest(&genesis) else {
+            return Ok(None);
+        };
+        let version_cid = latest_cid.to_string();
+        if let Some(data) = self.stored_latest(genesis_cid, &version_cid) {
+            return Ok(Some((data, version_cid)));
+        }
+
+        let data = match self.repo.lock().dag.get_node(&latest_cid) {
+            Ok(Some(node)) => node.payload().data.clone(),
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(anyhow::anyhow!("Failed to get node: {}", e)),
+        };
+        self.store_latest(genesis_cid, &version_cid, &data);
+        Ok(Some((data, version_cid)))
+    }
+
+    /// Data of the stored blob of a content if it holds `version_cid`.
+    ///
+    /// A blob is the version CID, a newline, then the content data.
+    fn stored_latest(&self, genesis_cid: &str, version_cid: &str) -> Option<Vec<u8>> {
+        let blob = match self.blob_store.as_ref()?.get(genesis_cid) {
+            Ok(blob) => blob?,
+            Err(e) => {
+                tracing::warn!("Failed to read blob of {}: {}", genesis_cid, e);
+                return None;
+            }
+        };
+        blob.strip_prefix(version_cid.as_bytes())?
+            .strip_prefix(b"\n")
+            .map(<[u8]>::to_vec)
+    }
+
+    fn store_latest(&self, genesis_cid: &str, version_cid: &str, data: &[u8]) {
+        let Some(blob_store) = &self.blob_store else {
+            return;
+        };
+        let mut blob = Vec::with_capacity(version_cid.len() + 1 + data.len());
+        blob.extend_from_slice(version_cid.as_bytes());
+        blob.push(b'\n');
+        blob.extend_from_slice(data);
+        if let Err(e) = blob_store.put(genesis_cid, &blob) {
+            tracing::warn!("Failed to store blob of {}: {}", genesis_cid, e);
+        }
+    }
+
     pub async fn health_check(&self) -> Result<()> {
         // A simple read operation to verify DB is responsive
         let _contents = self.list_contents().await?;
@@ -92,6 +682,131 @@ impl CrslCrdtRepository {
             .parse()
             .with_context(|| format!("Invalid CID: {}", cid_str))
     }
+
+    /// Serialize the operations of `genesis_cid` after `since_version` for
+    /// transfer to another node (or into a rebuilt database).
+    fn serialize_operations(
+        repo: &ContentRepo,
+        genesis_cid: &str,
+        since_version: Option<&str>,
+    ) -> Result<Vec<SerializedOperation>> {
+        let genesis = Self::parse_cid(genesis_cid)?;
+
+        let indexed_ops = repo
+            .get_operations_with_index(&genesis)
+            .map_err(|e| anyhow::anyhow!("Failed to get operations: {}", e))?;
+
+        // Filter by since_version if provided
+        // Find the index of the operation corresponding to since_version
+        let since_index = if let Some(since) = since_version {
+            let since_cid = Self::parse_cid(since)?;
+
+            // If since_version is the genesis CID, skip the first operation (Create)
+            if since_cid == genesis {
+                Some(1) // Skip index 1 (the Create operation)
+            } else {
+                // Find the operation index by matching the DAG node timestamp
+                match repo.dag.get_node(&since_cid) {
+                    Ok(Some(since_node)) => {
+                        let since_ts = since_node.timestamp();
+                        // DAG node timestamps may be in seconds while operation timestamps are in nanoseconds
+                        // Convert to nanoseconds if the timestamp appears to be in seconds
+                        // Use the end of the second to include all operations within that second
+                        let since_ts_nanos = if since_ts < 1_000_000_000_000 {
+                            // Likely in seconds, convert to nanoseconds (end of that second)
+                            since_ts * 1_000_000_000 + 999_999_999
+                        } else {
+                            since_ts
+                        };
+                        // Find the operation with the closest timestamp <= since_ts
+                        indexed_ops
+                            .iter()
+                            .filter(|(_, op)| op.timestamp <= since_ts_nanos)
+                            .map(|(idx, _)| *idx)
+                            .max()
+                    }
+                    Ok(None) => None,
+                    Err(_) => None,
+                }
+            }
+        } else {
+            None
+        };
+
+        // Get all DAG nodes for this genesis to find node timestamps
+        let mut history = repo
+            .linear_history(&genesis)
+            .map_err(|e| anyhow::anyhow!("Failed to get history: {}", e))?;
+        // A compacted log keeps the latest version without the versions
+        // between it and the genesis, so it may not be on the history path.
+        if let Some(latest) = repo.latest(&genesis) {
+            if !history.contains(&latest) {
+                history.push(latest);
+            }
+        }
+
+        // Build a map of operation timestamp -> DAG node timestamp
+        // This is needed because DAG node timestamps may differ from operation timestamps
+        let mut node_timestamps: Vec<(u64, u64)> = Vec::new();
+        for node_cid in &history {
+            if let Ok(Some(node)) = repo.dag.get_node(node_cid) {
+                node_timestamps.push((node.timestamp(), node.timestamp()));
+            }
+        }
+
+        let mut operations = Vec::new();
+        for (idx, op) in indexed_ops {
+            // Skip operations at or before the since_version index
+            if let Some(since_idx) = since_index {
+                if idx <= since_idx {
+                    continue;
+                }
+            }
+
+            // Find the corresponding DAG node timestamp
+            // For Create operations, use the genesis node timestamp
+            // For other operations, find the node with matching or closest timestamp
+            let node_timestamp = if matches!(op.kind, OperationType::Create(_)) {
+                // For Create operation, get the genesis node timestamp
+                repo.dag
+                    .get_node(&genesis)
+                    .ok()
+                    .flatten()
+                    .map(|n| n.timestamp())
+                    .unwrap_or(op.timestamp)
+            } else {
+                // For Update/Delete/Merge, find the node in history that corresponds to this operation
+                // The node timestamp should be close to the operation timestamp
+                history
+                    .iter()
+                    .filter_map(|cid| repo.dag.get_node(cid).ok().flatten())
+                    .find(|node| {
+                        // Node timestamp should be within a reasonable range of op timestamp
+                        // or we just find the closest one
+                        let node_ts = node.timestamp();
+                        // Allow some tolerance for timestamp matching
+                        node_ts >= op.timestamp.saturating_sub(1_000_000_000)
+                            && node_ts <= op.timestamp.saturating_add(1_000_000_000)
+                    })
+                    .map(|n| n.timestamp())
+                    .unwrap_or(op.timestamp)
+            };
+
+            // Serialize the operation using serde_json for network transfer
+            let serialized = serde_json::to_vec(&op)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize operation: {}", e))?;
+
+            operations.push(SerializedOperation {
+                data: serialized,
+                genesis_cid: genesis_cid.to_string(),
+                author: op.author.clone(),
+                timestamp: op.timestamp,
+                node_timestamp,
+            });
+        }
+
+        Ok(operations)
+    }
 }
 
 #[async_trait]
@@ -102,6 +817,8 @@ impl ContentRepository for CrslCrdtRepository {
         author: &str,
         access_policy: Option<AccessPolicy>,
     ) -> Result<CommitResult> {
+        self.reserve(data.len() as u64, 0)?;
+
         let placeholder = Self::generate_placeholder_cid(data);
         let payload = ContentPayload {
             data: data.to_vec(),
@@ -116,8 +833,10 @@ impl ContentRepository for CrslCrdtRepository {
 
         let genesis_cid = {
             let mut repo = self.repo.lock();
-            repo.commit_operation(op)
-                .map_err(|e| anyhow::anyhow!("Failed to commit create operation: {}", e))?
+            repo.commit_operation(op).map_err(|e| {
+                self.release(data.len() as u64);
+                anyhow::anyhow!("Failed to commit create operation: {}", e)
+            })?
         };
 
         Ok(CommitResult {
@@ -135,6 +854,7 @@ impl ContentRepository for CrslCrdtRepository {
         access_policy: Option<AccessPolicy>,
     ) -> Result<CommitResult> {
         let genesis = Self::parse_cid(genesis_cid)?;
+        self.reserve(0, data.len() as u64)?;
 
         // If no access_policy provided, preserve the existing one from the latest version
         let policy = if access_policy.is_some() {
@@ -160,8 +880,10 @@ impl ContentRepository for CrslCrdtRepository {
 
         let version_cid = {
             let mut repo = self.repo.lock();
-            repo.commit_operation(op)
-                .map_err(|e| anyhow::anyhow!("Failed to commit update operation: {}", e))?
+            repo.commit_operation(op).map_err(|e| {
+                self.release(data.len() as u64);
+                anyhow::anyhow!("Failed to commit update operation: {}", e)
+            })?
         };
 
         Ok(CommitResult {
@@ -172,46 +894,20 @@ impl ContentRepository for CrslCrdtRepository {
     }
 
     async fn get_latest(&self, genesis_cid: &str) -> Result<Option<Vec<u8>>> {
-        let genesis = Self::parse_cid(genesis_cid)?;
-
-        let repo = self.repo.lock();
-
-        // Get the latest version CID
-        match repo.latest(&genesis) {
-            Some(latest_cid) => {
-                // Get the node to retrieve payload (data part only)
-                match repo.dag.get_node(&latest_cid) {
-                    Ok(Some(node)) => Ok(Some(node.payload().data.clone())),
-                    Ok(None) => Ok(None),
-                    Err(e) => Err(anyhow::anyhow!("Failed to get node: {}", e)),
-                }
-            }
-            None => Ok(None),
+        if self.is_hidden(genesis_cid).await? {
+            return Ok(None);
         }
+        Ok(self.read_latest(genesis_cid)?.map(|(data, _)| data))
     }
 
     async fn get_latest_with_version(
         &self,
         genesis_cid: &str,
     ) -> Result<Option<(Vec<u8>, String)>> {
-        let genesis = Self::parse_cid(genesis_cid)?;
-
-        let repo = self.repo.lock();
-
-        // Get the latest version CID
-        match repo.latest(&genesis) {
-            Some(latest_cid) => {
-                // Get the node to retrieve payload (data part only)
-                match repo.dag.get_node(&latest_cid) {
-                    Ok(Some(node)) => {
-                        Ok(Some((node.payload().data.clone(), latest_cid.to_string())))
-                    }
-                    Ok(None) => Ok(None),
-                    Err(e) => Err(anyhow::anyhow!("Failed to get node: {}", e)),
-                }
-            }
-            None => Ok(None),
+        if self.is_hidden(genesis_cid).await? {
+            return Ok(None);
         }
+        self.read_latest(genesis_cid)
     }
 
     async fn get_version(&self, version_cid: &str) -> Result<Option<Vec<u8>>> {
@@ -226,6 +922,55 @@ impl ContentRepository for CrslCrdtRepository {
         }
     }
 
+    async fn get_at_version(&self, genesis_cid: &str, version: u64) -> Result<Option<Vec<u8>>> {
+        if version == 0 || self.is_hidden(genesis_cid).await? {
+            return Ok(None);
+        }
+        let genesis = Self::parse_cid(genesis_cid)?;
+
+        // A compacted log holds the Create operation, the snapshot state and
+        // the versions after it; versions in between were truncated.
+        let version = match self.snapshots.lock().get(genesis_cid) {
+            Some(snapshot) if version > 1 => {
+                if version < snapshot.version {
+                    return Ok(None);
+                }
+                version - snapshot.version + snapshot.operations().len() as u64
+            }
+            _ => version,
+        };
+
+        let repo = self.repo.lock();
+
+        let mut indexed_ops = repo
+            .get_operations_with_index(&genesis)
+            .map_err(|e| anyhow::anyhow!("Failed to get operations: {}", e))?;
+        indexed_ops.sort_by_key(|(idx, _)| *idx);
+
+        // The requested version must exist in the log; otherwise we would
+        // silently return the latest state for out-of-range versions.
+        if !indexed_ops.iter().any(|(idx, _)| *idx as u64 == version) {
+            return Ok(None);
+        }
+
+        // Replay operations (LWW) up to and including the requested version.
+        let mut state: Option<Vec<u8>> = None;
+        for (idx, op) in indexed_ops {
+            if idx as u64 > version {
+                break;
+            }
+            state = match op.kind {
+                OperationType::Create(payload) | OperationType::Update(payload) => {
+                    Some(payload.data)
+                }
+                // Delete (and any other payload-less operation) clears the state.
+                _ => None,
+            };
+        }
+
+        Ok(state)
+    }
+
     async fn get_access_policy(&self, genesis_cid: &str) -> Result<Option<AccessPolicy>> {
         let genesis = Self::parse_cid(genesis_cid)?;
 
@@ -263,6 +1008,9 @@ impl ContentRepository for CrslCrdtRepository {
                 .unwrap_or_default()
         };
 
+        let bytes = current_data.len() as u64;
+        self.reserve(0, bytes)?;
+
         let payload = ContentPayload {
             data: current_data,
             access_policy: Some(policy),
@@ -272,8 +1020,10 @@ impl ContentRepository for CrslCrdtRepository {
 
         let version_cid = {
             let mut repo = self.repo.lock();
-            repo.commit_operation(op)
-                .map_err(|e| anyhow::anyhow!("Failed to commit access policy update: {}", e))?
+            repo.commit_operation(op).map_err(|e| {
+                self.release(bytes);
+                anyhow::anyhow!("Failed to commit access policy update: {}", e)
+            })?
         };
 
         Ok(CommitResult {
@@ -300,146 +1050,124 @@ impl ContentRepository for CrslCrdtRepository {
         genesis_cid: &str,
         since_version: Option<&str>,
     ) -> Result<Vec<SerializedOperation>> {
-        let genesis = Self::parse_cid(genesis_cid)?;
-
-        let repo = self.repo.lock();
-
-        let indexed_ops = repo
-            .get_operations_with_index(&genesis)
-            .map_err(|e| anyhow::anyhow!("Failed to get operations: {}", e))?;
-
-        // Filter by since_version if provided
-        // Find the index of the operation corresponding to since_version
-        let since_index = if let Some(since) = since_version {
-            let since_cid = Self::parse_cid(since)?;
-
-            // If since_version is the genesis CID, skip the first operation (Create)
-            if since_cid == genesis {
-                Some(1) // Skip index 1 (the Create operation)
-            } else {
-                // Find the operation index by matching the DAG node timestamp
-                match repo.dag.get_node(&since_cid) {
-                    Ok(Some(since_node)) => {
-                        let since_ts = since_node.timestamp();
-                        // DAG node timestamps may be in seconds while operation timestamps are in nanoseconds
-                        // Convert to nanoseconds if the timestamp appears to be in seconds
-                        // Use the end of the second to include all operations within that second
-                        let since_ts_nanos = if since_ts < 1_000_000_000_000 {
-                            // Likely in seconds, convert to nanoseconds (end of that second)
-                            since_ts * 1_000_000_000 + 999_999_999
-                        } else {
-                            since_ts
-                        };
-                        // Find the operation with the closest timestamp <= since_ts
-                        indexed_ops
-                            .iter()
-                            .filter(|(_, op)| op.timestamp <= since_ts_nanos)
-                            .map(|(idx, _)| *idx)
-                            .max()
-                    }
-                    Ok(None) => None,
-                    Err(_) => None,
-                }
-            }
-        } else {
-            None
-        };
-
-        // Get all DAG nodes for this genesis to find node timestamps
-        let history = repo
-            .linear_history(&genesis)
-            .map_err(|e| anyhow::anyhow!("Failed to get history: {}", e))?;
-
-        // Build a map of operation timestamp -> DAG node timestamp
-        // This is needed because DAG node timestamps may differ from operation timestamps
-        let mut node_timestamps: Vec<(u64, u64)> = Vec::new();
-        for node_cid in &history {
-            if let Ok(Some(node)) = repo.dag.get_node(node_cid) {
-                node_timestamps.push((node.timestamp(), node.timestamp()));
-            }
-        }
-
-        let mut operations = Vec::new();
-        for (idx, op) in indexed_ops {
-            // Skip operations at or before the since_version index
-            if let Some(since_idx) = since_index {
-                if idx <= since_idx {
-                    continue;
-                }
-            }
-
-            // Find the corresponding DAG node timestamp
-            // For Create operations, use the genesis node timestamp
-            // For other operations, find the node with matching or closest timestamp
-            let node_timestamp = if matches!(op.kind, OperationType::Create(_)) {
-                // For Create operation, get the genesis node timestamp
-                repo.dag
-                    .get_node(&genesis)
-                    .ok()
-                    .flatten()
-                    .map(|n| n.timestamp())
-                    .unwrap_or(op.timestamp)
-            } else {
-                // For Update/Delete/Merge, find the node in history that corresponds to this operation
-                // The node timestamp should be close to the operation timestamp
-                history
-                    .iter()
-                    .filter_map(|cid| repo.dag.get_node(cid).ok().flatten())
-                    .find(|node| {
-                        // Node timestamp should be within a reasonable range of op timestamp
-                        // or we just find the closest one
-                        let node_ts = node.timestamp();
-                        // Allow some tolerance for timestamp matching
-                        node_ts >= op.timestamp.saturating_sub(1_000_000_000)
-                            && node_ts <= op.timestamp.saturating_add(1_000_000_000)
-                    })
-                    .map(|n| n.timestamp())
-                    .unwrap_or(op.timestamp)
-            };
-
-            // Serialize the operation using serde_json for network transfer
-            let serialized = serde_json::to_vec(&op)
-                .map_err(|e| anyhow::anyhow!("Failed to serialize operation: {}", e))?;
-
-            operations.push(SerializedOperation {
-                data: serialized,
-                genesis_cid: genesis_cid.to_string(),
-                author: op.author.clone(),
-                timestamp: op.timestamp,
-                node_timestamp,
-            });
+        if self.is_hidden(genesis_cid).await? {
+            return Ok(Vec::new());
         }
+        let repo = self.repo.lock();
+        Self::serialize_operations(&repo, genesis_cid, since_version)
+    }
 
-        Ok(operations)
+    async fn get_operations_with_snapshot(
+        &self,
+        genesis_cid: &str,
+        since_version: Option<&str>,
+    ) -> Result<(Option<ContentSnapshot>, Vec<SerializedOperation>)> {
+        if self.is_hidden(genesis_cid).await? {
+            return Ok((None, Vec::new()));
+        }
+        let ops = {
+            let repo = self.repo.lock();
+            Self::serialize_operations(&repo, genesis_cid, since_version)?
+        };
+        let Some(snapshot) = self.snapshots.lock().get(genesis_cid).cloned() else {
+            return Ok((None, ops));
+        };
+
+        // The requester is behind the snapshot if it still needs operations
+        // the snapshot stands in for.
+        let snapshot_ts = snapshot
+            .state_op
+            .as_ref()
+            .unwrap_or(&snapshot.create_op)
+            .timestamp;
+        if !ops.iter().any(|op| op.timestamp <= snapshot_ts) {
+            return Ok((None, ops));
+        }
+        let after = ops
+            .into_iter()
+            .filter(|op| op.timestamp > snapshot_ts)
+            .collect();
+        Ok((Some(snapshot), after))
     }
 
     async fn apply_operations(&self, operations: &[SerializedOperation]) -> Result<usize> {
+        // Resolve denied and evicted genesis CIDs before taking the
+        // (non-async) repo lock.
+        let mut denied = HashSet::new();
+        for serialized_op in operations {
+            if !denied.contains(&serialized_op.genesis_cid)
+                && self.is_hidden(&serialized_op.genesis_cid).await?
+            {
+                denied.insert(serialized_op.genesis_cid.clone());
+            }
+        }
+
+        let mut accepted = Vec::new();
+        for serialized_op in operations {
+            if denied.contains(&serialized_op.genesis_cid) {
+                tracing::warn!(
+                    "Refusing operation for denied or evicted content {}",
+                    serialized_op.genesis_cid
+                );
+                continue;
+            }
+            let bytes = serialized_op.data.len() as u64;
+            accepted.push((
+                &serialized_op.genesis_cid,
+                bytes,
+                Self::decode_operation(serialized_op)?,
+            ));
+        }
+
         let mut applied = 0;
+        // Reserved bytes of operations that were not applied.
+        let mut unused_bytes = 0u64;
 
-        let mut repo = self.repo.lock();
+        {
+            let mut repo = self.repo.lock();
 
-        for serialized_op in operations {
-            // Deserialize the operation
-            let mut op: Operation<Cid, ContentPayload> =
-                serde_json::from_slice(&serialized_op.data)
-                    .map_err(|e| anyhow::anyhow!("Failed to deserialize operation: {}", e))?;
-
-            // Set node_timestamp for import mode to ensure CID consistency across replicas
-            op.node_timestamp = Some(serialized_op.node_timestamp);
-
-            // Apply the operation
-            match repo.commit_operation(op) {
-                Ok(_) => applied += 1,
-                Err(e) => {
-                    // Log but continue - operation might be duplicate or conflict
-                    tracing::warn!("Failed to apply operation: {}", e);
+            // Content not stored yet is a new assignment and counts against
+            // the headroom kept for updates.
+            if self.quota_bytes.is_some() {
+                let mut new_content_bytes = 0u64;
+                let mut update_bytes = 0u64;
+                for (genesis_cid, bytes, _) in &accepted {
+                    let stored = Self::parse_cid(genesis_cid)
+                        .map(|genesis| repo.latest(&genesis).is_some())
+                        .unwrap_or(false);
+                    if stored {
+                        update_bytes += bytes;
+                    } else {
+                        new_content_bytes += bytes;
+                    }
+                }
+                self.reserve(new_content_bytes, update_bytes)?;
+            }
+
+            for (_, bytes, op) in accepted {
+                // Apply the operation
+                match repo.commit_operation(op) {
+                    Ok(_) => applied += 1,
+                    Err(e) => {
+                        // Log but continue - operation might be duplicate or conflict
+                        tracing::warn!("Failed to apply operation: {}", e);
+                        unused_bytes += bytes;
+                    }
                 }
             }
         }
+        self.release(unused_bytes);
 
         Ok(applied)
     }
 
+    async fn is_denied(&self, genesis_cid: &str) -> Result<bool> {
+        match &self.denylist {
+            Some(denylist) => denylist.is_denied(genesis_cid).await,
+            None => Ok(false),
+        }
+    }
+
     async fn exists(&self, genesis_cid: &str) -> Result<bool> {
         let genesis = match Self::parse_cid(genesis_cid) {
             Ok(cid) => cid,
@@ -467,23 +1195,14 @@ impl ContentRepository for CrslCrdtRepository {
 
     async fn list_contents(&self) -> Result<Vec<String>> {
         let repo = self.repo.lock();
+        Ok(Self::genesis_cids(&repo)?.into_iter().collect())
+    }
 
-        // Get all nodes and collect unique genesis CIDs
-        let node_map = repo
-            .dag
-            .storage
-            .get_node_map()
-            .map_err(|e| anyhow::anyhow!("Failed to get node map: {}", e))?;
-
-        let mut genesis_cids = std::collections::HashSet::new();
-        for cid in node_map.keys() {
-            // Try to get the genesis for each node
-            if let Ok(genesis) = repo.get_genesis(cid) {
-                genesis_cids.insert(genesis.to_string());
-            }
-        }
-
-        Ok(genesis_cids.into_iter().collect())
+    fn storage_usage(&self) -> Option<StorageUsage> {
+        self.quota_bytes.map(|quota_bytes| StorageUsage {
+            used_bytes: self.used_bytes.load(Ordering::Relaxed),
+            quota_bytes,
+        })
     }
 
     async fn prepare_create_operations(
@@ -643,6 +1362,53 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_denied_content_is_not_applied_or_served() {
+        use crate::domain::tombstone::Tombstone;
+        use crate::infrastructure::persistence::SledDenylistRepository;
+
+        let creator_tmp = tempdir().unwrap();
+        let creator_repo = CrslCrdtRepository::open(creator_tmp.path().join("crdt")).unwrap();
+        let prepared = creator_repo
+            .prepare_create_operations(b"denied payload", "author-a", None)
+            .await
+            .unwrap();
+
+        let receiver_tmp = tempdir().unwrap();
+        let denylist =
+            Arc::new(SledDenylistRepository::open(receiver_tmp.path().join("denylist")).unwrap());
+        denylist
+            .add_tombstone(&Tombstone::new(
+                prepared.genesis_cid.clone(),
+                "abuse".to_string(),
+            ))
+            .await
+            .unwrap();
+        let receiver_repo = CrslCrdtRepository::open(receiver_tmp.path().join("crdt"))
+            .unwrap()
+            .with_denylist(denylist);
+
+        assert!(receiver_repo
+            .is_denied(&prepared.genesis_cid)
+            .await
+            .unwrap());
+        let applied = receiver_repo
+            .apply_operations(&prepared.operations)
+            .await
+            .unwrap();
+        assert_eq!(applied, 0);
+        assert!(receiver_repo
+            .get_latest_with_version(&prepared.genesis_cid)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(receiver_repo
+            .get_operations(&prepared.genesis_cid, None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_create_and_get_content() {
         let tmp = tempdir().unwrap();
@@ -734,6 +1500,70 @@ mod tests {
         assert_eq!(retrieved, Some(data.to_vec()));
     }
 
+    #[tokio::test]
+    async fn test_get_at_version_replays_operations() {
+        let tmp = tempdir().unwrap();
+        let repo = CrslCrdtRepository::open(tmp.path()).unwrap();
+
+        let result = repo.create_content(b"v1", "author", None).await.unwrap();
+        repo.update_content(&result.genesis_cid, b"v2", "author", None)
+            .await
+            .unwrap();
+        repo.update_content(&result.genesis_cid, b"v3", "author", None)
+            .await
+            .unwrap();
+
+        let genesis = &result.genesis_cid;
+        assert_eq!(
+            repo.get_at_version(genesis, 1).await.unwrap(),
+            Some(b"v1".to_vec())
+        );
+        assert_eq!(
+            repo.get_at_version(genesis, 2).await.unwrap(),
+            Some(b"v2".to_vec())
+        );
+        assert_eq!(
+            repo.get_at_version(genesis, 3).await.unwrap(),
+            Some(b"v3".to_vec())
+        );
+        assert_eq!(repo.get_at_version(genesis, 0).await.unwrap(), None);
+        assert_eq!(repo.get_at_version(genesis, 4).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_at_version_on_replica() {
+        let source_dir = tempdir().unwrap();
+        let source = CrslCrdtRepository::open(source_dir.path()).unwrap();
+        let result = source.create_content(b"v1", "author", None).await.unwrap();
+        source
+            .update_content(&result.genesis_cid, b"v2", "author", None)
+            .await
+            .unwrap();
+
+        let replica_dir = tempdir().unwrap();
+        let replica = CrslCrdtRepository::open(replica_dir.path()).unwrap();
+        let ops = source
+            .get_operations(&result.genesis_cid, None)
+            .await
+            .unwrap();
+        replica.apply_operations(&ops).await.unwrap();
+
+        assert_eq!(
+            replica
+                .get_at_version(&result.genesis_cid, 1)
+                .await
+                .unwrap(),
+            Some(b"v1".to_vec())
+        );
+        assert_eq!(
+            replica
+                .get_at_version(&result.genesis_cid, 2)
+                .await
+                .unwrap(),
+            Some(b"v2".to_vec())
+        );
+    }
+
     #[tokio::test]
     async fn test_list_contents() {
         let tmp = tempdir().unwrap();
@@ -765,4 +1595,403 @@ mod tests {
         assert!(!operations.is_empty());
         assert_eq!(operations[0].genesis_cid, result.genesis_cid);
     }
+
+    #[tokio::test]
+    async fn test_evicted_content_is_hidden() {
+        use crate::domain::eviction::{Eviction, EvictionReason};
+        use crate::infrastructure::persistence::SledEvictionRepository;
+
+        let tmp = tempdir().unwrap();
+        let evictions =
+            Arc::new(SledEvictionRepository::open(tmp.path().join("evictions")).unwrap());
+        let repo = CrslCrdtRepository::open(tmp.path().join("crdt"))
+            .unwrap()
+            .with_evictions(evictions.clone());
+        let result = repo
+            .create_content(b"evicted", "author", None)
+            .await
+            .unwrap();
+
+        evictions
+            .save_eviction(&Eviction::new(
+                result.genesis_cid.clone(),
+                EvictionReason::RemovedFromNetwork,
+                0,
+            ))
+            .await
+            .unwrap();
+        assert!(repo
+            .get_latest(&result.genesis_cid)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repo
+            .get_operations(&result.genesis_cid, None)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Cancelling the eviction makes the retained data visible again.
+        assert!(evictions
+            .cancel_eviction(&result.genesis_cid)
+            .await
+            .unwrap());
+        assert_eq!(
+            repo.get_latest(&result.genesis_cid).await.unwrap(),
+            Some(b"evicted".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_purge_removes_content_and_keeps_others() {
+        let tmp = tempdir().unwrap();
+        let repo = CrslCrdtRepository::open(tmp.path()).unwrap();
+
+        let purged = repo
+            .create_content(b"purged", "author", None)
+            .await
+            .unwrap();
+        let kept = repo
+            .create_content(b"kept v1", "author", None)
+            .await
+            .unwrap();
+        repo.update_content(&kept.genesis_cid, b"kept v2", "author", None)
+            .await
+            .unwrap();
+        let history = repo.get_history(&kept.genesis_cid).await.unwrap();
+
+        // Nothing is removed when the eviction is no longer due.
+        assert!(!repo.purge(&purged.genesis_cid, || false).unwrap());
+        assert!(repo.exists(&purged.genesis_cid).await.unwrap());
+
+        assert!(repo.purge(&purged.genesis_cid, || true).unwrap());
+        assert!(!repo.exists(&purged.genesis_cid).await.unwrap());
+        assert_eq!(repo.get_history(&kept.genesis_cid).await.unwrap(), history);
+        assert!(!tmp.path().join(DEFAULT_DB_NAME).exists());
+        drop(repo);
+
+        // The rebuilt database is picked up on reopen.
+        let repo = CrslCrdtRepository::open(tmp.path()).unwrap();
+        assert_eq!(
+            repo.list_contents().await.unwrap(),
+            vec![kept.genesis_cid.clone()]
+        );
+        assert_eq!(
+            repo.get_latest(&kept.genesis_cid).await.unwrap(),
+            Some(b"kept v2".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_versions_are_served_from_blob_store() {
+        use crate::infrastructure::storage_tiers::{StorageTier, StorageTierConfig};
+
+        let tmp = tempdir().unwrap();
+        let blob_store = Arc::new(
+            TieredBlobStore::open(&tmp.path().join("blobs"), &StorageTierConfig::default())
+                .unwrap(),
+        );
+        let repo = CrslCrdtRepository::open(tmp.path().join("crdt"))
+            .unwrap()
+            .with_blob_store(blob_store.clone());
+
+        let result = repo.create_content(b"v1", "author", None).await.unwrap();
+        let genesis_cid = result.genesis_cid;
+        assert_eq!(
+            repo.get_latest(&genesis_cid).await.unwrap(),
+            Some(b"v1".to_vec())
+        );
+        assert_eq!(blob_store.tier_of(&genesis_cid), Some(StorageTier::Hot));
+
+        // A blob holding a superseded version is replaced on the next read.
+        let update = repo
+            .update_content(&genesis_cid, b"v2", "author", None)
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.get_latest_with_version(&genesis_cid).await.unwrap(),
+            Some((b"v2".to_vec(), update.version_cid.clone()))
+        );
+        assert_eq!(
+            repo.stored_latest(&genesis_cid, &update.version_cid),
+            Some(b"v2".to_vec())
+        );
+
+        assert!(repo.purge(&genesis_cid, || true).unwrap());
+        assert_eq!(blob_store.tier_of(&genesis_cid), None);
+    }
+
+    #[tokio::test]
+    async fn test_compact_truncates_log_and_serves_snapshot() {
+        let tmp = tempdir().unwrap();
+        let repo = CrslCrdtRepository::open(tmp.path()).unwrap();
+
+        let content = repo.create_content(b"v1", "author", None).await.unwrap();
+        for version in 2..=5 {
+            let data = format!("v{}", version);
+            repo.update_content(&content.genesis_cid, data.as_bytes(), "author", None)
+                .await
+                .unwrap();
+        }
+        let idle = repo.create_content(b"idle", "author", None).await.unwrap();
+        let idle_history = repo.get_history(&idle.genesis_cid).await.unwrap();
+
+        let (_, latest_version) = repo
+            .get_latest_with_version(&content.genesis_cid)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(repo.compact(10).unwrap(), 0);
+        assert_eq!(repo.compact(3).unwrap(), 1);
+        assert_eq!(
+            repo.get_operations(&content.genesis_cid, None)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        // Compaction does not change the latest version CID.
+        assert_eq!(
+            repo.get_latest_with_version(&content.genesis_cid)
+                .await
+                .unwrap()
+                .unwrap()
+                .1,
+            latest_version
+        );
+        assert_eq!(
+            repo.get_history(&idle.genesis_cid).await.unwrap(),
+            idle_history
+        );
+        assert_eq!(
+            repo.get_latest(&content.genesis_cid).await.unwrap(),
+            Some(b"v5".to_vec())
+        );
+
+        // Version numbers keep counting across the truncation.
+        let at = |version| repo.get_at_version(&content.genesis_cid, version);
+        assert_eq!(at(1).await.unwrap(), Some(b"v1".to_vec()));
+        assert_eq!(at(3).await.unwrap(), None);
+        assert_eq!(at(5).await.unwrap(), Some(b"v5".to_vec()));
+
+        repo.update_content(&content.genesis_cid, b"v6", "author", None)
+            .await
+            .unwrap();
+        assert_eq!(at(6).await.unwrap(), Some(b"v6".to_vec()));
+        drop(repo);
+
+        // Snapshots survive reopen; a requester with nothing gets the
+        // snapshot and the operation after it.
+        let repo = CrslCrdtRepository::open(tmp.path()).unwrap();
+        let (snapshot, ops) = repo
+            .get_operations_with_snapshot(&content.genesis_cid, None)
+            .await
+            .unwrap();
+        let snapshot = snapshot.unwrap();
+        assert_eq!(snapshot.version, 5);
+        assert_eq!(ops.len(), 1);
+
+        let replica_tmp = tempdir().unwrap();
+        let replica = CrslCrdtRepository::open(replica_tmp.path()).unwrap();
+        let mut received = snapshot.operations();
+        received.extend(ops);
+        replica.apply_operations(&received).await.unwrap();
+        assert_eq!(
+            replica.get_latest(&content.genesis_cid).await.unwrap(),
+            Some(b"v6".to_vec())
+        );
+
+        // A requester that is up to date with the snapshot gets no snapshot.
+        let (snapshot, ops) = repo
+            .get_operations_with_snapshot(&content.genesis_cid, Some(&latest_version))
+            .await
+            .unwrap();
+        assert!(snapshot.is_none());
+        assert_eq!(ops.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_compacted_and_full_replicas_converge() {
+        let compacted_tmp = tempdir().unwrap();
+        let compacted = CrslCrdtRepository::open(compacted_tmp.path()).unwrap();
+        let full_tmp = tempdir().unwrap();
+        let full = CrslCrdtRepository::open(full_tmp.path()).unwrap();
+
+        let content = compacted
+            .create_content(b"v1", "author", None)
+            .await
+            .unwrap();
+        for version in 2..=5 {
+            let data = format!("v{}", version);
+            compacted
+                .update_content(&content.genesis_cid, data.as_bytes(), "author", None)
+                .await
+                .unwrap();
+        }
+        full.apply_operations(
+            &compacted
+                .get_operations(&content.genesis_cid, None)
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        async fn latest_of(repo: &CrslCrdtRepository, genesis_cid: &str) -> (Vec<u8>, String) {
+            repo.get_latest_with_version(genesis_cid)
+                .await
+                .unwrap()
+                .unwrap()
+        }
+        let genesis_cid = content.genesis_cid.as_str();
+        let (_, v5) = latest_of(&full, genesis_cid).await;
+        assert_eq!(compacted.compact(3).unwrap(), 1);
+        assert_eq!(latest_of(&compacted, genesis_cid).await.1, v5);
+
+        // The compacted replica resolves the full replica's version and
+        // sends only the new operation.
+        compacted
+            .update_content(&content.genesis_cid, b"v6", "author", None)
+            .await
+            .unwrap();
+        let (snapshot, ops) = compacted
+            .get_operations_with_snapshot(&content.genesis_cid, Some(&v5))
+            .await
+            .unwrap();
+        assert!(snapshot.is_none());
+        assert_eq!(ops.len(), 1);
+        full.apply_operations(&ops).await.unwrap();
+        assert_eq!(
+            latest_of(&full, genesis_cid).await,
+            latest_of(&compacted, genesis_cid).await
+        );
+
+        // And the other way round.
+        let (_, v6) = latest_of(&compacted, genesis_cid).await;
+        full.update_content(&content.genesis_cid, b"v7", "author", None)
+            .await
+            .unwrap();
+        let ops = full
+            .get_operations(&content.genesis_cid, Some(&v6))
+            .await
+            .unwrap();
+        assert_eq!(ops.len(), 1);
+        compacted.apply_operations(&ops).await.unwrap();
+        assert_eq!(
+            latest_of(&compacted, genesis_cid).await,
+            latest_of(&full, genesis_cid).await
+        );
+        assert_eq!(latest_of(&full, genesis_cid).await.0, b"v7".to_vec());
+
+        // A full replica sent the snapshot ends up on the same version
+        // rather than on a branch of its own.
+        let (snapshot, ops) = compacted
+            .get_operations_with_snapshot(&content.genesis_cid, None)
+            .await
+            .unwrap();
+        let mut received = snapshot.unwrap().operations();
+        received.extend(ops);
+        full.apply_operations(&received).await.unwrap();
+        assert_eq!(
+            latest_of(&full, genesis_cid).await,
+            latest_of(&compacted, genesis_cid).await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quota_refuses_new_content_when_nearly_full() {
+        const QUOTA: u64 = 16 * 1024;
+
+        let creator_tmp = tempdir().unwrap();
+        let creator_repo = CrslCrdtRepository::open(creator_tmp.path()).unwrap();
+        let prepared = creator_repo
+            .prepare_create_operations(&[7u8; QUOTA as usize], "author", None)
+            .await
+            .unwrap();
+
+        let tmp = tempdir().unwrap();
+        let repo = CrslCrdtRepository::open(tmp.path())
+            .unwrap()
+            .with_quota(QUOTA);
+        let existing = repo
+            .create_content(b"existing", "author", None)
+            .await
+            .unwrap();
+        let usage = repo.storage_usage().unwrap();
+        assert_eq!(usage.quota_bytes, QUOTA);
+        assert!(usage.used_bytes > 0 && !usage.is_full());
+
+        // The new content alone exceeds the headroom left for new assignments.
+        let err = repo
+            .apply_operations(&prepared.operations)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Storage quota nearly full"));
+        assert!(!repo.exists(&prepared.genesis_cid).await.unwrap());
+        assert!(repo
+            .create_content(&[1u8; QUOTA as usize], "author", None)
+            .await
+            .is_err());
+
+        // Content already hosted can still be updated.
+        repo.update_content(&existing.genesis_cid, b"updated", "author", None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_writes_reserve_quota_without_rescanning() {
+        const QUOTA: u64 = 64 * 1024;
+
+        let tmp = tempdir().unwrap();
+        let repo = CrslCrdtRepository::open(tmp.path())
+            .unwrap()
+            .with_quota(QUOTA);
+        let measured = repo.storage_usage().unwrap().used_bytes;
+
+        let existing = repo
+            .create_content(&[1u8; 1000], "author", None)
+            .await
+            .unwrap();
+        assert_eq!(repo.storage_usage().unwrap().used_bytes, measured + 1000);
+
+        // A write that would overshoot the quota is refused up front, and
+        // the refused bytes are not counted.
+        let left = QUOTA - measured - 1000;
+        let err = repo
+            .update_content(
+                &existing.genesis_cid,
+                &vec![2u8; left as usize + 1],
+                "author",
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Storage quota exceeded"));
+        assert_eq!(repo.storage_usage().unwrap().used_bytes, measured + 1000);
+
+        repo.update_content(
+            &existing.genesis_cid,
+            &vec![2u8; left as usize],
+            "author",
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(repo.storage_usage().unwrap().is_full());
+
+        // Checking and reserving is one step, so a reserved budget is not
+        // handed out twice.
+        repo.release(100);
+        assert!(repo.reserve(0, 100).is_ok());
+        assert!(repo.reserve(0, 1).is_err());
+    }
+
+    #[test]
+    fn test_db_generation() {
+        assert_eq!(CrslCrdtRepository::db_generation("crdt_db"), Some(0));
+        assert_eq!(CrslCrdtRepository::db_generation("crdt_db.3"), Some(3));
+        assert_eq!(CrslCrdtRepository::db_generation("crdt_db.tmp"), None);
+        assert_eq!(CrslCrdtRepository::db_generation("CURRENT_DB"), None);
+    }
 }
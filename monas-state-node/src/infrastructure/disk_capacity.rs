@@ -3,7 +3,7 @@
 //! Provides cross-platform disk capacity queries with WASM fallback.
 
 use anyhow::Result;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Get disk capacity information for the given path.
 ///
@@ -34,6 +34,65 @@ pub fn get_disk_capacity(_path: &Path) -> Result<(u64, u64)> {
     Ok((0, 0))
 }
 
+/// Get disk capacity aggregated over several storage roots.
+///
+/// Roots on the same filesystem are counted once, so a node whose hot and
+/// cold directories share a disk does not over-report its capacity.
+///
+/// Returns a tuple of (total_capacity, available_capacity) in bytes.
+pub fn get_aggregate_disk_capacity(paths: &[PathBuf]) -> Result<(u64, u64)> {
+    let mut seen = Vec::new();
+    let (mut total, mut available) = (0u64, 0u64);
+    for path in paths {
+        let filesystem = filesystem_id(path)?;
+        if filesystem.is_some() && seen.contains(&filesystem) {
+            continue;
+        }
+        seen.push(filesystem);
+
+        let (t, a) = get_disk_capacity(path)?;
+        total = total.saturating_add(t);
+        available = available.saturating_add(a);
+    }
+    Ok((total, available))
+}
+
+/// Total size in bytes of the files under `path`, recursively.
+///
+/// Returns 0 if `path` does not exist.
+pub fn directory_size(path: &Path) -> Result<u64> {
+    let entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let mut size = 0u64;
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size = size.saturating_add(if metadata.is_dir() {
+            directory_size(&entry.path())?
+        } else {
+            metadata.len()
+        });
+    }
+    Ok(size)
+}
+
+/// Identifier of the filesystem containing `path` (the device ID on Unix).
+#[cfg(unix)]
+fn filesystem_id(path: &Path) -> Result<Option<u64>> {
+    use std::os::unix::fs::MetadataExt;
+
+    Ok(Some(std::fs::metadata(path)?.dev()))
+}
+
+/// Without a device ID every root is counted separately.
+#[cfg(not(unix))]
+fn filesystem_id(_path: &Path) -> Result<Option<u64>> {
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,6 +112,27 @@ mod tests {
         assert!(available <= total);
     }
 
+    #[test]
+    fn test_get_aggregate_disk_capacity_counts_filesystem_once() {
+        let path = PathBuf::from(".");
+        let single = get_disk_capacity(&path).unwrap();
+        let aggregate = get_aggregate_disk_capacity(&[path.clone(), path]).unwrap();
+        // Free space may change between the two queries, so only compare totals.
+        assert_eq!(aggregate.0, single.0);
+        assert_eq!(get_aggregate_disk_capacity(&[]).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn test_directory_size() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a"), [0u8; 100]).unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("sub").join("b"), [0u8; 50]).unwrap();
+
+        assert_eq!(directory_size(dir.path()).unwrap(), 150);
+        assert_eq!(directory_size(&dir.path().join("missing")).unwrap(), 0);
+    }
+
     #[test]
     fn test_get_disk_capacity_root() {
         // Test with root directory
@@ -83,6 +83,7 @@ mod tests {
                 content_id: "cid-1".to_string(),
                 added_node_id: "node-2".to_string(),
                 member_nodes: vec!["node-1".to_string(), "node-2".to_string()],
+                added_by_node_id: "node-1".to_string(),
                 timestamp: current_timestamp(),
             },
         ];
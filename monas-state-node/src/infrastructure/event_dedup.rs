@@ -0,0 +1,164 @@
+//! Deduplication of received domain events.
+//!
+//! Gossipsub only suppresses copies of the same message. An event that is
+//! published again (outbox retries, re-broadcasts by other nodes) arrives as a
+//! new message and would trigger the same sync work again. The seen-cache
+//! remembers a digest of every recently handled event so that the event
+//! handler processes each one at most once.
+
+use crate::domain::events::{Event, SignedEvent};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Default maximum number of remembered events.
+pub const DEFAULT_SEEN_EVENTS_CAPACITY: usize = 10_000;
+
+/// Default time an event is remembered. Copies arriving later are processed
+/// again, which is harmless as event handling is idempotent.
+pub const DEFAULT_SEEN_EVENTS_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// SHA-256 digest identifying an event by its full content.
+pub type EventDigest = [u8; 32];
+
+/// Digest of `event`, computed over the same canonical bytes that are signed.
+pub fn event_digest(event: &Event) -> EventDigest {
+    Sha256::digest(SignedEvent::signing_message(event)).into()
+}
+
+/// Bounded cache of recently seen events.
+///
+/// Entries expire after the TTL; when full, the oldest entry is evicted.
+#[derive(Debug)]
+pub struct SeenEvents {
+    seen: HashMap<EventDigest, Instant>,
+    /// Insertion order, for expiry and eviction.
+    order: VecDeque<(EventDigest, Instant)>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl Default for SeenEvents {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEEN_EVENTS_CAPACITY, DEFAULT_SEEN_EVENTS_TTL)
+    }
+}
+
+impl SeenEvents {
+    /// Create a cache holding at most `capacity` events for `ttl` each.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            seen: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: capacity.max(1),
+            ttl,
+        }
+    }
+
+    /// Record `event` as seen. Returns `false` if it was already seen within
+    /// the TTL, i.e. it should not be processed again.
+    pub fn insert(&mut self, event: &Event) -> bool {
+        self.insert_at(event_digest(event), Instant::now())
+    }
+
+    /// Forget `event` so that a later copy is processed again (e.g. after
+    /// handling it failed).
+    pub fn remove(&mut self, event: &Event) {
+        // The stale `order` entry is skipped when it is popped.
+        self.seen.remove(&event_digest(event));
+    }
+
+    /// Number of remembered events.
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Whether no events are remembered.
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    fn insert_at(&mut self, digest: EventDigest, now: Instant) -> bool {
+        self.expire(now);
+        if self.seen.contains_key(&digest) {
+            return false;
+        }
+
+        while self.seen.len() >= self.capacity {
+            match self.order.pop_front() {
+                Some((oldest, inserted_at)) => self.remove_entry(&oldest, inserted_at),
+                None => break,
+            }
+        }
+        self.seen.insert(digest, now);
+        self.order.push_back((digest, now));
+        true
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while let Some(&(digest, inserted_at)) = self.order.front() {
+            if now.duration_since(inserted_at) < self.ttl {
+                break;
+            }
+            self.order.pop_front();
+            self.remove_entry(&digest, inserted_at);
+        }
+    }
+
+    /// Remove `digest` unless it was removed and inserted again since.
+    fn remove_entry(&mut self, digest: &EventDigest, inserted_at: Instant) {
+        if self.seen.get(digest) == Some(&inserted_at) {
+            self.seen.remove(digest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content_updated(timestamp: u64) -> Event {
+        Event::ContentUpdated {
+            content_id: "cid-1".to_string(),
+            updated_node_id: "node-2".to_string(),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_event_is_rejected_until_expired() {
+        let mut seen = SeenEvents::new(10, Duration::from_secs(60));
+        let start = Instant::now();
+        let digest = event_digest(&content_updated(1));
+
+        assert!(seen.insert_at(digest, start));
+        assert!(!seen.insert_at(digest, start + Duration::from_secs(59)));
+        assert!(seen.insert_at(event_digest(&content_updated(2)), start));
+        assert!(seen.insert_at(digest, start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_oldest_event_is_evicted_when_full() {
+        let mut seen = SeenEvents::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+        let first = event_digest(&content_updated(1));
+
+        assert!(seen.insert_at(first, now));
+        assert!(seen.insert_at(event_digest(&content_updated(2)), now));
+        assert!(seen.insert_at(event_digest(&content_updated(3)), now));
+        assert_eq!(seen.len(), 2);
+        assert!(seen.insert_at(first, now));
+    }
+
+    #[test]
+    fn test_removed_event_is_processed_again() {
+        let mut seen = SeenEvents::default();
+        let event = content_updated(1);
+
+        assert!(seen.insert(&event));
+        seen.remove(&event);
+        assert!(seen.is_empty());
+        assert!(seen.insert(&event));
+        assert!(!seen.insert(&event));
+    }
+}
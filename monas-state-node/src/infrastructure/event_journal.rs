@@ -0,0 +1,358 @@
+//! Event Journal - Write-ahead log of received network events.
+//!
+//! Events received via gossip are journaled before they are handed to
+//! `StateNodeService::handle_sync_event` and marked applied afterwards. If
+//! the node stops between receipt and the repository write, the events still
+//! pending in the journal are replayed on the next start.
+//!
+//! Applied events are remembered by digest so that a copy arriving again
+//! (after a restart the in-memory seen-cache is empty) is not applied twice.
+//! Events that fail to apply stay pending and are retried, up to
+//! [`MAX_APPLY_ATTEMPTS`] times.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::domain::events::Event;
+use crate::infrastructure::event_dedup::event_digest;
+
+/// Default time applied event digests are kept for deduplication.
+pub const DEFAULT_APPLIED_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Number of failed attempts after which a pending event is dropped.
+pub const MAX_APPLY_ATTEMPTS: u32 = 10;
+
+/// An event received but not yet applied.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Position of the entry in the journal.
+    pub seq: u64,
+    /// Unix timestamp (milliseconds) at which the event was received.
+    pub received_at: u64,
+    /// PeerID of the peer the event was received from.
+    pub source_peer_id: Option<String>,
+    /// The received event.
+    pub event: Event,
+    /// Number of times applying the event failed.
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+/// Sled-backed write-ahead log of received events.
+pub struct SledEventJournal {
+    db: Arc<sled::Db>,
+    /// Pending entries keyed by big-endian sequence number.
+    pending_tree: sled::Tree,
+    /// Applied event digests mapped to the time (ms) they were applied.
+    applied_tree: sled::Tree,
+}
+
+impl SledEventJournal {
+    /// Open or create an event journal at the given path.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let db = Arc::new(sled::open(path.as_ref()).context("Failed to open event journal")?);
+        let pending_tree = db
+            .open_tree("pending")
+            .context("Failed to open pending tree")?;
+        let applied_tree = db
+            .open_tree("applied")
+            .context("Failed to open applied tree")?;
+
+        Ok(Self {
+            db,
+            pending_tree,
+            applied_tree,
+        })
+    }
+
+    /// Get current timestamp in milliseconds.
+    fn current_timestamp() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+
+    /// Whether `event` has already been applied.
+    pub fn is_applied(&self, event: &Event) -> Result<bool> {
+        self.applied_tree
+            .contains_key(event_digest(event))
+            .context("Failed to check applied status")
+    }
+
+    /// Durably journal `event` received from `source_peer_id` before it is
+    /// applied.
+    ///
+    /// Returns the sequence number of the entry, or `None` if the event has
+    /// already been applied and should be skipped.
+    pub async fn append(&self, event: &Event, source_peer_id: Option<&str>) -> Result<Option<u64>> {
+        if self.is_applied(event)? {
+            return Ok(None);
+        }
+
+        let seq = self
+            .db
+            .generate_id()
+            .context("Failed to allocate journal sequence")?;
+        let entry = JournalEntry {
+            seq,
+            received_at: Self::current_timestamp(),
+            source_peer_id: source_peer_id.map(str::to_string),
+            event: event.clone(),
+            attempts: 0,
+        };
+        let serialized = serde_json::to_vec(&entry).context("Failed to serialize journal entry")?;
+
+        self.pending_tree
+            .insert(seq.to_be_bytes(), serialized)
+            .context("Failed to journal event")?;
+        self.flush_async().await?;
+
+        Ok(Some(seq))
+    }
+
+    /// Mark the entry `seq` holding `event` as applied.
+    pub async fn mark_applied(&self, seq: u64, event: &Event) -> Result<()> {
+        self.applied_tree
+            .insert(
+                event_digest(event),
+                &Self::current_timestamp().to_be_bytes(),
+            )
+            .context("Failed to mark event as applied")?;
+        self.pending_tree
+            .remove(seq.to_be_bytes())
+            .context("Failed to remove journal entry")?;
+        self.flush_async().await
+    }
+
+    /// Record that applying the entry `seq` failed. The entry stays pending
+    /// for a later retry until it has failed [`MAX_APPLY_ATTEMPTS`] times,
+    /// when it is dropped so that only a later copy of the event applies it.
+    ///
+    /// Returns whether the entry is kept.
+    pub fn record_failure(&self, seq: u64) -> Result<bool> {
+        let key = seq.to_be_bytes();
+        let Some(value) = self
+            .pending_tree
+            .get(key)
+            .context("Failed to read journal entry")?
+        else {
+            return Ok(false);
+        };
+        let mut entry: JournalEntry =
+            serde_json::from_slice(&value).context("Failed to deserialize journal entry")?;
+        entry.attempts += 1;
+        if entry.attempts >= MAX_APPLY_ATTEMPTS {
+            self.pending_tree
+                .remove(key)
+                .context("Failed to remove journal entry")?;
+            return Ok(false);
+        }
+        let serialized = serde_json::to_vec(&entry).context("Failed to serialize journal entry")?;
+        self.pending_tree
+            .insert(key, serialized)
+            .context("Failed to update journal entry")?;
+        Ok(true)
+    }
+
+    /// Entries not yet applied, in the order they were received.
+    ///
+    /// Entries whose event was applied via another copy are dropped.
+    pub fn pending(&self) -> Result<Vec<JournalEntry>> {
+        let mut entries = Vec::new();
+        let mut stale = Vec::new();
+
+        for result in self.pending_tree.iter() {
+            let (key, value) = result.context("Failed to iterate journal entries")?;
+            let entry: JournalEntry =
+                serde_json::from_slice(&value).context("Failed to deserialize journal entry")?;
+            if self.is_applied(&entry.event)? {
+                stale.push(key);
+            } else {
+                entries.push(entry);
+            }
+        }
+
+        for key in stale {
+            self.pending_tree
+                .remove(key)
+                .context("Failed to remove journal entry")?;
+        }
+
+        Ok(entries)
+    }
+
+    /// Forget applied events older than `max_age`.
+    ///
+    /// Returns the number of digests removed.
+    pub fn cleanup_applied(&self, max_age: Duration) -> Result<usize> {
+        let now = Self::current_timestamp();
+        let max_age_ms = max_age.as_millis() as u64;
+
+        let mut to_remove = Vec::new();
+        for result in self.applied_tree.iter() {
+            let (key, value) = result.context("Failed to iterate applied events")?;
+            let applied_at = value
+                .as_ref()
+                .try_into()
+                .map(u64::from_be_bytes)
+                .unwrap_or(0);
+            if now.saturating_sub(applied_at) >= max_age_ms {
+                to_remove.push(key);
+            }
+        }
+
+        let removed = to_remove.len();
+        for key in to_remove {
+            self.applied_tree
+                .remove(key)
+                .context("Failed to remove applied event")?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Number of entries not yet applied.
+    pub fn pending_count(&self) -> usize {
+        self.pending_tree.len()
+    }
+
+    /// Number of applied events remembered.
+    pub fn applied_count(&self) -> usize {
+        self.applied_tree.len()
+    }
+
+    /// Flush all pending writes to disk.
+    pub fn flush(&self) -> Result<()> {
+        self.db.flush().context("Failed to flush event journal")?;
+        Ok(())
+    }
+
+    /// Flush all pending writes to disk without blocking the executor.
+    pub async fn flush_async(&self) -> Result<()> {
+        self.db
+            .flush_async()
+            .await
+            .context("Failed to flush event journal")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn content_updated(timestamp: u64) -> Event {
+        Event::ContentUpdated {
+            content_id: "cid-1".to_string(),
+            updated_node_id: "node-2".to_string(),
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unapplied_events_are_replayed_after_reopen() {
+        let tmp = tempdir().unwrap();
+        {
+            let journal = SledEventJournal::open(tmp.path()).unwrap();
+            let first = journal
+                .append(&content_updated(1), Some("peer-1"))
+                .await
+                .unwrap()
+                .unwrap();
+            journal
+                .append(&content_updated(2), None)
+                .await
+                .unwrap()
+                .unwrap();
+            journal
+                .mark_applied(first, &content_updated(1))
+                .await
+                .unwrap();
+        }
+
+        let journal = SledEventJournal::open(tmp.path()).unwrap();
+        let pending = journal.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].event, content_updated(2));
+        assert_eq!(pending[0].source_peer_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_applied_event_is_not_journaled_again() {
+        let tmp = tempdir().unwrap();
+        let journal = SledEventJournal::open(tmp.path()).unwrap();
+
+        let seq = journal
+            .append(&content_updated(1), None)
+            .await
+            .unwrap()
+            .unwrap();
+        // A second copy received before the first was applied.
+        journal
+            .append(&content_updated(1), None)
+            .await
+            .unwrap()
+            .unwrap();
+        journal
+            .mark_applied(seq, &content_updated(1))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            journal.append(&content_updated(1), None).await.unwrap(),
+            None
+        );
+        assert!(journal.pending().unwrap().is_empty());
+        assert_eq!(journal.pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_event_is_retried_until_attempts_run_out() {
+        let tmp = tempdir().unwrap();
+        let journal = SledEventJournal::open(tmp.path()).unwrap();
+
+        let failed = journal
+            .append(&content_updated(1), None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(journal.record_failure(failed).unwrap());
+        let pending = journal.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].attempts, 1);
+
+        for _ in 1..MAX_APPLY_ATTEMPTS - 1 {
+            assert!(journal.record_failure(failed).unwrap());
+        }
+        assert!(!journal.record_failure(failed).unwrap());
+        assert!(journal.pending().unwrap().is_empty());
+        assert!(!journal.is_applied(&content_updated(1)).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_applied() {
+        let tmp = tempdir().unwrap();
+        let journal = SledEventJournal::open(tmp.path()).unwrap();
+
+        let seq = journal
+            .append(&content_updated(2), None)
+            .await
+            .unwrap()
+            .unwrap();
+        journal
+            .mark_applied(seq, &content_updated(2))
+            .await
+            .unwrap();
+        assert_eq!(
+            journal.cleanup_applied(DEFAULT_APPLIED_RETENTION).unwrap(),
+            0
+        );
+        assert_eq!(journal.cleanup_applied(Duration::from_secs(0)).unwrap(), 1);
+        assert_eq!(journal.applied_count(), 0);
+    }
+}
@@ -0,0 +1,209 @@
+//! Event Log - Recording of received network events for deterministic replay.
+//!
+//! The recorder appends every event handed to
+//! `StateNodeService::handle_sync_event` as one JSON object per line
+//! (JSON Lines), together with the PeerID it was received from. A captured
+//! session can then be replayed through a fresh service in tests to check
+//! that event handling still converges to the same registry and
+//! content-network state.
+//!
+//! Recording is enabled with the `EVENT_LOG_PATH` environment variable.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::domain::events::Event;
+
+/// A single event as received from the network.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Position of the event in the recorded session (starting at 0).
+    #[serde(default)]
+    pub seq: u64,
+    /// Unix timestamp (milliseconds) at which the event was received.
+    #[serde(default)]
+    pub received_at: u64,
+    /// PeerID of the peer the event was received from.
+    #[serde(default)]
+    pub source_peer_id: Option<String>,
+    /// The received event.
+    pub event: Event,
+}
+
+/// Appends received events to a JSON Lines file.
+pub struct EventLogRecorder {
+    file: Mutex<File>,
+    next_seq: Mutex<u64>,
+}
+
+impl EventLogRecorder {
+    /// Open the log at `path`, appending to any previous session.
+    ///
+    /// Sequence numbers continue after the last entry already in the log.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open event log {}", path.display()))?;
+
+        let mut existing = String::new();
+        file.read_to_string(&mut existing)
+            .with_context(|| format!("Failed to read event log {}", path.display()))?;
+        // A line cut short by a crash is skipped, and terminated so that the
+        // next entry starts on a line of its own.
+        let next_seq = existing
+            .lines()
+            .rev()
+            .find_map(|line| serde_json::from_str::<RecordedEvent>(line).ok())
+            .map_or(0, |last| last.seq + 1);
+        if !existing.is_empty() && !existing.ends_with('\n') {
+            file.write_all(b"\n").context("Failed to write event log")?;
+        }
+
+        Ok(Self {
+            file: Mutex::new(file),
+            next_seq: Mutex::new(next_seq),
+        })
+    }
+
+    /// Append `event` received from `source_peer_id` to the log.
+    pub fn record(&self, event: &Event, source_peer_id: Option<&str>) -> Result<()> {
+        let mut seq = self.next_seq.lock().unwrap();
+        let record = RecordedEvent {
+            seq: *seq,
+            received_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            source_peer_id: source_peer_id.map(str::to_string),
+            event: event.clone(),
+        };
+        let mut line = serde_json::to_vec(&record).context("Failed to serialize event")?;
+        line.push(b'\n');
+
+        let mut file = self.file.lock().unwrap();
+        file.write_all(&line)
+            .and_then(|_| file.flush())
+            .context("Failed to write event log")?;
+        *seq += 1;
+        Ok(())
+    }
+}
+
+/// Parse a JSON Lines event log.
+///
+/// Blank lines and lines starting with `#` are skipped so that fixtures can
+/// be annotated by hand.
+pub fn parse_event_log(input: &str) -> Result<Vec<RecordedEvent>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid event log entry on line {}", i + 1))
+        })
+        .collect()
+}
+
+/// Read a JSON Lines event log from `path`.
+pub fn read_event_log<P: AsRef<Path>>(path: P) -> Result<Vec<RecordedEvent>> {
+    let path = path.as_ref();
+    let input = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read event log {}", path.display()))?;
+    parse_event_log(&input).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn node_created(node_id: &str) -> Event {
+        Event::NodeCreated {
+            node_id: node_id.to_string(),
+            total_capacity: 1000,
+            available_capacity: 500,
+            timestamp: 1,
+        }
+    }
+
+    #[test]
+    fn test_recorder_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("logs").join("session.jsonl");
+
+        let recorder = EventLogRecorder::open(&path).unwrap();
+        recorder
+            .record(&node_created("node-2"), Some("peer-2"))
+            .unwrap();
+        recorder.record(&node_created("node-3"), None).unwrap();
+
+        let events = read_event_log(&path).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].seq, 0);
+        assert_eq!(events[0].source_peer_id.as_deref(), Some("peer-2"));
+        assert_eq!(events[0].event, node_created("node-2"));
+        assert_eq!(events[1].seq, 1);
+        assert!(events[1].source_peer_id.is_none());
+    }
+
+    #[test]
+    fn test_reopened_recorder_continues_sequence() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("session.jsonl");
+
+        let recorder = EventLogRecorder::open(&path).unwrap();
+        recorder.record(&node_created("node-2"), None).unwrap();
+        recorder.record(&node_created("node-3"), None).unwrap();
+        drop(recorder);
+        // Simulate a crash in the middle of writing an entry.
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"seq\":2,\"eve")
+            .unwrap();
+
+        let recorder = EventLogRecorder::open(&path).unwrap();
+        recorder.record(&node_created("node-4"), None).unwrap();
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let events: Vec<RecordedEvent> = log
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let seqs: Vec<u64> = events.iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![0, 1, 2]);
+        assert_eq!(events[2].event, node_created("node-4"));
+    }
+
+    #[test]
+    fn test_parse_skips_comments_and_reports_bad_lines() {
+        let log = format!(
+            "# hand-written fixture\n\n{}\n",
+            serde_json::json!({ "event": node_created("node-2") })
+        );
+        let events = parse_event_log(&log).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].seq, 0);
+
+        let err = parse_event_log("# header\n{not json}\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+}
@@ -0,0 +1,244 @@
+//! Signing and verification of domain events with the node identity key.
+//!
+//! Events are signed with the same Ed25519 key that defines the node's
+//! PeerId, so verifying an event also proves which node published it.
+
+use crate::domain::events::{Event, SignedEvent};
+use crate::port::content_repository::SerializedOperation;
+use libp2p::identity::{Keypair, PublicKey, SigningError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Error type for event signature failures.
+#[derive(Debug, Error)]
+pub enum EventSignatureError {
+    #[error("Invalid signer public key: {0}")]
+    InvalidPublicKey(String),
+
+    #[error("Invalid event signature")]
+    InvalidSignature,
+
+    #[error("Event claims node {claimed} but was signed by {signer}")]
+    AuthorMismatch { claimed: String, signer: String },
+}
+
+/// Sign `event` with the node identity keypair.
+pub fn sign_event(event: Event, keypair: &Keypair) -> Result<SignedEvent, SigningError> {
+    let signature = keypair.sign(&SignedEvent::signing_message(&event))?;
+    Ok(SignedEvent {
+        event,
+        public_key: keypair.public().encode_protobuf(),
+        signature,
+    })
+}
+
+/// Verify the signature of `signed` and that the signer is the event's
+/// claimed author.
+///
+/// Returns the signer's node ID (PeerId) on success.
+pub fn verify_signed_event(signed: &SignedEvent) -> Result<String, EventSignatureError> {
+    let public_key = PublicKey::try_decode_protobuf(&signed.public_key)
+        .map_err(|e| EventSignatureError::InvalidPublicKey(e.to_string()))?;
+    if !public_key.verify(
+        &SignedEvent::signing_message(&signed.event),
+        &signed.signature,
+    ) {
+        return Err(EventSignatureError::InvalidSignature);
+    }
+
+    let signer = public_key.to_peer_id().to_string();
+    let claimed = signed.event.author_node_id();
+    if claimed != signer {
+        return Err(EventSignatureError::AuthorMismatch {
+            claimed: claimed.to_string(),
+            signer,
+        });
+    }
+    Ok(signer)
+}
+
+/// Type tag of a CRDT operation broadcast on an events topic.
+pub const CRDT_OPERATION_TYPE: &str = "crdt_operation";
+
+/// A CRDT operation broadcast on an events topic, signed with the
+/// broadcasting node's identity key like [`SignedEvent`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedOperationBroadcast {
+    /// Always [`CRDT_OPERATION_TYPE`].
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub genesis_cid: String,
+    pub operation: SerializedOperation,
+    /// The signer's libp2p public key (protobuf encoding).
+    pub public_key: Vec<u8>,
+    /// Signature over [`Self::signing_message`].
+    pub signature: Vec<u8>,
+}
+
+impl SignedOperationBroadcast {
+    /// Get the message to sign for a broadcast of `operation`.
+    pub fn signing_message(genesis_cid: &str, operation: &SerializedOperation) -> Vec<u8> {
+        let mut message = b"monas-crdt-operation:".to_vec();
+        // A struct of strings, bytes and integers always serializes.
+        message.extend(serde_json::to_vec(&(genesis_cid, operation)).unwrap_or_default());
+        message
+    }
+}
+
+/// Sign a broadcast of `operation` with the node identity keypair.
+pub fn sign_operation_broadcast(
+    genesis_cid: &str,
+    operation: &SerializedOperation,
+    keypair: &Keypair,
+) -> Result<SignedOperationBroadcast, SigningError> {
+    let signature = keypair.sign(&SignedOperationBroadcast::signing_message(
+        genesis_cid,
+        operation,
+    ))?;
+    Ok(SignedOperationBroadcast {
+        kind: CRDT_OPERATION_TYPE.to_string(),
+        genesis_cid: genesis_cid.to_string(),
+        operation: operation.clone(),
+        public_key: keypair.public().encode_protobuf(),
+        signature,
+    })
+}
+
+/// Verify the signature of `signed` and that the signer is the operation's
+/// author and the operation belongs to the announced content.
+///
+/// Returns the signer's node ID (PeerId) on success.
+pub fn verify_operation_broadcast(
+    signed: &SignedOperationBroadcast,
+) -> Result<String, EventSignatureError> {
+    let public_key = PublicKey::try_decode_protobuf(&signed.public_key)
+        .map_err(|e| EventSignatureError::InvalidPublicKey(e.to_string()))?;
+    let message = SignedOperationBroadcast::signing_message(&signed.genesis_cid, &signed.operation);
+    if signed.operation.genesis_cid != signed.genesis_cid
+        || !public_key.verify(&message, &signed.signature)
+    {
+        return Err(EventSignatureError::InvalidSignature);
+    }
+
+    let signer = public_key.to_peer_id().to_string();
+    if signed.operation.author != signer {
+        return Err(EventSignatureError::AuthorMismatch {
+            claimed: signed.operation.author.clone(),
+            signer,
+        });
+    }
+    Ok(signer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content_updated(node_id: &str) -> Event {
+        Event::ContentUpdated {
+            content_id: "cid-1".to_string(),
+            updated_node_id: node_id.to_string(),
+            timestamp: 12345,
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify_event() {
+        let keypair = Keypair::generate_ed25519();
+        let peer_id = keypair.public().to_peer_id().to_string();
+
+        let signed = sign_event(content_updated(&peer_id), &keypair).unwrap();
+        assert_eq!(verify_signed_event(&signed).unwrap(), peer_id);
+    }
+
+    #[test]
+    fn test_tampered_event_is_rejected() {
+        let keypair = Keypair::generate_ed25519();
+        let peer_id = keypair.public().to_peer_id().to_string();
+
+        let mut signed = sign_event(content_updated(&peer_id), &keypair).unwrap();
+        signed.event = Event::ContentUpdated {
+            content_id: "cid-2".to_string(),
+            updated_node_id: peer_id,
+            timestamp: 12345,
+        };
+        assert!(matches!(
+            verify_signed_event(&signed),
+            Err(EventSignatureError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_event_signed_by_other_node_is_rejected() {
+        let keypair = Keypair::generate_ed25519();
+        let victim = Keypair::generate_ed25519()
+            .public()
+            .to_peer_id()
+            .to_string();
+
+        let signed = sign_event(content_updated(&victim), &keypair).unwrap();
+        assert!(matches!(
+            verify_signed_event(&signed),
+            Err(EventSignatureError::AuthorMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_membership_change_must_be_signed_by_its_author() {
+        let keypair = Keypair::generate_ed25519();
+        let peer_id = keypair.public().to_peer_id().to_string();
+        let removed = |removed_by_node_id: &str| Event::ContentNetworkManagerRemoved {
+            content_id: "cid-1".to_string(),
+            removed_node_id: "node-2".to_string(),
+            member_nodes: vec![peer_id.clone()],
+            reason: "offline".to_string(),
+            removed_by_node_id: removed_by_node_id.to_string(),
+            timestamp: 12345,
+        };
+
+        let signed = sign_event(removed(&peer_id), &keypair).unwrap();
+        assert_eq!(verify_signed_event(&signed).unwrap(), peer_id);
+
+        // Events recorded before the author field default to no author.
+        for author in ["", "node-1"] {
+            let forged = sign_event(removed(author), &keypair).unwrap();
+            assert!(matches!(
+                verify_signed_event(&forged),
+                Err(EventSignatureError::AuthorMismatch { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_operation_broadcast_is_signed_by_its_author() {
+        let keypair = Keypair::generate_ed25519();
+        let peer_id = keypair.public().to_peer_id().to_string();
+        let operation = SerializedOperation {
+            data: b"op".to_vec(),
+            genesis_cid: "cid-1".to_string(),
+            author: peer_id.clone(),
+            timestamp: 1,
+            node_timestamp: 1,
+        };
+
+        let signed = sign_operation_broadcast("cid-1", &operation, &keypair).unwrap();
+        assert_eq!(verify_operation_broadcast(&signed).unwrap(), peer_id);
+
+        let mut tampered = signed.clone();
+        tampered.operation.data = b"other".to_vec();
+        assert!(matches!(
+            verify_operation_broadcast(&tampered),
+            Err(EventSignatureError::InvalidSignature)
+        ));
+
+        let foreign = SerializedOperation {
+            author: "node-1".to_string(),
+            ..operation
+        };
+        let forged = sign_operation_broadcast("cid-1", &foreign, &keypair).unwrap();
+        assert!(matches!(
+            verify_operation_broadcast(&forged),
+            Err(EventSignatureError::AuthorMismatch { .. })
+        ));
+    }
+}
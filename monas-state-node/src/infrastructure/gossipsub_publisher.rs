@@ -3,19 +3,203 @@
 //! This module provides an EventPublisher implementation that:
 //! - Publishes events locally via monas-event-manager EventBus
 //! - Publishes events to the P2P network via libp2p Gossipsub
+//!
+//! Network publications that fail (e.g. while no peers are connected) are
+//! kept in a bounded publish queue and retried by `retry_queued`, so events
+//! are not lost while the node is offline. Events for the same content are
+//! always published in the order they were submitted.
 
 use crate::domain::events::Event;
 use crate::port::event_publisher::EventPublisher;
 use crate::port::peer_network::PeerNetwork;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use futures::FutureExt;
 use monas_event_manager::{make_subscriber, EventBus};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 
 /// Default Gossipsub topic for state node events.
 pub const DEFAULT_EVENT_TOPIC: &str = "monas-events";
 
+/// Default maximum number of events held in the publish queue.
+pub const DEFAULT_PUBLISH_QUEUE_CAPACITY: usize = 10_000;
+
+/// An event waiting to be published to the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QueuedPublication {
+    /// Content the event belongs to (ordering key).
+    content_id: Option<String>,
+    /// Serialized event.
+    data: Vec<u8>,
+}
+
+#[derive(Default)]
+struct PublishQueueState {
+    entries: BTreeMap<u64, QueuedPublication>,
+    next_seq: u64,
+    enqueued_total: u64,
+    published_total: u64,
+    rejected_total: u64,
+}
+
+/// Snapshot of the publish queue counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PublishQueueMetrics {
+    /// Number of events currently waiting to be published.
+    pub depth: usize,
+    /// Maximum number of events the queue can hold.
+    pub capacity: usize,
+    /// Events queued since startup.
+    pub enqueued_total: u64,
+    /// Queued events published by a retry since startup.
+    pub published_total: u64,
+    /// Events rejected because the queue was full.
+    pub rejected_total: u64,
+}
+
+/// Result of a publish queue retry pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PublishQueueRetryResult {
+    /// Number of queued events published.
+    pub published: usize,
+    /// Number of events still waiting.
+    pub remaining: usize,
+}
+
+/// Bounded FIFO of events that could not be published to the network.
+///
+/// Entries are keyed by a monotonically increasing sequence number. When
+/// opened with a path, entries are also stored in Sled so that they survive
+/// a restart.
+pub struct PublishQueue {
+    capacity: usize,
+    state: Mutex<PublishQueueState>,
+    tree: Option<sled::Tree>,
+}
+
+impl PublishQueue {
+    /// Create an in-memory publish queue.
+    pub fn in_memory(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(PublishQueueState::default()),
+            tree: None,
+        }
+    }
+
+    /// Open or create a persistent publish queue at the given path.
+    ///
+    /// Events queued by a previous run are restored in their original order.
+    pub fn open<P: AsRef<Path>>(path: P, capacity: usize) -> Result<Self> {
+        let db = sled::open(path.as_ref()).context("Failed to open publish queue database")?;
+        let tree = db
+            .open_tree("publish_queue")
+            .context("Failed to open publish queue tree")?;
+
+        let mut state = PublishQueueState::default();
+        for item in tree.iter() {
+            let (key, value) = item.context("Failed to read publish queue")?;
+            let seq = u64::from_be_bytes(
+                key.as_ref()
+                    .try_into()
+                    .context("Invalid publish queue key")?,
+            );
+            let entry: QueuedPublication =
+                serde_json::from_slice(&value).context("Invalid publish queue entry")?;
+            state.entries.insert(seq, entry);
+            state.next_seq = seq + 1;
+        }
+
+        Ok(Self {
+            capacity,
+            state: Mutex::new(state),
+            tree: Some(tree),
+        })
+    }
+
+    /// Number of events currently queued.
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.state.lock().entries.is_empty()
+    }
+
+    /// Current queue counters.
+    pub fn metrics(&self) -> PublishQueueMetrics {
+        let state = self.state.lock();
+        PublishQueueMetrics {
+            depth: state.entries.len(),
+            capacity: self.capacity,
+            enqueued_total: state.enqueued_total,
+            published_total: state.published_total,
+            rejected_total: state.rejected_total,
+        }
+    }
+
+    /// Whether an earlier event for `content_id` is still waiting.
+    fn has_pending(&self, content_id: &Option<String>) -> bool {
+        self.state
+            .lock()
+            .entries
+            .values()
+            .any(|entry| &entry.content_id == content_id)
+    }
+
+    /// Append an event, failing when the queue is full.
+    fn push(&self, content_id: Option<String>, data: Vec<u8>) -> Result<()> {
+        let mut state = self.state.lock();
+        if state.entries.len() >= self.capacity {
+            state.rejected_total += 1;
+            anyhow::bail!(
+                "Publish queue is full ({} events); event dropped",
+                self.capacity
+            );
+        }
+
+        let seq = state.next_seq;
+        let entry = QueuedPublication { content_id, data };
+        if let Some(tree) = &self.tree {
+            let value = serde_json::to_vec(&entry).context("Failed to serialize queued event")?;
+            tree.insert(seq.to_be_bytes(), value)
+                .context("Failed to persist queued event")?;
+        }
+        state.entries.insert(seq, entry);
+        state.next_seq = seq + 1;
+        state.enqueued_total += 1;
+        Ok(())
+    }
+
+    /// Snapshot of the queued events in submission order.
+    fn snapshot(&self) -> Vec<(u64, QueuedPublication)> {
+        self.state
+            .lock()
+            .entries
+            .iter()
+            .map(|(seq, entry)| (*seq, entry.clone()))
+            .collect()
+    }
+
+    /// Remove an event after it has been published.
+    fn remove(&self, seq: u64) -> Result<()> {
+        if let Some(tree) = &self.tree {
+            tree.remove(seq.to_be_bytes())
+                .context("Failed to remove queued event")?;
+        }
+        let mut state = self.state.lock();
+        if state.entries.remove(&seq).is_some() {
+            state.published_total += 1;
+        }
+        Ok(())
+    }
+}
+
 /// Event publisher that supports both local and network delivery.
 ///
 /// This implementation:
@@ -28,6 +212,10 @@ pub struct GossipsubEventPublisher<P: PeerNetwork> {
     peer_network: Arc<P>,
     /// Gossipsub topic name.
     topic: String,
+    /// Events waiting to be (re)published to the network.
+    queue: PublishQueue,
+    /// Serializes retry passes so that queued events are not sent twice.
+    retry_lock: tokio::sync::Mutex<()>,
 }
 
 impl<P: PeerNetwork> GossipsubEventPublisher<P> {
@@ -41,6 +229,8 @@ impl<P: PeerNetwork> GossipsubEventPublisher<P> {
             local_bus: EventBus::new(),
             peer_network,
             topic: topic.unwrap_or_else(|| DEFAULT_EVENT_TOPIC.to_string()),
+            queue: PublishQueue::in_memory(DEFAULT_PUBLISH_QUEUE_CAPACITY),
+            retry_lock: tokio::sync::Mutex::new(()),
         }
     }
 
@@ -55,9 +245,23 @@ impl<P: PeerNetwork> GossipsubEventPublisher<P> {
             local_bus: EventBus::with_persistence(persistence_manager),
             peer_network,
             topic: topic.unwrap_or_else(|| DEFAULT_EVENT_TOPIC.to_string()),
+            queue: PublishQueue::in_memory(DEFAULT_PUBLISH_QUEUE_CAPACITY),
+            retry_lock: tokio::sync::Mutex::new(()),
         }
     }
 
+    /// Use the given publish queue (e.g. a persistent one) for events that
+    /// could not be published to the network.
+    pub fn with_publish_queue(mut self, queue: PublishQueue) -> Self {
+        self.queue = queue;
+        self
+    }
+
+    /// Get the publish queue counters.
+    pub fn queue_metrics(&self) -> PublishQueueMetrics {
+        self.queue.metrics()
+    }
+
     /// Get a reference to the underlying local EventBus.
     pub fn local_bus(&self) -> &EventBus {
         &self.local_bus
@@ -72,6 +276,39 @@ impl<P: PeerNetwork> GossipsubEventPublisher<P> {
     pub async fn register_event_type(&self) {
         self.local_bus.register_event_type::<Event>().await;
     }
+
+    /// Retry publishing queued events in submission order.
+    ///
+    /// When an event fails again, later events for the same content are held
+    /// back so that per-content ordering is preserved.
+    pub async fn retry_queued(&self) -> Result<PublishQueueRetryResult> {
+        let _guard = self.retry_lock.lock().await;
+        let mut result = PublishQueueRetryResult::default();
+        let mut blocked: HashSet<Option<String>> = HashSet::new();
+
+        for (seq, entry) in self.queue.snapshot() {
+            if blocked.contains(&entry.content_id) {
+                continue;
+            }
+            match self
+                .peer_network
+                .publish_event(&self.topic, &entry.data)
+                .await
+            {
+                Ok(()) => {
+                    self.queue.remove(seq)?;
+                    result.published += 1;
+                }
+                Err(e) => {
+                    tracing::debug!("Queued event still cannot be published: {}", e);
+                    blocked.insert(entry.content_id);
+                }
+            }
+        }
+
+        result.remaining = self.queue.len();
+        Ok(result)
+    }
 }
 
 #[async_trait]
@@ -86,16 +323,33 @@ impl<P: PeerNetwork + 'static> EventPublisher for GossipsubEventPublisher<P> {
     }
 
     /// Publish an event to the P2P network via Gossipsub.
+    ///
+    /// If the event cannot be published right now, or an earlier event for the
+    /// same content is still queued, it is added to the publish queue instead.
+    /// Fails only when the queue is full.
     async fn publish_to_network(&self, event: &Event) -> Result<()> {
         // Serialize the event to JSON
         let event_data = serde_json::to_vec(event)
             .map_err(|e| anyhow::anyhow!("Failed to serialize event: {}", e))?;
+        let content_id = event.content_id().map(str::to_string);
+
+        if self.queue.has_pending(&content_id) {
+            return self.queue.push(content_id, event_data);
+        }
 
         // Publish via Gossipsub
-        self.peer_network
+        if let Err(e) = self
+            .peer_network
             .publish_event(&self.topic, &event_data)
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to publish event to network: {}", e))
+        {
+            tracing::warn!(
+                "Failed to publish event to network, queued for retry: {}",
+                e
+            );
+            self.queue.push(content_id, event_data)?;
+        }
+        Ok(())
     }
 
     async fn subscribe<F>(&self, event_type: &str, handler: F) -> Result<()>
@@ -137,19 +391,35 @@ mod tests {
     use super::*;
     use crate::port::content_repository::SerializedOperation;
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, Ordering};
 
     /// Mock PeerNetwork for testing.
     #[allow(clippy::type_complexity)]
     struct MockPeerNetwork {
         published_events: Arc<tokio::sync::Mutex<Vec<(String, Vec<u8>)>>>,
+        offline: AtomicBool,
     }
 
     impl MockPeerNetwork {
         fn new() -> Self {
             Self {
                 published_events: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+                offline: AtomicBool::new(false),
             }
         }
+
+        fn set_offline(&self, offline: bool) {
+            self.offline.store(offline, Ordering::SeqCst);
+        }
+
+        async fn published(&self) -> Vec<Event> {
+            self.published_events
+                .lock()
+                .await
+                .iter()
+                .map(|(_, data)| serde_json::from_slice(data).unwrap())
+                .collect()
+        }
     }
 
     #[async_trait]
@@ -184,6 +454,9 @@ mod tests {
         }
 
         async fn publish_event(&self, topic: &str, event_data: &[u8]) -> Result<()> {
+            if self.offline.load(Ordering::SeqCst) {
+                anyhow::bail!("InsufficientPeers");
+            }
             self.published_events
                 .lock()
                 .await
@@ -199,6 +472,10 @@ mod tests {
             Ok(())
         }
 
+        async fn remove_provider(&self, _key: Vec<u8>) -> Result<()> {
+            Ok(())
+        }
+
         fn local_peer_id(&self) -> String {
             "mock-peer-id".to_string()
         }
@@ -284,6 +561,10 @@ mod tests {
         async fn connected_peer_count(&self) -> usize {
             0
         }
+
+        async fn connected_peer_ids(&self) -> Vec<String> {
+            vec![]
+        }
     }
 
     #[tokio::test]
@@ -377,4 +658,132 @@ mod tests {
         let published = network.published_events.lock().await;
         assert_eq!(published[0].0, custom_topic);
     }
+
+    fn content_updated(content_id: &str, timestamp: u64) -> Event {
+        Event::ContentUpdated {
+            content_id: content_id.to_string(),
+            updated_node_id: "node-1".to_string(),
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_publish_is_queued_and_retried() {
+        let network = Arc::new(MockPeerNetwork::new());
+        let publisher = GossipsubEventPublisher::new(network.clone(), None);
+
+        network.set_offline(true);
+        publisher
+            .publish_to_network(&content_updated("cid-1", 1))
+            .await
+            .unwrap();
+        assert_eq!(publisher.queue_metrics().depth, 1);
+
+        // Still offline: nothing is published and the event stays queued
+        let result = publisher.retry_queued().await.unwrap();
+        assert_eq!(result.published, 0);
+        assert_eq!(result.remaining, 1);
+
+        network.set_offline(false);
+        let result = publisher.retry_queued().await.unwrap();
+        assert_eq!(result.published, 1);
+        assert_eq!(result.remaining, 0);
+        assert_eq!(network.published().await, vec![content_updated("cid-1", 1)]);
+
+        let metrics = publisher.queue_metrics();
+        assert_eq!(metrics.depth, 0);
+        assert_eq!(metrics.enqueued_total, 1);
+        assert_eq!(metrics.published_total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_queue_preserves_per_content_order() {
+        let network = Arc::new(MockPeerNetwork::new());
+        let publisher = GossipsubEventPublisher::new(network.clone(), None);
+
+        network.set_offline(true);
+        publisher
+            .publish_to_network(&content_updated("cid-1", 1))
+            .await
+            .unwrap();
+        network.set_offline(false);
+
+        // cid-1 has a queued event, so the newer one must wait behind it
+        publisher
+            .publish_to_network(&content_updated("cid-1", 2))
+            .await
+            .unwrap();
+        // Other content is not held back
+        publisher
+            .publish_to_network(&content_updated("cid-2", 3))
+            .await
+            .unwrap();
+        assert_eq!(network.published().await, vec![content_updated("cid-2", 3)]);
+        assert_eq!(publisher.queue_metrics().depth, 2);
+
+        publisher.retry_queued().await.unwrap();
+        assert_eq!(
+            network.published().await,
+            vec![
+                content_updated("cid-2", 3),
+                content_updated("cid-1", 1),
+                content_updated("cid-1", 2),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_rejects_events() {
+        let network = Arc::new(MockPeerNetwork::new());
+        let publisher = GossipsubEventPublisher::new(network.clone(), None)
+            .with_publish_queue(PublishQueue::in_memory(1));
+
+        network.set_offline(true);
+        publisher
+            .publish_to_network(&content_updated("cid-1", 1))
+            .await
+            .unwrap();
+        let result = publisher
+            .publish_to_network(&content_updated("cid-2", 2))
+            .await;
+        assert!(result.is_err());
+
+        let metrics = publisher.queue_metrics();
+        assert_eq!(metrics.depth, 1);
+        assert_eq!(metrics.capacity, 1);
+        assert_eq!(metrics.rejected_total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_queue_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("publish_queue");
+        let network = Arc::new(MockPeerNetwork::new());
+        network.set_offline(true);
+
+        {
+            let publisher = GossipsubEventPublisher::new(network.clone(), None)
+                .with_publish_queue(PublishQueue::open(&path, 10).unwrap());
+            publisher
+                .publish_to_network(&content_updated("cid-1", 1))
+                .await
+                .unwrap();
+            publisher
+                .publish_to_network(&content_updated("cid-1", 2))
+                .await
+                .unwrap();
+        }
+
+        network.set_offline(false);
+        let publisher = GossipsubEventPublisher::new(network.clone(), None)
+            .with_publish_queue(PublishQueue::open(&path, 10).unwrap());
+        assert_eq!(publisher.queue_metrics().depth, 2);
+
+        let result = publisher.retry_queued().await.unwrap();
+        assert_eq!(result.published, 2);
+        assert_eq!(
+            network.published().await,
+            vec![content_updated("cid-1", 1), content_updated("cid-1", 2)]
+        );
+    }
 }
@@ -1,8 +1,10 @@
 //! Key management for node P-256 keys.
 
 use anyhow::{Context, Result};
-use p256::ecdsa::{SigningKey, VerifyingKey};
+use p256::ecdsa::signature::DigestSigner;
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
 use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
 use std::path::{Path, PathBuf};
 
 /// Node key pair for P-256 cryptography.
@@ -97,6 +99,17 @@ impl NodeKeyPair {
         &self.verifying_key
     }
 
+    /// Sign a message with the node key.
+    ///
+    /// Uses a SHA-256 digest, matching [`crate::infrastructure::crypto::verify_p256_signature`].
+    /// Returns the raw 64-byte signature.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        let (signature, _): (Signature, _) = self
+            .signing_key
+            .sign_digest(Sha256::new_with_prefix(message));
+        signature.to_vec()
+    }
+
     /// Generate NodeId from this key pair.
     pub fn node_id(&self) -> Result<crate::domain::value_objects::NodeId> {
         crate::domain::value_objects::NodeId::from_public_key(&self.public_key_bytes())
@@ -142,6 +155,19 @@ mod tests {
         assert_eq!(public_key[0], 0x04); // Uncompressed point indicator
     }
 
+    #[test]
+    fn test_sign_verifies_with_public_key() {
+        let key_pair = NodeKeyPair::generate();
+        let signature = key_pair.sign(b"message");
+
+        assert!(crate::infrastructure::crypto::verify_p256_signature(
+            b"message",
+            &signature,
+            &key_pair.public_key_bytes()
+        )
+        .is_ok());
+    }
+
     #[test]
     fn test_save_and_load_key_pair() {
         let tmp_dir = tempdir().unwrap();
@@ -1,14 +1,32 @@
 pub mod auth;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod crdt_repository;
 pub mod crypto;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod disk_capacity;
+pub mod event_dedup;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod event_journal;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod event_adapters;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod event_bus_publisher;
+pub mod event_log;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod event_signing;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod gossipsub_publisher;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod inbox_persistence;
 pub mod key_management;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod network;
+pub mod node_attestation;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod outbox_persistence;
 pub mod persistence;
 pub mod placement;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod reliable_event_publisher;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod storage_tiers;
@@ -0,0 +1,461 @@
+//! Bandwidth throttling and per-peer transfer limits.
+//!
+//! A home node shares its uplink with everything else on the network, and a
+//! large sync burst (a new member pulling every content, or many pushes at
+//! once) can saturate it. Two independent limits keep that in check:
+//!
+//! - Upload and download rate limits, enforced with token buckets on the
+//!   streams of the content protocol, which carries the bulk transfers. The
+//!   budget is shared by all streams, so it caps the node's total transfer
+//!   traffic. Gossip, DHT and other control traffic is never throttled.
+//! - A cap on concurrent content transfers per peer, across all connections
+//!   to it. Outgoing requests over the cap wait for a running one to finish;
+//!   incoming requests over the cap are refused.
+
+use async_trait::async_trait;
+use futures::{AsyncRead, AsyncWrite};
+use libp2p::request_response::Codec;
+use libp2p::PeerId;
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+/// Default maximum number of concurrent request-response transfers per peer.
+pub const DEFAULT_MAX_TRANSFERS_PER_PEER: usize = 32;
+
+/// Rate limits and transfer cap of the content protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BandwidthConfig {
+    /// Maximum bytes per second sent to peers. Unlimited when unset.
+    pub upload_bytes_per_sec: Option<u64>,
+    /// Maximum bytes per second received from peers. Unlimited when unset.
+    pub download_bytes_per_sec: Option<u64>,
+    /// Maximum number of concurrent content transfers per peer, in each
+    /// direction.
+    pub max_transfers_per_peer: usize,
+}
+
+impl Default for BandwidthConfig {
+    fn default() -> Self {
+        Self {
+            upload_bytes_per_sec: None,
+            download_bytes_per_sec: None,
+            max_transfers_per_peer: DEFAULT_MAX_TRANSFERS_PER_PEER,
+        }
+    }
+}
+
+/// Token bucket holding up to one second of traffic.
+#[derive(Debug)]
+struct TokenBucket {
+    bytes_per_sec: u64,
+    /// Available bytes. Negative after a read or write larger than what was
+    /// available; the deficit is paid back by waiting.
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(bytes_per_sec: u64, now: Instant) -> Self {
+        Self {
+            bytes_per_sec,
+            tokens: bytes_per_sec as f64,
+            refilled_at: now,
+        }
+    }
+
+    /// Take `bytes` from the bucket and return how long to wait before the
+    /// next transfer.
+    fn consume(&mut self, bytes: usize, now: Instant) -> Duration {
+        let rate = self.bytes_per_sec as f64;
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * rate).min(rate);
+        self.refilled_at = now;
+        self.tokens -= bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / rate)
+        }
+    }
+}
+
+/// Upload and download budget shared by all throttled streams.
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    upload: Option<Mutex<TokenBucket>>,
+    download: Option<Mutex<TokenBucket>>,
+}
+
+impl BandwidthLimiter {
+    /// Create a limiter for the rate limits of `config`, or `None` if no
+    /// rate is limited.
+    pub fn new(config: &BandwidthConfig) -> Option<Arc<Self>> {
+        if config.upload_bytes_per_sec.is_none() && config.download_bytes_per_sec.is_none() {
+            return None;
+        }
+        let now = Instant::now();
+        let bucket = |rate: Option<u64>| rate.map(|rate| Mutex::new(TokenBucket::new(rate, now)));
+        Some(Arc::new(Self {
+            upload: bucket(config.upload_bytes_per_sec),
+            download: bucket(config.download_bytes_per_sec),
+        }))
+    }
+
+    fn upload_delay(&self, bytes: usize) -> Duration {
+        Self::delay(&self.upload, bytes)
+    }
+
+    fn download_delay(&self, bytes: usize) -> Duration {
+        Self::delay(&self.download, bytes)
+    }
+
+    fn delay(bucket: &Option<Mutex<TokenBucket>>, bytes: usize) -> Duration {
+        match bucket {
+            Some(bucket) => bucket
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .consume(bytes, Instant::now()),
+            None => Duration::ZERO,
+        }
+    }
+}
+
+/// A stream throttled by a [`BandwidthLimiter`].
+///
+/// Every read or write is charged to the shared budget; when the budget is
+/// overdrawn, the next read or write waits until it is paid back. Without a
+/// limiter the stream is passed through unchanged.
+pub struct Throttled<S> {
+    inner: S,
+    limiter: Option<Arc<BandwidthLimiter>>,
+    read_delay: Option<Pin<Box<tokio::time::Sleep>>>,
+    write_delay: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl<S> Throttled<S> {
+    pub fn new(inner: S, limiter: Option<Arc<BandwidthLimiter>>) -> Self {
+        Self {
+            inner,
+            limiter,
+            read_delay: None,
+            write_delay: None,
+        }
+    }
+}
+
+/// Wait for a pending delay, clearing it once elapsed.
+fn poll_delay(delay: &mut Option<Pin<Box<tokio::time::Sleep>>>, cx: &mut Context<'_>) -> Poll<()> {
+    if let Some(sleep) = delay.as_mut() {
+        ready!(sleep.as_mut().poll(cx));
+        *delay = None;
+    }
+    Poll::Ready(())
+}
+
+fn schedule_delay(delay: &mut Option<Pin<Box<tokio::time::Sleep>>>, wait: Duration) {
+    if !wait.is_zero() {
+        *delay = Some(Box::pin(tokio::time::sleep(wait)));
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for Throttled<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(poll_delay(&mut this.read_delay, cx));
+        let read = ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        if let Some(limiter) = &this.limiter {
+            schedule_delay(&mut this.read_delay, limiter.download_delay(read));
+        }
+        Poll::Ready(Ok(read))
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for Throttled<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(poll_delay(&mut this.write_delay, cx));
+        let written = ready!(Pin::new(&mut this.inner).poll_write(cx, buf))?;
+        if let Some(limiter) = &this.limiter {
+            schedule_delay(&mut this.write_delay, limiter.upload_delay(written));
+        }
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
+
+/// A request-response codec whose streams are throttled by a
+/// [`BandwidthLimiter`]. Messages are encoded by the wrapped codec.
+#[derive(Debug, Clone)]
+pub struct ThrottledCodec<C> {
+    inner: C,
+    limiter: Option<Arc<BandwidthLimiter>>,
+}
+
+impl<C> ThrottledCodec<C> {
+    pub fn new(inner: C, limiter: Option<Arc<BandwidthLimiter>>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+#[async_trait]
+impl<C> Codec for ThrottledCodec<C>
+where
+    C: Codec + Send,
+    C::Protocol: Sync,
+{
+    type Protocol = C::Protocol;
+    type Request = C::Request;
+    type Response = C::Response;
+
+    async fn read_request<T>(
+        &mut self,
+        protocol: &Self::Protocol,
+        io: &mut T,
+    ) -> io::Result<Self::Request>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut io = Throttled::new(io, self.limiter.clone());
+        self.inner.read_request(protocol, &mut io).await
+    }
+
+    async fn read_response<T>(
+        &mut self,
+        protocol: &Self::Protocol,
+        io: &mut T,
+    ) -> io::Result<Self::Response>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut io = Throttled::new(io, self.limiter.clone());
+        self.inner.read_response(protocol, &mut io).await
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        protocol: &Self::Protocol,
+        io: &mut T,
+        request: Self::Request,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        let mut io = Throttled::new(io, self.limiter.clone());
+        self.inner.write_request(protocol, &mut io, request).await
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        protocol: &Self::Protocol,
+        io: &mut T,
+        response: Self::Response,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        let mut io = Throttled::new(io, self.limiter.clone());
+        self.inner.write_response(protocol, &mut io, response).await
+    }
+}
+
+/// Caps the number of concurrent outgoing transfers to each peer.
+#[derive(Debug)]
+pub struct TransferLimiter {
+    max_per_peer: usize,
+    peers: Mutex<HashMap<PeerId, Arc<Semaphore>>>,
+}
+
+impl TransferLimiter {
+    pub fn new(max_per_peer: usize) -> Self {
+        Self {
+            max_per_peer: max_per_peer.max(1),
+            peers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait for a transfer slot to `peer`. The slot is released when the
+    /// returned permit is dropped.
+    pub async fn acquire(&self, peer: &PeerId) -> OwnedSemaphorePermit {
+        let semaphore = {
+            let mut peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+            // Forget peers without running or waiting transfers (permits
+            // hold a reference) so the map does not grow with every peer
+            // ever contacted.
+            peers.retain(|_, semaphore| Arc::strong_count(semaphore) > 1);
+            peers
+                .entry(*peer)
+                .or_insert_with(|| Arc::new(Semaphore::new(self.max_per_peer)))
+                .clone()
+        };
+        semaphore
+            .acquire_owned()
+            .await
+            .expect("transfer semaphore is never closed")
+    }
+
+    /// Number of transfers to `peer` currently running.
+    pub fn active_transfers(&self, peer: &PeerId) -> usize {
+        self.peers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(peer)
+            .map(|semaphore| self.max_per_peer - semaphore.available_permits())
+            .unwrap_or(0)
+    }
+}
+
+/// Counts the incoming transfers of each peer that have not been answered
+/// yet, across all connections to it.
+#[derive(Debug)]
+pub struct InboundTransfers {
+    max_per_peer: usize,
+    active: HashMap<PeerId, usize>,
+}
+
+impl Default for InboundTransfers {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TRANSFERS_PER_PEER)
+    }
+}
+
+impl InboundTransfers {
+    pub fn new(max_per_peer: usize) -> Self {
+        Self {
+            max_per_peer: max_per_peer.max(1),
+            active: HashMap::new(),
+        }
+    }
+
+    /// Count a request received from `peer`. Returns whether it is within
+    /// the cap and may be served; it is counted either way, until [`end`] is
+    /// called for its response.
+    ///
+    /// [`end`]: Self::end
+    pub fn begin(&mut self, peer: PeerId) -> bool {
+        let active = self.active.entry(peer).or_insert(0);
+        *active += 1;
+        *active <= self.max_per_peer
+    }
+
+    /// Stop counting a request of `peer` once its response was sent or
+    /// failed.
+    pub fn end(&mut self, peer: &PeerId) {
+        if let Some(active) = self.active.get_mut(peer) {
+            *active -= 1;
+            if *active == 0 {
+                self.active.remove(peer);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::AsyncWriteExt;
+
+    #[test]
+    fn test_token_bucket_delays_after_budget_is_spent() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(1000, start);
+        assert_eq!(bucket.consume(600, start), Duration::ZERO);
+        assert_eq!(bucket.consume(900, start), Duration::from_millis(500));
+
+        // Half a second later the deficit is paid back.
+        let later = start + Duration::from_millis(500);
+        assert_eq!(bucket.consume(0, later), Duration::ZERO);
+
+        // Idle time never accumulates more than one second of traffic.
+        let idle = later + Duration::from_secs(10);
+        assert_eq!(bucket.consume(1000, idle), Duration::ZERO);
+        assert!(bucket.consume(1, idle) > Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_throttled_write_is_rate_limited() {
+        let limiter = BandwidthLimiter::new(&BandwidthConfig {
+            upload_bytes_per_sec: Some(1000),
+            ..BandwidthConfig::default()
+        });
+        assert!(limiter.is_some());
+        let mut stream = Throttled::new(futures::io::Cursor::new(Vec::new()), limiter);
+
+        let start = tokio::time::Instant::now();
+        stream.write_all(&[0; 1000]).await.unwrap();
+        stream.write_all(&[0; 2000]).await.unwrap();
+        stream.write_all(&[0; 1]).await.unwrap();
+        // The first second is the burst; the next 2000 bytes take 2 seconds.
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        assert_eq!(stream.inner.get_ref().len(), 3001);
+    }
+
+    #[test]
+    fn test_no_limiter_without_rate_limits() {
+        assert!(BandwidthLimiter::new(&BandwidthConfig::default()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_limiter_caps_concurrent_transfers() {
+        let limiter = TransferLimiter::new(2);
+        let peer = PeerId::random();
+
+        let first = limiter.acquire(&peer).await;
+        let _second = limiter.acquire(&peer).await;
+        assert_eq!(limiter.active_transfers(&peer), 2);
+
+        // A third transfer waits until a slot is released.
+        let waiting = tokio::time::timeout(Duration::from_millis(20), limiter.acquire(&peer));
+        assert!(waiting.await.is_err());
+        drop(first);
+        let _third = limiter.acquire(&peer).await;
+
+        // Other peers are not affected.
+        let other = PeerId::random();
+        let _other = limiter.acquire(&other).await;
+        assert_eq!(limiter.active_transfers(&other), 1);
+    }
+
+    #[test]
+    fn test_inbound_transfers_are_capped_per_peer() {
+        let mut inbound = InboundTransfers::new(2);
+        let peer = PeerId::random();
+
+        assert!(inbound.begin(peer));
+        assert!(inbound.begin(peer));
+        assert!(!inbound.begin(peer));
+        // Other peers are not affected.
+        assert!(inbound.begin(PeerId::random()));
+
+        // The refused request counts until its response is sent, too.
+        inbound.end(&peer);
+        assert!(!inbound.begin(peer));
+        inbound.end(&peer);
+        inbound.end(&peer);
+        assert!(inbound.begin(peer));
+        inbound.end(&peer);
+        inbound.end(&peer);
+        assert!(!inbound.active.contains_key(&peer));
+    }
+}
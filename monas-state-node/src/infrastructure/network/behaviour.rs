@@ -2,47 +2,88 @@
 //!
 //! Combines multiple libp2p protocols:
 //! - Kademlia DHT for peer discovery and content routing
-//! - Gossipsub for event propagation
+//! - Gossipsub for event propagation (signed, validated and peer-scored)
 //! - RequestResponse for direct peer communication
 //! - mDNS for local peer discovery
 //! - Identify for peer identification
+//! - Ping for measuring round-trip times to connected peers
+//! - Relay client and DCUtR for reaching nodes behind NAT
+//! - AutoNAT for detecting public reachability
+//! - Allow/block lists for operator connection gating
 
+use super::bandwidth::{BandwidthLimiter, ThrottledCodec, DEFAULT_MAX_TRANSFERS_PER_PEER};
 use super::protocol::{ContentRequest, ContentResponse};
 use super::public_key_protocol::{PublicKeyRequest, PublicKeyResponse};
 use libp2p::{
-    gossipsub, identify, kad,
+    allow_block_list::{self, AllowedPeers, BlockedPeers},
+    gossipsub, identify, kad, ping,
     request_response::{self, ProtocolSupport},
-    swarm::NetworkBehaviour,
+    swarm::{behaviour::toggle::Toggle, NetworkBehaviour},
     StreamProtocol,
 };
+use std::convert::Infallible;
+use std::sync::Arc;
 use std::time::Duration;
 
 #[cfg(not(target_arch = "wasm32"))]
-use libp2p::mdns;
+use libp2p::{autonat, dcutr, mdns, relay};
 
 /// Protocol name for content requests.
-pub const CONTENT_PROTOCOL_NAME: &str = "/monas/content/1.0.0";
+pub const CONTENT_PROTOCOL_NAME: &str = "/monas/content/1.1.0";
 
 /// Protocol name for public key exchange.
 pub const PUBLIC_KEY_PROTOCOL_NAME: &str = "/monas/public-key/1.0.0";
 
+/// Lifetime of provider records stored by other peers. A provider that stops
+/// re-announcing disappears from `get_providers` results after this.
+pub const PROVIDER_RECORD_TTL: Duration = Duration::from_secs(48 * 60 * 60);
+
+/// How often provider records are re-announced; well within
+/// [`PROVIDER_RECORD_TTL`] so records never expire while still provided.
+pub const PROVIDER_REPUBLISH_INTERVAL: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// Interval between pings to each connected peer.
+pub const PING_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Codec of the content protocol: CBOR messages on throttled streams.
+pub type ContentProtocolCodec =
+    ThrottledCodec<request_response::cbor::codec::Codec<ContentRequest, ContentResponse>>;
+
 /// Combined network behaviour for the state node.
 #[derive(NetworkBehaviour)]
 #[behaviour(to_swarm = "NodeBehaviourEvent")]
 pub struct NodeBehaviour {
+    /// Peers the operator blocked; connections to and from them are denied.
+    pub blocked_peers: allow_block_list::Behaviour<BlockedPeers>,
+    /// Peers the operator allowed. Only enabled when an allowlist is
+    /// enforced, in which case connections to any other peer are denied.
+    pub allowed_peers: Toggle<allow_block_list::Behaviour<AllowedPeers>>,
     /// Kademlia DHT for peer discovery and content routing.
     pub kademlia: kad::Behaviour<kad::store::MemoryStore>,
     /// Gossipsub for event propagation.
     pub gossipsub: gossipsub::Behaviour,
     /// RequestResponse for direct peer communication.
-    pub request_response: request_response::cbor::Behaviour<ContentRequest, ContentResponse>,
+    pub request_response: request_response::Behaviour<ContentProtocolCodec>,
     /// RequestResponse for public key exchange.
     pub public_key_protocol: request_response::cbor::Behaviour<PublicKeyRequest, PublicKeyResponse>,
     /// Identify for peer identification.
     pub identify: identify::Behaviour,
+    /// Ping for round-trip time measurement.
+    pub ping: ping::Behaviour,
     /// mDNS for local peer discovery (native only).
     #[cfg(not(target_arch = "wasm32"))]
     pub mdns: mdns::tokio::Behaviour,
+    /// Relay client for circuit reservations and relayed dials (native only).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub relay_client: relay::client::Behaviour,
+    /// DCUtR hole punching to upgrade relayed connections to direct ones
+    /// (native only).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub dcutr: dcutr::Behaviour,
+    /// AutoNAT probes to learn whether this node is publicly reachable and
+    /// confirm its external addresses (native only).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub autonat: autonat::Behaviour,
 }
 
 /// Events generated by the combined behaviour.
@@ -53,8 +94,22 @@ pub enum NodeBehaviourEvent {
     RequestResponse(request_response::Event<ContentRequest, ContentResponse>),
     PublicKeyProtocol(request_response::Event<PublicKeyRequest, PublicKeyResponse>),
     Identify(Box<identify::Event>),
+    Ping(ping::Event),
     #[cfg(not(target_arch = "wasm32"))]
     Mdns(mdns::Event),
+    #[cfg(not(target_arch = "wasm32"))]
+    RelayClient(Box<relay::client::Event>),
+    #[cfg(not(target_arch = "wasm32"))]
+    Dcutr(Box<dcutr::Event>),
+    #[cfg(not(target_arch = "wasm32"))]
+    Autonat(Box<autonat::Event>),
+}
+
+// The allow/block list behaviours never emit events.
+impl From<Infallible> for NodeBehaviourEvent {
+    fn from(event: Infallible) -> Self {
+        match event {}
+    }
 }
 
 impl From<kad::Event> for NodeBehaviourEvent {
@@ -87,6 +142,12 @@ impl From<identify::Event> for NodeBehaviourEvent {
     }
 }
 
+impl From<ping::Event> for NodeBehaviourEvent {
+    fn from(event: ping::Event) -> Self {
+        NodeBehaviourEvent::Ping(event)
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 impl From<mdns::Event> for NodeBehaviourEvent {
     fn from(event: mdns::Event) -> Self {
@@ -94,13 +155,108 @@ impl From<mdns::Event> for NodeBehaviourEvent {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+impl From<relay::client::Event> for NodeBehaviourEvent {
+    fn from(event: relay::client::Event) -> Self {
+        NodeBehaviourEvent::RelayClient(Box::new(event))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<dcutr::Event> for NodeBehaviourEvent {
+    fn from(event: dcutr::Event) -> Self {
+        NodeBehaviourEvent::Dcutr(Box::new(event))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<autonat::Event> for NodeBehaviourEvent {
+    fn from(event: autonat::Event) -> Self {
+        NodeBehaviourEvent::Autonat(Box::new(event))
+    }
+}
+
+/// Gossipsub peer scoring parameters.
+///
+/// Peers that deliver invalid messages lose score; below the thresholds they
+/// are no longer gossiped with, cannot publish to us, and are eventually
+/// graylisted (all their messages ignored).
+#[derive(Debug, Clone)]
+pub struct GossipsubScoring {
+    pub params: gossipsub::PeerScoreParams,
+    pub thresholds: gossipsub::PeerScoreThresholds,
+}
+
+impl GossipsubScoring {
+    /// Scoring for the given event topics.
+    ///
+    /// Each invalid message costs `20 * n^2` for the n-th offence, slowly
+    /// forgiven over ~10 minutes; a few are enough to graylist a peer that
+    /// has earned the maximum positive score.
+    pub fn for_topics(topics: &[String]) -> Self {
+        let topic_params = gossipsub::TopicScoreParams {
+            topic_weight: 1.0,
+            // Reward long-lived mesh peers and first deliveries, but cap both
+            // so good behaviour can't offset more than a couple of offences.
+            time_in_mesh_weight: 0.01,
+            time_in_mesh_quantum: Duration::from_secs(1),
+            time_in_mesh_cap: 3600.0,
+            first_message_deliveries_cap: 10.0,
+            // Events are sparse: don't penalize mesh peers for being quiet.
+            mesh_message_deliveries_weight: 0.0,
+            mesh_failure_penalty_weight: 0.0,
+            invalid_message_deliveries_weight: -20.0,
+            invalid_message_deliveries_decay: gossipsub::score_parameter_decay(
+                Duration::from_secs(600),
+            ),
+            ..Default::default()
+        };
+        let params = gossipsub::PeerScoreParams {
+            topics: topics
+                .iter()
+                .map(|topic| {
+                    (
+                        gossipsub::IdentTopic::new(topic).hash(),
+                        topic_params.clone(),
+                    )
+                })
+                .collect(),
+            // Home nodes often share a public IP (CGNAT, several nodes on
+            // one host), so colocation is not a sign of a Sybil attack here.
+            ip_colocation_factor_weight: 0.0,
+            ..Default::default()
+        };
+        Self {
+            params,
+            thresholds: gossipsub::PeerScoreThresholds::default(),
+        }
+    }
+}
+
 /// Configuration for creating a NodeBehaviour.
 #[derive(Debug, Clone)]
 pub struct BehaviourConfig {
     /// Protocol version string.
     pub protocol_version: String,
-    /// Agent version string.
+    /// Agent version string. Carries the node attestation, if any.
     pub agent_version: String,
+    /// Announce only confirmed external addresses via identify, instead of
+    /// every listen address (which may be private or unreachable).
+    pub hide_listen_addrs: bool,
+    /// Gossipsub peer scoring. `None` disables scoring.
+    pub gossipsub_scoring: Option<GossipsubScoring>,
+    /// Deny connections to every peer that is not explicitly allowed.
+    pub enforce_peer_allowlist: bool,
+    /// Maximum concurrent content protocol streams per connection. The
+    /// network layer additionally caps the transfers of each peer across
+    /// all its connections.
+    pub max_transfers_per_peer: usize,
+    /// Upload and download budget of the content protocol streams.
+    /// Unthrottled when unset.
+    pub bandwidth: Option<Arc<BandwidthLimiter>>,
+    /// Kademlia mode. `None` switches to server mode once an external
+    /// address is confirmed.
+    pub kademlia_mode: Option<kad::Mode>,
 }
 
 impl Default for BehaviourConfig {
@@ -108,45 +264,51 @@ impl Default for BehaviourConfig {
         Self {
             protocol_version: "/monas/1.0.0".to_string(),
             agent_version: format!("monas-state-node/{}", env!("CARGO_PKG_VERSION")),
+            hide_listen_addrs: false,
+            gossipsub_scoring: Some(GossipsubScoring::for_topics(&["monas-events".to_string()])),
+            enforce_peer_allowlist: false,
+            max_transfers_per_peer: DEFAULT_MAX_TRANSFERS_PER_PEER,
+            bandwidth: None,
+            kademlia_mode: None,
         }
     }
 }
 
 impl NodeBehaviour {
     /// Create a new NodeBehaviour with the given peer ID and configuration.
+    ///
+    /// `relay_client` is the behaviour half of [`relay::client::new`]; its
+    /// transport half must be part of the swarm transport.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn new(
         local_peer_id: libp2p::PeerId,
         keypair: &libp2p::identity::Keypair,
         config: BehaviourConfig,
+        relay_client: relay::client::Behaviour,
     ) -> anyhow::Result<Self> {
         // Kademlia configuration
         let mut kad_config = kad::Config::new(StreamProtocol::new("/monas/kad/1.0.0"));
         kad_config.set_query_timeout(Duration::from_secs(60));
+        kad_config.set_provider_record_ttl(Some(PROVIDER_RECORD_TTL));
+        kad_config.set_provider_publication_interval(Some(PROVIDER_REPUBLISH_INTERVAL));
+        // Incoming records are verified by the swarm loop before they are stored.
+        kad_config.set_record_filtering(kad::StoreInserts::FilterBoth);
         let store = kad::store::MemoryStore::new(local_peer_id);
         let mut kademlia = kad::Behaviour::with_config(local_peer_id, store, kad_config);
-        // Enable server mode so this node responds to Kademlia queries from other peers
-        kademlia.set_mode(Some(kad::Mode::Server));
-
-        // Gossipsub configuration
-        let gossipsub_config = gossipsub::ConfigBuilder::default()
-            .heartbeat_interval(Duration::from_secs(1))
-            .validation_mode(gossipsub::ValidationMode::Strict)
-            .build()
-            .map_err(|e| anyhow::anyhow!("Failed to create gossipsub config: {}", e))?;
-
-        let gossipsub = gossipsub::Behaviour::new(
-            gossipsub::MessageAuthenticity::Signed(keypair.clone()),
-            gossipsub_config,
-        )
-        .map_err(|e| anyhow::anyhow!("Failed to create gossipsub behaviour: {}", e))?;
+        // Unless pinned, Kademlia serves queries only once an external
+        // address is confirmed (by AutoNAT or configuration), so unreachable
+        // nodes stay out of peers' routing tables.
+        kademlia.set_mode(config.kademlia_mode);
+
+        let gossipsub = build_gossipsub(keypair, &config)?;
 
         // RequestResponse configuration using CBOR codec
         // Apply request timeout and limit concurrent streams to mitigate DoS
         let rr_config = request_response::Config::default()
             .with_request_timeout(Duration::from_secs(30))
-            .with_max_concurrent_streams(32);
-        let request_response = request_response::cbor::Behaviour::new(
+            .with_max_concurrent_streams(config.max_transfers_per_peer);
+        let request_response = request_response::Behaviour::with_codec(
+            ThrottledCodec::new(Default::default(), config.bandwidth.clone()),
             [(
                 StreamProtocol::new(CONTENT_PROTOCOL_NAME),
                 ProtocolSupport::Full,
@@ -166,22 +328,46 @@ impl NodeBehaviour {
             pk_config,
         );
 
+        // Connection gating; the allowlist is only active when enforced
+        let allowed_peers = Toggle::from(
+            config
+                .enforce_peer_allowlist
+                .then(allow_block_list::Behaviour::<AllowedPeers>::default),
+        );
+
         // Identify configuration
-        let identify = identify::Behaviour::new(identify::Config::new(
-            config.protocol_version,
-            keypair.public(),
-        ));
+        let identify = identify::Behaviour::new(
+            identify::Config::new(config.protocol_version, keypair.public())
+                .with_agent_version(config.agent_version)
+                .with_hide_listen_addrs(config.hide_listen_addrs),
+        );
+
+        // Ping measures the round-trip time to every connected peer
+        let ping = ping::Behaviour::new(ping::Config::new().with_interval(PING_INTERVAL));
 
         // mDNS configuration
         let mdns = mdns::tokio::Behaviour::new(mdns::Config::default(), local_peer_id)?;
 
+        // DCUtR coordinates hole punching over relayed connections
+        let dcutr = dcutr::Behaviour::new(local_peer_id);
+
+        // AutoNAT asks peers to dial us back on our candidate addresses. Only
+        // peers observed at global IPs are used, so a LAN cannot vouch for us.
+        let autonat = autonat::Behaviour::new(local_peer_id, autonat::Config::default());
+
         Ok(Self {
+            blocked_peers: Default::default(),
+            allowed_peers,
             kademlia,
             gossipsub,
             request_response,
             public_key_protocol,
             identify,
+            ping,
             mdns,
+            relay_client,
+            dcutr,
+            autonat,
         })
     }
 
@@ -195,30 +381,26 @@ impl NodeBehaviour {
         // Kademlia configuration
         let mut kad_config = kad::Config::new(StreamProtocol::new("/monas/kad/1.0.0"));
         kad_config.set_query_timeout(Duration::from_secs(60));
+        kad_config.set_provider_record_ttl(Some(PROVIDER_RECORD_TTL));
+        kad_config.set_provider_publication_interval(Some(PROVIDER_REPUBLISH_INTERVAL));
+        // Incoming records are verified by the swarm loop before they are stored.
+        kad_config.set_record_filtering(kad::StoreInserts::FilterBoth);
         let store = kad::store::MemoryStore::new(local_peer_id);
         let mut kademlia = kad::Behaviour::with_config(local_peer_id, store, kad_config);
-        // Enable server mode so this node responds to Kademlia queries from other peers
-        kademlia.set_mode(Some(kad::Mode::Server));
-
-        // Gossipsub configuration
-        let gossipsub_config = gossipsub::ConfigBuilder::default()
-            .heartbeat_interval(Duration::from_secs(1))
-            .validation_mode(gossipsub::ValidationMode::Strict)
-            .build()
-            .map_err(|e| anyhow::anyhow!("Failed to create gossipsub config: {}", e))?;
-
-        let gossipsub = gossipsub::Behaviour::new(
-            gossipsub::MessageAuthenticity::Signed(keypair.clone()),
-            gossipsub_config,
-        )
-        .map_err(|e| anyhow::anyhow!("Failed to create gossipsub behaviour: {}", e))?;
+        // Unless pinned, Kademlia serves queries only once an external
+        // address is confirmed (by AutoNAT or configuration), so unreachable
+        // nodes stay out of peers' routing tables.
+        kademlia.set_mode(config.kademlia_mode);
+
+        let gossipsub = build_gossipsub(keypair, &config)?;
 
         // RequestResponse configuration using CBOR codec
         // Apply request timeout and limit concurrent streams to mitigate DoS
         let rr_config = request_response::Config::default()
             .with_request_timeout(Duration::from_secs(30))
-            .with_max_concurrent_streams(32);
-        let request_response = request_response::cbor::Behaviour::new(
+            .with_max_concurrent_streams(config.max_transfers_per_peer);
+        let request_response = request_response::Behaviour::with_codec(
+            ThrottledCodec::new(Default::default(), config.bandwidth.clone()),
             [(
                 StreamProtocol::new(CONTENT_PROTOCOL_NAME),
                 ProtocolSupport::Full,
@@ -238,22 +420,66 @@ impl NodeBehaviour {
             pk_config,
         );
 
+        // Connection gating; the allowlist is only active when enforced
+        let allowed_peers = Toggle::from(
+            config
+                .enforce_peer_allowlist
+                .then(allow_block_list::Behaviour::<AllowedPeers>::default),
+        );
+
         // Identify configuration
-        let identify = identify::Behaviour::new(identify::Config::new(
-            config.protocol_version,
-            keypair.public(),
-        ));
+        let identify = identify::Behaviour::new(
+            identify::Config::new(config.protocol_version, keypair.public())
+                .with_agent_version(config.agent_version)
+                .with_hide_listen_addrs(config.hide_listen_addrs),
+        );
+
+        // Ping measures the round-trip time to every connected peer
+        let ping = ping::Behaviour::new(ping::Config::new().with_interval(PING_INTERVAL));
 
         Ok(Self {
+            blocked_peers: Default::default(),
+            allowed_peers,
             kademlia,
             gossipsub,
             request_response,
             public_key_protocol,
             identify,
+            ping,
         })
     }
 }
 
+/// Build gossipsub with signed messages, strict validation and scoring.
+///
+/// Messages are only forwarded once the application reports them valid via
+/// `report_message_validation_result`, so malformed events are neither
+/// propagated nor left unpunished.
+fn build_gossipsub(
+    keypair: &libp2p::identity::Keypair,
+    config: &BehaviourConfig,
+) -> anyhow::Result<gossipsub::Behaviour> {
+    let gossipsub_config = gossipsub::ConfigBuilder::default()
+        .heartbeat_interval(Duration::from_secs(1))
+        .validation_mode(gossipsub::ValidationMode::Strict)
+        .validate_messages()
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create gossipsub config: {}", e))?;
+
+    let mut gossipsub = gossipsub::Behaviour::new(
+        gossipsub::MessageAuthenticity::Signed(keypair.clone()),
+        gossipsub_config,
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to create gossipsub behaviour: {}", e))?;
+
+    if let Some(scoring) = &config.gossipsub_scoring {
+        gossipsub
+            .with_peer_score(scoring.params.clone(), scoring.thresholds.clone())
+            .map_err(|e| anyhow::anyhow!("Invalid gossipsub peer scoring: {}", e))?;
+    }
+    Ok(gossipsub)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -261,7 +487,7 @@ mod tests {
 
     #[test]
     fn test_content_protocol_name() {
-        assert_eq!(CONTENT_PROTOCOL_NAME, "/monas/content/1.0.0");
+        assert_eq!(CONTENT_PROTOCOL_NAME, "/monas/content/1.1.0");
     }
 
     #[test]
@@ -277,12 +503,18 @@ mod tests {
         let config = BehaviourConfig {
             protocol_version: "/custom/1.0.0".to_string(),
             agent_version: "custom-agent/1.0.0".to_string(),
+            hide_listen_addrs: true,
+            gossipsub_scoring: None,
+            enforce_peer_allowlist: true,
         };
 
         let cloned = config.clone();
 
         assert_eq!(cloned.protocol_version, "/custom/1.0.0");
         assert_eq!(cloned.agent_version, "custom-agent/1.0.0");
+        assert!(cloned.hide_listen_addrs);
+        assert!(cloned.gossipsub_scoring.is_none());
+        assert!(cloned.enforce_peer_allowlist);
     }
 
     #[test]
@@ -295,6 +527,18 @@ mod tests {
         assert!(debug_str.contains("agent_version"));
     }
 
+    #[test]
+    fn test_gossipsub_scoring_penalizes_invalid_messages() {
+        let scoring = GossipsubScoring::for_topics(&["monas-events".to_string()]);
+        let topic = gossipsub::IdentTopic::new("monas-events").hash();
+
+        let topic_params = &scoring.params.topics[&topic];
+        assert!(topic_params.invalid_message_deliveries_weight < 0.0);
+        assert_eq!(topic_params.mesh_message_deliveries_weight, 0.0);
+        assert!(scoring.params.validate().is_ok());
+        assert!(BehaviourConfig::default().gossipsub_scoring.is_some());
+    }
+
     #[test]
     fn test_node_behaviour_event_debug() {
         // Test that NodeBehaviourEvent implements Debug
@@ -310,8 +554,9 @@ mod tests {
         let keypair = Keypair::generate_ed25519();
         let local_peer_id = keypair.public().to_peer_id();
         let config = BehaviourConfig::default();
+        let (_relay_transport, relay_client) = relay::client::new(local_peer_id);
 
-        let result = NodeBehaviour::new(local_peer_id, &keypair, config);
+        let result = NodeBehaviour::new(local_peer_id, &keypair, config, relay_client);
 
         assert!(result.is_ok());
         let behaviour = result.unwrap();
@@ -322,7 +567,11 @@ mod tests {
         let _ = &behaviour.request_response;
         let _ = &behaviour.public_key_protocol;
         let _ = &behaviour.identify;
+        let _ = &behaviour.ping;
         let _ = &behaviour.mdns;
+        let _ = &behaviour.relay_client;
+        let _ = &behaviour.dcutr;
+        let _ = &behaviour.autonat;
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -333,11 +582,15 @@ mod tests {
         let config = BehaviourConfig {
             protocol_version: "/test/1.0.0".to_string(),
             agent_version: "test-agent/0.1.0".to_string(),
+            hide_listen_addrs: true,
+            gossipsub_scoring: None,
+            enforce_peer_allowlist: true,
         };
+        let (_relay_transport, relay_client) = relay::client::new(local_peer_id);
 
-        let result = NodeBehaviour::new(local_peer_id, &keypair, config);
+        let result = NodeBehaviour::new(local_peer_id, &keypair, config, relay_client);
 
-        assert!(result.is_ok());
+        assert!(result.unwrap().allowed_peers.is_enabled());
     }
 
     // Test From implementations for NodeBehaviourEvent
@@ -390,6 +643,16 @@ mod tests {
         assert_from_impl::<identify::Event, NodeBehaviourEvent>();
     }
 
+    #[test]
+    fn test_from_ping_event() {
+        fn assert_from_impl<T, U>()
+        where
+            U: From<T>,
+        {
+        }
+        assert_from_impl::<ping::Event, NodeBehaviourEvent>();
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[test]
     fn test_from_mdns_event() {
@@ -401,6 +664,19 @@ mod tests {
         assert_from_impl::<mdns::Event, NodeBehaviourEvent>();
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_from_relay_and_dcutr_events() {
+        fn assert_from_impl<T, U>()
+        where
+            U: From<T>,
+        {
+        }
+        assert_from_impl::<relay::client::Event, NodeBehaviourEvent>();
+        assert_from_impl::<dcutr::Event, NodeBehaviourEvent>();
+        assert_from_impl::<autonat::Event, NodeBehaviourEvent>();
+    }
+
     #[test]
     fn test_agent_version_contains_package_version() {
         let config = BehaviourConfig::default();
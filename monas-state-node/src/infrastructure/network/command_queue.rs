@@ -0,0 +1,276 @@
+//! Bounded command queue between `PeerNetwork` callers and the swarm loop.
+//!
+//! All network operations are funneled through one channel into the swarm
+//! event loop. When the loop falls behind, the channel fills up; instead of
+//! failing immediately or waiting forever, a sender waits up to
+//! `send_timeout` for room, retries a few times with a short backoff, and
+//! only then gives up with an error that names the queue depth. Queue depth
+//! and backpressure counters are exposed for monitoring.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::SendTimeoutError, error::TrySendError};
+
+/// Default maximum number of commands waiting for the swarm loop.
+pub const DEFAULT_COMMAND_QUEUE_CAPACITY: usize = 256;
+
+/// Capacity and backpressure policy of the command queue.
+#[derive(Debug, Clone)]
+pub struct CommandQueueConfig {
+    /// Maximum number of commands waiting for the swarm loop.
+    pub capacity: usize,
+    /// How long a single send attempt waits for room in a full queue.
+    pub send_timeout: Duration,
+    /// Number of send attempts before giving up.
+    pub max_send_attempts: u32,
+    /// Pause between send attempts.
+    pub retry_backoff: Duration,
+}
+
+impl Default for CommandQueueConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_COMMAND_QUEUE_CAPACITY,
+            send_timeout: Duration::from_secs(2),
+            max_send_attempts: 3,
+            retry_backoff: Duration::from_millis(100),
+        }
+    }
+}
+
+impl CommandQueueConfig {
+    /// Build the configuration from environment variables, falling back to
+    /// the defaults.
+    ///
+    /// - `SWARM_COMMAND_QUEUE_CAPACITY`: maximum number of queued commands
+    /// - `SWARM_COMMAND_SEND_TIMEOUT_MS`: wait per send attempt
+    /// - `SWARM_COMMAND_SEND_ATTEMPTS`: send attempts before giving up
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let env = |name: &str| std::env::var(name).ok();
+        Self {
+            capacity: env("SWARM_COMMAND_QUEUE_CAPACITY")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.capacity),
+            send_timeout: env("SWARM_COMMAND_SEND_TIMEOUT_MS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(default.send_timeout),
+            max_send_attempts: env("SWARM_COMMAND_SEND_ATTEMPTS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.max_send_attempts),
+            retry_backoff: default.retry_backoff,
+        }
+    }
+}
+
+/// Snapshot of the command queue counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CommandQueueMetrics {
+    /// Number of commands currently waiting for the swarm loop.
+    pub depth: usize,
+    /// Maximum number of commands the queue can hold.
+    pub capacity: usize,
+    /// Highest depth observed since startup.
+    pub max_depth: usize,
+    /// Commands queued since startup.
+    pub sent_total: u64,
+    /// Sends that found the queue full and had to wait.
+    pub full_total: u64,
+    /// Send attempts that timed out and were retried.
+    pub retried_total: u64,
+    /// Commands dropped because the queue stayed full.
+    pub rejected_total: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    max_depth: AtomicUsize,
+    sent_total: AtomicU64,
+    full_total: AtomicU64,
+    retried_total: AtomicU64,
+    rejected_total: AtomicU64,
+}
+
+/// Sending half of the command queue.
+#[derive(Debug)]
+pub struct CommandSender<T> {
+    tx: mpsc::Sender<T>,
+    config: Arc<CommandQueueConfig>,
+    counters: Arc<Counters>,
+}
+
+impl<T> Clone for CommandSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            config: self.config.clone(),
+            counters: self.counters.clone(),
+        }
+    }
+}
+
+/// Create a command queue with the given policy.
+pub fn command_queue<T>(config: CommandQueueConfig) -> (CommandSender<T>, mpsc::Receiver<T>) {
+    let (tx, rx) = mpsc::channel(config.capacity.max(1));
+    let sender = CommandSender {
+        tx,
+        config: Arc::new(config),
+        counters: Arc::default(),
+    };
+    (sender, rx)
+}
+
+impl<T> CommandSender<T> {
+    /// Queue `command`, waiting for room according to the backpressure
+    /// policy. Fails if the swarm loop has stopped or the queue stayed full.
+    pub async fn send(&self, command: T) -> Result<()> {
+        let mut command = match self.tx.try_send(command) {
+            Ok(()) => {
+                self.record_sent();
+                return Ok(());
+            }
+            Err(TrySendError::Closed(_)) => anyhow::bail!("Swarm command loop has stopped"),
+            Err(TrySendError::Full(command)) => {
+                self.counters.full_total.fetch_add(1, Ordering::Relaxed);
+                command
+            }
+        };
+
+        let attempts = self.config.max_send_attempts.max(1);
+        for attempt in 1..=attempts {
+            match self
+                .tx
+                .send_timeout(command, self.config.send_timeout)
+                .await
+            {
+                Ok(()) => {
+                    self.record_sent();
+                    return Ok(());
+                }
+                Err(SendTimeoutError::Closed(_)) => {
+                    anyhow::bail!("Swarm command loop has stopped")
+                }
+                Err(SendTimeoutError::Timeout(returned)) => {
+                    command = returned;
+                    if attempt < attempts {
+                        self.counters.retried_total.fetch_add(1, Ordering::Relaxed);
+                        tracing::debug!(
+                            "Swarm command queue full ({}/{}), retrying",
+                            self.depth(),
+                            self.capacity()
+                        );
+                        tokio::time::sleep(self.config.retry_backoff).await;
+                    }
+                }
+            }
+        }
+
+        self.counters.rejected_total.fetch_add(1, Ordering::Relaxed);
+        anyhow::bail!(
+            "Swarm command queue full ({}/{}) after {} attempts",
+            self.depth(),
+            self.capacity(),
+            attempts
+        )
+    }
+
+    /// Get the queue counters.
+    pub fn metrics(&self) -> CommandQueueMetrics {
+        CommandQueueMetrics {
+            depth: self.depth(),
+            capacity: self.capacity(),
+            max_depth: self.counters.max_depth.load(Ordering::Relaxed),
+            sent_total: self.counters.sent_total.load(Ordering::Relaxed),
+            full_total: self.counters.full_total.load(Ordering::Relaxed),
+            retried_total: self.counters.retried_total.load(Ordering::Relaxed),
+            rejected_total: self.counters.rejected_total.load(Ordering::Relaxed),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.tx.max_capacity()
+    }
+
+    fn depth(&self) -> usize {
+        self.tx.max_capacity() - self.tx.capacity()
+    }
+
+    fn record_sent(&self) {
+        self.counters.sent_total.fetch_add(1, Ordering::Relaxed);
+        self.counters
+            .max_depth
+            .fetch_max(self.depth(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(capacity: usize) -> CommandQueueConfig {
+        CommandQueueConfig {
+            capacity,
+            send_timeout: Duration::from_millis(20),
+            max_send_attempts: 2,
+            retry_backoff: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_tracks_depth() {
+        let (tx, mut rx) = command_queue::<u32>(config(4));
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+
+        let metrics = tx.metrics();
+        assert_eq!(metrics.depth, 2);
+        assert_eq!(metrics.capacity, 4);
+        assert_eq!(metrics.max_depth, 2);
+        assert_eq!(metrics.sent_total, 2);
+
+        assert_eq!(rx.recv().await, Some(1));
+        assert_eq!(tx.metrics().depth, 1);
+        assert_eq!(tx.metrics().max_depth, 2);
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_waits_for_room() {
+        let (tx, mut rx) = command_queue::<u32>(config(1));
+        tx.send(1).await.unwrap();
+
+        let sender = tx.clone();
+        let pending = tokio::spawn(async move { sender.send(2).await });
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(rx.recv().await, Some(1));
+
+        pending.await.unwrap().unwrap();
+        assert_eq!(rx.recv().await, Some(2));
+        assert_eq!(tx.metrics().full_total, 1);
+        assert_eq!(tx.metrics().rejected_total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_rejects_after_retries() {
+        let (tx, _rx) = command_queue::<u32>(config(1));
+        tx.send(1).await.unwrap();
+
+        let err = tx.send(2).await.unwrap_err();
+        assert!(err.to_string().contains("full (1/1)"));
+
+        let metrics = tx.metrics();
+        assert_eq!(metrics.full_total, 1);
+        assert_eq!(metrics.retried_total, 1);
+        assert_eq!(metrics.rejected_total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_fails_when_loop_stopped() {
+        let (tx, rx) = command_queue::<u32>(config(1));
+        drop(rx);
+        assert!(tx.send(1).await.is_err());
+    }
+}
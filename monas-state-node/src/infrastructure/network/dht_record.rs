@@ -0,0 +1,206 @@
+//! Signed DHT records.
+//!
+//! Values put into the Kademlia record store are wrapped in an envelope
+//! signed with the node identity key, so the publisher of a record is
+//! authenticated like the author of a domain event. Peers verify incoming
+//! records before storing them and keep only the most recently published
+//! record for each key. A stored record is only replaced by its own
+//! publisher, and keys that name a publisher only accept that publisher's
+//! records.
+
+use crate::domain::version_record::version_record_publisher;
+use crate::port::peer_network::DhtRecord;
+use libp2p::identity::{Keypair, PublicKey, SigningError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Maximum size of a record value. Records are meant for small metadata.
+pub const MAX_RECORD_VALUE_BYTES: usize = 1024;
+
+/// How far in the future a record's publication time may lie. Bounds how
+/// long a record with a skewed clock can shadow newer ones.
+pub const MAX_RECORD_CLOCK_SKEW_SECS: u64 = 5 * 60;
+
+/// Error type for DHT record failures.
+#[derive(Debug, Error)]
+pub enum RecordError {
+    #[error("Record value of {0} bytes exceeds the limit")]
+    TooLarge(usize),
+
+    #[error("Malformed record: {0}")]
+    Malformed(String),
+
+    #[error("Invalid publisher public key: {0}")]
+    InvalidPublicKey(String),
+
+    #[error("Invalid record signature")]
+    InvalidSignature,
+
+    #[error("Record published in the future")]
+    FromFuture,
+}
+
+/// Envelope stored as the value of a Kademlia record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SignedRecord {
+    value: Vec<u8>,
+    /// Unix seconds.
+    published_at: u64,
+    /// The publisher's libp2p public key (protobuf encoding).
+    public_key: Vec<u8>,
+    /// Signature over [`signing_message`].
+    signature: Vec<u8>,
+}
+
+/// Get the message to sign for `value` stored under `key`.
+fn signing_message(key: &[u8], value: &[u8], published_at: u64) -> Vec<u8> {
+    let mut message = b"monas-record:".to_vec();
+    message.extend((key.len() as u32).to_be_bytes());
+    message.extend(key);
+    message.extend(published_at.to_be_bytes());
+    message.extend(value);
+    message
+}
+
+/// Sign `value` for storage under `key` with the node identity keypair.
+///
+/// Returns the encoded envelope to store as the Kademlia record value.
+pub fn sign_record(
+    key: &[u8],
+    value: &[u8],
+    published_at: u64,
+    keypair: &Keypair,
+) -> Result<Vec<u8>, SigningError> {
+    let signature = keypair.sign(&signing_message(key, value, published_at))?;
+    let envelope = SignedRecord {
+        value: value.to_vec(),
+        published_at,
+        public_key: keypair.public().encode_protobuf(),
+        signature,
+    };
+    // Serializing byte vectors and an integer cannot fail.
+    Ok(serde_json::to_vec(&envelope).unwrap_or_default())
+}
+
+/// Verify the envelope `bytes` stored under `key` at time `now`.
+pub fn verify_record(key: &[u8], bytes: &[u8], now: u64) -> Result<DhtRecord, RecordError> {
+    let envelope: SignedRecord =
+        serde_json::from_slice(bytes).map_err(|e| RecordError::Malformed(e.to_string()))?;
+    if envelope.value.len() > MAX_RECORD_VALUE_BYTES {
+        return Err(RecordError::TooLarge(envelope.value.len()));
+    }
+    if envelope.published_at > now.saturating_add(MAX_RECORD_CLOCK_SKEW_SECS) {
+        return Err(RecordError::FromFuture);
+    }
+
+    let public_key = PublicKey::try_decode_protobuf(&envelope.public_key)
+        .map_err(|e| RecordError::InvalidPublicKey(e.to_string()))?;
+    if !public_key.verify(
+        &signing_message(key, &envelope.value, envelope.published_at),
+        &envelope.signature,
+    ) {
+        return Err(RecordError::InvalidSignature);
+    }
+
+    Ok(DhtRecord {
+        publisher: public_key.to_peer_id().to_string(),
+        value: envelope.value,
+        published_at: envelope.published_at,
+    })
+}
+
+/// Whether `incoming` should replace the record `existing` stored under
+/// `key`: it must be valid, signed by the publisher the key names (if any),
+/// and published by the publisher of any valid existing record after it.
+pub fn should_store(key: &[u8], existing: Option<&[u8]>, incoming: &[u8], now: u64) -> bool {
+    let Ok(incoming) = verify_record(key, incoming, now) else {
+        return false;
+    };
+    if version_record_publisher(key).is_some_and(|publisher| publisher != incoming.publisher) {
+        return false;
+    }
+    match existing.and_then(|bytes| verify_record(key, bytes, now).ok()) {
+        Some(existing) => {
+            incoming.publisher == existing.publisher
+                && incoming.published_at > existing.published_at
+        }
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn test_sign_and_verify_record() {
+        let keypair = Keypair::generate_ed25519();
+        let bytes = sign_record(b"key", b"value", NOW, &keypair).unwrap();
+
+        let record = verify_record(b"key", &bytes, NOW).unwrap();
+        assert_eq!(record.publisher, keypair.public().to_peer_id().to_string());
+        assert_eq!(record.value, b"value");
+        assert_eq!(record.published_at, NOW);
+
+        // A signature is bound to its key.
+        assert!(matches!(
+            verify_record(b"other", &bytes, NOW),
+            Err(RecordError::InvalidSignature)
+        ));
+        assert!(matches!(
+            verify_record(b"key", b"garbage", NOW),
+            Err(RecordError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_oversized_and_future_records() {
+        let keypair = Keypair::generate_ed25519();
+        let large = vec![0u8; MAX_RECORD_VALUE_BYTES + 1];
+        let bytes = sign_record(b"key", &large, NOW, &keypair).unwrap();
+        assert!(matches!(
+            verify_record(b"key", &bytes, NOW),
+            Err(RecordError::TooLarge(_))
+        ));
+
+        let future = NOW + MAX_RECORD_CLOCK_SKEW_SECS + 1;
+        let bytes = sign_record(b"key", b"value", future, &keypair).unwrap();
+        assert!(matches!(
+            verify_record(b"key", &bytes, NOW),
+            Err(RecordError::FromFuture)
+        ));
+    }
+
+    #[test]
+    fn test_only_newer_records_replace_stored_ones() {
+        let keypair = Keypair::generate_ed25519();
+        let older = sign_record(b"key", b"v1", NOW - 10, &keypair).unwrap();
+        let newer = sign_record(b"key", b"v2", NOW, &keypair).unwrap();
+
+        assert!(should_store(b"key", None, &older, NOW));
+        assert!(should_store(b"key", Some(&older), &newer, NOW));
+        assert!(!should_store(b"key", Some(&newer), &older, NOW));
+        assert!(!should_store(b"key", Some(&newer), &newer, NOW));
+        assert!(!should_store(b"key", None, b"garbage", NOW));
+    }
+
+    #[test]
+    fn test_records_are_only_replaced_by_their_publisher() {
+        let member = Keypair::generate_ed25519();
+        let stranger = Keypair::generate_ed25519();
+        let stored = sign_record(b"key", b"v1", NOW - 10, &member).unwrap();
+        // Newer, even from the future, but signed by someone else.
+        let forged = sign_record(b"key", b"v2", NOW + 60, &stranger).unwrap();
+        assert!(!should_store(b"key", Some(&stored), &forged, NOW));
+
+        // Version record keys only accept the publisher they name.
+        let member_id = member.public().to_peer_id().to_string();
+        let key = crate::domain::version_record::version_record_key("cid-1", &member_id);
+        let own = sign_record(&key, b"v1", NOW, &member).unwrap();
+        let squatted = sign_record(&key, b"v1", NOW, &stranger).unwrap();
+        assert!(should_store(&key, None, &own, NOW));
+        assert!(!should_store(&key, None, &squatted, NOW));
+    }
+}
@@ -7,26 +7,46 @@
 //! - mDNS for local peer discovery
 //! - WebRTC and TCP transports
 
-use super::behaviour::{BehaviourConfig, NodeBehaviour, NodeBehaviourEvent};
-use super::protocol::{ContentRequest, ContentResponse, PushBootstrap};
+use super::bandwidth::{BandwidthConfig, BandwidthLimiter, InboundTransfers, TransferLimiter};
+use super::behaviour::{BehaviourConfig, GossipsubScoring, NodeBehaviour, NodeBehaviourEvent};
+use super::command_queue::{command_queue, CommandQueueConfig, CommandQueueMetrics, CommandSender};
+use super::dht_record::{self, MAX_RECORD_VALUE_BYTES};
+use super::nat_traversal::{parse_relay_addr, Reachability, RelayRouting};
+use super::operation_batch::{self, OperationBatch};
+use super::peer_identity::PeerIdentityConfig;
+use super::protocol::{ContentRequest, ContentResponse, PushBootstrap, ReadCapability};
 use super::public_key_protocol::{NodePublicKey, PublicKeyRequest, PublicKeyResponse};
-use super::transport;
-use crate::domain::events::Event;
+use super::transport::{self, TransportConfig};
+use crate::domain::events::{Event, SignedEvent};
+use crate::domain::known_peer::{KnownPeer, MAX_KNOWN_PEER_ADDRS};
+use crate::domain::mirror::{MirrorPairingAcceptance, MirrorPairingRequest};
+use crate::domain::node_attestation::NodeAttestation;
+use crate::domain::peer_access::PeerAccess;
+use crate::domain::peer_latency::LatencyTracker;
+use crate::domain::sync_rules::SyncRules;
 use crate::infrastructure::disk_capacity;
-use crate::port::content_repository::{ContentRepository, SerializedOperation};
-use crate::port::peer_network::PeerNetwork;
+use crate::infrastructure::event_signing;
+use crate::infrastructure::node_attestation;
+use crate::port::content_repository::{ContentRepository, ContentSnapshot, SerializedOperation};
+use crate::port::peer_network::{ContentChunk, DhtRecord, PeerNetwork};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use futures::StreamExt;
 use libp2p::{
+    autonat,
     gossipsub::{self, IdentTopic},
-    identify, kad,
+    identify,
+    kad::{self, store::RecordStore},
+    relay,
     request_response::{self, OutboundRequestId, ResponseChannel},
-    swarm::SwarmEvent,
+    swarm::{
+        dial_opts::{DialOpts, PeerCondition},
+        DialError, SwarmEvent,
+    },
     Multiaddr, PeerId, Swarm,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -37,12 +57,27 @@ use tracing::{debug, error, info, warn};
 /// Default timeout for PeerNetwork operations (30 seconds).
 const PEER_NETWORK_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Largest byte range served for a single `FetchContentRange` request, well
+/// within the codec's response size limit.
+const MAX_CONTENT_RANGE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Default interval between random-walk Kademlia queries.
+pub const DEFAULT_RANDOM_WALK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 /// A relay request received from a remote peer via P2P protocol.
 /// The swarm loop sends these through a channel to the application layer (node.rs),
 /// which processes them using StateNodeService.
 pub struct RelayRequest {
     pub kind: RelayRequestKind,
-    pub reply: oneshot::Sender<Result<()>>,
+    pub reply: oneshot::Sender<Result<RelayReply>>,
+}
+
+/// The application layer's answer to a relay request.
+pub enum RelayReply {
+    /// The request was processed; no payload is returned.
+    Done,
+    /// A mirror pairing request was accepted.
+    MirrorPairAccepted(MirrorPairingAcceptance),
 }
 
 /// The kind of relay request.
@@ -66,6 +101,19 @@ pub enum RelayRequestKind {
         request_signature: Vec<u8>,
         timestamp: Option<u64>,
     },
+    /// Verify the read capability of a peer that is not a member of the
+    /// content network before its read request is served.
+    AuthorizeRead {
+        content_id: String,
+        /// The transport-authenticated peer that presented the capability.
+        requester_peer_id: String,
+        capability: ReadCapability,
+    },
+    MirrorPair {
+        /// The transport-authenticated peer that sent the request.
+        sender_peer_id: String,
+        request: MirrorPairingRequest,
+    },
 }
 
 /// Gossipsub message received from the network.
@@ -105,6 +153,63 @@ pub struct Libp2pNetworkConfig {
     /// learn how to dial this node. Empty by default (local/mDNS setups don't
     /// need it).
     pub external_addrs: Vec<Multiaddr>,
+    /// Selective sync rules advertised in capacity responses and enforced
+    /// when accepting membership of a new content network.
+    pub sync_rules: SyncRules,
+    /// Storage roots whose combined capacity is advertised in capacity
+    /// responses (e.g. a hot SSD and cold HDD tiers). Falls back to the data
+    /// directory when empty.
+    pub storage_dirs: Vec<PathBuf>,
+    /// Account-signed attestation of this node's identity, presented to peers
+    /// via identify. Must name this node's PeerId. Not presented when unset.
+    pub node_attestation: Option<NodeAttestation>,
+    /// Where the Ed25519 identity keypair (and thus the PeerId) is persisted,
+    /// and the optional passphrase protecting it.
+    pub identity: PeerIdentityConfig,
+    /// Circuit relay servers, each ending with `/p2p/<relay peer id>`.
+    /// When set, the node reserves a slot on every relay so peers behind NAT
+    /// can be reached, retries failed direct dials through them, and lets
+    /// DCUtR upgrade relayed connections to direct ones. Empty by default.
+    pub relay_addrs: Vec<Multiaddr>,
+    /// Extra AutoNAT servers, each ending with `/p2p/<peer id>`. Connected
+    /// peers are probed too, so this only matters when few peers are public.
+    pub autonat_servers: Vec<Multiaddr>,
+    /// Announce only confirmed external addresses via identify (and thus to
+    /// peers' Kademlia tables) instead of every listen address. Addresses are
+    /// confirmed by AutoNAT or given in `external_addrs`. On by default;
+    /// LAN-only setups, which have no confirmed address to announce, turn it
+    /// off.
+    pub advertise_confirmed_addrs_only: bool,
+    /// Pin the Kademlia mode. `None` (the default) lets AutoNAT decide: the
+    /// node serves queries once an external address is confirmed and stays a
+    /// client otherwise. AutoNAT only confirms public addresses, so LAN-only
+    /// setups pin `Some(Mode::Server)`.
+    pub kademlia_mode: Option<kad::Mode>,
+    /// Peers whose connections are denied. Loaded from the persisted peer
+    /// access list at startup and changed at runtime via `set_peer_access`.
+    pub blocked_peers: Vec<PeerId>,
+    /// Peers explicitly allowed. Only consulted when
+    /// `enforce_peer_allowlist` is set.
+    pub allowed_peers: Vec<PeerId>,
+    /// Deny connections to every peer not in `allowed_peers` (e.g. a private
+    /// deployment). Off by default.
+    pub enforce_peer_allowlist: bool,
+    /// Capacity and backpressure policy of the queue between `PeerNetwork`
+    /// calls and the swarm event loop.
+    pub command_queue: CommandQueueConfig,
+    /// Peers seen in earlier runs, reloaded from the peer store. Added to
+    /// Kademlia at startup like `bootstrap_nodes`.
+    pub known_peers: Vec<(PeerId, Vec<Multiaddr>)>,
+    /// Interval between lookups of a random peer ID, which discover new
+    /// peers and keep the routing table warm in small networks. Disabled
+    /// when `None`.
+    pub random_walk_interval: Option<Duration>,
+    /// Handshake parameters and optional private network key. Only nodes
+    /// sharing the same key (and Noise prologue) can connect.
+    pub transport: TransportConfig,
+    /// Upload/download rate limits of the content protocol and the cap on
+    /// concurrent transfers per peer. Unlimited rates by default.
+    pub bandwidth: BandwidthConfig,
 }
 
 impl Default for Libp2pNetworkConfig {
@@ -122,6 +227,22 @@ impl Default for Libp2pNetworkConfig {
             enable_mdns: true,
             gossipsub_topics: vec!["monas-events".to_string()],
             external_addrs: vec![],
+            sync_rules: SyncRules::default(),
+            storage_dirs: vec![],
+            node_attestation: None,
+            identity: PeerIdentityConfig::default(),
+            relay_addrs: vec![],
+            autonat_servers: vec![],
+            advertise_confirmed_addrs_only: true,
+            kademlia_mode: None,
+            blocked_peers: vec![],
+            allowed_peers: vec![],
+            enforce_peer_allowlist: false,
+            command_queue: CommandQueueConfig::default(),
+            known_peers: vec![],
+            random_walk_interval: Some(DEFAULT_RANDOM_WALK_INTERVAL),
+            transport: TransportConfig::default(),
+            bandwidth: BandwidthConfig::default(),
         }
     }
 }
@@ -139,7 +260,7 @@ enum SwarmCommand {
     },
     QueryCapacity {
         peer_id: PeerId,
-        reply: oneshot::Sender<Result<(u64, u64)>>,
+        reply: oneshot::Sender<Result<CapacityInfo>>,
     },
     PublishEvent {
         topic: String,
@@ -149,12 +270,25 @@ enum SwarmCommand {
     FetchContent {
         peer_id: PeerId,
         content_id: String,
+        capability: Option<ReadCapability>,
         reply: oneshot::Sender<Result<Vec<u8>>>,
     },
+    FetchContentRange {
+        peer_id: PeerId,
+        content_id: String,
+        offset: u64,
+        length: u64,
+        capability: Option<ReadCapability>,
+        reply: oneshot::Sender<Result<ContentChunk>>,
+    },
     PublishProvider {
         key: Vec<u8>,
         reply: oneshot::Sender<Result<()>>,
     },
+    RemoveProvider {
+        key: Vec<u8>,
+        reply: oneshot::Sender<Result<()>>,
+    },
     Dial {
         addr: Multiaddr,
         reply: oneshot::Sender<Result<()>>,
@@ -162,17 +296,23 @@ enum SwarmCommand {
     GetListenAddrs {
         reply: oneshot::Sender<Vec<Multiaddr>>,
     },
+    SetPeerAccess {
+        peer_id: PeerId,
+        access: Option<PeerAccess>,
+        reply: oneshot::Sender<Result<()>>,
+    },
     // ========== CRDT Sync Commands ==========
     FetchOperations {
         peer_id: PeerId,
         genesis_cid: String,
         since_version: Option<String>,
-        reply: oneshot::Sender<Result<Vec<SerializedOperation>>>,
+        capability: Option<ReadCapability>,
+        reply: oneshot::Sender<Result<OperationsWithVersion>>,
     },
     PushOperations {
         peer_id: PeerId,
         genesis_cid: String,
-        operations: Vec<SerializedOperation>,
+        batch: OperationBatch,
         bootstrap: Option<PushBootstrap>,
         reply: oneshot::Sender<Result<usize>>,
     },
@@ -180,6 +320,17 @@ enum SwarmCommand {
         key: Vec<u8>,
         reply: oneshot::Sender<Result<Vec<PeerId>>>,
     },
+    /// Store a signed record envelope in the DHT.
+    PutRecord {
+        key: Vec<u8>,
+        value: Vec<u8>,
+        reply: oneshot::Sender<Result<()>>,
+    },
+    /// Get the record envelopes stored under a key in the DHT.
+    GetRecords {
+        key: Vec<u8>,
+        reply: oneshot::Sender<Result<Vec<Vec<u8>>>>,
+    },
     QueryPublicKeys {
         peer_id: PeerId,
         node_ids: Vec<String>,
@@ -210,34 +361,60 @@ enum SwarmCommand {
         timestamp: Option<u64>,
         reply: oneshot::Sender<Result<bool>>,
     },
+    RequestMirrorPairing {
+        peer_id: PeerId,
+        request: MirrorPairingRequest,
+        reply: oneshot::Sender<Result<MirrorPairingAcceptance>>,
+    },
     /// Send a response back through a ResponseChannel.
     /// Used by spawned relay tasks to send responses without blocking the swarm loop.
     SendRelayResponse {
         channel: ResponseChannel<ContentResponse>,
         response: ContentResponse,
     },
+    /// Get the gossipsub mesh peers of each subscribed topic.
+    GetMeshPeers {
+        reply: oneshot::Sender<Vec<(String, Vec<PeerId>)>>,
+    },
+}
+
+/// Capacity and selective sync rules advertised by a peer.
+struct CapacityInfo {
+    available_capacity: u64,
+    sync_rules: SyncRules,
 }
 
 /// TTL for pending requests. Entries older than this are cleaned up to prevent memory leaks.
 const PENDING_REQUEST_TTL: Duration = Duration::from_secs(120);
 
+/// A pending record query: its reply channel and the record values found so far.
+type RecordQuery = (oneshot::Sender<Result<Vec<Vec<u8>>>>, Vec<Vec<u8>>);
+
+/// Fetched operations with the responder's latest version CID.
+type OperationsWithVersion = (Vec<SerializedOperation>, Option<String>);
+
 /// Pending requests tracking with TTL support.
 ///
 /// Each request tracks its creation time. A periodic sweep removes entries
 /// whose oneshot::Sender is closed (receiver timed out) or exceeded the TTL.
 #[derive(Default)]
 struct PendingRequests {
-    capacity_queries: HashMap<OutboundRequestId, oneshot::Sender<Result<(u64, u64)>>>,
+    capacity_queries: HashMap<OutboundRequestId, oneshot::Sender<Result<CapacityInfo>>>,
     content_fetches: HashMap<OutboundRequestId, oneshot::Sender<Result<Vec<u8>>>>,
+    content_range_fetches: HashMap<OutboundRequestId, oneshot::Sender<Result<ContentChunk>>>,
     kad_queries: HashMap<kad::QueryId, oneshot::Sender<Result<Vec<PeerId>>>>,
     kad_provider_queries: HashMap<kad::QueryId, oneshot::Sender<Result<Vec<PeerId>>>>,
-    operation_fetches:
-        HashMap<OutboundRequestId, oneshot::Sender<Result<Vec<SerializedOperation>>>>,
+    kad_put_queries: HashMap<kad::QueryId, oneshot::Sender<Result<()>>>,
+    kad_record_queries: HashMap<kad::QueryId, RecordQuery>,
+    operation_fetches: HashMap<OutboundRequestId, oneshot::Sender<Result<OperationsWithVersion>>>,
     operation_pushes: HashMap<OutboundRequestId, oneshot::Sender<Result<usize>>>,
     public_key_queries: HashMap<OutboundRequestId, oneshot::Sender<Result<Vec<NodePublicKey>>>>,
     relay_update_queries: HashMap<OutboundRequestId, oneshot::Sender<Result<bool>>>,
     relay_delete_queries: HashMap<OutboundRequestId, oneshot::Sender<Result<bool>>>,
     relay_invalidate_tokens_queries: HashMap<OutboundRequestId, oneshot::Sender<Result<bool>>>,
+    mirror_pairings: HashMap<OutboundRequestId, oneshot::Sender<Result<MirrorPairingAcceptance>>>,
+    /// Incoming content requests not answered yet, per peer.
+    inbound_transfers: InboundTransfers,
     /// Timestamps for all pending request IDs, used for TTL-based cleanup.
     timestamps: HashMap<u64, tokio::time::Instant>,
 }
@@ -252,8 +429,11 @@ impl PendingRequests {
         // Clean up closed senders from each map
         self.capacity_queries.retain(|_, s| !s.is_closed());
         self.content_fetches.retain(|_, s| !s.is_closed());
+        self.content_range_fetches.retain(|_, s| !s.is_closed());
         self.kad_queries.retain(|_, s| !s.is_closed());
         self.kad_provider_queries.retain(|_, s| !s.is_closed());
+        self.kad_put_queries.retain(|_, s| !s.is_closed());
+        self.kad_record_queries.retain(|_, (s, _)| !s.is_closed());
         self.operation_fetches.retain(|_, s| !s.is_closed());
         self.operation_pushes.retain(|_, s| !s.is_closed());
         self.public_key_queries.retain(|_, s| !s.is_closed());
@@ -261,6 +441,7 @@ impl PendingRequests {
         self.relay_delete_queries.retain(|_, s| !s.is_closed());
         self.relay_invalidate_tokens_queries
             .retain(|_, s| !s.is_closed());
+        self.mirror_pairings.retain(|_, s| !s.is_closed());
 
         // Clean up expired timestamps
         self.timestamps
@@ -270,20 +451,23 @@ impl PendingRequests {
 
 /// Channels for dispatching relay requests and sending responses back to the swarm.
 ///
-/// Relay requests (UpdateContent, DeleteContent, InvalidateTokens) are processed in
-/// spawned tasks to avoid blocking the swarm loop. `relay_tx` dispatches the
-/// request to the relay handler, and `command_tx` sends the response back to the
-/// swarm via `SwarmCommand::SendRelayResponse`.
+/// Relay requests (UpdateContent, DeleteContent, InvalidateTokens, MirrorPair) are
+/// processed in spawned tasks to avoid blocking the swarm loop. `relay_tx`
+/// dispatches the request to the relay handler, and `command_tx` sends the
+/// response back to the swarm via `SwarmCommand::SendRelayResponse`.
 #[derive(Clone)]
 struct RelayChannels {
     relay_tx: mpsc::Sender<RelayRequest>,
-    command_tx: mpsc::Sender<SwarmCommand>,
+    command_tx: CommandSender<SwarmCommand>,
 }
 
 /// libp2p-based network implementation.
 pub struct Libp2pNetwork {
     local_peer_id: PeerId,
-    command_tx: mpsc::Sender<SwarmCommand>,
+    /// Identity keypair, used to sign published domain events.
+    keypair: libp2p::identity::Keypair,
+    /// Bounded queue of commands for the swarm event loop.
+    command_tx: CommandSender<SwarmCommand>,
     /// Connected peers and their addresses.
     ///
     /// Updated by the swarm event loop when connections are established/closed.
@@ -315,43 +499,26 @@ pub struct Libp2pNetwork {
     content_network_repo: Option<
         Arc<RwLock<dyn crate::port::persistence::PersistentContentRepository + Send + Sync>>,
     >,
+    /// Verified attestations presented by peers during identify.
+    ///
+    /// Updated by the swarm event loop; used to answer owner queries for placement.
+    peer_attestations: Arc<RwLock<HashMap<PeerId, NodeAttestation>>>,
+    /// Public reachability as last reported by AutoNAT.
+    reachability: Arc<RwLock<Reachability>>,
+    /// Peers identified since startup, with their listen addresses and the
+    /// time they were last identified (Unix seconds).
+    ///
+    /// Updated by the swarm event loop; persisted by node.rs as known peers.
+    identified_peers: Arc<RwLock<HashMap<PeerId, (Vec<Multiaddr>, u64)>>>,
+    /// Caps concurrent outgoing content transfers to each peer.
+    transfers: TransferLimiter,
+    /// Rolling round-trip times of connected peers.
+    ///
+    /// Updated by the swarm event loop from ping results.
+    peer_latency: Arc<RwLock<LatencyTracker>>,
 }
 
 impl Libp2pNetwork {
-    /// Create a new libp2p network with the given configuration.
-    /// Load an Ed25519 keypair from disk, or generate a new one and save it.
-    fn load_or_generate_peer_keypair(
-        data_dir: &std::path::Path,
-    ) -> Result<libp2p::identity::Keypair> {
-        let key_path = data_dir.join("peer_key.ed25519");
-
-        if key_path.exists() {
-            let key_bytes =
-                std::fs::read(&key_path).context("Failed to read peer keypair from disk")?;
-            let keypair = libp2p::identity::Keypair::ed25519_from_bytes(key_bytes)
-                .map_err(|e| anyhow::anyhow!("Failed to decode peer keypair: {:?}", e))?;
-            info!("Loaded peer keypair from {}", key_path.display());
-            Ok(keypair)
-        } else {
-            let keypair = libp2p::identity::Keypair::generate_ed25519();
-            // Extract the raw Ed25519 secret key bytes for persistence
-            if let Ok(ed25519_kp) = keypair.clone().try_into_ed25519() {
-                let secret_bytes = ed25519_kp.secret().as_ref().to_vec();
-                if let Some(parent) = key_path.parent() {
-                    std::fs::create_dir_all(parent)
-                        .context("Failed to create data directory for peer key")?;
-                }
-                std::fs::write(&key_path, &secret_bytes)
-                    .context("Failed to write peer keypair to disk")?;
-                info!(
-                    "Generated and saved new peer keypair to {}",
-                    key_path.display()
-                );
-            }
-            Ok(keypair)
-        }
-    }
-
     /// Create a new libp2p network with the given configuration.
     pub async fn new(
         config: Libp2pNetworkConfig,
@@ -371,7 +538,10 @@ impl Libp2pNetwork {
             Arc<RwLock<dyn crate::port::persistence::PersistentContentRepository + Send + Sync>>,
         >,
     ) -> Result<Self> {
-        let keypair = Self::load_or_generate_peer_keypair(&data_dir)?;
+        let keypair = config
+            .identity
+            .load_or_generate(&data_dir)
+            .context("Failed to load peer identity")?;
         let local_peer_id = PeerId::from(keypair.public());
 
         // Load or generate P-256 key for node authentication
@@ -382,12 +552,55 @@ impl Libp2pNetwork {
 
         info!("Local peer ID: {}", local_peer_id);
 
-        // Build transport
+        let relay_routing =
+            RelayRouting::new(&config.relay_addrs).context("Invalid relay address")?;
+        let autonat_servers = config
+            .autonat_servers
+            .iter()
+            .map(parse_relay_addr)
+            .collect::<Result<Vec<_>>>()
+            .context("Invalid AutoNAT server address")?;
+
+        // Build transport, including the relay client transport for circuits
+        let (relay_transport, relay_client) = relay::client::new(local_peer_id);
         let transport =
-            transport::build_transport(&keypair).context("Failed to build transport")?;
+            transport::build_transport_with_relay(&keypair, relay_transport, &config.transport)
+                .context("Failed to build transport")?;
+        if let Some(psk) = &config.transport.psk {
+            info!(
+                "Private network enabled (swarm key fingerprint {}); QUIC and WebRTC are disabled",
+                psk.fingerprint()
+            );
+        }
 
-        // Build behaviour
-        let behaviour = NodeBehaviour::new(local_peer_id, &keypair, BehaviourConfig::default())?;
+        // Build behaviour, presenting our attestation (if any) via identify
+        let mut behaviour_config = BehaviourConfig {
+            hide_listen_addrs: config.advertise_confirmed_addrs_only,
+            kademlia_mode: config.kademlia_mode,
+            gossipsub_scoring: Some(GossipsubScoring::for_topics(&config.gossipsub_topics)),
+            enforce_peer_allowlist: config.enforce_peer_allowlist,
+            max_transfers_per_peer: config.bandwidth.max_transfers_per_peer,
+            bandwidth: BandwidthLimiter::new(&config.bandwidth),
+            ..Default::default()
+        };
+        if let Some(attestation) = &config.node_attestation {
+            let account_id = node_attestation::verify_node_attestation(
+                attestation,
+                &local_peer_id.to_string(),
+                crate::domain::events::current_timestamp(),
+            )
+            .context("Invalid node attestation")?;
+            info!("Node attested by account {}", account_id);
+            behaviour_config.agent_version = node_attestation::encode_agent_version(
+                &behaviour_config.agent_version,
+                attestation,
+            );
+        }
+        let mut behaviour =
+            NodeBehaviour::new(local_peer_id, &keypair, behaviour_config, relay_client)?;
+        for (peer_id, addr) in autonat_servers {
+            behaviour.autonat.add_server(peer_id, Some(addr));
+        }
 
         // Create swarm with connection limits to prevent FD/memory exhaustion (M-3).
         // idle_connection_timeout is set higher than the default sync_interval (30s)
@@ -397,6 +610,20 @@ impl Libp2pNetwork {
 
         let mut swarm = Swarm::new(transport, behaviour, local_peer_id, swarm_config);
 
+        // Restore the operator's peer access list before any connection is made
+        for peer_id in &config.allowed_peers {
+            Self::apply_peer_access(swarm.behaviour_mut(), *peer_id, Some(PeerAccess::Allowed));
+        }
+        for peer_id in &config.blocked_peers {
+            Self::apply_peer_access(swarm.behaviour_mut(), *peer_id, Some(PeerAccess::Blocked));
+        }
+        if config.enforce_peer_allowlist {
+            info!(
+                "Peer allowlist enforced ({} allowed peers)",
+                config.allowed_peers.len()
+            );
+        }
+
         // Start listening on configured addresses
         for addr in &config.listen_addrs {
             match swarm.listen_on(addr.clone()) {
@@ -414,6 +641,18 @@ impl Libp2pNetwork {
             info!("Advertising external address: {}", addr);
         }
 
+        // Reserve a circuit slot on each relay so peers can reach us through it.
+        // Listening on `<relay>/p2p-circuit` dials the relay and requests the
+        // reservation; it is renewed by the relay client automatically.
+        for (relay_peer_id, relay_addr) in relay_routing.relays() {
+            swarm.add_peer_address(*relay_peer_id, relay_addr.clone());
+            let circuit_addr = RelayRouting::reservation_addr(relay_addr);
+            match swarm.listen_on(circuit_addr.clone()) {
+                Ok(_) => info!("Requesting relay reservation via {}", circuit_addr),
+                Err(e) => warn!("Failed to listen via relay {}: {}", circuit_addr, e),
+            }
+        }
+
         // Subscribe to gossipsub topics
         for topic_name in &config.gossipsub_topics {
             let topic = IdentTopic::new(topic_name);
@@ -439,8 +678,22 @@ impl Libp2pNetwork {
             info!("Added bootstrap node: {} at {}", peer_id, addr);
         }
 
-        // Bootstrap Kademlia if we have bootstrap nodes
-        if !config.bootstrap_nodes.is_empty() {
+        // Add peers remembered from earlier runs
+        for (peer_id, addrs) in &config.known_peers {
+            for addr in addrs {
+                swarm
+                    .behaviour_mut()
+                    .kademlia
+                    .add_address(peer_id, addr.clone());
+                swarm.add_peer_address(*peer_id, addr.clone());
+            }
+        }
+        if !config.known_peers.is_empty() {
+            info!("Added {} known peers", config.known_peers.len());
+        }
+
+        // Bootstrap Kademlia if we have bootstrap nodes or known peers
+        if !config.bootstrap_nodes.is_empty() || !config.known_peers.is_empty() {
             if let Err(e) = swarm.behaviour_mut().kademlia.bootstrap() {
                 warn!("Failed to bootstrap Kademlia: {:?}", e);
             }
@@ -448,9 +701,13 @@ impl Libp2pNetwork {
 
         let connected_peers = Arc::new(RwLock::new(HashMap::new()));
         let connected_peers_clone = connected_peers.clone();
+        let peer_attestations = Arc::new(RwLock::new(HashMap::new()));
+        let reachability = Arc::new(RwLock::new(Reachability::default()));
+        let identified_peers = Arc::new(RwLock::new(HashMap::new()));
+        let peer_latency = Arc::new(RwLock::new(LatencyTracker::default()));
 
-        // Create command channel
-        let (command_tx, command_rx) = mpsc::channel(256);
+        // Create command queue
+        let (command_tx, command_rx) = command_queue(config.command_queue.clone());
 
         // Create broadcast channel for received events
         let (event_tx, _) = broadcast::channel(256);
@@ -458,7 +715,11 @@ impl Libp2pNetwork {
 
         // Clone for swarm loop
         let crdt_repo_clone = crdt_repo.clone();
-        let data_dir_clone = data_dir.clone();
+        let storage_dirs = if config.storage_dirs.is_empty() {
+            vec![data_dir.clone()]
+        } else {
+            config.storage_dirs.clone()
+        };
         let p256_signing_key_clone = p256_signing_key.clone();
 
         // Create relay request channel
@@ -470,20 +731,30 @@ impl Libp2pNetwork {
             command_tx: command_tx.clone(),
         };
         let content_network_repo_clone = content_network_repo.clone();
+        let sync_rules = Arc::new(config.sync_rules.clone());
         tokio::spawn(Self::run_swarm_loop(
             swarm,
             command_rx,
             connected_peers_clone,
             event_tx_clone,
             crdt_repo_clone,
-            data_dir_clone,
+            storage_dirs,
             p256_signing_key_clone,
             relay_channels,
             content_network_repo_clone,
+            sync_rules,
+            peer_attestations.clone(),
+            relay_routing,
+            reachability.clone(),
+            identified_peers.clone(),
+            peer_latency.clone(),
+            config.random_walk_interval,
+            config.bandwidth.max_transfers_per_peer,
         ));
 
         Ok(Self {
             local_peer_id,
+            keypair,
             command_tx,
             connected_peers,
             event_rx: event_tx,
@@ -492,6 +763,11 @@ impl Libp2pNetwork {
             p256_public_key,
             relay_request_rx: tokio::sync::Mutex::new(Some(relay_rx)),
             content_network_repo,
+            peer_attestations,
+            reachability,
+            identified_peers,
+            transfers: TransferLimiter::new(config.bandwidth.max_transfers_per_peer),
+            peer_latency,
         })
     }
 
@@ -512,14 +788,38 @@ impl Libp2pNetwork {
                 addr,
                 reply: reply_tx,
             })
-            .await
-            .map_err(|_| anyhow::anyhow!("Failed to send dial command"))?;
+            .await?;
         tokio::time::timeout(PEER_NETWORK_TIMEOUT, reply_rx)
             .await
             .map_err(|_| anyhow::anyhow!("dial timed out"))?
             .map_err(|_| anyhow::anyhow!("Dial response channel closed"))?
     }
 
+    /// Get the gossipsub mesh peers of each subscribed topic, keyed by topic.
+    ///
+    /// A published message reaches a peer directly only once the peer is in
+    /// the mesh of the topic, which happens on a heartbeat after connecting.
+    pub async fn mesh_peers(&self) -> BTreeMap<String, Vec<String>> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        if self
+            .command_tx
+            .send(SwarmCommand::GetMeshPeers { reply: reply_tx })
+            .await
+            .is_err()
+        {
+            return BTreeMap::new();
+        }
+        reply_rx
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(topic, peers)| {
+                let peers = peers.iter().map(PeerId::to_string).collect();
+                (topic, peers)
+            })
+            .collect()
+    }
+
     /// Take the relay request receiver.
     ///
     /// This can only be called once. Returns None on subsequent calls.
@@ -542,6 +842,48 @@ impl Libp2pNetwork {
         reply_rx.await.unwrap_or_default()
     }
 
+    /// Get the swarm command queue counters.
+    pub fn command_queue_metrics(&self) -> CommandQueueMetrics {
+        self.command_tx.metrics()
+    }
+
+    /// Whether this node is publicly reachable, as last reported by AutoNAT.
+    pub async fn reachability(&self) -> Reachability {
+        self.reachability.read().await.clone()
+    }
+
+    /// Currently connected peers and the addresses they are connected on.
+    pub async fn connected_peers(&self) -> Vec<(String, Vec<String>)> {
+        let mut peers: Vec<(String, Vec<String>)> = self
+            .connected_peers
+            .read()
+            .await
+            .iter()
+            .map(|(peer_id, addrs)| {
+                (
+                    peer_id.to_string(),
+                    addrs.iter().map(|addr| addr.to_string()).collect(),
+                )
+            })
+            .collect();
+        peers.sort();
+        peers
+    }
+
+    /// Peers identified since startup, for persisting as known peers.
+    pub async fn identified_peers(&self) -> Vec<KnownPeer> {
+        self.identified_peers
+            .read()
+            .await
+            .iter()
+            .map(|(peer_id, (addrs, last_seen))| KnownPeer {
+                peer_id: peer_id.to_string(),
+                addrs: addrs.iter().map(|addr| addr.to_string()).collect(),
+                last_seen: *last_seen,
+            })
+            .collect()
+    }
+
     /// Run the swarm event loop.
     #[allow(clippy::too_many_arguments)]
     async fn run_swarm_loop(
@@ -550,16 +892,34 @@ impl Libp2pNetwork {
         connected_peers: Arc<RwLock<HashMap<PeerId, Vec<Multiaddr>>>>,
         event_tx: broadcast::Sender<ReceivedEvent>,
         crdt_repo: Arc<dyn ContentRepository>,
-        data_dir: PathBuf,
+        storage_dirs: Vec<PathBuf>,
         p256_signing_key: Arc<crate::infrastructure::key_management::NodeKeyPair>,
         relay_channels: RelayChannels,
         content_network_repo: Option<
             Arc<RwLock<dyn crate::port::persistence::PersistentContentRepository + Send + Sync>>,
         >,
+        sync_rules: Arc<SyncRules>,
+        peer_attestations: Arc<RwLock<HashMap<PeerId, NodeAttestation>>>,
+        mut relay_routing: RelayRouting,
+        reachability: Arc<RwLock<Reachability>>,
+        identified_peers: Arc<RwLock<HashMap<PeerId, (Vec<Multiaddr>, u64)>>>,
+        peer_latency: Arc<RwLock<LatencyTracker>>,
+        random_walk_interval: Option<Duration>,
+        max_transfers_per_peer: usize,
     ) {
-        let mut pending = PendingRequests::default();
+        let mut pending = PendingRequests {
+            inbound_transfers: InboundTransfers::new(max_transfers_per_peer),
+            ..Default::default()
+        };
         let mut cleanup_interval = tokio::time::interval(Duration::from_secs(60));
         cleanup_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        // The timer branch is disabled when the random walk is off.
+        let random_walk_period = random_walk_interval.unwrap_or(DEFAULT_RANDOM_WALK_INTERVAL);
+        let mut random_walk = tokio::time::interval_at(
+            tokio::time::Instant::now() + random_walk_period,
+            random_walk_period,
+        );
+        random_walk.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
         loop {
             tokio::select! {
@@ -569,12 +929,47 @@ impl Libp2pNetwork {
                 }
                 // Handle swarm events
                 event = swarm.select_next_some() => {
-                    Self::handle_swarm_event(&mut swarm, &mut pending, &connected_peers, &event_tx, &crdt_repo, &data_dir, &p256_signing_key, &relay_channels, &content_network_repo, event).await;
+                    Self::handle_swarm_event(&mut swarm, &mut pending, &connected_peers, &event_tx, &crdt_repo, &storage_dirs, &p256_signing_key, &relay_channels, &content_network_repo, &sync_rules, &peer_attestations, &mut relay_routing, &reachability, &identified_peers, &peer_latency, event).await;
                 }
                 // Periodic cleanup of stale pending requests
                 _ = cleanup_interval.tick() => {
                     pending.cleanup_stale();
                 }
+                // Look up a random peer ID to discover peers beyond our buckets
+                _ = random_walk.tick(), if random_walk_interval.is_some() => {
+                    let target = PeerId::random();
+                    debug!("Starting random walk towards {}", target);
+                    swarm.behaviour_mut().kademlia.get_closest_peers(target);
+                }
+            }
+        }
+    }
+
+    /// Apply an operator access decision to the connection gating
+    /// behaviours. `None` clears any previous decision.
+    fn apply_peer_access(
+        behaviour: &mut NodeBehaviour,
+        peer_id: PeerId,
+        access: Option<PeerAccess>,
+    ) {
+        match access {
+            Some(PeerAccess::Blocked) => {
+                behaviour.blocked_peers.block_peer(peer_id);
+                if let Some(allowed) = behaviour.allowed_peers.as_mut() {
+                    allowed.disallow_peer(peer_id);
+                }
+            }
+            Some(PeerAccess::Allowed) => {
+                behaviour.blocked_peers.unblock_peer(peer_id);
+                if let Some(allowed) = behaviour.allowed_peers.as_mut() {
+                    allowed.allow_peer(peer_id);
+                }
+            }
+            None => {
+                behaviour.blocked_peers.unblock_peer(peer_id);
+                if let Some(allowed) = behaviour.allowed_peers.as_mut() {
+                    allowed.disallow_peer(peer_id);
+                }
             }
         }
     }
@@ -610,14 +1005,37 @@ impl Libp2pNetwork {
             SwarmCommand::FetchContent {
                 peer_id,
                 content_id,
+                capability,
                 reply,
             } => {
-                let request_id = swarm
-                    .behaviour_mut()
-                    .request_response
-                    .send_request(&peer_id, ContentRequest::FetchContent { content_id });
+                let request_id = swarm.behaviour_mut().request_response.send_request(
+                    &peer_id,
+                    ContentRequest::FetchContent {
+                        content_id,
+                        capability,
+                    },
+                );
                 pending.content_fetches.insert(request_id, reply);
             }
+            SwarmCommand::FetchContentRange {
+                peer_id,
+                content_id,
+                offset,
+                length,
+                capability,
+                reply,
+            } => {
+                let request_id = swarm.behaviour_mut().request_response.send_request(
+                    &peer_id,
+                    ContentRequest::FetchContentRange {
+                        content_id,
+                        offset,
+                        length,
+                        capability,
+                    },
+                );
+                pending.content_range_fetches.insert(request_id, reply);
+            }
             SwarmCommand::PublishProvider { key, reply } => {
                 let key = kad::RecordKey::new(&key);
                 let result = swarm
@@ -628,6 +1046,11 @@ impl Libp2pNetwork {
                     .map_err(|e| anyhow::anyhow!("Failed to start providing: {:?}", e));
                 let _ = reply.send(result);
             }
+            SwarmCommand::RemoveProvider { key, reply } => {
+                let key = kad::RecordKey::new(&key);
+                swarm.behaviour_mut().kademlia.stop_providing(&key);
+                let _ = reply.send(Ok(()));
+            }
             SwarmCommand::Dial { addr, reply } => {
                 let result = swarm
                     .dial(addr.clone())
@@ -638,10 +1061,19 @@ impl Libp2pNetwork {
                 let addrs: Vec<Multiaddr> = swarm.listeners().cloned().collect();
                 let _ = reply.send(addrs);
             }
+            SwarmCommand::SetPeerAccess {
+                peer_id,
+                access,
+                reply,
+            } => {
+                Self::apply_peer_access(swarm.behaviour_mut(), peer_id, access);
+                let _ = reply.send(Ok(()));
+            }
             SwarmCommand::FetchOperations {
                 peer_id,
                 genesis_cid,
                 since_version,
+                capability,
                 reply,
             } => {
                 let request_id = swarm.behaviour_mut().request_response.send_request(
@@ -649,6 +1081,8 @@ impl Libp2pNetwork {
                     ContentRequest::FetchOperations {
                         genesis_cid,
                         since_version,
+                        accept_snapshot: true,
+                        capability,
                     },
                 );
                 pending.operation_fetches.insert(request_id, reply);
@@ -656,20 +1090,15 @@ impl Libp2pNetwork {
             SwarmCommand::PushOperations {
                 peer_id,
                 genesis_cid,
-                operations,
+                batch,
                 bootstrap,
                 reply,
             } => {
-                // Convert SerializedOperation to Vec<u8> for wire format
-                let wire_ops: Vec<Vec<u8>> = operations
-                    .iter()
-                    .filter_map(|op| serde_json::to_vec(op).ok())
-                    .collect();
                 let request_id = swarm.behaviour_mut().request_response.send_request(
                     &peer_id,
                     ContentRequest::PushOperations {
                         genesis_cid,
-                        operations: wire_ops,
+                        batch,
                         bootstrap,
                     },
                 );
@@ -680,6 +1109,28 @@ impl Libp2pNetwork {
                 let query_id = swarm.behaviour_mut().kademlia.get_providers(key);
                 pending.kad_provider_queries.insert(query_id, reply);
             }
+            SwarmCommand::PutRecord { key, value, reply } => {
+                let record = kad::Record::new(kad::RecordKey::new(&key), value);
+                match swarm
+                    .behaviour_mut()
+                    .kademlia
+                    .put_record(record, kad::Quorum::One)
+                {
+                    Ok(query_id) => {
+                        pending.kad_put_queries.insert(query_id, reply);
+                    }
+                    Err(e) => {
+                        let _ = reply.send(Err(anyhow::anyhow!("Failed to store record: {:?}", e)));
+                    }
+                }
+            }
+            SwarmCommand::GetRecords { key, reply } => {
+                let key = kad::RecordKey::new(&key);
+                let query_id = swarm.behaviour_mut().kademlia.get_record(key);
+                pending
+                    .kad_record_queries
+                    .insert(query_id, (reply, Vec::new()));
+            }
             SwarmCommand::QueryPublicKeys {
                 peer_id,
                 node_ids,
@@ -756,6 +1207,17 @@ impl Libp2pNetwork {
                     .relay_invalidate_tokens_queries
                     .insert(request_id, reply);
             }
+            SwarmCommand::RequestMirrorPairing {
+                peer_id,
+                request,
+                reply,
+            } => {
+                let request_id = swarm
+                    .behaviour_mut()
+                    .request_response
+                    .send_request(&peer_id, ContentRequest::MirrorPair { request });
+                pending.mirror_pairings.insert(request_id, reply);
+            }
             SwarmCommand::SendRelayResponse { channel, response } => {
                 if let Err(e) = swarm
                     .behaviour_mut()
@@ -765,6 +1227,17 @@ impl Libp2pNetwork {
                     error!("Failed to send relay response: {:?}", e);
                 }
             }
+            SwarmCommand::GetMeshPeers { reply } => {
+                let gossipsub = &swarm.behaviour().gossipsub;
+                let mesh = gossipsub
+                    .topics()
+                    .map(|topic| {
+                        let peers = gossipsub.mesh_peers(topic).copied().collect();
+                        (topic.as_str().to_string(), peers)
+                    })
+                    .collect();
+                let _ = reply.send(mesh);
+            }
         }
     }
 
@@ -776,29 +1249,36 @@ impl Libp2pNetwork {
         connected_peers: &Arc<RwLock<HashMap<PeerId, Vec<Multiaddr>>>>,
         event_tx: &broadcast::Sender<ReceivedEvent>,
         crdt_repo: &Arc<dyn ContentRepository>,
-        data_dir: &std::path::Path,
+        storage_dirs: &[PathBuf],
         p256_signing_key: &Arc<crate::infrastructure::key_management::NodeKeyPair>,
         relay_channels: &RelayChannels,
         content_network_repo: &Option<
             Arc<RwLock<dyn crate::port::persistence::PersistentContentRepository + Send + Sync>>,
         >,
+        sync_rules: &SyncRules,
+        peer_attestations: &Arc<RwLock<HashMap<PeerId, NodeAttestation>>>,
+        relay_routing: &mut RelayRouting,
+        reachability: &Arc<RwLock<Reachability>>,
+        identified_peers: &Arc<RwLock<HashMap<PeerId, (Vec<Multiaddr>, u64)>>>,
+        peer_latency: &Arc<RwLock<LatencyTracker>>,
         event: SwarmEvent<NodeBehaviourEvent>,
     ) {
         match event {
             SwarmEvent::Behaviour(NodeBehaviourEvent::Kademlia(kad_event)) => {
-                Self::handle_kademlia_event(pending, kad_event).await;
+                Self::handle_kademlia_event(swarm, pending, kad_event).await;
             }
             SwarmEvent::Behaviour(NodeBehaviourEvent::Gossipsub(gossip_event)) => {
-                Self::handle_gossipsub_event(event_tx, *gossip_event).await;
+                Self::handle_gossipsub_event(swarm, event_tx, *gossip_event).await;
             }
             SwarmEvent::Behaviour(NodeBehaviourEvent::RequestResponse(rr_event)) => {
                 Self::handle_request_response_event(
                     swarm,
                     pending,
                     crdt_repo,
-                    data_dir,
+                    storage_dirs,
                     relay_channels,
                     content_network_repo,
+                    sync_rules,
                     rr_event,
                 )
                 .await;
@@ -808,12 +1288,69 @@ impl Libp2pNetwork {
                     .await;
             }
             SwarmEvent::Behaviour(NodeBehaviourEvent::Identify(identify_event)) => {
-                Self::handle_identify_event(swarm, *identify_event).await;
+                Self::handle_identify_event(
+                    swarm,
+                    peer_attestations,
+                    identified_peers,
+                    *identify_event,
+                )
+                .await;
+            }
+            SwarmEvent::Behaviour(NodeBehaviourEvent::Ping(ping_event)) => {
+                match ping_event.result {
+                    Ok(rtt) => peer_latency
+                        .write()
+                        .await
+                        .record(&ping_event.peer.to_string(), rtt),
+                    Err(e) => debug!("Ping to {} failed: {}", ping_event.peer, e),
+                }
             }
             #[cfg(not(target_arch = "wasm32"))]
             SwarmEvent::Behaviour(NodeBehaviourEvent::Mdns(mdns_event)) => {
                 Self::handle_mdns_event(swarm, connected_peers, mdns_event).await;
             }
+            #[cfg(not(target_arch = "wasm32"))]
+            SwarmEvent::Behaviour(NodeBehaviourEvent::RelayClient(relay_event)) => {
+                Self::handle_relay_client_event(*relay_event);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            SwarmEvent::Behaviour(NodeBehaviourEvent::Dcutr(dcutr_event)) => {
+                match dcutr_event.result {
+                    Ok(_) => info!(
+                        "Hole punch to {} succeeded; using a direct connection",
+                        dcutr_event.remote_peer_id
+                    ),
+                    Err(e) => debug!(
+                        "Hole punch to {} failed, staying relayed: {}",
+                        dcutr_event.remote_peer_id, e
+                    ),
+                }
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            SwarmEvent::Behaviour(NodeBehaviourEvent::Autonat(autonat_event)) => {
+                Self::handle_autonat_event(reachability, *autonat_event).await;
+            }
+            SwarmEvent::OutgoingConnectionError {
+                peer_id: Some(peer_id),
+                error,
+                ..
+            } => {
+                // A direct dial failed (e.g. the peer is behind NAT): retry via
+                // the configured relays. DCUtR then tries to go direct.
+                if matches!(error, DialError::Transport(_) | DialError::NoAddresses) {
+                    let relayed_addrs = relay_routing.relayed_dial_addrs(peer_id);
+                    if !relayed_addrs.is_empty() {
+                        debug!("Dial to {} failed ({}); retrying via relay", peer_id, error);
+                        let opts = DialOpts::peer_id(peer_id)
+                            .addresses(relayed_addrs)
+                            .condition(PeerCondition::Disconnected)
+                            .build();
+                        if let Err(e) = swarm.dial(opts) {
+                            debug!("Relayed dial to {} failed: {}", peer_id, e);
+                        }
+                    }
+                }
+            }
             SwarmEvent::ConnectionEstablished {
                 peer_id,
                 endpoint,
@@ -822,6 +1359,7 @@ impl Libp2pNetwork {
             } => {
                 let addr = endpoint.get_remote_address().clone();
                 info!("Connection established with {} at {}", peer_id, addr);
+                relay_routing.on_connection_established(&peer_id);
 
                 // Enforce connection limit (M-3): close excess connections to prevent
                 // FD/memory exhaustion. Limit total unique peers to 256.
@@ -841,17 +1379,84 @@ impl Libp2pNetwork {
             SwarmEvent::ConnectionClosed { peer_id, .. } => {
                 info!("Connection closed with {}", peer_id);
                 connected_peers.write().await.remove(&peer_id);
+                peer_latency.write().await.remove(&peer_id.to_string());
             }
             SwarmEvent::NewListenAddr { address, .. } => {
                 info!("Listening on {}", address);
             }
+            SwarmEvent::ExternalAddrConfirmed { address } => {
+                info!("External address confirmed: {}", address);
+            }
+            SwarmEvent::ExternalAddrExpired { address } => {
+                info!("External address expired: {}", address);
+            }
             _ => {}
         }
     }
 
-    async fn handle_kademlia_event(pending: &mut PendingRequests, event: kad::Event) {
+    /// Track reachability changes reported by AutoNAT.
+    ///
+    /// AutoNAT itself confirms the external address it was dialed back on,
+    /// which identify then announces and which switches Kademlia into server
+    /// mode; unreachable nodes stay Kademlia clients so peers don't add them
+    /// to their routing tables.
+    async fn handle_autonat_event(reachability: &Arc<RwLock<Reachability>>, event: autonat::Event) {
+        match event {
+            autonat::Event::StatusChanged { old, new } => {
+                let status = match new {
+                    autonat::NatStatus::Public(addr) => Reachability::Public(addr),
+                    autonat::NatStatus::Private => Reachability::Private,
+                    autonat::NatStatus::Unknown => Reachability::Unknown,
+                };
+                match &status {
+                    Reachability::Public(addr) => info!("Node is publicly reachable at {}", addr),
+                    Reachability::Private => {
+                        info!("Node is not publicly reachable (was {:?})", old)
+                    }
+                    Reachability::Unknown => debug!("Reachability unknown (was {:?})", old),
+                }
+                *reachability.write().await = status;
+            }
+            autonat::Event::InboundProbe(probe) => {
+                debug!("AutoNAT inbound probe: {:?}", probe);
+            }
+            autonat::Event::OutboundProbe(probe) => {
+                debug!("AutoNAT outbound probe: {:?}", probe);
+            }
+        }
+    }
+
+    fn handle_relay_client_event(event: relay::client::Event) {
+        match event {
+            relay::client::Event::ReservationReqAccepted {
+                relay_peer_id,
+                renewal,
+                ..
+            } => {
+                if renewal {
+                    debug!("Relay reservation renewed with {}", relay_peer_id);
+                } else {
+                    info!("Relay reservation accepted by {}", relay_peer_id);
+                }
+            }
+            relay::client::Event::OutboundCircuitEstablished { relay_peer_id, .. } => {
+                debug!("Outbound circuit established via {}", relay_peer_id);
+            }
+            relay::client::Event::InboundCircuitEstablished { src_peer_id, .. } => {
+                debug!("Inbound circuit established from {}", src_peer_id);
+            }
+        }
+    }
+
+    async fn handle_kademlia_event(
+        swarm: &mut Swarm<NodeBehaviour>,
+        pending: &mut PendingRequests,
+        event: kad::Event,
+    ) {
         match event {
-            kad::Event::OutboundQueryProgressed { id, result, .. } => {
+            kad::Event::OutboundQueryProgressed {
+                id, result, step, ..
+            } => {
                 match result {
                     kad::QueryResult::GetClosestPeers(Ok(ok)) => {
                         if let Some(reply) = pending.kad_queries.remove(&id) {
@@ -887,9 +1492,92 @@ impl Libp2pNetwork {
                                 reply.send(Err(anyhow::anyhow!("Provider query failed: {:?}", e)));
                         }
                     }
+                    kad::QueryResult::PutRecord(result) => {
+                        if let Some(reply) = pending.kad_put_queries.remove(&id) {
+                            let result = match result {
+                                Ok(_) => Ok(()),
+                                // The record is stored locally and republished
+                                // once peers are reachable.
+                                Err(kad::PutRecordError::QuorumFailed { key, .. }) => {
+                                    debug!("No peer stored record {:?} yet", key);
+                                    Ok(())
+                                }
+                                Err(e) => Err(anyhow::anyhow!("Failed to store record: {:?}", e)),
+                            };
+                            let _ = reply.send(result);
+                        }
+                    }
+                    kad::QueryResult::GetRecord(Ok(kad::GetRecordOk::FoundRecord(found))) => {
+                        if let Some((_, values)) = pending.kad_record_queries.get_mut(&id) {
+                            values.push(found.record.value);
+                        }
+                        if step.last {
+                            if let Some((reply, values)) = pending.kad_record_queries.remove(&id) {
+                                let _ = reply.send(Ok(values));
+                            }
+                        }
+                    }
+                    kad::QueryResult::GetRecord(Ok(
+                        kad::GetRecordOk::FinishedWithNoAdditionalRecord { .. },
+                    )) => {
+                        if let Some((reply, values)) = pending.kad_record_queries.remove(&id) {
+                            let _ = reply.send(Ok(values));
+                        }
+                    }
+                    kad::QueryResult::GetRecord(Err(e)) => {
+                        if let Some((reply, values)) = pending.kad_record_queries.remove(&id) {
+                            // Records found before the query failed are still usable.
+                            let result = match e {
+                                kad::GetRecordError::NotFound { .. } => Ok(values),
+                                _ if !values.is_empty() => Ok(values),
+                                e => Err(anyhow::anyhow!("Record query failed: {:?}", e)),
+                            };
+                            let _ = reply.send(result);
+                        }
+                    }
                     _ => {}
                 }
             }
+            // Record filtering is enabled, so records pushed by peers are only
+            // stored here once their signature checks out.
+            kad::Event::InboundRequest {
+                request:
+                    kad::InboundRequest::PutRecord {
+                        source,
+                        record: Some(record),
+                        ..
+                    },
+            } => {
+                let store = swarm.behaviour_mut().kademlia.store_mut();
+                let existing = store.get(&record.key).map(|r| r.value.clone());
+                if dht_record::should_store(
+                    &record.key.to_vec(),
+                    existing.as_deref(),
+                    &record.value,
+                    crate::domain::events::current_timestamp(),
+                ) {
+                    if let Err(e) = store.put(record) {
+                        warn!("Failed to store record from {}: {:?}", source, e);
+                    }
+                } else {
+                    debug!("Ignoring invalid or outdated record from {}", source);
+                }
+            }
+            kad::Event::InboundRequest {
+                request:
+                    kad::InboundRequest::AddProvider {
+                        record: Some(record),
+                    },
+            } => {
+                if let Err(e) = swarm
+                    .behaviour_mut()
+                    .kademlia
+                    .store_mut()
+                    .add_provider(record)
+                {
+                    warn!("Failed to store provider record: {:?}", e);
+                }
+            }
             kad::Event::RoutingUpdated { peer, .. } => {
                 debug!("Kademlia routing updated for peer: {}", peer);
             }
@@ -898,12 +1586,14 @@ impl Libp2pNetwork {
     }
 
     async fn handle_gossipsub_event(
+        swarm: &mut Swarm<NodeBehaviour>,
         event_tx: &broadcast::Sender<ReceivedEvent>,
         event: gossipsub::Event,
     ) {
         match event {
             gossipsub::Event::Message {
                 propagation_source,
+                message_id,
                 message,
                 ..
             } => {
@@ -913,9 +1603,10 @@ impl Libp2pNetwork {
                     message.data.len()
                 );
 
-                // Try to deserialize as a domain Event
-                match serde_json::from_slice::<Event>(&message.data) {
-                    Ok(domain_event) => {
+                // Messages are only forwarded once validated. Rejecting a
+                // malformed one also lowers the sender's peer score.
+                let acceptance = match decode_gossip_payload(&message.data) {
+                    GossipPayload::Event(domain_event) => {
                         info!(
                             "Received domain event from {}: {:?}",
                             propagation_source,
@@ -924,19 +1615,28 @@ impl Libp2pNetwork {
 
                         let received = ReceivedEvent {
                             source: propagation_source.to_string(),
-                            event: domain_event,
+                            event: *domain_event,
                         };
 
                         // Broadcast to all subscribers
                         if let Err(e) = event_tx.send(received) {
                             debug!("No subscribers for received event: {}", e);
                         }
+                        gossipsub::MessageAcceptance::Accept
                     }
-                    Err(e) => {
-                        // Not a domain event, might be a CRDT operation or other message
-                        debug!("Failed to deserialize gossipsub message as Event: {}", e);
+                    GossipPayload::CrdtOperation => gossipsub::MessageAcceptance::Accept,
+                    GossipPayload::Malformed(reason) => {
+                        warn!(
+                            "Rejecting malformed gossipsub message from {}: {}",
+                            propagation_source, reason
+                        );
+                        gossipsub::MessageAcceptance::Reject
                     }
-                }
+                };
+                let _ = swarm
+                    .behaviour_mut()
+                    .gossipsub
+                    .report_message_validation_result(&message_id, &propagation_source, acceptance);
             }
             gossipsub::Event::Subscribed { peer_id, topic } => {
                 debug!("Peer {} subscribed to {}", peer_id, topic);
@@ -952,11 +1652,12 @@ impl Libp2pNetwork {
         swarm: &mut Swarm<NodeBehaviour>,
         pending: &mut PendingRequests,
         crdt_repo: &Arc<dyn ContentRepository>,
-        data_dir: &std::path::Path,
+        storage_dirs: &[PathBuf],
         relay_channels: &RelayChannels,
         content_network_repo: &Option<
             Arc<RwLock<dyn crate::port::persistence::PersistentContentRepository + Send + Sync>>,
         >,
+        sync_rules: &SyncRules,
         event: request_response::Event<ContentRequest, ContentResponse>,
     ) {
         match event {
@@ -964,15 +1665,34 @@ impl Libp2pNetwork {
                 request_response::Message::Request {
                     request, channel, ..
                 } => {
+                    // Counted until ResponseSent or InboundFailure.
+                    if !pending.inbound_transfers.begin(peer) {
+                        warn!(
+                            "Refusing request from {}: too many concurrent transfers",
+                            peer
+                        );
+                        let response = ContentResponse::Error {
+                            message: "Too many concurrent transfers".to_string(),
+                        };
+                        if let Err(e) = swarm
+                            .behaviour_mut()
+                            .request_response
+                            .send_response(channel, response)
+                        {
+                            error!("Failed to send response: {:?}", e);
+                        }
+                        return;
+                    }
                     Self::handle_incoming_request(
                         swarm,
                         peer,
                         request,
                         channel,
                         crdt_repo,
-                        data_dir,
+                        storage_dirs,
                         relay_channels,
                         content_network_repo,
+                        sync_rules,
                     )
                     .await;
                 }
@@ -995,6 +1715,9 @@ impl Libp2pNetwork {
                 if let Some(reply) = pending.content_fetches.remove(&request_id) {
                     let _ = reply.send(Err(anyhow::anyhow!("{}", err_msg)));
                 }
+                if let Some(reply) = pending.content_range_fetches.remove(&request_id) {
+                    let _ = reply.send(Err(anyhow::anyhow!("{}", err_msg)));
+                }
                 if let Some(reply) = pending.operation_fetches.remove(&request_id) {
                     let _ = reply.send(Err(anyhow::anyhow!("{}", err_msg)));
                 }
@@ -1013,8 +1736,17 @@ impl Libp2pNetwork {
                 if let Some(reply) = pending.relay_invalidate_tokens_queries.remove(&request_id) {
                     let _ = reply.send(Err(anyhow::anyhow!("{}", err_msg)));
                 }
+                if let Some(reply) = pending.mirror_pairings.remove(&request_id) {
+                    let _ = reply.send(Err(anyhow::anyhow!("{}", err_msg)));
+                }
+            }
+            request_response::Event::ResponseSent { peer, .. } => {
+                pending.inbound_transfers.end(&peer);
+            }
+            request_response::Event::InboundFailure { peer, error, .. } => {
+                debug!("Inbound request from {} failed: {:?}", peer, error);
+                pending.inbound_transfers.end(&peer);
             }
-            _ => {}
         }
     }
 
@@ -1031,6 +1763,7 @@ impl Libp2pNetwork {
         sender_peer: &str,
         local_peer: &str,
         bootstrap: Option<&PushBootstrap>,
+        sync_rules: &SyncRules,
     ) -> std::result::Result<(), String> {
         let existing = repo
             .read()
@@ -1073,7 +1806,15 @@ impl Libp2pNetwork {
                 if bs.member_nodes.is_empty() {
                     return Err("bootstrap member_nodes is empty".to_string());
                 }
-
+                // Selective sync: refuse membership of content this node
+                // does not want to replicate.
+                if !sync_rules.accepts(&bs.attributes) {
+                    return Err(format!(
+                        "content {} does not match the selective sync rules of node {}",
+                        genesis_cid, local_peer
+                    ));
+                }
+
                 use crate::domain::content_network::ContentNetwork;
                 use crate::domain::value_objects::{ContentId, NodeId};
 
@@ -1109,14 +1850,77 @@ impl Libp2pNetwork {
         request: ContentRequest,
         channel: ResponseChannel<ContentResponse>,
         crdt_repo: &Arc<dyn ContentRepository>,
-        data_dir: &std::path::Path,
+        storage_dirs: &[PathBuf],
         relay_channels: &RelayChannels,
         content_network_repo: &Option<
             Arc<RwLock<dyn crate::port::persistence::PersistentContentRepository + Send + Sync>>,
         >,
+        sync_rules: &SyncRules,
     ) {
         debug!("Received request from {}: {:?}", peer, request);
 
+        // Read requests are served to members of the content network only.
+        // Other peers must present a read capability, which is verified by
+        // the application layer in a background task like a relay request.
+        if let Some((content_id, capability)) = Self::read_request_target(&request) {
+            if !Self::is_content_member(content_network_repo, &content_id, &peer).await {
+                let Some(capability) = capability else {
+                    let response = ContentResponse::Error {
+                        message: format!(
+                            "Peer {} is not a member of content network {}",
+                            peer, content_id
+                        ),
+                    };
+                    if let Err(e) = swarm
+                        .behaviour_mut()
+                        .request_response
+                        .send_response(channel, response)
+                    {
+                        error!("Failed to send response: {:?}", e);
+                    }
+                    return;
+                };
+
+                info!(
+                    "Verifying read capability of non-member {} for {}",
+                    peer, content_id
+                );
+                let channels = relay_channels.clone();
+                let crdt_repo = crdt_repo.clone();
+                tokio::spawn(async move {
+                    let (reply_tx, reply_rx) = oneshot::channel();
+                    let relay_req = RelayRequest {
+                        kind: RelayRequestKind::AuthorizeRead {
+                            content_id: content_id.clone(),
+                            requester_peer_id: peer.to_string(),
+                            capability,
+                        },
+                        reply: reply_tx,
+                    };
+                    let response = if channels.relay_tx.send(relay_req).await.is_ok() {
+                        match reply_rx.await {
+                            Ok(Ok(_)) => Self::read_response(&crdt_repo, request).await,
+                            Ok(Err(e)) => ContentResponse::Error {
+                                message: format!("Read of {} not authorized: {}", content_id, e),
+                            },
+                            Err(_) => ContentResponse::Error {
+                                message: "Relay handler dropped".to_string(),
+                            },
+                        }
+                    } else {
+                        ContentResponse::Error {
+                            message: "Relay channel closed".to_string(),
+                        }
+                    };
+                    let _ = channels
+                        .command_tx
+                        .send(SwarmCommand::SendRelayResponse { channel, response })
+                        .await;
+                });
+                return;
+            }
+        }
+
         // For relay requests (UpdateContent, DeleteContent, InvalidateTokens), we spawn a
         // background task to avoid blocking the swarm loop. The relay handler may need
         // to send SwarmCommands (e.g. publish_event, query_capacity) which would deadlock
@@ -1148,7 +1952,7 @@ impl Libp2pNetwork {
                     };
                     let response = if channels.relay_tx.send(relay_req).await.is_ok() {
                         match reply_rx.await {
-                            Ok(Ok(())) => ContentResponse::UpdateResult {
+                            Ok(Ok(_)) => ContentResponse::UpdateResult {
                                 content_id,
                                 success: true,
                             },
@@ -1195,7 +1999,7 @@ impl Libp2pNetwork {
                     };
                     let response = if channels.relay_tx.send(relay_req).await.is_ok() {
                         match reply_rx.await {
-                            Ok(Ok(())) => ContentResponse::DeleteResult {
+                            Ok(Ok(_)) => ContentResponse::DeleteResult {
                                 content_id,
                                 success: true,
                             },
@@ -1242,7 +2046,7 @@ impl Libp2pNetwork {
                     };
                     let response = if channels.relay_tx.send(relay_req).await.is_ok() {
                         match reply_rx.await {
-                            Ok(Ok(())) => ContentResponse::InvalidateTokensResult {
+                            Ok(Ok(_)) => ContentResponse::InvalidateTokensResult {
                                 content_id,
                                 success: true,
                             },
@@ -1265,113 +2069,78 @@ impl Libp2pNetwork {
                 });
                 return;
             }
+            ContentRequest::MirrorPair { request } => {
+                info!("Received mirror pairing request from {}", peer);
+                let channels = relay_channels.clone();
+                tokio::spawn(async move {
+                    let (reply_tx, reply_rx) = oneshot::channel();
+                    let relay_req = RelayRequest {
+                        kind: RelayRequestKind::MirrorPair {
+                            sender_peer_id: peer.to_string(),
+                            request,
+                        },
+                        reply: reply_tx,
+                    };
+                    let response = if channels.relay_tx.send(relay_req).await.is_ok() {
+                        match reply_rx.await {
+                            Ok(Ok(RelayReply::MirrorPairAccepted(acceptance))) => {
+                                ContentResponse::MirrorPairAccepted { acceptance }
+                            }
+                            Ok(Ok(RelayReply::Done)) => ContentResponse::Error {
+                                message: "Mirror pairing was not accepted".to_string(),
+                            },
+                            Ok(Err(e)) => ContentResponse::Error {
+                                message: format!("Mirror pairing failed: {}", e),
+                            },
+                            Err(_) => ContentResponse::Error {
+                                message: "Relay handler dropped".to_string(),
+                            },
+                        }
+                    } else {
+                        ContentResponse::Error {
+                            message: "Relay channel closed".to_string(),
+                        }
+                    };
+                    let _ = channels
+                        .command_tx
+                        .send(SwarmCommand::SendRelayResponse { channel, response })
+                        .await;
+                });
+                return;
+            }
             _ => {}
         }
 
         // Non-relay requests: handle synchronously in the swarm loop
         let response = match request {
-            ContentRequest::CapacityQuery => match disk_capacity::get_disk_capacity(data_dir) {
-                Ok((total, available)) => ContentResponse::CapacityResponse {
-                    total_capacity: total,
-                    available_capacity: available,
-                },
-                Err(e) => ContentResponse::Error {
-                    message: format!("Failed to get disk capacity: {}", e),
-                },
-            },
-            ContentRequest::FetchContent { content_id } => {
-                match crdt_repo.get_latest_with_version(&content_id).await {
-                    Ok(Some((data, version))) => ContentResponse::ContentData {
-                        content_id,
-                        data,
-                        version,
-                    },
-                    Ok(None) => ContentResponse::NotFound { content_id },
-                    Err(e) => ContentResponse::Error {
-                        message: format!("Failed to fetch content: {}", e),
-                    },
-                }
-            }
-            ContentRequest::SyncContent { content_id, .. } => {
-                // SyncContent returns the same as FetchContent (latest data)
-                match crdt_repo.get_latest_with_version(&content_id).await {
-                    Ok(Some((data, version))) => ContentResponse::ContentData {
-                        content_id,
-                        data,
-                        version,
-                    },
-                    Ok(None) => ContentResponse::NotFound { content_id },
+            ContentRequest::CapacityQuery => {
+                match disk_capacity::get_aggregate_disk_capacity(storage_dirs) {
+                    Ok(capacity) => {
+                        // Never advertise more than the storage budget allows.
+                        let (total, available) = match crdt_repo.storage_usage() {
+                            Some(usage) => usage.clamp_capacity(capacity),
+                            None => capacity,
+                        };
+                        ContentResponse::CapacityResponse {
+                            total_capacity: total,
+                            available_capacity: available,
+                            sync_rules: sync_rules.clone(),
+                        }
+                    }
                     Err(e) => ContentResponse::Error {
-                        message: format!("Failed to sync content: {}", e),
+                        message: format!("Failed to get disk capacity: {}", e),
                     },
                 }
             }
-            ContentRequest::FetchOperations {
-                genesis_cid,
-                since_version,
-            } => {
-                // Verify peer is a member of the content network
-                if let Some(repo) = content_network_repo {
-                    let is_member = repo
-                        .read()
-                        .await
-                        .get_content_network(&genesis_cid)
-                        .await
-                        .ok()
-                        .flatten()
-                        .map(|net| net.has_member_str(&peer.to_string()))
-                        .unwrap_or(false);
-                    if !is_member {
-                        ContentResponse::Error {
-                            message: format!(
-                                "Peer {} is not a member of content network {}",
-                                peer, genesis_cid
-                            ),
-                        }
-                    } else {
-                        match crdt_repo
-                            .get_operations(&genesis_cid, since_version.as_deref())
-                            .await
-                        {
-                            Ok(ops) => {
-                                let operations: Vec<Vec<u8>> = ops
-                                    .iter()
-                                    .filter_map(|op| serde_json::to_vec(op).ok())
-                                    .collect();
-                                ContentResponse::OperationsData {
-                                    genesis_cid,
-                                    operations,
-                                }
-                            }
-                            Err(e) => ContentResponse::Error {
-                                message: format!("Failed to fetch operations: {}", e),
-                            },
-                        }
-                    }
-                } else {
-                    match crdt_repo
-                        .get_operations(&genesis_cid, since_version.as_deref())
-                        .await
-                    {
-                        Ok(ops) => {
-                            let operations: Vec<Vec<u8>> = ops
-                                .iter()
-                                .filter_map(|op| serde_json::to_vec(op).ok())
-                                .collect();
-                            ContentResponse::OperationsData {
-                                genesis_cid,
-                                operations,
-                            }
-                        }
-                        Err(e) => ContentResponse::Error {
-                            message: format!("Failed to fetch operations: {}", e),
-                        },
-                    }
-                }
+            request @ (ContentRequest::FetchContent { .. }
+            | ContentRequest::FetchContentRange { .. }
+            | ContentRequest::SyncContent { .. }
+            | ContentRequest::FetchOperations { .. }) => {
+                Self::read_response(crdt_repo, request).await
             }
             ContentRequest::PushOperations {
                 genesis_cid,
-                operations,
+                batch,
                 bootstrap,
             } => {
                 // The receiver decides whether to accept this push. Cases:
@@ -1384,6 +2153,54 @@ impl Libp2pNetwork {
                 //   3. No local record AND no bootstrap: reject (unknown network).
                 //   4. `content_network_repo` is None (some test configurations):
                 //      fall through to apply_operations — legacy behavior.
+                //
+                // Denied (tombstoned) content is rejected up front so a
+                // bootstrap push cannot recreate its ContentNetwork record.
+                // A failed denylist lookup refuses the push as well: accepting
+                // it could bring tombstoned content back.
+                let refusal = match crdt_repo.is_denied(&genesis_cid).await {
+                    Ok(false) => None,
+                    Ok(true) => Some(format!("Content {} is denied by this node", genesis_cid)),
+                    Err(e) => {
+                        warn!("Denylist lookup failed for {}: {}", genesis_cid, e);
+                        Some(format!("Denylist lookup failed for {}", genesis_cid))
+                    }
+                };
+                if let Some(message) = refusal {
+                    let response = ContentResponse::Error { message };
+                    if let Err(e) = swarm
+                        .behaviour_mut()
+                        .request_response
+                        .send_response(channel, response)
+                    {
+                        error!("Failed to send response: {:?}", e);
+                    }
+                    return;
+                }
+
+                // A bootstrap push for content not stored yet is a new
+                // assignment: refuse it when the storage budget is nearly
+                // full, before the ContentNetwork record is persisted.
+                if let (Some(bs), Some(usage)) = (&bootstrap, crdt_repo.storage_usage()) {
+                    let stored = crdt_repo.exists(&genesis_cid).await.unwrap_or(false);
+                    if !stored && !usage.accepts_new_content(bs.attributes.size) {
+                        let response = ContentResponse::Error {
+                            message: format!(
+                                "Storage quota nearly full: {} of {} bytes used",
+                                usage.used_bytes, usage.quota_bytes
+                            ),
+                        };
+                        if let Err(e) = swarm
+                            .behaviour_mut()
+                            .request_response
+                            .send_response(channel, response)
+                        {
+                            error!("Failed to send response: {:?}", e);
+                        }
+                        return;
+                    }
+                }
+
                 if let Some(repo) = content_network_repo {
                     let local_id = swarm.local_peer_id().to_string();
                     let validation = Self::validate_push_eligibility(
@@ -1392,6 +2209,7 @@ impl Libp2pNetwork {
                         &peer.to_string(),
                         &local_id,
                         bootstrap.as_ref(),
+                        sync_rules,
                     )
                     .await;
 
@@ -1408,6 +2226,25 @@ impl Libp2pNetwork {
                     }
                 }
 
+                // Unpack the compressed batch; decompression is bounded, so
+                // the size check below sees the real payload size.
+                let operations = match batch.decode() {
+                    Ok(operations) => operations,
+                    Err(e) => {
+                        let response = ContentResponse::Error {
+                            message: format!("Invalid operation batch: {}", e),
+                        };
+                        if let Err(e) = swarm
+                            .behaviour_mut()
+                            .request_response
+                            .send_response(channel, response)
+                        {
+                            error!("Failed to send response: {:?}", e);
+                        }
+                        return;
+                    }
+                };
+
                 // Reject oversized payloads (max 16 MiB total)
                 const MAX_PUSH_PAYLOAD_BYTES: usize = 16 * 1024 * 1024;
                 let total_size: usize = operations.iter().map(|op| op.len()).sum();
@@ -1435,10 +2272,20 @@ impl Libp2pNetwork {
                     .collect();
 
                 match crdt_repo.apply_operations(&ops).await {
-                    Ok(count) => ContentResponse::PushResult {
-                        genesis_cid,
-                        accepted_count: count,
-                    },
+                    Ok(count) => {
+                        // A bootstrap push makes this node a member of new
+                        // content: announce it so fetchers can find us.
+                        if bootstrap.is_some() {
+                            let key = kad::RecordKey::new(&genesis_cid);
+                            if let Err(e) = swarm.behaviour_mut().kademlia.start_providing(key) {
+                                warn!("Failed to provide {}: {:?}", genesis_cid, e);
+                            }
+                        }
+                        ContentResponse::PushResult {
+                            genesis_cid,
+                            accepted_count: count,
+                        }
+                    }
                     Err(e) => ContentResponse::Error {
                         message: format!("Failed to apply operations: {}", e),
                     },
@@ -1447,7 +2294,8 @@ impl Libp2pNetwork {
             // Relay variants already handled above and returned early
             ContentRequest::UpdateContent { .. }
             | ContentRequest::DeleteContent { .. }
-            | ContentRequest::InvalidateTokens { .. } => unreachable!(),
+            | ContentRequest::InvalidateTokens { .. }
+            | ContentRequest::MirrorPair { .. } => unreachable!(),
         };
 
         if let Err(e) = swarm
@@ -1468,10 +2316,14 @@ impl Libp2pNetwork {
         if let Some(reply) = pending.capacity_queries.remove(&request_id) {
             match response {
                 ContentResponse::CapacityResponse {
-                    total_capacity,
                     available_capacity,
+                    sync_rules,
+                    ..
                 } => {
-                    let _ = reply.send(Ok((total_capacity, available_capacity)));
+                    let _ = reply.send(Ok(CapacityInfo {
+                        available_capacity,
+                        sync_rules,
+                    }));
                 }
                 ContentResponse::Error { message } => {
                     let _ = reply.send(Err(anyhow::anyhow!("Capacity query error: {}", message)));
@@ -1502,20 +2354,63 @@ impl Libp2pNetwork {
             return;
         }
 
+        // Handle content range fetch response
+        if let Some(reply) = pending.content_range_fetches.remove(&request_id) {
+            match response {
+                ContentResponse::ContentRange {
+                    data,
+                    version,
+                    total_size,
+                    ..
+                } => {
+                    let _ = reply.send(Ok(ContentChunk {
+                        data,
+                        version,
+                        total_size,
+                    }));
+                }
+                ContentResponse::NotFound { content_id } => {
+                    let _ = reply.send(Err(anyhow::anyhow!("Content not found: {}", content_id)));
+                }
+                ContentResponse::Error { message } => {
+                    let _ = reply.send(Err(anyhow::anyhow!("Fetch error: {}", message)));
+                }
+                _ => {
+                    let _ = reply.send(Err(anyhow::anyhow!("Unexpected response type")));
+                }
+            }
+            return;
+        }
+
         // Handle operation fetch response
         if let Some(reply) = pending.operation_fetches.remove(&request_id) {
             match response {
                 ContentResponse::OperationsData {
-                    operations,
+                    batches,
+                    snapshot,
+                    latest_version,
                     genesis_cid: _,
-                } => {
-                    // Deserialize operations from wire format
-                    let ops: Vec<SerializedOperation> = operations
-                        .iter()
-                        .filter_map(|bytes| serde_json::from_slice(bytes).ok())
-                        .collect();
-                    let _ = reply.send(Ok(ops));
-                }
+                } => match operation_batch::decode_batches(&batches) {
+                    Ok(decoded) => {
+                        // The snapshot stands in for the operations before
+                        // the ones sent, so it is applied first.
+                        let ops: Vec<SerializedOperation> = snapshot
+                            .map(|snapshot| snapshot.operations())
+                            .unwrap_or_default()
+                            .into_iter()
+                            .chain(
+                                // Deserialize operations from wire format
+                                decoded
+                                    .iter()
+                                    .filter_map(|bytes| serde_json::from_slice(bytes).ok()),
+                            )
+                            .collect();
+                        let _ = reply.send(Ok((ops, latest_version)));
+                    }
+                    Err(e) => {
+                        let _ = reply.send(Err(anyhow::anyhow!("Invalid operation batch: {}", e)));
+                    }
+                },
                 ContentResponse::NotFound { content_id } => {
                     let _ = reply.send(Err(anyhow::anyhow!("Content not found: {}", content_id)));
                 }
@@ -1593,6 +2488,22 @@ impl Libp2pNetwork {
                     let _ = reply.send(Err(anyhow::anyhow!("Unexpected response type")));
                 }
             }
+            return;
+        }
+
+        // Handle mirror pairing response
+        if let Some(reply) = pending.mirror_pairings.remove(&request_id) {
+            match response {
+                ContentResponse::MirrorPairAccepted { acceptance } => {
+                    let _ = reply.send(Ok(acceptance));
+                }
+                ContentResponse::Error { message } => {
+                    let _ = reply.send(Err(anyhow::anyhow!("Mirror pairing error: {}", message)));
+                }
+                _ => {
+                    let _ = reply.send(Err(anyhow::anyhow!("Unexpected response type")));
+                }
+            }
         }
     }
 
@@ -1702,14 +2613,63 @@ impl Libp2pNetwork {
         }
     }
 
-    async fn handle_identify_event(swarm: &mut Swarm<NodeBehaviour>, event: identify::Event) {
+    async fn handle_identify_event(
+        swarm: &mut Swarm<NodeBehaviour>,
+        peer_attestations: &Arc<RwLock<HashMap<PeerId, NodeAttestation>>>,
+        identified_peers: &Arc<RwLock<HashMap<PeerId, (Vec<Multiaddr>, u64)>>>,
+        event: identify::Event,
+    ) {
         if let identify::Event::Received { peer_id, info, .. } = event {
             info!(
-                "Identified peer {}: {} with {} addresses",
+                "Identified peer {} with {} addresses",
                 peer_id,
-                info.agent_version,
                 info.listen_addrs.len()
             );
+
+            // Remember which account owns the peer. A missing or invalid
+            // attestation drops any ownership learned earlier.
+            let attestation = match node_attestation::decode_agent_version(&info.agent_version) {
+                Ok(Some(attestation)) => match node_attestation::verify_node_attestation(
+                    &attestation,
+                    &peer_id.to_string(),
+                    crate::domain::events::current_timestamp(),
+                ) {
+                    Ok(account_id) => {
+                        debug!("Peer {} is attested by account {}", peer_id, account_id);
+                        Some(attestation)
+                    }
+                    Err(e) => {
+                        warn!("Rejected attestation from peer {}: {}", peer_id, e);
+                        None
+                    }
+                },
+                Ok(None) => None,
+                Err(e) => {
+                    warn!("Malformed attestation from peer {}: {}", peer_id, e);
+                    None
+                }
+            };
+            let mut attestations = peer_attestations.write().await;
+            match attestation {
+                Some(attestation) => attestations.insert(peer_id, attestation),
+                None => attestations.remove(&peer_id),
+            };
+            drop(attestations);
+
+            // Remember the peer so it can be dialed again after a restart.
+            if !info.listen_addrs.is_empty() {
+                let addrs = info
+                    .listen_addrs
+                    .iter()
+                    .take(MAX_KNOWN_PEER_ADDRS)
+                    .cloned()
+                    .collect();
+                identified_peers
+                    .write()
+                    .await
+                    .insert(peer_id, (addrs, crate::domain::events::current_timestamp()));
+            }
+
             // Add peer's addresses to Kademlia, and also make them available to
             // every behaviour (notably request-response) via the swarm's peer
             // address book. Without this, request-response dials can fail with
@@ -1771,43 +2731,346 @@ impl Libp2pNetwork {
 }
 
 impl Libp2pNetwork {
-    async fn send_push_operations(
+    /// Fetch the latest data of `content_id` from `peer_id`, presenting
+    /// `capability` when this node is not a member of the content network.
+    async fn request_content(
         &self,
         peer_id: &str,
-        genesis_cid: &str,
-        operations: &[SerializedOperation],
-        bootstrap: Option<PushBootstrap>,
-    ) -> Result<usize> {
+        content_id: &str,
+        capability: Option<ReadCapability>,
+    ) -> Result<Vec<u8>> {
         let peer_id = PeerId::from_str(peer_id)
             .map_err(|_| anyhow::anyhow!("Invalid peer ID: {}", peer_id))?;
-
-        let (tx, rx) = oneshot::channel();
-        self.command_tx
-            .send(SwarmCommand::PushOperations {
-                peer_id,
-                genesis_cid: genesis_cid.to_string(),
-                operations: operations.to_vec(),
-                bootstrap,
-                reply: tx,
-            })
-            .await
-            .map_err(|_| anyhow::anyhow!("Failed to send command"))?;
-
-        tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
-            .await
-            .map_err(|_| anyhow::anyhow!("push_operations timed out"))?
-            .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
+        // Waiting for a transfer slot counts towards the timeout.
+        tokio::time::timeout(PEER_NETWORK_TIMEOUT, async {
+            let _transfer = self.transfers.acquire(&peer_id).await;
+            let (tx, rx) = oneshot::channel();
+            self.command_tx
+                .send(SwarmCommand::FetchContent {
+                    peer_id,
+                    content_id: content_id.to_string(),
+                    capability,
+                    reply: tx,
+                })
+                .await?;
+            rx.await
+                .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
+        })
+        .await
+        .map_err(|_| anyhow::anyhow!("fetch_content timed out"))?
     }
-}
 
-#[async_trait]
-impl PeerNetwork for Libp2pNetwork {
+    /// Fetch `length` bytes of the latest data of `content_id` from
+    /// `peer_id` starting at `offset`, presenting `capability` when this node
+    /// is not a member of the content network.
+    async fn request_content_range(
+        &self,
+        peer_id: &str,
+        content_id: &str,
+        offset: u64,
+        length: u64,
+        capability: Option<ReadCapability>,
+    ) -> Result<ContentChunk> {
+        let peer_id = PeerId::from_str(peer_id)
+            .map_err(|_| anyhow::anyhow!("Invalid peer ID: {}", peer_id))?;
+        // Waiting for a transfer slot counts towards the timeout.
+        tokio::time::timeout(PEER_NETWORK_TIMEOUT, async {
+            let _transfer = self.transfers.acquire(&peer_id).await;
+            let (tx, rx) = oneshot::channel();
+            self.command_tx
+                .send(SwarmCommand::FetchContentRange {
+                    peer_id,
+                    content_id: content_id.to_string(),
+                    offset,
+                    length,
+                    capability,
+                    reply: tx,
+                })
+                .await?;
+            rx.await
+                .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
+        })
+        .await
+        .map_err(|_| anyhow::anyhow!("fetch_content_range timed out"))?
+    }
+
+    /// Fetch the CRDT operations of `genesis_cid` from `peer_id`, presenting
+    /// `capability` when this node is not a member of the content network.
+    async fn request_operations(
+        &self,
+        peer_id: &str,
+        genesis_cid: &str,
+        since_version: Option<&str>,
+        capability: Option<ReadCapability>,
+    ) -> Result<(Vec<SerializedOperation>, Option<String>)> {
+        let peer_id = PeerId::from_str(peer_id)
+            .map_err(|_| anyhow::anyhow!("Invalid peer ID: {}", peer_id))?;
+        // Waiting for a transfer slot counts towards the timeout.
+        tokio::time::timeout(PEER_NETWORK_TIMEOUT, async {
+            let _transfer = self.transfers.acquire(&peer_id).await;
+            let (tx, rx) = oneshot::channel();
+            self.command_tx
+                .send(SwarmCommand::FetchOperations {
+                    peer_id,
+                    genesis_cid: genesis_cid.to_string(),
+                    since_version: since_version.map(String::from),
+                    capability,
+                    reply: tx,
+                })
+                .await?;
+            rx.await
+                .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
+        })
+        .await
+        .map_err(|_| anyhow::anyhow!("fetch_operations timed out"))?
+    }
+
+    /// Push `operations` as compressed batches, one request per batch and in
+    /// order. Only the first request carries `bootstrap`, so the receiver
+    /// knows the network before the remaining batches arrive.
+    ///
+    /// Returns the total number of operations the peer accepted.
+    async fn send_push_operations(
+        &self,
+        peer_id: &str,
+        genesis_cid: &str,
+        operations: &[SerializedOperation],
+        bootstrap: Option<PushBootstrap>,
+    ) -> Result<usize> {
+        let peer_id = PeerId::from_str(peer_id)
+            .map_err(|_| anyhow::anyhow!("Invalid peer ID: {}", peer_id))?;
+
+        // Convert SerializedOperation to Vec<u8> for wire format
+        let wire_ops: Vec<Vec<u8>> = operations
+            .iter()
+            .filter_map(|op| serde_json::to_vec(op).ok())
+            .collect();
+        let mut batches = operation_batch::encode_batches(wire_ops)?;
+        if batches.is_empty() {
+            // A bootstrap push may carry no operations at all.
+            batches.push(OperationBatch::encode(&[])?);
+        }
+
+        let mut bootstrap = bootstrap;
+        let mut accepted = 0;
+        for batch in batches {
+            accepted += tokio::time::timeout(PEER_NETWORK_TIMEOUT, async {
+                let _transfer = self.transfers.acquire(&peer_id).await;
+                let (tx, rx) = oneshot::channel();
+                self.command_tx
+                    .send(SwarmCommand::PushOperations {
+                        peer_id,
+                        genesis_cid: genesis_cid.to_string(),
+                        batch,
+                        bootstrap: bootstrap.take(),
+                        reply: tx,
+                    })
+                    .await?;
+                rx.await
+                    .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("push_operations timed out"))??;
+        }
+        Ok(accepted)
+    }
+
+    /// Content ID and presented capability of a read request, or `None` if
+    /// `request` does not read content.
+    fn read_request_target(request: &ContentRequest) -> Option<(String, Option<ReadCapability>)> {
+        match request {
+            ContentRequest::FetchContent {
+                content_id,
+                capability,
+            }
+            | ContentRequest::FetchContentRange {
+                content_id,
+                capability,
+                ..
+            }
+            | ContentRequest::SyncContent {
+                content_id,
+                capability,
+                ..
+            } => Some((content_id.clone(), capability.clone())),
+            ContentRequest::FetchOperations {
+                genesis_cid,
+                capability,
+                ..
+            } => Some((genesis_cid.clone(), capability.clone())),
+            _ => None,
+        }
+    }
+
+    /// Whether `peer` is a member of the content network of `content_id`.
+    ///
+    /// Without a content network repository (some test configurations),
+    /// every peer is treated as a member.
+    async fn is_content_member(
+        content_network_repo: &Option<
+            Arc<RwLock<dyn crate::port::persistence::PersistentContentRepository + Send + Sync>>,
+        >,
+        content_id: &str,
+        peer: &PeerId,
+    ) -> bool {
+        let Some(repo) = content_network_repo else {
+            return true;
+        };
+        repo.read()
+            .await
+            .get_content_network(content_id)
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|net| net.has_member_str(&peer.to_string()))
+    }
+
+    /// Answer an authorized read request (`FetchContent`,
+    /// `FetchContentRange`, `SyncContent` or `FetchOperations`).
+    async fn read_response(
+        crdt_repo: &Arc<dyn ContentRepository>,
+        request: ContentRequest,
+    ) -> ContentResponse {
+        match request {
+            ContentRequest::FetchContent { content_id, .. } => {
+                match crdt_repo.get_latest_with_version(&content_id).await {
+                    Ok(Some((data, version))) => ContentResponse::ContentData {
+                        content_id,
+                        data,
+                        version,
+                    },
+                    Ok(None) => ContentResponse::NotFound { content_id },
+                    Err(e) => ContentResponse::Error {
+                        message: format!("Failed to fetch content: {}", e),
+                    },
+                }
+            }
+            ContentRequest::FetchContentRange {
+                content_id,
+                offset,
+                length,
+                ..
+            } => match crdt_repo.get_latest_with_version(&content_id).await {
+                Ok(Some((data, version))) => {
+                    let chunk = ContentChunk::cut(
+                        &data,
+                        version,
+                        offset,
+                        length.min(MAX_CONTENT_RANGE_BYTES),
+                    );
+                    ContentResponse::ContentRange {
+                        content_id,
+                        data: chunk.data,
+                        version: chunk.version,
+                        total_size: chunk.total_size,
+                    }
+                }
+                Ok(None) => ContentResponse::NotFound { content_id },
+                Err(e) => ContentResponse::Error {
+                    message: format!("Failed to fetch content: {}", e),
+                },
+            },
+            ContentRequest::SyncContent { content_id, .. } => {
+                // SyncContent returns the same as FetchContent (latest data)
+                match crdt_repo.get_latest_with_version(&content_id).await {
+                    Ok(Some((data, version))) => ContentResponse::ContentData {
+                        content_id,
+                        data,
+                        version,
+                    },
+                    Ok(None) => ContentResponse::NotFound { content_id },
+                    Err(e) => ContentResponse::Error {
+                        message: format!("Failed to sync content: {}", e),
+                    },
+                }
+            }
+            ContentRequest::FetchOperations {
+                genesis_cid,
+                since_version,
+                accept_snapshot,
+                ..
+            } => {
+                Self::fetch_operations_response(
+                    crdt_repo,
+                    genesis_cid,
+                    since_version.as_deref(),
+                    accept_snapshot,
+                )
+                .await
+            }
+            _ => ContentResponse::Error {
+                message: "Not a read request".to_string(),
+            },
+        }
+    }
+
+    /// Answer a `FetchOperations` request. Requesters that accept snapshots
+    /// and are behind the content's snapshot get the snapshot and the
+    /// operations after it instead of the operations it covers.
+    async fn fetch_operations_response(
+        crdt_repo: &Arc<dyn ContentRepository>,
+        genesis_cid: String,
+        since_version: Option<&str>,
+        accept_snapshot: bool,
+    ) -> ContentResponse {
+        let fetched = if accept_snapshot {
+            crdt_repo
+                .get_operations_with_snapshot(&genesis_cid, since_version)
+                .await
+        } else {
+            crdt_repo
+                .get_operations(&genesis_cid, since_version)
+                .await
+                .map(|ops| (None, ops))
+        };
+        match fetched {
+            Ok((snapshot, ops)) => {
+                let latest_version = crdt_repo
+                    .get_history(&genesis_cid)
+                    .await
+                    .ok()
+                    .and_then(|history| history.last().cloned());
+                Self::operations_response(genesis_cid, snapshot, &ops, latest_version)
+            }
+            Err(e) => ContentResponse::Error {
+                message: format!("Failed to fetch operations: {}", e),
+            },
+        }
+    }
+
+    /// Build an `OperationsData` response carrying `ops` as compressed
+    /// batches after `snapshot`.
+    fn operations_response(
+        genesis_cid: String,
+        snapshot: Option<ContentSnapshot>,
+        ops: &[SerializedOperation],
+        latest_version: Option<String>,
+    ) -> ContentResponse {
+        let operations: Vec<Vec<u8>> = ops
+            .iter()
+            .filter_map(|op| serde_json::to_vec(op).ok())
+            .collect();
+        match operation_batch::encode_batches(operations) {
+            Ok(batches) => ContentResponse::OperationsData {
+                genesis_cid,
+                batches,
+                snapshot,
+                latest_version,
+            },
+            Err(e) => ContentResponse::Error {
+                message: format!("Failed to encode operations: {}", e),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl PeerNetwork for Libp2pNetwork {
     async fn find_closest_peers(&self, key: Vec<u8>, k: usize) -> Result<Vec<String>> {
         let (tx, rx) = oneshot::channel();
         self.command_tx
             .send(SwarmCommand::FindClosestPeers { key, k, reply: tx })
-            .await
-            .map_err(|_| anyhow::anyhow!("Failed to send command"))?;
+            .await?;
 
         let peers = tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
             .await
@@ -1835,15 +3098,58 @@ impl PeerNetwork for Libp2pNetwork {
                 continue;
             }
 
-            if let Ok(Ok(Ok((_, available)))) = tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx).await
-            {
-                results.insert(peer_id_str.clone(), available);
+            if let Ok(Ok(Ok(info))) = tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx).await {
+                results.insert(peer_id_str.clone(), info.available_capacity);
             }
         }
 
         Ok(results)
     }
 
+    async fn query_node_sync_rules_batch(
+        &self,
+        peer_ids: &[String],
+    ) -> Result<HashMap<String, SyncRules>> {
+        // Query all peers concurrently so one slow peer does not hold up
+        // the others for the full timeout.
+        let queries = peer_ids.iter().map(|peer_id_str| async move {
+            let peer_id = PeerId::from_str(peer_id_str).ok()?;
+            let (tx, rx) = oneshot::channel();
+            self.command_tx
+                .send(SwarmCommand::QueryCapacity { peer_id, reply: tx })
+                .await
+                .ok()?;
+            match tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx).await {
+                Ok(Ok(Ok(info))) => Some((peer_id_str.clone(), info.sync_rules)),
+                _ => None,
+            }
+        });
+
+        Ok(futures::future::join_all(queries)
+            .await
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    async fn query_node_owners_batch(
+        &self,
+        peer_ids: &[String],
+    ) -> Result<HashMap<String, String>> {
+        let now = crate::domain::events::current_timestamp();
+        let attestations = self.peer_attestations.read().await;
+        Ok(peer_ids
+            .iter()
+            .filter_map(|peer_id_str| {
+                let peer_id = PeerId::from_str(peer_id_str).ok()?;
+                let attestation = attestations.get(&peer_id)?;
+                attestation
+                    .is_valid_at(now)
+                    .then(|| (peer_id_str.clone(), attestation.account_id()))
+            })
+            .collect())
+    }
+
     async fn query_node_public_keys_batch(
         &self,
         peer_ids: &[String],
@@ -1905,15 +3211,26 @@ impl PeerNetwork for Libp2pNetwork {
     }
 
     async fn publish_event(&self, topic: &str, event_data: &[u8]) -> Result<()> {
+        // Domain events go out signed so receivers can verify their author.
+        // Other payloads (e.g. CRDT operation broadcasts) are sent as-is.
+        let data = match serde_json::from_slice::<Event>(event_data) {
+            Ok(event) => {
+                let signed = event_signing::sign_event(event, &self.keypair)
+                    .map_err(|e| anyhow::anyhow!("Failed to sign event: {}", e))?;
+                serde_json::to_vec(&signed)
+                    .map_err(|e| anyhow::anyhow!("Failed to serialize signed event: {}", e))?
+            }
+            Err(_) => event_data.to_vec(),
+        };
+
         let (tx, rx) = oneshot::channel();
         self.command_tx
             .send(SwarmCommand::PublishEvent {
                 topic: topic.to_string(),
-                data: event_data.to_vec(),
+                data,
                 reply: tx,
             })
-            .await
-            .map_err(|_| anyhow::anyhow!("Failed to send command"))?;
+            .await?;
 
         tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
             .await
@@ -1922,31 +3239,36 @@ impl PeerNetwork for Libp2pNetwork {
     }
 
     async fn fetch_content(&self, peer_id: &str, content_id: &str) -> Result<Vec<u8>> {
-        let peer_id = PeerId::from_str(peer_id)
-            .map_err(|_| anyhow::anyhow!("Invalid peer ID: {}", peer_id))?;
+        self.request_content(peer_id, content_id, None).await
+    }
 
-        let (tx, rx) = oneshot::channel();
-        self.command_tx
-            .send(SwarmCommand::FetchContent {
-                peer_id,
-                content_id: content_id.to_string(),
-                reply: tx,
-            })
+    async fn fetch_content_with_capability(
+        &self,
+        peer_id: &str,
+        content_id: &str,
+        capability: &ReadCapability,
+    ) -> Result<Vec<u8>> {
+        self.request_content(peer_id, content_id, Some(capability.clone()))
             .await
-            .map_err(|_| anyhow::anyhow!("Failed to send command"))?;
+    }
 
-        tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
+    async fn fetch_content_range(
+        &self,
+        peer_id: &str,
+        content_id: &str,
+        offset: u64,
+        length: u64,
+        capability: Option<&ReadCapability>,
+    ) -> Result<ContentChunk> {
+        self.request_content_range(peer_id, content_id, offset, length, capability.cloned())
             .await
-            .map_err(|_| anyhow::anyhow!("fetch_content timed out"))?
-            .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
     }
 
     async fn publish_provider(&self, key: Vec<u8>) -> Result<()> {
         let (tx, rx) = oneshot::channel();
         self.command_tx
             .send(SwarmCommand::PublishProvider { key, reply: tx })
-            .await
-            .map_err(|_| anyhow::anyhow!("Failed to send command"))?;
+            .await?;
 
         tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
             .await
@@ -1954,6 +3276,18 @@ impl PeerNetwork for Libp2pNetwork {
             .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
     }
 
+    async fn remove_provider(&self, key: Vec<u8>) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::RemoveProvider { key, reply: tx })
+            .await?;
+
+        tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
+            .await
+            .map_err(|_| anyhow::anyhow!("remove_provider timed out"))?
+            .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
+    }
+
     fn local_peer_id(&self) -> String {
         self.local_peer_id.to_string()
     }
@@ -1972,24 +3306,32 @@ impl PeerNetwork for Libp2pNetwork {
         genesis_cid: &str,
         since_version: Option<&str>,
     ) -> Result<Vec<SerializedOperation>> {
-        let peer_id = PeerId::from_str(peer_id)
-            .map_err(|_| anyhow::anyhow!("Invalid peer ID: {}", peer_id))?;
+        let (operations, _) = self
+            .fetch_operations_with_version(peer_id, genesis_cid, since_version)
+            .await?;
+        Ok(operations)
+    }
 
-        let (tx, rx) = oneshot::channel();
-        self.command_tx
-            .send(SwarmCommand::FetchOperations {
-                peer_id,
-                genesis_cid: genesis_cid.to_string(),
-                since_version: since_version.map(String::from),
-                reply: tx,
-            })
+    async fn fetch_operations_with_version(
+        &self,
+        peer_id: &str,
+        genesis_cid: &str,
+        since_version: Option<&str>,
+    ) -> Result<(Vec<SerializedOperation>, Option<String>)> {
+        self.request_operations(peer_id, genesis_cid, since_version, None)
             .await
-            .map_err(|_| anyhow::anyhow!("Failed to send command"))?;
+    }
 
-        tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
-            .await
-            .map_err(|_| anyhow::anyhow!("fetch_operations timed out"))?
-            .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
+    async fn fetch_operations_with_capability(
+        &self,
+        peer_id: &str,
+        genesis_cid: &str,
+        capability: &ReadCapability,
+    ) -> Result<Vec<SerializedOperation>> {
+        let (operations, _) = self
+            .request_operations(peer_id, genesis_cid, None, Some(capability.clone()))
+            .await?;
+        Ok(operations)
     }
 
     async fn push_operations(
@@ -2018,12 +3360,10 @@ impl PeerNetwork for Libp2pNetwork {
         genesis_cid: &str,
         operation: &SerializedOperation,
     ) -> Result<()> {
-        // Create a broadcast message containing the operation
-        let broadcast_msg = serde_json::json!({
-            "type": "crdt_operation",
-            "genesis_cid": genesis_cid,
-            "operation": operation,
-        });
+        // Create a signed broadcast message containing the operation
+        let broadcast_msg =
+            event_signing::sign_operation_broadcast(genesis_cid, operation, &self.keypair)
+                .map_err(|e| anyhow::anyhow!("Failed to sign broadcast: {}", e))?;
         let data = serde_json::to_vec(&broadcast_msg)
             .map_err(|e| anyhow::anyhow!("Failed to serialize broadcast: {}", e))?;
 
@@ -2036,8 +3376,7 @@ impl PeerNetwork for Libp2pNetwork {
         let (tx, rx) = oneshot::channel();
         self.command_tx
             .send(SwarmCommand::GetProviders { key, reply: tx })
-            .await
-            .map_err(|_| anyhow::anyhow!("Failed to send command"))?;
+            .await?;
 
         let peers = tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
             .await
@@ -2046,6 +3385,60 @@ impl PeerNetwork for Libp2pNetwork {
         Ok(peers.into_iter().map(|p| p.to_string()).collect())
     }
 
+    async fn put_record(&self, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
+        if value.len() > MAX_RECORD_VALUE_BYTES {
+            anyhow::bail!("Record value of {} bytes exceeds the limit", value.len());
+        }
+        let value = dht_record::sign_record(
+            &key,
+            &value,
+            crate::domain::events::current_timestamp(),
+            &self.keypair,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to sign record: {}", e))?;
+
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::PutRecord {
+                key,
+                value,
+                reply: tx,
+            })
+            .await?;
+
+        tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
+            .await
+            .map_err(|_| anyhow::anyhow!("put_record timed out"))?
+            .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
+    }
+
+    async fn get_records(&self, key: Vec<u8>) -> Result<Vec<DhtRecord>> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::GetRecords {
+                key: key.clone(),
+                reply: tx,
+            })
+            .await?;
+
+        let values = tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
+            .await
+            .map_err(|_| anyhow::anyhow!("get_records timed out"))?
+            .map_err(|_| anyhow::anyhow!("Failed to receive response"))??;
+
+        let now = crate::domain::events::current_timestamp();
+        Ok(values
+            .iter()
+            .filter_map(|value| match dht_record::verify_record(&key, value, now) {
+                Ok(record) => Some(record),
+                Err(e) => {
+                    debug!("Ignoring DHT record: {}", e);
+                    None
+                }
+            })
+            .collect())
+    }
+
     async fn relay_update_content(
         &self,
         peer_id: &str,
@@ -2069,8 +3462,7 @@ impl PeerNetwork for Libp2pNetwork {
                 timestamp,
                 reply: tx,
             })
-            .await
-            .map_err(|_| anyhow::anyhow!("Failed to send command"))?;
+            .await?;
 
         tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
             .await
@@ -2099,8 +3491,7 @@ impl PeerNetwork for Libp2pNetwork {
                 timestamp,
                 reply: tx,
             })
-            .await
-            .map_err(|_| anyhow::anyhow!("Failed to send command"))?;
+            .await?;
 
         tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
             .await
@@ -2129,8 +3520,7 @@ impl PeerNetwork for Libp2pNetwork {
                 timestamp,
                 reply: tx,
             })
-            .await
-            .map_err(|_| anyhow::anyhow!("Failed to send command"))?;
+            .await?;
 
         tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
             .await
@@ -2138,9 +3528,106 @@ impl PeerNetwork for Libp2pNetwork {
             .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
     }
 
+    async fn request_mirror_pairing(
+        &self,
+        peer_id: &str,
+        request: &MirrorPairingRequest,
+    ) -> Result<MirrorPairingAcceptance> {
+        let peer_id = PeerId::from_str(peer_id)
+            .map_err(|_| anyhow::anyhow!("Invalid peer ID: {}", peer_id))?;
+
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::RequestMirrorPairing {
+                peer_id,
+                request: request.clone(),
+                reply: tx,
+            })
+            .await?;
+
+        tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
+            .await
+            .map_err(|_| anyhow::anyhow!("request_mirror_pairing timed out"))?
+            .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
+    }
+
+    async fn set_peer_access(&self, peer_id: &str, access: Option<PeerAccess>) -> Result<()> {
+        let peer_id = PeerId::from_str(peer_id)
+            .map_err(|_| anyhow::anyhow!("Invalid peer ID: {}", peer_id))?;
+
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(SwarmCommand::SetPeerAccess {
+                peer_id,
+                access,
+                reply: tx,
+            })
+            .await?;
+
+        tokio::time::timeout(PEER_NETWORK_TIMEOUT, rx)
+            .await
+            .map_err(|_| anyhow::anyhow!("set_peer_access timed out"))?
+            .map_err(|_| anyhow::anyhow!("Failed to receive response"))?
+    }
+
     async fn connected_peer_count(&self) -> usize {
         self.connected_peers.read().await.len()
     }
+
+    async fn connected_peer_ids(&self) -> Vec<String> {
+        self.connected_peers
+            .read()
+            .await
+            .keys()
+            .map(|peer_id| peer_id.to_string())
+            .collect()
+    }
+
+    async fn peer_latencies(&self) -> HashMap<String, Duration> {
+        self.peer_latency.read().await.snapshot()
+    }
+}
+
+/// Payload carried by a message on an events topic.
+enum GossipPayload {
+    /// A domain event with a valid author signature, handed to subscribers.
+    Event(Box<Event>),
+    /// A CRDT operation broadcast (see `broadcast_operation`) signed by its
+    /// author; propagated but otherwise ignored here.
+    CrdtOperation,
+    /// Anything else. Rejected so the sender is penalized.
+    Malformed(String),
+}
+
+fn decode_gossip_payload(data: &[u8]) -> GossipPayload {
+    let event_err = match serde_json::from_slice::<SignedEvent>(data) {
+        Ok(signed) => {
+            return match event_signing::verify_signed_event(&signed) {
+                Ok(_) => GossipPayload::Event(Box::new(signed.event)),
+                Err(e) => GossipPayload::Malformed(e.to_string()),
+            };
+        }
+        Err(e) => e,
+    };
+    if serde_json::from_slice::<Event>(data).is_ok() {
+        return GossipPayload::Malformed("unsigned event".to_string());
+    }
+    match serde_json::from_slice::<serde_json::Value>(data) {
+        Ok(value)
+            if value.get("type").and_then(|t| t.as_str())
+                == Some(event_signing::CRDT_OPERATION_TYPE) =>
+        {
+            match serde_json::from_value::<event_signing::SignedOperationBroadcast>(value)
+                .map_err(|e| e.to_string())
+                .and_then(|signed| {
+                    event_signing::verify_operation_broadcast(&signed).map_err(|e| e.to_string())
+                }) {
+                Ok(_) => GossipPayload::CrdtOperation,
+                Err(reason) => GossipPayload::Malformed(reason),
+            }
+        }
+        _ => GossipPayload::Malformed(event_err.to_string()),
+    }
 }
 
 #[cfg(test)]
@@ -2157,6 +3644,7 @@ mod tests {
             enable_mdns: false,
             gossipsub_topics: vec!["test".to_string()],
             external_addrs: vec![],
+            ..Default::default()
         };
 
         // Create a temporary directory for the CRDT repository
@@ -2171,4 +3659,66 @@ mod tests {
         let network = network.unwrap();
         assert!(!network.local_peer_id().is_empty());
     }
+
+    #[test]
+    fn test_decode_gossip_payload() {
+        let keypair = libp2p::identity::Keypair::generate_ed25519();
+        let event = Event::NodeCreated {
+            node_id: keypair.public().to_peer_id().to_string(),
+            total_capacity: 100,
+            available_capacity: 50,
+            timestamp: 1,
+        };
+        let signed = event_signing::sign_event(event.clone(), &keypair).unwrap();
+        assert!(matches!(
+            decode_gossip_payload(&serde_json::to_vec(&signed).unwrap()),
+            GossipPayload::Event(decoded) if *decoded == event
+        ));
+
+        // Unsigned events and events signed by another node are rejected.
+        assert!(matches!(
+            decode_gossip_payload(&serde_json::to_vec(&event).unwrap()),
+            GossipPayload::Malformed(_)
+        ));
+        let forged =
+            event_signing::sign_event(event, &libp2p::identity::Keypair::generate_ed25519())
+                .unwrap();
+        assert!(matches!(
+            decode_gossip_payload(&serde_json::to_vec(&forged).unwrap()),
+            GossipPayload::Malformed(_)
+        ));
+
+        let operation = SerializedOperation {
+            data: b"op".to_vec(),
+            genesis_cid: "cid".to_string(),
+            author: keypair.public().to_peer_id().to_string(),
+            timestamp: 1,
+            node_timestamp: 1,
+        };
+        let op = event_signing::sign_operation_broadcast("cid", &operation, &keypair).unwrap();
+        assert!(matches!(
+            decode_gossip_payload(&serde_json::to_vec(&op).unwrap()),
+            GossipPayload::CrdtOperation
+        ));
+
+        // Unsigned operation broadcasts are rejected.
+        let op = serde_json::json!({
+            "type": "crdt_operation",
+            "genesis_cid": "cid",
+            "operation": operation,
+        });
+        assert!(matches!(
+            decode_gossip_payload(&serde_json::to_vec(&op).unwrap()),
+            GossipPayload::Malformed(_)
+        ));
+
+        assert!(matches!(
+            decode_gossip_payload(b"not json"),
+            GossipPayload::Malformed(_)
+        ));
+        assert!(matches!(
+            decode_gossip_payload(br#"{"type":"unknown"}"#),
+            GossipPayload::Malformed(_)
+        ));
+    }
 }
@@ -1,18 +1,37 @@
 //! Network infrastructure using libp2p.
 //!
 //! This module provides P2P networking capabilities including:
-//! - Kademlia DHT for peer discovery and content routing
+//! - Kademlia DHT for peer discovery, content routing and signed records
 //! - Gossipsub for event propagation
 //! - RequestResponse for direct peer communication
 //! - mDNS for local peer discovery
-//! - WebRTC and TCP transports
+//! - Circuit relay and DCUtR hole punching for nodes behind NAT
+//! - Bandwidth throttling and per-peer transfer limits
+//! - WebRTC and TCP transports, optionally restricted to a private network
 
+pub mod bandwidth;
 pub mod behaviour;
+pub mod command_queue;
+pub mod dht_record;
 pub mod libp2p_network;
+pub mod nat_traversal;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod operation_batch;
+pub mod peer_identity;
 pub mod protocol;
 pub mod public_key_protocol;
 pub mod transport;
 
-pub use behaviour::{BehaviourConfig, NodeBehaviour, NodeBehaviourEvent};
-pub use libp2p_network::{GossipsubMessage, Libp2pNetwork, Libp2pNetworkConfig, ReceivedEvent};
+pub use bandwidth::BandwidthConfig;
+pub use behaviour::{
+    BehaviourConfig, NodeBehaviour, NodeBehaviourEvent, PROVIDER_RECORD_TTL,
+    PROVIDER_REPUBLISH_INTERVAL,
+};
+pub use command_queue::{CommandQueueConfig, CommandQueueMetrics};
+pub use libp2p_network::{
+    GossipsubMessage, Libp2pNetwork, Libp2pNetworkConfig, ReceivedEvent,
+    DEFAULT_RANDOM_WALK_INTERVAL,
+};
+pub use peer_identity::PeerIdentityConfig;
 pub use protocol::{ContentCodec, ContentRequest, ContentResponse};
+pub use transport::TransportConfig;
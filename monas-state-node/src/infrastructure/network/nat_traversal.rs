@@ -0,0 +1,192 @@
+//! NAT traversal through circuit relays and DCUtR hole punching.
+//!
+//! Home nodes behind NAT cannot accept inbound connections. When relay
+//! servers are configured, the node:
+//!
+//! 1. reserves a circuit slot on each relay (`<relay>/p2p-circuit`), so peers
+//!    can reach it through the relay;
+//! 2. retries a failed direct dial through the relays
+//!    (`<relay>/p2p-circuit/p2p/<peer>`);
+//! 3. lets DCUtR attempt a hole punch over the relayed connection, upgrading
+//!    it to a direct one when both sides are reachable.
+//!
+//! AutoNAT complements this: it tells the node whether it is publicly
+//! reachable at all, so it knows whether to serve the DHT or stay a client.
+//!
+//! Note: "relay" here means the libp2p circuit relay transport, not the
+//! content relay requests forwarded between content network members.
+
+use anyhow::Result;
+use libp2p::{multiaddr::Protocol, Multiaddr, PeerId};
+use std::collections::HashSet;
+
+/// Parse a relay server address. It must end with `/p2p/<relay peer id>`.
+pub fn parse_relay_addr(addr: &Multiaddr) -> Result<(PeerId, Multiaddr)> {
+    if addr.iter().any(|p| matches!(p, Protocol::P2pCircuit)) {
+        anyhow::bail!("Relay address must not contain /p2p-circuit: {}", addr);
+    }
+    match addr.iter().last() {
+        Some(Protocol::P2p(peer_id)) => Ok((peer_id, addr.clone())),
+        _ => anyhow::bail!("Relay address must end with /p2p/<peer id>: {}", addr),
+    }
+}
+
+/// Whether this node can be reached from the public internet, as reported
+/// by AutoNAT probes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Reachability {
+    /// Not enough probes have completed yet.
+    #[default]
+    Unknown,
+    /// Peers dialed us back on the given external address.
+    Public(Multiaddr),
+    /// Dial-back probes failed; the node is behind NAT or a firewall.
+    Private,
+}
+
+impl Reachability {
+    /// Whether the node is confirmed to be publicly reachable.
+    pub fn is_public(&self) -> bool {
+        matches!(self, Reachability::Public(_))
+    }
+
+    /// The confirmed external address, if publicly reachable.
+    pub fn public_addr(&self) -> Option<&Multiaddr> {
+        match self {
+            Reachability::Public(addr) => Some(addr),
+            _ => None,
+        }
+    }
+}
+
+/// Known relays and the peers currently being dialed through them.
+#[derive(Debug, Default)]
+pub struct RelayRouting {
+    relays: Vec<(PeerId, Multiaddr)>,
+    /// Peers whose direct dial failed and that are being dialed via a relay.
+    /// Prevents a failed relayed dial from triggering another relayed dial.
+    relayed_dials: HashSet<PeerId>,
+}
+
+impl RelayRouting {
+    /// Build the routing table from relay server addresses.
+    pub fn new(relay_addrs: &[Multiaddr]) -> Result<Self> {
+        let relays = relay_addrs
+            .iter()
+            .map(parse_relay_addr)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            relays,
+            relayed_dials: HashSet::new(),
+        })
+    }
+
+    /// Configured relays as `(relay peer id, relay address)`.
+    pub fn relays(&self) -> &[(PeerId, Multiaddr)] {
+        &self.relays
+    }
+
+    /// Whether `peer_id` is one of the configured relays.
+    pub fn is_relay(&self, peer_id: &PeerId) -> bool {
+        self.relays.iter().any(|(relay, _)| relay == peer_id)
+    }
+
+    /// Listen address that requests a reservation on `relay_addr`.
+    pub fn reservation_addr(relay_addr: &Multiaddr) -> Multiaddr {
+        relay_addr.clone().with(Protocol::P2pCircuit)
+    }
+
+    /// Addresses to dial `peer_id` through every relay after its direct dial
+    /// failed.
+    ///
+    /// Returns nothing for relays themselves, and for a peer that is already
+    /// being dialed through relays (i.e. the relayed dial itself failed); the
+    /// next direct dial failure for that peer triggers a fresh attempt.
+    pub fn relayed_dial_addrs(&mut self, peer_id: PeerId) -> Vec<Multiaddr> {
+        if self.relays.is_empty() || self.is_relay(&peer_id) {
+            return vec![];
+        }
+        if !self.relayed_dials.insert(peer_id) {
+            self.relayed_dials.remove(&peer_id);
+            return vec![];
+        }
+        self.relays
+            .iter()
+            .map(|(_, relay_addr)| Self::reservation_addr(relay_addr).with(Protocol::P2p(peer_id)))
+            .collect()
+    }
+
+    /// Record that a connection to `peer_id` was established.
+    pub fn on_connection_established(&mut self, peer_id: &PeerId) {
+        self.relayed_dials.remove(peer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relay_addr() -> (PeerId, Multiaddr) {
+        let relay = PeerId::random();
+        let addr: Multiaddr = format!("/ip4/203.0.113.5/tcp/4001/p2p/{relay}")
+            .parse()
+            .unwrap();
+        (relay, addr)
+    }
+
+    #[test]
+    fn test_parse_relay_addr_requires_peer_id() {
+        let (relay, addr) = relay_addr();
+        assert_eq!(parse_relay_addr(&addr).unwrap().0, relay);
+
+        let without_peer: Multiaddr = "/ip4/203.0.113.5/tcp/4001".parse().unwrap();
+        assert!(parse_relay_addr(&without_peer).is_err());
+
+        let circuit = RelayRouting::reservation_addr(&addr);
+        assert!(parse_relay_addr(&circuit).is_err());
+    }
+
+    #[test]
+    fn test_relayed_dial_addrs_go_through_each_relay() {
+        let (relay, addr) = relay_addr();
+        let mut routing = RelayRouting::new(std::slice::from_ref(&addr)).unwrap();
+        let peer = PeerId::random();
+
+        let addrs = routing.relayed_dial_addrs(peer);
+        assert_eq!(
+            addrs,
+            vec![addr
+                .clone()
+                .with(Protocol::P2pCircuit)
+                .with(Protocol::P2p(peer))]
+        );
+
+        // The relayed dial failed too: don't loop through the relay again.
+        assert!(routing.relayed_dial_addrs(peer).is_empty());
+        // A later direct dial failure retries through the relay.
+        assert_eq!(routing.relayed_dial_addrs(peer).len(), 1);
+
+        routing.on_connection_established(&peer);
+        assert_eq!(routing.relayed_dial_addrs(peer).len(), 1);
+
+        // Relays themselves are never dialed through a relay.
+        assert!(routing.relayed_dial_addrs(relay).is_empty());
+    }
+
+    #[test]
+    fn test_reachability_public_addr() {
+        assert!(!Reachability::default().is_public());
+        assert!(Reachability::Private.public_addr().is_none());
+
+        let addr: Multiaddr = "/ip4/203.0.113.7/tcp/4001".parse().unwrap();
+        let public = Reachability::Public(addr.clone());
+        assert!(public.is_public());
+        assert_eq!(public.public_addr(), Some(&addr));
+    }
+
+    #[test]
+    fn test_no_relays_means_no_fallback() {
+        let mut routing = RelayRouting::default();
+        assert!(routing.relayed_dial_addrs(PeerId::random()).is_empty());
+    }
+}
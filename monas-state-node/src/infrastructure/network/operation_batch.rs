@@ -0,0 +1,237 @@
+//! Batch framing and compression of CRDT operations on the content protocol.
+//!
+//! Operations are serialized as one JSON document each, which is repetitive
+//! and wasteful during large catch-up syncs. On the wire they are packed into
+//! batches instead: each operation is length-prefixed, the frames are
+//! concatenated and zstd-compressed. Batches are split so that their
+//! uncompressed size stays under [`MAX_BATCH_BYTES`]; pushes send one batch
+//! per request, keeping every request well within the codec's size limit.
+//! Decoding is bounded per batch and per message, so a small compressed
+//! message cannot expand into an arbitrarily large allocation.
+//!
+//! Frame layout (before compression), repeated `count` times:
+//!
+//! ```text
+//! [u32 little-endian length][operation bytes]
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Maximum uncompressed size of a batch. An operation larger than this is
+/// sent alone in its own batch.
+pub const MAX_BATCH_BYTES: usize = 512 * 1024;
+
+/// Maximum decompressed size accepted for a single batch, so a small
+/// compressed payload cannot expand into an arbitrarily large allocation.
+pub const MAX_DECODED_BATCH_BYTES: usize = 16 * 1024 * 1024;
+
+/// Maximum total decompressed size of the batches in one message. Encoding
+/// refuses operations above this, as the receiver would reject them.
+pub const MAX_DECODED_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// zstd compression level; favours speed, as batches are compressed on the
+/// swarm's request path.
+const COMPRESSION_LEVEL: i32 = 3;
+
+const LENGTH_PREFIX_BYTES: usize = 4;
+
+/// A compressed batch of serialized operations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationBatch {
+    /// Number of operations in the batch.
+    pub count: u32,
+    /// zstd-compressed, length-prefixed operations.
+    #[serde(with = "serde_bytes")]
+    pub data: Vec<u8>,
+}
+
+impl OperationBatch {
+    /// Compress `operations` into a single batch.
+    pub fn encode(operations: &[Vec<u8>]) -> Result<Self> {
+        let framed = frame(operations)?;
+        let data = zstd::bulk::compress(&framed, COMPRESSION_LEVEL)
+            .context("Failed to compress operation batch")?;
+        Ok(Self {
+            count: operations.len() as u32,
+            data,
+        })
+    }
+
+    /// Decompress the batch back into serialized operations.
+    pub fn decode(&self) -> Result<Vec<Vec<u8>>> {
+        Ok(self.decode_bounded(MAX_DECODED_BATCH_BYTES)?.0)
+    }
+
+    /// Decompress at most `limit` bytes, returning the operations and the
+    /// decompressed size.
+    fn decode_bounded(&self, limit: usize) -> Result<(Vec<Vec<u8>>, usize)> {
+        let framed = zstd::bulk::decompress(&self.data, limit)
+            .context("Failed to decompress operation batch")?;
+        Ok((unframe(&framed, self.count)?, framed.len()))
+    }
+}
+
+/// Split `operations` into compressed batches of at most
+/// [`MAX_BATCH_BYTES`] uncompressed, preserving their order.
+///
+/// Fails if the operations exceed [`MAX_DECODED_MESSAGE_BYTES`] in total.
+pub fn encode_batches(operations: Vec<Vec<u8>>) -> Result<Vec<OperationBatch>> {
+    let total: usize = operations
+        .iter()
+        .map(|op| LENGTH_PREFIX_BYTES + op.len())
+        .sum();
+    if total > MAX_DECODED_MESSAGE_BYTES {
+        anyhow::bail!(
+            "Operations take {} bytes, above the {} byte message limit",
+            total,
+            MAX_DECODED_MESSAGE_BYTES
+        );
+    }
+    split(operations, MAX_BATCH_BYTES)
+        .iter()
+        .map(|group| OperationBatch::encode(group))
+        .collect()
+}
+
+/// Decode `batches` in order into serialized operations, decompressing at
+/// most [`MAX_DECODED_MESSAGE_BYTES`] in total.
+pub fn decode_batches(batches: &[OperationBatch]) -> Result<Vec<Vec<u8>>> {
+    let mut operations = Vec::new();
+    let mut remaining = MAX_DECODED_MESSAGE_BYTES;
+    for batch in batches {
+        let (decoded, size) = batch.decode_bounded(remaining.min(MAX_DECODED_BATCH_BYTES))?;
+        remaining -= size;
+        operations.extend(decoded);
+    }
+    Ok(operations)
+}
+
+/// Group `operations` so that each group's framed size is at most
+/// `max_bytes`, except for single operations that exceed it on their own.
+fn split(operations: Vec<Vec<u8>>, max_bytes: usize) -> Vec<Vec<Vec<u8>>> {
+    let mut groups = Vec::new();
+    let mut current = Vec::new();
+    let mut current_bytes = 0;
+    for operation in operations {
+        let size = LENGTH_PREFIX_BYTES + operation.len();
+        if !current.is_empty() && current_bytes + size > max_bytes {
+            groups.push(std::mem::take(&mut current));
+            current_bytes = 0;
+        }
+        current_bytes += size;
+        current.push(operation);
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+    groups
+}
+
+fn frame(operations: &[Vec<u8>]) -> Result<Vec<u8>> {
+    let total: usize = operations
+        .iter()
+        .map(|op| LENGTH_PREFIX_BYTES + op.len())
+        .sum();
+    let mut framed = Vec::with_capacity(total);
+    for operation in operations {
+        let len = u32::try_from(operation.len()).context("Operation too large to frame")?;
+        framed.extend_from_slice(&len.to_le_bytes());
+        framed.extend_from_slice(operation);
+    }
+    Ok(framed)
+}
+
+fn unframe(mut framed: &[u8], count: u32) -> Result<Vec<Vec<u8>>> {
+    let mut operations = Vec::new();
+    while !framed.is_empty() {
+        if framed.len() < LENGTH_PREFIX_BYTES {
+            anyhow::bail!("Truncated operation length in batch");
+        }
+        let (prefix, rest) = framed.split_at(LENGTH_PREFIX_BYTES);
+        let len = u32::from_le_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]) as usize;
+        if rest.len() < len {
+            anyhow::bail!("Truncated operation in batch");
+        }
+        let (operation, rest) = rest.split_at(len);
+        operations.push(operation.to_vec());
+        framed = rest;
+    }
+    if operations.len() != count as usize {
+        anyhow::bail!(
+            "Operation batch declares {} operations but contains {}",
+            count,
+            operations.len()
+        );
+    }
+    Ok(operations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operation(byte: u8, len: usize) -> Vec<u8> {
+        vec![byte; len]
+    }
+
+    #[test]
+    fn test_batch_roundtrip() {
+        let operations = vec![
+            br#"{"op":"create"}"#.to_vec(),
+            Vec::new(),
+            operation(7, 10_000),
+        ];
+        let batch = OperationBatch::encode(&operations).unwrap();
+        assert_eq!(batch.count, 3);
+        assert!(batch.data.len() < 10_000);
+        assert_eq!(batch.decode().unwrap(), operations);
+    }
+
+    #[test]
+    fn test_split_respects_size_cap_and_order() {
+        let operations: Vec<Vec<u8>> = (0..5).map(|i| operation(i, 96)).collect();
+        // Two framed operations (2 * 100 bytes) fit per group.
+        let groups = split(operations.clone(), 200);
+        assert_eq!(
+            groups.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        assert_eq!(groups.concat(), operations);
+
+        // An oversized operation travels alone.
+        let groups = split(vec![operation(1, 10), operation(2, 500)], 200);
+        assert_eq!(groups.len(), 2);
+    }
+
+    #[test]
+    fn test_encode_and_decode_batches() {
+        let operations: Vec<Vec<u8>> = (0..40).map(|i| operation(i, 20_000)).collect();
+        let batches = encode_batches(operations.clone()).unwrap();
+        assert!(batches.len() > 1);
+        assert_eq!(decode_batches(&batches).unwrap(), operations);
+        assert!(encode_batches(Vec::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_decode_batches_bounds_total_size() {
+        // Each batch is within the per-batch limit, but together they expand
+        // past the message limit.
+        let batch = OperationBatch::encode(&[operation(0, MAX_DECODED_BATCH_BYTES - 8)]).unwrap();
+        let count = MAX_DECODED_MESSAGE_BYTES / MAX_DECODED_BATCH_BYTES + 1;
+        assert!(decode_batches(&vec![batch.clone(); count - 1]).is_ok());
+        assert!(decode_batches(&vec![batch; count]).is_err());
+
+        let oversized = vec![operation(0, MAX_DECODED_MESSAGE_BYTES)];
+        assert!(encode_batches(oversized).is_err());
+    }
+
+    #[test]
+    fn test_unframe_rejects_malformed_batches() {
+        let framed = frame(&[operation(1, 8)]).unwrap();
+        assert!(unframe(&framed[..framed.len() - 1], 1).is_err());
+        assert!(unframe(&framed[..2], 1).is_err());
+        assert!(unframe(&framed, 2).is_err());
+        assert_eq!(unframe(&framed, 1).unwrap(), vec![operation(1, 8)]);
+    }
+}
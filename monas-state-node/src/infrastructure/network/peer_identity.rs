@@ -0,0 +1,343 @@
+//! Persistent libp2p identity keypair.
+//!
+//! The PeerId is derived from the node's Ed25519 identity key, and DHT routing
+//! entries, provider records and content network memberships all refer to it.
+//! The key is therefore kept in the data directory and reused across restarts.
+//!
+//! Two on-disk formats are supported:
+//!
+//! - plaintext: the raw 32-byte Ed25519 secret (the original format)
+//! - encrypted: the secret sealed with AES-256-GCM under a key derived from a
+//!   passphrase with scrypt
+//!
+//! ```text
+//! "\0MPK" | version (1) | log_n (1) | r (4, BE) | p (4, BE) | salt (16) | nonce (12) | ciphertext
+//! ```
+//!
+//! When a passphrase is configured and an existing plaintext key is found, the
+//! key is re-written encrypted so the PeerId is preserved.
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{Context, Result};
+use libp2p::identity::Keypair;
+use rand::RngCore;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Default file name of the identity key inside the data directory.
+pub const DEFAULT_PEER_KEY_FILE: &str = "peer_key.ed25519";
+
+/// Prefix marking an encrypted key file. A plaintext key is exactly 32 bytes,
+/// so the two formats are also told apart by length.
+const ENCRYPTED_MAGIC: &[u8; 4] = b"\0MPK";
+const ENCRYPTED_VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = ENCRYPTED_MAGIC.len() + 1 + 1 + 4 + 4 + SALT_LEN + NONCE_LEN;
+const SECRET_LEN: usize = 32;
+
+/// Where the identity keypair is stored and how it is protected.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PeerIdentityConfig {
+    /// Key file path. Defaults to `peer_key.ed25519` in the data directory.
+    pub key_path: Option<PathBuf>,
+    /// Passphrase for encrypting the key file. Stored in plaintext when unset.
+    pub passphrase: Option<String>,
+    /// scrypt cost (log2 N) used when writing an encrypted key file. Reading
+    /// uses the parameters recorded in the file.
+    pub scrypt_log_n: u8,
+}
+
+impl Default for PeerIdentityConfig {
+    fn default() -> Self {
+        Self {
+            key_path: None,
+            passphrase: None,
+            scrypt_log_n: 15,
+        }
+    }
+}
+
+impl fmt::Debug for PeerIdentityConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PeerIdentityConfig")
+            .field("key_path", &self.key_path)
+            .field(
+                "passphrase",
+                &self.passphrase.as_ref().map(|_| "<redacted>"),
+            )
+            .field("scrypt_log_n", &self.scrypt_log_n)
+            .finish()
+    }
+}
+
+impl PeerIdentityConfig {
+    /// Load the configuration from environment variables.
+    ///
+    /// - `PEER_KEY_PATH`: key file path (default: `<data_dir>/peer_key.ed25519`)
+    /// - `PEER_KEY_PASSPHRASE`: passphrase for encrypting the key file
+    pub fn from_env() -> Self {
+        let non_empty = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Self {
+            key_path: non_empty("PEER_KEY_PATH").map(PathBuf::from),
+            passphrase: non_empty("PEER_KEY_PASSPHRASE"),
+            ..Self::default()
+        }
+    }
+
+    /// Resolve the key file path for `data_dir`.
+    pub fn key_path(&self, data_dir: &Path) -> PathBuf {
+        self.key_path
+            .clone()
+            .unwrap_or_else(|| data_dir.join(DEFAULT_PEER_KEY_FILE))
+    }
+
+    /// Load the identity keypair, or generate and save a new one.
+    pub fn load_or_generate(&self, data_dir: &Path) -> Result<Keypair> {
+        let key_path = self.key_path(data_dir);
+        if !key_path.exists() {
+            let keypair = Keypair::generate_ed25519();
+            self.save(&key_path, &keypair)?;
+            info!(
+                "Generated and saved new peer keypair to {}",
+                key_path.display()
+            );
+            return Ok(keypair);
+        }
+
+        let bytes = std::fs::read(&key_path).context("Failed to read peer keypair from disk")?;
+        if bytes.starts_with(ENCRYPTED_MAGIC) {
+            let passphrase = self.passphrase.as_deref().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Peer keypair at {} is encrypted but no passphrase was configured",
+                    key_path.display()
+                )
+            })?;
+            let keypair = keypair_from_secret(decrypt_secret(&bytes, passphrase)?)?;
+            info!("Loaded encrypted peer keypair from {}", key_path.display());
+            return Ok(keypair);
+        }
+
+        let keypair = keypair_from_secret(bytes)?;
+        if self.passphrase.is_some() {
+            self.save(&key_path, &keypair)?;
+            info!("Encrypted existing peer keypair at {}", key_path.display());
+        } else {
+            info!("Loaded peer keypair from {}", key_path.display());
+        }
+        Ok(keypair)
+    }
+
+    /// Write `keypair` to `path`, encrypted when a passphrase is configured.
+    pub fn save(&self, path: &Path, keypair: &Keypair) -> Result<()> {
+        let secret = keypair
+            .clone()
+            .try_into_ed25519()
+            .map_err(|_| anyhow::anyhow!("Peer keypair must be Ed25519"))?
+            .secret()
+            .as_ref()
+            .to_vec();
+        let contents = match &self.passphrase {
+            Some(passphrase) => encrypt_secret(&secret, passphrase, self.scrypt_log_n)?,
+            None => secret,
+        };
+        write_private_file(path, &contents)
+    }
+}
+
+fn keypair_from_secret(mut secret: Vec<u8>) -> Result<Keypair> {
+    if secret.len() != SECRET_LEN {
+        anyhow::bail!(
+            "Invalid peer keypair: expected {} bytes, got {}",
+            SECRET_LEN,
+            secret.len()
+        );
+    }
+    Keypair::ed25519_from_bytes(&mut secret)
+        .map_err(|e| anyhow::anyhow!("Failed to decode peer keypair: {:?}", e))
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> Result<[u8; 32]> {
+    let params = scrypt::Params::new(log_n, r, p, 32)
+        .map_err(|e| anyhow::anyhow!("Invalid scrypt parameters: {}", e))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+        .map_err(|e| anyhow::anyhow!("scrypt failed: {}", e))?;
+    Ok(key)
+}
+
+fn encrypt_secret(secret: &[u8], passphrase: &str, log_n: u8) -> Result<Vec<u8>> {
+    let (r, p) = (8u32, 1u32);
+    let mut salt = [0u8; SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let mut out = Vec::with_capacity(HEADER_LEN + SECRET_LEN + 16);
+    out.extend_from_slice(ENCRYPTED_MAGIC);
+    out.push(ENCRYPTED_VERSION);
+    out.push(log_n);
+    out.extend_from_slice(&r.to_be_bytes());
+    out.extend_from_slice(&p.to_be_bytes());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+
+    let key = derive_key(passphrase, &salt, log_n, r, p)?;
+    let cipher = Aes256Gcm::new(&key.into());
+    // The header is authenticated so the KDF parameters can't be swapped.
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: secret,
+                aad: &out,
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt peer keypair"))?;
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+fn decrypt_secret(bytes: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    if bytes.len() <= HEADER_LEN {
+        anyhow::bail!("Encrypted peer keypair is truncated");
+    }
+    let (header, ciphertext) = bytes.split_at(HEADER_LEN);
+    let version = header[ENCRYPTED_MAGIC.len()];
+    if version != ENCRYPTED_VERSION {
+        anyhow::bail!("Unsupported encrypted peer keypair version {}", version);
+    }
+    let mut offset = ENCRYPTED_MAGIC.len() + 1;
+    let log_n = header[offset];
+    offset += 1;
+    let r = u32::from_be_bytes(header[offset..offset + 4].try_into()?);
+    offset += 4;
+    let p = u32::from_be_bytes(header[offset..offset + 4].try_into()?);
+    offset += 4;
+    let salt = &header[offset..offset + SALT_LEN];
+    offset += SALT_LEN;
+    let nonce = &header[offset..offset + NONCE_LEN];
+
+    let key = derive_key(passphrase, salt, log_n, r, p)?;
+    let cipher = Aes256Gcm::new(&key.into());
+    cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map_err(|_| {
+            anyhow::anyhow!("Failed to decrypt peer keypair (wrong passphrase or corrupted file)")
+        })
+}
+
+/// Atomically write `contents` to `path` with owner-only permissions.
+fn write_private_file(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).context("Failed to create data directory for peer key")?;
+    }
+    let mut tmp_name = path.as_os_str().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&tmp_path)
+        .context("Failed to write peer keypair to disk")?;
+    file.write_all(contents)
+        .and_then(|_| file.sync_all())
+        .context("Failed to write peer keypair to disk")?;
+    std::fs::rename(&tmp_path, path).context("Failed to write peer keypair to disk")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn encrypted_config(passphrase: &str) -> PeerIdentityConfig {
+        PeerIdentityConfig {
+            passphrase: Some(passphrase.to_string()),
+            scrypt_log_n: 4,
+            ..PeerIdentityConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_plaintext_identity_is_stable_across_restarts() {
+        let dir = tempdir().unwrap();
+        let config = PeerIdentityConfig::default();
+
+        let first = config.load_or_generate(dir.path()).unwrap();
+        let second = config.load_or_generate(dir.path()).unwrap();
+
+        assert_eq!(first.public().to_peer_id(), second.public().to_peer_id());
+        let bytes = std::fs::read(dir.path().join(DEFAULT_PEER_KEY_FILE)).unwrap();
+        assert_eq!(bytes.len(), SECRET_LEN);
+    }
+
+    #[test]
+    fn test_encrypted_identity_requires_passphrase() {
+        let dir = tempdir().unwrap();
+        let config = encrypted_config("correct horse");
+
+        let first = config.load_or_generate(dir.path()).unwrap();
+        let bytes = std::fs::read(config.key_path(dir.path())).unwrap();
+        assert!(bytes.starts_with(ENCRYPTED_MAGIC));
+
+        let second = config.load_or_generate(dir.path()).unwrap();
+        assert_eq!(first.public().to_peer_id(), second.public().to_peer_id());
+
+        assert!(encrypted_config("wrong")
+            .load_or_generate(dir.path())
+            .is_err());
+        assert!(PeerIdentityConfig::default()
+            .load_or_generate(dir.path())
+            .is_err());
+    }
+
+    #[test]
+    fn test_plaintext_identity_is_encrypted_when_passphrase_is_set() {
+        let dir = tempdir().unwrap();
+        let plaintext = PeerIdentityConfig::default()
+            .load_or_generate(dir.path())
+            .unwrap();
+
+        let config = encrypted_config("pass");
+        let migrated = config.load_or_generate(dir.path()).unwrap();
+
+        assert_eq!(
+            plaintext.public().to_peer_id(),
+            migrated.public().to_peer_id()
+        );
+        let bytes = std::fs::read(config.key_path(dir.path())).unwrap();
+        assert!(bytes.starts_with(ENCRYPTED_MAGIC));
+    }
+
+    #[test]
+    fn test_custom_key_path() {
+        let dir = tempdir().unwrap();
+        let key_path = dir.path().join("keys").join("node.key");
+        let config = PeerIdentityConfig {
+            key_path: Some(key_path.clone()),
+            ..PeerIdentityConfig::default()
+        };
+
+        config.load_or_generate(dir.path()).unwrap();
+
+        assert!(key_path.exists());
+        assert!(!dir.path().join(DEFAULT_PEER_KEY_FILE).exists());
+    }
+}
@@ -6,13 +6,21 @@
 
 use serde::{Deserialize, Serialize};
 
-pub use crate::port::peer_network::PushBootstrap;
+use crate::domain::mirror::{MirrorPairingAcceptance, MirrorPairingRequest};
+use crate::domain::sync_rules::SyncRules;
+
+pub use super::operation_batch::OperationBatch;
+pub use crate::port::content_repository::ContentSnapshot;
+pub use crate::port::peer_network::{PushBootstrap, ReadCapability};
 
 /// Protocol name for capacity queries.
 pub const CAPACITY_PROTOCOL: &str = "/monas/capacity/1.0.0";
 
 /// Protocol name for content fetching.
-pub const CONTENT_PROTOCOL: &str = "/monas/content/1.0.0";
+///
+/// 1.1.0 carries operations only in compressed batches; peers speaking
+/// 1.0.0 fail protocol negotiation instead of receiving empty payloads.
+pub const CONTENT_PROTOCOL: &str = "/monas/content/1.1.0";
 
 /// Request types for the content protocol.
 ///
@@ -22,22 +30,52 @@ pub enum ContentRequest {
     /// Query the capacity of a node.
     CapacityQuery,
     /// Fetch content by CID.
-    FetchContent { content_id: String },
+    FetchContent {
+        content_id: String,
+        /// Read capability of a requester that is not a member of the
+        /// content network.
+        #[serde(default)]
+        capability: Option<ReadCapability>,
+    },
+    /// Fetch a byte range of a content's latest data, so large content can
+    /// be fetched in chunks from several peers at once.
+    FetchContentRange {
+        content_id: String,
+        offset: u64,
+        length: u64,
+        /// Read capability of a requester that is not a member of the
+        /// content network.
+        #[serde(default)]
+        capability: Option<ReadCapability>,
+    },
     /// Sync content from a node.
     SyncContent {
         content_id: String,
         from_version: Option<String>,
+        /// Read capability of a requester that is not a member of the
+        /// content network.
+        #[serde(default)]
+        capability: Option<ReadCapability>,
     },
     /// Fetch CRDT operations for a content.
     FetchOperations {
         genesis_cid: String,
         since_version: Option<String>,
+        /// Whether the requester can apply a snapshot in place of the
+        /// operations it covers. Unset by peers that predate snapshots.
+        #[serde(default)]
+        accept_snapshot: bool,
+        /// Read capability of a requester that is not a member of the
+        /// content network.
+        #[serde(default)]
+        capability: Option<ReadCapability>,
     },
     /// Push CRDT operations to a peer.
     PushOperations {
         genesis_cid: String,
-        /// Serialized operations (JSON-encoded)
-        operations: Vec<Vec<u8>>,
+        /// Compressed batch of serialized operations. Large pushes are split
+        /// into several requests of one batch each.
+        batch: OperationBatch,
         /// If set, the receiver is allowed to bootstrap a local
         /// `ContentNetwork` record from this payload (only the first push
         /// for a given genesis_cid carries this; update/delete pushes leave
@@ -66,6 +104,8 @@ pub enum ContentRequest {
         request_signature: Vec<u8>,
         timestamp: Option<u64>,
     },
+    /// Signed mirror pairing request from a secondary to its primary.
+    MirrorPair { request: MirrorPairingRequest },
 }
 
 /// Response types for the content protocol.
@@ -77,6 +117,10 @@ pub enum ContentResponse {
     CapacityResponse {
         total_capacity: u64,
         available_capacity: u64,
+        /// Selective sync rules of the responding node, so placement can
+        /// avoid nodes unwilling to replicate a given content.
+        #[serde(default)]
+        sync_rules: SyncRules,
     },
     /// Response to content fetch.
     ContentData {
@@ -84,10 +128,29 @@ pub enum ContentResponse {
         data: Vec<u8>,
         version: String,
     },
+    /// Response to a content range fetch.
+    ContentRange {
+        content_id: String,
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+        version: String,
+        /// Size of the whole data in bytes.
+        total_size: u64,
+    },
     /// Response with CRDT operations.
     OperationsData {
         genesis_cid: String,
-        operations: Vec<Vec<u8>>, // Serialized operations
+        /// Compressed batches of serialized operations, in order.
+        #[serde(default)]
+        batches: Vec<OperationBatch>,
+        /// Snapshot standing in for the operations before `batches`, sent
+        /// when the requester is behind it.
+        #[serde(default)]
+        snapshot: Option<ContentSnapshot>,
+        /// The responder's latest version CID. Unset by peers that predate
+        /// sync status reporting.
+        #[serde(default)]
+        latest_version: Option<String>,
     },
     /// Response to push operations request.
     PushResult {
@@ -101,6 +164,8 @@ pub enum ContentResponse {
     DeleteResult { content_id: String, success: bool },
     /// Response to relayed invalidate_tokens request.
     InvalidateTokensResult { content_id: String, success: bool },
+    /// Signed acceptance of a mirror pairing request.
+    MirrorPairAccepted { acceptance: MirrorPairingAcceptance },
     /// Content not found.
     NotFound { content_id: String },
     /// Error response.
@@ -136,12 +201,14 @@ mod tests {
         let resp = ContentResponse::CapacityResponse {
             total_capacity: 1000,
             available_capacity: 800,
+            sync_rules: SyncRules::default(),
         };
         let bytes = serde_json::to_vec(&resp).unwrap();
         let decoded: ContentResponse = serde_json::from_slice(&bytes).unwrap();
         if let ContentResponse::CapacityResponse {
             total_capacity,
             available_capacity,
+            ..
         } = decoded
         {
             assert_eq!(total_capacity, 1000);
@@ -150,4 +217,58 @@ mod tests {
             panic!("Expected CapacityResponse");
         }
     }
+
+    #[test]
+    fn test_capacity_response_sync_rules_roundtrip() {
+        let rules = SyncRules {
+            labels: vec!["sync:high".to_string()],
+            namespaces: vec![],
+            max_content_size: Some(1024),
+        };
+        let resp = ContentResponse::CapacityResponse {
+            total_capacity: 1000,
+            available_capacity: 800,
+            sync_rules: rules.clone(),
+        };
+        let bytes = serde_json::to_vec(&resp).unwrap();
+        let decoded: ContentResponse = serde_json::from_slice(&bytes).unwrap();
+        match decoded {
+            ContentResponse::CapacityResponse { sync_rules, .. } => assert_eq!(sync_rules, rules),
+            _ => panic!("Expected CapacityResponse"),
+        }
+    }
+
+    #[test]
+    fn test_capacity_response_without_sync_rules_defaults_to_replicate_all() {
+        let json = r#"{"CapacityResponse":{"total_capacity":1000,"available_capacity":800}}"#;
+        let decoded: ContentResponse = serde_json::from_str(json).unwrap();
+        match decoded {
+            ContentResponse::CapacityResponse { sync_rules, .. } => {
+                assert!(sync_rules.is_replicate_all())
+            }
+            _ => panic!("Expected CapacityResponse"),
+        }
+    }
+
+    #[test]
+    fn test_fetch_operations_from_older_peer_does_not_accept_snapshot() {
+        let json = r#"{"FetchOperations":{"genesis_cid":"cid-1","since_version":null}}"#;
+        let decoded: ContentRequest = serde_json::from_str(json).unwrap();
+        match decoded {
+            ContentRequest::FetchOperations {
+                accept_snapshot, ..
+            } => assert!(!accept_snapshot),
+            _ => panic!("Expected FetchOperations"),
+        }
+    }
+
+    #[test]
+    fn test_read_request_from_older_peer_has_no_capability() {
+        let json = r#"{"FetchContent":{"content_id":"cid-1"}}"#;
+        let decoded: ContentRequest = serde_json::from_str(json).unwrap();
+        match decoded {
+            ContentRequest::FetchContent { capability, .. } => assert!(capability.is_none()),
+            _ => panic!("Expected FetchContent"),
+        }
+    }
 }
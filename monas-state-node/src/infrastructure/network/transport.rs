@@ -2,15 +2,120 @@
 //!
 //! Provides transport builders for server-to-server communication:
 //! - TCP + QUIC + WebRTC with Noise encryption and Yamux multiplexing
+//! - Optional relay (circuit v2) transport for reaching nodes behind NAT
+//! - Optional private network (pnet) pre-shared key, which restricts the
+//!   overlay to nodes holding the same key
 //!
 //! WebRTC is included for future browser-to-server communication support.
 
+use anyhow::Context;
+use futures::future::Either;
+use futures::{AsyncRead, AsyncWrite};
 use libp2p::{
     core::{muxing::StreamMuxerBox, transport::Boxed, upgrade},
     dns,
     identity::Keypair,
-    noise, quic, tcp, yamux, PeerId, Transport,
+    noise,
+    pnet::{PnetConfig, PnetOutput, PreSharedKey},
+    quic, relay, tcp, yamux, PeerId, Transport,
 };
+use std::fmt;
+use std::time::Duration;
+
+/// Default timeout for the security and multiplexer handshake of a new
+/// connection.
+pub const DEFAULT_UPGRADE_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Handshake parameters and private network key of the transport.
+#[derive(Clone)]
+pub struct TransportConfig {
+    /// Pre-shared key of a private network. Connections to peers without
+    /// the same key fail before the Noise handshake. QUIC and WebRTC encrypt
+    /// below the libp2p upgrade and cannot use the key, so they are disabled
+    /// when it is set.
+    pub psk: Option<PreSharedKey>,
+    /// Noise handshake prologue. Peers with a different prologue fail the
+    /// handshake. Empty by default.
+    pub noise_prologue: Vec<u8>,
+    /// Timeout for the security and multiplexer handshake of TCP and relayed
+    /// connections.
+    pub upgrade_timeout: Duration,
+    /// Maximum number of concurrent Yamux streams per connection. The Yamux
+    /// default is used when unset.
+    pub yamux_max_streams: Option<usize>,
+}
+
+impl Default for TransportConfig {
+    fn default() -> Self {
+        Self {
+            psk: None,
+            noise_prologue: Vec::new(),
+            upgrade_timeout: DEFAULT_UPGRADE_TIMEOUT,
+            yamux_max_streams: None,
+        }
+    }
+}
+
+impl fmt::Debug for TransportConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransportConfig")
+            .field("psk", &self.psk.map(|psk| psk.fingerprint().to_string()))
+            .field("noise_prologue", &self.noise_prologue)
+            .field("upgrade_timeout", &self.upgrade_timeout)
+            .field("yamux_max_streams", &self.yamux_max_streams)
+            .finish()
+    }
+}
+
+impl TransportConfig {
+    fn noise_config(&self, keypair: &Keypair) -> anyhow::Result<noise::Config> {
+        Ok(noise::Config::new(keypair)?.with_prologue(self.noise_prologue.clone()))
+    }
+
+    fn yamux_config(&self) -> yamux::Config {
+        let mut config = yamux::Config::default();
+        if let Some(max_streams) = self.yamux_max_streams {
+            config.set_max_num_streams(max_streams);
+        }
+        config
+    }
+}
+
+/// Read a private network key file in the `/key/swarm/psk/1.0.0/` format
+/// (as used by IPFS).
+pub fn load_swarm_key(path: &std::path::Path) -> anyhow::Result<PreSharedKey> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read swarm key {}", path.display()))?;
+    content
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid swarm key {}: {:?}", path.display(), e))
+}
+
+/// Run the private network handshake on every connection of `transport`
+/// when a pre-shared key is given.
+fn with_psk<T>(
+    transport: T,
+    psk: Option<PreSharedKey>,
+) -> Boxed<Either<PnetOutput<T::Output>, T::Output>>
+where
+    T: Transport + Send + Unpin + 'static,
+    T::Output: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    T::Error: Send + Sync + 'static,
+    T::Dial: Send + 'static,
+    T::ListenerUpgrade: Send + 'static,
+{
+    match psk {
+        Some(psk) => transport
+            .and_then(move |socket, _| async move {
+                PnetConfig::new(psk)
+                    .handshake(socket)
+                    .await
+                    .map(Either::Left)
+            })
+            .boxed(),
+        None => transport.map(|socket, _| Either::Right(socket)).boxed(),
+    }
+}
 
 /// Build the transport layer for native platforms.
 ///
@@ -18,19 +123,19 @@ use libp2p::{
 /// - TCP: Traditional transport with Noise + Yamux
 /// - QUIC: Modern, efficient transport with built-in encryption
 /// - WebRTC: Required for browser communication (future)
-pub fn build_transport(keypair: &Keypair) -> anyhow::Result<Boxed<(PeerId, StreamMuxerBox)>> {
+///
+/// With a private network key only TCP is used.
+pub fn build_transport(
+    keypair: &Keypair,
+    config: &TransportConfig,
+) -> anyhow::Result<Boxed<(PeerId, StreamMuxerBox)>> {
     use rand::rngs::OsRng;
 
-    // TCP transport with DNS resolution
-    let tcp_transport = tcp::tokio::Transport::new(tcp::Config::default().nodelay(true));
-    let dns_tcp = dns::tokio::Transport::system(tcp_transport)?;
-
-    // Apply Noise encryption and Yamux multiplexing to TCP
-    let tcp_upgraded = dns_tcp
-        .upgrade(upgrade::Version::V1)
-        .authenticate(noise::Config::new(keypair)?)
-        .multiplex(yamux::Config::default())
-        .timeout(std::time::Duration::from_secs(20));
+    // TCP transport with DNS resolution, Noise encryption and Yamux multiplexing
+    let tcp_upgraded = build_tcp_transport(keypair, config)?;
+    if config.psk.is_some() {
+        return Ok(tcp_upgraded);
+    }
 
     // QUIC transport (includes its own encryption)
     let quic_transport = quic::tokio::Transport::new(quic::Config::new(keypair));
@@ -45,19 +150,42 @@ pub fn build_transport(keypair: &Keypair) -> anyhow::Result<Boxed<(PeerId, Strea
     let transport = tcp_upgraded
         .or_transport(quic_transport)
         .map(|either, _| match either {
-            futures::future::Either::Left((peer_id, muxer)) => {
-                (peer_id, StreamMuxerBox::new(muxer))
-            }
-            futures::future::Either::Right((peer_id, muxer)) => {
-                (peer_id, StreamMuxerBox::new(muxer))
-            }
+            Either::Left(output) => output,
+            Either::Right((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
         })
         .or_transport(webrtc_transport)
         .map(|either, _| match either {
-            futures::future::Either::Left((peer_id, muxer)) => (peer_id, muxer),
-            futures::future::Either::Right((peer_id, muxer)) => {
-                (peer_id, StreamMuxerBox::new(muxer))
-            }
+            Either::Left(output) => output,
+            Either::Right((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+        })
+        .boxed();
+
+    Ok(transport)
+}
+
+/// Build the native transport with an additional relay (circuit v2) transport.
+///
+/// `relay_transport` comes from [`libp2p::relay::client::new`], whose
+/// behaviour half must be part of the swarm's `NodeBehaviour`. Relayed
+/// connections are upgraded like plain TCP, including the private network
+/// handshake.
+pub fn build_transport_with_relay(
+    keypair: &Keypair,
+    relay_transport: relay::client::Transport,
+    config: &TransportConfig,
+) -> anyhow::Result<Boxed<(PeerId, StreamMuxerBox)>> {
+    let relay_upgraded = with_psk(relay_transport, config.psk)
+        .upgrade(upgrade::Version::V1)
+        .authenticate(config.noise_config(keypair)?)
+        .multiplex(config.yamux_config())
+        .timeout(config.upgrade_timeout)
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
+
+    let transport = build_transport(keypair, config)?
+        .or_transport(relay_upgraded)
+        .map(|either, _| match either {
+            Either::Left(output) => output,
+            Either::Right(output) => output,
         })
         .boxed();
 
@@ -65,15 +193,19 @@ pub fn build_transport(keypair: &Keypair) -> anyhow::Result<Boxed<(PeerId, Strea
 }
 
 /// Build a TCP-only transport for testing or simpler setups.
-pub fn build_tcp_transport(keypair: &Keypair) -> anyhow::Result<Boxed<(PeerId, StreamMuxerBox)>> {
+pub fn build_tcp_transport(
+    keypair: &Keypair,
+    config: &TransportConfig,
+) -> anyhow::Result<Boxed<(PeerId, StreamMuxerBox)>> {
     let tcp_transport = tcp::tokio::Transport::new(tcp::Config::default().nodelay(true));
     let dns_tcp = dns::tokio::Transport::system(tcp_transport)?;
 
-    let transport = dns_tcp
+    let transport = with_psk(dns_tcp, config.psk)
         .upgrade(upgrade::Version::V1)
-        .authenticate(noise::Config::new(keypair)?)
-        .multiplex(yamux::Config::default())
-        .timeout(std::time::Duration::from_secs(20))
+        .authenticate(config.noise_config(keypair)?)
+        .multiplex(config.yamux_config())
+        .timeout(config.upgrade_timeout)
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
         .boxed();
 
     Ok(transport)
@@ -127,10 +259,45 @@ mod tests {
     #[test]
     fn test_build_tcp_transport() {
         let keypair = Keypair::generate_ed25519();
-        let result = build_tcp_transport(&keypair);
+        let result = build_tcp_transport(&keypair, &TransportConfig::default());
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_build_transport_with_relay() {
+        let keypair = Keypair::generate_ed25519();
+        let (relay_transport, _relay_behaviour) = relay::client::new(keypair.public().to_peer_id());
+        let result =
+            build_transport_with_relay(&keypair, relay_transport, &TransportConfig::default());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_build_private_network_transport() {
+        let keypair = Keypair::generate_ed25519();
+        let config = TransportConfig {
+            psk: Some(PreSharedKey::new([7; 32])),
+            noise_prologue: b"monas-consortium".to_vec(),
+            yamux_max_streams: Some(64),
+            ..TransportConfig::default()
+        };
+        assert!(build_transport(&keypair, &config).is_ok());
+        // The key itself never appears in debug output.
+        assert!(!format!("{:?}", config).contains(&"07".repeat(32)));
+    }
+
+    #[test]
+    fn test_load_swarm_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("swarm.key");
+        let key = PreSharedKey::new([7; 32]);
+        std::fs::write(&path, key.to_string()).unwrap();
+        assert!(load_swarm_key(&path).unwrap() == key);
+
+        std::fs::write(&path, "not a key").unwrap();
+        assert!(load_swarm_key(&path).is_err());
+    }
+
     #[test]
     fn test_build_quic_transport() {
         let keypair = Keypair::generate_ed25519();
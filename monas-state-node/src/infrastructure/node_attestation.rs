@@ -0,0 +1,196 @@
+//! Signing, verification and identify transport of node attestations.
+//!
+//! libp2p identify has no extension fields, so the attestation travels in the
+//! agent version string: `<agent> attestation=<base64url(JSON)>`. Peers that
+//! do not know about attestations simply see a longer agent version.
+
+use crate::domain::node_attestation::NodeAttestation;
+use crate::infrastructure::crypto::{verify_p256_signature, SignatureVerifyError};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use p256::ecdsa::signature::DigestSigner;
+use p256::ecdsa::{Signature, SigningKey};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use thiserror::Error;
+
+/// Separator between the agent name and the encoded attestation.
+const AGENT_VERSION_MARKER: &str = " attestation=";
+
+/// Error type for node attestation failures.
+#[derive(Debug, Error)]
+pub enum NodeAttestationError {
+    #[error("Attestation is for peer {actual}, expected {expected}")]
+    PeerMismatch { expected: String, actual: String },
+
+    #[error("Attestation is not valid at this time")]
+    Expired,
+
+    #[error("Invalid attestation signature: {0}")]
+    InvalidSignature(#[from] SignatureVerifyError),
+
+    #[error("Malformed attestation: {0}")]
+    Malformed(String),
+}
+
+/// Sign `attestation` with the owning account's P-256 key.
+///
+/// The account public key in the attestation is replaced with the one of
+/// `account_key` so that the two always match.
+pub fn sign_node_attestation(
+    attestation: NodeAttestation,
+    account_key: &SigningKey,
+) -> NodeAttestation {
+    let attestation = NodeAttestation {
+        account_public_key: account_key
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec(),
+        ..attestation
+    };
+    let (signature, _): (Signature, _) =
+        account_key.sign_digest(Sha256::new_with_prefix(attestation.signing_message()));
+    attestation.with_signature(signature.to_vec())
+}
+
+/// Verify that `attestation` was signed by its account for `peer_id` and is
+/// valid at `now`.
+///
+/// Returns the owning account ID on success.
+pub fn verify_node_attestation(
+    attestation: &NodeAttestation,
+    peer_id: &str,
+    now: u64,
+) -> Result<String, NodeAttestationError> {
+    if attestation.peer_id != peer_id {
+        return Err(NodeAttestationError::PeerMismatch {
+            expected: peer_id.to_string(),
+            actual: attestation.peer_id.clone(),
+        });
+    }
+    if !attestation.is_valid_at(now) {
+        return Err(NodeAttestationError::Expired);
+    }
+    verify_p256_signature(
+        &attestation.signing_message(),
+        &attestation.signature,
+        &attestation.account_public_key,
+    )?;
+    Ok(attestation.account_id())
+}
+
+/// Append the attestation to an identify agent version string.
+pub fn encode_agent_version(agent_version: &str, attestation: &NodeAttestation) -> String {
+    let json = serde_json::to_vec(attestation).expect("NodeAttestation serializes to JSON");
+    format!(
+        "{}{}{}",
+        agent_version,
+        AGENT_VERSION_MARKER,
+        URL_SAFE_NO_PAD.encode(json)
+    )
+}
+
+/// Extract the attestation from an identify agent version string.
+///
+/// Returns `Ok(None)` when the peer did not present one.
+pub fn decode_agent_version(
+    agent_version: &str,
+) -> Result<Option<NodeAttestation>, NodeAttestationError> {
+    let Some((_, encoded)) = agent_version.rsplit_once(AGENT_VERSION_MARKER) else {
+        return Ok(None);
+    };
+    let json = URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|e| NodeAttestationError::Malformed(e.to_string()))?;
+    serde_json::from_slice(&json)
+        .map(Some)
+        .map_err(|e| NodeAttestationError::Malformed(e.to_string()))
+}
+
+/// Load a JSON-encoded attestation issued by the owning account.
+pub fn load_node_attestation(path: &Path) -> anyhow::Result<NodeAttestation> {
+    let json = std::fs::read(path)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::elliptic_curve::rand_core::OsRng;
+
+    fn signed(peer_id: &str, expires_at: Option<u64>) -> (NodeAttestation, SigningKey) {
+        let account_key = SigningKey::random(&mut OsRng);
+        let attestation = sign_node_attestation(
+            NodeAttestation::new(peer_id.to_string(), Vec::new(), 1_000, expires_at),
+            &account_key,
+        );
+        (attestation, account_key)
+    }
+
+    #[test]
+    fn verify_returns_owning_account() {
+        let (attestation, account_key) = signed("peer-a", Some(5_000));
+        let account_id = verify_node_attestation(&attestation, "peer-a", 2_000).unwrap();
+        assert_eq!(
+            account_id,
+            hex::encode(
+                account_key
+                    .verifying_key()
+                    .to_encoded_point(false)
+                    .as_bytes()
+            )
+        );
+    }
+
+    #[test]
+    fn verify_rejects_other_peers_and_expired_attestations() {
+        let (attestation, _) = signed("peer-a", Some(5_000));
+        assert!(matches!(
+            verify_node_attestation(&attestation, "peer-b", 2_000),
+            Err(NodeAttestationError::PeerMismatch { .. })
+        ));
+        assert!(matches!(
+            verify_node_attestation(&attestation, "peer-a", 5_000),
+            Err(NodeAttestationError::Expired)
+        ));
+    }
+
+    #[test]
+    fn verify_rejects_tampered_attestations() {
+        // Re-pointing an attestation at another node invalidates the signature.
+        let (attestation, _) = signed("peer-a", None);
+        let moved = NodeAttestation {
+            peer_id: "peer-b".to_string(),
+            ..attestation.clone()
+        };
+        assert!(matches!(
+            verify_node_attestation(&moved, "peer-b", 2_000),
+            Err(NodeAttestationError::InvalidSignature(_))
+        ));
+
+        // Claiming another account's ownership invalidates it as well.
+        let (other, _) = signed("peer-a", None);
+        let claimed = NodeAttestation {
+            account_public_key: other.account_public_key,
+            ..attestation
+        };
+        assert!(matches!(
+            verify_node_attestation(&claimed, "peer-a", 2_000),
+            Err(NodeAttestationError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn agent_version_round_trip() {
+        let (attestation, _) = signed("peer-a", None);
+        let agent = encode_agent_version("monas-state-node/0.1.0", &attestation);
+        assert!(agent.starts_with("monas-state-node/0.1.0 attestation="));
+        assert_eq!(decode_agent_version(&agent).unwrap(), Some(attestation));
+
+        assert_eq!(decode_agent_version("rust-libp2p/0.56").unwrap(), None);
+        assert!(matches!(
+            decode_agent_version("monas-state-node/0.1.0 attestation=!!"),
+            Err(NodeAttestationError::Malformed(_))
+        ));
+    }
+}
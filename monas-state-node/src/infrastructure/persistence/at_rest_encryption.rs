@@ -0,0 +1,313 @@
+//! Optional at-rest encryption for sled values.
+//!
+//! The node registry and content network trees describe who this node talks
+//! to and which content it replicates. When at-rest encryption is enabled,
+//! values are sealed with AES-256-GCM under a per-namespace key derived (via
+//! HKDF-SHA256) from a root key, so a copied data directory no longer leaks
+//! the social/content graph. Keys stay in plaintext because the repositories
+//! rely on them for lookups and range scans.
+//!
+//! The root key is derived from the node identity key, or supplied externally
+//! (e.g. fetched from an OS keystore by the launcher).
+//!
+//! Existing plaintext databases are migrated transparently: plaintext values
+//! are still readable, and [`ValueCipher::migrate_tree`] re-writes them sealed
+//! when encryption is first enabled.
+
+use crate::infrastructure::key_management::NodeKeyPair;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{Context, Result};
+use hkdf::Hkdf;
+use rand::RngCore;
+use sha2::Sha256;
+use std::fmt;
+
+/// Prefix marking a sealed value. Plaintext values are JSON and never start
+/// with a NUL byte, so legacy entries can be told apart without a side table.
+const SEALED_MAGIC: &[u8; 4] = b"\0MSE";
+/// Sealed value format version, stored right after the magic.
+const SEALED_VERSION: u8 = 1;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = SEALED_MAGIC.len() + 1 + NONCE_LEN;
+
+const ROOT_KEY_SALT: &[u8] = b"monas-state-node/at-rest/root";
+const NAMESPACE_INFO_PREFIX: &str = "monas-state-node/at-rest/v1/";
+
+/// Where the at-rest root key comes from.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub enum AtRestKeySource {
+    /// Values are stored in plaintext.
+    #[default]
+    Disabled,
+    /// Derive the root key from the node's P-256 identity key.
+    NodeIdentity,
+    /// Use an externally supplied 32-byte key (e.g. from an OS keystore).
+    Provided([u8; 32]),
+}
+
+impl AtRestKeySource {
+    /// Load the key source from environment variables.
+    ///
+    /// - `AT_REST_KEY`: hex-encoded 32-byte key; takes precedence when set
+    /// - `ENCRYPT_AT_REST`: `true`/`1` to derive the key from the node identity
+    ///
+    /// An invalid `AT_REST_KEY` is ignored with a warning.
+    pub fn from_env() -> Self {
+        if let Some(hex_key) = std::env::var("AT_REST_KEY")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+        {
+            match Self::parse_hex_key(hex_key.trim()) {
+                Ok(key) => return Self::Provided(key),
+                Err(e) => tracing::warn!("Ignoring AT_REST_KEY: {}", e),
+            }
+        }
+
+        let enabled = std::env::var("ENCRYPT_AT_REST")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+        if enabled {
+            Self::NodeIdentity
+        } else {
+            Self::Disabled
+        }
+    }
+
+    fn parse_hex_key(hex_key: &str) -> Result<[u8; 32]> {
+        let bytes = hex::decode(hex_key).context("key must be hex encoded")?;
+        bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("key must be 32 bytes"))
+    }
+
+    /// Resolve the root key for this source, or `None` when disabled.
+    pub fn resolve(&self, node_key: &NodeKeyPair) -> Option<AtRestKey> {
+        match self {
+            Self::Disabled => None,
+            Self::NodeIdentity => Some(AtRestKey::from_node_key(node_key)),
+            Self::Provided(key) => Some(AtRestKey::from_bytes(*key)),
+        }
+    }
+}
+
+impl fmt::Debug for AtRestKeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disabled => f.write_str("Disabled"),
+            Self::NodeIdentity => f.write_str("NodeIdentity"),
+            Self::Provided(_) => f.write_str("Provided(<redacted>)"),
+        }
+    }
+}
+
+/// Root key from which per-namespace value keys are derived.
+#[derive(Clone)]
+pub struct AtRestKey([u8; 32]);
+
+impl AtRestKey {
+    /// Use the given bytes as the root key.
+    pub fn from_bytes(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    /// Derive the root key from the node identity private key.
+    pub fn from_node_key(node_key: &NodeKeyPair) -> Self {
+        let hk = Hkdf::<Sha256>::new(Some(ROOT_KEY_SALT), &node_key.signing_key().to_bytes());
+        let mut okm = [0u8; 32];
+        hk.expand(b"root", &mut okm)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        Self(okm)
+    }
+
+    /// Derive the value cipher for a namespace (typically a sled tree name).
+    ///
+    /// Each namespace gets an independent key, so sealed values cannot be
+    /// moved between trees.
+    pub fn namespace(&self, namespace: &str) -> ValueCipher {
+        let hk =
+            Hkdf::<Sha256>::from_prk(&self.0).expect("32-byte root key is a valid HKDF-SHA256 PRK");
+        let mut key = [0u8; 32];
+        hk.expand(
+            format!("{NAMESPACE_INFO_PREFIX}{namespace}").as_bytes(),
+            &mut key,
+        )
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+        ValueCipher {
+            cipher: Aes256Gcm::new(&key.into()),
+        }
+    }
+}
+
+impl fmt::Debug for AtRestKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AtRestKey(<redacted>)")
+    }
+}
+
+/// AES-256-GCM cipher for the values of one namespace.
+///
+/// The sled key is bound as associated data, so a sealed value copied under
+/// another key fails to open.
+#[derive(Clone)]
+pub struct ValueCipher {
+    cipher: Aes256Gcm,
+}
+
+impl ValueCipher {
+    /// Whether the stored bytes are a sealed value.
+    pub fn is_sealed(stored: &[u8]) -> bool {
+        stored.len() >= HEADER_LEN && stored.starts_with(SEALED_MAGIC)
+    }
+
+    /// Encrypt a value stored under `key`.
+    pub fn seal(&self, key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: key,
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt value"))?;
+
+        let mut sealed = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+        sealed.extend_from_slice(SEALED_MAGIC);
+        sealed.push(SEALED_VERSION);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Decrypt a value stored under `key`.
+    ///
+    /// Plaintext values written before encryption was enabled are returned
+    /// as-is.
+    pub fn open(&self, key: &[u8], stored: &[u8]) -> Result<Vec<u8>> {
+        if !Self::is_sealed(stored) {
+            return Ok(stored.to_vec());
+        }
+        let version = stored[SEALED_MAGIC.len()];
+        if version != SEALED_VERSION {
+            anyhow::bail!("Unsupported sealed value version: {}", version);
+        }
+        let nonce = &stored[SEALED_MAGIC.len() + 1..HEADER_LEN];
+        self.cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: &stored[HEADER_LEN..],
+                    aad: key,
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to decrypt value (wrong key or tampered data)"))
+    }
+
+    /// Seal every plaintext value in the tree in place.
+    ///
+    /// Already sealed values are left untouched, so this is safe to run on
+    /// every startup. Returns the number of migrated entries.
+    pub fn migrate_tree(&self, tree: &sled::Tree) -> Result<usize> {
+        let mut migrated = 0;
+        for entry in tree.iter() {
+            let (key, value) = entry.context("Failed to iterate tree for migration")?;
+            if Self::is_sealed(&value) {
+                continue;
+            }
+            let sealed = self.seal(&key, &value)?;
+            // Only replace the value we read, in case it changed concurrently.
+            if tree
+                .compare_and_swap(&key, Some(value), Some(sealed))
+                .context("Failed to write migrated value")?
+                .is_ok()
+            {
+                migrated += 1;
+            }
+        }
+        Ok(migrated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn key() -> AtRestKey {
+        AtRestKey::from_bytes([7u8; 32])
+    }
+
+    #[test]
+    fn test_seal_and_open_roundtrip() {
+        let cipher = key().namespace("nodes");
+        let sealed = cipher.seal(b"node-1", b"{\"node_id\":\"node-1\"}").unwrap();
+
+        assert!(ValueCipher::is_sealed(&sealed));
+        assert_eq!(
+            cipher.open(b"node-1", &sealed).unwrap(),
+            b"{\"node_id\":\"node-1\"}"
+        );
+    }
+
+    #[test]
+    fn test_open_rejects_other_key_or_namespace() {
+        let cipher = key().namespace("nodes");
+        let sealed = cipher.seal(b"node-1", b"secret").unwrap();
+
+        // Bound to the sled key it was stored under.
+        assert!(cipher.open(b"node-2", &sealed).is_err());
+        // Each namespace has its own key.
+        assert!(key()
+            .namespace("content_networks")
+            .open(b"node-1", &sealed)
+            .is_err());
+        // A different root key cannot open it either.
+        assert!(AtRestKey::from_bytes([8u8; 32])
+            .namespace("nodes")
+            .open(b"node-1", &sealed)
+            .is_err());
+    }
+
+    #[test]
+    fn test_open_passes_through_legacy_plaintext() {
+        let cipher = key().namespace("nodes");
+        assert_eq!(cipher.open(b"k", b"{\"a\":1}").unwrap(), b"{\"a\":1}");
+    }
+
+    #[test]
+    fn test_migrate_tree_seals_plaintext_values_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = sled::open(temp_dir.path()).unwrap();
+        let tree = db.open_tree("nodes").unwrap();
+        tree.insert(b"a", b"{\"a\":1}".as_slice()).unwrap();
+        tree.insert(b"b", b"{\"b\":2}".as_slice()).unwrap();
+
+        let cipher = key().namespace("nodes");
+        assert_eq!(cipher.migrate_tree(&tree).unwrap(), 2);
+        assert_eq!(cipher.migrate_tree(&tree).unwrap(), 0);
+
+        let stored = tree.get(b"a").unwrap().unwrap();
+        assert!(ValueCipher::is_sealed(&stored));
+        assert_eq!(cipher.open(b"a", &stored).unwrap(), b"{\"a\":1}");
+    }
+
+    #[test]
+    fn test_node_identity_key_is_deterministic() {
+        let node_key = NodeKeyPair::generate();
+        let sealed = AtRestKey::from_node_key(&node_key)
+            .namespace("nodes")
+            .seal(b"k", b"v")
+            .unwrap();
+        assert_eq!(
+            AtRestKey::from_node_key(&node_key)
+                .namespace("nodes")
+                .open(b"k", &sealed)
+                .unwrap(),
+            b"v"
+        );
+    }
+}
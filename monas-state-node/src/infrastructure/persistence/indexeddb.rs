@@ -0,0 +1,196 @@
+//! Minimal async wrapper around the browser's IndexedDB API.
+//!
+//! Values are stored as JSON strings under string keys, matching the sled
+//! repositories' serialized format. Writes resolve once their transaction
+//! has committed, so there is nothing left to flush afterwards.
+
+use anyhow::{anyhow, Context, Result};
+use js_sys::{Array, Function, Promise};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    IdbDatabase, IdbFactory, IdbKeyRange, IdbRequest, IdbTransaction, IdbTransactionMode,
+};
+
+/// Schema version. Bump when adding object stores to an existing database.
+const DB_VERSION: u32 = 1;
+
+/// An open IndexedDB database.
+pub struct IndexedDb {
+    db: IdbDatabase,
+}
+
+impl IndexedDb {
+    /// Open or create the database `name`, creating any missing object store
+    /// from `stores`.
+    pub async fn open(name: &str, stores: &'static [&'static str]) -> Result<Self> {
+        // `indexedDB` is available on both windows and workers.
+        let factory: IdbFactory = js_sys::Reflect::get(&js_sys::global(), &"indexedDB".into())
+            .ok()
+            .and_then(|value| value.dyn_into().ok())
+            .ok_or_else(|| anyhow!("IndexedDB is not available"))?;
+        let request = factory
+            .open_with_u32(name, DB_VERSION)
+            .map_err(js_error)
+            .context("Failed to open IndexedDB database")?;
+
+        let on_upgrade_needed = {
+            let request = request.clone();
+            Closure::<dyn FnMut()>::new(move || {
+                let Ok(db) = request.result().and_then(|db| db.dyn_into::<IdbDatabase>()) else {
+                    return;
+                };
+                for store in stores {
+                    if !db.object_store_names().contains(store) {
+                        if let Err(e) = db.create_object_store(store) {
+                            tracing::error!("Failed to create object store {}: {:?}", store, e);
+                        }
+                    }
+                }
+            })
+        };
+        request.set_onupgradeneeded(Some(on_upgrade_needed.as_ref().unchecked_ref()));
+        let result = request_result(&request).await;
+        request.set_onupgradeneeded(None);
+
+        let db = result
+            .context("Failed to open IndexedDB database")?
+            .dyn_into::<IdbDatabase>()
+            .map_err(|_| anyhow!("Unexpected result opening IndexedDB database"))?;
+        Ok(Self { db })
+    }
+
+    /// Get the value stored under `key`.
+    pub async fn get(&self, store: &str, key: &str) -> Result<Option<String>> {
+        let (_, object_store) = self.begin(store, IdbTransactionMode::Readonly)?;
+        let request = object_store.get(&key.into()).map_err(js_error)?;
+        Ok(request_result(&request).await?.as_string())
+    }
+
+    /// Store `value` under `key`, replacing any previous value.
+    pub async fn put(&self, store: &str, key: &str, value: &str) -> Result<()> {
+        let (transaction, object_store) = self.begin(store, IdbTransactionMode::Readwrite)?;
+        object_store
+            .put_with_key(&value.into(), &key.into())
+            .map_err(js_error)?;
+        transaction_complete(&transaction).await
+    }
+
+    /// Remove the value stored under `key`, if any.
+    pub async fn delete(&self, store: &str, key: &str) -> Result<()> {
+        let (transaction, object_store) = self.begin(store, IdbTransactionMode::Readwrite)?;
+        object_store.delete(&key.into()).map_err(js_error)?;
+        transaction_complete(&transaction).await
+    }
+
+    /// All keys of `store`, in ascending order.
+    pub async fn keys(&self, store: &str) -> Result<Vec<String>> {
+        let (_, object_store) = self.begin(store, IdbTransactionMode::Readonly)?;
+        let request = object_store.get_all_keys().map_err(js_error)?;
+        strings(request_result(&request).await?)
+    }
+
+    /// Values of `store` whose keys sort strictly below `upper`.
+    pub async fn values_below(&self, store: &str, upper: &str) -> Result<Vec<String>> {
+        let (_, object_store) = self.begin(store, IdbTransactionMode::Readonly)?;
+        let range = IdbKeyRange::upper_bound_with_open(&upper.into(), true).map_err(js_error)?;
+        let request = object_store.get_all_with_key(&range).map_err(js_error)?;
+        strings(request_result(&request).await?)
+    }
+
+    fn begin(
+        &self,
+        store: &str,
+        mode: IdbTransactionMode,
+    ) -> Result<(IdbTransaction, web_sys::IdbObjectStore)> {
+        let transaction = self
+            .db
+            .transaction_with_str_and_mode(store, mode)
+            .map_err(js_error)
+            .with_context(|| format!("Failed to start transaction on {}", store))?;
+        let object_store = transaction.object_store(store).map_err(js_error)?;
+        Ok((transaction, object_store))
+    }
+}
+
+impl Drop for IndexedDb {
+    fn drop(&mut self) {
+        self.db.close();
+    }
+}
+
+/// Wait for `request` to succeed and return its result.
+///
+/// The handlers are one-shot closures; the one that never fires is leaked,
+/// which is a few bytes per request.
+async fn request_result(request: &IdbRequest) -> Result<JsValue> {
+    let promise = Promise::new(&mut |resolve: Function, reject: Function| {
+        let on_success = {
+            let request = request.clone();
+            Closure::once_into_js(move || {
+                let result = request.result().unwrap_or(JsValue::UNDEFINED);
+                let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+            })
+        };
+        let on_error = {
+            let request = request.clone();
+            Closure::once_into_js(move || {
+                let error = request
+                    .error()
+                    .ok()
+                    .flatten()
+                    .map(JsValue::from)
+                    .unwrap_or(JsValue::UNDEFINED);
+                let _ = reject.call1(&JsValue::UNDEFINED, &error);
+            })
+        };
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise).await.map_err(js_error)
+}
+
+/// Wait for `transaction` to commit.
+async fn transaction_complete(transaction: &IdbTransaction) -> Result<()> {
+    let promise = Promise::new(&mut |resolve: Function, reject: Function| {
+        let on_complete = Closure::once_into_js(move || {
+            let _ = resolve.call0(&JsValue::UNDEFINED);
+        });
+        let on_error = {
+            let transaction = transaction.clone();
+            Closure::once_into_js(move || {
+                let error = transaction
+                    .error()
+                    .map(JsValue::from)
+                    .unwrap_or_else(|| "Transaction aborted".into());
+                let _ = reject.call1(&JsValue::UNDEFINED, &error);
+            })
+        };
+        transaction.set_oncomplete(Some(on_complete.unchecked_ref()));
+        // An error aborts the transaction; aborts fire either way.
+        transaction.set_onabort(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise)
+        .await
+        .map(|_| ())
+        .map_err(js_error)
+        .context("IndexedDB transaction failed")
+}
+
+fn strings(array: JsValue) -> Result<Vec<String>> {
+    array
+        .dyn_into::<Array>()
+        .map_err(|_| anyhow!("Expected an array from IndexedDB"))?
+        .iter()
+        .map(|value| {
+            value
+                .as_string()
+                .ok_or_else(|| anyhow!("Expected a string in IndexedDB"))
+        })
+        .collect()
+}
+
+fn js_error(value: JsValue) -> anyhow::Error {
+    anyhow!("{:?}", value)
+}
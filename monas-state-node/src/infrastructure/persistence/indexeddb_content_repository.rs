@@ -0,0 +1,111 @@
+//! IndexedDB-based content network repository for browser builds.
+
+use crate::domain::content_network::ContentNetwork;
+use crate::infrastructure::persistence::indexeddb::IndexedDb;
+use crate::port::persistence::WasmContentRepository;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+const CONTENT_NETWORK_STORE_NAME: &str = "content_networks";
+const CAPACITY_INDEX_STORE_NAME: &str = "capacity_index";
+
+/// IndexedDB-based implementation of WasmContentRepository.
+///
+/// Stores content networks as JSON, with the same capacity index layout as
+/// the sled repository.
+pub struct IndexedDbContentRepository {
+    db: IndexedDb,
+}
+
+impl IndexedDbContentRepository {
+    /// Open or create the IndexedDB database with the given name.
+    pub async fn open(db_name: &str) -> Result<Self> {
+        let db = IndexedDb::open(
+            db_name,
+            &[CONTENT_NETWORK_STORE_NAME, CAPACITY_INDEX_STORE_NAME],
+        )
+        .await?;
+        Ok(Self { db })
+    }
+
+    /// Add a content to the capacity index.
+    pub async fn index_by_capacity(&self, content_id: &str, required_capacity: u64) -> Result<()> {
+        self.db
+            .put(
+                CAPACITY_INDEX_STORE_NAME,
+                &capacity_key(content_id, required_capacity),
+                content_id,
+            )
+            .await
+            .context("Failed to index capacity")
+    }
+
+    /// Remove a content from the capacity index.
+    pub async fn remove_from_capacity_index(
+        &self,
+        content_id: &str,
+        required_capacity: u64,
+    ) -> Result<()> {
+        self.db
+            .delete(
+                CAPACITY_INDEX_STORE_NAME,
+                &capacity_key(content_id, required_capacity),
+            )
+            .await
+            .context("Failed to remove from capacity index")
+    }
+}
+
+/// Capacity index key; hex-encoded so that string order matches numeric order.
+fn capacity_key(content_id: &str, required_capacity: u64) -> String {
+    format!("{:016x}:{}", required_capacity, content_id)
+}
+
+#[async_trait(?Send)]
+impl WasmContentRepository for IndexedDbContentRepository {
+    async fn find_assignable_cids(&self, capacity: u64) -> Result<Vec<String>> {
+        // Find all content with required_capacity <= capacity
+        let max_key = format!("{:016x}:", capacity.saturating_add(1));
+        self.db
+            .values_below(CAPACITY_INDEX_STORE_NAME, &max_key)
+            .await
+            .context("Failed to query capacity index")
+    }
+
+    async fn get_content_network(&self, content_id: &str) -> Result<Option<ContentNetwork>> {
+        match self.db.get(CONTENT_NETWORK_STORE_NAME, content_id).await? {
+            Some(value) => Ok(Some(
+                serde_json::from_str(&value).context("Failed to deserialize content network")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    async fn save_content_network(&self, net: ContentNetwork) -> Result<()> {
+        let value = serde_json::to_string(&net).context("Failed to serialize content network")?;
+        self.db
+            .put(
+                CONTENT_NETWORK_STORE_NAME,
+                net.content_id().as_str(),
+                &value,
+            )
+            .await
+            .context("Failed to save content network")
+    }
+
+    async fn delete_content_network(&self, content_id: &str) -> Result<()> {
+        self.db
+            .delete(CONTENT_NETWORK_STORE_NAME, content_id)
+            .await
+            .context("Failed to delete content network")
+    }
+
+    async fn list_content_networks(&self) -> Result<Vec<String>> {
+        self.db.keys(CONTENT_NETWORK_STORE_NAME).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        // Writes complete only after their transaction has committed.
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+//! IndexedDB-based node registry for browser builds.
+
+use crate::domain::state_node::NodeSnapshot;
+use crate::infrastructure::persistence::indexeddb::IndexedDb;
+use crate::port::persistence::WasmNodeRegistry;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+const NODE_STORE_NAME: &str = "nodes";
+
+/// IndexedDB-based implementation of WasmNodeRegistry.
+///
+/// Stores node snapshots as JSON, keyed by node ID.
+pub struct IndexedDbNodeRegistry {
+    db: IndexedDb,
+}
+
+impl IndexedDbNodeRegistry {
+    /// Open or create the IndexedDB database with the given name.
+    pub async fn open(db_name: &str) -> Result<Self> {
+        let db = IndexedDb::open(db_name, &[NODE_STORE_NAME]).await?;
+        Ok(Self { db })
+    }
+}
+
+#[async_trait(?Send)]
+impl WasmNodeRegistry for IndexedDbNodeRegistry {
+    async fn upsert_node(&self, node: &NodeSnapshot) -> Result<()> {
+        let value = serde_json::to_string(node).context("Failed to serialize node snapshot")?;
+        self.db
+            .put(NODE_STORE_NAME, &node.node_id, &value)
+            .await
+            .context("Failed to insert node")
+    }
+
+    async fn get_available_capacity(&self, node_id: &str) -> Result<Option<u64>> {
+        Ok(self
+            .get_node(node_id)
+            .await?
+            .map(|node| node.available_capacity))
+    }
+
+    async fn list_nodes(&self) -> Result<Vec<String>> {
+        self.db.keys(NODE_STORE_NAME).await
+    }
+
+    async fn get_node(&self, node_id: &str) -> Result<Option<NodeSnapshot>> {
+        match self.db.get(NODE_STORE_NAME, node_id).await? {
+            Some(value) => Ok(Some(
+                serde_json::from_str(&value).context("Failed to deserialize node")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    async fn delete_node(&self, node_id: &str) -> Result<()> {
+        self.db
+            .delete(NODE_STORE_NAME, node_id)
+            .await
+            .context("Failed to delete node")
+    }
+
+    async fn flush(&self) -> Result<()> {
+        // Writes complete only after their transaction has committed.
+        Ok(())
+    }
+}
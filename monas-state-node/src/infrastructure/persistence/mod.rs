@@ -2,27 +2,55 @@
 //!
 //! This module provides persistent storage implementations using sled.
 //!
-//! ## Future WASM Support
+//! ## WASM Support
 //!
-//! IndexedDB implementations are prepared in separate files for future browser support:
+//! Browser builds (`target_arch = "wasm32"`) store data in IndexedDB instead:
 //! - `indexeddb_node_registry.rs` - Node registry using IndexedDB
 //! - `indexeddb_content_repository.rs` - Content repository using IndexedDB
 //!
-//! These use `WasmNodeRegistry` and `WasmContentRepository` traits which are
-//! `?Send` to accommodate browser's single-threaded nature.
+//! These implement the `WasmNodeRegistry` and `WasmContentRepository` traits,
+//! which are `?Send` to accommodate the browser's single-threaded nature.
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod at_rest_encryption;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod sled_access_control_repository;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod sled_content_network_repository;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod sled_denylist_repository;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod sled_eviction_repository;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod sled_node_registry;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod sled_public_key_repository;
 
 // Re-export sled implementations
+#[cfg(not(target_arch = "wasm32"))]
+pub use at_rest_encryption::{AtRestKey, AtRestKeySource, ValueCipher};
+#[cfg(not(target_arch = "wasm32"))]
 pub use sled_access_control_repository::SledAccessControlRepository;
+#[cfg(not(target_arch = "wasm32"))]
 pub use sled_content_network_repository::SledContentNetworkRepository;
+#[cfg(not(target_arch = "wasm32"))]
+pub use sled_denylist_repository::SledDenylistRepository;
+#[cfg(not(target_arch = "wasm32"))]
+pub use sled_eviction_repository::SledEvictionRepository;
+#[cfg(not(target_arch = "wasm32"))]
 pub use sled_node_registry::SledNodeRegistry;
+#[cfg(not(target_arch = "wasm32"))]
 pub use sled_public_key_repository::SledPublicKeyRepository;
 
-// Future WASM implementations (prepared but not compiled by default)
-// To enable, add cfg(target_arch = "wasm32") and required dependencies
-// pub mod indexeddb_node_registry;
-// pub mod indexeddb_content_repository;
+// IndexedDB implementations (browser only)
+#[cfg(target_arch = "wasm32")]
+mod indexeddb;
+#[cfg(target_arch = "wasm32")]
+pub mod indexeddb_content_repository;
+#[cfg(target_arch = "wasm32")]
+pub mod indexeddb_node_registry;
+
+#[cfg(target_arch = "wasm32")]
+pub use indexeddb_content_repository::IndexedDbContentRepository;
+#[cfg(target_arch = "wasm32")]
+pub use indexeddb_node_registry::IndexedDbNodeRegistry;
@@ -1,6 +1,7 @@
 //! Sled-based persistent content network repository implementation.
 
 use crate::domain::content_network::ContentNetwork;
+use crate::infrastructure::persistence::at_rest_encryption::{AtRestKey, ValueCipher};
 use crate::port::persistence::PersistentContentRepository;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -16,18 +17,34 @@ const CAPACITY_INDEX_TREE_NAME: &str = "capacity_index";
 /// Stores content networks in a sled database with an index for capacity-based queries.
 pub struct SledContentNetworkRepository {
     db: Db,
+    /// Optional at-rest encryption of content network values.
+    cipher: Option<ValueCipher>,
 }
 
 impl SledContentNetworkRepository {
     /// Open or create a sled database at the given path.
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
         let db = sled::open(path.as_ref()).context("Failed to open sled database")?;
-        Ok(Self { db })
+        Ok(Self { db, cipher: None })
     }
 
     /// Open with an existing sled database instance.
     pub fn with_db(db: Db) -> Self {
-        Self { db }
+        Self { db, cipher: None }
+    }
+
+    /// Enable at-rest encryption of content network values (builder pattern).
+    ///
+    /// Existing plaintext entries are sealed in place. The capacity index is
+    /// left as-is: its keys must stay ordered for range queries.
+    pub fn with_encryption(mut self, key: &AtRestKey) -> Result<Self> {
+        let cipher = key.namespace(CONTENT_NETWORK_TREE_NAME);
+        let migrated = cipher.migrate_tree(&self.content_tree()?)?;
+        if migrated > 0 {
+            tracing::info!("Encrypted {} existing content network entries", migrated);
+        }
+        self.cipher = Some(cipher);
+        Ok(self)
     }
 
     /// Get the content networks tree.
@@ -93,7 +110,11 @@ impl PersistentContentRepository for SledContentNetworkRepository {
         let tree = self.content_tree()?;
         match tree.get(content_id.as_bytes())? {
             Some(bytes) => {
-                let network: ContentNetwork = serde_json::from_slice(&bytes)
+                let value = match &self.cipher {
+                    Some(cipher) => cipher.open(content_id.as_bytes(), &bytes)?,
+                    None => bytes.to_vec(),
+                };
+                let network: ContentNetwork = serde_json::from_slice(&value)
                     .context("Failed to deserialize content network")?;
                 Ok(Some(network))
             }
@@ -106,6 +127,10 @@ impl PersistentContentRepository for SledContentNetworkRepository {
         let capacity_tree = self.capacity_tree()?;
         let content_id = net.content_id().as_str().to_string();
         let value = serde_json::to_vec(&net).context("Failed to serialize content network")?;
+        let value = match &self.cipher {
+            Some(cipher) => cipher.seal(content_id.as_bytes(), &value)?,
+            None => value,
+        };
 
         (&content_tree, &capacity_tree)
             .transaction(
@@ -228,4 +253,51 @@ mod tests {
         repo.delete_content_network("cid-1").await.unwrap();
         assert!(repo.get_content_network("cid-1").await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn test_encrypted_repository_reads_legacy_and_new_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = sled::open(temp_dir.path()).unwrap();
+        let legacy = ContentNetwork::new(
+            ContentId::new("cid-legacy".to_string()).unwrap(),
+            NodeId::from_string("node-1".to_string()).unwrap(),
+        )
+        .unwrap();
+        SledContentNetworkRepository::with_db(db.clone())
+            .save_content_network(legacy.clone())
+            .await
+            .unwrap();
+
+        let key = AtRestKey::from_bytes([2u8; 32]);
+        let repo = SledContentNetworkRepository::with_db(db.clone())
+            .with_encryption(&key)
+            .unwrap();
+        let fresh = ContentNetwork::new(
+            ContentId::new("cid-new".to_string()).unwrap(),
+            NodeId::from_string("node-2".to_string()).unwrap(),
+        )
+        .unwrap();
+        repo.save_content_network(fresh.clone()).await.unwrap();
+
+        assert_eq!(
+            repo.get_content_network("cid-legacy").await.unwrap(),
+            Some(legacy)
+        );
+        assert_eq!(
+            repo.get_content_network("cid-new").await.unwrap(),
+            Some(fresh)
+        );
+
+        let tree = db.open_tree(CONTENT_NETWORK_TREE_NAME).unwrap();
+        for key in ["cid-legacy", "cid-new"] {
+            let raw = tree.get(key).unwrap().unwrap();
+            assert!(ValueCipher::is_sealed(&raw));
+        }
+
+        // Without the key the values are unreadable.
+        assert!(SledContentNetworkRepository::with_db(db)
+            .get_content_network("cid-new")
+            .await
+            .is_err());
+    }
 }
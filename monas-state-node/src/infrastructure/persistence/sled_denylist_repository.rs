@@ -0,0 +1,143 @@
+//! Sled-based persistent denylist repository implementation.
+
+use crate::domain::tombstone::Tombstone;
+use crate::port::persistence::PersistentDenylistRepository;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sled::Db;
+use std::path::Path;
+
+const TOMBSTONE_TREE_NAME: &str = "tombstones";
+
+/// Sled-based implementation of PersistentDenylistRepository.
+///
+/// Stores tombstones for denied content in a sled database so takedowns
+/// survive restarts and re-announcements from other nodes.
+pub struct SledDenylistRepository {
+    db: Db,
+}
+
+impl SledDenylistRepository {
+    /// Open or create a sled database at the given path.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let db = sled::open(path.as_ref()).context("Failed to open sled database")?;
+        Ok(Self { db })
+    }
+
+    /// Open with an existing sled database instance.
+    pub fn with_db(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Get the tombstone tree.
+    fn tombstone_tree(&self) -> Result<sled::Tree> {
+        self.db
+            .open_tree(TOMBSTONE_TREE_NAME)
+            .context("Failed to open tombstones tree")
+    }
+}
+
+#[async_trait]
+impl PersistentDenylistRepository for SledDenylistRepository {
+    async fn add_tombstone(&self, tombstone: &Tombstone) -> Result<()> {
+        let tree = self.tombstone_tree()?;
+        let value = serde_json::to_vec(tombstone).context("Failed to serialize tombstone")?;
+        tree.insert(tombstone.genesis_cid().as_bytes(), value)
+            .context("Failed to insert tombstone")?;
+        // Takedowns must not be lost on crash; flush eagerly.
+        self.db
+            .flush_async()
+            .await
+            .context("Failed to flush database")?;
+        Ok(())
+    }
+
+    async fn get_tombstone(&self, genesis_cid: &str) -> Result<Option<Tombstone>> {
+        let tree = self.tombstone_tree()?;
+        match tree.get(genesis_cid.as_bytes())? {
+            Some(bytes) => {
+                let tombstone: Tombstone =
+                    serde_json::from_slice(&bytes).context("Failed to deserialize tombstone")?;
+                Ok(Some(tombstone))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn is_denied(&self, genesis_cid: &str) -> Result<bool> {
+        let tree = self.tombstone_tree()?;
+        Ok(tree.contains_key(genesis_cid.as_bytes())?)
+    }
+
+    async fn list_tombstones(&self) -> Result<Vec<String>> {
+        let tree = self.tombstone_tree()?;
+        let mut genesis_cids = Vec::new();
+        for result in tree.iter() {
+            let (key, _) = result.context("Failed to iterate tombstones")?;
+            let genesis_cid =
+                String::from_utf8(key.to_vec()).context("Failed to decode genesis CID as UTF-8")?;
+            genesis_cids.push(genesis_cid);
+        }
+        Ok(genesis_cids)
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.db
+            .flush_async()
+            .await
+            .context("Failed to flush database")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_add_and_get_tombstone() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = SledDenylistRepository::open(temp_dir.path()).unwrap();
+
+        let tombstone = Tombstone::new("cid-1".to_string(), "abuse".to_string());
+        repo.add_tombstone(&tombstone).await.unwrap();
+
+        let retrieved = repo.get_tombstone("cid-1").await.unwrap().unwrap();
+        assert_eq!(retrieved, tombstone);
+        assert!(repo.is_denied("cid-1").await.unwrap());
+        assert!(!repo.is_denied("cid-2").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_list_tombstones() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = SledDenylistRepository::open(temp_dir.path()).unwrap();
+
+        repo.add_tombstone(&Tombstone::new("cid-1".to_string(), "a".to_string()))
+            .await
+            .unwrap();
+        repo.add_tombstone(&Tombstone::new("cid-2".to_string(), "b".to_string()))
+            .await
+            .unwrap();
+
+        let cids = repo.list_tombstones().await.unwrap();
+        assert_eq!(cids.len(), 2);
+        assert!(cids.contains(&"cid-1".to_string()));
+        assert!(cids.contains(&"cid-2".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_tombstone_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let repo = SledDenylistRepository::open(temp_dir.path()).unwrap();
+            repo.add_tombstone(&Tombstone::new("cid-1".to_string(), "legal".to_string()))
+                .await
+                .unwrap();
+        }
+
+        let repo = SledDenylistRepository::open(temp_dir.path()).unwrap();
+        assert!(repo.is_denied("cid-1").await.unwrap());
+    }
+}
@@ -0,0 +1,141 @@
+//! Sled-based persistent eviction repository implementation.
+
+use crate::domain::eviction::Eviction;
+use crate::port::persistence::PersistentEvictionRepository;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sled::Db;
+use std::path::Path;
+
+const EVICTION_TREE_NAME: &str = "evictions";
+
+/// Sled-based implementation of PersistentEvictionRepository.
+///
+/// Stores eviction records so that evicted content is still purged, and
+/// not re-served, after a restart.
+pub struct SledEvictionRepository {
+    db: Db,
+}
+
+impl SledEvictionRepository {
+    /// Open or create a sled database at the given path.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let db = sled::open(path.as_ref()).context("Failed to open sled database")?;
+        Ok(Self { db })
+    }
+
+    /// Open with an existing sled database instance.
+    pub fn with_db(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Get the eviction tree.
+    fn eviction_tree(&self) -> Result<sled::Tree> {
+        self.db
+            .open_tree(EVICTION_TREE_NAME)
+            .context("Failed to open evictions tree")
+    }
+}
+
+#[async_trait]
+impl PersistentEvictionRepository for SledEvictionRepository {
+    async fn save_eviction(&self, eviction: &Eviction) -> Result<()> {
+        let tree = self.eviction_tree()?;
+        let value = serde_json::to_vec(eviction).context("Failed to serialize eviction")?;
+        tree.insert(eviction.genesis_cid().as_bytes(), value)
+            .context("Failed to insert eviction")?;
+        Ok(())
+    }
+
+    async fn get_eviction(&self, genesis_cid: &str) -> Result<Option<Eviction>> {
+        let tree = self.eviction_tree()?;
+        match tree.get(genesis_cid.as_bytes())? {
+            Some(bytes) => {
+                let eviction: Eviction =
+                    serde_json::from_slice(&bytes).context("Failed to deserialize eviction")?;
+                Ok(Some(eviction))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn is_evicted(&self, genesis_cid: &str) -> Result<bool> {
+        let tree = self.eviction_tree()?;
+        Ok(tree.contains_key(genesis_cid.as_bytes())?)
+    }
+
+    async fn cancel_eviction(&self, genesis_cid: &str) -> Result<bool> {
+        let tree = self.eviction_tree()?;
+        Ok(tree
+            .remove(genesis_cid.as_bytes())
+            .context("Failed to remove eviction")?
+            .is_some())
+    }
+
+    async fn list_evictions(&self) -> Result<Vec<Eviction>> {
+        let tree = self.eviction_tree()?;
+        let mut evictions = Vec::new();
+        for result in tree.iter() {
+            let (_, value) = result.context("Failed to iterate evictions")?;
+            evictions
+                .push(serde_json::from_slice(&value).context("Failed to deserialize eviction")?);
+        }
+        Ok(evictions)
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.db
+            .flush_async()
+            .await
+            .context("Failed to flush database")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::eviction::EvictionReason;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_save_get_and_cancel_eviction() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = SledEvictionRepository::open(temp_dir.path()).unwrap();
+
+        let eviction = Eviction::new(
+            "cid-1".to_string(),
+            EvictionReason::RemovedFromNetwork,
+            60,
+        );
+        repo.save_eviction(&eviction).await.unwrap();
+
+        assert_eq!(repo.get_eviction("cid-1").await.unwrap(), Some(eviction));
+        assert!(repo.is_evicted("cid-1").await.unwrap());
+        assert!(!repo.is_evicted("cid-2").await.unwrap());
+        assert_eq!(repo.list_evictions().await.unwrap().len(), 1);
+
+        assert!(repo.cancel_eviction("cid-1").await.unwrap());
+        assert!(!repo.cancel_eviction("cid-1").await.unwrap());
+        assert!(!repo.is_evicted("cid-1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_eviction_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let repo = SledEvictionRepository::open(temp_dir.path()).unwrap();
+            repo.save_eviction(&Eviction::new(
+                "cid-1".to_string(),
+                EvictionReason::ContentDeleted,
+                60,
+            ))
+            .await
+            .unwrap();
+            repo.flush().await.unwrap();
+        }
+
+        let repo = SledEvictionRepository::open(temp_dir.path()).unwrap();
+        assert!(repo.is_evicted("cid-1").await.unwrap());
+    }
+}
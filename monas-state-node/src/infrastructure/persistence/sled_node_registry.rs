@@ -1,31 +1,75 @@
 //! Sled-based persistent node registry implementation.
 
+use crate::domain::known_peer::KnownPeer;
+use crate::domain::peer_access::PeerAccess;
 use crate::domain::state_node::NodeSnapshot;
-use crate::port::persistence::PersistentNodeRegistry;
+use crate::infrastructure::persistence::at_rest_encryption::{AtRestKey, ValueCipher};
+use crate::port::persistence::{
+    PersistentNodeRegistry, PersistentPeerAccessRepository, PersistentPeerStore,
+};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use sled::Db;
 use std::path::Path;
 
 const NODE_TREE_NAME: &str = "nodes";
+const PEER_ACCESS_TREE_NAME: &str = "peer_access";
+const KNOWN_PEERS_TREE_NAME: &str = "known_peers";
 
 /// Sled-based implementation of PersistentNodeRegistry.
 ///
-/// Stores node snapshots in a sled database for persistent storage.
+/// Stores node snapshots in a sled database for persistent storage, along
+/// with the operator's peer access list (see [`PersistentPeerAccessRepository`])
+/// and the peers this node has connected to (see [`PersistentPeerStore`]).
+#[derive(Clone)]
 pub struct SledNodeRegistry {
     db: Db,
+    /// Optional at-rest encryption of the stored values.
+    ciphers: Option<RegistryCiphers>,
+}
+
+/// Value ciphers of the registry trees, one namespace per tree.
+#[derive(Clone)]
+struct RegistryCiphers {
+    nodes: ValueCipher,
+    peer_access: ValueCipher,
+    known_peers: ValueCipher,
 }
 
 impl SledNodeRegistry {
     /// Open or create a sled database at the given path.
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
         let db = sled::open(path.as_ref()).context("Failed to open sled database")?;
-        Ok(Self { db })
+        Ok(Self { db, ciphers: None })
     }
 
     /// Open with an existing sled database instance.
     pub fn with_db(db: Db) -> Self {
-        Self { db }
+        Self { db, ciphers: None }
+    }
+
+    /// Enable at-rest encryption of stored values (builder pattern).
+    ///
+    /// Node snapshots, peer access entries and known peers are all sealed;
+    /// existing plaintext entries are sealed in place.
+    pub fn with_encryption(mut self, key: &AtRestKey) -> Result<Self> {
+        let ciphers = RegistryCiphers {
+            nodes: key.namespace(NODE_TREE_NAME),
+            peer_access: key.namespace(PEER_ACCESS_TREE_NAME),
+            known_peers: key.namespace(KNOWN_PEERS_TREE_NAME),
+        };
+        let migrated = ciphers.nodes.migrate_tree(&self.nodes_tree()?)?
+            + ciphers
+                .peer_access
+                .migrate_tree(&self.peer_access_tree()?)?
+            + ciphers
+                .known_peers
+                .migrate_tree(&self.known_peers_tree()?)?;
+        if migrated > 0 {
+            tracing::info!("Encrypted {} existing node registry entries", migrated);
+        }
+        self.ciphers = Some(ciphers);
+        Ok(self)
     }
 
     /// Get the nodes tree.
@@ -34,13 +78,95 @@ impl SledNodeRegistry {
             .open_tree(NODE_TREE_NAME)
             .context("Failed to open nodes tree")
     }
+
+    /// Get the peer access tree.
+    fn peer_access_tree(&self) -> Result<sled::Tree> {
+        self.db
+            .open_tree(PEER_ACCESS_TREE_NAME)
+            .context("Failed to open peer access tree")
+    }
+
+    /// Get the known peers tree.
+    fn known_peers_tree(&self) -> Result<sled::Tree> {
+        self.db
+            .open_tree(KNOWN_PEERS_TREE_NAME)
+            .context("Failed to open known peers tree")
+    }
+
+    fn encode_node(&self, node: &NodeSnapshot) -> Result<Vec<u8>> {
+        let value = serde_json::to_vec(node).context("Failed to serialize node snapshot")?;
+        seal(
+            self.ciphers.as_ref().map(|c| &c.nodes),
+            node.node_id.as_bytes(),
+            value,
+        )
+    }
+
+    fn decode_node(&self, node_id: &str, bytes: &[u8]) -> Result<NodeSnapshot> {
+        let value = open(
+            self.ciphers.as_ref().map(|c| &c.nodes),
+            node_id.as_bytes(),
+            bytes,
+        )?;
+        serde_json::from_slice(&value).context("Failed to deserialize node")
+    }
+
+    fn encode_peer_access(&self, peer_id: &str, access: PeerAccess) -> Result<Vec<u8>> {
+        let value = serde_json::to_vec(&access).context("Failed to serialize peer access")?;
+        seal(
+            self.ciphers.as_ref().map(|c| &c.peer_access),
+            peer_id.as_bytes(),
+            value,
+        )
+    }
+
+    fn decode_peer_access(&self, peer_id: &[u8], bytes: &[u8]) -> Result<PeerAccess> {
+        let value = open(
+            self.ciphers.as_ref().map(|c| &c.peer_access),
+            peer_id,
+            bytes,
+        )?;
+        serde_json::from_slice(&value).context("Failed to deserialize peer access")
+    }
+
+    fn encode_known_peer(&self, peer: &KnownPeer) -> Result<Vec<u8>> {
+        let value = serde_json::to_vec(peer).context("Failed to serialize known peer")?;
+        seal(
+            self.ciphers.as_ref().map(|c| &c.known_peers),
+            peer.peer_id.as_bytes(),
+            value,
+        )
+    }
+
+    fn decode_known_peer(&self, peer_id: &[u8], bytes: &[u8]) -> Result<KnownPeer> {
+        let value = open(
+            self.ciphers.as_ref().map(|c| &c.known_peers),
+            peer_id,
+            bytes,
+        )?;
+        serde_json::from_slice(&value).context("Failed to deserialize known peer")
+    }
+}
+
+fn seal(cipher: Option<&ValueCipher>, key: &[u8], value: Vec<u8>) -> Result<Vec<u8>> {
+    match cipher {
+        Some(cipher) => cipher.seal(key, &value),
+        None => Ok(value),
+    }
+}
+
+fn open(cipher: Option<&ValueCipher>, key: &[u8], stored: &[u8]) -> Result<Vec<u8>> {
+    match cipher {
+        Some(cipher) => cipher.open(key, stored),
+        None => Ok(stored.to_vec()),
+    }
 }
 
 #[async_trait]
 impl PersistentNodeRegistry for SledNodeRegistry {
     async fn upsert_node(&self, node: &NodeSnapshot) -> Result<()> {
         let tree = self.nodes_tree()?;
-        let value = serde_json::to_vec(node).context("Failed to serialize node snapshot")?;
+        let value = self.encode_node(node)?;
         tree.insert(node.node_id.as_bytes(), value)
             .context("Failed to insert node")?;
         Ok(())
@@ -50,8 +176,7 @@ impl PersistentNodeRegistry for SledNodeRegistry {
         let tree = self.nodes_tree()?;
         match tree.get(node_id.as_bytes())? {
             Some(bytes) => {
-                let node: NodeSnapshot =
-                    serde_json::from_slice(&bytes).context("Failed to deserialize node")?;
+                let node = self.decode_node(node_id, &bytes)?;
                 Ok(Some(node.available_capacity))
             }
             None => Ok(None),
@@ -73,11 +198,7 @@ impl PersistentNodeRegistry for SledNodeRegistry {
     async fn get_node(&self, node_id: &str) -> Result<Option<NodeSnapshot>> {
         let tree = self.nodes_tree()?;
         match tree.get(node_id.as_bytes())? {
-            Some(bytes) => {
-                let node: NodeSnapshot =
-                    serde_json::from_slice(&bytes).context("Failed to deserialize node")?;
-                Ok(Some(node))
-            }
+            Some(bytes) => Ok(Some(self.decode_node(node_id, &bytes)?)),
             None => Ok(None),
         }
     }
@@ -98,6 +219,89 @@ impl PersistentNodeRegistry for SledNodeRegistry {
     }
 }
 
+#[async_trait]
+impl PersistentPeerAccessRepository for SledNodeRegistry {
+    async fn set_peer_access(&self, peer_id: &str, access: PeerAccess) -> Result<()> {
+        let tree = self.peer_access_tree()?;
+        let value = self.encode_peer_access(peer_id, access)?;
+        tree.insert(peer_id.as_bytes(), value)
+            .context("Failed to insert peer access")?;
+        Ok(())
+    }
+
+    async fn get_peer_access(&self, peer_id: &str) -> Result<Option<PeerAccess>> {
+        let tree = self.peer_access_tree()?;
+        match tree.get(peer_id.as_bytes())? {
+            Some(bytes) => Ok(Some(self.decode_peer_access(peer_id.as_bytes(), &bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn remove_peer_access(&self, peer_id: &str) -> Result<()> {
+        let tree = self.peer_access_tree()?;
+        tree.remove(peer_id.as_bytes())
+            .context("Failed to remove peer access")?;
+        Ok(())
+    }
+
+    async fn list_peer_access(&self) -> Result<Vec<(String, PeerAccess)>> {
+        let tree = self.peer_access_tree()?;
+        let mut entries = Vec::new();
+        for result in tree.iter() {
+            let (key, value) = result.context("Failed to iterate peer access")?;
+            let peer_id =
+                String::from_utf8(key.to_vec()).context("Failed to decode peer ID as UTF-8")?;
+            let access = self.decode_peer_access(&key, &value)?;
+            entries.push((peer_id, access));
+        }
+        Ok(entries)
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.db
+            .flush_async()
+            .await
+            .context("Failed to flush database")?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PersistentPeerStore for SledNodeRegistry {
+    async fn save_known_peer(&self, peer: &KnownPeer) -> Result<()> {
+        let tree = self.known_peers_tree()?;
+        let value = self.encode_known_peer(peer)?;
+        tree.insert(peer.peer_id.as_bytes(), value)
+            .context("Failed to insert known peer")?;
+        Ok(())
+    }
+
+    async fn list_known_peers(&self) -> Result<Vec<KnownPeer>> {
+        let tree = self.known_peers_tree()?;
+        let mut peers = Vec::new();
+        for result in tree.iter() {
+            let (key, value) = result.context("Failed to iterate known peers")?;
+            peers.push(self.decode_known_peer(&key, &value)?);
+        }
+        Ok(peers)
+    }
+
+    async fn remove_known_peer(&self, peer_id: &str) -> Result<()> {
+        let tree = self.known_peers_tree()?;
+        tree.remove(peer_id.as_bytes())
+            .context("Failed to remove known peer")?;
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.db
+            .flush_async()
+            .await
+            .context("Failed to flush database")?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,6 +316,7 @@ mod tests {
             node_id: "node-1".to_string(),
             total_capacity: 1000,
             available_capacity: 800,
+            updated_at: 0,
         };
 
         registry.upsert_node(&node).await.unwrap();
@@ -132,11 +337,13 @@ mod tests {
             node_id: "node-1".to_string(),
             total_capacity: 1000,
             available_capacity: 800,
+            updated_at: 0,
         };
         let node2 = NodeSnapshot {
             node_id: "node-2".to_string(),
             total_capacity: 2000,
             available_capacity: 1500,
+            updated_at: 0,
         };
 
         registry.upsert_node(&node1).await.unwrap();
@@ -157,6 +364,7 @@ mod tests {
             node_id: "node-1".to_string(),
             total_capacity: 1000,
             available_capacity: 800,
+            updated_at: 0,
         };
 
         registry.upsert_node(&node).await.unwrap();
@@ -165,4 +373,145 @@ mod tests {
         registry.delete_node("node-1").await.unwrap();
         assert!(registry.get_node("node-1").await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn test_encryption_migrates_existing_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = sled::open(temp_dir.path()).unwrap();
+        let node = NodeSnapshot {
+            node_id: "node-1".to_string(),
+            total_capacity: 1000,
+            available_capacity: 800,
+            updated_at: 0,
+        };
+
+        // Written before encryption was enabled.
+        SledNodeRegistry::with_db(db.clone())
+            .upsert_node(&node)
+            .await
+            .unwrap();
+
+        let key = AtRestKey::from_bytes([1u8; 32]);
+        let registry = SledNodeRegistry::with_db(db.clone())
+            .with_encryption(&key)
+            .unwrap();
+        assert_eq!(registry.get_node("node-1").await.unwrap(), Some(node));
+
+        let raw = db
+            .open_tree(NODE_TREE_NAME)
+            .unwrap()
+            .get(b"node-1")
+            .unwrap()
+            .unwrap();
+        assert!(ValueCipher::is_sealed(&raw));
+        assert!(!raw.windows(6).any(|w| w == b"node-1"));
+    }
+
+    #[tokio::test]
+    async fn test_encryption_covers_peer_access_and_known_peers() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = sled::open(temp_dir.path()).unwrap();
+        let peer = KnownPeer {
+            peer_id: "peer-1".to_string(),
+            addrs: vec!["/ip4/192.0.2.1/tcp/9090".to_string()],
+            last_seen: 100,
+        };
+
+        // Written before encryption was enabled.
+        SledNodeRegistry::with_db(db.clone())
+            .set_peer_access("peer-1", PeerAccess::Blocked)
+            .await
+            .unwrap();
+
+        let key = AtRestKey::from_bytes([1u8; 32]);
+        let registry = SledNodeRegistry::with_db(db.clone())
+            .with_encryption(&key)
+            .unwrap();
+        registry.save_known_peer(&peer).await.unwrap();
+
+        assert_eq!(
+            registry.list_peer_access().await.unwrap(),
+            vec![("peer-1".to_string(), PeerAccess::Blocked)]
+        );
+        assert_eq!(registry.list_known_peers().await.unwrap(), vec![peer]);
+
+        for tree_name in [PEER_ACCESS_TREE_NAME, KNOWN_PEERS_TREE_NAME] {
+            let raw = db
+                .open_tree(tree_name)
+                .unwrap()
+                .get(b"peer-1")
+                .unwrap()
+                .unwrap();
+            assert!(ValueCipher::is_sealed(&raw));
+        }
+        let raw_peer = db
+            .open_tree(KNOWN_PEERS_TREE_NAME)
+            .unwrap()
+            .get(b"peer-1")
+            .unwrap()
+            .unwrap();
+        assert!(!raw_peer.windows(9).any(|w| w == b"192.0.2.1"));
+    }
+
+    #[tokio::test]
+    async fn test_peer_access_entries_persist_alongside_nodes() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let registry = SledNodeRegistry::open(temp_dir.path()).unwrap();
+            registry
+                .set_peer_access("peer-1", PeerAccess::Blocked)
+                .await
+                .unwrap();
+            registry
+                .set_peer_access("peer-2", PeerAccess::Allowed)
+                .await
+                .unwrap();
+            registry.remove_peer_access("peer-2").await.unwrap();
+            PersistentPeerAccessRepository::flush(&registry)
+                .await
+                .unwrap();
+        }
+
+        let registry = SledNodeRegistry::open(temp_dir.path()).unwrap();
+        assert_eq!(
+            registry.list_peer_access().await.unwrap(),
+            vec![("peer-1".to_string(), PeerAccess::Blocked)]
+        );
+        assert_eq!(registry.get_peer_access("peer-2").await.unwrap(), None);
+        // Peer access entries are not node snapshots.
+        assert!(registry.list_nodes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_known_peers_persist_across_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let peer = |peer_id: &str, last_seen| KnownPeer {
+            peer_id: peer_id.to_string(),
+            addrs: vec!["/ip4/192.0.2.1/tcp/9090".to_string()],
+            last_seen,
+        };
+        {
+            let registry = SledNodeRegistry::open(temp_dir.path()).unwrap();
+            registry
+                .save_known_peer(&peer("peer-1", 100))
+                .await
+                .unwrap();
+            registry
+                .save_known_peer(&peer("peer-2", 100))
+                .await
+                .unwrap();
+            registry
+                .save_known_peer(&peer("peer-1", 200))
+                .await
+                .unwrap();
+            registry.remove_known_peer("peer-2").await.unwrap();
+            PersistentPeerStore::flush(&registry).await.unwrap();
+        }
+
+        let registry = SledNodeRegistry::open(temp_dir.path()).unwrap();
+        assert_eq!(
+            registry.list_known_peers().await.unwrap(),
+            vec![peer("peer-1", 200)]
+        );
+    }
 }
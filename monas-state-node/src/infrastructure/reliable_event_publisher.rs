@@ -7,7 +7,7 @@
 
 use crate::domain::events::Event;
 use crate::infrastructure::inbox_persistence::SledInboxPersistence;
-use crate::infrastructure::outbox_persistence::SledOutboxPersistence;
+use crate::infrastructure::outbox_persistence::{PendingEvent, SledOutboxPersistence};
 use crate::port::peer_network::PeerNetwork;
 use anyhow::Result;
 use std::sync::Arc;
@@ -286,6 +286,15 @@ impl<P: PeerNetwork> ReliableEventPublisher<P> {
                 content_id.hash(&mut hasher);
                 timestamp.hash(&mut hasher);
             }
+            Event::NodeCapacityUpdated {
+                node_id, timestamp, ..
+            }
+            | Event::NodeDeparted {
+                node_id, timestamp, ..
+            } => {
+                node_id.hash(&mut hasher);
+                timestamp.hash(&mut hasher);
+            }
         }
 
         format!("{:016x}", hasher.finish())
@@ -312,6 +321,11 @@ impl<P: PeerNetwork> ReliableEventPublisher<P> {
         })
     }
 
+    /// Events still waiting for delivery to at least one target.
+    pub fn pending_events(&self) -> Result<Vec<PendingEvent>> {
+        self.outbox.get_pending_events(None)
+    }
+
     /// Flush all pending writes to disk.
     pub fn flush(&self) -> Result<()> {
         self.outbox.flush()?;
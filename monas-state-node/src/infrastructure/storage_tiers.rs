@@ -0,0 +1,358 @@
+//! Storage tiers for the state node.
+//!
+//! A node keeps its CRDT state and databases under the data directory (the
+//! hot tier, typically an SSD) and may add cold storage roots (typically
+//! HDDs) for content blobs. [`TieredBlobStore`] writes blobs to the hot tier;
+//! a periodic tiering pass moves blobs that have not been read for
+//! [`StorageTierConfig::cold_after`] to the cold root with the most free space,
+//! and reading a cold blob moves it back to the hot tier.
+
+use crate::infrastructure::disk_capacity;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Subdirectory of each storage root that holds content blobs.
+const BLOB_DIR: &str = "blobs";
+
+/// Default idle time after which a blob is moved to the cold tier (7 days).
+const DEFAULT_COLD_AFTER: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Storage tier a blob currently lives on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageTier {
+    Hot,
+    Cold,
+}
+
+/// Cold storage roots and the tiering policy.
+///
+/// The hot tier is always the node's data directory. Without cold roots the
+/// node behaves as a single-directory node and no blob is ever moved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageTierConfig {
+    /// Storage roots for infrequently accessed content blobs.
+    pub cold_dirs: Vec<PathBuf>,
+    /// Blobs not read for this long are moved to the cold tier.
+    pub cold_after: Duration,
+}
+
+impl Default for StorageTierConfig {
+    fn default() -> Self {
+        Self {
+            cold_dirs: Vec::new(),
+            cold_after: DEFAULT_COLD_AFTER,
+        }
+    }
+}
+
+impl StorageTierConfig {
+    /// Build the configuration from environment variables.
+    ///
+    /// - `COLD_DATA_DIRS`: comma-separated cold storage roots
+    /// - `TIERING_COLD_AFTER_SECS`: idle seconds before a blob is moved to
+    ///   the cold tier (default: 7 days)
+    pub fn from_env() -> Self {
+        Self {
+            cold_dirs: std::env::var("COLD_DATA_DIRS")
+                .ok()
+                .map(|v| {
+                    v.split(',')
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(PathBuf::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            cold_after: std::env::var("TIERING_COLD_AFTER_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_COLD_AFTER),
+        }
+    }
+
+    /// Returns true if at least one cold root is configured.
+    pub fn is_tiered(&self) -> bool {
+        !self.cold_dirs.is_empty()
+    }
+}
+
+/// Content blob store spread over a hot root and any number of cold roots.
+///
+/// The modification time of a blob file records when it was last written or
+/// read, so the tiering state survives restarts without a separate index.
+pub struct TieredBlobStore {
+    hot_root: PathBuf,
+    cold_roots: Vec<PathBuf>,
+    cold_after: Duration,
+}
+
+impl TieredBlobStore {
+    /// Open the store, creating the blob directories on every root.
+    pub fn open(hot_root: &Path, config: &StorageTierConfig) -> Result<Self> {
+        let store = Self {
+            hot_root: hot_root.to_path_buf(),
+            cold_roots: config.cold_dirs.clone(),
+            cold_after: config.cold_after,
+        };
+        for root in store.roots() {
+            fs::create_dir_all(root.join(BLOB_DIR))
+                .with_context(|| format!("Failed to create blob directory in {:?}", root))?;
+        }
+        Ok(store)
+    }
+
+    /// All storage roots, hot first.
+    pub fn roots(&self) -> Vec<PathBuf> {
+        std::iter::once(self.hot_root.clone())
+            .chain(self.cold_roots.iter().cloned())
+            .collect()
+    }
+
+    /// Total and available bytes across all tiers.
+    ///
+    /// Roots sharing a filesystem are only counted once.
+    pub fn capacity(&self) -> Result<(u64, u64)> {
+        disk_capacity::get_aggregate_disk_capacity(&self.roots())
+    }
+
+    /// Store a blob on the hot tier, replacing any existing copy.
+    pub fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        let path = self.hot_path(key);
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, data).with_context(|| format!("Failed to write blob {}", key))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to write blob {}", key))?;
+        for cold in self.cold_paths(key) {
+            remove_if_exists(&cold)?;
+        }
+        Ok(())
+    }
+
+    /// Read a blob. A blob found on the cold tier is moved back to the hot tier.
+    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let hot = self.hot_path(key);
+        if hot.exists() {
+            let data = fs::read(&hot).with_context(|| format!("Failed to read blob {}", key))?;
+            touch(&hot)?;
+            return Ok(Some(data));
+        }
+        for cold in self.cold_paths(key) {
+            if cold.exists() {
+                let data =
+                    fs::read(&cold).with_context(|| format!("Failed to read blob {}", key))?;
+                move_file(&cold, &hot)?;
+                touch(&hot)?;
+                return Ok(Some(data));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Remove a blob from every tier. Returns true if it existed.
+    pub fn remove(&self, key: &str) -> Result<bool> {
+        let mut removed = remove_if_exists(&self.hot_path(key))?;
+        for cold in self.cold_paths(key) {
+            removed |= remove_if_exists(&cold)?;
+        }
+        Ok(removed)
+    }
+
+    /// The tier a blob currently lives on, if it exists.
+    pub fn tier_of(&self, key: &str) -> Option<StorageTier> {
+        if self.hot_path(key).exists() {
+            Some(StorageTier::Hot)
+        } else if self.cold_paths(key).any(|p| p.exists()) {
+            Some(StorageTier::Cold)
+        } else {
+            None
+        }
+    }
+
+    /// Move blobs not read for `cold_after` to the cold tier.
+    ///
+    /// Returns the number of blobs moved. Does nothing without cold roots.
+    pub fn demote_idle(&self) -> Result<usize> {
+        self.demote_idle_at(SystemTime::now())
+    }
+
+    fn demote_idle_at(&self, now: SystemTime) -> Result<usize> {
+        if self.cold_roots.is_empty() {
+            return Ok(0);
+        }
+
+        let mut moved = 0;
+        for entry in fs::read_dir(self.hot_root.join(BLOB_DIR))? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "tmp") {
+                continue;
+            }
+            let modified = entry.metadata()?.modified()?;
+            let idle = now.duration_since(modified).unwrap_or_default();
+            if idle < self.cold_after {
+                continue;
+            }
+
+            let target = self
+                .roomiest_cold_root()?
+                .join(BLOB_DIR)
+                .join(entry.file_name());
+            move_file(&path, &target)?;
+            moved += 1;
+        }
+        Ok(moved)
+    }
+
+    /// The cold root with the most available space.
+    fn roomiest_cold_root(&self) -> Result<&PathBuf> {
+        let mut best: Option<(&PathBuf, u64)> = None;
+        for root in &self.cold_roots {
+            let (_, available) = disk_capacity::get_disk_capacity(root)?;
+            match best {
+                Some((_, most)) if most >= available => {}
+                _ => best = Some((root, available)),
+            }
+        }
+        best.map(|(root, _)| root)
+            .context("No cold storage root configured")
+    }
+
+    fn hot_path(&self, key: &str) -> PathBuf {
+        self.hot_root.join(BLOB_DIR).join(file_name(key))
+    }
+
+    fn cold_paths<'a>(&'a self, key: &str) -> impl Iterator<Item = PathBuf> + 'a {
+        let name = file_name(key);
+        self.cold_roots
+            .iter()
+            .map(move |root| root.join(BLOB_DIR).join(&name))
+    }
+}
+
+/// Blob keys are content IDs supplied by peers, so they are hex-encoded
+/// rather than used as file names directly.
+fn file_name(key: &str) -> String {
+    hex::encode(key.as_bytes())
+}
+
+fn touch(path: &Path) -> Result<()> {
+    fs::File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(SystemTime::now())?;
+    Ok(())
+}
+
+fn remove_if_exists(path: &Path) -> Result<bool> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Move a file, falling back to copy-and-delete across filesystems.
+/// The modification time is preserved so tiering decisions stay stable.
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    let modified = fs::metadata(from)?.modified()?;
+    fs::copy(from, to).with_context(|| format!("Failed to copy {:?} to {:?}", from, to))?;
+    fs::File::options()
+        .write(true)
+        .open(to)?
+        .set_modified(modified)?;
+    fs::remove_file(from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tiered_store(cold_after: Duration) -> (TieredBlobStore, TempDir, TempDir) {
+        let hot = TempDir::new().unwrap();
+        let cold = TempDir::new().unwrap();
+        let config = StorageTierConfig {
+            cold_dirs: vec![cold.path().to_path_buf()],
+            cold_after,
+        };
+        let store = TieredBlobStore::open(hot.path(), &config).unwrap();
+        (store, hot, cold)
+    }
+
+    #[test]
+    fn test_idle_blobs_move_to_cold_tier_and_back_on_read() {
+        let (store, _hot, _cold) = tiered_store(Duration::from_secs(60));
+        store.put("bafy-old", b"old").unwrap();
+        store.put("bafy-new", b"new").unwrap();
+
+        // Nothing is idle yet.
+        assert_eq!(store.demote_idle().unwrap(), 0);
+
+        // Backdate one blob past the threshold.
+        let old_path = store.hot_path("bafy-old");
+        fs::File::options()
+            .write(true)
+            .open(&old_path)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(120))
+            .unwrap();
+
+        assert_eq!(store.demote_idle().unwrap(), 1);
+        assert_eq!(store.tier_of("bafy-old"), Some(StorageTier::Cold));
+        assert_eq!(store.tier_of("bafy-new"), Some(StorageTier::Hot));
+
+        // Reading a cold blob promotes it.
+        assert_eq!(store.get("bafy-old").unwrap(), Some(b"old".to_vec()));
+        assert_eq!(store.tier_of("bafy-old"), Some(StorageTier::Hot));
+        assert_eq!(store.demote_idle().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_put_replaces_cold_copy_and_remove_clears_all_tiers() {
+        let (store, _hot, _cold) = tiered_store(Duration::ZERO);
+        store.put("bafy", b"v1").unwrap();
+        assert_eq!(store.demote_idle().unwrap(), 1);
+
+        store.put("bafy", b"v2").unwrap();
+        assert_eq!(store.tier_of("bafy"), Some(StorageTier::Hot));
+        assert!(store.cold_paths("bafy").all(|p| !p.exists()));
+        assert_eq!(store.get("bafy").unwrap(), Some(b"v2".to_vec()));
+
+        assert!(store.remove("bafy").unwrap());
+        assert_eq!(store.tier_of("bafy"), None);
+        assert!(!store.remove("bafy").unwrap());
+        assert_eq!(store.get("bafy").unwrap(), None);
+    }
+
+    #[test]
+    fn test_single_root_never_demotes() {
+        let hot = TempDir::new().unwrap();
+        let config = StorageTierConfig {
+            cold_after: Duration::ZERO,
+            ..StorageTierConfig::default()
+        };
+        let store = TieredBlobStore::open(hot.path(), &config).unwrap();
+        store.put("bafy", b"data").unwrap();
+
+        assert!(!config.is_tiered());
+        assert_eq!(store.demote_idle().unwrap(), 0);
+        assert_eq!(store.tier_of("bafy"), Some(StorageTier::Hot));
+        assert_eq!(store.roots(), vec![hot.path().to_path_buf()]);
+    }
+
+    #[test]
+    fn test_capacity_aggregates_all_roots() {
+        let (store, hot, _cold) = tiered_store(DEFAULT_COLD_AFTER);
+        // Both temp dirs usually live on the same filesystem.
+        let (total, available) = store.capacity().unwrap();
+        let (single_total, _) = disk_capacity::get_disk_capacity(hot.path()).unwrap();
+        assert!(total >= single_total);
+        assert!(available <= total);
+    }
+}
@@ -12,9 +12,10 @@ pub mod test_utils;
 pub use domain::{
     AccessControlError, AccessControlEvent, AccessControlUpdate, AccessPolicy, AccessPolicyError,
     AuthCapability, AuthToken, AuthTokenParseError, AuthTokenVerifier, AuthTokenVerifyError,
-    Capability, CapabilityAction, ContentAccessControl, ContentId, CrdtError, Identity,
-    IdentityError, IdentityType, KeyId, NetworkError, NodeCandidate, NodeId, NonEmptySet,
-    PlacementError, PlacementPolicy, StateNodeError, ValueError, VerifiedToken,
+    Capability, CapabilityAction, ContentAccessControl, ContentId, ContentSyncAttributes,
+    CrdtError, Identity, IdentityError, IdentityType, KeyId, NetworkError, NodeCandidate, NodeId,
+    NonEmptySet, PlacementError, PlacementPolicy, StateNodeError, SyncRules, ValueError,
+    VerifiedToken,
 };
 
 // Port layer exports (excluding AuthToken to avoid conflict with domain::AuthToken)
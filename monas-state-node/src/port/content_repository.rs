@@ -4,12 +4,13 @@
 //! with version history and multi-node synchronization support.
 
 use crate::domain::access_policy::AccessPolicy;
+use crate::domain::storage_budget::StorageUsage;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 /// Represents a CRDT operation that can be serialized and sent over the network.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SerializedOperation {
     /// The serialized operation bytes (CBOR encoded).
     pub data: Vec<u8>,
@@ -24,6 +25,39 @@ pub struct SerializedOperation {
     pub node_timestamp: u64,
 }
 
+/// Snapshot of a content's state, standing in for the operations up to it.
+///
+/// Every Create/Update operation carries the full content state, so the
+/// Create operation followed by the operation that produced the latest state
+/// reproduces that state without the operations in between. Repositories
+/// that compact their log keep only these two operations plus the ones after
+/// the snapshot, and serve a snapshot to peers too far behind to use the
+/// truncated log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentSnapshot {
+    /// The genesis CID of the content.
+    pub genesis_cid: String,
+    /// Number of versions the snapshot covers, counting versions truncated
+    /// by earlier snapshots.
+    pub version: u64,
+    /// The Create operation of the content.
+    pub create_op: SerializedOperation,
+    /// The operation that produced the state at `version`. `None` when that
+    /// is the Create operation itself.
+    pub state_op: Option<SerializedOperation>,
+    /// Unix timestamp (seconds) when the snapshot was taken.
+    pub created_at: u64,
+}
+
+impl ContentSnapshot {
+    /// The operations that reproduce the snapshot state, in order.
+    pub fn operations(&self) -> Vec<SerializedOperation> {
+        std::iter::once(self.create_op.clone())
+            .chain(self.state_op.clone())
+            .collect()
+    }
+}
+
 /// Result of committing content to the CRDT store.
 #[derive(Debug, Clone)]
 pub struct CommitResult {
@@ -118,6 +152,36 @@ pub trait ContentRepository: Send + Sync {
     /// The content data at that version, or None if not found.
     async fn get_version(&self, version_cid: &str) -> Result<Option<Vec<u8>>>;
 
+    /// Get content as it was at a historical version number.
+    ///
+    /// Versions are numbered from 1 (the Create operation) in the order the
+    /// operations appear in the content's log, so `version = 3` is the state
+    /// after the second update. Unlike [`get_version`](Self::get_version) the
+    /// caller does not need to know the version CID, which lets clients walk
+    /// a version-history UI by index.
+    ///
+    /// The default implementation looks up the `version`-th entry of
+    /// [`get_history`](Self::get_history). Implementations backed by an
+    /// operation log should override it to replay operations instead.
+    ///
+    /// # Arguments
+    /// * `genesis_cid` - The genesis CID of the content
+    /// * `version` - The 1-based version number
+    ///
+    /// # Returns
+    /// The content data at that version, or None if the content or version
+    /// does not exist.
+    async fn get_at_version(&self, genesis_cid: &str, version: u64) -> Result<Option<Vec<u8>>> {
+        if version == 0 || self.is_denied(genesis_cid).await? {
+            return Ok(None);
+        }
+        let history = self.get_history(genesis_cid).await?;
+        match history.get((version - 1) as usize) {
+            Some(version_cid) => self.get_version(version_cid).await,
+            None => Ok(None),
+        }
+    }
+
     /// Get the version history of content.
     ///
     /// # Arguments
@@ -141,6 +205,20 @@ pub trait ContentRepository: Send + Sync {
         since_version: Option<&str>,
     ) -> Result<Vec<SerializedOperation>>;
 
+    /// Get operations for synchronization, replacing those covered by a
+    /// snapshot with the snapshot when the requester is behind it.
+    ///
+    /// Returns the snapshot, if one is used, and the operations to apply
+    /// after it. The default implementation has no snapshots and returns
+    /// [`get_operations`](Self::get_operations).
+    async fn get_operations_with_snapshot(
+        &self,
+        genesis_cid: &str,
+        since_version: Option<&str>,
+    ) -> Result<(Option<ContentSnapshot>, Vec<SerializedOperation>)> {
+        Ok((None, self.get_operations(genesis_cid, since_version).await?))
+    }
+
     /// Apply operations received from another node.
     ///
     /// # Arguments
@@ -177,6 +255,30 @@ pub trait ContentRepository: Send + Sync {
     /// True if the genesis node is present in the local DAG.
     async fn has_genesis(&self, genesis_cid: &str) -> Result<bool>;
 
+    /// Check whether the content has been denied (taken down) on this node.
+    ///
+    /// Denied content must never be stored or served: implementations that
+    /// support a denylist refuse `apply_operations` and fetches for it.
+    /// The default implementation has no denylist and always returns `false`.
+    ///
+    /// # Arguments
+    /// * `genesis_cid` - The genesis CID to check
+    ///
+    /// # Returns
+    /// True if a tombstone exists for the content.
+    async fn is_denied(&self, _genesis_cid: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Get the bytes used against the storage budget.
+    ///
+    /// Implementations with a storage budget refuse new content once nearly
+    /// full; callers use the usage to report real available capacity. The
+    /// default implementation has no budget and returns `None`.
+    fn storage_usage(&self) -> Option<StorageUsage> {
+        None
+    }
+
     /// List all content genesis CIDs.
     ///
     /// # Returns
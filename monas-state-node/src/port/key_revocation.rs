@@ -0,0 +1,20 @@
+//! Account key revocation lookup.
+//!
+//! Accounts deactivated in monas-account keep their key, but signatures made
+//! with it must no longer be accepted. Authentication adapters consult this
+//! port before accepting a signature.
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Source of revoked account public keys.
+#[async_trait]
+pub trait KeyRevocationChecker: Send + Sync {
+    /// Whether the given SEC1-encoded P-256 public key has been revoked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the revocation list cannot be consulted. Callers
+    /// must treat this as a verification failure, not as "not revoked".
+    async fn is_revoked(&self, public_key: &[u8]) -> Result<bool>;
+}
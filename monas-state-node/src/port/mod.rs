@@ -8,6 +8,7 @@ pub mod authentication_service;
 pub mod authorization_service;
 pub mod content_repository;
 pub mod event_publisher;
+pub mod key_revocation;
 pub mod peer_network;
 pub mod persistence;
 pub mod public_key_registry;
@@ -17,6 +18,12 @@ pub use authentication_service::AuthenticationService;
 pub use authorization_service::{AuthorizationRequest, AuthorizationResult, AuthorizationService};
 pub use content_repository::{CommitResult, ContentRepository, SerializedOperation};
 pub use event_publisher::EventPublisher;
+pub use key_revocation::KeyRevocationChecker;
 pub use peer_network::PeerNetwork;
-pub use persistence::{PersistentContentRepository, PersistentNodeRegistry};
+pub use persistence::{
+    PersistentContentRepository, PersistentDenylistRepository, PersistentEvictionRepository,
+    PersistentNodeRegistry, PersistentPeerAccessRepository, PersistentPeerStore,
+};
+#[cfg(target_arch = "wasm32")]
+pub use persistence::{WasmContentRepository, WasmNodeRegistry};
 pub use public_key_registry::{InMemoryPublicKeyRegistry, PublicKeyRegistry};
@@ -1,10 +1,14 @@
 //! PeerNetwork trait - Abstract interface for P2P network operations
 
+use crate::domain::mirror::{MirrorPairingAcceptance, MirrorPairingRequest};
+use crate::domain::peer_access::PeerAccess;
+use crate::domain::sync_rules::{ContentSyncAttributes, SyncRules};
 use crate::port::content_repository::SerializedOperation;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// Payload that bootstraps a brand-new content network on the receiver of
 /// a `PushOperations` request.
@@ -26,6 +30,81 @@ pub struct PushBootstrap {
     pub member_nodes: Vec<String>,
     /// Wall-clock timestamp at creation, for auditability.
     pub created_at: u64,
+    /// Attributes of the new content. The receiver rejects the push if its
+    /// selective sync rules do not accept them.
+    #[serde(default)]
+    pub attributes: ContentSyncAttributes,
+}
+
+/// Capability token a peer presents to read content of a network it is not
+/// a member of.
+///
+/// Members are authorized by their transport-authenticated peer ID and send
+/// no capability. The reader's key signs [`ReadCapability::signing_message`],
+/// which binds the capability to one content, to the peer that presents it
+/// and to a time, so a serving node cannot replay it to other members or for
+/// other content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadCapability {
+    /// Key-ID token of the reader.
+    pub auth_token: String,
+    /// Signature over [`ReadCapability::signing_message`].
+    pub request_signature: Vec<u8>,
+    /// When the capability was signed (Unix seconds).
+    pub timestamp: u64,
+}
+
+impl ReadCapability {
+    /// Get the message a reader signs to let `requester_peer_id` fetch
+    /// `content_id` on its behalf.
+    ///
+    /// Format: `read:content/{content_id}/peer/{requester_peer_id}:{timestamp}`
+    pub fn signing_message(content_id: &str, requester_peer_id: &str, timestamp: u64) -> String {
+        crate::port::auth_token::RequestMetadata {
+            timestamp,
+            operation: "read".to_string(),
+            resource: format!("content/{}/peer/{}", content_id, requester_peer_id),
+        }
+        .signing_message()
+    }
+}
+
+/// A byte range of a content's latest data, as served by one peer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentChunk {
+    /// The requested bytes; shorter than requested at the end of the data.
+    pub data: Vec<u8>,
+    /// Version CID of the data the range was cut from. Empty when the peer
+    /// does not report one.
+    pub version: String,
+    /// Size of the whole data in bytes.
+    pub total_size: u64,
+}
+
+impl ContentChunk {
+    /// Cut the range `offset..offset + length` out of `data`, clamped to its
+    /// end.
+    pub fn cut(data: &[u8], version: String, offset: u64, length: u64) -> Self {
+        let total_size = data.len() as u64;
+        let start = offset.min(total_size);
+        let end = offset.saturating_add(length).min(total_size);
+        Self {
+            data: data[start as usize..end as usize].to_vec(),
+            version,
+            total_size,
+        }
+    }
+}
+
+/// A value stored in the DHT, after its signature has been verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DhtRecord {
+    /// Node ID (PeerId) of the node that signed the record.
+    pub publisher: String,
+    /// The stored value.
+    pub value: Vec<u8>,
+    /// When the record was published (Unix seconds).
+    pub published_at: u64,
 }
 
 /// Abstract interface for peer-to-peer network operations.
@@ -49,6 +128,28 @@ pub trait PeerNetwork: Send + Sync {
     /// Uses RequestResponse protocol to query multiple peers in parallel.
     async fn query_node_capacity_batch(&self, peer_ids: &[String]) -> Result<HashMap<String, u64>>;
 
+    /// Query the selective sync rules advertised by nodes in batch.
+    ///
+    /// Peers missing from the result did not advertise rules and are treated
+    /// as replicating everything.
+    async fn query_node_sync_rules_batch(
+        &self,
+        _peer_ids: &[String],
+    ) -> Result<HashMap<String, SyncRules>> {
+        Ok(HashMap::new())
+    }
+
+    /// Query the accounts that own nodes, as attested by the nodes themselves.
+    ///
+    /// Returns a map of peer_id -> account ID (hex-encoded account public key).
+    /// Peers missing from the result presented no valid attestation.
+    async fn query_node_owners_batch(
+        &self,
+        _peer_ids: &[String],
+    ) -> Result<HashMap<String, String>> {
+        Ok(HashMap::new())
+    }
+
     /// Query node public keys (P-256, SEC1 uncompressed format) in batch.
     ///
     /// Uses RequestResponse protocol to query multiple peers in parallel.
@@ -66,11 +167,69 @@ pub trait PeerNetwork: Send + Sync {
     /// Uses RequestResponse protocol.
     async fn fetch_content(&self, peer_id: &str, content_id: &str) -> Result<Vec<u8>>;
 
+    /// Fetch content from a peer whose content network this node is not a
+    /// member of, presenting a capability token that grants read access.
+    ///
+    /// The default implementation does not support capability tokens.
+    async fn fetch_content_with_capability(
+        &self,
+        _peer_id: &str,
+        _content_id: &str,
+        _capability: &ReadCapability,
+    ) -> Result<Vec<u8>> {
+        anyhow::bail!("Fetching with a capability token is not supported")
+    }
+
+    /// Fetch `length` bytes of the latest data of `content_id` from a peer,
+    /// starting at `offset`. `capability` is presented when this node is not
+    /// a member of the content network.
+    ///
+    /// The default implementation fetches the whole content and cuts the
+    /// range from it.
+    async fn fetch_content_range(
+        &self,
+        peer_id: &str,
+        content_id: &str,
+        offset: u64,
+        length: u64,
+        capability: Option<&ReadCapability>,
+    ) -> Result<ContentChunk> {
+        let data = match capability {
+            Some(capability) => {
+                self.fetch_content_with_capability(peer_id, content_id, capability)
+                    .await?
+            }
+            None => self.fetch_content(peer_id, content_id).await?,
+        };
+        Ok(ContentChunk::cut(&data, String::new(), offset, length))
+    }
+
     /// Announce this node as a provider for a content key.
     ///
     /// Uses Kademlia's start_providing.
     async fn publish_provider(&self, key: Vec<u8>) -> Result<()>;
 
+    /// Stop announcing this node as a provider for a content key.
+    ///
+    /// Uses Kademlia's stop_providing. Copies of the record already stored on
+    /// other peers are not revoked and expire after their TTL.
+    async fn remove_provider(&self, key: Vec<u8>) -> Result<()>;
+
+    /// Store a small value under `key` in the DHT, signed with the node
+    /// identity key.
+    ///
+    /// Peers keep only the most recently published value for each key.
+    async fn put_record(&self, _key: Vec<u8>, _value: Vec<u8>) -> Result<()> {
+        anyhow::bail!("DHT records are not supported by this network")
+    }
+
+    /// Get the values stored under `key` in the DHT.
+    ///
+    /// Only records with a valid signature are returned.
+    async fn get_records(&self, _key: Vec<u8>) -> Result<Vec<DhtRecord>> {
+        anyhow::bail!("DHT records are not supported by this network")
+    }
+
     /// Get the local peer ID as a string.
     fn local_peer_id(&self) -> String;
 
@@ -90,6 +249,38 @@ pub trait PeerNetwork: Send + Sync {
         since_version: Option<&str>,
     ) -> Result<Vec<SerializedOperation>>;
 
+    /// Fetch CRDT operations like [`fetch_operations`], together with the
+    /// peer's latest version CID when it reports one.
+    ///
+    /// The default implementation reports no version.
+    ///
+    /// [`fetch_operations`]: PeerNetwork::fetch_operations
+    async fn fetch_operations_with_version(
+        &self,
+        peer_id: &str,
+        genesis_cid: &str,
+        since_version: Option<&str>,
+    ) -> Result<(Vec<SerializedOperation>, Option<String>)> {
+        let operations = self
+            .fetch_operations(peer_id, genesis_cid, since_version)
+            .await?;
+        Ok((operations, None))
+    }
+
+    /// Fetch all CRDT operations of a content from a peer whose content
+    /// network this node is not a member of, presenting a capability that
+    /// grants read access.
+    ///
+    /// The default implementation does not support capability tokens.
+    async fn fetch_operations_with_capability(
+        &self,
+        _peer_id: &str,
+        _genesis_cid: &str,
+        _capability: &ReadCapability,
+    ) -> Result<Vec<SerializedOperation>> {
+        anyhow::bail!("Fetching with a capability token is not supported")
+    }
+
     /// Push CRDT operations to a peer that already knows this content network.
     ///
     /// The receiver verifies the sender is a known member. For the very first
@@ -171,8 +362,40 @@ pub trait PeerNetwork: Send + Sync {
         timestamp: Option<u64>,
     ) -> Result<bool>;
 
+    // ========== Mirror Methods ==========
+
+    /// Send a signed mirror pairing request to the primary node.
+    ///
+    /// Returns the primary's signed acceptance. The caller must verify it.
+    async fn request_mirror_pairing(
+        &self,
+        _peer_id: &str,
+        _request: &MirrorPairingRequest,
+    ) -> Result<MirrorPairingAcceptance> {
+        anyhow::bail!("Mirror pairing is not supported by this network")
+    }
+
+    // ========== Connection Gating Methods ==========
+
+    /// Apply an operator access decision to `peer_id`; `None` clears it.
+    ///
+    /// Blocking a peer also closes any open connection to it.
+    async fn set_peer_access(&self, _peer_id: &str, _access: Option<PeerAccess>) -> Result<()> {
+        anyhow::bail!("Peer access control is not supported by this network")
+    }
+
     // ========== Monitoring Methods ==========
 
     /// Get the number of currently connected peers.
     async fn connected_peer_count(&self) -> usize;
+
+    /// Get the peer IDs of currently connected peers.
+    async fn connected_peer_ids(&self) -> Vec<String>;
+
+    /// Rolling mean round-trip time of each connected peer, measured by ping.
+    ///
+    /// Peers that have not been measured yet are absent.
+    async fn peer_latencies(&self) -> HashMap<String, Duration> {
+        HashMap::new()
+    }
 }
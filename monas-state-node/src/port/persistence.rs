@@ -5,7 +5,11 @@ use async_trait::async_trait;
 
 use crate::domain::access_control::ContentAccessControl;
 use crate::domain::content_network::ContentNetwork;
+use crate::domain::eviction::Eviction;
+use crate::domain::known_peer::KnownPeer;
+use crate::domain::peer_access::PeerAccess;
 use crate::domain::state_node::NodeSnapshot;
+use crate::domain::tombstone::Tombstone;
 
 /// Abstract interface for node registry persistence.
 ///
@@ -100,3 +104,142 @@ pub trait PersistentAccessControlRepository: Send + Sync {
     /// Flush pending writes to disk.
     async fn flush(&self) -> Result<()>;
 }
+
+/// Denylist persistence operations.
+///
+/// Stores tombstones for content this node refuses to host. Tombstones are
+/// keyed by genesis CID and are never removed by network activity.
+#[async_trait]
+pub trait PersistentDenylistRepository: Send + Sync {
+    /// Record a tombstone. Overwrites any existing tombstone for the same CID.
+    async fn add_tombstone(&self, tombstone: &Tombstone) -> Result<()>;
+
+    /// Get the tombstone for a genesis CID, if any.
+    async fn get_tombstone(&self, genesis_cid: &str) -> Result<Option<Tombstone>>;
+
+    /// Check whether a genesis CID is denied.
+    async fn is_denied(&self, genesis_cid: &str) -> Result<bool>;
+
+    /// List all denied genesis CIDs.
+    async fn list_tombstones(&self) -> Result<Vec<String>>;
+
+    /// Flush pending writes to disk.
+    async fn flush(&self) -> Result<()>;
+}
+
+/// Eviction persistence operations.
+///
+/// Stores eviction records for content this node no longer hosts, keyed by
+/// genesis CID. Records are removed when the node becomes a member of the
+/// content again.
+#[async_trait]
+pub trait PersistentEvictionRepository: Send + Sync {
+    /// Record an eviction. Overwrites any existing record for the same CID.
+    async fn save_eviction(&self, eviction: &Eviction) -> Result<()>;
+
+    /// Get the eviction record for a genesis CID, if any.
+    async fn get_eviction(&self, genesis_cid: &str) -> Result<Option<Eviction>>;
+
+    /// Check whether a genesis CID is evicted.
+    async fn is_evicted(&self, genesis_cid: &str) -> Result<bool>;
+
+    /// Remove the eviction record for a genesis CID. Returns whether one
+    /// existed.
+    async fn cancel_eviction(&self, genesis_cid: &str) -> Result<bool>;
+
+    /// List all eviction records.
+    async fn list_evictions(&self) -> Result<Vec<Eviction>>;
+
+    /// Flush pending writes to disk.
+    async fn flush(&self) -> Result<()>;
+}
+
+/// Peer access list persistence operations.
+///
+/// Stores the operator's allow/block decisions per peer ID so they survive
+/// restarts.
+#[async_trait]
+pub trait PersistentPeerAccessRepository: Send + Sync {
+    /// Set the access decision for a peer. Overwrites any existing entry.
+    async fn set_peer_access(&self, peer_id: &str, access: PeerAccess) -> Result<()>;
+
+    /// Get the access decision for a peer, if any.
+    async fn get_peer_access(&self, peer_id: &str) -> Result<Option<PeerAccess>>;
+
+    /// Remove the entry for a peer, restoring the default.
+    async fn remove_peer_access(&self, peer_id: &str) -> Result<()>;
+
+    /// List all entries as `(peer_id, access)`.
+    async fn list_peer_access(&self) -> Result<Vec<(String, PeerAccess)>>;
+
+    /// Flush pending writes to disk.
+    async fn flush(&self) -> Result<()>;
+}
+
+/// Known peer persistence operations.
+///
+/// Stores peers this node has connected to, keyed by peer ID, so they can be
+/// dialed again after a restart.
+#[async_trait]
+pub trait PersistentPeerStore: Send + Sync {
+    /// Record a peer. Overwrites any existing entry for the same peer.
+    async fn save_known_peer(&self, peer: &KnownPeer) -> Result<()>;
+
+    /// List all known peers.
+    async fn list_known_peers(&self) -> Result<Vec<KnownPeer>>;
+
+    /// Forget a peer.
+    async fn remove_known_peer(&self, peer_id: &str) -> Result<()>;
+
+    /// Flush pending writes to disk.
+    async fn flush(&self) -> Result<()>;
+}
+
+/// Browser counterpart of [`PersistentNodeRegistry`].
+///
+/// Browser storage handles are tied to the single JavaScript thread, so the
+/// futures are not `Send` and implementations need not be `Sync`.
+#[cfg(target_arch = "wasm32")]
+#[async_trait(?Send)]
+pub trait WasmNodeRegistry {
+    /// Insert or update a node snapshot.
+    async fn upsert_node(&self, node: &NodeSnapshot) -> Result<()>;
+
+    /// Get the available capacity for a node.
+    async fn get_available_capacity(&self, node_id: &str) -> Result<Option<u64>>;
+
+    /// List all known node IDs.
+    async fn list_nodes(&self) -> Result<Vec<String>>;
+
+    /// Get a node snapshot by ID.
+    async fn get_node(&self, node_id: &str) -> Result<Option<NodeSnapshot>>;
+
+    /// Delete a node from the registry.
+    async fn delete_node(&self, node_id: &str) -> Result<()>;
+
+    /// Flush pending writes to storage.
+    async fn flush(&self) -> Result<()>;
+}
+
+/// Browser counterpart of [`PersistentContentRepository`].
+#[cfg(target_arch = "wasm32")]
+#[async_trait(?Send)]
+pub trait WasmContentRepository {
+    /// Find content IDs that can be assigned to a node with given capacity.
+    async fn find_assignable_cids(&self, capacity: u64) -> Result<Vec<String>>;
+
+    /// Get a content network by content ID.
+    async fn get_content_network(&self, content_id: &str) -> Result<Option<ContentNetwork>>;
+
+    /// Save a content network.
+    async fn save_content_network(&self, net: ContentNetwork) -> Result<()>;
+
+    /// Delete a content network.
+    async fn delete_content_network(&self, content_id: &str) -> Result<()>;
+
+    /// List all content network IDs.
+    async fn list_content_networks(&self) -> Result<Vec<String>>;
+
+    /// Flush pending writes to storage.
+    async fn flush(&self) -> Result<()>;
+}
@@ -0,0 +1,489 @@
+//! Operator admin API for the state node.
+//!
+//! Served on a separate listener that must be bound to a loopback address
+//! (see `StateNodeConfig::admin_addr`), so it is reachable only from the
+//! node's host. Requests from non-loopback addresses are refused as well,
+//! in case the listener ends up exposed through a proxy or port forward.
+//!
+//! A loopback listener is still reachable from any web page the operator
+//! opens in a browser, by cross-site requests or by DNS rebinding. Requests
+//! must therefore name a loopback host in `Host` (and in `Origin`, when
+//! sent) and carry the same operator token as the `/admin/*` endpoints of
+//! the public API in the `X-Admin-Token` header.
+
+use crate::application_service::content_sync_service::{AntiEntropySchedule, ScheduledSync};
+use crate::application_service::node::ReliablePublisher;
+use crate::domain::events::current_timestamp;
+use crate::domain::sync_status::ContentSyncStatus;
+use crate::infrastructure::gossipsub_publisher::PublishQueueMetrics;
+use crate::infrastructure::network::{CommandQueueMetrics, Libp2pNetwork};
+use crate::presentation::http_api::{extract_admin_token, AppState, ErrorResponse};
+use anyhow::Result;
+use axum::{
+    extract::{ConnectInfo, Path, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::RwLock;
+use tracing_subscriber::{reload, EnvFilter};
+
+/// Runtime control of the log filter, e.g. a `tracing_subscriber` reload
+/// handle.
+pub trait LogFilterControl: Send + Sync {
+    /// Current filter directives.
+    fn current(&self) -> String;
+
+    /// Replace the filter with `directives` (`EnvFilter` syntax, e.g.
+    /// `info,monas_state_node=debug`).
+    fn set(&self, directives: &str) -> Result<()>;
+}
+
+impl<S: 'static> LogFilterControl for reload::Handle<EnvFilter, S> {
+    fn current(&self) -> String {
+        self.with_current(|filter| filter.to_string())
+            .unwrap_or_default()
+    }
+
+    fn set(&self, directives: &str) -> Result<()> {
+        let filter = EnvFilter::try_new(directives)?;
+        self.reload(filter)?;
+        Ok(())
+    }
+}
+
+/// Everything the admin API reads from or acts on.
+pub struct AdminContext {
+    pub service: AppState,
+    pub network: Arc<Libp2pNetwork>,
+    pub reliable_publisher: Arc<ReliablePublisher>,
+    /// Anti-entropy schedule, as of the last completed round.
+    pub sync_schedule: Arc<RwLock<AntiEntropySchedule>>,
+    /// Log filter control; log level endpoints fail when absent.
+    pub log_filter: Option<Arc<dyn LogFilterControl>>,
+}
+
+/// Admin API state shared across handlers.
+pub type AdminState = Arc<AdminContext>;
+
+/// Create the admin API router.
+pub fn create_admin_router(state: AdminState) -> Router {
+    Router::new()
+        .route("/peers", get(list_peers))
+        .route("/peers/dial", post(dial_peer))
+        .route("/listen-addrs", get(listen_addrs))
+        .route("/contents", get(list_contents))
+        .route("/contents/:id", get(content_membership))
+        .route("/sync", get(sync_status))
+        .route("/sync/contents", get(list_content_sync_status))
+        .route("/sync/contents/:id", get(content_sync_status))
+        .route("/evictions", get(list_evictions))
+        .route("/retry-queues", get(retry_queues))
+        .route("/log-level", get(get_log_level).put(set_log_level))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_admin_token,
+        ))
+        .layer(middleware::from_fn(local_host_only))
+        .layer(middleware::from_fn(loopback_only))
+        .with_state(state)
+}
+
+// ============================================================================
+// Request/Response types
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct PeerEntry {
+    pub peer_id: String,
+    pub addrs: Vec<String>,
+    /// Gossipsub topics whose mesh includes the peer.
+    pub mesh_topics: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DialPeerRequest {
+    /// Multiaddr to dial, optionally ending in `/p2p/<peer id>`.
+    pub addr: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContentMembershipResponse {
+    pub content_id: String,
+    pub member_nodes: Vec<String>,
+    /// Whether this node is one of the members.
+    pub local_member: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContentSyncStatusEntry {
+    #[serde(flatten)]
+    pub status: ContentSyncStatus,
+    /// Whether the local replica is known to match its peers.
+    pub in_sync: bool,
+    /// Seconds since a sync last reached a peer.
+    pub lag_secs: Option<u64>,
+}
+
+impl From<ContentSyncStatus> for ContentSyncStatusEntry {
+    fn from(status: ContentSyncStatus) -> Self {
+        Self {
+            in_sync: status.is_in_sync(),
+            lag_secs: status.lag_secs(current_timestamp()),
+            status,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingEventEntry {
+    pub id: String,
+    pub event_type: String,
+    pub content_id: Option<String>,
+    pub remaining_targets: Vec<String>,
+    pub created_at: u64,
+    pub retry_count: u32,
+    pub last_attempt_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RetryQueuesResponse {
+    /// Events in the reliable publisher's outbox awaiting acknowledgement.
+    pub outbox: Vec<PendingEventEntry>,
+    /// Gossipsub publish queue (events held while the network is down).
+    pub publish_queue: PublishQueueMetrics,
+    /// Swarm command queue.
+    pub command_queue: CommandQueueMetrics,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogLevel {
+    /// Filter directives in `EnvFilter` syntax.
+    pub filter: String,
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (
+        status,
+        Json(ErrorResponse {
+            error: message.into(),
+        }),
+    )
+        .into_response()
+}
+
+// ============================================================================
+// Middleware
+// ============================================================================
+
+/// Refuse requests that do not come from a loopback address.
+async fn loopback_only(
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !remote.ip().is_loopback() {
+        tracing::warn!("Refused admin API request from {}", remote);
+        return error(StatusCode::FORBIDDEN, "Admin API is only available locally");
+    }
+    next.run(request).await
+}
+
+/// Refuse requests whose `Host` or `Origin` is not a loopback host, i.e.
+/// requests a browser sends on behalf of another site.
+async fn local_host_only(request: Request, next: Next) -> Response {
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .or_else(|| {
+            request
+                .uri()
+                .authority()
+                .map(|authority| authority.as_str())
+        });
+    if !host.is_some_and(is_loopback_host) {
+        tracing::warn!("Refused admin API request for host {:?}", host);
+        return error(StatusCode::FORBIDDEN, "Admin API is only available locally");
+    }
+    if let Some(origin) = request.headers().get(header::ORIGIN) {
+        let local = origin
+            .to_str()
+            .ok()
+            .and_then(|origin| origin.split_once("://"))
+            .is_some_and(|(_, authority)| is_loopback_host(authority));
+        if !local {
+            tracing::warn!("Refused admin API request from origin {:?}", origin);
+            return error(
+                StatusCode::FORBIDDEN,
+                "Cross-origin requests are not allowed",
+            );
+        }
+    }
+    next.run(request).await
+}
+
+/// Require the operator token in the `X-Admin-Token` header.
+async fn require_admin_token(
+    State(state): State<AdminState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Err(e) = state
+        .service
+        .verify_admin_token(extract_admin_token(request.headers()))
+    {
+        return e.into_response();
+    }
+    next.run(request).await
+}
+
+/// Whether `authority` (`host[:port]`) names the local host.
+fn is_loopback_host(authority: &str) -> bool {
+    let host = match authority.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    };
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List connected peers.
+async fn list_peers(State(state): State<AdminState>) -> impl IntoResponse {
+    let mesh = state.network.mesh_peers().await;
+    let peers: Vec<PeerEntry> = state
+        .network
+        .connected_peers()
+        .await
+        .into_iter()
+        .map(|(peer_id, addrs)| {
+            let mesh_topics = mesh
+                .iter()
+                .filter(|(_, peers)| peers.contains(&peer_id))
+                .map(|(topic, _)| topic.clone())
+                .collect();
+            PeerEntry {
+                peer_id,
+                addrs,
+                mesh_topics,
+            }
+        })
+        .collect();
+    Json(peers)
+}
+
+/// Dial a peer.
+async fn dial_peer(
+    State(state): State<AdminState>,
+    Json(request): Json<DialPeerRequest>,
+) -> impl IntoResponse {
+    let addr: libp2p::Multiaddr = match request.addr.parse() {
+        Ok(addr) => addr,
+        Err(e) => return error(StatusCode::BAD_REQUEST, format!("Invalid multiaddr: {}", e)),
+    };
+    match state.network.dial(addr).await {
+        Ok(()) => StatusCode::ACCEPTED.into_response(),
+        Err(e) => error(StatusCode::BAD_GATEWAY, format!("Dial failed: {}", e)),
+    }
+}
+
+/// List the addresses the node listens on.
+async fn listen_addrs(State(state): State<AdminState>) -> impl IntoResponse {
+    let addrs: Vec<String> = state
+        .network
+        .listen_addrs_raw()
+        .await
+        .into_iter()
+        .map(|addr| addr.to_string())
+        .collect();
+    Json(addrs)
+}
+
+/// List the content networks known to this node.
+async fn list_contents(State(state): State<AdminState>) -> impl IntoResponse {
+    match state.service.list_content_networks().await {
+        Ok(content_ids) => Json(content_ids).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Show the members of a content network.
+async fn content_membership(
+    State(state): State<AdminState>,
+    Path(content_id): Path<String>,
+) -> impl IntoResponse {
+    match state.service.get_content_network(&content_id).await {
+        Ok(network) => Json(ContentMembershipResponse {
+            content_id: network.content_id().as_str().to_string(),
+            local_member: network.has_member_str(state.service.local_node_id()),
+            member_nodes: network.member_nodes_as_strings(),
+        })
+        .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Show the anti-entropy sync state of each member content.
+async fn sync_status(State(state): State<AdminState>) -> impl IntoResponse {
+    let scheduled: Vec<ScheduledSync> = state.sync_schedule.read().await.scheduled(Instant::now());
+    Json(scheduled)
+}
+
+/// Show the replication status of each synced content.
+async fn list_content_sync_status(State(state): State<AdminState>) -> impl IntoResponse {
+    match state.service.list_sync_status().await {
+        Ok(statuses) => Json(
+            statuses
+                .into_iter()
+                .map(ContentSyncStatusEntry::from)
+                .collect::<Vec<_>>(),
+        )
+        .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Show the replication status of a content.
+async fn content_sync_status(
+    State(state): State<AdminState>,
+    Path(content_id): Path<String>,
+) -> impl IntoResponse {
+    match state.service.get_sync_status(&content_id).await {
+        Ok(status) => Json(ContentSyncStatusEntry::from(status)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// List evicted content and when its local data is (or was) purged.
+async fn list_evictions(State(state): State<AdminState>) -> impl IntoResponse {
+    match state.service.list_evictions().await {
+        Ok(evictions) => Json(evictions).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Show the pending retry queues.
+async fn retry_queues(State(state): State<AdminState>) -> impl IntoResponse {
+    let pending = match state.reliable_publisher.pending_events() {
+        Ok(pending) => pending,
+        Err(e) => {
+            tracing::error!("Failed to read outbox: {}", e);
+            return error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to read outbox");
+        }
+    };
+    let outbox = pending
+        .into_iter()
+        .map(|pending| PendingEventEntry {
+            event_type: pending.event.event_type().to_string(),
+            content_id: pending.event.content_id().map(str::to_string),
+            id: pending.id,
+            remaining_targets: pending.remaining_targets,
+            created_at: pending.created_at,
+            retry_count: pending.retry_count,
+            last_attempt_at: pending.last_attempt_at,
+        })
+        .collect();
+    Json(RetryQueuesResponse {
+        outbox,
+        publish_queue: state.service.event_publisher().queue_metrics(),
+        command_queue: state.network.command_queue_metrics(),
+    })
+    .into_response()
+}
+
+/// Show the current log filter.
+async fn get_log_level(State(state): State<AdminState>) -> impl IntoResponse {
+    match &state.log_filter {
+        Some(log_filter) => Json(LogLevel {
+            filter: log_filter.current(),
+        })
+        .into_response(),
+        None => error(
+            StatusCode::NOT_IMPLEMENTED,
+            "Log level control is not available",
+        ),
+    }
+}
+
+/// Replace the log filter.
+async fn set_log_level(
+    State(state): State<AdminState>,
+    Json(request): Json<LogLevel>,
+) -> impl IntoResponse {
+    let Some(log_filter) = &state.log_filter else {
+        return error(
+            StatusCode::NOT_IMPLEMENTED,
+            "Log level control is not available",
+        );
+    };
+    match log_filter.set(&request.filter) {
+        Ok(()) => {
+            tracing::info!("Log filter changed to {}", request.filter);
+            Json(LogLevel {
+                filter: log_filter.current(),
+            })
+            .into_response()
+        }
+        Err(e) => error(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid log filter: {}", e),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_reload_handle_log_filter_control() {
+        let (layer, handle) = reload::Layer::new(EnvFilter::new("info"));
+        let _subscriber = tracing_subscriber::registry().with(layer);
+
+        assert_eq!(handle.current(), "info");
+        handle.set("warn,monas_state_node=debug").unwrap();
+        assert!(handle.current().contains("monas_state_node=debug"));
+
+        assert!(handle.set("monas_state_node=loud").is_err());
+        assert!(handle.current().contains("monas_state_node=debug"));
+    }
+
+    #[test]
+    fn test_content_sync_status_entry_is_flattened() {
+        let entry = ContentSyncStatusEntry::from(ContentSyncStatus::new("cid-1".to_string()));
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["content_id"], "cid-1");
+        assert_eq!(json["in_sync"], false);
+        assert!(json["lag_secs"].is_null());
+    }
+
+    #[test]
+    fn test_is_loopback_host() {
+        assert!(is_loopback_host("localhost"));
+        assert!(is_loopback_host("LOCALHOST:8081"));
+        assert!(is_loopback_host("127.0.0.1:8081"));
+        assert!(is_loopback_host("[::1]:8081"));
+        assert!(!is_loopback_host("attacker.example"));
+        // A rebound name resolving to 127.0.0.1 is still a foreign host.
+        assert!(!is_loopback_host("localhost.attacker.example:8081"));
+        assert!(!is_loopback_host("192.168.1.10:8081"));
+        assert!(!is_loopback_host(""));
+    }
+
+    #[test]
+    fn test_log_level_deserialization() {
+        let request: LogLevel = serde_json::from_str(r#"{"filter":"debug"}"#).unwrap();
+        assert_eq!(request.filter, "debug");
+    }
+}
@@ -2,6 +2,8 @@
 
 use crate::application_service::state_node_service::StateNodeService;
 use crate::domain::errors::StateNodeError;
+use crate::domain::mirror::MirrorRole;
+use crate::domain::peer_access::PeerAccess;
 use crate::infrastructure::crdt_repository::CrslCrdtRepository;
 use crate::infrastructure::gossipsub_publisher::GossipsubEventPublisher;
 use crate::infrastructure::network::Libp2pNetwork;
@@ -10,7 +12,7 @@ use crate::infrastructure::persistence::{
 };
 use crate::port::auth_token::AuthToken;
 use crate::port::content_repository::ContentRepository;
-use crate::port::peer_network::PeerNetwork;
+use crate::port::peer_network::{PeerNetwork, ReadCapability};
 use axum::{
     extract::{DefaultBodyLimit, Path, Query, State},
     http::{HeaderMap, StatusCode},
@@ -72,7 +74,10 @@ pub fn create_router(state: AppState) -> Router {
         .route("/node/info", get(node_info))
         .route("/node/register", post(register_node))
         .route("/nodes", get(list_nodes))
+        .route("/nodes/:id", get(get_node))
+        .route("/mirror", get(mirror_status))
         .route("/contents", get(list_contents))
+        .route("/content/:id/network", get(get_content_network))
         // --- Authenticated endpoints ---
         .route("/content", post(create_content))
         .route("/content/:id", put(update_content).delete(delete_content))
@@ -81,10 +86,18 @@ pub fn create_router(state: AppState) -> Router {
         .route("/content/:id/data", get(get_content_data))
         .route("/content/:id/history", get(get_content_history))
         .route("/content/:id/version/:version", get(get_content_version))
+        .route("/content/:id/history/:version", get(get_content_at_version))
         .route(
             "/content/:id/access/invalidate",
             post(invalidate_tokens_handler),
         )
+        // --- Admin endpoints (operator token required) ---
+        .route("/admin/denylist", get(list_denylist).post(add_to_denylist))
+        .route("/admin/peers", get(list_peer_access))
+        .route(
+            "/admin/peers/:peer_id",
+            put(set_peer_access).delete(clear_peer_access),
+        )
         // Per-IP rate limit (inner layer, applied first)
         .layer(GovernorLayer {
             config: Arc::new(per_ip_config),
@@ -119,6 +132,15 @@ pub struct NodeInfoResponse {
     pub listen_addrs: Vec<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct MirrorStatusResponse {
+    pub enabled: bool,
+    pub role: Option<MirrorRole>,
+    pub peer_node_id: Option<String>,
+    pub paired: bool,
+    pub paired_at: Option<u64>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RegisterNodeRequest {
     pub total_capacity: u64,
@@ -131,9 +153,28 @@ pub struct RegisterNodeResponse {
     pub available_capacity: u64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct NodeResponse {
+    pub node_id: String,
+    pub total_capacity: u64,
+    pub available_capacity: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContentNetworkResponse {
+    pub content_id: String,
+    pub member_nodes: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateContentRequest {
     pub data: String, // Base64 encoded content
+    /// Selective sync labels (e.g. `sync:high`).
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Namespace used by selective sync rules.
+    #[serde(default)]
+    pub namespace: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -187,9 +228,11 @@ impl IntoResponse for StateNodeError {
             // Client errors: safe to expose the message
             StateNodeError::ContentNotFound(_) => self.to_string(),
             StateNodeError::ContentAlreadyExists(_) => self.to_string(),
+            StateNodeError::ContentDenied(_) => self.to_string(),
             StateNodeError::NodeNotFound(_) => self.to_string(),
             StateNodeError::InsufficientCapacity { .. } => self.to_string(),
             StateNodeError::NoAvailableMembers => self.to_string(),
+            StateNodeError::PlacementExhausted { .. } => self.to_string(),
             StateNodeError::NotAMember { .. } => self.to_string(),
             StateNodeError::PermissionDenied(_) => "Permission denied".to_string(),
             StateNodeError::InvalidUcanToken(_) => "Invalid authentication token".to_string(),
@@ -215,6 +258,34 @@ impl IntoResponse for StateNodeError {
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct DenyContentRequest {
+    /// Genesis CID of the content to deny.
+    pub content_id: String,
+    /// Reason for the takedown (recorded in the tombstone).
+    #[serde(default)]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DenyContentResponse {
+    pub content_id: String,
+    pub reason: String,
+    pub created_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetPeerAccessRequest {
+    /// `"blocked"` or `"allowed"`.
+    pub access: PeerAccess,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PeerAccessEntry {
+    pub peer_id: String,
+    pub access: PeerAccess,
+}
+
 #[derive(Debug, Serialize)]
 pub struct InvalidateTokensResponse {
     pub content_id: String,
@@ -277,6 +348,29 @@ fn extract_request_signature(headers: &HeaderMap) -> Option<Vec<u8>> {
         .ok()
 }
 
+/// Extract the operator token from the X-Admin-Token header.
+pub(crate) fn extract_admin_token(headers: &HeaderMap) -> Option<&str> {
+    headers.get("x-admin-token")?.to_str().ok()
+}
+
+/// Build the read capability this node presents when it pulls content from a
+/// member on the caller's behalf.
+///
+/// The caller signs [`ReadCapability::signing_message`] for this node's peer
+/// ID and sends it base64-encoded in the X-Read-Capability header, together
+/// with its key-ID token and X-Request-Timestamp. Returns None if any of them
+/// is missing.
+fn extract_read_capability(headers: &HeaderMap) -> Option<ReadCapability> {
+    let signature = base64::engine::general_purpose::STANDARD
+        .decode(headers.get("x-read-capability")?.to_str().ok()?)
+        .ok()?;
+    Some(ReadCapability {
+        auth_token: extract_auth_token(headers)?.as_str().to_string(),
+        request_signature: signature,
+        timestamp: extract_request_timestamp(headers)?,
+    })
+}
+
 /// Extract request timestamp from X-Request-Timestamp header.
 ///
 /// Returns None if the header is missing or cannot be parsed.
@@ -312,9 +406,13 @@ async fn liveness_check() -> impl IntoResponse {
 ///
 /// Returns 200 if the node is ready to serve traffic (DB responsive + network connected).
 /// Returns 503 if the node is not ready.
+/// Also reports the depth of the Gossipsub publish queue and of the swarm
+/// command queue.
 async fn readiness_check(State(state): State<AppState>) -> impl IntoResponse {
     let peer_count = state.peer_network().connected_peer_count().await;
     let db_ok = state.crdt_repo().health_check().await.is_ok();
+    let publish_queue = state.event_publisher().queue_metrics();
+    let command_queue = state.peer_network().command_queue_metrics();
 
     if db_ok {
         (
@@ -322,7 +420,9 @@ async fn readiness_check(State(state): State<AppState>) -> impl IntoResponse {
             Json(serde_json::json!({
                 "status": "ready",
                 "peers": peer_count,
-                "database": "ok"
+                "database": "ok",
+                "publish_queue": publish_queue,
+                "command_queue": command_queue
             })),
         )
     } else {
@@ -331,7 +431,9 @@ async fn readiness_check(State(state): State<AppState>) -> impl IntoResponse {
             Json(serde_json::json!({
                 "status": "not_ready",
                 "peers": peer_count,
-                "database": "error"
+                "database": "error",
+                "publish_queue": publish_queue,
+                "command_queue": command_queue
             })),
         )
     }
@@ -364,6 +466,19 @@ async fn node_info(State(state): State<AppState>) -> impl IntoResponse {
     }
 }
 
+/// Mirror mode status (public, no auth required).
+async fn mirror_status(State(state): State<AppState>) -> impl IntoResponse {
+    let config = state.mirror_config();
+    let pairing = state.mirror_pairing().await;
+    Json(MirrorStatusResponse {
+        enabled: config.is_some(),
+        role: config.map(|c| c.role),
+        peer_node_id: config.map(|c| c.peer_node_id.clone()),
+        paired: pairing.is_some(),
+        paired_at: pairing.map(|p| p.paired_at),
+    })
+}
+
 /// Register the local node (public, no auth required).
 ///
 /// This endpoint is called by the node operator to initialize the local node.
@@ -393,6 +508,25 @@ async fn list_nodes(State(state): State<AppState>) -> impl IntoResponse {
     }
 }
 
+/// Get a registered node (public, no auth required).
+///
+/// Returns capacity metadata only. Used for peer coordination.
+async fn get_node(State(state): State<AppState>, Path(node_id): Path<String>) -> impl IntoResponse {
+    match state.get_node(&node_id).await {
+        Ok(Some(node)) => Json(NodeResponse {
+            node_id: node.node_id,
+            total_capacity: node.total_capacity,
+            available_capacity: node.available_capacity,
+        })
+        .into_response(),
+        Ok(None) => match crate::domain::NodeId::from_string(node_id) {
+            Ok(id) => StateNodeError::NodeNotFound(id).into_response(),
+            Err(e) => StateNodeError::from(e).into_response(),
+        },
+        Err(e) => e.into_response(),
+    }
+}
+
 /// Create new content.
 async fn create_content(
     State(state): State<AppState>,
@@ -420,8 +554,10 @@ async fn create_content(
     let timestamp = extract_request_timestamp(&headers);
 
     match state
-        .create_content(
+        .create_content_with_attributes(
             &data,
+            req.labels,
+            req.namespace,
             token.as_ref(),
             request_signature.as_deref(),
             timestamp,
@@ -590,6 +726,24 @@ async fn list_contents(State(state): State<AppState>) -> impl IntoResponse {
     }
 }
 
+/// Get the member nodes of a content network (public, no auth required).
+///
+/// Returns node IDs only — no content data. Used to locate the nodes that
+/// hold a piece of content.
+async fn get_content_network(
+    State(state): State<AppState>,
+    Path(content_id): Path<String>,
+) -> impl IntoResponse {
+    match state.get_content_network(&content_id).await {
+        Ok(network) => Json(ContentNetworkResponse {
+            content_id: network.content_id().as_str().to_string(),
+            member_nodes: network.member_nodes_as_strings(),
+        })
+        .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
 /// Verify that the caller has read access to the given content.
 ///
 /// Extracts a Bearer token from the Authorization header, then checks:
@@ -688,7 +842,10 @@ async fn get_content_data(
     // the creator nor a member), pull it from a member first so the read below
     // and the access-policy check both see the real data. Best-effort: on
     // failure we fall through to the normal local read (which 404s as before).
-    let _ = state.ensure_content_local(&content_id).await;
+    let capability = extract_read_capability(&headers);
+    let _ = state
+        .ensure_content_local(&content_id, capability.as_ref())
+        .await;
 
     if let Err(response) = verify_read_access(&state, &headers, &content_id).await {
         return response;
@@ -697,12 +854,21 @@ async fn get_content_data(
     let crdt_repo = state.crdt_repo();
 
     // Get data based on version parameter
-    let data_result = if let Some(version) = &query.version {
+    let mut data_result = if let Some(version) = &query.version {
         crdt_repo.get_version(version).await
     } else {
         crdt_repo.get_latest(&content_id).await
     };
 
+    // The operations could not be pulled: fetch the latest data itself from
+    // the serving nodes, which verify the caller's read capability.
+    if query.version.is_none() && capability.is_some() && matches!(data_result, Ok(None)) {
+        match state.fetch_content(&content_id, capability.as_ref()).await {
+            Ok(fetched) => data_result = Ok(Some(fetched.data)),
+            Err(e) => tracing::debug!("Failed to fetch content {}: {}", content_id, e),
+        }
+    }
+
     match data_result {
         Ok(Some(data)) => {
             let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
@@ -742,7 +908,9 @@ async fn get_content_history(
     headers: HeaderMap,
 ) -> impl IntoResponse {
     // Bug #93: pull content from a member if we hold none locally (best-effort).
-    let _ = state.ensure_content_local(&content_id).await;
+    let _ = state
+        .ensure_content_local(&content_id, extract_read_capability(&headers).as_ref())
+        .await;
 
     if let Err(response) = verify_read_access(&state, &headers, &content_id).await {
         return response;
@@ -778,7 +946,9 @@ async fn get_content_version(
     headers: HeaderMap,
 ) -> impl IntoResponse {
     // Bug #93: pull content from a member if we hold none locally (best-effort).
-    let _ = state.ensure_content_local(&content_id).await;
+    let _ = state
+        .ensure_content_local(&content_id, extract_read_capability(&headers).as_ref())
+        .await;
 
     if let Err(response) = verify_read_access(&state, &headers, &content_id).await {
         return response;
@@ -816,6 +986,62 @@ async fn get_content_version(
     }
 }
 
+/// Get content data as it was at a historical version number.
+///
+/// Versions are numbered from 1 in log order; the state is reconstructed by
+/// replaying CRDT operations, so this also works for content replicated from
+/// other nodes. Requires authentication.
+async fn get_content_at_version(
+    State(state): State<AppState>,
+    Path((content_id, version)): Path<(String, u64)>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    // Bug #93: pull content from a member if we hold none locally (best-effort).
+    let _ = state
+        .ensure_content_local(&content_id, extract_read_capability(&headers).as_ref())
+        .await;
+
+    if let Err(response) = verify_read_access(&state, &headers, &content_id).await {
+        return response;
+    }
+
+    let crdt_repo = state.crdt_repo();
+
+    match crdt_repo.get_at_version(&content_id, version).await {
+        Ok(Some(data)) => {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+            Json(ContentDataResponse {
+                content_id,
+                data: encoded,
+                version: Some(version.to_string()),
+            })
+            .into_response()
+        }
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Version {} not found for content {}", version, content_id),
+            }),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!(
+                "Failed to get content {} at version {}: {}",
+                content_id,
+                version,
+                e
+            );
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Internal server error".to_string(),
+                }),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// Invalidate all AuthTokens for a content.
 ///
 /// Only the content owner can call this endpoint.
@@ -855,6 +1081,103 @@ async fn invalidate_tokens_handler(
     }
 }
 
+/// Deny (take down) content on this node (admin only).
+///
+/// Records a tombstone so the content is never stored, served, or re-synced
+/// by this node, even if other nodes announce it again.
+async fn add_to_denylist(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<DenyContentRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = state.verify_admin_token(extract_admin_token(&headers)) {
+        return e.into_response();
+    }
+
+    match state.deny_content(&req.content_id, &req.reason).await {
+        Ok(tombstone) => (
+            StatusCode::CREATED,
+            Json(DenyContentResponse {
+                content_id: tombstone.genesis_cid().to_string(),
+                reason: tombstone.reason().to_string(),
+                created_at: tombstone.created_at(),
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// List denied content IDs (admin only).
+async fn list_denylist(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Err(e) = state.verify_admin_token(extract_admin_token(&headers)) {
+        return e.into_response();
+    }
+
+    match state.list_denied_content().await {
+        Ok(content_ids) => Json::<Vec<String>>(content_ids).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Block or allow a peer (admin only).
+///
+/// Takes effect immediately (a blocked peer is disconnected) and persists
+/// across restarts.
+async fn set_peer_access(
+    State(state): State<AppState>,
+    Path(peer_id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<SetPeerAccessRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = state.verify_admin_token(extract_admin_token(&headers)) {
+        return e.into_response();
+    }
+
+    match state.set_peer_access(&peer_id, Some(req.access)).await {
+        Ok(()) => Json(PeerAccessEntry {
+            peer_id,
+            access: req.access,
+        })
+        .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Remove a peer's allow/block entry (admin only).
+async fn clear_peer_access(
+    State(state): State<AppState>,
+    Path(peer_id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = state.verify_admin_token(extract_admin_token(&headers)) {
+        return e.into_response();
+    }
+
+    match state.set_peer_access(&peer_id, None).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// List the peer access list (admin only).
+async fn list_peer_access(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Err(e) = state.verify_admin_token(extract_admin_token(&headers)) {
+        return e.into_response();
+    }
+
+    match state.list_peer_access().await {
+        Ok(entries) => Json(
+            entries
+                .into_iter()
+                .map(|(peer_id, access)| PeerAccessEntry { peer_id, access })
+                .collect::<Vec<_>>(),
+        )
+        .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -893,6 +1216,19 @@ mod tests {
         assert_eq!(decoded, b"Hello World");
     }
 
+    #[test]
+    fn test_create_content_request_with_sync_attributes() {
+        let json = r#"{"data": "SGVsbG8=", "labels": ["sync:high"], "namespace": "photos"}"#;
+        let request: CreateContentRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.labels, vec!["sync:high".to_string()]);
+        assert_eq!(request.namespace.as_deref(), Some("photos"));
+
+        let request: CreateContentRequest =
+            serde_json::from_str(r#"{"data": "SGVsbG8="}"#).unwrap();
+        assert!(request.labels.is_empty());
+        assert!(request.namespace.is_none());
+    }
+
     #[test]
     fn test_create_content_response_serialization() {
         let response = CreateContentResponse {
@@ -994,6 +1330,29 @@ mod tests {
         assert_eq!(query.version, None);
     }
 
+    #[test]
+    fn test_deny_content_request_deserialization() {
+        let json = r#"{"content_id": "cid-1", "reason": "abuse"}"#;
+        let request: DenyContentRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.content_id, "cid-1");
+        assert_eq!(request.reason, "abuse");
+
+        // Reason is optional
+        let json = r#"{"content_id": "cid-1"}"#;
+        let request: DenyContentRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.reason, "");
+    }
+
+    #[test]
+    fn test_set_peer_access_request_deserialization() {
+        let json = r#"{"access": "blocked"}"#;
+        let request: SetPeerAccessRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.access, PeerAccess::Blocked);
+
+        let json = r#"{"access": "banned"}"#;
+        assert!(serde_json::from_str::<SetPeerAccessRequest>(json).is_err());
+    }
+
     #[test]
     fn test_invalid_base64_data() {
         let invalid = "not-valid-base64!!!";
@@ -1,3 +1,5 @@
+pub mod admin_api;
 pub mod http_api;
 
+pub use admin_api::{create_admin_router, AdminContext, AdminState, LogFilterControl};
 pub use http_api::{create_router, AppState};
@@ -3,13 +3,17 @@
 //! This module provides mock implementations of the main traits
 //! to enable unit testing without real infrastructure dependencies.
 
+use crate::application_service::state_node_service::ApplyOutcome;
 use crate::domain::access_policy::AccessPolicy;
 use crate::domain::content_network::ContentNetwork;
 use crate::domain::events::Event;
+use crate::domain::peer_access::PeerAccess;
 use crate::domain::state_node::NodeSnapshot;
+use crate::domain::sync_rules::SyncRules;
+use crate::infrastructure::event_log::{read_event_log, RecordedEvent};
 use crate::port::content_repository::{CommitResult, ContentRepository, SerializedOperation};
 use crate::port::event_publisher::EventPublisher;
-use crate::port::peer_network::PeerNetwork;
+use crate::port::peer_network::{ContentChunk, DhtRecord, PeerNetwork, ReadCapability};
 use crate::port::persistence::{PersistentContentRepository, PersistentNodeRegistry};
 use anyhow::Result;
 use async_trait::async_trait;
@@ -29,11 +33,44 @@ pub type PublishedEvents = Arc<Mutex<Vec<(String, Vec<u8>)>>>;
 pub struct MockPeerNetwork {
     pub published_events: PublishedEvents,
     pub closest_peers: Arc<Mutex<Vec<String>>>,
+    /// When set, `find_closest_peers` returns only the first `k` closest peers
+    /// instead of all of them, like a real DHT lookup.
+    pub limit_closest_peers: bool,
+    /// The `k` of every `find_closest_peers` call, in order.
+    pub closest_peers_requests: Arc<Mutex<Vec<usize>>>,
     pub capacities: Arc<Mutex<HashMap<String, u64>>>,
+    pub sync_rules: Arc<Mutex<HashMap<String, SyncRules>>>,
+    /// Owning account of each attested peer.
+    pub owners: Arc<Mutex<HashMap<String, String>>>,
     pub public_keys: Arc<Mutex<HashMap<String, Vec<u8>>>>,
     pub providers: Arc<Mutex<Vec<String>>>,
+    /// Keys passed to `publish_provider`, in order.
+    pub provided_keys: Arc<Mutex<Vec<Vec<u8>>>>,
+    /// Keys passed to `remove_provider`, in order.
+    pub removed_provider_keys: Arc<Mutex<Vec<Vec<u8>>>>,
+    /// DHT records by key, served by `get_records`.
+    pub records: Arc<Mutex<HashMap<Vec<u8>, Vec<DhtRecord>>>>,
     pub fetched_operations: Arc<Mutex<Vec<SerializedOperation>>>,
+    /// Peers and capabilities passed to `fetch_operations_with_capability`
+    /// and `fetch_content_with_capability`, in order.
+    pub capability_fetches: Arc<Mutex<Vec<(String, ReadCapability)>>>,
+    /// Latest version reported by `fetch_operations_with_version`.
+    pub remote_version: Arc<Mutex<Option<String>>>,
+    /// Data served by `fetch_content`, per peer. When empty, every peer
+    /// serves empty content; otherwise unlisted peers fail.
+    pub peer_contents: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    /// Artificial latency of `fetch_content`, per peer.
+    pub fetch_delays: Arc<Mutex<HashMap<String, std::time::Duration>>>,
+    /// Peers and offsets passed to `fetch_content_range`, in order.
+    pub range_fetches: Arc<Mutex<Vec<(String, u64)>>>,
+    /// Round-trip times reported by `peer_latencies`.
+    pub latencies: Arc<Mutex<HashMap<String, std::time::Duration>>>,
     pub local_peer_id: String,
+    pub connected_peers: Arc<Mutex<Vec<String>>>,
+    /// Peers passed to `push_operations_with_bootstrap`, in order.
+    pub bootstrap_pushes: Arc<Mutex<Vec<String>>>,
+    /// Access decisions passed to `set_peer_access`, in order.
+    pub peer_access_updates: Arc<Mutex<Vec<(String, Option<PeerAccess>)>>>,
     pub relay_update_result: Arc<Mutex<Option<bool>>>,
     pub relay_delete_result: Arc<Mutex<Option<bool>>>,
     pub relay_invalidate_tokens_result: Arc<Mutex<Option<bool>>>,
@@ -54,11 +91,27 @@ impl MockPeerNetwork {
         Self {
             published_events: Arc::new(Mutex::new(Vec::new())),
             closest_peers: Arc::new(Mutex::new(Vec::new())),
+            limit_closest_peers: false,
+            closest_peers_requests: Arc::new(Mutex::new(Vec::new())),
             capacities: Arc::new(Mutex::new(HashMap::new())),
+            sync_rules: Arc::new(Mutex::new(HashMap::new())),
+            owners: Arc::new(Mutex::new(HashMap::new())),
             public_keys: Arc::new(Mutex::new(HashMap::new())),
             providers: Arc::new(Mutex::new(Vec::new())),
+            provided_keys: Arc::new(Mutex::new(Vec::new())),
+            removed_provider_keys: Arc::new(Mutex::new(Vec::new())),
+            records: Arc::new(Mutex::new(HashMap::new())),
             fetched_operations: Arc::new(Mutex::new(Vec::new())),
+            capability_fetches: Arc::new(Mutex::new(Vec::new())),
+            remote_version: Arc::new(Mutex::new(None)),
+            peer_contents: Arc::new(Mutex::new(HashMap::new())),
+            fetch_delays: Arc::new(Mutex::new(HashMap::new())),
+            range_fetches: Arc::new(Mutex::new(Vec::new())),
+            latencies: Arc::new(Mutex::new(HashMap::new())),
             local_peer_id: "mock-peer-id".to_string(),
+            connected_peers: Arc::new(Mutex::new(Vec::new())),
+            bootstrap_pushes: Arc::new(Mutex::new(Vec::new())),
+            peer_access_updates: Arc::new(Mutex::new(Vec::new())),
             relay_update_result: Arc::new(Mutex::new(Some(true))),
             relay_delete_result: Arc::new(Mutex::new(Some(true))),
             relay_invalidate_tokens_result: Arc::new(Mutex::new(Some(true))),
@@ -76,6 +129,13 @@ impl MockPeerNetwork {
         self
     }
 
+    pub fn with_connected_peers(self, peers: Vec<String>) -> Self {
+        Self {
+            connected_peers: Arc::new(Mutex::new(peers)),
+            ..self
+        }
+    }
+
     pub fn with_closest_peers(self, peers: Vec<String>) -> Self {
         Self {
             closest_peers: Arc::new(Mutex::new(peers)),
@@ -83,6 +143,16 @@ impl MockPeerNetwork {
         }
     }
 
+    /// Like [`Self::with_closest_peers`], but lookups return only the first `k`
+    /// peers (ordered closest first).
+    pub fn with_limited_closest_peers(self, peers: Vec<String>) -> Self {
+        Self {
+            closest_peers: Arc::new(Mutex::new(peers)),
+            limit_closest_peers: true,
+            ..self
+        }
+    }
+
     pub fn with_capacities(self, caps: HashMap<String, u64>) -> Self {
         Self {
             capacities: Arc::new(Mutex::new(caps)),
@@ -90,6 +160,20 @@ impl MockPeerNetwork {
         }
     }
 
+    pub fn with_sync_rules(self, rules: HashMap<String, SyncRules>) -> Self {
+        Self {
+            sync_rules: Arc::new(Mutex::new(rules)),
+            ..self
+        }
+    }
+
+    pub fn with_owners(self, owners: HashMap<String, String>) -> Self {
+        Self {
+            owners: Arc::new(Mutex::new(owners)),
+            ..self
+        }
+    }
+
     pub fn with_public_keys(self, keys: HashMap<String, Vec<u8>>) -> Self {
         Self {
             public_keys: Arc::new(Mutex::new(keys)),
@@ -104,18 +188,59 @@ impl MockPeerNetwork {
         }
     }
 
+    pub fn with_records(self, key: Vec<u8>, records: Vec<DhtRecord>) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(HashMap::from([(key, records)]))),
+            ..self
+        }
+    }
+
     pub fn with_fetched_operations(self, ops: Vec<SerializedOperation>) -> Self {
         Self {
             fetched_operations: Arc::new(Mutex::new(ops)),
             ..self
         }
     }
+
+    pub fn with_remote_version(self, version: &str) -> Self {
+        Self {
+            remote_version: Arc::new(Mutex::new(Some(version.to_string()))),
+            ..self
+        }
+    }
+
+    pub fn with_peer_contents(self, contents: HashMap<String, Vec<u8>>) -> Self {
+        Self {
+            peer_contents: Arc::new(Mutex::new(contents)),
+            ..self
+        }
+    }
+
+    pub fn with_latencies(self, latencies: HashMap<String, std::time::Duration>) -> Self {
+        Self {
+            latencies: Arc::new(Mutex::new(latencies)),
+            ..self
+        }
+    }
+
+    pub fn with_fetch_delays(self, delays: HashMap<String, std::time::Duration>) -> Self {
+        Self {
+            fetch_delays: Arc::new(Mutex::new(delays)),
+            ..self
+        }
+    }
 }
 
 #[async_trait]
 impl PeerNetwork for MockPeerNetwork {
-    async fn find_closest_peers(&self, _key: Vec<u8>, _k: usize) -> Result<Vec<String>> {
-        Ok(self.closest_peers.lock().await.clone())
+    async fn find_closest_peers(&self, _key: Vec<u8>, k: usize) -> Result<Vec<String>> {
+        self.closest_peers_requests.lock().await.push(k);
+        let peers = self.closest_peers.lock().await;
+        if self.limit_closest_peers {
+            Ok(peers.iter().take(k).cloned().collect())
+        } else {
+            Ok(peers.clone())
+        }
     }
 
     async fn query_node_capacity_batch(
@@ -125,6 +250,20 @@ impl PeerNetwork for MockPeerNetwork {
         Ok(self.capacities.lock().await.clone())
     }
 
+    async fn query_node_sync_rules_batch(
+        &self,
+        _peer_ids: &[String],
+    ) -> Result<HashMap<String, SyncRules>> {
+        Ok(self.sync_rules.lock().await.clone())
+    }
+
+    async fn query_node_owners_batch(
+        &self,
+        _peer_ids: &[String],
+    ) -> Result<HashMap<String, String>> {
+        Ok(self.owners.lock().await.clone())
+    }
+
     async fn query_node_public_keys_batch(
         &self,
         peer_ids: &[String],
@@ -156,14 +295,86 @@ impl PeerNetwork for MockPeerNetwork {
         Ok(())
     }
 
-    async fn fetch_content(&self, _peer_id: &str, _content_id: &str) -> Result<Vec<u8>> {
-        Ok(vec![])
+    async fn fetch_content(&self, peer_id: &str, content_id: &str) -> Result<Vec<u8>> {
+        let delay = self.fetch_delays.lock().await.get(peer_id).copied();
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+        let contents = self.peer_contents.lock().await;
+        if contents.is_empty() {
+            return Ok(vec![]);
+        }
+        contents
+            .get(peer_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("{} not found on {}", content_id, peer_id))
+    }
+
+    async fn fetch_content_with_capability(
+        &self,
+        peer_id: &str,
+        content_id: &str,
+        capability: &ReadCapability,
+    ) -> Result<Vec<u8>> {
+        self.capability_fetches
+            .lock()
+            .await
+            .push((peer_id.to_string(), capability.clone()));
+        self.fetch_content(peer_id, content_id).await
+    }
+
+    async fn fetch_content_range(
+        &self,
+        peer_id: &str,
+        content_id: &str,
+        offset: u64,
+        length: u64,
+        capability: Option<&ReadCapability>,
+    ) -> Result<ContentChunk> {
+        self.range_fetches
+            .lock()
+            .await
+            .push((peer_id.to_string(), offset));
+        let data = match capability {
+            Some(capability) => {
+                self.fetch_content_with_capability(peer_id, content_id, capability)
+                    .await?
+            }
+            None => self.fetch_content(peer_id, content_id).await?,
+        };
+        Ok(ContentChunk::cut(&data, String::new(), offset, length))
+    }
+
+    async fn publish_provider(&self, key: Vec<u8>) -> Result<()> {
+        self.provided_keys.lock().await.push(key);
+        Ok(())
+    }
+
+    async fn remove_provider(&self, key: Vec<u8>) -> Result<()> {
+        self.removed_provider_keys.lock().await.push(key);
+        Ok(())
     }
 
-    async fn publish_provider(&self, _key: Vec<u8>) -> Result<()> {
+    async fn put_record(&self, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
+        let record = DhtRecord {
+            publisher: self.local_peer_id.clone(),
+            value,
+            published_at: crate::domain::events::current_timestamp(),
+        };
+        self.records.lock().await.insert(key, vec![record]);
         Ok(())
     }
 
+    async fn get_records(&self, key: Vec<u8>) -> Result<Vec<DhtRecord>> {
+        Ok(self
+            .records
+            .lock()
+            .await
+            .get(&key)
+            .cloned()
+            .unwrap_or_default())
+    }
+
     fn local_peer_id(&self) -> String {
         self.local_peer_id.clone()
     }
@@ -181,6 +392,31 @@ impl PeerNetwork for MockPeerNetwork {
         Ok(self.fetched_operations.lock().await.clone())
     }
 
+    async fn fetch_operations_with_capability(
+        &self,
+        peer_id: &str,
+        _genesis_cid: &str,
+        capability: &ReadCapability,
+    ) -> Result<Vec<SerializedOperation>> {
+        self.capability_fetches
+            .lock()
+            .await
+            .push((peer_id.to_string(), capability.clone()));
+        Ok(self.fetched_operations.lock().await.clone())
+    }
+
+    async fn fetch_operations_with_version(
+        &self,
+        _peer_id: &str,
+        _genesis_cid: &str,
+        _since_version: Option<&str>,
+    ) -> Result<(Vec<SerializedOperation>, Option<String>)> {
+        Ok((
+            self.fetched_operations.lock().await.clone(),
+            self.remote_version.lock().await.clone(),
+        ))
+    }
+
     async fn push_operations(
         &self,
         _peer_id: &str,
@@ -192,11 +428,12 @@ impl PeerNetwork for MockPeerNetwork {
 
     async fn push_operations_with_bootstrap(
         &self,
-        _peer_id: &str,
+        peer_id: &str,
         _genesis_cid: &str,
         operations: &[SerializedOperation],
         _bootstrap: crate::port::peer_network::PushBootstrap,
     ) -> Result<usize> {
+        self.bootstrap_pushes.lock().await.push(peer_id.to_string());
         Ok(operations.len())
     }
 
@@ -266,7 +503,23 @@ impl PeerNetwork for MockPeerNetwork {
     }
 
     async fn connected_peer_count(&self) -> usize {
-        0
+        self.connected_peers.lock().await.len()
+    }
+
+    async fn set_peer_access(&self, peer_id: &str, access: Option<PeerAccess>) -> Result<()> {
+        self.peer_access_updates
+            .lock()
+            .await
+            .push((peer_id.to_string(), access));
+        Ok(())
+    }
+
+    async fn connected_peer_ids(&self) -> Vec<String> {
+        self.connected_peers.lock().await.clone()
+    }
+
+    async fn peer_latencies(&self) -> HashMap<String, std::time::Duration> {
+        self.latencies.lock().await.clone()
     }
 }
 
@@ -662,6 +915,7 @@ pub fn create_test_node(
         node_id: node_id.to_string(),
         total_capacity,
         available_capacity,
+        updated_at: 0,
     }
 }
 
@@ -675,3 +929,92 @@ pub fn create_test_operation(genesis_cid: &str, author: &str) -> SerializedOpera
         node_timestamp: 12345,
     }
 }
+
+// ============================================================================
+// Event log replay fixtures
+// ============================================================================
+
+/// Directory holding the replay fixtures (`tests/fixtures/event_logs`).
+pub fn event_log_fixture_dir() -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("event_logs")
+}
+
+/// A captured sequence of network events and the state it must converge to.
+///
+/// Events are given inline (`events`) and/or as a JSON Lines recording
+/// produced by `EventLogRecorder` (`recording`, relative to the fixture
+/// file). Inline events are replayed first.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ReplayFixture {
+    #[serde(default)]
+    pub description: String,
+    /// Node ID of the replaying node.
+    pub local_node_id: String,
+    #[serde(default)]
+    pub events: Vec<RecordedEvent>,
+    #[serde(default)]
+    pub recording: Option<String>,
+    pub expect: ReplayExpectation,
+}
+
+/// Expected result of replaying a fixture.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct ReplayExpectation {
+    /// Outcome of each event, in order. Not checked when omitted.
+    #[serde(default)]
+    pub outcomes: Option<Vec<ExpectedOutcome>>,
+    /// Nodes that must be in the registry.
+    #[serde(default)]
+    pub nodes: Vec<NodeSnapshot>,
+    /// Content networks that must exist, with their exact member set.
+    #[serde(default)]
+    pub content_networks: Vec<ExpectedContentNetwork>,
+    /// Content IDs that must have no content network.
+    #[serde(default)]
+    pub absent_content_networks: Vec<String>,
+}
+
+/// Expected outcome of a single replayed event.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(untagged)]
+pub enum ExpectedOutcome {
+    Outcome(ApplyOutcome),
+    /// The event must be rejected with an error containing this text.
+    Error {
+        error: String,
+    },
+}
+
+impl ExpectedOutcome {
+    pub fn matches(&self, actual: &Result<ApplyOutcome, String>) -> bool {
+        match (self, actual) {
+            (Self::Outcome(expected), Ok(actual)) => expected == actual,
+            (Self::Error { error }, Err(actual)) => actual.contains(error.as_str()),
+            _ => false,
+        }
+    }
+}
+
+/// Expected content network state.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ExpectedContentNetwork {
+    pub content_id: String,
+    pub members: Vec<String>,
+}
+
+impl ReplayFixture {
+    /// Load a fixture file, resolving its recording if any.
+    pub fn load(path: &std::path::Path) -> Result<Self> {
+        let input = std::fs::read_to_string(path)?;
+        let mut fixture: Self = serde_json::from_str(&input)
+            .map_err(|e| anyhow::anyhow!("Invalid fixture {}: {}", path.display(), e))?;
+        if let Some(recording) = &fixture.recording {
+            let base = path.parent().unwrap_or(std::path::Path::new("."));
+            fixture.events.extend(read_event_log(base.join(recording))?);
+        }
+        Ok(fixture)
+    }
+}
@@ -0,0 +1,206 @@
+//! HTTP-level tests for the operator admin API.
+//!
+//! Requests are sent through the real admin router with
+//! `tower::ServiceExt::oneshot`; the client address is supplied with
+//! `MockConnectInfo`, so no HTTP server is started.
+
+use axum::body::Body;
+use axum::extract::connect_info::MockConnectInfo;
+use axum::http::{header, Method, Request, StatusCode};
+use axum::response::Response;
+use axum::Router;
+use monas_state_node::application_service::content_sync_service::AntiEntropySchedule;
+use monas_state_node::application_service::state_node_service::StateNodeService;
+use monas_state_node::infrastructure::crdt_repository::CrslCrdtRepository;
+use monas_state_node::infrastructure::gossipsub_publisher::GossipsubEventPublisher;
+use monas_state_node::infrastructure::inbox_persistence::SledInboxPersistence;
+use monas_state_node::infrastructure::network::{Libp2pNetwork, Libp2pNetworkConfig};
+use monas_state_node::infrastructure::outbox_persistence::SledOutboxPersistence;
+use monas_state_node::infrastructure::persistence::{
+    SledAccessControlRepository, SledContentNetworkRepository, SledNodeRegistry,
+};
+use monas_state_node::infrastructure::reliable_event_publisher::{
+    ReliableEventPublisher, ReliablePublisherConfig,
+};
+use monas_state_node::port::content_repository::ContentRepository;
+use monas_state_node::port::peer_network::PeerNetwork;
+use monas_state_node::presentation::{create_admin_router, AdminContext};
+use serde_json::json;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+use tower::ServiceExt;
+
+const ADMIN_TOKEN: &str = "test-admin-token";
+
+/// Build the admin router over temporary storage and an isolated network.
+/// Requests appear to come from `client`.
+async fn create_test_router(client: SocketAddr) -> (Router, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+
+    let node_registry = SledNodeRegistry::open(temp_dir.path().join("nodes")).unwrap();
+    let content_repo = Arc::new(RwLock::new(
+        SledContentNetworkRepository::open(temp_dir.path().join("content")).unwrap(),
+    ));
+    let access_control_repo =
+        SledAccessControlRepository::open(temp_dir.path().join("access_control")).unwrap();
+    let crdt_repo = Arc::new(CrslCrdtRepository::open(temp_dir.path().join("crdt")).unwrap());
+    let crdt_repo_dyn: Arc<dyn ContentRepository> = crdt_repo.clone();
+
+    let network_config = Libp2pNetworkConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        bootstrap_nodes: vec![],
+        enable_mdns: false,
+        gossipsub_topics: vec!["test-events".to_string()],
+        external_addrs: vec![],
+        ..Default::default()
+    };
+    let network = Arc::new(
+        Libp2pNetwork::new(network_config, crdt_repo_dyn, temp_dir.path().to_path_buf())
+            .await
+            .unwrap(),
+    );
+    let node_id = network.local_peer_id();
+
+    let reliable_publisher = Arc::new(ReliableEventPublisher::new(
+        network.clone(),
+        SledOutboxPersistence::open(temp_dir.path().join("outbox")).unwrap(),
+        SledInboxPersistence::open(temp_dir.path().join("inbox")).unwrap(),
+        ReliablePublisherConfig::default(),
+        node_id.clone(),
+    ));
+    let event_publisher = GossipsubEventPublisher::new(network.clone(), None);
+    let service = StateNodeService::new(
+        node_registry,
+        content_repo,
+        network.clone(),
+        event_publisher,
+        crdt_repo,
+        node_id,
+    )
+    .with_access_control_repo(access_control_repo)
+    .with_admin_token(ADMIN_TOKEN.to_string());
+
+    let router = create_admin_router(Arc::new(AdminContext {
+        service: Arc::new(service),
+        network,
+        reliable_publisher,
+        sync_schedule: Arc::new(RwLock::new(AntiEntropySchedule::default())),
+        log_filter: None,
+    }))
+    .layer(MockConnectInfo(client));
+    (router, temp_dir)
+}
+
+fn local_client() -> SocketAddr {
+    "127.0.0.1:40000".parse().unwrap()
+}
+
+async fn send(router: &Router, method: Method, uri: &str, headers: &[(&str, &str)]) -> Response {
+    let mut request = Request::builder().method(method).uri(uri);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let request = request
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(json!({ "filter": "debug" }).to_string()))
+        .unwrap();
+    router.clone().oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_admin_api_accepts_local_requests_with_token() {
+    let (router, _temp_dir) = create_test_router(local_client()).await;
+
+    let response = send(
+        &router,
+        Method::GET,
+        "/peers",
+        &[("host", "127.0.0.1:8081"), ("x-admin-token", ADMIN_TOKEN)],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send(
+        &router,
+        Method::GET,
+        "/contents",
+        &[
+            ("host", "localhost:8081"),
+            ("origin", "http://localhost:8081"),
+            ("x-admin-token", ADMIN_TOKEN),
+        ],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_admin_api_requires_token() {
+    let (router, _temp_dir) = create_test_router(local_client()).await;
+
+    let response = send(
+        &router,
+        Method::GET,
+        "/peers",
+        &[("host", "127.0.0.1:8081")],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = send(
+        &router,
+        Method::PUT,
+        "/log-level",
+        &[("host", "127.0.0.1:8081"), ("x-admin-token", "wrong-token")],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_admin_api_refuses_browser_requests_from_other_sites() {
+    let (router, _temp_dir) = create_test_router(local_client()).await;
+
+    // DNS rebinding: the page's own host name resolves to 127.0.0.1.
+    let response = send(
+        &router,
+        Method::POST,
+        "/peers/dial",
+        &[
+            ("host", "attacker.example:8081"),
+            ("x-admin-token", ADMIN_TOKEN),
+        ],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Cross-site request to the loopback address.
+    let response = send(
+        &router,
+        Method::PUT,
+        "/log-level",
+        &[
+            ("host", "127.0.0.1:8081"),
+            ("origin", "https://attacker.example"),
+            ("x-admin-token", ADMIN_TOKEN),
+        ],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_admin_api_refuses_remote_clients() {
+    let (router, _temp_dir) = create_test_router("192.168.1.10:40000".parse().unwrap()).await;
+
+    let response = send(
+        &router,
+        Method::GET,
+        "/peers",
+        &[("host", "127.0.0.1:8081"), ("x-admin-token", ADMIN_TOKEN)],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,70 @@
+//! Authentication and authorization fixtures that accept every request.
+
+use monas_state_node::domain::identity::Identity;
+use monas_state_node::port::auth_token::{AuthContext, AuthToken};
+use monas_state_node::port::authentication_service::AuthenticationService;
+use monas_state_node::port::authorization_service::{
+    AuthorizationRequest, AuthorizationResult, AuthorizationService,
+};
+
+/// Authenticates any non-empty token as the user named by it.
+pub struct TestAuthService;
+
+#[async_trait::async_trait]
+impl AuthenticationService for TestAuthService {
+    async fn authenticate(
+        &self,
+        token: &AuthToken,
+        _context: Option<&AuthContext>,
+    ) -> anyhow::Result<Identity> {
+        Identity::user(token.as_str().to_string()).map_err(|e| anyhow::anyhow!(e.to_string()))
+    }
+
+    async fn is_valid(&self, token: &AuthToken) -> anyhow::Result<bool> {
+        Ok(!token.is_empty())
+    }
+
+    async fn verify_request_signature(
+        &self,
+        _token: &AuthToken,
+        _signature: &[u8],
+        _message: &str,
+        _timestamp: Option<u64>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn verify_jwt_signature(&self, _token: &AuthToken) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn get_issuer(&self, token: &AuthToken) -> anyhow::Result<Option<Identity>> {
+        Ok(Some(
+            Identity::user(token.as_str().to_string())
+                .map_err(|e| anyhow::anyhow!(e.to_string()))?,
+        ))
+    }
+}
+
+/// Grants every authorization request.
+pub struct AllowAllAuthorizationService;
+
+#[async_trait::async_trait]
+impl AuthorizationService for AllowAllAuthorizationService {
+    async fn authorize(
+        &self,
+        _request: &AuthorizationRequest,
+    ) -> anyhow::Result<AuthorizationResult> {
+        Ok(AuthorizationResult::Granted)
+    }
+}
+
+/// Token of the user `test-user`.
+pub fn test_token() -> AuthToken {
+    AuthToken::new("test-user".to_string())
+}
+
+/// Request signature accepted by [`TestAuthService`].
+pub fn test_request_signature() -> Vec<u8> {
+    vec![0x01]
+}
@@ -0,0 +1,481 @@
+//! In-process cluster of state nodes on localhost.
+
+use super::auth::{
+    test_request_signature, test_token, AllowAllAuthorizationService, TestAuthService,
+};
+use monas_state_node::application_service::content_sync_service::ContentSyncService;
+use monas_state_node::application_service::node::{
+    run_replication_monitor, ReceivedEventHandler, SyncService,
+};
+use monas_state_node::application_service::state_node_service::{ServiceConfig, StateNodeService};
+use monas_state_node::domain::events::Event;
+use monas_state_node::domain::peer_access::PeerAccess;
+use monas_state_node::infrastructure::crdt_repository::CrslCrdtRepository;
+use monas_state_node::infrastructure::event_journal::SledEventJournal;
+use monas_state_node::infrastructure::gossipsub_publisher::{
+    GossipsubEventPublisher, DEFAULT_EVENT_TOPIC,
+};
+use monas_state_node::infrastructure::network::{Libp2pNetwork, Libp2pNetworkConfig};
+use monas_state_node::infrastructure::persistence::{
+    SledAccessControlRepository, SledContentNetworkRepository, SledNodeRegistry,
+};
+use monas_state_node::port::content_repository::ContentRepository;
+use monas_state_node::port::peer_network::PeerNetwork;
+use monas_state_node::presentation::AppState;
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+/// Default time to wait for the cluster to converge.
+pub const CONVERGENCE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Interval at which convergence conditions are polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Options for starting a [`TestCluster`].
+#[derive(Debug, Clone)]
+pub struct ClusterOptions {
+    /// Discover peers via mDNS instead of dialing every node explicitly.
+    pub enable_mdns: bool,
+    /// Minimum number of members of each content network.
+    pub min_replication_factor: usize,
+    /// Seconds a member may stay unreachable before it is replaced.
+    pub member_dead_after_secs: u64,
+    /// Capacity each node registers with.
+    pub node_capacity: u64,
+    /// Run the replication monitor of every node at this interval.
+    pub repair_interval: Option<Duration>,
+}
+
+impl Default for ClusterOptions {
+    fn default() -> Self {
+        Self {
+            enable_mdns: false,
+            min_replication_factor: 2,
+            member_dead_after_secs: 0,
+            node_capacity: 10_000,
+            repair_interval: None,
+        }
+    }
+}
+
+/// A fully-wired node (service + network + temp dir owned together).
+pub struct ClusterNode {
+    pub service: AppState,
+    pub network: Arc<Libp2pNetwork>,
+    pub sync_service: SyncService,
+    /// Stops the background tasks of the node.
+    token: CancellationToken,
+    _temp_dir: TempDir,
+}
+
+impl ClusterNode {
+    async fn spawn(options: &ClusterOptions) -> Self {
+        let temp_dir = TempDir::new().unwrap();
+
+        let node_registry = SledNodeRegistry::open(temp_dir.path().join("nodes")).unwrap();
+        let content_repo = Arc::new(RwLock::new(
+            SledContentNetworkRepository::open(temp_dir.path().join("content")).unwrap(),
+        ));
+        let access_control_repo =
+            SledAccessControlRepository::open(temp_dir.path().join("access_control")).unwrap();
+        let crdt_repo = Arc::new(CrslCrdtRepository::open(temp_dir.path().join("crdt")).unwrap());
+        let journal =
+            Arc::new(SledEventJournal::open(temp_dir.path().join("event_journal")).unwrap());
+        let crdt_repo_dyn: Arc<dyn ContentRepository> = crdt_repo.clone();
+        // Let the PushOperations handler check membership, as StateNode::new does.
+        let content_repo_dyn: Arc<
+            RwLock<
+                dyn monas_state_node::port::persistence::PersistentContentRepository + Send + Sync,
+            >,
+        > = content_repo.clone();
+
+        let network_config = Libp2pNetworkConfig {
+            listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+            bootstrap_nodes: vec![],
+            enable_mdns: options.enable_mdns,
+            external_addrs: vec![],
+            // Loopback addresses are never confirmed by AutoNAT.
+            advertise_confirmed_addrs_only: false,
+            kademlia_mode: Some(libp2p::kad::Mode::Server),
+            ..Default::default()
+        };
+        let network = Arc::new(
+            Libp2pNetwork::with_content_network_repo(
+                network_config,
+                crdt_repo_dyn,
+                temp_dir.path().to_path_buf(),
+                Some(content_repo_dyn),
+            )
+            .await
+            .unwrap(),
+        );
+
+        let event_publisher = GossipsubEventPublisher::new(network.clone(), None);
+        event_publisher.register_event_type().await;
+
+        let node_id = network.local_peer_id();
+        let sync_service = ContentSyncService::new(
+            network.clone(),
+            crdt_repo.clone(),
+            content_repo.clone(),
+            node_id.clone(),
+        );
+        let service = Arc::new(
+            StateNodeService::with_config(
+                node_registry,
+                content_repo,
+                network.clone(),
+                event_publisher,
+                crdt_repo,
+                node_id,
+                ServiceConfig {
+                    min_replication_factor: options.min_replication_factor,
+                    member_dead_after_secs: options.member_dead_after_secs,
+                    ..ServiceConfig::default()
+                },
+            )
+            .with_access_control_repo(access_control_repo)
+            .with_authentication_service(TestAuthService)
+            .with_authorization_service(AllowAllAuthorizationService)
+            .with_sync_status(sync_service.status_tracker()),
+        );
+
+        // Received events are handled and members are repaired by the same
+        // code as in `StateNode::run`.
+        let token = CancellationToken::new();
+        let event_handler =
+            ReceivedEventHandler::new(service.clone(), sync_service.clone(), journal);
+        tokio::spawn(event_handler.run(network.subscribe_events(), token.clone()));
+        if let Some(interval) = options.repair_interval {
+            tokio::spawn(run_replication_monitor(
+                service.clone(),
+                interval,
+                token.clone(),
+            ));
+        }
+
+        Self {
+            service,
+            network,
+            sync_service,
+            token,
+            _temp_dir: temp_dir,
+        }
+    }
+
+    /// The node ID (libp2p PeerId) of this node.
+    pub fn node_id(&self) -> String {
+        self.network.local_peer_id()
+    }
+
+    /// Take the node offline: stop its background tasks and block `peers`,
+    /// which closes the connections to them and refuses new ones.
+    pub async fn stop(&self, peers: &[String]) {
+        self.token.cancel();
+        for peer in peers {
+            self.network
+                .set_peer_access(peer, Some(PeerAccess::Blocked))
+                .await
+                .unwrap();
+        }
+    }
+
+    /// Gossipsub topics whose mesh includes `peer`.
+    pub async fn mesh_topics(&self, peer: &str) -> BTreeSet<String> {
+        self.network
+            .mesh_peers()
+            .await
+            .into_iter()
+            .filter(|(_, peers)| peers.iter().any(|id| id == peer))
+            .map(|(topic, _)| topic)
+            .collect()
+    }
+
+    /// The latest data of `content_id` held by this node.
+    pub async fn latest(&self, content_id: &str) -> Option<Vec<u8>> {
+        self.service
+            .crdt_repo()
+            .get_latest(content_id)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Members of `content_id` as known by this node.
+    pub async fn members(&self, content_id: &str) -> Option<BTreeSet<String>> {
+        let network = self.service.get_content_network(content_id).await.ok()?;
+        Some(network.member_nodes_as_strings().into_iter().collect())
+    }
+}
+
+impl Drop for ClusterNode {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
+/// Poll `condition` until it holds, panicking after `timeout`.
+pub async fn wait_until<F, Fut>(what: &str, timeout: Duration, mut condition: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if condition().await {
+            return;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            panic!("timed out after {:?} waiting for {}", timeout, what);
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// A set of connected nodes on localhost.
+pub struct TestCluster {
+    nodes: Vec<ClusterNode>,
+    options: ClusterOptions,
+}
+
+impl TestCluster {
+    /// Start `size` nodes connected by explicit dialing, with every node
+    /// registered and known to all others.
+    pub async fn start(size: usize) -> Self {
+        Self::start_with(size, ClusterOptions::default()).await
+    }
+
+    /// Start `size` nodes with the given options.
+    pub async fn start_with(size: usize, options: ClusterOptions) -> Self {
+        let mut nodes = Vec::with_capacity(size);
+        for _ in 0..size {
+            nodes.push(ClusterNode::spawn(&options).await);
+        }
+        let cluster = Self { nodes, options };
+
+        if !cluster.options.enable_mdns {
+            cluster.connect_full_mesh().await;
+        }
+        cluster.wait_for_full_mesh().await;
+        cluster.wait_for_event_mesh().await;
+        cluster.register_nodes().await;
+        cluster
+    }
+
+    /// The `index`-th node.
+    pub fn node(&self, index: usize) -> &ClusterNode {
+        &self.nodes[index]
+    }
+
+    /// All nodes still running.
+    pub fn nodes(&self) -> &[ClusterNode] {
+        &self.nodes
+    }
+
+    /// The node with the given node ID.
+    pub fn node_by_id(&self, node_id: &str) -> &ClusterNode {
+        self.nodes
+            .iter()
+            .find(|node| node.node_id() == node_id)
+            .unwrap_or_else(|| panic!("no running node {}", node_id))
+    }
+
+    /// Dial every node from every other node.
+    pub async fn connect_full_mesh(&self) {
+        let mut addrs = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let network = &node.network;
+            wait_until("a listen address", CONVERGENCE_TIMEOUT, || async move {
+                !network.listen_addrs_raw().await.is_empty()
+            })
+            .await;
+            addrs.push(node.network.listen_addrs_raw().await[0].clone());
+        }
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            for (j, addr) in addrs.iter().enumerate() {
+                if i != j {
+                    let _ = node.network.dial(addr.clone()).await;
+                }
+            }
+        }
+    }
+
+    /// Wait until every node is connected to every other node.
+    pub async fn wait_for_full_mesh(&self) {
+        let nodes = &self.nodes;
+        wait_until("a full mesh", CONVERGENCE_TIMEOUT, || async move {
+            for node in nodes {
+                let connected: BTreeSet<String> = node
+                    .network
+                    .connected_peer_ids()
+                    .await
+                    .into_iter()
+                    .collect();
+                let complete = nodes
+                    .iter()
+                    .map(ClusterNode::node_id)
+                    .filter(|id| *id != node.node_id())
+                    .all(|id| connected.contains(&id));
+                if !complete {
+                    return false;
+                }
+            }
+            true
+        })
+        .await;
+    }
+
+    /// Wait until the gossipsub mesh of the events topic of every node
+    /// includes every other node, so that published events reach all nodes.
+    /// Clusters larger than the gossipsub mesh degree (6) never get there.
+    pub async fn wait_for_event_mesh(&self) {
+        let nodes = &self.nodes;
+        wait_until(
+            "the events topic mesh",
+            CONVERGENCE_TIMEOUT,
+            || async move {
+                for node in nodes {
+                    for other in nodes {
+                        if other.node_id() != node.node_id()
+                            && !node
+                                .mesh_topics(&other.node_id())
+                                .await
+                                .contains(DEFAULT_EVENT_TOPIC)
+                        {
+                            return false;
+                        }
+                    }
+                }
+                true
+            },
+        )
+        .await;
+    }
+
+    /// Register every node and wait until all nodes know each other.
+    pub async fn register_nodes(&self) {
+        for node in &self.nodes {
+            node.service
+                .register_node(self.options.node_capacity)
+                .await
+                .unwrap();
+        }
+
+        let nodes = &self.nodes;
+        wait_until("node registrations", CONVERGENCE_TIMEOUT, || async move {
+            for node in nodes {
+                for other in nodes {
+                    if !matches!(node.service.get_node(&other.node_id()).await, Ok(Some(_))) {
+                        return false;
+                    }
+                }
+            }
+            true
+        })
+        .await;
+    }
+
+    /// Create content on the `index`-th node and return its content ID.
+    pub async fn create_content(&self, index: usize, data: &[u8]) -> String {
+        let event = self.nodes[index]
+            .service
+            .create_content(
+                data,
+                Some(&test_token()),
+                Some(&test_request_signature()),
+                None,
+            )
+            .await
+            .expect("create_content should succeed");
+        match event {
+            Event::ContentCreated { content_id, .. } => content_id,
+            other => panic!("expected ContentCreated, got {:?}", other),
+        }
+    }
+
+    /// Update content on the node `node_id`.
+    pub async fn update_content(&self, node_id: &str, content_id: &str, data: &[u8]) {
+        self.node_by_id(node_id)
+            .service
+            .update_content(
+                content_id,
+                data,
+                Some(&test_token()),
+                Some(&test_request_signature()),
+                None,
+            )
+            .await
+            .expect("update_content should succeed");
+    }
+
+    /// Wait until all nodes agree on the members of `content_id`, and return
+    /// them.
+    pub async fn wait_for_members(&self, content_id: &str) -> BTreeSet<String> {
+        let nodes = &self.nodes;
+        wait_until("agreement on members", CONVERGENCE_TIMEOUT, || async move {
+            let mut views = BTreeSet::new();
+            for node in nodes {
+                match node.members(content_id).await {
+                    Some(members) => views.insert(members),
+                    None => return false,
+                };
+            }
+            views.len() == 1
+        })
+        .await;
+        self.nodes[0].members(content_id).await.unwrap_or_default()
+    }
+
+    /// Wait until all nodes agree on the members of `content_id` and every
+    /// member holds `data` as its latest version. Returns the members.
+    pub async fn wait_for_replicas(&self, content_id: &str, data: &[u8]) -> BTreeSet<String> {
+        let members = self.wait_for_members(content_id).await;
+        let replicas: Vec<&ClusterNode> = members.iter().map(|id| self.node_by_id(id)).collect();
+        let replicas = &replicas;
+        wait_until("replicas", CONVERGENCE_TIMEOUT, || async move {
+            for node in replicas {
+                if node.latest(content_id).await.as_deref() != Some(data) {
+                    return false;
+                }
+            }
+            true
+        })
+        .await;
+        members
+    }
+
+    /// Take the node `node_id` offline and wait until the others noticed.
+    pub async fn stop_node(&mut self, node_id: &str) {
+        let index = self
+            .nodes
+            .iter()
+            .position(|node| node.node_id() == node_id)
+            .unwrap_or_else(|| panic!("no running node {}", node_id));
+        let node = self.nodes.remove(index);
+        let peers: Vec<String> = self.nodes.iter().map(ClusterNode::node_id).collect();
+        node.stop(&peers).await;
+
+        let nodes = &self.nodes;
+        wait_until("the disconnect", CONVERGENCE_TIMEOUT, || async move {
+            for node in nodes {
+                if node
+                    .network
+                    .connected_peer_ids()
+                    .await
+                    .iter()
+                    .any(|id| id == node_id)
+                {
+                    return false;
+                }
+            }
+            true
+        })
+        .await;
+    }
+}
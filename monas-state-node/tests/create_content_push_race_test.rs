@@ -10,6 +10,11 @@
 //! `Event::ContentCreated` gossipsub message yet, so they have no
 //! `ContentNetwork` record and the membership check returns false.
 
+mod common;
+
+use common::auth::{
+    test_request_signature, test_token, AllowAllAuthorizationService, TestAuthService,
+};
 use monas_state_node::application_service::state_node_service::{
     NoOpAccessControlRepository, ServiceConfig, StateNodeService,
 };
@@ -20,11 +25,6 @@ use monas_state_node::infrastructure::network::{Libp2pNetwork, Libp2pNetworkConf
 use monas_state_node::infrastructure::persistence::{
     SledContentNetworkRepository, SledNodeRegistry,
 };
-use monas_state_node::port::auth_token::AuthToken;
-use monas_state_node::port::authentication_service::AuthenticationService;
-use monas_state_node::port::authorization_service::{
-    AuthorizationRequest, AuthorizationResult, AuthorizationService,
-};
 use monas_state_node::port::content_repository::ContentRepository;
 use monas_state_node::port::peer_network::PeerNetwork;
 use std::sync::Arc;
@@ -41,68 +41,6 @@ type TestService = StateNodeService<
     NoOpAccessControlRepository,
 >;
 
-struct TestAuthService;
-
-#[async_trait::async_trait]
-impl AuthenticationService for TestAuthService {
-    async fn authenticate(
-        &self,
-        token: &AuthToken,
-        _context: Option<&monas_state_node::port::auth_token::AuthContext>,
-    ) -> anyhow::Result<monas_state_node::domain::identity::Identity> {
-        monas_state_node::domain::identity::Identity::user(token.as_str().to_string())
-            .map_err(|e| anyhow::anyhow!(e.to_string()))
-    }
-
-    async fn is_valid(&self, token: &AuthToken) -> anyhow::Result<bool> {
-        Ok(!token.is_empty())
-    }
-
-    async fn verify_request_signature(
-        &self,
-        _token: &AuthToken,
-        _signature: &[u8],
-        _message: &str,
-        _timestamp: Option<u64>,
-    ) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn verify_jwt_signature(&self, _token: &AuthToken) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn get_issuer(
-        &self,
-        token: &AuthToken,
-    ) -> anyhow::Result<Option<monas_state_node::domain::identity::Identity>> {
-        Ok(Some(
-            monas_state_node::domain::identity::Identity::user(token.as_str().to_string())
-                .map_err(|e| anyhow::anyhow!(e.to_string()))?,
-        ))
-    }
-}
-
-struct AllowAllAuthorizationService;
-
-#[async_trait::async_trait]
-impl AuthorizationService for AllowAllAuthorizationService {
-    async fn authorize(
-        &self,
-        _request: &AuthorizationRequest,
-    ) -> anyhow::Result<AuthorizationResult> {
-        Ok(AuthorizationResult::Granted)
-    }
-}
-
-fn test_token() -> AuthToken {
-    AuthToken::new("test-user".to_string())
-}
-
-fn test_request_signature() -> Vec<u8> {
-    vec![0x01]
-}
-
 /// A fully-wired test node (service + network + temp dir owned together).
 struct TestNode {
     service: Arc<TestService>,
@@ -135,6 +73,10 @@ async fn spawn_test_node() -> TestNode {
         enable_mdns: false,
         gossipsub_topics: vec!["test-events".to_string()],
         external_addrs: vec![],
+        // Loopback addresses are never confirmed by AutoNAT.
+        advertise_confirmed_addrs_only: false,
+        kademlia_mode: Some(libp2p::kad::Mode::Server),
+        ..Default::default()
     };
 
     let network = Arc::new(
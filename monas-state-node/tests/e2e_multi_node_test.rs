@@ -31,6 +31,10 @@ async fn create_test_node() -> (StateNode, TempDir) {
             enable_mdns: false, // Disable mDNS to avoid interference between tests
             gossipsub_topics: vec![EVENTS_TOPIC.to_string()],
             external_addrs: vec![],
+            // Loopback addresses are never confirmed by AutoNAT.
+            advertise_confirmed_addrs_only: false,
+            kademlia_mode: Some(libp2p::kad::Mode::Server),
+            ..Default::default()
         },
         node_id: None,
         sync_interval_secs: 30,
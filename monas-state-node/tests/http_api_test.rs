@@ -0,0 +1,415 @@
+//! HTTP-level tests for the state node API.
+//!
+//! Requests are sent through the real router with `tower::ServiceExt::oneshot`,
+//! backed by sled repositories in a temporary directory and a localhost-only
+//! libp2p network, so no HTTP server is started.
+
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use axum::response::Response;
+use axum::Router;
+use base64::Engine;
+use monas_state_node::application_service::state_node_service::StateNodeService;
+use monas_state_node::infrastructure::crdt_repository::CrslCrdtRepository;
+use monas_state_node::infrastructure::gossipsub_publisher::GossipsubEventPublisher;
+use monas_state_node::infrastructure::network::{Libp2pNetwork, Libp2pNetworkConfig};
+use monas_state_node::infrastructure::persistence::{
+    SledAccessControlRepository, SledContentNetworkRepository, SledDenylistRepository,
+    SledNodeRegistry,
+};
+use monas_state_node::port::auth_token::{AuthContext, AuthToken};
+use monas_state_node::port::authentication_service::AuthenticationService;
+use monas_state_node::port::content_repository::ContentRepository;
+use monas_state_node::port::peer_network::PeerNetwork;
+use monas_state_node::port::persistence::PersistentDenylistRepository;
+use monas_state_node::presentation::create_router;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+use tower::ServiceExt;
+
+const ADMIN_TOKEN: &str = "test-admin-token";
+
+/// Token value that the test authentication service rejects.
+const REJECTED_TOKEN: &str = "rejected";
+
+/// Authenticates every non-empty token except [`REJECTED_TOKEN`] and accepts any signature.
+struct TestAuthService;
+
+#[async_trait::async_trait]
+impl AuthenticationService for TestAuthService {
+    async fn authenticate(
+        &self,
+        token: &AuthToken,
+        _context: Option<&AuthContext>,
+    ) -> anyhow::Result<monas_state_node::domain::identity::Identity> {
+        if token.as_str() == REJECTED_TOKEN {
+            anyhow::bail!("token rejected");
+        }
+        monas_state_node::domain::identity::Identity::user(token.as_str().to_string())
+            .map_err(|e| anyhow::anyhow!(e.to_string()))
+    }
+
+    async fn is_valid(&self, token: &AuthToken) -> anyhow::Result<bool> {
+        Ok(!token.is_empty() && token.as_str() != REJECTED_TOKEN)
+    }
+
+    async fn verify_request_signature(
+        &self,
+        _token: &AuthToken,
+        _signature: &[u8],
+        _message: &str,
+        _timestamp: Option<u64>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn verify_jwt_signature(&self, _token: &AuthToken) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn get_issuer(
+        &self,
+        token: &AuthToken,
+    ) -> anyhow::Result<Option<monas_state_node::domain::identity::Identity>> {
+        Ok(Some(
+            monas_state_node::domain::identity::Identity::user(token.as_str().to_string())
+                .map_err(|e| anyhow::anyhow!(e.to_string()))?,
+        ))
+    }
+}
+
+/// Build the production router over temporary storage and an isolated network.
+async fn create_test_router() -> (Router, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+
+    let node_registry = SledNodeRegistry::open(temp_dir.path().join("nodes")).unwrap();
+    let content_repo = Arc::new(RwLock::new(
+        SledContentNetworkRepository::open(temp_dir.path().join("content")).unwrap(),
+    ));
+    let access_control_repo =
+        SledAccessControlRepository::open(temp_dir.path().join("access_control")).unwrap();
+    let denylist: Arc<dyn PersistentDenylistRepository> =
+        Arc::new(SledDenylistRepository::open(temp_dir.path().join("denylist")).unwrap());
+    let crdt_repo = Arc::new(
+        CrslCrdtRepository::open(temp_dir.path().join("crdt"))
+            .unwrap()
+            .with_denylist(denylist.clone()),
+    );
+    let crdt_repo_dyn: Arc<dyn ContentRepository> = crdt_repo.clone();
+
+    let network_config = Libp2pNetworkConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        bootstrap_nodes: vec![],
+        enable_mdns: false,
+        gossipsub_topics: vec!["test-events".to_string()],
+        external_addrs: vec![],
+        ..Default::default()
+    };
+    let network = Arc::new(
+        Libp2pNetwork::new(network_config, crdt_repo_dyn, temp_dir.path().to_path_buf())
+            .await
+            .unwrap(),
+    );
+
+    let event_publisher = GossipsubEventPublisher::new(network.clone(), None);
+    event_publisher.register_event_type().await;
+    let node_id = network.local_peer_id();
+
+    let service = StateNodeService::new(
+        node_registry,
+        content_repo,
+        network,
+        event_publisher,
+        crdt_repo,
+        node_id,
+    )
+    .with_access_control_repo(access_control_repo)
+    .with_authentication_service(TestAuthService)
+    .with_denylist(denylist)
+    .with_admin_token(ADMIN_TOKEN.to_string());
+
+    (create_router(Arc::new(service)), temp_dir)
+}
+
+/// Send a request through the router.
+///
+/// A client address is always supplied via `X-Forwarded-For` because the per-IP
+/// rate limiter rejects requests whose client IP cannot be determined.
+async fn send(
+    router: &Router,
+    method: Method,
+    uri: &str,
+    headers: &[(&str, &str)],
+    body: Option<Value>,
+) -> Response {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("x-forwarded-for", "127.0.0.1");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+    router.clone().oneshot(request).await.unwrap()
+}
+
+async fn body_json(response: Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+/// Assert the `{ "error": ... }` envelope and return the message.
+async fn error_message(response: Response, status: StatusCode) -> String {
+    assert_eq!(response.status(), status);
+    let body = body_json(response).await;
+    let object = body.as_object().expect("error body is a JSON object");
+    assert_eq!(object.len(), 1, "unexpected error body: {body}");
+    object["error"].as_str().unwrap().to_string()
+}
+
+fn encode(data: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(data)
+}
+
+#[tokio::test]
+async fn test_health_endpoints_report_node_identity() {
+    let (router, _temp_dir) = create_test_router().await;
+
+    let response = send(&router, Method::GET, "/health", &[], None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let health = body_json(response).await;
+    assert_eq!(health["status"], "ok");
+    assert!(!health["node_id"].as_str().unwrap().is_empty());
+
+    let response = send(&router, Method::GET, "/health/live", &[], None).await;
+    assert_eq!(body_json(response).await, json!({ "status": "alive" }));
+
+    let response = send(&router, Method::GET, "/health/ready", &[], None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let ready = body_json(response).await;
+    assert_eq!(ready["status"], "ready");
+    assert_eq!(ready["database"], "ok");
+}
+
+#[tokio::test]
+async fn test_register_node_is_reflected_in_node_info_and_listing() {
+    let (router, _temp_dir) = create_test_router().await;
+
+    let response = send(&router, Method::GET, "/node/info", &[], None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let info = body_json(response).await;
+    assert!(info["total_capacity"].is_null());
+    let node_id = info["node_id"].as_str().unwrap().to_string();
+
+    let response = send(
+        &router,
+        Method::POST,
+        "/node/register",
+        &[],
+        Some(json!({ "total_capacity": 4096 })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let registered = body_json(response).await;
+    assert_eq!(registered["node_id"], node_id.as_str());
+    assert_eq!(registered["total_capacity"], 4096);
+    assert_eq!(registered["available_capacity"], 4096);
+
+    let response = send(&router, Method::GET, "/node/info", &[], None).await;
+    assert_eq!(body_json(response).await["total_capacity"], 4096);
+
+    let response = send(&router, Method::GET, "/nodes", &[], None).await;
+    assert_eq!(body_json(response).await, json!([node_id]));
+
+    let response = send(
+        &router,
+        Method::GET,
+        &format!("/nodes/{node_id}"),
+        &[],
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        body_json(response).await,
+        json!({ "node_id": node_id, "total_capacity": 4096, "available_capacity": 4096 })
+    );
+
+    let response = send(&router, Method::GET, "/nodes/unknown-node", &[], None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = send(&router, Method::GET, "/mirror", &[], None).await;
+    assert_eq!(body_json(response).await["enabled"], false);
+}
+
+#[tokio::test]
+async fn test_create_content_requires_authentication() {
+    let (router, _temp_dir) = create_test_router().await;
+    let body = json!({ "data": encode(b"hello") });
+
+    // No Authorization header
+    let response = send(&router, Method::POST, "/content", &[], Some(body.clone())).await;
+    assert_eq!(
+        error_message(response, StatusCode::UNAUTHORIZED).await,
+        "Authentication failed"
+    );
+
+    // Token without the request signature
+    let response = send(
+        &router,
+        Method::POST,
+        "/content",
+        &[("authorization", "Bearer user-a")],
+        Some(body.clone()),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Token rejected by the authentication service; details are not leaked
+    let signature = encode(b"sig");
+    let response = send(
+        &router,
+        Method::POST,
+        "/content",
+        &[
+            ("authorization", "Bearer rejected"),
+            ("x-request-signature", signature.as_str()),
+        ],
+        Some(body),
+    )
+    .await;
+    assert_eq!(
+        error_message(response, StatusCode::UNAUTHORIZED).await,
+        "Authentication failed"
+    );
+}
+
+#[tokio::test]
+async fn test_create_content_validation_and_placement_errors() {
+    let (router, _temp_dir) = create_test_router().await;
+    let signature = encode(b"sig");
+    let auth = [
+        ("authorization", "Bearer user-a"),
+        ("x-request-signature", signature.as_str()),
+    ];
+
+    let response = send(
+        &router,
+        Method::POST,
+        "/content",
+        &auth,
+        Some(json!({ "data": "not base64!" })),
+    )
+    .await;
+    assert!(error_message(response, StatusCode::BAD_REQUEST)
+        .await
+        .starts_with("Invalid base64 data"));
+
+    // Missing required field is rejected by the JSON extractor
+    let response = send(&router, Method::POST, "/content", &auth, Some(json!({}))).await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // An isolated node has no peers to place the content on
+    let response = send(
+        &router,
+        Method::POST,
+        "/content",
+        &auth,
+        Some(json!({ "data": encode(b"hello") })),
+    )
+    .await;
+    assert!(error_message(response, StatusCode::SERVICE_UNAVAILABLE)
+        .await
+        .starts_with("No available member nodes found"));
+}
+
+#[tokio::test]
+async fn test_content_reads_require_authorization_header() {
+    let (router, _temp_dir) = create_test_router().await;
+
+    let response = send(&router, Method::GET, "/content/unknown/data", &[], None).await;
+    assert_eq!(
+        error_message(response, StatusCode::UNAUTHORIZED).await,
+        "Authorization header is required"
+    );
+
+    let response = send(
+        &router,
+        Method::GET,
+        "/content/unknown/history",
+        &[("authorization", "Bearer rejected")],
+        None,
+    )
+    .await;
+    assert!(error_message(response, StatusCode::UNAUTHORIZED)
+        .await
+        .starts_with("Authentication failed"));
+
+    let response = send(&router, Method::GET, "/contents", &[], None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_json(response).await, json!([]));
+
+    // Membership lookups are public but report unknown content as missing
+    let response = send(&router, Method::GET, "/content/unknown/network", &[], None).await;
+    assert!(error_message(response, StatusCode::NOT_FOUND)
+        .await
+        .contains("unknown"));
+}
+
+#[tokio::test]
+async fn test_admin_denylist_requires_operator_token() {
+    let (router, _temp_dir) = create_test_router().await;
+
+    let response = send(&router, Method::GET, "/admin/denylist", &[], None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = send(
+        &router,
+        Method::GET,
+        "/admin/denylist",
+        &[("x-admin-token", "wrong-token")],
+        None,
+    )
+    .await;
+    assert_eq!(
+        error_message(response, StatusCode::UNAUTHORIZED).await,
+        "Authentication failed"
+    );
+
+    let response = send(
+        &router,
+        Method::POST,
+        "/admin/denylist",
+        &[("x-admin-token", ADMIN_TOKEN)],
+        Some(json!({ "content_id": "bafy-denied", "reason": "takedown" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let denied = body_json(response).await;
+    assert_eq!(denied["content_id"], "bafy-denied");
+    assert_eq!(denied["reason"], "takedown");
+
+    let response = send(
+        &router,
+        Method::GET,
+        "/admin/denylist",
+        &[("x-admin-token", ADMIN_TOKEN)],
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(body_json(response)
+        .await
+        .to_string()
+        .contains("bafy-denied"));
+}
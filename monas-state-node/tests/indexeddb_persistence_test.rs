@@ -0,0 +1,125 @@
+//! Browser tests for the IndexedDB persistence implementations.
+//!
+//! Run with `wasm-pack test --headless --firefox` (or `--chrome`).
+
+#![cfg(target_arch = "wasm32")]
+
+use monas_state_node::domain::content_network::ContentNetwork;
+use monas_state_node::domain::state_node::NodeSnapshot;
+use monas_state_node::domain::value_objects::{ContentId, NodeId};
+use monas_state_node::infrastructure::persistence::{
+    IndexedDbContentRepository, IndexedDbNodeRegistry,
+};
+use monas_state_node::port::persistence::{WasmContentRepository, WasmNodeRegistry};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn node(node_id: &str, available_capacity: u64) -> NodeSnapshot {
+    NodeSnapshot {
+        node_id: node_id.to_string(),
+        total_capacity: 1000,
+        available_capacity,
+        updated_at: 0,
+    }
+}
+
+fn network(content_id: &str, members: &[&str]) -> ContentNetwork {
+    let content_id = ContentId::new(content_id.to_string()).unwrap();
+    let first = NodeId::from_string(members[0].to_string()).unwrap();
+    let mut network = ContentNetwork::new(content_id, first).unwrap();
+    for member in &members[1..] {
+        network.add_member(NodeId::from_string(member.to_string()).unwrap());
+    }
+    network
+}
+
+#[wasm_bindgen_test]
+async fn test_node_registry_roundtrip() {
+    let registry = IndexedDbNodeRegistry::open("monas-test-node-registry")
+        .await
+        .unwrap();
+
+    registry.upsert_node(&node("node-1", 500)).await.unwrap();
+    registry.upsert_node(&node("node-2", 300)).await.unwrap();
+    registry.upsert_node(&node("node-1", 400)).await.unwrap();
+
+    assert_eq!(
+        registry.get_node("node-1").await.unwrap(),
+        Some(node("node-1", 400))
+    );
+    assert_eq!(
+        registry.get_available_capacity("node-2").await.unwrap(),
+        Some(300)
+    );
+    assert_eq!(
+        registry.list_nodes().await.unwrap(),
+        vec!["node-1".to_string(), "node-2".to_string()]
+    );
+
+    registry.delete_node("node-1").await.unwrap();
+    assert_eq!(registry.get_node("node-1").await.unwrap(), None);
+    registry.flush().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_node_registry_persists_across_reopen() {
+    let registry = IndexedDbNodeRegistry::open("monas-test-node-reopen")
+        .await
+        .unwrap();
+    registry.upsert_node(&node("node-1", 500)).await.unwrap();
+    drop(registry);
+
+    let registry = IndexedDbNodeRegistry::open("monas-test-node-reopen")
+        .await
+        .unwrap();
+    assert_eq!(
+        registry.get_node("node-1").await.unwrap(),
+        Some(node("node-1", 500))
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_content_repository_roundtrip() {
+    let repo = IndexedDbContentRepository::open("monas-test-content-repository")
+        .await
+        .unwrap();
+
+    let net = network("cid-1", &["node-1", "node-2"]);
+    repo.save_content_network(net.clone()).await.unwrap();
+    repo.save_content_network(network("cid-2", &["node-3"]))
+        .await
+        .unwrap();
+
+    assert_eq!(repo.get_content_network("cid-1").await.unwrap(), Some(net));
+    assert_eq!(
+        repo.list_content_networks().await.unwrap(),
+        vec!["cid-1".to_string(), "cid-2".to_string()]
+    );
+
+    repo.delete_content_network("cid-1").await.unwrap();
+    assert_eq!(repo.get_content_network("cid-1").await.unwrap(), None);
+    repo.flush().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_find_assignable_cids_by_capacity() {
+    let repo = IndexedDbContentRepository::open("monas-test-capacity-index")
+        .await
+        .unwrap();
+
+    repo.index_by_capacity("small", 100).await.unwrap();
+    repo.index_by_capacity("medium", 500).await.unwrap();
+    repo.index_by_capacity("large", 1000).await.unwrap();
+
+    assert_eq!(
+        repo.find_assignable_cids(500).await.unwrap(),
+        vec!["small".to_string(), "medium".to_string()]
+    );
+
+    repo.remove_from_capacity_index("small", 100).await.unwrap();
+    assert_eq!(
+        repo.find_assignable_cids(1000).await.unwrap(),
+        vec!["medium".to_string(), "large".to_string()]
+    );
+}
@@ -3,6 +3,11 @@
 //! These tests verify the end-to-end functionality of the state node,
 //! including content creation, node registration, and event handling.
 
+mod common;
+
+use common::auth::{
+    test_request_signature, test_token, AllowAllAuthorizationService, TestAuthService,
+};
 use monas_state_node::application_service::state_node_service::{
     NoOpAccessControlRepository, StateNodeService,
 };
@@ -44,68 +49,6 @@ type TestServiceWithAC = StateNodeService<
     SledAccessControlRepository,
 >;
 
-struct TestAuthService;
-
-#[async_trait::async_trait]
-impl AuthenticationService for TestAuthService {
-    async fn authenticate(
-        &self,
-        token: &AuthToken,
-        _context: Option<&monas_state_node::port::auth_token::AuthContext>,
-    ) -> anyhow::Result<monas_state_node::domain::identity::Identity> {
-        monas_state_node::domain::identity::Identity::user(token.as_str().to_string())
-            .map_err(|e| anyhow::anyhow!(e.to_string()))
-    }
-
-    async fn is_valid(&self, token: &AuthToken) -> anyhow::Result<bool> {
-        Ok(!token.is_empty())
-    }
-
-    async fn verify_request_signature(
-        &self,
-        _token: &AuthToken,
-        _signature: &[u8],
-        _message: &str,
-        _timestamp: Option<u64>,
-    ) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn verify_jwt_signature(&self, _token: &AuthToken) -> anyhow::Result<()> {
-        Ok(())
-    }
-
-    async fn get_issuer(
-        &self,
-        token: &AuthToken,
-    ) -> anyhow::Result<Option<monas_state_node::domain::identity::Identity>> {
-        Ok(Some(
-            monas_state_node::domain::identity::Identity::user(token.as_str().to_string())
-                .map_err(|e| anyhow::anyhow!(e.to_string()))?,
-        ))
-    }
-}
-
-struct AllowAllAuthorizationService;
-
-#[async_trait::async_trait]
-impl AuthorizationService for AllowAllAuthorizationService {
-    async fn authorize(
-        &self,
-        _request: &AuthorizationRequest,
-    ) -> anyhow::Result<AuthorizationResult> {
-        Ok(AuthorizationResult::Granted)
-    }
-}
-
-fn test_token() -> AuthToken {
-    AuthToken::new("test-user".to_string())
-}
-
-fn test_request_signature() -> Vec<u8> {
-    vec![0x01]
-}
-
 fn sign_access_control_update(update: &AccessControlUpdate) -> (Vec<u8>, Vec<u8>) {
     use p256::ecdsa::signature::DigestSigner;
     use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
@@ -141,6 +84,7 @@ async fn create_test_service() -> (Arc<TestService>, Arc<CrslCrdtRepository>, Te
         enable_mdns: false, // Disable mDNS for isolated tests
         gossipsub_topics: vec!["test-events".to_string()],
         external_addrs: vec![],
+        ..Default::default()
     };
 
     let network = Arc::new(
@@ -531,6 +475,7 @@ async fn create_test_service_with_ac() -> (Arc<TestServiceWithAC>, Arc<CrslCrdtR
         enable_mdns: false,
         gossipsub_topics: vec!["test-events".to_string()],
         external_addrs: vec![],
+        ..Default::default()
     };
 
     let network = Arc::new(
@@ -1231,6 +1176,7 @@ async fn create_test_service_deny_authz() -> (Arc<TestService>, Arc<CrslCrdtRepo
         enable_mdns: false,
         gossipsub_topics: vec!["test-events".to_string()],
         external_addrs: vec![],
+        ..Default::default()
     };
 
     let network = Arc::new(
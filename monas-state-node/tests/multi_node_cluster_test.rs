@@ -0,0 +1,87 @@
+//! End-to-end tests on an in-process cluster of real libp2p nodes.
+//!
+//! See `common/cluster.rs` for the harness.
+
+mod common;
+
+use common::cluster::{wait_until, ClusterOptions, TestCluster, CONVERGENCE_TIMEOUT};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_cluster_nodes_know_each_other() {
+    let cluster = TestCluster::start(3).await;
+
+    for node in cluster.nodes() {
+        let nodes = node.service.list_nodes().await.unwrap();
+        assert_eq!(
+            nodes.len(),
+            3,
+            "node {} should know all nodes",
+            node.node_id()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_created_content_replicates_to_members() {
+    let cluster = TestCluster::start(3).await;
+    let creator = cluster.node(0).node_id();
+
+    let content_id = cluster.create_content(0, b"created").await;
+    let members = cluster.wait_for_replicas(&content_id, b"created").await;
+
+    assert!(members.len() >= 2, "members: {:?}", members);
+    assert!(
+        !members.contains(&creator),
+        "creator should not be a member"
+    );
+}
+
+#[tokio::test]
+async fn test_update_converges_on_all_members() {
+    let cluster = TestCluster::start(3).await;
+    let content_id = cluster.create_content(0, b"v1").await;
+    let members = cluster.wait_for_replicas(&content_id, b"v1").await;
+
+    let updater = members.iter().next().unwrap();
+    cluster.update_content(updater, &content_id, b"v2").await;
+
+    cluster.wait_for_replicas(&content_id, b"v2").await;
+}
+
+#[tokio::test]
+async fn test_repair_replaces_stopped_member() {
+    let options = ClusterOptions {
+        repair_interval: Some(Duration::from_millis(500)),
+        ..ClusterOptions::default()
+    };
+    let mut cluster = TestCluster::start_with(4, options).await;
+    let content_id = cluster.create_content(0, b"replicated").await;
+    let members = cluster.wait_for_replicas(&content_id, b"replicated").await;
+    assert_eq!(members.len(), 2, "members: {:?}", members);
+
+    let mut members = members.into_iter();
+    let lost = members.next().unwrap();
+    let survivor = members.next().unwrap();
+    cluster.stop_node(&lost).await;
+
+    // The replication monitor of the survivor replaces the lost member.
+    let survivor_node = cluster.node_by_id(&survivor);
+    let (content_id_ref, lost_ref) = (&content_id, &lost);
+    wait_until(
+        "the lost member to be replaced",
+        CONVERGENCE_TIMEOUT,
+        || async move {
+            survivor_node
+                .members(content_id_ref)
+                .await
+                .is_some_and(|members| !members.contains(lost_ref))
+        },
+    )
+    .await;
+
+    let repaired = cluster.wait_for_replicas(&content_id, b"replicated").await;
+    assert_eq!(repaired.len(), 2, "repaired: {:?}", repaired);
+    assert!(repaired.contains(&survivor), "repaired: {:?}", repaired);
+    assert!(!repaired.contains(&lost), "repaired: {:?}", repaired);
+}
@@ -0,0 +1,87 @@
+//! Integration tests for private networks (pre-shared key).
+//!
+//! Two nodes on loopback connect only when they share the same key.
+
+use libp2p::pnet::PreSharedKey;
+use monas_state_node::infrastructure::crdt_repository::CrslCrdtRepository;
+use monas_state_node::infrastructure::network::{
+    Libp2pNetwork, Libp2pNetworkConfig, TransportConfig,
+};
+use monas_state_node::port::content_repository::ContentRepository;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+async fn create_node(psk: Option<PreSharedKey>) -> (Libp2pNetwork, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let crdt_repo: Arc<dyn ContentRepository> =
+        Arc::new(CrslCrdtRepository::open(temp_dir.path().join("crdt")).unwrap());
+    let config = Libp2pNetworkConfig {
+        listen_addrs: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        bootstrap_nodes: vec![],
+        enable_mdns: false,
+        gossipsub_topics: vec!["test".to_string()],
+        external_addrs: vec![],
+        // Loopback addresses are never confirmed by AutoNAT.
+        advertise_confirmed_addrs_only: false,
+        kademlia_mode: Some(libp2p::kad::Mode::Server),
+        transport: TransportConfig {
+            psk,
+            upgrade_timeout: Duration::from_secs(2),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let network = Libp2pNetwork::new(config, crdt_repo, temp_dir.path().to_path_buf())
+        .await
+        .unwrap();
+    (network, temp_dir)
+}
+
+/// Dial `listener` from `dialer` and report whether either side sees a
+/// connection within `timeout`.
+async fn connects(dialer: &Libp2pNetwork, listener: &Libp2pNetwork, timeout: Duration) -> bool {
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let addrs = listener.listen_addrs_raw().await;
+    assert!(!addrs.is_empty(), "listener should have a listen address");
+    // A failed handshake is reported asynchronously, so the dial result
+    // itself is not meaningful here.
+    let _ = dialer.dial(addrs[0].clone()).await;
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    while tokio::time::Instant::now() < deadline {
+        if !dialer.connected_peers().await.is_empty()
+            || !listener.connected_peers().await.is_empty()
+        {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_nodes_with_same_key_connect() {
+    let key = PreSharedKey::new([7; 32]);
+    let (network1, _dir1) = create_node(Some(key)).await;
+    let (network2, _dir2) = create_node(Some(key)).await;
+
+    assert!(connects(&network2, &network1, Duration::from_secs(5)).await);
+}
+
+#[tokio::test]
+async fn test_nodes_with_different_keys_do_not_connect() {
+    let (network1, _dir1) = create_node(Some(PreSharedKey::new([7; 32]))).await;
+    let (network2, _dir2) = create_node(Some(PreSharedKey::new([8; 32]))).await;
+
+    assert!(!connects(&network2, &network1, Duration::from_secs(2)).await);
+}
+
+#[tokio::test]
+async fn test_private_and_public_nodes_do_not_connect() {
+    let (private, _dir1) = create_node(Some(PreSharedKey::new([7; 32]))).await;
+    let (public, _dir2) = create_node(None).await;
+
+    assert!(!connects(&public, &private, Duration::from_secs(2)).await);
+    assert!(!connects(&private, &public, Duration::from_secs(2)).await);
+}
@@ -113,6 +113,10 @@ mod tests {
             enable_mdns: false,
             gossipsub_topics: vec!["test".to_string()],
             external_addrs: vec![],
+            // Loopback addresses are never confirmed by AutoNAT.
+            advertise_confirmed_addrs_only: false,
+            kademlia_mode: Some(libp2p::kad::Mode::Server),
+            ..Default::default()
         };
 
         let config2 = Libp2pNetworkConfig {
@@ -121,6 +125,10 @@ mod tests {
             enable_mdns: false,
             gossipsub_topics: vec!["test".to_string()],
             external_addrs: vec![],
+            // Loopback addresses are never confirmed by AutoNAT.
+            advertise_confirmed_addrs_only: false,
+            kademlia_mode: Some(libp2p::kad::Mode::Server),
+            ..Default::default()
         };
 
         let network1 = Libp2pNetwork::new(config1, crdt_repo1, tmp_dir1.path().to_path_buf())